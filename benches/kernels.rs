@@ -0,0 +1,45 @@
+//! Benchmarks of the forward and backward kernels of the most common nodes.
+//!
+//! Run with `cargo bench --features bench`.
+use neuronika::bench::{Bencher, Measurement};
+
+fn main() {
+    let bencher = Bencher::new().warm_up(5).samples(50);
+    let mut measurements: Vec<Measurement> = Vec::new();
+
+    let x = neuronika::rand((256, 512)).requires_grad();
+    let w = neuronika::rand((512, 512)).requires_grad();
+    let b = neuronika::rand(512).requires_grad();
+
+    let mm = x.clone().mm(w.clone());
+    measurements.push(bencher.forward("mm (256, 512) x (512, 512)", &mm));
+    measurements.push(bencher.backward("mm (256, 512) x (512, 512)", &mm));
+
+    let mm_t = x.clone().mm_t(w.clone());
+    measurements.push(bencher.forward("mm_t (256, 512) x (512, 512)", &mm_t));
+    measurements.push(bencher.backward("mm_t (256, 512) x (512, 512)", &mm_t));
+
+    let add = x.clone() + b;
+    measurements.push(bencher.forward("add (256, 512) + (512)", &add));
+    measurements.push(bencher.backward("add (256, 512) + (512)", &add));
+
+    let relu = x.clone().relu();
+    measurements.push(bencher.forward("relu (256, 512)", &relu));
+    measurements.push(bencher.backward("relu (256, 512)", &relu));
+
+    let sigmoid = x.clone().sigmoid();
+    measurements.push(bencher.forward("sigmoid (256, 512)", &sigmoid));
+    measurements.push(bencher.backward("sigmoid (256, 512)", &sigmoid));
+
+    let softmax = x.clone().softmax(1);
+    measurements.push(bencher.forward("softmax (256, 512)", &softmax));
+    measurements.push(bencher.backward("softmax (256, 512)", &softmax));
+
+    let sum = x.sum();
+    measurements.push(bencher.forward("sum (256, 512)", &sum));
+    measurements.push(bencher.backward("sum (256, 512)", &sum));
+
+    for measurement in measurements {
+        println!("{}", measurement);
+    }
+}
@@ -32,10 +32,10 @@ impl NeuralNetwork {
 
     fn forward<I, T, U>(&self, input: I) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
     where
-        I: MatMatMulT<Learnable<Ix2>>,
+        I: MatMatMulT<Learnable<Ix2>> + 'static,
         I::Output: Into<VarDiff<T, U>>,
-        T: Data<Dim = Ix2>,
-        U: Gradient<Dim = Ix2>,
+        T: Data<Dim = Ix2> + 'static,
+        U: Gradient<Dim = Ix2> + 'static,
     {
         let out1 = self.lin1.forward(input).relu();
         let out2 = self.lin2.forward(out1).relu();
@@ -0,0 +1,410 @@
+//! Automatic differentiation settings and utilities.
+//!
+//! # Anomaly Detection
+//!
+//! Non-finite values, once produced, silently propagate through the computational graph and end
+//! up in the parameters of a model. When anomaly detection is enabled, the data computed by every
+//! node during [`.forward()`](crate::Var::forward()) and the gradient of every node during
+//! [`.backward()`](crate::VarDiff::backward()) are checked, and the computation panics at the
+//! first node holding a `NaN` or an infinite value, reporting the node and the computation it
+//! results from.
+//!
+//! Anomaly detection slows the computation down considerably and should only be enabled for
+//! debugging purposes.
+//!
+//! ```should_panic
+//! use neuronika::autograd;
+//!
+//! autograd::set_detect_anomaly(true);
+//!
+//! let x = neuronika::full(3, -1.).requires_grad();
+//! let y = x.sqrt().sum();
+//!
+//! // Panics, as the square root of a negative number is NaN.
+//! y.forward();
+//! ```
+//!
+//! # Jacobians and Hessians
+//!
+//! [`jacobian`] and [`hessian`] compute the full derivative matrices of a function built with
+//! neuronika's operations. The function is given as a closure that receives the differentiable
+//! input and returns the output variable.
+//!
+//! ```
+//! use ndarray::array;
+//! use neuronika::autograd;
+//!
+//! // f(x) = x ⊙ x, whose Jacobian is diag(2x).
+//! let jacobian = autograd::jacobian(|x| x.clone() * x, &array![1., 2., 3.]);
+//!
+//! assert_eq!(
+//!     jacobian,
+//!     array![[2., 0., 0.], [0., 4., 0.], [0., 0., 6.]].into_dyn()
+//! );
+//! ```
+//!
+//! [`hessian`] is restricted to functions having a scalar output.
+//!
+//! ```
+//! use ndarray::array;
+//! use neuronika::autograd;
+//!
+//! // f(x) = Σ x³, whose Hessian is diag(6x).
+//! let hessian = autograd::hessian(|x| x.clone().pow(3).sum(), &array![1., 2.]);
+//!
+//! assert!((hessian[[0, 0]] - 6.).abs() < 1e-2);
+//! assert!((hessian[[1, 1]] - 12.).abs() < 1e-2);
+//! assert!(hessian[[0, 1]].abs() < 1e-2);
+//! ```
+//!
+//! # Per-sample Gradients
+//!
+//! [`per_sample_grad`] computes the gradient of the loss of every sample of a batch separately,
+//! as needed, for instance, by differentially private training and influence functions. It
+//! back-propagates through the graph once per sample, so it costs as many backward passes as the
+//! size of the batch.
+//!
+//! # Gradient Flow
+//!
+//! [`grad_flow`] inspects the gradients of a set of parameters after a call to
+//! [`.backward()`](crate::VarDiff::backward()) and returns a [`GradFlow`] report holding, for each
+//! parameter, the norm of its gradient, the fraction of its entries that are zero and the number
+//! of those that are `NaN`. Vanishing and exploding gradients can thus be spotted without
+//! iterating over the parameters manually.
+//!
+//! ```
+//! use neuronika::{autograd, nn::Linear};
+//!
+//! let lin = Linear::new(3, 2);
+//! let y = lin.forward(neuronika::rand((4, 3))).relu().sum();
+//! y.forward();
+//! y.backward(1.);
+//!
+//! let flow = autograd::grad_flow(&y.parameters());
+//! assert_eq!(flow.params().len(), 2);
+//! assert_eq!(flow.nans(), 0);
+//!
+//! println!("{}", flow);
+//! ```
+use crate::{
+    variable::{Input, InputBackward},
+    Data, Gradient, Param, VarDiff,
+};
+use ndarray::{Array, ArrayD, Axis, Dimension, Ix0, IxDyn, Slice};
+use std::{cell::Cell, fmt};
+
+thread_local! {
+    static DETECT_ANOMALY: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Enables or disables anomaly detection on the current thread.
+///
+/// # Arguments
+///
+/// `enabled` - whether anomaly detection should be enabled.
+pub fn set_detect_anomaly(enabled: bool) {
+    DETECT_ANOMALY.with(|detect_anomaly| detect_anomaly.set(enabled));
+}
+
+/// Returns `true` if anomaly detection is enabled on the current thread.
+pub fn is_anomaly_enabled() -> bool {
+    DETECT_ANOMALY.with(Cell::get)
+}
+
+/// Computes the Jacobian of `function` at `input`.
+///
+/// The result has shape *(output shape, input shape)*, its element at *(i, j)* is the partial
+/// derivative of the *i*-th element of the output with respect to the *j*-th element of the
+/// input, where *i* and *j* are multi-indices.
+///
+/// The Jacobian is assembled one row at a time with a vector-Jacobian product, thus `function`
+/// is evaluated once and back-propagated through as many times as the number of elements of its
+/// output.
+///
+/// # Arguments
+///
+/// * `function` - builds the computational graph of the function from its differentiable input.
+///
+/// * `input` - point at which the Jacobian is computed.
+pub fn jacobian<D, F, T, U>(function: F, input: &Array<f32, D>) -> ArrayD<f32>
+where
+    D: Dimension + 'static,
+    F: FnOnce(VarDiff<Input<D>, InputBackward<D>>) -> VarDiff<T, U>,
+    T: Data + ?Sized + 'static,
+    U: Gradient<Dim = T::Dim> + ?Sized + 'static,
+{
+    let input = crate::from_ndarray(input.clone()).requires_grad();
+    let output = function(input.clone());
+    output.forward();
+
+    let shape = [output.data().shape(), input.data().shape()].concat();
+    let mut jacobian = Array::zeros((output.data().len(), input.data().len()));
+    let mut seed = Array::zeros(output.data().raw_dim());
+    for (index, mut row) in jacobian.outer_iter_mut().enumerate() {
+        seed.fill(0.);
+        *seed.iter_mut().nth(index).unwrap() = 1.;
+
+        input.zero_grad();
+        output.backward_with(seed.clone());
+        row.assign(&Array::from_iter(input.grad().iter().copied()));
+    }
+
+    jacobian.into_shape(IxDyn(&shape)).unwrap()
+}
+
+/// Computes the Hessian of the scalar `function` at `input`.
+///
+/// The result has shape *(input shape, input shape)*, its element at *(i, j)* is the second
+/// order partial derivative of the output with respect to the *i*-th and the *j*-th elements of
+/// the input, where *i* and *j* are multi-indices.
+///
+/// The engine only computes first order derivatives, thus the Hessian is obtained by central
+/// differences of the exact gradients of `function`. The result is therefore an approximation,
+/// which is exact up to rounding for quadratic functions. The Hessian is symmetrized before being
+/// returned.
+///
+/// # Arguments
+///
+/// * `function` - builds the computational graph of a scalar function from its input.
+///
+/// * `input` - point at which the Hessian is computed.
+pub fn hessian<D, F, T, U>(function: F, input: &Array<f32, D>) -> ArrayD<f32>
+where
+    D: Dimension + 'static,
+    F: FnOnce(VarDiff<Input<D>, InputBackward<D>>) -> VarDiff<T, U>,
+    T: Data<Dim = Ix0> + ?Sized + 'static,
+    U: Gradient<Dim = Ix0> + ?Sized + 'static,
+{
+    let variable = crate::from_ndarray(input.clone()).requires_grad();
+    let output = function(variable.clone());
+
+    let gradient_at = |index: usize, value: f32| {
+        *variable.data_mut().iter_mut().nth(index).unwrap() = value;
+        output.forward();
+        variable.zero_grad();
+        output.backward(1.);
+        Array::from_iter(variable.grad().iter().copied())
+    };
+
+    let len = input.len();
+    let mut hessian = Array::zeros((len, len));
+    for (index, (&value, mut row)) in input.iter().zip(hessian.outer_iter_mut()).enumerate() {
+        let step = 5e-3 * value.abs().max(1.);
+        let forward = gradient_at(index, value + step);
+        let backward = gradient_at(index, value - step);
+        gradient_at(index, value);
+
+        row.assign(&((forward - backward) / (2. * step)));
+    }
+    let hessian = (&hessian + &hessian.t()) / 2.;
+
+    hessian
+        .into_shape(IxDyn(&[input.shape(), input.shape()].concat()))
+        .unwrap()
+}
+
+/// Computes the gradients of each sample's loss with respect to `params`.
+///
+/// `losses` must hold the unreduced losses of a batch, with the samples laid along its first
+/// axis. The result contains an array for each parameter, of shape *(batch size, parameter
+/// shape)*, whose *i*-th entry is the gradient of the sum of the losses of the *i*-th sample.
+///
+/// The graph is evaluated once and back-propagated through once per sample, so the cost is the
+/// one of a backward pass times the batch size. The forward pass must have been computed
+/// beforehand. The gradients accumulated in the parameters of the graph before the call are
+/// restored afterwards.
+///
+/// Per-sample gradients are only meaningful if the samples do not interact in the computation,
+/// for instance, batch normalization in training mode mixes the samples.
+///
+/// # Arguments
+///
+/// * `losses` - unreduced losses of the batch.
+///
+/// * `params` - parameters with respect to which the gradients are computed.
+///
+/// # Panics
+///
+/// If `losses` is zero-dimensional.
+///
+/// ```
+/// use ndarray::array;
+/// use neuronika::autograd;
+///
+/// let w = neuronika::from_ndarray(array![1., -1.]).requires_grad();
+/// let x = neuronika::from_ndarray(array![[1., 2.], [3., 4.], [5., 6.]]);
+///
+/// // Squared output of a linear model without bias.
+/// let losses = x.mv(w.clone()).pow(2);
+/// losses.forward();
+///
+/// let grads = autograd::per_sample_grad(&losses, &w.parameters());
+///
+/// assert_eq!(
+///     grads[0],
+///     array![[-2., -4.], [-6., -8.], [-10., -12.]].into_dyn()
+/// );
+/// ```
+pub fn per_sample_grad<T, U>(losses: &VarDiff<T, U>, params: &[Param]) -> Vec<ArrayD<f32>>
+where
+    T: Data + ?Sized + 'static,
+    U: Gradient<Dim = T::Dim> + ?Sized + 'static,
+{
+    assert!(
+        losses.data().ndim() > 0,
+        "error: the losses must have the samples laid along their first axis."
+    );
+
+    let batch_size = losses.data().len_of(Axis(0));
+    let mut grads: Vec<ArrayD<f32>> = params
+        .iter()
+        .map(|param| ArrayD::zeros(IxDyn(&[&[batch_size], param.grad.shape()].concat())))
+        .collect();
+
+    let accumulated: Vec<ArrayD<f32>> = losses
+        .parameters()
+        .iter()
+        .map(|param| param.grad.to_owned())
+        .collect();
+
+    let mut seed = Array::zeros(losses.data().raw_dim());
+    for sample in 0..batch_size {
+        seed.fill(0.);
+        seed.slice_axis_mut(Axis(0), Slice::from(sample..=sample))
+            .fill(1.);
+
+        losses.zero_grad();
+        losses.backward_with(seed.clone());
+        for (grad, param) in grads.iter_mut().zip(params) {
+            grad.index_axis_mut(Axis(0), sample).assign(&param.grad);
+        }
+    }
+
+    for (mut param, grad) in losses.parameters().into_iter().zip(accumulated) {
+        param.grad.assign(&grad);
+    }
+
+    grads
+}
+
+/// Gradient statistics of a single parameter, as reported by [`grad_flow`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct GradStats {
+    /// Shape of the parameter.
+    pub shape: Vec<usize>,
+    /// L2 norm of the gradient.
+    pub norm: f32,
+    /// Largest absolute value of the gradient, `NaN` entries excluded.
+    pub max_abs: f32,
+    /// Fraction of the entries of the gradient that are exactly zero.
+    pub zeros: f32,
+    /// Number of the entries of the gradient that are `NaN`.
+    pub nans: usize,
+}
+
+/// A report on the gradients of a set of parameters.
+///
+/// It is returned by [`grad_flow`] and can be printed with its [`Display`](fmt::Display)
+/// implementation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GradFlow {
+    params: Vec<GradStats>,
+}
+
+impl GradFlow {
+    /// Returns the statistics of each parameter, in the order they were given in.
+    pub fn params(&self) -> &[GradStats] {
+        &self.params
+    }
+
+    /// Returns the L2 norm of the gradients of all the parameters, taken as a single vector.
+    pub fn total_norm(&self) -> f32 {
+        self.params
+            .iter()
+            .map(|stats| stats.norm * stats.norm)
+            .sum::<f32>()
+            .sqrt()
+    }
+
+    /// Returns the number of `NaN` entries in the gradients of all the parameters.
+    pub fn nans(&self) -> usize {
+        self.params.iter().map(|stats| stats.nans).sum()
+    }
+
+    /// Returns the indices of the parameters whose gradient norm is lower than `threshold`.
+    pub fn vanishing(&self, threshold: f32) -> Vec<usize> {
+        self.select(|stats| stats.norm < threshold)
+    }
+
+    /// Returns the indices of the parameters whose gradient norm is greater than `threshold` or
+    /// isn't finite.
+    pub fn exploding(&self, threshold: f32) -> Vec<usize> {
+        self.select(|stats| !stats.norm.is_finite() || stats.norm > threshold)
+    }
+
+    fn select(&self, predicate: impl Fn(&GradStats) -> bool) -> Vec<usize> {
+        self.params
+            .iter()
+            .enumerate()
+            .filter(|(_, stats)| predicate(stats))
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+impl fmt::Display for GradFlow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>5}  {:<16}  {:>12}  {:>12}  {:>7}  {:>6}",
+            "Param", "Shape", "Norm", "Max", "Zeros", "NaNs"
+        )?;
+        for (index, stats) in self.params.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>5}  {:<16}  {:>12.6e}  {:>12.6e}  {:>6.2}%  {:>6}",
+                index,
+                format!("{:?}", stats.shape),
+                stats.norm,
+                stats.max_abs,
+                stats.zeros * 100.,
+                stats.nans
+            )?;
+        }
+        write!(f, "Total norm: {:e}", self.total_norm())
+    }
+}
+
+/// Computes the gradient statistics of `params`.
+///
+/// It should be called after [`.backward()`](crate::VarDiff::backward()) and before the
+/// gradients are zeroed by the optimizer.
+///
+/// # Arguments
+///
+/// `params` - parameters to inspect, as returned by `.parameters()`.
+pub fn grad_flow(params: &[Param]) -> GradFlow {
+    let params = params
+        .iter()
+        .map(|param| {
+            let grad = &param.grad;
+            let len = grad.len().max(1) as f32;
+
+            GradStats {
+                shape: grad.shape().to_vec(),
+                norm: grad.iter().map(|el| el * el).sum::<f32>().sqrt(),
+                max_abs: grad
+                    .iter()
+                    .filter(|el| !el.is_nan())
+                    .fold(0., |max: f32, el| max.max(el.abs())),
+                zeros: grad.iter().filter(|el| **el == 0.).count() as f32 / len,
+                nans: grad.iter().filter(|el| el.is_nan()).count(),
+            }
+        })
+        .collect();
+
+    GradFlow { params }
+}
+
+#[cfg(test)]
+mod test;
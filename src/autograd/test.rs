@@ -0,0 +1,198 @@
+use super::{hessian, is_anomaly_enabled, jacobian, per_sample_grad, set_detect_anomaly};
+use ndarray::array;
+
+#[test]
+fn set_detect_anomaly_transition() {
+    assert!(!is_anomaly_enabled());
+
+    set_detect_anomaly(true);
+    assert!(is_anomaly_enabled());
+
+    set_detect_anomaly(false);
+    assert!(!is_anomaly_enabled());
+}
+
+#[test]
+fn finite_computation() {
+    set_detect_anomaly(true);
+
+    let x = crate::full((2, 2), 4.).requires_grad();
+    let y = x.sqrt().sum();
+    y.forward();
+    y.backward(1.);
+
+    set_detect_anomaly(false);
+}
+
+#[test]
+#[should_panic(expected = "the data of node Logn [2, 2] contains non-finite values")]
+fn forward_anomaly() {
+    set_detect_anomaly(true);
+
+    let x = crate::zeros((2, 2)).requires_grad();
+    let y = (x.ln() * 2.).sum();
+    y.forward();
+}
+
+#[test]
+fn forward_anomaly_upstream() {
+    set_detect_anomaly(true);
+
+    let x = crate::zeros((2, 2));
+    let y = (x + 1.).ln() - crate::full((2, 2), f32::NAN);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| y.forward()));
+    set_detect_anomaly(false);
+
+    let message = result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("  Subtraction [2, 2]\n    Logn [2, 2]\n      AddScalar [2, 2]"));
+}
+
+#[test]
+#[should_panic(expected = "the gradient of node SumBackward [] contains non-finite values")]
+fn backward_anomaly() {
+    set_detect_anomaly(true);
+
+    let x = crate::ones((2, 2)).requires_grad();
+    let y = x.sum();
+    y.forward();
+    y.backward(f32::INFINITY);
+}
+
+#[test]
+fn jacobian_linear_map() {
+    let matrix = array![[1., 2., 3.], [4., 5., 6.]];
+
+    let jacobian = jacobian(
+        |x| crate::from_ndarray(matrix.clone()).mv(x),
+        &array![-1., 0., 1.],
+    );
+
+    assert_eq!(jacobian, matrix.into_dyn());
+}
+
+#[test]
+fn jacobian_shape() {
+    let jacobian = jacobian(|x| x.exp(), &array![[0., 0.], [0., 0.], [0., 0.]]);
+
+    assert_eq!(jacobian.shape(), &[3, 2, 3, 2]);
+    assert_eq!(jacobian.sum(), 6.);
+    assert_eq!(jacobian[[1, 0, 1, 0]], 1.);
+    assert_eq!(jacobian[[1, 0, 0, 1]], 0.);
+}
+
+#[test]
+fn hessian_quadratic_form() {
+    let matrix = array![[1., 2.], [0., 3.]];
+
+    let hessian = hessian(
+        |x| x.clone().vm(crate::from_ndarray(matrix.clone())).vv(x),
+        &array![0.5, -2.],
+    );
+
+    let expected = (&matrix + &matrix.t()).into_dyn();
+    assert_eq!(hessian.shape(), expected.shape());
+    assert!(hessian
+        .iter()
+        .zip(expected.iter())
+        .all(|(el, exp)| (el - exp).abs() < 1e-3));
+}
+
+#[test]
+fn per_sample_gradients() {
+    let inputs = array![[1., 2.], [-1., 0.5], [0., 3.]];
+    let targets = array![[1.], [0.], [2.]];
+    let weight = array![[0.5, -1.]];
+    let bias = array![0.25];
+
+    let build = |inputs, targets| {
+        let w = crate::from_ndarray(weight.clone()).requires_grad();
+        let b = crate::from_ndarray(bias.clone()).requires_grad();
+        let output = crate::from_ndarray(inputs).mm_t(w.clone()) + b.clone();
+        let losses = (output - crate::from_ndarray(targets)).pow(2);
+        losses.forward();
+        (losses, w, b)
+    };
+
+    let (losses, w, b) = build(inputs.clone(), targets.clone());
+    let grads = per_sample_grad(
+        &losses,
+        &[w.parameters().remove(0), b.parameters().remove(0)],
+    );
+    assert_eq!(grads[0].shape(), &[3, 1, 2]);
+    assert_eq!(grads[1].shape(), &[3, 1]);
+
+    for sample in 0..3 {
+        let (loss, w, b) = build(
+            inputs.slice(ndarray::s![sample..=sample, ..]).to_owned(),
+            targets.slice(ndarray::s![sample..=sample, ..]).to_owned(),
+        );
+        let loss = loss.sum();
+        loss.forward();
+        loss.backward(1.);
+
+        assert_eq!(
+            grads[0].index_axis(ndarray::Axis(0), sample),
+            w.grad().view().into_dyn()
+        );
+        assert_eq!(
+            grads[1].index_axis(ndarray::Axis(0), sample),
+            b.grad().view().into_dyn()
+        );
+    }
+}
+
+#[test]
+fn per_sample_gradients_keep_accumulated() {
+    let w = crate::from_ndarray(array![1., -1.]).requires_grad();
+    let x = crate::from_ndarray(array![[1., 2.], [3., 4.]]);
+    let losses = x.mv(w.clone()).pow(2);
+    losses.forward();
+
+    w.grad_mut().assign(&array![0.5, 0.25]);
+    per_sample_grad(&losses, &w.parameters());
+    assert_eq!(*w.grad(), array![0.5, 0.25]);
+}
+
+#[test]
+fn grad_flow() {
+    let w = crate::from_ndarray(array![3., 0., -4., 0.]).requires_grad();
+    let b = crate::from_ndarray(array![1.]).requires_grad();
+    let y = (w.clone() * crate::from_ndarray(array![1., 1., 1., 1.]) + b.clone()).sum();
+    y.forward();
+    y.backward(1.);
+
+    let mut params = w.parameters();
+    params.extend(b.parameters());
+    params[0].grad.assign(&array![3., 0., -4., 0.].into_dyn());
+
+    let flow = super::grad_flow(&params);
+    assert_eq!(
+        flow.params()[0],
+        super::GradStats {
+            shape: vec![4],
+            norm: 5.,
+            max_abs: 4.,
+            zeros: 0.5,
+            nans: 0,
+        }
+    );
+    assert_eq!(flow.params()[1].norm, 4.);
+    assert!((flow.total_norm() - 41f32.sqrt()).abs() < 1e-6);
+    assert_eq!(flow.vanishing(4.5), vec![1]);
+    assert_eq!(flow.exploding(4.5), vec![0]);
+    assert!(flow.to_string().ends_with("Total norm: 6.4031243e0"));
+}
+
+#[test]
+fn grad_flow_nan() {
+    let w = crate::from_ndarray(array![1., 2., 3.]).requires_grad();
+    let mut params = w.parameters();
+    params[0].grad.assign(&array![f32::NAN, 2., 0.].into_dyn());
+
+    let flow = super::grad_flow(&params);
+    assert_eq!(flow.nans(), 1);
+    assert_eq!(flow.params()[0].max_abs, 2.);
+    assert!(flow.params()[0].norm.is_nan());
+    assert_eq!(flow.exploding(f32::MAX), vec![0]);
+    assert!(flow.vanishing(f32::MAX).is_empty());
+}
@@ -0,0 +1,253 @@
+//! Micro-benchmarks of the forward and backward kernels of the computational nodes.
+//!
+//! A [`Bencher`] repeatedly evaluates a variable, after some warm-up runs, and collects the
+//! duration of each evaluation in a [`Measurement`]. When the operands of a variable are leaves,
+//! as in the example below, its forward and backward passes consist of a single node, so that
+//! the kernels of such node are measured in isolation, on the shapes of choice.
+//!
+//! ```
+//! use neuronika::bench::Bencher;
+//!
+//! let x = neuronika::rand((64, 128)).requires_grad();
+//! let w = neuronika::rand((32, 128)).requires_grad();
+//! let y = x.mm_t(w);
+//!
+//! let bencher = Bencher::new().warm_up(2).samples(10);
+//! let forward = bencher.forward("mm_t", &y);
+//! let backward = bencher.backward("mm_t", &y);
+//!
+//! println!("{}\n{}", forward, backward);
+//! assert_eq!(forward.samples().len(), 10);
+//! ```
+//!
+//! Measurements can be compared with [`.relative_to()`](Measurement::relative_to()) and, with the
+//! `serialize` feature, saved, so that performance regressions across releases can be detected.
+//! The benchmarks of the crate itself live in the `benches` directory and are run with
+//! `cargo bench --features bench`.
+use crate::{Data, Gradient, Var, VarDiff};
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{self, Display},
+    time::{Duration, Instant},
+};
+
+/// Variables whose forward pass can be benchmarked.
+pub trait BenchInput {
+    /// Computes the data of the variable, evaluating its whole history again.
+    fn forward(&self);
+}
+
+impl<T: ?Sized> BenchInput for Var<T>
+where
+    T: Data + 'static,
+{
+    fn forward(&self) {
+        Var::forward(self)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> BenchInput for VarDiff<T, U>
+where
+    T: Data + 'static,
+    U: Gradient<Dim = T::Dim> + 'static,
+{
+    fn forward(&self) {
+        VarDiff::forward(self)
+    }
+}
+
+/// Runs micro-benchmarks.
+///
+/// By default, each benchmark is preceded by *3* warm-up runs and is made of *20* samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bencher {
+    warm_up: usize,
+    samples: usize,
+}
+
+impl Bencher {
+    /// Creates a new bencher with the default settings.
+    pub fn new() -> Self {
+        Self {
+            warm_up: 3,
+            samples: 20,
+        }
+    }
+
+    /// Sets the number of runs that precede the measured ones.
+    pub fn warm_up(mut self, runs: usize) -> Self {
+        self.warm_up = runs;
+        self
+    }
+
+    /// Sets the number of measured runs.
+    ///
+    /// # Panics
+    ///
+    /// If `samples` is zero.
+    pub fn samples(mut self, samples: usize) -> Self {
+        assert!(
+            samples > 0,
+            "error: the number of samples must be positive."
+        );
+        self.samples = samples;
+        self
+    }
+
+    /// Measures the duration of `routine`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - name of the benchmark.
+    ///
+    /// * `routine` - code to benchmark.
+    pub fn run<F: FnMut()>(&self, name: &str, mut routine: F) -> Measurement {
+        for _ in 0..self.warm_up {
+            routine();
+        }
+
+        let samples = (0..self.samples)
+            .map(|_| {
+                let start = Instant::now();
+                routine();
+                start.elapsed()
+            })
+            .collect();
+
+        Measurement {
+            name: name.to_string(),
+            samples,
+        }
+    }
+
+    /// Measures the duration of the forward pass of `variable`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - name of the benchmark.
+    ///
+    /// * `variable` - variable to evaluate.
+    pub fn forward<V: BenchInput>(&self, name: &str, variable: &V) -> Measurement {
+        self.run(&format!("{} forward", name), || variable.forward())
+    }
+
+    /// Measures the duration of the backward pass of `variable`, whose data is computed once
+    /// beforehand.
+    ///
+    /// The gradient of `variable` is seeded with ones and the ones of its ancestors keep being
+    /// accumulated.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - name of the benchmark.
+    ///
+    /// * `variable` - variable to differentiate.
+    pub fn backward<T, U>(&self, name: &str, variable: &VarDiff<T, U>) -> Measurement
+    where
+        T: Data + 'static + ?Sized,
+        U: Gradient<Dim = T::Dim> + 'static + ?Sized,
+    {
+        variable.forward();
+        self.run(&format!("{} backward", name), || variable.backward(1.))
+    }
+}
+
+impl Default for Bencher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The durations of the runs of a benchmark.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    name: String,
+    samples: Vec<Duration>,
+}
+
+impl Measurement {
+    /// Returns the name of the benchmark.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the duration of each measured run, in order of execution.
+    pub fn samples(&self) -> &[Duration] {
+        &self.samples
+    }
+
+    /// Returns the mean duration of the runs.
+    pub fn mean(&self) -> Duration {
+        self.samples.iter().sum::<Duration>() / self.samples.len() as u32
+    }
+
+    /// Returns the median duration of the runs.
+    pub fn median(&self) -> Duration {
+        let mut samples = self.samples.clone();
+        samples.sort_unstable();
+
+        let middle = samples.len() / 2;
+        if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        }
+    }
+
+    /// Returns the duration of the fastest run.
+    pub fn min(&self) -> Duration {
+        self.samples.iter().min().copied().unwrap()
+    }
+
+    /// Returns the duration of the slowest run.
+    pub fn max(&self) -> Duration {
+        self.samples.iter().max().copied().unwrap()
+    }
+
+    /// Returns the standard deviation of the durations of the runs.
+    pub fn std_dev(&self) -> Duration {
+        if self.samples.len() < 2 {
+            return Duration::ZERO;
+        }
+
+        let mean = self.mean().as_secs_f64();
+        let variance = self
+            .samples
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / (self.samples.len() - 1) as f64;
+        Duration::from_secs_f64(variance.sqrt())
+    }
+
+    /// Returns the ratio between the median duration of `self` and the one of `baseline`.
+    ///
+    /// A value greater than *1* means that `self` is slower than `baseline`.
+    pub fn relative_to(&self, baseline: &Measurement) -> f64 {
+        self.median().as_secs_f64() / baseline.median().as_secs_f64()
+    }
+}
+
+impl Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<40} median {:>12?}  mean {:>12?} ± {:<12?} min {:>12?}  max {:>12?}  ({} samples)",
+            self.name,
+            self.median(),
+            self.mean(),
+            self.std_dev(),
+            self.min(),
+            self.max(),
+            self.samples.len()
+        )
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
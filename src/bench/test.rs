@@ -0,0 +1,57 @@
+use super::{Bencher, Measurement};
+use std::{cell::Cell, time::Duration};
+
+fn measurement(millis: &[u64]) -> Measurement {
+    Measurement {
+        name: "test".to_string(),
+        samples: millis.iter().copied().map(Duration::from_millis).collect(),
+    }
+}
+
+#[test]
+fn statistics() {
+    let measurement = measurement(&[4, 1, 3, 2]);
+
+    assert_eq!(measurement.mean(), Duration::from_micros(2500));
+    assert_eq!(measurement.median(), Duration::from_micros(2500));
+    assert_eq!(measurement.min(), Duration::from_millis(1));
+    assert_eq!(measurement.max(), Duration::from_millis(4));
+    assert!((measurement.std_dev().as_secs_f64() - 0.0012910).abs() < 1e-6);
+}
+
+#[test]
+fn relative_to() {
+    let (current, baseline) = (measurement(&[6, 2, 4]), measurement(&[2, 1, 3]));
+
+    assert!((current.relative_to(&baseline) - 2.).abs() < 1e-9);
+}
+
+#[test]
+fn runs() {
+    let calls = Cell::new(0);
+    let measurement = Bencher::new()
+        .warm_up(2)
+        .samples(5)
+        .run("count", || calls.set(calls.get() + 1));
+
+    assert_eq!(calls.get(), 7);
+    assert_eq!(measurement.name(), "count");
+    assert_eq!(measurement.samples().len(), 5);
+}
+
+#[test]
+fn backward() {
+    let x = crate::ones(3).requires_grad();
+    let y = x.clone() * 2.;
+
+    let measurement = Bencher::new().warm_up(1).samples(2).backward("mul", &y);
+
+    assert_eq!(measurement.name(), "mul backward");
+    assert_eq!(*x.grad(), ndarray::array![6., 6., 6.]);
+}
+
+#[test]
+#[should_panic(expected = "error: the number of samples must be positive.")]
+fn zero_samples() {
+    let _ = Bencher::new().samples(0);
+}
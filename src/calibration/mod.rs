@@ -0,0 +1,147 @@
+//! Post-training calibration of classifiers.
+//!
+//! The probabilities predicted by a trained classifier are often overconfident, so that they can
+//! not be read as the actual likelihood of the predictions being correct. Calibration fixes this
+//! after training, by fitting a transformation of the logits on a held-out validation set, whose
+//! logits are computed once by the frozen model.
+//!
+//! * [`TemperatureScaling`] - Divides the logits by a single temperature, which is fitted by
+//! minimizing the negative log likelihood of the validation targets. It leaves the predicted
+//! classes, and thus the accuracy, unchanged.
+//!
+//! The quality of the calibration is measured by the
+//! [`ExpectedCalibrationError`](crate::metrics::ExpectedCalibrationError).
+//!
+//! ```
+//! use neuronika::calibration::TemperatureScaling;
+//! use neuronika::metrics::{ExpectedCalibrationError, Metric};
+//!
+//! // The class predicted with probability 0.98 is right only three times out of four.
+//! let logits = ndarray::array![[4., 0.], [4., 0.], [4., 0.], [4., 0.]];
+//! let targets = ndarray::array![0., 0., 0., 1.];
+//!
+//! let mut scaling = TemperatureScaling::new();
+//! let mut ece = ExpectedCalibrationError::new(10);
+//! ece.update(&scaling.probabilities(&logits), &targets);
+//! let before = ece.compute();
+//!
+//! scaling.fit(&logits, &targets, 500, 0.05);
+//! ece.reset();
+//! ece.update(&scaling.probabilities(&logits), &targets);
+//!
+//! assert!(scaling.temperature() > 1.);
+//! assert!(ece.compute() < before);
+//! ```
+use crate::{
+    nn::loss::{nll_loss, Reduction},
+    optim::{Adam, L2},
+};
+use ndarray::{Array1, Array2};
+
+/// Calibrates the probabilities of a classifier by dividing its logits by a temperature, as
+/// described in [On Calibration of Modern Neural Networks](https://arxiv.org/abs/1706.04599).
+///
+/// ```text
+/// p = Softmax(z / T)
+/// ```
+///
+/// A temperature greater than *1* softens the probabilities, while one smaller than *1* sharpens
+/// them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TemperatureScaling {
+    temperature: f32,
+}
+
+impl TemperatureScaling {
+    /// Creates a new TemperatureScaling with a temperature of *1*, that leaves the logits
+    /// unchanged.
+    pub fn new() -> Self {
+        Self { temperature: 1. }
+    }
+
+    /// Returns the temperature.
+    pub fn temperature(&self) -> f32 {
+        self.temperature
+    }
+
+    /// Fits the temperature to the logits and the targets of a validation set by gradient descent,
+    /// starting from the current temperature, and returns the negative log likelihood of the
+    /// targets under the calibrated probabilities.
+    ///
+    /// The logarithm of the temperature is optimized, so that the temperature stays positive.
+    ///
+    /// # Arguments
+    ///
+    /// * `logits` - unnormalized scores of shape *(batch, classes)* computed by the frozen model.
+    ///
+    /// * `targets` - class indices of shape *(batch)*.
+    ///
+    /// * `iterations` - number of optimization steps.
+    ///
+    /// * `lr` - learning rate of the [`Adam`] optimizer.
+    ///
+    /// # Panics
+    ///
+    /// If the batch sizes of `logits` and `targets` differ.
+    pub fn fit(
+        &mut self,
+        logits: &Array2<f32>,
+        targets: &Array1<f32>,
+        iterations: usize,
+        lr: f32,
+    ) -> f32 {
+        assert_eq!(
+            logits.nrows(),
+            targets.len(),
+            "error: logits and targets have different batch sizes: {} and {}.",
+            logits.nrows(),
+            targets.len()
+        );
+
+        let log_temperature = crate::full(1, self.temperature.ln()).requires_grad();
+        let scaled = crate::from_ndarray(logits.clone()) / log_temperature.clone().exp();
+        let loss = nll_loss(
+            scaled.log_softmax(1),
+            crate::from_ndarray(targets.clone()),
+            Reduction::Mean,
+        );
+        let optimizer = Adam::new(loss.parameters(), lr, (0.9, 0.999), L2::new(0.), 1e-8);
+
+        for _ in 0..iterations {
+            loss.forward();
+            loss.backward(1.);
+            optimizer.step();
+            optimizer.zero_grad();
+        }
+        loss.forward();
+
+        self.temperature = log_temperature.data()[0].exp();
+        loss.item()
+    }
+
+    /// Divides `logits`, of shape *(batch, classes)*, by the temperature.
+    pub fn scale(&self, logits: &Array2<f32>) -> Array2<f32> {
+        logits / self.temperature
+    }
+
+    /// Returns the calibrated probabilities of `logits`, of shape *(batch, classes)*.
+    pub fn probabilities(&self, logits: &Array2<f32>) -> Array2<f32> {
+        let probabilities = crate::from_ndarray(self.scale(logits)).softmax(1);
+        probabilities.forward();
+
+        let data = probabilities.data().to_owned();
+        data
+    }
+}
+
+impl Default for TemperatureScaling {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,39 @@
+use super::TemperatureScaling;
+use crate::metrics::{ExpectedCalibrationError, Metric};
+use ndarray::array;
+
+#[test]
+fn identity() {
+    let scaling = TemperatureScaling::default();
+    let logits = array![[0., 2f32.ln()], [3f32.ln(), 0.]];
+
+    assert_eq!(scaling.temperature(), 1.);
+    assert_eq!(scaling.scale(&logits), logits);
+    let probabilities = scaling.probabilities(&logits);
+    assert!((probabilities[[0, 1]] - 2. / 3.).abs() < 1e-6);
+    assert!((probabilities[[1, 0]] - 0.75).abs() < 1e-6);
+}
+
+#[test]
+fn fit() {
+    // The optimal probability of the predicted class is 3 / 4, so that 4 / T = ln 3.
+    let logits = array![[4., 0.], [4., 0.], [0., 4.], [4., 0.]];
+    let targets = array![0., 0., 1., 1.];
+
+    let mut scaling = TemperatureScaling::new();
+    let nll = scaling.fit(&logits, &targets, 1_000, 0.05);
+
+    assert!((scaling.temperature() - 4. / 3f32.ln()).abs() < 1e-2);
+    assert!((nll - (-0.75 * 0.75f32.ln() - 0.25 * 0.25f32.ln())).abs() < 1e-4);
+
+    // The calibrated confidence matches the accuracy.
+    let mut ece = ExpectedCalibrationError::new(10);
+    ece.update(&scaling.probabilities(&logits), &targets);
+    assert!(ece.compute() < 1e-2);
+}
+
+#[test]
+#[should_panic(expected = "error: logits and targets have different batch sizes: 2 and 3.")]
+fn batch_size_mismatch() {
+    TemperatureScaling::new().fit(&array![[0., 1.], [1., 0.]], &array![0., 1., 0.], 1, 0.1);
+}
@@ -0,0 +1,128 @@
+use flate2::read::GzDecoder;
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Read},
+    path::Path,
+};
+
+/// Mirror of the MNIST files, the original website does not serve them to scripts.
+const MNIST_URL: &str = "https://ossci-datasets.s3.amazonaws.com/mnist/";
+
+const FASHION_MNIST_URL: &str = "http://fashion-mnist.s3-website.eu-central-1.amazonaws.com/";
+
+const CIFAR10_URL: &str = "https://www.cs.toronto.edu/~kriz/cifar-10-binary.tar.gz";
+
+/// Files of the datasets in the IDX format, they are distributed gzipped.
+const IDX_FILES: [&str; 4] = [
+    "train-images-idx3-ubyte",
+    "train-labels-idx1-ubyte",
+    "t10k-images-idx3-ubyte",
+    "t10k-labels-idx1-ubyte",
+];
+
+const CIFAR10_FILES: [&str; 6] = [
+    "data_batch_1.bin",
+    "data_batch_2.bin",
+    "data_batch_3.bin",
+    "data_batch_4.bin",
+    "data_batch_5.bin",
+    "test_batch.bin",
+];
+
+/// Starts the download of `url` and returns the body of the response.
+fn fetch(url: &str) -> io::Result<impl Read> {
+    ureq::get(url)
+        .call()
+        .map(ureq::Response::into_reader)
+        .map_err(|error| io::Error::other(format!("{}: {}", url, error)))
+}
+
+/// Decompresses the gzipped stream `reader` into the file at `path`.
+///
+/// The data is written to a temporary file first, so that an interrupted download never leaves
+/// a truncated file at `path`.
+fn gunzip<R: Read>(reader: R, path: &Path) -> io::Result<()> {
+    let partial = path.with_extension("part");
+    let mut writer = BufWriter::new(File::create(&partial)?);
+    io::copy(&mut GzDecoder::new(reader), &mut writer)?;
+    drop(writer);
+    fs::rename(partial, path)
+}
+
+/// Extracts the regular files of the gzipped tar archive `reader` into `dir`, dropping the
+/// directories they are in.
+fn untar<R: Read>(reader: R, dir: &Path) -> io::Result<()> {
+    let mut archive = tar::Archive::new(GzDecoder::new(reader));
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let path = entry.path()?.into_owned();
+        if let Some(name) = path.file_name() {
+            entry.unpack(dir.join(name))?;
+        }
+    }
+    Ok(())
+}
+
+/// Downloads the files in the IDX format from `url` into `dir`, skipping the ones already there.
+fn download_idx(url: &str, dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    for file in IDX_FILES {
+        let path = dir.join(file);
+        if !path.exists() {
+            gunzip(fetch(&format!("{}{}.gz", url, file))?, &path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Downloads the MNIST dataset into `dir` and decompresses it, so that it can be loaded with
+/// [`mnist`](super::mnist).
+///
+/// The files already in `dir` are not downloaded again.
+///
+/// # Errors
+///
+/// If the files cannot be downloaded or written.
+pub fn download_mnist<P: AsRef<Path>>(dir: P) -> io::Result<()> {
+    download_idx(MNIST_URL, dir.as_ref())
+}
+
+/// Downloads the Fashion-MNIST dataset into `dir` and decompresses it, so that it can be loaded
+/// with [`fashion_mnist`](super::fashion_mnist).
+///
+/// The files already in `dir` are not downloaded again.
+///
+/// # Errors
+///
+/// If the files cannot be downloaded or written.
+pub fn download_fashion_mnist<P: AsRef<Path>>(dir: P) -> io::Result<()> {
+    download_idx(FASHION_MNIST_URL, dir.as_ref())
+}
+
+/// Downloads the binary version of the CIFAR-10 dataset into `dir` and extracts it, so that it
+/// can be loaded with [`cifar10`](super::cifar10).
+///
+/// Nothing is downloaded if all the files are already in `dir`.
+///
+/// # Errors
+///
+/// If the archive cannot be downloaded or extracted.
+pub fn download_cifar10<P: AsRef<Path>>(dir: P) -> io::Result<()> {
+    let dir = dir.as_ref();
+    if CIFAR10_FILES.iter().all(|file| dir.join(file).exists()) {
+        return Ok(());
+    }
+
+    fs::create_dir_all(dir)?;
+    untar(fetch(CIFAR10_URL)?, dir)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
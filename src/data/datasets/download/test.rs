@@ -0,0 +1,62 @@
+use super::{gunzip, untar};
+use flate2::{write::GzEncoder, Compression};
+use std::{fs, io::Write, path::PathBuf};
+
+fn dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "neuronika-download-{}-{}",
+        name,
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn gunzip_file() {
+    let dir = dir("gunzip");
+    let path = dir.join("train-labels-idx1-ubyte");
+
+    gunzip(&gzip(&[0, 0, 8, 1, 0, 0, 0, 1, 7])[..], &path).unwrap();
+    assert_eq!(fs::read(&path).unwrap(), vec![0, 0, 8, 1, 0, 0, 0, 1, 7]);
+    assert!(!path.with_extension("part").exists());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn untar_archive() {
+    let dir = dir("untar");
+
+    let mut builder = tar::Builder::new(Vec::new());
+    let mut header = tar::Header::new_gnu();
+    header.set_entry_type(tar::EntryType::Directory);
+    header.set_size(0);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, "cifar-10-batches-bin/", &[][..])
+        .unwrap();
+    for (name, data) in [("data_batch_1.bin", &[1, 2][..]), ("readme.html", &[3][..])] {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, format!("cifar-10-batches-bin/{}", name), data)
+            .unwrap();
+    }
+    let archive = gzip(&builder.into_inner().unwrap());
+
+    untar(&archive[..], &dir).unwrap();
+    assert_eq!(fs::read(dir.join("data_batch_1.bin")).unwrap(), vec![1, 2]);
+    assert_eq!(fs::read(dir.join("readme.html")).unwrap(), vec![3]);
+    assert!(!dir.join("cifar-10-batches-bin").exists());
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,350 @@
+use super::{invalid_data, LabeledDataset};
+use crate::data::transforms::Transform;
+use ndarray::{Array, Array1, Array3, Ix1, Ix4};
+use std::{
+    fs,
+    io::{self, BufRead},
+    path::{Path, PathBuf},
+};
+
+/// A function that decodes the image stored in a file.
+type Decoder = dyn Fn(&Path) -> io::Result<Array3<f32>>;
+
+/// A dataset of images organized in a directory per class.
+///
+/// The root directory must contain a sub-directory for each class, holding the images of that
+/// class. Classes are sorted by name and labelled with their index in that order.
+///
+/// ```text
+/// root/
+/// ├── cat/
+/// │   ├── 001.ppm
+/// │   └── 002.ppm
+/// └── dog/
+///     └── 001.ppm
+/// ```
+///
+/// By default the images are decoded with [`read_netpbm`], which supports the binary PGM and PPM
+/// formats. With the `image` feature enabled they are decoded with `read_image` instead, which
+/// also supports BMP, GIF, JPEG and PNG. Any other format can be decoded by supplying a custom
+/// decoder with [`.with_decoder()`](ImageFolder::with_decoder).
+///
+/// Images are decoded lazily, either one at a time with [`.get()`](ImageFolder::get) or all at
+/// once with [`.load()`](ImageFolder::load), after going through the optional
+/// [`Transform`].
+///
+/// ```no_run
+/// use neuronika::data::{
+///     datasets::ImageFolder,
+///     transforms::{CenterCrop, Compose, Normalize, Resize},
+/// };
+///
+/// let folder = ImageFolder::new("data/pets").unwrap().with_transform(
+///     Compose::new()
+///         .then(Resize::new(72, 72))
+///         .then(CenterCrop::new(64, 64))
+///         .then(Normalize::new(&[0.5, 0.5, 0.5], &[0.25, 0.25, 0.25])),
+/// );
+///
+/// let dataset = folder.load().unwrap();
+/// assert_eq!(dataset.records().shape()[1..], [3, 64, 64]);
+/// ```
+pub struct ImageFolder {
+    classes: Vec<String>,
+    samples: Vec<(PathBuf, usize)>,
+    decoder: Box<Decoder>,
+    transform: Option<Box<dyn Transform>>,
+}
+
+/// Returns the sorted entries of `dir` that satisfy `filter`, skipping hidden ones.
+fn sorted_entries<F>(dir: &Path, filter: F) -> io::Result<Vec<PathBuf>>
+where
+    F: Fn(&Path) -> bool,
+{
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let visible = path
+            .file_name()
+            .is_some_and(|name| !name.to_string_lossy().starts_with('.'));
+        if visible && filter(&path) {
+            entries.push(path);
+        }
+    }
+    entries.sort();
+
+    Ok(entries)
+}
+
+#[cfg(not(feature = "image"))]
+fn default_decoder() -> Box<Decoder> {
+    use std::{fs::File, io::BufReader};
+
+    Box::new(|path| read_netpbm(BufReader::new(File::open(path)?)))
+}
+
+#[cfg(feature = "image")]
+fn default_decoder() -> Box<Decoder> {
+    Box::new(|path| read_image(path))
+}
+
+impl ImageFolder {
+    /// Scans `root` for the classes and their images.
+    ///
+    /// Hidden files and directories, whose names start with a dot, are ignored.
+    ///
+    /// # Errors
+    ///
+    /// If the directory cannot be read.
+    pub fn new<P: AsRef<Path>>(root: P) -> io::Result<Self> {
+        let mut classes = Vec::new();
+        let mut samples = Vec::new();
+        for (label, dir) in sorted_entries(root.as_ref(), Path::is_dir)?
+            .into_iter()
+            .enumerate()
+        {
+            classes.push(dir.file_name().unwrap().to_string_lossy().into_owned());
+            for path in sorted_entries(&dir, Path::is_file)? {
+                samples.push((path, label));
+            }
+        }
+
+        Ok(Self {
+            classes,
+            samples,
+            decoder: default_decoder(),
+            transform: None,
+        })
+    }
+
+    /// Sets the function used to decode the images.
+    ///
+    /// The decoder must return images of shape *(channels, height, width)*.
+    pub fn with_decoder<F>(mut self, decoder: F) -> Self
+    where
+        F: Fn(&Path) -> io::Result<Array3<f32>> + 'static,
+    {
+        self.decoder = Box::new(decoder);
+        self
+    }
+
+    /// Sets the transform applied to each decoded image.
+    pub fn with_transform<T: Transform + 'static>(mut self, transform: T) -> Self {
+        self.transform = Some(Box::new(transform));
+        self
+    }
+
+    /// Returns the names of the classes, sorted by label.
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    /// Returns the paths of the images together with their labels.
+    pub fn samples(&self) -> &[(PathBuf, usize)] {
+        &self.samples
+    }
+
+    /// Returns the number of images.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Returns `true` if there are no images.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Decodes and transforms the image at position `index`, returning it together with its
+    /// label.
+    ///
+    /// # Errors
+    ///
+    /// If the image cannot be decoded.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn get(&self, index: usize) -> io::Result<(Array3<f32>, usize)> {
+        let (path, label) = &self.samples[index];
+        let image = (self.decoder)(path).map_err(|error| {
+            io::Error::new(error.kind(), format!("{}: {}", path.display(), error))
+        })?;
+        let image = match &self.transform {
+            Some(transform) => transform.apply(image),
+            None => image,
+        };
+
+        Ok((image, *label))
+    }
+
+    /// Decodes and transforms all the images, returning them as a labeled dataset of shape
+    /// *(samples, channels, height, width)*.
+    ///
+    /// # Errors
+    ///
+    /// If an image cannot be decoded or if the transformed images have different shapes.
+    pub fn load(&self) -> io::Result<LabeledDataset<Ix4, Ix1>> {
+        let mut shape = None;
+        let mut records = Vec::new();
+        let mut labels = Vec::with_capacity(self.len());
+        for index in 0..self.len() {
+            let (image, label) = self.get(index)?;
+            match shape {
+                None => shape = Some(image.dim()),
+                Some(shape) if shape != image.dim() => {
+                    return Err(invalid_data(format!(
+                        "{} has shape {:?}, but previous images have shape {:?}",
+                        self.samples[index].0.display(),
+                        image.dim(),
+                        shape
+                    )))
+                }
+                _ => (),
+            }
+            records.extend(image.iter());
+            labels.push(label as f32);
+        }
+
+        let (channels, height, width) = shape.unwrap_or((0, 0, 0));
+        Ok(LabeledDataset::new(
+            Array::from_shape_vec((self.len(), channels, height, width), records).unwrap(),
+            Array1::from(labels),
+        ))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Netpbm ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Reads a non-negative integer of a Netpbm header, skipping the whitespace and the comments
+/// before it and consuming the single whitespace character after it.
+fn read_header_value<R: BufRead>(reader: &mut R) -> io::Result<usize> {
+    let mut byte = [0];
+    loop {
+        reader.read_exact(&mut byte)?;
+        match byte[0] {
+            b'#' => {
+                reader.read_until(b'\n', &mut Vec::new())?;
+            }
+            byte if byte.is_ascii_whitespace() => (),
+            _ => break,
+        }
+    }
+
+    let mut value = 0usize;
+    loop {
+        if !byte[0].is_ascii_digit() {
+            return Err(invalid_data(format!(
+                "unexpected character {:?} in Netpbm header",
+                byte[0] as char
+            )));
+        }
+        value = value
+            .checked_mul(10)
+            .and_then(|value| value.checked_add((byte[0] - b'0') as usize))
+            .ok_or_else(|| invalid_data("Netpbm header value out of range".to_string()))?;
+
+        reader.read_exact(&mut byte)?;
+        if byte[0].is_ascii_whitespace() {
+            return Ok(value);
+        }
+    }
+}
+
+/// Reads an image in the binary PGM (`P5`) or PPM (`P6`) format.
+///
+/// The image is returned with shape *(channels, height, width)*, with one channel for PGM and
+/// three for PPM, and its values are scaled to *[0, 1]*.
+///
+/// # Errors
+///
+/// If the data cannot be read or is not a valid binary PGM or PPM image.
+pub fn read_netpbm<R: BufRead>(mut reader: R) -> io::Result<Array3<f32>> {
+    let mut magic = [0; 2];
+    reader.read_exact(&mut magic)?;
+    let channels = match &magic {
+        b"P5" => 1,
+        b"P6" => 3,
+        _ => {
+            return Err(invalid_data(format!(
+                "unsupported Netpbm magic number {:?}",
+                String::from_utf8_lossy(&magic)
+            )))
+        }
+    };
+
+    let width = read_header_value(&mut reader)?;
+    let height = read_header_value(&mut reader)?;
+    let max = read_header_value(&mut reader)?;
+    if max == 0 || max > u16::MAX as usize {
+        return Err(invalid_data(format!(
+            "invalid Netpbm maximum value {}",
+            max
+        )));
+    }
+
+    let sample_size = if max > u8::MAX as usize { 2 } else { 1 };
+    let mut bytes = vec![0; height * width * channels * sample_size];
+    reader.read_exact(&mut bytes)?;
+    let samples: Vec<f32> = if sample_size == 1 {
+        bytes.iter().map(|&sample| sample as f32).collect()
+    } else {
+        bytes
+            .chunks_exact(2)
+            .map(|chunk| u16::from_be_bytes([chunk[0], chunk[1]]) as f32)
+            .collect()
+    };
+
+    // Samples are stored interleaved, one pixel after the other.
+    let image = Array::from_shape_vec((height, width, channels), samples).unwrap();
+    Ok(image
+        .permuted_axes([2, 0, 1])
+        .as_standard_layout()
+        .mapv(|sample| sample / max as f32))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Image ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Decodes the image stored in the file at `path` with the [`image`] crate, guessing its format
+/// from the content.
+///
+/// Grayscale images are returned with one channel and any other image with three, the alpha
+/// channel is dropped. The image has shape *(channels, height, width)* and its values are scaled
+/// to *[0, 1]*.
+///
+/// # Errors
+///
+/// If the file cannot be read or its format is not supported.
+#[cfg(feature = "image")]
+pub fn read_image<P: AsRef<Path>>(path: P) -> io::Result<Array3<f32>> {
+    use image::{io::Reader, ImageError};
+
+    let image = Reader::open(path)?
+        .with_guessed_format()?
+        .decode()
+        .map_err(|error| match error {
+            ImageError::IoError(error) => error,
+            error => invalid_data(error.to_string()),
+        })?;
+
+    let (width, height) = (image.width() as usize, image.height() as usize);
+    let (channels, samples) = if image.color().has_color() {
+        (3, image.into_rgb32f().into_raw())
+    } else {
+        (1, image.to_luma32f().into_raw())
+    };
+
+    // Samples are stored interleaved, one pixel after the other.
+    let image = Array::from_shape_vec((height, width, channels), samples).unwrap();
+    Ok(image
+        .permuted_axes([2, 0, 1])
+        .as_standard_layout()
+        .into_owned())
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
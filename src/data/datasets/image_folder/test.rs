@@ -0,0 +1,128 @@
+use super::{read_netpbm, ImageFolder};
+use crate::data::transforms::Resize;
+use ndarray::{array, Array3};
+use std::{fs, path::PathBuf};
+
+fn dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "neuronika-image-folder-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn pgm(width: usize, height: usize, pixels: &[u8]) -> Vec<u8> {
+    let mut bytes = format!("P5\n{} {}\n255\n", width, height).into_bytes();
+    bytes.extend_from_slice(pixels);
+    bytes
+}
+
+#[test]
+fn netpbm() {
+    let gray = read_netpbm(&pgm(2, 1, &[0, 255])[..]).unwrap();
+    assert_eq!(gray, array![[[0., 1.]]]);
+
+    let mut ppm = b"P6 # a comment\n2 1 # another one\n255 ".to_vec();
+    ppm.extend_from_slice(&[255, 0, 0, 0, 0, 255]);
+    let color = read_netpbm(&ppm[..]).unwrap();
+    assert_eq!(color, array![[[1., 0.]], [[0., 0.]], [[0., 1.]]]);
+
+    let mut wide = b"P5 1 1 1023\n".to_vec();
+    wide.extend_from_slice(&1023u16.to_be_bytes());
+    assert_eq!(read_netpbm(&wide[..]).unwrap(), array![[[1.]]]);
+}
+
+#[test]
+fn netpbm_errors() {
+    assert!(read_netpbm(&b"P3 1 1 255\n0 0 0"[..]).is_err());
+    assert!(read_netpbm(&b"P5 1 x 255\n\0"[..]).is_err());
+    assert!(read_netpbm(&b"P5 1 1 0\n\0"[..]).is_err());
+    assert!(read_netpbm(&pgm(2, 2, &[0, 1])[..]).is_err());
+}
+
+#[test]
+fn image_folder() {
+    let root = dir("load");
+    for class in ["dog", "cat", ".hidden"] {
+        fs::create_dir(root.join(class)).unwrap();
+    }
+    fs::write(root.join("cat/a.pgm"), pgm(2, 2, &[0, 51, 102, 255])).unwrap();
+    fs::write(root.join("cat/b.pgm"), pgm(4, 4, &[255; 16])).unwrap();
+    fs::write(root.join("cat/.DS_Store"), b"").unwrap();
+    fs::write(root.join("dog/c.pgm"), pgm(1, 1, &[0])).unwrap();
+    fs::write(root.join("README"), b"").unwrap();
+
+    let folder = ImageFolder::new(&root).unwrap();
+    assert_eq!(folder.classes(), &["cat", "dog"]);
+    assert_eq!(folder.len(), 3);
+    assert_eq!(folder.samples()[2], (root.join("dog/c.pgm"), 1));
+
+    let (image, label) = folder.get(0).unwrap();
+    assert_eq!(image, array![[[0., 0.2], [0.4, 1.]]]);
+    assert_eq!(label, 0);
+
+    // The images have different sizes.
+    assert!(folder.load().is_err());
+
+    let dataset = folder.with_transform(Resize::new(2, 2)).load().unwrap();
+    assert_eq!(dataset.records().shape(), &[3, 1, 2, 2]);
+    assert_eq!(dataset.labels(), &array![0., 0., 1.]);
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn custom_decoder() {
+    let root = dir("decoder");
+    fs::create_dir(root.join("class")).unwrap();
+    fs::write(root.join("class/image.raw"), [7]).unwrap();
+
+    let folder = ImageFolder::new(&root)
+        .unwrap()
+        .with_decoder(|path| Ok(Array3::from_elem((1, 1, 1), fs::read(path)?[0] as f32)));
+    assert_eq!(folder.get(0).unwrap().0, array![[[7.]]]);
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn image_decoder() {
+    use super::read_image;
+    use image::{GrayImage, RgbaImage};
+
+    let root = dir("image");
+    fs::create_dir(root.join("gray")).unwrap();
+    fs::create_dir(root.join("rgba")).unwrap();
+    GrayImage::from_raw(2, 1, vec![0, 51])
+        .unwrap()
+        .save(root.join("gray/a.png"))
+        .unwrap();
+    RgbaImage::from_raw(1, 1, vec![255, 0, 102, 7])
+        .unwrap()
+        .save(root.join("rgba/b.png"))
+        .unwrap();
+    fs::write(root.join("gray/c.pgm"), pgm(1, 1, &[255])).unwrap();
+
+    assert_eq!(
+        read_image(root.join("gray/a.png")).unwrap(),
+        array![[[0., 0.2]]]
+    );
+    assert!(read_image(root.join("missing.png")).is_err());
+
+    // The default decoder supports PNG images, dropping their alpha channel, as well as Netpbm.
+    let folder = ImageFolder::new(&root).unwrap();
+    assert_eq!(folder.get(1).unwrap().0, array![[[1.]]]);
+    assert_eq!(folder.get(2).unwrap().0, array![[[1.]], [[0.]], [[0.4]]]);
+
+    fs::write(root.join("gray/d.png"), b"not an image").unwrap();
+    assert_eq!(
+        read_image(root.join("gray/d.png")).unwrap_err().kind(),
+        std::io::ErrorKind::InvalidData
+    );
+
+    fs::remove_dir_all(root).unwrap();
+}
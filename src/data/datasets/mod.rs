@@ -0,0 +1,238 @@
+//! Common datasets.
+//!
+//! This module parses the files in which some popular computer vision datasets are distributed
+//! and returns them as [`LabeledDataset`]s ready to be batched. Images are returned as tensors of
+//! shape *(samples, channels, height, width)* with values in *[0, 1]*, while labels are class
+//! indices of shape *(samples)*, as required by [`nll_loss`](crate::nn::loss::nll_loss).
+//!
+//! The files are read from a local directory, where they must be decompressed. With the
+//! `download` feature enabled, `download_mnist`, `download_fashion_mnist` and
+//! `download_cifar10` fetch and decompress them.
+//!
+//! * [`mnist`] - The [MNIST](http://yann.lecun.com/exdb/mnist/) database of handwritten digits,
+//!   in the IDX format.
+//!
+//! * [`fashion_mnist`] - The [Fashion-MNIST](https://github.com/zalandoresearch/fashion-mnist)
+//!   dataset of Zalando's article images, in the same format as MNIST.
+//!
+//! * [`cifar10`] - The [CIFAR-10](https://www.cs.toronto.edu/~kriz/cifar.html) dataset of tiny
+//!   images, in the binary format.
+//!
+//! * [`ImageFolder`] - Any dataset of images organized in a directory per class.
+//!
+//! ```no_run
+//! use neuronika::data::datasets::{mnist, Split};
+//!
+//! let train = mnist("data/mnist", Split::Train).unwrap();
+//! for (images, labels) in train.batch(64) {
+//!     assert_eq!(images.shape()[1..], [1, 28, 28]);
+//! }
+//! ```
+use super::LabeledDataset;
+use ndarray::{Array, Array1, ArrayD, Axis, Ix1, Ix4, IxDyn};
+use std::{
+    fs::File,
+    io::{self, BufReader, Read},
+    path::Path,
+};
+
+#[cfg(feature = "download")]
+mod download;
+mod image_folder;
+
+#[cfg(feature = "download")]
+pub use download::{download_cifar10, download_fashion_mnist, download_mnist};
+#[cfg(feature = "image")]
+pub use image_folder::read_image;
+pub use image_folder::{read_netpbm, ImageFolder};
+
+/// Portion of a dataset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Split {
+    /// The training set.
+    Train,
+    /// The test set.
+    Test,
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ IDX ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Reads a tensor in the IDX format.
+///
+/// All the element types of the format are supported and converted to `f32`, no scaling is
+/// applied.
+///
+/// # Errors
+///
+/// If the data cannot be read or is not a valid IDX file.
+pub fn read_idx<R: Read>(mut reader: R) -> io::Result<ArrayD<f32>> {
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic)?;
+    if magic[0] != 0 || magic[1] != 0 {
+        return Err(invalid_data(format!(
+            "invalid IDX magic number {:?}",
+            magic
+        )));
+    }
+
+    let mut shape = Vec::with_capacity(magic[3] as usize);
+    for _ in 0..magic[3] {
+        let mut dimension = [0; 4];
+        reader.read_exact(&mut dimension)?;
+        shape.push(u32::from_be_bytes(dimension) as usize);
+    }
+
+    let len = shape.iter().product::<usize>();
+    let element_size = match magic[2] {
+        0x08 | 0x09 => 1,
+        0x0B => 2,
+        0x0C | 0x0D => 4,
+        0x0E => 8,
+        element_type => {
+            return Err(invalid_data(format!(
+                "invalid IDX element type {:#04x}",
+                element_type
+            )))
+        }
+    };
+    let mut bytes = vec![0; len * element_size];
+    reader.read_exact(&mut bytes)?;
+
+    let data: Vec<f32> = match magic[2] {
+        0x08 => bytes.iter().map(|&byte| byte as f32).collect(),
+        0x09 => bytes.iter().map(|&byte| byte as i8 as f32).collect(),
+        0x0B => bytes
+            .chunks_exact(2)
+            .map(|chunk| i16::from_be_bytes([chunk[0], chunk[1]]) as f32)
+            .collect(),
+        0x0C => bytes
+            .chunks_exact(4)
+            .map(|chunk| i32::from_be_bytes(chunk.try_into().unwrap()) as f32)
+            .collect(),
+        0x0D => bytes
+            .chunks_exact(4)
+            .map(|chunk| f32::from_be_bytes(chunk.try_into().unwrap()))
+            .collect(),
+        _ => bytes
+            .chunks_exact(8)
+            .map(|chunk| f64::from_be_bytes(chunk.try_into().unwrap()) as f32)
+            .collect(),
+    };
+
+    Array::from_shape_vec(IxDyn(&shape), data).map_err(|error| invalid_data(error.to_string()))
+}
+
+fn read_idx_file(path: &Path) -> io::Result<ArrayD<f32>> {
+    read_idx(BufReader::new(File::open(path)?))
+}
+
+/// Loads a dataset in the same format as MNIST from `dir`.
+fn load_idx_images(dir: &Path, split: Split) -> io::Result<LabeledDataset<Ix4, Ix1>> {
+    let prefix = match split {
+        Split::Train => "train",
+        Split::Test => "t10k",
+    };
+    let images = read_idx_file(&dir.join(format!("{}-images-idx3-ubyte", prefix)))?;
+    let labels = read_idx_file(&dir.join(format!("{}-labels-idx1-ubyte", prefix)))?;
+
+    if images.ndim() != 3 || labels.ndim() != 1 || images.shape()[0] != labels.len() {
+        return Err(invalid_data(format!(
+            "images of shape {:?} do not match labels of shape {:?}",
+            images.shape(),
+            labels.shape()
+        )));
+    }
+
+    let images = images
+        .insert_axis(Axis(1))
+        .into_dimensionality::<Ix4>()
+        .unwrap()
+        .mapv_into(|pixel| pixel / 255.);
+    let labels = labels.into_dimensionality().unwrap();
+    Ok(LabeledDataset::new(images, labels))
+}
+
+/// Loads the MNIST dataset from `dir`.
+///
+/// The directory must contain the decompressed files `train-images-idx3-ubyte`,
+/// `train-labels-idx1-ubyte`, `t10k-images-idx3-ubyte` and `t10k-labels-idx1-ubyte`. The images
+/// have shape *(samples, 1, 28, 28)*.
+///
+/// # Errors
+///
+/// If the files cannot be read or are not valid.
+pub fn mnist<P: AsRef<Path>>(dir: P, split: Split) -> io::Result<LabeledDataset<Ix4, Ix1>> {
+    load_idx_images(dir.as_ref(), split)
+}
+
+/// Loads the Fashion-MNIST dataset from `dir`.
+///
+/// The files have the same names and format as the ones of [`mnist`].
+///
+/// # Errors
+///
+/// If the files cannot be read or are not valid.
+pub fn fashion_mnist<P: AsRef<Path>>(dir: P, split: Split) -> io::Result<LabeledDataset<Ix4, Ix1>> {
+    load_idx_images(dir.as_ref(), split)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ CIFAR-10 ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Size in bytes of a CIFAR-10 record: a label followed by a 3x32x32 image.
+const CIFAR_RECORD: usize = 1 + 3 * 32 * 32;
+
+/// Loads the CIFAR-10 dataset from `dir`.
+///
+/// The directory must contain the files of the binary version, that is `data_batch_1.bin` to
+/// `data_batch_5.bin` for the training set and `test_batch.bin` for the test set. The images
+/// have shape *(samples, 3, 32, 32)*.
+///
+/// # Errors
+///
+/// If the files cannot be read or are not valid.
+pub fn cifar10<P: AsRef<Path>>(dir: P, split: Split) -> io::Result<LabeledDataset<Ix4, Ix1>> {
+    let files: Vec<String> = match split {
+        Split::Train => (1..=5)
+            .map(|batch| format!("data_batch_{}.bin", batch))
+            .collect(),
+        Split::Test => vec!["test_batch.bin".to_string()],
+    };
+
+    let mut bytes = Vec::new();
+    for file in files {
+        File::open(dir.as_ref().join(file))?.read_to_end(&mut bytes)?;
+    }
+    if bytes.len() % CIFAR_RECORD != 0 {
+        return Err(invalid_data(format!(
+            "the size of the CIFAR-10 files is not a multiple of {} bytes",
+            CIFAR_RECORD
+        )));
+    }
+
+    let samples = bytes.len() / CIFAR_RECORD;
+    let (mut images, mut labels) = (
+        Vec::with_capacity(samples * (CIFAR_RECORD - 1)),
+        Vec::with_capacity(samples),
+    );
+    for record in bytes.chunks_exact(CIFAR_RECORD) {
+        labels.push(record[0] as f32);
+        images.extend(record[1..].iter().map(|&pixel| pixel as f32 / 255.));
+    }
+
+    Ok(LabeledDataset::new(
+        Array::from_shape_vec((samples, 3, 32, 32), images).unwrap(),
+        Array1::from(labels),
+    ))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
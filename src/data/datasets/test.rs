@@ -0,0 +1,90 @@
+use super::{cifar10, fashion_mnist, mnist, read_idx, Split, CIFAR_RECORD};
+use std::{fs, path::PathBuf};
+
+fn dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "neuronika-datasets-{}-{}",
+        name,
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn idx(element_type: u8, shape: &[u32], data: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![0, 0, element_type, shape.len() as u8];
+    for dimension in shape {
+        bytes.extend_from_slice(&dimension.to_be_bytes());
+    }
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+#[test]
+fn idx_types() {
+    let unsigned = read_idx(&idx(0x08, &[2, 2], &[0, 1, 2, 255])[..]).unwrap();
+    assert_eq!(unsigned.shape(), &[2, 2]);
+    assert_eq!(unsigned.as_slice().unwrap(), &[0., 1., 2., 255.]);
+
+    let signed = read_idx(&idx(0x09, &[1], &[255])[..]).unwrap();
+    assert_eq!(signed.as_slice().unwrap(), &[-1.]);
+
+    let short = read_idx(&idx(0x0B, &[1], &[0xFF, 0xFE])[..]).unwrap();
+    assert_eq!(short.as_slice().unwrap(), &[-2.]);
+
+    let float = read_idx(&idx(0x0D, &[1], &1.5f32.to_be_bytes())[..]).unwrap();
+    assert_eq!(float.as_slice().unwrap(), &[1.5]);
+}
+
+#[test]
+fn idx_errors() {
+    assert!(read_idx(&[1, 0, 0x08, 0][..]).is_err());
+    assert!(read_idx(&[0, 0, 0x07, 0][..]).is_err());
+    // Truncated data.
+    assert!(read_idx(&idx(0x08, &[4], &[0, 1])[..]).is_err());
+}
+
+#[test]
+fn mnist_format() {
+    let dir = dir("mnist");
+    let pixels: Vec<u8> = (0..2 * 28 * 28).map(|pixel| (pixel % 256) as u8).collect();
+    fs::write(
+        dir.join("t10k-images-idx3-ubyte"),
+        idx(0x08, &[2, 28, 28], &pixels),
+    )
+    .unwrap();
+    fs::write(dir.join("t10k-labels-idx1-ubyte"), idx(0x08, &[2], &[7, 3])).unwrap();
+
+    let dataset = mnist(&dir, Split::Test).unwrap();
+    assert_eq!(dataset.records().shape(), &[2, 1, 28, 28]);
+    assert_eq!(dataset.records()[[0, 0, 9, 3]], 1.);
+    assert_eq!(dataset.labels().as_slice().unwrap(), &[7., 3.]);
+
+    assert_eq!(fashion_mnist(&dir, Split::Test).unwrap().len(), 2);
+    assert!(mnist(&dir, Split::Train).is_err());
+
+    fs::write(dir.join("t10k-labels-idx1-ubyte"), idx(0x08, &[1], &[7])).unwrap();
+    assert!(mnist(&dir, Split::Test).is_err());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn cifar10_format() {
+    let dir = dir("cifar10");
+    let mut bytes = vec![0; 3 * CIFAR_RECORD];
+    bytes[0] = 9;
+    bytes[1] = 255;
+    bytes[CIFAR_RECORD] = 4;
+    fs::write(dir.join("test_batch.bin"), &bytes).unwrap();
+
+    let dataset = cifar10(&dir, Split::Test).unwrap();
+    assert_eq!(dataset.records().shape(), &[3, 3, 32, 32]);
+    assert_eq!(dataset.records()[[0, 0, 0, 0]], 1.);
+    assert_eq!(dataset.labels().as_slice().unwrap(), &[9., 4., 0.]);
+
+    fs::write(dir.join("test_batch.bin"), &bytes[1..]).unwrap();
+    assert!(cifar10(&dir, Split::Test).is_err());
+
+    fs::remove_dir_all(dir).unwrap();
+}
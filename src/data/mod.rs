@@ -63,6 +63,39 @@
 //!     },
 //! );
 //! ```
+//!
+//! # Benchmark Datasets
+//!
+//! The [`datasets`] module loads some popular benchmark datasets, such as MNIST and CIFAR-10,
+//! from the files in which they are distributed, as well as generic folders of class-labelled
+//! images. The [`transforms`] module provides the image transforms applied while loading them.
+//!
+//! # Sampling
+//!
+//! The [`samplers`] module chooses which records make up each batch, for instance to oversample
+//! the minority classes of imbalanced datasets. Variable-length sequences are stored in the
+//! [`SequenceDataset`](sequences::SequenceDataset) of the [`sequences`] module, which pads them
+//! batch by batch.
+//!
+//! # Streaming
+//!
+//! Data that doesn't fit in memory can be read from disk one batch at a time with the
+//! [`IterableDataset`](streaming::IterableDataset)s of the [`streaming`] module.
+//!
+//! # Text
+//!
+//! The [`text`] module tokenizes texts, maps their tokens to ids with a
+//! [`Vocab`](text::Vocab) and packs batches of them into padded tensors of token ids.
+//!
+//! # Prefetching
+//!
+//! The [`prefetch`] module prepares the next batches on a background thread while the current one
+//! is used by the training step.
+//!
+//! # Replay
+//!
+//! Reinforcement learning agents sample their training batches from the past transitions stored
+//! in a [`ReplayBuffer`], uniformly or according to their priorities.
 
 use csv::{ReaderBuilder, StringRecord};
 use itertools::Itertools;
@@ -73,6 +106,21 @@ use rand::{rngs::StdRng, Rng, SeedableRng};
 use serde::de::DeserializeOwned;
 use std::{fs::File, io::Read};
 
+pub mod datasets;
+pub mod prefetch;
+pub mod samplers;
+pub mod sequences;
+pub mod streaming;
+pub mod text;
+pub mod transforms;
+
+mod replay;
+
+pub use replay::{ReplayBatch, ReplayBuffer};
+
+use samplers::{BatchSampler, SampledBatch, Sampler};
+use streaming::CsvStream;
+
 /// Computes the correct shape for the stacked records of a dataset.
 fn stacked_shape<D: Dimension>(rows: usize, shape: D) -> D::Larger {
     let mut new_shape = D::Larger::zeros(shape.ndim() + 1);
@@ -476,6 +524,36 @@ impl LabeledDataLoader {
         self.from_reader_fn(src, record_shape, label_shape, |r| r)
     }
 
+    /// Streams the records and labels of the specified `.csv` files, in order, applying the
+    /// previously supplied configuration.
+    ///
+    /// Unlike [`.from_csv()`](Self::from_csv), the files are not loaded in memory but read again
+    /// at each pass over the returned [`IterableDataset`](streaming::IterableDataset). All the
+    /// fields must be numeric.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - files from which to stream the data.
+    /// * `record_shape` - shape of a single record.
+    /// * `label_shape` - shape of a single label.
+    ///
+    /// # Panics
+    ///
+    /// If `record_shape` generates an empty record or `label_shape` generates an empty label.
+    pub fn stream_csv<P, S1, S2>(
+        &self,
+        paths: &[P],
+        record_shape: S1,
+        label_shape: S2,
+    ) -> CsvStream<S1::Dim, S2::Dim>
+    where
+        P: Into<std::path::PathBuf> + Clone,
+        S1: IntoDimension,
+        S2: IntoDimension,
+    {
+        CsvStream::new(self, paths, record_shape, label_shape)
+    }
+
     /// Builds a data collection by loading the content of the specified `.csv` file applying the
     /// previously supplied configuration. Applies `fn` to each record and each label.
     ///
@@ -632,6 +710,44 @@ impl<D1: RemoveAxis, D2: RemoveAxis> LabeledDataset<D1, D2> {
         LabeledBatch::new(&self.records, &self.labels, size)
     }
 
+    /// Divides the records drawn by `sampler` into batches of size `size`.
+    ///
+    /// The sampler is queried once per call, so a new iterator must be created at each epoch.
+    ///
+    /// # Arguments
+    ///
+    /// * `sampler` - sampler that draws the records.
+    /// * `size` - size of a single batch.
+    ///
+    /// # Panics
+    ///
+    /// If `size` is zero or if the sampler draws an index out of range.
+    pub fn sampled_batch<S: Sampler>(&self, sampler: &mut S, size: usize) -> SampledBatch<D1, D2> {
+        assert!(size > 0, "error: the batch size must be positive.");
+        let batches = sampler
+            .sample()
+            .chunks(size)
+            .map(<[usize]>::to_vec)
+            .collect();
+
+        SampledBatch::new(self, batches)
+    }
+
+    /// Iterates over the batches drawn by `sampler`.
+    ///
+    /// The sampler is queried once per call, so a new iterator must be created at each epoch.
+    ///
+    /// # Arguments
+    ///
+    /// `sampler` - sampler that draws the batches.
+    ///
+    /// # Panics
+    ///
+    /// If the sampler draws an index out of range.
+    pub fn batch_with<S: BatchSampler>(&self, sampler: &mut S) -> SampledBatch<D1, D2> {
+        SampledBatch::new(self, sampler.sample_batches())
+    }
+
     /// Splits a labeled dataset into non-overlapping new datasets of given lengths.
     ///
     /// # Arguments
@@ -0,0 +1,109 @@
+//! Background prefetching of batches.
+//!
+//! Loading, collating and transforming a batch can take as long as the training step that
+//! consumes it. [`.prefetch()`](Prefetch::prefetch) moves an iterator of batches to a background
+//! thread, which prepares the next batches while the current one is used, so that the
+//! data-loading latency is hidden behind the computation.
+//!
+//! Any work chained onto the iterator before `.prefetch()`, such as collation or augmentation, is
+//! performed on the background thread. The batches are yielded in their original order.
+//!
+//! ```
+//! use ndarray::{s, Array2};
+//! use neuronika::data::prefetch::Prefetch;
+//!
+//! let records = Array2::from_shape_fn((100, 4), |(row, column)| (row * column) as f32);
+//!
+//! let batches = (0..10)
+//!     .map(move |batch| records.slice(s![batch * 10..(batch + 1) * 10, ..]).to_owned())
+//!     .map(|batch| batch.mapv(|el| el / 100.))
+//!     .prefetch(2);
+//!
+//! for batch in batches {
+//!     assert_eq!(batch.shape(), &[10, 4]);
+//! }
+//! ```
+use std::{
+    panic,
+    sync::mpsc::{self, Receiver},
+    thread::{self, JoinHandle},
+};
+
+/// Extension trait that prefetches the items of an iterator on a background thread.
+pub trait Prefetch: Iterator + Sized {
+    /// Consumes `self` on a background thread, which runs ahead of the returned iterator by at
+    /// most `size` items.
+    ///
+    /// If the background thread panics, the panic is propagated to the thread consuming the
+    /// returned iterator once the items produced before the panic have been yielded.
+    ///
+    /// # Panics
+    ///
+    /// If `size` is zero.
+    fn prefetch(self, size: usize) -> Prefetched<Self::Item>
+    where
+        Self: Send + 'static,
+        Self::Item: Send + 'static,
+    {
+        assert!(
+            size > 0,
+            "error: the number of prefetched items must be positive."
+        );
+
+        let (sender, receiver) = mpsc::sync_channel(size);
+        let worker = thread::spawn(move || {
+            for item in self {
+                if sender.send(item).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Prefetched {
+            receiver: Some(receiver),
+            worker: Some(worker),
+        }
+    }
+}
+
+impl<I: Iterator> Prefetch for I {}
+
+/// Iterator over the items prepared by a background thread, see [`Prefetch`].
+///
+/// Dropping it stops the background thread as soon as the item being produced is ready.
+pub struct Prefetched<T> {
+    receiver: Option<Receiver<T>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl<T> Iterator for Prefetched<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.receiver.as_ref()?.recv().ok();
+        if item.is_none() {
+            self.receiver = None;
+            if let Some(Err(payload)) = self.worker.take().map(JoinHandle::join) {
+                panic::resume_unwind(payload);
+            }
+        }
+
+        item
+    }
+}
+
+impl<T> Drop for Prefetched<T> {
+    fn drop(&mut self) {
+        // Disconnects the channel, so that the background thread stops at its next send.
+        self.receiver = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,70 @@
+use super::Prefetch;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+#[test]
+fn order() {
+    let items: Vec<usize> = (0..100).map(|item| item * 2).prefetch(3).collect();
+
+    assert_eq!(items, (0..100).map(|item| item * 2).collect::<Vec<_>>());
+}
+
+#[test]
+fn runs_ahead() {
+    let produced = Arc::new(AtomicUsize::new(0));
+    let counter = produced.clone();
+    let mut items = (0..10)
+        .inspect(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+        .prefetch(2);
+
+    assert_eq!(items.next(), Some(0));
+
+    // The next items are produced without being requested.
+    let start = Instant::now();
+    while produced.load(Ordering::SeqCst) < 3 && start.elapsed() < Duration::from_secs(10) {
+        thread::yield_now();
+    }
+    assert!(produced.load(Ordering::SeqCst) >= 3);
+    assert!(produced.load(Ordering::SeqCst) <= 4);
+
+    assert_eq!(items.collect::<Vec<_>>(), (1..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn early_drop() {
+    let mut items = (0..).prefetch(4);
+
+    assert_eq!(items.next(), Some(0));
+    assert_eq!(items.next(), Some(1));
+    drop(items);
+}
+
+#[test]
+#[should_panic(expected = "error: the number of prefetched items must be positive.")]
+fn zero_size() {
+    let _ = (0..10).prefetch(0);
+}
+
+#[test]
+#[should_panic(expected = "corrupted batch")]
+fn worker_panic() {
+    let mut items = (0..10)
+        .inspect(|&item| {
+            if item == 2 {
+                panic!("corrupted batch");
+            }
+        })
+        .prefetch(1);
+
+    assert_eq!(items.next(), Some(0));
+    assert_eq!(items.next(), Some(1));
+    items.next();
+}
@@ -0,0 +1,400 @@
+use super::stacked_shape;
+use ndarray::{Array, Array1, ArrayBase, Axis, Data, Dimension, IntoDimension};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Added to the absolute errors so that every transition keeps a chance of being replayed.
+const EPSILON: f32 = 1e-6;
+
+/// A batch of transitions drawn from a [`ReplayBuffer`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplayBatch<D: Dimension, E: Dimension> {
+    /// States, stacked along the first axis.
+    pub states: Array<f32, D::Larger>,
+    /// Actions taken in the states, stacked along the first axis.
+    pub actions: Array<f32, E::Larger>,
+    /// Rewards received after the actions.
+    pub rewards: Array1<f32>,
+    /// States reached after the actions, stacked along the first axis.
+    pub next_states: Array<f32, D::Larger>,
+    /// One for the transitions that end an episode and zero for the others.
+    pub dones: Array1<f32>,
+    /// Positions of the transitions in the buffer, to be given back to
+    /// [`.update_priorities()`](ReplayBuffer::update_priorities()).
+    pub indices: Vec<usize>,
+    /// Importance sampling weights, by which the loss of each transition should be multiplied
+    /// to correct the bias of prioritized sampling. They are all one for uniform sampling.
+    pub weights: Array1<f32>,
+}
+
+/// A binary tree whose leaves are the priorities of the transitions and whose internal nodes are
+/// the sums of their children, so that transitions can be drawn and updated in logarithmic time.
+struct SumTree {
+    leaves: usize,
+    nodes: Vec<f64>,
+}
+
+impl SumTree {
+    fn new(capacity: usize) -> Self {
+        let leaves = capacity.next_power_of_two();
+        Self {
+            leaves,
+            nodes: vec![0.; 2 * leaves],
+        }
+    }
+
+    fn total(&self) -> f64 {
+        self.nodes[1]
+    }
+
+    fn get(&self, index: usize) -> f64 {
+        self.nodes[index + self.leaves]
+    }
+
+    fn set(&mut self, index: usize, priority: f64) {
+        let mut node = index + self.leaves;
+        self.nodes[node] = priority;
+        while node > 1 {
+            node /= 2;
+            self.nodes[node] = self.nodes[2 * node] + self.nodes[2 * node + 1];
+        }
+    }
+
+    /// Returns the index of the leaf at which the cumulative sum of the priorities exceeds `mass`.
+    fn find(&self, mut mass: f64) -> usize {
+        let mut node = 1;
+        while node < self.leaves {
+            if mass < self.nodes[2 * node] {
+                node *= 2;
+            } else {
+                mass -= self.nodes[2 * node];
+                node = 2 * node + 1;
+            }
+        }
+
+        node - self.leaves
+    }
+}
+
+/// The state of a prioritized replay buffer.
+struct Priorities {
+    alpha: f32,
+    beta: f32,
+    max: f32,
+    tree: SumTree,
+}
+
+/// A fixed-size memory of the transitions collected by a reinforcement learning agent.
+///
+/// Each transition is made of a state, of shape `D`, the action taken in it, of shape `E`, the
+/// reward received, the state reached and whether it ends the episode. Transitions are stored in
+/// preallocated tensors, and once the buffer is full each new transition replaces the oldest one.
+///
+/// Off-policy algorithms, such as DQN, train on random batches of past transitions, which
+/// [`.sample()`](ReplayBuffer::sample()) draws uniformly by default. A buffer created with
+/// [`.with_priorities()`](ReplayBuffer::with_priorities()) implements the prioritized
+/// experience replay described in [Prioritized Experience Replay](https://arxiv.org/abs/1511.05952)
+/// instead: transitions are drawn with probabilities proportional to their last temporal
+/// difference errors, given with [`.update_priorities()`](ReplayBuffer::update_priorities()),
+/// raised to the power *α*, and come with importance sampling weights.
+///
+/// ```
+/// use ndarray::{arr0, Array1};
+/// use neuronika::data::ReplayBuffer;
+///
+/// // States of four features and discrete actions, stored as scalars.
+/// let mut buffer = ReplayBuffer::new(1_000, 4, ())
+///     .with_priorities(0.6, 0.4)
+///     .with_seed(0);
+///
+/// for step in 0..100 {
+///     let state = Array1::from_elem(4, step as f32);
+///     let next_state = Array1::from_elem(4, (step + 1) as f32);
+///     buffer.push(&state, &arr0(1.), 1., &next_state, step == 99);
+/// }
+///
+/// let batch = buffer.sample(32);
+/// assert_eq!(batch.states.shape(), &[32, 4]);
+/// assert_eq!(batch.actions.shape(), &[32]);
+///
+/// // The temporal difference errors of the batch, computed by the agent.
+/// let errors = vec![0.5; 32];
+/// buffer.update_priorities(&batch.indices, &errors);
+/// ```
+pub struct ReplayBuffer<D: Dimension, E: Dimension> {
+    states: Array<f32, D::Larger>,
+    actions: Array<f32, E::Larger>,
+    rewards: Array1<f32>,
+    next_states: Array<f32, D::Larger>,
+    dones: Array1<f32>,
+    len: usize,
+    next: usize,
+    priorities: Option<Priorities>,
+    rng: StdRng,
+}
+
+impl<D: Dimension, E: Dimension> ReplayBuffer<D, E> {
+    /// Creates a new empty replay buffer with uniform sampling.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - maximum number of transitions stored.
+    ///
+    /// * `state_shape` - shape of each state.
+    ///
+    /// * `action_shape` - shape of each action, `()` for scalar actions.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is zero.
+    pub fn new<Sh1, Sh2>(capacity: usize, state_shape: Sh1, action_shape: Sh2) -> Self
+    where
+        Sh1: IntoDimension<Dim = D>,
+        Sh2: IntoDimension<Dim = E>,
+    {
+        assert!(capacity > 0, "error: the capacity must be positive.");
+
+        let state_shape = stacked_shape(capacity, state_shape.into_dimension());
+        let action_shape = stacked_shape(capacity, action_shape.into_dimension());
+        Self {
+            states: Array::zeros(state_shape.clone()),
+            actions: Array::zeros(action_shape),
+            rewards: Array1::zeros(capacity),
+            next_states: Array::zeros(state_shape),
+            dones: Array1::zeros(capacity),
+            len: 0,
+            next: 0,
+            priorities: None,
+            rng: StdRng::from_rng(rand::thread_rng()).unwrap(),
+        }
+    }
+
+    /// Turns on prioritized sampling.
+    ///
+    /// # Arguments
+    ///
+    /// * `alpha` - exponent of the priorities, *0* corresponds to uniform sampling.
+    ///
+    /// * `beta` - exponent of the importance sampling weights, in *[0, 1]*. It is usually
+    /// annealed towards *1* during training with [`.set_beta()`](ReplayBuffer::set_beta()).
+    ///
+    /// # Panics
+    ///
+    /// If `alpha` is negative or if `beta` doesn't lie in *[0, 1]*.
+    pub fn with_priorities(mut self, alpha: f32, beta: f32) -> Self {
+        assert!(
+            alpha >= 0.,
+            "error: {} is not a valid priority exponent.",
+            alpha
+        );
+        check_beta(beta);
+
+        let mut tree = SumTree::new(self.capacity());
+        (0..self.len).for_each(|index| tree.set(index, 1.));
+        self.priorities = Some(Priorities {
+            alpha,
+            beta,
+            max: 1.,
+            tree,
+        });
+        self
+    }
+
+    /// Seeds the sampling for results reproducibility.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Sets the exponent of the importance sampling weights.
+    ///
+    /// # Panics
+    ///
+    /// If the buffer is not prioritized or if `beta` doesn't lie in *[0, 1]*.
+    pub fn set_beta(&mut self, beta: f32) {
+        check_beta(beta);
+        self.prioritized().beta = beta;
+    }
+
+    /// Returns the maximum number of transitions stored.
+    pub fn capacity(&self) -> usize {
+        self.rewards.len()
+    }
+
+    /// Returns the number of transitions stored.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no transition is stored.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Stores a transition, replacing the oldest one if the buffer is full.
+    ///
+    /// In a prioritized buffer the new transition gets the largest priority seen so far, so that
+    /// it is replayed at least once soon.
+    ///
+    /// # Panics
+    ///
+    /// If the shapes of the states or of the action differ from the ones of the buffer.
+    pub fn push<S, T>(
+        &mut self,
+        state: &ArrayBase<S, D>,
+        action: &ArrayBase<T, E>,
+        reward: f32,
+        next_state: &ArrayBase<S, D>,
+        done: bool,
+    ) where
+        S: Data<Elem = f32>,
+        T: Data<Elem = f32>,
+    {
+        for (name, array, stored) in [
+            ("state", state.shape(), &self.states.shape()[1..]),
+            ("state", next_state.shape(), &self.states.shape()[1..]),
+            ("action", action.shape(), &self.actions.shape()[1..]),
+        ] {
+            assert_eq!(
+                array, stored,
+                "error: a {} of shape {:?} cannot be stored in a buffer of shape {:?}.",
+                name, array, stored
+            );
+        }
+
+        let index = self.next;
+        self.states.index_axis_mut(Axis(0), index).assign(state);
+        self.actions.index_axis_mut(Axis(0), index).assign(action);
+        self.rewards[index] = reward;
+        self.next_states
+            .index_axis_mut(Axis(0), index)
+            .assign(next_state);
+        self.dones[index] = if done { 1. } else { 0. };
+        if let Some(priorities) = &mut self.priorities {
+            let priority = priorities.max.powf(priorities.alpha);
+            priorities.tree.set(index, priority as f64);
+        }
+
+        self.next = (self.next + 1) % self.capacity();
+        self.len = (self.len + 1).min(self.capacity());
+    }
+
+    /// Draws a batch of `batch_size` transitions, with replacement.
+    ///
+    /// Prioritized buffers split the total priority in `batch_size` equal segments and draw a
+    /// transition from each of them. The importance sampling weights are normalized by their
+    /// maximum in the batch.
+    ///
+    /// # Panics
+    ///
+    /// If the buffer is empty.
+    pub fn sample(&mut self, batch_size: usize) -> ReplayBatch<D, E> {
+        assert!(
+            !self.is_empty(),
+            "error: cannot sample from an empty replay buffer."
+        );
+
+        let len = self.len;
+        let (indices, weights) = match &self.priorities {
+            None => (
+                (0..batch_size)
+                    .map(|_| self.rng.gen_range(0..len))
+                    .collect(),
+                Array1::ones(batch_size),
+            ),
+            Some(priorities) => {
+                let total = priorities.tree.total();
+                let segment = total / batch_size as f64;
+                let indices: Vec<usize> = (0..batch_size)
+                    .map(|i| {
+                        let mass = segment * (i as f64 + self.rng.gen::<f64>());
+                        priorities.tree.find(mass).min(len - 1)
+                    })
+                    .collect();
+
+                let mut weights: Array1<f32> = indices
+                    .iter()
+                    .map(|&index| {
+                        let probability = priorities.tree.get(index) / total;
+                        (len as f64 * probability).powf(-priorities.beta as f64) as f32
+                    })
+                    .collect();
+                let max = weights.fold(0f32, |max, &weight| max.max(weight));
+                weights.mapv_inplace(|weight| weight / max);
+
+                (indices, weights)
+            }
+        };
+
+        ReplayBatch {
+            states: self.states.select(Axis(0), &indices),
+            actions: self.actions.select(Axis(0), &indices),
+            rewards: self.rewards.select(Axis(0), &indices),
+            next_states: self.next_states.select(Axis(0), &indices),
+            dones: self.dones.select(Axis(0), &indices),
+            indices,
+            weights,
+        }
+    }
+
+    /// Sets the priorities of the transitions at `indices` from their temporal difference
+    /// `errors`, whose absolute values are used.
+    ///
+    /// # Panics
+    ///
+    /// If the buffer is not prioritized, if `indices` and `errors` have different lengths or if
+    /// an index is out of bounds.
+    pub fn update_priorities(&mut self, indices: &[usize], errors: &[f32]) {
+        assert_eq!(
+            indices.len(),
+            errors.len(),
+            "error: {} errors were given for {} transitions.",
+            errors.len(),
+            indices.len()
+        );
+        let len = self.len;
+        let priorities = self.prioritized();
+
+        for (&index, error) in indices.iter().zip(errors) {
+            assert!(
+                index < len,
+                "error: index {} is out of bounds for a replay buffer of {} transitions.",
+                index,
+                len
+            );
+
+            let priority = error.abs() + EPSILON;
+            priorities.max = priorities.max.max(priority);
+            priorities
+                .tree
+                .set(index, priority.powf(priorities.alpha) as f64);
+        }
+    }
+
+    /// Returns the state of the prioritized sampling.
+    ///
+    /// # Panics
+    ///
+    /// If the buffer is not prioritized.
+    fn prioritized(&mut self) -> &mut Priorities {
+        self.priorities
+            .as_mut()
+            .expect("error: the replay buffer is not prioritized.")
+    }
+}
+
+/// # Panics
+///
+/// If `beta` doesn't lie in *[0, 1]*.
+fn check_beta(beta: f32) {
+    assert!(
+        (0. ..=1.).contains(&beta),
+        "error: {} is not a valid importance sampling exponent.",
+        beta
+    );
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
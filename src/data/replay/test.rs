@@ -0,0 +1,147 @@
+use super::{ReplayBuffer, SumTree};
+use ndarray::{arr0, array, Array1, Ix0, Ix1};
+
+fn filled(capacity: usize, transitions: usize) -> ReplayBuffer<Ix1, Ix0> {
+    let mut buffer = ReplayBuffer::new(capacity, 2, ()).with_seed(0);
+    for step in 0..transitions {
+        let step = step as f32;
+        buffer.push(
+            &array![step, -step],
+            &arr0(step),
+            step * 10.,
+            &array![step + 1., -step - 1.],
+            false,
+        );
+    }
+
+    buffer
+}
+
+#[test]
+fn sum_tree() {
+    let mut tree = SumTree::new(5);
+    for (index, priority) in [1., 2., 3., 4., 0.].iter().enumerate() {
+        tree.set(index, *priority);
+    }
+
+    assert_eq!(tree.total(), 10.);
+    assert_eq!(tree.get(2), 3.);
+    assert_eq!(tree.find(0.5), 0);
+    assert_eq!(tree.find(1.), 1);
+    assert_eq!(tree.find(5.9), 2);
+    assert_eq!(tree.find(9.99), 3);
+
+    tree.set(3, 0.);
+    assert_eq!(tree.total(), 6.);
+}
+
+#[test]
+fn push() {
+    let mut buffer = filled(3, 2);
+    assert_eq!(buffer.len(), 2);
+    assert_eq!(buffer.capacity(), 3);
+
+    // The batch holds consistent transitions.
+    let batch = buffer.sample(8);
+    assert_eq!(batch.states.shape(), &[8, 2]);
+    assert_eq!(batch.actions.shape(), &[8]);
+    assert_eq!(batch.weights, Array1::ones(8));
+    for (i, &index) in batch.indices.iter().enumerate() {
+        assert!(index < 2);
+        let step = batch.actions[i];
+        assert_eq!(batch.states.row(i), array![step, -step]);
+        assert_eq!(batch.next_states.row(i), array![step + 1., -step - 1.]);
+        assert_eq!(batch.rewards[i], step * 10.);
+        assert_eq!(batch.dones[i], 0.);
+    }
+}
+
+#[test]
+fn push_full() {
+    let mut buffer = filled(3, 5);
+    assert_eq!(buffer.len(), 3);
+
+    // The two oldest transitions have been replaced.
+    let mut actions = buffer.sample(64).actions.to_vec();
+    actions.sort_by(|lhs, rhs| lhs.partial_cmp(rhs).unwrap());
+    actions.dedup();
+    assert_eq!(actions, vec![2., 3., 4.]);
+}
+
+#[test]
+fn prioritized() {
+    let mut buffer = filled(4, 4).with_priorities(1., 1.);
+
+    // The transitions start with the same priority.
+    let batch = buffer.sample(4);
+    assert_eq!(batch.weights, Array1::ones(4));
+
+    buffer.update_priorities(&[0, 1, 2, 3], &[3., 0., -1., 0.]);
+    let batch = buffer.sample(1_000);
+    let count = |step: usize| batch.indices.iter().filter(|&&index| index == step).count();
+    assert!(count(1) == 0 && count(3) == 0);
+    assert!(count(0) > 2 * count(2));
+
+    // The weights compensate the probabilities.
+    for (&index, weight) in batch.indices.iter().zip(&batch.weights) {
+        let expected = if index == 0 { 1. / 3. } else { 1. };
+        assert!((weight - expected).abs() < 1e-4);
+    }
+
+    // New transitions get the largest priority.
+    buffer.push(&array![9., 9.], &arr0(9.), 0., &array![9., 9.], true);
+    let batch = buffer.sample(1_000);
+    assert!(batch.indices.iter().filter(|&&index| index == 0).count() > 300);
+    assert!(batch.dones.iter().any(|&done| done == 1.));
+}
+
+#[test]
+fn seed() {
+    let indices = |seed| filled(10, 10).with_seed(seed).sample(5).indices;
+
+    assert_eq!(indices(3), indices(3));
+}
+
+#[test]
+#[should_panic(expected = "error: the capacity must be positive.")]
+fn zero_capacity() {
+    ReplayBuffer::new(0, 2, ());
+}
+
+#[test]
+#[should_panic(expected = "error: cannot sample from an empty replay buffer.")]
+fn sample_empty() {
+    filled(2, 0).sample(1);
+}
+
+#[test]
+#[should_panic(expected = "error: a state of shape [3] cannot be stored in a buffer of shape [2].")]
+fn push_wrong_shape() {
+    filled(2, 0).push(
+        &array![0., 0., 0.],
+        &arr0(0.),
+        0.,
+        &array![0., 0., 0.],
+        false,
+    );
+}
+
+#[test]
+#[should_panic(expected = "error: the replay buffer is not prioritized.")]
+fn update_priorities_uniform() {
+    filled(2, 2).update_priorities(&[0], &[1.]);
+}
+
+#[test]
+#[should_panic(expected = "error: index 2 is out of bounds for a replay buffer of 2 transitions.")]
+fn update_priorities_out_of_bounds() {
+    filled(4, 2)
+        .with_priorities(0.6, 0.4)
+        .update_priorities(&[2], &[1.]);
+}
+
+#[test]
+#[should_panic(expected = "error: 1.5 is not a valid importance sampling exponent.")]
+fn invalid_beta() {
+    filled(4, 2).with_priorities(0.6, 0.4).set_beta(1.5);
+}
@@ -0,0 +1,443 @@
+//! Samplers that decide which records make up each batch.
+//!
+//! By default, [`.batch()`](super::LabeledDataset::batch) visits the records of a dataset in
+//! their stored order. Samplers instead produce, at each epoch, the indices of the records to
+//! visit, which is useful to rebalance datasets in which some classes are much rarer than others.
+//!
+//! * [`WeightedRandomSampler`] - draws records with probabilities proportional to given weights.
+//!   Use [`WeightedRandomSampler::balanced()`] to oversample the minority classes so that each
+//!   class is drawn equally often.
+//!
+//! * [`StratifiedBatchSampler`] - shuffles the records while preserving, within every batch, the
+//!   proportions of the classes in the whole dataset.
+//!
+//! * [`BucketBatchSampler`] - groups records of similar lengths, to reduce the padding of the
+//!   variable-length sequences of a [`SequenceDataset`](super::sequences::SequenceDataset).
+//!
+//! Samplers implementing [`Sampler`] are consumed by
+//! [`.sampled_batch()`](super::LabeledDataset::sampled_batch), while the ones implementing
+//! [`BatchSampler`] are consumed by [`.batch_with()`](super::LabeledDataset::batch_with) and by
+//! [`SequenceDataset::batch_with()`](super::sequences::SequenceDataset::batch_with).
+//!
+//! ```
+//! use ndarray::array;
+//! use neuronika::data::{samplers::WeightedRandomSampler, DataLoader};
+//!
+//! let csv = "0.1,0\n0.2,0\n0.3,0\n0.4,1";
+//! let dataset = DataLoader::default()
+//!     .with_labels(&[1])
+//!     .without_headers()
+//!     .from_reader(csv.as_bytes(), 1, 1);
+//!
+//! let mut sampler = WeightedRandomSampler::balanced(dataset.labels());
+//! for (records, labels) in dataset.sampled_batch(&mut sampler, 2) {
+//!     assert_eq!(records.shape(), &[2, 1]);
+//! }
+//! ```
+use super::LabeledDataset;
+use ndarray::{Array, ArrayBase, Axis, Data, Dimension, RemoveAxis};
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    rngs::StdRng,
+    seq::SliceRandom,
+    Rng, SeedableRng,
+};
+use std::collections::BTreeMap;
+
+/// Produces the indices of the records to visit during an epoch.
+pub trait Sampler {
+    /// Returns the indices of the records to visit, in order.
+    fn sample(&mut self) -> Vec<usize>;
+}
+
+/// Produces the batches of records to visit during an epoch.
+pub trait BatchSampler {
+    /// Returns the indices of the records of each batch, in order.
+    fn sample_batches(&mut self) -> Vec<Vec<usize>>;
+}
+
+/// Returns the class of each record, given labels containing a class index per record.
+///
+/// # Panics
+///
+/// If the labels do not contain exactly one value per record.
+fn classes<S, D>(labels: &ArrayBase<S, D>) -> Vec<usize>
+where
+    S: Data<Elem = f32>,
+    D: Dimension,
+{
+    let records = labels.shape().first().copied().unwrap_or(0);
+    assert_eq!(
+        labels.len(),
+        records,
+        "error: expected a single class index per record, found labels of shape {:?}.",
+        labels.shape()
+    );
+
+    labels.iter().map(|&label| label as usize).collect()
+}
+
+/// Groups the indices of the records by class, in ascending class order.
+fn group_by_class(classes: &[usize]) -> Vec<Vec<usize>> {
+    let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (index, &class) in classes.iter().enumerate() {
+        groups.entry(class).or_default().push(index);
+    }
+
+    groups.into_values().collect()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ WeightedRandomSampler ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Draws records at random with probabilities proportional to their weights.
+///
+/// By default records are drawn with replacement, so that rare records can be visited several
+/// times per epoch.
+pub struct WeightedRandomSampler {
+    weights: Vec<f32>,
+    samples: usize,
+    replacement: bool,
+    rng: StdRng,
+}
+
+impl WeightedRandomSampler {
+    /// Creates a new weighted random sampler that draws `samples` records per epoch.
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - weight of each record, they don't need to sum to one.
+    /// * `samples` - number of records to draw at each epoch.
+    ///
+    /// # Panics
+    ///
+    /// If any weight is negative or not finite, or if all the weights are zero.
+    pub fn new(weights: &[f32], samples: usize) -> Self {
+        assert!(
+            weights
+                .iter()
+                .all(|weight| weight.is_finite() && *weight >= 0.),
+            "error: the weights must be finite and non-negative."
+        );
+        assert!(
+            weights.iter().any(|&weight| weight > 0.),
+            "error: at least one weight must be positive."
+        );
+
+        Self {
+            weights: weights.to_vec(),
+            samples,
+            replacement: true,
+            rng: StdRng::from_rng(rand::thread_rng()).unwrap(),
+        }
+    }
+
+    /// Creates a new weighted random sampler that draws each class equally often.
+    ///
+    /// Each record is weighted by the inverse of the frequency of its class and as many records
+    /// as the dataset holds are drawn at each epoch.
+    ///
+    /// # Arguments
+    ///
+    /// `labels` - class index of each record, of shape *(records)* or *(records, 1)*.
+    ///
+    /// # Panics
+    ///
+    /// If the labels do not contain exactly one value per record or are empty.
+    pub fn balanced<S, D>(labels: &ArrayBase<S, D>) -> Self
+    where
+        S: Data<Elem = f32>,
+        D: Dimension,
+    {
+        let classes = classes(labels);
+        let mut counts: BTreeMap<usize, usize> = BTreeMap::new();
+        for &class in &classes {
+            *counts.entry(class).or_default() += 1;
+        }
+
+        let weights: Vec<f32> = classes
+            .iter()
+            .map(|class| 1. / counts[class] as f32)
+            .collect();
+        Self::new(&weights, classes.len())
+    }
+
+    /// Draws the records without replacement, so that each record is visited at most once per
+    /// epoch.
+    ///
+    /// # Panics
+    ///
+    /// If fewer records than the number of samples have a positive weight.
+    pub fn without_replacement(mut self) -> Self {
+        let candidates = self.weights.iter().filter(|&&weight| weight > 0.).count();
+        assert!(
+            self.samples <= candidates,
+            "error: cannot draw {} records without replacement from {} candidates.",
+            self.samples,
+            candidates
+        );
+        self.replacement = false;
+        self
+    }
+
+    /// Seeds the sampler for results reproducibility.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Returns the weights of the records.
+    pub fn weights(&self) -> &[f32] {
+        &self.weights
+    }
+}
+
+impl Sampler for WeightedRandomSampler {
+    fn sample(&mut self) -> Vec<usize> {
+        if self.replacement {
+            let distribution = WeightedIndex::new(&self.weights).unwrap();
+            return (0..self.samples)
+                .map(|_| distribution.sample(&mut self.rng))
+                .collect();
+        }
+
+        // Weighted sampling without replacement by Efraimidis and Spirakis: each record gets the
+        // key u^(1 / weight) and the ones with the largest keys are drawn.
+        let mut keys: Vec<(f32, usize)> = self
+            .weights
+            .iter()
+            .enumerate()
+            .filter(|(_, &weight)| weight > 0.)
+            .map(|(index, &weight)| (self.rng.gen::<f32>().powf(1. / weight), index))
+            .collect();
+        keys.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        keys.into_iter()
+            .take(self.samples)
+            .map(|(_, index)| index)
+            .collect()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ StratifiedBatchSampler ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Shuffles the records into batches that preserve the class proportions of the whole dataset.
+///
+/// Every record is visited exactly once per epoch. The records of each class are shuffled and
+/// spread evenly across the epoch, so that each batch contains, up to rounding, the same share
+/// of every class as the dataset.
+pub struct StratifiedBatchSampler {
+    groups: Vec<Vec<usize>>,
+    batch_size: usize,
+    drop_last: bool,
+    rng: StdRng,
+}
+
+impl StratifiedBatchSampler {
+    /// Creates a new stratified batch sampler.
+    ///
+    /// # Arguments
+    ///
+    /// * `labels` - class index of each record, of shape *(records)* or *(records, 1)*.
+    /// * `batch_size` - size of a single batch.
+    ///
+    /// # Panics
+    ///
+    /// If the labels do not contain exactly one value per record or if `batch_size` is zero.
+    pub fn new<S, D>(labels: &ArrayBase<S, D>, batch_size: usize) -> Self
+    where
+        S: Data<Elem = f32>,
+        D: Dimension,
+    {
+        assert!(batch_size > 0, "error: the batch size must be positive.");
+
+        Self {
+            groups: group_by_class(&classes(labels)),
+            batch_size,
+            drop_last: false,
+            rng: StdRng::from_rng(rand::thread_rng()).unwrap(),
+        }
+    }
+
+    /// Drops the last incomplete batch, if the dataset size is not divisible by the batch size.
+    pub fn drop_last(mut self) -> Self {
+        self.drop_last = true;
+        self
+    }
+
+    /// Seeds the sampler for results reproducibility.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+}
+
+impl BatchSampler for StratifiedBatchSampler {
+    fn sample_batches(&mut self) -> Vec<Vec<usize>> {
+        // The i-th of the n shuffled records of a class is placed at the position (i + u) / n of
+        // the epoch, with u uniform in [0, 1), which interleaves the classes evenly.
+        let mut positions = Vec::new();
+        for group in &self.groups {
+            let mut group = group.clone();
+            group.shuffle(&mut self.rng);
+            let len = group.len() as f64;
+            for (rank, index) in group.into_iter().enumerate() {
+                positions.push(((rank as f64 + self.rng.gen::<f64>()) / len, index));
+            }
+        }
+        positions.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let order: Vec<usize> = positions.into_iter().map(|(_, index)| index).collect();
+        order
+            .chunks(self.batch_size)
+            .filter(|batch| !self.drop_last || batch.len() == self.batch_size)
+            .map(<[usize]>::to_vec)
+            .collect()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ BucketBatchSampler ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Groups records of similar lengths into the same batches, to reduce the padding of
+/// variable-length sequences.
+///
+/// Every record is visited exactly once per epoch. The records are shuffled and split into pools
+/// of several batches, each pool is sorted by length and divided into batches, and finally the
+/// order of the batches is shuffled. Larger pools yield less padding but less randomness.
+pub struct BucketBatchSampler {
+    lengths: Vec<usize>,
+    batch_size: usize,
+    pool: usize,
+    drop_last: bool,
+    rng: StdRng,
+}
+
+impl BucketBatchSampler {
+    /// Creates a new bucket batch sampler whose pools span the whole dataset.
+    ///
+    /// # Arguments
+    ///
+    /// * `lengths` - length of each record.
+    /// * `batch_size` - size of a single batch.
+    ///
+    /// # Panics
+    ///
+    /// If `batch_size` is zero.
+    pub fn new(lengths: &[usize], batch_size: usize) -> Self {
+        assert!(batch_size > 0, "error: the batch size must be positive.");
+
+        Self {
+            lengths: lengths.to_vec(),
+            batch_size,
+            pool: usize::MAX,
+            drop_last: false,
+            rng: StdRng::from_rng(rand::thread_rng()).unwrap(),
+        }
+    }
+
+    /// Sorts the records by length within pools of `batches` batches.
+    ///
+    /// # Panics
+    ///
+    /// If `batches` is zero.
+    pub fn with_pool(mut self, batches: usize) -> Self {
+        assert!(
+            batches > 0,
+            "error: the pool must contain at least one batch."
+        );
+        self.pool = batches;
+        self
+    }
+
+    /// Drops the incomplete batches, if the pool sizes are not divisible by the batch size.
+    pub fn drop_last(mut self) -> Self {
+        self.drop_last = true;
+        self
+    }
+
+    /// Seeds the sampler for results reproducibility.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+}
+
+impl BatchSampler for BucketBatchSampler {
+    fn sample_batches(&mut self) -> Vec<Vec<usize>> {
+        let mut order: Vec<usize> = (0..self.lengths.len()).collect();
+        order.shuffle(&mut self.rng);
+
+        let mut batches = Vec::new();
+        for pool in order.chunks_mut(self.pool.saturating_mul(self.batch_size)) {
+            // The sort is stable, so records of equal length stay shuffled.
+            pool.sort_by_key(|&index| self.lengths[index]);
+            batches.extend(
+                pool.chunks(self.batch_size)
+                    .filter(|batch| !self.drop_last || batch.len() == self.batch_size)
+                    .map(<[usize]>::to_vec),
+            );
+        }
+        batches.shuffle(&mut self.rng);
+
+        batches
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ SampledBatch ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Iterator over batches of labeled data chosen by a sampler.
+///
+/// Since the records of a batch are in general not contiguous, they are copied.
+pub struct SampledBatch<'a, D1, D2> {
+    dataset: &'a LabeledDataset<D1, D2>,
+    batches: std::vec::IntoIter<Vec<usize>>,
+}
+
+impl<'a, D1: RemoveAxis, D2: RemoveAxis> SampledBatch<'a, D1, D2> {
+    pub(super) fn new(dataset: &'a LabeledDataset<D1, D2>, batches: Vec<Vec<usize>>) -> Self {
+        if let Some(index) = batches
+            .iter()
+            .flatten()
+            .find(|&&index| index >= dataset.len())
+        {
+            panic!(
+                "error: sampled index {} is out of range for a dataset of {} records.",
+                index,
+                dataset.len()
+            );
+        }
+
+        Self {
+            dataset,
+            batches: batches.into_iter(),
+        }
+    }
+}
+
+impl<'a, D1: RemoveAxis, D2: RemoveAxis> Iterator for SampledBatch<'a, D1, D2> {
+    type Item = (Array<f32, D1>, Array<f32, D2>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.batches.next().map(|indices| {
+            (
+                self.dataset.records.select(Axis(0), &indices),
+                self.dataset.labels.select(Axis(0), &indices),
+            )
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.batches.size_hint()
+    }
+}
+
+impl<'a, D1: RemoveAxis, D2: RemoveAxis> ExactSizeIterator for SampledBatch<'a, D1, D2> {}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,109 @@
+use super::{BatchSampler, Sampler, StratifiedBatchSampler, WeightedRandomSampler};
+use crate::data::LabeledDataset;
+use ndarray::{array, Array1, Array2};
+
+fn imbalanced() -> LabeledDataset<ndarray::Ix2, ndarray::Ix1> {
+    // Nine records of class 0 and three of class 1.
+    let labels = Array1::from_shape_fn(12, |index| if index % 4 == 3 { 1. } else { 0. });
+    let records = Array2::from_shape_fn((12, 2), |(index, _)| index as f32);
+    LabeledDataset::new(records, labels)
+}
+
+#[test]
+fn weighted_with_replacement() {
+    let mut sampler = WeightedRandomSampler::new(&[0., 1., 3.], 4000).with_seed(0);
+    let indices = sampler.sample();
+
+    assert_eq!(indices.len(), 4000);
+    assert!(!indices.contains(&0));
+    let twos = indices.iter().filter(|&&index| index == 2).count();
+    assert!((twos as f32 / 4000. - 0.75).abs() < 0.05);
+}
+
+#[test]
+fn weighted_without_replacement() {
+    let mut sampler = WeightedRandomSampler::new(&[1., 0., 5., 2.], 3)
+        .without_replacement()
+        .with_seed(1);
+    let mut indices = sampler.sample();
+    indices.sort_unstable();
+
+    assert_eq!(indices, vec![0, 2, 3]);
+}
+
+#[test]
+#[should_panic(expected = "error: cannot draw 3 records without replacement from 2 candidates.")]
+fn weighted_too_many_samples() {
+    WeightedRandomSampler::new(&[1., 0., 5.], 3).without_replacement();
+}
+
+#[test]
+#[should_panic(expected = "error: at least one weight must be positive.")]
+fn weighted_all_zero() {
+    WeightedRandomSampler::new(&[0., 0.], 1);
+}
+
+#[test]
+fn balanced() {
+    let dataset = imbalanced();
+    let mut sampler = WeightedRandomSampler::balanced(dataset.labels()).with_seed(2);
+    assert!((sampler.weights()[0] - 1. / 9.).abs() < f32::EPSILON);
+    assert!((sampler.weights()[3] - 1. / 3.).abs() < f32::EPSILON);
+
+    let mut minority = 0;
+    for _ in 0..100 {
+        for (records, labels) in dataset.sampled_batch(&mut sampler, 5) {
+            assert_eq!(records.nrows(), labels.len());
+            minority += labels.iter().filter(|&&label| label == 1.).count();
+        }
+    }
+    assert!((minority as f32 / 1200. - 0.5).abs() < 0.05);
+}
+
+#[test]
+fn sampled_batch() {
+    let dataset = imbalanced();
+    let mut sampler =
+        WeightedRandomSampler::new(&[0., 0., 1., 0., 0., 0., 0., 0., 0., 0., 0., 0.], 5);
+    let batches: Vec<_> = dataset.sampled_batch(&mut sampler, 2).collect();
+
+    assert_eq!(batches.len(), 3);
+    assert_eq!(batches[2].0, array![[2., 2.]]);
+    assert_eq!(batches[0].1, array![0., 0.]);
+}
+
+#[test]
+fn stratified() {
+    let dataset = imbalanced();
+    let mut sampler = StratifiedBatchSampler::new(dataset.labels(), 4).with_seed(3);
+
+    for _ in 0..10 {
+        let batches = sampler.sample_batches();
+        assert_eq!(batches.len(), 3);
+
+        let mut visited: Vec<usize> = batches.iter().flatten().copied().collect();
+        visited.sort_unstable();
+        assert_eq!(visited, (0..12).collect::<Vec<_>>());
+
+        for (_, labels) in dataset.batch_with(&mut StratifiedBatchSampler::new(dataset.labels(), 4))
+        {
+            assert_eq!(labels.iter().filter(|&&label| label == 1.).count(), 1);
+        }
+    }
+}
+
+#[test]
+fn stratified_drop_last() {
+    let labels = array![[0.], [1.], [0.], [1.], [0.]];
+    let mut sampler = StratifiedBatchSampler::new(&labels, 2).drop_last();
+
+    assert_eq!(sampler.sample_batches().len(), 2);
+}
+
+#[test]
+#[should_panic(
+    expected = "error: expected a single class index per record, found labels of shape [2, 2]."
+)]
+fn one_hot_labels() {
+    StratifiedBatchSampler::new(&array![[1., 0.], [0., 1.]], 1);
+}
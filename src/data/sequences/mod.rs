@@ -0,0 +1,232 @@
+//! Variable-length sequences.
+//!
+//! Sequences of different lengths, such as sentences or time series, cannot be stored in a
+//! single tensor. A [`SequenceDataset`] keeps each of them in its own tensor of shape
+//! *(length, features)* and pads them only when they are batched, with [`pad_sequences`], up to
+//! the length of the longest sequence of the batch. The lengths and a mask of the valid steps are
+//! returned together with the padded batch, so that the padding can be ignored downstream.
+//!
+//! To minimize the padding, batches can be drawn with a
+//! [`BucketBatchSampler`](super::samplers::BucketBatchSampler), which groups sequences of
+//! similar lengths.
+//!
+//! ```
+//! use ndarray::{array, Array2};
+//! use neuronika::data::{samplers::BucketBatchSampler, sequences::SequenceDataset};
+//!
+//! let sequences: Vec<Array2<f32>> = (1..=6).map(|len| Array2::ones((len, 2))).collect();
+//! let dataset = SequenceDataset::new(sequences, array![0., 1., 0., 1., 0., 1.]);
+//!
+//! let mut sampler = BucketBatchSampler::new(&dataset.lengths(), 2);
+//! for (batch, labels) in dataset.batch_with(&mut sampler) {
+//!     // Sequences of lengths 1 and 2, 3 and 4 or 5 and 6 are batched together.
+//!     assert_eq!(batch.lengths[0].abs_diff(batch.lengths[1]), 1);
+//!     assert_eq!(batch.mask.sum(), batch.lengths.iter().sum::<usize>() as f32);
+//! }
+//! ```
+use super::samplers::BatchSampler;
+use ndarray::{s, Array, Array2, Array3, ArrayBase, Axis, Data, Ix2, RemoveAxis};
+
+/// A batch of sequences padded to the same length.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaddedBatch {
+    /// Padded sequences, of shape *(batch, steps, features)*.
+    pub data: Array3<f32>,
+    /// Length of each sequence before padding.
+    pub lengths: Vec<usize>,
+    /// Mask of shape *(batch, steps)*, equal to one at the valid steps and zero at the padded ones.
+    pub mask: Array2<f32>,
+}
+
+/// Pads `sequences`, each of shape *(length, features)*, to the length of the longest one.
+///
+/// # Arguments
+///
+/// * `sequences` - sequences to pad.
+/// * `padding` - value of the padded steps.
+///
+/// # Panics
+///
+/// If the sequences have different numbers of features.
+pub fn pad_sequences<'a, I, S>(sequences: I, padding: f32) -> PaddedBatch
+where
+    I: IntoIterator<Item = &'a ArrayBase<S, Ix2>>,
+    S: Data<Elem = f32> + 'a,
+{
+    let sequences: Vec<_> = sequences.into_iter().collect();
+    let features = sequences.first().map_or(0, |sequence| sequence.ncols());
+    assert!(
+        sequences
+            .iter()
+            .all(|sequence| sequence.ncols() == features),
+        "error: cannot pad sequences with different numbers of features."
+    );
+
+    let lengths: Vec<usize> = sequences.iter().map(|sequence| sequence.nrows()).collect();
+    let steps = lengths.iter().copied().max().unwrap_or(0);
+    let mut data = Array3::from_elem((sequences.len(), steps, features), padding);
+    let mut mask = Array2::zeros((sequences.len(), steps));
+    for (index, sequence) in sequences.iter().enumerate() {
+        let length = sequence.nrows();
+        data.slice_mut(s![index, ..length, ..]).assign(sequence);
+        mask.slice_mut(s![index, ..length]).fill(1.);
+    }
+
+    PaddedBatch {
+        data,
+        lengths,
+        mask,
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ SequenceDataset ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// A collection of labeled sequences of possibly different lengths.
+///
+/// `SequenceDataset` is generic on the dimensionality of the labels, specified by `D`.
+pub struct SequenceDataset<D> {
+    sequences: Vec<Array2<f32>>,
+    labels: Array<f32, D>,
+    padding: f32,
+}
+
+impl<D: RemoveAxis> SequenceDataset<D> {
+    /// Creates a new `SequenceDataset`.
+    ///
+    /// # Arguments
+    ///
+    /// * `sequences` - sequences to be stored, each of shape *(length, features)*.
+    /// * `labels` - labels to be stored, one per sequence along the outermost axis.
+    ///
+    /// # Panics
+    ///
+    /// If the number of labels differs from the number of sequences or if the sequences have
+    /// different numbers of features.
+    pub fn new(sequences: Vec<Array2<f32>>, labels: Array<f32, D>) -> Self {
+        assert_eq!(
+            sequences.len(),
+            labels.len_of(Axis(0)),
+            "error: the number of sequences and labels differ."
+        );
+        let features = sequences.first().map_or(0, |sequence| sequence.ncols());
+        assert!(
+            sequences
+                .iter()
+                .all(|sequence| sequence.ncols() == features),
+            "error: the sequences have different numbers of features."
+        );
+
+        Self {
+            sequences,
+            labels,
+            padding: 0.,
+        }
+    }
+
+    /// Sets the value of the padded steps, which defaults to zero.
+    pub fn with_padding(mut self, padding: f32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Returns a reference to the sequences.
+    pub fn sequences(&self) -> &[Array2<f32>] {
+        &self.sequences
+    }
+
+    /// Returns a reference to the labels.
+    pub fn labels(&self) -> &Array<f32, D> {
+        &self.labels
+    }
+
+    /// Returns the length of each sequence.
+    pub fn lengths(&self) -> Vec<usize> {
+        self.sequences.iter().map(Array2::nrows).collect()
+    }
+
+    /// Returns the number of sequences stored in the dataset.
+    pub fn len(&self) -> usize {
+        self.sequences.len()
+    }
+
+    /// Check whether the dataset is empty or not.
+    pub fn is_empty(&self) -> bool {
+        self.sequences.is_empty()
+    }
+
+    /// Divides the dataset into padded batches of size `size`, in the stored order.
+    ///
+    /// # Panics
+    ///
+    /// If `size` is zero.
+    pub fn batch(&self, size: usize) -> PaddedBatches<D> {
+        assert!(size > 0, "error: the batch size must be positive.");
+        let indices: Vec<usize> = (0..self.len()).collect();
+        PaddedBatches::new(self, indices.chunks(size).map(<[usize]>::to_vec).collect())
+    }
+
+    /// Iterates over the padded batches drawn by `sampler`.
+    ///
+    /// # Panics
+    ///
+    /// If the sampler draws an index out of range.
+    pub fn batch_with<S: BatchSampler>(&self, sampler: &mut S) -> PaddedBatches<D> {
+        PaddedBatches::new(self, sampler.sample_batches())
+    }
+}
+
+/// Iterator over padded batches of sequences and their labels.
+pub struct PaddedBatches<'a, D> {
+    dataset: &'a SequenceDataset<D>,
+    batches: std::vec::IntoIter<Vec<usize>>,
+}
+
+impl<'a, D: RemoveAxis> PaddedBatches<'a, D> {
+    fn new(dataset: &'a SequenceDataset<D>, batches: Vec<Vec<usize>>) -> Self {
+        if let Some(index) = batches
+            .iter()
+            .flatten()
+            .find(|&&index| index >= dataset.len())
+        {
+            panic!(
+                "error: sampled index {} is out of range for a dataset of {} records.",
+                index,
+                dataset.len()
+            );
+        }
+
+        Self {
+            dataset,
+            batches: batches.into_iter(),
+        }
+    }
+}
+
+impl<'a, D: RemoveAxis> Iterator for PaddedBatches<'a, D> {
+    type Item = (PaddedBatch, Array<f32, D>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.batches.next().map(|indices| {
+            (
+                pad_sequences(
+                    indices.iter().map(|&index| &self.dataset.sequences[index]),
+                    self.dataset.padding,
+                ),
+                self.dataset.labels.select(Axis(0), &indices),
+            )
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.batches.size_hint()
+    }
+}
+
+impl<'a, D: RemoveAxis> ExactSizeIterator for PaddedBatches<'a, D> {}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,80 @@
+use super::{pad_sequences, SequenceDataset};
+use crate::data::samplers::{BatchSampler, BucketBatchSampler};
+use ndarray::{array, Array2};
+
+fn dataset() -> SequenceDataset<ndarray::Ix1> {
+    let sequences = [3, 1, 4, 1, 5, 9, 2, 6]
+        .iter()
+        .map(|&len| Array2::from_elem((len, 2), len as f32))
+        .collect();
+    SequenceDataset::new(sequences, array![0., 1., 2., 3., 4., 5., 6., 7.])
+}
+
+#[test]
+fn padding() {
+    let batch = pad_sequences(&[array![[1., 2.]], array![[3., 4.], [5., 6.]]], -1.);
+
+    assert_eq!(
+        batch.data,
+        array![[[1., 2.], [-1., -1.]], [[3., 4.], [5., 6.]]]
+    );
+    assert_eq!(batch.lengths, vec![1, 2]);
+    assert_eq!(batch.mask, array![[1., 0.], [1., 1.]]);
+}
+
+#[test]
+#[should_panic(expected = "error: cannot pad sequences with different numbers of features.")]
+fn padding_features_mismatch() {
+    pad_sequences(&[Array2::zeros((1, 2)), Array2::zeros((1, 3))], 0.);
+}
+
+#[test]
+fn sequential_batches() {
+    let dataset = dataset().with_padding(-1.);
+    assert_eq!(dataset.lengths(), vec![3, 1, 4, 1, 5, 9, 2, 6]);
+
+    let batches: Vec<_> = dataset.batch(3).collect();
+    assert_eq!(batches.len(), 3);
+
+    let (batch, labels) = &batches[0];
+    assert_eq!(batch.data.shape(), &[3, 4, 2]);
+    assert_eq!(batch.data[[1, 1, 0]], -1.);
+    assert_eq!(labels, array![0., 1., 2.]);
+    assert_eq!(batches[2].0.lengths, vec![2, 6]);
+}
+
+#[test]
+fn bucketing() {
+    let dataset = dataset();
+    let mut sampler = BucketBatchSampler::new(&dataset.lengths(), 2).with_seed(0);
+
+    let mut padded = 0;
+    for (batch, labels) in dataset.batch_with(&mut sampler) {
+        assert_eq!(labels.len(), 2);
+        padded += batch.mask.len() - batch.mask.sum() as usize;
+    }
+    // Sorted lengths 1 1 2 3 4 5 6 9 are paired as (1, 1), (2, 3), (4, 5) and (6, 9).
+    assert_eq!(padded, 5);
+}
+
+#[test]
+fn bucketing_pools() {
+    let lengths: Vec<usize> = (0..20).collect();
+    let mut sampler = BucketBatchSampler::new(&lengths, 3)
+        .with_pool(2)
+        .drop_last()
+        .with_seed(1);
+
+    let batches = sampler.sample_batches();
+    // Pools of 6, 6, 6 and 2 records.
+    assert_eq!(batches.len(), 6);
+    for batch in batches {
+        assert!(batch.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+}
+
+#[test]
+#[should_panic(expected = "error: the number of sequences and labels differ.")]
+fn labels_mismatch() {
+    SequenceDataset::new(vec![Array2::zeros((1, 1))], array![0., 1.]);
+}
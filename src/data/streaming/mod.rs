@@ -0,0 +1,314 @@
+//! Streaming datasets, for data that doesn't fit in memory.
+//!
+//! An [`IterableDataset`] reads its samples from disk one at a time and can be traversed once
+//! per epoch, so that only the current batch needs to be held in memory. Streams yield
+//! [`io::Result`]s, as reading can fail halfway through an epoch.
+//!
+//! * [`LineDataset`] - parses each line of a set of text files, such as JSON lines or
+//!   whitespace-separated values, with a user-supplied function.
+//!
+//! * [`CsvStream`] - streams the records and labels of a set of *.csv* files, it is created with
+//!   [`LabeledDataLoader::stream_csv()`](super::LabeledDataLoader::stream_csv) and honours the
+//!   configuration of the loader.
+//!
+//! Samples are grouped with [`.batch()`](IterableDataset::batch) and pairs of tensors can be
+//! stacked into a batch with [`collate`].
+//!
+//! ```no_run
+//! use neuronika::data::{
+//!     streaming::{collate, IterableDataset},
+//!     DataLoader,
+//! };
+//!
+//! let mut loader = DataLoader::default().with_labels(&[4]);
+//! let stream = loader.stream_csv(&["shard_0.csv", "shard_1.csv"], 4, 1);
+//!
+//! for epoch in 0..5 {
+//!     for batch in stream.batch(32).unwrap() {
+//!         let (records, labels) = collate(&batch.unwrap());
+//!         assert_eq!(records.shape()[1], 4);
+//!     }
+//! }
+//! ```
+use super::LabeledDataLoader;
+use csv::StringRecord;
+use ndarray::{stack, Array, ArrayView, Axis, Dimension, IntoDimension};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    marker::PhantomData,
+    path::PathBuf,
+};
+
+/// A stream of fallible samples.
+pub type Stream<'a, T> = Box<dyn Iterator<Item = io::Result<T>> + 'a>;
+
+/// A labeled sample, made of a record and its label.
+pub type Sample<D1, D2> = (Array<f32, D1>, Array<f32, D2>);
+
+/// A dataset whose samples are read sequentially.
+pub trait IterableDataset {
+    /// Type of the samples.
+    type Item;
+
+    /// Starts a new pass over the samples.
+    ///
+    /// # Errors
+    ///
+    /// If the underlying source cannot be opened.
+    fn stream(&self) -> io::Result<Stream<'_, Self::Item>>;
+
+    /// Starts a new pass over the samples, grouping them into batches of size `size`.
+    ///
+    /// The last batch may be smaller. A sample that fails to be read ends the current batch and is
+    /// then yielded as an error, the following samples are grouped into the next batch.
+    ///
+    /// # Errors
+    ///
+    /// If the underlying source cannot be opened.
+    ///
+    /// # Panics
+    ///
+    /// If `size` is zero.
+    fn batch(&self, size: usize) -> io::Result<StreamBatch<'_, Self::Item>> {
+        assert!(size > 0, "error: the batch size must be positive.");
+        Ok(StreamBatch {
+            stream: self.stream()?,
+            size,
+            error: None,
+        })
+    }
+}
+
+/// Iterator over batches of samples of an [`IterableDataset`].
+pub struct StreamBatch<'a, T> {
+    stream: Stream<'a, T>,
+    size: usize,
+    error: Option<io::Error>,
+}
+
+impl<'a, T> Iterator for StreamBatch<'a, T> {
+    type Item = io::Result<Vec<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(error) = self.error.take() {
+            return Some(Err(error));
+        }
+
+        let mut batch = Vec::with_capacity(self.size);
+        for sample in self.stream.by_ref() {
+            match sample {
+                Ok(sample) => batch.push(sample),
+                Err(error) if batch.is_empty() => return Some(Err(error)),
+                Err(error) => {
+                    self.error = Some(error);
+                    break;
+                }
+            }
+            if batch.len() == self.size {
+                break;
+            }
+        }
+
+        if batch.is_empty() {
+            None
+        } else {
+            Some(Ok(batch))
+        }
+    }
+}
+
+/// Stacks a batch of pairs of tensors, such as records and labels, along a new outermost axis.
+///
+/// # Panics
+///
+/// If the batch is empty or if the tensors have different shapes.
+pub fn collate<D1, D2>(batch: &[Sample<D1, D2>]) -> Sample<D1::Larger, D2::Larger>
+where
+    D1: Dimension,
+    D2: Dimension,
+{
+    assert!(!batch.is_empty(), "error: cannot collate an empty batch.");
+    let records: Vec<ArrayView<f32, D1>> = batch.iter().map(|(record, _)| record.view()).collect();
+    let labels: Vec<ArrayView<f32, D2>> = batch.iter().map(|(_, label)| label.view()).collect();
+
+    (
+        stack(Axis(0), &records).expect("error: the records have different shapes."),
+        stack(Axis(0), &labels).expect("error: the labels have different shapes."),
+    )
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ LineDataset ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// A dataset streamed from the lines of a set of text files.
+///
+/// The files are read in the given order and each non-empty line is parsed into a sample by a
+/// user-supplied function.
+///
+/// ```
+/// use neuronika::data::streaming::{IterableDataset, LineDataset};
+/// use std::io;
+///
+/// let path = std::env::temp_dir().join("neuronika-line-dataset.txt");
+/// std::fs::write(&path, "1 2\n3 4\n").unwrap();
+///
+/// let dataset = LineDataset::new(&[&path], |line| {
+///     line.split_whitespace()
+///         .map(|value| value.parse::<f32>())
+///         .collect::<Result<Vec<f32>, _>>()
+///         .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+/// });
+///
+/// let samples: Vec<Vec<f32>> = dataset.stream().unwrap().map(Result::unwrap).collect();
+/// assert_eq!(samples, vec![vec![1., 2.], vec![3., 4.]]);
+/// ```
+pub struct LineDataset<F, T> {
+    paths: Vec<PathBuf>,
+    parse: F,
+    sample: PhantomData<fn() -> T>,
+}
+
+impl<F, T> LineDataset<F, T>
+where
+    F: Fn(&str) -> io::Result<T>,
+{
+    /// Creates a new `LineDataset`.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` - files to read, in order.
+    /// * `parse` - function that parses a line into a sample.
+    pub fn new<P: Into<PathBuf> + Clone>(paths: &[P], parse: F) -> Self {
+        Self {
+            paths: paths.iter().cloned().map(Into::into).collect(),
+            parse,
+            sample: PhantomData,
+        }
+    }
+
+    /// Returns the paths of the files.
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+}
+
+/// Opens the files lazily, one after the other, and chains the streams built from them.
+fn chain_files<'a, T, F>(paths: &'a [PathBuf], open: F) -> Stream<'a, T>
+where
+    T: 'a,
+    F: Fn(&'a PathBuf) -> io::Result<Stream<'a, T>> + 'a,
+{
+    Box::new(paths.iter().flat_map(move |path| match open(path) {
+        Ok(stream) => stream,
+        Err(error) => Box::new(std::iter::once(Err(error))),
+    }))
+}
+
+impl<F, T> IterableDataset for LineDataset<F, T>
+where
+    F: Fn(&str) -> io::Result<T>,
+{
+    type Item = T;
+
+    fn stream(&self) -> io::Result<Stream<'_, T>> {
+        Ok(chain_files(&self.paths, move |path| {
+            let lines = BufReader::new(File::open(path)?).lines();
+            Ok(Box::new(lines.filter_map(move |line| match line {
+                Ok(line) if line.trim().is_empty() => None,
+                Ok(line) => Some((self.parse)(&line)),
+                Err(error) => Some(Err(error)),
+            })))
+        }))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ CsvStream ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// A labeled dataset streamed from a set of *.csv* files.
+///
+/// Each sample is a pair made of a record of dimensionality `D1` and a label of dimensionality
+/// `D2`. See [`LabeledDataLoader::stream_csv()`](super::LabeledDataLoader::stream_csv).
+pub struct CsvStream<'a, D1, D2> {
+    loader: &'a LabeledDataLoader,
+    paths: Vec<PathBuf>,
+    record_shape: D1,
+    label_shape: D2,
+}
+
+impl<'a, D1: Dimension, D2: Dimension> CsvStream<'a, D1, D2> {
+    pub(super) fn new<P, S1, S2>(
+        loader: &'a LabeledDataLoader,
+        paths: &[P],
+        record_shape: S1,
+        label_shape: S2,
+    ) -> Self
+    where
+        P: Into<PathBuf> + Clone,
+        S1: IntoDimension<Dim = D1>,
+        S2: IntoDimension<Dim = D2>,
+    {
+        let record_shape = record_shape.into_dimension();
+        let label_shape = label_shape.into_dimension();
+        if record_shape.size() == 0 || label_shape.size() == 0 {
+            panic!("error: cannot handle empty records")
+        }
+
+        Self {
+            loader,
+            paths: paths.iter().cloned().map(Into::into).collect(),
+            record_shape,
+            label_shape,
+        }
+    }
+
+    /// Returns the paths of the files.
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    fn parse(&self, record: StringRecord) -> io::Result<Sample<D1, D2>> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        let (mut input, mut label) = (Vec::new(), Vec::new());
+        for (id, field) in record.iter().enumerate() {
+            let value = field.trim().parse::<f32>().map_err(|error| {
+                invalid(format!(
+                    "cannot parse field {:?} of column {}: {}",
+                    field, id, error
+                ))
+            })?;
+            match self.loader.labels.binary_search(&id) {
+                Ok(_) => label.push(value),
+                Err(_) => input.push(value),
+            }
+        }
+
+        Ok((
+            Array::from_shape_vec(self.record_shape.clone(), input)
+                .map_err(|error| invalid(format!("invalid record: {}", error)))?,
+            Array::from_shape_vec(self.label_shape.clone(), label)
+                .map_err(|error| invalid(format!("invalid label: {}", error)))?,
+        ))
+    }
+}
+
+impl<'a, D1: Dimension, D2: Dimension> IterableDataset for CsvStream<'a, D1, D2> {
+    type Item = Sample<D1, D2>;
+
+    fn stream(&self) -> io::Result<Stream<'_, Self::Item>> {
+        Ok(chain_files(&self.paths, move |path| {
+            let reader = self.loader.r_builder.from_path(path)?;
+            Ok(Box::new(
+                reader.into_records().map(move |record| self.parse(record?)),
+            ))
+        }))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
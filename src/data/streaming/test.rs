@@ -0,0 +1,100 @@
+use super::{collate, IterableDataset, LineDataset};
+use crate::data::DataLoader;
+use ndarray::array;
+use std::{fs, io, path::PathBuf};
+
+fn file(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "neuronika-streaming-{}-{}",
+        std::process::id(),
+        name
+    ));
+    fs::write(&path, content).unwrap();
+    path
+}
+
+fn parse(line: &str) -> io::Result<f32> {
+    line.trim()
+        .parse()
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+#[test]
+fn lines() {
+    let paths = [file("lines-0", "1\n2\n\n3\n"), file("lines-1", "4\n5")];
+    let dataset = LineDataset::new(&paths, parse);
+
+    for _ in 0..2 {
+        let batches: Vec<Vec<f32>> = dataset.batch(2).unwrap().map(Result::unwrap).collect();
+        assert_eq!(batches, vec![vec![1., 2.], vec![3., 4.], vec![5.]]);
+    }
+
+    for path in paths {
+        fs::remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn errors() {
+    let path = file("errors", "1\nx\n2\n");
+    let missing = std::env::temp_dir().join("neuronika-streaming-missing");
+    let dataset = LineDataset::new(&[path.clone(), missing], parse);
+
+    let batches: Vec<_> = dataset.batch(4).unwrap().collect();
+    assert_eq!(batches.len(), 4);
+    assert_eq!(batches[0].as_ref().unwrap(), &vec![1.]);
+    assert!(batches[1].is_err());
+    assert_eq!(batches[2].as_ref().unwrap(), &vec![2.]);
+    assert_eq!(
+        batches[3].as_ref().unwrap_err().kind(),
+        io::ErrorKind::NotFound
+    );
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn csv() {
+    let paths = [
+        file("csv-0.csv", "a,b,label\n1,2,0\n3,4,1\n"),
+        file("csv-1.csv", "a,b,label\n5,6,0\n"),
+    ];
+    let loader = DataLoader::default().with_labels(&[2]);
+    let stream = loader.stream_csv(&paths, 2, 1);
+
+    let batches: Vec<_> = stream.batch(2).unwrap().map(Result::unwrap).collect();
+    assert_eq!(batches.len(), 2);
+
+    let (records, labels) = collate(&batches[0]);
+    assert_eq!(records, array![[1., 2.], [3., 4.]]);
+    assert_eq!(labels, array![[0.], [1.]]);
+    assert_eq!(collate(&batches[1]).0, array![[5., 6.]]);
+
+    for path in paths {
+        fs::remove_file(path).unwrap();
+    }
+}
+
+#[test]
+fn csv_errors() {
+    let path = file("csv-errors.csv", "1,2,0\n1,x,0\n");
+    let mut loader = DataLoader::default().with_labels(&[2]);
+    loader.without_headers();
+    let stream = loader.stream_csv(&[&path], 2, 1);
+
+    let samples: Vec<_> = stream.stream().unwrap().collect();
+    assert!(samples[0].is_ok());
+    assert!(samples[1].is_err());
+
+    // The shape of the records doesn't match the one of the file.
+    let stream = loader.stream_csv(&[&path], 3, 1);
+    assert!(stream.stream().unwrap().next().unwrap().is_err());
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "error: cannot collate an empty batch.")]
+fn collate_empty() {
+    collate::<ndarray::Ix1, ndarray::Ix1>(&[]);
+}
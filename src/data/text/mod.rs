@@ -0,0 +1,382 @@
+//! Text tokenization and numericalization.
+//!
+//! Text is turned into the indices consumed by an [`Embedding`](crate::nn::Embedding) in three
+//! steps: a [`Tokenizer`] splits each text into tokens, a [`Vocab`] assigns an id to each token and
+//! [`.numericalize()`](Vocab::numericalize()) packs the ids of a batch of texts into a padded
+//! integer tensor, which is turned into a variable with [`indices`](crate::indices).
+//!
+//! Every vocabulary starts with four special tokens, whose ids are fixed: [`PAD_TOKEN`] fills the
+//! padded positions, [`UNK_TOKEN`] replaces the tokens missing from the vocabulary, and
+//! [`BOS_TOKEN`] and [`EOS_TOKEN`] mark the beginning and the end of each sequence when the
+//! vocabulary is built [`.with_boundaries()`](Vocab::with_boundaries()).
+//!
+//! ```
+//! use neuronika::data::text::{Tokenizer, Vocab, WhitespaceTokenizer, PAD_ID};
+//! use neuronika::nn::Embedding;
+//!
+//! let tokenizer = WhitespaceTokenizer::new().with_lowercase();
+//! let corpus: Vec<_> = ["The cat sat", "the dog sat down"]
+//!     .iter()
+//!     .map(|text| tokenizer.tokenize(text))
+//!     .collect();
+//!
+//! let vocab = Vocab::from_corpus(&corpus, 1);
+//! let batch = vocab.numericalize(&corpus);
+//! assert_eq!(batch.ids.shape(), &[2, 4]);
+//! assert_eq!(batch.ids[[0, 3]], PAD_ID as i64);
+//!
+//! let embedding = Embedding::new(vocab.len(), 8);
+//! let embedded = embedding.forward(neuronika::indices(batch.ids));
+//! embedded.forward();
+//! assert_eq!(embedded.data().shape(), &[2, 4, 8]);
+//! ```
+use ndarray::{s, Array2};
+use std::collections::HashMap;
+
+/// Token filling the padded positions of a batch.
+pub const PAD_TOKEN: &str = "<pad>";
+
+/// Token replacing the tokens that are missing from a vocabulary.
+pub const UNK_TOKEN: &str = "<unk>";
+
+/// Token marking the beginning of a sequence.
+pub const BOS_TOKEN: &str = "<bos>";
+
+/// Token marking the end of a sequence.
+pub const EOS_TOKEN: &str = "<eos>";
+
+/// Id of [`PAD_TOKEN`].
+pub const PAD_ID: usize = 0;
+
+/// Id of [`UNK_TOKEN`].
+pub const UNK_ID: usize = 1;
+
+/// Id of [`BOS_TOKEN`].
+pub const BOS_ID: usize = 2;
+
+/// Id of [`EOS_TOKEN`].
+pub const EOS_ID: usize = 3;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tokenizers ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Splits texts into tokens.
+pub trait Tokenizer {
+    /// Splits `text` into tokens.
+    fn tokenize(&self, text: &str) -> Vec<String>;
+
+    /// Joins `tokens` back into a text.
+    fn detokenize<S: AsRef<str>>(&self, tokens: &[S]) -> String;
+}
+
+/// Splits texts at whitespace.
+///
+/// The tokens are separated by any amount of Unicode whitespace, which is discarded, so that
+/// detokenization joins them with single spaces.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WhitespaceTokenizer {
+    lowercase: bool,
+}
+
+impl WhitespaceTokenizer {
+    /// Creates a new whitespace tokenizer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Converts the tokens to lowercase.
+    pub fn with_lowercase(mut self) -> Self {
+        self.lowercase = true;
+        self
+    }
+}
+
+impl Tokenizer for WhitespaceTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        text.split_whitespace()
+            .map(|token| {
+                if self.lowercase {
+                    token.to_lowercase()
+                } else {
+                    token.to_string()
+                }
+            })
+            .collect()
+    }
+
+    fn detokenize<S: AsRef<str>>(&self, tokens: &[S]) -> String {
+        tokens
+            .iter()
+            .map(AsRef::as_ref)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Splits texts into their UTF-8 bytes.
+///
+/// Each byte becomes a token made of the character with the same code point, so that every text
+/// is covered by the *256* tokens of [`Vocab::bytes()`] and no token is ever unknown.
+/// Detokenization maps the tokens back to bytes and replaces invalid UTF-8 sequences with
+/// [`U+FFFD`](std::char::REPLACEMENT_CHARACTER).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ByteTokenizer;
+
+impl ByteTokenizer {
+    /// Creates a new byte-level tokenizer.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Tokenizer for ByteTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        text.bytes()
+            .map(|byte| char::from(byte).to_string())
+            .collect()
+    }
+
+    /// Joins `tokens` back into a text, skipping the tokens that do not stand for a byte, such as
+    /// the special ones.
+    fn detokenize<S: AsRef<str>>(&self, tokens: &[S]) -> String {
+        let bytes: Vec<u8> = tokens
+            .iter()
+            .filter_map(|token| {
+                let mut chars = token.as_ref().chars();
+                match (chars.next(), chars.next()) {
+                    (Some(char), None) => u8::try_from(char).ok(),
+                    _ => None,
+                }
+            })
+            .collect();
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Vocab ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// A batch of token sequences padded to the same length.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenBatch {
+    /// Padded token ids, of shape *(batch, steps)*.
+    pub ids: Array2<i64>,
+    /// Length of each sequence before padding.
+    pub lengths: Vec<usize>,
+    /// Mask of shape *(batch, steps)*, equal to one at the valid steps and zero at the padded ones.
+    pub mask: Array2<f32>,
+}
+
+/// A bidirectional mapping between tokens and their ids.
+///
+/// The ids *0* to *3* belong to the special tokens, the following ones to the regular tokens in
+/// order of insertion.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Vocab {
+    tokens: Vec<String>,
+    ids: HashMap<String, usize>,
+    boundaries: bool,
+}
+
+impl Vocab {
+    /// Creates a new vocabulary holding only the special tokens.
+    pub fn new() -> Self {
+        let mut vocab = Self {
+            tokens: Vec::new(),
+            ids: HashMap::new(),
+            boundaries: false,
+        };
+        for token in [PAD_TOKEN, UNK_TOKEN, BOS_TOKEN, EOS_TOKEN] {
+            vocab.insert(token);
+        }
+
+        vocab
+    }
+
+    /// Creates a new vocabulary from the tokens of `corpus` that appear at least `min_frequency`
+    /// times.
+    ///
+    /// The tokens are sorted by decreasing frequency, ties are broken alphabetically, so that the
+    /// ids do not depend on the order of the corpus.
+    ///
+    /// # Arguments
+    ///
+    /// * `corpus` - tokenized texts.
+    ///
+    /// * `min_frequency` - minimum number of occurrences of a token.
+    pub fn from_corpus<I, T, S>(corpus: I, min_frequency: usize) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut frequencies: HashMap<String, usize> = HashMap::new();
+        for token in corpus.into_iter().flatten() {
+            *frequencies.entry(token.as_ref().to_string()).or_default() += 1;
+        }
+
+        let mut tokens: Vec<_> = frequencies
+            .into_iter()
+            .filter(|(_, frequency)| *frequency >= min_frequency)
+            .collect();
+        tokens.sort_unstable_by(|(a, a_freq), (b, b_freq)| b_freq.cmp(a_freq).then(a.cmp(b)));
+
+        let mut vocab = Self::new();
+        for (token, _) in tokens {
+            vocab.insert(&token);
+        }
+
+        vocab
+    }
+
+    /// Creates a new vocabulary holding the special tokens and the *256* tokens produced by a
+    /// [`ByteTokenizer`], the one of byte *b* having id *b + 4*.
+    pub fn bytes() -> Self {
+        let mut vocab = Self::new();
+        for byte in 0..=u8::MAX {
+            vocab.insert(&char::from(byte).to_string());
+        }
+
+        vocab
+    }
+
+    /// Surrounds each encoded sequence with [`BOS_TOKEN`] and [`EOS_TOKEN`].
+    pub fn with_boundaries(mut self) -> Self {
+        self.boundaries = true;
+        self
+    }
+
+    /// Adds `token` to the vocabulary, if missing, and returns its id.
+    pub fn insert(&mut self, token: &str) -> usize {
+        if let Some(&id) = self.ids.get(token) {
+            return id;
+        }
+
+        let id = self.tokens.len();
+        self.tokens.push(token.to_string());
+        self.ids.insert(token.to_string(), id);
+        id
+    }
+
+    /// Returns the number of tokens in the vocabulary, special ones included.
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Checks whether the vocabulary is empty. It is never the case, as the special tokens are
+    /// always present.
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Checks whether `token` is in the vocabulary.
+    pub fn contains(&self, token: &str) -> bool {
+        self.ids.contains_key(token)
+    }
+
+    /// Returns the id of `token`, or [`UNK_ID`] if it is missing from the vocabulary.
+    pub fn id(&self, token: &str) -> usize {
+        self.ids.get(token).copied().unwrap_or(UNK_ID)
+    }
+
+    /// Returns the token with id `id`.
+    ///
+    /// # Panics
+    ///
+    /// If `id` is out of range.
+    pub fn token(&self, id: usize) -> &str {
+        assert!(
+            id < self.len(),
+            "error: {} is not a valid id for a vocabulary of {} tokens.",
+            id,
+            self.len()
+        );
+
+        &self.tokens[id]
+    }
+
+    /// Returns the tokens of the vocabulary, sorted by id.
+    pub fn tokens(&self) -> &[String] {
+        &self.tokens
+    }
+
+    /// Encodes `tokens` into their ids, surrounding them with the ones of [`BOS_TOKEN`] and
+    /// [`EOS_TOKEN`] if the vocabulary was built [`.with_boundaries()`](Vocab::with_boundaries()).
+    pub fn encode<I, S>(&self, tokens: I) -> Vec<i64>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let ids = tokens
+            .into_iter()
+            .map(|token| self.id(token.as_ref()) as i64);
+        if self.boundaries {
+            std::iter::once(BOS_ID as i64)
+                .chain(ids)
+                .chain(std::iter::once(EOS_ID as i64))
+                .collect()
+        } else {
+            ids.collect()
+        }
+    }
+
+    /// Decodes `ids` into their tokens.
+    ///
+    /// # Panics
+    ///
+    /// If an id is out of range.
+    pub fn decode(&self, ids: &[i64]) -> Vec<&str> {
+        ids.iter()
+            .map(|&id| {
+                assert!(
+                    id >= 0,
+                    "error: {} is not a valid id for a vocabulary of {} tokens.",
+                    id,
+                    self.len()
+                );
+                self.token(id as usize)
+            })
+            .collect()
+    }
+
+    /// Encodes a batch of tokenized texts and pads them with [`PAD_ID`] to the length of the
+    /// longest one.
+    pub fn numericalize<I, T, S>(&self, sequences: I) -> TokenBatch
+    where
+        I: IntoIterator<Item = T>,
+        T: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let sequences: Vec<_> = sequences
+            .into_iter()
+            .map(|tokens| self.encode(tokens))
+            .collect();
+
+        let lengths: Vec<usize> = sequences.iter().map(Vec::len).collect();
+        let steps = lengths.iter().copied().max().unwrap_or(0);
+        let mut ids = Array2::from_elem((sequences.len(), steps), PAD_ID as i64);
+        let mut mask = Array2::zeros((sequences.len(), steps));
+        for (index, sequence) in sequences.iter().enumerate() {
+            let length = sequence.len();
+            ids.slice_mut(s![index, ..length])
+                .iter_mut()
+                .zip(sequence)
+                .for_each(|(id, &token)| *id = token);
+            mask.slice_mut(s![index, ..length]).fill(1.);
+        }
+
+        TokenBatch { ids, lengths, mask }
+    }
+}
+
+impl Default for Vocab {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,82 @@
+use super::{
+    ByteTokenizer, Tokenizer, Vocab, WhitespaceTokenizer, BOS_ID, EOS_ID, PAD_ID, PAD_TOKEN,
+    UNK_ID, UNK_TOKEN,
+};
+use ndarray::array;
+
+#[test]
+fn whitespace_tokenizer() {
+    let tokens = WhitespaceTokenizer::new().tokenize(" The  cat\tsat\n");
+    assert_eq!(tokens, vec!["The", "cat", "sat"]);
+
+    let tokenizer = WhitespaceTokenizer::new().with_lowercase();
+    let tokens = tokenizer.tokenize("The Cat");
+    assert_eq!(tokens, vec!["the", "cat"]);
+    assert_eq!(tokenizer.detokenize(&tokens), "the cat");
+}
+
+#[test]
+fn byte_tokenizer() {
+    let tokenizer = ByteTokenizer::new();
+    let tokens = tokenizer.tokenize("né");
+    // The accented letter takes two bytes.
+    assert_eq!(tokens.len(), 3);
+    assert_eq!(tokenizer.detokenize(&tokens), "né");
+
+    // Every byte is in the byte vocabulary, the special tokens are skipped when detokenizing.
+    let vocab = Vocab::bytes().with_boundaries();
+    assert_eq!(vocab.len(), 260);
+    let ids = vocab.encode(&tokens);
+    assert_eq!(ids[1], b'n' as i64 + 4);
+    assert!(!ids.contains(&(UNK_ID as i64)));
+    assert_eq!(tokenizer.detokenize(&vocab.decode(&ids)), "né");
+}
+
+#[test]
+fn vocab() {
+    let mut vocab = Vocab::new();
+    assert_eq!(vocab.len(), 4);
+    assert_eq!(vocab.token(PAD_ID), PAD_TOKEN);
+
+    assert_eq!(vocab.insert("cat"), 4);
+    assert_eq!(vocab.insert("cat"), 4);
+    assert!(vocab.contains("cat"));
+    assert_eq!(vocab.id("dog"), UNK_ID);
+    assert_eq!(
+        vocab.decode(&vocab.encode(["cat", "dog"])),
+        vec!["cat", UNK_TOKEN]
+    );
+}
+
+#[test]
+fn vocab_from_corpus() {
+    let corpus = [vec!["b", "a", "c"], vec!["a", "b", "a"], vec!["d"]];
+    let vocab = Vocab::from_corpus(&corpus, 2);
+
+    // Sorted by decreasing frequency, rare tokens are left out.
+    assert_eq!(&vocab.tokens()[4..], &["a", "b"]);
+    assert_eq!(vocab.id("c"), UNK_ID);
+}
+
+#[test]
+fn numericalize() {
+    let vocab = Vocab::from_corpus([["a", "b"]], 1).with_boundaries();
+    let batch = vocab.numericalize([vec!["a", "b", "a"], vec!["b"]]);
+
+    let (bos, eos, pad) = (BOS_ID as i64, EOS_ID as i64, PAD_ID as i64);
+    assert_eq!(
+        batch.ids,
+        array![[bos, 4, 5, 4, eos], [bos, 5, eos, pad, pad]]
+    );
+    assert_eq!(batch.lengths, vec![5, 3]);
+    assert_eq!(
+        batch.mask,
+        array![[1., 1., 1., 1., 1.], [1., 1., 1., 0., 0.]]
+    );
+}
+
+#[test]
+#[should_panic(expected = "error: 4 is not a valid id for a vocabulary of 4 tokens.")]
+fn vocab_token_fail() {
+    Vocab::new().token(4);
+}
@@ -0,0 +1,847 @@
+//! Image transforms.
+//!
+//! Transforms operate on single images stored as tensors of shape *(channels, height, width)*,
+//! which is the layout used by the [`datasets`](super::datasets) module and by the convolutional
+//! layers. They can be chained with [`Compose`] and applied while loading an
+//! [`ImageFolder`](super::datasets::ImageFolder), or to whole batches with
+//! [`.apply_batch()`](Transform::apply_batch).
+//!
+//! Besides the deterministic preprocessing transforms, the module provides the random data
+//! augmentations [`RandomCrop`], [`RandomHorizontalFlip`], [`ColorJitter`] and
+//! [`RandomErasing`]. They draw their parameters from a thread-local random number generator,
+//! which can be seeded with [`manual_seed`] for reproducibility.
+//!
+//! Finally, [`mixup`] and [`cutmix`] mix the samples of whole batches, already turned into
+//! variables, and return mixed soft targets.
+//!
+//! ```
+//! use ndarray::Array3;
+//! use neuronika::data::transforms::{CenterCrop, Compose, Normalize, Resize, Transform};
+//!
+//! let transform = Compose::new()
+//!     .then(Resize::new(36, 36))
+//!     .then(CenterCrop::new(32, 32))
+//!     .then(Normalize::new(&[0.5, 0.5, 0.5], &[0.25, 0.25, 0.25]));
+//!
+//! let image = transform.apply(Array3::zeros((3, 48, 64)));
+//! assert_eq!(image.shape(), &[3, 32, 32]);
+//! ```
+use crate::{variable::Input, Data, Var};
+use ndarray::{
+    s, stack, Array1, Array2, Array3, Array4, ArrayView3, Axis, Dimension, Ix2, Ix4, RemoveAxis,
+};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use rand_distr::{Beta, Distribution};
+use std::cell::RefCell;
+
+/// An image transform.
+///
+/// It is implemented by all the closures taking and returning an image.
+pub trait Transform {
+    /// Applies the transform to `image`, of shape *(channels, height, width)*.
+    fn apply(&self, image: Array3<f32>) -> Array3<f32>;
+
+    /// Applies the transform to each image of `batch`, of shape
+    /// *(samples, channels, height, width)*.
+    ///
+    /// Random transforms draw independent parameters for each image.
+    ///
+    /// # Panics
+    ///
+    /// If the transformed images have different shapes.
+    fn apply_batch(&self, batch: Array4<f32>) -> Array4<f32> {
+        let images: Vec<Array3<f32>> = batch
+            .outer_iter()
+            .map(|image| self.apply(image.to_owned()))
+            .collect();
+        if images.is_empty() {
+            return batch;
+        }
+
+        let views: Vec<ArrayView3<f32>> = images.iter().map(Array3::view).collect();
+        stack(Axis(0), &views).expect("error: the transformed images have different shapes.")
+    }
+}
+
+impl<F: Fn(Array3<f32>) -> Array3<f32>> Transform for F {
+    fn apply(&self, image: Array3<f32>) -> Array3<f32> {
+        self(image)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Compose ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// A pipeline of transforms, applied in the order in which they are added.
+#[derive(Default)]
+pub struct Compose {
+    transforms: Vec<Box<dyn Transform>>,
+}
+
+impl Compose {
+    /// Creates an empty pipeline, which leaves the images untouched.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `transform` to the pipeline.
+    pub fn then<T: Transform + 'static>(mut self, transform: T) -> Self {
+        self.transforms.push(Box::new(transform));
+        self
+    }
+
+    /// Returns the number of transforms in the pipeline.
+    pub fn len(&self) -> usize {
+        self.transforms.len()
+    }
+
+    /// Returns `true` if the pipeline contains no transforms.
+    pub fn is_empty(&self) -> bool {
+        self.transforms.is_empty()
+    }
+}
+
+impl Transform for Compose {
+    fn apply(&self, image: Array3<f32>) -> Array3<f32> {
+        self.transforms
+            .iter()
+            .fold(image, |image, transform| transform.apply(image))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Resize ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Resizes the images to a fixed size with bilinear interpolation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Resize {
+    height: usize,
+    width: usize,
+}
+
+impl Resize {
+    /// Creates a new resize transform.
+    ///
+    /// # Panics
+    ///
+    /// If either `height` or `width` is zero.
+    pub fn new(height: usize, width: usize) -> Self {
+        assert!(
+            height > 0 && width > 0,
+            "error: cannot resize to a {}x{} image.",
+            height,
+            width
+        );
+        Self { height, width }
+    }
+}
+
+/// Returns, for each output coordinate, the two input coordinates to interpolate and the weight
+/// of the second one. Pixel centers are aligned, as in most imaging libraries.
+fn sampling_grid(input: usize, output: usize) -> Vec<(usize, usize, f32)> {
+    let scale = input as f32 / output as f32;
+    (0..output)
+        .map(|index| {
+            let position = ((index as f32 + 0.5) * scale - 0.5).clamp(0., (input - 1) as f32);
+            let low = position.floor() as usize;
+            let high = (low + 1).min(input - 1);
+            (low, high, position - low as f32)
+        })
+        .collect()
+}
+
+impl Transform for Resize {
+    fn apply(&self, image: Array3<f32>) -> Array3<f32> {
+        let (channels, height, width) = image.dim();
+        if (height, width) == (self.height, self.width) {
+            return image;
+        }
+
+        let (rows, columns) = (
+            sampling_grid(height, self.height),
+            sampling_grid(width, self.width),
+        );
+        Array3::from_shape_fn(
+            (channels, self.height, self.width),
+            |(channel, row, column)| {
+                let (top, bottom, y) = rows[row];
+                let (left, right, x) = columns[column];
+                let upper =
+                    image[[channel, top, left]] * (1. - x) + image[[channel, top, right]] * x;
+                let lower =
+                    image[[channel, bottom, left]] * (1. - x) + image[[channel, bottom, right]] * x;
+                upper * (1. - y) + lower * y
+            },
+        )
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ CenterCrop ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Crops the central region of the images.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CenterCrop {
+    height: usize,
+    width: usize,
+}
+
+impl CenterCrop {
+    /// Creates a new center crop transform.
+    pub fn new(height: usize, width: usize) -> Self {
+        Self { height, width }
+    }
+}
+
+/// Crops a `height` by `width` region of `image` starting at (`top`, `left`).
+///
+/// # Panics
+///
+/// If the region does not fit in the image.
+pub(crate) fn crop(
+    image: &Array3<f32>,
+    top: usize,
+    left: usize,
+    height: usize,
+    width: usize,
+) -> Array3<f32> {
+    let (_, image_height, image_width) = image.dim();
+    assert!(
+        top + height <= image_height && left + width <= image_width,
+        "error: cannot crop a {}x{} region from a {}x{} image.",
+        height,
+        width,
+        image_height,
+        image_width
+    );
+    image
+        .slice(s![.., top..top + height, left..left + width])
+        .to_owned()
+}
+
+impl Transform for CenterCrop {
+    /// # Panics
+    ///
+    /// If the image is smaller than the crop.
+    fn apply(&self, image: Array3<f32>) -> Array3<f32> {
+        let (_, height, width) = image.dim();
+        crop(
+            &image,
+            height.saturating_sub(self.height) / 2,
+            width.saturating_sub(self.width) / 2,
+            self.height,
+            self.width,
+        )
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Normalize ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Normalizes each channel of the images with the given mean and standard deviation.
+///
+/// ```text
+/// output[c] = (input[c] - mean[c]) / std[c]
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Normalize {
+    mean: Vec<f32>,
+    std: Vec<f32>,
+}
+
+impl Normalize {
+    /// Creates a new normalize transform.
+    ///
+    /// # Panics
+    ///
+    /// If `mean` and `std` have different lengths or if any standard deviation is not positive.
+    pub fn new(mean: &[f32], std: &[f32]) -> Self {
+        assert_eq!(
+            mean.len(),
+            std.len(),
+            "error: mean and standard deviation have different lengths."
+        );
+        assert!(
+            std.iter().all(|&std| std > 0.),
+            "error: the standard deviations must be positive."
+        );
+        Self {
+            mean: mean.to_vec(),
+            std: std.to_vec(),
+        }
+    }
+}
+
+impl Transform for Normalize {
+    /// # Panics
+    ///
+    /// If the number of channels of the image differs from the one of the transform.
+    fn apply(&self, mut image: Array3<f32>) -> Array3<f32> {
+        assert_eq!(
+            image.dim().0,
+            self.mean.len(),
+            "error: cannot normalize an image with {} channels using {} channel statistics.",
+            image.dim().0,
+            self.mean.len()
+        );
+        for ((mut channel, mean), std) in image
+            .outer_iter_mut()
+            .zip(self.mean.iter())
+            .zip(self.std.iter())
+        {
+            channel.mapv_inplace(|value| (value - mean) / std);
+        }
+        image
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Randomness ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+thread_local! {
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
+}
+
+/// Seeds the random number generator used by the augmentations of the current thread.
+///
+/// This makes the augmentations reproducible.
+///
+/// ```
+/// use ndarray::Array3;
+/// use neuronika::data::transforms::{manual_seed, RandomCrop, Transform};
+///
+/// let image = Array3::from_shape_fn((1, 8, 8), |(_, h, w)| (h * 8 + w) as f32);
+/// let crop = RandomCrop::new(4, 4);
+///
+/// manual_seed(42);
+/// let first = crop.apply(image.clone());
+/// manual_seed(42);
+/// assert_eq!(crop.apply(image), first);
+/// ```
+pub fn manual_seed(seed: u64) {
+    RNG.with(|rng| *rng.borrow_mut() = StdRng::seed_from_u64(seed));
+}
+
+fn with_rng<T, F: FnOnce(&mut StdRng) -> T>(f: F) -> T {
+    RNG.with(|rng| f(&mut rng.borrow_mut()))
+}
+
+/// Returns `true` with probability `p`.
+fn happens(p: f64) -> bool {
+    with_rng(|rng| rng.gen_bool(p))
+}
+
+fn check_probability(p: f64) {
+    assert!(
+        (0. ..=1.).contains(&p),
+        "error: {} is not a valid probability.",
+        p
+    );
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ RandomCrop ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Crops a region at a random position of the images, optionally padding them with zeros first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RandomCrop {
+    height: usize,
+    width: usize,
+    padding: usize,
+}
+
+impl RandomCrop {
+    /// Creates a new random crop transform.
+    pub fn new(height: usize, width: usize) -> Self {
+        Self {
+            height,
+            width,
+            padding: 0,
+        }
+    }
+
+    /// Pads each side of the images with `padding` zeros before cropping them.
+    pub fn with_padding(mut self, padding: usize) -> Self {
+        self.padding = padding;
+        self
+    }
+}
+
+impl Transform for RandomCrop {
+    /// # Panics
+    ///
+    /// If the padded image is smaller than the crop.
+    fn apply(&self, image: Array3<f32>) -> Array3<f32> {
+        let image = if self.padding > 0 {
+            let (channels, height, width) = image.dim();
+            let mut padded = Array3::zeros((
+                channels,
+                height + 2 * self.padding,
+                width + 2 * self.padding,
+            ));
+            padded
+                .slice_mut(s![
+                    ..,
+                    self.padding..self.padding + height,
+                    self.padding..self.padding + width
+                ])
+                .assign(&image);
+            padded
+        } else {
+            image
+        };
+
+        let (_, height, width) = image.dim();
+        let (top, left) = with_rng(|rng| {
+            (
+                rng.gen_range(0..=height.saturating_sub(self.height)),
+                rng.gen_range(0..=width.saturating_sub(self.width)),
+            )
+        });
+        crop(&image, top, left, self.height, self.width)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ RandomHorizontalFlip ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Mirrors the images horizontally with a given probability.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RandomHorizontalFlip {
+    p: f64,
+}
+
+impl RandomHorizontalFlip {
+    /// Creates a new random horizontal flip transform, which flips the images with probability
+    /// `p`.
+    ///
+    /// # Panics
+    ///
+    /// If `p` is not in *[0, 1]*.
+    pub fn new(p: f64) -> Self {
+        check_probability(p);
+        Self { p }
+    }
+}
+
+impl Default for RandomHorizontalFlip {
+    /// Flips the images with probability *0.5*.
+    fn default() -> Self {
+        Self::new(0.5)
+    }
+}
+
+impl Transform for RandomHorizontalFlip {
+    fn apply(&self, image: Array3<f32>) -> Array3<f32> {
+        if happens(self.p) {
+            image.slice(s![.., .., ..;-1]).to_owned()
+        } else {
+            image
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ ColorJitter ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Randomly changes the brightness, contrast and saturation of the images.
+///
+/// Each adjustment uses a factor drawn uniformly from *[1 - strength, 1 + strength]*, where the
+/// strength is set with the corresponding method and defaults to zero, which disables it. The
+/// images are expected to have values in *[0, 1]* and are clamped to that range.
+///
+/// * **brightness** - scales the values of the image.
+///
+/// * **contrast** - blends the image with its mean luminance.
+///
+/// * **saturation** - blends the image with its luminance, it is only applied to images with
+///   three channels.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ColorJitter {
+    brightness: f32,
+    contrast: f32,
+    saturation: f32,
+}
+
+impl ColorJitter {
+    /// Creates a new color jitter transform that leaves the images untouched.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the strength of the brightness adjustment.
+    pub fn with_brightness(mut self, strength: f32) -> Self {
+        self.brightness = strength;
+        self
+    }
+
+    /// Sets the strength of the contrast adjustment.
+    pub fn with_contrast(mut self, strength: f32) -> Self {
+        self.contrast = strength;
+        self
+    }
+
+    /// Sets the strength of the saturation adjustment.
+    pub fn with_saturation(mut self, strength: f32) -> Self {
+        self.saturation = strength;
+        self
+    }
+}
+
+/// Draws a factor uniformly from *[1 - strength, 1 + strength]*, never negative.
+fn jitter_factor(strength: f32) -> f32 {
+    if strength <= 0. {
+        return 1.;
+    }
+    with_rng(|rng| rng.gen_range((1. - strength).max(0.)..=1. + strength))
+}
+
+/// Returns the luminance of an image, of shape *(1, height, width)*.
+fn luminance(image: &Array3<f32>) -> Array3<f32> {
+    if image.dim().0 == 3 {
+        &image.slice(s![0..1, .., ..]) * 0.299
+            + &image.slice(s![1..2, .., ..]) * 0.587
+            + &image.slice(s![2..3, .., ..]) * 0.114
+    } else {
+        image.mean_axis(Axis(0)).unwrap().insert_axis(Axis(0))
+    }
+}
+
+/// Blends `image` with `other`, `factor` being the weight of the former.
+fn blend(image: Array3<f32>, other: &Array3<f32>, factor: f32) -> Array3<f32> {
+    (image * factor + &(other * (1. - factor))).mapv_into(|value| value.clamp(0., 1.))
+}
+
+impl Transform for ColorJitter {
+    fn apply(&self, image: Array3<f32>) -> Array3<f32> {
+        let mut image = image * jitter_factor(self.brightness);
+        image.mapv_inplace(|value| value.clamp(0., 1.));
+
+        let factor = jitter_factor(self.contrast);
+        if factor != 1. {
+            let mean = luminance(&image).mean().unwrap_or(0.);
+            image = blend(image, &Array3::from_elem((1, 1, 1), mean), factor);
+        }
+
+        let factor = jitter_factor(self.saturation);
+        if factor != 1. && image.dim().0 == 3 {
+            let gray = luminance(&image);
+            image = blend(image, &gray, factor);
+        }
+
+        image
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ RandomErasing ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Fills a random rectangle of the images with a constant value, with a given probability.
+///
+/// The area of the rectangle, relative to the one of the image, and its aspect ratio are drawn
+/// uniformly from the given ranges, the latter on a logarithmic scale. Using a fixed scale and a
+/// unit ratio yields square masks, as in *cutout*.
+///
+/// See also [Random Erasing Data Augmentation](https://arxiv.org/abs/1708.04896) and
+/// [Improved Regularization of Convolutional Neural Networks with Cutout](https://arxiv.org/abs/1708.04552).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RandomErasing {
+    p: f64,
+    scale: (f32, f32),
+    ratio: (f32, f32),
+    value: f32,
+}
+
+impl RandomErasing {
+    /// Creates a new random erasing transform, which erases a rectangle of the images with
+    /// probability `p`.
+    ///
+    /// The default scale range is *[0.02, 0.33]*, the default ratio range is *[0.3, 3.3]* and the
+    /// rectangle is filled with zeros.
+    ///
+    /// # Panics
+    ///
+    /// If `p` is not in *[0, 1]*.
+    pub fn new(p: f64) -> Self {
+        check_probability(p);
+        Self {
+            p,
+            scale: (0.02, 0.33),
+            ratio: (0.3, 3.3),
+            value: 0.,
+        }
+    }
+
+    /// Sets the range of the area of the rectangle, relative to the one of the image.
+    ///
+    /// # Panics
+    ///
+    /// If the range is empty or not contained in *(0, 1]*.
+    pub fn with_scale(mut self, min: f32, max: f32) -> Self {
+        assert!(
+            0. < min && min <= max && max <= 1.,
+            "error: invalid scale range [{}, {}].",
+            min,
+            max
+        );
+        self.scale = (min, max);
+        self
+    }
+
+    /// Sets the range of the aspect ratio, width over height, of the rectangle.
+    ///
+    /// # Panics
+    ///
+    /// If the range is empty or not positive.
+    pub fn with_ratio(mut self, min: f32, max: f32) -> Self {
+        assert!(
+            0. < min && min <= max,
+            "error: invalid ratio range [{}, {}].",
+            min,
+            max
+        );
+        self.ratio = (min, max);
+        self
+    }
+
+    /// Sets the value the rectangle is filled with.
+    pub fn with_value(mut self, value: f32) -> Self {
+        self.value = value;
+        self
+    }
+}
+
+impl Transform for RandomErasing {
+    fn apply(&self, mut image: Array3<f32>) -> Array3<f32> {
+        if !happens(self.p) {
+            return image;
+        }
+
+        let (_, height, width) = image.dim();
+        let area = (height * width) as f32;
+        // Rectangles that do not fit are discarded, giving up after a few attempts.
+        let region = with_rng(|rng| {
+            (0..10).find_map(|_| {
+                let target = area * rng.gen_range(self.scale.0..=self.scale.1);
+                let ratio = rng.gen_range(self.ratio.0.ln()..=self.ratio.1.ln()).exp();
+                let erased_height = (target / ratio).sqrt().round() as usize;
+                let erased_width = (target * ratio).sqrt().round() as usize;
+                if erased_height == 0
+                    || erased_width == 0
+                    || erased_height > height
+                    || erased_width > width
+                {
+                    return None;
+                }
+                let top = rng.gen_range(0..=height - erased_height);
+                let left = rng.gen_range(0..=width - erased_width);
+                Some((top, left, erased_height, erased_width))
+            })
+        });
+
+        if let Some((top, left, height, width)) = region {
+            image
+                .slice_mut(s![.., top..top + height, left..left + width])
+                .fill(self.value);
+        }
+        image
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Batch Mixing ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// A batch whose samples have been mixed by [`mixup`] or [`cutmix`].
+pub struct Mixed<D: Dimension + 'static> {
+    /// The mixed inputs.
+    pub inputs: Var<Input<D>>,
+    /// The mixed targets, of shape *(samples, classes)*.
+    pub targets: Var<Input<Ix2>>,
+    /// The weight of the original samples in the mix.
+    pub lambda: f32,
+    /// The index of the sample each sample has been mixed with.
+    pub permutation: Vec<usize>,
+}
+
+/// Returns the one-hot encoding of the class indices `labels`.
+///
+/// # Panics
+///
+/// If a label isn't a valid class index.
+///
+/// ```
+/// use ndarray::array;
+/// use neuronika::data::transforms::one_hot;
+///
+/// assert_eq!(one_hot(&array![2., 0.], 3), array![[0., 0., 1.], [1., 0., 0.]]);
+/// ```
+pub fn one_hot(labels: &Array1<f32>, classes: usize) -> Array2<f32> {
+    let mut encoded = Array2::zeros((labels.len(), classes));
+    for (mut row, &label) in encoded.outer_iter_mut().zip(labels) {
+        let class = label as usize;
+        assert!(
+            label >= 0. && label.fract() == 0. && class < classes,
+            "error: {} is not a valid class index.",
+            label
+        );
+        row[class] = 1.;
+    }
+    encoded
+}
+
+/// Draws the mixing weight from *Beta(alpha, alpha)* and a random pairing of the samples.
+fn mixing(alpha: f32, samples: usize) -> (f32, Vec<usize>) {
+    assert!(alpha > 0., "error: {} is not a valid alpha.", alpha);
+
+    let beta = Beta::new(alpha, alpha).unwrap();
+    with_rng(|rng| {
+        let mut permutation: Vec<usize> = (0..samples).collect();
+        permutation.shuffle(rng);
+        (beta.sample(rng), permutation)
+    })
+}
+
+fn check_batch(samples: usize, targets: usize) {
+    assert_eq!(
+        samples, targets,
+        "error: the inputs and the targets have a different number of samples."
+    );
+}
+
+/// Mixes the targets of each sample with the ones of its pair.
+fn mix_targets<T: ?Sized + Data<Dim = Ix2>>(
+    targets: &Var<T>,
+    lambda: f32,
+    permutation: &[usize],
+) -> Var<Input<Ix2>> {
+    let targets = targets.data();
+    let paired = targets.select(Axis(0), permutation);
+    crate::from_ndarray(&*targets * lambda + paired * (1. - lambda))
+}
+
+/// Applies **mixup** to a batch.
+///
+/// Each sample is replaced by the convex combination *λx + (1 - λ)x'* with another sample *x'* of
+/// the same batch, and its targets are mixed with the same weight, as described in
+/// [mixup: Beyond Empirical Risk Minimization](https://arxiv.org/abs/1710.09412). The weight *λ*
+/// is drawn from *Beta(alpha, alpha)* once per batch.
+///
+/// The mixed targets are probability distributions over the classes, so they can be given to
+/// [`kldiv_loss`](crate::nn::loss::kldiv_loss) together with the log-probabilities of the model,
+/// which has the same gradient as the cross entropy with soft targets. One-hot targets can be
+/// obtained from class indices with [`one_hot`].
+///
+/// # Arguments
+///
+/// * `inputs` - batch of shape *(samples, ...)*.
+///
+/// * `targets` - targets of shape *(samples, classes)*.
+///
+/// * `alpha` - concentration of the *Beta* distribution the weight is drawn from.
+///
+/// # Panics
+///
+/// If `alpha` isn't positive or if `inputs` and `targets` have a different number of samples.
+///
+/// ```
+/// use ndarray::array;
+/// use neuronika::data::transforms::{mixup, one_hot};
+///
+/// let inputs = neuronika::rand((4, 3));
+/// let targets = neuronika::from_ndarray(one_hot(&array![0., 1., 1., 0.], 2));
+///
+/// let mixed = mixup(&inputs, &targets, 0.4);
+/// assert_eq!(mixed.inputs.data().shape(), &[4, 3]);
+/// assert!((mixed.targets.data().sum() - 4.).abs() < 1e-5);
+/// ```
+pub fn mixup<T, U>(inputs: &Var<T>, targets: &Var<U>, alpha: f32) -> Mixed<T::Dim>
+where
+    T: ?Sized + Data,
+    T::Dim: RemoveAxis,
+    U: ?Sized + Data<Dim = Ix2>,
+{
+    let samples = inputs.data().len_of(Axis(0));
+    check_batch(samples, targets.data().len_of(Axis(0)));
+
+    let (lambda, permutation) = mixing(alpha, samples);
+    let mixed = {
+        let inputs = inputs.data();
+        let paired = inputs.select(Axis(0), &permutation);
+        &*inputs * lambda + paired * (1. - lambda)
+    };
+
+    Mixed {
+        inputs: crate::from_ndarray(mixed),
+        targets: mix_targets(targets, lambda, &permutation),
+        lambda,
+        permutation,
+    }
+}
+
+/// Applies **CutMix** to a batch of images.
+///
+/// A rectangle of each image is replaced with the same region of another image of the same batch,
+/// and the targets are mixed proportionally to the area of the rectangle, as described in
+/// [CutMix: Regularization Strategy to Train Strong Classifiers with Localizable Features](https://arxiv.org/abs/1905.04899).
+/// The rectangle covers a fraction *1 - λ* of the images, with *λ* drawn from
+/// *Beta(alpha, alpha)* once per batch, and it's clipped at the borders, in which case the
+/// reported *λ* is adjusted to the actual area.
+///
+/// See [`mixup`] for the usage of the mixed targets.
+///
+/// # Arguments
+///
+/// * `inputs` - images of shape *(samples, channels, height, width)*.
+///
+/// * `targets` - targets of shape *(samples, classes)*.
+///
+/// * `alpha` - concentration of the *Beta* distribution the weight is drawn from.
+///
+/// # Panics
+///
+/// If `alpha` isn't positive or if `inputs` and `targets` have a different number of samples.
+pub fn cutmix<T, U>(inputs: &Var<T>, targets: &Var<U>, alpha: f32) -> Mixed<Ix4>
+where
+    T: ?Sized + Data<Dim = Ix4>,
+    U: ?Sized + Data<Dim = Ix2>,
+{
+    let mut mixed = inputs.data().to_owned();
+    let (samples, _, height, width) = mixed.dim();
+    check_batch(samples, targets.data().len_of(Axis(0)));
+
+    let (lambda, permutation) = mixing(alpha, samples);
+    let cut = (1. - lambda).sqrt();
+    let (cut_height, cut_width) = (
+        (height as f32 * cut).round() as usize,
+        (width as f32 * cut).round() as usize,
+    );
+    let (center_y, center_x) = with_rng(|rng| {
+        (
+            rng.gen_range(0..height.max(1)),
+            rng.gen_range(0..width.max(1)),
+        )
+    });
+
+    let top = center_y.saturating_sub(cut_height / 2);
+    let bottom = (center_y + cut_height - cut_height / 2).min(height);
+    let left = center_x.saturating_sub(cut_width / 2);
+    let right = (center_x + cut_width - cut_width / 2).min(width);
+
+    let region = s![.., .., top..bottom, left..right];
+    let paired = inputs.data().select(Axis(0), &permutation);
+    mixed.slice_mut(region).assign(&paired.slice(region));
+
+    let lambda = 1. - ((bottom - top) * (right - left)) as f32 / (height * width).max(1) as f32;
+    Mixed {
+        inputs: crate::from_ndarray(mixed),
+        targets: mix_targets(targets, lambda, &permutation),
+        lambda,
+        permutation,
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,245 @@
+use super::{
+    cutmix, manual_seed, mixup, one_hot, CenterCrop, ColorJitter, Compose, Normalize, RandomCrop,
+    RandomErasing, RandomHorizontalFlip, Resize, Transform,
+};
+use ndarray::{array, Array3, Array4};
+
+#[test]
+fn resize() {
+    let image = array![[[0., 1.], [2., 3.]]];
+
+    let upsampled = Resize::new(4, 4).apply(image.clone());
+    assert_eq!(upsampled.shape(), &[1, 4, 4]);
+    assert_eq!(upsampled[[0, 0, 0]], 0.);
+    assert_eq!(upsampled[[0, 3, 3]], 3.);
+    assert!((upsampled[[0, 1, 1]] - 0.75).abs() < 1e-6);
+
+    let downsampled = Resize::new(1, 1).apply(image.clone());
+    assert_eq!(downsampled, array![[[1.5]]]);
+
+    assert_eq!(Resize::new(2, 2).apply(image.clone()), image);
+}
+
+#[test]
+fn center_crop() {
+    let image = Array3::from_shape_fn((2, 4, 5), |(c, h, w)| (c * 100 + h * 10 + w) as f32);
+    let cropped = CenterCrop::new(2, 3).apply(image);
+
+    assert_eq!(
+        cropped,
+        array![
+            [[11., 12., 13.], [21., 22., 23.]],
+            [[111., 112., 113.], [121., 122., 123.]]
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "error: cannot crop a 3x3 region from a 2x2 image.")]
+fn center_crop_too_large() {
+    CenterCrop::new(3, 3).apply(Array3::zeros((1, 2, 2)));
+}
+
+#[test]
+fn normalize() {
+    let image = array![[[1., 3.]], [[2., 2.]]];
+    let normalized = Normalize::new(&[1., 0.], &[2., 0.5]).apply(image);
+
+    assert_eq!(normalized, array![[[0., 1.]], [[4., 4.]]]);
+}
+
+#[test]
+#[should_panic(
+    expected = "error: cannot normalize an image with 3 channels using 1 channel statistics."
+)]
+fn normalize_channels_mismatch() {
+    Normalize::new(&[0.], &[1.]).apply(Array3::zeros((3, 1, 1)));
+}
+
+#[test]
+fn compose() {
+    let transform = Compose::new()
+        .then(|image: Array3<f32>| image + 1.)
+        .then(CenterCrop::new(1, 1));
+    assert_eq!(transform.len(), 2);
+
+    let image = transform.apply(Array3::zeros((1, 3, 3)));
+    assert_eq!(image, array![[[1.]]]);
+
+    assert!(Compose::new().is_empty());
+}
+
+#[test]
+fn apply_batch() {
+    let batch = Array4::from_shape_fn((2, 1, 4, 4), |(n, ..)| n as f32);
+    let transformed = CenterCrop::new(2, 2).apply_batch(batch);
+
+    assert_eq!(transformed.shape(), &[2, 1, 2, 2]);
+    assert_eq!(transformed.sum(), 4.);
+}
+
+#[test]
+fn random_crop() {
+    let image = Array3::from_shape_fn((1, 4, 4), |(_, h, w)| (h * 4 + w) as f32);
+
+    let cropped = RandomCrop::new(4, 4).apply(image.clone());
+    assert_eq!(cropped, image);
+
+    let padded = RandomCrop::new(6, 6).with_padding(1).apply(image.clone());
+    assert_eq!(padded.sum(), image.sum());
+    assert_eq!(padded[[0, 0, 0]], 0.);
+
+    manual_seed(0);
+    for _ in 0..10 {
+        let cropped = RandomCrop::new(2, 2).apply(image.clone());
+        let corner = cropped[[0, 0, 0]];
+        assert_eq!(
+            cropped,
+            array![[[corner, corner + 1.], [corner + 4., corner + 5.]]]
+        );
+    }
+}
+
+#[test]
+fn seeded() {
+    let image = Array3::from_shape_fn((3, 8, 8), |(c, h, w)| (c + h + w) as f32 / 17.);
+    let transform = Compose::new()
+        .then(RandomCrop::new(6, 6))
+        .then(RandomHorizontalFlip::default())
+        .then(ColorJitter::new().with_brightness(0.5))
+        .then(RandomErasing::new(0.5));
+
+    manual_seed(7);
+    let first = transform.apply(image.clone());
+    manual_seed(7);
+    assert_eq!(transform.apply(image), first);
+}
+
+#[test]
+fn horizontal_flip() {
+    let image = array![[[1., 2., 3.]]];
+
+    assert_eq!(
+        RandomHorizontalFlip::new(1.).apply(image.clone()),
+        array![[[3., 2., 1.]]]
+    );
+    assert_eq!(RandomHorizontalFlip::new(0.).apply(image.clone()), image);
+}
+
+#[test]
+#[should_panic(expected = "error: 2 is not a valid probability.")]
+fn invalid_probability() {
+    RandomHorizontalFlip::new(2.);
+}
+
+#[test]
+fn color_jitter() {
+    let image = Array3::from_shape_fn((3, 4, 4), |(c, h, w)| (c * 16 + h * 4 + w) as f32 / 48.);
+    assert_eq!(ColorJitter::new().apply(image.clone()), image);
+
+    for _ in 0..10 {
+        let jittered = ColorJitter::new()
+            .with_brightness(0.8)
+            .with_contrast(0.8)
+            .with_saturation(0.8)
+            .apply(image.clone());
+        assert_eq!(jittered.shape(), image.shape());
+        assert!(jittered.iter().all(|&value| (0. ..=1.).contains(&value)));
+    }
+
+    // A gray image is not affected by the saturation.
+    let gray = Array3::from_elem((3, 2, 2), 0.5);
+    let saturated = ColorJitter::new().with_saturation(0.9).apply(gray.clone());
+    assert!(saturated.iter().all(|&value| (value - 0.5).abs() < 1e-6));
+}
+
+#[test]
+fn random_erasing() {
+    let image = Array3::from_elem((2, 8, 8), 1.);
+    assert_eq!(RandomErasing::new(0.).apply(image.clone()), image);
+
+    let erased = RandomErasing::new(1.)
+        .with_scale(0.25, 0.25)
+        .with_ratio(1., 1.)
+        .with_value(-1.)
+        .apply(image);
+    // A 4x4 square is erased in both channels.
+    assert_eq!(erased.iter().filter(|&&value| value == -1.).count(), 32);
+}
+
+#[test]
+fn one_hot_encoding() {
+    assert_eq!(
+        one_hot(&array![1., 0., 1.], 2),
+        array![[0., 1.], [1., 0.], [0., 1.]]
+    );
+}
+
+#[test]
+#[should_panic(expected = "error: 3 is not a valid class index.")]
+fn one_hot_out_of_range() {
+    one_hot(&array![0., 3.], 3);
+}
+
+#[test]
+fn mixup_batch() {
+    let inputs = crate::from_ndarray(array![[0., 1.], [2., 3.], [4., 5.]]);
+    let targets = crate::from_ndarray(one_hot(&array![0., 1., 2.], 3));
+
+    manual_seed(3);
+    let mixed = mixup(&inputs, &targets, 0.5);
+    let (lambda, permutation) = (mixed.lambda, &mixed.permutation);
+    assert!((0. ..=1.).contains(&lambda));
+
+    let mut sorted = permutation.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, vec![0, 1, 2]);
+
+    for (sample, &pair) in permutation.iter().enumerate() {
+        let expected =
+            &inputs.data().row(sample) * lambda + &inputs.data().row(pair) * (1. - lambda);
+        assert_eq!(mixed.inputs.data().row(sample), expected);
+
+        let mut target = ndarray::Array1::zeros(3);
+        target[sample] += lambda;
+        target[pair] += 1. - lambda;
+        assert_eq!(mixed.targets.data().row(sample), target);
+    }
+
+    manual_seed(3);
+    assert_eq!(mixup(&inputs, &targets, 0.5).lambda, lambda);
+}
+
+#[test]
+fn cutmix_batch() {
+    let inputs = crate::from_ndarray(Array4::from_shape_fn((2, 1, 4, 4), |(n, ..)| n as f32));
+    let targets = crate::from_ndarray(one_hot(&array![0., 1.], 2));
+
+    manual_seed(11);
+    let mixed = cutmix(&inputs, &targets, 1.);
+    assert_eq!(mixed.permutation, vec![1, 0]);
+    assert!(mixed.lambda < 1.);
+    let data = mixed.inputs.data();
+
+    for (sample, &pair) in mixed.permutation.iter().enumerate() {
+        let image = data.index_axis(ndarray::Axis(0), sample);
+        let pasted = image.iter().filter(|el| **el != sample as f32).count();
+        if pair != sample {
+            assert_eq!(pasted as f32 / 16., 1. - mixed.lambda);
+            assert!(image.iter().all(|el| *el == 0. || *el == 1.));
+        }
+        assert!((mixed.targets.data().row(sample).sum() - 1.).abs() < 1e-6);
+    }
+}
+
+#[test]
+#[should_panic(expected = "error: 0 is not a valid alpha.")]
+fn mixup_invalid_alpha() {
+    mixup(&crate::zeros((2, 2)), &crate::zeros((2, 2)), 0.);
+}
+
+#[test]
+#[should_panic(expected = "error: the inputs and the targets have a different number of samples.")]
+fn mixup_wrong_targets() {
+    mixup(&crate::zeros((2, 2)), &crate::zeros((3, 2)), 1.);
+}
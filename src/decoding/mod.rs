@@ -0,0 +1,229 @@
+//! Decoding of token sequences.
+//!
+//! Sequence to sequence models generate their output one token at a time, each step being
+//! conditioned on the tokens generated so far. This module provides the search strategies that
+//! drive such a generation, while the model itself is supplied as a *step function*.
+//!
+//! A step function receives the prefixes of the hypotheses currently being expanded, all starting
+//! with the start token, and returns a tensor of shape *(hypotheses, vocabulary)* holding the
+//! **log-probabilities** of the next token of each of them. It is usually a closure running the
+//! forward pass of a model and returning its output data, such as a
+//! [`.log_softmax()`](crate::Var::log_softmax()).
+//!
+//! * [`greedy_search()`] - Picks the most likely token at each step.
+//!
+//! * [`beam_search()`] - Keeps the most likely hypotheses at each step, finding sequences that are
+//! more likely as a whole than the greedy one.
+//!
+//! ```
+//! use ndarray::{Array2, Axis};
+//! use neuronika::decoding;
+//!
+//! // A bigram model over the vocabulary {0: <s>, 1: </s>, 2: a, 3: b}.
+//! let bigrams = ndarray::array![
+//!     [0., 0., 0.6, 0.4],
+//!     [0., 1., 0., 0.],
+//!     [0., 0.4, 0.3, 0.3],
+//!     [0., 0.9, 0.05, 0.05],
+//! ]
+//! .mapv(f32::ln);
+//! let step = |prefixes: &[Vec<usize>]| {
+//!     let last: Vec<usize> = prefixes.iter().map(|prefix| prefix[prefix.len() - 1]).collect();
+//!     bigrams.select(Axis(0), &last)
+//! };
+//!
+//! let greedy = decoding::greedy_search(step, 0, 1, 10);
+//! assert_eq!(greedy.tokens, vec![2, 1]);
+//!
+//! let beams = decoding::beam_search(step, 0, 1, 2, 10, 0.);
+//! assert_eq!(beams[0].tokens, vec![3, 1]);
+//! assert!(beams[0].score > greedy.score);
+//! ```
+use ndarray::{Array2, Axis};
+use std::cmp::Ordering;
+
+/// A generated sequence along with its score.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hypothesis {
+    /// The generated tokens, excluding the start token. The last one is the end token, unless
+    /// the generation was stopped by the maximum length.
+    pub tokens: Vec<usize>,
+    /// The sum of the log-probabilities of the generated tokens.
+    pub score: f32,
+}
+
+impl Hypothesis {
+    /// Returns the score of `self` divided by its length raised to `length_penalty`.
+    ///
+    /// A penalty of *0* leaves the score unchanged, that favours short sequences, while a penalty
+    /// of *1* gives the average log-probability of the tokens.
+    pub fn normalized_score(&self, length_penalty: f32) -> f32 {
+        self.score / (self.tokens.len().max(1) as f32).powf(length_penalty)
+    }
+}
+
+/// Calls `step` on `prefixes` and checks the shape of the returned log-probabilities.
+fn log_probs<F>(step: &mut F, prefixes: &[Vec<usize>]) -> Array2<f32>
+where
+    F: FnMut(&[Vec<usize>]) -> Array2<f32>,
+{
+    let log_probs = step(prefixes);
+    assert_eq!(
+        log_probs.nrows(),
+        prefixes.len(),
+        "error: the step function returned {} rows of log-probabilities for {} hypotheses.",
+        log_probs.nrows(),
+        prefixes.len()
+    );
+
+    log_probs
+}
+
+/// Compares two scores so that the greatest comes first and NaNs come last.
+fn descending(lhs: f32, rhs: f32) -> Ordering {
+    rhs.partial_cmp(&lhs)
+        .unwrap_or_else(|| lhs.is_nan().cmp(&rhs.is_nan()))
+}
+
+/// Generates a sequence by picking the most likely token at each step.
+///
+/// # Arguments
+///
+/// * `step` - step function, see the [module-level documentation](self).
+///
+/// * `start` - token the generation starts from.
+///
+/// * `end` - token that ends the generation.
+///
+/// * `max_len` - maximum number of tokens to generate.
+///
+/// # Panics
+///
+/// If `step` does not return a single row of log-probabilities.
+pub fn greedy_search<F>(mut step: F, start: usize, end: usize, max_len: usize) -> Hypothesis
+where
+    F: FnMut(&[Vec<usize>]) -> Array2<f32>,
+{
+    let mut prefix = vec![vec![start]];
+    let mut score = 0.;
+    for _ in 0..max_len {
+        let log_probs = log_probs(&mut step, &prefix);
+        let (token, log_prob) = log_probs
+            .row(0)
+            .iter()
+            .copied()
+            .enumerate()
+            .min_by(|(_, lhs), (_, rhs)| descending(*lhs, *rhs))
+            .expect("error: the step function returned an empty vocabulary.");
+
+        prefix[0].push(token);
+        score += log_prob;
+        if token == end {
+            break;
+        }
+    }
+
+    let mut tokens = prefix.pop().unwrap();
+    tokens.remove(0);
+    Hypothesis { tokens, score }
+}
+
+/// Generates sequences by keeping the `beam_size` most likely hypotheses at each step.
+///
+/// At each step every hypothesis is extended with every token, and the `beam_size` most likely
+/// extensions are kept. Those ending with `end` are set aside as finished, so that the beam shrinks
+/// until all the hypotheses are finished or `max_len` tokens have been generated.
+///
+/// Returns at most `beam_size` hypotheses, sorted from the best to the worst according to their
+/// [normalized score](Hypothesis::normalized_score()).
+///
+/// # Arguments
+///
+/// * `step` - step function, see the [module-level documentation](self).
+///
+/// * `start` - token the generation starts from.
+///
+/// * `end` - token that ends the generation.
+///
+/// * `beam_size` - number of hypotheses kept at each step.
+///
+/// * `max_len` - maximum number of tokens to generate.
+///
+/// * `length_penalty` - exponent of the length the scores are divided by when ranking the
+/// finished hypotheses.
+///
+/// # Panics
+///
+/// If `beam_size` is zero or if `step` does not return a row of log-probabilities for each
+/// hypothesis.
+pub fn beam_search<F>(
+    mut step: F,
+    start: usize,
+    end: usize,
+    beam_size: usize,
+    max_len: usize,
+    length_penalty: f32,
+) -> Vec<Hypothesis>
+where
+    F: FnMut(&[Vec<usize>]) -> Array2<f32>,
+{
+    assert!(beam_size > 0, "error: the beam size must be positive.");
+
+    let (mut beams, mut scores) = (vec![vec![start]], vec![0.]);
+    let mut finished = Vec::new();
+    for _ in 0..max_len {
+        if beams.is_empty() {
+            break;
+        }
+
+        let log_probs = log_probs(&mut step, &beams);
+        let mut candidates: Vec<(f32, usize, usize)> = log_probs
+            .axis_iter(Axis(0))
+            .zip(&scores)
+            .enumerate()
+            .flat_map(|(beam, (log_probs, score))| {
+                log_probs
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(token, log_prob)| (score + log_prob, beam, token))
+            })
+            .collect();
+        candidates.sort_by(|lhs, rhs| descending(lhs.0, rhs.0));
+        candidates.truncate(beam_size);
+
+        let (mut next_beams, mut next_scores) = (Vec::new(), Vec::new());
+        for (score, beam, token) in candidates {
+            let mut tokens = beams[beam].clone();
+            tokens.push(token);
+            if token == end {
+                finished.push((tokens, score));
+            } else {
+                next_beams.push(tokens);
+                next_scores.push(score);
+            }
+        }
+        beams = next_beams;
+        scores = next_scores;
+    }
+    finished.extend(beams.into_iter().zip(scores));
+
+    let mut hypotheses: Vec<Hypothesis> = finished
+        .into_iter()
+        .map(|(mut tokens, score)| {
+            tokens.remove(0);
+            Hypothesis { tokens, score }
+        })
+        .collect();
+    hypotheses.sort_by(|lhs, rhs| {
+        descending(
+            lhs.normalized_score(length_penalty),
+            rhs.normalized_score(length_penalty),
+        )
+    });
+    hypotheses.truncate(beam_size);
+
+    hypotheses
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,117 @@
+use super::{beam_search, greedy_search, Hypothesis};
+use ndarray::{array, Array2, Axis};
+
+/// A bigram model over the vocabulary {0: <s>, 1: </s>, 2: a, 3: b}.
+fn bigrams() -> Array2<f32> {
+    array![
+        [0., 0., 0.6, 0.4],
+        [0., 1., 0., 0.],
+        [0., 0.4, 0.3, 0.3],
+        [0., 0.9, 0.05, 0.05],
+    ]
+    .mapv(f32::ln)
+}
+
+fn step(bigrams: &Array2<f32>) -> impl FnMut(&[Vec<usize>]) -> Array2<f32> + '_ {
+    move |prefixes| {
+        let last: Vec<usize> = prefixes
+            .iter()
+            .map(|prefix| prefix[prefix.len() - 1])
+            .collect();
+        bigrams.select(Axis(0), &last)
+    }
+}
+
+fn assert_hypothesis(hypothesis: &Hypothesis, tokens: &[usize], probability: f32) {
+    assert_eq!(hypothesis.tokens, tokens);
+    assert!((hypothesis.score - probability.ln()).abs() < 1e-5);
+}
+
+#[test]
+fn greedy() {
+    let bigrams = bigrams();
+
+    assert_hypothesis(&greedy_search(step(&bigrams), 0, 1, 10), &[2, 1], 0.24);
+}
+
+#[test]
+fn greedy_max_len() {
+    let bigrams = bigrams();
+
+    assert_hypothesis(&greedy_search(step(&bigrams), 0, 1, 1), &[2], 0.6);
+    assert_hypothesis(&greedy_search(step(&bigrams), 0, 1, 0), &[], 1.);
+}
+
+#[test]
+fn beam() {
+    let bigrams = bigrams();
+    let hypotheses = beam_search(step(&bigrams), 0, 1, 2, 10, 0.);
+
+    assert_eq!(hypotheses.len(), 2);
+    assert_hypothesis(&hypotheses[0], &[3, 1], 0.36);
+    assert_hypothesis(&hypotheses[1], &[2, 1], 0.24);
+}
+
+#[test]
+fn beam_of_one_is_greedy() {
+    let bigrams = bigrams();
+    let hypotheses = beam_search(step(&bigrams), 0, 1, 1, 10, 0.);
+
+    assert_eq!(hypotheses, vec![greedy_search(step(&bigrams), 0, 1, 10)]);
+}
+
+#[test]
+fn beam_max_len() {
+    let bigrams = bigrams();
+    let hypotheses = beam_search(step(&bigrams), 0, 1, 3, 2, 0.);
+
+    // The third best extension of the first step is not finished within two tokens.
+    assert_eq!(hypotheses.len(), 3);
+    assert_hypothesis(&hypotheses[0], &[3, 1], 0.36);
+    assert_hypothesis(&hypotheses[1], &[2, 1], 0.24);
+    assert_hypothesis(&hypotheses[2], &[2, 2], 0.18);
+}
+
+#[test]
+fn beam_length_penalty() {
+    // Ending right away is likely, but continuing leads to a very likely sequence.
+    let bigrams = array![
+        [0., 0.5, 0.5, 0.],
+        [0., 1., 0., 0.],
+        [0., 0., 0., 1.],
+        [0., 0.9, 0., 0.1],
+    ]
+    .mapv(f32::ln);
+
+    let hypotheses = beam_search(step(&bigrams), 0, 1, 2, 3, 0.);
+    assert_hypothesis(&hypotheses[0], &[1], 0.5);
+
+    let hypotheses = beam_search(step(&bigrams), 0, 1, 2, 3, 1.);
+    assert_hypothesis(&hypotheses[0], &[2, 3, 1], 0.45);
+}
+
+#[test]
+fn normalized_score() {
+    let hypothesis = Hypothesis {
+        tokens: vec![2, 3, 1],
+        score: -3.,
+    };
+
+    assert_eq!(hypothesis.normalized_score(0.), -3.);
+    assert_eq!(hypothesis.normalized_score(1.), -1.);
+}
+
+#[test]
+#[should_panic(
+    expected = "error: the step function returned 2 rows of log-probabilities for 1 \
+                           hypotheses."
+)]
+fn wrong_step() {
+    greedy_search(|_| Array2::zeros((2, 4)), 0, 1, 10);
+}
+
+#[test]
+#[should_panic(expected = "error: the beam size must be positive.")]
+fn empty_beam() {
+    beam_search(|_| Array2::zeros((1, 4)), 0, 1, 0, 10, 0.);
+}
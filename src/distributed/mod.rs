@@ -0,0 +1,533 @@
+//! Distributed data-parallel training.
+//!
+//! In data-parallel training every process holds a replica of the model and computes the
+//! gradients of a different shard of each batch. The gradients are then averaged across the
+//! processes, so that the replicas take the same optimization step and stay identical.
+//!
+//! * [`Communicator`] - collective operations among a group of processes. [`TcpRing`]
+//!   implements them over TCP with the ring algorithm, which sends about twice the size of the
+//!   reduced buffer per process regardless of the number of processes. Other transports, such as
+//!   MPI, can be plugged in by implementing the trait.
+//!
+//! * [`DistributedDataParallel`] - synchronizes the parameters of the replicas when created, and
+//!   averages their gradients at each [`.step()`](DistributedDataParallel::step()), which is
+//!   meant to be called between the backward pass and the optimizer step.
+//!
+//! * [`ParameterSet`] - a flattened snapshot of the parameters of a model. Snapshots collected
+//!   from several workers can be combined with
+//!   [`ParameterSet::weighted_average()`], which is the aggregation step of federated averaging
+//!   and of population-based training, and loaded back into a model.
+//!
+//! The parameters must be listed in the same order on every process. The ones returned by
+//! [`Module::parameters()`] follow the registration order of the layers, and the ones of a
+//! variable the order in which its computation reaches them.
+//!
+//! ```no_run
+//! use neuronika::distributed::{DistributedDataParallel, TcpRing};
+//! use neuronika::nn::{Linear, ModelStatus};
+//! use neuronika::optim::{L2, SGD};
+//!
+//! let rank = std::env::var("RANK").unwrap().parse().unwrap();
+//! let group = TcpRing::new(rank, &["10.0.0.1:29500", "10.0.0.2:29500"]).unwrap();
+//!
+//! let mut status = ModelStatus::default();
+//! let lin = status.register(Linear::new(3, 1));
+//!
+//! let ddp = DistributedDataParallel::new(status.parameters(), group).unwrap();
+//! let optim = SGD::new(status.parameters(), 0.01, L2::new(0.));
+//!
+//! let loss = lin.forward(neuronika::rand((8, 3))).sum();
+//! loss.forward();
+//! loss.backward(1.);
+//! ddp.step().unwrap();
+//! optim.step();
+//! ```
+//!
+//! [`Module::parameters()`]: crate::nn::Module::parameters()
+use crate::{
+    nn::{params_to_vec, vec_to_params},
+    Param,
+};
+use ndarray::ArrayViewMutD;
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::RefCell,
+    io::{self, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Time within which the processes of a [`TcpRing`] must have started listening.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Collective operations among a group of processes.
+///
+/// Every process of the group must call the same collective operations, in the same order and
+/// with buffers of the same length.
+pub trait Communicator {
+    /// Returns the rank of this process, between 0 and the number of processes excluded.
+    fn rank(&self) -> usize;
+
+    /// Returns the number of processes of the group.
+    fn world_size(&self) -> usize;
+
+    /// Replaces `buffer` with the element-wise sum of the buffers of all the processes.
+    ///
+    /// # Errors
+    ///
+    /// If the communication with another process fails.
+    fn all_reduce(&mut self, buffer: &mut [f32]) -> io::Result<()>;
+
+    /// Replaces `buffer` with the buffer of the process of rank `root`.
+    ///
+    /// # Errors
+    ///
+    /// If the communication with another process fails.
+    fn broadcast(&mut self, buffer: &mut [f32], root: usize) -> io::Result<()>;
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ TcpRing ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// A group of processes arranged in a ring, each one connected over TCP to the next.
+pub struct TcpRing {
+    rank: usize,
+    world_size: usize,
+    links: Option<(TcpStream, TcpStream)>,
+}
+
+impl TcpRing {
+    /// Joins the group of processes listening at `addresses`, as the process of rank `rank`.
+    ///
+    /// The process listens at `addresses[rank]`, connects to the process of the next rank and
+    /// accepts the connection of the process of the previous one, so that this blocks until all
+    /// the processes have joined.
+    ///
+    /// # Arguments
+    ///
+    /// * `rank` - rank of this process.
+    ///
+    /// * `addresses` - addresses of all the processes, ordered by rank.
+    ///
+    /// # Errors
+    ///
+    /// If the address of this process cannot be bound, or if the next process doesn't start
+    /// listening within a minute.
+    ///
+    /// # Panics
+    ///
+    /// If `rank` is out of range for `addresses`.
+    pub fn new<A: ToSocketAddrs>(rank: usize, addresses: &[A]) -> io::Result<Self> {
+        let world_size = addresses.len();
+        assert!(
+            rank < world_size,
+            "error: rank {} is out of range for {} processes.",
+            rank,
+            world_size
+        );
+
+        if world_size == 1 {
+            return Ok(Self {
+                rank,
+                world_size,
+                links: None,
+            });
+        }
+
+        let listener = TcpListener::bind(&addresses[rank])?;
+        let next = connect(&addresses[(rank + 1) % world_size])?;
+        let (previous, _) = listener.accept()?;
+        next.set_nodelay(true)?;
+        previous.set_nodelay(true)?;
+
+        Ok(Self {
+            rank,
+            world_size,
+            links: Some((next, previous)),
+        })
+    }
+
+    /// Sends `outgoing` to the next process while receiving `incoming` from the previous one.
+    ///
+    /// Sending runs on a separate thread, as every process of the ring sends before receiving.
+    fn exchange(&mut self, outgoing: &[f32], incoming: &mut [f32]) -> io::Result<()> {
+        let (next, previous) = match self.links.as_mut() {
+            Some(links) => links,
+            None => return Ok(()),
+        };
+
+        let bytes: Vec<u8> = outgoing.iter().flat_map(|el| el.to_le_bytes()).collect();
+        let mut received = vec![0; incoming.len() * 4];
+        thread::scope(|scope| {
+            let sender = scope.spawn(move || next.write_all(&bytes));
+            previous.read_exact(&mut received)?;
+            sender.join().expect("error: the sending thread panicked.")
+        })?;
+
+        for (el, bytes) in incoming.iter_mut().zip(received.chunks_exact(4)) {
+            *el = f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+        Ok(())
+    }
+}
+
+/// Connects to `address`, retrying until the process listening there has started.
+fn connect<A: ToSocketAddrs>(address: A) -> io::Result<TcpStream> {
+    let start = Instant::now();
+    loop {
+        match TcpStream::connect(&address) {
+            Ok(stream) => return Ok(stream),
+            Err(error) if start.elapsed() > CONNECT_TIMEOUT => return Err(error),
+            Err(_) => thread::sleep(Duration::from_millis(50)),
+        }
+    }
+}
+
+/// Returns the range of the `chunk`-th of the `chunks` chunks a buffer of length `len` is split
+/// into by the ring algorithm.
+fn chunk_range(len: usize, chunks: usize, chunk: usize) -> std::ops::Range<usize> {
+    chunk * len / chunks..(chunk + 1) * len / chunks
+}
+
+impl Communicator for TcpRing {
+    fn rank(&self) -> usize {
+        self.rank
+    }
+
+    fn world_size(&self) -> usize {
+        self.world_size
+    }
+
+    fn all_reduce(&mut self, buffer: &mut [f32]) -> io::Result<()> {
+        let (rank, size, len) = (self.rank, self.world_size, buffer.len());
+
+        // Reduce-scatter: after it, each process holds the sum of one of the chunks.
+        let mut incoming = Vec::new();
+        for step in 0..size - 1 {
+            let send = chunk_range(len, size, (rank + size - step) % size);
+            let receive = chunk_range(len, size, (rank + 2 * size - step - 1) % size);
+            incoming.resize(receive.len(), 0.);
+            self.exchange(&buffer[send], &mut incoming)?;
+            buffer[receive]
+                .iter_mut()
+                .zip(&incoming)
+                .for_each(|(el, incoming_el)| *el += incoming_el);
+        }
+
+        // All-gather: the summed chunks go around the ring.
+        for step in 0..size - 1 {
+            let send = chunk_range(len, size, (rank + 1 + size - step) % size);
+            let receive = chunk_range(len, size, (rank + size - step) % size);
+            incoming.resize(receive.len(), 0.);
+            self.exchange(&buffer[send], &mut incoming)?;
+            buffer[receive].copy_from_slice(&incoming);
+        }
+
+        Ok(())
+    }
+
+    fn broadcast(&mut self, buffer: &mut [f32], root: usize) -> io::Result<()> {
+        assert!(
+            root < self.world_size,
+            "error: root {} is out of range for {} processes.",
+            root,
+            self.world_size
+        );
+
+        let (next, previous) = match self.links.as_mut() {
+            Some(links) => links,
+            None => return Ok(()),
+        };
+
+        // The buffer travels along the ring, the process preceding the root doesn't forward it.
+        if self.rank != root {
+            let mut received = vec![0; buffer.len() * 4];
+            previous.read_exact(&mut received)?;
+            for (el, bytes) in buffer.iter_mut().zip(received.chunks_exact(4)) {
+                *el = f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+            }
+        }
+        if (self.rank + 1) % self.world_size != root {
+            let bytes: Vec<u8> = buffer.iter().flat_map(|el| el.to_le_bytes()).collect();
+            next.write_all(&bytes)?;
+        }
+
+        Ok(())
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ DistributedDataParallel ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Keeps the replicas of a model in sync across a group of processes.
+///
+/// The gradients of all the parameters are packed into a single buffer, so that each step
+/// performs one all-reduce.
+pub struct DistributedDataParallel<'a, C: Communicator> {
+    grads: RefCell<Vec<ArrayViewMutD<'a, f32>>>,
+    communicator: RefCell<C>,
+}
+
+impl<'a, C: Communicator> DistributedDataParallel<'a, C> {
+    /// Creates a new data-parallel synchronizer, replacing the parameters of every process with
+    /// the ones of the process of rank 0.
+    ///
+    /// The shapes of the parameters are exchanged first, so that replicas whose parameters are
+    /// listed in different orders or have different shapes are detected before any value is
+    /// overwritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `parameters` - vector of [`Param`] of the replica, in the same order on every process,
+    ///   such as the ones returned by [`Module::parameters()`](crate::nn::Module::parameters()).
+    ///
+    /// * `communicator` - group of processes holding the replicas.
+    ///
+    /// # Errors
+    ///
+    /// If the communication with another process fails, or if the shapes of the parameters
+    /// differ from the ones of the process of rank 0 on any of the processes.
+    pub fn new(parameters: Vec<Param<'a>>, mut communicator: C) -> io::Result<Self> {
+        let (mut data, grads): (Vec<_>, Vec<_>) = parameters
+            .into_iter()
+            .map(|param| (param.data, param.grad))
+            .unzip();
+
+        let shapes: Vec<&[usize]> = data.iter().map(|data| data.shape()).collect();
+        check_shapes(&shapes, &mut communicator)?;
+
+        let mut buffer: Vec<f32> = data.iter().flat_map(|data| data.iter().copied()).collect();
+        communicator.broadcast(&mut buffer, 0)?;
+        let mut values = buffer.into_iter();
+        data.iter_mut()
+            .flat_map(|data| data.iter_mut())
+            .zip(&mut values)
+            .for_each(|(el, value)| *el = value);
+
+        Ok(Self {
+            grads: RefCell::new(grads),
+            communicator: RefCell::new(communicator),
+        })
+    }
+
+    /// Returns the rank of this process.
+    pub fn rank(&self) -> usize {
+        self.communicator.borrow().rank()
+    }
+
+    /// Returns the number of processes.
+    pub fn world_size(&self) -> usize {
+        self.communicator.borrow().world_size()
+    }
+
+    /// Replaces the gradients of the parameters with their average across the processes.
+    ///
+    /// # Errors
+    ///
+    /// If the communication with another process fails, in which case the gradients are left
+    /// untouched.
+    pub fn step(&self) -> io::Result<()> {
+        let mut grads = self.grads.borrow_mut();
+        let mut communicator = self.communicator.borrow_mut();
+
+        let mut buffer: Vec<f32> = grads.iter().flat_map(|grad| grad.iter().copied()).collect();
+        communicator.all_reduce(&mut buffer)?;
+
+        let world_size = communicator.world_size() as f32;
+        grads
+            .iter_mut()
+            .flat_map(|grad| grad.iter_mut())
+            .zip(buffer)
+            .for_each(|(el, sum)| *el = sum / world_size);
+        Ok(())
+    }
+}
+
+/// Encodes `shapes` as a buffer of floats. Each value is split in two halves of 24 bits, which
+/// single precision floats represent exactly.
+fn encode_shapes(shapes: &[&[usize]]) -> Vec<f32> {
+    std::iter::once(shapes.len())
+        .chain(
+            shapes
+                .iter()
+                .flat_map(|shape| std::iter::once(shape.len()).chain(shape.iter().copied())),
+        )
+        .flat_map(|value| [(value >> 24) as f32, (value & 0xff_ffff) as f32])
+        .collect()
+}
+
+/// Checks that the parameters described by `shapes` have the same shapes on all the processes
+/// of the group, failing on every process if they differ on any of them.
+fn check_shapes<C: Communicator>(shapes: &[&[usize]], communicator: &mut C) -> io::Result<()> {
+    let local = encode_shapes(shapes);
+
+    // The length of the description of rank 0 is sent first, so that its description can be
+    // broadcast into a buffer of the right size.
+    let mut len = [local.len() as f32];
+    communicator.broadcast(&mut len, 0)?;
+    let mut root = local.clone();
+    root.resize(len[0] as usize, 0.);
+    communicator.broadcast(&mut root, 0)?;
+
+    let mut mismatches = [(root != local) as u8 as f32];
+    communicator.all_reduce(&mut mismatches)?;
+    if mismatches[0] > 0. {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "the parameters of {} processes have shapes different from the ones of rank 0, \
+                 the ones of rank {} have shapes {:?}",
+                mismatches[0],
+                communicator.rank(),
+                shapes
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ ParameterSet ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// A snapshot of the parameters of a model, flattened into a single vector.
+///
+/// The values of the parameters are concatenated in the order of the [`Param`]s they are taken
+/// from, and their shapes are kept to check that the snapshot is loaded into a model with the
+/// same structure. A [`Param`] carries no identity, so snapshots are matched by position: they
+/// must be taken from and loaded into parameters listed in the same order, such as the ones
+/// returned by [`Module::parameters()`], which follow the registration order of the layers, or
+/// by [`VarDiff::parameters()`](crate::VarDiff::parameters()), which follow the order in which
+/// the computation of the variable reaches them.
+///
+/// ```
+/// use neuronika::distributed::ParameterSet;
+/// use neuronika::nn::{Linear, ModelStatus};
+///
+/// let mut status = ModelStatus::default();
+/// let lin = status.register(Linear::new(2, 1));
+///
+/// // Two workers trained on 10 and 30 samples.
+/// lin.weight.data_mut().fill(1.);
+/// let first = ParameterSet::from_params(&status.parameters());
+/// lin.weight.data_mut().fill(5.);
+/// let second = ParameterSet::from_params(&status.parameters());
+///
+/// let average = ParameterSet::weighted_average(&[first, second], &[10., 30.]);
+/// average.load(&mut status.parameters());
+/// assert_eq!(*lin.weight.data(), ndarray::Array::from_elem((1, 2), 4.));
+/// ```
+///
+/// [`Module::parameters()`]: crate::nn::Module::parameters()
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParameterSet {
+    values: Vec<f32>,
+    shapes: Vec<Vec<usize>>,
+}
+
+impl ParameterSet {
+    /// Takes a snapshot of the data of `params`, in their order.
+    pub fn from_params(params: &[Param]) -> Self {
+        Self {
+            values: params_to_vec(params),
+            shapes: params
+                .iter()
+                .map(|param| param.data.shape().to_vec())
+                .collect(),
+        }
+    }
+
+    /// Returns the values of the parameters, concatenated.
+    pub fn values(&self) -> &[f32] {
+        &self.values
+    }
+
+    /// Returns the shapes of the parameters.
+    pub fn shapes(&self) -> &[Vec<usize>] {
+        &self.shapes
+    }
+
+    /// Returns the total number of values.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the snapshot holds no value.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Copies the snapshot into the data of `params`, which must be listed in the order the
+    /// snapshot was taken in.
+    ///
+    /// # Panics
+    ///
+    /// If the number or the shapes of `params` differ from the ones of the snapshot.
+    pub fn load(&self, params: &mut [Param]) {
+        let shapes: Vec<&[usize]> = params.iter().map(|param| param.data.shape()).collect();
+        assert!(
+            shapes.iter().eq(self.shapes.iter()),
+            "error: cannot load parameters of shapes {:?} into parameters of shapes {:?}.",
+            self.shapes,
+            shapes
+        );
+
+        vec_to_params(params, &self.values);
+    }
+
+    /// Returns the average of `sets`, each one weighted by the corresponding element of
+    /// `weights`.
+    ///
+    /// The weights are normalized to sum to one. In federated averaging they are the numbers of
+    /// samples each worker was trained on.
+    ///
+    /// # Panics
+    ///
+    /// If `sets` is empty, if the sets describe parameters of different shapes, if `weights`
+    /// doesn't hold one weight per set or if the weights are negative or sum to zero.
+    pub fn weighted_average(sets: &[ParameterSet], weights: &[f32]) -> Self {
+        assert!(
+            !sets.is_empty(),
+            "error: cannot average an empty collection of parameters."
+        );
+        assert_eq!(
+            sets.len(),
+            weights.len(),
+            "error: {} weights were given for {} parameter sets.",
+            weights.len(),
+            sets.len()
+        );
+        assert!(
+            sets.iter().all(|set| set.shapes == sets[0].shapes),
+            "error: cannot average parameter sets of different shapes."
+        );
+        let total: f32 = weights.iter().sum();
+        assert!(
+            weights.iter().all(|&weight| weight >= 0.) && total > 0.,
+            "error: the weights must be non-negative and not all zero, got {:?}.",
+            weights
+        );
+
+        let mut values = vec![0.; sets[0].len()];
+        for (set, weight) in sets.iter().zip(weights) {
+            values
+                .iter_mut()
+                .zip(&set.values)
+                .for_each(|(el, value)| *el += value * weight / total);
+        }
+
+        Self {
+            values,
+            shapes: sets[0].shapes.clone(),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
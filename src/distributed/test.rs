@@ -0,0 +1,193 @@
+use super::{chunk_range, Communicator, DistributedDataParallel, ParameterSet, TcpRing};
+use std::{net::TcpListener, thread};
+
+/// Returns `size` addresses on the loopback interface that are free at the time of the call.
+fn addresses(size: usize) -> Vec<String> {
+    let listeners: Vec<TcpListener> = (0..size)
+        .map(|_| TcpListener::bind("127.0.0.1:0").unwrap())
+        .collect();
+    listeners
+        .iter()
+        .map(|listener| listener.local_addr().unwrap().to_string())
+        .collect()
+}
+
+/// Runs `f` on a ring of `size` processes, each one on its own thread, and collects the results
+/// by rank.
+fn run<T, F>(size: usize, f: F) -> Vec<T>
+where
+    T: Send + 'static,
+    F: Fn(TcpRing) -> T + Send + Copy + 'static,
+{
+    let addresses = addresses(size);
+    let workers: Vec<_> = (0..size)
+        .map(|rank| {
+            let addresses = addresses.clone();
+            thread::spawn(move || f(TcpRing::new(rank, &addresses).unwrap()))
+        })
+        .collect();
+
+    workers
+        .into_iter()
+        .map(|worker| worker.join().unwrap())
+        .collect()
+}
+
+#[test]
+fn chunks() {
+    let ranges: Vec<_> = (0..3).map(|chunk| chunk_range(7, 3, chunk)).collect();
+
+    assert_eq!(ranges, vec![0..2, 2..4, 4..7]);
+}
+
+#[test]
+fn single_process() {
+    let mut group = TcpRing::new(0, &["127.0.0.1:0"]).unwrap();
+    let mut buffer = vec![1., 2.];
+
+    group.all_reduce(&mut buffer).unwrap();
+    group.broadcast(&mut buffer, 0).unwrap();
+    assert_eq!(buffer, vec![1., 2.]);
+    assert_eq!((group.rank(), group.world_size()), (0, 1));
+}
+
+#[test]
+#[should_panic(expected = "error: rank 2 is out of range for 2 processes.")]
+fn rank_out_of_range() {
+    let _ = TcpRing::new(2, &["127.0.0.1:0", "127.0.0.1:0"]);
+}
+
+#[test]
+fn all_reduce() {
+    let buffers = run(3, |mut group| {
+        let rank = group.rank() as f32;
+        let mut buffer: Vec<f32> = (0..7).map(|el| el as f32 * (rank + 1.)).collect();
+        group.all_reduce(&mut buffer).unwrap();
+        buffer
+    });
+
+    let expected: Vec<f32> = (0..7).map(|el| el as f32 * 6.).collect();
+    for buffer in buffers {
+        assert_eq!(buffer, expected);
+    }
+}
+
+#[test]
+fn all_reduce_short() {
+    // Some of the chunks are empty when the buffer is shorter than the ring.
+    let buffers = run(4, |mut group| {
+        let mut buffer = vec![group.rank() as f32; 2];
+        group.all_reduce(&mut buffer).unwrap();
+        buffer
+    });
+
+    for buffer in buffers {
+        assert_eq!(buffer, vec![6., 6.]);
+    }
+}
+
+#[test]
+fn broadcast() {
+    let buffers = run(3, |mut group| {
+        let mut buffer = vec![group.rank() as f32; 3];
+        group.broadcast(&mut buffer, 1).unwrap();
+        buffer
+    });
+
+    for buffer in buffers {
+        assert_eq!(buffer, vec![1.; 3]);
+    }
+}
+
+#[test]
+fn data_parallel() {
+    let results = run(2, |group| {
+        let rank = group.rank() as f32;
+        let w = crate::full(2, rank + 1.).requires_grad();
+        let loss = (w.clone() * crate::full(2, rank + 1.)).sum();
+
+        let ddp = DistributedDataParallel::new(loss.parameters(), group).unwrap();
+        assert_eq!(ddp.world_size(), 2);
+
+        loss.forward();
+        loss.backward(1.);
+        ddp.step().unwrap();
+
+        let (data, grad) = (w.data().to_vec(), w.grad().to_vec());
+        (data, grad)
+    });
+
+    for (data, grad) in results {
+        // The parameters of rank 0 are broadcast, and the gradients 1 and 2 are averaged.
+        assert_eq!(data, vec![1., 1.]);
+        assert_eq!(grad, vec![1.5, 1.5]);
+    }
+}
+
+#[test]
+fn data_parallel_shape_mismatch() {
+    let results = run(3, |group| {
+        let rank = group.rank();
+        let w = crate::full(if rank == 1 { 3 } else { 2 }, rank as f32).requires_grad();
+
+        let error = DistributedDataParallel::new(w.parameters(), group)
+            .err()
+            .map(|error| error.kind());
+        let data = w.data().to_vec();
+        (error, data)
+    });
+
+    // Every process fails and keeps its own parameters.
+    for (rank, (error, data)) in results.into_iter().enumerate() {
+        assert_eq!(error, Some(std::io::ErrorKind::InvalidInput));
+        assert!(data.iter().all(|&el| el == rank as f32));
+    }
+}
+
+#[test]
+fn parameter_set() {
+    let mut status = crate::nn::ModelStatus::default();
+    let lin = status.register(crate::nn::Linear::new(2, 1));
+    lin.weight.data_mut().assign(&ndarray::array![[1., 2.]]);
+    lin.bias.data_mut().assign(&ndarray::array![3.]);
+
+    // The parameters of a model status follow the registration order.
+    let set = ParameterSet::from_params(&status.parameters());
+    assert_eq!(set.values(), &[1., 2., 3.]);
+    assert_eq!(set.shapes(), &[vec![1, 2], vec![1]]);
+
+    lin.weight.data_mut().fill(0.);
+    lin.bias.data_mut().fill(0.);
+    set.load(&mut status.parameters());
+    assert_eq!(*lin.weight.data(), ndarray::array![[1., 2.]]);
+    assert_eq!(*lin.bias.data(), ndarray::array![3.]);
+}
+
+#[test]
+#[should_panic(
+    expected = "error: cannot load parameters of shapes [[2]] into parameters of shapes [[3]]."
+)]
+fn parameter_set_load_fail() {
+    let set = ParameterSet::from_params(&crate::ones(2).requires_grad().parameters());
+
+    set.load(&mut crate::ones(3).requires_grad().parameters());
+}
+
+#[test]
+fn weighted_average() {
+    let first = ParameterSet::from_params(&crate::full(3, 1.).requires_grad().parameters());
+    let second = ParameterSet::from_params(&crate::full(3, 4.).requires_grad().parameters());
+
+    let average = ParameterSet::weighted_average(&[first.clone(), second], &[2., 1.]);
+    assert_eq!(average.values(), &[2., 2., 2.]);
+    assert_eq!(average.shapes(), first.shapes());
+}
+
+#[test]
+#[should_panic(expected = "error: cannot average parameter sets of different shapes.")]
+fn weighted_average_fail() {
+    let first = ParameterSet::from_params(&crate::ones(3).requires_grad().parameters());
+    let second = ParameterSet::from_params(&crate::ones((3, 1)).requires_grad().parameters());
+
+    ParameterSet::weighted_average(&[first, second], &[1., 1.]);
+}
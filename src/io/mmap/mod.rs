@@ -0,0 +1,142 @@
+use super::{data_size, invalid_data, read_npy_header, DType};
+use ndarray::{ArrayViewD, IxDyn};
+use std::{fs::File, io, os::unix::io::AsRawFd, path::Path, ptr, slice};
+
+/// A tensor stored in a `.npy` file and mapped into memory.
+///
+/// The file is not read when it is opened: its pages are loaded by the operating system the
+/// first time they are accessed, and can be evicted when memory is scarce, so that tensors larger
+/// than the available RAM, such as huge embedding tables or datasets, can be used. Only the
+/// elements actually read, for instance the rows selected by a batch of token ids, are ever
+/// loaded.
+///
+/// The elements are accessed through a read-only [`.view()`](MappedNpy::view()). As variables
+/// own their data, a mapped tensor cannot back a parameter directly: the slices needed by each
+/// step are fed to the graph instead, which copies only them. An embedding table can be looked
+/// up within the graph with
+/// [`IndexVar::mapped_embedding()`](crate::IndexVar::mapped_embedding()), which reads the rows
+/// selected by the indices at each forward pass.
+///
+/// The file must hold little-endian `f32` elements in C order, as the ones written by
+/// [`write_npy`](super::write_npy) and by NumPy for `float32` arrays, and must not be modified
+/// while it is mapped. Memory mapping is available on Unix platforms only.
+///
+/// ```
+/// use ndarray::{array, Axis};
+/// use neuronika::io::{write_npy, MappedNpy};
+///
+/// let path = std::env::temp_dir().join("neuronika-mapped-table.npy");
+/// let table = array![[0., 1.], [2., 3.], [4., 5.]];
+/// write_npy(std::fs::File::create(&path).unwrap(), &table).unwrap();
+///
+/// let mapped = MappedNpy::open(&path).unwrap();
+/// assert_eq!(mapped.shape(), &[3, 2]);
+///
+/// // Looks up rows 2 and 0, loading only the pages that contain them.
+/// let rows = neuronika::from_ndarray(mapped.view().select(Axis(0), &[2, 0]));
+/// assert_eq!(*rows.data(), array![[4., 5.], [0., 1.]].into_dyn());
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct MappedNpy {
+    address: *mut libc::c_void,
+    len: usize,
+    offset: usize,
+    shape: Vec<usize>,
+}
+
+impl MappedNpy {
+    /// Maps the `.npy` file at `path` into memory.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be read or mapped, or if it is not a valid `.npy` file holding
+    /// little-endian `f32` elements in C order.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut file = File::open(path)?;
+        let (dtype, big_endian, fortran_order, shape, offset) = read_npy_header(&mut file)?;
+        if dtype != DType::Float(4) || big_endian || fortran_order {
+            return Err(invalid_data(
+                "only little-endian f32 .npy files in C order can be mapped".to_string(),
+            ));
+        }
+
+        let data_len = data_size(dtype, &shape)?;
+        let len = file.metadata()?.len() as usize;
+        if len - offset < data_len {
+            return Err(invalid_data("truncated .npy file".to_string()));
+        }
+
+        // Safe because the mapping is private and read-only, and it's checked for failure. The
+        // file is never empty, as it holds at least the header.
+        let address = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_PRIVATE,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if address == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mapped = Self {
+            address,
+            len,
+            offset,
+            shape,
+        };
+        if !mapped.data_address().is_aligned() {
+            return Err(invalid_data(
+                "the data of the .npy file is not aligned".to_string(),
+            ));
+        }
+
+        Ok(mapped)
+    }
+
+    /// Returns the shape of the tensor.
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// Returns a read-only view of the tensor.
+    pub fn view(&self) -> ArrayViewD<'_, f32> {
+        let len = self.shape.iter().product::<usize>();
+        let data = if len == 0 {
+            &[]
+        } else {
+            // Safe because the mapping is valid for as long as `self` is, the elements were
+            // checked to fit in it and to be aligned, and every bit pattern is a valid `f32`.
+            unsafe { slice::from_raw_parts(self.data_address(), len) }
+        };
+
+        ArrayViewD::from_shape(IxDyn(&self.shape), data).unwrap()
+    }
+
+    fn data_address(&self) -> *const f32 {
+        // Safe because the offset was checked to be within the mapping.
+        unsafe { (self.address as *const u8).add(self.offset) as *const f32 }
+    }
+}
+
+impl Drop for MappedNpy {
+    fn drop(&mut self) {
+        // Safe because the mapping was created in `open` and it's unmapped only once.
+        unsafe {
+            libc::munmap(self.address, self.len);
+        }
+    }
+}
+
+// Safe because the mapping is read-only.
+unsafe impl Send for MappedNpy {}
+unsafe impl Sync for MappedNpy {}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,74 @@
+use super::MappedNpy;
+use crate::io::write_npy;
+use ndarray::{array, Array, Axis};
+use std::{fs::File, path::PathBuf};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "neuronika-mmap-{}-{}.npy",
+        name,
+        std::process::id()
+    ))
+}
+
+#[test]
+fn view() {
+    let path = temp_path("view");
+    let tensor = Array::from_shape_fn((4, 3, 2), |(i, j, k)| (i * 6 + j * 2 + k) as f32);
+    write_npy(File::create(&path).unwrap(), &tensor).unwrap();
+
+    let mapped = MappedNpy::open(&path).unwrap();
+    assert_eq!(mapped.shape(), &[4, 3, 2]);
+    assert_eq!(mapped.view(), tensor.view().into_dyn());
+    assert_eq!(
+        mapped.view().select(Axis(0), &[3, 1]),
+        tensor.select(Axis(0), &[3, 1]).into_dyn()
+    );
+
+    drop(mapped);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn empty() {
+    let path = temp_path("empty");
+    write_npy(
+        File::create(&path).unwrap(),
+        &Array::<f32, _>::zeros((0, 3)),
+    )
+    .unwrap();
+
+    let mapped = MappedNpy::open(&path).unwrap();
+    assert_eq!(mapped.view().shape(), &[0, 3]);
+
+    drop(mapped);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn unsupported() {
+    let path = temp_path("unsupported");
+    let header = "{'descr': '<f8', 'fortran_order': False, 'shape': (1,), }";
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend_from_slice(&1f64.to_le_bytes());
+    std::fs::write(&path, bytes).unwrap();
+
+    assert!(MappedNpy::open(&path).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn truncated() {
+    let path = temp_path("truncated");
+    let mut bytes = Vec::new();
+    write_npy(&mut bytes, &array![1., 2., 3.]).unwrap();
+    bytes.truncate(bytes.len() - 4);
+    std::fs::write(&path, bytes).unwrap();
+
+    assert!(MappedNpy::open(&path).is_err());
+
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,619 @@
+//! Tensor input and output.
+//!
+//! This module reads and writes tensors in the formats used by [NumPy] and reads PyTorch
+//! checkpoints, so that data and model weights can be exchanged with Python tooling.
+//!
+//! * [`read_npy`] and [`write_npy`] - single tensors in the `.npy` format.
+//!
+//! * [`read_npz`] and [`write_npz`] - named collections of tensors in the `.npz` format, as
+//!   produced by `numpy.savez` and `numpy.savez_compressed`.
+//!
+//! * [`save_parameters`] and [`load_parameters`] - the parameters of a model, stored as an
+//!   `.npz` archive whose entries are named `arr_0`, `arr_1` and so on, in the order of
+//!   [`.parameters()`](crate::nn::ModelStatus::parameters).
+//!
+//! * [`MappedNpy`] - a `.npy` file mapped into memory, to use tensors larger than the available
+//!   RAM without loading them, such as the embedding tables looked up with
+//!   [`IndexVar::mapped_embedding()`](crate::IndexVar::mapped_embedding()).
+//!
+//! * [`StateDict`] - the tensors of a PyTorch checkpoint, to initialize neuronika layers with
+//!   pretrained weights.
+//!
+//! All the supported element types are converted to `f32` when reading, while tensors are always
+//! written as little-endian `f32`.
+//!
+//! ```
+//! use ndarray::array;
+//! use neuronika::io::{read_npy, write_npy};
+//!
+//! let mut bytes = Vec::new();
+//! write_npy(&mut bytes, &array![[1., 2.], [3., 4.]]).unwrap();
+//!
+//! let tensor = read_npy(&bytes[..]).unwrap();
+//! assert_eq!(tensor.shape(), &[2, 2]);
+//! ```
+//!
+//! [NumPy]: https://numpy.org/doc/stable/reference/generated/numpy.lib.format.html
+use crate::Param;
+use flate2::read::DeflateDecoder;
+use ndarray::{Array, ArrayBase, ArrayD, ArrayViewD, Data, Dimension, IxDyn};
+use std::{
+    borrow::Cow,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+#[cfg(unix)]
+mod mmap;
+mod torch;
+
+#[cfg(unix)]
+pub use mmap::MappedNpy;
+pub use torch::StateDict;
+
+const NPY_MAGIC: &[u8] = b"\x93NUMPY";
+
+pub(crate) fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ NPY ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Element type of a `.npy` file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DType {
+    Bool,
+    Int(usize),
+    UInt(usize),
+    Float(usize),
+}
+
+impl DType {
+    /// Parses a type descriptor such as `<f4`, returning the type and whether it's big-endian.
+    fn parse(descr: &str) -> io::Result<(Self, bool)> {
+        let unsupported = || invalid_data(format!("unsupported .npy type {:?}", descr));
+
+        let mut chars = descr.chars();
+        let big_endian = match chars.next() {
+            Some('<') | Some('|') | Some('=') => false,
+            Some('>') => true,
+            _ => return Err(unsupported()),
+        };
+        let kind = chars.next().ok_or_else(unsupported)?;
+        let size: usize = chars.as_str().parse().map_err(|_| unsupported())?;
+
+        let dtype = match (kind, size) {
+            ('b', 1) => DType::Bool,
+            ('i', 1 | 2 | 4 | 8) => DType::Int(size),
+            ('u', 1 | 2 | 4 | 8) => DType::UInt(size),
+            ('f', 4 | 8) => DType::Float(size),
+            _ => return Err(unsupported()),
+        };
+        Ok((dtype, big_endian))
+    }
+
+    fn size(self) -> usize {
+        match self {
+            DType::Bool => 1,
+            DType::Int(size) | DType::UInt(size) | DType::Float(size) => size,
+        }
+    }
+
+    /// Converts an element, whose bytes are in little-endian order, to `f32`.
+    fn decode(self, bytes: &[u8]) -> f32 {
+        let mut buffer = [0; 8];
+        buffer[..bytes.len()].copy_from_slice(bytes);
+        let unsigned = u64::from_le_bytes(buffer);
+        match self {
+            DType::Bool => (unsigned != 0) as u8 as f32,
+            DType::UInt(_) => unsigned as f32,
+            DType::Int(size) => {
+                // Sign-extends the value.
+                let shift = 64 - 8 * size;
+                ((unsigned << shift) as i64 >> shift) as f32
+            }
+            DType::Float(4) => f32::from_bits(unsigned as u32),
+            DType::Float(_) => f64::from_bits(unsigned) as f32,
+        }
+    }
+}
+
+/// Returns the value of `key` in the Python dictionary literal of a `.npy` header.
+fn header_value<'a>(header: &'a str, key: &str) -> io::Result<&'a str> {
+    let missing = || invalid_data(format!("missing {:?} in .npy header", key));
+
+    let start = header.find(&format!("'{}'", key)).ok_or_else(missing)? + key.len() + 2;
+    let value = header[start..]
+        .trim_start()
+        .strip_prefix(':')
+        .ok_or_else(missing)?;
+    let value = value.trim_start();
+    let end = match value.chars().next() {
+        Some('(') => value.find(')').map(|end| end + 1),
+        Some(quote @ ('\'' | '"')) => value[1..].find(quote).map(|end| end + 2),
+        _ => value.find([',', '}']),
+    }
+    .ok_or_else(missing)?;
+
+    Ok(&value[..end])
+}
+
+/// Parses the header of a `.npy` file, returning the type, the endianness, the memory order and
+/// the shape of the tensor.
+fn parse_header(header: &str) -> io::Result<(DType, bool, bool, Vec<usize>)> {
+    let descr = header_value(header, "descr")?.trim_matches(|c| c == '\'' || c == '"');
+    let (dtype, big_endian) = DType::parse(descr)?;
+
+    let fortran_order = match header_value(header, "fortran_order")? {
+        "True" => true,
+        "False" => false,
+        value => {
+            return Err(invalid_data(format!(
+                "invalid fortran_order {:?} in .npy header",
+                value
+            )))
+        }
+    };
+
+    let shape = header_value(header, "shape")?
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(',')
+        .map(str::trim)
+        .filter(|dimension| !dimension.is_empty())
+        .map(|dimension| {
+            dimension
+                .parse()
+                .map_err(|_| invalid_data(format!("invalid shape {:?} in .npy header", header)))
+        })
+        .collect::<io::Result<Vec<usize>>>()?;
+
+    Ok((dtype, big_endian, fortran_order, shape))
+}
+
+/// Reads the magic string and the header of a `.npy` file, returning the type, the endianness,
+/// the memory order and the shape of the tensor, together with the offset of its data.
+fn read_npy_header<R: Read>(reader: &mut R) -> io::Result<(DType, bool, bool, Vec<usize>, usize)> {
+    let mut magic = [0; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic[..6] != NPY_MAGIC {
+        return Err(invalid_data("invalid .npy magic string".to_string()));
+    }
+
+    let (header_len, len_size) = match magic[6] {
+        1 => {
+            let mut len = [0; 2];
+            reader.read_exact(&mut len)?;
+            (u16::from_le_bytes(len) as usize, 2)
+        }
+        2 | 3 => {
+            let mut len = [0; 4];
+            reader.read_exact(&mut len)?;
+            (u32::from_le_bytes(len) as usize, 4)
+        }
+        version => {
+            return Err(invalid_data(format!(
+                "unsupported .npy version {}.{}",
+                version, magic[7]
+            )))
+        }
+    };
+    let mut header = vec![0; header_len];
+    reader.read_exact(&mut header)?;
+    let header = String::from_utf8(header)
+        .map_err(|_| invalid_data("the .npy header is not valid UTF-8".to_string()))?;
+    let (dtype, big_endian, fortran_order, shape) = parse_header(&header)?;
+
+    Ok((
+        dtype,
+        big_endian,
+        fortran_order,
+        shape,
+        NPY_MAGIC.len() + 2 + len_size + header_len,
+    ))
+}
+
+/// Returns the size in bytes of the data of a `.npy` file holding a tensor of shape `shape` with
+/// elements of type `dtype`.
+///
+/// # Errors
+///
+/// If the size overflows.
+fn data_size(dtype: DType, shape: &[usize]) -> io::Result<usize> {
+    shape
+        .iter()
+        .try_fold(dtype.size(), |size, &len| size.checked_mul(len))
+        .ok_or_else(|| invalid_data(format!("the .npy shape {:?} is too large", shape)))
+}
+
+/// Reads a tensor in the `.npy` format.
+///
+/// Boolean, integer and floating point element types are supported, in any byte order and in
+/// both C and Fortran memory order. They are converted to `f32`.
+///
+/// # Errors
+///
+/// If the data cannot be read or is not a valid `.npy` file of a supported type.
+pub fn read_npy<R: Read>(mut reader: R) -> io::Result<ArrayD<f32>> {
+    let (dtype, big_endian, fortran_order, shape, _) = read_npy_header(&mut reader)?;
+
+    // The data is read incrementally, so that a corrupted shape cannot cause an allocation
+    // larger than the file.
+    let size = data_size(dtype, &shape)?;
+    let mut bytes = Vec::new();
+    reader.take(size as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != size {
+        return Err(invalid_data("truncated .npy file".to_string()));
+    }
+    let data: Vec<f32> = bytes
+        .chunks_exact_mut(dtype.size())
+        .map(|element| {
+            if big_endian {
+                element.reverse();
+            }
+            dtype.decode(element)
+        })
+        .collect();
+
+    if fortran_order {
+        // Fortran order is the C order of the reversed shape.
+        let reversed: Vec<usize> = shape.iter().rev().copied().collect();
+        let tensor = Array::from_shape_vec(IxDyn(&reversed), data).unwrap();
+        Ok(tensor.reversed_axes().as_standard_layout().into_owned())
+    } else {
+        Ok(Array::from_shape_vec(IxDyn(&shape), data).unwrap())
+    }
+}
+
+/// Writes a tensor in the `.npy` format, as little-endian `f32`.
+///
+/// # Errors
+///
+/// If the data cannot be written.
+pub fn write_npy<W, S, D>(mut writer: W, tensor: &ArrayBase<S, D>) -> io::Result<()>
+where
+    W: Write,
+    S: Data<Elem = f32>,
+    D: Dimension,
+{
+    let shape = match tensor.shape() {
+        [dimension] => format!("({},)", dimension),
+        shape => format!(
+            "({})",
+            shape
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let mut header = format!(
+        "{{'descr': '<f4', 'fortran_order': False, 'shape': {}, }}",
+        shape
+    );
+    // The header is padded so that the data is aligned to 64 bytes.
+    let unpadded = NPY_MAGIC.len() + 4 + header.len() + 1;
+    header.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+    header.push('\n');
+
+    writer.write_all(NPY_MAGIC)?;
+    writer.write_all(&[1, 0])?;
+    writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+    for value in tensor.iter() {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+
+    Ok(())
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ NPZ ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+const LOCAL_HEADER: u32 = 0x0403_4B50;
+const CENTRAL_HEADER: u32 = 0x0201_4B50;
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4B50;
+
+/// Compression methods of the entries of a ZIP archive.
+const STORED: u16 = 0;
+const DEFLATED: u16 = 8;
+
+fn u16_at(bytes: &[u8], offset: usize) -> io::Result<u16> {
+    bytes
+        .get(offset..offset + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .ok_or_else(|| invalid_data("truncated archive".to_string()))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> io::Result<u32> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or_else(|| invalid_data("truncated archive".to_string()))
+}
+
+fn u64_at(bytes: &[u8], offset: usize) -> io::Result<u64> {
+    bytes
+        .get(offset..offset + 8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or_else(|| invalid_data("truncated archive".to_string()))
+}
+
+/// Replaces the sizes and the offset of a central directory entry that overflow 32 bits with
+/// the ones stored in its ZIP64 extra field.
+fn zip64_fields(extra: &[u8], fields: &mut [u64; 3]) -> io::Result<()> {
+    let mut offset = 0;
+    while offset + 4 <= extra.len() {
+        let (id, len) = (u16_at(extra, offset)?, u16_at(extra, offset + 2)? as usize);
+        if id == 1 {
+            let mut position = offset + 4;
+            for field in fields.iter_mut().filter(|field| **field == u32::MAX as u64) {
+                *field = u64_at(extra, position)?;
+                position += 8;
+            }
+            return Ok(());
+        }
+        offset += 4 + len;
+    }
+    Ok(())
+}
+
+/// Returns the names and the contents of the entries of a ZIP archive, in the order of its
+/// central directory. Stored entries are borrowed from `bytes`, while deflated ones are
+/// decompressed.
+///
+/// # Errors
+///
+/// If the archive is not valid or if any of its entries uses another compression method.
+fn zip_entries(bytes: &[u8]) -> io::Result<Vec<(String, Cow<'_, [u8]>)>> {
+    let truncated = || invalid_data("truncated archive".to_string());
+
+    // The end of central directory record is followed by a comment of at most 64 KiB.
+    let end = (0..bytes.len().saturating_sub(21))
+        .rev()
+        .take(u16::MAX as usize + 1)
+        .find(|&offset| u32_at(bytes, offset).ok() == Some(END_OF_CENTRAL_DIRECTORY))
+        .ok_or_else(|| invalid_data("not a ZIP archive".to_string()))?;
+    let count = u16_at(bytes, end + 10)?;
+    let mut offset = u32_at(bytes, end + 16)? as usize;
+
+    let mut entries = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        if u32_at(bytes, offset)? != CENTRAL_HEADER {
+            return Err(invalid_data("invalid central directory".to_string()));
+        }
+        let method = u16_at(bytes, offset + 10)?;
+        let mut fields = [
+            u32_at(bytes, offset + 24)? as u64,
+            u32_at(bytes, offset + 20)? as u64,
+            u32_at(bytes, offset + 42)? as u64,
+        ];
+        let name_len = u16_at(bytes, offset + 28)? as usize;
+        let extra_len = u16_at(bytes, offset + 30)? as usize;
+        let comment_len = u16_at(bytes, offset + 32)? as usize;
+        let name = bytes
+            .get(offset + 46..offset + 46 + name_len)
+            .ok_or_else(truncated)?;
+        let name = String::from_utf8_lossy(name).into_owned();
+        let extra = bytes
+            .get(offset + 46 + name_len..offset + 46 + name_len + extra_len)
+            .ok_or_else(truncated)?;
+        zip64_fields(extra, &mut fields)?;
+        offset += 46 + name_len + extra_len + comment_len;
+
+        let [size, compressed_size, local] = fields;
+        let local = local as usize;
+        if u32_at(bytes, local)? != LOCAL_HEADER {
+            return Err(invalid_data(format!("invalid entry {:?}", name)));
+        }
+        let start =
+            local + 30 + u16_at(bytes, local + 26)? as usize + u16_at(bytes, local + 28)? as usize;
+        let data = bytes
+            .get(start..start + compressed_size as usize)
+            .ok_or_else(truncated)?;
+
+        let data = match method {
+            STORED => Cow::Borrowed(data),
+            DEFLATED => {
+                // The size declared by the archive is only checked once the entry is inflated,
+                // which never goes past it.
+                let mut inflated = Vec::new();
+                DeflateDecoder::new(data)
+                    .take(size.saturating_add(1))
+                    .read_to_end(&mut inflated)
+                    .map_err(|error| invalid_data(format!("entry {:?}: {}", name, error)))?;
+                if inflated.len() as u64 != size {
+                    return Err(invalid_data(format!("invalid entry {:?}", name)));
+                }
+                Cow::Owned(inflated)
+            }
+            _ => {
+                return Err(invalid_data(format!(
+                    "entry {:?} uses the unsupported compression method {}",
+                    name, method
+                )))
+            }
+        };
+
+        entries.push((name, data));
+    }
+
+    Ok(entries)
+}
+
+/// Reads all the tensors of an archive in the `.npz` format.
+///
+/// The tensors are returned in the order in which they are stored, together with their names,
+/// without the `.npy` extension.
+///
+/// # Errors
+///
+/// If the data cannot be read or is not a valid `.npz` archive.
+pub fn read_npz<R: Read>(mut reader: R) -> io::Result<Vec<(String, ArrayD<f32>)>> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    zip_entries(&bytes)?
+        .into_iter()
+        .map(|(name, data)| {
+            let tensor = read_npy(&*data)?;
+            let name = name.strip_suffix(".npy").unwrap_or(&name).to_string();
+            Ok((name, tensor))
+        })
+        .collect()
+}
+
+/// Writes named tensors to an archive in the `.npz` format, as produced by `numpy.savez`.
+///
+/// The entries are not compressed and are named after the tensors with the `.npy` extension.
+///
+/// # Errors
+///
+/// If the data cannot be written or if the archive exceeds 4 GiB.
+pub fn write_npz<W: Write>(mut writer: W, tensors: &[(&str, ArrayViewD<f32>)]) -> io::Result<()> {
+    let too_large = || invalid_data("the .npz archive exceeds 4 GiB".to_string());
+
+    let mut central_directory = Vec::new();
+    let mut offset = 0u32;
+    for (name, tensor) in tensors {
+        let name = format!("{}.npy", name);
+        let mut data = Vec::new();
+        write_npy(&mut data, tensor)?;
+        let size = u32::try_from(data.len()).map_err(|_| too_large())?;
+        let crc = crc32(&data);
+
+        // Fields shared by the local header and the central directory: version needed to
+        // extract, flags, method, time, date, CRC, sizes and name length.
+        let mut common = Vec::with_capacity(26);
+        common.extend_from_slice(&20u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        // 1980-01-01, the earliest date that can be represented.
+        common.extend_from_slice(&0x21u16.to_le_bytes());
+        common.extend_from_slice(&crc.to_le_bytes());
+        common.extend_from_slice(&size.to_le_bytes());
+        common.extend_from_slice(&size.to_le_bytes());
+        common.extend_from_slice(&(name.len() as u16).to_le_bytes());
+
+        writer.write_all(&LOCAL_HEADER.to_le_bytes())?;
+        writer.write_all(&common)?;
+        writer.write_all(&0u16.to_le_bytes())?;
+        writer.write_all(name.as_bytes())?;
+        writer.write_all(&data)?;
+
+        central_directory.extend_from_slice(&CENTRAL_HEADER.to_le_bytes());
+        central_directory.extend_from_slice(&20u16.to_le_bytes());
+        central_directory.extend_from_slice(&common);
+        // Extra field and comment lengths, disk number, internal and external attributes.
+        central_directory.extend_from_slice(&[0; 12]);
+        central_directory.extend_from_slice(&offset.to_le_bytes());
+        central_directory.extend_from_slice(name.as_bytes());
+
+        offset = (30 + name.len() as u32)
+            .checked_add(size)
+            .and_then(|len| offset.checked_add(len))
+            .ok_or_else(too_large)?;
+    }
+
+    let entries = u16::try_from(tensors.len())
+        .map_err(|_| invalid_data("too many tensors for a .npz archive".to_string()))?;
+    writer.write_all(&central_directory)?;
+    writer.write_all(&END_OF_CENTRAL_DIRECTORY.to_le_bytes())?;
+    writer.write_all(&[0; 4])?;
+    writer.write_all(&entries.to_le_bytes())?;
+    writer.write_all(&entries.to_le_bytes())?;
+    writer.write_all(&(central_directory.len() as u32).to_le_bytes())?;
+    writer.write_all(&offset.to_le_bytes())?;
+    writer.write_all(&0u16.to_le_bytes())?;
+
+    Ok(())
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Parameters ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Saves the data of `params` to the `.npz` archive at `path`.
+///
+/// The tensors are named `arr_0`, `arr_1` and so on, as with `numpy.savez(path, *params)`.
+///
+/// # Errors
+///
+/// If the file cannot be written.
+pub fn save_parameters<P: AsRef<Path>>(path: P, params: &[Param]) -> io::Result<()> {
+    let names: Vec<String> = (0..params.len()).map(|i| format!("arr_{}", i)).collect();
+    let tensors: Vec<(&str, ArrayViewD<f32>)> = names
+        .iter()
+        .zip(params)
+        .map(|(name, param)| (name.as_str(), param.data.view()))
+        .collect();
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_npz(&mut writer, &tensors)?;
+    writer.flush()
+}
+
+/// Loads the data of `params` from the `.npz` archive at `path`, written by
+/// [`save_parameters`].
+///
+/// The tensors are matched to the parameters by position. The parameters are left untouched if
+/// an error occurs.
+///
+/// # Errors
+///
+/// If the file cannot be read, or if the number or the shapes of its tensors don't match the
+/// ones of the parameters.
+pub fn load_parameters<P: AsRef<Path>>(path: P, params: &mut [Param]) -> io::Result<()> {
+    let tensors = read_npz(BufReader::new(File::open(path)?))?;
+    if tensors.len() != params.len() {
+        return Err(invalid_data(format!(
+            "the archive holds {} tensors, but there are {} parameters",
+            tensors.len(),
+            params.len()
+        )));
+    }
+    for (i, ((_, tensor), param)) in tensors.iter().zip(params.iter()).enumerate() {
+        if tensor.shape() != param.data.shape() {
+            return Err(invalid_data(format!(
+                "tensor {} has shape {:?}, but the parameter has shape {:?}",
+                i,
+                tensor.shape(),
+                param.data.shape()
+            )));
+        }
+    }
+
+    for ((_, tensor), param) in tensors.iter().zip(params.iter_mut()) {
+        param.data.assign(tensor);
+    }
+    Ok(())
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Checksums ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Computes the reflected CRC-32 of `data` with the given polynomial.
+pub(crate) fn crc32_with(polynomial: u32, data: &[u8]) -> u32 {
+    !data.iter().fold(!0, |crc, &byte| {
+        (0..8).fold(crc ^ byte as u32, |crc, _| {
+            if crc & 1 == 1 {
+                (crc >> 1) ^ polynomial
+            } else {
+                crc >> 1
+            }
+        })
+    })
+}
+
+/// CRC-32 as used by ZIP and PNG.
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    crc32_with(0xEDB8_8320, data)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
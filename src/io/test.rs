@@ -0,0 +1,245 @@
+use super::{read_npy, read_npz, write_npy, write_npz};
+use ndarray::{array, Array, ArrayD, IxDyn};
+use std::io::ErrorKind;
+
+/// Builds a `.npy` file with a version 1.0 header.
+fn npy(header: &str, data: &[u8]) -> Vec<u8> {
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+#[test]
+fn npy_round_trip() {
+    let tensor = Array::from_shape_fn((2, 3, 4), |(i, j, k)| (i * 12 + j * 4 + k) as f32 - 5.5);
+    let mut bytes = Vec::new();
+    write_npy(&mut bytes, &tensor).unwrap();
+
+    // The data is aligned to 64 bytes.
+    assert_eq!((bytes.len() - 24 * 4) % 64, 0);
+    assert_eq!(read_npy(&bytes[..]).unwrap(), tensor.into_dyn());
+
+    let mut bytes = Vec::new();
+    write_npy(&mut bytes, &array![1., 2.]).unwrap();
+    assert!(String::from_utf8_lossy(&bytes).contains("'shape': (2,)"));
+
+    // Non-contiguous tensors are written in logical order.
+    let mut bytes = Vec::new();
+    write_npy(&mut bytes, &array![[1., 2.], [3., 4.]].t()).unwrap();
+    assert_eq!(
+        read_npy(&bytes[..]).unwrap(),
+        array![[1., 3.], [2., 4.]].into_dyn()
+    );
+}
+
+#[test]
+fn npy_scalar() {
+    let mut bytes = Vec::new();
+    write_npy(&mut bytes, &ArrayD::from_elem(IxDyn(&[]), 7.)).unwrap();
+    assert_eq!(read_npy(&bytes[..]).unwrap().shape(), &[] as &[usize]);
+}
+
+#[test]
+fn npy_types() {
+    let doubles: Vec<u8> = [1.5f64, -2.]
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect();
+    let tensor = read_npy(
+        &npy(
+            "{'descr': '<f8', 'fortran_order': False, 'shape': (2,), }\n",
+            &doubles,
+        )[..],
+    )
+    .unwrap();
+    assert_eq!(tensor, array![1.5, -2.].into_dyn());
+
+    let tensor = read_npy(
+        &npy(
+            "{'descr': '>i2', 'fortran_order': False, 'shape': (2,), }\n",
+            &[0xFF, 0xFE, 0x01, 0x00],
+        )[..],
+    )
+    .unwrap();
+    assert_eq!(tensor, array![-2., 256.].into_dyn());
+
+    let tensor = read_npy(
+        &npy(
+            "{'descr': '|u1', 'fortran_order': True, 'shape': (2, 3), }\n",
+            &[1, 4, 2, 5, 3, 6],
+        )[..],
+    )
+    .unwrap();
+    assert_eq!(tensor, array![[1., 2., 3.], [4., 5., 6.]].into_dyn());
+
+    let tensor = read_npy(
+        &npy(
+            "{'descr': '|b1', 'fortran_order': False, 'shape': (), }\n",
+            &[1],
+        )[..],
+    )
+    .unwrap();
+    assert_eq!(tensor.sum(), 1.);
+}
+
+#[test]
+fn npy_errors() {
+    assert!(read_npy(&b"\x93NUMPZ\x01\x00"[..]).is_err());
+    assert!(read_npy(
+        &npy(
+            "{'descr': '<c8', 'fortran_order': False, 'shape': (1,), }\n",
+            &[0; 8]
+        )[..]
+    )
+    .is_err());
+    assert!(read_npy(&npy("{'descr': '<f4', 'shape': (1,), }\n", &[0; 4])[..]).is_err());
+    assert!(read_npy(
+        &npy(
+            "{'descr': '<f4', 'fortran_order': False, 'shape': (2,), }\n",
+            &[0; 4]
+        )[..]
+    )
+    .is_err());
+}
+
+#[test]
+fn npy_malformed_shape() {
+    // The number of elements overflows.
+    let header = format!(
+        "{{'descr': '<f4', 'fortran_order': False, 'shape': ({}, {}), }}\n",
+        usize::MAX / 2,
+        3
+    );
+    let error = read_npy(&npy(&header, &[0; 4])[..]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    // The size of the data overflows, while the number of elements does not.
+    let header = format!(
+        "{{'descr': '<f8', 'fortran_order': False, 'shape': ({},), }}\n",
+        usize::MAX / 4
+    );
+    let error = read_npy(&npy(&header, &[0; 8])[..]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+    // The data is far smaller than the shape declares.
+    let header = "{'descr': '<f4', 'fortran_order': False, 'shape': (1099511627776,), }\n";
+    let error = read_npy(&npy(header, &[0; 4])[..]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn npz_round_trip() {
+    let weight = array![[1., 2.], [3., 4.]].into_dyn();
+    let bias = array![0.5].into_dyn();
+    let mut bytes = Vec::new();
+    write_npz(
+        &mut bytes,
+        &[("weight", weight.view()), ("bias", bias.view())],
+    )
+    .unwrap();
+
+    let tensors = read_npz(&bytes[..]).unwrap();
+    assert_eq!(tensors.len(), 2);
+    assert_eq!(tensors[0], ("weight".to_string(), weight));
+    assert_eq!(tensors[1], ("bias".to_string(), bias));
+
+    assert!(read_npz(&bytes[..bytes.len() - 30]).is_err());
+}
+
+/// Deflates the single entry of an archive written by `write_npz`, as `numpy.savez_compressed`
+/// does.
+fn deflate_npz(bytes: &[u8], method: u16) -> Vec<u8> {
+    use flate2::{write::DeflateEncoder, Compression};
+    use std::io::Write;
+
+    let find = |signature: [u8; 4]| {
+        bytes
+            .windows(4)
+            .position(|window| window == signature)
+            .unwrap()
+    };
+    let (central, end) = (
+        find([0x50, 0x4B, 0x01, 0x02]),
+        find([0x50, 0x4B, 0x05, 0x06]),
+    );
+    let start = 30 + u16::from_le_bytes([bytes[26], bytes[27]]) as usize;
+
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&bytes[start..central]).unwrap();
+    let data = encoder.finish().unwrap();
+    let size = (data.len() as u32).to_le_bytes();
+
+    let mut local = bytes[..start].to_vec();
+    local[8..10].copy_from_slice(&method.to_le_bytes());
+    local[18..22].copy_from_slice(&size);
+    let mut directory = bytes[central..].to_vec();
+    directory[10..12].copy_from_slice(&method.to_le_bytes());
+    directory[20..24].copy_from_slice(&size);
+    let offset = ((start + data.len()) as u32).to_le_bytes();
+    directory[end - central + 16..end - central + 20].copy_from_slice(&offset);
+
+    [local, data, directory].concat()
+}
+
+#[test]
+fn npz_compressed() {
+    let tensor = Array::linspace(0., 1., 64)
+        .into_shape((8, 8))
+        .unwrap()
+        .into_dyn();
+    let mut bytes = Vec::new();
+    write_npz(&mut bytes, &[("x", tensor.view())]).unwrap();
+
+    let compressed = deflate_npz(&bytes, 8);
+    assert!(compressed.len() < bytes.len());
+    assert_eq!(
+        read_npz(&compressed[..]).unwrap(),
+        vec![("x".to_string(), tensor)]
+    );
+
+    // The declared size of an entry is checked against the inflated one, whichever is larger.
+    for size in [u32::MAX - 1, 1] {
+        let mut malformed = compressed.clone();
+        let central = malformed
+            .windows(4)
+            .position(|window| window == [0x50, 0x4B, 0x01, 0x02])
+            .unwrap();
+        malformed[central + 24..central + 28].copy_from_slice(&size.to_le_bytes());
+        let error = read_npz(&malformed[..]).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    // Compression methods other than deflate are rejected.
+    let error = read_npz(&deflate_npz(&bytes, 12)[..]).unwrap_err();
+    assert!(error.to_string().contains("compression method 12"));
+}
+
+#[test]
+fn parameters() {
+    use crate::nn::{Linear, ModelStatus};
+
+    let path = std::env::temp_dir().join(format!("neuronika-io-{}.npz", std::process::id()));
+    let mut status = ModelStatus::default();
+    let _linear = status.register(Linear::new(3, 2));
+    super::save_parameters(&path, &status.parameters()).unwrap();
+    let saved: Vec<_> = status
+        .parameters()
+        .iter()
+        .map(|param| param.data.to_owned())
+        .collect();
+
+    let mut other = ModelStatus::default();
+    let _other = other.register(Linear::new(3, 2));
+    super::load_parameters(&path, &mut other.parameters()).unwrap();
+    for (param, saved) in other.parameters().iter().zip(&saved) {
+        assert_eq!(&param.data, saved);
+    }
+
+    let mut wrong = ModelStatus::default();
+    let _wrong = wrong.register(Linear::new(2, 2));
+    assert!(super::load_parameters(&path, &mut wrong.parameters()).is_err());
+
+    std::fs::remove_file(path).unwrap();
+}
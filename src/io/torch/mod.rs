@@ -0,0 +1,759 @@
+use super::{invalid_data, zip_entries};
+use crate::{nn::Learnable, Param};
+use ndarray::{ArrayD, Dimension, IxDyn};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufReader, Read},
+    path::Path,
+};
+
+/// Named tensors of a PyTorch checkpoint.
+///
+/// Checkpoints must have been written by `torch.save` with the zip-based format, the default
+/// since PyTorch 1.6, and hold a state dict, that is a possibly nested dictionary of tensors.
+/// Nested dictionaries, such as the ones of checkpoints of the form
+/// `{"model": model.state_dict(), "epoch": epoch}`, are flattened by joining their keys with a
+/// dot, while values other than tensors are skipped. Tensors of any floating point, integer or
+/// boolean type are converted to `f32`.
+///
+/// Tensors can be loaded either by name into a given learnable variable, with
+/// [`.load()`](StateDict::load()), or by position and shape into the parameters of a model, with
+/// [`.load_parameters()`](StateDict::load_parameters()). PyTorch and neuronika layers store
+/// their weights with the same shapes, for instance *(out_features, in_features)* for linear
+/// layers.
+///
+/// ```no_run
+/// use neuronika::{io::StateDict, nn::Linear};
+///
+/// let fc = Linear::new(784, 10);
+///
+/// let state = StateDict::open("classifier.pt").unwrap();
+/// state.load("fc.weight", &fc.weight).unwrap();
+/// state.load("fc.bias", &fc.bias).unwrap();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct StateDict {
+    tensors: Vec<(String, ArrayD<f32>)>,
+}
+
+impl StateDict {
+    /// Reads a PyTorch checkpoint from the file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be read or is not a supported PyTorch checkpoint.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads a PyTorch checkpoint.
+    ///
+    /// # Errors
+    ///
+    /// If the data cannot be read or is not a supported PyTorch checkpoint.
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        if !bytes.starts_with(b"PK") {
+            return Err(invalid_data(
+                "only checkpoints in the zip-based format of PyTorch 1.6 and later are supported"
+                    .to_string(),
+            ));
+        }
+
+        let entries = zip_entries(&bytes)?;
+        let (name, pickle) = entries
+            .iter()
+            .find(|(name, _)| name.ends_with("data.pkl"))
+            .ok_or_else(|| invalid_data("the checkpoint has no data.pkl entry".to_string()))?;
+        let prefix = format!("{}data/", name.trim_end_matches("data.pkl"));
+        let storages = entries
+            .iter()
+            .filter_map(|(name, data)| Some((name.strip_prefix(&prefix)?.to_string(), &**data)))
+            .collect();
+
+        let root = Unpickler::new(pickle, storages).load()?;
+        let mut tensors = Vec::new();
+        flatten(String::new(), root, &mut tensors);
+
+        Ok(Self { tensors })
+    }
+
+    /// Returns the names of the tensors, in the order in which they are stored.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.tensors.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the tensor named `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&ArrayD<f32>> {
+        self.tensors
+            .iter()
+            .find(|(other, _)| other == name)
+            .map(|(_, tensor)| tensor)
+    }
+
+    /// Returns the number of tensors.
+    pub fn len(&self) -> usize {
+        self.tensors.len()
+    }
+
+    /// Returns `true` if there are no tensors.
+    pub fn is_empty(&self) -> bool {
+        self.tensors.is_empty()
+    }
+
+    /// Returns the tensors together with their names.
+    pub fn into_tensors(self) -> Vec<(String, ArrayD<f32>)> {
+        self.tensors
+    }
+
+    /// Copies the tensor named `name` into the data of `variable`.
+    ///
+    /// # Errors
+    ///
+    /// If there's no tensor named `name` or if its shape differs from the one of `variable`.
+    pub fn load<D: Dimension>(&self, name: &str, variable: &Learnable<D>) -> io::Result<()> {
+        let tensor = self
+            .get(name)
+            .ok_or_else(|| invalid_data(format!("no tensor named {:?} in the state dict", name)))?;
+
+        let mut data = variable.data_mut();
+        if tensor.shape() != data.shape() {
+            return Err(invalid_data(format!(
+                "tensor {:?} has shape {:?}, but the variable has shape {:?}",
+                name,
+                tensor.shape(),
+                data.shape()
+            )));
+        }
+        data.assign(&tensor.view().into_dimensionality::<D>().unwrap());
+
+        Ok(())
+    }
+
+    /// Copies the tensors into the data of `params`, matching them by position and shape.
+    ///
+    /// Each parameter receives the first of the following tensors with the same shape, the
+    /// tensors in between, such as the running statistics of batch normalization layers, are
+    /// skipped. The parameters are left untouched if an error occurs.
+    ///
+    /// # Errors
+    ///
+    /// If a parameter cannot be matched.
+    pub fn load_parameters(&self, params: &mut [Param]) -> io::Result<()> {
+        let mut tensors = self.tensors.iter();
+        let mut matches = Vec::with_capacity(params.len());
+        for (i, param) in params.iter().enumerate() {
+            let tensor = tensors
+                .find(|(_, tensor)| tensor.shape() == param.data.shape())
+                .ok_or_else(|| {
+                    invalid_data(format!(
+                        "no tensor matches parameter {} of shape {:?}",
+                        i,
+                        param.data.shape()
+                    ))
+                })?;
+            matches.push(&tensor.1);
+        }
+
+        for (param, tensor) in params.iter_mut().zip(matches) {
+            param.data.assign(tensor);
+        }
+        Ok(())
+    }
+}
+
+impl From<Vec<(String, ArrayD<f32>)>> for StateDict {
+    /// Creates a state dict from named tensors, such as the ones of a checkpoint that has been
+    /// converted to another format and read with [`read_npz()`](super::read_npz()).
+    fn from(tensors: Vec<(String, ArrayD<f32>)>) -> Self {
+        Self { tensors }
+    }
+}
+
+/// Collects the tensors of `value`, naming them after their path in the nested dictionaries.
+fn flatten(prefix: String, value: Value, tensors: &mut Vec<(String, ArrayD<f32>)>) {
+    match value {
+        Value::Tensor(tensor) => tensors.push((prefix, tensor)),
+        Value::Dict(items) => {
+            for (key, value) in items {
+                let key = match key {
+                    Value::Str(key) => key,
+                    Value::Int(key) => key.to_string(),
+                    _ => continue,
+                };
+                let name = if prefix.is_empty() {
+                    key
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten(name, value, tensors);
+            }
+        }
+        _ => (),
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Storages ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Element type of a PyTorch storage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum StorageType {
+    Float,
+    Double,
+    Half,
+    BFloat16,
+    Long,
+    Int,
+    Short,
+    Char,
+    Byte,
+    Bool,
+}
+
+impl StorageType {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "FloatStorage" => StorageType::Float,
+            "DoubleStorage" => StorageType::Double,
+            "HalfStorage" => StorageType::Half,
+            "BFloat16Storage" => StorageType::BFloat16,
+            "LongStorage" => StorageType::Long,
+            "IntStorage" => StorageType::Int,
+            "ShortStorage" => StorageType::Short,
+            "CharStorage" => StorageType::Char,
+            "ByteStorage" => StorageType::Byte,
+            "BoolStorage" => StorageType::Bool,
+            _ => return None,
+        })
+    }
+
+    fn size(self) -> usize {
+        match self {
+            StorageType::Double | StorageType::Long => 8,
+            StorageType::Float | StorageType::Int => 4,
+            StorageType::Half | StorageType::BFloat16 | StorageType::Short => 2,
+            StorageType::Char | StorageType::Byte | StorageType::Bool => 1,
+        }
+    }
+
+    /// Converts the little-endian bytes of a storage to `f32`.
+    fn decode(self, bytes: &[u8]) -> Vec<f32> {
+        bytes
+            .chunks_exact(self.size())
+            .map(|bytes| match self {
+                StorageType::Float => f32::from_le_bytes(bytes.try_into().unwrap()),
+                StorageType::Double => f64::from_le_bytes(bytes.try_into().unwrap()) as f32,
+                StorageType::Half => f16_to_f32(u16::from_le_bytes([bytes[0], bytes[1]])),
+                StorageType::BFloat16 => {
+                    f32::from_bits((u16::from_le_bytes([bytes[0], bytes[1]]) as u32) << 16)
+                }
+                StorageType::Long => i64::from_le_bytes(bytes.try_into().unwrap()) as f32,
+                StorageType::Int => i32::from_le_bytes(bytes.try_into().unwrap()) as f32,
+                StorageType::Short => i16::from_le_bytes([bytes[0], bytes[1]]) as f32,
+                StorageType::Char => bytes[0] as i8 as f32,
+                StorageType::Byte => bytes[0] as f32,
+                StorageType::Bool => (bytes[0] != 0) as u8 as f32,
+            })
+            .collect()
+    }
+}
+
+/// Converts an IEEE 754 half precision number to `f32`.
+fn f16_to_f32(half: u16) -> f32 {
+    let sign = if half & 0x8000 == 0 { 1. } else { -1. };
+    let exponent = ((half >> 10) & 0x1F) as i32;
+    let mantissa = (half & 0x3FF) as f32;
+
+    sign * match exponent {
+        0 => mantissa * 2f32.powi(-24),
+        0x1F if mantissa == 0. => f32::INFINITY,
+        0x1F => f32::NAN,
+        _ => (1. + mantissa / 1024.) * 2f32.powi(exponent - 15),
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Unpickler ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// A Python object, as far as it's needed to rebuild a state dict.
+#[derive(Clone, Debug)]
+enum Value {
+    None,
+    Int(i64),
+    Str(String),
+    Tuple(Vec<Value>),
+    List(Vec<Value>),
+    Dict(Vec<(Value, Value)>),
+    Global(String, String),
+    Storage(StorageType, String),
+    Tensor(ArrayD<f32>),
+    /// Any other object, such as booleans, floats or instances of classes.
+    Other,
+}
+
+impl Value {
+    fn into_usizes(self) -> io::Result<Vec<usize>> {
+        match self {
+            Value::Tuple(values) | Value::List(values) => values
+                .into_iter()
+                .map(|value| match value {
+                    Value::Int(value) if value >= 0 => Ok(value as usize),
+                    _ => Err(invalid_data("invalid tensor size or stride".to_string())),
+                })
+                .collect(),
+            _ => Err(invalid_data("invalid tensor size or stride".to_string())),
+        }
+    }
+}
+
+/// A minimal interpreter of the pickle protocol, restricted to the objects found in state dicts.
+struct Unpickler<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    stack: Vec<Value>,
+    marks: Vec<usize>,
+    memo: HashMap<u32, Value>,
+    storages: HashMap<String, &'a [u8]>,
+}
+
+impl<'a> Unpickler<'a> {
+    fn new(bytes: &'a [u8], storages: HashMap<String, &'a [u8]>) -> Self {
+        Self {
+            bytes,
+            position: 0,
+            stack: Vec::new(),
+            marks: Vec::new(),
+            memo: HashMap::new(),
+            storages,
+        }
+    }
+
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        let bytes = self
+            .bytes
+            .get(self.position..self.position + len)
+            .ok_or_else(|| invalid_data("truncated pickle".to_string()))?;
+        self.position += len;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> io::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> io::Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn line(&mut self) -> io::Result<String> {
+        let len = self.bytes[self.position..]
+            .iter()
+            .position(|&byte| byte == b'\n')
+            .ok_or_else(|| invalid_data("truncated pickle".to_string()))?;
+        let line = String::from_utf8_lossy(self.take(len)?).into_owned();
+        self.position += 1;
+        Ok(line)
+    }
+
+    fn string(&mut self, len: usize) -> io::Result<Value> {
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec())
+            .map(Value::Str)
+            .map_err(|_| invalid_data("invalid string in pickle".to_string()))
+    }
+
+    fn pop(&mut self) -> io::Result<Value> {
+        self.stack
+            .pop()
+            .ok_or_else(|| invalid_data("pickle stack underflow".to_string()))
+    }
+
+    fn top(&mut self) -> io::Result<&mut Value> {
+        self.stack
+            .last_mut()
+            .ok_or_else(|| invalid_data("pickle stack underflow".to_string()))
+    }
+
+    /// Pops the values pushed since the last mark.
+    fn pop_mark(&mut self) -> io::Result<Vec<Value>> {
+        let mark = self
+            .marks
+            .pop()
+            .ok_or_else(|| invalid_data("missing mark in pickle".to_string()))?;
+        if mark > self.stack.len() {
+            return Err(invalid_data("pickle stack underflow".to_string()));
+        }
+        Ok(self.stack.split_off(mark))
+    }
+
+    fn memo_get(&mut self, key: u32) -> io::Result<()> {
+        let value = self
+            .memo
+            .get(&key)
+            .cloned()
+            .ok_or_else(|| invalid_data(format!("missing memo entry {} in pickle", key)))?;
+        self.stack.push(value);
+        Ok(())
+    }
+
+    fn memo_put(&mut self, key: u32) -> io::Result<()> {
+        let value = self.top()?.clone();
+        self.memo.insert(key, value);
+        Ok(())
+    }
+
+    fn set_items(&mut self, items: Vec<Value>) -> io::Result<()> {
+        match self.top()? {
+            Value::Dict(dict) => {
+                let mut items = items.into_iter();
+                while let (Some(key), Some(value)) = (items.next(), items.next()) {
+                    dict.push((key, value));
+                }
+                Ok(())
+            }
+            _ => Err(invalid_data(
+                "cannot set items of a non-dict in pickle".to_string(),
+            )),
+        }
+    }
+
+    fn append(&mut self, items: Vec<Value>) -> io::Result<()> {
+        match self.top()? {
+            Value::List(list) => {
+                list.extend(items);
+                Ok(())
+            }
+            _ => Err(invalid_data(
+                "cannot append to a non-list in pickle".to_string(),
+            )),
+        }
+    }
+
+    /// Resolves a persistent id, which PyTorch uses to reference storages.
+    fn persistent_load(&self, pid: Value) -> io::Result<Value> {
+        let invalid = || invalid_data("invalid storage reference in pickle".to_string());
+
+        let fields = match pid {
+            Value::Tuple(fields) => fields,
+            _ => return Err(invalid()),
+        };
+        match fields.as_slice() {
+            [Value::Str(kind), Value::Global(_, name), Value::Str(key), ..]
+                if kind == "storage" =>
+            {
+                let storage_type = StorageType::from_name(name)
+                    .ok_or_else(|| invalid_data(format!("unsupported storage type {}", name)))?;
+                Ok(Value::Storage(storage_type, key.clone()))
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Calls a global with the given arguments.
+    fn reduce(&self, function: Value, args: Value) -> io::Result<Value> {
+        let (module, name) = match function {
+            Value::Global(module, name) => (module, name),
+            _ => return Ok(Value::Other),
+        };
+        let args = match args {
+            Value::Tuple(args) => args,
+            _ => Vec::new(),
+        };
+
+        match (module.as_str(), name.as_str()) {
+            ("collections", "OrderedDict") => Ok(Value::Dict(Vec::new())),
+            ("torch._utils", "_rebuild_tensor" | "_rebuild_tensor_v2") => self.rebuild_tensor(args),
+            ("torch._utils", "_rebuild_parameter" | "_rebuild_parameter_with_state") => args
+                .into_iter()
+                .next()
+                .ok_or_else(|| invalid_data("missing parameter data in pickle".to_string())),
+            _ => Ok(Value::Other),
+        }
+    }
+
+    /// Rebuilds a tensor from its storage, offset, size and stride.
+    fn rebuild_tensor(&self, args: Vec<Value>) -> io::Result<Value> {
+        let mut args = args.into_iter();
+        let (storage_type, key) = match args.next() {
+            Some(Value::Storage(storage_type, key)) => (storage_type, key),
+            _ => return Err(invalid_data("invalid tensor storage in pickle".to_string())),
+        };
+        let offset = match args.next() {
+            Some(Value::Int(offset)) if offset >= 0 => offset as usize,
+            _ => return Err(invalid_data("invalid tensor offset in pickle".to_string())),
+        };
+        let size = args.next().map_or(Ok(Vec::new()), Value::into_usizes)?;
+        let stride = args.next().map_or(Ok(Vec::new()), Value::into_usizes)?;
+
+        let bytes = self
+            .storages
+            .get(&key)
+            .ok_or_else(|| invalid_data(format!("missing storage {} in the checkpoint", key)))?;
+        let storage = storage_type.decode(bytes);
+
+        // Checks that the last element of the tensor is within the storage.
+        let last = size
+            .iter()
+            .zip(&stride)
+            .map(|(size, stride)| size.saturating_sub(1) * stride)
+            .sum::<usize>()
+            + offset;
+        if size.len() != stride.len()
+            || (size.iter().all(|&size| size > 0) && last >= storage.len())
+        {
+            return Err(invalid_data(format!(
+                "tensor of size {:?} and stride {:?} exceeds storage {}",
+                size, stride, key
+            )));
+        }
+
+        let tensor = ArrayD::from_shape_fn(IxDyn(&size), |index| {
+            let position = index
+                .slice()
+                .iter()
+                .zip(&stride)
+                .map(|(index, stride)| index * stride)
+                .sum::<usize>();
+            storage[offset + position]
+        });
+        Ok(Value::Tensor(tensor))
+    }
+
+    /// Runs the pickle program and returns the resulting object.
+    fn load(mut self) -> io::Result<Value> {
+        loop {
+            let opcode = self.byte()?;
+            match opcode {
+                // PROTO
+                0x80 => {
+                    self.byte()?;
+                }
+                // FRAME
+                0x95 => {
+                    self.u64()?;
+                }
+                // STOP
+                b'.' => return self.pop(),
+                // MARK
+                b'(' => self.marks.push(self.stack.len()),
+                // POP
+                b'0' => {
+                    self.pop()?;
+                }
+                // POP_MARK
+                b'1' => {
+                    self.pop_mark()?;
+                }
+                // DUP
+                b'2' => {
+                    let value = self.top()?.clone();
+                    self.stack.push(value);
+                }
+                // NONE, NEWTRUE, NEWFALSE, the booleans are not needed.
+                b'N' => self.stack.push(Value::None),
+                0x88 => self.stack.push(Value::Other),
+                0x89 => self.stack.push(Value::Other),
+                // BININT, BININT1, BININT2
+                b'J' => {
+                    let value = self.u32()? as i32;
+                    self.stack.push(Value::Int(value as i64));
+                }
+                b'K' => {
+                    let value = self.byte()?;
+                    self.stack.push(Value::Int(value as i64));
+                }
+                b'M' => {
+                    let value = self.u16()?;
+                    self.stack.push(Value::Int(value as i64));
+                }
+                // LONG1
+                0x8A => {
+                    let len = self.byte()? as usize;
+                    let bytes = self.take(len)?;
+                    if len > 8 {
+                        return Err(invalid_data("integer out of range in pickle".to_string()));
+                    }
+                    // Little-endian two's complement, sign-extended to 64 bits.
+                    let fill = if bytes.last().is_some_and(|&byte| byte & 0x80 != 0) {
+                        0xFF
+                    } else {
+                        0
+                    };
+                    let mut buffer = [fill; 8];
+                    buffer[..len].copy_from_slice(bytes);
+                    self.stack.push(Value::Int(i64::from_le_bytes(buffer)));
+                }
+                // BINFLOAT, the floats are not needed.
+                b'G' => {
+                    self.take(8)?;
+                    self.stack.push(Value::Other);
+                }
+                // SHORT_BINUNICODE, BINUNICODE, BINUNICODE8
+                0x8C => {
+                    let len = self.byte()? as usize;
+                    let value = self.string(len)?;
+                    self.stack.push(value);
+                }
+                b'X' => {
+                    let len = self.u32()? as usize;
+                    let value = self.string(len)?;
+                    self.stack.push(value);
+                }
+                0x8D => {
+                    let len = self.u64()? as usize;
+                    let value = self.string(len)?;
+                    self.stack.push(value);
+                }
+                // SHORT_BINSTRING, BINSTRING
+                b'U' => {
+                    let len = self.byte()? as usize;
+                    let value = String::from_utf8_lossy(self.take(len)?).into_owned();
+                    self.stack.push(Value::Str(value));
+                }
+                b'T' => {
+                    let len = self.u32()? as usize;
+                    let value = String::from_utf8_lossy(self.take(len)?).into_owned();
+                    self.stack.push(Value::Str(value));
+                }
+                // SHORT_BINBYTES, BINBYTES, the bytes are not needed.
+                b'C' => {
+                    let len = self.byte()? as usize;
+                    self.take(len)?;
+                    self.stack.push(Value::Other);
+                }
+                b'B' => {
+                    let len = self.u32()? as usize;
+                    self.take(len)?;
+                    self.stack.push(Value::Other);
+                }
+                // EMPTY_TUPLE, TUPLE, TUPLE1, TUPLE2, TUPLE3
+                b')' => self.stack.push(Value::Tuple(Vec::new())),
+                b't' => {
+                    let values = self.pop_mark()?;
+                    self.stack.push(Value::Tuple(values));
+                }
+                0x85..=0x87 => {
+                    let len = (opcode - 0x84) as usize;
+                    if self.stack.len() < len {
+                        return Err(invalid_data("pickle stack underflow".to_string()));
+                    }
+                    let values = self.stack.split_off(self.stack.len() - len);
+                    self.stack.push(Value::Tuple(values));
+                }
+                // EMPTY_LIST, LIST, APPEND, APPENDS
+                b']' => self.stack.push(Value::List(Vec::new())),
+                b'l' => {
+                    let values = self.pop_mark()?;
+                    self.stack.push(Value::List(values));
+                }
+                b'a' => {
+                    let value = self.pop()?;
+                    self.append(vec![value])?;
+                }
+                b'e' => {
+                    let values = self.pop_mark()?;
+                    self.append(values)?;
+                }
+                // EMPTY_DICT, DICT, SETITEM, SETITEMS
+                b'}' => self.stack.push(Value::Dict(Vec::new())),
+                b'd' => {
+                    let values = self.pop_mark()?;
+                    self.stack.push(Value::Dict(Vec::new()));
+                    self.set_items(values)?;
+                }
+                b's' => {
+                    let value = self.pop()?;
+                    let key = self.pop()?;
+                    self.set_items(vec![key, value])?;
+                }
+                b'u' => {
+                    let values = self.pop_mark()?;
+                    self.set_items(values)?;
+                }
+                // BINPUT, LONG_BINPUT, MEMOIZE
+                b'q' => {
+                    let key = self.byte()? as u32;
+                    self.memo_put(key)?;
+                }
+                b'r' => {
+                    let key = self.u32()?;
+                    self.memo_put(key)?;
+                }
+                0x94 => {
+                    let key = self.memo.len() as u32;
+                    self.memo_put(key)?;
+                }
+                // BINGET, LONG_BINGET
+                b'h' => {
+                    let key = self.byte()? as u32;
+                    self.memo_get(key)?;
+                }
+                b'j' => {
+                    let key = self.u32()?;
+                    self.memo_get(key)?;
+                }
+                // GLOBAL, STACK_GLOBAL
+                b'c' => {
+                    let module = self.line()?;
+                    let name = self.line()?;
+                    self.stack.push(Value::Global(module, name));
+                }
+                0x93 => {
+                    let name = self.pop()?;
+                    let module = self.pop()?;
+                    match (module, name) {
+                        (Value::Str(module), Value::Str(name)) => {
+                            self.stack.push(Value::Global(module, name))
+                        }
+                        _ => return Err(invalid_data("invalid global in pickle".to_string())),
+                    }
+                }
+                // REDUCE, NEWOBJ
+                b'R' => {
+                    let args = self.pop()?;
+                    let function = self.pop()?;
+                    let value = self.reduce(function, args)?;
+                    self.stack.push(value);
+                }
+                0x81 => {
+                    self.pop()?;
+                    self.pop()?;
+                    self.stack.push(Value::Other);
+                }
+                // BUILD, the state of the objects is not needed.
+                b'b' => {
+                    self.pop()?;
+                }
+                // BINPERSID
+                b'Q' => {
+                    let pid = self.pop()?;
+                    let value = self.persistent_load(pid)?;
+                    self.stack.push(value);
+                }
+                _ => {
+                    return Err(invalid_data(format!(
+                        "unsupported pickle opcode {:#04x}",
+                        opcode
+                    )))
+                }
+            }
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,189 @@
+use super::{f16_to_f32, StateDict};
+use crate::nn::{Linear, ModelStatus};
+use ndarray::array;
+
+/// A checkpoint in the format of `torch.save`, equivalent to:
+///
+/// ```python
+/// weight = torch.arange(6.).reshape(2, 3)
+/// torch.save({
+///     "model": OrderedDict([
+///         ("fc.weight", nn.Parameter(weight)),
+///         ("fc.bias", torch.tensor([9., -1.5, 2.25], dtype=torch.float64)[1:]),
+///         ("bn.num_batches_tracked", torch.tensor(42)),
+///         ("fc.weight_t", weight.t()),
+///     ]),
+///     "epoch": 3,
+///     "lr": 0.1,
+///     "flag": True,
+/// }, "checkpoint.pt")
+/// ```
+const CHECKPOINT: &[u8] = &[
+    0x50, 0x4B, 0x03, 0x04, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x21, 0x00, 0x9A, 0xAC,
+    0x3E, 0xE3, 0x0D, 0x02, 0x00, 0x00, 0x0D, 0x02, 0x00, 0x00, 0x10, 0x00, 0x08, 0x00, 0x61, 0x72,
+    0x63, 0x68, 0x69, 0x76, 0x65, 0x2F, 0x64, 0x61, 0x74, 0x61, 0x2E, 0x70, 0x6B, 0x6C, 0x34, 0x12,
+    0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x02, 0x7D, 0x71, 0x00, 0x28, 0x58, 0x05, 0x00, 0x00,
+    0x00, 0x6D, 0x6F, 0x64, 0x65, 0x6C, 0x71, 0x01, 0x63, 0x63, 0x6F, 0x6C, 0x6C, 0x65, 0x63, 0x74,
+    0x69, 0x6F, 0x6E, 0x73, 0x0A, 0x4F, 0x72, 0x64, 0x65, 0x72, 0x65, 0x64, 0x44, 0x69, 0x63, 0x74,
+    0x0A, 0x71, 0x02, 0x29, 0x52, 0x71, 0x03, 0x28, 0x58, 0x09, 0x00, 0x00, 0x00, 0x66, 0x63, 0x2E,
+    0x77, 0x65, 0x69, 0x67, 0x68, 0x74, 0x71, 0x04, 0x63, 0x74, 0x6F, 0x72, 0x63, 0x68, 0x2E, 0x5F,
+    0x75, 0x74, 0x69, 0x6C, 0x73, 0x0A, 0x5F, 0x72, 0x65, 0x62, 0x75, 0x69, 0x6C, 0x64, 0x5F, 0x70,
+    0x61, 0x72, 0x61, 0x6D, 0x65, 0x74, 0x65, 0x72, 0x0A, 0x71, 0x05, 0x63, 0x74, 0x6F, 0x72, 0x63,
+    0x68, 0x2E, 0x5F, 0x75, 0x74, 0x69, 0x6C, 0x73, 0x0A, 0x5F, 0x72, 0x65, 0x62, 0x75, 0x69, 0x6C,
+    0x64, 0x5F, 0x74, 0x65, 0x6E, 0x73, 0x6F, 0x72, 0x5F, 0x76, 0x32, 0x0A, 0x71, 0x06, 0x28, 0x28,
+    0x58, 0x07, 0x00, 0x00, 0x00, 0x73, 0x74, 0x6F, 0x72, 0x61, 0x67, 0x65, 0x71, 0x07, 0x63, 0x74,
+    0x6F, 0x72, 0x63, 0x68, 0x0A, 0x46, 0x6C, 0x6F, 0x61, 0x74, 0x53, 0x74, 0x6F, 0x72, 0x61, 0x67,
+    0x65, 0x0A, 0x71, 0x08, 0x58, 0x01, 0x00, 0x00, 0x00, 0x30, 0x71, 0x09, 0x58, 0x03, 0x00, 0x00,
+    0x00, 0x63, 0x70, 0x75, 0x71, 0x0A, 0x4B, 0x06, 0x74, 0x71, 0x0B, 0x51, 0x4B, 0x00, 0x4B, 0x02,
+    0x4B, 0x03, 0x86, 0x71, 0x0C, 0x4B, 0x03, 0x4B, 0x01, 0x86, 0x71, 0x0D, 0x89, 0x68, 0x02, 0x29,
+    0x52, 0x71, 0x0E, 0x74, 0x71, 0x0F, 0x52, 0x71, 0x10, 0x88, 0x68, 0x02, 0x29, 0x52, 0x71, 0x11,
+    0x87, 0x71, 0x12, 0x52, 0x71, 0x13, 0x58, 0x07, 0x00, 0x00, 0x00, 0x66, 0x63, 0x2E, 0x62, 0x69,
+    0x61, 0x73, 0x71, 0x14, 0x68, 0x06, 0x28, 0x28, 0x68, 0x07, 0x63, 0x74, 0x6F, 0x72, 0x63, 0x68,
+    0x0A, 0x44, 0x6F, 0x75, 0x62, 0x6C, 0x65, 0x53, 0x74, 0x6F, 0x72, 0x61, 0x67, 0x65, 0x0A, 0x71,
+    0x15, 0x58, 0x01, 0x00, 0x00, 0x00, 0x31, 0x71, 0x16, 0x68, 0x0A, 0x4B, 0x03, 0x74, 0x71, 0x17,
+    0x51, 0x4B, 0x01, 0x4B, 0x02, 0x85, 0x71, 0x18, 0x4B, 0x01, 0x85, 0x71, 0x19, 0x89, 0x68, 0x02,
+    0x29, 0x52, 0x71, 0x1A, 0x74, 0x71, 0x1B, 0x52, 0x71, 0x1C, 0x58, 0x16, 0x00, 0x00, 0x00, 0x62,
+    0x6E, 0x2E, 0x6E, 0x75, 0x6D, 0x5F, 0x62, 0x61, 0x74, 0x63, 0x68, 0x65, 0x73, 0x5F, 0x74, 0x72,
+    0x61, 0x63, 0x6B, 0x65, 0x64, 0x71, 0x1D, 0x68, 0x06, 0x28, 0x28, 0x68, 0x07, 0x63, 0x74, 0x6F,
+    0x72, 0x63, 0x68, 0x0A, 0x4C, 0x6F, 0x6E, 0x67, 0x53, 0x74, 0x6F, 0x72, 0x61, 0x67, 0x65, 0x0A,
+    0x71, 0x1E, 0x58, 0x01, 0x00, 0x00, 0x00, 0x32, 0x71, 0x1F, 0x68, 0x0A, 0x4B, 0x01, 0x74, 0x71,
+    0x20, 0x51, 0x4B, 0x00, 0x29, 0x29, 0x89, 0x68, 0x02, 0x29, 0x52, 0x71, 0x21, 0x74, 0x71, 0x22,
+    0x52, 0x71, 0x23, 0x58, 0x0B, 0x00, 0x00, 0x00, 0x66, 0x63, 0x2E, 0x77, 0x65, 0x69, 0x67, 0x68,
+    0x74, 0x5F, 0x74, 0x71, 0x24, 0x68, 0x06, 0x28, 0x28, 0x68, 0x07, 0x68, 0x08, 0x68, 0x09, 0x68,
+    0x0A, 0x4B, 0x06, 0x74, 0x71, 0x25, 0x51, 0x4B, 0x00, 0x4B, 0x03, 0x4B, 0x02, 0x86, 0x71, 0x26,
+    0x4B, 0x01, 0x4B, 0x03, 0x86, 0x71, 0x27, 0x89, 0x68, 0x02, 0x29, 0x52, 0x71, 0x28, 0x74, 0x71,
+    0x29, 0x52, 0x71, 0x2A, 0x75, 0x58, 0x05, 0x00, 0x00, 0x00, 0x65, 0x70, 0x6F, 0x63, 0x68, 0x71,
+    0x2B, 0x4B, 0x03, 0x58, 0x02, 0x00, 0x00, 0x00, 0x6C, 0x72, 0x71, 0x2C, 0x47, 0x3F, 0xB9, 0x99,
+    0x99, 0x99, 0x99, 0x99, 0x9A, 0x58, 0x04, 0x00, 0x00, 0x00, 0x66, 0x6C, 0x61, 0x67, 0x71, 0x2D,
+    0x88, 0x75, 0x2E, 0x50, 0x4B, 0x03, 0x04, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x21,
+    0x00, 0x17, 0x90, 0xE7, 0x91, 0x18, 0x00, 0x00, 0x00, 0x18, 0x00, 0x00, 0x00, 0x0E, 0x00, 0x08,
+    0x00, 0x61, 0x72, 0x63, 0x68, 0x69, 0x76, 0x65, 0x2F, 0x64, 0x61, 0x74, 0x61, 0x2F, 0x30, 0x34,
+    0x12, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x3F, 0x00,
+    0x00, 0x00, 0x40, 0x00, 0x00, 0x40, 0x40, 0x00, 0x00, 0x80, 0x40, 0x00, 0x00, 0xA0, 0x40, 0x50,
+    0x4B, 0x03, 0x04, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x21, 0x00, 0x54, 0x9C, 0xB9,
+    0x76, 0x18, 0x00, 0x00, 0x00, 0x18, 0x00, 0x00, 0x00, 0x0E, 0x00, 0x08, 0x00, 0x61, 0x72, 0x63,
+    0x68, 0x69, 0x76, 0x65, 0x2F, 0x64, 0x61, 0x74, 0x61, 0x2F, 0x31, 0x34, 0x12, 0x04, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x22, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0xF8, 0xBF, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x40, 0x50, 0x4B, 0x03, 0x04, 0x14,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x21, 0x00, 0xF7, 0xA1, 0x94, 0x0D, 0x08, 0x00, 0x00,
+    0x00, 0x08, 0x00, 0x00, 0x00, 0x0E, 0x00, 0x08, 0x00, 0x61, 0x72, 0x63, 0x68, 0x69, 0x76, 0x65,
+    0x2F, 0x64, 0x61, 0x74, 0x61, 0x2F, 0x32, 0x34, 0x12, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x2A,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x50, 0x4B, 0x03, 0x04, 0x14, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x21, 0x00, 0xD1, 0x9E, 0x67, 0x55, 0x02, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00,
+    0x00, 0x0F, 0x00, 0x08, 0x00, 0x61, 0x72, 0x63, 0x68, 0x69, 0x76, 0x65, 0x2F, 0x76, 0x65, 0x72,
+    0x73, 0x69, 0x6F, 0x6E, 0x34, 0x12, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x33, 0x0A, 0x50, 0x4B,
+    0x01, 0x02, 0x14, 0x03, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x21, 0x00, 0x9A, 0xAC,
+    0x3E, 0xE3, 0x0D, 0x02, 0x00, 0x00, 0x0D, 0x02, 0x00, 0x00, 0x10, 0x00, 0x08, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x01, 0x00, 0x00, 0x00, 0x00, 0x61, 0x72, 0x63, 0x68,
+    0x69, 0x76, 0x65, 0x2F, 0x64, 0x61, 0x74, 0x61, 0x2E, 0x70, 0x6B, 0x6C, 0x34, 0x12, 0x04, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x50, 0x4B, 0x01, 0x02, 0x14, 0x03, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x21, 0x00, 0x17, 0x90, 0xE7, 0x91, 0x18, 0x00, 0x00, 0x00, 0x18, 0x00, 0x00, 0x00,
+    0x0E, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x01, 0x43, 0x02,
+    0x00, 0x00, 0x61, 0x72, 0x63, 0x68, 0x69, 0x76, 0x65, 0x2F, 0x64, 0x61, 0x74, 0x61, 0x2F, 0x30,
+    0x34, 0x12, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x50, 0x4B, 0x01, 0x02, 0x14, 0x03, 0x14, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x21, 0x00, 0x54, 0x9C, 0xB9, 0x76, 0x18, 0x00, 0x00, 0x00,
+    0x18, 0x00, 0x00, 0x00, 0x0E, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x80, 0x01, 0x8F, 0x02, 0x00, 0x00, 0x61, 0x72, 0x63, 0x68, 0x69, 0x76, 0x65, 0x2F, 0x64, 0x61,
+    0x74, 0x61, 0x2F, 0x31, 0x34, 0x12, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x50, 0x4B, 0x01, 0x02,
+    0x14, 0x03, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x21, 0x00, 0xF7, 0xA1, 0x94, 0x0D,
+    0x08, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x0E, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x80, 0x01, 0xDB, 0x02, 0x00, 0x00, 0x61, 0x72, 0x63, 0x68, 0x69, 0x76,
+    0x65, 0x2F, 0x64, 0x61, 0x74, 0x61, 0x2F, 0x32, 0x34, 0x12, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x50, 0x4B, 0x01, 0x02, 0x14, 0x03, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x21, 0x00,
+    0xD1, 0x9E, 0x67, 0x55, 0x02, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x0F, 0x00, 0x08, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x01, 0x17, 0x03, 0x00, 0x00, 0x61, 0x72,
+    0x63, 0x68, 0x69, 0x76, 0x65, 0x2F, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6F, 0x6E, 0x34, 0x12, 0x04,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x50, 0x4B, 0x05, 0x06, 0x00, 0x00, 0x00, 0x00, 0x05, 0x00, 0x05,
+    0x00, 0x57, 0x01, 0x00, 0x00, 0x4E, 0x03, 0x00, 0x00, 0x00, 0x00,
+];
+
+#[test]
+fn read() {
+    let state = StateDict::read(CHECKPOINT).unwrap();
+
+    assert_eq!(
+        state.names().collect::<Vec<_>>(),
+        vec![
+            "model.fc.weight",
+            "model.fc.bias",
+            "model.bn.num_batches_tracked",
+            "model.fc.weight_t"
+        ]
+    );
+    assert_eq!(
+        state.get("model.fc.weight").unwrap(),
+        array![[0., 1., 2.], [3., 4., 5.]].into_dyn()
+    );
+    assert_eq!(
+        state.get("model.fc.bias").unwrap(),
+        array![-1.5, 2.25].into_dyn()
+    );
+    assert_eq!(
+        state.get("model.bn.num_batches_tracked").unwrap().sum(),
+        42.
+    );
+    assert_eq!(
+        state.get("model.fc.weight_t").unwrap(),
+        array![[0., 3.], [1., 4.], [2., 5.]].into_dyn()
+    );
+    assert!(state.get("epoch").is_none());
+}
+
+#[test]
+fn load() {
+    let state = StateDict::read(CHECKPOINT).unwrap();
+    let fc = Linear::new(3, 2);
+
+    state.load("model.fc.weight", &fc.weight).unwrap();
+    state.load("model.fc.bias", &fc.bias).unwrap();
+    assert_eq!(*fc.weight.data(), array![[0., 1., 2.], [3., 4., 5.]]);
+    assert_eq!(*fc.bias.data(), array![-1.5, 2.25]);
+
+    assert!(state.load("model.fc.weight_t", &fc.weight).is_err());
+    assert!(state.load("missing", &fc.weight).is_err());
+}
+
+#[test]
+fn load_parameters() {
+    let state = StateDict::read(CHECKPOINT).unwrap();
+
+    let mut status = ModelStatus::default();
+    let _fc = status.register(Linear::new(3, 2));
+    state.load_parameters(&mut status.parameters()).unwrap();
+    let params = status.parameters();
+    assert_eq!(
+        params[0].data,
+        array![[0., 1., 2.], [3., 4., 5.]].into_dyn()
+    );
+    assert_eq!(params[1].data, array![-1.5, 2.25].into_dyn());
+
+    // The second layer would need a tensor of shape (2, 2).
+    let mut status = ModelStatus::default();
+    let _first = status.register(Linear::new(3, 2));
+    let _second = status.register(Linear::new(2, 2));
+    assert!(state.load_parameters(&mut status.parameters()).is_err());
+}
+
+#[test]
+fn errors() {
+    assert!(StateDict::read(&b"\x80\x02}q\x00."[..]).is_err());
+
+    // Truncates the pickle of the checkpoint.
+    let mut corrupted = CHECKPOINT.to_vec();
+    let pickle = corrupted
+        .windows(4)
+        .position(|window| window == [0x80, 0x02, 0x7D, 0x71])
+        .unwrap();
+    corrupted[pickle + 1] = 0xFF;
+    corrupted[pickle + 2] = 0xFF;
+    assert!(StateDict::read(&corrupted[..]).is_err());
+}
+
+#[test]
+fn half_precision() {
+    assert_eq!(f16_to_f32(0x3C00), 1.);
+    assert_eq!(f16_to_f32(0xC000), -2.);
+    assert_eq!(f16_to_f32(0x0001), 2f32.powi(-24));
+    assert_eq!(f16_to_f32(0x7C00), f32::INFINITY);
+    assert!(f16_to_f32(0x7E00).is_nan());
+}
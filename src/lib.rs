@@ -186,6 +186,11 @@
 //!}                                                // ---+             |- Graph is freed and
 //!                                                 // -----------------+  only leaves remain
 //!```
+//!
+//! A graph can also be handled explicitly, by turning its output into a [`Graph`] with
+//! `.into_graph()`. The handle evaluates, differentiates and frees the graph as a whole. Graphs
+//! with multiple outputs, such as the losses of a multi-task model, are built with
+//! [`Graph::join()`].
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/neuronika/neuronika/main/misc/neuronika_brain.svg"
 )]
@@ -193,20 +198,52 @@
     html_favicon_url = "https://raw.githubusercontent.com/neuronika/neuronika/main/misc/neuronika_brain.ico"
 )]
 
+pub mod autograd;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod calibration;
 pub mod data;
+pub mod decoding;
+pub mod distributed;
+pub mod io;
+pub mod logging;
+pub mod metrics;
+pub mod models;
 pub mod nn;
 pub mod optim;
+mod parallel;
+mod print;
+pub mod profiler;
+mod random;
+pub mod rl;
+pub mod trainer;
+pub mod tune;
 mod variable;
-use ndarray::{Array, Array2, Dimension, Ix1, Ix2, ShapeBuilder};
+use ndarray::{Array, Array2, ArrayBase, ArrayD, Dimension, Ix1, Ix2, ShapeBuilder};
 use ndarray_rand::rand_distr::Uniform;
 use ndarray_rand::RandomExt;
+pub use parallel::{
+    is_deterministic, num_threads, set_blas_num_threads_fn, set_deterministic, set_num_threads,
+};
+pub use print::{print_options, set_print_options, PrintOptions};
+pub use random::manual_seed;
+use std::{cell::RefCell, rc::Rc};
+use variable::{check_cat, check_stack, IndexInput, Input, InputBackward};
 pub use variable::{
-    Backward, Cache, Cat, Convolve, ConvolveWithGroups, Data, Eval, Forward, Gradient, MatMatMul,
-    MatMatMulT, MatVecMul, Overwrite, Param, Stack, Var, VarDiff, VecMatMul, VecVecMul,
+    Backward, Cache, Cat, Convolve, ConvolveWithGroups, Data, Eval, Forward, Gradient, Graph,
+    IndexData, IndexVar, MatMatMul, MatMatMulT, MatVecMul, Maximum, Minimum, Overwrite, Param, Pow,
+    ShapeError, Shaped, Stack, Var, VarDiff, VecMatMul, VecVecMul,
 };
-use variable::{Input, InputBackward};
 
-/// Creates a variable from a **[ndarray]** array that owns its data.
+/// Creates a variable from a **[ndarray]** array.
+///
+/// An array that owns its data, such as an [`Array`] or a [`CowArray`](ndarray::CowArray) in its
+/// owned variant, is moved into the variable without copying its elements. Any other array, such
+/// as a view, is copied, as the data of a variable must live as long as the graph it belongs to.
+///
+/// Producers of large batches, like memory-mapped datasets, can thus hand out
+/// [`CowArray`](ndarray::CowArray)s and have the owned ones fed at no extra cost. To avoid copies
+/// altogether, the variable can instead share its data with the producer, see [`from_shared()`].
 ///
 /// # Examples
 ///
@@ -218,9 +255,63 @@ use variable::{Input, InputBackward};
 /// let t = neuronika::from_ndarray(a.clone());
 ///
 /// assert_eq!(*t.data(), a);
+///
+/// let pointer = a.as_ptr();
+/// let t = neuronika::from_ndarray(ndarray::CowArray::from(a));
+///
+/// assert_eq!(t.data().as_ptr(), pointer);
+/// ```
+pub fn from_ndarray<S, D>(array: ArrayBase<S, D>) -> Var<Input<D>>
+where
+    S: ndarray::Data<Elem = f32>,
+    D: Dimension,
+{
+    Input::new(array.into_owned())
+}
+
+/// Creates a variable whose data is shared with the caller.
+///
+/// The variable holds `data` rather than a copy of it, so that the elements of the array, and the
+/// array itself, can be changed through `data` between evaluations, without going through the
+/// variable. A producer of batches, such as a memory-mapped dataset, can then decode each batch
+/// directly into the data of the variable, or move a new array in, which doesn't copy its
+/// elements either.
+///
+/// # Examples
+///
+/// ```
+/// use std::{cell::RefCell, rc::Rc};
+///
+/// let batch = Rc::new(RefCell::new(ndarray::array![[1., 2.], [3., 4.]]));
+/// let pointer = batch.borrow().as_ptr();
+///
+/// let x = neuronika::from_shared(batch.clone());
+/// let y = x.clone() * 2.;
+/// assert_eq!(x.data().as_ptr(), pointer);
+///
+/// // The next batch is written in place.
+/// batch.borrow_mut().fill(1.);
+/// y.forward();
+/// assert_eq!(*y.data(), ndarray::array![[2., 2.], [2., 2.]]);
+/// ```
+pub fn from_shared<D: Dimension>(data: Rc<RefCell<Array<f32, D>>>) -> Var<Input<D>> {
+    Input::shared(data)
+}
+
+/// Creates an index variable from a **[ndarray]** array of integers that owns its data.
+///
+/// Index variables hold token ids, class labels and any other data used to select elements, see
+/// [`IndexVar`].
+///
+/// # Examples
+///
+/// ```
+/// let tokens = neuronika::indices(ndarray::array![[3, 1, 4], [1, 5, 9]]);
+///
+/// assert_eq!(*tokens.data(), ndarray::array![[3, 1, 4], [1, 5, 9]]);
 /// ```
-pub fn from_ndarray<D: Dimension>(array: Array<f32, D>) -> Var<Input<D>> {
-    Input::new(array)
+pub fn indices<D: Dimension>(array: Array<i64, D>) -> IndexVar<IndexInput<D>> {
+    IndexInput::new(array)
 }
 
 /// Creates a variable with zeroed data.
@@ -296,7 +387,7 @@ pub fn full<D: Dimension, Sh: ShapeBuilder<Dim = D>>(shape: Sh, elem: f32) -> Va
 /// assert_eq!(t.data().shape(), &[4, 5, 6]);
 /// ```
 pub fn rand<D: Dimension, Sh: ShapeBuilder<Dim = D>>(shape: Sh) -> Var<Input<D>> {
-    Input::new(Array::random(shape, Uniform::new(0., 1.)))
+    random::with_rng(|rng| Input::new(Array::random_using(shape, Uniform::new(0., 1.), rng)))
 }
 
 /// Creates a variable with an identity matrix of size *n*.
@@ -442,6 +533,193 @@ where
     Stack::stack(lhs, rhs, axis)
 }
 
+/// Computes the element-wise maximum between `lhs` and `rhs`, broadcasting them to a common
+/// shape.
+///
+/// The gradient of each element flows to the larger operand, and is split evenly between the two
+/// when they are equal. This is handy to clamp a variable against another one or to build hinge
+/// losses.
+///
+/// # Arguments
+///
+/// * `lhs` - variable.
+///
+/// * `rhs` - other variable.
+///
+/// ```
+/// let scores = neuronika::from_ndarray(ndarray::array![-1., 0.5, 3.]).requires_grad();
+/// let margins = neuronika::from_ndarray(ndarray::array![0., 1., 0.]);
+///
+/// let hinge = neuronika::maximum(scores.clone(), margins);
+/// hinge.forward();
+/// assert_eq!(*hinge.data(), ndarray::array![0., 1., 3.]);
+///
+/// hinge.backward(1.);
+/// assert_eq!(*scores.grad(), ndarray::array![0., 0., 1.]);
+/// ```
+///
+/// # Panics
+///
+/// If the variables cannot be broadcast to a common shape.
+pub fn maximum<Lhs, Rhs>(lhs: Lhs, rhs: Rhs) -> <Lhs as Maximum<Rhs>>::Output
+where
+    Lhs: Maximum<Rhs>,
+{
+    Maximum::maximum(lhs, rhs)
+}
+
+/// Computes the element-wise minimum between `lhs` and `rhs`, broadcasting them to a common
+/// shape.
+///
+/// The gradient of each element flows to the smaller operand, and is split evenly between the
+/// two when they are equal.
+///
+/// # Arguments
+///
+/// * `lhs` - variable.
+///
+/// * `rhs` - other variable.
+///
+/// ```
+/// let x = neuronika::from_ndarray(ndarray::array![[-2., 0.5], [3., 1.]]);
+/// let upper = neuronika::from_ndarray(ndarray::array![1., 2.]);
+///
+/// let clamped = neuronika::minimum(x, upper);
+/// clamped.forward();
+/// assert_eq!(*clamped.data(), ndarray::array![[-2., 0.5], [1., 1.]]);
+/// ```
+///
+/// # Panics
+///
+/// If the variables cannot be broadcast to a common shape.
+pub fn minimum<Lhs, Rhs>(lhs: Lhs, rhs: Rhs) -> <Lhs as Minimum<Rhs>>::Output
+where
+    Lhs: Minimum<Rhs>,
+{
+    Minimum::minimum(lhs, rhs)
+}
+
+/// Raises each element of `base` to the power given by the corresponding element of `exponent`,
+/// broadcasting them to a common shape.
+///
+/// Unlike [`Var::pow()`], whose exponent is a fixed integer, here the exponent is a variable and
+/// can be learned. The gradient with respect to it is `base.powf(exponent) * base.ln()`, so the
+/// base should be positive wherever the exponent requires a gradient. Where the power is zero,
+/// such gradient is taken to be zero.
+///
+/// # Arguments
+///
+/// * `base` - variable.
+///
+/// * `exponent` - variable.
+///
+/// ```
+/// let base = neuronika::from_ndarray(ndarray::array![1., 2., 4.]);
+/// let exponent = neuronika::full(3, 0.5).requires_grad();
+///
+/// let root = neuronika::pow(base, exponent.clone());
+/// root.forward();
+/// assert_eq!(*root.data(), ndarray::array![1., 2_f32.sqrt(), 2.]);
+///
+/// root.backward(1.);
+/// assert_eq!(exponent.grad()[0], 0.);
+/// assert_eq!(exponent.grad()[2], 2. * 4_f32.ln());
+/// ```
+///
+/// # Panics
+///
+/// If the variables cannot be broadcast to a common shape.
+pub fn pow<Lhs, Rhs>(base: Lhs, exponent: Rhs) -> <Lhs as Pow<Rhs>>::Output
+where
+    Lhs: Pow<Rhs>,
+{
+    Pow::powf(base, exponent)
+}
+
+/// Checked version of [`cat`].
+///
+/// # Errors
+///
+/// Returns a [`ShapeError`] if the variables have mismatching shapes, apart from along `axis`, or
+/// if `axis` is out of bounds.
+///
+/// ```
+/// let x = neuronika::ones((2, 3));
+/// let y = neuronika::ones((2, 4));
+///
+/// assert_eq!(neuronika::try_cat(x.clone(), y.clone(), 1).unwrap().data().shape(), &[2, 7]);
+/// assert!(neuronika::try_cat(x, y, 0).is_err());
+/// ```
+pub fn try_cat<Lhs, Rhs>(
+    lhs: Lhs,
+    rhs: Rhs,
+    axis: usize,
+) -> Result<<Lhs as Cat<Rhs>>::Output, ShapeError>
+where
+    Lhs: Cat<Rhs> + Shaped,
+    Rhs: Shaped,
+{
+    check_cat(&lhs.shape(), &rhs.shape(), axis)?;
+    Ok(Cat::cat(lhs, rhs, axis))
+}
+
+/// Checked version of [`stack`].
+///
+/// # Errors
+///
+/// Returns a [`ShapeError`] if the variables have different shapes or if `axis` is out of bounds.
+pub fn try_stack<Lhs, Rhs>(
+    lhs: Lhs,
+    rhs: Rhs,
+    axis: usize,
+) -> Result<<Lhs as Stack<Rhs>>::Output, ShapeError>
+where
+    Lhs: Stack<Rhs> + Shaped,
+    Rhs: Shaped,
+{
+    check_stack(&lhs.shape(), &rhs.shape(), axis)?;
+    Ok(Stack::stack(lhs, rhs, axis))
+}
+
+/// Back-propagates through the computational graph of `loss` and returns the gradients of the
+/// loss with respect to `params` as owned arrays.
+///
+/// The gradients of all the differentiable leaves of `loss` are zeroed before the backward pass,
+/// so that the returned arrays do not contain contributions from previous ones. The data of the
+/// parameters is left untouched, as is the state of any optimizer. This is useful to write custom
+/// training loops or to manipulate the gradients directly.
+///
+/// The forward pass must have been computed beforehand.
+///
+/// # Arguments
+///
+/// * `loss` - variable to differentiate, its gradient is seeded with ones.
+///
+/// * `params` - parameters with respect to which the gradients are computed.
+///
+/// ```
+/// use ndarray::array;
+///
+/// let w = neuronika::from_ndarray(array![1., 2.]).requires_grad();
+/// let x = neuronika::from_ndarray(array![3., 4.]);
+/// let loss = (w * x).sum();
+/// loss.forward();
+///
+/// let grads = neuronika::grad(&loss, &loss.parameters());
+///
+/// assert_eq!(grads[0], array![3., 4.].into_dyn());
+/// ```
+pub fn grad<T, U>(loss: &VarDiff<T, U>, params: &[Param]) -> Vec<ArrayD<f32>>
+where
+    T: Data + ?Sized + 'static,
+    U: Gradient<Dim = T::Dim> + ?Sized + 'static,
+{
+    loss.zero_grad();
+    loss.backward(1.);
+
+    params.iter().map(|param| param.grad.to_owned()).collect()
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -453,6 +731,20 @@ mod tests {
         assert_eq!(*t.data(), a);
     }
 
+    #[test]
+    fn from_ndarray_cow() {
+        use super::from_ndarray;
+        let a = ndarray::array![[1., 2.], [3., 4.]];
+
+        let borrowed = from_ndarray(ndarray::CowArray::from(a.view()));
+        assert_eq!(*borrowed.data(), a);
+        assert_ne!(borrowed.data().as_ptr(), a.as_ptr());
+
+        let pointer = a.as_ptr();
+        let owned = from_ndarray(ndarray::CowArray::from(a));
+        assert_eq!(owned.data().as_ptr(), pointer);
+    }
+
     #[test]
     fn zeros() {
         use super::zeros;
@@ -552,4 +844,21 @@ mod tests {
         let tensor = range(0., 5., 1.);
         assert!(*tensor.data() == ndarray::arr1(&[0., 1., 2., 3., 4.]))
     }
+
+    #[test]
+    fn grad_test() {
+        use super::*;
+        let w = from_ndarray(ndarray::arr1(&[1., 2.])).requires_grad();
+        let b = from_ndarray(ndarray::arr1(&[0.5])).requires_grad();
+        let loss = (w.clone() * w.clone() + b.clone()).sum();
+        loss.forward();
+
+        // Gradients from previous passes are not accumulated.
+        loss.backward(1.);
+        let grads = grad(&loss, &[w.parameters().remove(0), b.parameters().remove(0)]);
+
+        assert_eq!(grads[0], ndarray::arr1(&[2., 4.]).into_dyn());
+        assert_eq!(grads[1], ndarray::arr1(&[2.]).into_dyn());
+        assert_eq!(*w.data(), ndarray::arr1(&[1., 2.]));
+    }
 }
@@ -0,0 +1,232 @@
+use crate::trainer::{Callback, Control, EpochLogs};
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+/// Format of the records written by a [`MetricsLogger`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// Comma-separated values, with a header naming the columns.
+    Csv,
+    /// One JSON object per line.
+    JsonLines,
+}
+
+/// Specifies when a [`MetricsLogger`] flushes its records to the file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Flush {
+    /// After every record.
+    Always,
+    /// After every given number of records.
+    Every(usize),
+    /// Only when [`.flush()`](MetricsLogger::flush()) is called or the logger is dropped.
+    Manual,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ MetricsLogger ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Appends named values to a CSV or JSON lines file.
+///
+/// Each record holds a step followed by some named values. In the CSV format the columns are
+/// fixed by the first record; later records can omit some of them, which are left empty, but
+/// cannot add new ones.
+///
+/// The logger is also a [`Callback`]: when registered to a [`Trainer`](crate::trainer::Trainer)
+/// it writes a record at the end of each epoch with the training and validation losses and
+/// metrics, named as in [`EpochLogs::value()`].
+///
+/// ```no_run
+/// use neuronika::logging::{Flush, Format, MetricsLogger};
+///
+/// let mut logger = MetricsLogger::new("metrics.csv", Format::Csv)
+///     .unwrap()
+///     .with_flush(Flush::Every(10));
+///
+/// for step in 0..100 {
+///     logger.log(step, &[("loss", 1. / (step + 1) as f32)]).unwrap();
+/// }
+/// ```
+pub struct MetricsLogger {
+    file: BufWriter<File>,
+    format: Format,
+    flush: Flush,
+    columns: Option<Vec<String>>,
+    pending: usize,
+}
+
+impl MetricsLogger {
+    /// Creates a new logger that appends its records to the file at `path`, which is created if
+    /// it does not exist. Records are flushed after each write.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be opened.
+    pub fn new<P: AsRef<Path>>(path: P, format: Format) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let columns = if format == Format::Csv && file.metadata()?.len() > 0 {
+            // The header is already there, the columns will be taken from the first record.
+            Some(Vec::new())
+        } else {
+            None
+        };
+
+        Ok(Self {
+            file: BufWriter::new(file),
+            format,
+            flush: Flush::Always,
+            columns,
+            pending: 0,
+        })
+    }
+
+    /// Sets when the records are flushed.
+    pub fn with_flush(mut self, flush: Flush) -> Self {
+        self.flush = flush;
+        self
+    }
+
+    /// Writes a record.
+    ///
+    /// # Errors
+    ///
+    /// If the record cannot be written or, in the CSV format, if it holds a value that is not
+    /// in the columns.
+    pub fn log(&mut self, step: usize, values: &[(&str, f32)]) -> io::Result<()> {
+        match self.format {
+            Format::Csv => self.write_csv(step, values)?,
+            Format::JsonLines => self.write_json(step, values)?,
+        }
+
+        self.pending += 1;
+        let flush = match self.flush {
+            Flush::Always => true,
+            Flush::Every(records) => self.pending >= records,
+            Flush::Manual => false,
+        };
+        if flush {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Flushes the buffered records to the file.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.pending = 0;
+        self.file.flush()
+    }
+
+    fn write_csv(&mut self, step: usize, values: &[(&str, f32)]) -> io::Result<()> {
+        let columns = match &mut self.columns {
+            Some(columns) => columns,
+            columns @ None => {
+                let names: Vec<String> = values.iter().map(|(name, _)| name.to_string()).collect();
+                writeln!(
+                    self.file,
+                    "step{}",
+                    names
+                        .iter()
+                        .map(|name| format!(",{}", name))
+                        .collect::<String>()
+                )?;
+                columns.insert(names)
+            }
+        };
+        if columns.is_empty() {
+            // Appending to an existing file: its columns are assumed to match the first record.
+            columns.extend(values.iter().map(|(name, _)| name.to_string()));
+        }
+
+        if let Some((name, _)) = values
+            .iter()
+            .find(|(name, _)| !columns.iter().any(|column| column == name))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("error: {} is not a column of the CSV file.", name),
+            ));
+        }
+
+        let mut line = step.to_string();
+        for column in columns.iter() {
+            line.push(',');
+            if let Some((_, value)) = values.iter().find(|(name, _)| name == column) {
+                line.push_str(&value.to_string());
+            }
+        }
+        writeln!(self.file, "{}", line)
+    }
+
+    fn write_json(&mut self, step: usize, values: &[(&str, f32)]) -> io::Result<()> {
+        let mut line = format!("{{\"step\":{}", step);
+        for (name, value) in values {
+            line.push_str(&format!(",{}:", json_string(name)));
+            if value.is_finite() {
+                line.push_str(&value.to_string());
+            } else {
+                line.push_str("null");
+            }
+        }
+        line.push('}');
+        writeln!(self.file, "{}", line)
+    }
+}
+
+impl Callback for MetricsLogger {
+    fn on_epoch_end(&mut self, logs: &EpochLogs) -> Control {
+        let mut values = vec![("loss".to_string(), logs.train.loss)];
+        values.extend(logs.train.metrics.iter().cloned());
+        if let Some(validation) = &logs.validation {
+            values.push(("val_loss".to_string(), validation.loss));
+            values.extend(
+                validation
+                    .metrics
+                    .iter()
+                    .map(|(name, value)| (format!("val_{}", name), *value)),
+            );
+        }
+
+        let values: Vec<(&str, f32)> = values
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value))
+            .collect();
+        self.log(logs.epoch, &values)
+            .expect("error: cannot write the metrics of the epoch.");
+        Control::Continue
+    }
+}
+
+impl Drop for MetricsLogger {
+    fn drop(&mut self) {
+        let _ = self.file.flush();
+    }
+}
+
+/// Quotes and escapes `string` as a JSON string.
+fn json_string(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len() + 2);
+    escaped.push('"');
+    for character in string.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            character if character.is_control() => {
+                escaped.push_str(&format!("\\u{:04x}", character as u32))
+            }
+            character => escaped.push(character),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,97 @@
+use super::{json_string, Flush, Format, MetricsLogger};
+use crate::trainer::{Callback, EpochLogs, Logs};
+use std::{fs, path::PathBuf};
+
+fn path(name: &str) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("neuronika-metrics-{}-{}", name, std::process::id()));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn csv() {
+    let path = path("csv");
+    {
+        let mut logger = MetricsLogger::new(&path, Format::Csv).unwrap();
+        logger.log(0, &[("loss", 0.5), ("accuracy", 0.25)]).unwrap();
+        logger.log(1, &[("accuracy", 0.75)]).unwrap();
+        assert!(logger.log(2, &[("f1", 0.75)]).is_err());
+    }
+    {
+        // Appending to an existing file does not repeat the header.
+        let mut logger = MetricsLogger::new(&path, Format::Csv).unwrap();
+        logger.log(2, &[("loss", 0.125), ("accuracy", 1.)]).unwrap();
+    }
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "step,loss,accuracy\n0,0.5,0.25\n1,,0.75\n2,0.125,1\n"
+    );
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn json_lines() {
+    let path = path("jsonl");
+    let mut logger = MetricsLogger::new(&path, Format::JsonLines).unwrap();
+    logger
+        .log(3, &[("loss", 0.5), ("grad \"norm\"", f32::NAN)])
+        .unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "{\"step\":3,\"loss\":0.5,\"grad \\\"norm\\\"\":null}\n"
+    );
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn flush() {
+    let path = path("flush");
+    let mut logger = MetricsLogger::new(&path, Format::JsonLines)
+        .unwrap()
+        .with_flush(Flush::Every(2));
+
+    logger.log(0, &[("loss", 1.)]).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "");
+
+    logger.log(1, &[("loss", 1.)]).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 2);
+
+    let mut logger = logger.with_flush(Flush::Manual);
+    logger.log(2, &[("loss", 1.)]).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 2);
+
+    drop(logger);
+    assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 3);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn callback() {
+    let path = path("callback");
+    let mut logger = MetricsLogger::new(&path, Format::Csv).unwrap();
+    logger.on_epoch_end(&EpochLogs {
+        epoch: 0,
+        train: Logs {
+            loss: 0.5,
+            metrics: vec![("accuracy".to_string(), 0.75)],
+        },
+        validation: Some(Logs {
+            loss: 1.,
+            metrics: vec![("accuracy".to_string(), 0.5)],
+        }),
+    });
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "step,loss,accuracy,val_loss,val_accuracy\n0,0.5,0.75,1,0.5\n"
+    );
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn escape() {
+    assert_eq!(json_string("a\tb\u{1}"), "\"a\\tb\\u0001\"");
+}
@@ -0,0 +1,15 @@
+//! Logging of training runs.
+//!
+//! This module provides writers that persist the evolution of the losses, of the metrics and of
+//! the parameters of a model during training, so that it can be monitored and inspected
+//! afterwards.
+//!
+//! * [`TensorBoardWriter`] - Writes scalars, histograms and images in the
+//!   [TensorBoard](https://www.tensorflow.org/tensorboard) event file format.
+//!
+//! * [`MetricsLogger`] - Appends named values to a CSV or JSON lines file.
+pub use metrics_logger::{Flush, Format, MetricsLogger};
+pub use tensorboard::TensorBoardWriter;
+
+mod metrics_logger;
+mod tensorboard;
@@ -0,0 +1,413 @@
+use crate::{
+    io::{crc32, crc32_with},
+    Param,
+};
+use ndarray::{Array3, ArrayBase, Axis, Dimension};
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Number of buckets of the histograms.
+const HISTOGRAM_BUCKETS: usize = 30;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ TensorBoardWriter ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Writes summaries in the TensorBoard event file format.
+///
+/// Each writer creates a new event file in its log directory, which can be monitored by running
+/// `tensorboard --logdir <log directory>`.
+///
+/// ```no_run
+/// use neuronika::logging::TensorBoardWriter;
+///
+/// let mut writer = TensorBoardWriter::new("runs/experiment").unwrap();
+/// let weight = neuronika::rand((3, 3)).requires_grad();
+///
+/// for step in 0..10 {
+///     writer.add_scalar("loss", 1. / (step + 1) as f32, step).unwrap();
+/// }
+/// writer.add_parameters("weight", &weight.parameters(), 10).unwrap();
+/// writer.flush().unwrap();
+/// ```
+pub struct TensorBoardWriter {
+    path: PathBuf,
+    file: BufWriter<File>,
+}
+
+impl TensorBoardWriter {
+    /// Creates a new writer that stores its events in `log_dir`, which is created if it does not
+    /// exist.
+    ///
+    /// # Errors
+    ///
+    /// If the directory or the event file cannot be created.
+    pub fn new<P: AsRef<Path>>(log_dir: P) -> io::Result<Self> {
+        fs::create_dir_all(&log_dir)?;
+
+        let (seconds, nanos) = {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            (now.as_secs(), now.subsec_nanos())
+        };
+        let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "localhost".to_string());
+        // The nanoseconds tell apart the files of writers created within the same second.
+        let path = log_dir.as_ref().join(format!(
+            "events.out.tfevents.{}.{}.{}",
+            seconds, host, nanos
+        ));
+
+        let mut writer = Self {
+            file: BufWriter::new(File::create(&path)?),
+            path,
+        };
+        let mut event = Event::new(0);
+        event.proto.string(3, "brain.Event:2");
+        writer.write_event(event)?;
+
+        Ok(writer)
+    }
+
+    /// Returns the path of the event file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes a scalar value.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - name of the scalar.
+    ///
+    /// * `value` - value of the scalar.
+    ///
+    /// * `step` - training step associated with the value.
+    pub fn add_scalar(&mut self, tag: &str, value: f32, step: usize) -> io::Result<()> {
+        let mut summary_value = Proto::default();
+        summary_value.string(1, tag);
+        summary_value.float(2, value);
+
+        self.write_summary(summary_value, step)
+    }
+
+    /// Writes a histogram of the elements of a tensor.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - name of the histogram.
+    ///
+    /// * `values` - tensor whose elements are counted.
+    ///
+    /// * `step` - training step associated with the histogram.
+    pub fn add_histogram<S, D>(
+        &mut self,
+        tag: &str,
+        values: &ArrayBase<S, D>,
+        step: usize,
+    ) -> io::Result<()>
+    where
+        S: ndarray::Data<Elem = f32>,
+        D: Dimension,
+    {
+        let mut summary_value = Proto::default();
+        summary_value.string(1, tag);
+        summary_value.message(5, &histogram(values));
+
+        self.write_summary(summary_value, step)
+    }
+
+    /// Writes the histograms of the values and of the gradients of some parameters.
+    ///
+    /// The histograms of the i-th parameter are tagged `<prefix>/<i>/data` and
+    /// `<prefix>/<i>/grad`.
+    pub fn add_parameters(
+        &mut self,
+        prefix: &str,
+        params: &[Param],
+        step: usize,
+    ) -> io::Result<()> {
+        for (i, param) in params.iter().enumerate() {
+            self.add_histogram(&format!("{}/{}/data", prefix, i), &param.data, step)?;
+            self.add_histogram(&format!("{}/{}/grad", prefix, i), &param.grad, step)?;
+        }
+        Ok(())
+    }
+
+    /// Writes an image.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - name of the image.
+    ///
+    /// * `image` - tensor of shape *(channels, height, width)* with values in *[0, 1]*. The
+    ///   channels can be 1 for grayscale, 3 for RGB and 4 for RGBA images.
+    ///
+    /// * `step` - training step associated with the image.
+    ///
+    /// # Panics
+    ///
+    /// If the number of channels is not 1, 3 or 4.
+    pub fn add_image(&mut self, tag: &str, image: &Array3<f32>, step: usize) -> io::Result<()> {
+        let (channels, height, width) = image.dim();
+        assert!(
+            matches!(channels, 1 | 3 | 4),
+            "error: images must have 1, 3 or 4 channels, got {}.",
+            channels
+        );
+
+        let mut proto_image = Proto::default();
+        proto_image.varint(1, height as u64);
+        proto_image.varint(2, width as u64);
+        proto_image.varint(3, channels as u64);
+        proto_image.bytes(4, &png(image));
+
+        let mut summary_value = Proto::default();
+        summary_value.string(1, tag);
+        summary_value.message(4, &proto_image);
+
+        self.write_summary(summary_value, step)
+    }
+
+    /// Flushes the buffered events to the event file.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+
+    fn write_summary(&mut self, summary_value: Proto, step: usize) -> io::Result<()> {
+        let mut summary = Proto::default();
+        summary.message(1, &summary_value);
+
+        let mut event = Event::new(step);
+        event.proto.message(5, &summary);
+        self.write_event(event)
+    }
+
+    /// Writes an event as a TFRecord.
+    fn write_event(&mut self, event: Event) -> io::Result<()> {
+        let data = event.proto.0;
+        let length = (data.len() as u64).to_le_bytes();
+
+        self.file.write_all(&length)?;
+        self.file.write_all(&masked_crc32c(&length).to_le_bytes())?;
+        self.file.write_all(&data)?;
+        self.file.write_all(&masked_crc32c(&data).to_le_bytes())
+    }
+}
+
+impl Drop for TensorBoardWriter {
+    fn drop(&mut self) {
+        let _ = self.file.flush();
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Protocol Buffers ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Minimal encoder of protocol buffers messages.
+#[derive(Default)]
+struct Proto(Vec<u8>);
+
+impl Proto {
+    fn raw_varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.0.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+
+    fn key(&mut self, field: u64, wire_type: u64) {
+        self.raw_varint(field << 3 | wire_type);
+    }
+
+    fn varint(&mut self, field: u64, value: u64) {
+        self.key(field, 0);
+        self.raw_varint(value);
+    }
+
+    fn double(&mut self, field: u64, value: f64) {
+        self.key(field, 1);
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn float(&mut self, field: u64, value: f32) {
+        self.key(field, 5);
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn bytes(&mut self, field: u64, value: &[u8]) {
+        self.key(field, 2);
+        self.raw_varint(value.len() as u64);
+        self.0.extend_from_slice(value);
+    }
+
+    fn string(&mut self, field: u64, value: &str) {
+        self.bytes(field, value.as_bytes());
+    }
+
+    fn message(&mut self, field: u64, value: &Proto) {
+        self.bytes(field, &value.0);
+    }
+
+    fn packed_doubles(&mut self, field: u64, values: &[f64]) {
+        let bytes: Vec<u8> = values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        self.bytes(field, &bytes);
+    }
+}
+
+/// An `Event` message, time-stamped at its creation.
+struct Event {
+    proto: Proto,
+}
+
+impl Event {
+    fn new(step: usize) -> Self {
+        let wall_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64();
+
+        let mut proto = Proto::default();
+        proto.double(1, wall_time);
+        proto.varint(2, step as u64);
+        Self { proto }
+    }
+}
+
+/// Encodes a `HistogramProto` message with uniformly spaced buckets.
+fn histogram<S, D>(values: &ArrayBase<S, D>) -> Proto
+where
+    S: ndarray::Data<Elem = f32>,
+    D: Dimension,
+{
+    let (min, max, sum, sum_squares) = values.iter().fold(
+        (f64::INFINITY, f64::NEG_INFINITY, 0., 0.),
+        |(min, max, sum, sum_squares), &value| {
+            let value = value as f64;
+            (
+                min.min(value),
+                max.max(value),
+                sum + value,
+                sum_squares + value * value,
+            )
+        },
+    );
+    let (min, max) = if values.is_empty() {
+        (0., 0.)
+    } else {
+        (min, max)
+    };
+
+    let width = (max - min) / HISTOGRAM_BUCKETS as f64;
+    let mut counts = vec![0.; HISTOGRAM_BUCKETS];
+    for &value in values.iter() {
+        let bucket = if width > 0. {
+            (((value as f64 - min) / width) as usize).min(HISTOGRAM_BUCKETS - 1)
+        } else {
+            HISTOGRAM_BUCKETS - 1
+        };
+        counts[bucket] += 1.;
+    }
+    let limits: Vec<f64> = (1..=HISTOGRAM_BUCKETS)
+        .map(|bucket| min + width * bucket as f64)
+        .collect();
+
+    let mut proto = Proto::default();
+    proto.double(1, min);
+    proto.double(2, max);
+    proto.double(3, values.len() as f64);
+    proto.double(4, sum);
+    proto.double(5, sum_squares);
+    proto.packed_doubles(6, &limits);
+    proto.packed_doubles(7, &counts);
+    proto
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Checksums ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// CRC-32C (Castagnoli) as used by TFRecords.
+fn crc32c(data: &[u8]) -> u32 {
+    crc32_with(0x82F6_3B78, data)
+}
+
+fn masked_crc32c(data: &[u8]) -> u32 {
+    let crc = crc32c(data);
+    crc.rotate_right(15).wrapping_add(0xA282_EAD8)
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % 65521;
+        (a, (b + a) % 65521)
+    });
+    b << 16 | a
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ PNG ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Encodes an image of shape *(channels, height, width)* as an uncompressed PNG.
+fn png(image: &Array3<f32>) -> Vec<u8> {
+    let (channels, height, width) = image.dim();
+    let color_type = match channels {
+        1 => 0,
+        3 => 2,
+        _ => 6,
+    };
+
+    // Each scanline starts with its filter type, which is always none.
+    let mut raw = Vec::with_capacity(height * (width * channels + 1));
+    let pixels = image.view().permuted_axes([1, 2, 0]);
+    for row in pixels.axis_iter(Axis(0)) {
+        raw.push(0);
+        raw.extend(
+            row.iter()
+                .map(|&value| (value.clamp(0., 1.) * 255.).round() as u8),
+        );
+    }
+
+    // Zlib stream made of stored deflate blocks.
+    let mut zlib = vec![0x78, 0x01];
+    let blocks = raw.chunks(u16::MAX as usize).collect::<Vec<_>>();
+    for (i, block) in blocks.iter().enumerate() {
+        let length = block.len() as u16;
+        zlib.push((i + 1 == blocks.len()) as u8);
+        zlib.extend_from_slice(&length.to_le_bytes());
+        zlib.extend_from_slice(&(!length).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    if blocks.is_empty() {
+        zlib.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(width as u32).to_be_bytes());
+    header.extend_from_slice(&(height as u32).to_be_bytes());
+    header.extend_from_slice(&[8, color_type, 0, 0, 0]);
+
+    let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+    for (kind, data) in [(b"IHDR", &header), (b"IDAT", &zlib), (b"IEND", &Vec::new())] {
+        png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        let start = png.len();
+        png.extend_from_slice(kind);
+        png.extend_from_slice(data);
+        let crc = crc32(&png[start..]);
+        png.extend_from_slice(&crc.to_be_bytes());
+    }
+    png
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
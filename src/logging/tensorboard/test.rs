@@ -0,0 +1,108 @@
+use super::{adler32, crc32, crc32c, masked_crc32c, png, Proto, TensorBoardWriter};
+use ndarray::Array3;
+use std::{fs, path::PathBuf};
+
+fn log_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "neuronika-tensorboard-{}-{}",
+        name,
+        std::process::id()
+    ))
+}
+
+/// Reads back the records of an event file, checking their checksums.
+fn read_records(writer: &TensorBoardWriter) -> Vec<Vec<u8>> {
+    let bytes = fs::read(writer.path()).unwrap();
+    let (mut records, mut offset) = (Vec::new(), 0);
+    while offset < bytes.len() {
+        let length_bytes = &bytes[offset..offset + 8];
+        let length = u64::from_le_bytes(length_bytes.try_into().unwrap()) as usize;
+        let length_crc = u32::from_le_bytes(bytes[offset + 8..offset + 12].try_into().unwrap());
+        assert_eq!(length_crc, masked_crc32c(length_bytes));
+
+        let data = &bytes[offset + 12..offset + 12 + length];
+        let data_crc = u32::from_le_bytes(
+            bytes[offset + 12 + length..offset + 16 + length]
+                .try_into()
+                .unwrap(),
+        );
+        assert_eq!(data_crc, masked_crc32c(data));
+
+        records.push(data.to_vec());
+        offset += 16 + length;
+    }
+    records
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+#[test]
+fn checksums() {
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    assert_eq!(crc32c(b"123456789"), 0xE306_9283);
+    assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+}
+
+#[test]
+fn varint() {
+    let mut proto = Proto::default();
+    proto.varint(1, 300);
+
+    assert_eq!(proto.0, vec![0x08, 0xAC, 0x02]);
+}
+
+#[test]
+fn events() {
+    let dir = log_dir("events");
+    let mut writer = TensorBoardWriter::new(&dir).unwrap();
+    let weight = crate::ones((2, 2)).requires_grad();
+
+    writer.add_scalar("loss", 0.5, 3).unwrap();
+    writer
+        .add_parameters("weight", &weight.parameters(), 3)
+        .unwrap();
+    writer
+        .add_image("image", &Array3::from_elem((3, 2, 2), 0.5), 3)
+        .unwrap();
+    writer.flush().unwrap();
+
+    let records = read_records(&writer);
+    assert_eq!(records.len(), 5);
+    assert!(contains(&records[0], b"brain.Event:2"));
+    assert!(contains(&records[1], b"loss"));
+    assert!(contains(&records[1], &0.5f32.to_le_bytes()));
+    assert!(contains(&records[2], b"weight/0/data"));
+    assert!(contains(&records[3], b"weight/0/grad"));
+    assert!(contains(&records[4], b"IHDR"));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn png_encoding() {
+    let encoded = png(&Array3::from_elem((1, 2, 3), 1.));
+
+    assert_eq!(
+        &encoded[..8],
+        &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]
+    );
+    // Width and height in the header.
+    assert_eq!(&encoded[16..24], &[0, 0, 0, 3, 0, 0, 0, 2]);
+    // Two scanlines of a filter byte and three white pixels.
+    assert!(contains(&encoded, &[0, 255, 255, 255, 0, 255, 255, 255]));
+    assert_eq!(&encoded[encoded.len() - 8..encoded.len() - 4], b"IEND");
+}
+
+#[test]
+#[should_panic(expected = "error: images must have 1, 3 or 4 channels, got 2.")]
+fn image_channels() {
+    let dir = log_dir("channels");
+    let mut writer = TensorBoardWriter::new(&dir).unwrap();
+    fs::remove_dir_all(dir).unwrap();
+
+    let _ = writer.add_image("image", &Array3::zeros((2, 2, 2)), 0);
+}
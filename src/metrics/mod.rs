@@ -0,0 +1,1163 @@
+//! Evaluation metrics.
+//!
+//! Every metric is *streaming*: it is updated once per batch with [`.update()`](Metric::update())
+//! and accumulates the statistics it needs, so that the final value is computed over the whole
+//! dataset with [`.compute()`](Metric::compute()) regardless of how it was split in batches.
+//!
+//! All metrics consume the same kind of tensors:
+//!
+//! * **predictions** - a tensor of shape *(batch, classes)* holding a score for each class. The
+//!   predicted class is the one with the highest score. When there is a single column, the task is
+//!   considered binary and a sample is assigned to class *1* if its score is at least *0.5*.
+//!
+//! * **targets** - a tensor of shape *(batch)* holding the index of the true class of each sample,
+//!   as required by [`nll_loss`](crate::nn::loss::nll_loss).
+//!
+//! ```
+//! use neuronika::metrics::{Accuracy, Average, F1Score, Metric};
+//! use ndarray::array;
+//!
+//! let mut accuracy = Accuracy::new();
+//! let mut f1 = F1Score::new(3, Average::Macro);
+//!
+//! let batches = [
+//!     (array![[0.8, 0.1, 0.1], [0.2, 0.7, 0.1]], array![0., 1.]),
+//!     (array![[0.1, 0.2, 0.7], [0.6, 0.3, 0.1]], array![2., 1.]),
+//! ];
+//! for (predictions, targets) in batches.iter() {
+//!     accuracy.update(predictions, targets);
+//!     f1.update(predictions, targets);
+//! }
+//!
+//! assert_eq!(accuracy.compute(), 0.75);
+//! ```
+//!
+//! # Available Metrics
+//!
+//! * [`Accuracy`] - Fraction of correctly classified samples.
+//!
+//! * [`Precision`] - Fraction of the samples assigned to a class that actually belong to it.
+//!
+//! * [`Recall`] - Fraction of the samples of a class that were assigned to it.
+//!
+//! * [`F1Score`] - Harmonic mean of precision and recall.
+//!
+//! * [`ConfusionMatrix`] - Counts of the samples of each class assigned to each class. All the
+//!   other metrics can be derived from it.
+//!
+//! The per-class metrics are reduced to a single value as specified by [`Average`].
+//!
+//! # Ranking Metrics
+//!
+//! The following metrics do not depend on a decision threshold and are computed from the scores
+//! themselves, that are stored by the metric until it is reset.
+//!
+//! * [`RocAuc`] - Area under the receiver operating characteristic curve.
+//!
+//! * [`AveragePrecision`] - Area under the precision-recall curve. Its multi-class version is
+//!   also known as *mean average precision*.
+//!
+//! In the binary case the scores are those of the positive class. In the multi-class case each
+//! class is evaluated one-vs-rest and the results are averaged over the classes.
+//!
+//! # Language Modelling Metrics
+//!
+//! The following metrics evaluate next token predictions, whose batches are the tokens of a
+//! batch of sequences flattened to shape *(batch * length, vocabulary)*. The positions whose
+//! target is the padding token, set with `.with_padding()`, are ignored.
+//!
+//! * [`Perplexity`] - Exponential of the mean negative log likelihood of the targets.
+//!
+//! * [`TopKAccuracy`] - Fraction of the targets among the *k* highest scoring tokens.
+//!
+//! # Calibration Metrics
+//!
+//! * [`ExpectedCalibrationError`] - Average gap between the confidence and the accuracy of the
+//!   predictions. It expects the predictions to be probabilities, and is usually tracked before and
+//!   after a [`TemperatureScaling`](crate::calibration::TemperatureScaling).
+//!
+//! # Regression and Segmentation Metrics
+//!
+//! The following metrics compare predictions and targets element by element and implement
+//! [`ElementwiseMetric`], which accepts tensors of any dimensionality as long as their shapes
+//! match. The data of a variable can be passed directly with `&*var.data()`.
+//!
+//! * [`R2Score`] - Coefficient of determination.
+//!
+//! * [`Rmse`] - Root mean squared error.
+//!
+//! * [`Mape`] - Mean absolute percentage error.
+//!
+//! * [`Iou`] - Intersection over union of binary masks, also known as *Jaccard index*.
+//!
+//! * [`Dice`] - Dice coefficient of binary masks.
+//!
+//! ```
+//! use neuronika::metrics::{ElementwiseMetric, Rmse};
+//!
+//! let prediction = neuronika::from_ndarray(ndarray::array![[1., 2.], [3., 4.]]);
+//! let target = ndarray::array![[1., 2.], [3., 2.]];
+//!
+//! let mut rmse = Rmse::new();
+//! rmse.update(&*prediction.data(), &target);
+//!
+//! assert_eq!(rmse.compute(), 1.);
+//! ```
+use ndarray::{Array, Array1, Array2, Axis, Dimension, Zip};
+
+/// A metric that is accumulated batch after batch.
+pub trait Metric {
+    /// Accumulates the statistics of a batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `predictions` - scores of shape *(batch, classes)*.
+    ///
+    /// * `targets` - class indices of shape *(batch)*.
+    ///
+    /// # Panics
+    ///
+    /// If the batch sizes of `predictions` and `targets` differ or if a target is not a valid
+    /// class index.
+    fn update(&mut self, predictions: &Array2<f32>, targets: &Array1<f32>);
+
+    /// Computes the value of the metric over all the batches seen so far.
+    fn compute(&self) -> f32;
+
+    /// Discards all the accumulated statistics.
+    fn reset(&mut self);
+}
+
+/// Specifies how per-class metrics are reduced to a single value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Average {
+    /// The metric is computed for each class and the unweighted mean is taken.
+    Macro,
+    /// The metric is computed over the total true positives, false positives and false negatives.
+    Micro,
+    /// The metric is computed for each class and the mean, weighted by the number of samples of
+    /// each class, is taken.
+    Weighted,
+}
+
+/// Returns the class predicted for each sample.
+fn predicted_classes(predictions: &Array2<f32>) -> impl Iterator<Item = usize> + '_ {
+    let binary = predictions.ncols() == 1;
+    predictions.axis_iter(Axis(0)).map(move |scores| {
+        if binary {
+            (scores[0] >= 0.5) as usize
+        } else {
+            scores
+                .iter()
+                .enumerate()
+                .fold((0, f32::NEG_INFINITY), |(argmax, max), (class, &score)| {
+                    if score > max {
+                        (class, score)
+                    } else {
+                        (argmax, max)
+                    }
+                })
+                .0
+        }
+    })
+}
+
+/// Checks the batch sizes of predictions and targets.
+fn check_batch(predictions: &Array2<f32>, targets: &Array1<f32>) {
+    assert_eq!(
+        predictions.nrows(),
+        targets.len(),
+        "error: predictions and targets have different batch sizes: {} and {}.",
+        predictions.nrows(),
+        targets.len()
+    );
+}
+
+/// Returns `numerator / denominator`, or zero if the denominator is zero.
+fn ratio(numerator: f32, denominator: f32) -> f32 {
+    if denominator == 0. {
+        0.
+    } else {
+        numerator / denominator
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Accuracy ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Fraction of correctly classified samples.
+#[derive(Clone, Debug, Default)]
+pub struct Accuracy {
+    correct: usize,
+    total: usize,
+}
+
+impl Accuracy {
+    /// Creates a new accuracy metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Metric for Accuracy {
+    fn update(&mut self, predictions: &Array2<f32>, targets: &Array1<f32>) {
+        check_batch(predictions, targets);
+
+        self.correct += predicted_classes(predictions)
+            .zip(targets.iter())
+            .filter(|(predicted, &target)| *predicted == target as usize)
+            .count();
+        self.total += targets.len();
+    }
+
+    fn compute(&self) -> f32 {
+        ratio(self.correct as f32, self.total as f32)
+    }
+
+    fn reset(&mut self) {
+        self.correct = 0;
+        self.total = 0;
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ ConfusionMatrix ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Counts of the samples of each class assigned to each class.
+///
+/// The entry at row *i* and column *j* is the number of samples of class *i* that were assigned
+/// to class *j*.
+#[derive(Clone, Debug)]
+pub struct ConfusionMatrix {
+    counts: Array2<usize>,
+}
+
+impl ConfusionMatrix {
+    /// Creates a new confusion matrix for `classes` classes.
+    ///
+    /// # Panics
+    ///
+    /// If `classes` is less than 2.
+    pub fn new(classes: usize) -> Self {
+        assert!(
+            classes >= 2,
+            "error: a classification task needs at least 2 classes, got {}.",
+            classes
+        );
+
+        Self {
+            counts: Array2::zeros((classes, classes)),
+        }
+    }
+
+    /// Accumulates the statistics of a batch. See [`Metric::update()`].
+    pub fn update(&mut self, predictions: &Array2<f32>, targets: &Array1<f32>) {
+        check_batch(predictions, targets);
+
+        let classes = self.classes();
+        for (predicted, &target) in predicted_classes(predictions).zip(targets.iter()) {
+            let target = target as usize;
+            assert!(
+                target < classes && predicted < classes,
+                "error: class index out of range for {} classes.",
+                classes
+            );
+            self.counts[[target, predicted]] += 1;
+        }
+    }
+
+    /// Discards all the accumulated statistics.
+    pub fn reset(&mut self) {
+        self.counts.fill(0);
+    }
+
+    /// Returns the number of classes.
+    pub fn classes(&self) -> usize {
+        self.counts.nrows()
+    }
+
+    /// Returns the accumulated counts.
+    pub fn counts(&self) -> &Array2<usize> {
+        &self.counts
+    }
+
+    /// Returns the number of samples correctly assigned to `class`.
+    pub fn true_positives(&self, class: usize) -> usize {
+        self.counts[[class, class]]
+    }
+
+    /// Returns the number of samples wrongly assigned to `class`.
+    pub fn false_positives(&self, class: usize) -> usize {
+        self.counts.column(class).sum() - self.true_positives(class)
+    }
+
+    /// Returns the number of samples of `class` that were assigned to another class.
+    pub fn false_negatives(&self, class: usize) -> usize {
+        self.counts.row(class).sum() - self.true_positives(class)
+    }
+
+    /// Returns the number of samples of `class`.
+    pub fn support(&self, class: usize) -> usize {
+        self.counts.row(class).sum()
+    }
+
+    /// Returns the fraction of correctly classified samples.
+    pub fn accuracy(&self) -> f32 {
+        ratio(self.counts.diag().sum() as f32, self.counts.sum() as f32)
+    }
+
+    /// Returns the precision of `class`.
+    pub fn precision(&self, class: usize) -> f32 {
+        let tp = self.true_positives(class) as f32;
+        ratio(tp, tp + self.false_positives(class) as f32)
+    }
+
+    /// Returns the recall of `class`.
+    pub fn recall(&self, class: usize) -> f32 {
+        let tp = self.true_positives(class) as f32;
+        ratio(tp, tp + self.false_negatives(class) as f32)
+    }
+
+    /// Returns the F1 score of `class`.
+    pub fn f1(&self, class: usize) -> f32 {
+        let (precision, recall) = (self.precision(class), self.recall(class));
+        ratio(2. * precision * recall, precision + recall)
+    }
+
+    /// Reduces a per-class metric as specified by `average`. `micro` is the value of the metric
+    /// computed from the total counts.
+    fn average<F>(&self, average: Average, per_class: F, micro: f32) -> f32
+    where
+        F: Fn(&Self, usize) -> f32,
+    {
+        let classes = self.classes();
+        match average {
+            Average::Macro => {
+                (0..classes)
+                    .map(|class| per_class(self, class))
+                    .sum::<f32>()
+                    / classes as f32
+            }
+            Average::Micro => micro,
+            Average::Weighted => ratio(
+                (0..classes)
+                    .map(|class| per_class(self, class) * self.support(class) as f32)
+                    .sum(),
+                self.counts.sum() as f32,
+            ),
+        }
+    }
+
+    /// Returns the precision averaged over the classes.
+    pub fn average_precision(&self, average: Average) -> f32 {
+        self.average(average, Self::precision, self.accuracy())
+    }
+
+    /// Returns the recall averaged over the classes.
+    pub fn average_recall(&self, average: Average) -> f32 {
+        self.average(average, Self::recall, self.accuracy())
+    }
+
+    /// Returns the F1 score averaged over the classes.
+    pub fn average_f1(&self, average: Average) -> f32 {
+        // When every sample is assigned to exactly one class, micro-averaged precision, recall
+        // and F1 score all coincide with the accuracy.
+        self.average(average, Self::f1, self.accuracy())
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Precision, Recall, F1 ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+macro_rules! averaged_metric {
+    ($(#[$meta:meta])* $name:ident, $compute:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug)]
+        pub struct $name {
+            matrix: ConfusionMatrix,
+            average: Average,
+        }
+
+        impl $name {
+            /// Creates a new metric for `classes` classes, reduced as specified by `average`.
+            ///
+            /// # Panics
+            ///
+            /// If `classes` is less than 2.
+            pub fn new(classes: usize, average: Average) -> Self {
+                Self {
+                    matrix: ConfusionMatrix::new(classes),
+                    average,
+                }
+            }
+
+            /// Returns the underlying confusion matrix.
+            pub fn confusion_matrix(&self) -> &ConfusionMatrix {
+                &self.matrix
+            }
+        }
+
+        impl Metric for $name {
+            fn update(&mut self, predictions: &Array2<f32>, targets: &Array1<f32>) {
+                self.matrix.update(predictions, targets);
+            }
+
+            fn compute(&self) -> f32 {
+                self.matrix.$compute(self.average)
+            }
+
+            fn reset(&mut self) {
+                self.matrix.reset();
+            }
+        }
+    };
+}
+
+averaged_metric!(
+    /// Fraction of the samples assigned to a class that actually belong to it.
+    Precision,
+    average_precision
+);
+
+averaged_metric!(
+    /// Fraction of the samples of a class that were assigned to it.
+    Recall,
+    average_recall
+);
+
+averaged_metric!(
+    /// Harmonic mean of precision and recall.
+    F1Score,
+    average_f1
+);
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Ranking Metrics ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Scores and targets accumulated by the ranking metrics.
+#[derive(Clone, Debug, Default)]
+struct Ranking {
+    columns: usize,
+    scores: Vec<f32>,
+    targets: Vec<usize>,
+}
+
+impl Ranking {
+    fn update(&mut self, predictions: &Array2<f32>, targets: &Array1<f32>) {
+        check_batch(predictions, targets);
+
+        if self.targets.is_empty() {
+            self.columns = predictions.ncols();
+        }
+        assert_eq!(
+            self.columns,
+            predictions.ncols(),
+            "error: predictions have {} columns, but previous batches had {}.",
+            predictions.ncols(),
+            self.columns
+        );
+
+        let classes = self.columns.max(2);
+        for &target in targets {
+            let target = target as usize;
+            assert!(
+                target < classes,
+                "error: class index out of range for {} classes.",
+                classes
+            );
+            self.targets.push(target);
+        }
+        self.scores.extend(predictions.iter());
+    }
+
+    fn reset(&mut self) {
+        self.scores.clear();
+        self.targets.clear();
+    }
+
+    /// Returns the score of each sample for `class` and whether it belongs to it, sorted by
+    /// decreasing score.
+    fn one_vs_rest(&self, class: usize) -> Vec<(f32, bool)> {
+        let mut pairs: Vec<(f32, bool)> = if self.columns == 1 {
+            self.scores
+                .iter()
+                .zip(&self.targets)
+                .map(|(&score, &target)| (score, target == 1))
+                .collect()
+        } else {
+            self.scores
+                .chunks(self.columns)
+                .zip(&self.targets)
+                .map(|(scores, &target)| (scores[class], target == class))
+                .collect()
+        };
+        pairs.sort_by(|a, b| b.0.total_cmp(&a.0));
+        pairs
+    }
+
+    /// Averages `per_class` over the classes for which it is defined.
+    fn average<F>(&self, per_class: F) -> f32
+    where
+        F: Fn(&[(f32, bool)]) -> Option<f32>,
+    {
+        if self.columns == 1 {
+            return per_class(&self.one_vs_rest(1)).unwrap_or(0.);
+        }
+
+        let values: Vec<f32> = (0..self.columns)
+            .filter_map(|class| per_class(&self.one_vs_rest(class)))
+            .collect();
+        ratio(values.iter().sum(), values.len() as f32)
+    }
+}
+
+/// Splits pairs sorted by decreasing score into groups of equal score and returns the number of
+/// positives and negatives in each group.
+fn tied_groups(pairs: &[(f32, bool)]) -> Vec<(usize, usize)> {
+    let mut groups: Vec<(usize, usize)> = Vec::new();
+    let mut last = None;
+    for &(score, positive) in pairs {
+        if last != Some(score) {
+            groups.push((0, 0));
+            last = Some(score);
+        }
+        let group = groups.last_mut().unwrap();
+        if positive {
+            group.0 += 1;
+        } else {
+            group.1 += 1;
+        }
+    }
+    groups
+}
+
+/// Computes the area under the ROC curve, ties counting as half a correct ordering. Returns
+/// `None` if there are no positives or no negatives.
+fn roc_auc(pairs: &[(f32, bool)]) -> Option<f32> {
+    // Counts the pairs in which a negative is scored higher than a positive.
+    let (mut misranked, mut negatives_above) = (0., 0.);
+    for (positives, negatives) in tied_groups(pairs) {
+        misranked += positives as f64 * (negatives_above + negatives as f64 / 2.);
+        negatives_above += negatives as f64;
+    }
+
+    let (positives, negatives) = (
+        pairs.iter().filter(|(_, positive)| *positive).count() as f64,
+        negatives_above,
+    );
+    if positives == 0. || negatives == 0. {
+        return None;
+    }
+    Some((1. - misranked / (positives * negatives)) as f32)
+}
+
+/// Computes the average precision. Returns `None` if there are no positives.
+fn average_precision(pairs: &[(f32, bool)]) -> Option<f32> {
+    let positives = pairs.iter().filter(|(_, positive)| *positive).count();
+    if positives == 0 {
+        return None;
+    }
+
+    let (mut true_positives, mut retrieved, mut area) = (0, 0, 0.);
+    for (group_positives, group_negatives) in tied_groups(pairs) {
+        true_positives += group_positives;
+        retrieved += group_positives + group_negatives;
+        area +=
+            group_positives as f64 / positives as f64 * true_positives as f64 / retrieved as f64;
+    }
+    Some(area as f32)
+}
+
+/// Area under the receiver operating characteristic curve.
+///
+/// It is the probability that a randomly chosen sample of a class is scored higher than a
+/// randomly chosen sample of another class. In the multi-class case, classes with no positive or
+/// no negative samples are excluded from the average.
+#[derive(Clone, Debug, Default)]
+pub struct RocAuc {
+    ranking: Ranking,
+}
+
+impl RocAuc {
+    /// Creates a new ROC-AUC metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Metric for RocAuc {
+    fn update(&mut self, predictions: &Array2<f32>, targets: &Array1<f32>) {
+        self.ranking.update(predictions, targets);
+    }
+
+    fn compute(&self) -> f32 {
+        self.ranking.average(roc_auc)
+    }
+
+    fn reset(&mut self) {
+        self.ranking.reset();
+    }
+}
+
+/// Area under the precision-recall curve, computed as the mean of the precisions at each
+/// threshold weighted by the increase in recall.
+///
+/// In the multi-class case, classes with no positive samples are excluded from the average.
+#[derive(Clone, Debug, Default)]
+pub struct AveragePrecision {
+    ranking: Ranking,
+}
+
+impl AveragePrecision {
+    /// Creates a new average precision metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Metric for AveragePrecision {
+    fn update(&mut self, predictions: &Array2<f32>, targets: &Array1<f32>) {
+        self.ranking.update(predictions, targets);
+    }
+
+    fn compute(&self) -> f32 {
+        self.ranking.average(average_precision)
+    }
+
+    fn reset(&mut self) {
+        self.ranking.reset();
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Language Modelling Metrics ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Perplexity of a language model.
+///
+/// It is the exponential of the mean negative log likelihood of the target tokens, so that a
+/// model that assigns probability *1* to every target scores *1*, while one that is uniform over
+/// a vocabulary of *V* tokens scores *V*.
+///
+/// The predictions must be log-probabilities, such as the output of a
+/// [`.log_softmax()`](crate::Var::log_softmax()). Alternatively, the metric can be updated with
+/// the summed negative log likelihood of a batch through
+/// [`.update_nll()`](Perplexity::update_nll()), which avoids computing it twice when it is also
+/// the training loss.
+///
+/// ```
+/// use neuronika::metrics::{Metric, Perplexity};
+/// use ndarray::array;
+///
+/// // Two sequences of two tokens each, the last token of the second one is padding.
+/// let predictions = array![
+///     [0.5, 0.25, 0.25],
+///     [0.2, 0.75, 0.05],
+///     [0.4, 0.5, 0.1],
+///     [0.8, 0.1, 0.1],
+/// ];
+/// let targets = array![0., 1., 1., 2.];
+///
+/// let mut perplexity = Perplexity::new().with_padding(2);
+/// perplexity.update(&predictions.mapv(f32::ln), &targets);
+///
+/// let expected = (0.5_f32 * 0.75 * 0.5).powf(-1. / 3.);
+/// assert!((perplexity.compute() - expected).abs() < 1e-5);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Perplexity {
+    nll: f64,
+    tokens: usize,
+    padding: Option<usize>,
+}
+
+impl Perplexity {
+    /// Creates a new perplexity metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ignores the tokens whose target is `padding`.
+    pub fn with_padding(mut self, padding: usize) -> Self {
+        self.padding = Some(padding);
+        self
+    }
+
+    /// Accumulates the summed negative log likelihood `nll` of a batch of `tokens` tokens,
+    /// padding excluded.
+    ///
+    /// # Panics
+    ///
+    /// If `nll` is negative.
+    pub fn update_nll(&mut self, nll: f32, tokens: usize) {
+        assert!(
+            nll >= 0.,
+            "error: {} is not a valid negative log likelihood.",
+            nll
+        );
+
+        self.nll += nll as f64;
+        self.tokens += tokens;
+    }
+}
+
+impl Metric for Perplexity {
+    fn update(&mut self, predictions: &Array2<f32>, targets: &Array1<f32>) {
+        check_batch(predictions, targets);
+
+        let vocabulary = predictions.ncols();
+        for (log_probabilities, &target) in predictions.axis_iter(Axis(0)).zip(targets.iter()) {
+            if self.padding == Some(target as usize) {
+                continue;
+            }
+            let target = target as usize;
+            assert!(
+                target < vocabulary,
+                "error: class index out of range for {} classes.",
+                vocabulary
+            );
+
+            self.nll -= log_probabilities[target] as f64;
+            self.tokens += 1;
+        }
+    }
+
+    /// Computes the perplexity over all the tokens seen so far. It is *1* if no token was seen.
+    fn compute(&self) -> f32 {
+        ratio(self.nll as f32, self.tokens as f32).exp()
+    }
+
+    fn reset(&mut self) {
+        self.nll = 0.;
+        self.tokens = 0;
+    }
+}
+
+/// Sparse top-k accuracy.
+///
+/// It is the fraction of the target tokens that are among the *k* highest scoring classes of
+/// their predictions. The targets are class indices, as for the other metrics, and the tokens
+/// whose target is the padding one, set with [`.with_padding()`](TopKAccuracy::with_padding()),
+/// are ignored. Ties are resolved in favour of the target, and with *k = 1* the metric is the
+/// plain accuracy over the tokens that are not padding.
+///
+/// ```
+/// use neuronika::metrics::{Metric, TopKAccuracy};
+/// use ndarray::array;
+///
+/// let predictions = array![[0.1, 0.6, 0.3], [0.5, 0.2, 0.3], [0.2, 0.3, 0.5]];
+/// let targets = array![2., 1., 0.];
+///
+/// let mut top2 = TopKAccuracy::new(2).with_padding(0);
+/// top2.update(&predictions, &targets);
+///
+/// assert_eq!(top2.compute(), 0.5);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TopKAccuracy {
+    k: usize,
+    correct: usize,
+    total: usize,
+    padding: Option<usize>,
+}
+
+impl TopKAccuracy {
+    /// Creates a new top-k accuracy metric.
+    ///
+    /// # Panics
+    ///
+    /// If `k` is zero.
+    pub fn new(k: usize) -> Self {
+        assert!(k > 0, "error: k must be positive.");
+
+        Self {
+            k,
+            correct: 0,
+            total: 0,
+            padding: None,
+        }
+    }
+
+    /// Ignores the tokens whose target is `padding`.
+    pub fn with_padding(mut self, padding: usize) -> Self {
+        self.padding = Some(padding);
+        self
+    }
+}
+
+impl Metric for TopKAccuracy {
+    fn update(&mut self, predictions: &Array2<f32>, targets: &Array1<f32>) {
+        check_batch(predictions, targets);
+
+        let classes = predictions.ncols();
+        for (scores, &target) in predictions.axis_iter(Axis(0)).zip(targets.iter()) {
+            if self.padding == Some(target as usize) {
+                continue;
+            }
+            let target = target as usize;
+            assert!(
+                target < classes,
+                "error: class index out of range for {} classes.",
+                classes
+            );
+
+            // The rank of the target is the number of classes scoring strictly higher.
+            let rank = scores
+                .iter()
+                .filter(|&&score| score > scores[target])
+                .count();
+            self.correct += (rank < self.k) as usize;
+            self.total += 1;
+        }
+    }
+
+    fn compute(&self) -> f32 {
+        ratio(self.correct as f32, self.total as f32)
+    }
+
+    fn reset(&mut self) {
+        self.correct = 0;
+        self.total = 0;
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Calibration Metrics ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Expected calibration error.
+///
+/// The samples are grouped in bins of equal width according to their confidence, that is the
+/// probability of their predicted class, and the absolute differences between the accuracy and
+/// the mean confidence of each bin are averaged, weighted by the number of samples in the bins.
+/// A perfectly calibrated classifier scores *0*.
+///
+/// The predictions must be probabilities, such as the output of a
+/// [`.softmax()`](crate::Var::softmax()). In the binary case the confidence of a sample is the
+/// probability of the class it is assigned to.
+#[derive(Clone, Debug)]
+pub struct ExpectedCalibrationError {
+    counts: Vec<usize>,
+    correct: Vec<usize>,
+    confidences: Vec<f64>,
+}
+
+impl ExpectedCalibrationError {
+    /// Creates a new expected calibration error metric with `bins` bins.
+    ///
+    /// # Panics
+    ///
+    /// If `bins` is zero.
+    pub fn new(bins: usize) -> Self {
+        assert!(bins > 0, "error: the number of bins must be positive.");
+
+        Self {
+            counts: vec![0; bins],
+            correct: vec![0; bins],
+            confidences: vec![0.; bins],
+        }
+    }
+}
+
+impl Metric for ExpectedCalibrationError {
+    fn update(&mut self, predictions: &Array2<f32>, targets: &Array1<f32>) {
+        check_batch(predictions, targets);
+
+        let bins = self.counts.len();
+        let binary = predictions.ncols() == 1;
+        for ((scores, predicted), &target) in predictions
+            .axis_iter(Axis(0))
+            .zip(predicted_classes(predictions))
+            .zip(targets.iter())
+        {
+            let confidence = if binary {
+                scores[0].max(1. - scores[0])
+            } else {
+                scores[predicted]
+            };
+            let bin = ((confidence * bins as f32) as usize).min(bins - 1);
+
+            self.counts[bin] += 1;
+            self.correct[bin] += (predicted == target as usize) as usize;
+            self.confidences[bin] += confidence as f64;
+        }
+    }
+
+    fn compute(&self) -> f32 {
+        // The gap of each bin weighted by its share of the samples.
+        let gaps: f64 = self
+            .correct
+            .iter()
+            .zip(&self.confidences)
+            .map(|(&correct, &confidence)| (correct as f64 - confidence).abs())
+            .sum();
+        ratio(gaps as f32, self.counts.iter().sum::<usize>() as f32)
+    }
+
+    fn reset(&mut self) {
+        self.counts.iter_mut().for_each(|count| *count = 0);
+        self.correct.iter_mut().for_each(|correct| *correct = 0);
+        self.confidences
+            .iter_mut()
+            .for_each(|confidence| *confidence = 0.);
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Regression and Segmentation Metrics ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// A metric that is accumulated batch after batch and compares predictions and targets element
+/// by element.
+pub trait ElementwiseMetric {
+    /// Accumulates the statistics of a batch.
+    ///
+    /// # Panics
+    ///
+    /// If `predictions` and `targets` have different shapes.
+    fn update<D: Dimension>(&mut self, predictions: &Array<f32, D>, targets: &Array<f32, D>);
+
+    /// Computes the value of the metric over all the batches seen so far.
+    fn compute(&self) -> f32;
+
+    /// Discards all the accumulated statistics.
+    fn reset(&mut self);
+}
+
+/// Checks the shapes of predictions and targets.
+fn check_shapes<D: Dimension>(predictions: &Array<f32, D>, targets: &Array<f32, D>) {
+    assert_eq!(
+        predictions.shape(),
+        targets.shape(),
+        "error: predictions and targets have different shapes: {:?} and {:?}.",
+        predictions.shape(),
+        targets.shape()
+    );
+}
+
+/// Coefficient of determination.
+///
+/// It is the fraction of the variance of the targets that is explained by the predictions. A
+/// perfect regressor scores *1*, while one that always predicts the mean of the targets scores
+/// *0*.
+#[derive(Clone, Debug, Default)]
+pub struct R2Score {
+    count: usize,
+    targets_sum: f64,
+    targets_squares_sum: f64,
+    squared_errors_sum: f64,
+}
+
+impl R2Score {
+    /// Creates a new R² metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ElementwiseMetric for R2Score {
+    fn update<D: Dimension>(&mut self, predictions: &Array<f32, D>, targets: &Array<f32, D>) {
+        check_shapes(predictions, targets);
+
+        Zip::from(predictions)
+            .and(targets)
+            .for_each(|&prediction, &target| {
+                let (prediction, target) = (prediction as f64, target as f64);
+                self.targets_sum += target;
+                self.targets_squares_sum += target * target;
+                self.squared_errors_sum += (prediction - target).powi(2);
+            });
+        self.count += targets.len();
+    }
+
+    fn compute(&self) -> f32 {
+        let total_sum_of_squares =
+            self.targets_squares_sum - self.targets_sum.powi(2) / self.count as f64;
+        if self.count == 0 || total_sum_of_squares <= 0. {
+            return 0.;
+        }
+        (1. - self.squared_errors_sum / total_sum_of_squares) as f32
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Root mean squared error.
+#[derive(Clone, Debug, Default)]
+pub struct Rmse {
+    count: usize,
+    squared_errors_sum: f64,
+}
+
+impl Rmse {
+    /// Creates a new RMSE metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ElementwiseMetric for Rmse {
+    fn update<D: Dimension>(&mut self, predictions: &Array<f32, D>, targets: &Array<f32, D>) {
+        check_shapes(predictions, targets);
+
+        Zip::from(predictions)
+            .and(targets)
+            .for_each(|&prediction, &target| {
+                self.squared_errors_sum += (prediction as f64 - target as f64).powi(2)
+            });
+        self.count += targets.len();
+    }
+
+    fn compute(&self) -> f32 {
+        ratio(self.squared_errors_sum as f32, self.count as f32).sqrt()
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Mean absolute percentage error, expressed as a fraction.
+///
+/// Targets whose magnitude is smaller than [`f32::EPSILON`] are clamped to it to avoid divisions
+/// by zero.
+#[derive(Clone, Debug, Default)]
+pub struct Mape {
+    count: usize,
+    percentage_errors_sum: f64,
+}
+
+impl Mape {
+    /// Creates a new MAPE metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ElementwiseMetric for Mape {
+    fn update<D: Dimension>(&mut self, predictions: &Array<f32, D>, targets: &Array<f32, D>) {
+        check_shapes(predictions, targets);
+
+        Zip::from(predictions)
+            .and(targets)
+            .for_each(|&prediction, &target| {
+                self.percentage_errors_sum +=
+                    ((prediction - target) / target.abs().max(f32::EPSILON)).abs() as f64
+            });
+        self.count += targets.len();
+    }
+
+    fn compute(&self) -> f32 {
+        ratio(self.percentage_errors_sum as f32, self.count as f32)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Intersection and union of binary masks, accumulated over all the batches.
+#[derive(Clone, Debug, Default)]
+struct Overlap {
+    intersection: usize,
+    predicted: usize,
+    target: usize,
+}
+
+impl Overlap {
+    /// Accumulates the masks obtained by thresholding the predictions and the targets at *0.5*.
+    fn update<D: Dimension>(&mut self, predictions: &Array<f32, D>, targets: &Array<f32, D>) {
+        check_shapes(predictions, targets);
+
+        Zip::from(predictions)
+            .and(targets)
+            .for_each(|&prediction, &target| {
+                let (prediction, target) = (prediction >= 0.5, target >= 0.5);
+                self.intersection += (prediction && target) as usize;
+                self.predicted += prediction as usize;
+                self.target += target as usize;
+            });
+    }
+}
+
+/// Intersection over union of binary masks.
+///
+/// Predictions and targets are turned into masks by thresholding them at *0.5*. The intersection
+/// and the union are accumulated over all the batches. If both masks are empty the score is *1*.
+#[derive(Clone, Debug, Default)]
+pub struct Iou {
+    overlap: Overlap,
+}
+
+impl Iou {
+    /// Creates a new IoU metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ElementwiseMetric for Iou {
+    fn update<D: Dimension>(&mut self, predictions: &Array<f32, D>, targets: &Array<f32, D>) {
+        self.overlap.update(predictions, targets);
+    }
+
+    fn compute(&self) -> f32 {
+        let Overlap {
+            intersection,
+            predicted,
+            target,
+        } = self.overlap;
+        let union = predicted + target - intersection;
+        if union == 0 {
+            return 1.;
+        }
+        intersection as f32 / union as f32
+    }
+
+    fn reset(&mut self) {
+        self.overlap = Overlap::default();
+    }
+}
+
+/// Dice coefficient of binary masks.
+///
+/// Predictions and targets are turned into masks by thresholding them at *0.5*. The statistics
+/// are accumulated over all the batches. If both masks are empty the score is *1*.
+#[derive(Clone, Debug, Default)]
+pub struct Dice {
+    overlap: Overlap,
+}
+
+impl Dice {
+    /// Creates a new Dice metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ElementwiseMetric for Dice {
+    fn update<D: Dimension>(&mut self, predictions: &Array<f32, D>, targets: &Array<f32, D>) {
+        self.overlap.update(predictions, targets);
+    }
+
+    fn compute(&self) -> f32 {
+        let Overlap {
+            intersection,
+            predicted,
+            target,
+        } = self.overlap;
+        if predicted + target == 0 {
+            return 1.;
+        }
+        2. * intersection as f32 / (predicted + target) as f32
+    }
+
+    fn reset(&mut self) {
+        self.overlap = Overlap::default();
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
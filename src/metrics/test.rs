@@ -0,0 +1,285 @@
+use super::{
+    Accuracy, Average, AveragePrecision, ConfusionMatrix, Dice, ElementwiseMetric,
+    ExpectedCalibrationError, F1Score, Iou, Mape, Metric, Perplexity, Precision, R2Score, Recall,
+    Rmse, RocAuc, TopKAccuracy,
+};
+use ndarray::{array, Array1, Array2};
+
+fn batch() -> (Array2<f32>, Array1<f32>) {
+    // Predicted classes: 0, 1, 1, 2, 2, 0.
+    let predictions = array![
+        [0.9, 0.05, 0.05],
+        [0.1, 0.8, 0.1],
+        [0.3, 0.6, 0.1],
+        [0.2, 0.2, 0.6],
+        [0.1, 0.3, 0.6],
+        [0.5, 0.4, 0.1],
+    ];
+    let targets = array![0., 1., 0., 2., 1., 0.];
+
+    (predictions, targets)
+}
+
+#[test]
+fn accuracy_streaming() {
+    let (predictions, targets) = batch();
+    let mut accuracy = Accuracy::new();
+
+    accuracy.update(&predictions, &targets);
+    assert!((accuracy.compute() - 4. / 6.).abs() < f32::EPSILON);
+
+    accuracy.update(&array![[0.1, 0.1, 0.8], [0.7, 0.2, 0.1]], &array![2., 0.]);
+    assert!((accuracy.compute() - 6. / 8.).abs() < f32::EPSILON);
+
+    accuracy.reset();
+    assert_eq!(accuracy.compute(), 0.);
+}
+
+#[test]
+fn confusion_matrix() {
+    let (predictions, targets) = batch();
+    let mut matrix = ConfusionMatrix::new(3);
+    matrix.update(&predictions, &targets);
+
+    assert_eq!(matrix.counts(), &array![[2, 1, 0], [0, 1, 1], [0, 0, 1]]);
+    assert_eq!(matrix.true_positives(1), 1);
+    assert_eq!(matrix.false_positives(1), 1);
+    assert_eq!(matrix.false_negatives(1), 1);
+    assert_eq!(matrix.support(0), 3);
+
+    assert!((matrix.precision(0) - 1.).abs() < f32::EPSILON);
+    assert!((matrix.recall(0) - 2. / 3.).abs() < f32::EPSILON);
+    assert!((matrix.precision(2) - 0.5).abs() < f32::EPSILON);
+    assert!((matrix.recall(2) - 1.).abs() < f32::EPSILON);
+    assert!((matrix.f1(0) - 0.8).abs() < 1e-6);
+
+    matrix.reset();
+    assert_eq!(matrix.counts(), &Array2::<usize>::zeros((3, 3)));
+}
+
+#[test]
+fn binary() {
+    let mut matrix = ConfusionMatrix::new(2);
+    matrix.update(&array![[0.2], [0.5], [0.9], [0.4]], &array![0., 1., 0., 1.]);
+
+    assert_eq!(matrix.counts(), &array![[1, 1], [1, 1]]);
+}
+
+#[test]
+fn averages() {
+    let (predictions, targets) = batch();
+    let (mut precision, mut recall, mut f1) = (
+        Precision::new(3, Average::Macro),
+        Recall::new(3, Average::Weighted),
+        F1Score::new(3, Average::Micro),
+    );
+    precision.update(&predictions, &targets);
+    recall.update(&predictions, &targets);
+    f1.update(&predictions, &targets);
+
+    // Per-class precision: 1, 1/2, 1/2.
+    assert!((precision.compute() - 2. / 3.).abs() < 1e-6);
+    // Per-class recall: 2/3, 1/2, 1 with supports 3, 2, 1.
+    assert!((recall.compute() - 4. / 6.).abs() < 1e-6);
+    // Micro-averaged F1 coincides with the accuracy.
+    assert!((f1.compute() - 4. / 6.).abs() < 1e-6);
+}
+
+#[test]
+fn undefined_is_zero() {
+    let mut precision = Precision::new(3, Average::Macro);
+    precision.update(&array![[1., 0., 0.]], &array![0.]);
+
+    assert!((precision.confusion_matrix().precision(1)).abs() < f32::EPSILON);
+    assert!((precision.compute() - 1. / 3.).abs() < 1e-6);
+}
+
+#[test]
+#[should_panic(expected = "error: predictions and targets have different batch sizes: 2 and 1.")]
+fn batch_size_mismatch() {
+    Accuracy::new().update(&array![[1., 0.], [0., 1.]], &array![0.]);
+}
+
+#[test]
+#[should_panic(expected = "error: class index out of range for 2 classes.")]
+fn class_out_of_range() {
+    ConfusionMatrix::new(2).update(&array![[1., 0.]], &array![3.]);
+}
+
+#[test]
+fn roc_auc_binary() {
+    let mut auc = RocAuc::new();
+    auc.update(&array![[0.1], [0.4]], &array![0., 0.]);
+    auc.update(&array![[0.35], [0.8]], &array![1., 1.]);
+
+    // Of the 4 positive-negative pairs, only (0.35, 0.4) is misranked.
+    assert!((auc.compute() - 0.75).abs() < 1e-6);
+
+    // Ties count as half a correct ordering.
+    auc.reset();
+    auc.update(&array![[0.5], [0.5]], &array![0., 1.]);
+    assert!((auc.compute() - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn roc_auc_multiclass() {
+    let mut auc = RocAuc::new();
+    auc.update(
+        &array![[0.8, 0.1, 0.1], [0.1, 0.8, 0.1], [0.1, 0.1, 0.8]],
+        &array![0., 1., 2.],
+    );
+
+    assert!((auc.compute() - 1.).abs() < 1e-6);
+}
+
+#[test]
+fn average_precision() {
+    let mut ap = AveragePrecision::new();
+    ap.update(
+        &array![[0.1], [0.4], [0.35], [0.8]],
+        &array![0., 0., 1., 1.],
+    );
+
+    // Precisions at the positives are 1 and 2 / 3.
+    assert!((ap.compute() - 5. / 6.).abs() < 1e-6);
+
+    ap.reset();
+    ap.update(&array![[0.1]], &array![0.]);
+    assert_eq!(ap.compute(), 0.);
+}
+
+#[test]
+#[should_panic(expected = "error: predictions have 2 columns, but previous batches had 3.")]
+fn ranking_columns_mismatch() {
+    let mut auc = RocAuc::new();
+    auc.update(&array![[0.8, 0.1, 0.1]], &array![0.]);
+    auc.update(&array![[0.8, 0.1]], &array![0.]);
+}
+
+#[test]
+fn expected_calibration_error() {
+    let mut ece = ExpectedCalibrationError::new(2);
+
+    // A confidence of 0.9 and an accuracy of 1 in the upper bin, a confidence of 0.4 and an
+    // accuracy of 0.5 in the lower one.
+    ece.update(
+        &array![[0.9, 0.1], [0.1, 0.9], [0.4, 0.3], [0.3, 0.4]],
+        &array![0., 1., 0., 0.],
+    );
+    assert!((ece.compute() - 0.1).abs() < 1e-6);
+
+    // Binary predictions, all in the upper bin.
+    ece.reset();
+    ece.update(&array![[0.8], [0.2]], &array![1., 1.]);
+    assert!((ece.compute() - 0.3).abs() < 1e-6);
+
+    ece.reset();
+    assert_eq!(ece.compute(), 0.);
+}
+
+#[test]
+#[should_panic(expected = "error: the number of bins must be positive.")]
+fn expected_calibration_error_fail() {
+    ExpectedCalibrationError::new(0);
+}
+
+#[test]
+fn regression() {
+    let (mut r2, mut rmse, mut mape) = (R2Score::new(), Rmse::new(), Mape::new());
+    let (predictions, targets) = (array![[1., 2.], [3., 5.]], array![[1., 2.], [3., 4.]]);
+    for _ in 0..2 {
+        r2.update(&predictions, &targets);
+        rmse.update(&predictions, &targets);
+        mape.update(&predictions, &targets);
+    }
+
+    // The targets have a total sum of squares of 5 per batch.
+    assert!((r2.compute() - 0.8).abs() < 1e-6);
+    assert!((rmse.compute() - 0.5).abs() < 1e-6);
+    assert!((mape.compute() - 0.0625).abs() < 1e-6);
+
+    r2.reset();
+    rmse.reset();
+    assert_eq!(r2.compute(), 0.);
+    assert_eq!(rmse.compute(), 0.);
+}
+
+#[test]
+fn segmentation() {
+    let (mut iou, mut dice) = (Iou::new(), Dice::new());
+    assert_eq!(iou.compute(), 1.);
+
+    let predictions = array![[[0.9, 0.8], [0.2, 0.7]]];
+    let targets = array![[[1., 0.], [0., 1.]]];
+    iou.update(&predictions, &targets);
+    dice.update(&predictions, &targets);
+
+    assert!((iou.compute() - 2. / 3.).abs() < 1e-6);
+    assert!((dice.compute() - 0.8).abs() < 1e-6);
+}
+
+#[test]
+#[should_panic(expected = "error: predictions and targets have different shapes: [2] and [3].")]
+fn shape_mismatch() {
+    Rmse::new().update(&array![1., 2.], &array![1., 2., 3.]);
+}
+
+#[test]
+fn perplexity() {
+    // A uniform model over a vocabulary of four tokens.
+    let predictions = Array2::from_elem((3, 4), 0.25_f32.ln());
+    let mut perplexity = Perplexity::new();
+    perplexity.update(&predictions, &array![0., 3., 1.]);
+    assert!((perplexity.compute() - 4.).abs() < 1e-5);
+
+    // The summed negative log likelihood of two more tokens with probability 1/2.
+    perplexity.update_nll(2. * 2_f32.ln(), 2);
+    assert!((perplexity.compute() - (4_f32.powi(3) * 4.).powf(1. / 5.)).abs() < 1e-5);
+
+    perplexity.reset();
+    assert_eq!(perplexity.compute(), 1.);
+
+    // Padding tokens do not count.
+    let mut perplexity = Perplexity::new().with_padding(0);
+    perplexity.update(&array![[0., -1.], [-10., -2.]], &array![1., 0.]);
+    assert!((perplexity.compute() - 1_f32.exp()).abs() < 1e-5);
+}
+
+#[test]
+#[should_panic(expected = "error: -1 is not a valid negative log likelihood.")]
+fn perplexity_fail() {
+    Perplexity::new().update_nll(-1., 1);
+}
+
+#[test]
+fn top_k_accuracy() {
+    let (predictions, targets) = batch();
+
+    // With k equal to 1 it is the accuracy.
+    let mut top1 = TopKAccuracy::new(1);
+    top1.update(&predictions, &targets);
+    assert!((top1.compute() - 4. / 6.).abs() < f32::EPSILON);
+
+    let mut top2 = TopKAccuracy::new(2);
+    top2.update(&predictions, &targets);
+    assert_eq!(top2.compute(), 1.);
+
+    // Ties are resolved in favour of the target.
+    let mut top1 = TopKAccuracy::new(1);
+    top1.update(&array![[0.5, 0.5]], &array![1.]);
+    assert_eq!(top1.compute(), 1.);
+
+    // Padding tokens do not count.
+    let mut top1 = TopKAccuracy::new(1).with_padding(1);
+    top1.update(&predictions, &targets);
+    assert!((top1.compute() - 3. / 4.).abs() < f32::EPSILON);
+
+    top1.reset();
+    assert_eq!(top1.compute(), 0.);
+}
+
+#[test]
+#[should_panic(expected = "error: class index out of range for 3 classes.")]
+fn top_k_accuracy_fail() {
+    TopKAccuracy::new(1).update(&array![[0.1, 0.2, 0.7]], &array![3.]);
+}
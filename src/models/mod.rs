@@ -0,0 +1,1224 @@
+//! Reference vision and text models.
+//!
+//! This module provides reference implementations of some well known neural networks for image
+//! and text classification, assembled from the layers of the [`nn`](crate::nn) module.
+//!
+//! # Vision models
+//!
+//! * [`LeNet5`] - the LeNet-5 network of
+//!   [Gradient-Based Learning Applied to Document Recognition](http://yann.lecun.com/exdb/publis/pdf/lecun-01a.pdf).
+//!
+//! * [`VGG`] - the networks of
+//!   [Very Deep Convolutional Networks for Large-Scale Image Recognition](https://arxiv.org/abs/1409.1556),
+//!   see [`VGG::vgg11()`], [`VGG::vgg13()`], [`VGG::vgg16()`] and [`VGG::vgg19()`].
+//!
+//! * [`ResNet`] - the residual networks of
+//!   [Deep Residual Learning for Image Recognition](https://arxiv.org/abs/1512.03385), see
+//!   [`ResNet::resnet18()`] and [`ResNet::resnet50()`].
+//!
+//! * [`MobileNetV2`] - the network of
+//!   [MobileNetV2: Inverted Residuals and Linear Bottlenecks](https://arxiv.org/abs/1801.04381).
+//!
+//! All the models take a batch of images of shape *(N, C, H, W)* and return the unnormalized
+//! scores of shape *(N, num_classes)*. They are [`Module`]s, so they can be switched between
+//! training and inference mode, which affects their batch normalization and dropout layers, and
+//! summarized.
+//!
+//! Pretrained weights published for the torchvision implementations of the same networks can be
+//! loaded with the `load_state_dict()` method of each model, either directly from a PyTorch
+//! checkpoint or from the `.npz` archive of a converted one. The tensors are looked up by their
+//! torchvision names, and since neuronika's convolutions always have a bias, the biases that are
+//! missing from the state dict are set to zero.
+//!
+//! ```no_run
+//! use neuronika::{
+//!     io::{read_npz, StateDict},
+//!     models::ResNet,
+//!     nn::Module,
+//! };
+//! use std::{fs::File, io::BufReader};
+//!
+//! // Converted with:
+//! // numpy.savez("resnet18.npz", **{k: v.numpy() for k, v in model.state_dict().items()})
+//! let tensors = read_npz(BufReader::new(File::open("resnet18.npz").unwrap())).unwrap();
+//!
+//! let model = ResNet::resnet18(1000);
+//! model.load_state_dict(&StateDict::from(tensors)).unwrap();
+//! model.eval();
+//!
+//! let images = neuronika::rand((1, 3, 224, 224));
+//! let scores = model.forward(images);
+//! scores.forward();
+//! ```
+//!
+//! # Text models
+//!
+//! * [`TextCNN`] - the convolutional network of
+//!   [Convolutional Neural Networks for Sentence Classification](https://arxiv.org/abs/1408.5882).
+//!
+//! * [`BiLSTMAttention`] - a bidirectional LSTM with attention pooling, as in
+//!   [Attention-Based Bidirectional Long Short-Term Memory Networks for Relation Classification](https://aclanthology.org/P16-2034/).
+//!
+//! * [`TransformerClassifier`] - a Transformer encoder, as in
+//!   [Attention Is All You Need](https://arxiv.org/abs/1706.03762), followed by a mean pooling over
+//!   time.
+//!
+//! All the text models take an [`IndexVar`](crate::IndexVar) holding a batch of sequences of token
+//! ids of shape *(N, L)* and return the unnormalized scores of shape *(N, num_classes)*. The whole
+//! batch is unrolled into the computational graph, so the sequences must share the same length.
+use crate::{
+    io::{invalid_data, StateDict},
+    nn::{
+        init, summary::ShapeInference, BatchNorm2d, Conv2d, Dropout, Flatten, GlobalAvgPool2d,
+        GroupedConv2d, Learnable, Linear, MaxPool2d, ModelStatus, Module, Register, Zero,
+    },
+    variable::RawParam,
+    Convolve, Data, Gradient, VarDiff,
+};
+use ndarray::{Dimension, Ix2, Ix4};
+use std::{
+    cell::{Cell, RefCell},
+    io,
+    rc::Rc,
+};
+
+mod text;
+
+pub use text::{BiLSTMAttention, TextCNN, TransformerClassifier};
+
+/// A dynamically typed batch of feature maps.
+type FeatureMaps = VarDiff<dyn Data<Dim = Ix4>, dyn Gradient<Dim = Ix4>>;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Blocks ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Creates a square convolution initialized as in torchvision, that is with a Kaiming normal
+/// weight in fan-out mode and a zero bias.
+fn conv(
+    in_channels: usize,
+    out_channels: usize,
+    kernel_size: usize,
+    stride: usize,
+    padding: usize,
+) -> Conv2d<Zero> {
+    let conv = Conv2d::new(
+        in_channels,
+        out_channels,
+        (kernel_size, kernel_size),
+        (padding, padding),
+        Zero,
+        (stride, stride),
+        (1, 1),
+    );
+    kaiming_normal(&conv.weight);
+    init::zeros(&conv.bias);
+
+    conv
+}
+
+/// Creates a square depthwise convolution initialized as in torchvision.
+fn depthwise_conv(channels: usize, kernel_size: usize, stride: usize) -> GroupedConv2d<Zero> {
+    let conv = GroupedConv2d::new(
+        1,
+        channels,
+        (kernel_size, kernel_size),
+        (kernel_size / 2, kernel_size / 2),
+        Zero,
+        (stride, stride),
+        (1, 1),
+        channels,
+    );
+    kaiming_normal(&conv.weight);
+    init::zeros(&conv.bias);
+
+    conv
+}
+
+/// Fills the weight of a convolution with values drawn from *N(0, 2 / fan_out)*.
+fn kaiming_normal(weight: &Learnable<Ix4>) {
+    let fan_out = weight.data().shape().iter().product::<usize>() / weight.data().shape()[1];
+    init::normal(weight, 0., (2. / fan_out as f32).sqrt());
+}
+
+/// Creates a batch normalization with the default parameters of torchvision.
+fn batch_norm(num_features: usize) -> BatchNorm2d {
+    BatchNorm2d::new(num_features, 0.1, 1e-5)
+}
+
+/// Computes *min(max(0, x), 6)*.
+fn relu6(input: FeatureMaps) -> FeatureMaps {
+    (input.clone().relu() - (input - 6.).relu()).into_dyn()
+}
+
+/// Composes the shape inferences of the components of a block applied sequentially.
+fn chain(shapes: Vec<ShapeInference>) -> ShapeInference {
+    Rc::new(move |input_shape| {
+        shapes
+            .iter()
+            .fold(input_shape.to_vec(), |shape, inference| inference(&shape))
+    })
+}
+
+/// A convolution followed by a batch normalization.
+struct ConvBn {
+    conv: Conv2d<Zero>,
+    bn: BatchNorm2d,
+}
+
+impl ConvBn {
+    fn new(in_channels: usize, out_channels: usize, kernel_size: usize, stride: usize) -> Self {
+        Self {
+            conv: conv(
+                in_channels,
+                out_channels,
+                kernel_size,
+                stride,
+                kernel_size / 2,
+            ),
+            bn: batch_norm(out_channels),
+        }
+    }
+
+    fn forward(&self, input: FeatureMaps) -> FeatureMaps {
+        self.bn.forward(self.conv.forward(input)).into_dyn()
+    }
+
+    fn load(&self, state: &StateDict, conv: &str, bn: &str) -> io::Result<()> {
+        load_conv(state, conv, &self.conv.weight, &self.conv.bias)?;
+        load_batch_norm(state, bn, &self.bn)
+    }
+}
+
+impl Register for ConvBn {
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.conv.register_params(params);
+        self.bn.register_params(params);
+    }
+
+    fn register_status(&mut self, status: Rc<Cell<bool>>) {
+        self.bn.register_status(status);
+    }
+
+    fn shape_inference(&self) -> ShapeInference {
+        chain(vec![self.conv.shape_inference(), self.bn.shape_inference()])
+    }
+
+    fn non_trainable(&self) -> usize {
+        self.bn.non_trainable()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Pretrained Weights ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Copies the tensor named `name` into `tensor`, which must have the same number of elements.
+fn copy<D: Dimension>(
+    state: &StateDict,
+    name: &str,
+    tensor: &mut ndarray::Array<f32, D>,
+) -> io::Result<()> {
+    let source = state
+        .get(name)
+        .ok_or_else(|| invalid_data(format!("no tensor named {:?} in the state dict", name)))?;
+
+    if source.len() != tensor.len() {
+        return Err(invalid_data(format!(
+            "tensor {:?} has shape {:?}, but the variable has shape {:?}",
+            name,
+            source.shape(),
+            tensor.shape()
+        )));
+    }
+    tensor
+        .iter_mut()
+        .zip(source.iter())
+        .for_each(|(dst, src)| *dst = *src);
+
+    Ok(())
+}
+
+/// Loads the weight and the bias of the convolution named `prefix`, zeroing the bias if it is
+/// missing.
+fn load_conv(
+    state: &StateDict,
+    prefix: &str,
+    weight: &Learnable<Ix4>,
+    bias: &Learnable<ndarray::Ix3>,
+) -> io::Result<()> {
+    state.load(&format!("{}.weight", prefix), weight)?;
+
+    let name = format!("{}.bias", prefix);
+    match state.get(&name) {
+        Some(_) => copy(state, &name, &mut bias.data_mut()),
+        None => {
+            init::zeros(bias);
+            Ok(())
+        }
+    }
+}
+
+/// Loads the affine parameters and the running statistics of the batch normalization named
+/// `prefix`.
+fn load_batch_norm(state: &StateDict, prefix: &str, bn: &BatchNorm2d) -> io::Result<()> {
+    copy(
+        state,
+        &format!("{}.weight", prefix),
+        &mut bn.weight.data_mut(),
+    )?;
+    copy(state, &format!("{}.bias", prefix), &mut bn.bias.data_mut())?;
+    copy(
+        state,
+        &format!("{}.running_mean", prefix),
+        &mut bn.running_mean.borrow_mut(),
+    )?;
+    copy(
+        state,
+        &format!("{}.running_var", prefix),
+        &mut RefCell::borrow_mut(&bn.running_var),
+    )
+}
+
+/// Loads the weight and the bias of the linear layer named `prefix`.
+fn load_linear(state: &StateDict, prefix: &str, linear: &Linear) -> io::Result<()> {
+    state.load(&format!("{}.weight", prefix), &linear.weight)?;
+    state.load(&format!("{}.bias", prefix), &linear.bias)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ LeNet5 ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// The **LeNet-5** convolutional network.
+///
+/// It takes single channel images of shape *(N, 1, 28, 28)*, which are zero padded to *32 x 32*
+/// by the first convolution, and uses max pooling and ReLU activations in place of the original
+/// subsampling layers and sigmoids.
+///
+/// ```
+/// use neuronika::{models::LeNet5, nn::Module};
+///
+/// let model = LeNet5::new(10);
+/// assert_eq!(model.summary(&[1, 1, 28, 28]).output_shape(), &[1, 10]);
+///
+/// let scores = model.forward(neuronika::rand((4, 1, 28, 28)));
+/// scores.forward();
+/// assert_eq!(scores.data().shape(), &[4, 10]);
+/// ```
+pub struct LeNet5 {
+    conv1: Conv2d<Zero>,
+    pool1: MaxPool2d,
+    conv2: Conv2d<Zero>,
+    pool2: MaxPool2d,
+    flatten: Flatten,
+    fc1: Linear,
+    fc2: Linear,
+    fc3: Linear,
+    status: ModelStatus,
+}
+
+impl LeNet5 {
+    /// Creates a new LeNet-5.
+    ///
+    /// # Arguments
+    ///
+    /// `num_classes` - number of classes.
+    pub fn new(num_classes: usize) -> Self {
+        let mut status = ModelStatus::default();
+
+        Self {
+            conv1: status.register(Conv2d::new(1, 6, (5, 5), (2, 2), Zero, (1, 1), (1, 1))),
+            pool1: status.register(MaxPool2d::new((2, 2), (2, 2), (0, 0))),
+            conv2: status.register(Conv2d::new(6, 16, (5, 5), (0, 0), Zero, (1, 1), (1, 1))),
+            pool2: status.register(MaxPool2d::new((2, 2), (2, 2), (0, 0))),
+            flatten: status.register(Flatten::new()),
+            fc1: status.register(Linear::new(16 * 5 * 5, 120)),
+            fc2: status.register(Linear::new(120, 84)),
+            fc3: status.register(Linear::new(84, num_classes)),
+            status,
+        }
+    }
+
+    /// Computes the scores of a batch of images of shape *(N, 1, 28, 28)*.
+    pub fn forward<I, T, U>(
+        &self,
+        input: I,
+    ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
+    where
+        I: Convolve<I, Learnable<Ix4>, Zero> + 'static,
+        I::Output: Into<VarDiff<T, U>>,
+        T: Data<Dim = Ix4> + 'static,
+        U: Gradient<Dim = Ix4> + 'static,
+    {
+        let out = self.pool1.forward(self.conv1.forward(input).relu());
+        let out = self.pool2.forward(self.conv2.forward(out).relu());
+        let out = self.fc1.forward(self.flatten.forward(out)).relu();
+        let out = self.fc2.forward(out).relu();
+        self.fc3.forward(out)
+    }
+
+    /// Loads the weights named `conv1`, `conv2`, `fc1`, `fc2` and `fc3` from `state`.
+    ///
+    /// # Errors
+    ///
+    /// If a weight is missing or has the wrong number of elements.
+    pub fn load_state_dict(&self, state: &StateDict) -> io::Result<()> {
+        load_conv(state, "conv1", &self.conv1.weight, &self.conv1.bias)?;
+        load_conv(state, "conv2", &self.conv2.weight, &self.conv2.bias)?;
+        load_linear(state, "fc1", &self.fc1)?;
+        load_linear(state, "fc2", &self.fc2)?;
+        load_linear(state, "fc3", &self.fc3)
+    }
+}
+
+impl Module for LeNet5 {
+    fn status(&self) -> &ModelStatus {
+        &self.status
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ VGG ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// A layer of the feature extractor of a [`VGG`] network.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VGGLayer {
+    /// A *3 x 3* convolution with the given number of output channels, followed by a ReLU.
+    Conv(usize),
+    /// A *2 x 2* max pooling with stride *2*.
+    MaxPool,
+}
+
+/// The configuration of VGG-11.
+const VGG11: [VGGLayer; 13] = {
+    use VGGLayer::{Conv as C, MaxPool as M};
+    [
+        C(64),
+        M,
+        C(128),
+        M,
+        C(256),
+        C(256),
+        M,
+        C(512),
+        C(512),
+        M,
+        C(512),
+        C(512),
+        M,
+    ]
+};
+
+/// The configuration of VGG-13.
+const VGG13: [VGGLayer; 15] = {
+    use VGGLayer::{Conv as C, MaxPool as M};
+    [
+        C(64),
+        C(64),
+        M,
+        C(128),
+        C(128),
+        M,
+        C(256),
+        C(256),
+        M,
+        C(512),
+        C(512),
+        M,
+        C(512),
+        C(512),
+        M,
+    ]
+};
+
+/// The configuration of VGG-16.
+const VGG16: [VGGLayer; 18] = {
+    use VGGLayer::{Conv as C, MaxPool as M};
+    [
+        C(64),
+        C(64),
+        M,
+        C(128),
+        C(128),
+        M,
+        C(256),
+        C(256),
+        C(256),
+        M,
+        C(512),
+        C(512),
+        C(512),
+        M,
+        C(512),
+        C(512),
+        C(512),
+        M,
+    ]
+};
+
+/// The configuration of VGG-19.
+const VGG19: [VGGLayer; 21] = {
+    use VGGLayer::{Conv as C, MaxPool as M};
+    [
+        C(64),
+        C(64),
+        M,
+        C(128),
+        C(128),
+        M,
+        C(256),
+        C(256),
+        C(256),
+        C(256),
+        M,
+        C(512),
+        C(512),
+        C(512),
+        C(512),
+        M,
+        C(512),
+        C(512),
+        C(512),
+        C(512),
+        M,
+    ]
+};
+
+/// A layer of the feature extractor of a VGG network, together with its index in the
+/// torchvision implementation.
+enum VGGFeature {
+    Conv(usize, Box<Conv2d<Zero>>, Option<Box<BatchNorm2d>>),
+    MaxPool(MaxPool2d),
+}
+
+/// A **VGG** convolutional network.
+///
+/// The feature extractor is made of *3 x 3* convolutions, optionally followed by batch
+/// normalizations, and of max poolings, while the classifier is made of three linear layers with
+/// dropout in between. As neuronika has no adaptive pooling, the size of the input images must
+/// be given at construction, the torchvision networks take *224 x 224* images.
+///
+/// ```
+/// use neuronika::{
+///     models::{VGGLayer, VGG},
+///     nn::Module,
+/// };
+///
+/// let config = [VGGLayer::Conv(4), VGGLayer::MaxPool, VGGLayer::Conv(8), VGGLayer::MaxPool];
+/// let model = VGG::new(&config, true, (3, 16, 16), 32, 10);
+/// assert_eq!(model.summary(&[1, 3, 16, 16]).output_shape(), &[1, 10]);
+/// ```
+pub struct VGG {
+    features: Vec<VGGFeature>,
+    flatten: Flatten,
+    fc1: Linear,
+    drop1: Dropout,
+    fc2: Linear,
+    drop2: Dropout,
+    fc3: Linear,
+    status: ModelStatus,
+}
+
+impl VGG {
+    /// Creates a new VGG network.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - layers of the feature extractor.
+    ///
+    /// * `batch_norm` - whether each convolution is followed by a batch normalization.
+    ///
+    /// * `input_size` - shape *(C, H, W)* of the input images.
+    ///
+    /// * `hidden_features` - number of features of the hidden layers of the classifier, *4096*
+    /// in the original networks.
+    ///
+    /// * `num_classes` - number of classes.
+    ///
+    /// # Panics
+    ///
+    /// If the images are too small for the max poolings of `config`.
+    pub fn new(
+        config: &[VGGLayer],
+        batch_norm: bool,
+        input_size: (usize, usize, usize),
+        hidden_features: usize,
+        num_classes: usize,
+    ) -> Self {
+        let mut status = ModelStatus::default();
+        let (mut channels, mut height, mut width) = input_size;
+        let mut index = 0;
+
+        let features = config
+            .iter()
+            .map(|layer| match *layer {
+                VGGLayer::Conv(out_channels) => {
+                    let conv = status.register(conv(channels, out_channels, 3, 1, 1));
+                    let bn = batch_norm.then(|| status.register(self::batch_norm(out_channels)));
+                    let feature = VGGFeature::Conv(index, Box::new(conv), bn.map(Box::new));
+                    index += if batch_norm { 3 } else { 2 };
+                    channels = out_channels;
+                    feature
+                }
+                VGGLayer::MaxPool => {
+                    assert!(
+                        height >= 2 && width >= 2,
+                        "error: the input images of shape {:?} are too small for VGG.",
+                        input_size
+                    );
+                    height /= 2;
+                    width /= 2;
+                    index += 1;
+                    VGGFeature::MaxPool(status.register(MaxPool2d::new((2, 2), (2, 2), (0, 0))))
+                }
+            })
+            .collect();
+
+        let classifier = |status: &mut ModelStatus, in_features, out_features| {
+            let linear = status.register(Linear::new(in_features, out_features));
+            init::normal(&linear.weight, 0., 0.01);
+            init::zeros(&linear.bias);
+            linear
+        };
+
+        Self {
+            features,
+            flatten: status.register(Flatten::new()),
+            fc1: classifier(&mut status, channels * height * width, hidden_features),
+            drop1: status.register(Dropout::new(0.5)),
+            fc2: classifier(&mut status, hidden_features, hidden_features),
+            drop2: status.register(Dropout::new(0.5)),
+            fc3: classifier(&mut status, hidden_features, num_classes),
+            status,
+        }
+    }
+
+    /// Creates a VGG-11 network for *224 x 224* RGB images.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_norm` - whether each convolution is followed by a batch normalization.
+    ///
+    /// * `num_classes` - number of classes.
+    pub fn vgg11(batch_norm: bool, num_classes: usize) -> Self {
+        Self::new(&VGG11, batch_norm, (3, 224, 224), 4096, num_classes)
+    }
+
+    /// Creates a VGG-13 network for *224 x 224* RGB images.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_norm` - whether each convolution is followed by a batch normalization.
+    ///
+    /// * `num_classes` - number of classes.
+    pub fn vgg13(batch_norm: bool, num_classes: usize) -> Self {
+        Self::new(&VGG13, batch_norm, (3, 224, 224), 4096, num_classes)
+    }
+
+    /// Creates a VGG-16 network for *224 x 224* RGB images.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_norm` - whether each convolution is followed by a batch normalization.
+    ///
+    /// * `num_classes` - number of classes.
+    pub fn vgg16(batch_norm: bool, num_classes: usize) -> Self {
+        Self::new(&VGG16, batch_norm, (3, 224, 224), 4096, num_classes)
+    }
+
+    /// Creates a VGG-19 network for *224 x 224* RGB images.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_norm` - whether each convolution is followed by a batch normalization.
+    ///
+    /// * `num_classes` - number of classes.
+    pub fn vgg19(batch_norm: bool, num_classes: usize) -> Self {
+        Self::new(&VGG19, batch_norm, (3, 224, 224), 4096, num_classes)
+    }
+
+    /// Computes the scores of a batch of images of the shape given at construction.
+    pub fn forward<I, T, U>(
+        &self,
+        input: I,
+    ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
+    where
+        I: Convolve<I, Learnable<Ix4>, Zero> + 'static,
+        I::Output: Into<VarDiff<T, U>>,
+        T: Data<Dim = Ix4> + 'static,
+        U: Gradient<Dim = Ix4> + 'static,
+    {
+        let mut layers = self.features.iter();
+        let mut out = match layers.next() {
+            Some(VGGFeature::Conv(_, conv, bn)) => conv_bn_relu(conv.forward(input), bn.as_deref()),
+            _ => panic!("error: the feature extractor of VGG must start with a convolution."),
+        };
+        for layer in layers {
+            out = match layer {
+                VGGFeature::Conv(_, conv, bn) => conv_bn_relu(conv.forward(out), bn.as_deref()),
+                VGGFeature::MaxPool(pool) => pool.forward(out).into_dyn(),
+            };
+        }
+
+        let out = self.fc1.forward(self.flatten.forward(out)).relu();
+        let out = self.fc2.forward(self.drop1.forward(out)).relu();
+        self.fc3.forward(self.drop2.forward(out))
+    }
+
+    /// Loads the weights named as in the torchvision implementation, that is `features.{i}` for
+    /// the layers of the feature extractor and `classifier.{0, 3, 6}` for the linear layers.
+    ///
+    /// # Errors
+    ///
+    /// If a weight is missing or has the wrong number of elements.
+    pub fn load_state_dict(&self, state: &StateDict) -> io::Result<()> {
+        for layer in &self.features {
+            if let VGGFeature::Conv(index, conv, bn) = layer {
+                load_conv(
+                    state,
+                    &format!("features.{}", index),
+                    &conv.weight,
+                    &conv.bias,
+                )?;
+                if let Some(bn) = bn {
+                    load_batch_norm(state, &format!("features.{}", index + 1), bn)?;
+                }
+            }
+        }
+
+        load_linear(state, "classifier.0", &self.fc1)?;
+        load_linear(state, "classifier.3", &self.fc2)?;
+        load_linear(state, "classifier.6", &self.fc3)
+    }
+}
+
+/// Applies the optional batch normalization and the activation following a convolution of VGG.
+fn conv_bn_relu<T, U>(input: VarDiff<T, U>, bn: Option<&BatchNorm2d>) -> FeatureMaps
+where
+    T: Data<Dim = Ix4> + 'static,
+    U: Gradient<Dim = Ix4> + 'static,
+{
+    match bn {
+        Some(bn) => bn.forward(input).relu().into_dyn(),
+        None => input.relu().into_dyn(),
+    }
+}
+
+impl Module for VGG {
+    fn status(&self) -> &ModelStatus {
+        &self.status
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ ResNet ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// The residual block of a [`ResNet`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResidualBlock {
+    /// Two *3 x 3* convolutions, used by ResNet-18 and ResNet-34.
+    Basic,
+    /// A *1 x 1* convolution reducing the channels, a *3 x 3* convolution and a *1 x 1*
+    /// convolution expanding the channels four times, used by ResNet-50 and deeper networks.
+    Bottleneck,
+}
+
+impl ResidualBlock {
+    /// Returns the ratio between the output channels of the block and its width.
+    fn expansion(self) -> usize {
+        match self {
+            Self::Basic => 1,
+            Self::Bottleneck => 4,
+        }
+    }
+}
+
+/// A residual block, the stride is applied by its *3 x 3* convolution.
+///
+/// It is registered as a single component, as its shortcut branch is not applied sequentially
+/// to the other layers.
+struct Residual {
+    layers: Vec<ConvBn>,
+    downsample: Option<ConvBn>,
+}
+
+impl Residual {
+    fn new(block: ResidualBlock, in_channels: usize, width: usize, stride: usize) -> Self {
+        let out_channels = width * block.expansion();
+        let layers = match block {
+            ResidualBlock::Basic => vec![
+                ConvBn::new(in_channels, width, 3, stride),
+                ConvBn::new(width, width, 3, 1),
+            ],
+            ResidualBlock::Bottleneck => vec![
+                ConvBn::new(in_channels, width, 1, 1),
+                ConvBn::new(width, width, 3, stride),
+                ConvBn::new(width, out_channels, 1, 1),
+            ],
+        };
+        let downsample = (stride != 1 || in_channels != out_channels)
+            .then(|| ConvBn::new(in_channels, out_channels, 1, stride));
+
+        Self { layers, downsample }
+    }
+
+    fn forward(&self, input: FeatureMaps) -> FeatureMaps {
+        let last = self.layers.len() - 1;
+        let mut out = input.clone();
+        for (i, layer) in self.layers.iter().enumerate() {
+            out = layer.forward(out);
+            if i != last {
+                out = out.relu().into_dyn();
+            }
+        }
+
+        let identity = match &self.downsample {
+            Some(downsample) => downsample.forward(input),
+            None => input,
+        };
+        (out + identity).relu().into_dyn()
+    }
+
+    fn load(&self, state: &StateDict, prefix: &str) -> io::Result<()> {
+        for (i, layer) in self.layers.iter().enumerate() {
+            layer.load(
+                state,
+                &format!("{}.conv{}", prefix, i + 1),
+                &format!("{}.bn{}", prefix, i + 1),
+            )?;
+        }
+        match &self.downsample {
+            Some(downsample) => downsample.load(
+                state,
+                &format!("{}.downsample.0", prefix),
+                &format!("{}.downsample.1", prefix),
+            ),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Register for Residual {
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.layers
+            .iter()
+            .chain(&self.downsample)
+            .for_each(|layer| layer.register_params(params));
+    }
+
+    fn register_status(&mut self, status: Rc<Cell<bool>>) {
+        self.layers
+            .iter_mut()
+            .chain(&mut self.downsample)
+            .for_each(|layer| layer.register_status(status.clone()));
+    }
+
+    fn shape_inference(&self) -> ShapeInference {
+        chain(self.layers.iter().map(Register::shape_inference).collect())
+    }
+
+    fn non_trainable(&self) -> usize {
+        self.layers
+            .iter()
+            .chain(&self.downsample)
+            .map(Register::non_trainable)
+            .sum()
+    }
+}
+
+/// A **residual** convolutional network.
+///
+/// A *7 x 7* convolution with stride *2* and a max pooling are followed by four stages of
+/// residual blocks, the first blocks of the last three stages halve the size of the feature maps
+/// and the stages double their width. The feature maps are then averaged and classified by a
+/// linear layer, so that images of any size of at least *32 x 32* can be fed to the network.
+///
+/// ```
+/// use neuronika::{
+///     models::{ResNet, ResidualBlock},
+///     nn::Module,
+/// };
+///
+/// let model = ResNet::new(ResidualBlock::Basic, [1, 1, 1, 1], 4, 10);
+/// assert_eq!(model.summary(&[1, 3, 32, 32]).output_shape(), &[1, 10]);
+/// ```
+pub struct ResNet {
+    stem: ConvBn,
+    pool: MaxPool2d,
+    stages: Vec<Vec<Residual>>,
+    avg_pool: GlobalAvgPool2d,
+    flatten: Flatten,
+    fc: Linear,
+    status: ModelStatus,
+}
+
+impl ResNet {
+    /// Creates a new ResNet for RGB images.
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - kind of residual block.
+    ///
+    /// * `layers` - number of blocks of each stage.
+    ///
+    /// * `width` - width of the blocks of the first stage, *64* in the original networks.
+    ///
+    /// * `num_classes` - number of classes.
+    pub fn new(block: ResidualBlock, layers: [usize; 4], width: usize, num_classes: usize) -> Self {
+        let mut status = ModelStatus::default();
+        let stem = status.register(ConvBn::new(3, width, 7, 2));
+        let pool = status.register(MaxPool2d::new((3, 3), (2, 2), (1, 1)));
+
+        let mut in_channels = width;
+        let stages = layers
+            .iter()
+            .enumerate()
+            .map(|(stage, &blocks)| {
+                let stage_width = width << stage;
+                (0..blocks)
+                    .map(|i| {
+                        let stride = if stage > 0 && i == 0 { 2 } else { 1 };
+                        let residual =
+                            status.register(Residual::new(block, in_channels, stage_width, stride));
+                        in_channels = stage_width * block.expansion();
+                        residual
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            stem,
+            pool,
+            stages,
+            avg_pool: status.register(GlobalAvgPool2d::new()),
+            flatten: status.register(Flatten::new()),
+            fc: status.register(Linear::new(in_channels, num_classes)),
+            status,
+        }
+    }
+
+    /// Creates a ResNet-18.
+    ///
+    /// # Arguments
+    ///
+    /// `num_classes` - number of classes.
+    pub fn resnet18(num_classes: usize) -> Self {
+        Self::new(ResidualBlock::Basic, [2, 2, 2, 2], 64, num_classes)
+    }
+
+    /// Creates a ResNet-50.
+    ///
+    /// # Arguments
+    ///
+    /// `num_classes` - number of classes.
+    pub fn resnet50(num_classes: usize) -> Self {
+        Self::new(ResidualBlock::Bottleneck, [3, 4, 6, 3], 64, num_classes)
+    }
+
+    /// Computes the scores of a batch of RGB images.
+    pub fn forward<I, T, U>(
+        &self,
+        input: I,
+    ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
+    where
+        I: Convolve<I, Learnable<Ix4>, Zero> + 'static,
+        I::Output: Into<VarDiff<T, U>>,
+        T: Data<Dim = Ix4> + 'static,
+        U: Gradient<Dim = Ix4> + 'static,
+    {
+        let out = self.stem.bn.forward(self.stem.conv.forward(input)).relu();
+        let mut out = self.pool.forward(out).into_dyn();
+        for residual in self.stages.iter().flatten() {
+            out = residual.forward(out);
+        }
+
+        self.fc
+            .forward(self.flatten.forward(self.avg_pool.forward(out)))
+    }
+
+    /// Loads the weights named as in the torchvision implementation, such as `conv1`, `bn1`,
+    /// `layer1.0.conv1` and `fc`.
+    ///
+    /// # Errors
+    ///
+    /// If a weight is missing or has the wrong number of elements.
+    pub fn load_state_dict(&self, state: &StateDict) -> io::Result<()> {
+        self.stem.load(state, "conv1", "bn1")?;
+        for (stage, residuals) in self.stages.iter().enumerate() {
+            for (i, residual) in residuals.iter().enumerate() {
+                residual.load(state, &format!("layer{}.{}", stage + 1, i))?;
+            }
+        }
+        load_linear(state, "fc", &self.fc)
+    }
+}
+
+impl Module for ResNet {
+    fn status(&self) -> &ModelStatus {
+        &self.status
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ MobileNetV2 ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// The expansion factor, the output channels, the number of blocks and the stride of the first
+/// block of each stage of MobileNetV2.
+const INVERTED_RESIDUALS: [(usize, usize, usize, usize); 7] = [
+    (1, 16, 1, 1),
+    (6, 24, 2, 2),
+    (6, 32, 3, 2),
+    (6, 64, 4, 2),
+    (6, 96, 3, 1),
+    (6, 160, 3, 2),
+    (6, 320, 1, 1),
+];
+
+/// Rounds `channels` to the nearest multiple of *8*, without going below *90%* of it.
+fn make_divisible(channels: f32) -> usize {
+    let rounded = (((channels + 4.) as usize) / 8 * 8).max(8);
+    if (rounded as f32) < 0.9 * channels {
+        rounded + 8
+    } else {
+        rounded
+    }
+}
+
+/// An inverted residual block of MobileNetV2, registered as a single component.
+struct InvertedResidual {
+    expand: Option<ConvBn>,
+    depthwise: GroupedConv2d<Zero>,
+    depthwise_bn: BatchNorm2d,
+    project: ConvBn,
+    residual: bool,
+}
+
+impl InvertedResidual {
+    fn new(in_channels: usize, out_channels: usize, stride: usize, expansion: usize) -> Self {
+        let hidden = in_channels * expansion;
+
+        Self {
+            expand: (expansion != 1).then(|| ConvBn::new(in_channels, hidden, 1, 1)),
+            depthwise: depthwise_conv(hidden, 3, stride),
+            depthwise_bn: batch_norm(hidden),
+            project: ConvBn::new(hidden, out_channels, 1, 1),
+            residual: stride == 1 && in_channels == out_channels,
+        }
+    }
+
+    fn forward(&self, input: FeatureMaps) -> FeatureMaps {
+        let out = match &self.expand {
+            Some(expand) => relu6(expand.forward(input.clone())),
+            None => input.clone(),
+        };
+        let out = relu6(
+            self.depthwise_bn
+                .forward(self.depthwise.forward(out))
+                .into_dyn(),
+        );
+        let out = self.project.forward(out);
+
+        if self.residual {
+            (out + input).into_dyn()
+        } else {
+            out
+        }
+    }
+
+    fn load(&self, state: &StateDict, prefix: &str) -> io::Result<()> {
+        let mut index = 0;
+        if let Some(expand) = &self.expand {
+            expand.load(
+                state,
+                &format!("{}.conv.0.0", prefix),
+                &format!("{}.conv.0.1", prefix),
+            )?;
+            index += 1;
+        }
+
+        load_conv(
+            state,
+            &format!("{}.conv.{}.0", prefix, index),
+            &self.depthwise.weight,
+            &self.depthwise.bias,
+        )?;
+        load_batch_norm(
+            state,
+            &format!("{}.conv.{}.1", prefix, index),
+            &self.depthwise_bn,
+        )?;
+        self.project.load(
+            state,
+            &format!("{}.conv.{}", prefix, index + 1),
+            &format!("{}.conv.{}", prefix, index + 2),
+        )
+    }
+}
+
+impl Register for InvertedResidual {
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        if let Some(expand) = &self.expand {
+            expand.register_params(params);
+        }
+        self.depthwise.register_params(params);
+        self.depthwise_bn.register_params(params);
+        self.project.register_params(params);
+    }
+
+    fn register_status(&mut self, status: Rc<Cell<bool>>) {
+        if let Some(expand) = &mut self.expand {
+            expand.register_status(status.clone());
+        }
+        self.depthwise_bn.register_status(status.clone());
+        self.project.register_status(status);
+    }
+
+    fn shape_inference(&self) -> ShapeInference {
+        let mut shapes: Vec<_> = self.expand.iter().map(Register::shape_inference).collect();
+        shapes.push(self.depthwise.shape_inference());
+        shapes.push(self.depthwise_bn.shape_inference());
+        shapes.push(self.project.shape_inference());
+        chain(shapes)
+    }
+
+    fn non_trainable(&self) -> usize {
+        self.expand.as_ref().map_or(0, Register::non_trainable)
+            + self.depthwise_bn.non_trainable()
+            + self.project.non_trainable()
+    }
+}
+
+/// The **MobileNetV2** convolutional network.
+///
+/// A *3 x 3* convolution with stride *2* is followed by seventeen inverted residual blocks, made
+/// of a pointwise expansion, a depthwise convolution and a linear pointwise projection, and by a
+/// pointwise convolution to *1280* channels. The feature maps are then averaged and classified
+/// by a linear layer after dropout, so that images of any size of at least *32 x 32* can be fed
+/// to the network.
+///
+/// ```
+/// use neuronika::{models::MobileNetV2, nn::Module};
+///
+/// let model = MobileNetV2::new(0.25, 10);
+/// assert_eq!(model.summary(&[1, 3, 32, 32]).output_shape(), &[1, 10]);
+/// ```
+pub struct MobileNetV2 {
+    stem: ConvBn,
+    blocks: Vec<InvertedResidual>,
+    head: ConvBn,
+    avg_pool: GlobalAvgPool2d,
+    flatten: Flatten,
+    dropout: Dropout,
+    fc: Linear,
+    status: ModelStatus,
+}
+
+impl MobileNetV2 {
+    /// Creates a new MobileNetV2 for RGB images.
+    ///
+    /// # Arguments
+    ///
+    /// * `width_mult` - multiplier of the number of channels of each layer, *1.0* in the
+    /// original network.
+    ///
+    /// * `num_classes` - number of classes.
+    pub fn new(width_mult: f32, num_classes: usize) -> Self {
+        let mut status = ModelStatus::default();
+        let mut in_channels = make_divisible(32. * width_mult);
+        let last_channels = make_divisible(1280. * width_mult.max(1.));
+        let stem = status.register(ConvBn::new(3, in_channels, 3, 2));
+
+        let mut blocks = Vec::new();
+        for &(expansion, channels, repeats, stride) in &INVERTED_RESIDUALS {
+            let out_channels = make_divisible(channels as f32 * width_mult);
+            for i in 0..repeats {
+                let stride = if i == 0 { stride } else { 1 };
+                blocks.push(status.register(InvertedResidual::new(
+                    in_channels,
+                    out_channels,
+                    stride,
+                    expansion,
+                )));
+                in_channels = out_channels;
+            }
+        }
+
+        let head = status.register(ConvBn::new(in_channels, last_channels, 1, 1));
+        let avg_pool = status.register(GlobalAvgPool2d::new());
+        let flatten = status.register(Flatten::new());
+        let dropout = status.register(Dropout::new(0.2));
+        let fc = status.register(Linear::new(last_channels, num_classes));
+        init::normal(&fc.weight, 0., 0.01);
+        init::zeros(&fc.bias);
+
+        Self {
+            stem,
+            blocks,
+            head,
+            avg_pool,
+            flatten,
+            dropout,
+            fc,
+            status,
+        }
+    }
+
+    /// Computes the scores of a batch of RGB images.
+    pub fn forward<I, T, U>(
+        &self,
+        input: I,
+    ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
+    where
+        I: Convolve<I, Learnable<Ix4>, Zero> + 'static,
+        I::Output: Into<VarDiff<T, U>>,
+        T: Data<Dim = Ix4> + 'static,
+        U: Gradient<Dim = Ix4> + 'static,
+    {
+        let out = self.stem.bn.forward(self.stem.conv.forward(input));
+        let mut out = relu6(out.into_dyn());
+        for block in &self.blocks {
+            out = block.forward(out);
+        }
+        let out = relu6(self.head.forward(out));
+
+        let out = self.flatten.forward(self.avg_pool.forward(out));
+        self.fc.forward(self.dropout.forward(out))
+    }
+
+    /// Loads the weights named as in the torchvision implementation, such as `features.0.0`,
+    /// `features.1.conv.0.0` and `classifier.1`.
+    ///
+    /// # Errors
+    ///
+    /// If a weight is missing or has the wrong number of elements.
+    pub fn load_state_dict(&self, state: &StateDict) -> io::Result<()> {
+        self.stem.load(state, "features.0.0", "features.0.1")?;
+        for (i, block) in self.blocks.iter().enumerate() {
+            block.load(state, &format!("features.{}", i + 1))?;
+        }
+        let last = self.blocks.len() + 1;
+        self.head.load(
+            state,
+            &format!("features.{}.0", last),
+            &format!("features.{}.1", last),
+        )?;
+        load_linear(state, "classifier.1", &self.fc)
+    }
+}
+
+impl Module for MobileNetV2 {
+    fn status(&self) -> &ModelStatus {
+        &self.status
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
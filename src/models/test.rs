@@ -0,0 +1,312 @@
+use super::{ConvBn, LeNet5, MobileNetV2, ResNet, ResidualBlock, VGGLayer, VGG};
+use crate::{io::StateDict, nn::Module};
+use ndarray::{ArrayD, IxDyn};
+
+/// Returns a state dict holding a tensor full of `value` for each parameter of the registered
+/// components of `model`, named after `names`.
+fn state_dict(model: &impl Module, names: &[&str], value: f32) -> StateDict {
+    let tensors = model
+        .parameters()
+        .iter()
+        .zip(names)
+        .map(|(param, name)| {
+            (
+                name.to_string(),
+                ArrayD::from_elem(IxDyn(param.data.shape()), value),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    StateDict::from(tensors)
+}
+
+#[test]
+fn lenet5() {
+    let model = LeNet5::new(10);
+
+    let summary = model.summary(&[2, 1, 28, 28]);
+    assert_eq!(summary.output_shape(), &[2, 10]);
+    assert_eq!(summary.trainable(), 61706);
+
+    let scores = model.forward(crate::rand((2, 1, 28, 28)));
+    scores.forward();
+    assert_eq!(scores.data().shape(), &[2, 10]);
+
+    let loss = scores.sum();
+    loss.forward();
+    loss.backward(1.);
+    assert!(model
+        .parameters()
+        .iter()
+        .all(|param| param.grad.iter().all(|el| el.is_finite())));
+}
+
+#[test]
+fn lenet5_load_state_dict() {
+    let model = LeNet5::new(10);
+    let names = [
+        "conv1.weight",
+        "conv1.bias",
+        "conv2.weight",
+        "conv2.bias",
+        "fc1.weight",
+        "fc1.bias",
+        "fc2.weight",
+        "fc2.bias",
+        "fc3.weight",
+        "fc3.bias",
+    ];
+
+    model
+        .load_state_dict(&state_dict(&model, &names, 0.5))
+        .unwrap();
+    assert!(model
+        .parameters()
+        .iter()
+        .all(|param| param.data.iter().all(|&el| el == 0.5)));
+
+    let incomplete = state_dict(&model, &names[..9], 0.5);
+    assert!(model.load_state_dict(&incomplete).is_err());
+}
+
+#[test]
+fn vgg() {
+    let config = [
+        VGGLayer::Conv(4),
+        VGGLayer::MaxPool,
+        VGGLayer::Conv(8),
+        VGGLayer::MaxPool,
+    ];
+    let model = VGG::new(&config, true, (3, 8, 8), 16, 5);
+
+    let summary = model.summary(&[2, 3, 8, 8]);
+    assert_eq!(summary.output_shape(), &[2, 5]);
+    assert_eq!(
+        summary.trainable(),
+        (3 * 4 * 9 + 4)
+            + 2 * 4
+            + (4 * 8 * 9 + 8)
+            + 2 * 8
+            + (32 * 16 + 16)
+            + (16 * 16 + 16)
+            + (16 * 5 + 5)
+    );
+    assert_eq!(summary.non_trainable(), 2 * 4 + 2 * 8);
+
+    let scores = model.forward(crate::rand((2, 3, 8, 8)));
+    scores.forward();
+    assert_eq!(scores.data().shape(), &[2, 5]);
+}
+
+#[test]
+fn vgg_load_state_dict() {
+    let model = VGG::new(
+        &[VGGLayer::Conv(4), VGGLayer::MaxPool],
+        false,
+        (3, 4, 4),
+        8,
+        2,
+    );
+    let names = [
+        "features.0.weight",
+        "features.0.bias",
+        "classifier.0.weight",
+        "classifier.0.bias",
+        "classifier.3.weight",
+        "classifier.3.bias",
+        "classifier.6.weight",
+        "classifier.6.bias",
+    ];
+
+    model
+        .load_state_dict(&state_dict(&model, &names, 1.))
+        .unwrap();
+    assert!(model
+        .parameters()
+        .iter()
+        .all(|param| param.data.iter().all(|&el| el == 1.)));
+}
+
+#[test]
+fn vgg16() {
+    let summary = VGG::vgg16(false, 1000).summary(&[1, 3, 224, 224]);
+
+    assert_eq!(summary.output_shape(), &[1, 1000]);
+    assert_eq!(summary.trainable(), 138357544);
+}
+
+#[test]
+fn resnet() {
+    let model = ResNet::new(ResidualBlock::Basic, [1, 1, 1, 1], 2, 3);
+
+    let summary = model.summary(&[2, 3, 32, 32]);
+    assert_eq!(summary.output_shape(), &[2, 3]);
+
+    let scores = model.forward(crate::rand((2, 3, 32, 32)));
+    scores.forward();
+    assert_eq!(scores.data().shape(), &[2, 3]);
+
+    let loss = scores.sum();
+    loss.forward();
+    loss.backward(1.);
+    assert!(model
+        .parameters()
+        .iter()
+        .all(|param| param.grad.iter().all(|el| el.is_finite())));
+}
+
+#[test]
+fn resnet_eval() {
+    let model = ResNet::new(ResidualBlock::Bottleneck, [1, 1, 1, 1], 1, 2);
+    let input = crate::rand((1, 3, 32, 32));
+
+    model.eval();
+    let scores = model.forward(input.clone());
+    scores.forward();
+    let first = scores.data().clone();
+
+    scores.forward();
+    assert_eq!(*scores.data(), first);
+}
+
+#[test]
+fn resnet18() {
+    let summary = ResNet::resnet18(1000).summary(&[1, 3, 224, 224]);
+
+    // The convolutions of neuronika have a bias, unlike the ones of torchvision.
+    let biases = 64 + 4 * (64 + 128 + 256 + 512) + 128 + 256 + 512;
+    assert_eq!(summary.output_shape(), &[1, 1000]);
+    assert_eq!(summary.trainable(), 11689512 + biases);
+}
+
+#[test]
+fn resnet50() {
+    let summary = ResNet::resnet50(1000).summary(&[1, 3, 224, 224]);
+
+    assert_eq!(summary.output_shape(), &[1, 1000]);
+    // The residual blocks are summarized as a whole.
+    assert_eq!(summary.layers().len(), 1 + 1 + 16 + 3);
+    assert_eq!(summary.layers()[2].output_shape, &[1, 256, 56, 56]);
+}
+
+#[test]
+fn resnet_load_state_dict() {
+    let model = ResNet::new(ResidualBlock::Basic, [1, 1, 0, 0], 2, 3);
+
+    // The state dicts of torchvision have no convolution biases, flat normalization weights and
+    // the number of batches tracked by each normalization.
+    let conv = |name: &str, shape: &[usize]| {
+        vec![(
+            format!("{}.weight", name),
+            ArrayD::from_elem(IxDyn(shape), 2.),
+        )]
+    };
+    let bn = |name: &str, channels: usize| {
+        [
+            ("weight", 2.),
+            ("bias", 2.),
+            ("running_mean", 3.),
+            ("running_var", 4.),
+        ]
+        .iter()
+        .map(|(param, value)| {
+            (
+                format!("{}.{}", name, param),
+                ArrayD::from_elem(IxDyn(&[channels]), *value),
+            )
+        })
+        .chain(std::iter::once((
+            format!("{}.num_batches_tracked", name),
+            ArrayD::zeros(IxDyn(&[])),
+        )))
+        .collect::<Vec<_>>()
+    };
+    let tensors = vec![
+        conv("conv1", &[2, 3, 7, 7]),
+        bn("bn1", 2),
+        conv("layer1.0.conv1", &[2, 2, 3, 3]),
+        bn("layer1.0.bn1", 2),
+        conv("layer1.0.conv2", &[2, 2, 3, 3]),
+        bn("layer1.0.bn2", 2),
+        conv("layer2.0.conv1", &[4, 2, 3, 3]),
+        bn("layer2.0.bn1", 4),
+        conv("layer2.0.conv2", &[4, 4, 3, 3]),
+        bn("layer2.0.bn2", 4),
+        conv("layer2.0.downsample.0", &[4, 2, 1, 1]),
+        bn("layer2.0.downsample.1", 4),
+        vec![
+            (
+                "fc.weight".to_string(),
+                ArrayD::from_elem(IxDyn(&[3, 4]), 2.),
+            ),
+            ("fc.bias".to_string(), ArrayD::from_elem(IxDyn(&[3]), 2.)),
+        ],
+    ];
+    let state = StateDict::from(tensors.into_iter().flatten().collect::<Vec<_>>());
+    model.load_state_dict(&state).unwrap();
+
+    let downsample = model.stages[1][0].downsample.as_ref().unwrap();
+    for layer in std::iter::once(&model.stem)
+        .chain(
+            model
+                .stages
+                .iter()
+                .flatten()
+                .flat_map(|block| &block.layers),
+        )
+        .chain(std::iter::once(downsample))
+    {
+        assert!(layer.conv.weight.data().iter().all(|&el| el == 2.));
+        assert!(layer.conv.bias.data().iter().all(|&el| el == 0.));
+        assert!(layer.bn.weight.data().iter().all(|&el| el == 2.));
+        assert!(layer.bn.bias.data().iter().all(|&el| el == 2.));
+        assert!(layer.bn.running_mean.borrow().iter().all(|&el| el == 3.));
+        assert!(layer.bn.running_var.borrow().iter().all(|&el| el == 4.));
+    }
+    assert!(model.fc.weight.data().iter().all(|&el| el == 2.));
+
+    let missing = StateDict::from(conv("conv1", &[2, 3, 7, 7]));
+    assert!(model.load_state_dict(&missing).is_err());
+}
+
+#[test]
+fn mobilenet_v2() {
+    let model = MobileNetV2::new(0.25, 4);
+
+    let summary = model.summary(&[1, 3, 32, 32]);
+    assert_eq!(summary.output_shape(), &[1, 4]);
+
+    let scores = model.forward(crate::rand((1, 3, 32, 32)));
+    scores.forward();
+    assert_eq!(scores.data().shape(), &[1, 4]);
+
+    let loss = scores.sum();
+    loss.forward();
+    loss.backward(1.);
+    assert!(model
+        .parameters()
+        .iter()
+        .all(|param| param.grad.iter().all(|el| el.is_finite())));
+}
+
+#[test]
+fn mobilenet_v2_params() {
+    let model = MobileNetV2::new(1., 1000);
+    let summary = model.summary(&[1, 3, 224, 224]);
+
+    // The convolutions of neuronika have a bias, unlike the ones of torchvision.
+    let conv_bn_bias = |layer: &ConvBn| layer.conv.bias.data().len();
+    let biases = conv_bn_bias(&model.stem)
+        + conv_bn_bias(&model.head)
+        + model
+            .blocks
+            .iter()
+            .map(|block| {
+                block.expand.as_ref().map_or(0, conv_bn_bias)
+                    + block.depthwise.bias.data().len()
+                    + conv_bn_bias(&block.project)
+            })
+            .sum::<usize>();
+    assert_eq!(summary.output_shape(), &[1, 1000]);
+    assert_eq!(summary.trainable(), 3504872 + biases);
+}
@@ -0,0 +1,715 @@
+use crate::{
+    nn::{
+        summary::ShapeInference, Conv2d, Dropout, Embedding, LSTMCell, LayerNorm, Linear,
+        ModelStatus, Module, MultiheadAttention, Register, Zero,
+    },
+    variable::RawParam,
+    Data, Gradient, IndexData, IndexVar, VarDiff,
+};
+use ndarray::{Array1, Array2, Ix2, Ix3};
+use std::{cell::Cell, rc::Rc};
+
+/// A dynamically typed batch of feature vectors.
+type Features = VarDiff<dyn Data<Dim = Ix2>, dyn Gradient<Dim = Ix2>>;
+
+/// Splits a batch of embedded sequences of shape *(N, L, E)* into its *L* time steps, each of
+/// shape *(N, E)*.
+fn time_steps<T: ?Sized, U: ?Sized>(embedded: VarDiff<T, U>) -> Vec<Features>
+where
+    T: Data<Dim = Ix3> + 'static,
+    U: Gradient<Dim = Ix3> + 'static,
+{
+    let (batch_size, _, embedding_dim) = embedded.data().dim();
+
+    embedded
+        .chunks((batch_size, 1, embedding_dim))
+        .into_iter()
+        .map(|step| step.flatten().into_dyn())
+        .collect()
+}
+
+/// Asserts that `input_shape` is the shape of a batch of sequences of `features` features.
+fn check_sequences(name: &str, input_shape: &[usize], features: usize) {
+    assert!(
+        input_shape.len() == 3 && input_shape[2] == features,
+        "error: {} expects an input of shape (N, L, {}), got {:?}.",
+        name,
+        features,
+        input_shape
+    );
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ TextCNN ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// The parallel convolutions of a [`TextCNN`], each followed by a max pooling over time.
+///
+/// They are registered as a single component, as they are not applied sequentially.
+struct TemporalConvolutions {
+    convs: Vec<Conv2d<Zero>>,
+}
+
+impl TemporalConvolutions {
+    fn forward<T: ?Sized, U: ?Sized>(&self, embedded: VarDiff<T, U>) -> Features
+    where
+        T: Data<Dim = Ix3> + 'static,
+        U: Gradient<Dim = Ix3> + 'static,
+    {
+        // The embedded sequences are seen as single channel images of shape (L, E).
+        let images = embedded.unsqueeze(1);
+
+        let features: Vec<_> = self
+            .convs
+            .iter()
+            .map(|conv| {
+                let maps = conv.forward(images.clone()).relu();
+                let steps = maps.data().shape()[2];
+                maps.max_pool2d((steps, 1), (steps, 1), (0, 0))
+                    .flatten()
+                    .into_dyn()
+            })
+            .collect();
+
+        VarDiff::cat(&features, 1).into_dyn()
+    }
+}
+
+impl Register for TemporalConvolutions {
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.convs
+            .iter()
+            .for_each(|conv| conv.register_params(params));
+    }
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        let kernels: Vec<_> = self
+            .convs
+            .iter()
+            .map(|conv| conv.weight.data().shape().to_vec())
+            .collect();
+
+        Rc::new(move |input_shape| {
+            check_sequences("TextCNN", input_shape, kernels[0][3]);
+            for kernel in &kernels {
+                assert!(
+                    input_shape[1] >= kernel[2],
+                    "error: TextCNN needs sequences of at least {} tokens, got {:?}.",
+                    kernel[2],
+                    input_shape
+                );
+            }
+
+            let filters = kernels.iter().map(|kernel| kernel[0]).sum();
+            vec![input_shape[0], filters]
+        })
+    }
+}
+
+/// The convolutional sentence classifier of
+/// [Convolutional Neural Networks for Sentence Classification](https://arxiv.org/abs/1408.5882).
+///
+/// The embedded tokens are convolved with filters spanning several consecutive tokens, the
+/// responses of each filter are max pooled over time and the resulting features are classified by
+/// a linear layer after dropout.
+///
+/// ```
+/// use neuronika::{models::TextCNN, nn::Module};
+///
+/// let model = TextCNN::new(100, 16, &[2, 3, 4], 8, 0.5, 2);
+/// assert_eq!(model.summary(&[4, 12]).output_shape(), &[4, 2]);
+///
+/// let tokens = neuronika::indices(ndarray::Array2::from_elem((4, 12), 7));
+/// let scores = model.forward(tokens);
+/// scores.forward();
+/// assert_eq!(scores.data().shape(), &[4, 2]);
+/// ```
+pub struct TextCNN {
+    embedding: Embedding,
+    convs: TemporalConvolutions,
+    dropout: Dropout,
+    fc: Linear,
+    status: ModelStatus,
+}
+
+impl TextCNN {
+    /// Creates a new TextCNN.
+    ///
+    /// # Arguments
+    ///
+    /// * `vocab_size` - number of tokens of the vocabulary.
+    ///
+    /// * `embedding_dim` - size of the embeddings of the tokens.
+    ///
+    /// * `kernel_sizes` - number of consecutive tokens spanned by the filters of each
+    /// convolution, *[3, 4, 5]* in the original network.
+    ///
+    /// * `num_filters` - number of filters of each convolution.
+    ///
+    /// * `dropout` - probability of an element of the features to be zeroed during training.
+    ///
+    /// * `num_classes` - number of classes.
+    ///
+    /// # Panics
+    ///
+    /// If `kernel_sizes` is empty.
+    pub fn new(
+        vocab_size: usize,
+        embedding_dim: usize,
+        kernel_sizes: &[usize],
+        num_filters: usize,
+        dropout: f64,
+        num_classes: usize,
+    ) -> Self {
+        assert!(
+            !kernel_sizes.is_empty(),
+            "error: TextCNN needs at least one kernel size."
+        );
+
+        let mut status = ModelStatus::default();
+        let convs = kernel_sizes
+            .iter()
+            .map(|&kernel_size| {
+                Conv2d::new(
+                    1,
+                    num_filters,
+                    (kernel_size, embedding_dim),
+                    (0, 0),
+                    Zero,
+                    (1, 1),
+                    (1, 1),
+                )
+            })
+            .collect();
+
+        Self {
+            embedding: status.register(Embedding::new(vocab_size, embedding_dim)),
+            convs: status.register(TemporalConvolutions { convs }),
+            dropout: status.register(Dropout::new(dropout)),
+            fc: status.register(Linear::new(num_filters * kernel_sizes.len(), num_classes)),
+            status,
+        }
+    }
+
+    /// Computes the scores of a batch of sequences of tokens.
+    ///
+    /// # Arguments
+    ///
+    /// `tokens` - index variable of shape *(N, L)* containing the ids of the tokens, the sequences
+    /// must be at least as long as the largest kernel size.
+    pub fn forward<T: ?Sized>(
+        &self,
+        tokens: IndexVar<T>,
+    ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
+    where
+        T: IndexData<Dim = Ix2> + 'static,
+    {
+        let features = self.convs.forward(self.embedding.forward(tokens));
+        self.fc.forward(self.dropout.forward(features))
+    }
+}
+
+impl Module for TextCNN {
+    fn status(&self) -> &ModelStatus {
+        &self.status
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ BiLSTMAttention ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// A bidirectional LSTM, made of a cell reading the sequences forwards and of one reading them
+/// backwards.
+struct BiLSTM {
+    forward_cell: LSTMCell,
+    backward_cell: LSTMCell,
+}
+
+impl BiLSTM {
+    /// Returns the concatenation of the hidden states of the two directions at each time step.
+    fn forward(&self, steps: &[Features]) -> Vec<Features> {
+        let forward_states = Self::run(&self.forward_cell, steps.iter());
+        let mut backward_states = Self::run(&self.backward_cell, steps.iter().rev());
+        backward_states.reverse();
+
+        forward_states
+            .into_iter()
+            .zip(backward_states)
+            .map(|(forward, backward)| VarDiff::cat(&[forward, backward], 1).into_dyn())
+            .collect()
+    }
+
+    /// Feeds `steps` to `cell` in order, starting from a zero state, and returns the hidden
+    /// states.
+    fn run<'a>(cell: &LSTMCell, steps: impl Iterator<Item = &'a Features>) -> Vec<Features> {
+        let hidden_size = cell.weight_hh.data().ncols();
+        let mut hidden_states = Vec::new();
+        let mut state: Option<(Features, Features)> = None;
+
+        for step in steps {
+            let (cell_state, hidden) = state.unwrap_or_else(|| {
+                let zeros = || {
+                    crate::zeros((step.data().nrows(), hidden_size))
+                        .requires_grad()
+                        .into_dyn()
+                };
+                (zeros(), zeros())
+            });
+            let (cell_state, hidden) = cell.forward((cell_state, hidden), step.clone());
+            let (cell_state, hidden) = (cell_state.into_dyn(), hidden.into_dyn());
+
+            hidden_states.push(hidden.clone());
+            state = Some((cell_state, hidden));
+        }
+
+        hidden_states
+    }
+}
+
+impl Register for BiLSTM {
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.forward_cell.register_params(params);
+        self.backward_cell.register_params(params);
+    }
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        let (input_size, hidden_size) = (
+            self.forward_cell.weight_ih.data().ncols(),
+            self.forward_cell.weight_hh.data().ncols(),
+        );
+
+        Rc::new(move |input_shape| {
+            check_sequences("BiLSTM", input_shape, input_size);
+            vec![input_shape[0], input_shape[1], 2 * hidden_size]
+        })
+    }
+}
+
+/// The attention pooling of a [`BiLSTMAttention`], which averages the hidden states weighted by
+/// their similarity with a learned context vector.
+struct AttentionPooling {
+    projection: Linear,
+    context: Linear,
+}
+
+impl AttentionPooling {
+    fn forward(&self, states: Vec<Features>) -> Features {
+        let batch_size = states[0].data().nrows();
+        let scores: Vec<_> = states
+            .iter()
+            .map(|state| {
+                let projected = self.projection.forward(state.clone()).tanh();
+                self.context.forward(projected).into_dyn()
+            })
+            .collect();
+        let weights = VarDiff::cat(&scores, 1).softmax(1).chunks((batch_size, 1));
+
+        weights
+            .into_iter()
+            .zip(states)
+            .map(|(weight, state)| (weight * state).into_dyn())
+            .reduce(|pooled, weighted| (pooled + weighted).into_dyn())
+            .unwrap()
+    }
+}
+
+impl Register for AttentionPooling {
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.projection.register_params(params);
+        self.context.register_params(params);
+    }
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        let features = self.projection.weight.data().ncols();
+
+        Rc::new(move |input_shape| {
+            check_sequences("AttentionPooling", input_shape, features);
+            vec![input_shape[0], features]
+        })
+    }
+}
+
+/// A bidirectional LSTM classifier with attention pooling, as in
+/// [Attention-Based Bidirectional Long Short-Term Memory Networks for Relation Classification](https://aclanthology.org/P16-2034/).
+///
+/// The embedded tokens are read by an LSTM in both directions, the hidden states of the two
+/// directions are concatenated and averaged with weights given by the softmax of their scores
+/// against a learned context vector. The result is classified by a linear layer after dropout.
+///
+/// ```
+/// use neuronika::{models::BiLSTMAttention, nn::Module};
+///
+/// let model = BiLSTMAttention::new(100, 16, 8, 0.5, 3);
+/// assert_eq!(model.summary(&[4, 5]).output_shape(), &[4, 3]);
+///
+/// let tokens = neuronika::indices(ndarray::Array2::from_elem((4, 5), 7));
+/// let scores = model.forward(tokens);
+/// scores.forward();
+/// assert_eq!(scores.data().shape(), &[4, 3]);
+/// ```
+pub struct BiLSTMAttention {
+    embedding: Embedding,
+    lstm: BiLSTM,
+    attention: AttentionPooling,
+    dropout: Dropout,
+    fc: Linear,
+    status: ModelStatus,
+}
+
+impl BiLSTMAttention {
+    /// Creates a new BiLSTMAttention.
+    ///
+    /// # Arguments
+    ///
+    /// * `vocab_size` - number of tokens of the vocabulary.
+    ///
+    /// * `embedding_dim` - size of the embeddings of the tokens.
+    ///
+    /// * `hidden_size` - number of features of the hidden state of each direction.
+    ///
+    /// * `dropout` - probability of an element of the pooled features to be zeroed during
+    /// training.
+    ///
+    /// * `num_classes` - number of classes.
+    pub fn new(
+        vocab_size: usize,
+        embedding_dim: usize,
+        hidden_size: usize,
+        dropout: f64,
+        num_classes: usize,
+    ) -> Self {
+        let mut status = ModelStatus::default();
+        let features = 2 * hidden_size;
+
+        Self {
+            embedding: status.register(Embedding::new(vocab_size, embedding_dim)),
+            lstm: status.register(BiLSTM {
+                forward_cell: LSTMCell::new(embedding_dim, hidden_size),
+                backward_cell: LSTMCell::new(embedding_dim, hidden_size),
+            }),
+            attention: status.register(AttentionPooling {
+                projection: Linear::new(features, features),
+                context: Linear::new(features, 1),
+            }),
+            dropout: status.register(Dropout::new(dropout)),
+            fc: status.register(Linear::new(features, num_classes)),
+            status,
+        }
+    }
+
+    /// Computes the scores of a batch of sequences of tokens.
+    ///
+    /// # Arguments
+    ///
+    /// `tokens` - index variable of shape *(N, L)* containing the ids of the tokens.
+    pub fn forward<T: ?Sized>(
+        &self,
+        tokens: IndexVar<T>,
+    ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
+    where
+        T: IndexData<Dim = Ix2> + 'static,
+    {
+        let states = self
+            .lstm
+            .forward(&time_steps(self.embedding.forward(tokens)));
+        let pooled = self.attention.forward(states);
+        self.fc.forward(self.dropout.forward(pooled))
+    }
+}
+
+impl Module for BiLSTMAttention {
+    fn status(&self) -> &ModelStatus {
+        &self.status
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ TransformerClassifier ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// The sum of the embeddings of the tokens and of the learned embeddings of their positions.
+///
+/// The embedded sequences of a batch are laid out time step after time step, so that the
+/// embedding of the *t*-th token of the *n*-th sequence is the row *t N + n* of the output.
+struct TokenEmbedding {
+    tokens: Embedding,
+    positions: Embedding,
+}
+
+impl TokenEmbedding {
+    fn forward<T: ?Sized>(&self, tokens: IndexVar<T>) -> Features
+    where
+        T: IndexData<Dim = Ix2> + 'static,
+    {
+        let (batch_size, len) = tokens.data().dim();
+        let max_len = self.positions.weight.data().nrows();
+        assert!(
+            len <= max_len,
+            "error: TransformerClassifier supports sequences of at most {} tokens, got {}.",
+            max_len,
+            len
+        );
+
+        let steps = time_steps(self.tokens.forward(tokens));
+        let positions = Array1::from_shape_fn(len * batch_size, |row| (row / batch_size) as i64);
+
+        (VarDiff::cat(&steps, 0) + self.positions.forward(crate::indices(positions))).into_dyn()
+    }
+}
+
+impl Register for TokenEmbedding {
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.tokens.register_params(params);
+        self.positions.register_params(params);
+    }
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        self.tokens.shape_inference()
+    }
+}
+
+/// A post-normalization Transformer encoder layer, made of a self-attention block and of a
+/// feed-forward block, each wrapped in a residual connection followed by a layer normalization.
+struct EncoderLayer {
+    attention: MultiheadAttention,
+    norm1: LayerNorm,
+    linear1: Linear,
+    linear2: Linear,
+    norm2: LayerNorm,
+    dropout: Dropout,
+}
+
+impl EncoderLayer {
+    fn new(d_model: usize, num_heads: usize, dim_feedforward: usize, dropout: f64) -> Self {
+        Self {
+            attention: MultiheadAttention::new(d_model, num_heads),
+            norm1: LayerNorm::new(d_model, 1e-5),
+            linear1: Linear::new(d_model, dim_feedforward),
+            linear2: Linear::new(dim_feedforward, d_model),
+            norm2: LayerNorm::new(d_model, 1e-5),
+            dropout: Dropout::new(dropout),
+        }
+    }
+
+    fn forward(&self, input: Features, mask: &Array2<f32>) -> Features {
+        let attended =
+            self.attention
+                .forward(input.clone(), input.clone(), input.clone(), Some(mask));
+        let out = self
+            .norm1
+            .forward(input + self.dropout.forward(attended))
+            .into_dyn();
+
+        let hidden = self.linear1.forward(out.clone()).relu();
+        let projected = self.linear2.forward(self.dropout.forward(hidden));
+        self.norm2
+            .forward(out + self.dropout.forward(projected))
+            .into_dyn()
+    }
+}
+
+impl Register for EncoderLayer {
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.attention.register_params(params);
+        self.norm1.register_params(params);
+        self.linear1.register_params(params);
+        self.linear2.register_params(params);
+        self.norm2.register_params(params);
+    }
+
+    fn register_status(&mut self, status: Rc<Cell<bool>>) {
+        self.dropout.register_status(status);
+    }
+
+    fn shape_inference(&self) -> ShapeInference {
+        let d_model = self.linear1.weight.data().ncols();
+
+        Rc::new(move |input_shape| {
+            check_sequences("EncoderLayer", input_shape, d_model);
+            input_shape.to_vec()
+        })
+    }
+}
+
+/// Averages the encoded sequences over time.
+struct MeanPooling;
+
+impl MeanPooling {
+    /// Averages the rows of `encoded` belonging to each of the `batch_size` sequences.
+    fn forward(&self, encoded: Features, batch_size: usize) -> Features {
+        let rows = encoded.data().nrows();
+        let len = rows / batch_size;
+        let average = Array2::from_shape_fn((batch_size, rows), |(sequence, row)| {
+            if row % batch_size == sequence {
+                1. / len as f32
+            } else {
+                0.
+            }
+        });
+
+        crate::from_ndarray(average).mm(encoded).into_dyn()
+    }
+}
+
+impl Register for MeanPooling {
+    fn register_params(&self, _: &mut Vec<RawParam>) {}
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        Rc::new(|input_shape| {
+            assert_eq!(
+                input_shape.len(),
+                3,
+                "error: MeanPooling expects an input of shape (N, L, E), got {:?}.",
+                input_shape
+            );
+            vec![input_shape[0], input_shape[2]]
+        })
+    }
+}
+
+/// A Transformer encoder classifier, built from the encoder of
+/// [Attention Is All You Need](https://arxiv.org/abs/1706.03762).
+///
+/// The tokens and their positions are embedded and fed to a stack of encoder layers, the encoded
+/// sequences are averaged over time and classified by a linear layer.
+///
+/// The sequences of a batch attend to each other's tokens only, which is achieved by stacking
+/// them in a single sequence and masking the attention between tokens of different sequences, so
+/// that the cost of the attention grows with the square of the number of tokens of the batch.
+///
+/// ```
+/// use neuronika::{models::TransformerClassifier, nn::Module};
+///
+/// let model = TransformerClassifier::new(100, 16, 8, 2, 16, 2, 0.1, 3);
+/// assert_eq!(model.summary(&[4, 6]).output_shape(), &[4, 3]);
+///
+/// let tokens = neuronika::indices(ndarray::Array2::from_elem((4, 6), 7));
+/// let scores = model.forward(tokens);
+/// scores.forward();
+/// assert_eq!(scores.data().shape(), &[4, 3]);
+/// ```
+pub struct TransformerClassifier {
+    embedding: TokenEmbedding,
+    layers: Vec<EncoderLayer>,
+    pooling: MeanPooling,
+    fc: Linear,
+    status: ModelStatus,
+}
+
+impl TransformerClassifier {
+    /// Creates a new TransformerClassifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `vocab_size` - number of tokens of the vocabulary.
+    ///
+    /// * `max_len` - maximum length of the sequences.
+    ///
+    /// * `d_model` - number of features of the embeddings and of the encoded tokens.
+    ///
+    /// * `num_heads` - number of heads of the self-attention of each encoder layer.
+    ///
+    /// * `dim_feedforward` - number of hidden features of the feed-forward block of each encoder
+    /// layer.
+    ///
+    /// * `num_layers` - number of encoder layers.
+    ///
+    /// * `dropout` - probability of an element of the output of each block to be zeroed during
+    /// training.
+    ///
+    /// * `num_classes` - number of classes.
+    ///
+    /// # Panics
+    ///
+    /// If `d_model` is not divisible by `num_heads`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        vocab_size: usize,
+        max_len: usize,
+        d_model: usize,
+        num_heads: usize,
+        dim_feedforward: usize,
+        num_layers: usize,
+        dropout: f64,
+        num_classes: usize,
+    ) -> Self {
+        let mut status = ModelStatus::default();
+        let embedding = status.register(TokenEmbedding {
+            tokens: Embedding::new(vocab_size, d_model),
+            positions: Embedding::new(max_len, d_model),
+        });
+        let layers = (0..num_layers)
+            .map(|_| {
+                status.register(EncoderLayer::new(
+                    d_model,
+                    num_heads,
+                    dim_feedforward,
+                    dropout,
+                ))
+            })
+            .collect();
+
+        Self {
+            embedding,
+            layers,
+            pooling: status.register(MeanPooling),
+            fc: status.register(Linear::new(d_model, num_classes)),
+            status,
+        }
+    }
+
+    /// Computes the scores of a batch of sequences of tokens.
+    ///
+    /// # Arguments
+    ///
+    /// `tokens` - index variable of shape *(N, L)* containing the ids of the tokens, with *L* at
+    /// most `max_len`.
+    pub fn forward<T: ?Sized>(
+        &self,
+        tokens: IndexVar<T>,
+    ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
+    where
+        T: IndexData<Dim = Ix2> + 'static,
+    {
+        let batch_size = tokens.data().nrows();
+        let mut out = self.embedding.forward(tokens);
+
+        // Tokens of different sequences lie in rows that differ modulo the batch size.
+        let rows = out.data().nrows();
+        let mask = Array2::from_shape_fn((rows, rows), |(i, j)| {
+            if i % batch_size == j % batch_size {
+                0.
+            } else {
+                f32::MIN
+            }
+        });
+        for layer in &self.layers {
+            out = layer.forward(out, &mask);
+        }
+
+        self.fc.forward(self.pooling.forward(out, batch_size))
+    }
+}
+
+impl Module for TransformerClassifier {
+    fn status(&self) -> &ModelStatus {
+        &self.status
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,170 @@
+use super::{BiLSTMAttention, TextCNN, TransformerClassifier};
+use crate::{
+    nn::{
+        loss::{nll_loss, Reduction},
+        Module,
+    },
+    optim::{Adam, L2},
+    Data, Gradient, IndexData, IndexVar, Var, VarDiff,
+};
+use ndarray::{array, Array1, Array2, Ix1, Ix2};
+
+/// Returns a batch of 4 sequences of 6 tokens, where the sequences of the first class contain
+/// token 1 and those of the second class contain token 2.
+fn sequences() -> (Array2<i64>, Array1<f32>) {
+    let tokens = array![
+        [3, 1, 4, 5, 6, 7],
+        [8, 9, 3, 4, 1, 5],
+        [3, 2, 4, 5, 6, 7],
+        [8, 9, 3, 4, 2, 5],
+    ];
+
+    (tokens, array![0., 0., 1., 1.])
+}
+
+/// Checks that the gradient of every parameter of `model` is finite after backpropagating the
+/// sum of `scores`.
+fn check_gradients<T: ?Sized, U: ?Sized>(model: &impl Module, scores: VarDiff<T, U>)
+where
+    T: Data<Dim = Ix2> + 'static,
+    U: Gradient<Dim = Ix2> + 'static,
+{
+    let loss = scores.sum();
+    loss.forward();
+    loss.backward(1.);
+    assert!(model
+        .parameters()
+        .iter()
+        .all(|param| param.grad.iter().all(|el| el.is_finite())));
+}
+
+/// Trains `model` on [`sequences()`] and checks that its loss decreases.
+fn check_training<T: ?Sized, U: ?Sized>(
+    model: &impl Module,
+    forward: impl Fn(IndexVar<dyn IndexData<Dim = Ix2>>) -> VarDiff<T, U>,
+) where
+    T: Data<Dim = Ix2> + 'static,
+    U: Gradient<Dim = Ix2> + 'static,
+{
+    let (tokens, labels) = sequences();
+    let scores = forward(crate::indices(tokens).into_dyn());
+    let target: Var<dyn Data<Dim = Ix1>> = crate::from_ndarray(labels).into_dyn();
+    let loss = nll_loss(scores.log_softmax(1), target, Reduction::Mean);
+    let optim = Adam::new(model.parameters(), 0.01, (0.9, 0.999), L2::new(0.), 1e-8);
+
+    loss.forward();
+    let first = loss.data()[()];
+    for _ in 0..20 {
+        loss.backward(1.);
+        optim.step();
+        optim.zero_grad();
+        loss.forward();
+    }
+
+    assert!(loss.data()[()] < first);
+}
+
+#[test]
+fn text_cnn() {
+    let model = TextCNN::new(10, 8, &[2, 3], 4, 0.5, 2);
+
+    let summary = model.summary(&[4, 6]);
+    assert_eq!(summary.output_shape(), &[4, 2]);
+    // Embedding, convolutions and linear layer.
+    assert_eq!(
+        summary.trainable(),
+        10 * 8 + (4 * 2 * 8 + 4) + (4 * 3 * 8 + 4) + 8 * 2 + 2
+    );
+
+    let scores = model.forward(crate::indices(sequences().0));
+    scores.forward();
+    assert_eq!(scores.data().shape(), &[4, 2]);
+    check_gradients(&model, scores);
+}
+
+#[test]
+#[should_panic]
+fn text_cnn_short_sequences() {
+    let model = TextCNN::new(10, 8, &[2, 3], 4, 0.5, 2);
+    model.summary(&[4, 2]);
+}
+
+#[test]
+fn text_cnn_training() {
+    let model = TextCNN::new(10, 8, &[2, 3], 4, 0., 2);
+    check_training(&model, |tokens| model.forward(tokens));
+}
+
+#[test]
+fn bilstm_attention() {
+    let model = BiLSTMAttention::new(10, 8, 4, 0.5, 3);
+
+    let summary = model.summary(&[4, 6]);
+    assert_eq!(summary.output_shape(), &[4, 3]);
+    // Embedding, two LSTM cells, attention and linear layer.
+    assert_eq!(
+        summary.trainable(),
+        10 * 8 + 2 * (4 * 4 * (8 + 4) + 2 * 4 * 4) + (8 * 8 + 8 + 8 + 1) + 8 * 3 + 3
+    );
+
+    let scores = model.forward(crate::indices(sequences().0));
+    scores.forward();
+    assert_eq!(scores.data().shape(), &[4, 3]);
+    check_gradients(&model, scores);
+}
+
+#[test]
+fn bilstm_attention_training() {
+    let model = BiLSTMAttention::new(10, 8, 4, 0., 2);
+    check_training(&model, |tokens| model.forward(tokens));
+}
+
+#[test]
+fn transformer_classifier() {
+    let model = TransformerClassifier::new(10, 6, 8, 2, 16, 2, 0.1, 3);
+
+    let summary = model.summary(&[4, 6]);
+    assert_eq!(summary.output_shape(), &[4, 3]);
+    let layer = 4 * (8 * 8 + 8) + 2 * (2 * 8) + (8 * 16 + 16) + (16 * 8 + 8);
+    assert_eq!(summary.trainable(), 10 * 8 + 6 * 8 + 2 * layer + 8 * 3 + 3);
+
+    let scores = model.forward(crate::indices(sequences().0));
+    scores.forward();
+    assert_eq!(scores.data().shape(), &[4, 3]);
+    check_gradients(&model, scores);
+}
+
+#[test]
+fn transformer_classifier_independent_sequences() {
+    let model = TransformerClassifier::new(10, 6, 8, 2, 16, 1, 0.1, 3);
+    model.eval();
+
+    let (mut tokens, _) = sequences();
+    let scores = model.forward(crate::indices(tokens.clone()));
+    scores.forward();
+    let first = scores.data().clone();
+
+    // Changing the last sequence must not affect the scores of the others.
+    tokens.row_mut(3).fill(0);
+    let scores = model.forward(crate::indices(tokens));
+    scores.forward();
+    for row in 0..3 {
+        for (a, b) in scores.data().row(row).iter().zip(first.row(row)) {
+            assert!((a - b).abs() < 1e-5);
+        }
+    }
+    assert_ne!(scores.data().row(3), first.row(3));
+}
+
+#[test]
+#[should_panic]
+fn transformer_classifier_long_sequences() {
+    let model = TransformerClassifier::new(10, 4, 8, 2, 16, 1, 0.1, 3);
+    model.forward(crate::indices(sequences().0));
+}
+
+#[test]
+fn transformer_classifier_training() {
+    let model = TransformerClassifier::new(10, 6, 8, 2, 16, 1, 0., 2);
+    check_training(&model, |tokens| model.forward(tokens));
+}
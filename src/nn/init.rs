@@ -20,7 +20,6 @@
 //! ```
 use super::Learnable;
 use ndarray::{Dimension, Ix2};
-use rand::thread_rng;
 use rand_distr::{Distribution, Normal, Uniform};
 
 /// Returns the recommended gain value for the given non-linearity function.
@@ -190,10 +189,11 @@ pub fn dirac<D: Dimension>(param: &Learnable<D>, groups: usize) {
 /// If `low` >= `high`.
 pub fn uniform<D: Dimension>(param: &Learnable<D>, low: f32, high: f32) {
     let unif_dstr = Uniform::new(low, high);
-    let mut t_rng = thread_rng();
-    param
-        .data_mut()
-        .map_inplace(|el| *el = unif_dstr.sample(&mut t_rng));
+    crate::random::with_rng(|rng| {
+        param
+            .data_mut()
+            .map_inplace(|el| *el = unif_dstr.sample(rng))
+    });
 }
 
 /// Fills the differentiable leaf variable with elements drawn from the normal distribution
@@ -208,10 +208,11 @@ pub fn uniform<D: Dimension>(param: &Learnable<D>, low: f32, high: f32) {
 /// * `std` - standard deviation of the normal distribution.
 pub fn normal<D: Dimension>(param: &Learnable<D>, mean: f32, std: f32) {
     let norm_dstr = Normal::new(mean, std).unwrap();
-    let mut t_rng = thread_rng();
-    param
-        .data_mut()
-        .map_inplace(|el| *el = norm_dstr.sample(&mut t_rng));
+    crate::random::with_rng(|rng| {
+        param
+            .data_mut()
+            .map_inplace(|el| *el = norm_dstr.sample(rng))
+    });
 }
 
 /// Fills the differentiable leaf variable with values according to the method described in
@@ -229,10 +230,11 @@ pub fn xavier_uniform<D: Dimension>(param: &Learnable<D>, gain: f32) {
     let std = gain * (2. / ((fan_in + fan_out) as f32)).sqrt();
     let a = 3.0_f32.sqrt() * std;
     let unif_distr = Uniform::new(-a, a);
-    let mut t_rng = thread_rng();
-    param
-        .data_mut()
-        .map_inplace(|el| *el = unif_distr.sample(&mut t_rng));
+    crate::random::with_rng(|rng| {
+        param
+            .data_mut()
+            .map_inplace(|el| *el = unif_distr.sample(rng))
+    });
 }
 
 /// Fills the differentiable leaf variable with values according to the method described in
@@ -251,8 +253,9 @@ pub fn xavier_normal<D: Dimension>(param: &Learnable<D>, gain: f32) {
     let (fan_in, fan_out) = calculate_fan_in_fan_out(param);
     let std = gain * (2. / ((fan_in + fan_out) as f32)).sqrt();
     let norm_distr = Normal::new(0., std).unwrap();
-    let mut t_rng = thread_rng();
-    param
-        .data_mut()
-        .map_inplace(|el| *el = norm_distr.sample(&mut t_rng));
+    crate::random::with_rng(|rng| {
+        param
+            .data_mut()
+            .map_inplace(|el| *el = norm_distr.sample(rng))
+    });
 }
@@ -10,8 +10,11 @@
 //! * [`mse_loss`] - Measures the mean squared error between each element in the input and the
 //! target.
 //!
+//! * [`msle_loss`] - Measures the mean squared logarithmic error between each element in the input
+//! and the target.
+//!
 //! * [`mae_loss`] - Measures the mean absolute error between each element in the input and the
-//! target.
+//! target. It is also available as [`l1_loss`].
 //!
 //! ## Probabilistic losses
 //!
@@ -23,16 +26,42 @@
 //! * [`nll_loss`] -  Measures the negative log likelihood between the target and the input.
 //!
 //! * [`kldiv_loss`] -  Measures the Kullback-Leibler divergence between the target and the input.
+//!
+//! ## Knowledge distillation
+//!
+//! * [`distillation_loss`] - Mixes the Kullback-Leibler divergence between the temperature-scaled
+//! distributions of a student and of a teacher with the negative log likelihood of the student.
+//!
+//! ## Sampled losses
+//!
+//! When the number of classes is so large that computing every logit is not affordable, as for
+//! the output layer of a language model, the following losses compute the logits of the target
+//! and of a handful of negative classes drawn by a [`NegativeSampler`]. The gradient of the output
+//! embedding matrix is sparse, only the rows of those classes receive a contribution.
+//!
+//! * [`sampled_softmax_loss`] - Estimates the negative log likelihood of the target under the full
+//! softmax from the sampled classes.
+//!
+//! * [`negative_sampling_loss`] - Measures the logistic loss of telling the target apart from the
+//! sampled classes.
+//!
+//! ## Checked losses
+//!
+//! Each loss has a checked counterpart, such as [`try_mse_loss`], that validates the shapes of the
+//! input and of the target and returns a [`ShapeError`] instead of building an inconsistent
+//! computational graph.
 use super::{
     variable::{
-        BCELoss, BCELossBackward, BCEWithLogitsLoss, BCEWithLogitsLossBackward, KLDivLoss,
-        KLDivLossBackward, MAELoss, MAELossBackward, MSELoss, MSELossBackward, NLLLoss,
-        NLLLossBackward,
+        check_loss, check_nll_loss, BCELoss, BCELossBackward, BCEWithLogitsLoss,
+        BCEWithLogitsLossBackward, KLDivLoss, KLDivLossBackward, MAELoss, MAELossBackward, MSELoss,
+        MSELossBackward, MSLELoss, MSLELossBackward, NLLLoss, NLLLossBackward, SampledObjective,
+        SampledSoftmaxLoss, SampledSoftmaxLossBackward, ShapeError, Shaped,
     },
-    Data, Gradient, Var, VarDiff,
+    Data, Gradient, IndexData, IndexVar, Var, VarDiff,
 };
-use ndarray::Dimension;
-use std::fmt::Debug;
+use ndarray::{Dimension, Ix0, Ix1, Ix2};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{cell::RefCell, fmt::Debug, rc::Rc};
 
 /// Specifies the reduction to apply to the *loss* output.
 #[derive(Clone, Debug)]
@@ -74,6 +103,38 @@ where
     VarDiff::from(backward_node, input.past, var)
 }
 
+/// Computes the **mean squared logarithmic error** *(MSLE)* between each element in the input x
+/// and target y.
+///
+/// ```text
+///        1   n
+/// Lᴏss = ―   ∑ (ln(1 + xᵢ) - ln(1 + ʏᵢ))²
+///        n  i=1
+/// ```
+///
+/// Both the input and the target are expected to be greater than -1.
+pub fn msle_loss<T: ?Sized, U: ?Sized, V: ?Sized>(
+    mut input: VarDiff<T, U>,
+    target: Var<V>,
+    reduction: Reduction,
+) -> VarDiff<MSLELoss<T, V>, MSLELossBackward<U, T, V>>
+where
+    T: Data,
+    U: Gradient<Dim = T::Dim>,
+    V: Data<Dim = T::Dim>,
+{
+    input.var.past.merge(target.past);
+    let forward_node = MSLELoss::new(
+        input.var.node.clone(),
+        target.node.clone(),
+        reduction.clone(),
+    );
+    let var = Var::from(forward_node, input.var.past);
+
+    let backward_node = MSLELossBackward::new(input.node, input.var.node, target.node, reduction);
+    VarDiff::from(backward_node, input.past, var)
+}
+
 /// Computes the **mean absolute error** *(MAE)* between each element in the input x and target y.
 ///
 /// ```text
@@ -103,6 +164,22 @@ where
     VarDiff::from(backward_node, input.past, var)
 }
 
+/// Computes the **L1 loss** between each element in the input x and target y.
+///
+/// This is an alias of [`mae_loss`]. The gradient is zero wherever the input matches the target.
+pub fn l1_loss<T: ?Sized, U: ?Sized, V: ?Sized>(
+    input: VarDiff<T, U>,
+    target: Var<V>,
+    reduction: Reduction,
+) -> VarDiff<MAELoss<T, V>, MAELossBackward<U, T, V>>
+where
+    T: Data,
+    U: Gradient<Dim = T::Dim>,
+    V: Data<Dim = T::Dim>,
+{
+    mae_loss(input, target, reduction)
+}
+
 /// Computes the **binary cross entropy** between the target y and input x.
 ///
 /// ```text
@@ -259,3 +336,526 @@ where
     let backward_node = KLDivLossBackward::new(input.node, target.node, reduction);
     VarDiff::from(backward_node, input.past, var)
 }
+
+/// Computes the **knowledge distillation** loss between a student and a teacher.
+///
+/// ```text
+/// Lᴏss = α T² KLDɪᴠ(ʟᴏɢ_sᴏғᴛᴍᴀx(s / T), sᴏғᴛᴍᴀx(t / T)) + (1 - α) NLL(ʟᴏɢ_sᴏғᴛᴍᴀx(s), ʏ)
+/// ```
+///
+/// Both `student` and `teacher` are expected to contain raw, unnormalized scores with the classes
+/// along the second axis, while `target` holds the class indices in the same format accepted by
+/// [`nll_loss`]. The soft term is scaled by the square of the temperature so that its gradient
+/// keeps the same magnitude as the one of the hard term for any choice of `temperature`.
+///
+/// The teacher takes part only in the forward pass. It is usually obtained by detaching the
+/// output of a model whose gradients have been disabled:
+///
+/// ```
+/// use neuronika::nn::{loss::{distillation_loss, Reduction}, Linear};
+///
+/// let teacher = Linear::new(4, 3);
+/// let student = Linear::new(4, 3);
+///
+/// let input = neuronika::rand((8, 4));
+/// let target = neuronika::from_ndarray(ndarray::Array::from_elem(8, 1.));
+///
+/// let teacher_output = teacher.forward(input.clone());
+/// teacher_output.no_grad();
+///
+/// let loss = distillation_loss(
+///     student.forward(input),
+///     teacher_output.detach(),
+///     target,
+///     2.,
+///     0.5,
+///     Reduction::Mean,
+/// );
+/// loss.forward();
+/// loss.backward(1.);
+///
+/// assert!(student.weight.grad().iter().any(|grad| *grad != 0.));
+/// ```
+///
+/// # Panics
+///
+/// If `temperature` isn't strictly positive or if `alpha` doesn't lie in *[0, 1]*.
+pub fn distillation_loss<T: ?Sized, U: ?Sized, V: ?Sized, W: ?Sized>(
+    student: VarDiff<T, U>,
+    teacher: Var<V>,
+    target: Var<W>,
+    temperature: f32,
+    alpha: f32,
+    reduction: Reduction,
+) -> VarDiff<impl Data<Dim = Ix0>, impl Gradient<Dim = Ix0>>
+where
+    T: Data<Dim = <W::Dim as Dimension>::Larger>,
+    U: Gradient<Dim = T::Dim>,
+    V: Data<Dim = T::Dim>,
+    W: Data,
+    T::Dim: Copy,
+{
+    assert!(
+        temperature > 0.,
+        "error: {} is not a valid temperature.",
+        temperature
+    );
+    assert!(
+        (0. ..=1.).contains(&alpha),
+        "error: {} is not a valid mixing weight.",
+        alpha
+    );
+
+    let soft_targets = (teacher * temperature.recip()).softmax(1);
+    let soft = kldiv_loss(
+        (student.clone() * temperature.recip()).log_softmax(1),
+        soft_targets,
+        reduction.clone(),
+    );
+    let hard = nll_loss(student.log_softmax(1), target, reduction);
+
+    soft * (alpha * temperature * temperature) + hard * (1. - alpha)
+}
+
+/// Draws the **negative classes** of a [`sampled_softmax_loss`] or of a
+/// [`negative_sampling_loss`].
+///
+/// The classes are drawn with replacement, a fixed number of them for each sample at every
+/// forward pass. Clones share the same random number generator, so that a sampler can be reused
+/// across the graphs built at each training step.
+///
+/// ```
+/// use neuronika::nn::loss::NegativeSampler;
+///
+/// // Frequent words are drawn more often, but less than proportionally to their counts.
+/// let sampler = NegativeSampler::unigram(&[120, 40, 3, 1], 0.75, 5).with_seed(0);
+/// assert_eq!(sampler.classes(), 4);
+/// assert_eq!(sampler.samples(), 5);
+/// ```
+#[derive(Clone)]
+pub struct NegativeSampler {
+    cumulative: Rc<Vec<f64>>,
+    log_probabilities: Rc<Vec<f32>>,
+    samples: usize,
+    rng: Rc<RefCell<StdRng>>,
+}
+
+impl NegativeSampler {
+    /// Creates a sampler drawing `samples` negatives from `classes` equally likely classes.
+    ///
+    /// # Panics
+    ///
+    /// If `classes` or `samples` is zero.
+    pub fn uniform(classes: usize, samples: usize) -> Self {
+        Self::from_weights(vec![1.; classes], samples)
+    }
+
+    /// Creates a sampler drawing `samples` negatives from the **unigram** distribution of `counts`
+    /// raised to `power`. The classes that never occur are never drawn.
+    ///
+    /// A `power` of *0.75* is the usual choice for word embeddings, as it draws rare classes more
+    /// often than their frequency alone would.
+    ///
+    /// # Panics
+    ///
+    /// If `power` is negative, if all the counts are zero or if `samples` is zero.
+    pub fn unigram(counts: &[usize], power: f32, samples: usize) -> Self {
+        assert!(power >= 0., "error: {} is not a valid power.", power);
+
+        let weights = counts
+            .iter()
+            .map(|&count| {
+                if count == 0 {
+                    0.
+                } else {
+                    (count as f64).powf(power as f64)
+                }
+            })
+            .collect();
+        Self::from_weights(weights, samples)
+    }
+
+    fn from_weights(weights: Vec<f64>, samples: usize) -> Self {
+        assert!(
+            samples > 0,
+            "error: the number of samples must be positive."
+        );
+        let total: f64 = weights.iter().sum();
+        assert!(
+            total > 0.,
+            "error: the sampling distribution must have some positive weight."
+        );
+
+        let log_probabilities = weights
+            .iter()
+            .map(|weight| (weight / total).ln() as f32)
+            .collect();
+        let cumulative = weights
+            .iter()
+            .scan(0., |sum, weight| {
+                *sum += weight;
+                Some(*sum)
+            })
+            .collect();
+
+        Self {
+            cumulative: Rc::new(cumulative),
+            log_probabilities: Rc::new(log_probabilities),
+            samples,
+            rng: Rc::new(RefCell::new(StdRng::from_rng(rand::thread_rng()).unwrap())),
+        }
+    }
+
+    /// Seeds the random number generator of the sampler, making the draws reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Rc::new(RefCell::new(StdRng::seed_from_u64(seed)));
+        self
+    }
+
+    /// Returns the number of classes the sampler draws from.
+    pub fn classes(&self) -> usize {
+        self.cumulative.len()
+    }
+
+    /// Returns the number of negatives drawn for each sample.
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Draws a class.
+    pub(crate) fn sample(&self) -> usize {
+        let total = self.cumulative[self.cumulative.len() - 1];
+        let point = self.rng.borrow_mut().gen::<f64>() * total;
+        self.cumulative
+            .partition_point(|&sum| sum <= point)
+            .min(self.cumulative.len() - 1)
+    }
+
+    /// Returns the logarithm of the expected number of times `class` is drawn for a sample.
+    pub(crate) fn log_expected_count(&self, class: usize) -> f32 {
+        self.log_probabilities[class] + (self.samples as f32).ln()
+    }
+}
+
+impl Debug for NegativeSampler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NegativeSampler")
+            .field("classes", &self.classes())
+            .field("samples", &self.samples)
+            .finish()
+    }
+}
+
+/// Computes the **sampled softmax** loss of the hidden states `input`, of shape *(N, D)*, against
+/// the output embedding `weight`, of shape *(C, D)*.
+///
+/// ```text
+/// Lᴏss = -zₜ + ʟᴏɢ(ᴇxᴘ(zₜ) + ∑ ᴇxᴘ(zⱼ - ʟᴏɢ(k Q(ⱼ))))
+///                           j∈S
+/// ```
+///
+/// Only the logits `z` of the target `t` and of the *k* negatives `S` drawn by `sampler` from the
+/// distribution `Q` are computed, each the dot product of a hidden state with a row of `weight`.
+/// The negative logits are corrected by their expected counts so that the loss estimates the
+/// negative log likelihood under the softmax over all the *C* classes, and the negatives that
+/// coincide with the target are left out. New negatives are drawn at every forward pass.
+///
+/// The gradient of `weight` is sparse: only the rows of the targets and of the negatives are
+/// updated. When the given reduction is equal to [`Reduction::Mean`] the total loss is divided by
+/// the batch size.
+///
+/// ```
+/// use neuronika::nn::loss::{sampled_softmax_loss, NegativeSampler, Reduction};
+///
+/// let hidden = neuronika::rand((4, 8)).requires_grad();
+/// let weight = neuronika::rand((1000, 8)).requires_grad();
+/// let target = neuronika::indices(ndarray::array![3, 14, 159, 265]);
+///
+/// let sampler = NegativeSampler::uniform(1000, 10).with_seed(0);
+/// let loss = sampled_softmax_loss(hidden, weight.clone(), target, &sampler, Reduction::Mean);
+/// loss.forward();
+/// loss.backward(1.);
+///
+/// // At most 4 targets and 40 negatives received a gradient.
+/// let touched = weight
+///     .grad()
+///     .rows()
+///     .into_iter()
+///     .filter(|row| row.iter().any(|grad| *grad != 0.))
+///     .count();
+/// assert!(touched <= 44);
+/// ```
+///
+/// # Panics
+///
+/// If the hidden states and `weight` have a different number of features, if there is not a
+/// target for each hidden state, if `sampler` doesn't draw from the rows of `weight` or, during
+/// the forward pass, if a target is out of range.
+pub fn sampled_softmax_loss<T: ?Sized, U: ?Sized, V: ?Sized, W: ?Sized, I: ?Sized>(
+    input: VarDiff<T, U>,
+    weight: VarDiff<V, W>,
+    target: IndexVar<I>,
+    sampler: &NegativeSampler,
+    reduction: Reduction,
+) -> VarDiff<impl Data<Dim = Ix0>, impl Gradient<Dim = Ix0>>
+where
+    T: Data<Dim = Ix2> + 'static,
+    U: Gradient<Dim = Ix2> + 'static,
+    V: Data<Dim = Ix2> + 'static,
+    W: Gradient<Dim = Ix2> + 'static,
+    I: IndexData<Dim = Ix1> + 'static,
+{
+    sampled_loss(
+        input,
+        weight,
+        target,
+        sampler,
+        SampledObjective::Softmax,
+        reduction,
+    )
+}
+
+/// Computes the **negative sampling** loss of the hidden states `input`, of shape *(N, D)*,
+/// against the output embedding `weight`, of shape *(C, D)*.
+///
+/// ```text
+/// Lᴏss = -ʟᴏɢ(σ(zₜ)) - ∑ ʟᴏɢ(σ(-zⱼ))
+///                     j∈S
+/// ```
+///
+/// Each target `t` is told apart from the *k* negatives `S` drawn by `sampler` as a binary
+/// classification problem, as in *word2vec*. Only the logits `z` of those classes are computed,
+/// each the dot product of a hidden state with a row of `weight`, and the negatives that
+/// coincide with the target are left out. New negatives are drawn at every forward pass.
+///
+/// The gradient of `weight` is sparse: only the rows of the targets and of the negatives are
+/// updated. When the given reduction is equal to [`Reduction::Mean`] the total loss is divided by
+/// the batch size.
+///
+/// ```
+/// use neuronika::nn::loss::{negative_sampling_loss, NegativeSampler, Reduction};
+///
+/// let hidden = neuronika::rand((2, 8)).requires_grad();
+/// let weight = neuronika::rand((500, 8)).requires_grad();
+/// let target = neuronika::indices(ndarray::array![7, 42]);
+///
+/// let sampler = NegativeSampler::unigram(&vec![1; 500], 0.75, 5).with_seed(0);
+/// let loss = negative_sampling_loss(hidden, weight, target, &sampler, Reduction::Sum);
+/// loss.forward();
+/// assert!(loss.data()[()] > 0.);
+/// ```
+///
+/// # Panics
+///
+/// If the hidden states and `weight` have a different number of features, if there is not a
+/// target for each hidden state, if `sampler` doesn't draw from the rows of `weight` or, during
+/// the forward pass, if a target is out of range.
+pub fn negative_sampling_loss<T: ?Sized, U: ?Sized, V: ?Sized, W: ?Sized, I: ?Sized>(
+    input: VarDiff<T, U>,
+    weight: VarDiff<V, W>,
+    target: IndexVar<I>,
+    sampler: &NegativeSampler,
+    reduction: Reduction,
+) -> VarDiff<impl Data<Dim = Ix0>, impl Gradient<Dim = Ix0>>
+where
+    T: Data<Dim = Ix2> + 'static,
+    U: Gradient<Dim = Ix2> + 'static,
+    V: Data<Dim = Ix2> + 'static,
+    W: Gradient<Dim = Ix2> + 'static,
+    I: IndexData<Dim = Ix1> + 'static,
+{
+    sampled_loss(
+        input,
+        weight,
+        target,
+        sampler,
+        SampledObjective::NegativeSampling,
+        reduction,
+    )
+}
+
+fn sampled_loss<T: ?Sized, U: ?Sized, V: ?Sized, W: ?Sized, I: ?Sized>(
+    input: VarDiff<T, U>,
+    weight: VarDiff<V, W>,
+    target: IndexVar<I>,
+    sampler: &NegativeSampler,
+    objective: SampledObjective,
+    reduction: Reduction,
+) -> VarDiff<impl Data<Dim = Ix0>, impl Gradient<Dim = Ix0>>
+where
+    T: Data<Dim = Ix2> + 'static,
+    U: Gradient<Dim = Ix2> + 'static,
+    V: Data<Dim = Ix2> + 'static,
+    W: Gradient<Dim = Ix2> + 'static,
+    I: IndexData<Dim = Ix1> + 'static,
+{
+    let mut past = input.var.past;
+    past.merge(weight.var.past);
+    past.merge(target.past);
+    let forward_node = SampledSoftmaxLoss::new(
+        input.var.node,
+        weight.var.node,
+        target.node,
+        sampler.clone(),
+        objective,
+        reduction,
+    );
+    let var = Var::from(forward_node, past);
+
+    let mut past = input.past;
+    past.merge(weight.past);
+    let backward_node = SampledSoftmaxLossBackward::new(input.node, weight.node, var.node.clone());
+    VarDiff::from(backward_node, past, var)
+}
+
+/// Checked version of [`mse_loss`].
+///
+/// # Errors
+///
+/// Returns a [`ShapeError`] if the input and the target have different shapes.
+///
+/// ```
+/// use neuronika::nn::loss::{try_mse_loss, Reduction};
+///
+/// let input = neuronika::rand((4, 3)).requires_grad();
+/// let target = neuronika::rand((4, 2));
+///
+/// let error = try_mse_loss(input, target, Reduction::Mean).unwrap_err();
+/// assert_eq!(error.operation(), "mse_loss");
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn try_mse_loss<T: ?Sized, U: ?Sized, V: ?Sized>(
+    input: VarDiff<T, U>,
+    target: Var<V>,
+    reduction: Reduction,
+) -> Result<VarDiff<MSELoss<T, V>, MSELossBackward<U, T, V>>, ShapeError>
+where
+    T: Data,
+    U: Gradient<Dim = T::Dim>,
+    V: Data<Dim = T::Dim>,
+{
+    check_loss("mse_loss", &input.shape(), &target.shape())?;
+    Ok(mse_loss(input, target, reduction))
+}
+
+/// Checked version of [`msle_loss`].
+///
+/// # Errors
+///
+/// Returns a [`ShapeError`] if the input and the target have different shapes.
+#[allow(clippy::type_complexity)]
+pub fn try_msle_loss<T: ?Sized, U: ?Sized, V: ?Sized>(
+    input: VarDiff<T, U>,
+    target: Var<V>,
+    reduction: Reduction,
+) -> Result<VarDiff<MSLELoss<T, V>, MSLELossBackward<U, T, V>>, ShapeError>
+where
+    T: Data,
+    U: Gradient<Dim = T::Dim>,
+    V: Data<Dim = T::Dim>,
+{
+    check_loss("msle_loss", &input.shape(), &target.shape())?;
+    Ok(msle_loss(input, target, reduction))
+}
+
+/// Checked version of [`mae_loss`].
+///
+/// # Errors
+///
+/// Returns a [`ShapeError`] if the input and the target have different shapes.
+#[allow(clippy::type_complexity)]
+pub fn try_mae_loss<T: ?Sized, U: ?Sized, V: ?Sized>(
+    input: VarDiff<T, U>,
+    target: Var<V>,
+    reduction: Reduction,
+) -> Result<VarDiff<MAELoss<T, V>, MAELossBackward<U, T, V>>, ShapeError>
+where
+    T: Data,
+    U: Gradient<Dim = T::Dim>,
+    V: Data<Dim = T::Dim>,
+{
+    check_loss("mae_loss", &input.shape(), &target.shape())?;
+    Ok(mae_loss(input, target, reduction))
+}
+
+/// Checked version of [`bce_loss`].
+///
+/// # Errors
+///
+/// Returns a [`ShapeError`] if the input and the target have different shapes.
+#[allow(clippy::type_complexity)]
+pub fn try_bce_loss<T: ?Sized, U: ?Sized, V: ?Sized>(
+    input: VarDiff<T, U>,
+    target: Var<V>,
+    reduction: Reduction,
+) -> Result<VarDiff<BCELoss<T, V>, BCELossBackward<U, T, V>>, ShapeError>
+where
+    T: Data,
+    U: Gradient<Dim = T::Dim>,
+    V: Data<Dim = T::Dim>,
+{
+    check_loss("bce_loss", &input.shape(), &target.shape())?;
+    Ok(bce_loss(input, target, reduction))
+}
+
+/// Checked version of [`bce_with_logits_loss`].
+///
+/// # Errors
+///
+/// Returns a [`ShapeError`] if the input and the target have different shapes.
+#[allow(clippy::type_complexity)]
+pub fn try_bce_with_logits_loss<T: ?Sized, U: ?Sized, V: ?Sized>(
+    input: VarDiff<T, U>,
+    target: Var<V>,
+    reduction: Reduction,
+) -> Result<VarDiff<BCEWithLogitsLoss<T, V>, BCEWithLogitsLossBackward<U, T, V>>, ShapeError>
+where
+    T: Data,
+    U: Gradient<Dim = T::Dim>,
+    V: Data<Dim = T::Dim>,
+{
+    check_loss("bce_with_logits_loss", &input.shape(), &target.shape())?;
+    Ok(bce_with_logits_loss(input, target, reduction))
+}
+
+/// Checked version of [`nll_loss`].
+///
+/// # Errors
+///
+/// Returns a [`ShapeError`] if the shape of the target differs from the shape of the input
+/// without its second axis, the one of the classes.
+#[allow(clippy::type_complexity)]
+pub fn try_nll_loss<T: ?Sized, U: ?Sized, V: ?Sized>(
+    input: VarDiff<T, U>,
+    target: Var<V>,
+    reduction: Reduction,
+) -> Result<VarDiff<NLLLoss<T, V>, NLLLossBackward<U, V>>, ShapeError>
+where
+    T: Data<Dim = <V::Dim as Dimension>::Larger>,
+    U: Gradient<Dim = T::Dim>,
+    V: Data,
+    T::Dim: Copy,
+{
+    check_nll_loss(&input.shape(), &target.shape())?;
+    Ok(nll_loss(input, target, reduction))
+}
+
+/// Checked version of [`kldiv_loss`].
+///
+/// # Errors
+///
+/// Returns a [`ShapeError`] if the input and the target have different shapes.
+#[allow(clippy::type_complexity)]
+pub fn try_kldiv_loss<T: ?Sized, U: ?Sized, V: ?Sized>(
+    input: VarDiff<T, U>,
+    target: Var<V>,
+    reduction: Reduction,
+) -> Result<VarDiff<KLDivLoss<T, V>, KLDivLossBackward<U, V>>, ShapeError>
+where
+    T: Data,
+    U: Gradient<Dim = T::Dim>,
+    V: Data<Dim = T::Dim>,
+{
+    check_loss("kldiv_loss", &input.shape(), &target.shape())?;
+    Ok(kldiv_loss(input, target, reduction))
+}
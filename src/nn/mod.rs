@@ -75,10 +75,10 @@
 //!         input: I,
 //!     ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
 //!     where
-//!         I: MatMatMulT<Learnable<Ix2>>,
+//!         I: MatMatMulT<Learnable<Ix2>> + 'static,
 //!         I::Output: Into<VarDiff<T, U>>,
-//!         T: Data<Dim = Ix2> + Forward,
-//!         U: Gradient<Dim = Ix2>,
+//!         T: Data<Dim = Ix2> + Forward + 'static,
+//!         U: Gradient<Dim = Ix2> + 'static,
 //!     {
 //!         let out1 = self.lin1.forward(input).relu();
 //!         let out2 = self.lin2.forward(out1).relu();
@@ -119,10 +119,10 @@
 //! #         input: I,
 //! #     ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
 //! #     where
-//! #         I: MatMatMulT<Learnable<Ix2>>,
+//! #         I: MatMatMulT<Learnable<Ix2>> + 'static,
 //! #         I::Output: Into<VarDiff<T, U>>,
-//! #         T: Data<Dim = Ix2> + Forward,
-//! #         U: Gradient<Dim = Ix2>,
+//! #         T: Data<Dim = Ix2> + Forward + 'static,
+//! #         U: Gradient<Dim = Ix2> + 'static,
 //! #     {
 //! #         let out1 = self.lin1.forward(input).relu();
 //! #         let out2 = self.lin2.forward(out1).relu();
@@ -173,10 +173,10 @@
 //! #         input: I,
 //! #     ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
 //! #     where
-//! #         I: MatMatMulT<Learnable<Ix2>>,
+//! #         I: MatMatMulT<Learnable<Ix2>> + 'static,
 //! #         I::Output: Into<VarDiff<T, U>>,
-//! #         T: Data<Dim = Ix2>,
-//! #         U: Gradient<Dim = Ix2>,
+//! #         T: Data<Dim = Ix2> + 'static,
+//! #         U: Gradient<Dim = Ix2> + 'static,
 //! #     {
 //! #         let out1 = self.lin1.forward(input).relu();
 //! #         let out2 = self.lin2.forward(out1).relu();
@@ -303,8 +303,7 @@
 //! [`.train()`]: VarDiff::train()
 //!
 //! ```
-//!  use neuronika::Param;
-//!  use neuronika::nn::{ModelStatus, Linear, Dropout};
+//!  use neuronika::nn::{ModelStatus, Module, Linear, Dropout};
 //!
 //!  struct NeuralNetwork {
 //!     lin1: Linear,
@@ -330,23 +329,91 @@
 //!              status,
 //!          }
 //!      }
+//!  }
 //!
-//!      fn parameters(&self) -> Vec<Param> {
-//!          self.status.parameters()
-//!      }
-//!      
-//!      /// Switches the network in training mode.
-//!      fn train(&self) {
-//!          self.status.train()
-//!      }
-//!      
-//!      /// Switches the network in inference mode.
-//!      fn eval(&self) {
-//!          self.status.eval()
+//!  // By implementing Module the network gets .parameters(), .train() and .eval() for free.
+//!  impl Module for NeuralNetwork {
+//!      fn status(&self) -> &ModelStatus {
+//!          &self.status
 //!      }
 //!  }
 //! ```
 //!
+//! Modules can be nested. A module registered with [`ModelStatus::register_module`] shares its
+//! parameters with the enclosing one and follows its status, so that switching the outermost
+//! module recursively switches all the components it's made of.
+//!
+//! ```
+//! # use neuronika::nn::{ModelStatus, Module, Linear, Dropout};
+//! # struct NeuralNetwork {
+//! #    lin1: Linear,
+//! #    drop: Dropout,
+//! #    lin2: Linear,
+//! #    status: ModelStatus,
+//! # }
+//! # impl NeuralNetwork {
+//! #     fn new() -> Self {
+//! #         let mut status = ModelStatus::default();
+//! #         Self {
+//! #             lin1: status.register(Linear::new(25, 35)),
+//! #             drop: status.register(Dropout::new(0.5)),
+//! #             lin2: status.register(Linear::new(35, 5)),
+//! #             status,
+//! #         }
+//! #     }
+//! # }
+//! # impl Module for NeuralNetwork {
+//! #     fn status(&self) -> &ModelStatus {
+//! #         &self.status
+//! #     }
+//! # }
+//! struct Ensemble {
+//!     first: NeuralNetwork,
+//!     second: NeuralNetwork,
+//!     status: ModelStatus,
+//! }
+//!
+//! impl Ensemble {
+//!     fn new() -> Self {
+//!         let mut status = ModelStatus::default();
+//!
+//!         Self {
+//!             first: status.register_module(NeuralNetwork::new()),
+//!             second: status.register_module(NeuralNetwork::new()),
+//!             status,
+//!         }
+//!     }
+//! }
+//!
+//! impl Module for Ensemble {
+//!     fn status(&self) -> &ModelStatus {
+//!         &self.status
+//!     }
+//! }
+//!
+//! let ensemble = Ensemble::new();
+//! assert_eq!(ensemble.parameters().len(), 8);
+//!
+//! ensemble.eval();
+//! assert!(!ensemble.first.is_training());
+//! assert!(!ensemble.second.drop.status.get());
+//!
+//! ensemble.train();
+//! assert!(ensemble.second.is_training());
+//! ```
+//!
+//! [`predict_mc()`] evaluates a model in inference mode while keeping its dropout layers active,
+//! returning the mean and the variance of several stochastic forward passes as a cheap estimate
+//! of the model's uncertainty.
+//!
+//! # Parameter Vectors
+//!
+//! [`params_to_vec()`] flattens the parameters of a model into a single vector and
+//! [`vec_to_params()`] writes such a vector back, so that black-box optimizers, such as
+//! evolution strategies and CMA-ES, can drive a model without going through the gradients.
+//! [`sync_params()`] copies or averages the parameters of a model into another one with the same
+//! structure, as the target networks of DQN and DDPG are kept in sync with the trained ones.
+//!
 //! # Layers
 //!
 //! Here are listed all neuronika's building blocks.
@@ -355,12 +422,29 @@
 //!
 //! * [`nn::Linear`](struct@Linear) - Applies a linear transformation to the incoming data.
 //!
+//! * [`nn::BinaryLinear`](struct@BinaryLinear) - Applies a linear transformation with binarized
+//! weights to the incoming data.
+//!
+//! * [`nn::Bilinear`](struct@Bilinear) - Applies a bilinear transformation to two incoming inputs.
+//!
+//! * [`nn::FactorizedBilinearPooling`](struct@FactorizedBilinearPooling) - Approximates a bilinear
+//! transformation of two incoming inputs with low-rank factors.
+//!
+//! * [`nn::Highway`](struct@Highway) - Mixes a non-linear transformation of the incoming data with
+//! the data itself through a learned gate.
+//!
 //! ## Recurrent Layers
 //!
 //! * [`nn::GRUCell`](struct@GRUCell) - A gated recurrent unit cell.
 //!
 //! * [`nn::LSTMCell`](struct@LSTMCell) - A long short term memory cell.
 //!
+//! * [`nn::GRU`](struct@GRU) - A multi-layer, possibly bidirectional, gated recurrent unit
+//! network reading a [`PackedSequence`].
+//!
+//! * [`nn::LSTM`](struct@LSTM) - A multi-layer, possibly bidirectional, long short term memory
+//! network reading a [`PackedSequence`].
+//!
 //! ## Convolution Layers
 //!
 //! * [`nn::Conv1d`](struct@Conv1d) - Applies a temporal convolution over an input signal composed
@@ -381,22 +465,133 @@
 //! * [`nn::GroupedConv3d`](struct@GroupedConv3d) - Applies a grouped volumetric convolution over an
 //! input signal composed of several input planes.
 //!
+//! ## Pooling Layers
+//!
+//! * [`nn::MaxPool2d`](struct@MaxPool2d) - Applies a spatial max pooling over an input signal
+//! composed of several input planes.
+//!
+//! * [`nn::AvgPool2d`](struct@AvgPool2d) - Applies a spatial average pooling over an input signal
+//! composed of several input planes.
+//!
+//! * [`nn::GlobalAvgPool2d`](struct@GlobalAvgPool2d) - Averages each plane of an input signal
+//! composed of several input planes.
+//!
+//! * [`nn::sequence_mask`](fn@sequence_mask) - Creates the mask of the positions of a batch of
+//! padded sequences that are not padding, which is taken by the
+//! [`.masked_mean_pool()`](crate::Var::masked_mean_pool()) and
+//! [`.masked_max_pool()`](crate::Var::masked_max_pool()) methods of the variables.
+//!
+//! ## Convolutional Blocks
+//!
+//! * [`nn::SqueezeExcitation`](struct@SqueezeExcitation) - Rescales the channels of a batch of
+//! feature maps by weights computed from their global averages.
+//!
+//! * [`nn::ResidualBlock`](struct@ResidualBlock) - Adds a stack of convolutions to a shortcut
+//! connection, projecting the input when its shape differs from the one of the output.
+//!
+//! * [`nn::TemporalBlock`](struct@TemporalBlock) - Adds two weight normalized, dilated causal
+//! convolutions to a shortcut connection.
+//!
+//! * [`nn::TCN`](struct@TCN) - Stacks temporal blocks with exponentially increasing dilations.
+//!
+//! ## Normalization Layers
+//!
+//! * [`nn::BatchNorm1d`](struct@BatchNorm1d) - Applies batch normalization over a batch of
+//! feature vectors.
+//!
+//! * [`nn::BatchNorm2d`](struct@BatchNorm2d) - Applies batch normalization over a batch of feature
+//! maps.
+//!
+//! * [`nn::LayerNorm`](struct@LayerNorm) - Applies layer normalization over each feature vector of
+//! a batch.
+//!
+//! ## Sparse Layers
+//!
+//! * [`nn::Embedding`](struct@Embedding) - A lookup table storing the embeddings of a fixed size
+//! vocabulary.
+//!
+//! ## Attention Layers
+//!
+//! * [`nn::MultiheadAttention`](struct@MultiheadAttention) - Allows a sequence to attend to the
+//! elements of another sequence through several attention heads.
+//!
+//! * [`nn::scaled_dot_product_attention`](fn@scaled_dot_product_attention) - Computes the attention
+//! of a query over a key and a value in a single step, it is the building block of the attention
+//! layers. [`nn::scaled_dot_product_attention_with_weights`](fn@scaled_dot_product_attention_with_weights)
+//! also returns the attention probabilities.
+//!
+//! * [`nn::causal_mask`](fn@causal_mask) and [`nn::padding_mask`](fn@padding_mask) - Create the
+//! masks preventing a sequence from attending to the following positions and to the padding.
+//!
+//! * [`nn::PositionalEncoding`](struct@PositionalEncoding) - Adds to a sequence an encoding of the
+//! positions of its elements, either made of fixed sinusoids or learned.
+//!
+//! * [`nn::RotaryEmbedding`](struct@RotaryEmbedding) - Rotates the features of queries and keys by
+//! angles proportional to their positions, making their dot products depend on relative positions.
+//!
+//! ## Graph Layers
+//!
+//! * [`nn::GraphConv`](struct@GraphConv) - A graph convolution, averaging the features of the
+//! neighbours of each node with weights depending on their degrees.
+//!
+//! * [`nn::GATConv`](struct@GATConv) - A graph attention layer, averaging the features of the
+//! neighbours of each node with learned attention weights.
+//!
+//! ## Structured Prediction Layers
+//!
+//! * [`nn::CRF`](struct@CRF) - A linear-chain conditional random field, scoring and decoding
+//! sequences of tags.
+//!
 //! ## Dropout Layers
 //!
 //! * [`nn::Dropout`](struct@Dropout) - During training, randomly zeroes some of the elements of
 //! the input variable with probability *p* using samples from a Bernoulli distribution.
+//!
+//! * [`nn::DropPath`](struct@DropPath) - During training, randomly drops whole samples of a
+//! residual branch with probability *p*, also known as stochastic depth.
+//!
+//! * [`nn::VariationalDropout`](struct@VariationalDropout) - During training, randomly zeroes
+//! some of the elements of the inputs or of the states of a recurrent network with the same mask
+//! at every time step.
+//!
+//! * [`nn::Zoneout`](struct@Zoneout) - During training, randomly preserves some of the elements
+//! of the states of a recurrent network instead of updating them.
+//!
+//! ## Utility Layers
+//!
+//! * [`nn::Flatten`](struct@Flatten) - Flattens all the axes of the input but the first one.
+//!
+//! * [`nn::GLU`](struct@GLU) - Splits the input in two halves along an axis and gates the first by
+//! the sigmoid of the second.
 use super::{Input, InputBackward, Param};
 use crate::variable::{
-    self, Convolve, ConvolveWithGroups, Data, Dropout as DropoutNode,
-    DropoutBackward as DropoutBackwardNode, Eval, Gradient, MatMatMulT, Overwrite, RawParam,
-    Tensor, Var, VarDiff,
+    self, AvgPool as AvgPoolNode, AvgPoolBackward as AvgPoolBackwardNode, CRFLogLikelihood,
+    CRFLogLikelihoodBackward, Convolve, ConvolveWithGroups, Data, Dropout as DropoutNode,
+    DropoutBackward as DropoutBackwardNode, DropoutMask, Eval, Flatten as FlattenNode,
+    FlattenBackward as FlattenBackwardNode, GLUBackward as GLUBackwardNode, Gradient, IndexData,
+    IndexVar, MatMatMul, MatMatMulT, MaxPool as MaxPoolNode,
+    MaxPoolBackward as MaxPoolBackwardNode, Output, Overwrite, RawParam, Rotary as RotaryNode,
+    RotaryBackward, ScaledDotProductAttention as ScaledDotProductAttentionNode,
+    ScaledDotProductAttentionBackward, Sign, SignBackward, Tensor, Var, VarDiff, GLU as GLUNode,
 };
 pub use crate::variable::{Constant, PaddingMode, Reflective, Replicative, Zero};
-use ndarray::{Ix1, Ix2, Ix3, Ix4, Ix5};
-use std::{cell::Cell, rc::Rc};
+use ndarray::{
+    Array, Array2, Array3, DimMax, Dimension, Ix1, Ix2, Ix3, Ix4, Ix5, ShapeBuilder, Zip,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 pub mod init;
 pub mod loss;
+pub mod prune;
+pub mod summary;
+
+use summary::{
+    convolution_shape, linear_shape, normalization_shape, pooling_shape, Layer, LayerSummary,
+    ShapeInference, Summary,
+};
 
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
@@ -415,6 +610,9 @@ pub type Learnable<D> = VarDiff<Input<D>, InputBackward<D>>;
 pub struct ModelStatus {
     params: Vec<RawParam>,
     train: Rc<Cell<bool>>,
+    submodules: Vec<Rc<Cell<bool>>>,
+    layers: Vec<Layer>,
+    dropouts: Vec<Rc<DropoutMask>>,
 }
 
 impl ModelStatus {
@@ -443,11 +641,79 @@ impl ModelStatus {
     ///
     /// `component` - layer to be registered.
     pub fn register<T: Register>(&mut self, mut component: T) -> T {
+        let start = self.params.len();
         component.register_params(&mut self.params);
         component.register_status(self.train.clone());
+        component.register_dropouts(&mut self.dropouts);
+        self.layers.push(Layer::new::<T>(
+            start..self.params.len(),
+            component.shape_inference(),
+            component.non_trainable(),
+        ));
         component
     }
 
+    /// Registers a sub-module.
+    ///
+    /// The parameters of `module` are appended to the ones of `self`, and switching `self` in
+    /// training or inference mode will switch `module` and all of its own sub-modules accordingly.
+    ///
+    /// # Arguments
+    ///
+    /// `module` - sub-module to be registered.
+    pub fn register_module<M: Module>(&mut self, module: M) -> M {
+        let status = module.status();
+        let offset = self.params.len();
+        self.params.extend(status.params.iter().cloned());
+        self.layers
+            .extend(status.layers.iter().map(|layer| layer.shifted(offset)));
+        self.submodules.push(status.train.clone());
+        self.submodules.extend(status.submodules.iter().cloned());
+        self.dropouts.extend(status.dropouts.iter().cloned());
+        status.set_status(self.is_training());
+        module
+    }
+
+    /// Returns a summary of the registered components, listed in registration order.
+    ///
+    /// The shape of the output of each component is inferred by feeding it the output shape of
+    /// the previous one, starting from `input_shape`, thus the summary is meaningful for models
+    /// that apply their components sequentially in the same order they were registered in.
+    ///
+    /// # Arguments
+    ///
+    /// `input_shape` - shape of the input of the model, batch axis included.
+    ///
+    /// # Panics
+    ///
+    /// If the shape of the input of a component isn't the one it expects.
+    pub fn summary(&self, input_shape: &[usize]) -> Summary {
+        let mut shape = input_shape.to_vec();
+        let layers = self
+            .layers
+            .iter()
+            .map(|layer| {
+                shape = (layer.shape)(&shape);
+                LayerSummary {
+                    name: layer.name.to_string(),
+                    output_shape: shape.clone(),
+                    trainable: self.params[layer.params.clone()]
+                        .iter()
+                        .map(RawParam::len)
+                        .sum(),
+                    non_trainable: layer.non_trainable,
+                }
+            })
+            .collect();
+
+        Summary::new(input_shape, layers)
+    }
+
+    /// Returns `true` if the status is set to training mode.
+    pub fn is_training(&self) -> bool {
+        self.train.get()
+    }
+
     /// Sets the status in training mode.
     pub fn train(&self) {
         <Self as Eval>::train(self)
@@ -465,19 +731,293 @@ impl Default for ModelStatus {
         Self {
             params: Vec::new(),
             train: Rc::new(Cell::new(true)),
+            submodules: Vec::new(),
+            layers: Vec::new(),
+            dropouts: Vec::new(),
         }
     }
 }
 
+impl ModelStatus {
+    fn set_status(&self, train: bool) {
+        self.train.set(train);
+        self.submodules
+            .iter()
+            .for_each(|submodule| submodule.set(train));
+    }
+
+    fn set_dropouts_active(&self, active: bool) {
+        self.dropouts
+            .iter()
+            .for_each(|mask| mask.set_keep_active(active));
+    }
+}
+
 impl Eval for ModelStatus {
-    /// Sets the status to train.
+    /// Sets the status and those of all the registered sub-modules to train.
+    fn train(&self) {
+        self.set_status(true)
+    }
+
+    /// Sets the status and those of all the registered sub-modules to eval.
+    fn eval(&self) {
+        self.set_status(false)
+    }
+}
+
+/// Returns the data of `params` concatenated into a single vector, in the order of `params`.
+///
+/// ```
+/// use neuronika::nn::{self, Linear, ModelStatus};
+///
+/// let mut status = ModelStatus::default();
+/// let _linear = status.register(Linear::new(3, 2));
+/// let mut params = status.parameters();
+///
+/// // A random perturbation, as taken by evolution strategies.
+/// let mut values = nn::params_to_vec(&params);
+/// assert_eq!(values.len(), 8);
+/// values.iter_mut().for_each(|value| *value += 0.01);
+///
+/// nn::vec_to_params(&mut params, &values);
+/// assert_eq!(nn::params_to_vec(&params), values);
+/// ```
+pub fn params_to_vec(params: &[Param]) -> Vec<f32> {
+    params
+        .iter()
+        .flat_map(|param| param.data.iter().copied())
+        .collect()
+}
+
+/// Copies `values` into the data of `params`, which are filled in order, see
+/// [`params_to_vec()`].
+///
+/// # Panics
+///
+/// If the length of `values` differs from the total number of elements of `params`.
+pub fn vec_to_params(params: &mut [Param], values: &[f32]) {
+    let len: usize = params.iter().map(|param| param.data.len()).sum();
+    assert_eq!(
+        len,
+        values.len(),
+        "error: {} values were given for parameters of {} elements.",
+        values.len(),
+        len
+    );
+
+    params
+        .iter_mut()
+        .flat_map(|param| param.data.iter_mut())
+        .zip(values)
+        .for_each(|(el, value)| *el = *value);
+}
+
+/// Moves the parameters of `target` towards those of `source`, which must have the same structure.
+///
+/// ```text
+/// θₜ = τ θₛ + (1 - τ) θₜ
+/// ```
+///
+/// A `tau` of *1* copies the parameters of `source`, as done periodically with the target
+/// networks of DQN, while a small `tau` performs the Polyak averaging used by DDPG and SAC after
+/// each update.
+///
+/// ```
+/// use neuronika::nn::{self, Linear, Module, ModelStatus};
+///
+/// struct QNetwork {
+///     linear: Linear,
+///     status: ModelStatus,
+/// }
+///
+/// impl QNetwork {
+///     fn new() -> Self {
+///         let mut status = ModelStatus::default();
+///         Self {
+///             linear: status.register(Linear::new(4, 2)),
+///             status,
+///         }
+///     }
+/// }
+///
+/// impl Module for QNetwork {
+///     fn status(&self) -> &ModelStatus {
+///         &self.status
+///     }
+/// }
+///
+/// let online = QNetwork::new();
+/// let target = QNetwork::new();
+///
+/// nn::sync_params(&target, &online, 1.);
+/// assert_eq!(*target.linear.weight.data(), *online.linear.weight.data());
+///
+/// nn::sync_params(&target, &online, 0.005);
+/// ```
+///
+/// # Panics
+///
+/// If the parameters of the two models have different shapes or if `tau` doesn't lie in
+/// *[0, 1]*.
+pub fn sync_params<M, N>(target: &M, source: &N, tau: f32)
+where
+    M: Module + ?Sized,
+    N: Module + ?Sized,
+{
+    assert!(
+        (0. ..=1.).contains(&tau),
+        "error: {} is not a valid interpolation factor.",
+        tau
+    );
+
+    let source_params = source.parameters();
+    let mut target_params = target.parameters();
+    let same_shapes = source_params.len() == target_params.len()
+        && source_params
+            .iter()
+            .zip(&target_params)
+            .all(|(source, target)| source.data.shape() == target.data.shape());
+    assert!(
+        same_shapes,
+        "error: the parameters of the source and of the target models have different shapes."
+    );
+
+    let mut values = params_to_vec(&source_params);
+    if tau < 1. {
+        values
+            .iter_mut()
+            .zip(params_to_vec(&target_params))
+            .for_each(|(value, target)| *value = tau * *value + (1. - tau) * target);
+    }
+    vec_to_params(&mut target_params, &values);
+}
+
+/// Estimates the predictive mean and variance of a model by Monte Carlo dropout.
+///
+/// The model is set in inference mode while its dropout layers are kept active, then `output`
+/// is evaluated `n_samples` times, each time with new dropout masks. The element-wise mean and
+/// variance of the results are returned, and the previous status of the model is restored.
+///
+/// A differentiable output can be passed by means of [`.detach()`](VarDiff::detach()).
+///
+/// # Arguments
+///
+/// * `model` - model whose dropout layers are kept active.
+///
+/// * `output` - output of the model, computed from the input to evaluate.
+///
+/// * `n_samples` - number of stochastic forward passes.
+///
+/// # Panics
+///
+/// If `n_samples` is zero.
+///
+/// ```
+/// use neuronika::nn::{self, Dropout, Linear, ModelStatus, Module};
+///
+/// struct Network {
+///     lin: Linear,
+///     drop: Dropout,
+///     status: ModelStatus,
+/// }
+///
+/// impl Module for Network {
+///     fn status(&self) -> &ModelStatus {
+///         &self.status
+///     }
+/// }
+///
+/// let mut status = ModelStatus::default();
+/// let network = Network {
+///     lin: status.register(Linear::new(3, 2)),
+///     drop: status.register(Dropout::new(0.5)),
+///     status,
+/// };
+///
+/// let output = network.drop.forward(network.lin.forward(neuronika::rand((4, 3))));
+/// network.eval();
+///
+/// let (mean, variance) = nn::predict_mc(&network, &output.detach(), 20);
+/// assert_eq!(mean.shape(), &[4, 2]);
+/// assert!(variance.iter().all(|el| *el >= 0.));
+/// assert!(!network.is_training());
+/// ```
+pub fn predict_mc<M, T>(
+    model: &M,
+    output: &Var<T>,
+    n_samples: usize,
+) -> (Tensor<T::Dim>, Tensor<T::Dim>)
+where
+    M: Module + ?Sized,
+    T: Data + ?Sized,
+{
+    assert!(
+        n_samples > 0,
+        "error: the number of samples must be positive."
+    );
+
+    let status = model.status();
+    let training = status.is_training();
+    status.set_status(false);
+    status.set_dropouts_active(true);
+
+    // Welford's online algorithm.
+    output.forward();
+    let mut mean = output.data().clone();
+    let mut sum_squares = Tensor::zeros(mean.raw_dim());
+    for sample in 2..=n_samples {
+        output.forward();
+        let data = output.data();
+        Zip::from(&mut mean)
+            .and(&mut sum_squares)
+            .and(&*data)
+            .for_each(|mean, sum_squares, &el| {
+                let delta = el - *mean;
+                *mean += delta / sample as f32;
+                *sum_squares += delta * (el - *mean);
+            });
+    }
+
+    status.set_dropouts_active(false);
+    status.set_status(training);
+
+    (mean, sum_squares / n_samples as f32)
+}
+
+/// A neural network module.
+///
+/// A module is any struct owning a [`ModelStatus`]. Its components and sub-modules are registered
+/// to such status, so that switching the module in training or inference mode recursively switches
+/// all of them.
+pub trait Module {
+    /// Returns the status of the module.
+    fn status(&self) -> &ModelStatus;
+
+    /// Returns the learnable parameters of the module and of all of its sub-modules.
+    fn parameters(&self) -> Vec<Param<'_>> {
+        self.status().parameters()
+    }
+
+    /// Sets the module and all of its sub-modules in training mode.
     fn train(&self) {
-        self.train.set(true)
+        self.status().train()
     }
 
-    /// Sets the status to eval.
+    /// Sets the module and all of its sub-modules in inference mode.
     fn eval(&self) {
-        self.train.set(false)
+        self.status().eval()
+    }
+
+    /// Returns `true` if the module is in training mode.
+    fn is_training(&self) -> bool {
+        self.status().is_training()
+    }
+
+    /// Returns a summary of the components of the module and of all of its sub-modules.
+    ///
+    /// See [`ModelStatus::summary()`] for further details.
+    fn summary(&self, input_shape: &[usize]) -> Summary {
+        self.status().summary(input_shape)
     }
 }
 
@@ -487,7 +1027,9 @@ impl Eval for ModelStatus {
 pub trait DropoutInput {
     type Output;
 
-    fn dropout(self, p: f64, status: Rc<Cell<bool>>) -> Self::Output;
+    fn dropout(self, p: f64, status: Rc<Cell<bool>>, mask: Rc<DropoutMask>) -> Self::Output;
+
+    fn drop_path(self, p: f64, status: Rc<Cell<bool>>, mask: Rc<DropoutMask>) -> Self::Output;
 }
 
 impl<T: ?Sized, U: ?Sized> DropoutInput for VarDiff<T, U>
@@ -497,8 +1039,12 @@ where
 {
     type Output = VarDiff<DropoutNode<T>, DropoutBackwardNode<U, T>>;
 
-    fn dropout(self, p: f64, status: Rc<Cell<bool>>) -> Self::Output {
-        self.dropout_with_status(p, status)
+    fn dropout(self, p: f64, status: Rc<Cell<bool>>, mask: Rc<DropoutMask>) -> Self::Output {
+        self.dropout_with_status(p, status, mask)
+    }
+
+    fn drop_path(self, p: f64, status: Rc<Cell<bool>>, mask: Rc<DropoutMask>) -> Self::Output {
+        self.drop_path_with_status(p, status, mask)
     }
 }
 
@@ -508,8 +1054,12 @@ where
 {
     type Output = Var<DropoutNode<T>>;
 
-    fn dropout(self, p: f64, status: Rc<Cell<bool>>) -> Self::Output {
-        self.dropout_with_status(p, status)
+    fn dropout(self, p: f64, status: Rc<Cell<bool>>, mask: Rc<DropoutMask>) -> Self::Output {
+        self.dropout_with_status(p, status, mask)
+    }
+
+    fn drop_path(self, p: f64, status: Rc<Cell<bool>>, mask: Rc<DropoutMask>) -> Self::Output {
+        self.drop_path_with_status(p, status, mask)
     }
 }
 
@@ -520,6 +1070,28 @@ pub trait Register {
 
     /// Register `self`'s status to the model's status state `status`.
     fn register_status(&mut self, status: Rc<Cell<bool>>);
+
+    /// Returns the function inferring the shape of `self`'s output from the one of its input.
+    ///
+    /// It is used by [`ModelStatus::summary()`]. The default implementation leaves the shape
+    /// unchanged.
+    fn shape_inference(&self) -> ShapeInference {
+        Rc::new(<[usize]>::to_vec)
+    }
+
+    /// Returns the number of elements of the state of `self` that is not updated by the
+    /// optimizers, such as the running statistics of a batch normalization.
+    ///
+    /// It is used by [`ModelStatus::summary()`]. The default implementation returns *0*.
+    fn non_trainable(&self) -> usize {
+        0
+    }
+
+    /// Registers the masks of `self`'s dropout layers to the model's status masks `masks`.
+    ///
+    /// It is used by [`predict_mc()`] to keep such layers active in inference mode. The default
+    /// implementation registers nothing.
+    fn register_dropouts(&self, _masks: &mut Vec<Rc<DropoutMask>>) {}
 }
 
 /// During training, randomly zeroes some of the elements of `self` with probability *p* using
@@ -532,9 +1104,31 @@ pub trait Register {
 ///
 /// Furthermore, the outputs are scaled by a factor of 1/(1 - p) during training. This means
 /// that during evaluation the resulting variable simply computes an identity function.
+///
+/// The mask can be frozen with [`.freeze_mask()`](Dropout::freeze_mask()), so that the following
+/// forward calls of the layer reuse the last mask drawn instead of drawing a new one, as long as
+/// the shape of the input does not change. This allows, for instance, for Monte Carlo dropout with
+/// fixed masks and for DropConnect experiments. Each layer is frozen independently of the others,
+/// and independently of its training or inference status.
+///
+/// ```
+/// use neuronika::nn::Dropout;
+///
+/// let dropout = Dropout::new(0.5);
+/// let input = neuronika::ones((4, 4));
+///
+/// let first = dropout.forward(input.clone());
+/// first.forward();
+///
+/// dropout.freeze_mask();
+/// let second = dropout.forward(input);
+/// second.forward();
+/// assert_eq!(*first.data(), *second.data());
+/// ```
 pub struct Dropout {
     pub status: Rc<Cell<bool>>,
     pub p: f64,
+    mask: Rc<DropoutMask>,
 }
 
 impl Dropout {
@@ -545,7 +1139,11 @@ impl Dropout {
     /// `p` - probability of an element to be zeroed.
     pub fn new(p: f64) -> Self {
         let status = Rc::new(Cell::new(true));
-        Self { status, p }
+        Self {
+            status,
+            p,
+            mask: Rc::default(),
+        }
     }
 
     /// Applies the dropout to the variable in input.
@@ -554,7 +1152,22 @@ impl Dropout {
     ///
     /// `input`  - variable in input to the layer.
     pub fn forward<I: DropoutInput>(&self, input: I) -> I::Output {
-        input.dropout(self.p, self.status.clone())
+        input.dropout(self.p, self.status.clone(), self.mask.clone())
+    }
+
+    /// Freezes the mask, so that the following forward calls reuse the last mask drawn.
+    pub fn freeze_mask(&self) {
+        self.mask.freeze()
+    }
+
+    /// Unfreezes the mask, so that each forward call draws a new mask.
+    pub fn unfreeze_mask(&self) {
+        self.mask.unfreeze()
+    }
+
+    /// Returns `true` if the mask is frozen.
+    pub fn is_mask_frozen(&self) -> bool {
+        self.mask.is_frozen()
     }
 }
 
@@ -574,48 +1187,310 @@ impl Register for Dropout {
     }
 
     fn register_params(&self, _: &mut Vec<RawParam>) {}
+
+    fn register_dropouts(&self, masks: &mut Vec<Rc<DropoutMask>>) {
+        masks.push(self.mask.clone());
+    }
 }
 
-/// Applies a **linear transformation** to the incoming data.
+/// During training, randomly drops whole samples of the input with probability *p*, also known
+/// as **stochastic depth**.
+///
+/// It is meant to be applied to the residual branches of a network, so that, for each sample of
+/// the batch, a branch is skipped altogether as described in the paper
+/// [Deep Networks with Stochastic Depth](https://arxiv.org/abs/1603.09382). The first axis of the
+/// input is the batch axis.
+///
+/// The outputs are scaled by a factor of 1/(1 - p) during training, so that their expectation
+/// is unchanged. This means that during evaluation the resulting variable simply computes an
+/// identity function.
 ///
-/// ```text
-/// ʏ = xAᵀ + b
 /// ```
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct Linear {
-    pub weight: Learnable<Ix2>,
-    pub bias: Learnable<Ix1>,
+/// use neuronika::nn::{DropPath, Linear};
+///
+/// let (branch, drop_path) = (Linear::new(4, 4), DropPath::new(0.2));
+///
+/// let input = neuronika::rand((8, 4));
+/// let output = input.clone() + drop_path.forward(branch.forward(input));
+/// output.forward();
+/// ```
+pub struct DropPath {
+    pub status: Rc<Cell<bool>>,
+    pub p: f64,
+    mask: Rc<DropoutMask>,
 }
 
-impl Linear {
-    /// Creates a linear layer.
+impl DropPath {
+    /// Creates a stochastic depth layer.
     ///
     /// # Arguments
     ///
-    /// * `in_features` – size of each input sample.
-    ///
-    /// * `out_features` – size of each output sample.
-    ///
-    /// The learnable weight of the layer is of shape `(out_features, in_features)`. The learnable
-    /// bias of the layer is of shape `out_features`.
-    ///
-    /// The values for both the weight and bias are initialized from *U(-k, k)* where
-    /// `k = (1. / in_features as f32).sqrt()`.
-    pub fn new(in_features: usize, out_features: usize) -> Self {
-        let weight = Input::new(Tensor::zeros((out_features, in_features))).requires_grad();
-        let bias = Input::new(Tensor::zeros(out_features)).requires_grad();
-        let k = (1. / (in_features as f32)).sqrt();
-        init::uniform(&weight, -k, k);
-        init::uniform(&bias, -k, k);
-
-        Self { weight, bias }
+    /// `p` - probability of a sample to be dropped.
+    pub fn new(p: f64) -> Self {
+        let status = Rc::new(Cell::new(true));
+        Self {
+            status,
+            p,
+            mask: Rc::default(),
+        }
     }
 
-    /// Applies the linear transformation *y = xA^T + b* to the incoming data.
+    /// Applies the stochastic depth to the variable in input.
     ///
     /// # Arguments
     ///
-    /// `data` - a variable of shape *(N, in_features)*, the output's shape will be
+    /// `input`  - variable in input to the layer, usually the output of a residual branch.
+    pub fn forward<I: DropoutInput>(&self, input: I) -> I::Output {
+        input.drop_path(self.p, self.status.clone(), self.mask.clone())
+    }
+}
+
+impl Eval for DropPath {
+    fn eval(&self) {
+        self.status.set(false)
+    }
+
+    fn train(&self) {
+        self.status.set(true)
+    }
+}
+
+impl Register for DropPath {
+    fn register_status(&mut self, status: Rc<Cell<bool>>) {
+        self.status = status;
+    }
+
+    fn register_params(&self, _: &mut Vec<RawParam>) {}
+
+    fn register_dropouts(&self, masks: &mut Vec<Rc<DropoutMask>>) {
+        masks.push(self.mask.clone());
+    }
+}
+
+/// **Variational dropout** for recurrent networks, also known as locked dropout.
+///
+/// Differently from [`Dropout`], the same mask is applied at every time step of a sequence, as
+/// described in the paper
+/// [A Theoretically Grounded Application of Dropout in Recurrent Neural Networks](https://arxiv.org/abs/1512.05287).
+/// The mask is a variable created once per sequence with [`.mask()`](VariationalDropout::mask()),
+/// which is then multiplied with the inputs or the hidden states of each step. A new mask is drawn
+/// at every forward pass.
+///
+/// The mask is scaled by a factor of 1/(1 - p) during training and is filled with ones during
+/// evaluation.
+///
+/// ```
+/// use neuronika::nn::{GRUCell, VariationalDropout};
+///
+/// let (cell, dropout) = (GRUCell::new(3, 5), VariationalDropout::new(0.25));
+///
+/// let mask = dropout.mask((2, 5));
+/// let mut hidden = neuronika::zeros((2, 5)).requires_grad().into_dyn();
+/// for _ in 0..4 {
+///     let input = neuronika::rand((2, 3));
+///     hidden = cell.forward(hidden * mask.clone(), input).into_dyn();
+/// }
+/// hidden.forward();
+/// ```
+pub struct VariationalDropout {
+    pub status: Rc<Cell<bool>>,
+    pub p: f64,
+    mask: Rc<DropoutMask>,
+}
+
+impl VariationalDropout {
+    /// Creates a variational dropout layer.
+    ///
+    /// # Arguments
+    ///
+    /// `p` - probability of an element to be zeroed.
+    pub fn new(p: f64) -> Self {
+        let status = Rc::new(Cell::new(true));
+        Self {
+            status,
+            p,
+            mask: Rc::default(),
+        }
+    }
+
+    /// Returns a mask of the given shape, to be shared by all the time steps of a sequence.
+    ///
+    /// # Arguments
+    ///
+    /// `shape` - shape of the mask, usually *(batch, features)*.
+    pub fn mask<D: Dimension, Sh: ShapeBuilder<Dim = D>>(
+        &self,
+        shape: Sh,
+    ) -> Var<DropoutNode<Input<D>>> {
+        Input::new(Tensor::ones(shape)).dropout_with_status(
+            self.p,
+            self.status.clone(),
+            self.mask.clone(),
+        )
+    }
+}
+
+impl Eval for VariationalDropout {
+    fn eval(&self) {
+        self.status.set(false)
+    }
+
+    fn train(&self) {
+        self.status.set(true)
+    }
+}
+
+impl Register for VariationalDropout {
+    fn register_status(&mut self, status: Rc<Cell<bool>>) {
+        self.status = status;
+    }
+
+    fn register_params(&self, _: &mut Vec<RawParam>) {}
+
+    fn register_dropouts(&self, masks: &mut Vec<Rc<DropoutMask>>) {
+        masks.push(self.mask.clone());
+    }
+}
+
+/// **Zoneout** regularization of the states of recurrent networks.
+///
+/// During training, each element of a state keeps its previous value with probability *p*,
+/// instead of being updated, as described in the paper
+/// [Zoneout: Regularizing RNNs by Randomly Preserving Hidden Activations](https://arxiv.org/abs/1606.01305).
+/// During evaluation the new state is the expectation *p · previous + (1 - p) · next*.
+///
+/// Both the hidden and the cell's state of an [`LSTMCell`] can be zoned out, possibly with
+/// different probabilities.
+///
+/// ```
+/// use neuronika::nn::{LSTMCell, Zoneout};
+///
+/// let (cell, zoneout_c, zoneout_h) = (LSTMCell::new(3, 5), Zoneout::new(0.5), Zoneout::new(0.05));
+///
+/// let mut cell_state = neuronika::zeros((2, 5)).requires_grad().into_dyn();
+/// let mut hidden = neuronika::zeros((2, 5)).requires_grad().into_dyn();
+/// for _ in 0..4 {
+///     let input = neuronika::rand((2, 3));
+///     let (next_cell_state, next_hidden) =
+///         cell.forward((cell_state.clone(), hidden.clone()), input);
+///     cell_state = zoneout_c.forward(cell_state, next_cell_state).into_dyn();
+///     hidden = zoneout_h.forward(hidden, next_hidden).into_dyn();
+/// }
+/// hidden.forward();
+/// ```
+pub struct Zoneout {
+    pub status: Rc<Cell<bool>>,
+    pub p: f64,
+    mask: Rc<DropoutMask>,
+}
+
+impl Zoneout {
+    /// Creates a zoneout layer.
+    ///
+    /// # Arguments
+    ///
+    /// `p` - probability of an element to keep its previous value.
+    pub fn new(p: f64) -> Self {
+        let status = Rc::new(Cell::new(true));
+        Self {
+            status,
+            p,
+            mask: Rc::default(),
+        }
+    }
+
+    /// Computes the state following `previous`, given the `next` one computed by a recurrent
+    /// cell.
+    ///
+    /// # Arguments
+    ///
+    /// * `previous` - state at the previous time step, of shape *(batch, hidden_size)*.
+    ///
+    /// * `next` - state computed by the cell at the current time step, of shape
+    /// *(batch, hidden_size)*.
+    pub fn forward<Pf: ?Sized, Pb: ?Sized, Nf: ?Sized, Nb: ?Sized>(
+        &self,
+        previous: VarDiff<Pf, Pb>,
+        next: VarDiff<Nf, Nb>,
+    ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
+    where
+        Pf: Data<Dim = Ix2>,
+        Pb: Gradient<Dim = Ix2>,
+        Nf: Data<Dim = Ix2>,
+        Nb: Gradient<Dim = Ix2>,
+    {
+        // Dropout scales its non-zero elements back to ones during training, while it leaves the
+        // expectation 1 - p unchanged during evaluation.
+        let update = Input::new(Tensor::from_elem(next.data().raw_dim(), 1. - self.p as f32))
+            .dropout_with_status(self.p, self.status.clone(), self.mask.clone());
+        let keep = 1. - update.clone();
+
+        next * update + previous * keep
+    }
+}
+
+impl Eval for Zoneout {
+    fn eval(&self) {
+        self.status.set(false)
+    }
+
+    fn train(&self) {
+        self.status.set(true)
+    }
+}
+
+impl Register for Zoneout {
+    fn register_status(&mut self, status: Rc<Cell<bool>>) {
+        self.status = status;
+    }
+
+    fn register_params(&self, _: &mut Vec<RawParam>) {}
+
+    fn register_dropouts(&self, masks: &mut Vec<Rc<DropoutMask>>) {
+        masks.push(self.mask.clone());
+    }
+}
+
+/// Applies a **linear transformation** to the incoming data.
+///
+/// ```text
+/// ʏ = xAᵀ + b
+/// ```
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Linear {
+    pub weight: Learnable<Ix2>,
+    pub bias: Learnable<Ix1>,
+}
+
+impl Linear {
+    /// Creates a linear layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `in_features` – size of each input sample.
+    ///
+    /// * `out_features` – size of each output sample.
+    ///
+    /// The learnable weight of the layer is of shape `(out_features, in_features)`. The learnable
+    /// bias of the layer is of shape `out_features`.
+    ///
+    /// The values for both the weight and bias are initialized from *U(-k, k)* where
+    /// `k = (1. / in_features as f32).sqrt()`.
+    pub fn new(in_features: usize, out_features: usize) -> Self {
+        let weight = Input::new(Tensor::zeros((out_features, in_features))).requires_grad();
+        let bias = Input::new(Tensor::zeros(out_features)).requires_grad();
+        let k = (1. / (in_features as f32)).sqrt();
+        init::uniform(&weight, -k, k);
+        init::uniform(&bias, -k, k);
+
+        Self { weight, bias }
+    }
+
+    /// Applies the linear transformation *y = xA^T + b* to the incoming data.
+    ///
+    /// # Arguments
+    ///
+    /// `data` - a variable of shape *(N, in_features)*, the output's shape will be
     /// *(N, out_features)*.
     pub fn forward<I, T, U>(
         &self,
@@ -624,8 +1499,8 @@ impl Linear {
     where
         I: MatMatMulT<Learnable<Ix2>>,
         I::Output: Into<VarDiff<T, U>>,
-        T: Data<Dim = Ix2>,
-        U: Gradient<Dim = Ix2>,
+        T: Data<Dim = Ix2> + 'static,
+        U: Gradient<Dim = Ix2> + 'static,
     {
         input.mm_t(self.weight.clone()).into() + self.bias.clone()
     }
@@ -638,999 +1513,4640 @@ impl Register for Linear {
         self.bias.register_params(params);
     }
 
-    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        let shape = self.weight.data().raw_dim();
+        linear_shape("Linear", shape[1], shape[0])
+    }
+}
+
+/// Applies a **binarized linear transformation** to the incoming data.
+///
+/// ```text
+/// ʏ = x sɪɢɴ(A)ᵀ + b
+/// ```
+///
+/// The layer keeps real-valued weights, that are binarized to *±1* on every forward pass as
+/// described in
+/// [BinaryConnect: Training Deep Neural Networks with binary weights during propagations](https://arxiv.org/abs/1511.00363).
+/// The gradient flows back to the real-valued weights through the straight-through estimator of
+/// [`.sign()`](VarDiff::sign()), so that they can be updated by any optimizer.
+///
+/// ```
+/// use neuronika::nn::BinaryLinear;
+///
+/// let lin = BinaryLinear::new(3, 2);
+/// let y = lin.forward(neuronika::rand((4, 3)));
+/// y.forward();
+///
+/// assert_eq!(y.data().shape(), &[4, 2]);
+/// ```
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct BinaryLinear {
+    pub weight: Learnable<Ix2>,
+    pub bias: Learnable<Ix1>,
+}
+
+impl BinaryLinear {
+    /// Creates a binarized linear layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `in_features` – size of each input sample.
+    ///
+    /// * `out_features` – size of each output sample.
+    ///
+    /// The real-valued weight of the layer is of shape `(out_features, in_features)` and is
+    /// initialized from *U(-1, 1)*. The learnable bias of the layer is of shape `out_features`
+    /// and is initialized from *U(-k, k)* where `k = (1. / in_features as f32).sqrt()`.
+    pub fn new(in_features: usize, out_features: usize) -> Self {
+        let weight = Input::new(Tensor::zeros((out_features, in_features))).requires_grad();
+        let bias = Input::new(Tensor::zeros(out_features)).requires_grad();
+        let k = (1. / (in_features as f32)).sqrt();
+        init::uniform(&weight, -1., 1.);
+        init::uniform(&bias, -k, k);
+
+        Self { weight, bias }
+    }
+
+    /// Applies the transformation *y = x sign(A)^T + b* to the incoming data.
+    ///
+    /// # Arguments
+    ///
+    /// `data` - a variable of shape *(N, in_features)*, the output's shape will be
+    /// *(N, out_features)*.
+    pub fn forward<I, T, U>(
+        &self,
+        input: I,
+    ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
+    where
+        I: MatMatMulT<BinaryWeight>,
+        I::Output: Into<VarDiff<T, U>>,
+        T: Data<Dim = Ix2> + 'static,
+        U: Gradient<Dim = Ix2> + 'static,
+    {
+        input.mm_t(self.weight.clone().sign()).into() + self.bias.clone()
+    }
+}
+
+/// The binarized weight of a [`BinaryLinear`] layer.
+pub type BinaryWeight = VarDiff<Sign<Input<Ix2>>, SignBackward<InputBackward<Ix2>, Input<Ix2>>>;
+
+impl Register for BinaryLinear {
+    /// Registers the real-valued weight and the bias of this `BinaryLinear` instance.
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.weight.register_params(params);
+        self.bias.register_params(params);
+    }
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        let shape = self.weight.data().raw_dim();
+        linear_shape("BinaryLinear", shape[1], shape[0])
+    }
+}
+
+/// Applies a **bilinear transformation** to two incoming inputs.
+///
+/// ```text
+/// ʏ = x₁ᵀAx₂ + b
+/// ```
+///
+/// ```
+/// use neuronika::nn::Bilinear;
+///
+/// let bilinear = Bilinear::new(3, 4, 2);
+/// let y = bilinear.forward(
+///     neuronika::rand((5, 3)).requires_grad(),
+///     neuronika::rand((5, 4)).requires_grad(),
+/// );
+/// y.forward();
+///
+/// assert_eq!(y.data().shape(), &[5, 2]);
+/// ```
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Bilinear {
+    pub weight: Learnable<Ix3>,
+    pub bias: Learnable<Ix1>,
+}
+
+impl Bilinear {
+    /// Creates a bilinear layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `in1_features` – size of each sample of the first input.
+    ///
+    /// * `in2_features` – size of each sample of the second input.
+    ///
+    /// * `out_features` – size of each output sample.
+    ///
+    /// The learnable weight of the layer is of shape `(out_features, in1_features, in2_features)`.
+    /// The learnable bias of the layer is of shape `out_features`.
+    ///
+    /// The values for both the weight and bias are initialized from *U(-k, k)* where
+    /// `k = (1. / in1_features as f32).sqrt()`.
+    pub fn new(in1_features: usize, in2_features: usize, out_features: usize) -> Self {
+        let weight =
+            Input::new(Tensor::zeros((out_features, in1_features, in2_features))).requires_grad();
+        let bias = Input::new(Tensor::zeros(out_features)).requires_grad();
+        let k = (1. / (in1_features as f32)).sqrt();
+        init::uniform(&weight, -k, k);
+        init::uniform(&bias, -k, k);
+
+        Self { weight, bias }
+    }
+
+    /// Applies the transformation *y = x₁ᵀAx₂ + b* to the incoming data.
+    ///
+    /// # Arguments
+    ///
+    /// * `input1` - a variable of shape *(N, in1_features)*.
+    ///
+    /// * `input2` - a variable of shape *(N, in2_features)*.
+    ///
+    /// The output's shape will be *(N, out_features)*.
+    pub fn forward<T1: ?Sized, U1: ?Sized, T2: ?Sized, U2: ?Sized>(
+        &self,
+        input1: VarDiff<T1, U1>,
+        input2: VarDiff<T2, U2>,
+    ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
+    where
+        T1: Data<Dim = Ix2> + 'static,
+        U1: Gradient<Dim = Ix2> + 'static,
+        T2: Data<Dim = Ix2> + 'static,
+        U2: Gradient<Dim = Ix2> + 'static,
+    {
+        let (in1_features, in2_features) = {
+            let shape = self.weight.data();
+            (shape.shape()[1], shape.shape()[2])
+        };
+
+        // The outer product of each pair of samples, flattened in row-major order, is obtained
+        // by spreading the features of both the inputs and by multiplying them.
+        let spread1 =
+            Tensor::from_shape_fn((in1_features, in1_features * in2_features), |(i, k)| {
+                (k / in2_features == i) as u8 as f32
+            });
+        let spread2 =
+            Tensor::from_shape_fn((in2_features, in1_features * in2_features), |(j, k)| {
+                (k % in2_features == j) as u8 as f32
+            });
+        let outer = input1.mm(Input::new(spread1)) * input2.mm(Input::new(spread2));
+
+        outer.mm_t(self.weight.clone().flatten()) + self.bias.clone()
+    }
+}
+
+impl Register for Bilinear {
+    /// Registers the weight and the bias of this `Bilinear` instance.
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.weight.register_params(params);
+        self.bias.register_params(params);
+    }
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        let shape = self.weight.data().raw_dim();
+        linear_shape("Bilinear", shape[1], shape[0])
+    }
+}
+
+/// Applies a **factorized bilinear pooling** to two incoming inputs.
+///
+/// ```text
+/// ʏ = SumPool(x₁Uᵀ ⊙ x₂Vᵀ, k)
+/// ```
+///
+/// The bilinear weight of each output feature is approximated by a product of two matrices of
+/// rank `k`, as described in
+/// [Multi-modal Factorized Bilinear Pooling with Co-Attention Learning for Visual Question Answering](https://arxiv.org/abs/1708.01471),
+/// so that the number of parameters grows linearly, instead of quadratically, with the sizes of
+/// the inputs. The sum pooling adds up each group of `k` consecutive features of the product.
+///
+/// ```
+/// use neuronika::nn::FactorizedBilinearPooling;
+///
+/// let pooling = FactorizedBilinearPooling::new(3, 4, 2, 5);
+/// let y = pooling.forward(
+///     neuronika::rand((6, 3)).requires_grad(),
+///     neuronika::rand((6, 4)).requires_grad(),
+/// );
+/// y.forward();
+///
+/// assert_eq!(y.data().shape(), &[6, 2]);
+/// ```
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct FactorizedBilinearPooling {
+    pub proj1: Linear,
+    pub proj2: Linear,
+    pub factor: usize,
+}
+
+impl FactorizedBilinearPooling {
+    /// Creates a factorized bilinear pooling layer.
+    ///
+    /// # Arguments
+    ///
+    /// * `in1_features` – size of each sample of the first input.
+    ///
+    /// * `in2_features` – size of each sample of the second input.
+    ///
+    /// * `out_features` – size of each output sample.
+    ///
+    /// * `factor` – rank *k* of the factorization.
+    ///
+    /// The projections of the inputs are [`Linear`] layers with `out_features * factor` output
+    /// features.
+    ///
+    /// # Panics
+    ///
+    /// If `factor` is zero.
+    pub fn new(
+        in1_features: usize,
+        in2_features: usize,
+        out_features: usize,
+        factor: usize,
+    ) -> Self {
+        assert!(factor > 0, "error: the factor must be positive.");
+
+        Self {
+            proj1: Linear::new(in1_features, out_features * factor),
+            proj2: Linear::new(in2_features, out_features * factor),
+            factor,
+        }
+    }
+
+    /// Computes the factorized bilinear pooling of the incoming data.
+    ///
+    /// # Arguments
+    ///
+    /// * `input1` - a variable of shape *(N, in1_features)*.
+    ///
+    /// * `input2` - a variable of shape *(N, in2_features)*.
+    ///
+    /// The output's shape will be *(N, out_features)*.
+    pub fn forward<T1: ?Sized, U1: ?Sized, T2: ?Sized, U2: ?Sized>(
+        &self,
+        input1: VarDiff<T1, U1>,
+        input2: VarDiff<T2, U2>,
+    ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
+    where
+        T1: Data<Dim = Ix2> + 'static,
+        U1: Gradient<Dim = Ix2> + 'static,
+        T2: Data<Dim = Ix2> + 'static,
+        U2: Gradient<Dim = Ix2> + 'static,
+    {
+        let features = self.proj1.weight.data().nrows();
+        let pool = Tensor::from_shape_fn((features, features / self.factor), |(k, o)| {
+            (k / self.factor == o) as u8 as f32
+        });
+
+        (self.proj1.forward(input1) * self.proj2.forward(input2)).mm(Input::new(pool))
+    }
+}
+
+impl Register for FactorizedBilinearPooling {
+    /// Registers the weights and the biases of the projections of this
+    /// `FactorizedBilinearPooling` instance.
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.proj1.register_params(params);
+        self.proj2.register_params(params);
+    }
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        let shape = self.proj1.weight.data().raw_dim();
+        linear_shape(
+            "FactorizedBilinearPooling",
+            shape[1],
+            shape[0] / self.factor,
+        )
+    }
+}
+
+/// Applies a **highway** transformation to the incoming data.
+///
+/// ```text
+/// ʏ = T(x) ⊙ H(x) + (1 - T(x)) ⊙ x
+///
+/// H(x) = ReLU(xAₕᵀ + bₕ)
+///
+/// T(x) = σ(xAₜᵀ + bₜ)
+/// ```
+///
+/// The gate *T* decides, feature by feature, how much of the transformation *H* is let through
+/// and how much of the input is carried over unchanged, as described in
+/// [Highway Networks](https://arxiv.org/abs/1505.00387).
+///
+/// ```
+/// use neuronika::nn::Highway;
+///
+/// let highway = Highway::new(3);
+/// let y = highway.forward(neuronika::rand((4, 3)).requires_grad());
+/// y.forward();
+///
+/// assert_eq!(y.data().shape(), &[4, 3]);
+/// ```
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Highway {
+    pub transform: Linear,
+    pub gate: Linear,
+}
+
+impl Highway {
+    /// Creates a highway layer.
+    ///
+    /// # Arguments
+    ///
+    /// `features` – size of each input and output sample.
+    ///
+    /// Both the transformation and the gate are [`Linear`] layers. The bias of the gate is
+    /// initialized to *-1*, so that the layer initially favours carrying over its input.
+    pub fn new(features: usize) -> Self {
+        let gate = Linear::new(features, features);
+        init::constant(&gate.bias, -1.);
+
+        Self {
+            transform: Linear::new(features, features),
+            gate,
+        }
+    }
+
+    /// Applies the highway transformation to the incoming data.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - a variable of shape *(N, features)*, the output's shape will be
+    /// *(N, features)*.
+    pub fn forward<T: ?Sized, U: ?Sized>(
+        &self,
+        input: VarDiff<T, U>,
+    ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
+    where
+        T: Data<Dim = Ix2> + 'static,
+        U: Gradient<Dim = Ix2> + 'static,
+    {
+        let transform = self.transform.forward(input.clone()).relu();
+        let gate = self.gate.forward(input.clone()).sigmoid();
+
+        gate.clone() * transform + (1. - gate) * input
+    }
+}
+
+impl Register for Highway {
+    /// Registers the weights and the biases of the transformation and of the gate of this
+    /// `Highway` instance.
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.transform.register_params(params);
+        self.gate.register_params(params);
+    }
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        let shape = self.transform.weight.data().raw_dim();
+        linear_shape("Highway", shape[1], shape[0])
+    }
+}
+
+/// A **long short-term memory (LSTM)** cell.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[allow(clippy::upper_case_acronyms)]
+pub struct LSTMCell {
+    pub weight_ih: Learnable<Ix2>,
+    pub weight_hh: Learnable<Ix2>,
+    pub bias_ih: Learnable<Ix1>,
+    pub bias_hh: Learnable<Ix1>,
+}
+
+impl LSTMCell {
+    /// Creates a new LSTMCell.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_size` - number of expected features in the input.
+    ///
+    /// * `hidden_size` - number of features in the hidden state.
+    ///
+    /// All the weight and biases are initialized from *U(-k, k)* where
+    /// `k = (1. / hidden_size as f32).sqrt()`.
+    pub fn new(input_size: usize, hidden_size: usize) -> Self {
+        let (weight_ih_shape, weight_hh_shape, bias_shape) = {
+            let xhidden_size = 4 * hidden_size;
+            (
+                (xhidden_size, input_size),
+                (xhidden_size, hidden_size),
+                xhidden_size,
+            )
+        };
+        let weight_ih = Input::new(Tensor::zeros(weight_ih_shape)).requires_grad();
+        let weight_hh = Input::new(Tensor::zeros(weight_hh_shape)).requires_grad();
+        let bias_ih = Input::new(Tensor::zeros(bias_shape)).requires_grad();
+        let bias_hh = Input::new(Tensor::zeros(bias_shape)).requires_grad();
+
+        let k = 1. / (hidden_size as f32).sqrt();
+        init::uniform(&weight_ih, -k, k);
+        init::uniform(&weight_hh, -k, k);
+        init::uniform(&bias_ih, -k, k);
+        init::uniform(&bias_hh, -k, k);
+
+        Self {
+            weight_ih,
+            weight_hh,
+            bias_ih,
+            bias_hh,
+        }
+    }
+
+    /// Computes a single **LSTM step**.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - a tuple of tensors, both of shape *(batch, hidden_size)*, containing the
+    /// initial hidden state for each element in the batch and the initial cell's state for
+    /// each element in the batch.
+    ///
+    /// * `input` - a variable containing the input features of shape *(batch, input_size)*.
+    ///
+    /// The **output** is a tuple of tensors made of the next hidden state for each element in
+    /// the batch, of shape *(batch, hidden_size)* and the next cell's state for each element in
+    /// the batch, of shape *(batch, hidden_size)*.
+    pub fn forward<Cf: ?Sized, Cb: ?Sized, Hf: ?Sized, Hb: ?Sized, I, T, U>(
+        &self,
+        state: (VarDiff<Cf, Cb>, VarDiff<Hf, Hb>),
+        input: I,
+    ) -> (
+        VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>,
+        VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>,
+    )
+    where
+        Cf: Data<Dim = Ix2>,
+        Cb: Gradient<Dim = Ix2>,
+        Hf: Data<Dim = Ix2>,
+        Hb: Gradient<Dim = Ix2>,
+        I: MatMatMulT<Learnable<Ix2>>,
+        I::Output: Into<VarDiff<T, U>>,
+        T: Data<Dim = Ix2> + 'static,
+        U: Gradient<Dim = Ix2> + 'static,
+    {
+        let (cell_state, hidden) = state;
+        let gates = hidden.mm_t(self.weight_hh.clone())
+            + self.bias_hh.clone()
+            + input.mm_t(self.weight_ih.clone()).into()
+            + self.bias_ih.clone();
+        let gate_shape = {
+            let (gates_shape_rows, gates_shape_cols) = gates.data().dim();
+            (gates_shape_rows, gates_shape_cols / 4)
+        };
+        let chunked_gates = gates.chunks(gate_shape);
+        let (input_gate, forget_gate, cell_state_gate, output_gate) = (
+            chunked_gates[0].clone().sigmoid(),
+            chunked_gates[1].clone().tanh(),
+            chunked_gates[2].clone().sigmoid(),
+            chunked_gates[3].clone().sigmoid(),
+        );
+        let new_cell_state = forget_gate * cell_state + (input_gate * cell_state_gate);
+        let new_hidden = output_gate * new_cell_state.clone().tanh();
+
+        (new_cell_state, new_hidden)
+    }
+}
+
+impl Register for LSTMCell {
+    /// Registers the weights and the biases of this LSTMCell instance.
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.weight_hh.register_params(params);
+        self.weight_ih.register_params(params);
+        self.bias_hh.register_params(params);
+        self.bias_ih.register_params(params);
+    }
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        let shape = self.weight_hh.data().raw_dim();
+        linear_shape("LSTMCell", self.weight_ih.data().shape()[1], shape[1])
+    }
+}
+
+/// A **gated recurrent unit (GRU)** cell.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[allow(clippy::upper_case_acronyms)]
+pub struct GRUCell {
+    pub weight_ih: Learnable<Ix2>,
+    pub weight_hh: Learnable<Ix2>,
+    pub bias_ih: Learnable<Ix1>,
+    pub bias_hh: Learnable<Ix1>,
+}
+
+impl GRUCell {
+    /// Creates a new GRUCell.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_size` - number of expected features in the input.
+    ///
+    /// * `hidden_size` - number of features in the hidden state.
+    ///
+    /// All the weight and biases are initialized from *U(-k, k)* where
+    /// `k = (1. / hidden_size as f32).sqrt()`.
+    pub fn new(input_size: usize, hidden_size: usize) -> Self {
+        let (weight_ih_shape, weight_hh_shape, bias_shape) = {
+            let xhidden_size = 3 * hidden_size;
+            (
+                (xhidden_size, input_size),
+                (xhidden_size, hidden_size),
+                xhidden_size,
+            )
+        };
+        let weight_ih = Input::new(Tensor::zeros(weight_ih_shape)).requires_grad();
+        let weight_hh = Input::new(Tensor::zeros(weight_hh_shape)).requires_grad();
+        let bias_ih = Input::new(Tensor::zeros(bias_shape)).requires_grad();
+        let bias_hh = Input::new(Tensor::zeros(bias_shape)).requires_grad();
+
+        let k = 1. / (hidden_size as f32).sqrt();
+        init::uniform(&weight_ih, -k, k);
+        init::uniform(&weight_hh, -k, k);
+        init::uniform(&bias_ih, -k, k);
+        init::uniform(&bias_hh, -k, k);
+
+        Self {
+            weight_ih,
+            weight_hh,
+            bias_ih,
+            bias_hh,
+        }
+    }
+
+    /// Computes a single **GRU step**.
+    ///
+    /// * `hidden` - a variable of shape *(batch, hidden_size)*, containing the initial hidden state
+    /// for each element in the batch.
+    ///
+    /// * `input` - a variable containing the input features of shape *(batch, input_size)*.
+    ///
+    /// The **output** is  a variable made of the next hidden state for each element in
+    /// the batch, of shape *(batch, hidden_size)*.
+    pub fn forward<Hf: ?Sized, Hb: ?Sized, I, T, U>(
+        &self,
+        hidden: VarDiff<Hf, Hb>,
+        input: I,
+    ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
+    where
+        Hf: Data<Dim = Ix2>,
+        Hb: Gradient<Dim = Ix2>,
+        I: MatMatMulT<Learnable<Ix2>>,
+        I::Output: Into<VarDiff<T, U>>,
+        T: Data<Dim = Ix2> + 'static,
+        U: Gradient<Dim = Ix2> + 'static,
+    {
+        let (igates, hgates) = {
+            (
+                input.mm_t(self.weight_ih.clone()).into() + self.bias_ih.clone(),
+                hidden.clone().mm_t(self.weight_hh.clone()) + self.bias_hh.clone(),
+            )
+        };
+        let gate_shape = {
+            let (gates_shape_rows, gates_shape_cols) = hgates.data().dim();
+            (gates_shape_rows, gates_shape_cols / 3)
+        };
+        let (chunked_igates, chunked_hgates) =
+            (igates.chunks(gate_shape), hgates.chunks(gate_shape));
+
+        let reset_gate = (chunked_hgates[0].clone() + chunked_igates[0].clone()).sigmoid();
+        let input_gate = (chunked_hgates[1].clone() + chunked_igates[1].clone()).sigmoid();
+        let new_gate =
+            (chunked_igates[2].clone() + (chunked_hgates[2].clone() * reset_gate)).tanh();
+        (hidden - new_gate.clone()) * input_gate + new_gate
+    }
+}
+
+impl Register for GRUCell {
+    /// Registers the weights and the biases of this `GRUCell` instance.
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.weight_hh.register_params(params);
+        self.weight_ih.register_params(params);
+        self.bias_hh.register_params(params);
+        self.bias_ih.register_params(params);
+    }
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        let shape = self.weight_hh.data().raw_dim();
+        linear_shape("GRUCell", self.weight_ih.data().shape()[1], shape[1])
+    }
+}
+
+/// A batch of sequences of different lengths, packed so that no computation is wasted on padding.
+///
+/// The sequences are sorted by decreasing length and their time steps are stored one after the
+/// other: the *t*-th time step holds the *t*-th element of the `batch_sizes[t]` sequences that
+/// are longer than *t*. The packed data has thus shape *(L₁ + ... + Lₙ, features)*.
+///
+/// Packed sequences are consumed by the recurrent layers [`LSTM`] and [`GRU`].
+///
+/// ```
+/// use neuronika::nn::PackedSequence;
+///
+/// let sequences = neuronika::rand((3, 4, 2)).requires_grad();
+/// let packed = PackedSequence::pack(sequences, &[2, 4, 1]);
+///
+/// assert_eq!(packed.batch_sizes(), &[3, 2, 1, 1]);
+/// assert_eq!(packed.sorted_indices(), &[1, 0, 2]);
+/// assert_eq!(packed.data().data().shape(), &[7, 2]);
+/// ```
+pub struct PackedSequence {
+    steps: Vec<Sequence>,
+    data: Sequence,
+    batch_sizes: Vec<usize>,
+    sorted_indices: Vec<usize>,
+}
+
+impl PackedSequence {
+    /// Packs a batch of padded sequences.
+    ///
+    /// # Arguments
+    ///
+    /// * `sequences` - padded sequences of shape *(batch, length, features)*.
+    ///
+    /// * `lengths` - length of each sequence, in any order.
+    ///
+    /// # Panics
+    ///
+    /// If the number of lengths differs from the number of sequences, or if a length is zero or
+    /// longer than the padded sequences.
+    pub fn pack<T: ?Sized, U: ?Sized>(sequences: VarDiff<T, U>, lengths: &[usize]) -> Self
+    where
+        T: Data<Dim = Ix3> + 'static,
+        U: Gradient<Dim = Ix3> + 'static,
+    {
+        let (batch_size, max_len, features) = sequences.data().dim();
+        assert_eq!(
+            lengths.len(),
+            batch_size,
+            "error: {} lengths were given for {} sequences.",
+            lengths.len(),
+            batch_size
+        );
+        assert!(
+            lengths.iter().all(|&len| len > 0 && len <= max_len),
+            "error: the lengths of the sequences must be between 1 and {}, got {:?}.",
+            max_len,
+            lengths
+        );
+
+        let mut sorted_indices: Vec<_> = (0..batch_size).collect();
+        sorted_indices.sort_by(|&a, &b| lengths[b].cmp(&lengths[a]));
+        let batch_sizes: Vec<_> = (0..lengths[sorted_indices[0]])
+            .map(|t| lengths.iter().filter(|&&len| len > t).count())
+            .collect();
+
+        let steps = sequences
+            .chunks((batch_size, 1, features))
+            .into_iter()
+            .zip(&batch_sizes)
+            .map(|(step, &size)| select_rows(&step.flatten().into_dyn(), &sorted_indices[..size]))
+            .collect();
+
+        Self::from_steps(steps, batch_sizes, sorted_indices)
+    }
+
+    fn from_steps(
+        steps: Vec<Sequence>,
+        batch_sizes: Vec<usize>,
+        sorted_indices: Vec<usize>,
+    ) -> Self {
+        let data = VarDiff::cat(&steps, 0).into_dyn();
+
+        Self {
+            steps,
+            data,
+            batch_sizes,
+            sorted_indices,
+        }
+    }
+
+    /// Returns the packed data, of shape *(L₁ + ... + Lₙ, features)*.
+    pub fn data(&self) -> &Sequence {
+        &self.data
+    }
+
+    /// Returns the number of sequences at each time step.
+    pub fn batch_sizes(&self) -> &[usize] {
+        &self.batch_sizes
+    }
+
+    /// Returns the indices of the sequences sorted by decreasing length.
+    pub fn sorted_indices(&self) -> &[usize] {
+        &self.sorted_indices
+    }
+
+    /// Returns the length of each sequence, in the original order.
+    pub fn lengths(&self) -> Vec<usize> {
+        let mut lengths = vec![0; self.sorted_indices.len()];
+        for &size in &self.batch_sizes {
+            self.sorted_indices[..size]
+                .iter()
+                .for_each(|&index| lengths[index] += 1);
+        }
+
+        lengths
+    }
+
+    /// Pads the sequences with zeros, returning a variable of shape *(batch, length, features)*
+    /// with the sequences in the original order.
+    pub fn pad(&self) -> VarDiff<dyn Data<Dim = Ix3>, dyn Gradient<Dim = Ix3>> {
+        let batch_size = self.sorted_indices.len();
+        let unsorted = unsorted_indices(&self.sorted_indices);
+
+        let steps: Vec<_> = self
+            .steps
+            .iter()
+            .map(|step| {
+                let (size, features) = step.data().dim();
+                let step = if size < batch_size {
+                    let padding = crate::zeros((batch_size - size, features))
+                        .requires_grad()
+                        .into_dyn();
+                    VarDiff::cat(&[step.clone(), padding], 0).into_dyn()
+                } else {
+                    step.clone()
+                };
+
+                select_rows(&step, &unsorted)
+            })
+            .collect();
+
+        VarDiff::stack(&steps, 1).into_dyn()
+    }
+}
+
+/// A batch of feature vectors.
+type Sequence = VarDiff<dyn Data<Dim = Ix2>, dyn Gradient<Dim = Ix2>>;
+
+/// Returns the rows of `input` at `indices`.
+fn select_rows(input: &Sequence, indices: &[usize]) -> Sequence {
+    let features = input.data().ncols();
+    let indices = Array2::from_shape_fn((indices.len(), features), |(row, _)| indices[row] as i64);
+
+    input.clone().gather(0, crate::indices(indices)).into_dyn()
+}
+
+/// Returns the positions of the sequences in the sorted order, i.e. the inverse permutation of
+/// `sorted_indices`.
+fn unsorted_indices(sorted_indices: &[usize]) -> Vec<usize> {
+    let mut unsorted = vec![0; sorted_indices.len()];
+    sorted_indices
+        .iter()
+        .enumerate()
+        .for_each(|(position, &index)| unsorted[index] = position);
+
+    unsorted
+}
+
+/// Feeds the time steps of a packed sequence to a recurrent cell, starting from zero states.
+///
+/// `cell` computes the next states from the current ones and the input, with the hidden state
+/// last. The hidden states of all the time steps are returned together with the final states of
+/// each sequence, which are taken at its own last time step and are in the sorted order.
+fn run_packed(
+    steps: &[Sequence],
+    batch_sizes: &[usize],
+    hidden_size: usize,
+    num_states: usize,
+    reverse: bool,
+    cell: impl Fn(Vec<Sequence>, Sequence) -> Vec<Sequence>,
+) -> (Vec<Sequence>, Vec<Sequence>) {
+    let zeros = |rows| crate::zeros((rows, hidden_size)).requires_grad().into_dyn();
+    let mut outputs = vec![None; steps.len()];
+
+    if reverse {
+        // The sequences join the batch as their last time step is reached.
+        let initial: Vec<_> = (0..num_states).map(|_| zeros(batch_sizes[0])).collect();
+        let mut states: Vec<Sequence> = Vec::new();
+        for t in (0..steps.len()).rev() {
+            let (current, size) = (
+                states.first().map_or(0, |s| s.data().nrows()),
+                batch_sizes[t],
+            );
+            if size > current {
+                let joining = (current..size).collect::<Vec<_>>();
+                states = initial
+                    .iter()
+                    .enumerate()
+                    .map(|(i, initial)| {
+                        let joining = select_rows(initial, &joining);
+                        match states.get(i) {
+                            Some(state) => VarDiff::cat(&[state.clone(), joining], 0).into_dyn(),
+                            None => joining,
+                        }
+                    })
+                    .collect();
+            }
+            states = cell(states, steps[t].clone());
+            outputs[t] = states.last().cloned();
+        }
+
+        (outputs.into_iter().flatten().collect(), states)
+    } else {
+        // The sequences leave the batch after their last time step, along with their states.
+        let mut states: Vec<_> = (0..num_states).map(|_| zeros(batch_sizes[0])).collect();
+        let mut finished: Vec<Vec<Sequence>> = vec![Vec::new(); num_states];
+        for (t, step) in steps.iter().enumerate() {
+            let (current, size) = (states[0].data().nrows(), batch_sizes[t]);
+            if size < current {
+                let (leaving, staying) = (
+                    (size..current).collect::<Vec<_>>(),
+                    (0..size).collect::<Vec<_>>(),
+                );
+                states = states
+                    .iter()
+                    .zip(&mut finished)
+                    .map(|(state, finished)| {
+                        finished.push(select_rows(state, &leaving));
+                        select_rows(state, &staying)
+                    })
+                    .collect();
+            }
+            states = cell(states, step.clone());
+            outputs[t] = states.last().cloned();
+        }
+
+        let states = states
+            .into_iter()
+            .zip(finished)
+            .map(|(state, mut finished)| {
+                finished.push(state);
+                finished.reverse();
+                VarDiff::cat(&finished, 0).into_dyn()
+            })
+            .collect();
+
+        (outputs.into_iter().flatten().collect(), states)
+    }
+}
+
+/// Feeds a packed sequence to a stack of recurrent layers, each made of one cell per direction.
+///
+/// Returns the hidden states of the last layer and the final states of each layer and direction,
+/// in the original order of the sequences.
+fn run_layers<C>(
+    cells: &[C],
+    bidirectional: bool,
+    input: &PackedSequence,
+    hidden_size: usize,
+    num_states: usize,
+    cell_forward: impl Fn(&C, Vec<Sequence>, Sequence) -> Vec<Sequence>,
+) -> (PackedSequence, Vec<Vec<Sequence>>) {
+    let directions = if bidirectional { 2 } else { 1 };
+    let unsorted = unsorted_indices(&input.sorted_indices);
+    let mut steps = input.steps.clone();
+    let mut final_states = Vec::with_capacity(cells.len());
+
+    for layer in cells.chunks(directions) {
+        let mut layer_outputs: Vec<Vec<Sequence>> = vec![Vec::new(); steps.len()];
+        for (direction, cell) in layer.iter().enumerate() {
+            let (outputs, states) = run_packed(
+                &steps,
+                &input.batch_sizes,
+                hidden_size,
+                num_states,
+                direction == 1,
+                |states, step| cell_forward(cell, states, step),
+            );
+            layer_outputs
+                .iter_mut()
+                .zip(outputs)
+                .for_each(|(layer_output, output)| layer_output.push(output));
+            final_states.push(
+                states
+                    .iter()
+                    .map(|state| select_rows(state, &unsorted))
+                    .collect(),
+            );
+        }
+
+        steps = layer_outputs
+            .into_iter()
+            .map(|mut outputs| match outputs.len() {
+                1 => outputs.pop().unwrap(),
+                _ => VarDiff::cat(&outputs, 1).into_dyn(),
+            })
+            .collect();
+    }
+
+    let output = PackedSequence::from_steps(
+        steps,
+        input.batch_sizes.clone(),
+        input.sorted_indices.clone(),
+    );
+
+    (output, final_states)
+}
+
+/// A multi-layer, possibly bidirectional, **long short-term memory (LSTM)** network.
+///
+/// Each layer is made of an [`LSTMCell`] per direction. The first layer reads the input sequences,
+/// while each of the following ones reads the hidden states of the previous layer, concatenated
+/// along the features axis when the network is bidirectional.
+///
+/// ```
+/// use neuronika::nn::{PackedSequence, LSTM};
+///
+/// let lstm = LSTM::new(3, 5, 2, true);
+///
+/// let sequences = neuronika::rand((4, 6, 3)).requires_grad();
+/// let input = PackedSequence::pack(sequences, &[6, 2, 4, 1]);
+///
+/// let (output, states) = lstm.forward(&input);
+/// assert_eq!(output.pad().data().shape(), &[4, 6, 10]);
+///
+/// // The final cell's and hidden states of each layer and direction.
+/// assert_eq!(states.len(), 4);
+/// assert_eq!(states[0].1.data().shape(), &[4, 5]);
+/// ```
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[allow(clippy::upper_case_acronyms)]
+pub struct LSTM {
+    /// The cells of the network, layer by layer, the forward one before the backward one.
+    pub cells: Vec<LSTMCell>,
+    pub bidirectional: bool,
+}
+
+impl LSTM {
+    /// Creates a new LSTM.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_size` - number of expected features in the input.
+    ///
+    /// * `hidden_size` - number of features in the hidden state.
+    ///
+    /// * `num_layers` - number of stacked layers.
+    ///
+    /// * `bidirectional` - whether each layer reads the sequences backwards too.
+    ///
+    /// # Panics
+    ///
+    /// If `num_layers` is zero.
+    pub fn new(
+        input_size: usize,
+        hidden_size: usize,
+        num_layers: usize,
+        bidirectional: bool,
+    ) -> Self {
+        let cells = recurrent_sizes(input_size, hidden_size, num_layers, bidirectional)
+            .map(|input_size| LSTMCell::new(input_size, hidden_size))
+            .collect();
+
+        Self {
+            cells,
+            bidirectional,
+        }
+    }
+
+    /// Computes the hidden states of the last layer at each time step of `input`.
+    ///
+    /// The **output** is a tuple made of the hidden states, packed as `input`, of shape
+    /// *(L₁ + ... + Lₙ, directions * hidden_size)*, and of the final cell's and hidden states of
+    /// each layer and direction, of shape *(batch, hidden_size)*. The final states of each
+    /// sequence are taken at its own last time step and are in the original order.
+    pub fn forward(&self, input: &PackedSequence) -> (PackedSequence, Vec<(Sequence, Sequence)>) {
+        let hidden_size = self.cells[0].weight_hh.data().ncols();
+        let (output, states) = run_layers(
+            &self.cells,
+            self.bidirectional,
+            input,
+            hidden_size,
+            2,
+            |cell, states, step| {
+                let (cell_state, hidden) =
+                    cell.forward((states[0].clone(), states[1].clone()), step);
+                vec![cell_state.into_dyn(), hidden.into_dyn()]
+            },
+        );
+
+        let states = states
+            .into_iter()
+            .map(|mut states| {
+                let hidden = states.pop().unwrap();
+                (states.pop().unwrap(), hidden)
+            })
+            .collect();
+
+        (output, states)
+    }
+}
+
+impl Register for LSTM {
+    /// Registers the weights and the biases of all the cells of this `LSTM` instance.
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.cells
+            .iter()
+            .for_each(|cell| cell.register_params(params));
+    }
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        let directions = if self.bidirectional { 2 } else { 1 };
+        linear_shape(
+            "LSTM",
+            self.cells[0].weight_ih.data().ncols(),
+            directions * self.cells[0].weight_hh.data().ncols(),
+        )
+    }
+}
+
+/// A multi-layer, possibly bidirectional, **gated recurrent unit (GRU)** network.
+///
+/// Each layer is made of a [`GRUCell`] per direction. The first layer reads the input sequences,
+/// while each of the following ones reads the hidden states of the previous layer, concatenated
+/// along the features axis when the network is bidirectional.
+///
+/// ```
+/// use neuronika::nn::{PackedSequence, GRU};
+///
+/// let gru = GRU::new(3, 5, 1, false);
+///
+/// let sequences = neuronika::rand((2, 3, 3)).requires_grad();
+/// let (output, states) = gru.forward(&PackedSequence::pack(sequences, &[3, 2]));
+///
+/// assert_eq!(output.data().data().shape(), &[5, 5]);
+/// assert_eq!(states[0].data().shape(), &[2, 5]);
+/// ```
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[allow(clippy::upper_case_acronyms)]
+pub struct GRU {
+    /// The cells of the network, layer by layer, the forward one before the backward one.
+    pub cells: Vec<GRUCell>,
+    pub bidirectional: bool,
+}
+
+impl GRU {
+    /// Creates a new GRU.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_size` - number of expected features in the input.
+    ///
+    /// * `hidden_size` - number of features in the hidden state.
+    ///
+    /// * `num_layers` - number of stacked layers.
+    ///
+    /// * `bidirectional` - whether each layer reads the sequences backwards too.
+    ///
+    /// # Panics
+    ///
+    /// If `num_layers` is zero.
+    pub fn new(
+        input_size: usize,
+        hidden_size: usize,
+        num_layers: usize,
+        bidirectional: bool,
+    ) -> Self {
+        let cells = recurrent_sizes(input_size, hidden_size, num_layers, bidirectional)
+            .map(|input_size| GRUCell::new(input_size, hidden_size))
+            .collect();
+
+        Self {
+            cells,
+            bidirectional,
+        }
+    }
+
+    /// Computes the hidden states of the last layer at each time step of `input`.
+    ///
+    /// The **output** is a tuple made of the hidden states, packed as `input`, of shape
+    /// *(L₁ + ... + Lₙ, directions * hidden_size)*, and of the final hidden states of each layer
+    /// and direction, of shape *(batch, hidden_size)*. The final states of each sequence are
+    /// taken at its own last time step and are in the original order.
+    pub fn forward(&self, input: &PackedSequence) -> (PackedSequence, Vec<Sequence>) {
+        let hidden_size = self.cells[0].weight_hh.data().ncols();
+        let (output, states) = run_layers(
+            &self.cells,
+            self.bidirectional,
+            input,
+            hidden_size,
+            1,
+            |cell, states, step| vec![cell.forward(states[0].clone(), step).into_dyn()],
+        );
+
+        let states = states
+            .into_iter()
+            .map(|mut states| states.pop().unwrap())
+            .collect();
+
+        (output, states)
+    }
+}
+
+impl Register for GRU {
+    /// Registers the weights and the biases of all the cells of this `GRU` instance.
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.cells
+            .iter()
+            .for_each(|cell| cell.register_params(params));
+    }
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        let directions = if self.bidirectional { 2 } else { 1 };
+        linear_shape(
+            "GRU",
+            self.cells[0].weight_ih.data().ncols(),
+            directions * self.cells[0].weight_hh.data().ncols(),
+        )
+    }
+}
+
+/// Returns the input size of each cell of a recurrent network.
+fn recurrent_sizes(
+    input_size: usize,
+    hidden_size: usize,
+    num_layers: usize,
+    bidirectional: bool,
+) -> impl Iterator<Item = usize> {
+    assert!(
+        num_layers > 0,
+        "error: a recurrent network needs at least one layer."
+    );
+
+    let directions = if bidirectional { 2 } else { 1 };
+    (0..num_layers).flat_map(move |layer| {
+        let input_size = if layer == 0 {
+            input_size
+        } else {
+            directions * hidden_size
+        };
+        std::iter::repeat_n(input_size, directions)
+    })
+}
+
+/// Applies a **temporal convolution** over an input signal composed of several input planes.
+///
+/// See also [`GroupedConv1d`].
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Conv1d<Pad: PaddingMode> {
+    pub padding: usize,
+    pub padding_mode: Pad,
+    pub stride: usize,
+    pub dilation: usize,
+    pub weight: Learnable<Ix3>,
+    pub bias: Learnable<Ix2>,
+}
+
+impl<Pad: PaddingMode> Conv1d<Pad> {
+    /// Creates a new Conv1d.
+    ///
+    /// # Arguments
+    ///
+    /// * `in_channels` - number of planes in the input signal.
+    ///
+    /// * `out_channels` - number of planes in the output signal.
+    ///
+    /// * `kernel_size` - size of the kernel, a number for this one-dimensional case.
+    ///
+    /// * `padding` - padding to be applied to the input, a number for this one-dimensional case.
+    ///
+    /// * `padding_mode` - padding mode, it can be: [`Zero`], [`Constant`], [`Reflective`] or
+    /// [`Replicative`].
+    ///
+    /// * `stride` - stride of the convolution, a number for this one-dimensional case.
+    ///
+    /// * `dilation` - controls the spacing between the kernel points, a number for this
+    /// one-dimensional case.
+    ///
+    /// The weight and the bias of the layer are initialized from *U(-k, k)* where
+    /// `k = (1. /(in_channels * kernel_size) as f32).sqrt()`.
+    pub fn new(
+        in_channels: usize,
+        out_channels: usize,
+        kernel_size: usize,
+        padding: usize,
+        padding_mode: Pad,
+        stride: usize,
+        dilation: usize,
+    ) -> Self {
+        let weight =
+            Input::new(Tensor::zeros((out_channels, in_channels, kernel_size))).requires_grad();
+        let bias = Input::new(Tensor::zeros((out_channels, 1))).requires_grad();
+
+        let k = (1. / (in_channels * kernel_size) as f32).sqrt();
+        init::uniform(&weight, -k, k);
+        init::uniform(&bias, -k, k);
+
+        Self {
+            padding,
+            padding_mode,
+            stride,
+            dilation,
+            weight,
+            bias,
+        }
+    }
+
+    /// Computes a 1-dimensional convolution *(cross correlation)*.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - signal to convolve.
+    ///
+    /// The **input** must be of shape *(N, Cin, L)*
+    /// * **N** is the batch size
+    /// * **Cin** is the number of input channels
+    /// * **L** is the **length** of the input
+    ///
+    /// The **kernel** must be of shape *(Cout, Cin, Lk)*
+    /// * **Cout** is the number of output channels
+    /// * **Cin** is the number of input channels
+    /// * **Lk** is the **length** of the kernel
+    ///
+    /// The resulting output shape will be *(N, Cout, Lout)*
+    pub fn forward<I, T, U>(
+        &self,
+        input: I,
+    ) -> VarDiff<impl Data<Dim = Ix3>, impl Gradient<Dim = Ix3>>
+    where
+        I: Convolve<I, Learnable<Ix3>, Pad>,
+        I::Output: Into<VarDiff<T, U>>,
+        T: Data<Dim = Ix3> + 'static,
+        U: Gradient<Dim = Ix3> + 'static,
+    {
+        I::convolve(
+            input,
+            self.weight.clone(),
+            &[self.stride],
+            &[self.dilation],
+            &[self.padding],
+            self.padding_mode,
+        )
+        .into()
+            + self.bias.clone()
+    }
+}
+
+impl<Pad: PaddingMode> Register for Conv1d<Pad> {
+    /// Registers the weight and the bias of this `Conv1d` instance.
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.weight.register_params(params);
+        self.bias.register_params(params);
+    }
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        convolution_shape(
+            "Conv1d",
+            self.weight.data().shape(),
+            1,
+            &[self.padding],
+            &[self.stride],
+            &[self.dilation],
+        )
+    }
+}
+
+/// Applies a **grouped temporal convolution** over an input signal composed of several input
+/// planes.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct GroupedConv1d<Pad: PaddingMode> {
+    pub padding: usize,
+    pub padding_mode: Pad,
+    pub stride: usize,
+    pub dilation: usize,
+    pub groups: usize,
+    pub weight: Learnable<Ix3>,
+    pub bias: Learnable<Ix2>,
+}
+
+impl<Pad: PaddingMode> GroupedConv1d<Pad> {
+    /// Creates a new GroupedConv1d.
+    ///
+    /// # Arguments
+    ///
+    /// * `in_channels` - number of planes in the input signal.
+    ///
+    /// * `out_channels` - number of planes in the output signal.
+    ///
+    /// * `kernel_size` - size of the kernel, a number for this one-dimensional case.
+    ///
+    /// * `padding` - padding to be applied to the input, a number for this one-dimensional case.
+    ///
+    /// * `padding_mode` - padding mode, it can be: [`Zero`], [`Constant`], [`Reflective`] or
+    /// [`Replicative`].
+    ///
+    /// * `stride` - stride of the convolution, a number for this one-dimensional case.
+    ///
+    /// * `dilation` - controls the spacing between the kernel points, a number for this
+    /// one-dimensional case.
+    ///
+    /// * `groups` -  controls the connections between inputs and outputs. `in_channels` and
+    /// `out_channels` must both be **divisible by groups**.
+    ///
+    /// For example:
+    /// * at `groups = 1`, all inputs are convolved to all outputs.
+    /// * at `groups = 2`, the operation becomes equivalent to having two convolutional layers side
+    /// by side, each seeing half the input channels and producing half the output channels, and
+    /// both subsequently concatenated.
+    ///* at `groups = in_channels`, each input channel is convolved with its own set of filters.
+    ///
+    /// The weight and the bias of the layer are initialized from *U(-k, k)* where
+    /// `k = (groups /(in_channels * kernel_size) as f32).sqrt()`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        in_channels: usize,
+        out_channels: usize,
+        kernel_size: usize,
+        padding: usize,
+        padding_mode: Pad,
+        stride: usize,
+        dilation: usize,
+        groups: usize,
+    ) -> Self {
+        let weight = Input::new(Tensor::zeros((
+            out_channels,
+            in_channels / groups,
+            kernel_size,
+        )))
+        .requires_grad();
+        let bias = Input::new(Tensor::zeros((out_channels, 1))).requires_grad();
+
+        let k = (groups as f32 / (in_channels * kernel_size) as f32).sqrt();
+        init::uniform(&weight, -k, k);
+        init::uniform(&bias, -k, k);
+
+        Self {
+            padding,
+            padding_mode,
+            stride,
+            dilation,
+            groups,
+            weight,
+            bias,
+        }
+    }
+
+    /// Computes a 1-dimensional grouped convolution *(cross correlation)*.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - signal to convolve.
+    ///
+    /// The **input** must be of shape *(N, Cin, L)*
+    /// * **N** is the batch size
+    /// * **Cin** is the number of input channels
+    /// * **L** is the **length** of the input
+    ///
+    /// The **kernel** must be of shape *(Cout, Cin, Lk)*
+    /// * **Cout** is the number of output channels
+    /// * **Cin** is the number of input channels
+    /// * **Lk** is the **length** of the kernel
+    ///
+    /// The resulting output shape will be *(N, Cout, Lout)*
+    pub fn forward<I, T, U>(
+        &self,
+        input: I,
+    ) -> VarDiff<impl Data<Dim = Ix3>, impl Gradient<Dim = Ix3>>
+    where
+        I: ConvolveWithGroups<I, Learnable<Ix3>, Pad>,
+        I::Output: Into<VarDiff<T, U>>,
+        T: Data<Dim = Ix3> + 'static,
+        U: Gradient<Dim = Ix3> + 'static,
+    {
+        I::convolve_with_groups(
+            input,
+            self.weight.clone(),
+            &[self.stride],
+            &[self.dilation],
+            &[self.padding],
+            self.padding_mode,
+            self.groups,
+        )
+        .into()
+            + self.bias.clone()
+    }
+}
+
+impl<Pad: PaddingMode> Register for GroupedConv1d<Pad> {
+    /// Registers the weight and the bias of this `GroupedConv1d` instance.
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.weight.register_params(params);
+        self.bias.register_params(params);
+    }
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        convolution_shape(
+            "GroupedConv1d",
+            self.weight.data().shape(),
+            self.groups,
+            &[self.padding],
+            &[self.stride],
+            &[self.dilation],
+        )
+    }
+}
+
+/// Applies a **spatial convolution** over an input signal composed of several input planes.
+///
+/// See also [`GroupedConv2d`].
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Conv2d<Pad: PaddingMode> {
+    pub padding: (usize, usize),
+    pub padding_mode: Pad,
+    pub stride: (usize, usize),
+    pub dilation: (usize, usize),
+    pub weight: Learnable<Ix4>,
+    pub bias: Learnable<Ix3>,
+}
+
+impl<Pad: PaddingMode> Conv2d<Pad> {
+    /// Creates a new Conv2d.
+    ///
+    /// # Arguments
+    ///
+    /// * `in_channels` - number of planes in the input signal.
+    ///
+    /// * `out_channels` - number of planes in the output signal.
+    ///
+    /// * `kernel_size` - size of the kernel, a 2-tuple for this two-dimensional case.
+    ///
+    /// * `padding` - padding to be applied to the input, a 2-tuple for this two-dimensional case.
+    ///
+    /// * `padding_mode` - padding mode, it can be: [`Zero`], [`Constant`], [`Reflective`] or
+    /// [`Replicative`].
+    ///
+    /// * `stride` - stride of the convolution, a 2-tuple for this two-dimensional case.
+    ///
+    /// * `dilation` - controls the spacing between the kernel points, a 2-tuple for this
+    /// two-dimensional case.
+    ///
+    /// The weight and the bias are initialized from *U(-k, k)* where
+    /// `k = (1. /(in_channels * kernel_w * kernel_h) as f32).sqrt()`.
+    pub fn new(
+        in_channels: usize,
+        out_channels: usize,
+        kernel_size: (usize, usize),
+        padding: (usize, usize),
+        padding_mode: Pad,
+        stride: (usize, usize),
+        dilation: (usize, usize),
+    ) -> Self {
+        let (kernel_h, kernel_w) = kernel_size;
+        let weight = Input::new(Tensor::zeros((
+            out_channels,
+            in_channels,
+            kernel_h,
+            kernel_w,
+        )))
+        .requires_grad();
+        let bias = Input::new(Tensor::zeros((out_channels, 1, 1))).requires_grad();
+
+        let k = (1. / (in_channels * kernel_h * kernel_w) as f32).sqrt();
+        init::uniform(&weight, -k, k);
+        init::uniform(&bias, -k, k);
+
+        Self {
+            padding,
+            padding_mode,
+            stride,
+            dilation,
+            weight,
+            bias,
+        }
+    }
+
+    /// Computes a 2-dimensional convolution *(cross correlation)*.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - the signal to convolve.
+    ///
+    /// The **input** must be of shape *(N, Cin, H, W)*
+    /// * **N** is the batch size
+    /// * **Cin** is the number of input channels
+    /// * **H** is the **height** of the input
+    /// * **W** is the **width** of the input
+    ///
+    /// The **kernel** must be of shape *(Cout, Cin, Hk, Wk)*
+    /// * **Cout** is the number of output channels
+    /// * **Cin** is the number of input channels
+    /// * **Hk** is the **height** of the kernel
+    /// * **Wk** is the **width** of the kernel
+    ///
+    /// The resulting output shape will be *(N, Cout, Hout, Wout)*
+    pub fn forward<I, T, U>(
+        &self,
+        input: I,
+    ) -> VarDiff<impl Data<Dim = Ix4>, impl Gradient<Dim = Ix4>>
+    where
+        I: Convolve<I, Learnable<Ix4>, Pad>,
+        I::Output: Into<VarDiff<T, U>>,
+        T: Data<Dim = Ix4> + 'static,
+        U: Gradient<Dim = Ix4> + Overwrite + 'static,
+    {
+        let (stride_h, stride_w) = self.stride;
+        let (padding_h, padding_w) = self.padding;
+        let (dilation_h, dilation_w) = self.dilation;
+
+        I::convolve(
+            input,
+            self.weight.clone(),
+            &[stride_h, stride_w],
+            &[dilation_h, dilation_w],
+            &[padding_h, padding_w],
+            self.padding_mode,
+        )
+        .into()
+            + self.bias.clone()
+    }
+}
+
+impl<Pad: PaddingMode> Register for Conv2d<Pad> {
+    /// Registers the weight and the bias of this `Conv2d` instance.
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.weight.register_params(params);
+        self.bias.register_params(params);
+    }
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        convolution_shape(
+            "Conv2d",
+            self.weight.data().shape(),
+            1,
+            &[self.padding.0, self.padding.1],
+            &[self.stride.0, self.stride.1],
+            &[self.dilation.0, self.dilation.1],
+        )
+    }
+}
+
+/// Applies a **spatial grouped convolution** over an input signal composed of several input planes.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct GroupedConv2d<Pad: PaddingMode> {
+    pub padding: (usize, usize),
+    pub padding_mode: Pad,
+    pub stride: (usize, usize),
+    pub dilation: (usize, usize),
+    pub groups: usize,
+    pub weight: Learnable<Ix4>,
+    pub bias: Learnable<Ix3>,
+}
+
+impl<Pad: PaddingMode> GroupedConv2d<Pad> {
+    /// Creates a new GroupedConv2d.
+    ///
+    /// # Arguments
+    ///
+    /// * `in_channels` - number of planes in the input signal.
+    ///
+    /// * `out_channels` - number of planes in the output signal.
+    ///
+    /// * `kernel_size` - size of the kernel, a 2-tuple  for this two-dimensional case.
+    ///
+    /// * `padding` - padding to be applied to the input, a 2-tuple  for this two-dimensional case.
+    ///
+    /// * `padding_mode` - padding mode, it can be: [`Zero`], [`Constant`], [`Reflective`] or
+    /// [`Replicative`].
+    ///
+    /// * `stride` - stride of the convolution, a 2-tuple  for this two-dimensional case.
+    ///
+    /// * `dilation` - controls the spacing between the kernel points, a 2-tuple  for this
+    /// two-dimensional case.
+    ///
+    /// * `groups` -  controls the connections between inputs and outputs. `in_channels` and
+    /// `out_channels` must both be divisible by groups.
+    ///
+    /// For example:
+    /// * at `groups = 1`, all inputs are convolved to all outputs.
+    /// *  at `groups = 2`, the operation becomes equivalent to having two convolutional layers
+    /// side by side, each seeing half the input channels and producing half the output channels,
+    /// and both subsequently concatenated.
+    /// * at `groups = in_channels`, each input channel is convolved with its own set of filters.
+    ///
+    /// The weight and the bias of the layer are initialized from *U(-k, k)* where
+    /// `k = (groups /(in_channels * kernel_h * kernel_w) as f32).sqrt()`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        in_channels: usize,
+        out_channels: usize,
+        kernel_size: (usize, usize),
+        padding: (usize, usize),
+        padding_mode: Pad,
+        stride: (usize, usize),
+        dilation: (usize, usize),
+        groups: usize,
+    ) -> Self {
+        let (kernel_h, kernel_w) = kernel_size;
+        let weight = Input::new(Tensor::zeros((
+            out_channels,
+            in_channels,
+            kernel_h,
+            kernel_w,
+        )))
+        .requires_grad();
+        let bias = Input::new(Tensor::zeros((out_channels, 1, 1))).requires_grad();
+
+        let k = (groups as f32 / (in_channels * kernel_h * kernel_w) as f32).sqrt();
+        init::uniform(&weight, -k, k);
+        init::uniform(&bias, -k, k);
+
+        Self {
+            padding,
+            padding_mode,
+            stride,
+            dilation,
+            groups,
+            weight,
+            bias,
+        }
+    }
+
+    /// Computes a 2-dimensional grouped convolution *(cross correlation)*.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - the signal to convolve.
+    ///
+    /// The **input** must be of shape *(N, Cin, H, W)*
+    /// * **N** is the batch size
+    /// * **Cin** is the number of input channels
+    /// * **H** is the **height** of the input
+    /// * **W** is the **width** of the input
+    ///
+    /// The **kernel** must be of shape *(Cout, Cin, Hk, Wk)*
+    /// * **Cout** is the number of output channels
+    /// * **Cin** is the number of input channels
+    /// * **Hk** is the **height** of the kernel
+    /// * **Wk** is the **width** of the kernel
+    ///
+    /// The resulting output shape will be *(N, Cout, Hout, Wout)*
+    pub fn forward<I, T, U>(
+        &self,
+        input: I,
+    ) -> VarDiff<impl Data<Dim = Ix4>, impl Gradient<Dim = Ix4>>
+    where
+        I: ConvolveWithGroups<I, Learnable<Ix4>, Pad>,
+        I::Output: Into<VarDiff<T, U>>,
+        T: Data<Dim = Ix4> + 'static,
+        U: Gradient<Dim = Ix4> + 'static,
+    {
+        let (stride_h, stride_w) = self.stride;
+        let (padding_h, padding_w) = self.padding;
+        let (dilation_h, dilation_w) = self.dilation;
+
+        I::convolve_with_groups(
+            input,
+            self.weight.clone(),
+            &[stride_h, stride_w],
+            &[dilation_h, dilation_w],
+            &[padding_h, padding_w],
+            self.padding_mode,
+            self.groups,
+        )
+        .into()
+            + self.bias.clone()
+    }
+}
+
+impl<Pad: PaddingMode> Register for GroupedConv2d<Pad> {
+    /// Registers the weight and the bias of this `GroupedConv2d` instance.
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.weight.register_params(params);
+        self.bias.register_params(params);
+    }
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        convolution_shape(
+            "GroupedConv2d",
+            self.weight.data().shape(),
+            self.groups,
+            &[self.padding.0, self.padding.1],
+            &[self.stride.0, self.stride.1],
+            &[self.dilation.0, self.dilation.1],
+        )
+    }
+}
+
+/// Applies a **volumetric convolution** over an input signal composed of several input planes.
+///
+/// See also [`GroupedConv3d`].
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Conv3d<Pad: PaddingMode> {
+    pub padding: (usize, usize, usize),
+    pub padding_mode: Pad,
+    pub stride: (usize, usize, usize),
+    pub dilation: (usize, usize, usize),
+    pub weight: Learnable<Ix5>,
+    pub bias: Learnable<Ix4>,
+}
+
+impl<Pad: PaddingMode> Conv3d<Pad> {
+    /// Creates a new Conv3d.
+    ///
+    /// # Arguments
+    ///
+    /// * `in_channels` - number of planes in the input signal.
+    ///
+    /// * `out_channels` - number of planes in the output signal.
+    ///
+    /// * `kernel_size` - size of the kernel, a 3-tuple for this three-dimensional case.
+    ///
+    /// * `padding` - padding to be applied to the input, a 3-tuple for this three-dimensional case.
+    ///
+    /// * `padding_mode` - padding mode, it can be: [`Zero`], [`Constant`], [`Reflective`] or
+    /// [`Replicative`].
+    ///
+    /// * `stride` - stride of the convolution, a 3-tuple for this three-dimensional case.
+    ///
+    /// * `dilation` - controls the spacing between the kernel points, a 3-tuple for this
+    /// three-dimensional case.
+    ///
+    /// The weight and the bias of the layer are initialized from *U(-k, k)* where
+    /// `k = (1. /(in_channels * kernel_d * kernel_w * kernel_h) as f32).sqrt()`.
+    pub fn new(
+        in_channels: usize,
+        out_channels: usize,
+        kernel_size: (usize, usize, usize),
+        padding: (usize, usize, usize),
+        padding_mode: Pad,
+        stride: (usize, usize, usize),
+        dilation: (usize, usize, usize),
+    ) -> Self {
+        let (kernel_d, kernel_h, kernel_w) = kernel_size;
+        let weight = Input::new(Tensor::zeros((
+            out_channels,
+            in_channels,
+            kernel_d,
+            kernel_h,
+            kernel_w,
+        )))
+        .requires_grad();
+        let bias = Input::new(Tensor::zeros((out_channels, 1, 1, 1))).requires_grad();
+
+        let k = (1. / (in_channels * kernel_d * kernel_h * kernel_w) as f32).sqrt();
+        init::uniform(&weight, -k, k);
+        init::uniform(&bias, -k, k);
+
+        Self {
+            padding,
+            padding_mode,
+            stride,
+            dilation,
+            weight,
+            bias,
+        }
+    }
+
+    /// Computes a 3-dimensional convolution *(cross correlation)*.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - signal to convolve.
+    ///
+    /// The **input** must be of shape *(N, Cin, D, H, W)*
+    /// * **N** is the batch size
+    /// * **Cin** is the number of input channels
+    /// * **D** is the **depth** of the input
+    /// * **H** is the **height** of the input
+    /// * **W** is the **width** of the input
+    ///
+    /// The **kernel** must be of shape *(Cout, Cin, Dk,  Hk, Wk)*
+    /// * **Cout** is the number of output channels
+    /// * **Cin** is the number of input channels
+    /// * **Dk** is the **depth** of the kernel
+    /// * **Hk** is the **height** of the kernel
+    /// * **Wk** is the **width** of the kernel
+    ///
+    /// The resulting output shape will be *(N, Cout, Dout, Hout, Wout)*
+    pub fn forward<I, T, U>(
+        &self,
+        input: I,
+    ) -> VarDiff<impl Data<Dim = Ix5>, impl Gradient<Dim = Ix5>>
+    where
+        I: Convolve<I, Learnable<Ix5>, Pad>,
+        I::Output: Into<VarDiff<T, U>>,
+        T: Data<Dim = Ix5> + 'static,
+        U: Gradient<Dim = Ix5> + 'static,
+    {
+        let (stride_d, stride_h, stride_w) = self.stride;
+        let (padding_d, padding_h, padding_w) = self.padding;
+        let (dilation_d, dilation_h, dilation_w) = self.dilation;
+
+        I::convolve(
+            input,
+            self.weight.clone(),
+            &[stride_d, stride_h, stride_w],
+            &[dilation_d, dilation_h, dilation_w],
+            &[padding_d, padding_h, padding_w],
+            self.padding_mode,
+        )
+        .into()
+            + self.bias.clone()
+    }
+}
+
+impl<Pad: PaddingMode> Register for Conv3d<Pad> {
+    /// Registers the weight and the bias of this `Conv3d` instance.
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.weight.register_params(params);
+        self.bias.register_params(params);
+    }
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        convolution_shape(
+            "Conv3d",
+            self.weight.data().shape(),
+            1,
+            &[self.padding.0, self.padding.1, self.padding.2],
+            &[self.stride.0, self.stride.1, self.stride.2],
+            &[self.dilation.0, self.dilation.1, self.dilation.2],
+        )
+    }
+}
+
+/// Applies a **grouped volumetric convolution** over an input signal composed of several input
+/// planes.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct GroupedConv3d<Pad: PaddingMode> {
+    pub padding: (usize, usize, usize),
+    pub padding_mode: Pad,
+    pub stride: (usize, usize, usize),
+    pub dilation: (usize, usize, usize),
+    pub groups: usize,
+    pub weight: Learnable<Ix5>,
+    pub bias: Learnable<Ix4>,
+}
+
+impl<Pad: PaddingMode> GroupedConv3d<Pad> {
+    /// Creates a new GroupedConv3d.
+    ///
+    /// # Arguments
+    ///
+    /// * `in_channels` - number of planes in the input signal.
+    ///
+    /// * `out_channels` - number of planes in the output signal.
+    ///
+    /// * `kernel_size` - size of the kernel, a 3-tuple  for this three-dimensional case.
+    ///
+    /// * `padding` - padding to be applied to the input, a 3-tuple  for this three-dimensional case.
+    ///
+    /// * `padding_mode` - padding mode, it can be: [`Zero`], [`Constant`], [`Reflective`] or
+    /// [`Replicative`].
+    ///
+    /// * `stride` - stride of the convolution, a 3-tuple  for this three-dimensional case.
+    ///
+    /// * `dilation` - controls the spacing between the kernel points, a 3-tuple  for this
+    /// three-dimensional case.
+    ///
+    /// * `groups` -  controls the connections between inputs and outputs. `in_channels` and
+    /// `out_channels` must both be divisible by groups.
+    ///
+    /// For example:
+    /// * at `groups = 1`, all inputs are convolved to all outputs.
+    /// *  at `groups = 2`, the operation becomes equivalent to having two convolutional layers
+    /// side by side, each seeing half the input channels and producing half the output channels,
+    /// and both subsequently concatenated.
+    /// * at `groups = in_channels`, each input channel is convolved with its own set of filters.
+    ///
+    /// The weight and the bias are initialized from *U(-k, k)* where
+    /// `k = (groups /(in_channels * kernel_d * kernel_h * kernel_w) as f32).sqrt()`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        in_channels: usize,
+        out_channels: usize,
+        kernel_size: (usize, usize, usize),
+        padding: (usize, usize, usize),
+        padding_mode: Pad,
+        stride: (usize, usize, usize),
+        dilation: (usize, usize, usize),
+        groups: usize,
+    ) -> Self {
+        let (kernel_d, kernel_h, kernel_w) = kernel_size;
+        let weight = Input::new(Tensor::zeros((
+            out_channels,
+            in_channels,
+            kernel_d,
+            kernel_h,
+            kernel_w,
+        )))
+        .requires_grad();
+        let bias = Input::new(Tensor::zeros((out_channels, 1, 1, 1))).requires_grad();
+
+        let k = (1. / (in_channels * kernel_d * kernel_h * kernel_w) as f32).sqrt();
+        init::uniform(&weight, -k, k);
+        init::uniform(&bias, -k, k);
+
+        Self {
+            padding,
+            padding_mode,
+            stride,
+            dilation,
+            groups,
+            weight,
+            bias,
+        }
+    }
+
+    /// Computes a 3-dimensional grouped convolution *(cross correlation)*.
+    ///
+    /// `input` - the signal to convolve.
+    ///
+    /// The **input** must be of shape *(N, Cin, D, H, W)*
+    /// * **N** is the batch size
+    /// * **Cin** is the number of input channels
+    /// * **D** is the **depth** of the input
+    /// * **H** is the **height** of the input
+    /// * **W** is the **width** of the input
+    ///
+    /// The **kernel** must be of shape *(Cout, Cin, Dk,  Hk, Wk)*
+    /// * **Cout** is the number of output channels
+    /// * **Cin** is the number of input channels
+    /// * **Dk** is the **depth** of the kernel
+    /// * **Hk** is the **height** of the kernel
+    /// * **Wk** is the **width** of the kernel
+    ///
+    /// The resulting output shape will be *(N, Cout, Dout, Hout, Wout)*
+    pub fn forward<I, T, U>(
+        &self,
+        input: I,
+    ) -> VarDiff<impl Data<Dim = Ix5>, impl Gradient<Dim = Ix5>>
+    where
+        I: ConvolveWithGroups<I, Learnable<Ix5>, Pad>,
+        I::Output: Into<VarDiff<T, U>>,
+        T: Data<Dim = Ix5> + 'static,
+        U: Gradient<Dim = Ix5> + 'static,
+    {
+        let (stride_d, stride_h, stride_w) = self.stride;
+        let (padding_d, padding_h, padding_w) = self.padding;
+        let (dilation_d, dilation_h, dilation_w) = self.dilation;
+
+        I::convolve_with_groups(
+            input,
+            self.weight.clone(),
+            &[stride_d, stride_h, stride_w],
+            &[dilation_d, dilation_h, dilation_w],
+            &[padding_d, padding_h, padding_w],
+            self.padding_mode,
+            self.groups,
+        )
+        .into()
+            + self.bias.clone()
+    }
+}
+
+impl<Pad: PaddingMode> Register for GroupedConv3d<Pad> {
+    /// Registers the weight and the bias of this `GroupedConv3d` instance.
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.weight.register_params(params);
+        self.bias.register_params(params);
+    }
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        convolution_shape(
+            "GroupedConv3d",
+            self.weight.data().shape(),
+            self.groups,
+            &[self.padding.0, self.padding.1, self.padding.2],
+            &[self.stride.0, self.stride.1, self.stride.2],
+            &[self.dilation.0, self.dilation.1, self.dilation.2],
+        )
+    }
+}
+
+/// Pooling input.
+///
+/// This trait is implemented by `Var` and `VarDiff`.
+pub trait PoolInput {
+    type MaxOutput;
+    type AvgOutput;
+
+    fn max_pool2d(
+        self,
+        kernel_size: (usize, usize),
+        stride: (usize, usize),
+        padding: (usize, usize),
+    ) -> Self::MaxOutput;
+
+    fn avg_pool2d(
+        self,
+        kernel_size: (usize, usize),
+        stride: (usize, usize),
+        padding: (usize, usize),
+    ) -> Self::AvgOutput;
+
+    /// Returns the height and the width of the input.
+    fn spatial_size(&self) -> (usize, usize);
+}
+
+impl<T: ?Sized, U: ?Sized> PoolInput for VarDiff<T, U>
+where
+    T: Data<Dim = Ix4> + 'static,
+    U: Gradient<Dim = Ix4> + 'static,
+{
+    type MaxOutput = VarDiff<MaxPoolNode<T>, MaxPoolBackwardNode<U, T>>;
+    type AvgOutput = VarDiff<AvgPoolNode<T>, AvgPoolBackwardNode<U>>;
+
+    fn max_pool2d(
+        self,
+        kernel_size: (usize, usize),
+        stride: (usize, usize),
+        padding: (usize, usize),
+    ) -> Self::MaxOutput {
+        VarDiff::max_pool2d(self, kernel_size, stride, padding)
+    }
+
+    fn avg_pool2d(
+        self,
+        kernel_size: (usize, usize),
+        stride: (usize, usize),
+        padding: (usize, usize),
+    ) -> Self::AvgOutput {
+        VarDiff::avg_pool2d(self, kernel_size, stride, padding)
+    }
+
+    fn spatial_size(&self) -> (usize, usize) {
+        let data = self.data();
+        (data.shape()[2], data.shape()[3])
+    }
+}
+
+impl<T: ?Sized> PoolInput for Var<T>
+where
+    T: Data<Dim = Ix4> + 'static,
+{
+    type MaxOutput = Var<MaxPoolNode<T>>;
+    type AvgOutput = Var<AvgPoolNode<T>>;
+
+    fn max_pool2d(
+        self,
+        kernel_size: (usize, usize),
+        stride: (usize, usize),
+        padding: (usize, usize),
+    ) -> Self::MaxOutput {
+        Var::max_pool2d(self, kernel_size, stride, padding)
+    }
+
+    fn avg_pool2d(
+        self,
+        kernel_size: (usize, usize),
+        stride: (usize, usize),
+        padding: (usize, usize),
+    ) -> Self::AvgOutput {
+        Var::avg_pool2d(self, kernel_size, stride, padding)
+    }
+
+    fn spatial_size(&self) -> (usize, usize) {
+        let data = self.data();
+        (data.shape()[2], data.shape()[3])
+    }
+}
+
+/// Applies a **spatial max pooling** over an input signal composed of several input planes.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct MaxPool2d {
+    pub kernel_size: (usize, usize),
+    pub stride: (usize, usize),
+    pub padding: (usize, usize),
+}
+
+impl MaxPool2d {
+    /// Creates a new MaxPool2d.
+    ///
+    /// # Arguments
+    ///
+    /// * `kernel_size` - size of the pooling window, a 2-tuple for this two-dimensional case.
+    ///
+    /// * `stride` - stride of the pooling window, a 2-tuple for this two-dimensional case.
+    ///
+    /// * `padding` - implicit negative infinity padding to be added on both sides, a 2-tuple for
+    /// this two-dimensional case. It must be at most half of the kernel size.
+    pub fn new(
+        kernel_size: (usize, usize),
+        stride: (usize, usize),
+        padding: (usize, usize),
+    ) -> Self {
+        Self {
+            kernel_size,
+            stride,
+            padding,
+        }
+    }
+
+    /// Computes the maximum of each window of the input.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - variable of shape *(N, C, H, W)*.
+    pub fn forward<I: PoolInput>(&self, input: I) -> I::MaxOutput {
+        input.max_pool2d(self.kernel_size, self.stride, self.padding)
+    }
+}
+
+impl Register for MaxPool2d {
+    fn register_params(&self, _: &mut Vec<RawParam>) {}
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        pooling_shape("MaxPool2d", self.kernel_size, self.stride, self.padding)
+    }
+}
+
+/// Applies a **spatial average pooling** over an input signal composed of several input planes.
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct AvgPool2d {
+    pub kernel_size: (usize, usize),
+    pub stride: (usize, usize),
+    pub padding: (usize, usize),
+}
+
+impl AvgPool2d {
+    /// Creates a new AvgPool2d.
+    ///
+    /// # Arguments
+    ///
+    /// * `kernel_size` - size of the pooling window, a 2-tuple for this two-dimensional case.
+    ///
+    /// * `stride` - stride of the pooling window, a 2-tuple for this two-dimensional case.
+    ///
+    /// * `padding` - implicit zero padding to be added on both sides, a 2-tuple for this
+    /// two-dimensional case. It must be at most half of the kernel size, the padded entries are
+    /// included in the averages.
+    pub fn new(
+        kernel_size: (usize, usize),
+        stride: (usize, usize),
+        padding: (usize, usize),
+    ) -> Self {
+        Self {
+            kernel_size,
+            stride,
+            padding,
+        }
+    }
+
+    /// Computes the average of each window of the input.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - variable of shape *(N, C, H, W)*.
+    pub fn forward<I: PoolInput>(&self, input: I) -> I::AvgOutput {
+        input.avg_pool2d(self.kernel_size, self.stride, self.padding)
+    }
+}
+
+impl Register for AvgPool2d {
+    fn register_params(&self, _: &mut Vec<RawParam>) {}
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        pooling_shape("AvgPool2d", self.kernel_size, self.stride, self.padding)
+    }
+}
+
+/// Averages each plane of an input signal composed of several input planes.
+///
+/// The output has shape *(N, C, 1, 1)* whatever the height and the width of the input.
+#[derive(Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct GlobalAvgPool2d;
+
+impl GlobalAvgPool2d {
+    /// Creates a new GlobalAvgPool2d.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Computes the average of each plane of the input.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - variable of shape *(N, C, H, W)*.
+    pub fn forward<I: PoolInput>(&self, input: I) -> I::AvgOutput {
+        let size = input.spatial_size();
+        input.avg_pool2d(size, size, (0, 0))
+    }
+}
+
+impl Register for GlobalAvgPool2d {
+    fn register_params(&self, _: &mut Vec<RawParam>) {}
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        Rc::new(|input_shape| {
+            assert_eq!(
+                input_shape.len(),
+                4,
+                "error: GlobalAvgPool2d expects an input of shape (N, C, H, W), got {:?}.",
+                input_shape
+            );
+            vec![input_shape[0], input_shape[1], 1, 1]
+        })
+    }
+}
+
+/// A **squeeze-and-excitation** block.
+///
+/// ```text
+/// s = σ(ReLU(GAP(x)A₁ᵀ + b₁)A₂ᵀ + b₂)
+///
+/// ʏ = s ⊙ x
+/// ```
+///
+/// Each feature map is summarized by its global average, the averages are mapped to a weight per
+/// channel by two linear layers, and the channels of the input are rescaled by such weights, as
+/// described in [Squeeze-and-Excitation Networks](https://arxiv.org/abs/1709.01507).
+///
+/// ```
+/// use neuronika::nn::SqueezeExcitation;
+///
+/// let se = SqueezeExcitation::new(8, 4);
+/// let y = se.forward(neuronika::rand((2, 8, 5, 5)).requires_grad());
+/// y.forward();
+///
+/// assert_eq!(y.data().shape(), &[2, 8, 5, 5]);
+/// ```
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct SqueezeExcitation {
+    pub squeeze: Linear,
+    pub excitation: Linear,
+}
+
+impl SqueezeExcitation {
+    /// Creates a new SqueezeExcitation.
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - number of channels of the input.
+    ///
+    /// * `reduction` - ratio between the number of channels and the number of hidden features of
+    /// the block, which are at least one.
+    ///
+    /// # Panics
+    ///
+    /// If `reduction` is zero.
+    pub fn new(channels: usize, reduction: usize) -> Self {
+        assert!(reduction > 0, "error: the reduction must be positive.");
+        let hidden = (channels / reduction).max(1);
+
+        Self {
+            squeeze: Linear::new(channels, hidden),
+            excitation: Linear::new(hidden, channels),
+        }
+    }
+
+    /// Rescales the channels of the input.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - variable of shape *(N, C, H, W)*, the output's shape will be the same.
+    pub fn forward<T: ?Sized, U: ?Sized>(
+        &self,
+        input: VarDiff<T, U>,
+    ) -> VarDiff<impl Data<Dim = Ix4>, impl Gradient<Dim = Ix4>>
+    where
+        T: Data<Dim = Ix4> + 'static,
+        U: Gradient<Dim = Ix4> + 'static,
+    {
+        let averages = GlobalAvgPool2d.forward(input.clone()).flatten();
+        let weights = self
+            .excitation
+            .forward(self.squeeze.forward(averages).relu())
+            .sigmoid();
+
+        input * weights.unsqueeze(2).unsqueeze(3)
+    }
+}
+
+impl Register for SqueezeExcitation {
+    /// Registers the weights and the biases of the linear layers of this `SqueezeExcitation`
+    /// instance.
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.squeeze.register_params(params);
+        self.excitation.register_params(params);
+    }
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        let channels = self.squeeze.weight.data().ncols();
+        Rc::new(move |input_shape| {
+            assert!(
+                input_shape.len() == 4 && input_shape[1] == channels,
+                "error: SqueezeExcitation expects an input of shape (N, {}, H, W), got {:?}.",
+                channels,
+                input_shape
+            );
+            input_shape.to_vec()
+        })
+    }
+}
+
+/// A **residual** block.
+///
+/// ```text
+/// ʏ = ReLU(F(x) + S(x))
+/// ```
+///
+/// The residual branch *F* is a stack of convolutions, each followed by a batch normalization,
+/// with rectified linear units in between, as described in
+/// [Deep Residual Learning for Image Recognition](https://arxiv.org/abs/1512.03385). The shortcut
+/// *S* is the identity when the input and the output have the same shape, otherwise it is a
+/// *1 x 1* convolution followed by a batch normalization that projects the input to the shape of
+/// the output.
+///
+/// ```
+/// use neuronika::nn::ResidualBlock;
+///
+/// let block = ResidualBlock::basic(4, 8, 2);
+/// assert!(block.shortcut.is_some());
+///
+/// let y = block.forward(neuronika::rand((2, 4, 6, 6)).requires_grad());
+/// y.forward();
+///
+/// assert_eq!(y.data().shape(), &[2, 8, 3, 3]);
+/// ```
+pub struct ResidualBlock {
+    pub convs: Vec<Conv2d<Zero>>,
+    pub norms: Vec<BatchNorm2d>,
+    pub shortcut: Option<(Conv2d<Zero>, BatchNorm2d)>,
+}
+
+impl ResidualBlock {
+    /// Creates a new basic ResidualBlock, made of two *3 x 3* convolutions.
+    ///
+    /// # Arguments
+    ///
+    /// * `in_channels` - number of channels of the input.
+    ///
+    /// * `out_channels` - number of channels of the output.
+    ///
+    /// * `stride` - stride of the first convolution and of the shortcut.
+    pub fn basic(in_channels: usize, out_channels: usize, stride: usize) -> Self {
+        Self::new(
+            in_channels,
+            out_channels,
+            stride,
+            vec![
+                (in_channels, out_channels, 3, stride),
+                (out_channels, out_channels, 3, 1),
+            ],
+        )
+    }
+
+    /// Creates a new bottleneck ResidualBlock, made of a *1 x 1* convolution reducing the channels
+    /// to `width`, a *3 x 3* convolution and a *1 x 1* convolution expanding them to
+    /// `out_channels`. ResNet-50 and deeper networks use `out_channels = 4 * width`.
+    ///
+    /// # Arguments
+    ///
+    /// * `in_channels` - number of channels of the input.
+    ///
+    /// * `width` - number of channels of the inner convolutions.
+    ///
+    /// * `out_channels` - number of channels of the output.
+    ///
+    /// * `stride` - stride of the *3 x 3* convolution and of the shortcut.
+    pub fn bottleneck(
+        in_channels: usize,
+        width: usize,
+        out_channels: usize,
+        stride: usize,
+    ) -> Self {
+        Self::new(
+            in_channels,
+            out_channels,
+            stride,
+            vec![
+                (in_channels, width, 1, 1),
+                (width, width, 3, stride),
+                (width, out_channels, 1, 1),
+            ],
+        )
+    }
+
+    /// Creates the block from the input channels, the output channels, the kernel size and the
+    /// stride of each convolution of the residual branch.
+    fn new(
+        in_channels: usize,
+        out_channels: usize,
+        stride: usize,
+        layers: Vec<(usize, usize, usize, usize)>,
+    ) -> Self {
+        assert!(stride > 0, "error: the stride must be positive.");
+
+        let conv = |in_channels, out_channels, kernel_size: usize, stride| {
+            Conv2d::new(
+                in_channels,
+                out_channels,
+                (kernel_size, kernel_size),
+                (kernel_size / 2, kernel_size / 2),
+                Zero,
+                (stride, stride),
+                (1, 1),
+            )
+        };
+        let (convs, norms) = layers
+            .into_iter()
+            .map(|(in_channels, out_channels, kernel_size, stride)| {
+                (
+                    conv(in_channels, out_channels, kernel_size, stride),
+                    BatchNorm2d::new(out_channels, 0.1, 1e-5),
+                )
+            })
+            .unzip();
+        let shortcut = (stride != 1 || in_channels != out_channels).then(|| {
+            (
+                conv(in_channels, out_channels, 1, stride),
+                BatchNorm2d::new(out_channels, 0.1, 1e-5),
+            )
+        });
+
+        Self {
+            convs,
+            norms,
+            shortcut,
+        }
+    }
+
+    /// Computes a forward pass.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - variable of shape *(N, C, H, W)*.
+    pub fn forward<T, U>(
+        &self,
+        input: VarDiff<T, U>,
+    ) -> VarDiff<impl Data<Dim = Ix4>, impl Gradient<Dim = Ix4>>
+    where
+        T: Data<Dim = Ix4> + 'static,
+        U: Gradient<Dim = Ix4> + 'static,
+    {
+        let input = input.into_dyn();
+
+        let last = self.convs.len() - 1;
+        let mut out = input.clone();
+        for (i, (conv, norm)) in self.convs.iter().zip(&self.norms).enumerate() {
+            out = norm.forward(conv.forward(out)).into_dyn();
+            if i != last {
+                out = out.relu().into_dyn();
+            }
+        }
+
+        let identity = match &self.shortcut {
+            Some((conv, norm)) => norm.forward(conv.forward(input)).into_dyn(),
+            None => input,
+        };
+        (out + identity).relu()
+    }
+}
+
+impl Register for ResidualBlock {
+    /// Registers the parameters of the convolutions and of the batch normalizations of this
+    /// `ResidualBlock` instance.
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        for (conv, norm) in self
+            .convs
+            .iter()
+            .zip(&self.norms)
+            .chain(self.shortcut.as_ref().map(|(conv, norm)| (conv, norm)))
+        {
+            conv.register_params(params);
+            norm.register_params(params);
+        }
+    }
+
+    fn register_status(&mut self, status: Rc<Cell<bool>>) {
+        self.norms
+            .iter_mut()
+            .chain(self.shortcut.as_mut().map(|(_, norm)| norm))
+            .for_each(|norm| norm.register_status(status.clone()));
+    }
+
+    fn shape_inference(&self) -> ShapeInference {
+        let shapes: Vec<_> = self
+            .convs
+            .iter()
+            .zip(&self.norms)
+            .flat_map(|(conv, norm)| [conv.shape_inference(), norm.shape_inference()])
+            .collect();
+
+        Rc::new(move |input_shape| {
+            shapes
+                .iter()
+                .fold(input_shape.to_vec(), |shape, inference| inference(&shape))
+        })
+    }
+
+    fn non_trainable(&self) -> usize {
+        self.norms
+            .iter()
+            .chain(self.shortcut.as_ref().map(|(_, norm)| norm))
+            .map(Register::non_trainable)
+            .sum()
+    }
+}
+
+/// A **temporal block**, the building block of a [`TCN`].
+///
+/// ```text
+/// ʏ = ReLU(F(x) + S(x))
+/// ```
+///
+/// The residual branch *F* is made of two dilated causal convolutions, each followed by a
+/// rectified linear unit and a dropout, as described in
+/// [An Empirical Evaluation of Generic Convolutional and Recurrent Networks for Sequence Modeling](https://arxiv.org/abs/1803.01271).
+/// A convolution is causal when each step of its output depends only on the same and on the
+/// previous steps of its input: the input is padded by *(kernel_size - 1) * dilation* steps and
+/// the trailing steps of the output are discarded, so that the length of the sequence is
+/// unchanged.
+///
+/// The kernels are reparametrized by
+/// [weight normalization](https://arxiv.org/abs/1602.07868), so that the kernel of each output
+/// channel is *g v / ‖v‖*, where the direction *v* is the weight of the corresponding
+/// convolution and the gain *g* is learned separately. The shortcut *S* is the identity when the
+/// input and the output have the same number of channels, otherwise it is a *1 x 1* convolution.
+///
+/// ```
+/// use neuronika::nn::TemporalBlock;
+///
+/// let block = TemporalBlock::new(3, 8, 3, 2, 0.1);
+/// assert!(block.downsample.is_some());
+///
+/// let y = block.forward(neuronika::rand((4, 3, 20)).requires_grad());
+/// y.forward();
+///
+/// assert_eq!(y.data().shape(), &[4, 8, 20]);
+/// ```
+pub struct TemporalBlock {
+    pub convs: Vec<Conv1d<Zero>>,
+    pub gains: Vec<Learnable<Ix1>>,
+    pub dropouts: Vec<Dropout>,
+    pub downsample: Option<Conv1d<Zero>>,
+}
+
+impl TemporalBlock {
+    /// Creates a new TemporalBlock.
+    ///
+    /// # Arguments
+    ///
+    /// * `in_channels` - number of channels of the input.
+    ///
+    /// * `out_channels` - number of channels of the output.
+    ///
+    /// * `kernel_size` - size of the kernels of the convolutions.
+    ///
+    /// * `dilation` - spacing between the kernel points of the convolutions.
+    ///
+    /// * `dropout` - probability of an element to be zeroed after each convolution.
+    ///
+    /// The gains are initialized to the norms of the directions, so that the kernels are
+    /// initially equal to the weights of the convolutions.
+    ///
+    /// # Panics
+    ///
+    /// If `kernel_size` or `dilation` is zero.
+    pub fn new(
+        in_channels: usize,
+        out_channels: usize,
+        kernel_size: usize,
+        dilation: usize,
+        dropout: f64,
+    ) -> Self {
+        assert!(kernel_size > 0, "error: the kernel size must be positive.");
+        assert!(dilation > 0, "error: the dilation must be positive.");
+
+        let padding = (kernel_size - 1) * dilation;
+        let convs: Vec<_> = [in_channels, out_channels]
+            .iter()
+            .map(|&in_channels| {
+                Conv1d::new(
+                    in_channels,
+                    out_channels,
+                    kernel_size,
+                    padding,
+                    Zero,
+                    1,
+                    dilation,
+                )
+            })
+            .collect();
+        let gains = convs
+            .iter()
+            .map(|conv| {
+                let norms = conv
+                    .weight
+                    .data()
+                    .outer_iter()
+                    .map(|kernel| kernel.iter().map(|el| el * el).sum::<f32>().sqrt())
+                    .collect();
+                Input::new(norms).requires_grad()
+            })
+            .collect();
+        let downsample = (in_channels != out_channels)
+            .then(|| Conv1d::new(in_channels, out_channels, 1, 0, Zero, 1, 1));
+
+        Self {
+            convs,
+            gains,
+            dropouts: vec![Dropout::new(dropout), Dropout::new(dropout)],
+            downsample,
+        }
+    }
+
+    /// Applies the `i`-th weight normalized causal convolution to `input`.
+    fn causal_conv<T: ?Sized, U: ?Sized>(
+        &self,
+        i: usize,
+        input: VarDiff<T, U>,
+    ) -> VarDiff<dyn Data<Dim = Ix3>, dyn Gradient<Dim = Ix3>>
+    where
+        T: Data<Dim = Ix3> + 'static,
+        U: Gradient<Dim = Ix3> + Overwrite + 'static,
+    {
+        let conv = &self.convs[i];
+        let direction = conv.weight.clone();
+        let scale =
+            self.gains[i].clone() / direction.clone().flatten().norm_axis::<Ix1>(2., 1, false);
+        let kernel = direction * scale.unsqueeze(1).unsqueeze(2);
+
+        let (batch_size, _, len) = input.data().dim();
+        let out_channels = conv.weight.data().len_of(ndarray::Axis(0));
+        let output = VarDiff::convolve(
+            input,
+            kernel,
+            &[conv.stride],
+            &[conv.dilation],
+            &[conv.padding],
+            Zero,
+        ) + conv.bias.clone();
+
+        // Discards the trailing steps, which depend on the padding after the input.
+        output
+            .chunks((batch_size, out_channels, len))
+            .swap_remove(0)
+            .into_dyn()
+    }
+
+    /// Computes a forward pass.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - variable of shape *(N, Cin, L)*, the output's shape will be *(N, Cout, L)*.
+    pub fn forward<T, U>(
+        &self,
+        input: VarDiff<T, U>,
+    ) -> VarDiff<impl Data<Dim = Ix3>, impl Gradient<Dim = Ix3>>
+    where
+        T: Data<Dim = Ix3> + 'static,
+        U: Gradient<Dim = Ix3> + Overwrite + 'static,
+    {
+        self.forward_dyn(input.into_dyn())
+    }
+
+    /// Computes a forward pass on a type erased input, so that blocks can be chained.
+    fn forward_dyn(
+        &self,
+        input: VarDiff<dyn Data<Dim = Ix3>, dyn Gradient<Dim = Ix3>>,
+    ) -> VarDiff<impl Data<Dim = Ix3>, impl Gradient<Dim = Ix3>> {
+        let mut out = input.clone();
+        for (i, dropout) in self.dropouts.iter().enumerate() {
+            out = dropout.forward(self.causal_conv(i, out).relu()).into_dyn();
+        }
+
+        let identity = match &self.downsample {
+            Some(conv) => conv.forward(input).into_dyn(),
+            None => input,
+        };
+        (out + identity).relu()
+    }
+}
+
+impl Register for TemporalBlock {
+    /// Registers the directions, the gains and the biases of the convolutions and the parameters
+    /// of the downsampling convolution of this `TemporalBlock` instance.
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        for (conv, gain) in self.convs.iter().zip(&self.gains) {
+            conv.register_params(params);
+            gain.register_params(params);
+        }
+        if let Some(conv) = &self.downsample {
+            conv.register_params(params);
+        }
+    }
+
+    fn register_status(&mut self, status: Rc<Cell<bool>>) {
+        self.dropouts
+            .iter_mut()
+            .for_each(|dropout| dropout.register_status(status.clone()));
+    }
+
+    fn shape_inference(&self) -> ShapeInference {
+        let (out_channels, in_channels) = {
+            let weight = self.convs[0].weight.data();
+            (
+                weight.len_of(ndarray::Axis(0)),
+                weight.len_of(ndarray::Axis(1)),
+            )
+        };
+        Rc::new(move |input_shape| {
+            assert!(
+                input_shape.len() == 3 && input_shape[1] == in_channels,
+                "error: TemporalBlock expects an input of shape (N, {}, L), got {:?}.",
+                in_channels,
+                input_shape
+            );
+            vec![input_shape[0], out_channels, input_shape[2]]
+        })
+    }
+
+    fn register_dropouts(&self, masks: &mut Vec<Rc<DropoutMask>>) {
+        self.dropouts
+            .iter()
+            .for_each(|dropout| dropout.register_dropouts(masks));
+    }
+}
+
+/// A **temporal convolutional network**, a stack of [`TemporalBlock`]s whose dilations double at
+/// each block.
+///
+/// As its convolutions are causal, the output at each step of the sequence only depends on the
+/// current and on the previous steps of the input, so that the network can replace a recurrent
+/// one for sequence modelling while processing all the steps in parallel. The number of steps
+/// seen by each output grows exponentially with the number of blocks, see
+/// [`.receptive_field()`](TCN::receptive_field()).
+///
+/// ```
+/// use neuronika::nn::TCN;
+///
+/// let tcn = TCN::new(2, &[16, 16, 8], 3, 0.);
+/// assert_eq!(tcn.receptive_field(), 29);
+///
+/// let y = tcn.forward(neuronika::rand((4, 2, 50)).requires_grad());
+/// y.forward();
+///
+/// assert_eq!(y.data().shape(), &[4, 8, 50]);
+/// ```
+#[allow(clippy::upper_case_acronyms)]
+pub struct TCN {
+    pub blocks: Vec<TemporalBlock>,
+}
+
+impl TCN {
+    /// Creates a new TCN.
+    ///
+    /// # Arguments
+    ///
+    /// * `in_channels` - number of channels of the input.
+    ///
+    /// * `channels` - number of output channels of each block, the dilation of the *i*-th block
+    /// is *2ⁱ*.
+    ///
+    /// * `kernel_size` - size of the kernels of the convolutions.
+    ///
+    /// * `dropout` - probability of an element to be zeroed after each convolution.
+    ///
+    /// # Panics
+    ///
+    /// If `channels` is empty or `kernel_size` is zero.
+    pub fn new(in_channels: usize, channels: &[usize], kernel_size: usize, dropout: f64) -> Self {
+        assert!(
+            !channels.is_empty(),
+            "error: a temporal convolutional network needs at least one block."
+        );
+
+        let blocks = std::iter::once(in_channels)
+            .chain(channels.iter().copied())
+            .zip(channels)
+            .enumerate()
+            .map(|(i, (in_channels, &out_channels))| {
+                TemporalBlock::new(in_channels, out_channels, kernel_size, 1 << i, dropout)
+            })
+            .collect();
+
+        Self { blocks }
+    }
+
+    /// Returns the number of steps of the input on which each step of the output depends.
+    pub fn receptive_field(&self) -> usize {
+        1 + self
+            .blocks
+            .iter()
+            .map(|block| 2 * block.convs[0].padding)
+            .sum::<usize>()
+    }
+
+    /// Computes a forward pass.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - variable of shape *(N, Cin, L)*, the output's shape will be *(N, Cout, L)* where
+    /// *Cout* is the number of output channels of the last block.
+    pub fn forward<T, U>(
+        &self,
+        input: VarDiff<T, U>,
+    ) -> VarDiff<impl Data<Dim = Ix3>, impl Gradient<Dim = Ix3>>
+    where
+        T: Data<Dim = Ix3> + 'static,
+        U: Gradient<Dim = Ix3> + Overwrite + 'static,
+    {
+        let (last, blocks) = self.blocks.split_last().unwrap();
+        let out = blocks.iter().fold(input.into_dyn(), |out, block| {
+            block.forward_dyn(out).into_dyn()
+        });
+
+        last.forward_dyn(out)
+    }
+}
+
+impl Register for TCN {
+    /// Registers the parameters of the blocks of this `TCN` instance.
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.blocks
+            .iter()
+            .for_each(|block| block.register_params(params));
+    }
+
+    fn register_status(&mut self, status: Rc<Cell<bool>>) {
+        self.blocks
+            .iter_mut()
+            .for_each(|block| block.register_status(status.clone()));
+    }
+
+    fn shape_inference(&self) -> ShapeInference {
+        let shapes: Vec<_> = self.blocks.iter().map(Register::shape_inference).collect();
+
+        Rc::new(move |input_shape| {
+            shapes
+                .iter()
+                .fold(input_shape.to_vec(), |shape, inference| inference(&shape))
+        })
+    }
+
+    fn register_dropouts(&self, masks: &mut Vec<Rc<DropoutMask>>) {
+        self.blocks
+            .iter()
+            .for_each(|block| block.register_dropouts(masks));
+    }
+}
+
+/// Flatten input.
+///
+/// This trait is implemented by `Var` and `VarDiff`.
+pub trait FlattenInput {
+    type Output;
+
+    fn flatten(self) -> Self::Output;
+}
+
+impl<T: ?Sized, U: ?Sized> FlattenInput for VarDiff<T, U>
+where
+    T: Data + 'static,
+    U: Gradient<Dim = T::Dim> + 'static,
+{
+    type Output = VarDiff<FlattenNode<T>, FlattenBackwardNode<U>>;
+
+    fn flatten(self) -> Self::Output {
+        VarDiff::flatten(self)
+    }
+}
+
+impl<T: ?Sized> FlattenInput for Var<T>
+where
+    T: Data + 'static,
+{
+    type Output = Var<FlattenNode<T>>;
+
+    fn flatten(self) -> Self::Output {
+        Var::flatten(self)
+    }
+}
+
+/// Flattens all the axes of the input but the first one, so that a batch of feature maps can be
+/// fed to a linear layer.
+#[derive(Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Flatten;
+
+impl Flatten {
+    /// Creates a new Flatten.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Flattens the input into a variable of shape *(N, features)*.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - variable of shape *(N, ...)*.
+    pub fn forward<I: FlattenInput>(&self, input: I) -> I::Output {
+        input.flatten()
+    }
+}
+
+impl Register for Flatten {
+    fn register_params(&self, _: &mut Vec<RawParam>) {}
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        Rc::new(|input_shape| vec![input_shape[0], input_shape[1..].iter().product()])
+    }
+}
+
+/// Gated linear unit input.
+///
+/// This trait is implemented by `Var` and `VarDiff`.
+pub trait GLUInput {
+    type Output;
+
+    fn glu(self, axis: usize) -> Self::Output;
+}
+
+impl<T: ?Sized, U: ?Sized> GLUInput for VarDiff<T, U>
+where
+    T: Data + 'static,
+    U: Gradient<Dim = T::Dim> + 'static,
+{
+    type Output = VarDiff<GLUNode<T>, GLUBackwardNode<U, T>>;
+
+    fn glu(self, axis: usize) -> Self::Output {
+        VarDiff::glu(self, axis)
+    }
+}
+
+impl<T: ?Sized> GLUInput for Var<T>
+where
+    T: Data + 'static,
+{
+    type Output = Var<GLUNode<T>>;
+
+    fn glu(self, axis: usize) -> Self::Output {
+        Var::glu(self, axis)
+    }
+}
+
+/// Applies the **gated linear unit** function.
+///
+/// ```text
+/// GLU(a, b) = a ⊙ σ(b)
+/// ```
+///
+/// The input is split in two halves *a* and *b* along `axis`, as in
+/// [Language Modeling with Gated Convolutional Networks](https://arxiv.org/abs/1612.08083).
+///
+/// ```
+/// use neuronika::nn::GLU;
+///
+/// let glu = GLU::new(1);
+/// let y = glu.forward(neuronika::rand((4, 6)));
+/// y.forward();
+///
+/// assert_eq!(y.data().shape(), &[4, 3]);
+/// ```
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct GLU {
+    pub axis: usize,
+}
+
+impl GLU {
+    /// Creates a new GLU splitting its input along `axis`.
+    pub fn new(axis: usize) -> Self {
+        Self { axis }
+    }
+
+    /// Applies the gated linear unit to the input.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - variable whose size along the axis of this layer is even, the output's size along
+    /// such axis will be halved.
+    ///
+    /// # Panics
+    ///
+    /// If the size of the input along the axis of this layer is odd.
+    pub fn forward<I: GLUInput>(&self, input: I) -> I::Output {
+        input.glu(self.axis)
+    }
+}
+
+impl Register for GLU {
+    fn register_params(&self, _: &mut Vec<RawParam>) {}
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        let axis = self.axis;
+        Rc::new(move |input_shape| {
+            let mut output_shape = input_shape.to_vec();
+            output_shape[axis] /= 2;
+            output_shape
+        })
+    }
+}
+
+/// Applies **batch normalization** over a batch of feature vectors.
+///
+/// ```text
+/// ʏ = (x - E[x]) / √(Var[x] + ε) * γ + β
+/// ```
+///
+/// During training the mean and the variance are the ones of the batch and running estimates of
+/// them are kept, during evaluation such estimates are used instead.
+pub struct BatchNorm1d {
+    pub status: Rc<Cell<bool>>,
+    pub momentum: f32,
+    pub eps: f32,
+    pub weight: Learnable<Ix1>,
+    pub bias: Learnable<Ix1>,
+    pub running_mean: Rc<RefCell<Tensor<Ix1>>>,
+    pub running_var: Rc<RefCell<Tensor<Ix1>>>,
+}
+
+impl BatchNorm1d {
+    /// Creates a new BatchNorm1d.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_features` - number of features of the input.
+    ///
+    /// * `momentum` - weight of the statistics of the current batch in the update of the running
+    /// estimates, a common choice is *0.1*.
+    ///
+    /// * `eps` - value added to the variance for numerical stability, a common choice is *1e-5*.
+    ///
+    /// The learnable weight *γ* and bias *β* are of shape `num_features` and are initialized to
+    /// ones and zeros respectively, as are the running variance and mean.
+    pub fn new(num_features: usize, momentum: f32, eps: f32) -> Self {
+        Self {
+            status: Rc::new(Cell::new(true)),
+            momentum,
+            eps,
+            weight: Input::new(Tensor::ones(num_features)).requires_grad(),
+            bias: Input::new(Tensor::zeros(num_features)).requires_grad(),
+            running_mean: Rc::new(RefCell::new(Tensor::zeros(num_features))),
+            running_var: Rc::new(RefCell::new(Tensor::ones(num_features))),
+        }
+    }
+
+    /// Normalizes the input.
+    ///
+    /// # Arguments
+    ///
+    /// `input` - variable of shape *(N, num_features)*.
+    pub fn forward<T: ?Sized, U: ?Sized>(
+        &self,
+        input: VarDiff<T, U>,
+    ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
+    where
+        T: Data<Dim = Ix2> + 'static,
+        U: Gradient<Dim = Ix2> + 'static,
+    {
+        input.batch_norm_with_status(
+            self.running_mean.clone(),
+            self.running_var.clone(),
+            self.momentum,
+            self.eps,
+            self.status.clone(),
+        ) * self.weight.clone()
+            + self.bias.clone()
+    }
+}
+
+impl Eval for BatchNorm1d {
+    fn eval(&self) {
+        self.status.set(false)
+    }
+
+    fn train(&self) {
+        self.status.set(true)
+    }
+}
+
+impl Register for BatchNorm1d {
+    /// Registers the weight and the bias of this `BatchNorm1d` instance.
+    fn register_params(&self, params: &mut Vec<RawParam>) {
+        self.weight.register_params(params);
+        self.bias.register_params(params);
+    }
+
+    fn register_status(&mut self, status: Rc<Cell<bool>>) {
+        self.status = status;
+    }
+
+    fn shape_inference(&self) -> ShapeInference {
+        normalization_shape("BatchNorm1d", self.running_mean.borrow().len(), 2)
+    }
+
+    fn non_trainable(&self) -> usize {
+        self.running_mean.borrow().len() + self.running_var.borrow().len()
+    }
 }
 
-/// A **long short-term memory (LSTM)** cell.
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-#[allow(clippy::upper_case_acronyms)]
-pub struct LSTMCell {
-    pub weight_ih: Learnable<Ix2>,
-    pub weight_hh: Learnable<Ix2>,
-    pub bias_ih: Learnable<Ix1>,
-    pub bias_hh: Learnable<Ix1>,
+/// Applies **batch normalization** over a batch of feature maps.
+///
+/// ```text
+/// ʏ = (x - E[x]) / √(Var[x] + ε) * γ + β
+/// ```
+///
+/// The statistics are computed for each channel over the batch and the spatial axes. During
+/// training the mean and the variance are the ones of the batch and running estimates of them are
+/// kept, during evaluation such estimates are used instead.
+pub struct BatchNorm2d {
+    pub status: Rc<Cell<bool>>,
+    pub momentum: f32,
+    pub eps: f32,
+    pub weight: Learnable<Ix3>,
+    pub bias: Learnable<Ix3>,
+    pub running_mean: Rc<RefCell<Tensor<Ix1>>>,
+    pub running_var: Rc<RefCell<Tensor<Ix1>>>,
 }
 
-impl LSTMCell {
-    /// Creates a new LSTMCell.
+impl BatchNorm2d {
+    /// Creates a new BatchNorm2d.
     ///
     /// # Arguments
     ///
-    /// * `input_size` - number of expected features in the input.
+    /// * `num_features` - number of channels of the input.
     ///
-    /// * `hidden_size` - number of features in the hidden state.
+    /// * `momentum` - weight of the statistics of the current batch in the update of the running
+    /// estimates, a common choice is *0.1*.
     ///
-    /// All the weight and biases are initialized from *U(-k, k)* where
-    /// `k = (1. / hidden_size as f32).sqrt()`.
-    pub fn new(input_size: usize, hidden_size: usize) -> Self {
-        let (weight_ih_shape, weight_hh_shape, bias_shape) = {
-            let xhidden_size = 4 * hidden_size;
-            (
-                (xhidden_size, input_size),
-                (xhidden_size, hidden_size),
-                xhidden_size,
-            )
-        };
-        let weight_ih = Input::new(Tensor::zeros(weight_ih_shape)).requires_grad();
-        let weight_hh = Input::new(Tensor::zeros(weight_hh_shape)).requires_grad();
-        let bias_ih = Input::new(Tensor::zeros(bias_shape)).requires_grad();
-        let bias_hh = Input::new(Tensor::zeros(bias_shape)).requires_grad();
-
-        let k = 1. / (hidden_size as f32).sqrt();
-        init::uniform(&weight_ih, -k, k);
-        init::uniform(&weight_hh, -k, k);
-        init::uniform(&bias_ih, -k, k);
-        init::uniform(&bias_hh, -k, k);
-
+    /// * `eps` - value added to the variance for numerical stability, a common choice is *1e-5*.
+    ///
+    /// The learnable weight *γ* and bias *β* are of shape `(num_features, 1, 1)` and are
+    /// initialized to ones and zeros respectively, as are the running variance and mean.
+    pub fn new(num_features: usize, momentum: f32, eps: f32) -> Self {
         Self {
-            weight_ih,
-            weight_hh,
-            bias_ih,
-            bias_hh,
+            status: Rc::new(Cell::new(true)),
+            momentum,
+            eps,
+            weight: Input::new(Tensor::ones((num_features, 1, 1))).requires_grad(),
+            bias: Input::new(Tensor::zeros((num_features, 1, 1))).requires_grad(),
+            running_mean: Rc::new(RefCell::new(Tensor::zeros(num_features))),
+            running_var: Rc::new(RefCell::new(Tensor::ones(num_features))),
         }
     }
 
-    /// Computes a single **LSTM step**.
+    /// Normalizes the input.
     ///
     /// # Arguments
     ///
-    /// * `state` - a tuple of tensors, both of shape *(batch, hidden_size)*, containing the
-    /// initial hidden state for each element in the batch and the initial cell's state for
-    /// each element in the batch.
-    ///
-    /// * `input` - a variable containing the input features of shape *(batch, input_size)*.
-    ///
-    /// The **output** is a tuple of tensors made of the next hidden state for each element in
-    /// the batch, of shape *(batch, hidden_size)* and the next cell's state for each element in
-    /// the batch, of shape *(batch, hidden_size)*.
-    pub fn forward<Cf: ?Sized, Cb: ?Sized, Hf: ?Sized, Hb: ?Sized, I, T, U>(
+    /// `input` - variable of shape *(N, num_features, H, W)*.
+    pub fn forward<T: ?Sized, U: ?Sized>(
         &self,
-        state: (VarDiff<Cf, Cb>, VarDiff<Hf, Hb>),
-        input: I,
-    ) -> (
-        VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>,
-        VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>,
-    )
+        input: VarDiff<T, U>,
+    ) -> VarDiff<impl Data<Dim = Ix4>, impl Gradient<Dim = Ix4>>
     where
-        Cf: Data<Dim = Ix2>,
-        Cb: Gradient<Dim = Ix2>,
-        Hf: Data<Dim = Ix2>,
-        Hb: Gradient<Dim = Ix2>,
-        I: MatMatMulT<Learnable<Ix2>>,
-        I::Output: Into<VarDiff<T, U>>,
-        T: Data<Dim = Ix2>,
-        U: Gradient<Dim = Ix2>,
+        T: Data<Dim = Ix4> + 'static,
+        U: Gradient<Dim = Ix4> + 'static,
     {
-        let (cell_state, hidden) = state;
-        let gates = hidden.mm_t(self.weight_hh.clone())
-            + self.bias_hh.clone()
-            + input.mm_t(self.weight_ih.clone()).into()
-            + self.bias_ih.clone();
-        let gate_shape = {
-            let (gates_shape_rows, gates_shape_cols) = gates.data().dim();
-            (gates_shape_rows, gates_shape_cols / 4)
-        };
-        let chunked_gates = gates.chunks(gate_shape);
-        let (input_gate, forget_gate, cell_state_gate, output_gate) = (
-            chunked_gates[0].clone().sigmoid(),
-            chunked_gates[1].clone().tanh(),
-            chunked_gates[2].clone().sigmoid(),
-            chunked_gates[3].clone().sigmoid(),
-        );
-        let new_cell_state = forget_gate * cell_state + (input_gate * cell_state_gate);
-        let new_hidden = output_gate * new_cell_state.clone().tanh();
+        input.batch_norm_with_status(
+            self.running_mean.clone(),
+            self.running_var.clone(),
+            self.momentum,
+            self.eps,
+            self.status.clone(),
+        ) * self.weight.clone()
+            + self.bias.clone()
+    }
+}
 
-        (new_cell_state, new_hidden)
+impl Eval for BatchNorm2d {
+    fn eval(&self) {
+        self.status.set(false)
+    }
+
+    fn train(&self) {
+        self.status.set(true)
     }
 }
 
-impl Register for LSTMCell {
-    /// Registers the weights and the biases of this LSTMCell instance.
+impl Register for BatchNorm2d {
+    /// Registers the weight and the bias of this `BatchNorm2d` instance.
     fn register_params(&self, params: &mut Vec<RawParam>) {
-        self.weight_hh.register_params(params);
-        self.weight_ih.register_params(params);
-        self.bias_hh.register_params(params);
-        self.bias_ih.register_params(params);
+        self.weight.register_params(params);
+        self.bias.register_params(params);
     }
 
-    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+    fn register_status(&mut self, status: Rc<Cell<bool>>) {
+        self.status = status;
+    }
+
+    fn shape_inference(&self) -> ShapeInference {
+        normalization_shape("BatchNorm2d", self.running_mean.borrow().len(), 4)
+    }
+
+    fn non_trainable(&self) -> usize {
+        self.running_mean.borrow().len() + self.running_var.borrow().len()
+    }
 }
 
-/// A **gated recurrent unit (GRU)** cell.
+/// Applies **layer normalization** over a batch of feature vectors.
+///
+/// ```text
+/// ʏ = (x - E[x]) / √(Var[x] + ε) * γ + β
+/// ```
+///
+/// Unlike batch normalization, the statistics are computed over the features of each sample, as
+/// described in [Layer Normalization](https://arxiv.org/abs/1607.06450), thus the layer behaves
+/// the same during training and evaluation.
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-#[allow(clippy::upper_case_acronyms)]
-pub struct GRUCell {
-    pub weight_ih: Learnable<Ix2>,
-    pub weight_hh: Learnable<Ix2>,
-    pub bias_ih: Learnable<Ix1>,
-    pub bias_hh: Learnable<Ix1>,
+pub struct LayerNorm {
+    pub eps: f32,
+    pub weight: Learnable<Ix1>,
+    pub bias: Learnable<Ix1>,
 }
 
-impl GRUCell {
-    /// Creates a new GRUCell.
+impl LayerNorm {
+    /// Creates a new LayerNorm.
     ///
     /// # Arguments
     ///
-    /// * `input_size` - number of expected features in the input.
+    /// * `normalized_shape` - number of features of the input.
     ///
-    /// * `hidden_size` - number of features in the hidden state.
+    /// * `eps` - value added to the variance for numerical stability, a common choice is *1e-5*.
     ///
-    /// All the weight and biases are initialized from *U(-k, k)* where
-    /// `k = (1. / hidden_size as f32).sqrt()`.
-    pub fn new(input_size: usize, hidden_size: usize) -> Self {
-        let (weight_ih_shape, weight_hh_shape, bias_shape) = {
-            let xhidden_size = 3 * hidden_size;
-            (
-                (xhidden_size, input_size),
-                (xhidden_size, hidden_size),
-                xhidden_size,
-            )
-        };
-        let weight_ih = Input::new(Tensor::zeros(weight_ih_shape)).requires_grad();
-        let weight_hh = Input::new(Tensor::zeros(weight_hh_shape)).requires_grad();
-        let bias_ih = Input::new(Tensor::zeros(bias_shape)).requires_grad();
-        let bias_hh = Input::new(Tensor::zeros(bias_shape)).requires_grad();
-
-        let k = 1. / (hidden_size as f32).sqrt();
-        init::uniform(&weight_ih, -k, k);
-        init::uniform(&weight_hh, -k, k);
-        init::uniform(&bias_ih, -k, k);
-        init::uniform(&bias_hh, -k, k);
-
+    /// The learnable weight *γ* and bias *β* are of shape `normalized_shape` and are initialized
+    /// to ones and zeros respectively.
+    pub fn new(normalized_shape: usize, eps: f32) -> Self {
         Self {
-            weight_ih,
-            weight_hh,
-            bias_ih,
-            bias_hh,
+            eps,
+            weight: Input::new(Tensor::ones(normalized_shape)).requires_grad(),
+            bias: Input::new(Tensor::zeros(normalized_shape)).requires_grad(),
         }
     }
 
-    /// Computes a single **GRU step**.
-    ///
-    /// * `hidden` - a variable of shape *(batch, hidden_size)*, containing the initial hidden state
-    /// for each element in the batch.
+    /// Normalizes the input.
     ///
-    /// * `input` - a variable containing the input features of shape *(batch, input_size)*.
+    /// # Arguments
     ///
-    /// The **output** is  a variable made of the next hidden state for each element in
-    /// the batch, of shape *(batch, hidden_size)*.
-    pub fn forward<Hf: ?Sized, Hb: ?Sized, I, T, U>(
+    /// `input` - variable of shape *(N, normalized_shape)*.
+    pub fn forward<T: ?Sized, U: ?Sized>(
         &self,
-        hidden: VarDiff<Hf, Hb>,
-        input: I,
+        input: VarDiff<T, U>,
     ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
     where
-        Hf: Data<Dim = Ix2>,
-        Hb: Gradient<Dim = Ix2>,
-        I: MatMatMulT<Learnable<Ix2>>,
-        I::Output: Into<VarDiff<T, U>>,
-        T: Data<Dim = Ix2>,
-        U: Gradient<Dim = Ix2>,
+        T: Data<Dim = Ix2> + 'static,
+        U: Gradient<Dim = Ix2> + 'static,
     {
-        let (igates, hgates) = {
-            (
-                input.mm_t(self.weight_ih.clone()).into() + self.bias_ih.clone(),
-                hidden.clone().mm_t(self.weight_hh.clone()) + self.bias_hh.clone(),
-            )
-        };
-        let gate_shape = {
-            let (gates_shape_rows, gates_shape_cols) = hgates.data().dim();
-            (gates_shape_rows, gates_shape_cols / 3)
-        };
-        let (chunked_igates, chunked_hgates) =
-            (igates.chunks(gate_shape), hgates.chunks(gate_shape));
+        // The averages over the features are computed as products with a constant column.
+        let features = input.data().ncols();
+        let average = crate::full((features, 1), 1. / features as f32);
 
-        let reset_gate = (chunked_hgates[0].clone() + chunked_igates[0].clone()).sigmoid();
-        let input_gate = (chunked_hgates[1].clone() + chunked_igates[1].clone()).sigmoid();
-        let new_gate =
-            (chunked_igates[2].clone() + (chunked_hgates[2].clone() * reset_gate)).tanh();
-        (hidden - new_gate.clone()) * input_gate + new_gate
+        let mean = MatMatMul::mm(input.clone(), average.clone());
+        let centered = input - mean;
+        let variance = centered.clone().pow(2).mm(average);
+
+        centered / (variance + self.eps).sqrt() * self.weight.clone() + self.bias.clone()
     }
 }
 
-impl Register for GRUCell {
-    /// Registers the weights and the biases of this `GRUCell` instance.
+impl Register for LayerNorm {
+    /// Registers the weight and the bias of this `LayerNorm` instance.
     fn register_params(&self, params: &mut Vec<RawParam>) {
-        self.weight_hh.register_params(params);
-        self.weight_ih.register_params(params);
-        self.bias_hh.register_params(params);
-        self.bias_ih.register_params(params);
+        self.weight.register_params(params);
+        self.bias.register_params(params);
     }
 
     fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        normalization_shape("LayerNorm", self.weight.data().len(), 2)
+    }
 }
 
-/// Applies a **temporal convolution** over an input signal composed of several input planes.
+/// A lookup table storing the **embeddings** of a fixed dictionary.
 ///
-/// See also [`GroupedConv1d`].
+/// It is often used to store word embeddings, which are retrieved by feeding the layer the
+/// indices of the words.
+///
+/// ```
+/// use neuronika::nn::Embedding;
+///
+/// let embedding = Embedding::new(100, 8);
+/// let tokens = neuronika::indices(ndarray::array![[4, 17, 0], [99, 3, 3]]);
+///
+/// let embedded = embedding.forward(tokens);
+/// embedded.forward();
+/// assert_eq!(embedded.data().shape(), &[2, 3, 8]);
+/// ```
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct Conv1d<Pad: PaddingMode> {
-    pub padding: usize,
-    pub padding_mode: Pad,
-    pub stride: usize,
-    pub dilation: usize,
-    pub weight: Learnable<Ix3>,
-    pub bias: Learnable<Ix2>,
+pub struct Embedding {
+    pub weight: Learnable<Ix2>,
 }
 
-impl<Pad: PaddingMode> Conv1d<Pad> {
-    /// Creates a new Conv1d.
-    ///
-    /// # Arguments
-    ///
-    /// * `in_channels` - number of planes in the input signal.
+impl Embedding {
+    /// Creates a new Embedding.
     ///
-    /// * `out_channels` - number of planes in the output signal.
-    ///
-    /// * `kernel_size` - size of the kernel, a number for this one-dimensional case.
-    ///
-    /// * `padding` - padding to be applied to the input, a number for this one-dimensional case.
-    ///
-    /// * `padding_mode` - padding mode, it can be: [`Zero`], [`Constant`], [`Reflective`] or
-    /// [`Replicative`].
-    ///
-    /// * `stride` - stride of the convolution, a number for this one-dimensional case.
-    ///
-    /// * `dilation` - controls the spacing between the kernel points, a number for this
-    /// one-dimensional case.
-    ///
-    /// The weight and the bias of the layer are initialized from *U(-k, k)* where
-    /// `k = (1. /(in_channels * kernel_size) as f32).sqrt()`.
-    pub fn new(
-        in_channels: usize,
-        out_channels: usize,
-        kernel_size: usize,
-        padding: usize,
-        padding_mode: Pad,
-        stride: usize,
-        dilation: usize,
-    ) -> Self {
-        let weight =
-            Input::new(Tensor::zeros((out_channels, in_channels, kernel_size))).requires_grad();
-        let bias = Input::new(Tensor::zeros((out_channels, 1))).requires_grad();
-
-        let k = (1. / (in_channels * kernel_size) as f32).sqrt();
-        init::uniform(&weight, -k, k);
-        init::uniform(&bias, -k, k);
+    /// # Arguments
+    ///
+    /// * `num_embeddings` - size of the dictionary.
+    ///
+    /// * `embedding_dim` - size of each embedding.
+    ///
+    /// The learnable weight of the layer is of shape `(num_embeddings, embedding_dim)` and is
+    /// initialized from *N(0, 1)*.
+    pub fn new(num_embeddings: usize, embedding_dim: usize) -> Self {
+        let weight = Input::new(Tensor::zeros((num_embeddings, embedding_dim))).requires_grad();
+        init::normal(&weight, 0., 1.);
 
-        Self {
-            padding,
-            padding_mode,
-            stride,
-            dilation,
-            weight,
-            bias,
-        }
+        Self { weight }
     }
 
-    /// Computes a 1-dimensional convolution *(cross correlation)*.
+    /// Retrieves the embeddings of the input indices.
     ///
     /// # Arguments
     ///
-    /// `input` - signal to convolve.
-    ///
-    /// The **input** must be of shape *(N, Cin, L)*
-    /// * **N** is the batch size
-    /// * **Cin** is the number of input channels
-    /// * **L** is the **length** of the input
+    /// `input` - index variable of any shape. The output's shape will be the one of `input` with
+    /// an additional last axis of length `embedding_dim`.
     ///
-    /// The **kernel** must be of shape *(Cout, Cin, Lk)*
-    /// * **Cout** is the number of output channels
-    /// * **Cin** is the number of input channels
-    /// * **Lk** is the **length** of the kernel
+    /// # Panics
     ///
-    /// The resulting output shape will be *(N, Cout, Lout)*
-    pub fn forward<I, T, U>(
+    /// If an index is negative or is not smaller than `num_embeddings`.
+    pub fn forward<T: ?Sized>(
         &self,
-        input: I,
-    ) -> VarDiff<impl Data<Dim = Ix3>, impl Gradient<Dim = Ix3>>
+        input: IndexVar<T>,
+    ) -> VarDiff<
+        impl Data<Dim = <T::Dim as Dimension>::Larger>,
+        impl Gradient<Dim = <T::Dim as Dimension>::Larger>,
+    >
     where
-        I: Convolve<I, Learnable<Ix3>, Pad>,
-        I::Output: Into<VarDiff<T, U>>,
-        T: Data<Dim = Ix3>,
-        U: Gradient<Dim = Ix3>,
+        T: IndexData + 'static,
     {
-        I::convolve(
-            input,
-            self.weight.clone(),
-            &[self.stride],
-            &[self.dilation],
-            &[self.padding],
-            self.padding_mode,
-        )
-        .into()
-            + self.bias.clone()
+        input.embedding(self.weight.clone())
     }
 }
 
-impl<Pad: PaddingMode> Register for Conv1d<Pad> {
-    /// Registers the weight and the bias of this `Conv1d` instance.
+impl Register for Embedding {
+    /// Registers the weight of this `Embedding` instance.
     fn register_params(&self, params: &mut Vec<RawParam>) {
         self.weight.register_params(params);
-        self.bias.register_params(params);
     }
 
     fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        let embedding_dim = self.weight.data().ncols();
+        Rc::new(move |input_shape| {
+            let mut output_shape = input_shape.to_vec();
+            output_shape.push(embedding_dim);
+            output_shape
+        })
+    }
 }
 
-/// Applies a **grouped temporal convolution** over an input signal composed of several input
-/// planes.
+/// Computes the **scaled dot-product attention** of `query` over `key` and `value`.
+///
+/// ```text
+/// Attention(Q, K, V) = Softmax(QKᵀ / √d + M)V
+/// ```
+///
+/// The whole computation is carried out by a single node, that keeps only the attention
+/// probabilities besides the result, instead of materializing every intermediate step.
+///
+/// All the leading axes of the operands but the last two are batch axes, along which each
+/// matrix attends independently.
+///
+/// ```
+/// use neuronika::nn;
+///
+/// let query = neuronika::rand((4, 5, 8)).requires_grad();
+/// let key = neuronika::rand((4, 7, 8)).requires_grad();
+/// let value = neuronika::rand((4, 7, 3)).requires_grad();
+///
+/// let out = nn::scaled_dot_product_attention(query, key, value, None, None);
+/// out.forward();
+/// assert_eq!(out.data().shape(), &[4, 5, 3]);
+/// ```
+///
+/// Dropout is applied to the attention probabilities by passing a [`Dropout`] layer, whose
+/// status follows the model it is registered with.
+///
+/// ```
+/// use neuronika::nn::{self, Dropout, ModelStatus};
+///
+/// let mut status = ModelStatus::default();
+/// let dropout = status.register(Dropout::new(0.1));
+///
+/// let query = neuronika::rand((5, 8)).requires_grad();
+/// let out = nn::scaled_dot_product_attention(
+///     query.clone(),
+///     query.clone(),
+///     query,
+///     None,
+///     Some(&dropout),
+/// );
+///
+/// // The attention probabilities are no longer dropped.
+/// status.eval();
+/// out.forward();
+/// ```
+///
+/// # Arguments
+///
+/// * `query` - variable of shape *(..., L, d)*.
+///
+/// * `key` - variable of shape *(..., S, d)*.
+///
+/// * `value` - variable of shape *(..., S, dᵥ)*.
+///
+/// * `attn_mask` - optional tensor *M*, broadcastable to *(..., L, S)*, added to the attention
+/// scores before the softmax. The positions that must not be attended to should hold a large
+/// negative value, such as `f32::MIN`, or negative infinity. Queries that cannot attend to any
+/// position produce zeros. Such masks can be built with [`causal_mask()`] and [`padding_mask()`].
+///
+/// * `dropout` - optional dropout layer, zeroing each attention probability with its probability
+/// *p* while it is in training mode. The remaining ones are scaled by a factor of 1/(1 - p).
+///
+/// The output's shape will be *(..., L, dᵥ)*.
+///
+/// # Panics
+///
+/// If the shapes of the operands are not compatible, if `attn_mask` cannot be broadcast to the
+/// shape of the attention scores or if the probability of `dropout` is not between 0 and 1.
+pub fn scaled_dot_product_attention<
+    Qf: ?Sized,
+    Qb: ?Sized,
+    Kf: ?Sized,
+    Kb: ?Sized,
+    Vf: ?Sized,
+    Vb: ?Sized,
+    D,
+>(
+    mut query: VarDiff<Qf, Qb>,
+    key: VarDiff<Kf, Kb>,
+    value: VarDiff<Vf, Vb>,
+    attn_mask: Option<&Array<f32, D>>,
+    dropout: Option<&Dropout>,
+) -> VarDiff<impl Data<Dim = D>, impl Gradient<Dim = D>>
+where
+    Qf: Data<Dim = D> + 'static,
+    Qb: Gradient<Dim = D> + 'static,
+    Kf: Data<Dim = D> + 'static,
+    Kb: Gradient<Dim = D> + 'static,
+    Vf: Data<Dim = D> + 'static,
+    Vb: Gradient<Dim = D> + 'static,
+    D: Dimension + 'static,
+{
+    query.var.past.merge(key.var.past);
+    query.var.past.merge(value.var.past);
+    let forward_node = ScaledDotProductAttentionNode::new(
+        query.var.node,
+        key.var.node,
+        value.var.node,
+        attn_mask.cloned(),
+        dropout.map_or(0., |dropout| dropout.p),
+        dropout.map_or_else(
+            || Rc::new(Cell::new(true)),
+            |dropout| dropout.status.clone(),
+        ),
+    );
+    let var = Var::from_changeable(forward_node, query.var.past);
+
+    query.past.merge(key.past);
+    query.past.merge(value.past);
+    let backward_node =
+        ScaledDotProductAttentionBackward::new(query.node, key.node, value.node, var.node.clone());
+    VarDiff::from(backward_node, query.past, var)
+}
+
+/// Computes the **scaled dot-product attention** of `query` over `key` and `value`, as
+/// [`scaled_dot_product_attention()`] does, and also returns the attention probabilities.
+///
+/// The probabilities, of shape *(..., L, S)*, are the ones before the dropout. They are
+/// differentiable, so that auxiliary losses can be computed on them, and can be detached with
+/// `.detach()` when they are only needed for visualization.
+///
+/// ```
+/// use neuronika::nn;
+///
+/// let query = neuronika::rand((5, 8)).requires_grad();
+/// let key = neuronika::rand((7, 8)).requires_grad();
+/// let value = neuronika::rand((7, 3)).requires_grad();
+///
+/// let (out, weights) =
+///     nn::scaled_dot_product_attention_with_weights(query, key, value, None, None);
+/// weights.forward();
+/// assert_eq!(out.data().shape(), &[5, 3]);
+/// assert_eq!(weights.data().shape(), &[5, 7]);
+/// ```
+///
+/// # Panics
+///
+/// If the shapes of the operands are not compatible, if `attn_mask` cannot be broadcast to the
+/// shape of the attention scores or if the probability of `dropout` is not between 0 and 1.
+pub fn scaled_dot_product_attention_with_weights<
+    Qf: ?Sized,
+    Qb: ?Sized,
+    Kf: ?Sized,
+    Kb: ?Sized,
+    Vf: ?Sized,
+    Vb: ?Sized,
+    D,
+>(
+    mut query: VarDiff<Qf, Qb>,
+    key: VarDiff<Kf, Kb>,
+    value: VarDiff<Vf, Vb>,
+    attn_mask: Option<&Array<f32, D>>,
+    dropout: Option<&Dropout>,
+) -> (
+    VarDiff<impl Data<Dim = D>, impl Gradient<Dim = D>>,
+    VarDiff<impl Data<Dim = D>, impl Gradient<Dim = D>>,
+)
+where
+    Qf: Data<Dim = D> + 'static,
+    Qb: Gradient<Dim = D> + 'static,
+    Kf: Data<Dim = D> + 'static,
+    Kb: Gradient<Dim = D> + 'static,
+    Vf: Data<Dim = D> + 'static,
+    Vb: Gradient<Dim = D> + 'static,
+    D: Dimension + 'static,
+{
+    query.var.past.merge(key.var.past);
+    query.var.past.merge(value.var.past);
+    let forward_node = ScaledDotProductAttentionNode::new(
+        query.var.node,
+        key.var.node,
+        value.var.node,
+        attn_mask.cloned(),
+        dropout.map_or(0., |dropout| dropout.p),
+        dropout.map_or_else(
+            || Rc::new(Cell::new(true)),
+            |dropout| dropout.status.clone(),
+        ),
+    );
+    let var = Var::from_changeable(forward_node, query.var.past);
+    let vars = (0..2)
+        .map(|i| Var::from(Output::new(var.node.clone(), i), var.past.clone()))
+        .collect();
+
+    query.past.merge(key.past);
+    query.past.merge(value.past);
+    let backward_node = ScaledDotProductAttentionBackward::with_weights(
+        query.node,
+        key.node,
+        value.node,
+        var.node.clone(),
+    );
+    let mut outputs = VarDiff::outputs(backward_node, query.past, vars);
+    let weights = outputs.pop().unwrap();
+    (outputs.pop().unwrap(), weights)
+}
+
+/// Creates a **causal mask** for a sequence of length `len`, preventing each position from
+/// attending to the following ones.
+///
+/// The mask is a variable of shape *(len, len)* holding zeros at the positions that can be attended
+/// to and negative infinity at the ones that must not, so that it can either be added to the
+/// attention scores, as [`scaled_dot_product_attention()`] and [`MultiheadAttention`] do, or
+/// passed to `.masked_fill()`, which fills the non-zero positions.
+///
+/// ```
+/// use neuronika::nn;
+///
+/// let mask = nn::causal_mask(3);
+/// assert_eq!(
+///     *mask.data(),
+///     ndarray::array![
+///         [0., f32::NEG_INFINITY, f32::NEG_INFINITY],
+///         [0., 0., f32::NEG_INFINITY],
+///         [0., 0., 0.],
+///     ]
+/// );
+/// ```
+pub fn causal_mask(len: usize) -> Var<Input<Ix2>> {
+    Input::new(Tensor::from_shape_fn((len, len), |(row, col)| {
+        if col > row {
+            f32::NEG_INFINITY
+        } else {
+            0.
+        }
+    }))
+}
+
+/// Creates a **padding mask** for a batch of sequences of lengths `lengths` padded to `max_len`,
+/// preventing the padding positions from being attended to.
+///
+/// The mask is a variable of shape *(B, 1, max_len)*, with *B* the number of sequences, holding
+/// zeros at the positions that can be attended to and negative infinity at the padding ones. It
+/// broadcasts over the attention scores of shape *(B, L, max_len)* and can be summed to a
+/// [`causal_mask()`].
+///
+/// ```
+/// use neuronika::nn;
+///
+/// let mask = nn::padding_mask(&[2, 3], 3);
+/// assert_eq!(
+///     *mask.data(),
+///     ndarray::array![[[0., 0., f32::NEG_INFINITY]], [[0., 0., 0.]]]
+/// );
+///
+/// let query = neuronika::rand((2, 3, 4)).requires_grad();
+/// let mask = mask + nn::causal_mask(3);
+/// mask.forward();
+///
+/// let out =
+///     nn::scaled_dot_product_attention(query.clone(), query.clone(), query, Some(&mask.data()), None);
+/// out.forward();
+/// assert_eq!(out.data().shape(), &[2, 3, 4]);
+/// ```
+///
+/// # Panics
+///
+/// If any of the lengths is greater than `max_len`.
+pub fn padding_mask(lengths: &[usize], max_len: usize) -> Var<Input<Ix3>> {
+    if let Some(len) = lengths.iter().find(|&&len| len > max_len) {
+        panic!(
+            "error: a sequence of length {} cannot be padded to length {}.",
+            len, max_len
+        );
+    }
+
+    Input::new(Tensor::from_shape_fn(
+        (lengths.len(), 1, max_len),
+        |(sequence, _, position)| {
+            if position >= lengths[sequence] {
+                f32::NEG_INFINITY
+            } else {
+                0.
+            }
+        },
+    ))
+}
+
+/// Creates a **sequence mask** for a batch of sequences of lengths `lengths` padded to `max_len`.
+///
+/// The mask is a variable of shape *(B, max_len)*, with *B* the number of sequences, holding ones
+/// at the positions of the sequences and zeros at the padding ones. It is meant to be given to the
+/// masked poolings of the variables, which exclude the padding from the reductions and from their
+/// gradients.
+///
+/// ```
+/// use neuronika::nn;
+///
+/// let mask = nn::sequence_mask(&[2, 3], 3);
+/// assert_eq!(*mask.data(), ndarray::array![[1., 1., 0.], [1., 1., 1.]]);
+///
+/// let sequences = neuronika::rand((2, 3, 4)).requires_grad();
+/// let embeddings = sequences.masked_mean_pool(mask);
+/// embeddings.forward();
+/// assert_eq!(embeddings.data().shape(), &[2, 4]);
+/// ```
+///
+/// # Panics
+///
+/// If any of the lengths is greater than `max_len`.
+pub fn sequence_mask(lengths: &[usize], max_len: usize) -> Var<Input<Ix2>> {
+    if let Some(len) = lengths.iter().find(|&&len| len > max_len) {
+        panic!(
+            "error: a sequence of length {} cannot be padded to length {}.",
+            len, max_len
+        );
+    }
+
+    Input::new(Tensor::from_shape_fn(
+        (lengths.len(), max_len),
+        |(sequence, position)| (position < lengths[sequence]) as usize as f32,
+    ))
+}
+
+/// Allows a sequence to jointly attend to information from different representation subspaces,
+/// as described in [Attention Is All You Need](https://arxiv.org/abs/1706.03762).
+///
+/// ```text
+/// MultiHead(Q, K, V) = Concat(head₁, ..., headₕ)Wᴼ
+///
+/// headᵢ = Softmax(QWᵢᑫ(KWᵢᴷ)ᵀ / √dₖ)VWᵢⱽ
+/// ```
+///
+/// The query, key and value are sequences of shape *(L, embed_dim)*. Several sequences can be
+/// processed at once by stacking them along the first axis and by masking the attention between
+/// positions of different sequences.
+///
+/// ```
+/// use neuronika::nn::MultiheadAttention;
+///
+/// let attention = MultiheadAttention::new(8, 2);
+/// let sequence = neuronika::rand((5, 8)).requires_grad();
+///
+/// let out = attention.forward(sequence.clone(), sequence.clone(), sequence, None);
+/// out.forward();
+/// assert_eq!(out.data().shape(), &[5, 8]);
+/// ```
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct GroupedConv1d<Pad: PaddingMode> {
-    pub padding: usize,
-    pub padding_mode: Pad,
-    pub stride: usize,
-    pub dilation: usize,
-    pub groups: usize,
-    pub weight: Learnable<Ix3>,
-    pub bias: Learnable<Ix2>,
+pub struct MultiheadAttention {
+    pub num_heads: usize,
+    pub q_proj: Linear,
+    pub k_proj: Linear,
+    pub v_proj: Linear,
+    pub out_proj: Linear,
 }
 
-impl<Pad: PaddingMode> GroupedConv1d<Pad> {
-    /// Creates a new GroupedConv1d.
+impl MultiheadAttention {
+    /// Creates a new MultiheadAttention.
     ///
     /// # Arguments
     ///
-    /// * `in_channels` - number of planes in the input signal.
+    /// * `embed_dim` - number of features of the query, key and value.
     ///
-    /// * `out_channels` - number of planes in the output signal.
+    /// * `num_heads` - number of parallel attention heads, each of them attends to `embed_dim /
+    /// num_heads` of the projected features.
     ///
-    /// * `kernel_size` - size of the kernel, a number for this one-dimensional case.
+    /// The weights of the query, key and value projections are initialized with
+    /// [`init::xavier_uniform()`] and all the biases are initialized to zero.
     ///
-    /// * `padding` - padding to be applied to the input, a number for this one-dimensional case.
+    /// # Panics
     ///
-    /// * `padding_mode` - padding mode, it can be: [`Zero`], [`Constant`], [`Reflective`] or
-    /// [`Replicative`].
+    /// If `embed_dim` is not divisible by `num_heads`.
+    pub fn new(embed_dim: usize, num_heads: usize) -> Self {
+        assert!(
+            num_heads > 0 && embed_dim.is_multiple_of(num_heads),
+            "error: embed_dim {} is not divisible by num_heads {}.",
+            embed_dim,
+            num_heads
+        );
+
+        let projection = || {
+            let linear = Linear::new(embed_dim, embed_dim);
+            init::xavier_uniform(&linear.weight, 1.);
+            init::zeros(&linear.bias);
+            linear
+        };
+        let out_proj = Linear::new(embed_dim, embed_dim);
+        init::zeros(&out_proj.bias);
+
+        Self {
+            num_heads,
+            q_proj: projection(),
+            k_proj: projection(),
+            v_proj: projection(),
+            out_proj,
+        }
+    }
+
+    /// Computes the attention of `query` over `key` and `value`.
     ///
-    /// * `stride` - stride of the convolution, a number for this one-dimensional case.
+    /// # Arguments
     ///
-    /// * `dilation` - controls the spacing between the kernel points, a number for this
-    /// one-dimensional case.
+    /// * `query` - variable of shape *(L, embed_dim)*.
     ///
-    /// * `groups` -  controls the connections between inputs and outputs. `in_channels` and
-    /// `out_channels` must both be **divisible by groups**.
+    /// * `key` - variable of shape *(S, embed_dim)*.
     ///
-    /// For example:
-    /// * at `groups = 1`, all inputs are convolved to all outputs.
-    /// * at `groups = 2`, the operation becomes equivalent to having two convolutional layers side
-    /// by side, each seeing half the input channels and producing half the output channels, and
-    /// both subsequently concatenated.
-    ///* at `groups = in_channels`, each input channel is convolved with its own set of filters.
+    /// * `value` - variable of shape *(S, embed_dim)*.
     ///
-    /// The weight and the bias of the layer are initialized from *U(-k, k)* where
-    /// `k = (groups /(in_channels * kernel_size) as f32).sqrt()`.
-    #[allow(clippy::too_many_arguments)]
-    pub fn new(
-        in_channels: usize,
-        out_channels: usize,
-        kernel_size: usize,
-        padding: usize,
-        padding_mode: Pad,
-        stride: usize,
-        dilation: usize,
-        groups: usize,
-    ) -> Self {
-        let weight = Input::new(Tensor::zeros((
-            out_channels,
-            in_channels / groups,
-            kernel_size,
-        )))
-        .requires_grad();
-        let bias = Input::new(Tensor::zeros((out_channels, 1))).requires_grad();
+    /// * `attn_mask` - optional tensor of shape *(L, S)* added to the attention scores of each
+    /// head before the softmax. The positions that must not be attended to should hold a large
+    /// negative value, such as `f32::MIN`.
+    ///
+    /// The output's shape will be *(L, embed_dim)*.
+    pub fn forward<Qf: ?Sized, Qb: ?Sized, Kf: ?Sized, Kb: ?Sized, Vf: ?Sized, Vb: ?Sized>(
+        &self,
+        query: VarDiff<Qf, Qb>,
+        key: VarDiff<Kf, Kb>,
+        value: VarDiff<Vf, Vb>,
+        attn_mask: Option<&Array2<f32>>,
+    ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
+    where
+        Qf: Data<Dim = Ix2> + 'static,
+        Qb: Gradient<Dim = Ix2> + 'static,
+        Kf: Data<Dim = Ix2> + 'static,
+        Kb: Gradient<Dim = Ix2> + 'static,
+        Vf: Data<Dim = Ix2> + 'static,
+        Vb: Gradient<Dim = Ix2> + 'static,
+    {
+        let (target_len, source_len, embed_dim) = (
+            query.data().nrows(),
+            key.data().nrows(),
+            query.data().ncols(),
+        );
+        let head_dim = embed_dim / self.num_heads;
 
-        let k = (groups as f32 / (in_channels * kernel_size) as f32).sqrt();
-        init::uniform(&weight, -k, k);
-        init::uniform(&bias, -k, k);
+        let queries = self.q_proj.forward(query).chunks((target_len, head_dim));
+        let keys = self.k_proj.forward(key).chunks((source_len, head_dim));
+        let values = self.v_proj.forward(value).chunks((source_len, head_dim));
 
-        Self {
-            padding,
-            padding_mode,
-            stride,
-            dilation,
-            groups,
-            weight,
-            bias,
-        }
+        let heads: Vec<_> = itertools::izip!(queries, keys, values)
+            .map(|(query, key, value)| {
+                scaled_dot_product_attention(query, key, value, attn_mask, None).into_dyn()
+            })
+            .collect();
+
+        self.out_proj.forward(VarDiff::cat(&heads, 1))
     }
 
-    /// Computes a 1-dimensional grouped convolution *(cross correlation)*.
-    ///
-    /// # Arguments
+    /// Computes the attention of `query` over `key` and `value`, as
+    /// [`.forward()`](MultiheadAttention::forward()) does, and also returns the attention
+    /// probabilities of all the heads.
     ///
-    /// `input` - signal to convolve.
+    /// The probabilities have shape *(num_heads, L, S)*. They are differentiable, so that
+    /// auxiliary losses can be computed on them, and can be detached with `.detach()` when they
+    /// are only needed for visualization.
     ///
-    /// The **input** must be of shape *(N, Cin, L)*
-    /// * **N** is the batch size
-    /// * **Cin** is the number of input channels
-    /// * **L** is the **length** of the input
+    /// ```
+    /// use neuronika::nn::MultiheadAttention;
     ///
-    /// The **kernel** must be of shape *(Cout, Cin, Lk)*
-    /// * **Cout** is the number of output channels
-    /// * **Cin** is the number of input channels
-    /// * **Lk** is the **length** of the kernel
+    /// let attention = MultiheadAttention::new(8, 2);
+    /// let sequence = neuronika::rand((5, 8)).requires_grad();
     ///
-    /// The resulting output shape will be *(N, Cout, Lout)*
-    pub fn forward<I, T, U>(
+    /// let (out, weights) =
+    ///     attention.forward_with_weights(sequence.clone(), sequence.clone(), sequence, None);
+    /// out.forward();
+    /// weights.forward();
+    /// assert_eq!(weights.data().shape(), &[2, 5, 5]);
+    /// ```
+    pub fn forward_with_weights<
+        Qf: ?Sized,
+        Qb: ?Sized,
+        Kf: ?Sized,
+        Kb: ?Sized,
+        Vf: ?Sized,
+        Vb: ?Sized,
+    >(
         &self,
-        input: I,
-    ) -> VarDiff<impl Data<Dim = Ix3>, impl Gradient<Dim = Ix3>>
+        query: VarDiff<Qf, Qb>,
+        key: VarDiff<Kf, Kb>,
+        value: VarDiff<Vf, Vb>,
+        attn_mask: Option<&Array2<f32>>,
+    ) -> (
+        VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>,
+        VarDiff<impl Data<Dim = Ix3>, impl Gradient<Dim = Ix3>>,
+    )
     where
-        I: ConvolveWithGroups<I, Learnable<Ix3>, Pad>,
-        I::Output: Into<VarDiff<T, U>>,
-        T: Data<Dim = Ix3>,
-        U: Gradient<Dim = Ix3>,
+        Qf: Data<Dim = Ix2> + 'static,
+        Qb: Gradient<Dim = Ix2> + 'static,
+        Kf: Data<Dim = Ix2> + 'static,
+        Kb: Gradient<Dim = Ix2> + 'static,
+        Vf: Data<Dim = Ix2> + 'static,
+        Vb: Gradient<Dim = Ix2> + 'static,
     {
-        I::convolve_with_groups(
-            input,
-            self.weight.clone(),
-            &[self.stride],
-            &[self.dilation],
-            &[self.padding],
-            self.padding_mode,
-            self.groups,
+        let (target_len, source_len, embed_dim) = (
+            query.data().nrows(),
+            key.data().nrows(),
+            query.data().ncols(),
+        );
+        let head_dim = embed_dim / self.num_heads;
+
+        let queries = self.q_proj.forward(query).chunks((target_len, head_dim));
+        let keys = self.k_proj.forward(key).chunks((source_len, head_dim));
+        let values = self.v_proj.forward(value).chunks((source_len, head_dim));
+
+        let (heads, weights): (Vec<_>, Vec<_>) = itertools::izip!(queries, keys, values)
+            .map(|(query, key, value)| {
+                let (head, weights) =
+                    scaled_dot_product_attention_with_weights(query, key, value, attn_mask, None);
+                (head.into_dyn(), weights.into_dyn())
+            })
+            .unzip();
+
+        (
+            self.out_proj.forward(VarDiff::cat(&heads, 1)),
+            VarDiff::stack(&weights, 0),
         )
-        .into()
-            + self.bias.clone()
     }
 }
 
-impl<Pad: PaddingMode> Register for GroupedConv1d<Pad> {
-    /// Registers the weight and the bias of this `GroupedConv1d` instance.
+impl Register for MultiheadAttention {
+    /// Registers the weights and the biases of the projections of this `MultiheadAttention`
+    /// instance.
     fn register_params(&self, params: &mut Vec<RawParam>) {
-        self.weight.register_params(params);
-        self.bias.register_params(params);
+        self.q_proj.register_params(params);
+        self.k_proj.register_params(params);
+        self.v_proj.register_params(params);
+        self.out_proj.register_params(params);
     }
 
     fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        let embed_dim = self.out_proj.weight.data().nrows();
+        linear_shape("MultiheadAttention", embed_dim, embed_dim)
+    }
 }
 
-/// Applies a **spatial convolution** over an input signal composed of several input planes.
+/// Adds to a sequence an encoding of the **positions** of its elements, which the attention layers
+/// alone are not aware of.
+///
+/// The encoding can either be the fixed one of
+/// [Attention Is All You Need](https://arxiv.org/abs/1706.03762)
+///
+/// ```text
+/// PE(pos, 2i) = sin(pos / 10000²ⁱᐟᵈ)
+///
+/// PE(pos, 2i + 1) = cos(pos / 10000²ⁱᐟᵈ)
+/// ```
 ///
-/// See also [`GroupedConv2d`].
+/// or be learned along with the rest of the model.
+///
+/// ```
+/// use neuronika::nn::PositionalEncoding;
+///
+/// let encoding = PositionalEncoding::sinusoidal(100, 8);
+/// let sequence = neuronika::rand((2, 5, 8)).requires_grad();
+///
+/// let encoded = encoding.forward(sequence);
+/// encoded.forward();
+/// assert_eq!(encoded.data().shape(), &[2, 5, 8]);
+/// ```
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct Conv2d<Pad: PaddingMode> {
-    pub padding: (usize, usize),
-    pub padding_mode: Pad,
-    pub stride: (usize, usize),
-    pub dilation: (usize, usize),
-    pub weight: Learnable<Ix4>,
-    pub bias: Learnable<Ix3>,
+pub struct PositionalEncoding {
+    pub encoding: Learnable<Ix2>,
+    pub learned: bool,
 }
 
-impl<Pad: PaddingMode> Conv2d<Pad> {
-    /// Creates a new Conv2d.
+impl PositionalEncoding {
+    /// Creates a new PositionalEncoding made of fixed sinusoids.
     ///
     /// # Arguments
     ///
-    /// * `in_channels` - number of planes in the input signal.
-    ///
-    /// * `out_channels` - number of planes in the output signal.
+    /// * `max_len` - maximum length of the sequences.
     ///
-    /// * `kernel_size` - size of the kernel, a 2-tuple for this two-dimensional case.
+    /// * `d_model` - number of features of each element of the sequences.
     ///
-    /// * `padding` - padding to be applied to the input, a 2-tuple for this two-dimensional case.
+    /// The encoding is not registered as a parameter, thus it is never updated by the optimizers.
+    pub fn sinusoidal(max_len: usize, d_model: usize) -> Self {
+        let table = Tensor::from_shape_fn((max_len, d_model), |(position, feature)| {
+            let exponent = (feature - feature % 2) as f32 / d_model as f32;
+            let angle = position as f32 / 10_000_f32.powf(exponent);
+            if feature % 2 == 0 {
+                angle.sin()
+            } else {
+                angle.cos()
+            }
+        });
+
+        Self {
+            encoding: Input::new(table).requires_grad(),
+            learned: false,
+        }
+    }
+
+    /// Creates a new learned PositionalEncoding.
     ///
-    /// * `padding_mode` - padding mode, it can be: [`Zero`], [`Constant`], [`Reflective`] or
-    /// [`Replicative`].
+    /// # Arguments
     ///
-    /// * `stride` - stride of the convolution, a 2-tuple for this two-dimensional case.
+    /// * `max_len` - maximum length of the sequences.
     ///
-    /// * `dilation` - controls the spacing between the kernel points, a 2-tuple for this
-    /// two-dimensional case.
+    /// * `d_model` - number of features of each element of the sequences.
     ///
-    /// The weight and the bias are initialized from *U(-k, k)* where
-    /// `k = (1. /(in_channels * kernel_w * kernel_h) as f32).sqrt()`.
-    pub fn new(
-        in_channels: usize,
-        out_channels: usize,
-        kernel_size: (usize, usize),
-        padding: (usize, usize),
-        padding_mode: Pad,
-        stride: (usize, usize),
-        dilation: (usize, usize),
-    ) -> Self {
-        let (kernel_h, kernel_w) = kernel_size;
-        let weight = Input::new(Tensor::zeros((
-            out_channels,
-            in_channels,
-            kernel_h,
-            kernel_w,
-        )))
-        .requires_grad();
-        let bias = Input::new(Tensor::zeros((out_channels, 1, 1))).requires_grad();
-
-        let k = (1. / (in_channels * kernel_h * kernel_w) as f32).sqrt();
-        init::uniform(&weight, -k, k);
-        init::uniform(&bias, -k, k);
+    /// The learnable encoding is of shape `(max_len, d_model)` and is initialized from *N(0, 1)*.
+    pub fn learned(max_len: usize, d_model: usize) -> Self {
+        let encoding = Input::new(Tensor::zeros((max_len, d_model))).requires_grad();
+        init::normal(&encoding, 0., 1.);
 
         Self {
-            padding,
-            padding_mode,
-            stride,
-            dilation,
-            weight,
-            bias,
+            encoding,
+            learned: true,
         }
     }
 
-    /// Computes a 2-dimensional convolution *(cross correlation)*.
+    /// Adds the encoding of the positions to `input`.
     ///
     /// # Arguments
     ///
-    /// `input` - the signal to convolve.
-    ///
-    /// The **input** must be of shape *(N, Cin, H, W)*
-    /// * **N** is the batch size
-    /// * **Cin** is the number of input channels
-    /// * **H** is the **height** of the input
-    /// * **W** is the **width** of the input
+    /// `input` - variable of shape *(..., L, d_model)*, where *L* is the length of the sequence.
     ///
-    /// The **kernel** must be of shape *(Cout, Cin, Hk, Wk)*
-    /// * **Cout** is the number of output channels
-    /// * **Cin** is the number of input channels
-    /// * **Hk** is the **height** of the kernel
-    /// * **Wk** is the **width** of the kernel
+    /// # Panics
     ///
-    /// The resulting output shape will be *(N, Cout, Hout, Wout)*
-    pub fn forward<I, T, U>(
+    /// If *L* is greater than `max_len`.
+    pub fn forward<T: ?Sized, U: ?Sized, D>(
         &self,
-        input: I,
-    ) -> VarDiff<impl Data<Dim = Ix4>, impl Gradient<Dim = Ix4>>
+        input: VarDiff<T, U>,
+    ) -> VarDiff<
+        impl Data<Dim = <D as DimMax<Ix2>>::Output>,
+        impl Gradient<Dim = <D as DimMax<Ix2>>::Output>,
+    >
     where
-        I: Convolve<I, Learnable<Ix4>, Pad>,
-        I::Output: Into<VarDiff<T, U>>,
-        T: Data<Dim = Ix4>,
-        U: Gradient<Dim = Ix4> + Overwrite,
+        T: Data<Dim = D> + 'static,
+        U: Gradient<Dim = D> + 'static,
+        D: Dimension + DimMax<Ix2> + 'static,
     {
-        let (stride_h, stride_w) = self.stride;
-        let (padding_h, padding_w) = self.padding;
-        let (dilation_h, dilation_w) = self.dilation;
+        let (len, max_len) = {
+            let shape = input.data().shape().to_vec();
+            (
+                shape[shape.len().saturating_sub(2)],
+                self.encoding.data().nrows(),
+            )
+        };
+        assert!(
+            len <= max_len,
+            "error: a sequence of length {} is longer than the {} positions of the encoding.",
+            len,
+            max_len
+        );
 
-        I::convolve(
-            input,
-            self.weight.clone(),
-            &[stride_h, stride_w],
-            &[dilation_h, dilation_w],
-            &[padding_h, padding_w],
-            self.padding_mode,
-        )
-        .into()
-            + self.bias.clone()
+        let positions = crate::indices(ndarray::Array::from_iter(0..len as i64));
+        input + positions.embedding(self.encoding.clone())
     }
 }
 
-impl<Pad: PaddingMode> Register for Conv2d<Pad> {
-    /// Registers the weight and the bias of this `Conv2d` instance.
+impl Register for PositionalEncoding {
+    /// Registers the encoding of this `PositionalEncoding` instance if it is learned.
     fn register_params(&self, params: &mut Vec<RawParam>) {
-        self.weight.register_params(params);
-        self.bias.register_params(params);
+        if self.learned {
+            self.encoding.register_params(params);
+        }
     }
 
     fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn non_trainable(&self) -> usize {
+        if self.learned {
+            0
+        } else {
+            self.encoding.data().len()
+        }
+    }
 }
 
-/// Applies a **spatial grouped convolution** over an input signal composed of several input planes.
+/// Applies **rotary position embeddings** to a sequence of queries or keys, as described in
+/// [RoFormer: Enhanced Transformer with Rotary Position Embedding](https://arxiv.org/abs/2104.09864).
+///
+/// Each pair of consecutive features of the element at position *m* is rotated by the angle
+/// *mθᵢ*, with *θᵢ = base⁻²ⁱᐟᵈ*. As rotations preserve dot products up to the difference of their
+/// angles, the attention scores between rotated queries and keys depend only on the relative
+/// positions of the elements.
+///
+/// The whole transform is carried out by a single node.
+///
+/// ```
+/// use neuronika::nn::RotaryEmbedding;
+///
+/// let rotary = RotaryEmbedding::new(10_000.);
+/// let query = neuronika::rand((2, 5, 8)).requires_grad();
+///
+/// let rotated = rotary.forward(query);
+/// rotated.forward();
+/// assert_eq!(rotated.data().shape(), &[2, 5, 8]);
+/// ```
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct GroupedConv2d<Pad: PaddingMode> {
-    pub padding: (usize, usize),
-    pub padding_mode: Pad,
-    pub stride: (usize, usize),
-    pub dilation: (usize, usize),
-    pub groups: usize,
-    pub weight: Learnable<Ix4>,
-    pub bias: Learnable<Ix3>,
+pub struct RotaryEmbedding {
+    pub base: f32,
 }
 
-impl<Pad: PaddingMode> GroupedConv2d<Pad> {
-    /// Creates a new GroupedConv2d.
+impl RotaryEmbedding {
+    /// Creates a new RotaryEmbedding.
     ///
     /// # Arguments
     ///
-    /// * `in_channels` - number of planes in the input signal.
-    ///
-    /// * `out_channels` - number of planes in the output signal.
-    ///
-    /// * `kernel_size` - size of the kernel, a 2-tuple  for this two-dimensional case.
+    /// `base` - base of the frequencies of the rotations, usually *10000*.
+    pub fn new(base: f32) -> Self {
+        Self { base }
+    }
+
+    /// Rotates the features of `input`.
     ///
-    /// * `padding` - padding to be applied to the input, a 2-tuple  for this two-dimensional case.
+    /// # Arguments
     ///
-    /// * `padding_mode` - padding mode, it can be: [`Zero`], [`Constant`], [`Reflective`] or
-    /// [`Replicative`].
+    /// `input` - variable of shape *(..., L, d)*, where *L* is the length of the sequence and *d*
+    /// is even.
     ///
-    /// * `stride` - stride of the convolution, a 2-tuple  for this two-dimensional case.
+    /// # Panics
     ///
-    /// * `dilation` - controls the spacing between the kernel points, a 2-tuple  for this
-    /// two-dimensional case.
+    /// If `input` has less than two dimensions or *d* is odd.
+    pub fn forward<T: ?Sized, U: ?Sized>(
+        &self,
+        input: VarDiff<T, U>,
+    ) -> VarDiff<impl Data<Dim = T::Dim>, impl Gradient<Dim = T::Dim>>
+    where
+        T: Data + 'static,
+        U: Gradient<Dim = T::Dim> + 'static,
+    {
+        let var = Var::from(RotaryNode::new(input.var.node, self.base), input.var.past);
+        VarDiff::from(RotaryBackward::new(input.node, self.base), input.past, var)
+    }
+}
+
+impl Register for RotaryEmbedding {
+    fn register_params(&self, _: &mut Vec<RawParam>) {}
+
+    fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+}
+
+/// A linear-chain **conditional random field**, as described in
+/// [Conditional Random Fields: Probabilistic Models for Segmenting and Labeling Sequence Data](https://repository.upenn.edu/cis_papers/159/).
+///
+/// It is usually stacked on top of a recurrent or attention encoder computing the *emission*
+/// scores of each tag at each position of a sequence, and models the dependencies between
+/// consecutive tags with learnable *transition* scores. The score of a sequence of tags
+/// *y₁, ..., yₗ* is
+///
+/// ```text
+/// s(y) = startᵧ₁ + Σₜ emissionsₜ,ᵧₜ + Σₜ transitionsᵧₜ₋₁,ᵧₜ + endᵧₗ
+/// ```
+///
+/// and its probability is its exponential normalized over all the possible sequences.
+///
+/// The layer is trained by maximizing the log-likelihood of the given tags, computed with the
+/// forward algorithm, and is used for inference by decoding the most likely tags with the
+/// Viterbi algorithm.
+///
+/// ```
+/// use neuronika::nn::CRF;
+///
+/// let crf = CRF::new(3);
+/// let emissions = neuronika::rand((2, 4, 3)).requires_grad();
+/// let tags = neuronika::indices(ndarray::array![[0, 1, 1, 2], [2, 0, 0, 0]]);
+///
+/// let loss = -crf.forward(emissions.clone(), tags, &[4, 2]).mean();
+/// loss.forward();
+/// loss.backward(1.);
+///
+/// let decoded = crf.decode(&emissions.data(), &[4, 2]);
+/// assert_eq!(decoded[0].len(), 4);
+/// assert_eq!(decoded[1].len(), 2);
+/// ```
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct CRF {
+    pub transitions: Learnable<Ix2>,
+    pub start_transitions: Learnable<Ix1>,
+    pub end_transitions: Learnable<Ix1>,
+}
+
+impl CRF {
+    /// Creates a new CRF.
     ///
-    /// * `groups` -  controls the connections between inputs and outputs. `in_channels` and
-    /// `out_channels` must both be divisible by groups.
+    /// # Arguments
     ///
-    /// For example:
-    /// * at `groups = 1`, all inputs are convolved to all outputs.
-    /// *  at `groups = 2`, the operation becomes equivalent to having two convolutional layers
-    /// side by side, each seeing half the input channels and producing half the output channels,
-    /// and both subsequently concatenated.
-    /// * at `groups = in_channels`, each input channel is convolved with its own set of filters.
+    /// `num_tags` - number of tags.
     ///
-    /// The weight and the bias of the layer are initialized from *U(-k, k)* where
-    /// `k = (groups /(in_channels * kernel_h * kernel_w) as f32).sqrt()`.
-    #[allow(clippy::too_many_arguments)]
-    pub fn new(
-        in_channels: usize,
-        out_channels: usize,
-        kernel_size: (usize, usize),
-        padding: (usize, usize),
-        padding_mode: Pad,
-        stride: (usize, usize),
-        dilation: (usize, usize),
-        groups: usize,
-    ) -> Self {
-        let (kernel_h, kernel_w) = kernel_size;
-        let weight = Input::new(Tensor::zeros((
-            out_channels,
-            in_channels,
-            kernel_h,
-            kernel_w,
-        )))
-        .requires_grad();
-        let bias = Input::new(Tensor::zeros((out_channels, 1, 1))).requires_grad();
-
-        let k = (groups as f32 / (in_channels * kernel_h * kernel_w) as f32).sqrt();
-        init::uniform(&weight, -k, k);
-        init::uniform(&bias, -k, k);
+    /// The learnable transitions are of shape `(num_tags, num_tags)`, the element at row *i* and
+    /// column *j* being the score of tag *j* following tag *i*, while the learnable start and
+    /// end transitions are of shape `num_tags`. They are all initialized from *U(-0.1, 0.1)*.
+    pub fn new(num_tags: usize) -> Self {
+        let transitions = Input::new(Tensor::zeros((num_tags, num_tags))).requires_grad();
+        let start_transitions = Input::new(Tensor::zeros(num_tags)).requires_grad();
+        let end_transitions = Input::new(Tensor::zeros(num_tags)).requires_grad();
+        init::uniform(&transitions, -0.1, 0.1);
+        init::uniform(&start_transitions, -0.1, 0.1);
+        init::uniform(&end_transitions, -0.1, 0.1);
 
         Self {
-            padding,
-            padding_mode,
-            stride,
-            dilation,
-            groups,
-            weight,
-            bias,
+            transitions,
+            start_transitions,
+            end_transitions,
         }
     }
 
-    /// Computes a 2-dimensional grouped convolution *(cross correlation)*.
+    /// Computes the log-likelihood of `tags` given `emissions`.
+    ///
+    /// The whole computation is carried out by a single node. The result is a variable of shape
+    /// *(B)* holding the log-likelihood of each sequence, whose negated mean is a suitable loss.
     ///
     /// # Arguments
     ///
-    /// `input` - the signal to convolve.
+    /// * `emissions` - variable of shape *(B, L, num_tags)* holding the emission scores of *B*
+    /// sequences padded to length *L*.
     ///
-    /// The **input** must be of shape *(N, Cin, H, W)*
-    /// * **N** is the batch size
-    /// * **Cin** is the number of input channels
-    /// * **H** is the **height** of the input
-    /// * **W** is the **width** of the input
+    /// * `tags` - index variable of shape *(B, L)* holding the tags of the sequences. The tags at
+    /// the padding positions are ignored.
     ///
-    /// The **kernel** must be of shape *(Cout, Cin, Hk, Wk)*
-    /// * **Cout** is the number of output channels
-    /// * **Cin** is the number of input channels
-    /// * **Hk** is the **height** of the kernel
-    /// * **Wk** is the **width** of the kernel
+    /// * `lengths` - lengths of the sequences.
     ///
-    /// The resulting output shape will be *(N, Cout, Hout, Wout)*
-    pub fn forward<I, T, U>(
+    /// # Panics
+    ///
+    /// If the shapes of `emissions` and `tags` do not match, if the number of lengths is not *B*
+    /// or a length is not between 1 and *L*, or, during the forward pass, if a tag is out of
+    /// range.
+    pub fn forward<T: ?Sized, U: ?Sized, I: ?Sized>(
         &self,
-        input: I,
-    ) -> VarDiff<impl Data<Dim = Ix4>, impl Gradient<Dim = Ix4>>
+        emissions: VarDiff<T, U>,
+        tags: IndexVar<I>,
+        lengths: &[usize],
+    ) -> VarDiff<impl Data<Dim = Ix1>, impl Gradient<Dim = Ix1>>
     where
-        I: ConvolveWithGroups<I, Learnable<Ix4>, Pad>,
-        I::Output: Into<VarDiff<T, U>>,
-        T: Data<Dim = Ix4>,
-        U: Gradient<Dim = Ix4>,
+        T: Data<Dim = Ix3> + 'static,
+        U: Gradient<Dim = Ix3> + 'static,
+        I: IndexData<Dim = Ix2> + 'static,
     {
-        let (stride_h, stride_w) = self.stride;
-        let (padding_h, padding_w) = self.padding;
-        let (dilation_h, dilation_w) = self.dilation;
+        let (transitions, start, end) = (
+            self.transitions.clone(),
+            self.start_transitions.clone(),
+            self.end_transitions.clone(),
+        );
 
-        I::convolve_with_groups(
-            input,
-            self.weight.clone(),
-            &[stride_h, stride_w],
-            &[dilation_h, dilation_w],
-            &[padding_h, padding_w],
-            self.padding_mode,
-            self.groups,
-        )
-        .into()
-            + self.bias.clone()
+        let mut past = emissions.var.past;
+        past.merge(transitions.var.past);
+        past.merge(start.var.past);
+        past.merge(end.var.past);
+        past.merge(tags.past);
+        let forward_node = CRFLogLikelihood::new(
+            emissions.var.node,
+            transitions.var.node,
+            start.var.node,
+            end.var.node,
+            tags.node,
+            lengths.to_vec(),
+        );
+        let var = Var::from(forward_node, past);
+
+        let mut past = emissions.past;
+        past.merge(transitions.past);
+        past.merge(start.past);
+        past.merge(end.past);
+        let backward_node = CRFLogLikelihoodBackward::new(
+            emissions.node,
+            transitions.node,
+            start.node,
+            end.node,
+            var.node.clone(),
+        );
+        VarDiff::from(backward_node, past, var)
+    }
+
+    /// Returns the most likely tags of each sequence given `emissions`, decoded with the Viterbi
+    /// algorithm.
+    ///
+    /// # Arguments
+    ///
+    /// * `emissions` - tensor of shape *(B, L, num_tags)* holding the emission scores of *B*
+    /// sequences padded to length *L*.
+    ///
+    /// * `lengths` - lengths of the sequences.
+    ///
+    /// # Panics
+    ///
+    /// If the number of lengths is not *B* or a length is greater than *L*.
+    pub fn decode(&self, emissions: &Array3<f32>, lengths: &[usize]) -> Vec<Vec<usize>> {
+        let (batch, max_len, _) = emissions.dim();
+        assert_eq!(
+            lengths.len(),
+            batch,
+            "error: {} lengths were given for a batch of {} sequences.",
+            lengths.len(),
+            batch
+        );
+        if let Some(len) = lengths.iter().find(|&&len| len > max_len) {
+            panic!(
+                "error: a sequence of length {} cannot be padded to length {}.",
+                len, max_len
+            );
+        }
+
+        let transitions = self.transitions.data();
+        let (start, end) = (self.start_transitions.data(), self.end_transitions.data());
+        emissions
+            .outer_iter()
+            .zip(lengths)
+            .map(|(emissions, &len)| {
+                if len == 0 {
+                    return Vec::new();
+                }
+
+                let mut scores = &emissions.row(0) + &*start;
+                let mut backpointers = Vec::with_capacity(len - 1);
+                for position in 1..len {
+                    let (best, pointers): (Vec<f32>, Vec<usize>) = transitions
+                        .columns()
+                        .into_iter()
+                        .zip(emissions.row(position))
+                        .map(|(incoming, emission)| {
+                            let (previous, score) = best_tag(
+                                scores
+                                    .iter()
+                                    .zip(incoming)
+                                    .map(|(score, transition)| score + transition),
+                            );
+                            (score + emission, previous)
+                        })
+                        .unzip();
+                    scores = Array::from(best);
+                    backpointers.push(pointers);
+                }
+
+                let mut tag = best_tag((&scores + &*end).into_iter()).0;
+                let mut tags = vec![tag];
+                for pointers in backpointers.iter().rev() {
+                    tag = pointers[tag];
+                    tags.push(tag);
+                }
+                tags.reverse();
+                tags
+            })
+            .collect()
     }
 }
 
-impl<Pad: PaddingMode> Register for GroupedConv2d<Pad> {
-    /// Registers the weight and the bias of this `GroupedConv2d` instance.
+/// Returns the position and the value of the greatest of `scores`, the first one in case of ties.
+fn best_tag<I: Iterator<Item = f32>>(scores: I) -> (usize, f32) {
+    scores
+        .enumerate()
+        .fold((0, f32::NEG_INFINITY), |best, (tag, score)| {
+            if score > best.1 {
+                (tag, score)
+            } else {
+                best
+            }
+        })
+}
+
+impl Register for CRF {
+    /// Registers the transitions, the start transitions and the end transitions of this `CRF`
+    /// instance.
     fn register_params(&self, params: &mut Vec<RawParam>) {
-        self.weight.register_params(params);
-        self.bias.register_params(params);
+        self.transitions.register_params(params);
+        self.start_transitions.register_params(params);
+        self.end_transitions.register_params(params);
     }
 
     fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        Rc::new(|input_shape| input_shape[..1].to_vec())
+    }
 }
 
-/// Applies a **volumetric convolution** over an input signal composed of several input planes.
+/// Builds the weighted adjacency matrix, of shape *(num_nodes, num_nodes)*, of the graph whose
+/// edges are described by `edge_index`, adding a self-loop to the nodes that lack one.
 ///
-/// See also [`GroupedConv3d`].
+/// The element at row *i* and column *j* counts the edges going from node *j* to node *i*, so
+/// that each row collects the messages received by a node.
+///
+/// # Panics
+///
+/// If `edge_index` has not two rows or a node is out of range.
+fn adjacency(edge_index: &Array2<i64>, num_nodes: usize) -> Tensor<Ix2> {
+    assert_eq!(
+        edge_index.nrows(),
+        2,
+        "error: the edge index must have two rows, but it has {}.",
+        edge_index.nrows()
+    );
+
+    let mut adjacency = Tensor::zeros((num_nodes, num_nodes));
+    for edge in edge_index.columns() {
+        let (source, target) = (edge[0], edge[1]);
+        assert!(
+            (0..num_nodes as i64).contains(&source) && (0..num_nodes as i64).contains(&target),
+            "error: the edge from {} to {} is out of range for {} nodes.",
+            source,
+            target,
+            num_nodes
+        );
+        adjacency[[target as usize, source as usize]] += 1.;
+    }
+    adjacency
+        .diag_mut()
+        .mapv_inplace(|loops: f32| loops.max(1.));
+
+    adjacency
+}
+
+/// Applies a **graph convolution** over the features of the nodes of a graph, as described in
+/// [Semi-Supervised Classification with Graph Convolutional Networks](https://arxiv.org/abs/1609.02907).
+///
+/// ```text
+/// X' = D̂⁻¹ᐟ²ÂD̂⁻¹ᐟ²XAᵀ + b
+/// ```
+///
+/// where *Â* is the adjacency matrix of the graph with self-loops added and *D̂* is the diagonal
+/// matrix of the degrees of its nodes.
+///
+/// The graph is described by an *edge index* of shape *(2, E)*, each of its columns holding the
+/// source and the target node of an edge. The messages flow from the sources to the targets.
+///
+/// ```
+/// use neuronika::nn::GraphConv;
+///
+/// let conv = GraphConv::new(4, 8);
+/// let features = neuronika::rand((3, 4)).requires_grad();
+/// let edge_index = ndarray::array![[0, 1, 1, 2], [1, 0, 2, 1]];
+///
+/// let out = conv.forward(features, &edge_index);
+/// out.forward();
+/// assert_eq!(out.data().shape(), &[3, 8]);
+/// ```
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct Conv3d<Pad: PaddingMode> {
-    pub padding: (usize, usize, usize),
-    pub padding_mode: Pad,
-    pub stride: (usize, usize, usize),
-    pub dilation: (usize, usize, usize),
-    pub weight: Learnable<Ix5>,
-    pub bias: Learnable<Ix4>,
+pub struct GraphConv {
+    pub weight: Learnable<Ix2>,
+    pub bias: Learnable<Ix1>,
 }
 
-impl<Pad: PaddingMode> Conv3d<Pad> {
-    /// Creates a new Conv3d.
+impl GraphConv {
+    /// Creates a new GraphConv.
     ///
     /// # Arguments
     ///
-    /// * `in_channels` - number of planes in the input signal.
-    ///
-    /// * `out_channels` - number of planes in the output signal.
-    ///
-    /// * `kernel_size` - size of the kernel, a 3-tuple for this three-dimensional case.
-    ///
-    /// * `padding` - padding to be applied to the input, a 3-tuple for this three-dimensional case.
-    ///
-    /// * `padding_mode` - padding mode, it can be: [`Zero`], [`Constant`], [`Reflective`] or
-    /// [`Replicative`].
-    ///
-    /// * `stride` - stride of the convolution, a 3-tuple for this three-dimensional case.
+    /// * `in_features` - number of features of each node.
     ///
-    /// * `dilation` - controls the spacing between the kernel points, a 3-tuple for this
-    /// three-dimensional case.
+    /// * `out_features` - number of features of each node of the output.
     ///
-    /// The weight and the bias of the layer are initialized from *U(-k, k)* where
-    /// `k = (1. /(in_channels * kernel_d * kernel_w * kernel_h) as f32).sqrt()`.
-    pub fn new(
-        in_channels: usize,
-        out_channels: usize,
-        kernel_size: (usize, usize, usize),
-        padding: (usize, usize, usize),
-        padding_mode: Pad,
-        stride: (usize, usize, usize),
-        dilation: (usize, usize, usize),
-    ) -> Self {
-        let (kernel_d, kernel_h, kernel_w) = kernel_size;
-        let weight = Input::new(Tensor::zeros((
-            out_channels,
-            in_channels,
-            kernel_d,
-            kernel_h,
-            kernel_w,
-        )))
-        .requires_grad();
-        let bias = Input::new(Tensor::zeros((out_channels, 1, 1, 1))).requires_grad();
-
-        let k = (1. / (in_channels * kernel_d * kernel_h * kernel_w) as f32).sqrt();
-        init::uniform(&weight, -k, k);
-        init::uniform(&bias, -k, k);
+    /// The learnable weight is of shape `(out_features, in_features)` and is initialized with
+    /// [`init::xavier_uniform()`], while the learnable bias is of shape `out_features` and is
+    /// initialized to zero.
+    pub fn new(in_features: usize, out_features: usize) -> Self {
+        let weight = Input::new(Tensor::zeros((out_features, in_features))).requires_grad();
+        let bias = Input::new(Tensor::zeros(out_features)).requires_grad();
+        init::xavier_uniform(&weight, 1.);
 
-        Self {
-            padding,
-            padding_mode,
-            stride,
-            dilation,
-            weight,
-            bias,
-        }
+        Self { weight, bias }
     }
 
-    /// Computes a 3-dimensional convolution *(cross correlation)*.
+    /// Convolves the features of the nodes of the graph described by `edge_index`.
     ///
     /// # Arguments
     ///
-    /// `input` - signal to convolve.
+    /// * `input` - variable of shape *(N, in_features)* holding the features of the *N* nodes.
     ///
-    /// The **input** must be of shape *(N, Cin, D, H, W)*
-    /// * **N** is the batch size
-    /// * **Cin** is the number of input channels
-    /// * **D** is the **depth** of the input
-    /// * **H** is the **height** of the input
-    /// * **W** is the **width** of the input
+    /// * `edge_index` - edge index of shape *(2, E)*.
     ///
-    /// The **kernel** must be of shape *(Cout, Cin, Dk,  Hk, Wk)*
-    /// * **Cout** is the number of output channels
-    /// * **Cin** is the number of input channels
-    /// * **Dk** is the **depth** of the kernel
-    /// * **Hk** is the **height** of the kernel
-    /// * **Wk** is the **width** of the kernel
+    /// The output's shape will be *(N, out_features)*.
     ///
-    /// The resulting output shape will be *(N, Cout, Dout, Hout, Wout)*
-    pub fn forward<I, T, U>(
+    /// # Panics
+    ///
+    /// If `edge_index` has not two rows or a node is out of range.
+    pub fn forward<T: ?Sized, U: ?Sized>(
         &self,
-        input: I,
-    ) -> VarDiff<impl Data<Dim = Ix5>, impl Gradient<Dim = Ix5>>
+        input: VarDiff<T, U>,
+        edge_index: &Array2<i64>,
+    ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
     where
-        I: Convolve<I, Learnable<Ix5>, Pad>,
-        I::Output: Into<VarDiff<T, U>>,
-        T: Data<Dim = Ix5>,
-        U: Gradient<Dim = Ix5>,
+        T: Data<Dim = Ix2> + 'static,
+        U: Gradient<Dim = Ix2> + 'static,
     {
-        let (stride_d, stride_h, stride_w) = self.stride;
-        let (padding_d, padding_h, padding_w) = self.padding;
-        let (dilation_d, dilation_h, dilation_w) = self.dilation;
+        let mut adjacency = adjacency(edge_index, input.data().nrows());
+        let scale = adjacency
+            .sum_axis(ndarray::Axis(1))
+            .mapv(|degree| degree.sqrt().recip());
+        adjacency *= &scale.view().insert_axis(ndarray::Axis(1));
+        adjacency *= &scale;
 
-        I::convolve(
-            input,
-            self.weight.clone(),
-            &[stride_d, stride_h, stride_w],
-            &[dilation_d, dilation_h, dilation_w],
-            &[padding_d, padding_h, padding_w],
-            self.padding_mode,
-        )
-        .into()
-            + self.bias.clone()
+        Input::new(adjacency).mm(input.mm_t(self.weight.clone())) + self.bias.clone()
     }
 }
 
-impl<Pad: PaddingMode> Register for Conv3d<Pad> {
-    /// Registers the weight and the bias of this `Conv3d` instance.
+impl Register for GraphConv {
+    /// Registers the weight and the bias of this `GraphConv` instance.
     fn register_params(&self, params: &mut Vec<RawParam>) {
         self.weight.register_params(params);
         self.bias.register_params(params);
     }
 
     fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        let shape = self.weight.data().raw_dim();
+        linear_shape("GraphConv", shape[1], shape[0])
+    }
 }
 
-/// Applies a **grouped volumetric convolution** over an input signal composed of several input
-/// planes.
+/// Applies a **graph attention** layer over the features of the nodes of a graph, as described in
+/// [Graph Attention Networks](https://arxiv.org/abs/1710.10903).
+///
+/// ```text
+/// xᵢ' = ‖ₖ Σⱼ αᵢⱼᵏWᵏxⱼ + b
+///
+/// αᵢⱼᵏ = Softmaxⱼ(LeakyReLU(aₜᵏ·Wᵏxᵢ + aₛᵏ·Wᵏxⱼ))
+/// ```
+///
+/// where *j* ranges over the neighbours of node *i*, itself included, and *‖* denotes the
+/// concatenation of the heads. The negative slope of the leaky ReLU is *0.2*.
+///
+/// The graph is described by an *edge index* of shape *(2, E)*, each of its columns holding the
+/// source and the target node of an edge. The messages flow from the sources to the targets.
+///
+/// ```
+/// use neuronika::nn::GATConv;
+///
+/// let conv = GATConv::new(4, 8, 2);
+/// let features = neuronika::rand((3, 4)).requires_grad();
+/// let edge_index = ndarray::array![[0, 1, 1, 2], [1, 0, 2, 1]];
+///
+/// let out = conv.forward(features, &edge_index);
+/// out.forward();
+/// assert_eq!(out.data().shape(), &[3, 16]);
+/// ```
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct GroupedConv3d<Pad: PaddingMode> {
-    pub padding: (usize, usize, usize),
-    pub padding_mode: Pad,
-    pub stride: (usize, usize, usize),
-    pub dilation: (usize, usize, usize),
-    pub groups: usize,
-    pub weight: Learnable<Ix5>,
-    pub bias: Learnable<Ix4>,
+pub struct GATConv {
+    pub weight: Learnable<Ix2>,
+    pub att_src: Vec<Learnable<Ix2>>,
+    pub att_dst: Vec<Learnable<Ix2>>,
+    pub bias: Learnable<Ix1>,
 }
 
-impl<Pad: PaddingMode> GroupedConv3d<Pad> {
-    /// Creates a new GroupedConv3d.
+impl GATConv {
+    /// Creates a new GATConv.
     ///
     /// # Arguments
     ///
-    /// * `in_channels` - number of planes in the input signal.
-    ///
-    /// * `out_channels` - number of planes in the output signal.
-    ///
-    /// * `kernel_size` - size of the kernel, a 3-tuple  for this three-dimensional case.
-    ///
-    /// * `padding` - padding to be applied to the input, a 3-tuple  for this three-dimensional case.
-    ///
-    /// * `padding_mode` - padding mode, it can be: [`Zero`], [`Constant`], [`Reflective`] or
-    /// [`Replicative`].
-    ///
-    /// * `stride` - stride of the convolution, a 3-tuple  for this three-dimensional case.
+    /// * `in_features` - number of features of each node.
     ///
-    /// * `dilation` - controls the spacing between the kernel points, a 3-tuple  for this
-    /// three-dimensional case.
-    ///
-    /// * `groups` -  controls the connections between inputs and outputs. `in_channels` and
-    /// `out_channels` must both be divisible by groups.
+    /// * `out_features` - number of features of each node computed by each head.
     ///
-    /// For example:
-    /// * at `groups = 1`, all inputs are convolved to all outputs.
-    /// *  at `groups = 2`, the operation becomes equivalent to having two convolutional layers
-    /// side by side, each seeing half the input channels and producing half the output channels,
-    /// and both subsequently concatenated.
-    /// * at `groups = in_channels`, each input channel is convolved with its own set of filters.
+    /// * `num_heads` - number of attention heads, whose outputs are concatenated.
     ///
-    /// The weight and the bias are initialized from *U(-k, k)* where
-    /// `k = (groups /(in_channels * kernel_d * kernel_h * kernel_w) as f32).sqrt()`.
-    #[allow(clippy::too_many_arguments)]
-    pub fn new(
-        in_channels: usize,
-        out_channels: usize,
-        kernel_size: (usize, usize, usize),
-        padding: (usize, usize, usize),
-        padding_mode: Pad,
-        stride: (usize, usize, usize),
-        dilation: (usize, usize, usize),
-        groups: usize,
-    ) -> Self {
-        let (kernel_d, kernel_h, kernel_w) = kernel_size;
-        let weight = Input::new(Tensor::zeros((
-            out_channels,
-            in_channels,
-            kernel_d,
-            kernel_h,
-            kernel_w,
-        )))
-        .requires_grad();
-        let bias = Input::new(Tensor::zeros((out_channels, 1, 1, 1))).requires_grad();
-
-        let k = (1. / (in_channels * kernel_d * kernel_h * kernel_w) as f32).sqrt();
-        init::uniform(&weight, -k, k);
-        init::uniform(&bias, -k, k);
+    /// The learnable weight is of shape `(num_heads * out_features, in_features)`, and each head
+    /// has a learnable source and target attention vector of shape `(out_features, 1)`. They are
+    /// initialized with [`init::xavier_uniform()`], while the learnable bias, of shape
+    /// `num_heads * out_features`, is initialized to zero.
+    pub fn new(in_features: usize, out_features: usize, num_heads: usize) -> Self {
+        let weight =
+            Input::new(Tensor::zeros((num_heads * out_features, in_features))).requires_grad();
+        let bias = Input::new(Tensor::zeros(num_heads * out_features)).requires_grad();
+        init::xavier_uniform(&weight, 1.);
+        let attention = || {
+            (0..num_heads)
+                .map(|_| {
+                    let attention = Input::new(Tensor::zeros((out_features, 1))).requires_grad();
+                    init::xavier_uniform(&attention, 1.);
+                    attention
+                })
+                .collect()
+        };
 
         Self {
-            padding,
-            padding_mode,
-            stride,
-            dilation,
-            groups,
             weight,
+            att_src: attention(),
+            att_dst: attention(),
             bias,
         }
     }
 
-    /// Computes a 3-dimensional grouped convolution *(cross correlation)*.
+    /// Computes the attention of each node over its neighbours in the graph described by
+    /// `edge_index`.
     ///
-    /// `input` - the signal to convolve.
+    /// # Arguments
     ///
-    /// The **input** must be of shape *(N, Cin, D, H, W)*
-    /// * **N** is the batch size
-    /// * **Cin** is the number of input channels
-    /// * **D** is the **depth** of the input
-    /// * **H** is the **height** of the input
-    /// * **W** is the **width** of the input
+    /// * `input` - variable of shape *(N, in_features)* holding the features of the *N* nodes.
     ///
-    /// The **kernel** must be of shape *(Cout, Cin, Dk,  Hk, Wk)*
-    /// * **Cout** is the number of output channels
-    /// * **Cin** is the number of input channels
-    /// * **Dk** is the **depth** of the kernel
-    /// * **Hk** is the **height** of the kernel
-    /// * **Wk** is the **width** of the kernel
+    /// * `edge_index` - edge index of shape *(2, E)*.
     ///
-    /// The resulting output shape will be *(N, Cout, Dout, Hout, Wout)*
-    pub fn forward<I, T, U>(
+    /// The output's shape will be *(N, num_heads * out_features)*.
+    ///
+    /// # Panics
+    ///
+    /// If `edge_index` has not two rows or a node is out of range.
+    pub fn forward<T: ?Sized, U: ?Sized>(
         &self,
-        input: I,
-    ) -> VarDiff<impl Data<Dim = Ix5>, impl Gradient<Dim = Ix5>>
+        input: VarDiff<T, U>,
+        edge_index: &Array2<i64>,
+    ) -> VarDiff<impl Data<Dim = Ix2>, impl Gradient<Dim = Ix2>>
     where
-        I: ConvolveWithGroups<I, Learnable<Ix5>, Pad>,
-        I::Output: Into<VarDiff<T, U>>,
-        T: Data<Dim = Ix5>,
-        U: Gradient<Dim = Ix5>,
+        T: Data<Dim = Ix2> + 'static,
+        U: Gradient<Dim = Ix2> + 'static,
     {
-        let (stride_d, stride_h, stride_w) = self.stride;
-        let (padding_d, padding_h, padding_w) = self.padding;
-        let (dilation_d, dilation_h, dilation_w) = self.dilation;
+        let num_nodes = input.data().nrows();
+        let mask = Input::new(adjacency(edge_index, num_nodes).mapv(|edges| {
+            if edges > 0. {
+                0.
+            } else {
+                f32::NEG_INFINITY
+            }
+        }));
+        let out_features = self.weight.data().nrows() / self.att_src.len();
 
-        I::convolve_with_groups(
-            input,
-            self.weight.clone(),
-            &[stride_d, stride_h, stride_w],
-            &[dilation_d, dilation_h, dilation_w],
-            &[padding_d, padding_h, padding_w],
-            self.padding_mode,
-            self.groups,
+        let projected = input.mm_t(self.weight.clone());
+        let heads: Vec<_> = itertools::izip!(
+            projected.chunks((num_nodes, out_features)),
+            &self.att_src,
+            &self.att_dst
         )
-        .into()
-            + self.bias.clone()
+        .map(|(features, att_src, att_dst)| {
+            let scores =
+                features.clone().mm(att_dst.clone()) + features.clone().mm(att_src.clone()).t();
+            let scores = crate::maximum(scores.clone(), scores * 0.2) + mask.clone();
+            scores.softmax(1).mm(features).into_dyn()
+        })
+        .collect();
+
+        VarDiff::cat(&heads, 1) + self.bias.clone()
     }
 }
 
-impl<Pad: PaddingMode> Register for GroupedConv3d<Pad> {
-    /// Registers the weight and the bias of this `GroupedConv3d` instance.
+impl Register for GATConv {
+    /// Registers the weight, the attention vectors and the bias of this `GATConv` instance.
     fn register_params(&self, params: &mut Vec<RawParam>) {
         self.weight.register_params(params);
+        self.att_src
+            .iter()
+            .chain(&self.att_dst)
+            .for_each(|attention| attention.register_params(params));
         self.bias.register_params(params);
     }
 
     fn register_status(&mut self, _: Rc<Cell<bool>>) {}
+
+    fn shape_inference(&self) -> ShapeInference {
+        let shape = self.weight.data().raw_dim();
+        linear_shape("GATConv", shape[1], shape[0])
+    }
 }
@@ -0,0 +1,376 @@
+//! Pruning of neural components.
+//!
+//! Pruning removes the less important weights of a trained model by setting them to zero, so
+//! that the model can be compressed. Each pruning method returns a [`Mask`] that holds the
+//! parameter it was computed for together with a tensor of zeros and ones of the same shape.
+//!
+//! * [`l1_unstructured`] - Prunes the individual entries having the smallest magnitude.
+//!
+//! * [`ln_structured`] - Prunes the entire slices along an axis, such as the output channels of a
+//! convolution, having the smallest L2 norm.
+//!
+//! Masks are persistent: a [`Pruner`] collects them and wraps the optimizer of the model into a
+//! [`Pruned`] one, which masks the gradients before every step and re-applies the masks to the
+//! data of the parameters after it. The pruned entries thus keep on being zero in every
+//! subsequent forward pass, whether the optimizer is stepped by hand or by a
+//! [`Trainer`](crate::trainer::Trainer).
+//!
+//! ```
+//! use neuronika::nn::{prune, Linear};
+//! use neuronika::optim::{Optimizer, L2, SGD};
+//!
+//! let lin = Linear::new(10, 4);
+//!
+//! let mut pruner = prune::Pruner::new();
+//! pruner.push(prune::l1_unstructured(&lin.weight, 0.5));
+//! pruner.push(prune::ln_structured(&lin.bias, 0.25, 0));
+//!
+//! assert_eq!(pruner.sparsity(), 21. / 44.);
+//!
+//! let loss = lin.forward(neuronika::rand((2, 10))).sum();
+//! let optim = pruner.wrap(SGD::new(loss.parameters(), 0.1, L2::new(0.)));
+//!
+//! loss.forward();
+//! loss.backward(1.);
+//! optim.step();
+//!
+//! assert_eq!(optim.pruner().sparsity(), 21. / 44.);
+//! ```
+use super::Learnable;
+use crate::{optim::Optimizer, trainer::Callback};
+use ndarray::{Array, Axis, Dimension, RemoveAxis};
+use std::marker::PhantomData;
+
+/// Checks that `amount` is a valid fraction and returns the number of the `len` items to prune.
+fn to_prune(amount: f32, len: usize) -> usize {
+    assert!(
+        (0. ..=1.).contains(&amount),
+        "error: {} is not a valid pruning amount.",
+        amount
+    );
+
+    (amount * len as f32).round() as usize
+}
+
+/// Returns the indices of `scores` sorted in ascending order of score.
+fn ranking(scores: &[f32]) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..scores.len()).collect();
+    indices.sort_by(|&i, &j| scores[i].total_cmp(&scores[j]));
+    indices
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Mask ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// The pruning mask of a parameter.
+///
+/// The mask shares the data of the parameter it was computed for, and is applied to it upon
+/// creation.
+pub struct Mask<D: Dimension + 'static> {
+    param: Learnable<D>,
+    mask: Array<f32, D>,
+}
+
+impl<D: Dimension + 'static> Mask<D> {
+    /// Creates a custom mask for `param`.
+    ///
+    /// # Arguments
+    ///
+    /// * `param` - parameter to prune.
+    ///
+    /// * `mask` - tensor having the same shape of `param`, whose zero entries mark the entries of
+    /// `param` to prune.
+    ///
+    /// # Panics
+    ///
+    /// If the shapes of `param` and `mask` differ.
+    pub fn new(param: &Learnable<D>, mask: Array<f32, D>) -> Self {
+        assert_eq!(
+            param.data().shape(),
+            mask.shape(),
+            "error: the mask must have the same shape of the parameter."
+        );
+
+        let mask = mask.mapv(|el| if el == 0. { 0. } else { 1. });
+        let mask = Self {
+            param: param.clone(),
+            mask,
+        };
+        mask.apply();
+        mask
+    }
+
+    /// Returns the tensor of zeros and ones of this mask.
+    pub fn mask(&self) -> &Array<f32, D> {
+        &self.mask
+    }
+
+    /// Sets the pruned entries of the parameter to zero.
+    pub fn apply(&self) {
+        *self.param.data_mut() *= &self.mask;
+    }
+
+    /// Sets the gradient of the pruned entries of the parameter to zero.
+    pub fn apply_to_grad(&self) {
+        *self.param.grad_mut() *= &self.mask;
+    }
+
+    /// Returns the number of pruned entries.
+    pub fn pruned(&self) -> usize {
+        self.mask.iter().filter(|el| **el == 0.).count()
+    }
+
+    /// Returns the number of entries of the parameter.
+    pub fn len(&self) -> usize {
+        self.mask.len()
+    }
+
+    /// Returns `true` if the parameter has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.mask.is_empty()
+    }
+
+    /// Returns the fraction of pruned entries.
+    pub fn sparsity(&self) -> f32 {
+        self.pruned() as f32 / self.len().max(1) as f32
+    }
+}
+
+/// Prunes the `amount` fraction of the entries of `param` having the smallest absolute value.
+///
+/// Entries that are already zero, such as the ones pruned by a previous mask, are the first to
+/// be selected, thus pruning can be performed iteratively.
+///
+/// # Arguments
+///
+/// * `param` - parameter to prune.
+///
+/// * `amount` - fraction of the entries to prune, the number of entries is rounded to the
+/// nearest integer.
+///
+/// # Panics
+///
+/// If `amount` doesn't lie in *[0, 1]*.
+pub fn l1_unstructured<D: Dimension + 'static>(param: &Learnable<D>, amount: f32) -> Mask<D> {
+    let data = param.data();
+    let scores: Vec<f32> = data.iter().map(|el| el.abs()).collect();
+
+    let mut mask = Array::ones(data.raw_dim());
+    let pruned = ranking(&scores);
+    let mut entries: Vec<&mut f32> = mask.iter_mut().collect();
+    for &index in &pruned[..to_prune(amount, scores.len())] {
+        *entries[index] = 0.;
+    }
+    drop(data);
+
+    Mask::new(param, mask)
+}
+
+/// Prunes the `amount` fraction of the slices of `param` along `axis` having the smallest L2
+/// norm.
+///
+/// With `axis` set to *0* entire output channels of a convolution, or entire output features of
+/// a linear layer, are removed.
+///
+/// # Arguments
+///
+/// * `param` - parameter to prune.
+///
+/// * `amount` - fraction of the slices to prune, the number of slices is rounded to the nearest
+/// integer.
+///
+/// * `axis` - axis along which the slices are taken.
+///
+/// # Panics
+///
+/// If `amount` doesn't lie in *[0, 1]* or if `axis` is out of bounds.
+pub fn ln_structured<D: RemoveAxis + 'static>(
+    param: &Learnable<D>,
+    amount: f32,
+    axis: usize,
+) -> Mask<D> {
+    let data = param.data();
+    assert!(
+        axis < data.ndim(),
+        "error: axis {} is out of bounds for a parameter with {} dimensions.",
+        axis,
+        data.ndim()
+    );
+
+    let scores: Vec<f32> = data
+        .axis_iter(Axis(axis))
+        .map(|slice| slice.iter().map(|el| el * el).sum::<f32>().sqrt())
+        .collect();
+
+    let mut mask = Array::ones(data.raw_dim());
+    for &index in &ranking(&scores)[..to_prune(amount, scores.len())] {
+        mask.index_axis_mut(Axis(axis), index).fill(0.);
+    }
+    drop(data);
+
+    Mask::new(param, mask)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Pruner ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// A mask of any dimensionality.
+trait AnyMask {
+    fn apply(&self);
+
+    fn apply_to_grad(&self);
+
+    fn pruned(&self) -> usize;
+
+    fn len(&self) -> usize;
+}
+
+impl<D: Dimension + 'static> AnyMask for Mask<D> {
+    fn apply(&self) {
+        Mask::apply(self)
+    }
+
+    fn apply_to_grad(&self) {
+        Mask::apply_to_grad(self)
+    }
+
+    fn pruned(&self) -> usize {
+        Mask::pruned(self)
+    }
+
+    fn len(&self) -> usize {
+        Mask::len(self)
+    }
+}
+
+/// A collection of pruning masks.
+///
+/// Applying the pruner applies all of its masks. To keep them applied while the model is trained,
+/// [`.wrap()`](Pruner::wrap) its optimizer. As a [`Callback`] the pruner applies them after every
+/// training batch.
+#[derive(Default)]
+pub struct Pruner {
+    masks: Vec<Box<dyn AnyMask>>,
+}
+
+impl Pruner {
+    /// Creates an empty pruner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `mask` to the pruner.
+    pub fn push<D: Dimension + 'static>(&mut self, mask: Mask<D>) {
+        self.masks.push(Box::new(mask));
+    }
+
+    /// Sets the pruned entries of all the parameters to zero.
+    pub fn apply(&self) {
+        self.masks.iter().for_each(|mask| mask.apply());
+    }
+
+    /// Sets the gradient of the pruned entries of all the parameters to zero.
+    pub fn apply_to_grad(&self) {
+        self.masks.iter().for_each(|mask| mask.apply_to_grad());
+    }
+
+    /// Wraps `optimizer`, so that the masks of `self` are enforced at every step.
+    pub fn wrap<'a, O: Optimizer<'a>>(self, optimizer: O) -> Pruned<'a, O> {
+        Pruned {
+            optimizer,
+            pruner: self,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of masks.
+    pub fn len(&self) -> usize {
+        self.masks.len()
+    }
+
+    /// Returns `true` if the pruner has no masks.
+    pub fn is_empty(&self) -> bool {
+        self.masks.is_empty()
+    }
+
+    /// Returns the fraction of pruned entries of each mask, in insertion order.
+    pub fn sparsities(&self) -> Vec<f32> {
+        self.masks
+            .iter()
+            .map(|mask| mask.pruned() as f32 / mask.len().max(1) as f32)
+            .collect()
+    }
+
+    /// Returns the fraction of pruned entries over all the parameters.
+    pub fn sparsity(&self) -> f32 {
+        let pruned: usize = self.masks.iter().map(|mask| mask.pruned()).sum();
+        let len: usize = self.masks.iter().map(|mask| mask.len()).sum();
+        pruned as f32 / len.max(1) as f32
+    }
+}
+
+impl Callback for Pruner {
+    /// Re-applies the masks after the optimizer has updated the parameters.
+    fn on_batch_end(&mut self, _epoch: usize, _batch: usize, _loss: f32) {
+        self.apply()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Pruned ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// An optimizer that enforces the masks of a [`Pruner`].
+///
+/// Before each step the gradients of the pruned entries are set to zero, so that the state of
+/// the optimizer, such as momentum, is not updated for them, and after the step the masks are
+/// re-applied to the data of the parameters. It is created with [`Pruner::wrap`].
+pub struct Pruned<'a, O: Optimizer<'a>> {
+    optimizer: O,
+    pruner: Pruner,
+    _marker: PhantomData<&'a ()>,
+}
+
+impl<'a, O: Optimizer<'a>> Pruned<'a, O> {
+    /// Returns the pruner whose masks are enforced.
+    pub fn pruner(&self) -> &Pruner {
+        &self.pruner
+    }
+
+    /// Returns the wrapped optimizer.
+    pub fn optimizer(&self) -> &O {
+        &self.optimizer
+    }
+
+    /// Returns the wrapped optimizer and the pruner.
+    pub fn into_inner(self) -> (O, Pruner) {
+        (self.optimizer, self.pruner)
+    }
+}
+
+impl<'a, O: Optimizer<'a>> Optimizer<'a> for Pruned<'a, O> {
+    type ParamRepr = O::ParamRepr;
+
+    fn step(&self) {
+        self.pruner.apply_to_grad();
+        self.optimizer.step();
+        self.pruner.apply();
+    }
+
+    fn zero_grad(&self) {
+        self.optimizer.zero_grad()
+    }
+
+    fn get_lr(&self) -> f32 {
+        self.optimizer.get_lr()
+    }
+
+    fn set_lr(&self, lr: f32) {
+        self.optimizer.set_lr(lr)
+    }
+}
+
+#[cfg(test)]
+mod test;
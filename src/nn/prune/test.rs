@@ -0,0 +1,111 @@
+use super::{l1_unstructured, ln_structured, Mask, Pruner};
+use crate::{
+    optim::{Adam, Optimizer, L2, SGD},
+    trainer::Callback,
+};
+use ndarray::array;
+
+#[test]
+fn unstructured() {
+    let param = crate::from_ndarray(array![[1., -5., 0.5], [-2., 4., 3.]]).requires_grad();
+
+    let mask = l1_unstructured(&param, 0.5);
+    assert_eq!(mask.mask(), &array![[0., 1., 0.], [0., 1., 1.]]);
+    assert_eq!(*param.data(), array![[0., -5., 0.], [0., 4., 3.]]);
+    assert_eq!(mask.pruned(), 3);
+    assert_eq!(mask.sparsity(), 0.5);
+
+    // Pruning is iterative, the entries already pruned are the first to be selected.
+    let mask = l1_unstructured(&param, 4. / 6.);
+    assert_eq!(mask.mask(), &array![[0., 1., 0.], [0., 1., 0.]]);
+}
+
+#[test]
+fn structured() {
+    let param = crate::from_ndarray(array![[1., 1.], [3., 0.], [-1., -2.]]).requires_grad();
+
+    let rows = ln_structured(&param, 0.34, 0);
+    assert_eq!(rows.mask(), &array![[0., 0.], [1., 1.], [1., 1.]]);
+    assert_eq!(*param.data(), array![[0., 0.], [3., 0.], [-1., -2.]]);
+
+    let columns = ln_structured(&param, 0.5, 1);
+    assert_eq!(columns.mask(), &array![[1., 0.], [1., 0.], [1., 0.]]);
+}
+
+#[test]
+fn persistence() {
+    let param = crate::from_ndarray(array![1., -2., 3., 4.]).requires_grad();
+    let loss = (param.clone() * 2.).sum();
+    let optimizer = SGD::new(param.parameters(), 0.5, crate::optim::L2::new(0.));
+
+    let mut pruner = Pruner::new();
+    pruner.push(Mask::new(&param, array![1., 0., 1., 0.]));
+    assert_eq!(pruner.len(), 1);
+
+    loss.forward();
+    loss.backward(1.);
+    optimizer.step();
+    assert_eq!(*param.data(), array![0., -1., 2., -1.]);
+
+    pruner.on_batch_end(0, 0, 0.);
+    assert_eq!(*param.data(), array![0., 0., 2., 0.]);
+}
+
+#[test]
+fn pruned_optimizer() {
+    let param = crate::from_ndarray(array![1., -2., 3., 4.]).requires_grad();
+    let loss = (param.clone() * 2.).sum();
+
+    let mut pruner = Pruner::new();
+    pruner.push(Mask::new(&param, array![1., 0., 1., 0.]));
+    let optimizer = pruner.wrap(Adam::new(
+        param.parameters(),
+        0.1,
+        (0.9, 0.999),
+        L2::new(0.),
+        1e-8,
+    ));
+
+    // The pruned entries stay at zero through the steps, without any callback.
+    for _ in 0..3 {
+        loss.forward();
+        optimizer.zero_grad();
+        loss.backward(1.);
+        optimizer.step();
+        assert_eq!(param.data()[1], 0.);
+        assert_eq!(param.data()[3], 0.);
+        assert_eq!(param.grad()[1], 0.);
+    }
+    assert!(param.data()[0] < 1.);
+
+    optimizer.set_lr(0.5);
+    assert_eq!(optimizer.get_lr(), 0.5);
+    assert_eq!(optimizer.pruner().len(), 1);
+}
+
+#[test]
+fn sparsity() {
+    let first = crate::ones((2, 2)).requires_grad();
+    let second = crate::ones(4).requires_grad();
+
+    let mut pruner = Pruner::new();
+    assert!(pruner.is_empty());
+    assert_eq!(pruner.sparsity(), 0.);
+
+    pruner.push(l1_unstructured(&first, 1.));
+    pruner.push(l1_unstructured(&second, 0.5));
+    assert_eq!(pruner.sparsities(), vec![1., 0.5]);
+    assert_eq!(pruner.sparsity(), 0.75);
+}
+
+#[test]
+#[should_panic(expected = "error: 1.5 is not a valid pruning amount.")]
+fn invalid_amount() {
+    l1_unstructured(&crate::ones(3).requires_grad(), 1.5);
+}
+
+#[test]
+#[should_panic(expected = "error: the mask must have the same shape of the parameter.")]
+fn wrong_mask() {
+    Mask::new(&crate::ones(3).requires_grad(), array![1., 0.]);
+}
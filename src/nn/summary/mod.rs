@@ -0,0 +1,335 @@
+//! Model summaries.
+//!
+//! A [`Summary`] lists the components registered to a [`ModelStatus`] in registration order,
+//! together with the shape of their outputs and the number of their parameters. The shapes are
+//! obtained by a shape-inference pass that feeds the output shape of each component to the next
+//! one, so that no tensor is allocated and no computation is performed.
+//!
+//! ```
+//! use neuronika::nn::{Dropout, Linear, ModelStatus, Module};
+//!
+//! struct NeuralNetwork {
+//!     lin1: Linear,
+//!     drop: Dropout,
+//!     lin2: Linear,
+//!     status: ModelStatus,
+//! }
+//!
+//! impl NeuralNetwork {
+//!     fn new() -> Self {
+//!         let mut status = ModelStatus::default();
+//!
+//!         Self {
+//!             lin1: status.register(Linear::new(25, 35)),
+//!             drop: status.register(Dropout::new(0.5)),
+//!             lin2: status.register(Linear::new(35, 5)),
+//!             status,
+//!         }
+//!     }
+//! }
+//!
+//! impl Module for NeuralNetwork {
+//!     fn status(&self) -> &ModelStatus {
+//!         &self.status
+//!     }
+//! }
+//!
+//! let summary = NeuralNetwork::new().summary(&[8, 25]);
+//! assert_eq!(summary.output_shape(), &[8, 5]);
+//! assert_eq!(summary.trainable(), 25 * 35 + 35 + 35 * 5 + 5);
+//!
+//! println!("{}", summary);
+//! ```
+//!
+//! [`ModelStatus`]: super::ModelStatus
+use std::{fmt, ops::Range, rc::Rc};
+
+/// A function computing the shape of the output of a component from the shape of its input.
+pub type ShapeInference = Rc<dyn Fn(&[usize]) -> Vec<usize>>;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Layer ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// A component registered to a model's status.
+#[derive(Clone)]
+pub(super) struct Layer {
+    pub(super) name: &'static str,
+    pub(super) params: Range<usize>,
+    pub(super) shape: ShapeInference,
+    pub(super) non_trainable: usize,
+}
+
+impl Layer {
+    pub(super) fn new<T: ?Sized>(
+        params: Range<usize>,
+        shape: ShapeInference,
+        non_trainable: usize,
+    ) -> Self {
+        Self {
+            name: short_name(std::any::type_name::<T>()),
+            params,
+            shape,
+            non_trainable,
+        }
+    }
+
+    /// Returns a copy of `self` whose parameters are shifted by `offset`.
+    pub(super) fn shifted(&self, offset: usize) -> Self {
+        Self {
+            params: self.params.start + offset..self.params.end + offset,
+            ..self.clone()
+        }
+    }
+}
+
+/// Strips the module path and the generic parameters from a type name.
+fn short_name(name: &'static str) -> &'static str {
+    let name = &name[..name.find('<').unwrap_or(name.len())];
+    &name[name.rfind("::").map_or(0, |pos| pos + 2)..]
+}
+
+/// Returns the shape inference of a linear transformation mapping `in_features` to
+/// `out_features` along the last axis.
+pub(super) fn linear_shape(
+    name: &'static str,
+    in_features: usize,
+    out_features: usize,
+) -> ShapeInference {
+    Rc::new(move |input_shape| {
+        let features = input_shape.last().copied().unwrap_or(0);
+        assert_eq!(
+            features, in_features,
+            "error: {} expects {} input features, got {}.",
+            name, in_features, features
+        );
+
+        let mut output_shape = input_shape.to_vec();
+        *output_shape.last_mut().unwrap() = out_features;
+        output_shape
+    })
+}
+
+/// Returns the shape inference of a convolution whose kernel has shape `weight_shape`.
+pub(super) fn convolution_shape(
+    name: &'static str,
+    weight_shape: &[usize],
+    groups: usize,
+    padding: &[usize],
+    stride: &[usize],
+    dilation: &[usize],
+) -> ShapeInference {
+    let (out_channels, in_channels) = (weight_shape[0], weight_shape[1] * groups);
+    let kernel = weight_shape[2..].to_vec();
+    let (padding, stride, dilation) = (padding.to_vec(), stride.to_vec(), dilation.to_vec());
+
+    Rc::new(move |input_shape| {
+        assert!(
+            input_shape.len() == kernel.len() + 2 && input_shape[1] == in_channels,
+            "error: {} expects an input of shape (N, {}, ...) with {} spatial axes, got {:?}.",
+            name,
+            in_channels,
+            kernel.len(),
+            input_shape
+        );
+
+        let spatial = input_shape[2..].iter().enumerate().map(|(i, size)| {
+            let span = dilation[i] * (kernel[i] - 1) + 1;
+            let padded = size + 2 * padding[i];
+            assert!(
+                padded >= span,
+                "error: {} cannot convolve an input of shape {:?}.",
+                name,
+                input_shape
+            );
+            (padded - span) / stride[i] + 1
+        });
+
+        [input_shape[0], out_channels]
+            .iter()
+            .copied()
+            .chain(spatial)
+            .collect()
+    })
+}
+
+/// Returns the shape inference of a two-dimensional pooling.
+pub(super) fn pooling_shape(
+    name: &'static str,
+    kernel_size: (usize, usize),
+    stride: (usize, usize),
+    padding: (usize, usize),
+) -> ShapeInference {
+    let (kernel, stride, padding) = (
+        [kernel_size.0, kernel_size.1],
+        [stride.0, stride.1],
+        [padding.0, padding.1],
+    );
+
+    Rc::new(move |input_shape| {
+        assert_eq!(
+            input_shape.len(),
+            4,
+            "error: {} expects an input of shape (N, C, H, W), got {:?}.",
+            name,
+            input_shape
+        );
+
+        let spatial = input_shape[2..].iter().enumerate().map(|(i, size)| {
+            let padded = size + 2 * padding[i];
+            assert!(
+                padded >= kernel[i],
+                "error: {} cannot pool an input of shape {:?}.",
+                name,
+                input_shape
+            );
+            (padded - kernel[i]) / stride[i] + 1
+        });
+
+        input_shape[..2].iter().copied().chain(spatial).collect()
+    })
+}
+
+/// Returns the shape inference of a normalization over `channels` channels of inputs with `ndim`
+/// axes.
+pub(super) fn normalization_shape(
+    name: &'static str,
+    channels: usize,
+    ndim: usize,
+) -> ShapeInference {
+    Rc::new(move |input_shape| {
+        assert!(
+            input_shape.len() == ndim && input_shape[1] == channels,
+            "error: {} expects an input with {} axes and {} channels, got {:?}.",
+            name,
+            ndim,
+            channels,
+            input_shape
+        );
+        input_shape.to_vec()
+    })
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Summary ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// A row of a [`Summary`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LayerSummary {
+    /// The name of the component's type.
+    pub name: String,
+    /// The shape of the component's output.
+    pub output_shape: Vec<usize>,
+    /// The number of learnable parameters of the component.
+    pub trainable: usize,
+    /// The number of parameters of the component that are not updated by the optimizers.
+    pub non_trainable: usize,
+}
+
+/// A table of the components of a model.
+///
+/// It is returned by [`ModelStatus::summary()`](super::ModelStatus::summary()) and can be printed
+/// with its [`Display`](fmt::Display) implementation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Summary {
+    input_shape: Vec<usize>,
+    layers: Vec<LayerSummary>,
+}
+
+impl Summary {
+    pub(super) fn new(input_shape: &[usize], layers: Vec<LayerSummary>) -> Self {
+        Self {
+            input_shape: input_shape.to_vec(),
+            layers,
+        }
+    }
+
+    /// Returns the rows of the summary, one for each registered component.
+    pub fn layers(&self) -> &[LayerSummary] {
+        &self.layers
+    }
+
+    /// Returns the shape of the input the summary was computed for.
+    pub fn input_shape(&self) -> &[usize] {
+        &self.input_shape
+    }
+
+    /// Returns the output shape of the last component, or the input shape if there are none.
+    pub fn output_shape(&self) -> &[usize] {
+        self.layers
+            .last()
+            .map_or(&self.input_shape, |layer| &layer.output_shape)
+    }
+
+    /// Returns the total number of learnable parameters.
+    pub fn trainable(&self) -> usize {
+        self.layers.iter().map(|layer| layer.trainable).sum()
+    }
+
+    /// Returns the total number of parameters that are not updated by the optimizers.
+    pub fn non_trainable(&self) -> usize {
+        self.layers.iter().map(|layer| layer.non_trainable).sum()
+    }
+
+    /// Returns the total number of parameters.
+    pub fn total(&self) -> usize {
+        self.trainable() + self.non_trainable()
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows: Vec<[String; 3]> = self
+            .layers
+            .iter()
+            .map(|layer| {
+                [
+                    layer.name.clone(),
+                    format!("{:?}", layer.output_shape),
+                    (layer.trainable + layer.non_trainable).to_string(),
+                ]
+            })
+            .collect();
+
+        let header = ["Layer", "Output Shape", "Param #"];
+        let mut widths = header.map(str::len);
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+        let rule = widths.iter().sum::<usize>() + 4;
+
+        writeln!(
+            f,
+            "{:<w0$}  {:<w1$}  {:>w2$}",
+            header[0],
+            header[1],
+            header[2],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2]
+        )?;
+        writeln!(f, "{}", "=".repeat(rule))?;
+        for row in &rows {
+            writeln!(
+                f,
+                "{:<w0$}  {:<w1$}  {:>w2$}",
+                row[0],
+                row[1],
+                row[2],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2]
+            )?;
+        }
+        writeln!(f, "{}", "=".repeat(rule))?;
+        writeln!(f, "Total params: {}", self.total())?;
+        writeln!(f, "Trainable params: {}", self.trainable())?;
+        write!(f, "Non-trainable params: {}", self.non_trainable())
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,139 @@
+use super::{super::*, short_name};
+
+struct Network {
+    conv: Conv2d<Zero>,
+    _drop: Dropout,
+    lin: Linear,
+    status: ModelStatus,
+}
+
+impl Network {
+    fn new() -> Self {
+        let mut status = ModelStatus::default();
+
+        Self {
+            conv: status.register(Conv2d::new(3, 4, (3, 3), (1, 1), Zero, (2, 2), (1, 1))),
+            _drop: status.register(Dropout::new(0.5)),
+            lin: status.register(Linear::new(4, 2)),
+            status,
+        }
+    }
+}
+
+impl Module for Network {
+    fn status(&self) -> &ModelStatus {
+        &self.status
+    }
+}
+
+#[test]
+fn names() {
+    assert_eq!(short_name(std::any::type_name::<Linear>()), "Linear");
+    assert_eq!(short_name(std::any::type_name::<Conv2d<Zero>>()), "Conv2d");
+    assert_eq!(short_name("Plain"), "Plain");
+}
+
+#[test]
+fn summary() {
+    let network = Network::new();
+    let summary = network.summary(&[8, 3, 9, 7]);
+
+    let shapes: Vec<_> = summary
+        .layers()
+        .iter()
+        .map(|layer| (layer.name.as_str(), layer.output_shape.clone()))
+        .collect();
+    assert_eq!(
+        shapes,
+        vec![
+            ("Conv2d", vec![8, 4, 5, 4]),
+            ("Dropout", vec![8, 4, 5, 4]),
+            ("Linear", vec![8, 4, 5, 2]),
+        ]
+    );
+
+    assert_eq!(summary.input_shape(), &[8, 3, 9, 7]);
+    assert_eq!(summary.output_shape(), &[8, 4, 5, 2]);
+    assert_eq!(summary.layers()[0].trainable, 4 * 3 * 3 * 3 + 4);
+    assert_eq!(summary.layers()[1].trainable, 0);
+    assert_eq!(summary.trainable(), 112 + 10);
+    assert_eq!(summary.non_trainable(), 0);
+    assert_eq!(
+        summary.total(),
+        network.parameters().iter().map(|p| p.data.len()).sum()
+    );
+}
+
+#[test]
+fn display() {
+    let summary = Network::new().summary(&[8, 3, 9, 7]);
+
+    assert_eq!(
+        summary.to_string(),
+        "Layer    Output Shape  Param #\n\
+         ==============================\n\
+         Conv2d   [8, 4, 5, 4]      112\n\
+         Dropout  [8, 4, 5, 4]        0\n\
+         Linear   [8, 4, 5, 2]       10\n\
+         ==============================\n\
+         Total params: 122\n\
+         Trainable params: 122\n\
+         Non-trainable params: 0"
+    );
+}
+
+#[test]
+fn sub_modules() {
+    struct Ensemble {
+        _first: Network,
+        _lin: Linear,
+        status: ModelStatus,
+    }
+
+    impl Module for Ensemble {
+        fn status(&self) -> &ModelStatus {
+            &self.status
+        }
+    }
+
+    let mut status = ModelStatus::default();
+    let ensemble = Ensemble {
+        _first: status.register_module(Network::new()),
+        _lin: status.register(Linear::new(2, 6)),
+        status,
+    };
+
+    let summary = ensemble.summary(&[8, 3, 9, 7]);
+    let parameters: Vec<_> = summary
+        .layers()
+        .iter()
+        .map(|layer| layer.trainable)
+        .collect();
+    assert_eq!(parameters, vec![112, 0, 10, 18]);
+    assert_eq!(summary.output_shape(), &[8, 4, 5, 6]);
+}
+
+#[test]
+fn recurrent() {
+    let mut status = ModelStatus::default();
+    let _lstm = status.register(LSTMCell::new(3, 5));
+    let _gru = status.register(GRUCell::new(5, 2));
+
+    let summary = status.summary(&[4, 3]);
+    assert_eq!(summary.output_shape(), &[4, 2]);
+    assert_eq!(summary.layers()[0].trainable, 4 * 5 * (3 + 5 + 2));
+}
+
+#[test]
+#[should_panic(expected = "error: Linear expects 4 input features, got 3.")]
+fn wrong_features() {
+    Network::new().lin.shape_inference()(&[2, 3]);
+}
+
+#[test]
+#[should_panic(
+    expected = "error: Conv2d expects an input of shape (N, 3, ...) with 2 spatial axes"
+)]
+fn wrong_channels() {
+    Network::new().conv.shape_inference()(&[2, 2, 9, 7]);
+}
@@ -1,6 +1,5 @@
 use super::{Optimizer, Param, Penalty};
 use ndarray::{ArrayD, ArrayViewMutD, Zip};
-use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
 use std::cell::{Cell, RefCell};
 
 /// **Adagrad** optimizer.
@@ -116,7 +115,7 @@ impl<'a, T: Penalty> Optimizer<'a> for Adagrad<'a, T> {
             &self.eps.get(),
         );
 
-        params.par_iter_mut().for_each(|param| {
+        crate::parallel::for_each_mut(&mut params, |param| {
             let (step, grad_sq) = (&mut param.step, &mut param.grad_sq);
 
             *step += 1;
@@ -141,7 +140,7 @@ impl<'a, T: Penalty> Optimizer<'a> for Adagrad<'a, T> {
     }
 
     fn zero_grad(&self) {
-        self.params.borrow_mut().par_iter_mut().for_each(|param| {
+        crate::parallel::for_each_mut(&mut self.params.borrow_mut(), |param| {
             let grad = &mut param.grad;
             Zip::from(grad).for_each(|grad_el| *grad_el = 0.);
         });
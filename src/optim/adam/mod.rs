@@ -1,6 +1,5 @@
 use super::{Optimizer, Param, Penalty};
 use ndarray::{ArrayD, ArrayViewMutD, Zip};
-use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
 use std::cell::{Cell, RefCell};
 
 /// **Adam** optimizer.
@@ -121,7 +120,7 @@ impl<'a, T: Penalty> Optimizer<'a> for Adam<'a, T> {
             &self.eps.get(),
         );
 
-        params.par_iter_mut().for_each(|param| {
+        crate::parallel::for_each_mut(&mut params, |param| {
             let (step, exp_avg, exp_avg_sq) =
                 (&mut param.step, &mut param.exp_avg, &mut param.exp_avg_sq);
 
@@ -157,7 +156,7 @@ impl<'a, T: Penalty> Optimizer<'a> for Adam<'a, T> {
     }
 
     fn zero_grad(&self) {
-        self.params.borrow_mut().par_iter_mut().for_each(|param| {
+        crate::parallel::for_each_mut(&mut self.params.borrow_mut(), |param| {
             let grad = &mut param.grad;
             Zip::from(grad).for_each(|grad_el| *grad_el = 0.);
         });
@@ -0,0 +1,358 @@
+use super::{Optimizer, Param, Penalty};
+use crate::{autograd, Data, Gradient, VarDiff};
+use ndarray::{ArrayD, ArrayViewMutD, Axis, Zip};
+use rand_distr::{Distribution, Normal};
+use std::cell::{Cell, RefCell};
+
+/// **Differentially Private Stochastic Gradient Descent** optimizer.
+///
+/// It implements the algorithm from
+/// [Deep Learning with Differential Privacy](https://arxiv.org/abs/1607.00133).
+///
+/// The gradient of each sample of the batch is computed separately and clipped so that its
+/// L2 norm, computed over all the parameters, is at most `max_grad_norm`. The clipped gradients
+/// are then summed, perturbed with Gaussian noise of standard deviation
+/// `noise_multiplier * max_grad_norm` and averaged over the batch. The privacy spent during
+/// training is tracked by a [`PrivacyAccountant`].
+///
+/// As the per-sample gradients are needed, the gradients must be computed with
+/// [`.compute_grad()`](DpSgd::compute_grad()) rather than with
+/// [`.backward()`](VarDiff::backward()).
+///
+/// ```
+/// use neuronika::optim::{DpSgd, L2};
+///
+/// let x = neuronika::rand((4, 3));
+/// let y = neuronika::rand((4, 1));
+/// let w = neuronika::rand((1, 3)).requires_grad();
+///
+/// // The losses of each sample, left unreduced.
+/// let losses = (x.mm_t(w) - y).pow(2);
+///
+/// let optim = DpSgd::new(losses.parameters(), 0.1, L2::new(0.), 1.0, 1.1, 0.01);
+///
+/// losses.forward();
+/// optim.compute_grad(&losses);
+/// optim.step();
+///
+/// println!("ε = {}", optim.accountant().epsilon(1e-5));
+/// ```
+pub struct DpSgd<'a, T> {
+    params: RefCell<Vec<DpSgdParam<'a>>>,
+    lr: Cell<f32>,
+    penalty: T,
+    max_grad_norm: Cell<f32>,
+    noise_multiplier: Cell<f32>,
+    accountant: RefCell<PrivacyAccountant>,
+}
+
+/// The parameter representation used by the *DP-SGD* optimizer.
+pub struct DpSgdParam<'a> {
+    data: ArrayViewMutD<'a, f32>,
+    grad: ArrayViewMutD<'a, f32>,
+    clipped: ArrayD<f32>,
+}
+
+impl<'a> From<Param<'a>> for DpSgdParam<'a> {
+    fn from(param: Param<'a>) -> Self {
+        let Param { data, grad } = param;
+        let clipped = ArrayD::zeros(grad.raw_dim());
+        Self {
+            data,
+            grad,
+            clipped,
+        }
+    }
+}
+
+impl<'a, T: Penalty> Optimizer<'a> for DpSgd<'a, T> {
+    type ParamRepr = DpSgdParam<'a>;
+
+    fn step(&self) {
+        let (lr, penalty, mut params) = (self.lr.get(), &self.penalty, self.params.borrow_mut());
+        crate::parallel::for_each_mut(&mut params, |param| {
+            let (data, grad) = (&mut param.data, &param.grad);
+            Zip::from(data).and(grad).for_each(|data_el, grad_el| {
+                *data_el += -(grad_el + penalty.penalize(data_el)) * lr
+            });
+        });
+    }
+
+    fn zero_grad(&self) {
+        crate::parallel::for_each_mut(&mut self.params.borrow_mut(), |param| {
+            let grad = &mut param.grad;
+            Zip::from(grad).for_each(|grad_el| *grad_el = 0.);
+        });
+    }
+
+    fn get_lr(&self) -> f32 {
+        self.lr.get()
+    }
+
+    fn set_lr(&self, lr: f32) {
+        self.lr.set(lr)
+    }
+}
+
+impl<'a, T: Penalty> DpSgd<'a, T> {
+    /// Creates a new *DP-SGD* optimizer.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - vector of [`Param`] to optimize.
+    ///
+    /// * `lr` - learning rate.
+    ///
+    /// * `penalty` - penalty regularization.
+    ///
+    /// * `max_grad_norm` - maximum L2 norm of the gradient of each sample.
+    ///
+    /// * `noise_multiplier` - ratio between the standard deviation of the noise and the maximum
+    ///   norm.
+    ///
+    /// * `sample_rate` - probability of each sample of the dataset to be included in a batch,
+    ///   i.e. the batch size divided by the size of the dataset.
+    ///
+    /// # Panics
+    ///
+    /// If `max_grad_norm` is not positive or `noise_multiplier` is negative, or if either is not
+    /// finite.
+    pub fn new(
+        parameters: Vec<Param<'a>>,
+        lr: f32,
+        penalty: T,
+        max_grad_norm: f32,
+        noise_multiplier: f32,
+        sample_rate: f32,
+    ) -> Self {
+        assert!(
+            max_grad_norm > 0. && max_grad_norm.is_finite(),
+            "error: the maximum gradient norm must be positive and finite, got {}.",
+            max_grad_norm
+        );
+        assert!(
+            noise_multiplier >= 0. && noise_multiplier.is_finite(),
+            "error: the noise multiplier must be non negative and finite, got {}.",
+            noise_multiplier
+        );
+        let params = RefCell::new(Self::build_params(parameters));
+        let lr = Cell::new(lr);
+        let accountant = RefCell::new(PrivacyAccountant::new(noise_multiplier, sample_rate));
+
+        Self {
+            params,
+            lr,
+            penalty,
+            max_grad_norm: Cell::new(max_grad_norm),
+            noise_multiplier: Cell::new(noise_multiplier),
+            accountant,
+        }
+    }
+
+    /// Computes the privatized gradients of the parameters and stores them in their gradients.
+    ///
+    /// `losses` must hold the unreduced losses of the batch, with the samples laid along its first
+    /// axis. The forward pass must have been computed beforehand. The per-sample gradients are
+    /// computed by [`autograd::per_sample_grad`].
+    ///
+    /// Each call is accounted for by the optimizer's [`PrivacyAccountant`].
+    ///
+    /// # Arguments
+    ///
+    /// `losses` - unreduced losses of the batch.
+    ///
+    /// # Panics
+    ///
+    /// If `losses` is zero-dimensional.
+    pub fn compute_grad<U, V>(&self, losses: &VarDiff<U, V>)
+    where
+        U: Data + ?Sized + 'static,
+        V: Gradient<Dim = U::Dim> + ?Sized + 'static,
+    {
+        let (max_grad_norm, mut params) = (self.max_grad_norm.get(), self.params.borrow_mut());
+        let grads = {
+            let views: Vec<Param> = params
+                .iter_mut()
+                .map(|param| Param {
+                    data: param.data.view_mut(),
+                    grad: param.grad.view_mut(),
+                })
+                .collect();
+            autograd::per_sample_grad(losses, &views)
+        };
+        params.iter_mut().for_each(|param| param.clipped.fill(0.));
+
+        let batch_size = losses.data().len_of(Axis(0));
+        for sample in 0..batch_size {
+            let norm = grads
+                .iter()
+                .map(|grad| {
+                    let grad = grad.index_axis(Axis(0), sample);
+                    grad.iter().map(|el| el * el).sum::<f32>()
+                })
+                .sum::<f32>()
+                .sqrt();
+            let factor = (max_grad_norm / (norm + 1e-6)).min(1.);
+            params.iter_mut().zip(&grads).for_each(|(param, grad)| {
+                Zip::from(&mut param.clipped)
+                    .and(grad.index_axis(Axis(0), sample))
+                    .for_each(|clipped_el, grad_el| *clipped_el += grad_el * factor)
+            });
+        }
+
+        let std = self.noise_multiplier.get() * max_grad_norm;
+        let noise = Normal::new(0., std).unwrap();
+        crate::random::with_rng(|rng| {
+            params.iter_mut().for_each(|param| {
+                Zip::from(&mut param.grad)
+                    .and(&param.clipped)
+                    .for_each(|grad_el, clipped_el| {
+                        *grad_el = (clipped_el + noise.sample(rng)) / batch_size as f32
+                    })
+            })
+        });
+
+        self.accountant.borrow_mut().step();
+    }
+
+    /// Return the current learning rate.
+    pub fn get_lr(&self) -> f32 {
+        Optimizer::get_lr(self)
+    }
+
+    /// Sets `lr` as the  new value for the learning rate.
+    pub fn set_lr(&self, lr: f32) {
+        Optimizer::set_lr(self, lr);
+    }
+
+    /// Returns the maximum L2 norm of the gradient of each sample.
+    pub fn get_max_grad_norm(&self) -> f32 {
+        self.max_grad_norm.get()
+    }
+
+    /// Returns the noise multiplier.
+    pub fn get_noise_multiplier(&self) -> f32 {
+        self.noise_multiplier.get()
+    }
+
+    /// Returns the privacy accountant of this optimizer.
+    pub fn accountant(&self) -> PrivacyAccountant {
+        self.accountant.borrow().clone()
+    }
+
+    /// Performs a single differentially private stochastic gradient descent optimization step.
+    pub fn step(&self) {
+        Optimizer::step(self);
+    }
+
+    /// Zeroes the gradient of this optimizer's parameters.
+    pub fn zero_grad(&self) {
+        Optimizer::zero_grad(self);
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ PrivacyAccountant ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Rényi differential privacy accountant for the sampled Gaussian mechanism.
+///
+/// It keeps track of the number of steps taken by [`DpSgd`] and converts the privacy spent into
+/// an *(ε, δ)* guarantee, following
+/// [Rényi Differential Privacy of the Sampled Gaussian Mechanism](https://arxiv.org/abs/1908.10530).
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrivacyAccountant {
+    noise_multiplier: f32,
+    sample_rate: f32,
+    steps: usize,
+}
+
+impl PrivacyAccountant {
+    /// Rényi divergence orders over which the conversion to *(ε, δ)* is optimized.
+    const ORDERS: std::ops::RangeInclusive<u32> = 2..=256;
+
+    /// Creates a new privacy accountant.
+    ///
+    /// # Arguments
+    ///
+    /// * `noise_multiplier` - ratio between the standard deviation of the noise and the clipping
+    ///   norm.
+    ///
+    /// * `sample_rate` - probability of each sample to be included in a batch.
+    pub fn new(noise_multiplier: f32, sample_rate: f32) -> Self {
+        Self {
+            noise_multiplier,
+            sample_rate,
+            steps: 0,
+        }
+    }
+
+    /// Records a step of the mechanism.
+    pub fn step(&mut self) {
+        self.steps += 1;
+    }
+
+    /// Returns the number of steps recorded.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Returns the ε spent so far for the given δ.
+    ///
+    /// # Arguments
+    ///
+    /// `delta` - probability of the privacy guarantee being broken.
+    pub fn epsilon(&self, delta: f64) -> f64 {
+        if self.steps == 0 {
+            return 0.;
+        }
+        if self.noise_multiplier == 0. {
+            return f64::INFINITY;
+        }
+
+        Self::ORDERS
+            .map(|order| {
+                self.steps as f64 * self.rdp(order) + (1. / delta).ln() / (order as f64 - 1.)
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Computes the Rényi differential privacy of a single step for an integer `order`.
+    fn rdp(&self, order: u32) -> f64 {
+        let (q, sigma) = (self.sample_rate as f64, self.noise_multiplier as f64);
+        if q == 0. {
+            return 0.;
+        }
+        if q >= 1. {
+            return order as f64 / (2. * sigma * sigma);
+        }
+
+        // Terms of the binomial expansion, in log space.
+        let mut log_binomial = 0.;
+        let terms: Vec<f64> = (0..=order)
+            .map(|k| {
+                if k > 0 {
+                    log_binomial += ((order - k + 1) as f64).ln() - (k as f64).ln();
+                }
+                let k = k as f64;
+                log_binomial
+                    + k * q.ln()
+                    + (order as f64 - k) * (1. - q).ln()
+                    + (k * k - k) / (2. * sigma * sigma)
+            })
+            .collect();
+
+        let max = terms.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let log_a = max
+            + terms
+                .iter()
+                .map(|term| (term - max).exp())
+                .sum::<f64>()
+                .ln();
+
+        log_a / (order as f64 - 1.)
+    }
+}
+
+#[cfg(test)]
+mod test;
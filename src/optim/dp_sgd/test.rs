@@ -0,0 +1,100 @@
+use super::{super::L2, DpSgd, PrivacyAccountant};
+
+#[test]
+fn creation() {
+    let optim = DpSgd::new(Vec::new(), 1e-2, L2::new(1e-2), 1.0, 1.1, 0.01);
+
+    assert_eq!(optim.params.borrow().len(), 0);
+    assert!((optim.get_lr() - 1e-2).abs() <= f32::EPSILON);
+    assert!((optim.get_max_grad_norm() - 1.0).abs() <= f32::EPSILON);
+    assert!((optim.get_noise_multiplier() - 1.1).abs() <= f32::EPSILON);
+    assert_eq!(optim.accountant().steps(), 0);
+}
+
+#[test]
+#[should_panic(expected = "error: the maximum gradient norm must be positive and finite, got 0.")]
+fn creation_zero_max_grad_norm() {
+    DpSgd::new(Vec::new(), 1e-2, L2::new(1e-2), 0., 1.1, 0.01);
+}
+
+#[test]
+#[should_panic(expected = "error: the noise multiplier must be non negative and finite, got -1.")]
+fn creation_negative_noise_multiplier() {
+    DpSgd::new(Vec::new(), 1e-2, L2::new(1e-2), 1.0, -1., 0.01);
+}
+
+#[test]
+fn set_lr() {
+    let optim = DpSgd::new(Vec::new(), 1e-2, L2::new(1e-2), 1.0, 1.1, 0.01);
+    optim.set_lr(1e-3);
+
+    assert!((optim.get_lr() - 1e-3).abs() <= f32::EPSILON);
+}
+
+#[test]
+fn compute_grad_clipping() {
+    let w = crate::from_ndarray(ndarray::array![1., 0.]).requires_grad();
+    let x = crate::from_ndarray(ndarray::array![[0.1, 0.], [3., 4.]]);
+    let losses = x.mv(w.clone());
+    losses.forward();
+
+    // Without noise the gradients are the mean of the clipped per-sample gradients.
+    let optim = DpSgd::new(losses.parameters(), 1e-2, L2::new(0.), 1.0, 0., 0.5);
+    optim.compute_grad(&losses);
+
+    let expected = ndarray::array![(0.1 + 0.6) / 2., 0.8 / 2.];
+    assert!(w
+        .grad()
+        .iter()
+        .zip(expected.iter())
+        .all(|(el, exp)| (el - exp).abs() < 1e-4));
+    assert_eq!(optim.accountant().steps(), 1);
+}
+
+#[test]
+fn accountant() {
+    let mut accountant = PrivacyAccountant::new(1.0, 1.);
+    assert_eq!(accountant.epsilon(1e-5), 0.);
+
+    // Without subsampling the Rényi privacy of the Gaussian mechanism is α / 2σ².
+    accountant.step();
+    let expected = (2..=256)
+        .map(|order| order as f64 / 2. + (1e5_f64).ln() / (order as f64 - 1.))
+        .fold(f64::INFINITY, f64::min);
+    assert!((accountant.epsilon(1e-5) - expected).abs() < 1e-9);
+
+    // Subsampling amplifies privacy, while more steps spend more of it.
+    let mut subsampled = PrivacyAccountant::new(1.0, 0.01);
+    subsampled.step();
+    let single = subsampled.epsilon(1e-5);
+    assert!(single < expected);
+
+    subsampled.step();
+    assert!(subsampled.epsilon(1e-5) > single);
+}
+
+const EPOCHS: usize = 200;
+
+#[test]
+fn step() {
+    let x = crate::rand((3, 3));
+    let y = crate::rand((3, 3));
+    let z = x.clone().mm(y);
+
+    let w = crate::rand((3, 3)).requires_grad();
+    let losses = (x.mm(w) - z).pow(2);
+    losses.forward();
+
+    let first_value = losses.data().sum();
+    let optim = DpSgd::new(losses.parameters(), 0.1, L2::new(0.), 10., 0., 1.);
+
+    for _ in 0..EPOCHS {
+        losses.forward();
+        optim.compute_grad(&losses);
+
+        optim.step();
+        optim.zero_grad();
+    }
+    losses.forward();
+    assert!(losses.data().sum() < first_value);
+}
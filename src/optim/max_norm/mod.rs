@@ -0,0 +1,95 @@
+use super::Param;
+use crate::variable::renorm;
+use ndarray::ArrayViewMutD;
+use std::cell::RefCell;
+
+/// **Max-norm** constraint on parameters.
+///
+/// After each call to [`.step()`](MaxNorm::step()) every sub-tensor along `axis` of the
+/// constrained parameters has a `p`-norm of at most `max_norm`, the sub-tensors exceeding it
+/// being rescaled onto the norm ball. It is meant to be stepped right after the optimizer, and is
+/// commonly applied to embedding tables and to the weights of dropout-regularized networks.
+///
+/// See [`.renorm()`](crate::Var::renorm()) for the differentiable counterpart.
+///
+/// ```
+/// use neuronika::optim::{MaxNorm, L2, SGD};
+///
+/// let w = neuronika::from_ndarray(ndarray::array![[3., -4.], [0.5, 0.]]).requires_grad();
+/// let loss = w.clone().sum();
+///
+/// let optim = SGD::new(loss.parameters(), 0., L2::new(0.));
+/// let constraint = MaxNorm::new(loss.parameters(), 2., 0, 1.);
+///
+/// loss.forward();
+/// loss.backward(1.);
+/// optim.step();
+/// constraint.step();
+///
+/// assert_eq!(*w.data(), ndarray::array![[0.6, -0.8], [0.5, 0.]]);
+/// ```
+pub struct MaxNorm<'a> {
+    params: RefCell<Vec<ArrayViewMutD<'a, f32>>>,
+    p: f32,
+    axis: usize,
+    max_norm: f32,
+}
+
+impl<'a> MaxNorm<'a> {
+    /// Creates a new max-norm constraint.
+    ///
+    /// # Arguments
+    ///
+    /// * `parameters` - vector of [`Param`] to constrain.
+    ///
+    /// * `p` - order of the norm, at least 1.
+    ///
+    /// * `axis` - axis along which the constrained sub-tensors are taken.
+    ///
+    /// * `max_norm` - largest norm allowed.
+    ///
+    /// # Panics
+    ///
+    /// If `p` is smaller than 1 or `axis` is out of bounds for one of the parameters.
+    pub fn new(parameters: Vec<Param<'a>>, p: f32, axis: usize, max_norm: f32) -> Self {
+        assert!(
+            p >= 1.,
+            "error: the order of a norm must be at least 1, got {}.",
+            p
+        );
+        let params = parameters
+            .into_iter()
+            .map(|param| {
+                assert!(
+                    axis < param.data.ndim(),
+                    "error: axis {} is out of bounds for a parameter of {} dimensions.",
+                    axis,
+                    param.data.ndim()
+                );
+                param.data
+            })
+            .collect();
+
+        Self {
+            params: RefCell::new(params),
+            p,
+            axis,
+            max_norm,
+        }
+    }
+
+    /// Returns the largest norm allowed.
+    pub fn get_max_norm(&self) -> f32 {
+        self.max_norm
+    }
+
+    /// Rescales the sub-tensors of the parameters whose norm exceeds the maximum one.
+    pub fn step(&self) {
+        for param in self.params.borrow_mut().iter_mut() {
+            renorm(param.view_mut(), self.p, self.axis, self.max_norm);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
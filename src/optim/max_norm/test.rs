@@ -0,0 +1,46 @@
+use super::{super::L2, super::SGD, MaxNorm};
+
+#[test]
+fn creation() {
+    let constraint = MaxNorm::new(Vec::new(), 2., 0, 3.);
+
+    assert_eq!(constraint.params.borrow().len(), 0);
+    assert!((constraint.get_max_norm() - 3.).abs() <= f32::EPSILON);
+}
+
+#[test]
+#[should_panic(expected = "error: axis 2 is out of bounds for a parameter of 2 dimensions.")]
+fn axis_out_of_bounds() {
+    let w = crate::ones((2, 2)).requires_grad();
+    MaxNorm::new(w.parameters(), 2., 2, 1.);
+}
+
+#[test]
+fn step() {
+    let w = crate::from_ndarray(ndarray::array![[1., 0.], [0., 0.5]]).requires_grad();
+    let loss = (w.clone() * -1.).sum();
+
+    let optim = SGD::new(loss.parameters(), 1., L2::new(0.));
+    let constraint = MaxNorm::new(loss.parameters(), 1., 1, 1.);
+
+    loss.forward();
+    loss.backward(1.);
+    optim.step();
+    constraint.step();
+
+    // The columns [2, 1] and [1, 1.5] are scaled to unit L1 norm.
+    let expected = ndarray::array![[2. / 3., 0.4], [1. / 3., 0.6]];
+    assert!(w
+        .data()
+        .iter()
+        .zip(expected.iter())
+        .all(|(el, expected)| (el - expected).abs() <= 1e-6));
+
+    // Parameters within the ball are left untouched.
+    constraint.step();
+    assert!(w
+        .data()
+        .iter()
+        .zip(expected.iter())
+        .all(|(el, expected)| (el - expected).abs() <= 1e-6));
+}
@@ -207,6 +207,8 @@
 //!
 //! * [`AMSGrad`] - Implements the AMSGrad algorithm.
 //!
+//! * [`DpSgd`] - Implements the differentially private stochastic gradient descent algorithm.
+//!
 //! * [`RMSProp`] - Implements the RMSProp algorithm.
 //!
 //! * [`SGD`] - Implements the stochastic gradient descent algorithm.
@@ -214,6 +216,8 @@ use crate::variable::Param;
 pub use adagrad::{Adagrad, AdagradParam};
 pub use adam::{Adam, AdamParam};
 pub use amsgrad::{AMSGrad, AMSGradParam};
+pub use dp_sgd::{DpSgd, DpSgdParam, PrivacyAccountant};
+pub use max_norm::MaxNorm;
 pub use rmsprop::{
     RMSProp, RMSPropCentered, RMSPropCenteredParam, RMSPropCenteredWithMomentum,
     RMSPropCenteredWithMomentumParam, RMSPropParam, RMSPropWithMomentum, RMSPropWithMomentumParam,
@@ -348,9 +352,16 @@ impl Penalty for ElasticNet {
 mod adagrad;
 mod adam;
 mod amsgrad;
+mod dp_sgd;
 mod rmsprop;
 mod sgd;
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Constraints ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+mod max_norm;
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Learning Rate Schedulers ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
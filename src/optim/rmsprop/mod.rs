@@ -1,6 +1,5 @@
 use super::{Optimizer, Param, Penalty};
 use ndarray::{ArrayD, ArrayViewMutD, Zip};
-use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
 use std::cell::{Cell, RefCell};
 
 /// **RMSProp** optimizer.
@@ -182,7 +181,7 @@ impl<'a, T: Penalty> Optimizer<'a> for RMSProp<'a, T> {
             &self.eps.get(),
         );
 
-        params.par_iter_mut().for_each(|param| {
+        crate::parallel::for_each_mut(&mut params, |param| {
             let square_avg = &mut param.square_avg;
 
             let mut p_grad = param.grad.to_owned();
@@ -206,7 +205,7 @@ impl<'a, T: Penalty> Optimizer<'a> for RMSProp<'a, T> {
     }
 
     fn zero_grad(&self) {
-        self.params.borrow_mut().par_iter_mut().for_each(|param| {
+        crate::parallel::for_each_mut(&mut self.params.borrow_mut(), |param| {
             let grad = &mut param.grad;
             Zip::from(grad).for_each(|grad_el| *grad_el = 0.);
         });
@@ -350,7 +349,7 @@ impl<'a, T: Penalty> Optimizer<'a> for RMSPropWithMomentum<'a, T> {
             &self.momentum.get(),
         );
 
-        params.par_iter_mut().for_each(|param| {
+        crate::parallel::for_each_mut(&mut params, |param| {
             let (square_avg, buffer) = (&mut param.square_avg, &mut param.buffer);
 
             let mut p_grad = param.grad.to_owned();
@@ -378,7 +377,7 @@ impl<'a, T: Penalty> Optimizer<'a> for RMSPropWithMomentum<'a, T> {
     }
 
     fn zero_grad(&self) {
-        self.params.borrow_mut().par_iter_mut().for_each(|param| {
+        crate::parallel::for_each_mut(&mut self.params.borrow_mut(), |param| {
             let grad = &mut param.grad;
             Zip::from(grad).for_each(|grad_el| *grad_el = 0.);
         });
@@ -514,7 +513,7 @@ impl<'a, T: Penalty> Optimizer<'a> for RMSPropCentered<'a, T> {
             &self.eps.get(),
         );
 
-        params.par_iter_mut().for_each(|param| {
+        crate::parallel::for_each_mut(&mut params, |param| {
             let (square_avg, grad_avg) = (&mut param.square_avg, &mut param.grad_avg);
 
             let mut p_grad = param.grad.to_owned();
@@ -547,7 +546,7 @@ impl<'a, T: Penalty> Optimizer<'a> for RMSPropCentered<'a, T> {
     }
 
     fn zero_grad(&self) {
-        self.params.borrow_mut().par_iter_mut().for_each(|param| {
+        crate::parallel::for_each_mut(&mut self.params.borrow_mut(), |param| {
             let grad = &mut param.grad;
             Zip::from(grad).for_each(|grad_el| *grad_el = 0.);
         });
@@ -712,7 +711,7 @@ impl<'a, T: Penalty> Optimizer<'a> for RMSPropCenteredWithMomentum<'a, T> {
             &self.momentum.get(),
         );
 
-        params.par_iter_mut().for_each(|param| {
+        crate::parallel::for_each_mut(&mut params, |param| {
             let (square_avg, grad_avg, buffer) = (
                 &mut param.square_avg,
                 &mut param.grad_avg,
@@ -752,7 +751,7 @@ impl<'a, T: Penalty> Optimizer<'a> for RMSPropCenteredWithMomentum<'a, T> {
     }
 
     fn zero_grad(&self) {
-        self.params.borrow_mut().par_iter_mut().for_each(|param| {
+        crate::parallel::for_each_mut(&mut self.params.borrow_mut(), |param| {
             let grad = &mut param.grad;
             Zip::from(grad).for_each(|grad_el| *grad_el = 0.);
         });
@@ -1,6 +1,5 @@
 use super::{Optimizer, Param, Penalty};
 use ndarray::{ArrayD, ArrayViewMutD, Zip};
-use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
 use std::cell::{Cell, RefCell};
 
 #[allow(clippy::upper_case_acronyms)]
@@ -30,7 +29,7 @@ impl<'a, T: Penalty> Optimizer<'a> for SGD<'a, T> {
 
     fn step(&self) {
         let (lr, penalty, mut params) = (self.lr.get(), &self.penalty, self.params.borrow_mut());
-        params.par_iter_mut().for_each(|param| {
+        crate::parallel::for_each_mut(&mut params, |param| {
             let (data, grad) = (&mut param.data, &param.grad);
             Zip::from(data).and(grad).for_each(|data_el, grad_el| {
                 *data_el += -(grad_el + penalty.penalize(data_el)) * lr
@@ -39,7 +38,7 @@ impl<'a, T: Penalty> Optimizer<'a> for SGD<'a, T> {
     }
 
     fn zero_grad(&self) {
-        self.params.borrow_mut().par_iter_mut().for_each(|param| {
+        crate::parallel::for_each_mut(&mut self.params.borrow_mut(), |param| {
             let grad = &mut param.grad;
             Zip::from(grad).for_each(|grad_el| *grad_el = 0.);
         });
@@ -195,7 +194,7 @@ impl<'a, T: Penalty> Optimizer<'a> for SGDWithMomentum<'a, T> {
             self.params.borrow_mut(),
         );
 
-        params.par_iter_mut().for_each(|param| {
+        crate::parallel::for_each_mut(&mut params, |param| {
             let mut p_grad = param.grad.to_owned();
             Zip::from(&mut p_grad)
                 .and(&param.data)
@@ -219,7 +218,7 @@ impl<'a, T: Penalty> Optimizer<'a> for SGDWithMomentum<'a, T> {
     }
 
     fn zero_grad(&self) {
-        self.params.borrow_mut().par_iter_mut().for_each(|param| {
+        crate::parallel::for_each_mut(&mut self.params.borrow_mut(), |param| {
             let grad = &mut param.grad;
             Zip::from(grad).for_each(|grad_el| *grad_el = 0.);
         });
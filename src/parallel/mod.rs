@@ -0,0 +1,203 @@
+//! Configuration of the threads used by the parallel kernels.
+//!
+//! The optimizers and the convolutions split their work among the threads of a [rayon] pool. By
+//! default it is rayon's global pool, which has one thread per logical core, unless the
+//! `RAYON_NUM_THREADS` environment variable says otherwise. [`set_num_threads()`] replaces it
+//! with a dedicated pool of the given size, while [`set_deterministic()`] runs them sequentially.
+//! The BLAS library selected with the `blas` feature can follow the same setting through
+//! [`set_blas_num_threads_fn()`].
+use rayon::{
+    iter::{IntoParallelRefMutIterator, ParallelIterator},
+    ThreadPool, ThreadPoolBuilder,
+};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, OnceLock, RwLock,
+};
+
+static POOL: RwLock<Option<Arc<ThreadPool>>> = RwLock::new(None);
+
+static DETERMINISTIC: AtomicBool = AtomicBool::new(false);
+
+static SEQUENTIAL_POOL: OnceLock<ThreadPool> = OnceLock::new();
+
+static BLAS_NUM_THREADS_FN: RwLock<Option<fn(usize)>> = RwLock::new(None);
+
+/// Sets the number of threads used by the parallel kernels of neuronika.
+///
+/// This limits the optimizers and the convolutions, which otherwise use one thread per logical
+/// core. A value of zero restores the default. The setting is global and affects the kernels
+/// running on every thread.
+///
+/// Matrix products are not affected: they are single-threaded, unless the
+/// `matrixmultiply-threading` feature is enabled, in which case they read the number of threads
+/// from the `MATMUL_NUM_THREADS` environment variable, or they are delegated to the BLAS library
+/// selected with the `blas` feature, which has its own setting, such as `OPENBLAS_NUM_THREADS`.
+/// Such a library is given the same number of threads if its setter has been registered with
+/// [`set_blas_num_threads_fn()`], in which case the convolutions run their matrix products one
+/// after the other, leaving their parallelism to the library, so that the total number of
+/// threads does not exceed the configured one.
+///
+/// ```
+/// neuronika::set_num_threads(2);
+/// assert_eq!(neuronika::num_threads(), 2);
+///
+/// neuronika::set_num_threads(0);
+/// ```
+///
+/// # Panics
+///
+/// If the threads cannot be spawned.
+pub fn set_num_threads(num_threads: usize) {
+    let pool = if num_threads == 0 {
+        None
+    } else {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .thread_name(|index| format!("neuronika-{}", index))
+            .build()
+            .expect("error: cannot spawn the threads of the pool.");
+        Some(Arc::new(pool))
+    };
+
+    *POOL.write().unwrap() = pool;
+
+    if let Some(f) = *BLAS_NUM_THREADS_FN.read().unwrap() {
+        f(self::num_threads());
+    }
+}
+
+/// Registers the function that sets the number of threads of the BLAS library selected with the
+/// `blas` feature, so that [`set_num_threads()`] also applies to the matrix products.
+///
+/// The backends of BLAS have no common interface to configure their threads, so that the
+/// function is provided by the user. With OpenBLAS, for instance, it would call
+/// `openblas_set_num_threads`, and with Intel MKL `mkl_set_num_threads`. It is called with the
+/// new number of threads at each call of [`set_num_threads()`], and with the number of threads
+/// of the default pool when the default is restored.
+///
+/// Once such a function is registered, the convolutions no longer distribute their matrix
+/// products among the threads of the pool, as each of them would spawn the threads of the BLAS
+/// library in turn. They compute them sequentially, each on all the threads of the library.
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static BLAS_THREADS: AtomicUsize = AtomicUsize::new(0);
+///
+/// neuronika::set_blas_num_threads_fn(|num_threads| {
+///     // unsafe { openblas_set_num_threads(num_threads as _) }
+///     BLAS_THREADS.store(num_threads, Ordering::SeqCst)
+/// });
+///
+/// neuronika::set_num_threads(2);
+/// assert_eq!(BLAS_THREADS.load(Ordering::SeqCst), 2);
+/// ```
+pub fn set_blas_num_threads_fn(f: fn(usize)) {
+    *BLAS_NUM_THREADS_FN.write().unwrap() = Some(f);
+}
+
+/// Returns the number of threads used by the parallel kernels of neuronika.
+///
+/// This is *1* when the deterministic mode is enabled.
+pub fn num_threads() -> usize {
+    if is_deterministic() {
+        return 1;
+    }
+
+    match &*POOL.read().unwrap() {
+        Some(pool) => pool.current_num_threads(),
+        None => rayon::current_num_threads(),
+    }
+}
+
+/// Enables or disables the deterministic mode of neuronika.
+///
+/// When it is enabled, the parallel kernels, that is the optimizers and the convolutions, are
+/// executed on a single thread. The price is the loss of the parallelism of such kernels, which
+/// run roughly [`num_threads()`] times slower than they otherwise would. The setting is global
+/// and it takes precedence over [`set_num_threads()`].
+///
+/// Each thread of such kernels writes its own part of the output, so that their results do not
+/// depend on the number of threads or on their scheduling in the first place, and the mode is
+/// not needed to reproduce them. A run is reproduced exactly by seeding the random number
+/// generator with [`manual_seed()`](crate::manual_seed()), as well as the components holding a
+/// generator of their own, such as the samplers. The parameters of a variable and of a model are
+/// always listed in the same order. Matrix products are reproducible as long as the BLAS library
+/// selected with the `blas` feature, if any, is.
+///
+/// The mode is disabled by default.
+///
+/// ```
+/// neuronika::set_deterministic(true);
+/// assert!(neuronika::is_deterministic());
+/// assert_eq!(neuronika::num_threads(), 1);
+///
+/// neuronika::set_deterministic(false);
+/// ```
+pub fn set_deterministic(deterministic: bool) {
+    DETERMINISTIC.store(deterministic, Ordering::SeqCst);
+}
+
+/// Returns `true` if the deterministic mode is enabled.
+pub fn is_deterministic() -> bool {
+    DETERMINISTIC.load(Ordering::SeqCst)
+}
+
+/// Executes `op`, and the parallel iterators it runs, within the configured pool.
+pub(crate) fn install<R, F>(op: F) -> R
+where
+    F: FnOnce() -> R + Send,
+    R: Send,
+{
+    if is_deterministic() {
+        return sequential_pool().install(op);
+    }
+
+    let pool = POOL.read().unwrap().clone();
+    match pool {
+        Some(pool) => pool.install(op),
+        None => op(),
+    }
+}
+
+/// Executes `op`, and the parallel iterators it runs, within the configured pool. The iterators
+/// must compute matrix products, which are run sequentially if the BLAS library follows the
+/// number of threads of neuronika, see [`set_blas_num_threads_fn()`].
+pub(crate) fn install_matmul<R, F>(op: F) -> R
+where
+    F: FnOnce() -> R + Send,
+    R: Send,
+{
+    if BLAS_NUM_THREADS_FN.read().unwrap().is_some() {
+        return sequential_pool().install(op);
+    }
+
+    install(op)
+}
+
+/// Returns the pool with a single thread, used to run the parallel iterators sequentially.
+fn sequential_pool() -> &'static ThreadPool {
+    SEQUENTIAL_POOL.get_or_init(|| {
+        ThreadPoolBuilder::new()
+            .num_threads(1)
+            .thread_name(|_| "neuronika-sequential".to_string())
+            .build()
+            .expect("error: cannot spawn the threads of the pool.")
+    })
+}
+
+/// Calls `f` on each of `items` in parallel, within the configured pool.
+pub(crate) fn for_each_mut<T, F>(items: &mut [T], f: F)
+where
+    T: Send,
+    F: Fn(&mut T) + Send + Sync,
+{
+    install(|| items.par_iter_mut().for_each(f))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,46 @@
+use super::{
+    for_each_mut, install, install_matmul, is_deterministic, num_threads, set_blas_num_threads_fn,
+    set_deterministic, set_num_threads,
+};
+use std::sync::Mutex;
+
+static BLAS_THREADS: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+
+// The settings are global, so that the tests changing them are run as a single one.
+#[test]
+fn settings() {
+    set_num_threads(3);
+    assert_eq!(num_threads(), 3);
+    assert_eq!(install(rayon::current_num_threads), 3);
+    assert_eq!(install_matmul(rayon::current_num_threads), 3);
+
+    let mut items: Vec<usize> = (0..10).collect();
+    for_each_mut(&mut items, |item| *item *= 2);
+    assert_eq!(items, (0..10).map(|item| item * 2).collect::<Vec<_>>());
+
+    set_deterministic(true);
+    assert!(is_deterministic());
+    assert_eq!(num_threads(), 1);
+    assert_eq!(install(rayon::current_num_threads), 1);
+
+    for_each_mut(&mut items, |item| *item /= 2);
+    assert_eq!(items, (0..10).collect::<Vec<_>>());
+
+    set_deterministic(false);
+    assert_eq!(num_threads(), 3);
+
+    set_num_threads(0);
+    assert_eq!(num_threads(), rayon::current_num_threads());
+
+    // Once the BLAS library follows the setting, the matrix products are left to it.
+    set_blas_num_threads_fn(|num_threads| BLAS_THREADS.lock().unwrap().push(num_threads));
+    set_num_threads(3);
+    assert_eq!(install(rayon::current_num_threads), 3);
+    assert_eq!(install_matmul(rayon::current_num_threads), 1);
+
+    set_num_threads(0);
+    assert_eq!(
+        *BLAS_THREADS.lock().unwrap(),
+        vec![3, rayon::current_num_threads()]
+    );
+}
@@ -0,0 +1,235 @@
+//! Formatting of the data and of the gradients of variables.
+//!
+//! The [`Display`](std::fmt::Display) implementations of the variables and of the nodes print
+//! their tensors according to the global [`PrintOptions`], which are replaced with
+//! [`set_print_options()`]. Large tensors are summarized, so that only the elements at the edges of
+//! each axis are printed, and long rows are wrapped. The alternate flag, as in `{:#}`, prints every
+//! element regardless of the options, while the precision of the formatter, as in `{:.2}`, takes
+//! priority over the one of the options.
+use ndarray::{ArrayBase, ArrayViewD, Axis, Data, Dimension, Ix1};
+use std::{fmt, sync::RwLock};
+
+static OPTIONS: RwLock<PrintOptions> = RwLock::new(PrintOptions::new());
+
+/// The options used to print tensors.
+///
+/// By default the elements are printed with the shortest representation that round-trips,
+/// tensors with *500* or more elements are summarized with *3* elements at each edge of every
+/// axis, and lines are wrapped at *80* characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrintOptions {
+    precision: Option<usize>,
+    threshold: usize,
+    edge_items: usize,
+    line_width: usize,
+}
+
+impl PrintOptions {
+    /// Creates new print options with the default settings.
+    pub const fn new() -> Self {
+        Self {
+            precision: None,
+            threshold: 500,
+            edge_items: 3,
+            line_width: 80,
+        }
+    }
+
+    /// Sets the number of digits printed after the decimal point. `None` prints the shortest
+    /// representation of each element.
+    pub fn precision(mut self, precision: Option<usize>) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Sets the number of elements from which tensors are summarized.
+    pub fn threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Sets the number of elements printed at the beginning and at the end of each axis of a
+    /// summarized tensor.
+    ///
+    /// # Panics
+    ///
+    /// If `edge_items` is zero.
+    pub fn edge_items(mut self, edge_items: usize) -> Self {
+        assert!(
+            edge_items > 0,
+            "error: the number of edge items must be positive."
+        );
+        self.edge_items = edge_items;
+        self
+    }
+
+    /// Sets the number of characters after which rows are wrapped.
+    pub fn line_width(mut self, line_width: usize) -> Self {
+        self.line_width = line_width;
+        self
+    }
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sets the options used to print tensors.
+///
+/// The setting is global and affects the tensors printed on every thread.
+///
+/// ```
+/// use neuronika::PrintOptions;
+///
+/// neuronika::set_print_options(PrintOptions::new().precision(Some(2)).edge_items(2));
+///
+/// let x = neuronika::full(1_000, 1. / 3.);
+/// assert_eq!(format!("{}", x), "[0.33, 0.33, ..., 0.33, 0.33]");
+///
+/// neuronika::set_print_options(PrintOptions::default());
+/// ```
+pub fn set_print_options(options: PrintOptions) {
+    *OPTIONS.write().unwrap() = options;
+}
+
+/// Returns the options used to print tensors.
+pub fn print_options() -> PrintOptions {
+    *OPTIONS.read().unwrap()
+}
+
+/// Formats `tensor` according to the global print options. The precision is ignored by integer
+/// elements.
+pub(crate) fn fmt_tensor<A, S, D>(
+    tensor: &ArrayBase<S, D>,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result
+where
+    A: fmt::Display,
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    fmt_tensor_with(tensor, f, print_options())
+}
+
+fn fmt_tensor_with<A, S, D>(
+    tensor: &ArrayBase<S, D>,
+    f: &mut fmt::Formatter<'_>,
+    mut options: PrintOptions,
+) -> fmt::Result
+where
+    A: fmt::Display,
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    if let Some(precision) = f.precision() {
+        options.precision = Some(precision);
+    }
+    let summarize = !f.alternate() && tensor.len() >= options.threshold;
+
+    fmt_view(tensor.view().into_dyn(), f, &options, summarize, 0)
+}
+
+fn fmt_view<A: fmt::Display>(
+    view: ArrayViewD<A>,
+    f: &mut fmt::Formatter<'_>,
+    options: &PrintOptions,
+    summarize: bool,
+    depth: usize,
+) -> fmt::Result {
+    if view.is_empty() {
+        return write!(f, "{}{}", "[".repeat(view.ndim()), "]".repeat(view.ndim()));
+    }
+
+    match view.shape() {
+        [] => f.write_str(&fmt_element(&view[[]], options)),
+        &[len] => {
+            let view = view.into_dimensionality::<Ix1>().unwrap();
+            let indent = " ".repeat(depth + 1);
+            let mut column = depth + 1;
+
+            f.write_str("[")?;
+            for (position, index) in edge_indices(len, options.edge_items, summarize).enumerate() {
+                let element = match index {
+                    Some(index) => fmt_element(&view[index], options),
+                    None => "...".to_string(),
+                };
+
+                if position > 0 {
+                    f.write_str(",")?;
+                    column += 1;
+                    if column + 1 + element.len() > options.line_width {
+                        write!(f, "\n{}", indent)?;
+                        column = depth + 1;
+                    } else {
+                        f.write_str(" ")?;
+                        column += 1;
+                    }
+                }
+                f.write_str(&element)?;
+                column += element.len();
+            }
+            f.write_str("]")
+        }
+        shape => {
+            let separator = format!(
+                ",\n{}{}",
+                "\n".repeat(shape.len() - 2),
+                " ".repeat(depth + 1)
+            );
+
+            f.write_str("[")?;
+            for (position, index) in
+                edge_indices(shape[0], options.edge_items, summarize).enumerate()
+            {
+                if position > 0 {
+                    f.write_str(&separator)?;
+                }
+                match index {
+                    Some(index) => fmt_view(
+                        view.index_axis(Axis(0), index),
+                        f,
+                        options,
+                        summarize,
+                        depth + 1,
+                    )?,
+                    None => f.write_str("...")?,
+                }
+            }
+            f.write_str("]")
+        }
+    }
+}
+
+/// Returns the indices of the elements of an axis of length `len` that are printed, with `None`
+/// in place of the ellipsis.
+fn edge_indices(
+    len: usize,
+    edge: usize,
+    summarize: bool,
+) -> Box<dyn Iterator<Item = Option<usize>>> {
+    if !summarize || len <= 2 * edge {
+        Box::new((0..len).map(Some))
+    } else {
+        Box::new(
+            (0..edge)
+                .map(Some)
+                .chain(std::iter::once(None))
+                .chain((len - edge..len).map(Some)),
+        )
+    }
+}
+
+fn fmt_element<A: fmt::Display>(element: &A, options: &PrintOptions) -> String {
+    match options.precision {
+        Some(precision) => format!("{:.*}", precision, element),
+        None => element.to_string(),
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,88 @@
+use super::{fmt_tensor_with, print_options, PrintOptions};
+use ndarray::{Array, ArrayD, Dimension};
+use std::fmt;
+
+struct Formatted(ArrayD<f32>, PrintOptions);
+
+impl fmt::Display for Formatted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_tensor_with(&self.0, f, self.1)
+    }
+}
+
+fn formatted<D: Dimension>(tensor: Array<f32, D>, options: PrintOptions) -> Formatted {
+    Formatted(tensor.into_dyn(), options)
+}
+
+#[test]
+fn defaults() {
+    assert_eq!(print_options(), PrintOptions::default());
+
+    // Small tensors are printed as ndarray does.
+    for tensor in [
+        Array::from_elem((), 2.).into_dyn(),
+        Array::linspace(0., 1., 5).into_dyn(),
+        Array::from_shape_fn((2, 3), |(i, j)| (i * 3 + j) as f32).into_dyn(),
+        Array::from_shape_fn((2, 2, 2), |(i, j, k)| (i * 4 + j * 2 + k) as f32 - 3.5).into_dyn(),
+        Array::zeros((0, 3)).into_dyn(),
+    ] {
+        assert_eq!(
+            format!("{}", formatted(tensor.clone(), PrintOptions::new())),
+            format!("{}", tensor)
+        );
+    }
+}
+
+#[test]
+fn precision() {
+    let tensor = ndarray::array![[1., 1. / 3.], [-2.5, 1e-3]];
+    let options = PrintOptions::new().precision(Some(2));
+
+    assert_eq!(
+        format!("{}", formatted(tensor.clone(), options)),
+        "[[1.00, 0.33],\n [-2.50, 0.00]]"
+    );
+    // The precision of the formatter has priority.
+    assert_eq!(
+        format!("{:.1}", formatted(tensor, options)),
+        "[[1.0, 0.3],\n [-2.5, 0.0]]"
+    );
+}
+
+#[test]
+fn summarization() {
+    let tensor = Array::from_shape_fn((10, 10), |(i, j)| (i * 10 + j) as f32);
+    let options = PrintOptions::new().threshold(50).edge_items(2);
+
+    assert_eq!(
+        format!("{}", formatted(tensor.clone(), options)),
+        "[[0, 1, ..., 8, 9],\n [10, 11, ..., 18, 19],\n ...,\n [80, 81, ..., 88, 89],\n [90, 91, ..., 98, 99]]"
+    );
+    // The alternate flag prints every element.
+    assert_eq!(
+        format!("{:#}", formatted(tensor.clone(), options)),
+        format!("{}", tensor)
+    );
+    // Tensors below the threshold are not summarized.
+    assert_eq!(
+        format!("{}", formatted(tensor.clone(), options.threshold(101))),
+        format!("{}", tensor)
+    );
+}
+
+#[test]
+fn line_width() {
+    let tensor = Array::from_shape_fn((2, 6), |(i, j)| (i * 6 + j) as f32 * 100.);
+    let options = PrintOptions::new().line_width(20);
+
+    assert_eq!(
+        format!("{}", formatted(tensor, options)),
+        "[[0, 100, 200, 300,\n  400, 500],\n [600, 700, 800, 900,\n  1000, 1100]]"
+    );
+}
+
+#[test]
+#[should_panic(expected = "error: the number of edge items must be positive.")]
+fn edge_items_fail() {
+    PrintOptions::new().edge_items(0);
+}
@@ -0,0 +1,242 @@
+//! Per-node profiling of the forward and backward passes.
+//!
+//! When profiling is enabled, every computational node evaluated by a call to
+//! [`.forward()`](crate::Var::forward()) or [`.backward()`](crate::VarDiff::backward()) on the
+//! current thread is timed. The records are grouped by node type into a [`Report`], that lists
+//! them sorted by total time, so that the operations dominating a training step can be easily
+//! spotted.
+//!
+//! ```
+//! use neuronika::profiler;
+//!
+//! let x = neuronika::rand((64, 32)).requires_grad();
+//! let w = neuronika::rand((16, 32)).requires_grad();
+//! let y = x.mm_t(w).relu().sum();
+//!
+//! profiler::start();
+//! y.forward();
+//! y.backward(1.);
+//! let report = profiler::stop();
+//!
+//! println!("{}", report);
+//! assert!(report.summary().iter().any(|entry| entry.name == "MatrixMatrixMulT"));
+//! ```
+//!
+//! The report can also be exported in the [Chrome trace event format] with
+//! [`.to_chrome_trace()`](Report::to_chrome_trace()) and inspected with tools such as
+//! `chrome://tracing` or [Perfetto](https://ui.perfetto.dev).
+//!
+//! [Chrome trace event format]: https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{self, Display, Write},
+    time::{Duration, Instant},
+};
+
+thread_local! {
+    static PROFILER: RefCell<Option<Profiler>> = const { RefCell::new(None) };
+}
+
+/// The pass during which a node was evaluated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pass {
+    Forward,
+    Backward,
+}
+
+impl Display for Pass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Pass::Forward => write!(f, "forward"),
+            Pass::Backward => write!(f, "backward"),
+        }
+    }
+}
+
+/// A single evaluation of a computational node.
+#[derive(Debug, Clone)]
+pub struct Event {
+    /// Type of the node.
+    pub name: &'static str,
+    /// Pass during which the node was evaluated.
+    pub pass: Pass,
+    /// Time elapsed from the start of the profiling to the beginning of the evaluation.
+    pub start: Duration,
+    /// Duration of the evaluation.
+    pub duration: Duration,
+    /// Size in bytes of the data, or of the gradient, computed by the node.
+    pub bytes: usize,
+}
+
+/// Statistics about all the evaluations of the nodes of a given type during a given pass.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// Type of the nodes.
+    pub name: &'static str,
+    /// Pass during which the nodes were evaluated.
+    pub pass: Pass,
+    /// Number of evaluations.
+    pub calls: usize,
+    /// Total duration of the evaluations.
+    pub total: Duration,
+    /// Total size in bytes of the data, or of the gradients, computed by the nodes.
+    pub bytes: usize,
+}
+
+/// The result of a profiling session.
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    events: Vec<Event>,
+}
+
+impl Report {
+    /// Returns the recorded events in chronological order.
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Returns the recorded events grouped by node type and pass, sorted by decreasing total
+    /// time.
+    pub fn summary(&self) -> Vec<Entry> {
+        let mut entries: HashMap<(&'static str, Pass), Entry> = HashMap::new();
+        for event in &self.events {
+            let entry = entries.entry((event.name, event.pass)).or_insert(Entry {
+                name: event.name,
+                pass: event.pass,
+                calls: 0,
+                total: Duration::ZERO,
+                bytes: 0,
+            });
+            entry.calls += 1;
+            entry.total += event.duration;
+            entry.bytes += event.bytes;
+        }
+
+        let mut entries: Vec<Entry> = entries.into_values().collect();
+        entries.sort_by(|a, b| {
+            b.total
+                .cmp(&a.total)
+                .then_with(|| a.name.cmp(b.name))
+                .then_with(|| (a.pass as u8).cmp(&(b.pass as u8)))
+        });
+        entries
+    }
+
+    /// Returns the total time spent in the evaluation of the nodes.
+    pub fn total(&self) -> Duration {
+        self.events.iter().map(|event| event.duration).sum()
+    }
+
+    /// Serializes the recorded events in the Chrome trace event format.
+    pub fn to_chrome_trace(&self) -> String {
+        let mut trace = String::from("{\"traceEvents\":[");
+        for (i, event) in self.events.iter().enumerate() {
+            if i > 0 {
+                trace.push(',');
+            }
+            let _ = write!(
+                trace,
+                "{{\"name\":\"{}\",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":0,\"tid\":0,\"args\":{{\"bytes\":{}}}}}",
+                event.name,
+                event.pass,
+                event.start.as_secs_f64() * 1e6,
+                event.duration.as_secs_f64() * 1e6,
+                event.bytes
+            );
+        }
+        trace.push_str("]}");
+
+        trace
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.total().as_secs_f64();
+        writeln!(
+            f,
+            "{:<28}{:<10}{:>8}{:>14}{:>9}{:>14}",
+            "node", "pass", "calls", "time (ms)", "%", "bytes"
+        )?;
+        for entry in self.summary() {
+            let time = entry.total.as_secs_f64();
+            let percentage = if total > 0. { time / total * 100. } else { 0. };
+            writeln!(
+                f,
+                "{:<28}{:<10}{:>8}{:>14.3}{:>9.2}{:>14}",
+                entry.name,
+                entry.pass,
+                entry.calls,
+                time * 1e3,
+                percentage,
+                entry.bytes
+            )?;
+        }
+        write!(f, "total: {:.3} ms", total * 1e3)
+    }
+}
+
+/// Collects the events of the current profiling session.
+struct Profiler {
+    origin: Instant,
+    events: Vec<Event>,
+}
+
+/// Enables profiling on the current thread, discarding the events of any ongoing session.
+pub fn start() {
+    PROFILER.with(|profiler| {
+        *profiler.borrow_mut() = Some(Profiler {
+            origin: Instant::now(),
+            events: Vec::new(),
+        })
+    });
+}
+
+/// Disables profiling on the current thread and returns the report of the session.
+///
+/// If profiling wasn't enabled the report is empty.
+pub fn stop() -> Report {
+    PROFILER.with(|profiler| {
+        profiler
+            .borrow_mut()
+            .take()
+            .map_or_else(Report::default, |profiler| Report {
+                events: profiler.events,
+            })
+    })
+}
+
+/// Returns `true` if profiling is enabled on the current thread.
+pub fn is_enabled() -> bool {
+    PROFILER.with(|profiler| profiler.borrow().is_some())
+}
+
+/// Times the evaluation `evaluation` of a node and records it.
+///
+/// # Arguments
+///
+/// * `pass` - pass during which the node is evaluated.
+/// * `name` - type of the node.
+/// * `bytes` - size of the data, or of the gradient, computed by the node.
+/// * `evaluation` - evaluation of the node.
+pub(crate) fn record<F: FnOnce()>(pass: Pass, name: &'static str, bytes: usize, evaluation: F) {
+    let begin = Instant::now();
+    evaluation();
+    let duration = begin.elapsed();
+
+    PROFILER.with(|profiler| {
+        if let Some(profiler) = profiler.borrow_mut().as_mut() {
+            profiler.events.push(Event {
+                name,
+                pass,
+                start: begin.duration_since(profiler.origin),
+                duration,
+                bytes,
+            });
+        }
+    });
+}
+
+#[cfg(test)]
+mod test;
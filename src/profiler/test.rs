@@ -0,0 +1,69 @@
+use super::{is_enabled, record, start, stop, Pass};
+
+#[test]
+fn start_stop() {
+    assert!(!is_enabled());
+    assert!(stop().events().is_empty());
+
+    start();
+    assert!(is_enabled());
+
+    let report = stop();
+    assert!(!is_enabled());
+    assert!(report.events().is_empty());
+}
+
+#[test]
+fn record_only_when_enabled() {
+    record(Pass::Forward, "Exp", 4, || {});
+
+    start();
+    record(Pass::Forward, "Exp", 4, || {});
+    record(Pass::Backward, "ExpBackward", 4, || {});
+    record(Pass::Forward, "Exp", 8, || {});
+    let report = stop();
+
+    assert_eq!(report.events().len(), 3);
+
+    let summary = report.summary();
+    assert_eq!(summary.len(), 2);
+
+    let exp = summary.iter().find(|entry| entry.name == "Exp").unwrap();
+    assert_eq!(exp.pass, Pass::Forward);
+    assert_eq!(exp.calls, 2);
+    assert_eq!(exp.bytes, 12);
+}
+
+#[test]
+fn graph() {
+    let x = crate::ones((3, 3)).requires_grad();
+    let y = (x.exp() * 2.).sum();
+
+    start();
+    y.forward();
+    y.backward(1.);
+    let report = stop();
+
+    let summary = report.summary();
+    let find = |name: &str, pass: Pass| {
+        summary
+            .iter()
+            .find(|entry| entry.name == name && entry.pass == pass)
+            .cloned()
+    };
+
+    assert_eq!(find("Exp", Pass::Forward).unwrap().bytes, 36);
+    assert_eq!(find("Sum", Pass::Forward).unwrap().bytes, 4);
+    assert_eq!(find("ExpBackward", Pass::Backward).unwrap().calls, 1);
+    assert!(find("Exp", Pass::Backward).is_none());
+}
+
+#[test]
+fn chrome_trace() {
+    start();
+    record(Pass::Backward, "SumBackward", 4, || {});
+    let trace = stop().to_chrome_trace();
+
+    assert!(trace.starts_with("{\"traceEvents\":[{\"name\":\"SumBackward\",\"cat\":\"backward\""));
+    assert!(trace.ends_with("\"args\":{\"bytes\":4}}]}"));
+}
@@ -0,0 +1,41 @@
+//! The random number generator of neuronika.
+//!
+//! The random leaves created by [`rand()`](crate::rand()), the initializers of
+//! [`nn::init`](crate::nn::init), the masks of the dropout and the noise of
+//! [`DpSgd`](crate::optim::DpSgd) are drawn from a generator kept by each thread, which is seeded
+//! from the entropy of the system unless [`manual_seed()`] is called.
+use rand::{rngs::StdRng, SeedableRng};
+use std::cell::RefCell;
+
+thread_local! {
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
+}
+
+/// Seeds the random number generator used by neuronika on the current thread.
+///
+/// This makes the random leaves, the initialization of the layers, the dropout masks and the
+/// noise of differentially private training reproducible. The samplers, the augmentations and
+/// the other components holding a generator of their own are seeded separately.
+///
+/// ```
+/// neuronika::manual_seed(42);
+/// let first = neuronika::rand((2, 3));
+/// neuronika::manual_seed(42);
+/// let second = neuronika::rand((2, 3));
+///
+/// assert_eq!(*first.data(), *second.data());
+/// ```
+pub fn manual_seed(seed: u64) {
+    RNG.with(|rng| *rng.borrow_mut() = StdRng::seed_from_u64(seed));
+}
+
+/// Calls `f` with the random number generator of the current thread.
+pub(crate) fn with_rng<T, F: FnOnce(&mut StdRng) -> T>(f: F) -> T {
+    RNG.with(|rng| f(&mut rng.borrow_mut()))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,51 @@
+use super::manual_seed;
+use crate::{
+    nn::Linear,
+    optim::{DpSgd, L2},
+};
+
+#[test]
+fn initialization() {
+    manual_seed(7);
+    let first = Linear::new(4, 3);
+    manual_seed(7);
+    let second = Linear::new(4, 3);
+
+    assert_eq!(*first.weight.data(), *second.weight.data());
+    assert_eq!(*first.bias.data(), *second.bias.data());
+}
+
+#[test]
+fn dropout() {
+    let masks: Vec<_> = (0..2)
+        .map(|_| {
+            manual_seed(7);
+            let output = crate::ones(64).dropout(0.5);
+            output.forward();
+            let mask = output.data().clone();
+            mask
+        })
+        .collect();
+
+    assert_eq!(masks[0], masks[1]);
+}
+
+#[test]
+fn dp_sgd_noise() {
+    let grads: Vec<_> = (0..2)
+        .map(|_| {
+            let w = crate::from_ndarray(ndarray::array![1., 0.]).requires_grad();
+            let x = crate::from_ndarray(ndarray::array![[0.1, 0.], [3., 4.]]);
+            let losses = x.mv(w.clone());
+            losses.forward();
+
+            manual_seed(7);
+            let optim = DpSgd::new(losses.parameters(), 1e-2, L2::new(0.), 1.0, 1.1, 0.5);
+            optim.compute_grad(&losses);
+            let grad = w.grad().clone();
+            grad
+        })
+        .collect();
+
+    assert_eq!(grads[0], grads[1]);
+}
@@ -0,0 +1,226 @@
+//! Utilities for reinforcement learning.
+//!
+//! Policy-gradient methods train a policy on the *trajectories* it collects by interacting with
+//! one or more environments. The functions of this module expect the trajectories of a batch of
+//! environments run in parallel to be stored in two-dimensional arrays of shape *(T, N)*, where
+//! *T* is the number of time steps and *N* is the number of environments. Alongside the rewards,
+//! a trajectory stores a `dones` array, whose elements are *1* at the steps that end an episode and
+//! *0* elsewhere, so that the returns are not propagated across the boundaries of the episodes.
+//!
+//! * [`discounted_returns`] - Computes the discounted sum of the future rewards at each step.
+//!
+//! * [`generalized_advantage_estimation`] - Computes the advantages of the actions taken at each
+//! step, together with the returns used as targets by the value function.
+//!
+//! * [`entropy_bonus`] - Computes a differentiable term rewarding the entropy of the policy, which
+//! is added to the loss to encourage exploration.
+//!
+//! The returns and the advantages are targets, and are thus computed directly on arrays outside of
+//! the computational graph, in a single backward pass over the time steps.
+//!
+//! ```
+//! use neuronika::rl::{entropy_bonus, generalized_advantage_estimation};
+//!
+//! // Three steps of two environments. The first environment ends its episode at the second step.
+//! let rewards = ndarray::array![[1., 0.], [1., 0.], [1., 1.]];
+//! let dones = ndarray::array![[0., 0.], [1., 0.], [0., 0.]];
+//! let values = ndarray::array![[0.5, 0.1], [0.5, 0.2], [0.5, 0.3]];
+//! let bootstrap = ndarray::array![0.5, 0.4];
+//!
+//! let (advantages, returns) =
+//!     generalized_advantage_estimation(&rewards, &values, &dones, &bootstrap, 0.99, 0.95);
+//! assert_eq!(advantages.dim(), (3, 2));
+//! assert_eq!(returns, &advantages + &values);
+//!
+//! // The policy logits of the six steps, one row each.
+//! let logits = neuronika::rand((6, 4)).requires_grad();
+//! let loss = entropy_bonus(logits.clone(), 0.01);
+//! loss.forward();
+//! loss.backward(1.);
+//! ```
+use crate::{Data, Gradient, VarDiff};
+use ndarray::{Array1, Array2, Axis, Ix0, Ix2, Zip};
+
+/// Checks the shapes of a batch of trajectories and the discount factor.
+///
+/// # Panics
+///
+/// If the shapes are inconsistent or `gamma` doesn't lie in *[0, 1]*.
+fn check_trajectories(
+    rewards: &Array2<f32>,
+    dones: &Array2<f32>,
+    bootstrap: &Array1<f32>,
+    gamma: f32,
+) {
+    assert!(
+        rewards.dim() == dones.dim(),
+        "error: rewards of shape {:?} and dones of shape {:?} differ.",
+        rewards.shape(),
+        dones.shape()
+    );
+    assert!(
+        rewards.ncols() == bootstrap.len(),
+        "error: trajectories of {} environments cannot be bootstrapped from {} values.",
+        rewards.ncols(),
+        bootstrap.len()
+    );
+    assert!(
+        (0. ..=1.).contains(&gamma),
+        "error: {} is not a valid discount factor.",
+        gamma
+    );
+}
+
+/// Computes the discounted returns of a batch of trajectories.
+///
+/// ```text
+/// Gₜ = rₜ + γ (1 - dₜ) Gₜ₊₁
+/// ```
+///
+/// The return after the last step is given by `bootstrap`, which usually holds the values
+/// estimated for the states reached by the environments, or zeros if the trajectories are complete.
+///
+/// # Arguments
+///
+/// * `rewards` - rewards of shape *(T, N)*.
+///
+/// * `dones` - episode terminations of shape *(T, N)*.
+///
+/// * `bootstrap` - returns after the last step, of shape *(N)*.
+///
+/// * `gamma` - discount factor, in *[0, 1]*.
+///
+/// # Panics
+///
+/// If the shapes are inconsistent or `gamma` doesn't lie in *[0, 1]*.
+pub fn discounted_returns(
+    rewards: &Array2<f32>,
+    dones: &Array2<f32>,
+    bootstrap: &Array1<f32>,
+    gamma: f32,
+) -> Array2<f32> {
+    check_trajectories(rewards, dones, bootstrap, gamma);
+
+    let mut returns = Array2::zeros(rewards.raw_dim());
+    let mut next = bootstrap.clone();
+    for t in (0..rewards.nrows()).rev() {
+        Zip::from(&mut next)
+            .and(rewards.index_axis(Axis(0), t))
+            .and(dones.index_axis(Axis(0), t))
+            .for_each(|next, reward, done| *next = reward + gamma * (1. - done) * *next);
+        returns.index_axis_mut(Axis(0), t).assign(&next);
+    }
+
+    returns
+}
+
+/// Computes the advantages of a batch of trajectories with the generalized advantage estimation
+/// described in [High-Dimensional Continuous Control Using Generalized Advantage Estimation](https://arxiv.org/abs/1506.02438).
+///
+/// ```text
+/// δₜ = rₜ + γ (1 - dₜ) Vₜ₊₁ - Vₜ
+///
+/// Aₜ = δₜ + γλ (1 - dₜ) Aₜ₊₁
+/// ```
+///
+/// Returns the advantages and the returns, which are the sums of the advantages and of the values
+/// and are the targets of the value function. A `lambda` of *0* gives the one-step temporal
+/// difference errors, while a `lambda` of *1* gives the discounted returns minus the values.
+///
+/// # Arguments
+///
+/// * `rewards` - rewards of shape *(T, N)*.
+///
+/// * `values` - values estimated for the states of shape *(T, N)*.
+///
+/// * `dones` - episode terminations of shape *(T, N)*.
+///
+/// * `bootstrap` - values estimated for the states after the last step, of shape *(N)*.
+///
+/// * `gamma` - discount factor, in *[0, 1]*.
+///
+/// * `lambda` - trade-off between bias and variance, in *[0, 1]*.
+///
+/// # Panics
+///
+/// If the shapes are inconsistent or `gamma` or `lambda` don't lie in *[0, 1]*.
+pub fn generalized_advantage_estimation(
+    rewards: &Array2<f32>,
+    values: &Array2<f32>,
+    dones: &Array2<f32>,
+    bootstrap: &Array1<f32>,
+    gamma: f32,
+    lambda: f32,
+) -> (Array2<f32>, Array2<f32>) {
+    check_trajectories(rewards, dones, bootstrap, gamma);
+    assert!(
+        rewards.dim() == values.dim(),
+        "error: rewards of shape {:?} and values of shape {:?} differ.",
+        rewards.shape(),
+        values.shape()
+    );
+    assert!(
+        (0. ..=1.).contains(&lambda),
+        "error: {} is not a valid GAE parameter.",
+        lambda
+    );
+
+    let mut advantages = Array2::zeros(rewards.raw_dim());
+    let mut next_value = bootstrap.clone();
+    let mut next_advantage = Array1::zeros(bootstrap.raw_dim());
+    for t in (0..rewards.nrows()).rev() {
+        Zip::from(&mut next_advantage)
+            .and(&mut next_value)
+            .and(rewards.index_axis(Axis(0), t))
+            .and(values.index_axis(Axis(0), t))
+            .and(dones.index_axis(Axis(0), t))
+            .for_each(|advantage, next_value, reward, value, done| {
+                let delta = reward + gamma * (1. - done) * *next_value - value;
+                *advantage = delta + gamma * lambda * (1. - done) * *advantage;
+                *next_value = *value;
+            });
+        advantages
+            .index_axis_mut(Axis(0), t)
+            .assign(&next_advantage);
+    }
+    let returns = &advantages + values;
+
+    (advantages, returns)
+}
+
+/// Computes the entropy bonus of a batch of categorical policies.
+///
+/// ```text
+///           β   n   c
+/// Bᴏɴᴜs = - ―   ∑   ∑ pᵢⱼ log pᵢⱼ
+///           n  i=1 j=1
+/// ```
+///
+/// `logits` holds the raw, unnormalized scores of the actions, with the actions along the second
+/// axis. The result is *-β* times the mean entropy of the policies, so that adding it to the loss
+/// of a policy-gradient method rewards the policies that keep exploring.
+///
+/// # Arguments
+///
+/// * `logits` - scores of shape *(n, c)*.
+///
+/// * `coefficient` - weight *β* of the bonus.
+pub fn entropy_bonus<T: ?Sized, U: ?Sized>(
+    logits: VarDiff<T, U>,
+    coefficient: f32,
+) -> VarDiff<impl Data<Dim = Ix0>, impl Gradient<Dim = Ix0>>
+where
+    T: Data<Dim = Ix2>,
+    U: Gradient<Dim = Ix2>,
+{
+    let batch_size = logits.data().nrows() as f32;
+    let probabilities = logits.clone().softmax(1);
+
+    (probabilities * logits.log_softmax(1)).sum() * (coefficient / batch_size)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
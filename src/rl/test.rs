@@ -0,0 +1,161 @@
+use super::{discounted_returns, entropy_bonus, generalized_advantage_estimation};
+use ndarray::{array, Array1, Array2};
+
+fn assert_close(lhs: &Array2<f32>, rhs: &Array2<f32>) {
+    assert_eq!(lhs.dim(), rhs.dim());
+    assert!(
+        lhs.iter()
+            .zip(rhs)
+            .all(|(lhs, rhs)| (lhs - rhs).abs() < 1e-6),
+        "arrays differ:\n{}\n{}",
+        lhs,
+        rhs
+    );
+}
+
+#[test]
+fn returns() {
+    let rewards = array![[1., 1.], [2., 0.], [3., 1.]];
+    let dones = array![[0., 0.], [1., 0.], [0., 0.]];
+    let bootstrap = array![10., 0.];
+
+    let returns = discounted_returns(&rewards, &dones, &bootstrap, 0.5);
+    assert_close(
+        &returns,
+        &array![[1. + 0.5 * 2., 1. + 0.5 * 0.5], [2., 0.5], [3. + 5., 1.]],
+    );
+}
+
+#[test]
+fn returns_undiscounted() {
+    let rewards = Array2::ones((4, 3));
+    let dones = Array2::zeros((4, 3));
+
+    let returns = discounted_returns(&rewards, &dones, &Array1::zeros(3), 1.);
+    assert_close(
+        &returns,
+        &array![[4.], [3.], [2.], [1.]]
+            .broadcast((4, 3))
+            .unwrap()
+            .to_owned(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "error: rewards of shape [2, 2] and dones of shape [2, 3] differ.")]
+fn returns_shape_fail() {
+    discounted_returns(
+        &Array2::zeros((2, 2)),
+        &Array2::zeros((2, 3)),
+        &Array1::zeros(2),
+        0.9,
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "error: trajectories of 2 environments cannot be bootstrapped from 3 values."
+)]
+fn returns_bootstrap_fail() {
+    discounted_returns(
+        &Array2::zeros((2, 2)),
+        &Array2::zeros((2, 2)),
+        &Array1::zeros(3),
+        0.9,
+    );
+}
+
+#[test]
+#[should_panic(expected = "error: 1.5 is not a valid discount factor.")]
+fn returns_gamma_fail() {
+    discounted_returns(
+        &Array2::zeros((2, 2)),
+        &Array2::zeros((2, 2)),
+        &Array1::zeros(2),
+        1.5,
+    );
+}
+
+#[test]
+fn gae() {
+    let rewards = array![[1., 0.], [1., 0.], [1., 1.]];
+    let dones = array![[0., 0.], [1., 0.], [0., 0.]];
+    let values = array![[0.5, 0.1], [0.5, 0.2], [0.5, 0.3]];
+    let bootstrap = array![0.5, 0.4];
+
+    // With lambda equal to 1 the advantages are the returns minus the values.
+    let (advantages, returns) =
+        generalized_advantage_estimation(&rewards, &values, &dones, &bootstrap, 0.9, 1.);
+    let expected = discounted_returns(&rewards, &dones, &bootstrap, 0.9);
+    assert_close(&returns, &expected);
+    assert_close(&advantages, &(&expected - &values));
+
+    // With lambda equal to 0 the advantages are the temporal difference errors.
+    let (advantages, returns) =
+        generalized_advantage_estimation(&rewards, &values, &dones, &bootstrap, 0.9, 0.);
+    let deltas = array![
+        [1. + 0.9 * 0.5 - 0.5, 0.9 * 0.2 - 0.1],
+        [1. - 0.5, 0.9 * 0.3 - 0.2],
+        [1. + 0.9 * 0.5 - 0.5, 1. + 0.9 * 0.4 - 0.3]
+    ];
+    assert_close(&advantages, &deltas);
+    assert_close(&returns, &(&deltas + &values));
+
+    // Intermediate values mix the errors of the following steps.
+    let (advantages, _) =
+        generalized_advantage_estimation(&rewards, &values, &dones, &bootstrap, 0.9, 0.5);
+    let last = deltas[[2, 1]];
+    let middle = deltas[[1, 1]] + 0.45 * last;
+    assert!((advantages[[0, 1]] - (deltas[[0, 1]] + 0.45 * middle)).abs() < 1e-6);
+    // The episode of the first environment ends at the second step.
+    assert!((advantages[[1, 0]] - deltas[[1, 0]]).abs() < 1e-6);
+}
+
+#[test]
+#[should_panic(expected = "error: rewards of shape [3, 2] and values of shape [2, 2] differ.")]
+fn gae_shape_fail() {
+    generalized_advantage_estimation(
+        &Array2::zeros((3, 2)),
+        &Array2::zeros((2, 2)),
+        &Array2::zeros((3, 2)),
+        &Array1::zeros(2),
+        0.9,
+        0.9,
+    );
+}
+
+#[test]
+#[should_panic(expected = "error: -0.5 is not a valid GAE parameter.")]
+fn gae_lambda_fail() {
+    generalized_advantage_estimation(
+        &Array2::zeros((3, 2)),
+        &Array2::zeros((3, 2)),
+        &Array2::zeros((3, 2)),
+        &Array1::zeros(2),
+        0.9,
+        -0.5,
+    );
+}
+
+#[test]
+fn entropy() {
+    // A uniform policy over four actions and a deterministic one.
+    let logits = crate::from_ndarray(array![[0., 0., 0., 0.], [100., 0., 0., 0.]]).requires_grad();
+    let bonus = entropy_bonus(logits.clone(), 0.5);
+    bonus.forward();
+
+    assert!((bonus.data()[()] + 0.5 * 4f32.ln() / 2.).abs() < 1e-5);
+
+    // The gradient vanishes at the maximum of the entropy.
+    bonus.backward(1.);
+    assert!(logits.grad().row(0).iter().all(|grad| grad.abs() < 1e-6));
+
+    // Minimizing the bonus increases the entropy of a peaked policy.
+    let logits = crate::from_ndarray(array![[2., 0., 0.]]).requires_grad();
+    let bonus = entropy_bonus(logits.clone(), 1.);
+    bonus.forward();
+    bonus.backward(1.);
+    let grad = logits.grad();
+    assert!(grad[[0, 0]] > 0.);
+    assert!(grad[[0, 1]] < 0.);
+}
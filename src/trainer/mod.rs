@@ -0,0 +1,804 @@
+//! High-level training loop.
+//!
+//! Training a neural network with neuronika always follows the same steps: the training set is
+//! split in batches, for each batch the output of the model and the loss are computed, the
+//! gradients are back-propagated and the optimizer updates the parameters. At the end of each
+//! epoch the model is usually evaluated on a validation set.
+//!
+//! [`Trainer`] implements such loop once and for all. It takes a model, an optimizer and a list of
+//! [metrics](crate::metrics) and runs a given number of epochs of training and validation,
+//! returning the [`History`] of the losses and of the metrics.
+//!
+//! ```
+//! use neuronika::data::DataLoader;
+//! use neuronika::metrics::Accuracy;
+//! use neuronika::nn::{loss, Linear, ModelStatus, Module};
+//! use neuronika::optim::{SGD, L2};
+//! use neuronika::trainer::Trainer;
+//!
+//! struct Model {
+//!     linear: Linear,
+//!     status: ModelStatus,
+//! }
+//!
+//! impl Module for Model {
+//!     fn status(&self) -> &ModelStatus {
+//!         &self.status
+//!     }
+//! }
+//!
+//! let mut status = ModelStatus::default();
+//! let model = Model {
+//!     linear: status.register(Linear::new(2, 1)),
+//!     status,
+//! };
+//!
+//! let csv = "0,0,0\n0,1,1\n1,0,1\n1,1,0\n";
+//! let mut dataset = DataLoader::default()
+//!     .with_labels(&[2])
+//!     .without_headers()
+//!     .from_reader(csv.as_bytes(), 2, 1);
+//!
+//! let optimizer = SGD::new(model.parameters(), 0.1, L2::new(0.));
+//! let history = Trainer::new(&model, &optimizer)
+//!     .with_epochs(3)
+//!     .with_batch_size(2)
+//!     .with_metric("accuracy", Accuracy::new())
+//!     .fit(
+//!         &mut dataset,
+//!         None,
+//!         |input| model.linear.forward(input).sigmoid(),
+//!         |output, target| loss::mse_loss(output, target, loss::Reduction::Mean),
+//!     );
+//!
+//! assert_eq!(history.epochs().len(), 3);
+//! ```
+//!
+//! # Callbacks
+//!
+//! The loop can be customized by registering [`Callback`]s, that are notified at the start and at
+//! the end of each epoch and at the end of each training batch, and that can stop the training
+//! early by returning [`Control::Stop`].
+//!
+//! The following callbacks are provided.
+//!
+//! * [`EarlyStopping`] - Stops the training when a monitored value stops improving.
+//!
+//! * [`ModelCheckpoint`] - Saves the model whenever a monitored value improves.
+//!
+//! Both of them monitor a value of the [`EpochLogs`], named as described in
+//! [`.value()`](EpochLogs::value()).
+//!
+//! # Progress Reporting
+//!
+//! The progress of each epoch, including the throughput in samples per second, the estimated
+//! remaining time and the current loss, is described by [`Progress`] and can be displayed by
+//! registering a [`ProgressReporter`]. It forwards the progress to a [`ProgressFrontend`], such
+//! as the terminal [`ProgressBar`], or any user-defined one.
+//!
+//! # Sanity Checks
+//!
+//! Before a full training, [`.overfit_single_batch()`](Trainer::overfit_single_batch()) checks
+//! that the model is able to memorize a single batch, which quickly reveals broken gradients and
+//! data pipelines.
+//!
+//! # Metrics
+//!
+//! Registered metrics are updated with the output of the model and with the targets of each batch.
+//! The output is viewed as a tensor of shape *(batch, scores)* and the targets are flattened, as
+//! described in the [metrics](crate::metrics) module.
+use crate::{
+    data::LabeledDataset,
+    metrics::Metric,
+    nn::Module,
+    optim::Optimizer,
+    variable::{Data, Gradient, Input},
+    Var, VarDiff,
+};
+use ndarray::{Array1, Array2, Axis, Dimension, Ix0, RemoveAxis};
+pub use progress::{Progress, ProgressBar, ProgressFrontend, ProgressReporter};
+use std::{
+    fmt::{self, Display},
+    time::Instant,
+};
+
+mod progress;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Logs ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Loss and metrics computed over a whole dataset.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Logs {
+    /// Mean of the losses of the batches, weighted by their sizes.
+    pub loss: f32,
+    /// Names and values of the registered metrics.
+    pub metrics: Vec<(String, f32)>,
+}
+
+impl Logs {
+    /// Returns the value of the metric named `name`, if any.
+    pub fn metric(&self, name: &str) -> Option<f32> {
+        self.metrics
+            .iter()
+            .find(|(metric, _)| metric == name)
+            .map(|(_, value)| *value)
+    }
+}
+
+impl Display for Logs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "loss: {:.4}", self.loss)?;
+        for (name, value) in &self.metrics {
+            write!(f, " - {}: {:.4}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Results of a training epoch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EpochLogs {
+    /// Index of the epoch, starting from zero.
+    pub epoch: usize,
+    /// Loss and metrics on the training set.
+    pub train: Logs,
+    /// Loss and metrics on the validation set, if one was provided.
+    pub validation: Option<Logs>,
+}
+
+impl EpochLogs {
+    /// Returns the value named `name`, if any.
+    ///
+    /// `"loss"` is the training loss, while any other name is looked up among the training
+    /// metrics. The same names prefixed by `"val_"` refer to the validation set.
+    pub fn value(&self, name: &str) -> Option<f32> {
+        let (logs, name) = match name.strip_prefix("val_") {
+            Some(name) => (self.validation.as_ref()?, name),
+            None => (&self.train, name),
+        };
+
+        if name == "loss" {
+            Some(logs.loss)
+        } else {
+            logs.metric(name)
+        }
+    }
+
+    /// Returns the value named `monitor`.
+    ///
+    /// # Panics
+    ///
+    /// If there is no such value.
+    pub(crate) fn monitored(&self, monitor: &str) -> f32 {
+        self.value(monitor).unwrap_or_else(|| {
+            panic!(
+                "error: no value named {} in the logs of epoch {}.",
+                monitor, self.epoch
+            )
+        })
+    }
+}
+
+impl Display for EpochLogs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "epoch {} - {}", self.epoch + 1, self.train)?;
+        if let Some(validation) = &self.validation {
+            write!(f, " - validation {}", validation)?;
+        }
+        Ok(())
+    }
+}
+
+/// Results of a whole training.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct History {
+    epochs: Vec<EpochLogs>,
+}
+
+impl History {
+    /// Returns the results of each epoch that was run.
+    pub fn epochs(&self) -> &[EpochLogs] {
+        &self.epochs
+    }
+
+    /// Returns the results of the last epoch that was run, if any.
+    pub fn last(&self) -> Option<&EpochLogs> {
+        self.epochs.last()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Callbacks ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Tells the trainer whether to keep on training.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    /// Run the next epoch.
+    Continue,
+    /// Stop the training after the current epoch.
+    Stop,
+}
+
+/// Hooks into the training loop.
+///
+/// All methods have a default implementation that does nothing.
+pub trait Callback {
+    /// Called before the start of each epoch.
+    fn on_epoch_start(&mut self, _epoch: usize) {}
+
+    /// Called after each training batch with the loss computed on it.
+    fn on_batch_end(&mut self, _epoch: usize, _batch: usize, _loss: f32) {}
+
+    /// Called after each training batch with the progress of the epoch.
+    fn on_progress(&mut self, _progress: &Progress) {}
+
+    /// Called at the end of each epoch, after the validation.
+    fn on_epoch_end(&mut self, _logs: &EpochLogs) -> Control {
+        Control::Continue
+    }
+}
+
+/// Whether a monitored value should be minimized or maximized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Lower values are better.
+    Min,
+    /// Higher values are better.
+    Max,
+}
+
+impl Mode {
+    /// Returns the default mode for the value named `monitor`: losses are minimized, while metrics
+    /// are maximized.
+    fn infer(monitor: &str) -> Self {
+        if monitor.ends_with("loss") {
+            Mode::Min
+        } else {
+            Mode::Max
+        }
+    }
+}
+
+/// Keeps track of the best value of a monitored quantity.
+#[derive(Clone, Debug)]
+struct Best {
+    monitor: String,
+    mode: Mode,
+    min_delta: f32,
+    value: Option<f32>,
+}
+
+impl Best {
+    fn new(monitor: &str) -> Self {
+        Self {
+            monitor: monitor.to_string(),
+            mode: Mode::infer(monitor),
+            min_delta: 0.,
+            value: None,
+        }
+    }
+
+    /// Updates the best value with the one in `logs` and returns `true` if it improved.
+    fn update(&mut self, logs: &EpochLogs) -> bool {
+        let value = logs.monitored(&self.monitor);
+        let improved = match (self.value, self.mode) {
+            (None, _) => true,
+            (Some(best), Mode::Min) => value < best - self.min_delta,
+            (Some(best), Mode::Max) => value > best + self.min_delta,
+        };
+        if improved {
+            self.value = Some(value);
+        }
+        improved
+    }
+}
+
+/// Stops the training when a monitored value has stopped improving.
+///
+/// ```
+/// use neuronika::trainer::{EarlyStopping, Mode};
+///
+/// // Stops if the validation accuracy does not increase by more than 0.01 for 5 epochs in a row.
+/// let early_stopping = EarlyStopping::new("val_accuracy", 5)
+///     .with_mode(Mode::Max)
+///     .with_min_delta(0.01);
+/// ```
+#[derive(Clone, Debug)]
+pub struct EarlyStopping {
+    best: Best,
+    patience: usize,
+    wait: usize,
+}
+
+impl EarlyStopping {
+    /// Creates a new early stopping callback.
+    ///
+    /// # Arguments
+    ///
+    /// * `monitor` - name of the monitored value. Values whose name ends with `"loss"` are
+    ///   minimized, while all other values are maximized.
+    ///
+    /// * `patience` - number of epochs with no improvement after which the training is stopped.
+    pub fn new(monitor: &str, patience: usize) -> Self {
+        Self {
+            best: Best::new(monitor),
+            patience,
+            wait: 0,
+        }
+    }
+
+    /// Sets whether the monitored value should be minimized or maximized.
+    pub fn with_mode(mut self, mode: Mode) -> Self {
+        self.best.mode = mode;
+        self
+    }
+
+    /// Sets the minimum change of the monitored value that counts as an improvement.
+    pub fn with_min_delta(mut self, min_delta: f32) -> Self {
+        self.best.min_delta = min_delta.abs();
+        self
+    }
+
+    /// Returns the best value seen so far, if any.
+    pub fn best(&self) -> Option<f32> {
+        self.best.value
+    }
+}
+
+impl Callback for EarlyStopping {
+    fn on_epoch_end(&mut self, logs: &EpochLogs) -> Control {
+        if self.best.update(logs) {
+            self.wait = 0;
+            return Control::Continue;
+        }
+
+        self.wait += 1;
+        if self.wait >= self.patience {
+            Control::Stop
+        } else {
+            Control::Continue
+        }
+    }
+}
+
+/// Saves the model at the end of the epochs.
+///
+/// The saving itself is delegated to a closure, so that the model can be persisted in any format.
+/// When the `serialize` feature is enabled the model can, for instance, be written with any
+/// [serde](https://serde.rs) serializer.
+///
+/// ```
+/// use neuronika::trainer::ModelCheckpoint;
+/// use std::{cell::RefCell, rc::Rc};
+///
+/// let saved = Rc::new(RefCell::new(Vec::new()));
+/// let checkpoint = ModelCheckpoint::new("val_loss", {
+///     let saved = saved.clone();
+///     move |logs| saved.borrow_mut().push(logs.epoch)
+/// })
+/// .with_save_best_only();
+/// ```
+pub struct ModelCheckpoint<'a> {
+    best: Best,
+    save_best_only: bool,
+    save: Box<dyn FnMut(&EpochLogs) + 'a>,
+}
+
+impl<'a> ModelCheckpoint<'a> {
+    /// Creates a new checkpoint callback that saves the model at the end of every epoch.
+    ///
+    /// # Arguments
+    ///
+    /// * `monitor` - name of the monitored value. Values whose name ends with `"loss"` are
+    ///   minimized, while all other values are maximized.
+    ///
+    /// * `save` - saves the model, it is passed the logs of the current epoch.
+    pub fn new<F: FnMut(&EpochLogs) + 'a>(monitor: &str, save: F) -> Self {
+        Self {
+            best: Best::new(monitor),
+            save_best_only: false,
+            save: Box::new(save),
+        }
+    }
+
+    /// Saves the model only when the monitored value improves.
+    pub fn with_save_best_only(mut self) -> Self {
+        self.save_best_only = true;
+        self
+    }
+
+    /// Sets whether the monitored value should be minimized or maximized.
+    pub fn with_mode(mut self, mode: Mode) -> Self {
+        self.best.mode = mode;
+        self
+    }
+
+    /// Returns the best value seen so far, if any.
+    pub fn best(&self) -> Option<f32> {
+        self.best.value
+    }
+}
+
+impl<'a> Callback for ModelCheckpoint<'a> {
+    fn on_epoch_end(&mut self, logs: &EpochLogs) -> Control {
+        if self.best.update(logs) || !self.save_best_only {
+            (self.save)(logs);
+        }
+        Control::Continue
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Trainer ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Runs the training loop of a model.
+///
+/// See the [module-level documentation](self) for an example.
+pub struct Trainer<'a, M, O> {
+    model: &'a M,
+    optimizer: &'a O,
+    epochs: usize,
+    batch_size: usize,
+    shuffle: bool,
+    verbose: bool,
+    metrics: Vec<(String, Box<dyn Metric + 'a>)>,
+    callbacks: Vec<Box<dyn Callback + 'a>>,
+}
+
+impl<'a, 'b, M, O> Trainer<'a, M, O>
+where
+    M: Module,
+    O: Optimizer<'b>,
+{
+    /// Creates a new trainer for `model` that updates its parameters with `optimizer`.
+    ///
+    /// By default the trainer runs a single epoch, with batches of size 32, without shuffling the
+    /// training set and without printing the progress.
+    pub fn new(model: &'a M, optimizer: &'a O) -> Self {
+        Self {
+            model,
+            optimizer,
+            epochs: 1,
+            batch_size: 32,
+            shuffle: false,
+            verbose: false,
+            metrics: Vec::new(),
+            callbacks: Vec::new(),
+        }
+    }
+
+    /// Sets the number of epochs.
+    pub fn with_epochs(mut self, epochs: usize) -> Self {
+        self.epochs = epochs;
+        self
+    }
+
+    /// Sets the batch size.
+    ///
+    /// # Panics
+    ///
+    /// If `batch_size` is zero.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "error: the batch size must be positive.");
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Shuffles the training set at the start of each epoch.
+    pub fn with_shuffle(mut self) -> Self {
+        self.shuffle = true;
+        self
+    }
+
+    /// Prints the logs of each epoch to the standard output.
+    pub fn with_verbose(mut self) -> Self {
+        self.verbose = true;
+        self
+    }
+
+    /// Registers a metric under `name`.
+    pub fn with_metric<T: Metric + 'a>(mut self, name: &str, metric: T) -> Self {
+        self.metrics.push((name.to_string(), Box::new(metric)));
+        self
+    }
+
+    /// Registers a callback.
+    pub fn with_callback<T: Callback + 'a>(mut self, callback: T) -> Self {
+        self.callbacks.push(Box::new(callback));
+        self
+    }
+
+    /// Trains the model.
+    ///
+    /// The model is set in training mode during each epoch and in inference mode during the
+    /// validation. It is left in training mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `train` - training set.
+    ///
+    /// * `validation` - optional validation set.
+    ///
+    /// * `forward` - computes the output of the model given a batch of records.
+    ///
+    /// * `loss` - computes the loss given the output of the model and a batch of labels.
+    pub fn fit<D1, D2, F, L, T, U, V, W>(
+        &mut self,
+        train: &mut LabeledDataset<D1, D2>,
+        validation: Option<&LabeledDataset<D1, D2>>,
+        mut forward: F,
+        mut loss: L,
+    ) -> History
+    where
+        D1: RemoveAxis + 'static,
+        D2: RemoveAxis + 'static,
+        F: FnMut(Var<Input<D1>>) -> VarDiff<T, U>,
+        L: FnMut(VarDiff<T, U>, Var<Input<D2>>) -> VarDiff<V, W>,
+        T: Data + 'static,
+        U: Gradient<Dim = T::Dim> + 'static,
+        V: Data<Dim = Ix0> + 'static,
+        W: Gradient<Dim = Ix0> + 'static,
+    {
+        let mut history = History::default();
+
+        for epoch in 0..self.epochs {
+            self.callbacks
+                .iter_mut()
+                .for_each(|callback| callback.on_epoch_start(epoch));
+
+            if self.shuffle {
+                train.shuffle();
+            }
+
+            self.model.train();
+            let train_logs = self.run(train, epoch, true, &mut forward, &mut loss);
+
+            let validation_logs = validation.map(|validation| {
+                self.model.eval();
+                let logs = self.run(validation, epoch, false, &mut forward, &mut loss);
+                self.model.train();
+                logs
+            });
+
+            let logs = EpochLogs {
+                epoch,
+                train: train_logs,
+                validation: validation_logs,
+            };
+            if self.verbose {
+                println!("{}", logs);
+            }
+
+            let mut control = Control::Continue;
+            for callback in self.callbacks.iter_mut() {
+                if callback.on_epoch_end(&logs) == Control::Stop {
+                    control = Control::Stop;
+                }
+            }
+            history.epochs.push(logs);
+
+            if control == Control::Stop {
+                break;
+            }
+        }
+
+        history
+    }
+
+    /// Trains the model on the first batch of `train` for `steps` steps and checks that the loss
+    /// falls below `threshold`, returning the loss before each step and after the last one.
+    ///
+    /// A model with enough capacity should be able to memorize a single batch, so that this is a
+    /// quick sanity check catching broken gradients, wrong losses or corrupted data pipelines
+    /// before running a full training. The model is set in training mode, the metrics and the
+    /// callbacks are not used.
+    ///
+    /// ```
+    /// # use neuronika::data::DataLoader;
+    /// # use neuronika::nn::{loss, Linear, ModelStatus, Module};
+    /// # use neuronika::optim::{SGD, L2};
+    /// # use neuronika::trainer::Trainer;
+    /// # struct Model {
+    /// #     linear: Linear,
+    /// #     status: ModelStatus,
+    /// # }
+    /// # impl Module for Model {
+    /// #     fn status(&self) -> &ModelStatus {
+    /// #         &self.status
+    /// #     }
+    /// # }
+    /// # let mut status = ModelStatus::default();
+    /// # let model = Model {
+    /// #     linear: status.register(Linear::new(2, 1)),
+    /// #     status,
+    /// # };
+    /// # let csv = "0,0,0\n0,1,1\n1,0,1\n1,1,1\n";
+    /// # let dataset = DataLoader::default()
+    /// #     .with_labels(&[2])
+    /// #     .without_headers()
+    /// #     .from_reader(csv.as_bytes(), 2, 1);
+    /// let optimizer = SGD::new(model.parameters(), 1., L2::new(0.));
+    /// let losses = Trainer::new(&model, &optimizer)
+    ///     .with_batch_size(4)
+    ///     .overfit_single_batch(
+    ///         &dataset,
+    ///         500,
+    ///         0.1,
+    ///         |input| model.linear.forward(input).sigmoid(),
+    ///         |output, target| loss::mse_loss(output, target, loss::Reduction::Mean),
+    ///     );
+    ///
+    /// assert!(losses[losses.len() - 1] < losses[0]);
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `train` - training set, whose first batch is used.
+    ///
+    /// * `steps` - number of optimization steps.
+    ///
+    /// * `threshold` - value the loss must fall below.
+    ///
+    /// * `forward` - computes the output of the model given a batch of records.
+    ///
+    /// * `loss` - computes the loss given the output of the model and a batch of labels.
+    ///
+    /// # Panics
+    ///
+    /// If `train` is empty, or if the loss is not below `threshold` after the last step.
+    pub fn overfit_single_batch<D1, D2, F, L, T, U, V, W>(
+        &mut self,
+        train: &LabeledDataset<D1, D2>,
+        steps: usize,
+        threshold: f32,
+        mut forward: F,
+        mut loss: L,
+    ) -> Vec<f32>
+    where
+        D1: RemoveAxis + 'static,
+        D2: RemoveAxis + 'static,
+        F: FnMut(Var<Input<D1>>) -> VarDiff<T, U>,
+        L: FnMut(VarDiff<T, U>, Var<Input<D2>>) -> VarDiff<V, W>,
+        T: Data + 'static,
+        U: Gradient<Dim = T::Dim> + 'static,
+        V: Data<Dim = Ix0> + 'static,
+        W: Gradient<Dim = Ix0> + 'static,
+    {
+        let (records, labels) = train
+            .batch(self.batch_size)
+            .next()
+            .expect("error: cannot overfit a batch of an empty dataset.");
+
+        self.model.train();
+        let output = forward(crate::from_ndarray(records.to_owned()));
+        let batch_loss = loss(output, crate::from_ndarray(labels.to_owned()));
+
+        let mut losses = Vec::with_capacity(steps + 1);
+        for _ in 0..steps {
+            batch_loss.forward();
+            losses.push(batch_loss.data()[()]);
+
+            self.optimizer.zero_grad();
+            batch_loss.backward(1.);
+            self.optimizer.step();
+        }
+        batch_loss.forward();
+        let last = batch_loss.data()[()];
+        losses.push(last);
+
+        assert!(
+            last < threshold,
+            "error: the loss did not fall below {} after {} steps on a single batch, it is {}.",
+            threshold,
+            steps,
+            last
+        );
+
+        losses
+    }
+
+    /// Runs a single pass over `dataset`, updating the parameters if `training` is `true`.
+    fn run<D1, D2, F, L, T, U, V, W>(
+        &mut self,
+        dataset: &LabeledDataset<D1, D2>,
+        epoch: usize,
+        training: bool,
+        forward: &mut F,
+        loss: &mut L,
+    ) -> Logs
+    where
+        D1: RemoveAxis + 'static,
+        D2: RemoveAxis + 'static,
+        F: FnMut(Var<Input<D1>>) -> VarDiff<T, U>,
+        L: FnMut(VarDiff<T, U>, Var<Input<D2>>) -> VarDiff<V, W>,
+        T: Data + 'static,
+        U: Gradient<Dim = T::Dim> + 'static,
+        V: Data<Dim = Ix0> + 'static,
+        W: Gradient<Dim = Ix0> + 'static,
+    {
+        self.metrics
+            .iter_mut()
+            .for_each(|(_, metric)| metric.reset());
+
+        let (mut total_loss, mut samples) = (0., 0);
+        let (start, batches) = (Instant::now(), dataset.len().div_ceil(self.batch_size));
+        for (batch, (records, labels)) in dataset.batch(self.batch_size).enumerate() {
+            let size = records.len_of(Axis(0));
+            let targets = labels.to_owned();
+
+            let output = forward(crate::from_ndarray(records.to_owned()));
+            let batch_loss = loss(output.clone(), crate::from_ndarray(targets.clone()));
+            batch_loss.forward();
+            let value = batch_loss.data()[()];
+
+            if training {
+                self.optimizer.zero_grad();
+                batch_loss.backward(1.);
+                self.optimizer.step();
+                self.callbacks
+                    .iter_mut()
+                    .for_each(|callback| callback.on_batch_end(epoch, batch, value));
+            }
+
+            if !self.metrics.is_empty() {
+                let (predictions, targets) = as_metric_input(&*output.data(), &targets, size);
+                self.metrics
+                    .iter_mut()
+                    .for_each(|(_, metric)| metric.update(&predictions, &targets));
+            }
+
+            total_loss += value * size as f32;
+            samples += size;
+
+            if training {
+                let progress = Progress {
+                    epoch,
+                    epochs: self.epochs,
+                    batch: batch + 1,
+                    batches,
+                    samples,
+                    elapsed: start.elapsed(),
+                    loss: total_loss / samples as f32,
+                };
+                self.callbacks
+                    .iter_mut()
+                    .for_each(|callback| callback.on_progress(&progress));
+            }
+        }
+
+        Logs {
+            loss: if samples == 0 {
+                0.
+            } else {
+                total_loss / samples as f32
+            },
+            metrics: self
+                .metrics
+                .iter()
+                .map(|(name, metric)| (name.clone(), metric.compute()))
+                .collect(),
+        }
+    }
+}
+
+/// Views the output of a model as a tensor of shape *(batch, scores)* and flattens the targets.
+fn as_metric_input<D1: Dimension, D2: Dimension>(
+    output: &ndarray::Array<f32, D1>,
+    targets: &ndarray::Array<f32, D2>,
+    size: usize,
+) -> (Array2<f32>, Array1<f32>) {
+    let predictions = Array2::from_shape_vec(
+        (size, output.len() / size),
+        output.iter().copied().collect(),
+    )
+    .expect("error: the output of the model cannot be viewed as a batch of scores.");
+
+    (predictions, targets.iter().copied().collect())
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
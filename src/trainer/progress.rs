@@ -0,0 +1,167 @@
+use super::{Callback, Control, EpochLogs};
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
+
+/// Progress of the current training epoch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Progress {
+    /// Index of the epoch, starting from zero.
+    pub epoch: usize,
+    /// Total number of epochs.
+    pub epochs: usize,
+    /// Number of batches processed in the epoch.
+    pub batch: usize,
+    /// Total number of batches in the epoch.
+    pub batches: usize,
+    /// Number of samples processed in the epoch.
+    pub samples: usize,
+    /// Time elapsed since the start of the epoch.
+    pub elapsed: Duration,
+    /// Mean of the losses of the batches processed so far, weighted by their sizes.
+    pub loss: f32,
+}
+
+impl Progress {
+    /// Returns the number of samples processed per second.
+    pub fn throughput(&self) -> f32 {
+        let seconds = self.elapsed.as_secs_f32();
+        if seconds == 0. {
+            0.
+        } else {
+            self.samples as f32 / seconds
+        }
+    }
+
+    /// Returns the estimated time needed to complete the epoch.
+    pub fn eta(&self) -> Duration {
+        if self.batch == 0 {
+            return Duration::ZERO;
+        }
+        self.elapsed
+            .mul_f64((self.batches - self.batch) as f64 / self.batch as f64)
+    }
+}
+
+/// A frontend that displays the progress of the training.
+///
+/// Implement this trait to show the progress in a custom terminal or graphical interface and
+/// register it to a [`Trainer`](super::Trainer) through a [`ProgressReporter`].
+pub trait ProgressFrontend {
+    /// Called after each training batch.
+    fn update(&mut self, progress: &Progress);
+
+    /// Called at the end of each epoch, after the validation.
+    fn finish(&mut self, logs: &EpochLogs);
+}
+
+/// Callback that forwards the progress of the training to a [`ProgressFrontend`].
+///
+/// ```
+/// use neuronika::trainer::{ProgressBar, ProgressReporter};
+///
+/// let reporter = ProgressReporter::new(ProgressBar::new());
+/// ```
+pub struct ProgressReporter<F> {
+    frontend: F,
+}
+
+impl<F: ProgressFrontend> ProgressReporter<F> {
+    /// Creates a new progress reporter.
+    pub fn new(frontend: F) -> Self {
+        Self { frontend }
+    }
+
+    /// Returns the frontend.
+    pub fn frontend(&self) -> &F {
+        &self.frontend
+    }
+}
+
+impl<F: ProgressFrontend> Callback for ProgressReporter<F> {
+    fn on_progress(&mut self, progress: &Progress) {
+        self.frontend.update(progress);
+    }
+
+    fn on_epoch_end(&mut self, logs: &EpochLogs) -> Control {
+        self.frontend.finish(logs);
+        Control::Continue
+    }
+}
+
+/// A terminal progress bar.
+///
+/// It displays the number of processed batches, the throughput, the estimated remaining time and
+/// the current loss on a single line, which is overwritten after each batch and replaced by the
+/// logs at the end of each epoch.
+///
+/// ```text
+/// epoch 2/10 [=========>          ] 47/94 - 12034 samples/s - ETA 0s - loss: 0.3412
+/// ```
+pub struct ProgressBar<W> {
+    writer: W,
+    width: usize,
+}
+
+impl ProgressBar<io::Stderr> {
+    /// Creates a new progress bar that writes to the standard error.
+    pub fn new() -> Self {
+        Self::with_writer(io::stderr())
+    }
+}
+
+impl Default for ProgressBar<io::Stderr> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: Write> ProgressBar<W> {
+    /// Creates a new progress bar that writes to `writer`.
+    pub fn with_writer(writer: W) -> Self {
+        Self { writer, width: 20 }
+    }
+
+    /// Returns the writer.
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+}
+
+impl<W: Write> ProgressFrontend for ProgressBar<W> {
+    fn update(&mut self, progress: &Progress) {
+        let filled = (progress.batch * self.width)
+            .checked_div(progress.batches)
+            .unwrap_or(self.width);
+        let bar = match filled {
+            0 => " ".repeat(self.width),
+            filled if filled >= self.width => "=".repeat(self.width),
+            filled => format!(
+                "{}>{}",
+                "=".repeat(filled - 1),
+                " ".repeat(self.width - filled)
+            ),
+        };
+
+        // The progress is displayed on a best-effort basis.
+        let _ = write!(
+            self.writer,
+            "\repoch {}/{} [{}] {}/{} - {:.0} samples/s - ETA {}s - loss: {:.4}",
+            progress.epoch + 1,
+            progress.epochs,
+            bar,
+            progress.batch,
+            progress.batches,
+            progress.throughput(),
+            progress.eta().as_secs(),
+            progress.loss
+        );
+        let _ = self.writer.flush();
+    }
+
+    fn finish(&mut self, logs: &EpochLogs) {
+        let _ = writeln!(self.writer, "\r\x1b[2K{}", logs);
+        let _ = self.writer.flush();
+    }
+}
@@ -0,0 +1,373 @@
+use super::{
+    Callback, Control, EarlyStopping, EpochLogs, Logs, ModelCheckpoint, Progress, ProgressBar,
+    ProgressFrontend, ProgressReporter, Trainer,
+};
+use crate::{
+    data::{DataLoader, LabeledDataset},
+    metrics::Accuracy,
+    nn::{loss, Linear, ModelStatus, Module},
+    optim::{L2, SGD},
+};
+use ndarray::Ix2;
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+const DATASET: &str = "\
+    0,0,0\n\
+    0,1,0\n\
+    1,0,1\n\
+    1,1,1\n\
+    0,0,0\n\
+    1,1,1\n";
+
+struct Model {
+    linear: Linear,
+    status: ModelStatus,
+}
+
+impl Model {
+    fn new() -> Self {
+        let mut status = ModelStatus::default();
+        Self {
+            linear: status.register(Linear::new(2, 1)),
+            status,
+        }
+    }
+}
+
+impl Module for Model {
+    fn status(&self) -> &ModelStatus {
+        &self.status
+    }
+}
+
+fn dataset() -> LabeledDataset<Ix2, Ix2> {
+    DataLoader::default()
+        .with_labels(&[2])
+        .without_headers()
+        .from_reader(DATASET.as_bytes(), 2, 1)
+}
+
+#[derive(Default)]
+struct Recorder {
+    events: Rc<RefCell<Vec<String>>>,
+    stop_after: Option<usize>,
+}
+
+impl Callback for Recorder {
+    fn on_epoch_start(&mut self, epoch: usize) {
+        self.events.borrow_mut().push(format!("start {}", epoch));
+    }
+
+    fn on_batch_end(&mut self, epoch: usize, batch: usize, _loss: f32) {
+        self.events
+            .borrow_mut()
+            .push(format!("batch {} {}", epoch, batch));
+    }
+
+    fn on_epoch_end(&mut self, logs: &EpochLogs) -> Control {
+        self.events.borrow_mut().push(format!("end {}", logs.epoch));
+        match self.stop_after {
+            Some(epoch) if epoch == logs.epoch => Control::Stop,
+            _ => Control::Continue,
+        }
+    }
+}
+
+#[test]
+fn fit_decreases_loss() {
+    let model = Model::new();
+    let optimizer = SGD::new(model.parameters(), 1., L2::new(0.));
+    let (mut train, validation) = (dataset(), dataset());
+
+    let history = Trainer::new(&model, &optimizer)
+        .with_epochs(50)
+        .with_batch_size(4)
+        .with_shuffle()
+        .with_metric("accuracy", Accuracy::new())
+        .fit(
+            &mut train,
+            Some(&validation),
+            |input| model.linear.forward(input).sigmoid(),
+            |output, target| loss::mse_loss(output, target, loss::Reduction::Mean),
+        );
+
+    let (first, last) = (&history.epochs()[0], history.last().unwrap());
+    assert_eq!(history.epochs().len(), 50);
+    assert!(last.train.loss < first.train.loss);
+    assert!(
+        last.validation
+            .as_ref()
+            .unwrap()
+            .metric("accuracy")
+            .unwrap()
+            == 1.
+    );
+    assert!(last.train.metric("precision").is_none());
+    assert!(model.is_training());
+}
+
+#[test]
+fn overfit_single_batch() {
+    let model = Model::new();
+    let optimizer = SGD::new(model.parameters(), 1., L2::new(0.));
+
+    let losses = Trainer::new(&model, &optimizer)
+        .with_batch_size(4)
+        .overfit_single_batch(
+            &dataset(),
+            300,
+            0.1,
+            |input| model.linear.forward(input).sigmoid(),
+            |output, target| loss::mse_loss(output, target, loss::Reduction::Mean),
+        );
+
+    assert_eq!(losses.len(), 301);
+    assert!(losses[300] < losses[0]);
+    assert!(model.is_training());
+}
+
+#[test]
+#[should_panic(expected = "error: the loss did not fall below 0 after 2 steps on a single batch")]
+fn overfit_single_batch_fail() {
+    let model = Model::new();
+    let optimizer = SGD::new(model.parameters(), 1., L2::new(0.));
+
+    Trainer::new(&model, &optimizer).overfit_single_batch(
+        &dataset(),
+        2,
+        0.,
+        |input| model.linear.forward(input).sigmoid(),
+        |output, target| loss::mse_loss(output, target, loss::Reduction::Mean),
+    );
+}
+
+#[test]
+fn callbacks() {
+    let model = Model::new();
+    let optimizer = SGD::new(model.parameters(), 0.1, L2::new(0.));
+    let mut train = dataset();
+    let events = Rc::new(RefCell::new(Vec::new()));
+
+    let history = Trainer::new(&model, &optimizer)
+        .with_epochs(5)
+        .with_batch_size(4)
+        .with_callback(Recorder {
+            events: events.clone(),
+            stop_after: Some(1),
+        })
+        .fit(
+            &mut train,
+            None,
+            |input| model.linear.forward(input),
+            |output, target| loss::mse_loss(output, target, loss::Reduction::Mean),
+        );
+
+    assert_eq!(history.epochs().len(), 2);
+    assert!(history.last().unwrap().validation.is_none());
+    assert_eq!(
+        *events.borrow(),
+        vec![
+            "start 0",
+            "batch 0 0",
+            "batch 0 1",
+            "end 0",
+            "start 1",
+            "batch 1 0",
+            "batch 1 1",
+            "end 1"
+        ]
+    );
+}
+
+#[test]
+fn logs_display() {
+    let logs = EpochLogs {
+        epoch: 0,
+        train: Logs {
+            loss: 0.5,
+            metrics: vec![("accuracy".to_string(), 0.75)],
+        },
+        validation: None,
+    };
+
+    assert_eq!(
+        format!("{}", logs),
+        "epoch 1 - loss: 0.5000 - accuracy: 0.7500"
+    );
+}
+
+#[test]
+#[should_panic(expected = "error: the batch size must be positive.")]
+fn zero_batch_size() {
+    let model = Model::new();
+    let optimizer = SGD::new(model.parameters(), 0.1, L2::new(0.));
+    let _ = Trainer::new(&model, &optimizer).with_batch_size(0);
+}
+
+fn epoch_logs(epoch: usize, loss: f32, accuracy: f32) -> EpochLogs {
+    EpochLogs {
+        epoch,
+        train: Logs {
+            loss,
+            metrics: vec![("accuracy".to_string(), accuracy)],
+        },
+        validation: Some(Logs {
+            loss: loss * 2.,
+            metrics: Vec::new(),
+        }),
+    }
+}
+
+#[test]
+fn logs_value() {
+    let logs = epoch_logs(0, 0.5, 0.75);
+
+    assert_eq!(logs.value("loss"), Some(0.5));
+    assert_eq!(logs.value("accuracy"), Some(0.75));
+    assert_eq!(logs.value("val_loss"), Some(1.));
+    assert_eq!(logs.value("val_accuracy"), None);
+}
+
+#[test]
+fn early_stopping() {
+    let mut early_stopping = EarlyStopping::new("val_loss", 2);
+
+    assert_eq!(
+        early_stopping.on_epoch_end(&epoch_logs(0, 1., 0.)),
+        Control::Continue
+    );
+    assert_eq!(
+        early_stopping.on_epoch_end(&epoch_logs(1, 2., 0.)),
+        Control::Continue
+    );
+    assert_eq!(
+        early_stopping.on_epoch_end(&epoch_logs(2, 0.5, 0.)),
+        Control::Continue
+    );
+    assert_eq!(
+        early_stopping.on_epoch_end(&epoch_logs(3, 0.5, 0.)),
+        Control::Continue
+    );
+    assert_eq!(
+        early_stopping.on_epoch_end(&epoch_logs(4, 0.6, 0.)),
+        Control::Stop
+    );
+    assert_eq!(early_stopping.best(), Some(1.));
+}
+
+#[test]
+fn early_stopping_max() {
+    let mut early_stopping = EarlyStopping::new("accuracy", 1).with_min_delta(0.1);
+
+    early_stopping.on_epoch_end(&epoch_logs(0, 0., 0.5));
+    assert_eq!(
+        early_stopping.on_epoch_end(&epoch_logs(1, 0., 0.55)),
+        Control::Stop
+    );
+    assert_eq!(early_stopping.best(), Some(0.5));
+}
+
+#[test]
+#[should_panic(expected = "error: no value named val_accuracy in the logs of epoch 0.")]
+fn early_stopping_missing_value() {
+    EarlyStopping::new("val_accuracy", 1).on_epoch_end(&epoch_logs(0, 0., 0.));
+}
+
+#[test]
+fn model_checkpoint() {
+    let saved = RefCell::new(Vec::new());
+    let mut checkpoint =
+        ModelCheckpoint::new("accuracy", |logs| saved.borrow_mut().push(logs.epoch))
+            .with_save_best_only();
+
+    for (epoch, accuracy) in [0.5, 0.7, 0.6, 0.8].iter().enumerate() {
+        checkpoint.on_epoch_end(&epoch_logs(epoch, 0., *accuracy));
+    }
+    assert_eq!(checkpoint.best(), Some(0.8));
+    drop(checkpoint);
+
+    assert_eq!(*saved.borrow(), vec![0, 1, 3]);
+}
+
+#[test]
+fn fit_with_early_stopping() {
+    let model = Model::new();
+    let optimizer = SGD::new(model.parameters(), 0., L2::new(0.));
+    let mut train = dataset();
+
+    // With a null learning rate the loss never improves.
+    let history = Trainer::new(&model, &optimizer)
+        .with_epochs(10)
+        .with_callback(EarlyStopping::new("loss", 3))
+        .fit(
+            &mut train,
+            None,
+            |input| model.linear.forward(input),
+            |output, target| loss::mse_loss(output, target, loss::Reduction::Mean),
+        );
+
+    assert_eq!(history.epochs().len(), 4);
+}
+
+#[derive(Default)]
+struct Frontend {
+    updates: Vec<Progress>,
+    finished: usize,
+}
+
+impl ProgressFrontend for Rc<RefCell<Frontend>> {
+    fn update(&mut self, progress: &Progress) {
+        self.borrow_mut().updates.push(progress.clone());
+    }
+
+    fn finish(&mut self, _logs: &EpochLogs) {
+        self.borrow_mut().finished += 1;
+    }
+}
+
+#[test]
+fn progress_reporting() {
+    let model = Model::new();
+    let optimizer = SGD::new(model.parameters(), 0.1, L2::new(0.));
+    let mut train = dataset();
+    let frontend = Rc::new(RefCell::new(Frontend::default()));
+
+    Trainer::new(&model, &optimizer)
+        .with_epochs(2)
+        .with_batch_size(4)
+        .with_callback(ProgressReporter::new(frontend.clone()))
+        .fit(
+            &mut train,
+            None,
+            |input| model.linear.forward(input),
+            |output, target| loss::mse_loss(output, target, loss::Reduction::Mean),
+        );
+
+    let frontend = frontend.borrow();
+    assert_eq!(frontend.finished, 2);
+    assert_eq!(frontend.updates.len(), 4);
+
+    let last = &frontend.updates[3];
+    assert_eq!((last.epoch, last.epochs), (1, 2));
+    assert_eq!((last.batch, last.batches, last.samples), (2, 2, 6));
+    assert_eq!(last.eta(), Duration::ZERO);
+}
+
+#[test]
+fn progress_bar() {
+    let mut bar = ProgressBar::with_writer(Vec::new());
+    bar.update(&Progress {
+        epoch: 0,
+        epochs: 2,
+        batch: 1,
+        batches: 4,
+        samples: 100,
+        elapsed: Duration::from_secs(2),
+        loss: 0.5,
+    });
+
+    assert_eq!(
+        String::from_utf8(bar.writer().clone()).unwrap(),
+        "\repoch 1/2 [====>               ] 1/4 - 50 samples/s - ETA 6s - loss: 0.5000"
+    );
+}
@@ -0,0 +1,565 @@
+//! Hyperparameter search.
+//!
+//! A [`Study`] runs a number of *trials*, each training a model with a different set of
+//! hyperparameters drawn from a [`SearchSpace`], and keeps track of the value of an objective,
+//! such as a validation loss, reached by each of them.
+//!
+//! The hyperparameters of each trial are chosen by a [`Sampler`]. The following are provided.
+//!
+//! * [`RandomSearch`] - Draws each hyperparameter at random from its distribution.
+//!
+//! * [`GridSearch`] - Visits every combination of a finite set of values.
+//!
+//! External searchers, such as Bayesian optimizers, can drive a study by implementing
+//! [`Sampler`], which is given the results of all the previous trials.
+//!
+//! # Pruning
+//!
+//! A trial reports the intermediate values of the objective, usually once per epoch, through the
+//! [`Reporter`] trait, and stops as soon as [`.should_prune()`](Reporter::should_prune()) returns
+//! `true`, so that unpromising trials don't waste the whole training budget. Whether a trial must
+//! be pruned is decided by a [`Pruner`], such as the [`MedianPruner`]. A [`PruningCallback`]
+//! reports a value of the logs of a [`Trainer`](crate::trainer::Trainer) at the end of each epoch
+//! and stops the training when the trial is pruned.
+//!
+//! ```
+//! use neuronika::trainer::Mode;
+//! use neuronika::tune::{Distribution, MedianPruner, RandomSearch, Reporter, SearchSpace, Study};
+//!
+//! let space = SearchSpace::new()
+//!     .with("lr", Distribution::LogUniform(1e-4, 1e-1))
+//!     .with("hidden", Distribution::Choice(vec![16., 32., 64.]));
+//!
+//! let mut study = Study::new(space, RandomSearch::new().with_seed(0), Mode::Min)
+//!     .with_pruner(MedianPruner::new(2, 1));
+//! study.optimize(10, |trial| {
+//!     let (lr, hidden) = (trial.params().get("lr"), trial.params().get("hidden"));
+//!
+//!     // A stand-in for a training loop reporting its validation loss after each epoch.
+//!     let mut loss = 1.;
+//!     for epoch in 0..5 {
+//!         loss = (lr.ln() + 5.).abs() + 1. / hidden + 1. / (epoch + 1) as f32;
+//!         trial.report(epoch, loss);
+//!         if trial.should_prune() {
+//!             break;
+//!         }
+//!     }
+//!     loss
+//! });
+//!
+//! let best = study.best().unwrap();
+//! assert!(best.params.get("lr") > 1e-4);
+//! ```
+use crate::trainer::{Callback, Control, EpochLogs, Mode};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::fmt::{self, Display};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Search Space ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// The values a hyperparameter can take.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Distribution {
+    /// One of a finite set of values, such as the sizes of a layer.
+    Choice(Vec<f32>),
+    /// A value in *[low, high)*, drawn uniformly.
+    Uniform(f32, f32),
+    /// A value in *[low, high)*, whose logarithm is drawn uniformly, such as a learning rate.
+    LogUniform(f32, f32),
+}
+
+impl Distribution {
+    /// Draws a value.
+    fn sample<R: Rng>(&self, rng: &mut R) -> f32 {
+        match self {
+            Self::Choice(values) => values[rng.gen_range(0..values.len())],
+            Self::Uniform(low, high) => rng.gen_range(*low..*high),
+            Self::LogUniform(low, high) => rng.gen_range(low.ln()..high.ln()).exp(),
+        }
+    }
+}
+
+/// The hyperparameters explored by a [`Study`], each with its [`Distribution`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchSpace {
+    dimensions: Vec<(String, Distribution)>,
+}
+
+impl SearchSpace {
+    /// Creates a new empty search space.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the hyperparameter `name`, taking the values of `distribution`.
+    ///
+    /// # Panics
+    ///
+    /// If a hyperparameter named `name` already exists, if a choice is empty, if a range is empty
+    /// or if a logarithmic range is not positive.
+    pub fn with(mut self, name: &str, distribution: Distribution) -> Self {
+        assert!(
+            self.dimensions.iter().all(|(other, _)| other != name),
+            "error: the hyperparameter {} is already in the search space.",
+            name
+        );
+        let valid = match &distribution {
+            Distribution::Choice(values) => !values.is_empty(),
+            Distribution::Uniform(low, high) => low < high,
+            Distribution::LogUniform(low, high) => *low > 0. && low < high,
+        };
+        assert!(
+            valid,
+            "error: invalid distribution {:?} for the hyperparameter {}.",
+            distribution, name
+        );
+
+        self.dimensions.push((name.to_string(), distribution));
+        self
+    }
+
+    /// Returns the names and the distributions of the hyperparameters, in insertion order.
+    pub fn dimensions(&self) -> &[(String, Distribution)] {
+        &self.dimensions
+    }
+}
+
+/// The hyperparameters of a trial.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Params {
+    values: Vec<(String, f32)>,
+}
+
+impl Params {
+    /// Creates a new set of hyperparameters from their names and values.
+    pub fn new(values: Vec<(String, f32)>) -> Self {
+        Self { values }
+    }
+
+    /// Returns the value of the hyperparameter `name`.
+    ///
+    /// # Panics
+    ///
+    /// If there is no such hyperparameter.
+    pub fn get(&self, name: &str) -> f32 {
+        self.values
+            .iter()
+            .find(|(other, _)| other == name)
+            .map(|(_, value)| *value)
+            .unwrap_or_else(|| panic!("error: no hyperparameter named {}.", name))
+    }
+
+    /// Returns the names and the values of the hyperparameters.
+    pub fn values(&self) -> &[(String, f32)] {
+        &self.values
+    }
+}
+
+impl Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, value)) in self.values.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: {}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Samplers ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Chooses the hyperparameters of the trials of a [`Study`].
+pub trait Sampler {
+    /// Returns the hyperparameters of the next trial, or `None` if the search is over.
+    ///
+    /// # Arguments
+    ///
+    /// * `space` - hyperparameters to choose.
+    ///
+    /// * `trials` - results of the previous trials.
+    fn sample(&mut self, space: &SearchSpace, trials: &[TrialResult]) -> Option<Params>;
+}
+
+/// Draws each hyperparameter at random from its distribution.
+pub struct RandomSearch {
+    rng: StdRng,
+}
+
+impl RandomSearch {
+    /// Creates a new random search.
+    pub fn new() -> Self {
+        Self {
+            rng: StdRng::from_rng(rand::thread_rng()).unwrap(),
+        }
+    }
+
+    /// Seeds the search for results reproducibility.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+}
+
+impl Default for RandomSearch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sampler for RandomSearch {
+    fn sample(&mut self, space: &SearchSpace, _: &[TrialResult]) -> Option<Params> {
+        let values = space
+            .dimensions()
+            .iter()
+            .map(|(name, distribution)| (name.clone(), distribution.sample(&mut self.rng)))
+            .collect();
+
+        Some(Params::new(values))
+    }
+}
+
+/// Visits every combination of the values of the hyperparameters, the last hyperparameter
+/// changing fastest.
+///
+/// All the hyperparameters must be [`Distribution::Choice`]s.
+#[derive(Clone, Debug, Default)]
+pub struct GridSearch {
+    next: usize,
+}
+
+impl GridSearch {
+    /// Creates a new grid search.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Sampler for GridSearch {
+    /// # Panics
+    ///
+    /// If a hyperparameter is not a [`Distribution::Choice`].
+    fn sample(&mut self, space: &SearchSpace, _: &[TrialResult]) -> Option<Params> {
+        let choices: Vec<(&String, &Vec<f32>)> = space
+            .dimensions()
+            .iter()
+            .map(|(name, distribution)| match distribution {
+                Distribution::Choice(values) => (name, values),
+                _ => panic!(
+                    "error: the grid search needs a finite set of values for {}.",
+                    name
+                ),
+            })
+            .collect();
+
+        let combinations: usize = choices.iter().map(|(_, values)| values.len()).product();
+        if self.next >= combinations {
+            return None;
+        }
+
+        let mut index = self.next;
+        let mut values: Vec<(String, f32)> = choices
+            .iter()
+            .rev()
+            .map(|(name, values)| {
+                let value = values[index % values.len()];
+                index /= values.len();
+                ((*name).clone(), value)
+            })
+            .collect();
+        values.reverse();
+        self.next += 1;
+
+        Some(Params::new(values))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Pruning ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Receives the intermediate values of the objective of a trial.
+pub trait Reporter {
+    /// Reports the value of the objective at `step`, usually an epoch.
+    fn report(&mut self, step: usize, value: f32);
+
+    /// Returns `true` if the trial should be stopped, given the values reported so far.
+    fn should_prune(&self) -> bool;
+}
+
+/// Decides whether a trial should be stopped.
+pub trait Pruner {
+    /// Returns `true` if a trial that reported `value` at `step` should be stopped.
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - step of the report.
+    ///
+    /// * `value` - reported value.
+    ///
+    /// * `mode` - whether the objective is minimized or maximized.
+    ///
+    /// * `trials` - results of the previous trials.
+    fn prune(&self, step: usize, value: f32, mode: Mode, trials: &[TrialResult]) -> bool;
+}
+
+/// Never stops a trial.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NopPruner;
+
+impl Pruner for NopPruner {
+    fn prune(&self, _: usize, _: f32, _: Mode, _: &[TrialResult]) -> bool {
+        false
+    }
+}
+
+/// Stops a trial whose intermediate value is worse than the median of the values reported at the
+/// same step by the previous trials.
+#[derive(Clone, Copy, Debug)]
+pub struct MedianPruner {
+    startup_trials: usize,
+    warmup_steps: usize,
+}
+
+impl MedianPruner {
+    /// Creates a new median pruner.
+    ///
+    /// # Arguments
+    ///
+    /// * `startup_trials` - number of completed trials before which no trial is pruned.
+    ///
+    /// * `warmup_steps` - number of steps of each trial before which it is not pruned.
+    pub fn new(startup_trials: usize, warmup_steps: usize) -> Self {
+        Self {
+            startup_trials,
+            warmup_steps,
+        }
+    }
+}
+
+impl Pruner for MedianPruner {
+    fn prune(&self, step: usize, value: f32, mode: Mode, trials: &[TrialResult]) -> bool {
+        let completed = trials.iter().filter(|trial| !trial.pruned).count();
+        if step < self.warmup_steps || completed < self.startup_trials {
+            return false;
+        }
+
+        let mut values: Vec<f32> = trials
+            .iter()
+            .filter_map(|trial| {
+                trial
+                    .reports
+                    .iter()
+                    .find(|(other, _)| *other == step)
+                    .map(|(_, value)| *value)
+            })
+            .collect();
+        if values.is_empty() {
+            return false;
+        }
+
+        values.sort_by(|lhs, rhs| lhs.partial_cmp(rhs).unwrap());
+        let middle = values.len() / 2;
+        let median = if values.len().is_multiple_of(2) {
+            (values[middle - 1] + values[middle]) / 2.
+        } else {
+            values[middle]
+        };
+
+        match mode {
+            Mode::Min => value > median,
+            Mode::Max => value < median,
+        }
+    }
+}
+
+/// Reports a value of the logs of a [`Trainer`](crate::trainer::Trainer) at the end of each epoch
+/// and stops the training when the trial is pruned.
+///
+/// The monitored value is named as described in [`.value()`](EpochLogs::value()).
+pub struct PruningCallback<'a, R: Reporter> {
+    monitor: String,
+    reporter: &'a mut R,
+}
+
+impl<'a, R: Reporter> PruningCallback<'a, R> {
+    /// Creates a new pruning callback reporting the value named `monitor` to `reporter`.
+    pub fn new(monitor: &str, reporter: &'a mut R) -> Self {
+        Self {
+            monitor: monitor.to_string(),
+            reporter,
+        }
+    }
+}
+
+impl<'a, R: Reporter> Callback for PruningCallback<'a, R> {
+    /// # Panics
+    ///
+    /// If there is no value named as the monitored one in `logs`.
+    fn on_epoch_end(&mut self, logs: &EpochLogs) -> Control {
+        self.reporter
+            .report(logs.epoch, logs.monitored(&self.monitor));
+
+        if self.reporter.should_prune() {
+            Control::Stop
+        } else {
+            Control::Continue
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Study ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// A running trial, handed to the objective of a [`Study`].
+pub struct Trial<'a> {
+    id: usize,
+    params: Params,
+    reports: Vec<(usize, f32)>,
+    pruned: bool,
+    mode: Mode,
+    pruner: &'a dyn Pruner,
+    trials: &'a [TrialResult],
+}
+
+impl<'a> Trial<'a> {
+    /// Returns the index of the trial, starting from zero.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns the hyperparameters of the trial.
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+}
+
+impl<'a> Reporter for Trial<'a> {
+    fn report(&mut self, step: usize, value: f32) {
+        self.reports.push((step, value));
+        self.pruned = self.pruner.prune(step, value, self.mode, self.trials);
+    }
+
+    fn should_prune(&self) -> bool {
+        self.pruned
+    }
+}
+
+/// The outcome of a trial.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrialResult {
+    /// Index of the trial, starting from zero.
+    pub id: usize,
+    /// Hyperparameters of the trial.
+    pub params: Params,
+    /// Value of the objective returned by the trial.
+    pub value: f32,
+    /// Intermediate values reported by the trial, along with their steps.
+    pub reports: Vec<(usize, f32)>,
+    /// Whether the trial was pruned.
+    pub pruned: bool,
+}
+
+/// A hyperparameter search.
+///
+/// See the [module-level documentation](self) for an example.
+pub struct Study<S: Sampler> {
+    space: SearchSpace,
+    sampler: S,
+    pruner: Box<dyn Pruner>,
+    mode: Mode,
+    trials: Vec<TrialResult>,
+}
+
+impl<S: Sampler> Study<S> {
+    /// Creates a new study.
+    ///
+    /// # Arguments
+    ///
+    /// * `space` - hyperparameters to search.
+    ///
+    /// * `sampler` - chooses the hyperparameters of each trial.
+    ///
+    /// * `mode` - whether the objective is minimized or maximized.
+    pub fn new(space: SearchSpace, sampler: S, mode: Mode) -> Self {
+        Self {
+            space,
+            sampler,
+            pruner: Box::new(NopPruner),
+            mode,
+            trials: Vec::new(),
+        }
+    }
+
+    /// Sets the pruner deciding whether a trial should be stopped. By default no trial is pruned.
+    pub fn with_pruner<P: Pruner + 'static>(mut self, pruner: P) -> Self {
+        self.pruner = Box::new(pruner);
+        self
+    }
+
+    /// Runs at most `trials` trials, stopping earlier if the sampler is exhausted.
+    ///
+    /// The objective trains a model with the hyperparameters of the given [`Trial`], reporting
+    /// the intermediate values to it, and returns the final value of the objective.
+    pub fn optimize<F>(&mut self, trials: usize, mut objective: F)
+    where
+        F: FnMut(&mut Trial) -> f32,
+    {
+        for _ in 0..trials {
+            let params = match self.sampler.sample(&self.space, &self.trials) {
+                Some(params) => params,
+                None => break,
+            };
+
+            let mut trial = Trial {
+                id: self.trials.len(),
+                params,
+                reports: Vec::new(),
+                pruned: false,
+                mode: self.mode,
+                pruner: self.pruner.as_ref(),
+                trials: &self.trials,
+            };
+            let value = objective(&mut trial);
+
+            let Trial {
+                id,
+                params,
+                reports,
+                pruned,
+                ..
+            } = trial;
+            self.trials.push(TrialResult {
+                id,
+                params,
+                value,
+                reports,
+                pruned,
+            });
+        }
+    }
+
+    /// Returns the results of the trials run so far.
+    pub fn trials(&self) -> &[TrialResult] {
+        &self.trials
+    }
+
+    /// Returns the completed trial with the best value, if any. Pruned trials are ignored.
+    pub fn best(&self) -> Option<&TrialResult> {
+        let better = |lhs: &&TrialResult, rhs: &&TrialResult| match self.mode {
+            Mode::Min => rhs.value.partial_cmp(&lhs.value),
+            Mode::Max => lhs.value.partial_cmp(&rhs.value),
+        };
+
+        self.trials
+            .iter()
+            .filter(|trial| !trial.pruned && !trial.value.is_nan())
+            .max_by(|lhs, rhs| better(lhs, rhs).unwrap())
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
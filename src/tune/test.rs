@@ -0,0 +1,203 @@
+use super::{
+    Distribution, GridSearch, MedianPruner, Params, Pruner, PruningCallback, RandomSearch,
+    Reporter, Sampler, SearchSpace, Study, TrialResult,
+};
+use crate::trainer::{Callback, Control, EpochLogs, Logs, Mode};
+
+fn space() -> SearchSpace {
+    SearchSpace::new()
+        .with("lr", Distribution::LogUniform(1e-4, 1e-1))
+        .with("momentum", Distribution::Uniform(0., 0.9))
+        .with("hidden", Distribution::Choice(vec![8., 16.]))
+}
+
+fn result(id: usize, reports: Vec<(usize, f32)>, pruned: bool) -> TrialResult {
+    TrialResult {
+        id,
+        params: Params::default(),
+        value: reports.last().map_or(0., |(_, value)| *value),
+        reports,
+        pruned,
+    }
+}
+
+#[test]
+fn random_search() {
+    let space = space();
+    let mut sampler = RandomSearch::new().with_seed(0);
+
+    for _ in 0..100 {
+        let params = sampler.sample(&space, &[]).unwrap();
+        assert!((1e-4..1e-1).contains(&params.get("lr")));
+        assert!((0. ..0.9).contains(&params.get("momentum")));
+        assert!([8., 16.].contains(&params.get("hidden")));
+    }
+
+    // The same seed draws the same hyperparameters.
+    assert_eq!(
+        RandomSearch::new().with_seed(7).sample(&space, &[]),
+        RandomSearch::new().with_seed(7).sample(&space, &[])
+    );
+}
+
+#[test]
+fn grid_search() {
+    let space = SearchSpace::new()
+        .with("a", Distribution::Choice(vec![1., 2.]))
+        .with("b", Distribution::Choice(vec![3., 4., 5.]));
+    let mut sampler = GridSearch::new();
+
+    let mut grid = Vec::new();
+    while let Some(params) = sampler.sample(&space, &[]) {
+        grid.push((params.get("a"), params.get("b")));
+    }
+    assert_eq!(
+        grid,
+        vec![(1., 3.), (1., 4.), (1., 5.), (2., 3.), (2., 4.), (2., 5.)]
+    );
+}
+
+#[test]
+#[should_panic(expected = "error: the grid search needs a finite set of values for lr.")]
+fn grid_search_fail() {
+    GridSearch::new().sample(&space(), &[]);
+}
+
+#[test]
+#[should_panic(expected = "error: the hyperparameter lr is already in the search space.")]
+fn search_space_duplicate() {
+    space().with("lr", Distribution::Uniform(0., 1.));
+}
+
+#[test]
+#[should_panic(
+    expected = "error: invalid distribution LogUniform(0.0, 1.0) for the hyperparameter lr."
+)]
+fn search_space_invalid() {
+    SearchSpace::new().with("lr", Distribution::LogUniform(0., 1.));
+}
+
+#[test]
+#[should_panic(expected = "error: no hyperparameter named dropout.")]
+fn params_get_fail() {
+    Params::new(vec![("lr".to_string(), 0.1)]).get("dropout");
+}
+
+#[test]
+fn params_display() {
+    let params = Params::new(vec![("lr".to_string(), 0.5), ("hidden".to_string(), 16.)]);
+
+    assert_eq!(format!("{}", params), "lr: 0.5, hidden: 16");
+}
+
+#[test]
+fn median_pruner() {
+    let pruner = MedianPruner::new(2, 1);
+    let trials = vec![
+        result(0, vec![(0, 1.), (1, 0.5), (2, 0.25)], false),
+        result(1, vec![(0, 2.), (1, 1.5)], true),
+        result(2, vec![(0, 3.), (1, 2.5), (2, 2.)], false),
+    ];
+
+    // The median at the second step is 1.5.
+    assert!(pruner.prune(1, 1.6, Mode::Min, &trials));
+    assert!(!pruner.prune(1, 1.4, Mode::Min, &trials));
+    assert!(pruner.prune(1, 1.4, Mode::Max, &trials));
+    // The median at the third step is the mean of 0.25 and 2.
+    assert!(!pruner.prune(2, 1.1, Mode::Min, &trials));
+    assert!(pruner.prune(2, 1.2, Mode::Min, &trials));
+    // No trial is pruned during the warmup.
+    assert!(!pruner.prune(0, 10., Mode::Min, &trials));
+    // Nor before the startup trials are completed.
+    assert!(!pruner.prune(1, 10., Mode::Min, &trials[..2]));
+    // Nor at steps that no trial reached.
+    assert!(!pruner.prune(3, 10., Mode::Min, &trials));
+}
+
+#[test]
+fn study() {
+    let space = SearchSpace::new().with("x", Distribution::Choice(vec![3., -1., 2., 0.5]));
+    let mut study = Study::new(space, GridSearch::new(), Mode::Min);
+
+    let mut ids = Vec::new();
+    study.optimize(10, |trial| {
+        ids.push(trial.id());
+        trial.params().get("x").powi(2)
+    });
+
+    // The grid is exhausted after four trials.
+    assert_eq!(ids, vec![0, 1, 2, 3]);
+    assert_eq!(study.trials().len(), 4);
+    assert_eq!(study.best().unwrap().params.get("x"), 0.5);
+}
+
+#[test]
+fn study_pruning() {
+    let space = SearchSpace::new().with("x", Distribution::Choice(vec![1., 2., 3., 0.5]));
+    let mut study =
+        Study::new(space, GridSearch::new(), Mode::Max).with_pruner(MedianPruner::new(1, 0));
+
+    study.optimize(4, |trial| {
+        let x = trial.params().get("x");
+        let mut value = 0.;
+        for step in 0..5 {
+            value = x * (step + 1) as f32;
+            trial.report(step, value);
+            if trial.should_prune() {
+                break;
+            }
+        }
+        value
+    });
+
+    let pruned: Vec<bool> = study.trials().iter().map(|trial| trial.pruned).collect();
+    assert_eq!(pruned, vec![false, false, false, true]);
+    assert_eq!(study.trials()[3].reports, vec![(0, 0.5)]);
+    assert_eq!(study.best().unwrap().params.get("x"), 3.);
+}
+
+#[derive(Default)]
+struct Recorder {
+    reports: Vec<(usize, f32)>,
+}
+
+impl Reporter for Recorder {
+    fn report(&mut self, step: usize, value: f32) {
+        self.reports.push((step, value));
+    }
+
+    fn should_prune(&self) -> bool {
+        self.reports.len() > 1
+    }
+}
+
+fn epoch_logs(epoch: usize, loss: f32) -> EpochLogs {
+    EpochLogs {
+        epoch,
+        train: Logs {
+            loss,
+            metrics: Vec::new(),
+        },
+        validation: Some(Logs {
+            loss: loss * 2.,
+            metrics: Vec::new(),
+        }),
+    }
+}
+
+#[test]
+fn pruning_callback() {
+    let mut recorder = Recorder::default();
+    let mut callback = PruningCallback::new("val_loss", &mut recorder);
+
+    assert_eq!(callback.on_epoch_end(&epoch_logs(0, 1.)), Control::Continue);
+    assert_eq!(callback.on_epoch_end(&epoch_logs(1, 0.5)), Control::Stop);
+    assert_eq!(recorder.reports, vec![(0, 2.), (1, 1.)]);
+}
+
+#[test]
+#[should_panic(expected = "error: no value named accuracy in the logs of epoch 0.")]
+fn pruning_callback_fail() {
+    let mut recorder = Recorder::default();
+    PruningCallback::new("accuracy", &mut recorder).on_epoch_end(&epoch_logs(0, 1.));
+}
@@ -0,0 +1,156 @@
+use super::{ParamSet, RawParam, VarDiffHistory, VarHistory};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Graph ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// A handle to a whole computational graph.
+///
+/// It is obtained with [`Var::into_graph()`](crate::Var::into_graph()) or
+/// [`VarDiff::into_graph()`](crate::VarDiff::into_graph()) and takes ownership of the variable,
+/// together with all the nodes it results from, exposing the operations that act on the graph as
+/// a whole. Graphs with multiple outputs are built with [`Graph::join()`].
+///
+/// The nodes of a graph are reference counted and shared by every variable built on top of them,
+/// a node is freed once no variable nor graph refers to it anymore. Dropping a graph thus frees
+/// all of its nodes at once, provided that no other variable referring to them is still alive,
+/// such as the leaves and the intermediate results kept by the user. Parameters, in particular,
+/// usually outlive the graphs built from them, as they are owned by the model.
+///
+/// ```
+/// use ndarray::array;
+///
+/// let x = neuronika::zeros(2);
+/// let w = neuronika::from_ndarray(array![3., 4.]).requires_grad();
+/// let graph = (x.clone() * w.clone()).sum().into_graph();
+/// assert_eq!(graph.node_count(), 4);
+///
+/// x.set_data(array![1., 2.]);
+/// graph.zero_grad();
+/// graph.forward();
+/// graph.backward(1.);
+/// assert_eq!(*w.grad(), array![1., 2.]);
+///
+/// // Frees the multiplication and the sum.
+/// drop(graph);
+/// ```
+pub struct Graph {
+    forward: VarHistory,
+    backward: Option<VarDiffHistory>,
+    seeds: Vec<Box<dyn Fn(f32)>>,
+}
+
+impl Graph {
+    /// Returns a new graph made of the nodes of `forward` and `backward`, whose outputs are seeded
+    /// by `seeds` at each backward pass.
+    ///
+    /// # Arguments
+    ///
+    /// * `forward` - forward history of the graph.
+    /// * `backward` - backward history of the graph, if differentiable.
+    /// * `seeds` - fill the gradients of the differentiable outputs with a given value.
+    pub(crate) fn new(
+        forward: VarHistory,
+        backward: Option<VarDiffHistory>,
+        seeds: Vec<Box<dyn Fn(f32)>>,
+    ) -> Self {
+        Self {
+            forward,
+            backward,
+            seeds,
+        }
+    }
+
+    /// Joins `graphs` into a single graph with multiple outputs, such as the losses of a
+    /// multi-task model.
+    ///
+    /// The histories of the graphs are merged, thus the nodes they share are evaluated and
+    /// counted once. A backward pass seeds the gradients of all the differentiable outputs and
+    /// accumulates their contributions into the leaves in one sweep.
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use neuronika::Graph;
+    ///
+    /// let w = neuronika::from_ndarray(array![1., 2.]).requires_grad();
+    /// let shared = w.clone() * 2.;
+    /// let first = shared.clone().sum();
+    /// let second = (shared * 3.).sum();
+    ///
+    /// let graph = Graph::join(vec![first.into_graph(), second.into_graph()]);
+    /// assert_eq!(graph.node_count(), 5);
+    ///
+    /// graph.forward();
+    /// graph.backward(1.);
+    /// assert_eq!(*w.grad(), array![8., 8.]);
+    /// ```
+    pub fn join<I>(graphs: I) -> Self
+    where
+        I: IntoIterator<Item = Graph>,
+    {
+        let mut forward = VarHistory::new();
+        let mut backward: Option<VarDiffHistory> = None;
+        let mut seeds = Vec::new();
+        for graph in graphs {
+            forward.merge(graph.forward);
+            if let Some(history) = graph.backward {
+                backward
+                    .get_or_insert_with(|| VarDiffHistory::new(ParamSet::default()))
+                    .merge(history);
+            }
+            seeds.extend(graph.seeds);
+        }
+
+        Self::new(forward, backward, seeds)
+    }
+
+    /// Evaluates every node of the graph.
+    ///
+    /// Differently from [`.forward()`](crate::Var::forward()), all the nodes are evaluated again,
+    /// so that the data fed to the leaves since the last evaluation is always taken into account.
+    pub fn forward(&self) {
+        self.forward.reset();
+        self.forward.forward(false);
+    }
+
+    /// Back-propagates through the graph, seeding the gradients of its differentiable outputs
+    /// with `seed`.
+    ///
+    /// As for [`.backward()`](crate::VarDiff::backward()), the gradients of the differentiable
+    /// leaves are accumulated.
+    ///
+    /// # Arguments
+    ///
+    /// `seed` - value used to fill the gradients of the outputs.
+    ///
+    /// # Panics
+    ///
+    /// If none of the outputs of the graph is differentiable.
+    pub fn backward(&self, seed: f32) {
+        let history = self
+            .backward
+            .as_ref()
+            .expect("error: cannot back-propagate through a non differentiable graph.");
+        history.fit_gradients();
+        history.set_overwrite();
+        // An output may be an ancestor of another one, its seed must not be overwritten.
+        for fill in &self.seeds {
+            fill(seed);
+        }
+        history.backward();
+    }
+
+    /// Zeroes the gradients of the differentiable leaves of the graph.
+    pub fn zero_grad(&self) {
+        if let Some(history) = &self.backward {
+            for mut param in history.parameters.iter().cloned().map(RawParam::into_param) {
+                param.grad.fill(0.);
+            }
+        }
+    }
+
+    /// Returns the number of nodes of the graph, leaves included.
+    pub fn node_count(&self) -> usize {
+        self.forward.node_count()
+    }
+}
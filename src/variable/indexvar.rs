@@ -0,0 +1,271 @@
+#[cfg(unix)]
+use super::MappedEmbedding;
+use super::{
+    BinCount, Data, Embedding, EmbeddingBackward, Forward, Gradient, IndexData, IndexTensor,
+    OneHot, Unique, Var, VarDiff, VarHistory, OPERATIONS_COUNTER,
+};
+#[cfg(unix)]
+use crate::io::MappedNpy;
+use ndarray::Ix2;
+use std::{
+    cell::{Ref, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// A non-differentiable variable holding integer indices.
+///
+/// Index variables carry data that selects elements rather than data that is computed upon, such
+/// as token ids, class labels or positions. Keeping them as integers spares the operations that
+/// consume them from rounding floating point values, and makes the intent explicit in their
+/// signatures.
+///
+/// They are created with [`neuronika::indices()`](crate::indices()), or from a floating point
+/// variable with [`Var::to_indices()`], and can feed [`.embedding()`], [`.one_hot()`] and the
+/// `.gather()` method of [`Var`](Var::gather()) and [`VarDiff`](VarDiff::gather()).
+///
+/// ```
+/// let tokens = neuronika::indices(ndarray::array![[1, 4], [9, 4]]);
+/// let encoded = tokens.one_hot(10);
+///
+/// encoded.forward();
+/// assert_eq!(encoded.data().shape(), &[2, 2, 10]);
+/// ```
+///
+/// [`.embedding()`]: IndexVar::embedding()
+/// [`.one_hot()`]: IndexVar::one_hot()
+pub struct IndexVar<T: ?Sized>
+where
+    T: IndexData + 'static,
+{
+    pub(crate) node: Rc<T>,
+    pub(crate) past: VarHistory,
+}
+
+impl<T: ?Sized> Clone for IndexVar<T>
+where
+    T: IndexData + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            node: self.node.clone(),
+            past: self.past.clone(),
+        }
+    }
+}
+
+impl<T: IndexData + 'static> IndexVar<T> {
+    pub(crate) fn new(node: T) -> Self {
+        let node = Rc::new(node);
+        let mut past = VarHistory::new();
+        past.append_index_node(unsafe { OPERATIONS_COUNTER.next() }, &node);
+
+        Self { node, past }
+    }
+
+    /// Transforms `self` into a dynamically typed index variable.
+    pub fn into_dyn(self) -> IndexVar<dyn IndexData<Dim = T::Dim>> {
+        let Self { node, past } = self;
+
+        IndexVar {
+            node: node as Rc<dyn IndexData<Dim = T::Dim>>,
+            past,
+        }
+    }
+}
+
+impl<T: IndexData + Forward + 'static> IndexVar<T> {
+    /// Creates a new index variable from a node.
+    pub(crate) fn from(node: T, mut past: VarHistory) -> Self {
+        let id = unsafe { OPERATIONS_COUNTER.next() };
+        let node = Rc::new(node);
+        past.append_index_node(id, &node);
+        past.append_forward(id, node.clone());
+
+        Self { node, past }
+    }
+}
+
+impl<T: ?Sized> IndexVar<T>
+where
+    T: IndexData + 'static,
+{
+    /// Returns an immutable reference to the indices inside `self`.
+    ///
+    /// At the variable's creation the indices are filled with zeros. You can populate them with a
+    /// call to [`.forward()`](IndexVar::forward()).
+    pub fn data(&self) -> Ref<IndexTensor<T::Dim>> {
+        self.node.data()
+    }
+
+    /// Returns a mutable reference to the indices inside `self`.
+    ///
+    /// At the variable's creation the indices are filled with zeros. You can populate them with a
+    /// call to [`.forward()`](IndexVar::forward()).
+    pub fn data_mut(&self) -> RefMut<IndexTensor<T::Dim>> {
+        self.node.data_mut()
+    }
+
+    /// Propagates the computations forwards and populates all the variables from the leaves of the
+    /// graph to `self`.
+    pub fn forward(&self) {
+        self.past.forward(self.node.was_computed());
+    }
+
+    /// Encodes each index of `self` as a vector of length `classes` whose only non-zero element,
+    /// equal to one, is at the position given by the index.
+    ///
+    /// The result has the shape of `self` with an additional last axis of length `classes`.
+    ///
+    /// ```
+    /// let labels = neuronika::indices(ndarray::array![0, 2]);
+    ///
+    /// let encoded = labels.one_hot(3);
+    /// encoded.forward();
+    /// assert_eq!(*encoded.data(), ndarray::array![[1., 0., 0.], [0., 0., 1.]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// During the forward pass, if an index is negative or is not smaller than `classes`.
+    pub fn one_hot(self, classes: usize) -> Var<OneHot<T>> {
+        Var::from(OneHot::new(self.node, classes), self.past)
+    }
+
+    /// Returns the distinct indices of `self`, sorted in ascending order, as a one-dimensional
+    /// index variable.
+    ///
+    /// The length of the result depends on the indices, and is known only after the forward pass.
+    ///
+    /// ```
+    /// let labels = neuronika::indices(ndarray::array![[3, 1], [3, 0]]);
+    ///
+    /// let classes = labels.unique();
+    /// classes.forward();
+    /// assert_eq!(*classes.data(), ndarray::array![0, 1, 3]);
+    /// ```
+    pub fn unique(self) -> IndexVar<Unique<T>> {
+        IndexVar::from(Unique::new(self.node), self.past)
+    }
+
+    /// Counts the occurrences of each index of `self`, returning a one-dimensional variable of
+    /// length `bins` whose element at position *i* is the number of indices equal to *i*.
+    ///
+    /// Weighted counts can be computed with the `.bincount()` method of [`Var`](Var::bincount())
+    /// and [`VarDiff`](VarDiff::bincount()).
+    ///
+    /// ```
+    /// let labels = neuronika::indices(ndarray::array![0, 2, 2, 2]);
+    ///
+    /// let counts = labels.bincount(4);
+    /// counts.forward();
+    /// assert_eq!(*counts.data(), ndarray::array![1., 0., 3., 0.]);
+    ///
+    /// // Class frequency based weights for a loss.
+    /// let weights = 4. / (counts + 1.);
+    /// weights.forward();
+    /// assert_eq!(*weights.data(), ndarray::array![2., 4., 1., 4.]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// During the forward pass, if an index is negative or is not smaller than `bins`.
+    pub fn bincount(self, bins: usize) -> Var<BinCount<T>> {
+        Var::from(BinCount::new(self.node, bins), self.past)
+    }
+
+    /// Looks up the rows of `weight` indexed by the elements of `self`, returning a differentiable
+    /// variable whose shape is the one of `self` with an additional last axis holding the rows.
+    ///
+    /// Gradients flow only towards `weight`, each row accumulating the gradients of all the
+    /// entries that looked it up.
+    ///
+    /// ```
+    /// use neuronika::nn::init;
+    ///
+    /// let weight = neuronika::zeros((10, 3)).requires_grad();
+    /// init::ones(&weight);
+    /// let tokens = neuronika::indices(ndarray::array![[1, 4], [9, 4]]);
+    ///
+    /// let embeddings = tokens.embedding(weight);
+    /// embeddings.forward();
+    /// assert_eq!(embeddings.data().shape(), &[2, 2, 3]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// During the forward pass, if an element of `self` is negative or is not smaller than the
+    /// number of rows of `weight`.
+    pub fn embedding<F: ?Sized, B: ?Sized>(
+        self,
+        weight: VarDiff<F, B>,
+    ) -> VarDiff<Embedding<T, F>, EmbeddingBackward<B, T>>
+    where
+        F: Data<Dim = Ix2> + 'static,
+        B: Gradient<Dim = Ix2> + 'static,
+    {
+        let node = EmbeddingBackward::new(weight.node, self.node.clone());
+        let mut past = self.past;
+        past.merge(weight.var.past);
+        let var = Var::from(Embedding::new(self.node, weight.var.node), past);
+        VarDiff::from(node, weight.past, var)
+    }
+
+    /// Looks up the rows of the memory-mapped `table` indexed by the elements of `self`,
+    /// returning a variable whose shape is the one of `self` with an additional last axis holding
+    /// the rows.
+    ///
+    /// Only the rows looked up are read from the mapping, so that embedding tables larger than
+    /// the available memory can be used for inference. The table is not differentiable.
+    ///
+    /// ```
+    /// use neuronika::io::{write_npy, MappedNpy};
+    /// use std::rc::Rc;
+    ///
+    /// let path = std::env::temp_dir().join("neuronika-mapped-embedding.npy");
+    /// let table = ndarray::array![[0., 1.], [2., 3.], [4., 5.]];
+    /// write_npy(std::fs::File::create(&path).unwrap(), &table).unwrap();
+    ///
+    /// let table = Rc::new(MappedNpy::open(&path).unwrap());
+    /// let tokens = neuronika::indices(ndarray::array![2, 0]);
+    ///
+    /// let embeddings = tokens.mapped_embedding(table);
+    /// embeddings.forward();
+    /// assert_eq!(*embeddings.data(), ndarray::array![[4., 5.], [0., 1.]]);
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `table` doesn't have 2 dimensions, or, during the forward pass, if an element of `self`
+    /// is negative or is not smaller than the number of rows of `table`.
+    #[cfg(unix)]
+    pub fn mapped_embedding(self, table: Rc<MappedNpy>) -> Var<MappedEmbedding<T>> {
+        Var::from(MappedEmbedding::new(self.node, table), self.past)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Debug ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<T: ?Sized> Debug for IndexVar<T>
+where
+    T: IndexData + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IndexVar")
+            .field("node", &self.node)
+            .field("past", &self.past.len())
+            .finish()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Display ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<T: ?Sized> Display for IndexVar<T>
+where
+    T: IndexData + Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&*self.node, f)
+    }
+}
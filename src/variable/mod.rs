@@ -1,11 +1,22 @@
+mod graph;
+mod indexvar;
 mod node;
+mod shape;
 mod var;
 mod vardiff;
 
-use ndarray::{ArrayViewMutD, Ix, RawArrayViewMut};
+use crate::{
+    autograd,
+    profiler::{self, Pass},
+};
+pub use graph::Graph;
+pub use indexvar::IndexVar;
+use ndarray::{ArrayViewMutD, Dimension, Ix, RawArrayViewMut};
+pub use shape::{ShapeError, Shaped};
 use std::{
     cell::{Ref, RefCell},
     collections::{BTreeMap, HashSet},
+    fmt::Write,
     hash::{Hash, Hasher},
     rc::Rc,
 };
@@ -14,8 +25,13 @@ pub use vardiff::VarDiff;
 
 pub(crate) use node::*;
 pub use node::{
-    Backward, Cache, Constant, Convolve, ConvolveWithGroups, Data, Eval, Forward, Gradient, Input,
-    InputBackward, Overwrite, PaddingMode, Reflective, Replicative, Zero,
+    Backward, Cache, Constant, Convolve, ConvolveWithGroups, Data, Eval, Forward, Gradient,
+    IndexData, IndexInput, Input, InputBackward, Overwrite, PaddingMode, Reflective, Replicative,
+    Zero,
+};
+pub(crate) use shape::{
+    check_cat, check_convolution, check_loss, check_mm, check_mv, check_nll_loss, check_stack,
+    check_vm, check_vv,
 };
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -46,6 +62,9 @@ pub struct VarHistory {
     path: BTreeMap<usize, Rc<dyn Forward>>,
     buffer: RefCell<Vec<Rc<dyn Forward>>>,
     changeables: HashSet<Changeable>,
+    nodes: BTreeMap<usize, NodeInfo>,
+    head: Option<usize>,
+    operands: Vec<usize>,
 }
 
 impl VarHistory {
@@ -55,16 +74,226 @@ impl VarHistory {
             path: BTreeMap::new(),
             buffer: RefCell::new(Vec::new()),
             changeables: HashSet::new(),
+            nodes: BTreeMap::new(),
+            head: None,
+            operands: Vec::new(),
         }
     }
 
     /// Merges `self` and `other`. This is equivalent to a set-intersection.
     ///
+    /// The node `other` belongs to becomes an operand of the next node appended to `self`.
+    ///
     /// # Arguments
     ///
     /// `other` - other VarHistory.
     pub(crate) fn merge(&mut self, mut other: VarHistory) {
         self.path.append(&mut other.path);
+        self.buffer.get_mut().truncate(0);
+        for (id, info) in other.nodes {
+            self.nodes
+                .entry(id)
+                .and_modify(|node| node.requires_grad |= info.requires_grad)
+                .or_insert(info);
+        }
+        self.operands.extend(other.head);
+    }
+
+    /// Appends the description of a new node to `self`, making it the node `self` belongs to. The
+    /// new node has id `id` and its operands are the nodes of the histories merged so far.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - id of the new node.
+    /// * `node` - new node.
+    pub(crate) fn append_node<T: Data + 'static>(&mut self, id: usize, node: &Rc<T>) {
+        let finite = {
+            let node = node.clone();
+            Rc::new(move || node.data().iter().all(|el| el.is_finite()))
+        };
+        let info = NodeInfo::new(std::any::type_name::<T>(), node.data().shape(), finite);
+        self.append_info(id, info);
+    }
+
+    /// Appends the description of a new node holding integer indices to `self`, making it the node
+    /// `self` belongs to. The new node has id `id` and its operands are the nodes of the histories
+    /// merged so far.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - id of the new node.
+    /// * `node` - new node.
+    pub(crate) fn append_index_node<T: IndexData + 'static>(&mut self, id: usize, node: &Rc<T>) {
+        let mut info = NodeInfo::new(
+            std::any::type_name::<T>(),
+            node.data().shape(),
+            Rc::new(|| true),
+        );
+        info.element_size = std::mem::size_of::<i64>();
+        self.append_info(id, info);
+    }
+
+    /// Appends `info` to `self`, with the nodes of the histories merged so far as operands.
+    fn append_info(&mut self, id: usize, mut info: NodeInfo) {
+        info.operands = self
+            .head
+            .into_iter()
+            .chain(self.operands.drain(..))
+            .collect();
+        self.nodes.insert(id, info);
+        self.head = Some(id);
+    }
+
+    /// Panics if the data of the node with id `id` contains non-finite values, reporting such
+    /// node together with its ancestors.
+    ///
+    /// # Arguments
+    ///
+    /// `id` - id of the node.
+    pub(crate) fn check_anomaly(&self, id: usize) {
+        let node = match self.nodes.get(&id) {
+            Some(node) => node,
+            None => return,
+        };
+
+        if !(node.finite)() {
+            let mut upstream = String::new();
+            self.write_upstream(id, 1, &mut upstream, &mut HashSet::new());
+            panic!(
+                "error: anomaly detected, the data of node {} {:?} contains non-finite values.\n\
+                Upstream computation:\n{}",
+                node.short_name(),
+                node.shape,
+                upstream
+            );
+        }
+    }
+
+    /// Writes into `upstream` the description of the node with id `id` and of its ancestors,
+    /// indented according to their depth. Already visited nodes are not expanded again.
+    fn write_upstream(
+        &self,
+        id: usize,
+        depth: usize,
+        upstream: &mut String,
+        visited: &mut HashSet<usize>,
+    ) {
+        let node = match self.nodes.get(&id) {
+            Some(node) => node,
+            None => return,
+        };
+
+        let _ = writeln!(
+            upstream,
+            "{:indent$}{} {:?}",
+            "",
+            node.short_name(),
+            node.shape,
+            indent = depth * 2
+        );
+        if visited.insert(id) {
+            for operand in &node.operands {
+                self.write_upstream(*operand, depth + 1, upstream, visited);
+            }
+        }
+    }
+
+    /// Propagates the computations forwards through the nodes of `self`.
+    ///
+    /// # Arguments
+    ///
+    /// `recompute` - whether the node `self` belongs to was already computed, in which case the
+    /// user wants to compute the whole history again.
+    pub(crate) fn forward(&self, recompute: bool) {
+        if recompute {
+            assert_eq!(self.len(), self.buffer().len());
+            for node in self.buffer().iter() {
+                node.reset_computation();
+            }
+        }
+
+        self.prepare_buffer();
+
+        let buffer = self.buffer();
+        let mut res = buffer.binary_search_by(|n| {
+            if n.was_computed() {
+                std::cmp::Ordering::Less
+            } else {
+                std::cmp::Ordering::Greater
+            }
+        });
+
+        if let Err(i) = res {
+            if buffer.get(i).is_some() {
+                res = Ok(i);
+            }
+        };
+
+        if let Ok(pos) = res {
+            let detect_anomaly = autograd::is_anomaly_enabled();
+            if profiler::is_enabled() || detect_anomaly {
+                for (id, node) in self.ids().skip(pos).zip(&buffer[pos..]) {
+                    let (name, bytes) = self.describe(*id);
+                    profiler::record(Pass::Forward, name, bytes, || node.forward());
+                    if detect_anomaly {
+                        self.check_anomaly(*id);
+                    }
+                }
+            } else {
+                for node in &buffer[pos..] {
+                    node.forward();
+                }
+            }
+        }
+    }
+
+    /// Returns the short name and the size in bytes of the data of the node with id `id`.
+    ///
+    /// # Arguments
+    ///
+    /// `id` - id of the node.
+    pub(crate) fn describe(&self, id: usize) -> (&'static str, usize) {
+        self.nodes
+            .get(&id)
+            .map_or(UNDESCRIBED_NODE, |node| (node.short_name(), node.bytes()))
+    }
+
+    /// Returns the ids of the nodes in the forward path, in the same order as the buffer.
+    pub(crate) fn ids(&self) -> impl DoubleEndedIterator<Item = &usize> {
+        self.path.keys()
+    }
+
+    /// Marks the node `self` belongs to as differentiable.
+    pub(crate) fn set_requires_grad(&mut self) {
+        if let Some(node) = self.head.and_then(|id| self.nodes.get_mut(&id)) {
+            node.requires_grad = true;
+        }
+    }
+
+    /// Renders the computational graph described by `self` in the Graphviz DOT language.
+    pub(crate) fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n");
+        for (id, node) in &self.nodes {
+            let _ = write!(
+                dot,
+                "    {} [label=\"{} {:?}\"",
+                id,
+                node.short_name(),
+                node.shape
+            );
+            if node.requires_grad {
+                dot.push_str(", style=filled, fillcolor=lightblue");
+            }
+            dot.push_str("];\n");
+        }
+        for (id, node) in &self.nodes {
+            for operand in &node.operands {
+                let _ = writeln!(dot, "    {} -> {};", operand, id);
+            }
+        }
+        dot.push('}');
+
+        dot
     }
 
     /// Appends a new forward computational node to `self`. The new node has id `id`.
@@ -97,6 +326,11 @@ impl VarHistory {
         self.path.is_empty()
     }
 
+    /// Returns the number of nodes described by `self`, leaves included.
+    pub(crate) fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
     /// Prepares the buffer. Clones and transfers the content of the forward path
     /// into a vector. Such vector will be used to perform the actual forward pass.
     pub(crate) fn prepare_buffer(&self) {
@@ -109,15 +343,80 @@ impl VarHistory {
     pub(crate) fn buffer(&self) -> Ref<[Rc<dyn Forward>]> {
         Ref::map(self.buffer.borrow(), |vec| &vec[..])
     }
+
+    /// Marks all the nodes of `self` as not computed.
+    pub(crate) fn reset(&self) {
+        self.prepare_buffer();
+        for node in self.buffer().iter() {
+            node.reset_computation();
+        }
+    }
+}
+
+#[derive(Clone)]
+/// The description of a node of a computational graph, used to render, profile and check it.
+struct NodeInfo {
+    name: &'static str,
+    shape: Vec<Ix>,
+    element_size: usize,
+    operands: Vec<usize>,
+    requires_grad: bool,
+    finite: Rc<dyn Fn() -> bool>,
+}
+
+impl NodeInfo {
+    /// Returns a new `NodeInfo` describing a node with no operands.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - type name of the node.
+    /// * `shape` - shape of the node's buffer.
+    /// * `finite` - returns `true` if the node's buffer contains only finite values.
+    fn new(name: &'static str, shape: &[Ix], finite: Rc<dyn Fn() -> bool>) -> Self {
+        Self {
+            name,
+            shape: shape.to_vec(),
+            element_size: std::mem::size_of::<f32>(),
+            operands: Vec::new(),
+            requires_grad: false,
+            finite,
+        }
+    }
+
+    /// Returns the name of the node's type stripped of its path and generic parameters.
+    fn short_name(&self) -> &'static str {
+        short_type_name(self.name)
+    }
+
+    /// Returns the size in bytes of the node's buffer.
+    fn bytes(&self) -> usize {
+        self.shape.iter().product::<usize>() * self.element_size
+    }
+}
+
+/// Strips `name` of its path and generic parameters.
+///
+/// # Arguments
+///
+/// `name` - type name to strip.
+pub(crate) fn short_type_name(name: &'static str) -> &'static str {
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
 }
 
+/// Name and size under which the nodes that are not described by their history, such as hooks,
+/// are profiled.
+const UNDESCRIBED_NODE: (&str, usize) = ("Hook", 0);
+
 #[derive(Clone)]
 /// The computational backward-history of a variable. It keeps track of the computation up to the
 /// variable to whom the struct belongs.
 pub struct VarDiffHistory {
     path: BTreeMap<usize, Rc<dyn Backward>>,
     buffer: RefCell<Vec<Rc<dyn Backward>>>,
-    parameters: HashSet<RawParam>,
+    parameters: ParamSet,
+    nodes: BTreeMap<usize, NodeInfo>,
+    fits: BTreeMap<usize, Rc<dyn Fn()>>,
 }
 
 impl VarDiffHistory {
@@ -126,11 +425,13 @@ impl VarDiffHistory {
     /// # Arguments
     ///
     /// ` parameters` - parameters to store.
-    pub(crate) fn new(parameters: HashSet<RawParam>) -> Self {
+    pub(crate) fn new(parameters: ParamSet) -> Self {
         Self {
             path: BTreeMap::new(),
             buffer: RefCell::new(Vec::new()),
             parameters,
+            nodes: BTreeMap::new(),
+            fits: BTreeMap::new(),
         }
     }
 
@@ -141,7 +442,114 @@ impl VarDiffHistory {
     /// `other` - other VarDiffHistory.
     pub(crate) fn merge(&mut self, mut other: VarDiffHistory) {
         self.path.append(&mut other.path);
+        self.buffer.get_mut().truncate(0);
         self.parameters.extend(other.parameters);
+        self.nodes.append(&mut other.nodes);
+        self.fits.append(&mut other.fits);
+    }
+
+    /// Appends the description of a new backward node to `self`. The new node has id `id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - id of the new node.
+    /// * `node` - new node.
+    /// * `data` - forward node whose data the gradient of `node` refers to.
+    pub(crate) fn append_node<T, U>(&mut self, id: usize, node: &Rc<T>, data: &Rc<U>)
+    where
+        T: Gradient + 'static,
+        U: Data + ?Sized + 'static,
+    {
+        let finite = {
+            let node = node.clone();
+            Rc::new(move || node.gradient().iter().all(|el| el.is_finite()))
+        };
+        let info = NodeInfo::new(std::any::type_name::<T>(), node.gradient().shape(), finite);
+        self.nodes.insert(id, info);
+
+        let fit = {
+            let (node, data) = (node.clone(), data.clone());
+            Rc::new(move || {
+                let data = data.data();
+                let mut gradient = node.gradient_mut();
+                if gradient.shape() != data.shape() {
+                    let mut shape = gradient.raw_dim();
+                    shape.slice_mut().copy_from_slice(data.shape());
+                    *gradient = Tensor::zeros(shape);
+                }
+            })
+        };
+        self.fits.insert(id, fit);
+    }
+
+    /// Reallocates the gradients whose shape no longer matches the one of the corresponding data.
+    /// This happens when the graph is evaluated on inputs with a different shape, such as batches
+    /// of a different size.
+    pub(crate) fn fit_gradients(&self) {
+        for fit in self.fits.values() {
+            fit();
+        }
+    }
+
+    /// Marks the gradients of all the nodes of `self` as overwritable, so that the results of a
+    /// previous backward pass over a shared portion of the graph are discarded.
+    pub(crate) fn set_overwrite(&self) {
+        self.prepare_buffer();
+        for node in self.buffer().iter() {
+            node.set_overwrite(true);
+        }
+    }
+
+    /// Propagates the gradients backwards through the nodes of `self`.
+    pub(crate) fn backward(&self) {
+        self.prepare_buffer();
+        let buffer = self.buffer();
+        let detect_anomaly = autograd::is_anomaly_enabled();
+        if profiler::is_enabled() || detect_anomaly {
+            for (id, node) in self.ids().rev().zip(buffer.iter().rev()) {
+                if detect_anomaly {
+                    self.check_anomaly(*id);
+                }
+                let (name, bytes) = self.describe(*id);
+                profiler::record(Pass::Backward, name, bytes, || node.backward());
+            }
+        } else {
+            for node in buffer.iter().rev() {
+                node.backward();
+            }
+        }
+    }
+
+    /// Panics if the gradient of the node with id `id` contains non-finite values.
+    ///
+    /// # Arguments
+    ///
+    /// `id` - id of the node.
+    pub(crate) fn check_anomaly(&self, id: usize) {
+        if let Some(node) = self.nodes.get(&id) {
+            assert!(
+                (node.finite)(),
+                "error: anomaly detected, the gradient of node {} {:?} contains non-finite values.",
+                node.short_name(),
+                node.shape
+            );
+        }
+    }
+
+    /// Returns the short name and the size in bytes of the gradient of the node with id `id`.
+    ///
+    /// # Arguments
+    ///
+    /// `id` - id of the node.
+    pub(crate) fn describe(&self, id: usize) -> (&'static str, usize) {
+        self.nodes
+            .get(&id)
+            .map_or(UNDESCRIBED_NODE, |node| (node.short_name(), node.bytes()))
+    }
+
+    /// Returns the ids of the nodes in the backward path, in the same order as the buffer.
+    pub(crate) fn ids(&self) -> impl DoubleEndedIterator<Item = &usize> {
+        self.path.keys()
     }
 
     /// Appends a new backward computational node to `self`. The new node has id `id`.
@@ -196,6 +604,11 @@ impl RawParam {
         Self { data, grad, shape }
     }
 
+    /// Returns the number of elements of the differentiable variable that it refers to.
+    pub(crate) fn len(&self) -> usize {
+        self.shape.iter().product()
+    }
+
     /// Consumes the RawParam, yielding mutable views over the data and the gradient of the
     /// differentiable variable that it refers to. The lifetime `'a` is for the
     /// scope of the borrow.
@@ -212,6 +625,41 @@ impl RawParam {
     }
 }
 
+/// A set of [`RawParam`] that keeps the order in which they were inserted, so that the
+/// parameters of a variable are listed in the same order at every run.
+#[derive(Clone, Default)]
+pub(crate) struct ParamSet {
+    order: Vec<RawParam>,
+    members: HashSet<RawParam>,
+}
+
+impl ParamSet {
+    /// Inserts `param` at the end of `self`, unless it is already there.
+    pub(crate) fn insert(&mut self, param: RawParam) {
+        if self.members.insert(param.clone()) {
+            self.order.push(param);
+        }
+    }
+
+    /// Inserts the parameters of `other` at the end of `self`, skipping the ones already there.
+    pub(crate) fn extend(&mut self, other: ParamSet) {
+        for param in other.order {
+            self.insert(param);
+        }
+    }
+
+    /// Returns the number of parameters in `self`.
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Returns an iterator over the parameters of `self`, in insertion order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &RawParam> {
+        self.order.iter()
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Param Struct ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -363,6 +811,62 @@ pub trait Stack<Rhs> {
     fn stack(self, other: Rhs, axis: usize) -> Self::Output;
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Maximum and Minimum traits ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Element-wise maximum.
+pub trait Maximum<Rhs> {
+    /// The type of the element-wise maximum's result. See the [*differentiability arithmetic*]
+    /// for more details.
+    ///
+    /// [*differentiability arithmetic*]: index.html#differentiability-arithmetic
+    type Output;
+
+    /// Computes the element-wise maximum between `self` and `other`, broadcasting them to a
+    /// common shape.
+    ///
+    /// The gradient of each element flows to the larger operand, and is split evenly between the
+    /// two when they are equal. `NaN`s are propagated.
+    fn maximum(self, other: Rhs) -> Self::Output;
+}
+
+/// Element-wise minimum.
+pub trait Minimum<Rhs> {
+    /// The type of the element-wise minimum's result. See the [*differentiability arithmetic*]
+    /// for more details.
+    ///
+    /// [*differentiability arithmetic*]: index.html#differentiability-arithmetic
+    type Output;
+
+    /// Computes the element-wise minimum between `self` and `other`, broadcasting them to a
+    /// common shape.
+    ///
+    /// The gradient of each element flows to the smaller operand, and is split evenly between the
+    /// two when they are equal. `NaN`s are propagated.
+    fn minimum(self, other: Rhs) -> Self::Output;
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Pow trait ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Element-wise power with a variable or a scalar exponent.
+pub trait Pow<Rhs> {
+    /// The type of the power's result. See the [*differentiability arithmetic*] for more details.
+    ///
+    /// [*differentiability arithmetic*]: index.html#differentiability-arithmetic
+    type Output;
+
+    /// Raises each element of `self` to the power given by the corresponding element of
+    /// `exponent`, broadcasting them to a common shape. A scalar exponent is applied to every
+    /// element of `self`.
+    ///
+    /// The gradient with respect to the exponent is computed through the natural logarithm of
+    /// `self`, hence the base should be positive wherever the exponent requires a gradient.
+    fn powf(self, exponent: Rhs) -> Self::Output;
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -1,8 +1,9 @@
 #[cfg(test)]
 use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
 use super::{
-    cobroadcasted_zeros, expect_tensor, expect_tensor_mut, push_gradient, reduce, Backward,
-    BroadTensor, Broadcasted, Cache, Data, Forward, Gradient, Overwrite, Tensor,
+    cobroadcasted_shape, cobroadcasted_zeros, expect_tensor, expect_tensor_mut, fit_shape,
+    push_gradient, reduce, Backward, BroadTensor, Broadcasted, Cache, Data, Forward, Gradient,
+    Overwrite, Tensor,
 };
 use ndarray::{DimMax, Dimension, Zip};
 use std::{
@@ -87,6 +88,10 @@ where
         }
 
         self.computed.set(true);
+        fit_shape(
+            &self.data,
+            cobroadcasted_shape(&self.left.data(), &self.right.data()),
+        );
         Zip::from(&mut *self.data.borrow_mut())
             .and_broadcast(&*self.left.data())
             .and_broadcast(&*self.right.data())
@@ -115,7 +120,7 @@ where
     Lhs::Dim: Dimension + DimMax<Rhs::Dim>,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(f, "{}", &self.data.borrow())
+        crate::print::fmt_tensor(&self.data.borrow(), f)
     }
 }
 
@@ -232,7 +237,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", &gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
@@ -346,7 +351,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", &gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
@@ -1,8 +1,9 @@
 #[cfg(test)]
 use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
 use super::{
-    cobroadcasted_zeros, expect_tensor, expect_tensor_mut, push_gradient, reduce, Backward,
-    BroadTensor, Broadcasted, Cache, Data, Forward, Gradient, Overwrite, Tensor,
+    cobroadcasted_shape, cobroadcasted_zeros, expect_tensor, expect_tensor_mut, fit_gradient_shape,
+    fit_shape, push_gradient, reduce, Backward, BroadTensor, Broadcasted, Cache, Data, Forward,
+    Gradient, Overwrite, Tensor,
 };
 use ndarray::{DimMax, Dimension, Zip};
 use std::{
@@ -88,6 +89,10 @@ where
         }
 
         self.computed.set(true);
+        fit_shape(
+            &self.data,
+            cobroadcasted_shape(&self.left.data(), &self.right.data()),
+        );
         Zip::from(&mut *self.data.borrow_mut())
             .and_broadcast(&*self.left.data())
             .and_broadcast(&*self.right.data())
@@ -116,7 +121,7 @@ where
     Lhs::Dim: Dimension + DimMax<Rhs::Dim>,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.data.borrow())
+        crate::print::fmt_tensor(&self.data.borrow(), f)
     }
 }
 
@@ -225,6 +230,7 @@ where
     LhsG::Dim: Dimension + DimMax<RhsG::Dim>,
 {
     fn backward(&self) {
+        fit_gradient_shape(&self.buffer, self.gradient().raw_dim());
         let gradient = self.gradient();
         let mut buffer = expect_tensor_mut(&self.buffer);
 
@@ -283,7 +289,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
@@ -366,6 +372,7 @@ where
     LhsG::Dim: Dimension + DimMax<RhsD::Dim>,
 {
     fn backward(&self) {
+        fit_gradient_shape(&self.buffer, self.gradient().raw_dim());
         let gradient = self.gradient();
         let mut buffer = expect_tensor_mut(&self.buffer);
 
@@ -408,7 +415,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
@@ -500,6 +507,7 @@ where
     LhsD::Dim: Dimension + DimMax<RhsG::Dim>,
 {
     fn backward(&self) {
+        fit_gradient_shape(&self.buffer, self.gradient().raw_dim());
         let gradient = self.gradient();
         let mut buffer = expect_tensor_mut(&self.buffer);
 
@@ -545,7 +553,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
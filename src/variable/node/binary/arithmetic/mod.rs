@@ -4,8 +4,9 @@ mod multiplication;
 mod subtraction;
 
 use super::{
-    cobroadcasted_zeros, expect_tensor, expect_tensor_mut, push_gradient, reduce, Backward,
-    BroadTensor, Broadcasted, Cache, Data, Forward, Gradient, Overwrite, Tensor,
+    cobroadcasted_shape, cobroadcasted_zeros, expect_tensor, expect_tensor_mut, fit_gradient_shape,
+    fit_shape, push_gradient, reduce, Backward, BroadTensor, Broadcasted, Cache, Data, Forward,
+    Gradient, Overwrite, Tensor,
 };
 
 #[cfg(test)]
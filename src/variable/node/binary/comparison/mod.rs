@@ -0,0 +1,159 @@
+#[cfg(test)]
+use super::{assert_almost_equals, new_input, new_tensor};
+use super::{
+    cobroadcasted_shape, cobroadcasted_zeros, fit_shape, BroadTensor, Broadcasted, Cache, Data,
+    Forward, Tensor,
+};
+use ndarray::{DimMax, Dimension, Zip};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// The relation tested by a [`Comparison`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparator {
+    Greater,
+    Less,
+    GreaterEqual,
+    LessEqual,
+    Equal,
+}
+
+impl Comparator {
+    /// Returns `true` if `left` and `right` satisfy the relation. Comparisons involving `NaN`s
+    /// are always false.
+    fn holds(self, left: f32, right: f32) -> bool {
+        match self {
+            Comparator::Greater => left > right,
+            Comparator::Less => left < right,
+            Comparator::GreaterEqual => left >= right,
+            Comparator::LessEqual => left <= right,
+            Comparator::Equal => left == right,
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Comparison ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct Comparison<Lhs: ?Sized, Rhs: ?Sized>
+where
+    Lhs: Data,
+    Rhs: Data,
+    Lhs::Dim: Dimension + DimMax<Rhs::Dim>,
+{
+    left: Rc<Lhs>,
+    right: Rc<Rhs>,
+    comparator: Comparator,
+    data: RefCell<BroadTensor<Lhs::Dim, Rhs::Dim>>,
+    computed: Cell<bool>,
+}
+
+impl<Lhs: ?Sized, Rhs: ?Sized> Comparison<Lhs, Rhs>
+where
+    Lhs: Data,
+    Rhs: Data,
+    Lhs::Dim: Dimension + DimMax<Rhs::Dim>,
+{
+    pub fn new(left: Rc<Lhs>, right: Rc<Rhs>, comparator: Comparator) -> Self {
+        let data = RefCell::new(cobroadcasted_zeros(&left.data(), &right.data()));
+
+        Self {
+            left,
+            right,
+            comparator,
+            data,
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<Lhs: ?Sized, Rhs: ?Sized> Data for Comparison<Lhs, Rhs>
+where
+    Lhs: Data,
+    Rhs: Data,
+    Lhs::Dim: Dimension + DimMax<Rhs::Dim>,
+{
+    type Dim = Broadcasted<Lhs::Dim, Rhs::Dim>;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<Lhs: ?Sized, Rhs: ?Sized> Cache for Comparison<Lhs, Rhs>
+where
+    Lhs: Data,
+    Rhs: Data,
+    Lhs::Dim: Dimension + DimMax<Rhs::Dim>,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<Lhs: ?Sized, Rhs: ?Sized> Forward for Comparison<Lhs, Rhs>
+where
+    Lhs: Data,
+    Rhs: Data,
+    Lhs::Dim: Dimension + DimMax<Rhs::Dim>,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        fit_shape(
+            &self.data,
+            cobroadcasted_shape(&self.left.data(), &self.right.data()),
+        );
+        let comparator = self.comparator;
+        Zip::from(&mut *self.data.borrow_mut())
+            .and_broadcast(&*self.left.data())
+            .and_broadcast(&*self.right.data())
+            .for_each(|v, &l, &r| *v = comparator.holds(l, r) as usize as f32);
+    }
+}
+
+impl<Lhs: ?Sized, Rhs: ?Sized> Debug for Comparison<Lhs, Rhs>
+where
+    Lhs: Data,
+    Rhs: Data,
+    Lhs::Dim: Dimension + DimMax<Rhs::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Comparison")
+            .field("data", &self.data.borrow())
+            .field("comparator", &self.comparator)
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<Lhs: ?Sized, Rhs: ?Sized> Display for Comparison<Lhs, Rhs>
+where
+    Lhs: Data,
+    Rhs: Data,
+    Lhs::Dim: Dimension + DimMax<Rhs::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
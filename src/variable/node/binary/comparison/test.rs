@@ -0,0 +1,135 @@
+use super::{
+    assert_almost_equals, new_input, new_tensor, Cache, Comparator, Comparison, Data, Forward,
+    Tensor,
+};
+
+#[test]
+fn creation() {
+    let left = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+    let right = new_input((3, 3), vec![0.; 9]);
+    let node = Comparison::new(left, right, Comparator::Greater);
+
+    assert_eq!(*node.data(), Tensor::from_elem((3, 3), 0.));
+    assert_eq!(*node.data_mut(), Tensor::from_elem((3, 3), 0.));
+    assert!(!node.was_computed());
+}
+
+#[test]
+fn computation_was_computed_transition() {
+    let left = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+    let right = new_input((3, 3), vec![0.; 9]);
+    let node = Comparison::new(left, right, Comparator::Greater);
+
+    node.forward();
+    assert!(node.was_computed());
+
+    node.forward();
+    assert!(node.was_computed());
+
+    node.reset_computation();
+    assert!(!node.was_computed());
+
+    node.reset_computation();
+    assert!(!node.was_computed());
+}
+
+#[test]
+fn forward() {
+    let left = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+    let right = new_input((3, 3), vec![0.; 9]);
+    let node = Comparison::new(left, right.clone(), Comparator::Greater);
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    node.forward();
+    assert_almost_equals(
+        &*node.data(),
+        &new_tensor((3, 3), vec![0., 0., 0., 0., 0., 1., 1., 1., 1.]),
+    );
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    *right.data_mut() = new_tensor((3, 3), vec![2.; 9]);
+    node.forward();
+    assert_almost_equals(
+        &*node.data(),
+        &new_tensor((3, 3), vec![0., 0., 0., 0., 0., 1., 1., 1., 1.]),
+    );
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    node.reset_computation();
+    node.forward();
+    assert_almost_equals(
+        &*node.data(),
+        &new_tensor((3, 3), vec![0., 0., 0., 0., 0., 0., 0., 1., 1.]),
+    );
+}
+
+#[test]
+fn comparators() {
+    let comparisons = [
+        (Comparator::Greater, vec![0., 0., 1.]),
+        (Comparator::Less, vec![1., 0., 0.]),
+        (Comparator::GreaterEqual, vec![0., 1., 1.]),
+        (Comparator::LessEqual, vec![1., 1., 0.]),
+        (Comparator::Equal, vec![0., 1., 0.]),
+    ];
+
+    for (comparator, expected) in comparisons {
+        let left = new_input(3, vec![-1., 0., 1.]);
+        let right = new_input(3, vec![0.; 3]);
+        let node = Comparison::new(left, right, comparator);
+
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor(3, expected));
+    }
+}
+
+#[test]
+fn broadcast_forward() {
+    let left = new_input((2, 1), vec![-0.5, 0.5]);
+    let right = new_input(3, vec![-1., 0., 1.]);
+    let node = Comparison::new(left, right, Comparator::LessEqual);
+
+    assert_eq!(*node.data(), Tensor::from_elem((2, 3), 0.));
+    node.forward();
+    assert_almost_equals(
+        &*node.data(),
+        &new_tensor((2, 3), vec![0., 1., 1., 0., 0., 1.]),
+    );
+}
+
+#[test]
+fn nan_forward() {
+    let left = new_input(2, vec![f32::NAN, f32::NAN]);
+    let right = new_input(2, vec![0., f32::NAN]);
+
+    for comparator in [
+        Comparator::GreaterEqual,
+        Comparator::LessEqual,
+        Comparator::Equal,
+    ] {
+        let node = Comparison::new(left.clone(), right.clone(), comparator);
+
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor(2, vec![0., 0.]));
+    }
+}
+
+#[test]
+fn debug() {
+    let left = new_input(1, vec![0.]);
+    let right = new_input(1, vec![0.]);
+    let node = Comparison::new(left, right, Comparator::Equal);
+
+    let output = "Comparison { data: [0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1, comparator: Equal, computed: false }";
+
+    assert_eq!(output, format!("{:?}", node));
+}
+
+#[test]
+fn display() {
+    let left = new_input(1, vec![0.]);
+    let right = new_input(1, vec![0.]);
+    let node = Comparison::new(left, right, Comparator::Equal);
+
+    assert_eq!(format!("{}", node.data()), format!("{}", node));
+}
@@ -1,8 +1,8 @@
 #[cfg(test)]
 use super::{new_backward_input, new_input};
 use crate::variable::{
-    expect_tensor, expect_tensor_mut, Backward, Cache, Data as NData, Forward, Gradient, Overwrite,
-    Tensor, Var, VarDiff,
+    check_convolution, expect_tensor, expect_tensor_mut, fit_shape, Backward, Cache, Data as NData,
+    Forward, Gradient, Overwrite, ShapeError, Shaped, Tensor, Var, VarDiff,
 };
 use ndarray::{Dimension, RemoveAxis};
 use std::{
@@ -47,6 +47,45 @@ pub trait Convolve<Inp, Ker, Pad: PaddingMode> {
         padding: &[usize],
         padding_mode: Pad,
     ) -> Self::Output;
+
+    /// Checked version of [`convolve`](Convolve::convolve()). The arguments are validated
+    /// against the shapes of `input` and `kernel` when the computational graph is built, rather
+    /// than when it is evaluated.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ShapeError`] if `stride`, `dilation` or `padding` do not match the
+    /// dimensionality of the convolution, if the kernel expects a different number of input
+    /// channels or if it is larger than the padded input.
+    fn try_convolve(
+        input: Inp,
+        kernel: Ker,
+        stride: &[usize],
+        dilation: &[usize],
+        padding: &[usize],
+        padding_mode: Pad,
+    ) -> Result<Self::Output, ShapeError>
+    where
+        Inp: Shaped,
+        Ker: Shaped,
+    {
+        check_convolution(
+            &input.shape(),
+            &kernel.shape(),
+            stride,
+            dilation,
+            padding,
+            1,
+        )?;
+        Ok(Self::convolve(
+            input,
+            kernel,
+            stride,
+            dilation,
+            padding,
+            padding_mode,
+        ))
+    }
 }
 
 impl<F1: ?Sized, F2: ?Sized, Pad> Convolve<Self, Var<F2>, Pad> for Var<F1>
@@ -190,6 +229,44 @@ pub trait ConvolveWithGroups<Inp, Ker, Pad: PaddingMode> {
         padding_mode: Pad,
         groups: usize,
     ) -> Self::Output;
+
+    /// Checked version of [`convolve_with_groups`](ConvolveWithGroups::convolve_with_groups()).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ShapeError`] in the same cases as [`Convolve::try_convolve`] and if the
+    /// numbers of input and output channels are not divisible by `groups`.
+    fn try_convolve_with_groups(
+        input: Inp,
+        kernel: Ker,
+        stride: &[usize],
+        dilation: &[usize],
+        padding: &[usize],
+        padding_mode: Pad,
+        groups: usize,
+    ) -> Result<Self::Output, ShapeError>
+    where
+        Inp: Shaped,
+        Ker: Shaped,
+    {
+        check_convolution(
+            &input.shape(),
+            &kernel.shape(),
+            stride,
+            dilation,
+            padding,
+            groups,
+        )?;
+        Ok(Self::convolve_with_groups(
+            input,
+            kernel,
+            stride,
+            dilation,
+            padding,
+            padding_mode,
+            groups,
+        ))
+    }
 }
 
 impl<F1: ?Sized, F2: ?Sized, Pad> ConvolveWithGroups<Self, Var<F2>, Pad> for Var<F1>
@@ -440,6 +517,14 @@ where
         }
 
         self.computed.set(true);
+        let shape: Inp::Dim = conv_out_shape(
+            self.input.data().shape(),
+            self.kernel.data().shape(),
+            &self.padding,
+            &self.stride,
+            &self.dilation,
+        );
+        fit_shape(&self.data, shape);
         let (input, kernel, mut output_map, stride, dilation, padding, padding_mode) = (
             self.input.data(),
             self.kernel.data(),
@@ -488,7 +573,7 @@ where
     Pad: PaddingMode,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(f, "{}", &self.data.borrow())
+        crate::print::fmt_tensor(&self.data.borrow(), f)
     }
 }
 
@@ -601,6 +686,14 @@ where
         }
 
         self.computed.set(true);
+        let shape: Inp::Dim = conv_out_shape(
+            self.input.data().shape(),
+            self.kernel.data().shape(),
+            &self.padding,
+            &self.stride,
+            &self.dilation,
+        );
+        fit_shape(&self.data, shape);
         let (input, kernel, mut output_map, stride, dilation, padding, padding_mode, groups) = (
             self.input.data(),
             self.kernel.data(),
@@ -666,7 +759,7 @@ where
     Pad: PaddingMode,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(f, "{}", &self.data.borrow())
+        crate::print::fmt_tensor(&self.data.borrow(), f)
     }
 }
 
@@ -899,7 +992,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", &gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
@@ -1082,7 +1175,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", &gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
@@ -1326,7 +1419,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", &gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
@@ -1523,7 +1616,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", &gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
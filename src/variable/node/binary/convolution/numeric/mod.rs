@@ -224,16 +224,10 @@ pub(super) fn conv_out_shape<D: Dimension>(
     stride: &[usize],
     dilation: &[usize],
 ) -> D {
-    let mut output_map_shape: D =
-        conv_out_shape_padded(input_shape, kernel_shape, stride, dilation);
-    // Applies the padding.
-    itertools::izip!(
-        output_map_shape.slice_mut().iter_mut().skip(2), // Skips batch size and out channels.
-        padding,
-        stride,
-    )
-    .for_each(|(output_map_dim, padding, stride)| *output_map_dim += 2 * padding / stride);
-    output_map_shape
+    // The padding must be applied before the stride, as the output of a strided convolution isn't
+    // linear in the size of its input.
+    let padded_input_shape: D = padded_shape(input_shape, padding);
+    conv_out_shape_padded(padded_input_shape.slice(), kernel_shape, stride, dilation)
 }
 
 /// Computes the shape of the array resulting from the **n**-dimensional convolution
@@ -378,13 +372,15 @@ where
         array.view().into_shape(original_dim).unwrap(),
     );
 
-    padded_view_mut
-        .outer_iter_mut()
-        .into_par_iter()
-        .zip(original_view.outer_iter())
-        .for_each(|(mut pad_sample, original_sample)| {
-            padding_mode.pad_inplace(&mut pad_sample, &original_sample, padding)
-        });
+    crate::parallel::install(|| {
+        padded_view_mut
+            .outer_iter_mut()
+            .into_par_iter()
+            .zip(original_view.outer_iter())
+            .for_each(|(mut pad_sample, original_sample)| {
+                padding_mode.pad_inplace(&mut pad_sample, &original_sample, padding)
+            })
+    });
     padded
 }
 
@@ -620,15 +616,17 @@ fn assign_from_cols<D: Dimension, S: DataMut<Elem = f32>, T: Data<Elem = f32>>(
     let from_cols = columns.into_shape(dest_windows_mut.raw_dim()).unwrap();
 
     // Safe because each sample is independent from one another.
-    dest_windows_mut
+    let samples = dest_windows_mut
         .axis_iter_mut(Axis(0))
         .into_par_iter()
-        .zip(from_cols.axis_iter(Axis(0)))
-        .for_each(|(mut dest_view, src_view)| {
+        .zip(from_cols.axis_iter(Axis(0)));
+    crate::parallel::install(|| {
+        samples.for_each(|(mut dest_view, src_view)| {
             Zip::from(&mut dest_view)
                 .and(&src_view)
                 .for_each(|dest_view_el, src_view_el| *dest_view_el += *src_view_el)
-        });
+        })
+    });
 }
 
 /// Partitions the **flattened input**, the **flattened kernel** and the **output map**
@@ -778,9 +776,9 @@ pub(super) fn convolution<
         .to_shape(columns_shape(input, kernel_shape, stride, dilation))
         .unwrap();
 
-    Zip::from(input_columns.axis_iter(Axis(0)))
-        .and(output.axis_iter_mut(Axis(0)))
-        .par_for_each(|input_sample_columns, output_sample| {
+    let samples = Zip::from(input_columns.axis_iter(Axis(0))).and(output.axis_iter_mut(Axis(0)));
+    crate::parallel::install_matmul(|| {
+        samples.par_for_each(|input_sample_columns, output_sample| {
             let flat_shape = flat_shape(&output_sample);
             let mut flattened_sample_out_view_mut = output_sample.into_shape(flat_shape).unwrap();
             general_mat_mul(
@@ -790,7 +788,8 @@ pub(super) fn convolution<
                 0.,
                 &mut flattened_sample_out_view_mut,
             );
-        });
+        })
+    });
 }
 
 /// Performs the **back-propagation** for an an **n-dimensional** convolution where
@@ -832,27 +831,29 @@ pub(super) fn convolution_backward_input<
         grad.shape(),
     );
 
+    // The buffer has the same layout as the columns of the forward pass.
     let mut buffer_shape = Ix3::zeros(3);
     buffer_shape[0] = grad_shape[0];
-    buffer_shape[1] = flattened_kernel.shape()[1];
-    buffer_shape[2] = grad_shape.iter().skip(2).product();
+    buffer_shape[1] = grad_shape.iter().skip(2).product();
+    buffer_shape[2] = flattened_kernel.shape()[1];
     let mut buffer = Array::<f32, Ix3>::zeros(buffer_shape);
 
-    Zip::from(grad.axis_iter(Axis(0)))
-        .and(buffer.axis_iter_mut(Axis(0)))
-        .par_for_each(|gradient_sample, mut buffer_sample| {
+    let samples = Zip::from(grad.axis_iter(Axis(0))).and(buffer.axis_iter_mut(Axis(0)));
+    crate::parallel::install_matmul(|| {
+        samples.par_for_each(|gradient_sample, mut buffer_sample| {
             let gradient_sample_flat_shape = flat_shape(&gradient_sample);
             let flattened_sample_in = gradient_sample
                 .into_shape(gradient_sample_flat_shape)
                 .unwrap();
             general_mat_mul(
                 1.,
-                &flattened_kernel.t(),
-                &flattened_sample_in,
+                &flattened_sample_in.t(),
+                &flattened_kernel,
                 0.,
                 &mut buffer_sample,
             );
-        });
+        })
+    });
 
     if padding.iter().all(|pad| *pad == 0) {
         assign_from_cols(input_grad, buffer, kernel_shape, stride, dilation);
@@ -864,14 +865,17 @@ pub(super) fn convolution_backward_input<
         // The actual input's incoming gradient is extracted from the buffer and assigned.
         let actual_gradient = unpad(&padded_buffer, padding);
         let input_gradient_zip = Zip::from(input_grad).and(actual_gradient);
-        if overwrite_input_grad {
-            input_gradient_zip
-                .par_for_each(|input_grad_el, incoming_grad_el| *input_grad_el = *incoming_grad_el);
-        } else {
-            input_gradient_zip.par_for_each(|input_grad_el, incoming_grad_el| {
-                *input_grad_el += *incoming_grad_el
-            });
-        }
+        crate::parallel::install(|| {
+            if overwrite_input_grad {
+                input_gradient_zip.par_for_each(|input_grad_el, incoming_grad_el| {
+                    *input_grad_el = *incoming_grad_el
+                });
+            } else {
+                input_gradient_zip.par_for_each(|input_grad_el, incoming_grad_el| {
+                    *input_grad_el += *incoming_grad_el
+                });
+            }
+        });
     }
 }
 
@@ -923,9 +927,9 @@ pub(super) fn convolution_backward_kernel<
     matrix_shape[1] = columns_shape[2];
     let input_matrix = input_windows.to_shape(matrix_shape).unwrap();
 
-    Zip::from(kernel_grad.axis_iter_mut(Axis(0)))
-        .and(grad.axis_iter(Axis(1)))
-        .par_for_each(|kernel_grad_view_mut, grad_view| {
+    let channels = Zip::from(kernel_grad.axis_iter_mut(Axis(0))).and(grad.axis_iter(Axis(1)));
+    crate::parallel::install_matmul(|| {
+        channels.par_for_each(|kernel_grad_view_mut, grad_view| {
             let kernel_grad_numel = kernel_grad_view_mut.shape().iter().product::<usize>();
             let grad_view_numel = grad_view.shape().iter().product::<usize>();
 
@@ -938,7 +942,8 @@ pub(super) fn convolution_backward_kernel<
                     .into_shape((1, kernel_grad_numel))
                     .unwrap(),
             );
-        });
+        })
+    });
 }
 
 /// Performs an **n-dimensional grouped** convolution where **n** can be either *1*, *2* or *3*.
@@ -969,13 +974,15 @@ pub(super) fn convolution_with_groups<D: Dimension + RemoveAxis>(
 ) {
     let (input_groups, kernel_groups, output_buffer_groups) =
         group_inputs(input, kernel, output, groups);
-    kernel_groups
-        .into_par_iter()
-        .zip(input_groups.into_iter())
-        .zip(output_buffer_groups.into_iter())
-        .for_each(|((kernel, input), mut output)| {
-            convolution(&input, &kernel, &mut output, stride, dilation);
-        });
+    crate::parallel::install_matmul(|| {
+        kernel_groups
+            .into_par_iter()
+            .zip(input_groups.into_iter())
+            .zip(output_buffer_groups.into_iter())
+            .for_each(|((kernel, input), mut output)| {
+                convolution(&input, &kernel, &mut output, stride, dilation);
+            })
+    });
 }
 
 /// Performs the **back-propagation** for an an **n-dimensional** grouped convolution where
@@ -1023,33 +1030,35 @@ pub(super) fn convolution_with_groups_backward<D: Dimension + RemoveAxis>(
     let (input_grad_groups, kernel_grad_groups, grad_groups, input_groups, kernel_groups) =
         group_gradients(input_grad, kernel_grad, grad, input, kernel, groups);
 
-    grad_groups
-        .into_par_iter()
-        .zip(kernel_grad_groups.into_iter())
-        .zip(input_grad_groups.into_iter())
-        .zip(kernel_groups.into_iter())
-        .zip(input_groups.into_iter())
-        .for_each(
-            |((((gradient, mut kernel_gradient), mut input_gradient), kernel), input)| {
-                convolution_backward_kernel(
-                    &mut kernel_gradient,
-                    &gradient,
-                    &input,
-                    stride,
-                    dilation,
-                    overwrite_kernel_grad,
-                );
-                convolution_backward_input(
-                    &mut input_gradient,
-                    &gradient,
-                    &kernel,
-                    padding,
-                    stride,
-                    dilation,
-                    overwrite_input_grad,
-                )
-            },
-        );
+    crate::parallel::install_matmul(|| {
+        grad_groups
+            .into_par_iter()
+            .zip(kernel_grad_groups.into_iter())
+            .zip(input_grad_groups.into_iter())
+            .zip(kernel_groups.into_iter())
+            .zip(input_groups.into_iter())
+            .for_each(
+                |((((gradient, mut kernel_gradient), mut input_gradient), kernel), input)| {
+                    convolution_backward_kernel(
+                        &mut kernel_gradient,
+                        &gradient,
+                        &input,
+                        stride,
+                        dilation,
+                        overwrite_kernel_grad,
+                    );
+                    convolution_backward_input(
+                        &mut input_gradient,
+                        &gradient,
+                        &kernel,
+                        padding,
+                        stride,
+                        dilation,
+                        overwrite_input_grad,
+                    )
+                },
+            )
+    });
 }
 
 /// Performs the **back-propagation** for an an **n-dimensional** grouped convolution where
@@ -1092,20 +1101,22 @@ pub(super) fn convolution_with_groups_unary_backward<D: Dimension + RemoveAxis>(
     let (kernel_grad_groups, grad_groups, input_groups) =
         group_gradients_unary(kernel_grad, grad, input, groups);
 
-    grad_groups
-        .into_par_iter()
-        .zip(kernel_grad_groups.into_iter())
-        .zip(input_groups.into_iter())
-        .for_each(|((gradient, mut kernel_gradient), input)| {
-            convolution_backward_kernel(
-                &mut kernel_gradient,
-                &gradient,
-                &input,
-                stride,
-                dilation,
-                overwrite_kernel_grad,
-            )
-        });
+    crate::parallel::install_matmul(|| {
+        grad_groups
+            .into_par_iter()
+            .zip(kernel_grad_groups.into_iter())
+            .zip(input_groups.into_iter())
+            .for_each(|((gradient, mut kernel_gradient), input)| {
+                convolution_backward_kernel(
+                    &mut kernel_gradient,
+                    &gradient,
+                    &input,
+                    stride,
+                    dilation,
+                    overwrite_kernel_grad,
+                )
+            })
+    });
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
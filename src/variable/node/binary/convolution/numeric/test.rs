@@ -485,6 +485,52 @@ mod convolution_numeric {
         assert_eq!(kernel_grad, true_kernel_grad_elems);
     }
 
+    #[test]
+    fn conv1d_backward_input_asymmetric() {
+        use ndarray::prelude::*;
+
+        // An asymmetric kernel and an incoming gradient that only flows through the first output
+        // step, so that misplaced gradients are detected.
+        let kernel = array![[[1., 2., 3.], [4., 5., 6.]]];
+        let stride = &[1];
+        let dilation = &[1];
+
+        let mut input_grad = Array::<f32, _>::zeros((1, 2, 6));
+        let mut conv_out_grad = Array::<f32, _>::zeros((1, 1, 4));
+        conv_out_grad[[0, 0, 0]] = 1.;
+        convolution_backward_input(
+            &mut input_grad,
+            &conv_out_grad,
+            &kernel,
+            &[0],
+            stride,
+            dilation,
+            true,
+        );
+        assert_eq!(
+            input_grad,
+            array![[[1., 2., 3., 0., 0., 0.], [4., 5., 6., 0., 0., 0.]]]
+        );
+
+        // With a padding of two steps the first output step only sees the first input step.
+        let mut input_grad = Array::<f32, _>::zeros((1, 2, 6));
+        let mut conv_out_grad = Array::<f32, _>::zeros((1, 1, 8));
+        conv_out_grad[[0, 0, 0]] = 1.;
+        convolution_backward_input(
+            &mut input_grad,
+            &conv_out_grad,
+            &kernel,
+            &[2],
+            stride,
+            dilation,
+            true,
+        );
+        assert_eq!(
+            input_grad,
+            array![[[3., 0., 0., 0., 0., 0.], [6., 0., 0., 0., 0., 0.]]]
+        );
+    }
+
     #[test]
     fn conv2d() {
         use ndarray::Ix4;
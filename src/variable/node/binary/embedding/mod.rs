@@ -0,0 +1,399 @@
+#[cfg(test)]
+use super::{
+    assert_almost_equals, new_backward_input, new_index_input, new_input, new_tensor, IndexTensor,
+};
+use super::{
+    expect_tensor, expect_tensor_mut, fit_shape, Backward, Cache, Data, Forward, Gradient,
+    IndexData, Overwrite, Tensor,
+};
+#[cfg(unix)]
+use crate::io::MappedNpy;
+use ndarray::{Axis, Dimension, Ix2};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// Returns the shape of the embeddings of the indices of shape `shape`, that is `shape` with an
+/// additional last axis of length `embedding_dim`.
+fn embedded<D: Dimension>(shape: &D, embedding_dim: usize) -> D::Larger {
+    let mut embedded_shape = shape.insert_axis(Axis(shape.ndim()));
+    embedded_shape[shape.ndim()] = embedding_dim;
+    embedded_shape
+}
+
+/// Converts the element `index` of an indices variable to a row of a table with `rows` rows.
+fn to_row(index: i64, rows: usize) -> usize {
+    assert!(
+        index >= 0 && (index as usize) < rows,
+        "error: index {} is out of range for an embedding table with {} rows.",
+        index,
+        rows
+    );
+    index as usize
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Embedding ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct Embedding<T: ?Sized, U: ?Sized>
+where
+    T: IndexData,
+    U: Data<Dim = Ix2>,
+{
+    indices: Rc<T>,
+    weight: Rc<U>,
+    data: RefCell<Tensor<<T::Dim as Dimension>::Larger>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized, U: ?Sized> Embedding<T, U>
+where
+    T: IndexData,
+    U: Data<Dim = Ix2>,
+{
+    pub fn new(indices: Rc<T>, weight: Rc<U>) -> Self {
+        let shape = embedded(&indices.data().raw_dim(), weight.data().len_of(Axis(1)));
+        let data = RefCell::new(Tensor::zeros(shape));
+
+        Self {
+            indices,
+            weight,
+            data,
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Cache for Embedding<T, U>
+where
+    T: IndexData,
+    U: Data<Dim = Ix2>,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Forward for Embedding<T, U>
+where
+    T: IndexData,
+    U: Data<Dim = Ix2>,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let (indices, weight) = (self.indices.data(), self.weight.data());
+        let (rows, embedding_dim) = weight.dim();
+        fit_shape(&self.data, embedded(&indices.raw_dim(), embedding_dim));
+
+        let mut data = self.data.borrow_mut();
+        let last_axis = Axis(data.ndim() - 1);
+        data.lanes_mut(last_axis)
+            .into_iter()
+            .zip(indices.iter())
+            .for_each(|(mut embedding, index)| {
+                embedding.assign(&weight.row(to_row(*index, rows)));
+            });
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Data for Embedding<T, U>
+where
+    T: IndexData,
+    U: Data<Dim = Ix2>,
+{
+    type Dim = <T::Dim as Dimension>::Larger;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Debug for Embedding<T, U>
+where
+    T: IndexData,
+    U: Data<Dim = Ix2>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Embedding")
+            .field("data", &self.data.borrow())
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Display for Embedding<T, U>
+where
+    T: IndexData,
+    U: Data<Dim = Ix2>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ MappedEmbedding ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(unix)]
+pub struct MappedEmbedding<T: ?Sized>
+where
+    T: IndexData,
+{
+    indices: Rc<T>,
+    table: Rc<MappedNpy>,
+    data: RefCell<Tensor<<T::Dim as Dimension>::Larger>>,
+    computed: Cell<bool>,
+}
+
+#[cfg(unix)]
+impl<T: ?Sized> MappedEmbedding<T>
+where
+    T: IndexData,
+{
+    pub fn new(indices: Rc<T>, table: Rc<MappedNpy>) -> Self {
+        assert_eq!(
+            table.shape().len(),
+            2,
+            "error: an embedding table must have 2 dimensions, found one of shape {:?}.",
+            table.shape()
+        );
+        let shape = embedded(&indices.data().raw_dim(), table.shape()[1]);
+        let data = RefCell::new(Tensor::zeros(shape));
+
+        Self {
+            indices,
+            table,
+            data,
+            computed: Cell::new(false),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl<T: ?Sized> Cache for MappedEmbedding<T>
+where
+    T: IndexData,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+#[cfg(unix)]
+impl<T: ?Sized> Forward for MappedEmbedding<T>
+where
+    T: IndexData,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let indices = self.indices.data();
+        // Viewing the table reads nothing, only the pages of the rows looked up are loaded.
+        let table = self.table.view().into_dimensionality::<Ix2>().unwrap();
+        let (rows, embedding_dim) = table.dim();
+        fit_shape(&self.data, embedded(&indices.raw_dim(), embedding_dim));
+
+        let mut data = self.data.borrow_mut();
+        let last_axis = Axis(data.ndim() - 1);
+        data.lanes_mut(last_axis)
+            .into_iter()
+            .zip(indices.iter())
+            .for_each(|(mut embedding, index)| {
+                embedding.assign(&table.row(to_row(*index, rows)));
+            });
+    }
+}
+
+#[cfg(unix)]
+impl<T: ?Sized> Data for MappedEmbedding<T>
+where
+    T: IndexData,
+{
+    type Dim = <T::Dim as Dimension>::Larger;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+#[cfg(unix)]
+impl<T: ?Sized> Debug for MappedEmbedding<T>
+where
+    T: IndexData,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MappedEmbedding")
+            .field("data", &self.data.borrow())
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+#[cfg(unix)]
+impl<T: ?Sized> Display for MappedEmbedding<T>
+where
+    T: IndexData,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ EmbeddingBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct EmbeddingBackward<T: ?Sized, U: ?Sized>
+where
+    T: Gradient<Dim = Ix2>,
+    U: IndexData,
+{
+    gradient: RefCell<Option<Tensor<<U::Dim as Dimension>::Larger>>>,
+    shape: <U::Dim as Dimension>::Larger,
+    overwrite: Cell<bool>,
+    diff_weight: Rc<T>,
+    indices: Rc<U>,
+}
+
+impl<T: ?Sized, U: ?Sized> EmbeddingBackward<T, U>
+where
+    T: Gradient<Dim = Ix2>,
+    U: IndexData,
+{
+    pub fn new(diff_weight: Rc<T>, indices: Rc<U>) -> Self {
+        let shape = embedded(
+            &indices.data().raw_dim(),
+            diff_weight.gradient().len_of(Axis(1)),
+        );
+
+        Self {
+            gradient: RefCell::new(Some(Tensor::zeros(shape.clone()))),
+            shape,
+            overwrite: Cell::new(true),
+            diff_weight,
+            indices,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Gradient for EmbeddingBackward<T, U>
+where
+    T: Gradient<Dim = Ix2>,
+    U: IndexData,
+{
+    type Dim = <U::Dim as Dimension>::Larger;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Overwrite for EmbeddingBackward<T, U>
+where
+    T: Gradient<Dim = Ix2>,
+    U: IndexData,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Backward for EmbeddingBackward<T, U>
+where
+    T: Gradient<Dim = Ix2>,
+    U: IndexData,
+{
+    fn backward(&self) {
+        let mut weight_grad = self.diff_weight.gradient_mut();
+        let (gradient, indices) = (self.gradient(), self.indices.data());
+
+        // The rows that are not looked up receive no gradient.
+        if self.diff_weight.can_overwrite() {
+            weight_grad.fill(0.);
+            self.diff_weight.set_overwrite(false);
+        }
+
+        let rows = weight_grad.nrows();
+        let last_axis = Axis(gradient.ndim() - 1);
+        gradient
+            .lanes(last_axis)
+            .into_iter()
+            .zip(indices.iter())
+            .for_each(|(embedding_grad, index)| {
+                let mut row = weight_grad.row_mut(to_row(*index, rows));
+                row += &embedding_grad;
+            });
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape.clone()));
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Debug for EmbeddingBackward<T, U>
+where
+    T: Gradient<Dim = Ix2>,
+    U: IndexData,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EmbeddingBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Display for EmbeddingBackward<T, U>
+where
+    T: Gradient<Dim = Ix2>,
+    U: IndexData,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,296 @@
+#[cfg(unix)]
+use super::MappedEmbedding;
+use super::{
+    assert_almost_equals, new_backward_input, new_index_input, new_input, new_tensor, Backward,
+    Cache, Data, Embedding, EmbeddingBackward, Forward, Gradient, IndexData, IndexTensor,
+    Overwrite, Tensor,
+};
+
+mod forward {
+    use super::{
+        assert_almost_equals, new_index_input, new_input, new_tensor, Cache, Data, Embedding,
+        Forward, IndexData, IndexTensor, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let indices = new_index_input((2, 2), vec![1, 3, 3, 0]);
+        let weight = new_input((4, 2), (0..8).map(|el| el as f32).collect());
+        let node = Embedding::new(indices, weight);
+
+        assert_eq!(*node.data(), Tensor::from_elem((2, 2, 2), 0.));
+        assert_eq!(*node.data_mut(), Tensor::from_elem((2, 2, 2), 0.));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let indices = new_index_input((2, 2), vec![1, 3, 3, 0]);
+        let weight = new_input((4, 2), (0..8).map(|el| el as f32).collect());
+        let node = Embedding::new(indices, weight);
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward() {
+        let indices = new_index_input((2, 2), vec![1, 3, 3, 0]);
+        let weight = new_input((4, 2), (0..8).map(|el| el as f32).collect());
+        let node = Embedding::new(indices.clone(), weight);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 2, 2), vec![2., 3., 6., 7., 6., 7., 0., 1.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *indices.data_mut() = IndexTensor::from_shape_vec((2, 2), vec![2, 2, 1, 0]).unwrap();
+
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 2, 2), vec![2., 3., 6., 7., 6., 7., 0., 1.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 2, 2), vec![4., 5., 4., 5., 2., 3., 0., 1.]),
+        );
+    }
+
+    #[test]
+    fn forward_vector() {
+        let indices = new_index_input(3, vec![2, 0, 2]);
+        let weight = new_input((3, 1), vec![-1., 0., 1.]);
+        let node = Embedding::new(indices, weight);
+
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((3, 1), vec![1., -1., 1.]));
+    }
+
+    #[test]
+    #[should_panic(expected = "error: index 4 is out of range for an embedding table with 4 rows.")]
+    fn forward_out_of_range() {
+        let indices = new_index_input(2, vec![1, 4]);
+        let weight = new_input((4, 2), vec![0.; 8]);
+        let node = Embedding::new(indices, weight);
+
+        node.forward();
+    }
+
+    #[test]
+    fn debug() {
+        let indices = new_index_input(2, vec![0, 0]);
+        let weight = new_input((1, 1), vec![0.]);
+        let node = Embedding::new(indices, weight);
+
+        let output = "Embedding { data: [[0.0],\n [0.0]], shape=[2, 1], strides=[1, 1], layout=CFcf (0xf), const ndim=2, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let indices = new_index_input(2, vec![0, 0]);
+        let weight = new_input((1, 1), vec![0.]);
+        let node = Embedding::new(indices, weight);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        assert_almost_equals, new_backward_input, new_index_input, new_tensor, Backward,
+        EmbeddingBackward, Gradient, Overwrite, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let node = EmbeddingBackward::new(
+            new_backward_input((4, 2), vec![0.; 8]),
+            new_index_input((2, 2), vec![1, 3, 3, 0]),
+        );
+
+        assert_eq!(*node.gradient(), Tensor::from_elem((2, 2, 2), 0.));
+        assert_eq!(*node.gradient_mut(), Tensor::from_elem((2, 2, 2), 0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn computation_state_transition() {
+        let diff = new_backward_input((4, 2), vec![0.; 8]);
+        let node = EmbeddingBackward::new(diff.clone(), new_index_input((2, 2), vec![1, 3, 3, 0]));
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        diff.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!diff.can_overwrite());
+    }
+
+    #[test]
+    fn backward() {
+        let diff = new_backward_input((4, 2), vec![0.; 8]);
+        let node = EmbeddingBackward::new(diff.clone(), new_index_input((2, 2), vec![1, 3, 3, 0]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((2, 2, 2), vec![1.; 8]);
+        assert_almost_equals(&*node.gradient(), &new_tensor((2, 2, 2), vec![1.; 8]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((4, 2), vec![1., 1., 1., 1., 0., 0., 2., 2.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((4, 2), vec![2., 2., 2., 2., 0., 0., 4., 4.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((4, 2), vec![1., 1., 1., 1., 0., 0., 2., 2.]),
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let node = EmbeddingBackward::new(
+            new_backward_input((1, 1), vec![0.]),
+            new_index_input(2, vec![0, 0]),
+        );
+
+        let output = "EmbeddingBackward { gradient: Some([[0.0],\n [0.0]], shape=[2, 1], strides=[1, 1], layout=CFcf (0xf), const ndim=2), overwrite: true }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = EmbeddingBackward::new(
+            new_backward_input((1, 1), vec![0.]),
+            new_index_input(2, vec![0, 0]),
+        );
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+
+    #[test]
+    fn no_grad() {
+        // EmbeddingBackward
+        let node = EmbeddingBackward::new(
+            new_backward_input((4, 2), vec![0.; 8]),
+            new_index_input((2, 2), vec![1, 3, 3, 0]),
+        );
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+    }
+}
+
+#[cfg(unix)]
+mod mapped {
+    use super::{
+        assert_almost_equals, new_index_input, new_tensor, Cache, Data, Forward, IndexData,
+        IndexTensor, MappedEmbedding,
+    };
+    use crate::io::{write_npy, MappedNpy};
+    use ndarray::{Array, Dimension};
+    use std::{fs::File, path::PathBuf, rc::Rc};
+
+    /// Writes `table` to a temporary `.npy` file and maps it.
+    fn new_table<D: Dimension>(name: &str, table: Array<f32, D>) -> (Rc<MappedNpy>, PathBuf) {
+        let path = std::env::temp_dir().join(format!(
+            "neuronika-mapped-embedding-{}-{}.npy",
+            name,
+            std::process::id()
+        ));
+        write_npy(File::create(&path).unwrap(), &table).unwrap();
+
+        (Rc::new(MappedNpy::open(&path).unwrap()), path)
+    }
+
+    #[test]
+    fn forward() {
+        let (table, path) = new_table(
+            "forward",
+            Array::from_shape_fn((4, 2), |(i, j)| (i * 2 + j) as f32),
+        );
+        let indices = new_index_input((2, 2), vec![1, 3, 3, 0]);
+        let node = MappedEmbedding::new(indices.clone(), table);
+
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 2, 2), vec![2., 3., 6., 7., 6., 7., 0., 1.]),
+        );
+
+        // The shape follows the indices.
+        *indices.data_mut() = IndexTensor::from_shape_vec((1, 3), vec![2, 2, 1]).unwrap();
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((1, 3, 2), vec![4., 5., 4., 5., 2., 3.]),
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "error: an embedding table must have 2 dimensions, found one of shape [4]."
+    )]
+    fn creation_fail() {
+        let (table, path) = new_table("creation-fail", Array::zeros(4));
+        std::fs::remove_file(&path).unwrap();
+
+        MappedEmbedding::new(new_index_input(2, vec![0, 1]), table);
+    }
+
+    #[test]
+    #[should_panic(expected = "error: index 4 is out of range for an embedding table with 4 rows.")]
+    fn forward_out_of_range() {
+        let (table, path) = new_table("out-of-range", Array::zeros((4, 2)));
+        std::fs::remove_file(&path).unwrap();
+        let node = MappedEmbedding::new(new_index_input(2, vec![1, 4]), table);
+
+        node.forward();
+    }
+}
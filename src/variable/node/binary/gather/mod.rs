@@ -0,0 +1,292 @@
+#[cfg(test)]
+use super::{
+    assert_almost_equals, new_backward_input, new_index_input, new_input, new_tensor, IndexTensor,
+};
+use super::{
+    expect_tensor, expect_tensor_mut, fit_shape, Backward, Cache, Data, Forward, Gradient,
+    IndexData, Overwrite, Tensor,
+};
+use ndarray::{Dimension, IxDyn};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// Returns the position of the operand's element gathered at position `position` of the
+/// result, that is `position` with the coordinate along `axis` replaced by `index`.
+///
+/// # Arguments
+///
+/// * `position` - position of an element of the result.
+///
+/// * `shape` - shape of the operand.
+///
+/// * `axis` - axis along which the elements are gathered.
+///
+/// * `index` - index of the gathered element along `axis`.
+fn gathered(mut position: IxDyn, shape: &[usize], axis: usize, index: i64) -> IxDyn {
+    assert!(
+        index >= 0 && (index as usize) < shape[axis],
+        "error: index {} is out of range for axis {} of length {}.",
+        index,
+        axis,
+        shape[axis]
+    );
+    position[axis] = index as usize;
+    assert!(
+        position
+            .slice()
+            .iter()
+            .zip(shape)
+            .all(|(&pos, &len)| pos < len),
+        "error: the indices exceed the operand of shape {:?} along an axis other than {}.",
+        shape,
+        axis
+    );
+    position
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Gather ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct Gather<T: ?Sized, U: ?Sized>
+where
+    T: Data,
+    U: IndexData<Dim = T::Dim>,
+{
+    operand: Rc<T>,
+    indices: Rc<U>,
+    axis: usize,
+    data: RefCell<Tensor<T::Dim>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized, U: ?Sized> Gather<T, U>
+where
+    T: Data,
+    U: IndexData<Dim = T::Dim>,
+{
+    pub fn new(operand: Rc<T>, indices: Rc<U>, axis: usize) -> Self {
+        let data = RefCell::new(Tensor::zeros(indices.data().raw_dim()));
+
+        Self {
+            operand,
+            indices,
+            axis,
+            data,
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Cache for Gather<T, U>
+where
+    T: Data,
+    U: IndexData<Dim = T::Dim>,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Forward for Gather<T, U>
+where
+    T: Data,
+    U: IndexData<Dim = T::Dim>,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let (operand, indices) = (self.operand.data(), self.indices.data());
+        fit_shape(&self.data, indices.raw_dim());
+
+        let operand = operand.view().into_dyn();
+        self.data
+            .borrow_mut()
+            .iter_mut()
+            .zip(indices.view().into_dyn().indexed_iter())
+            .for_each(|(el, (position, &index))| {
+                *el = operand[gathered(position, operand.shape(), self.axis, index)];
+            });
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Data for Gather<T, U>
+where
+    T: Data,
+    U: IndexData<Dim = T::Dim>,
+{
+    type Dim = T::Dim;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Debug for Gather<T, U>
+where
+    T: Data,
+    U: IndexData<Dim = T::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Gather")
+            .field("data", &self.data.borrow())
+            .field("axis", &self.axis)
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Display for Gather<T, U>
+where
+    T: Data,
+    U: IndexData<Dim = T::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ GatherBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct GatherBackward<T: ?Sized, U: ?Sized>
+where
+    T: Gradient,
+    U: IndexData<Dim = T::Dim>,
+{
+    gradient: RefCell<Option<Tensor<T::Dim>>>,
+    shape: T::Dim,
+    overwrite: Cell<bool>,
+    diff_operand: Rc<T>,
+    indices: Rc<U>,
+    axis: usize,
+}
+
+impl<T: ?Sized, U: ?Sized> GatherBackward<T, U>
+where
+    T: Gradient,
+    U: IndexData<Dim = T::Dim>,
+{
+    pub fn new(diff_operand: Rc<T>, indices: Rc<U>, axis: usize) -> Self {
+        let shape = indices.data().raw_dim();
+
+        Self {
+            gradient: RefCell::new(Some(Tensor::zeros(shape.clone()))),
+            shape,
+            overwrite: Cell::new(true),
+            diff_operand,
+            indices,
+            axis,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Gradient for GatherBackward<T, U>
+where
+    T: Gradient,
+    U: IndexData<Dim = T::Dim>,
+{
+    type Dim = T::Dim;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Overwrite for GatherBackward<T, U>
+where
+    T: Gradient,
+    U: IndexData<Dim = T::Dim>,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Backward for GatherBackward<T, U>
+where
+    T: Gradient,
+    U: IndexData<Dim = T::Dim>,
+{
+    fn backward(&self) {
+        let mut operand_grad = self.diff_operand.gradient_mut();
+        let (gradient, indices) = (self.gradient(), self.indices.data());
+
+        // The elements that are not gathered receive no gradient.
+        if self.diff_operand.can_overwrite() {
+            operand_grad.fill(0.);
+            self.diff_operand.set_overwrite(false);
+        }
+
+        let mut operand_grad = operand_grad.view_mut().into_dyn();
+        let shape = operand_grad.shape().to_vec();
+        gradient
+            .iter()
+            .zip(indices.view().into_dyn().indexed_iter())
+            .for_each(|(grad, (position, &index))| {
+                operand_grad[gathered(position, &shape, self.axis, index)] += grad;
+            });
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape.clone()));
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Debug for GatherBackward<T, U>
+where
+    T: Gradient,
+    U: IndexData<Dim = T::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GatherBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("axis", &self.axis)
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Display for GatherBackward<T, U>
+where
+    T: Gradient,
+    U: IndexData<Dim = T::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
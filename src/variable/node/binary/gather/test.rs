@@ -0,0 +1,230 @@
+use super::{
+    assert_almost_equals, new_backward_input, new_index_input, new_input, new_tensor, Backward,
+    Cache, Data, Forward, Gather, GatherBackward, Gradient, IndexData, IndexTensor, Overwrite,
+    Tensor,
+};
+
+mod forward {
+    use super::{
+        assert_almost_equals, new_index_input, new_input, new_tensor, Cache, Data, Forward, Gather,
+        IndexData, IndexTensor, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let input = new_input((2, 3), vec![0., 1., 2., 3., 4., 5.]);
+        let indices = new_index_input((2, 2), vec![2, 0, 1, 1]);
+        let node = Gather::new(input, indices, 1);
+
+        assert_eq!(*node.data(), Tensor::from_elem((2, 2), 0.));
+        assert_eq!(*node.data_mut(), Tensor::from_elem((2, 2), 0.));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let input = new_input((2, 3), vec![0., 1., 2., 3., 4., 5.]);
+        let indices = new_index_input((2, 2), vec![2, 0, 1, 1]);
+        let node = Gather::new(input, indices, 1);
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward() {
+        let input = new_input((2, 3), vec![0., 1., 2., 3., 4., 5.]);
+        let indices = new_index_input((2, 2), vec![2, 0, 1, 1]);
+        let node = Gather::new(input, indices.clone(), 1);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((2, 2), vec![2., 0., 4., 4.]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *indices.data_mut() = IndexTensor::from_shape_vec((2, 2), vec![0, 1, 2, 0]).unwrap();
+
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((2, 2), vec![2., 0., 4., 4.]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((2, 2), vec![0., 1., 5., 3.]));
+    }
+
+    #[test]
+    fn forward_rows() {
+        let input = new_input((2, 3), vec![0., 1., 2., 3., 4., 5.]);
+        let indices = new_index_input((1, 3), vec![1, 0, 1]);
+        let node = Gather::new(input, indices, 0);
+
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((1, 3), vec![3., 1., 5.]));
+    }
+
+    #[test]
+    #[should_panic(expected = "error: index 3 is out of range for axis 1 of length 3.")]
+    fn forward_out_of_range() {
+        let input = new_input((2, 3), vec![0.; 6]);
+        let indices = new_index_input((2, 1), vec![0, 3]);
+        let node = Gather::new(input, indices, 1);
+
+        node.forward();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "error: the indices exceed the operand of shape [1, 3] along an axis other than 1."
+    )]
+    fn forward_mismatching_shapes() {
+        let input = new_input((1, 3), vec![0.; 3]);
+        let indices = new_index_input((3, 1), vec![0, 0, 0]);
+        let node = Gather::new(input, indices, 1);
+
+        node.forward();
+    }
+
+    #[test]
+    fn debug() {
+        let input = new_input(2, vec![0., 1.]);
+        let indices = new_index_input(1, vec![1]);
+        let node = Gather::new(input, indices, 0);
+
+        let output = "Gather { data: [0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1, axis: 0, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let input = new_input(2, vec![0., 1.]);
+        let indices = new_index_input(1, vec![1]);
+        let node = Gather::new(input, indices, 0);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        assert_almost_equals, new_backward_input, new_index_input, new_tensor, Backward,
+        GatherBackward, Gradient, Overwrite, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let node = GatherBackward::new(
+            new_backward_input((2, 3), vec![0.; 6]),
+            new_index_input((2, 2), vec![2, 0, 1, 1]),
+            1,
+        );
+
+        assert_eq!(*node.gradient(), Tensor::from_elem((2, 2), 0.));
+        assert_eq!(*node.gradient_mut(), Tensor::from_elem((2, 2), 0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn computation_state_transition() {
+        let diff = new_backward_input((2, 3), vec![0.; 6]);
+        let node = GatherBackward::new(diff.clone(), new_index_input((2, 2), vec![2, 0, 1, 1]), 1);
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        diff.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!diff.can_overwrite());
+    }
+
+    #[test]
+    fn backward() {
+        let diff = new_backward_input((2, 3), vec![0.; 6]);
+        let node = GatherBackward::new(diff.clone(), new_index_input((2, 2), vec![2, 0, 1, 1]), 1);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((2, 2), vec![1.; 4]);
+        assert_almost_equals(&*node.gradient(), &new_tensor((2, 2), vec![1.; 4]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![1., 0., 1., 0., 2., 0.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![2., 0., 2., 0., 4., 0.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![1., 0., 1., 0., 2., 0.]),
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let node = GatherBackward::new(
+            new_backward_input(2, vec![0.; 2]),
+            new_index_input(1, vec![1]),
+            0,
+        );
+
+        let output = "GatherBackward { gradient: Some([0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1), axis: 0, overwrite: true }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = GatherBackward::new(
+            new_backward_input(2, vec![0.; 2]),
+            new_index_input(1, vec![1]),
+            0,
+        );
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+
+    #[test]
+    fn no_grad() {
+        // GatherBackward
+        let node = GatherBackward::new(
+            new_backward_input((2, 3), vec![0.; 6]),
+            new_index_input((2, 2), vec![2, 0, 1, 1]),
+            1,
+        );
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+    }
+}
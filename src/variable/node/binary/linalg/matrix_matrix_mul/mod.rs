@@ -1,8 +1,8 @@
 #[cfg(test)]
 use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
 use super::{
-    expect_tensor, expect_tensor_mut, push_mat_mat_gradient, Backward, Cache, Data, DotDim,
-    Forward, Gradient, Overwrite, Tensor,
+    expect_tensor, expect_tensor_mut, fit_shape, push_mat_mat_gradient, Backward, Cache, Data,
+    DotDim, Forward, Gradient, Overwrite, Tensor,
 };
 use ndarray::{linalg::general_mat_mul, Ix2};
 use std::{
@@ -84,6 +84,8 @@ where
         }
 
         self.computed.set(true);
+        let shape = DotDim::shape(self.left.data().raw_dim(), self.right.data().raw_dim());
+        fit_shape(&self.data, Ix2(shape[0], shape[1]));
         general_mat_mul(
             1.0,
             &*self.left.data(),
@@ -113,7 +115,7 @@ where
     Rhs: Data<Dim = Ix2>,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(f, "{}", &self.data.borrow())
+        crate::print::fmt_tensor(&self.data.borrow(), f)
     }
 }
 
@@ -252,7 +254,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", &gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
@@ -363,7 +365,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", &gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
@@ -474,7 +476,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", &gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
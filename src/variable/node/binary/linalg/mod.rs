@@ -5,7 +5,7 @@ mod vector_matrix_mul;
 mod vector_vector_mul;
 
 use super::{
-    expect_tensor, expect_tensor_mut, push_mat_mat_gradient, push_mat_vec_gradient,
+    expect_tensor, expect_tensor_mut, fit_shape, push_mat_mat_gradient, push_mat_vec_gradient,
     push_vec_mat_gradient, push_vec_vec_gradient, Backward, Cache, Data, DotDim, Forward, Gradient,
     Overwrite, Tensor,
 };
@@ -3,6 +3,7 @@ mod bce_with_logits_loss;
 mod kldiv_loss;
 mod mae_loss;
 mod mse_loss;
+mod msle_loss;
 mod nll_loss;
 
 use super::{
@@ -19,4 +20,5 @@ pub(crate) use bce_with_logits_loss::{BCEWithLogitsLoss, BCEWithLogitsLossBackwa
 pub(crate) use kldiv_loss::{KLDivLoss, KLDivLossBackward};
 pub(crate) use mae_loss::{MAELoss, MAELossBackward};
 pub(crate) use mse_loss::{MSELoss, MSELossBackward};
+pub(crate) use msle_loss::{MSLELoss, MSLELossBackward};
 pub(crate) use nll_loss::{NLLLoss, NLLLossBackward};
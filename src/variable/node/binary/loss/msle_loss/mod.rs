@@ -0,0 +1,301 @@
+#[cfg(test)]
+use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
+use super::{
+    expect_tensor, expect_tensor_mut, Backward, Cache, Data, Forward, Gradient, Overwrite,
+    Reduction, Tensor,
+};
+use ndarray::{arr0, Ix0, Zip};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ MSLELoss ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[allow(clippy::upper_case_acronyms)]
+pub struct MSLELoss<T: ?Sized, U: ?Sized>
+where
+    T: Data,
+    U: Data<Dim = T::Dim>,
+{
+    input: Rc<T>,
+    target: Rc<U>,
+    data: RefCell<Tensor<Ix0>>,
+    reduction: Reduction,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized, U: ?Sized> MSLELoss<T, U>
+where
+    T: Data,
+    U: Data<Dim = T::Dim>,
+{
+    pub(crate) fn new(input: Rc<T>, target: Rc<U>, reduction: Reduction) -> Self {
+        Self {
+            input,
+            target,
+            data: RefCell::new(arr0(0.)),
+            reduction,
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Data for MSLELoss<T, U>
+where
+    T: Data,
+    U: Data<Dim = T::Dim>,
+{
+    type Dim = Ix0;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Cache for MSLELoss<T, U>
+where
+    T: Data,
+    U: Data<Dim = T::Dim>,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Forward for MSLELoss<T, U>
+where
+    T: Data,
+    U: Data<Dim = T::Dim>,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let (mut loss_data, input_data, target_data) = {
+            (
+                self.data.borrow_mut(),
+                self.input.data(),
+                self.target.data(),
+            )
+        };
+        *loss_data = {
+            let total_loss = Zip::from(&*input_data)
+                .and(&*target_data)
+                .fold(0.0, |loss, input, target| {
+                    loss + (input.ln_1p() - target.ln_1p()).powi(2)
+                });
+            match self.reduction {
+                Reduction::Mean => arr0(total_loss / input_data.len() as f32),
+                Reduction::Sum => arr0(total_loss),
+            }
+        };
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Debug for MSLELoss<T, U>
+where
+    T: Data,
+    U: Data<Dim = T::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MSLELoss")
+            .field("data", &self.data.borrow())
+            .field("reduction", &self.reduction)
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Display for MSLELoss<T, U>
+where
+    T: Data,
+    U: Data<Dim = T::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+/// Derivative of the squared logarithmic error with respect to the input.
+fn msle_derivative(input: f32, target: f32) -> f32 {
+    2. * (input.ln_1p() - target.ln_1p()) / (1. + input)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ MSLELossBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[allow(clippy::upper_case_acronyms)]
+pub struct MSLELossBackward<T: ?Sized, U: ?Sized, V: ?Sized>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    V: Data<Dim = U::Dim>,
+{
+    gradient: RefCell<Option<Tensor<Ix0>>>,
+    overwrite: Cell<bool>,
+    diff_input: Rc<T>,
+    input: Rc<U>,
+    target: Rc<V>,
+    reduction: Reduction,
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> MSLELossBackward<T, U, V>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    V: Data<Dim = U::Dim>,
+{
+    pub(crate) fn new(
+        diff_input: Rc<T>,
+        input: Rc<U>,
+        target: Rc<V>,
+        reduction: Reduction,
+    ) -> Self {
+        Self {
+            diff_input,
+            input,
+            target,
+            gradient: RefCell::new(Some(arr0(0.))),
+            reduction,
+            overwrite: Cell::new(true),
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Gradient for MSLELossBackward<T, U, V>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    V: Data<Dim = U::Dim>,
+{
+    type Dim = Ix0;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Overwrite for MSLELossBackward<T, U, V>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    V: Data<Dim = T::Dim>,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Backward for MSLELossBackward<T, U, V>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    V: Data<Dim = T::Dim>,
+{
+    fn backward(&self) {
+        let (mut operand_gradient, gradient, input_data, target_data) = {
+            (
+                self.diff_input.gradient_mut(),
+                self.gradient(),
+                self.input.data(),
+                self.target.data(),
+            )
+        };
+
+        let zip = Zip::from(&mut *operand_gradient)
+            .and_broadcast(&*gradient)
+            .and(&*input_data)
+            .and(&*target_data);
+        match self.reduction {
+            Reduction::Mean => {
+                let n = input_data.len() as f32;
+                if self.diff_input.can_overwrite() {
+                    zip.for_each(|op_grad, grad, input, target| {
+                        *op_grad = msle_derivative(*input, *target) * grad / n
+                    });
+                    self.diff_input.set_overwrite(false);
+                } else {
+                    zip.for_each(|op_grad, grad, input, target| {
+                        *op_grad += msle_derivative(*input, *target) * grad / n
+                    });
+                }
+            }
+            Reduction::Sum => {
+                if self.diff_input.can_overwrite() {
+                    zip.for_each(|op_grad, grad, input, target| {
+                        *op_grad = msle_derivative(*input, *target) * grad
+                    });
+                    self.diff_input.set_overwrite(false);
+                } else {
+                    zip.for_each(|op_grad, grad, input, target| {
+                        *op_grad += msle_derivative(*input, *target) * grad
+                    });
+                }
+            }
+        }
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(arr0(0.));
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Debug for MSLELossBackward<T, U, V>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    V: Data<Dim = T::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MSLELossBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("reduction", &self.reduction)
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Display for MSLELossBackward<T, U, V>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    V: Data<Dim = T::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
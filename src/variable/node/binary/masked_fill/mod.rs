@@ -0,0 +1,249 @@
+#[cfg(test)]
+use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
+use super::{
+    expect_tensor, expect_tensor_mut, fit_shape, Backward, Cache, Data, Forward, Gradient,
+    Overwrite, Tensor,
+};
+use ndarray::Zip;
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ MaskedFill ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct MaskedFill<T: ?Sized, U: ?Sized>
+where
+    T: Data,
+    U: Data,
+{
+    operand: Rc<T>,
+    mask: Rc<U>,
+    value: f32,
+    data: RefCell<Tensor<T::Dim>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized, U: ?Sized> MaskedFill<T, U>
+where
+    T: Data,
+    U: Data,
+{
+    pub fn new(operand: Rc<T>, mask: Rc<U>, value: f32) -> Self {
+        let data = RefCell::new(Tensor::zeros(operand.data().raw_dim()));
+
+        Self {
+            operand,
+            mask,
+            value,
+            data,
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Cache for MaskedFill<T, U>
+where
+    T: Data,
+    U: Data,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Forward for MaskedFill<T, U>
+where
+    T: Data,
+    U: Data,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let operand = self.operand.data();
+        fit_shape(&self.data, operand.raw_dim());
+
+        let value = self.value;
+        Zip::from(&mut *self.data.borrow_mut())
+            .and(&*operand)
+            .and_broadcast(&*self.mask.data())
+            .for_each(|v, &o, &m| *v = if m != 0. { value } else { o });
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Data for MaskedFill<T, U>
+where
+    T: Data,
+    U: Data,
+{
+    type Dim = T::Dim;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Debug for MaskedFill<T, U>
+where
+    T: Data,
+    U: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MaskedFill")
+            .field("data", &self.data.borrow())
+            .field("value", &self.value)
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Display for MaskedFill<T, U>
+where
+    T: Data,
+    U: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ MaskedFillBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct MaskedFillBackward<T: ?Sized, U: ?Sized>
+where
+    T: Gradient,
+    U: Data,
+{
+    gradient: RefCell<Option<Tensor<T::Dim>>>,
+    shape: T::Dim,
+    overwrite: Cell<bool>,
+    diff_operand: Rc<T>,
+    mask: Rc<U>,
+}
+
+impl<T: ?Sized, U: ?Sized> MaskedFillBackward<T, U>
+where
+    T: Gradient,
+    U: Data,
+{
+    pub fn new(diff_operand: Rc<T>, mask: Rc<U>) -> Self {
+        let shape = diff_operand.gradient().raw_dim();
+
+        Self {
+            gradient: RefCell::new(Some(Tensor::zeros(shape.clone()))),
+            shape,
+            overwrite: Cell::new(true),
+            diff_operand,
+            mask,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Gradient for MaskedFillBackward<T, U>
+where
+    T: Gradient,
+    U: Data,
+{
+    type Dim = T::Dim;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Overwrite for MaskedFillBackward<T, U>
+where
+    T: Gradient,
+    U: Data,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Backward for MaskedFillBackward<T, U>
+where
+    T: Gradient,
+    U: Data,
+{
+    fn backward(&self) {
+        let mut op_grad = self.diff_operand.gradient_mut();
+        let grad = self.gradient();
+        let mask = self.mask.data();
+
+        // The filled elements receive no gradient.
+        let zip = Zip::from(&mut *op_grad).and(&*grad).and_broadcast(&*mask);
+        if self.diff_operand.can_overwrite() {
+            zip.for_each(|op_grad_el, &grad_el, &mask_el| {
+                *op_grad_el = ((mask_el == 0.) as usize as f32) * grad_el
+            });
+            self.diff_operand.set_overwrite(false);
+        } else {
+            zip.for_each(|op_grad_el, &grad_el, &mask_el| {
+                *op_grad_el += ((mask_el == 0.) as usize as f32) * grad_el
+            });
+        }
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape.clone()));
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Debug for MaskedFillBackward<T, U>
+where
+    T: Gradient,
+    U: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MaskedFillBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Display for MaskedFillBackward<T, U>
+where
+    T: Gradient,
+    U: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
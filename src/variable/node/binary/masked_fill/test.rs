@@ -0,0 +1,206 @@
+use super::{
+    assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Cache, Data,
+    Forward, Gradient, MaskedFill, MaskedFillBackward, Overwrite, Tensor,
+};
+
+mod forward {
+    use super::{
+        assert_almost_equals, new_input, new_tensor, Cache, Data, Forward, MaskedFill, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let node = MaskedFill::new(
+            new_input((2, 2), vec![1., 2., 3., 4.]),
+            new_input((2, 2), vec![0., 1., 1., 0.]),
+            -1.,
+        );
+
+        assert_eq!(*node.data(), Tensor::from_elem((2, 2), 0.));
+        assert_eq!(*node.data_mut(), Tensor::from_elem((2, 2), 0.));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let node = MaskedFill::new(
+            new_input((2, 2), vec![1., 2., 3., 4.]),
+            new_input((2, 2), vec![0., 1., 1., 0.]),
+            -1.,
+        );
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward() {
+        let mask = new_input((2, 2), vec![0., 1., 1., 0.]);
+        let node = MaskedFill::new(new_input((2, 2), vec![1., 2., 3., 4.]), mask.clone(), -1.);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((2, 2), vec![1., -1., -1., 4.]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *mask.data_mut() = new_tensor((2, 2), vec![1., 0., 0., 0.]);
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((2, 2), vec![1., -1., -1., 4.]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((2, 2), vec![-1., 2., 3., 4.]));
+    }
+
+    #[test]
+    fn broadcast_forward() {
+        let node = MaskedFill::new(
+            new_input((2, 3), vec![1., 2., 3., 4., 5., 6.]),
+            new_input(3, vec![0., 0., 1.]),
+            f32::NEG_INFINITY,
+        );
+
+        node.forward();
+        assert_eq!(
+            *node.data(),
+            new_tensor(
+                (2, 3),
+                vec![1., 2., f32::NEG_INFINITY, 4., 5., f32::NEG_INFINITY]
+            )
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let node = MaskedFill::new(new_input(1, vec![0.]), new_input(1, vec![0.]), -1.);
+
+        let output = "MaskedFill { data: [0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1, value: -1.0, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = MaskedFill::new(new_input(1, vec![0.]), new_input(1, vec![0.]), -1.);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Gradient,
+        MaskedFillBackward, Overwrite, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let node = MaskedFillBackward::new(
+            new_backward_input((2, 2), vec![0.; 4]),
+            new_input((2, 2), vec![0., 1., 1., 0.]),
+        );
+
+        assert_eq!(*node.gradient(), Tensor::from_elem((2, 2), 0.));
+        assert_eq!(*node.gradient_mut(), Tensor::from_elem((2, 2), 0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn computation_state_transition() {
+        let diff = new_backward_input((2, 2), vec![0.; 4]);
+        let node = MaskedFillBackward::new(diff.clone(), new_input((2, 2), vec![0., 1., 1., 0.]));
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        diff.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!diff.can_overwrite());
+    }
+
+    #[test]
+    fn backward() {
+        let diff = new_backward_input((2, 3), vec![0.; 6]);
+        let node = MaskedFillBackward::new(diff.clone(), new_input(3, vec![0., 1., 0.]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((2, 3), vec![1.; 6]);
+        assert_almost_equals(&*node.gradient(), &new_tensor((2, 3), vec![1.; 6]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![1., 0., 1., 1., 0., 1.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![2., 0., 2., 2., 0., 2.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![1., 0., 1., 1., 0., 1.]),
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let node = MaskedFillBackward::new(new_backward_input(1, vec![0.]), new_input(1, vec![0.]));
+
+        let output = "MaskedFillBackward { gradient: Some([0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1), overwrite: true }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = MaskedFillBackward::new(new_backward_input(1, vec![0.]), new_input(1, vec![0.]));
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+
+    #[test]
+    fn no_grad() {
+        // MaskedFillBackward
+        let node = MaskedFillBackward::new(
+            new_backward_input((3, 3), vec![0.; 9]),
+            new_input((3, 3), vec![0.; 9]),
+        );
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+    }
+}
@@ -0,0 +1,534 @@
+#[cfg(test)]
+use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
+use super::{
+    expect_tensor, expect_tensor_mut, fit_shape, Backward, Cache, Data, Forward, Gradient,
+    Overwrite, Tensor,
+};
+use ndarray::{Dimension, Ix2, Ix3, Zip};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// Returns the shape of the result of a masked pooling over a batch of sequences of shape
+/// `shape`, checking that the mask has shape `mask_shape`.
+fn pooled_shape(shape: Ix3, mask_shape: Ix2) -> Ix2 {
+    assert!(
+        mask_shape == Ix2(shape[0], shape[1]),
+        "error: a mask of shape {:?} cannot be applied to sequences of shape {:?}.",
+        mask_shape.slice(),
+        shape.slice()
+    );
+
+    Ix2(shape[0], shape[2])
+}
+
+/// Returns the number of positions of `mask` that are not padding.
+fn valid_positions<'a>(mask: impl IntoIterator<Item = &'a f32>) -> usize {
+    mask.into_iter().filter(|&&el| el != 0.).count()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ MaskedMeanPool ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct MaskedMeanPool<T: ?Sized, U: ?Sized>
+where
+    T: Data<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    operand: Rc<T>,
+    mask: Rc<U>,
+    data: RefCell<Tensor<Ix2>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized, U: ?Sized> MaskedMeanPool<T, U>
+where
+    T: Data<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    pub fn new(operand: Rc<T>, mask: Rc<U>) -> Self {
+        let shape = pooled_shape(operand.data().raw_dim(), mask.data().raw_dim());
+        let data = RefCell::new(Tensor::zeros(shape));
+
+        Self {
+            operand,
+            mask,
+            data,
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Cache for MaskedMeanPool<T, U>
+where
+    T: Data<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Forward for MaskedMeanPool<T, U>
+where
+    T: Data<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let operand = self.operand.data();
+        let mask = self.mask.data();
+        fit_shape(&self.data, pooled_shape(operand.raw_dim(), mask.raw_dim()));
+
+        // The mean of a sequence made only of padding is zero.
+        Zip::from(self.data.borrow_mut().rows_mut())
+            .and(operand.outer_iter())
+            .and(mask.rows())
+            .for_each(|mut pooled, sequence, mask| {
+                pooled.fill(0.);
+                sequence
+                    .outer_iter()
+                    .zip(mask)
+                    .filter(|(_, &mask_el)| mask_el != 0.)
+                    .for_each(|(step, _)| pooled += &step);
+                pooled /= valid_positions(mask).max(1) as f32;
+            });
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Data for MaskedMeanPool<T, U>
+where
+    T: Data<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    type Dim = Ix2;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Debug for MaskedMeanPool<T, U>
+where
+    T: Data<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MaskedMeanPool")
+            .field("data", &self.data.borrow())
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Display for MaskedMeanPool<T, U>
+where
+    T: Data<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ MaskedMeanPoolBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct MaskedMeanPoolBackward<T: ?Sized, U: ?Sized>
+where
+    T: Gradient<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    gradient: RefCell<Option<Tensor<Ix2>>>,
+    shape: Ix2,
+    overwrite: Cell<bool>,
+    diff_operand: Rc<T>,
+    mask: Rc<U>,
+}
+
+impl<T: ?Sized, U: ?Sized> MaskedMeanPoolBackward<T, U>
+where
+    T: Gradient<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    pub fn new(diff_operand: Rc<T>, mask: Rc<U>) -> Self {
+        let shape = pooled_shape(diff_operand.gradient().raw_dim(), mask.data().raw_dim());
+
+        Self {
+            gradient: RefCell::new(Some(Tensor::zeros(shape))),
+            shape,
+            overwrite: Cell::new(true),
+            diff_operand,
+            mask,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Gradient for MaskedMeanPoolBackward<T, U>
+where
+    T: Gradient<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    type Dim = Ix2;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Overwrite for MaskedMeanPoolBackward<T, U>
+where
+    T: Gradient<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Backward for MaskedMeanPoolBackward<T, U>
+where
+    T: Gradient<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    fn backward(&self) {
+        let mut op_grad = self.diff_operand.gradient_mut();
+        if self.diff_operand.can_overwrite() {
+            op_grad.fill(0.);
+            self.diff_operand.set_overwrite(false);
+        }
+
+        // The padding positions receive no gradient.
+        let mask = self.mask.data();
+        Zip::from(op_grad.outer_iter_mut())
+            .and(self.gradient().rows())
+            .and(mask.rows())
+            .for_each(|mut sequence_grad, grad, mask| {
+                let len = valid_positions(mask).max(1) as f32;
+                sequence_grad
+                    .outer_iter_mut()
+                    .zip(mask)
+                    .filter(|(_, &mask_el)| mask_el != 0.)
+                    .for_each(|(mut step_grad, _)| step_grad.scaled_add(1. / len, &grad));
+            });
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape));
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Debug for MaskedMeanPoolBackward<T, U>
+where
+    T: Gradient<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MaskedMeanPoolBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Display for MaskedMeanPoolBackward<T, U>
+where
+    T: Gradient<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ MaskedMaxPool ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct MaskedMaxPool<T: ?Sized, U: ?Sized>
+where
+    T: Data<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    operand: Rc<T>,
+    mask: Rc<U>,
+    data: RefCell<Tensor<Ix2>>,
+    indices: RefCell<Vec<Option<usize>>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized, U: ?Sized> MaskedMaxPool<T, U>
+where
+    T: Data<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    pub fn new(operand: Rc<T>, mask: Rc<U>) -> Self {
+        let shape = pooled_shape(operand.data().raw_dim(), mask.data().raw_dim());
+        let indices = RefCell::new(vec![None; shape.size()]);
+        let data = RefCell::new(Tensor::zeros(shape));
+
+        Self {
+            operand,
+            mask,
+            data,
+            indices,
+            computed: Cell::new(false),
+        }
+    }
+
+    /// Returns the time step of the maximum of each feature of each sequence, which is `None`
+    /// for the sequences made only of padding.
+    pub(crate) fn indices(&self) -> Ref<Vec<Option<usize>>> {
+        self.indices.borrow()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Cache for MaskedMaxPool<T, U>
+where
+    T: Data<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Forward for MaskedMaxPool<T, U>
+where
+    T: Data<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let operand = self.operand.data();
+        let mask = self.mask.data();
+        let shape = pooled_shape(operand.raw_dim(), mask.raw_dim());
+        fit_shape(&self.data, shape);
+
+        let mut indices = self.indices.borrow_mut();
+        indices.clear();
+        indices.resize(shape.size(), None);
+
+        // The maximum of a sequence made only of padding is zero.
+        let mut data = self.data.borrow_mut();
+        data.indexed_iter_mut()
+            .zip(indices.iter_mut())
+            .for_each(|(((b, e), pooled), index)| {
+                *index = None;
+                *pooled = 0.;
+                for (t, &mask_el) in mask.row(b).iter().enumerate() {
+                    let el = operand[[b, t, e]];
+                    if mask_el != 0. && (index.is_none() || el > *pooled) {
+                        *index = Some(t);
+                        *pooled = el;
+                    }
+                }
+            });
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Data for MaskedMaxPool<T, U>
+where
+    T: Data<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    type Dim = Ix2;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Debug for MaskedMaxPool<T, U>
+where
+    T: Data<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MaskedMaxPool")
+            .field("data", &self.data.borrow())
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Display for MaskedMaxPool<T, U>
+where
+    T: Data<Dim = Ix3>,
+    U: Data<Dim = Ix2>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ MaskedMaxPoolBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct MaskedMaxPoolBackward<T: ?Sized, U: ?Sized, V: ?Sized>
+where
+    T: Gradient<Dim = Ix3>,
+    U: Data<Dim = Ix3>,
+    V: Data<Dim = Ix2>,
+{
+    gradient: RefCell<Option<Tensor<Ix2>>>,
+    shape: Ix2,
+    overwrite: Cell<bool>,
+    diff_operand: Rc<T>,
+    no_diff_operand: Rc<MaskedMaxPool<U, V>>,
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> MaskedMaxPoolBackward<T, U, V>
+where
+    T: Gradient<Dim = Ix3>,
+    U: Data<Dim = Ix3>,
+    V: Data<Dim = Ix2>,
+{
+    pub fn new(diff_operand: Rc<T>, no_diff_operand: Rc<MaskedMaxPool<U, V>>) -> Self {
+        let shape = no_diff_operand.data().raw_dim();
+
+        Self {
+            gradient: RefCell::new(Some(Tensor::zeros(shape))),
+            shape,
+            overwrite: Cell::new(true),
+            diff_operand,
+            no_diff_operand,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Gradient for MaskedMaxPoolBackward<T, U, V>
+where
+    T: Gradient<Dim = Ix3>,
+    U: Data<Dim = Ix3>,
+    V: Data<Dim = Ix2>,
+{
+    type Dim = Ix2;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Overwrite for MaskedMaxPoolBackward<T, U, V>
+where
+    T: Gradient<Dim = Ix3>,
+    U: Data<Dim = Ix3>,
+    V: Data<Dim = Ix2>,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Backward for MaskedMaxPoolBackward<T, U, V>
+where
+    T: Gradient<Dim = Ix3>,
+    U: Data<Dim = Ix3>,
+    V: Data<Dim = Ix2>,
+{
+    fn backward(&self) {
+        let mut op_grad = self.diff_operand.gradient_mut();
+        if self.diff_operand.can_overwrite() {
+            op_grad.fill(0.);
+            self.diff_operand.set_overwrite(false);
+        }
+
+        let indices = self.no_diff_operand.indices();
+        self.gradient()
+            .indexed_iter()
+            .zip(indices.iter())
+            .filter_map(|(entry, index)| index.map(|t| (entry, t)))
+            .for_each(|(((b, e), grad_el), t)| op_grad[[b, t, e]] += grad_el);
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape));
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Debug for MaskedMaxPoolBackward<T, U, V>
+where
+    T: Gradient<Dim = Ix3>,
+    U: Data<Dim = Ix3>,
+    V: Data<Dim = Ix2>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MaskedMaxPoolBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Display for MaskedMaxPoolBackward<T, U, V>
+where
+    T: Gradient<Dim = Ix3>,
+    U: Data<Dim = Ix3>,
+    V: Data<Dim = Ix2>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,310 @@
+use super::{
+    assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Cache, Data,
+    Forward, Gradient, MaskedMaxPool, MaskedMaxPoolBackward, MaskedMeanPool,
+    MaskedMeanPoolBackward, Overwrite, Tensor,
+};
+
+fn sequences() -> Vec<f32> {
+    vec![1., 2., 3., -4., 5., 6., -1., 0., 2., -3., 7., 8.]
+}
+
+mod forward {
+    use super::{
+        assert_almost_equals, new_input, new_tensor, sequences, Cache, Data, Forward,
+        MaskedMaxPool, MaskedMeanPool, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let node = MaskedMeanPool::new(
+            new_input((2, 3, 2), sequences()),
+            new_input((2, 3), vec![1., 1., 0., 0., 1., 0.]),
+        );
+
+        assert_eq!(*node.data(), Tensor::from_elem((2, 2), 0.));
+        assert_eq!(*node.data_mut(), Tensor::from_elem((2, 2), 0.));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    #[should_panic(expected = "error: a mask of shape [2, 2] cannot be applied to sequences")]
+    fn wrong_mask() {
+        MaskedMaxPool::new(
+            new_input((2, 3, 2), sequences()),
+            new_input((2, 2), vec![1.; 4]),
+        );
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let node = MaskedMaxPool::new(
+            new_input((2, 3, 2), sequences()),
+            new_input((2, 3), vec![1., 1., 0., 0., 1., 0.]),
+        );
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn mean_forward() {
+        let mask = new_input((2, 3), vec![1., 1., 0., 0., 1., 0.]);
+        let node = MaskedMeanPool::new(new_input((2, 3, 2), sequences()), mask.clone());
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((2, 2), vec![2., -1., 2., -3.]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *mask.data_mut() = new_tensor((2, 3), vec![1., 1., 1., 0., 0., 0.]);
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((2, 2), vec![2., -1., 2., -3.]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 2), vec![3., 4. / 3., 0., 0.]),
+        );
+    }
+
+    #[test]
+    fn max_forward() {
+        let mask = new_input((2, 3), vec![1., 1., 0., 0., 1., 0.]);
+        let node = MaskedMaxPool::new(new_input((2, 3, 2), sequences()), mask.clone());
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((2, 2), vec![3., 2., 2., -3.]));
+        assert_eq!(*node.indices(), vec![Some(1), Some(0), Some(1), Some(1)]);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *mask.data_mut() = new_tensor((2, 3), vec![1., 1., 1., 0., 0., 0.]);
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((2, 2), vec![3., 2., 2., -3.]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((2, 2), vec![5., 6., 0., 0.]));
+        assert_eq!(*node.indices(), vec![Some(2), Some(2), None, None]);
+    }
+
+    #[test]
+    fn debug() {
+        let node = MaskedMeanPool::new(new_input((1, 1, 1), vec![0.]), new_input((1, 1), vec![1.]));
+
+        let output = "MaskedMeanPool { data: [[0.0]], shape=[1, 1], strides=[1, 1], layout=CFcf (0xf), const ndim=2, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = MaskedMaxPool::new(new_input((1, 1, 1), vec![0.]), new_input((1, 1), vec![1.]));
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        assert_almost_equals, new_backward_input, new_input, new_tensor, sequences, Backward,
+        Forward, Gradient, MaskedMaxPool, MaskedMaxPoolBackward, MaskedMeanPoolBackward, Overwrite,
+        Tensor,
+    };
+    use std::rc::Rc;
+
+    #[test]
+    fn creation() {
+        let node = MaskedMeanPoolBackward::new(
+            new_backward_input((2, 3, 2), vec![0.; 12]),
+            new_input((2, 3), vec![1., 1., 0., 0., 1., 0.]),
+        );
+
+        assert_eq!(*node.gradient(), Tensor::from_elem((2, 2), 0.));
+        assert_eq!(*node.gradient_mut(), Tensor::from_elem((2, 2), 0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn computation_state_transition() {
+        let diff = new_backward_input((2, 3, 2), vec![0.; 12]);
+        let node = MaskedMeanPoolBackward::new(
+            diff.clone(),
+            new_input((2, 3), vec![1., 1., 0., 0., 1., 0.]),
+        );
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        diff.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!diff.can_overwrite());
+    }
+
+    #[test]
+    fn mean_backward() {
+        let diff = new_backward_input((2, 3, 2), vec![0.; 12]);
+        let node = MaskedMeanPoolBackward::new(
+            diff.clone(),
+            new_input((2, 3), vec![1., 1., 0., 0., 1., 0.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((2, 2), vec![1.; 4]);
+        assert_almost_equals(&*node.gradient(), &new_tensor((2, 2), vec![1.; 4]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (2, 3, 2),
+                vec![0.5, 0.5, 0.5, 0.5, 0., 0., 0., 0., 1., 1., 0., 0.],
+            ),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (2, 3, 2),
+                vec![1., 1., 1., 1., 0., 0., 0., 0., 2., 2., 0., 0.],
+            ),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (2, 3, 2),
+                vec![0.5, 0.5, 0.5, 0.5, 0., 0., 0., 0., 1., 1., 0., 0.],
+            ),
+        );
+    }
+
+    #[test]
+    fn max_backward() {
+        let diff = new_backward_input((2, 3, 2), vec![0.; 12]);
+        let pool = Rc::new(MaskedMaxPool::new(
+            new_input((2, 3, 2), sequences()),
+            new_input((2, 3), vec![1., 1., 0., 0., 1., 0.]),
+        ));
+        let node = MaskedMaxPoolBackward::new(diff.clone(), pool.clone());
+        pool.forward();
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((2, 2), vec![1.; 4]);
+        assert_almost_equals(&*node.gradient(), &new_tensor((2, 2), vec![1.; 4]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (2, 3, 2),
+                vec![0., 1., 1., 0., 0., 0., 0., 0., 1., 1., 0., 0.],
+            ),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (2, 3, 2),
+                vec![0., 2., 2., 0., 0., 0., 0., 0., 2., 2., 0., 0.],
+            ),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (2, 3, 2),
+                vec![0., 1., 1., 0., 0., 0., 0., 0., 1., 1., 0., 0.],
+            ),
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let node = MaskedMeanPoolBackward::new(
+            new_backward_input((1, 1, 1), vec![0.]),
+            new_input((1, 1), vec![1.]),
+        );
+
+        let output = "MaskedMeanPoolBackward { gradient: Some([[0.0]], shape=[1, 1], strides=[1, 1], layout=CFcf (0xf), const ndim=2), overwrite: true }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = MaskedMeanPoolBackward::new(
+            new_backward_input((1, 1, 1), vec![0.]),
+            new_input((1, 1), vec![1.]),
+        );
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+
+    #[test]
+    fn no_grad() {
+        // MaskedMeanPoolBackward
+        let node = MaskedMeanPoolBackward::new(
+            new_backward_input((2, 3, 2), vec![0.; 12]),
+            new_input((2, 3), vec![1.; 6]),
+        );
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+
+        // MaskedMaxPoolBackward
+        let node = MaskedMaxPoolBackward::new(
+            new_backward_input((2, 3, 2), vec![0.; 12]),
+            Rc::new(MaskedMaxPool::new(
+                new_input((2, 3, 2), vec![0.; 12]),
+                new_input((2, 3), vec![1.; 6]),
+            )),
+        );
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+    }
+}
@@ -0,0 +1,463 @@
+#[cfg(test)]
+use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
+use super::{
+    cobroadcasted_shape, cobroadcasted_zeros, expect_tensor, expect_tensor_mut, fit_gradient_shape,
+    fit_shape, push_gradient, reduce, Backward, BroadTensor, Broadcasted, Cache, Data, Forward,
+    Gradient, Overwrite, Tensor,
+};
+use ndarray::{DimMax, Dimension, Zip};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// Returns the larger between `left` and `right`, propagating `NaN`s.
+fn maximum(left: f32, right: f32) -> f32 {
+    if left.is_nan() || left > right {
+        left
+    } else {
+        right
+    }
+}
+
+/// Returns the fraction of the gradient of the maximum between `left` and `right` that is routed
+/// to `left`.
+///
+/// The whole gradient goes to the larger operand, or to the `NaN` one, and is split evenly
+/// between the two when they are equal.
+fn left_share(left: f32, right: f32) -> f32 {
+    if left == right {
+        0.5
+    } else if left.is_nan() || left > right {
+        1.
+    } else {
+        0.
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Maximum ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct Maximum<Lhs: ?Sized, Rhs: ?Sized>
+where
+    Lhs: Data,
+    Rhs: Data,
+    Lhs::Dim: Dimension + DimMax<Rhs::Dim>,
+{
+    left: Rc<Lhs>,
+    right: Rc<Rhs>,
+    data: RefCell<BroadTensor<Lhs::Dim, Rhs::Dim>>,
+    computed: Cell<bool>,
+}
+
+impl<Lhs: ?Sized, Rhs: ?Sized> Maximum<Lhs, Rhs>
+where
+    Lhs: Data,
+    Rhs: Data,
+    Lhs::Dim: Dimension + DimMax<Rhs::Dim>,
+{
+    pub fn new(left: Rc<Lhs>, right: Rc<Rhs>) -> Self {
+        let data = RefCell::new(cobroadcasted_zeros(&left.data(), &right.data()));
+
+        Self {
+            left,
+            right,
+            data,
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<Lhs: ?Sized, Rhs: ?Sized> Data for Maximum<Lhs, Rhs>
+where
+    Lhs: Data,
+    Rhs: Data,
+    Lhs::Dim: Dimension + DimMax<Rhs::Dim>,
+{
+    type Dim = Broadcasted<Lhs::Dim, Rhs::Dim>;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<Lhs: ?Sized, Rhs: ?Sized> Cache for Maximum<Lhs, Rhs>
+where
+    Lhs: Data,
+    Rhs: Data,
+    Lhs::Dim: Dimension + DimMax<Rhs::Dim>,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<Lhs: ?Sized, Rhs: ?Sized> Forward for Maximum<Lhs, Rhs>
+where
+    Lhs: Data,
+    Rhs: Data,
+    Lhs::Dim: Dimension + DimMax<Rhs::Dim>,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        fit_shape(
+            &self.data,
+            cobroadcasted_shape(&self.left.data(), &self.right.data()),
+        );
+        Zip::from(&mut *self.data.borrow_mut())
+            .and_broadcast(&*self.left.data())
+            .and_broadcast(&*self.right.data())
+            .for_each(|v, &l, &r| *v = maximum(l, r));
+    }
+}
+
+impl<Lhs: ?Sized, Rhs: ?Sized> Debug for Maximum<Lhs, Rhs>
+where
+    Lhs: Data,
+    Rhs: Data,
+    Lhs::Dim: Dimension + DimMax<Rhs::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Maximum")
+            .field("data", &self.data.borrow())
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<Lhs: ?Sized, Rhs: ?Sized> Display for Maximum<Lhs, Rhs>
+where
+    Lhs: Data,
+    Rhs: Data,
+    Lhs::Dim: Dimension + DimMax<Rhs::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ MaximumBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct MaximumBackward<LhsD: ?Sized, LhsG: ?Sized, RhsD: ?Sized, RhsG: ?Sized>
+where
+    LhsD: Data,
+    RhsD: Data,
+    LhsG: Gradient,
+    RhsG: Gradient,
+    LhsD::Dim: Dimension + DimMax<RhsD::Dim>,
+    LhsG::Dim: Dimension + DimMax<RhsG::Dim>,
+{
+    gradient: RefCell<Option<BroadTensor<LhsG::Dim, RhsG::Dim>>>,
+    shape: Broadcasted<LhsG::Dim, RhsG::Dim>,
+    overwrite: Cell<bool>,
+    buffer: RefCell<Option<BroadTensor<LhsG::Dim, RhsG::Dim>>>,
+    left_data: Rc<LhsD>,
+    left_grad: Rc<LhsG>,
+    right_data: Rc<RhsD>,
+    right_grad: Rc<RhsG>,
+}
+
+impl<LhsD: ?Sized, LhsG: ?Sized, RhsD: ?Sized, RhsG: ?Sized> MaximumBackward<LhsD, LhsG, RhsD, RhsG>
+where
+    LhsD: Data,
+    RhsD: Data,
+    LhsG: Gradient,
+    RhsG: Gradient,
+    LhsD::Dim: Dimension + DimMax<RhsD::Dim>,
+    LhsG::Dim: Dimension + DimMax<RhsG::Dim>,
+{
+    pub fn new(
+        left_data: Rc<LhsD>,
+        left_grad: Rc<LhsG>,
+        right_data: Rc<RhsD>,
+        right_grad: Rc<RhsG>,
+    ) -> Self {
+        let gradient = cobroadcasted_zeros(&left_grad.gradient(), &right_grad.gradient());
+        let shape = gradient.raw_dim();
+
+        Self {
+            gradient: RefCell::new(Some(gradient)),
+            shape: shape.clone(),
+            overwrite: Cell::new(true),
+            buffer: RefCell::new(Some(Tensor::zeros(shape))),
+            left_data,
+            left_grad,
+            right_data,
+            right_grad,
+        }
+    }
+}
+
+impl<LhsD: ?Sized, LhsG: ?Sized, RhsD: ?Sized, RhsG: ?Sized> Gradient
+    for MaximumBackward<LhsD, LhsG, RhsD, RhsG>
+where
+    LhsD: Data,
+    RhsD: Data,
+    LhsG: Gradient,
+    RhsG: Gradient,
+    LhsD::Dim: Dimension + DimMax<RhsD::Dim>,
+    LhsG::Dim: Dimension + DimMax<RhsG::Dim>,
+{
+    type Dim = Broadcasted<LhsG::Dim, RhsG::Dim>;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<LhsD: ?Sized, LhsG: ?Sized, RhsD: ?Sized, RhsG: ?Sized> Overwrite
+    for MaximumBackward<LhsD, LhsG, RhsD, RhsG>
+where
+    LhsD: Data,
+    RhsD: Data,
+    LhsG: Gradient,
+    RhsG: Gradient,
+    LhsD::Dim: Dimension + DimMax<RhsD::Dim>,
+    LhsG::Dim: Dimension + DimMax<RhsG::Dim>,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<LhsD: ?Sized, LhsG: ?Sized, RhsD: ?Sized, RhsG: ?Sized> Backward
+    for MaximumBackward<LhsD, LhsG, RhsD, RhsG>
+where
+    LhsD: Data,
+    RhsD: Data,
+    LhsG: Gradient,
+    RhsG: Gradient,
+    LhsD::Dim: Dimension + DimMax<RhsD::Dim>,
+    LhsG::Dim: Dimension + DimMax<RhsG::Dim>,
+{
+    fn backward(&self) {
+        fit_gradient_shape(&self.buffer, self.gradient().raw_dim());
+        let gradient = self.gradient();
+        let mut buffer = expect_tensor_mut(&self.buffer);
+        let (left_data, right_data) = (self.left_data.data(), self.right_data.data());
+
+        Zip::from(&mut *buffer)
+            .and(&*gradient)
+            .and_broadcast(&*left_data)
+            .and_broadcast(&*right_data)
+            .for_each(|d, g, &l, &r| *d = g * left_share(l, r));
+        let reduced = reduce(self.left_grad.gradient().raw_dim(), &buffer);
+        push_gradient(&*self.left_grad, &reduced);
+
+        Zip::from(&mut *buffer)
+            .and(&*gradient)
+            .and_broadcast(&*left_data)
+            .and_broadcast(&*right_data)
+            .for_each(|d, g, &l, &r| *d = g * left_share(r, l));
+        let reduced = reduce(self.right_grad.gradient().raw_dim(), &buffer);
+        push_gradient(&*self.right_grad, &reduced);
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape.clone()));
+    }
+}
+
+impl<LhsD: ?Sized, LhsG: ?Sized, RhsD: ?Sized, RhsG: ?Sized> Debug
+    for MaximumBackward<LhsD, LhsG, RhsD, RhsG>
+where
+    LhsD: Data,
+    RhsD: Data,
+    LhsG: Gradient,
+    RhsG: Gradient,
+    LhsD::Dim: Dimension + DimMax<RhsD::Dim>,
+    LhsG::Dim: Dimension + DimMax<RhsG::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MaximumBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<LhsD: ?Sized, LhsG: ?Sized, RhsD: ?Sized, RhsG: ?Sized> Display
+    for MaximumBackward<LhsD, LhsG, RhsD, RhsG>
+where
+    LhsD: Data,
+    RhsD: Data,
+    LhsG: Gradient,
+    RhsG: Gradient,
+    LhsD::Dim: Dimension + DimMax<RhsD::Dim>,
+    LhsG::Dim: Dimension + DimMax<RhsG::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ MaximumBackwardUnary ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct MaximumBackwardUnary<T: ?Sized, U: ?Sized, V: ?Sized>
+where
+    T: Data,
+    U: Gradient,
+    V: Data,
+    U::Dim: Dimension + DimMax<V::Dim>,
+{
+    gradient: RefCell<Option<BroadTensor<U::Dim, V::Dim>>>,
+    shape: Broadcasted<U::Dim, V::Dim>,
+    overwrite: Cell<bool>,
+    buffer: RefCell<Option<BroadTensor<U::Dim, V::Dim>>>,
+    diff_data: Rc<T>,
+    diff_operand: Rc<U>,
+    no_diff_operand: Rc<V>,
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> MaximumBackwardUnary<T, U, V>
+where
+    T: Data,
+    U: Gradient,
+    V: Data,
+    U::Dim: Dimension + DimMax<V::Dim>,
+{
+    pub fn new(diff_data: Rc<T>, diff_operand: Rc<U>, no_diff_operand: Rc<V>) -> Self {
+        let gradient = cobroadcasted_zeros(&diff_operand.gradient(), &no_diff_operand.data());
+        let shape = gradient.raw_dim();
+
+        Self {
+            gradient: RefCell::new(Some(gradient)),
+            shape: shape.clone(),
+            overwrite: Cell::new(true),
+            buffer: RefCell::new(Some(Tensor::zeros(shape))),
+            diff_data,
+            diff_operand,
+            no_diff_operand,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Gradient for MaximumBackwardUnary<T, U, V>
+where
+    T: Data,
+    U: Gradient,
+    V: Data,
+    U::Dim: Dimension + DimMax<V::Dim>,
+{
+    type Dim = Broadcasted<U::Dim, V::Dim>;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Overwrite for MaximumBackwardUnary<T, U, V>
+where
+    T: Data,
+    U: Gradient,
+    V: Data,
+    U::Dim: Dimension + DimMax<V::Dim>,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Backward for MaximumBackwardUnary<T, U, V>
+where
+    T: Data,
+    U: Gradient,
+    V: Data,
+    U::Dim: Dimension + DimMax<V::Dim>,
+{
+    fn backward(&self) {
+        fit_gradient_shape(&self.buffer, self.gradient().raw_dim());
+        let gradient = self.gradient();
+        let mut buffer = expect_tensor_mut(&self.buffer);
+
+        Zip::from(&mut *buffer)
+            .and(&*gradient)
+            .and_broadcast(&*self.diff_data.data())
+            .and_broadcast(&*self.no_diff_operand.data())
+            .for_each(|d, g, &v, &w| *d = g * left_share(v, w));
+        let reduced = reduce(self.diff_operand.gradient().raw_dim(), &buffer);
+        push_gradient(&*self.diff_operand, &reduced);
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape.clone()));
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Debug for MaximumBackwardUnary<T, U, V>
+where
+    T: Data,
+    U: Gradient,
+    V: Data,
+    U::Dim: Dimension + DimMax<V::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MaximumBackwardUnary")
+            .field("gradient", &self.gradient.borrow())
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Display for MaximumBackwardUnary<T, U, V>
+where
+    T: Data,
+    U: Gradient,
+    V: Data,
+    U::Dim: Dimension + DimMax<V::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,383 @@
+use super::{
+    assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Cache, Data,
+    Forward, Gradient, Minimum, MinimumBackward, MinimumBackwardUnary, Overwrite, Tensor,
+};
+
+mod forward {
+    use super::{
+        assert_almost_equals, new_input, new_tensor, Cache, Data, Forward, Minimum, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let left = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+        let right = new_input((3, 3), vec![0.; 9]);
+        let node = Minimum::new(left, right);
+
+        assert_eq!(*node.data(), Tensor::from_elem((3, 3), 0.));
+        assert_eq!(*node.data_mut(), Tensor::from_elem((3, 3), 0.));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let left = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+        let right = new_input((3, 3), vec![0.; 9]);
+        let node = Minimum::new(left, right);
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward() {
+        let left = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+        let right = new_input((3, 3), vec![0.; 9]);
+        let node = Minimum::new(left, right.clone());
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((3, 3), vec![-4., -3., -2., -1., 0., 0., 0., 0., 0.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *right.data_mut() = new_tensor((3, 3), vec![2.; 9]);
+        assert_almost_equals(&*right.data(), &new_tensor((3, 3), vec![2.; 9]));
+
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((3, 3), vec![-4., -3., -2., -1., 0., 0., 0., 0., 0.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 2., 2.]),
+        );
+    }
+
+    #[test]
+    fn broadcast_forward() {
+        let left = new_input((1, 3), vec![-1., 0., 1.]);
+        let right = new_input((2, 1), vec![-0.5, 0.5]);
+        let node = Minimum::new(left, right);
+
+        assert_eq!(*node.data(), Tensor::from_elem((2, 3), 0.));
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 3), vec![-1., -0.5, -0.5, -1., 0., 0.5]),
+        );
+    }
+
+    #[test]
+    fn nan_forward() {
+        let left = new_input(3, vec![f32::NAN, 1., f32::NAN]);
+        let right = new_input(3, vec![1., f32::NAN, f32::NAN]);
+        let node = Minimum::new(left, right);
+
+        node.forward();
+        assert!(node.data().iter().all(|el| el.is_nan()));
+    }
+
+    #[test]
+    fn debug() {
+        let left = new_input(1, vec![0.]);
+        let right = new_input(1, vec![0.]);
+        let node = Minimum::new(left, right);
+
+        let output = "Minimum { data: [0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let left = new_input(1, vec![0.]);
+        let right = new_input(1, vec![0.]);
+        let node = Minimum::new(left, right);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Gradient,
+        MinimumBackward, MinimumBackwardUnary, Overwrite, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let node = MinimumBackward::new(
+            new_input((3, 3), vec![3.; 9]),
+            new_backward_input((3, 3), vec![0.; 9]),
+            new_input((3, 3), vec![5.; 9]),
+            new_backward_input((3, 3), vec![0.; 9]),
+        );
+
+        assert_eq!(*node.gradient(), Tensor::from_elem((3, 3), 0.));
+        assert_eq!(*node.gradient_mut(), Tensor::from_elem((3, 3), 0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn computation_state_transition() {
+        let lhs = new_backward_input((3, 3), vec![0.; 9]);
+        let rhs = new_backward_input((3, 3), vec![0.; 9]);
+        let node = MinimumBackward::new(
+            new_input((3, 3), vec![3.; 9]),
+            lhs.clone(),
+            new_input((3, 3), vec![5.; 9]),
+            rhs.clone(),
+        );
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!lhs.can_overwrite());
+        assert!(!rhs.can_overwrite());
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!lhs.can_overwrite());
+        assert!(!rhs.can_overwrite());
+
+        lhs.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(lhs.can_overwrite());
+        assert!(!rhs.can_overwrite());
+
+        rhs.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(lhs.can_overwrite());
+        assert!(rhs.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(lhs.can_overwrite());
+        assert!(rhs.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!lhs.can_overwrite());
+        assert!(!rhs.can_overwrite());
+    }
+
+    #[test]
+    fn backward() {
+        let lhs = new_backward_input((3, 3), vec![0.; 9]);
+        let rhs = new_backward_input((3, 3), vec![0.; 9]);
+        let node = MinimumBackward::new(
+            new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]),
+            lhs.clone(),
+            new_input((3, 3), vec![0.; 9]),
+            rhs.clone(),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((3, 3), vec![1.; 9]);
+        assert_almost_equals(&*node.gradient(), &new_tensor((3, 3), vec![1.; 9]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*lhs.gradient(),
+            &new_tensor((3, 3), vec![1., 1., 1., 1., 0.5, 0., 0., 0., 0.]),
+        );
+        assert_almost_equals(
+            &*rhs.gradient(),
+            &new_tensor((3, 3), vec![0., 0., 0., 0., 0.5, 1., 1., 1., 1.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*lhs.gradient(),
+            &new_tensor((3, 3), vec![2., 2., 2., 2., 1., 0., 0., 0., 0.]),
+        );
+        assert_almost_equals(
+            &*rhs.gradient(),
+            &new_tensor((3, 3), vec![0., 0., 0., 0., 1., 2., 2., 2., 2.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        lhs.set_overwrite(true);
+        rhs.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*lhs.gradient(),
+            &new_tensor((3, 3), vec![1., 1., 1., 1., 0.5, 0., 0., 0., 0.]),
+        );
+        assert_almost_equals(
+            &*rhs.gradient(),
+            &new_tensor((3, 3), vec![0., 0., 0., 0., 0.5, 1., 1., 1., 1.]),
+        );
+    }
+
+    #[test]
+    fn backward_broadcast() {
+        let lhs = new_backward_input(3, vec![0.; 3]);
+        let rhs = new_backward_input((2, 1), vec![0.; 2]);
+        let node = MinimumBackward::new(
+            new_input(3, vec![-1., 0., 1.]),
+            lhs.clone(),
+            new_input((2, 1), vec![-0.5, 0.5]),
+            rhs.clone(),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((2, 3), vec![1.; 6]);
+        assert_almost_equals(&*node.gradient(), &new_tensor((2, 3), vec![1.; 6]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(&*lhs.gradient(), &new_tensor(3, vec![2., 1., 0.]));
+        assert_almost_equals(&*rhs.gradient(), &new_tensor((2, 1), vec![2., 1.]));
+    }
+
+    #[test]
+    fn backward_unary() {
+        let diff = new_backward_input((3, 3), vec![0.; 9]);
+        let node = MinimumBackwardUnary::new(
+            new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]),
+            diff.clone(),
+            new_input((3, 3), vec![0.; 9]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((3, 3), vec![1.; 9]);
+        assert_almost_equals(&*node.gradient(), &new_tensor((3, 3), vec![1.; 9]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((3, 3), vec![1., 1., 1., 1., 0.5, 0., 0., 0., 0.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((3, 3), vec![2., 2., 2., 2., 1., 0., 0., 0., 0.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((3, 3), vec![1., 1., 1., 1., 0.5, 0., 0., 0., 0.]),
+        );
+    }
+
+    #[test]
+    fn backward_unary_broadcast() {
+        let diff = new_backward_input(3, vec![0.; 3]);
+        let node = MinimumBackwardUnary::new(
+            new_input(3, vec![-1., 0., 1.]),
+            diff.clone(),
+            new_input((2, 1), vec![-0.5, 0.5]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((2, 3), vec![1.; 6]);
+        assert_almost_equals(&*node.gradient(), &new_tensor((2, 3), vec![1.; 6]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(&*diff.gradient(), &new_tensor(3, vec![2., 1., 0.]));
+    }
+
+    #[test]
+    fn no_grad() {
+        // MinimumBackward
+        let node = MinimumBackward::new(
+            new_input((3, 3), vec![0.; 9]),
+            new_backward_input((3, 3), vec![0.; 9]),
+            new_input((3, 3), vec![0.; 9]),
+            new_backward_input((3, 3), vec![0.; 9]),
+        );
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+
+        // MinimumBackwardUnary
+        let node = MinimumBackwardUnary::new(
+            new_input((3, 3), vec![0.; 9]),
+            new_backward_input((3, 3), vec![0.; 9]),
+            new_input((3, 3), vec![0.; 9]),
+        );
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+    }
+
+    #[test]
+    fn debug() {
+        let node = MinimumBackward::new(
+            new_input(1, vec![0.]),
+            new_backward_input(1, vec![0.]),
+            new_input(1, vec![0.]),
+            new_backward_input(1, vec![0.]),
+        );
+
+        let output = "MinimumBackward { gradient: Some([0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1), overwrite: true }";
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn debug_unary() {
+        let node = MinimumBackwardUnary::new(
+            new_input(1, vec![0.]),
+            new_backward_input(1, vec![0.]),
+            new_input(1, vec![0.]),
+        );
+
+        let output = "MinimumBackwardUnary { gradient: Some([0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1), overwrite: true }";
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = MinimumBackward::new(
+            new_input(1, vec![0.]),
+            new_backward_input(1, vec![0.]),
+            new_input(1, vec![0.]),
+            new_backward_input(1, vec![0.]),
+        );
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+
+    #[test]
+    fn display_unary() {
+        let node = MinimumBackwardUnary::new(
+            new_input(1, vec![0.]),
+            new_backward_input(1, vec![0.]),
+            new_input(1, vec![0.]),
+        );
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+}
@@ -1,24 +1,47 @@
 mod arithmetic;
+mod comparison;
 mod concatenate;
 mod convolution;
+mod embedding;
+mod gather;
 mod linalg;
 mod loss;
+mod masked_fill;
+mod masked_pool;
+mod maximum;
+mod minimum;
+mod scatter;
 mod stack;
+mod tensor_power;
+mod weighted_bincount;
 
 use super::{
-    cobroadcasted_zeros, expect_tensor, expect_tensor_mut, push_gradient, push_mat_mat_gradient,
-    push_mat_vec_gradient, push_vec_mat_gradient, push_vec_vec_gradient, reduce, Backward,
-    BroadTensor, Broadcasted, Cache, Data, DotDim, Forward, Gradient, Overwrite, Tensor,
+    cobroadcasted_shape, cobroadcasted_zeros, expect_tensor, expect_tensor_mut, fit_gradient_shape,
+    fit_shape, push_gradient, push_mat_mat_gradient, push_mat_vec_gradient, push_vec_mat_gradient,
+    push_vec_vec_gradient, reduce, Backward, BroadTensor, Broadcasted, Cache, Data, DotDim,
+    Forward, Gradient, IndexData, Overwrite, Tensor,
 };
 
 #[cfg(test)]
-use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
+use super::{
+    assert_almost_equals, new_backward_input, new_index_input, new_input, new_tensor, IndexTensor,
+};
 
 pub(crate) use arithmetic::*;
+pub(crate) use comparison::*;
 pub(crate) use concatenate::*;
+pub(crate) use embedding::*;
+pub(crate) use gather::*;
 pub(crate) use linalg::*;
 pub(crate) use loss::*;
+pub(crate) use masked_fill::*;
+pub(crate) use masked_pool::*;
+pub(crate) use maximum::*;
+pub(crate) use minimum::*;
+pub(crate) use scatter::*;
 pub(crate) use stack::*;
+pub(crate) use tensor_power::*;
+pub(crate) use weighted_bincount::*;
 
 pub use convolution::{
     Constant, Convolve, ConvolveWithGroups, PaddingMode, Reflective, Replicative, Zero,
@@ -0,0 +1,388 @@
+#[cfg(test)]
+use super::{
+    assert_almost_equals, new_backward_input, new_index_input, new_input, new_tensor, IndexTensor,
+};
+use super::{
+    expect_tensor, expect_tensor_mut, fit_shape, Backward, Cache, Data, Forward, Gradient,
+    IndexData, Overwrite, Tensor,
+};
+use ndarray::{Array, Axis, Dimension, Ix1, Zip};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// The reduction applied to the elements of each segment by a [`Scatter`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SegmentReduction {
+    Add,
+    Mean,
+    Max,
+}
+
+/// Returns the segment `index` as a position along the reduced axis.
+///
+/// # Arguments
+///
+/// * `index` - segment index.
+///
+/// * `num_segments` - number of segments.
+fn segment(index: i64, num_segments: usize) -> usize {
+    assert!(
+        index >= 0 && (index as usize) < num_segments,
+        "error: segment index {} is out of range for {} segments.",
+        index,
+        num_segments
+    );
+    index as usize
+}
+
+/// Returns the shape of the result of a scatter along `axis`, checking that there is one segment
+/// index for each element of the axis.
+///
+/// # Arguments
+///
+/// * `shape` - shape of the operand.
+///
+/// * `indices` - number of segment indices.
+///
+/// * `axis` - reduced axis.
+///
+/// * `num_segments` - number of segments.
+fn scattered<D: Dimension>(mut shape: D, indices: usize, axis: usize, num_segments: usize) -> D {
+    assert_eq!(
+        indices, shape[axis],
+        "error: {} segment indices were given for axis {} of length {}.",
+        indices, axis, shape[axis]
+    );
+    shape[axis] = num_segments;
+    shape
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Scatter ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct Scatter<T: ?Sized, I: ?Sized>
+where
+    T: Data,
+    I: IndexData<Dim = Ix1>,
+{
+    operand: Rc<T>,
+    indices: Rc<I>,
+    axis: usize,
+    reduction: SegmentReduction,
+    counts: RefCell<Vec<usize>>,
+    sources: RefCell<Array<usize, T::Dim>>,
+    data: RefCell<Tensor<T::Dim>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized, I: ?Sized> Scatter<T, I>
+where
+    T: Data,
+    I: IndexData<Dim = Ix1>,
+{
+    pub fn new(
+        operand: Rc<T>,
+        indices: Rc<I>,
+        axis: usize,
+        num_segments: usize,
+        reduction: SegmentReduction,
+    ) -> Self {
+        let shape = scattered(
+            operand.data().raw_dim(),
+            indices.data().len(),
+            axis,
+            num_segments,
+        );
+
+        Self {
+            operand,
+            indices,
+            axis,
+            reduction,
+            counts: RefCell::new(vec![0; num_segments]),
+            sources: RefCell::new(Array::from_elem(shape.clone(), usize::MAX)),
+            data: RefCell::new(Tensor::zeros(shape)),
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized, I: ?Sized> Cache for Scatter<T, I>
+where
+    T: Data,
+    I: IndexData<Dim = Ix1>,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized, I: ?Sized> Forward for Scatter<T, I>
+where
+    T: Data,
+    I: IndexData<Dim = Ix1>,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let (operand, indices) = (self.operand.data(), self.indices.data());
+        let shape = scattered(
+            operand.raw_dim(),
+            indices.len(),
+            self.axis,
+            self.counts.borrow().len(),
+        );
+        fit_shape(&self.data, shape.clone());
+        fit_shape(&self.sources, shape);
+        let (mut data, mut counts, mut sources) = (
+            self.data.borrow_mut(),
+            self.counts.borrow_mut(),
+            self.sources.borrow_mut(),
+        );
+        data.fill(0.);
+        counts.iter_mut().for_each(|count| *count = 0);
+        sources.fill(usize::MAX);
+
+        let axis = Axis(self.axis);
+        let operand = operand.view().into_dyn();
+        let mut data = data.view_mut().into_dyn();
+        let mut sources = sources.view_mut().into_dyn();
+        for (source, &index) in indices.iter().enumerate() {
+            let segment = segment(index, counts.len());
+            counts[segment] += 1;
+
+            let elements = operand.index_axis(axis, source);
+            let mut reduced = data.index_axis_mut(axis, segment);
+            match self.reduction {
+                SegmentReduction::Add | SegmentReduction::Mean => reduced += &elements,
+                SegmentReduction::Max => Zip::from(&mut reduced)
+                    .and(&mut sources.index_axis_mut(axis, segment))
+                    .and(&elements)
+                    .for_each(|reduced_el, argmax, &el| {
+                        if *argmax == usize::MAX || el > *reduced_el {
+                            *reduced_el = el;
+                            *argmax = source;
+                        }
+                    }),
+            }
+        }
+
+        if self.reduction == SegmentReduction::Mean {
+            counts
+                .iter()
+                .enumerate()
+                .filter(|(_, &count)| count > 0)
+                .for_each(|(segment, &count)| {
+                    data.index_axis_mut(axis, segment)
+                        .mapv_inplace(|el| el / count as f32)
+                });
+        }
+    }
+}
+
+impl<T: ?Sized, I: ?Sized> Data for Scatter<T, I>
+where
+    T: Data,
+    I: IndexData<Dim = Ix1>,
+{
+    type Dim = T::Dim;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized, I: ?Sized> Debug for Scatter<T, I>
+where
+    T: Data,
+    I: IndexData<Dim = Ix1>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Scatter")
+            .field("data", &self.data.borrow())
+            .field("axis", &self.axis)
+            .field("reduction", &self.reduction)
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, I: ?Sized> Display for Scatter<T, I>
+where
+    T: Data,
+    I: IndexData<Dim = Ix1>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ ScatterBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct ScatterBackward<T: ?Sized, U: ?Sized, I: ?Sized>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    I: IndexData<Dim = Ix1>,
+{
+    gradient: RefCell<Option<Tensor<T::Dim>>>,
+    shape: T::Dim,
+    overwrite: Cell<bool>,
+    diff_operand: Rc<T>,
+    scatter: Rc<Scatter<U, I>>,
+}
+
+impl<T: ?Sized, U: ?Sized, I: ?Sized> ScatterBackward<T, U, I>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    I: IndexData<Dim = Ix1>,
+{
+    pub fn new(diff_operand: Rc<T>, scatter: Rc<Scatter<U, I>>) -> Self {
+        let shape = scatter.data().raw_dim();
+
+        Self {
+            gradient: RefCell::new(Some(Tensor::zeros(shape.clone()))),
+            shape,
+            overwrite: Cell::new(true),
+            diff_operand,
+            scatter,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, I: ?Sized> Gradient for ScatterBackward<T, U, I>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    I: IndexData<Dim = Ix1>,
+{
+    type Dim = T::Dim;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, I: ?Sized> Overwrite for ScatterBackward<T, U, I>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    I: IndexData<Dim = Ix1>,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, I: ?Sized> Backward for ScatterBackward<T, U, I>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    I: IndexData<Dim = Ix1>,
+{
+    fn backward(&self) {
+        let mut operand_grad = self.diff_operand.gradient_mut();
+        let gradient = self.gradient();
+        let (indices, counts, sources) = (
+            self.scatter.indices.data(),
+            self.scatter.counts.borrow(),
+            self.scatter.sources.borrow(),
+        );
+
+        // Under the max reduction only the maxima of the segments receive a gradient.
+        if self.diff_operand.can_overwrite() {
+            operand_grad.fill(0.);
+            self.diff_operand.set_overwrite(false);
+        }
+
+        let axis = Axis(self.scatter.axis);
+        let gradient = gradient.view().into_dyn();
+        let sources = sources.view().into_dyn();
+        let mut operand_grad = operand_grad.view_mut().into_dyn();
+        for (source, &index) in indices.iter().enumerate() {
+            let segment = segment(index, counts.len());
+            let grad = gradient.index_axis(axis, segment);
+            let mut operand_grad = operand_grad.index_axis_mut(axis, source);
+            match self.scatter.reduction {
+                SegmentReduction::Add => operand_grad += &grad,
+                SegmentReduction::Mean => {
+                    operand_grad.scaled_add(1. / counts[segment] as f32, &grad)
+                }
+                SegmentReduction::Max => Zip::from(&mut operand_grad)
+                    .and(&grad)
+                    .and(&sources.index_axis(axis, segment))
+                    .for_each(|operand_grad_el, &grad_el, &argmax| {
+                        if argmax == source {
+                            *operand_grad_el += grad_el;
+                        }
+                    }),
+            }
+        }
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape.clone()));
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, I: ?Sized> Debug for ScatterBackward<T, U, I>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    I: IndexData<Dim = Ix1>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScatterBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, I: ?Sized> Display for ScatterBackward<T, U, I>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    I: IndexData<Dim = Ix1>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,355 @@
+use super::{
+    assert_almost_equals, new_backward_input, new_index_input, new_input, new_tensor, Backward,
+    Cache, Data, Forward, Gradient, IndexData, IndexTensor, Overwrite, Scatter, ScatterBackward,
+    SegmentReduction, Tensor,
+};
+use crate::variable::{IndexInput, Input, InputBackward};
+use ndarray::{Ix1, Ix2};
+use std::rc::Rc;
+
+// Four rows grouped into three segments, the middle one is empty.
+const OPERAND: [f32; 8] = [1., 2., 3., -1., 5., 0., -2., 4.];
+const INDICES: [i64; 4] = [0, 2, 0, 2];
+
+fn new_scatter(reduction: SegmentReduction) -> Scatter<Input<Ix2>, IndexInput<Ix1>> {
+    Scatter::new(
+        new_input((4, 2), OPERAND.to_vec()),
+        new_index_input(4, INDICES.to_vec()),
+        0,
+        3,
+        reduction,
+    )
+}
+
+type Node = ScatterBackward<InputBackward<Ix2>, Input<Ix2>, IndexInput<Ix1>>;
+
+fn new_scatter_backward(reduction: SegmentReduction) -> (Node, Rc<InputBackward<Ix2>>) {
+    let diff = new_backward_input((4, 2), vec![0.; 8]);
+    let forward = Rc::new(new_scatter(reduction));
+    forward.forward();
+
+    (ScatterBackward::new(diff.clone(), forward), diff)
+}
+
+mod forward {
+    use super::{
+        assert_almost_equals, new_index_input, new_input, new_scatter, new_tensor, Cache, Data,
+        Forward, IndexData, IndexTensor, Scatter, SegmentReduction, Tensor, INDICES, OPERAND,
+    };
+
+    #[test]
+    fn creation() {
+        let node = new_scatter(SegmentReduction::Add);
+
+        assert_eq!(*node.data(), Tensor::from_elem((3, 2), 0.));
+        assert_eq!(*node.data_mut(), Tensor::from_elem((3, 2), 0.));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    #[should_panic(expected = "error: 3 segment indices were given for axis 0 of length 4.")]
+    fn creation_fail() {
+        Scatter::new(
+            new_input((4, 2), OPERAND.to_vec()),
+            new_index_input(3, vec![0, 1, 2]),
+            0,
+            3,
+            SegmentReduction::Add,
+        );
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let node = new_scatter(SegmentReduction::Add);
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward_add() {
+        let indices = new_index_input(4, vec![0, 2, 0, 2]);
+        let node = Scatter::new(
+            new_input((4, 2), OPERAND.to_vec()),
+            indices.clone(),
+            0,
+            3,
+            SegmentReduction::Add,
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((3, 2), vec![6., 2., 0., 0., 1., 3.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *indices.data_mut() = IndexTensor::from_elem(4, 1);
+
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((3, 2), vec![6., 2., 0., 0., 1., 3.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((3, 2), vec![0., 0., 7., 5., 0., 0.]),
+        );
+    }
+
+    #[test]
+    fn forward_mean() {
+        let node = new_scatter(SegmentReduction::Mean);
+
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((3, 2), vec![3., 1., 0., 0., 0.5, 1.5]),
+        );
+    }
+
+    #[test]
+    fn forward_max() {
+        let node = new_scatter(SegmentReduction::Max);
+
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((3, 2), vec![5., 2., 0., 0., 3., 4.]),
+        );
+    }
+
+    #[test]
+    fn forward_columns() {
+        let node = Scatter::new(
+            new_input((2, 3), vec![1., 2., 3., 4., 5., 6.]),
+            new_index_input(3, vec![1, 0, 1]),
+            1,
+            2,
+            SegmentReduction::Add,
+        );
+
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((2, 2), vec![2., 4., 5., 10.]));
+    }
+
+    #[test]
+    fn forward_shape_change() {
+        let (operand, indices) = (
+            new_input((4, 2), OPERAND.to_vec()),
+            new_index_input(4, INDICES.to_vec()),
+        );
+        let node = Scatter::new(
+            operand.clone(),
+            indices.clone(),
+            0,
+            3,
+            SegmentReduction::Max,
+        );
+
+        // The number of segments stays the same, the length of the reduced axis follows the
+        // operand.
+        *operand.data_mut() = new_tensor((2, 3), vec![1., 2., 3., 4., 5., 6.]);
+        *indices.data_mut() = IndexTensor::from_vec(vec![1, 1]);
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((3, 3), vec![0., 0., 0., 4., 5., 6., 0., 0., 0.]),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "error: 4 segment indices were given for axis 0 of length 2.")]
+    fn forward_shape_change_fail() {
+        let operand = new_input((4, 2), OPERAND.to_vec());
+        let node = Scatter::new(
+            operand.clone(),
+            new_index_input(4, INDICES.to_vec()),
+            0,
+            3,
+            SegmentReduction::Add,
+        );
+
+        *operand.data_mut() = Tensor::zeros((2, 2));
+        node.forward();
+    }
+
+    #[test]
+    #[should_panic(expected = "error: segment index 3 is out of range for 3 segments.")]
+    fn forward_out_of_range() {
+        let node = Scatter::new(
+            new_input((4, 2), OPERAND.to_vec()),
+            new_index_input(4, vec![0, 3, 0, 2]),
+            0,
+            3,
+            SegmentReduction::Add,
+        );
+
+        node.forward();
+    }
+
+    #[test]
+    fn debug() {
+        let node = Scatter::new(
+            new_input(2, vec![0., 1.]),
+            new_index_input(2, vec![0, 0]),
+            0,
+            1,
+            SegmentReduction::Max,
+        );
+
+        let output = "Scatter { data: [0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1, axis: 0, reduction: Max, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = new_scatter(SegmentReduction::Add);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        assert_almost_equals, new_scatter_backward, new_tensor, Backward, Gradient, Overwrite,
+        SegmentReduction, Tensor,
+    };
+
+    const GRADIENT: [f32; 6] = [1., 2., 3., 4., 5., 6.];
+
+    #[test]
+    fn creation() {
+        let (node, _) = new_scatter_backward(SegmentReduction::Add);
+
+        assert_eq!(*node.gradient(), Tensor::from_elem((3, 2), 0.));
+        assert_eq!(*node.gradient_mut(), Tensor::from_elem((3, 2), 0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn computation_state_transition() {
+        let (node, diff) = new_scatter_backward(SegmentReduction::Add);
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        diff.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!diff.can_overwrite());
+    }
+
+    #[test]
+    fn backward_add() {
+        let (node, diff) = new_scatter_backward(SegmentReduction::Add);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((3, 2), GRADIENT.to_vec());
+        assert_almost_equals(&*node.gradient(), &new_tensor((3, 2), GRADIENT.to_vec()));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((4, 2), vec![1., 2., 5., 6., 1., 2., 5., 6.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((4, 2), vec![2., 4., 10., 12., 2., 4., 10., 12.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((4, 2), vec![1., 2., 5., 6., 1., 2., 5., 6.]),
+        );
+    }
+
+    #[test]
+    fn backward_mean() {
+        let (node, diff) = new_scatter_backward(SegmentReduction::Mean);
+
+        *node.gradient_mut() = new_tensor((3, 2), GRADIENT.to_vec());
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((4, 2), vec![0.5, 1., 2.5, 3., 0.5, 1., 2.5, 3.]),
+        );
+    }
+
+    #[test]
+    fn backward_max() {
+        let (node, diff) = new_scatter_backward(SegmentReduction::Max);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((3, 2), GRADIENT.to_vec());
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((4, 2), vec![0., 2., 5., 0., 1., 0., 0., 6.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((4, 2), vec![0., 2., 5., 0., 1., 0., 0., 6.]),
+        );
+    }
+
+    #[test]
+    fn no_grad() {
+        let (node, _) = new_scatter_backward(SegmentReduction::Add);
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+    }
+
+    #[test]
+    fn debug() {
+        let (node, _) = new_scatter_backward(SegmentReduction::Add);
+
+        let output = "ScatterBackward { gradient: Some([[0.0, 0.0],\n [0.0, 0.0],\n [0.0, 0.0]], shape=[3, 2], strides=[2, 1], layout=Cc (0x5), const ndim=2), overwrite: true }";
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let (node, _) = new_scatter_backward(SegmentReduction::Add);
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+}
@@ -1,8 +1,8 @@
 #[cfg(test)]
 use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
 use super::{
-    expect_tensor, expect_tensor_mut, push_gradient, Backward, Cache, Data, Forward, Gradient,
-    Overwrite, Tensor,
+    expect_tensor, expect_tensor_mut, fit_shape, push_gradient, Backward, Cache, Data, Forward,
+    Gradient, Overwrite, Tensor,
 };
 use ndarray::{stack, Axis, Dimension, RemoveAxis, Zip};
 use std::{
@@ -99,6 +99,9 @@ where
         }
 
         self.computed.set(true);
+        let mut shape = self.left.data().raw_dim().insert_axis(Axis(self.axis));
+        shape[self.axis] = 2;
+        fit_shape(&self.data, shape);
         let lhs_data = self.left.data();
         let rhs_data = self.right.data();
         let mut data = self.data.borrow_mut();
@@ -147,7 +150,7 @@ where
     Lhs::Dim: RemoveAxis,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(f, "{}", &self.data.borrow())
+        crate::print::fmt_tensor(&self.data.borrow(), f)
     }
 }
 
@@ -284,7 +287,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", &gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
@@ -404,7 +407,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", &gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
@@ -524,7 +527,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", &gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
@@ -0,0 +1,419 @@
+use super::{
+    assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Cache, Data,
+    Forward, Gradient, Overwrite, Tensor, TensorPower, TensorPowerBackward,
+    TensorPowerBackwardLeft, TensorPowerBackwardRight,
+};
+use std::f32::consts::{FRAC_1_SQRT_2, SQRT_2};
+
+const BASE: [f32; 6] = [1., 2., 3., 4., 0., 0.5];
+const EXPONENT: [f32; 6] = [2., 0.5, -1., 0., 2., 3.];
+
+mod forward {
+    use super::{
+        assert_almost_equals, new_input, new_tensor, Cache, Data, Forward, Tensor, TensorPower,
+        BASE, EXPONENT, SQRT_2,
+    };
+
+    #[test]
+    fn creation() {
+        let node = TensorPower::new(
+            new_input((2, 3), BASE.to_vec()),
+            new_input((2, 3), EXPONENT.to_vec()),
+        );
+
+        assert_eq!(*node.data(), Tensor::from_elem((2, 3), 0.));
+        assert_eq!(*node.data_mut(), Tensor::from_elem((2, 3), 0.));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let node = TensorPower::new(
+            new_input((2, 3), BASE.to_vec()),
+            new_input((2, 3), EXPONENT.to_vec()),
+        );
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward() {
+        let exponent = new_input((2, 3), EXPONENT.to_vec());
+        let node = TensorPower::new(new_input((2, 3), BASE.to_vec()), exponent.clone());
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 3), vec![1., SQRT_2, 0.333333, 1., 0., 0.125]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *exponent.data_mut() = new_tensor((2, 3), vec![1.; 6]);
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 3), vec![1., SQRT_2, 0.333333, 1., 0., 0.125]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((2, 3), BASE.to_vec()));
+    }
+
+    #[test]
+    fn broadcast_forward() {
+        let node = TensorPower::new(
+            new_input((2, 3), BASE.to_vec()),
+            new_input(3, vec![2., 0.5, -1.]),
+        );
+
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 3), vec![1., SQRT_2, 0.333333, 16., 0., 2.]),
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let node = TensorPower::new(new_input(1, vec![0.]), new_input(1, vec![0.]));
+
+        let output = "TensorPower { data: [0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = TensorPower::new(new_input(1, vec![0.]), new_input(1, vec![0.]));
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Gradient,
+        Overwrite, Tensor, TensorPowerBackward, TensorPowerBackwardLeft, TensorPowerBackwardRight,
+        BASE, EXPONENT, FRAC_1_SQRT_2,
+    };
+
+    #[test]
+    fn creation() {
+        let node = TensorPowerBackward::new(
+            new_input((2, 3), BASE.to_vec()),
+            new_backward_input((2, 3), vec![0.; 6]),
+            new_input((2, 3), EXPONENT.to_vec()),
+            new_backward_input((2, 3), vec![0.; 6]),
+        );
+
+        assert_eq!(*node.gradient(), Tensor::from_elem((2, 3), 0.));
+        assert_eq!(*node.gradient_mut(), Tensor::from_elem((2, 3), 0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn computation_state_transition() {
+        let lhs = new_backward_input((2, 3), vec![0.; 6]);
+        let rhs = new_backward_input((2, 3), vec![0.; 6]);
+        let node = TensorPowerBackward::new(
+            new_input((2, 3), BASE.to_vec()),
+            lhs.clone(),
+            new_input((2, 3), EXPONENT.to_vec()),
+            rhs.clone(),
+        );
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!lhs.can_overwrite());
+        assert!(!rhs.can_overwrite());
+
+        lhs.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(lhs.can_overwrite());
+        assert!(!rhs.can_overwrite());
+
+        rhs.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(lhs.can_overwrite());
+        assert!(rhs.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(lhs.can_overwrite());
+        assert!(rhs.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!lhs.can_overwrite());
+        assert!(!rhs.can_overwrite());
+    }
+
+    #[test]
+    fn backward() {
+        let lhs = new_backward_input((2, 3), vec![0.; 6]);
+        let rhs = new_backward_input((2, 3), vec![0.; 6]);
+        let node = TensorPowerBackward::new(
+            new_input((2, 3), BASE.to_vec()),
+            lhs.clone(),
+            new_input((2, 3), EXPONENT.to_vec()),
+            rhs.clone(),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((2, 3), vec![1.; 6]);
+        assert_almost_equals(&*node.gradient(), &new_tensor((2, 3), vec![1.; 6]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*lhs.gradient(),
+            &new_tensor((2, 3), vec![2., 0.353553, -0.111111, 0., 0., 0.75]),
+        );
+        assert_almost_equals(
+            &*rhs.gradient(),
+            &new_tensor(
+                (2, 3),
+                vec![0., 0.980258, 0.366204, 1.386294, 0., -0.086643],
+            ),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*lhs.gradient(),
+            &new_tensor((2, 3), vec![4., FRAC_1_SQRT_2, -0.222222, 0., 0., 1.5]),
+        );
+        assert_almost_equals(
+            &*rhs.gradient(),
+            &new_tensor(
+                (2, 3),
+                vec![0., 1.960516, 0.732408, 2.772588, 0., -0.173286],
+            ),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        lhs.set_overwrite(true);
+        rhs.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*lhs.gradient(),
+            &new_tensor((2, 3), vec![2., 0.353553, -0.111111, 0., 0., 0.75]),
+        );
+        assert_almost_equals(
+            &*rhs.gradient(),
+            &new_tensor(
+                (2, 3),
+                vec![0., 0.980258, 0.366204, 1.386294, 0., -0.086643],
+            ),
+        );
+    }
+
+    #[test]
+    fn backward_broadcast() {
+        let lhs = new_backward_input((2, 3), vec![0.; 6]);
+        let rhs = new_backward_input(3, vec![0.; 3]);
+        let node = TensorPowerBackward::new(
+            new_input((2, 3), BASE.to_vec()),
+            lhs.clone(),
+            new_input(3, vec![2., 0.5, -1.]),
+            rhs.clone(),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((2, 3), vec![1.; 6]);
+        assert_almost_equals(&*node.gradient(), &new_tensor((2, 3), vec![1.; 6]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*rhs.gradient(),
+            &new_tensor(3, vec![22.18071, 0.980258, -1.02009]),
+        );
+    }
+
+    #[test]
+    fn backward_left() {
+        let diff = new_backward_input((2, 3), vec![0.; 6]);
+        let node = TensorPowerBackwardLeft::new(
+            new_input((2, 3), BASE.to_vec()),
+            diff.clone(),
+            new_input((2, 3), EXPONENT.to_vec()),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((2, 3), vec![1.; 6]);
+        assert_almost_equals(&*node.gradient(), &new_tensor((2, 3), vec![1.; 6]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![2., 0.353553, -0.111111, 0., 0., 0.75]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![4., FRAC_1_SQRT_2, -0.222222, 0., 0., 1.5]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![2., 0.353553, -0.111111, 0., 0., 0.75]),
+        );
+    }
+
+    #[test]
+    fn backward_right() {
+        let diff = new_backward_input((2, 3), vec![0.; 6]);
+        let node = TensorPowerBackwardRight::new(
+            new_input((2, 3), BASE.to_vec()),
+            new_input((2, 3), EXPONENT.to_vec()),
+            diff.clone(),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((2, 3), vec![1.; 6]);
+        assert_almost_equals(&*node.gradient(), &new_tensor((2, 3), vec![1.; 6]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (2, 3),
+                vec![0., 0.980258, 0.366204, 1.386294, 0., -0.086643],
+            ),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (2, 3),
+                vec![0., 1.960516, 0.732408, 2.772588, 0., -0.173286],
+            ),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (2, 3),
+                vec![0., 0.980258, 0.366204, 1.386294, 0., -0.086643],
+            ),
+        );
+    }
+
+    #[test]
+    fn no_grad() {
+        // TensorPowerBackward
+        let node = TensorPowerBackward::new(
+            new_input((3, 3), vec![0.; 9]),
+            new_backward_input((3, 3), vec![0.; 9]),
+            new_input((3, 3), vec![0.; 9]),
+            new_backward_input((3, 3), vec![0.; 9]),
+        );
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+
+        // TensorPowerBackwardLeft
+        let node = TensorPowerBackwardLeft::new(
+            new_input((3, 3), vec![0.; 9]),
+            new_backward_input((3, 3), vec![0.; 9]),
+            new_input((3, 3), vec![0.; 9]),
+        );
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+
+        // TensorPowerBackwardRight
+        let node = TensorPowerBackwardRight::new(
+            new_input((3, 3), vec![0.; 9]),
+            new_input((3, 3), vec![0.; 9]),
+            new_backward_input((3, 3), vec![0.; 9]),
+        );
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+    }
+
+    #[test]
+    fn debug() {
+        let node = TensorPowerBackward::new(
+            new_input(1, vec![0.]),
+            new_backward_input(1, vec![0.]),
+            new_input(1, vec![0.]),
+            new_backward_input(1, vec![0.]),
+        );
+
+        let output = "TensorPowerBackward { gradient: Some([0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1), overwrite: true }";
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn debug_left() {
+        let node = TensorPowerBackwardLeft::new(
+            new_input(1, vec![0.]),
+            new_backward_input(1, vec![0.]),
+            new_input(1, vec![0.]),
+        );
+
+        let output = "TensorPowerBackwardLeft { gradient: Some([0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1), overwrite: true }";
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn debug_right() {
+        let node = TensorPowerBackwardRight::new(
+            new_input(1, vec![0.]),
+            new_input(1, vec![0.]),
+            new_backward_input(1, vec![0.]),
+        );
+
+        let output = "TensorPowerBackwardRight { gradient: Some([0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1), overwrite: true }";
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = TensorPowerBackward::new(
+            new_input(1, vec![0.]),
+            new_backward_input(1, vec![0.]),
+            new_input(1, vec![0.]),
+            new_backward_input(1, vec![0.]),
+        );
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+}
@@ -0,0 +1,264 @@
+#[cfg(test)]
+use super::{assert_almost_equals, new_backward_input, new_index_input, new_input, new_tensor};
+use super::{
+    expect_tensor, expect_tensor_mut, Backward, Cache, Data, Forward, Gradient, IndexData,
+    Overwrite, Tensor,
+};
+use ndarray::{Ix1, Zip};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// Converts the element `index` of an indices variable to one of `bins` bins.
+fn to_bin(index: i64, bins: usize) -> usize {
+    assert!(
+        index >= 0 && (index as usize) < bins,
+        "error: index {} is out of range for a count with {} bins.",
+        index,
+        bins
+    );
+    index as usize
+}
+
+/// Asserts that the weights have the same shape of the indices they are paired with.
+fn check_weights(weights: &[usize], indices: &[usize]) {
+    assert_eq!(
+        weights, indices,
+        "error: the weights of shape {:?} do not match the indices of shape {:?}.",
+        weights, indices
+    );
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ WeightedBinCount ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct WeightedBinCount<T: ?Sized, U: ?Sized>
+where
+    T: Data,
+    U: IndexData<Dim = T::Dim>,
+{
+    weights: Rc<T>,
+    indices: Rc<U>,
+    data: RefCell<Tensor<Ix1>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized, U: ?Sized> WeightedBinCount<T, U>
+where
+    T: Data,
+    U: IndexData<Dim = T::Dim>,
+{
+    pub fn new(weights: Rc<T>, indices: Rc<U>, bins: usize) -> Self {
+        let data = RefCell::new(Tensor::zeros(bins));
+
+        Self {
+            weights,
+            indices,
+            data,
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Cache for WeightedBinCount<T, U>
+where
+    T: Data,
+    U: IndexData<Dim = T::Dim>,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Forward for WeightedBinCount<T, U>
+where
+    T: Data,
+    U: IndexData<Dim = T::Dim>,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let (weights, indices) = (self.weights.data(), self.indices.data());
+        check_weights(weights.shape(), indices.shape());
+
+        let mut data = self.data.borrow_mut();
+        data.fill(0.);
+        let bins = data.len();
+        Zip::from(&*weights)
+            .and(&*indices)
+            .for_each(|weight, &index| data[to_bin(index, bins)] += weight);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Data for WeightedBinCount<T, U>
+where
+    T: Data,
+    U: IndexData<Dim = T::Dim>,
+{
+    type Dim = Ix1;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Debug for WeightedBinCount<T, U>
+where
+    T: Data,
+    U: IndexData<Dim = T::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WeightedBinCount")
+            .field("data", &self.data.borrow())
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Display for WeightedBinCount<T, U>
+where
+    T: Data,
+    U: IndexData<Dim = T::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ WeightedBinCountBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct WeightedBinCountBackward<T: ?Sized, U: ?Sized>
+where
+    T: Gradient,
+    U: IndexData<Dim = T::Dim>,
+{
+    gradient: RefCell<Option<Tensor<Ix1>>>,
+    shape: Ix1,
+    overwrite: Cell<bool>,
+    diff_weights: Rc<T>,
+    indices: Rc<U>,
+}
+
+impl<T: ?Sized, U: ?Sized> WeightedBinCountBackward<T, U>
+where
+    T: Gradient,
+    U: IndexData<Dim = T::Dim>,
+{
+    pub fn new(diff_weights: Rc<T>, indices: Rc<U>, bins: usize) -> Self {
+        let shape = Ix1(bins);
+
+        Self {
+            gradient: RefCell::new(Some(Tensor::zeros(shape))),
+            shape,
+            overwrite: Cell::new(true),
+            diff_weights,
+            indices,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Gradient for WeightedBinCountBackward<T, U>
+where
+    T: Gradient,
+    U: IndexData<Dim = T::Dim>,
+{
+    type Dim = Ix1;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Overwrite for WeightedBinCountBackward<T, U>
+where
+    T: Gradient,
+    U: IndexData<Dim = T::Dim>,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Backward for WeightedBinCountBackward<T, U>
+where
+    T: Gradient,
+    U: IndexData<Dim = T::Dim>,
+{
+    fn backward(&self) {
+        let mut weights_grad = self.diff_weights.gradient_mut();
+        let (gradient, indices) = (self.gradient(), self.indices.data());
+        check_weights(weights_grad.shape(), indices.shape());
+
+        // Each weight receives the gradient of the bin it was added to.
+        let bins = gradient.len();
+        let zip = Zip::from(&mut *weights_grad).and(&*indices);
+        if self.diff_weights.can_overwrite() {
+            zip.for_each(|weight_grad, &index| *weight_grad = gradient[to_bin(index, bins)]);
+            self.diff_weights.set_overwrite(false);
+        } else {
+            zip.for_each(|weight_grad, &index| *weight_grad += gradient[to_bin(index, bins)]);
+        }
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape));
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Debug for WeightedBinCountBackward<T, U>
+where
+    T: Gradient,
+    U: IndexData<Dim = T::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WeightedBinCountBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Display for WeightedBinCountBackward<T, U>
+where
+    T: Gradient,
+    U: IndexData<Dim = T::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
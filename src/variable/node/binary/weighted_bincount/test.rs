@@ -0,0 +1,217 @@
+use super::{
+    assert_almost_equals, new_backward_input, new_index_input, new_input, new_tensor, Backward,
+    Cache, Data, Forward, Gradient, Overwrite, Tensor, WeightedBinCount, WeightedBinCountBackward,
+};
+
+mod forward {
+    use super::{
+        assert_almost_equals, new_index_input, new_input, new_tensor, Cache, Data, Forward, Tensor,
+        WeightedBinCount,
+    };
+
+    #[test]
+    fn creation() {
+        let weights = new_input((2, 2), vec![0.5, 1., 2., 4.]);
+        let indices = new_index_input((2, 2), vec![1, 0, 1, 2]);
+        let node = WeightedBinCount::new(weights, indices, 3);
+
+        assert_eq!(*node.data(), Tensor::from_elem(3, 0.));
+        assert_eq!(*node.data_mut(), Tensor::from_elem(3, 0.));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let weights = new_input((2, 2), vec![0.5, 1., 2., 4.]);
+        let indices = new_index_input((2, 2), vec![1, 0, 1, 2]);
+        let node = WeightedBinCount::new(weights, indices, 3);
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward() {
+        let weights = new_input((2, 2), vec![0.5, 1., 2., 4.]);
+        let indices = new_index_input((2, 2), vec![1, 0, 1, 2]);
+        let node = WeightedBinCount::new(weights.clone(), indices, 3);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor(3, vec![1., 2.5, 4.]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        weights.data_mut().fill(1.);
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor(3, vec![1., 2.5, 4.]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor(3, vec![1., 2., 1.]));
+    }
+
+    #[test]
+    #[should_panic(expected = "error: index -1 is out of range for a count with 3 bins.")]
+    fn forward_out_of_range() {
+        let weights = new_input(2, vec![1., 1.]);
+        let indices = new_index_input(2, vec![0, -1]);
+        let node = WeightedBinCount::new(weights, indices, 3);
+
+        node.forward();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "error: the weights of shape [3] do not match the indices of shape [2]."
+    )]
+    fn forward_shape_mismatch() {
+        let weights = new_input(3, vec![1., 1., 1.]);
+        let indices = new_index_input(2, vec![0, 1]);
+        let node = WeightedBinCount::new(weights, indices, 3);
+
+        node.forward();
+    }
+
+    #[test]
+    fn debug() {
+        let weights = new_input(1, vec![1.]);
+        let indices = new_index_input(1, vec![0]);
+        let node = WeightedBinCount::new(weights, indices, 1);
+
+        let output = "WeightedBinCount { data: [0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let weights = new_input(1, vec![1.]);
+        let indices = new_index_input(1, vec![0]);
+        let node = WeightedBinCount::new(weights, indices, 1);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        assert_almost_equals, new_backward_input, new_index_input, new_tensor, Backward, Gradient,
+        Overwrite, Tensor, WeightedBinCountBackward,
+    };
+
+    #[test]
+    fn creation() {
+        let node = WeightedBinCountBackward::new(
+            new_backward_input((2, 2), vec![0.; 4]),
+            new_index_input((2, 2), vec![1, 0, 1, 2]),
+            3,
+        );
+
+        assert_eq!(*node.gradient(), Tensor::from_elem(3, 0.));
+        assert_eq!(*node.gradient_mut(), Tensor::from_elem(3, 0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn computation_state_transition() {
+        let diff = new_backward_input((2, 2), vec![0.; 4]);
+        let node = WeightedBinCountBackward::new(
+            diff.clone(),
+            new_index_input((2, 2), vec![1, 0, 1, 2]),
+            3,
+        );
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        diff.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!diff.can_overwrite());
+    }
+
+    #[test]
+    fn backward() {
+        let diff = new_backward_input((2, 2), vec![0.; 4]);
+        let node = WeightedBinCountBackward::new(
+            diff.clone(),
+            new_index_input((2, 2), vec![1, 0, 1, 2]),
+            3,
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor(3, vec![1., 2., 3.]);
+        assert_almost_equals(&*node.gradient(), &new_tensor(3, vec![1., 2., 3.]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(&*diff.gradient(), &new_tensor((2, 2), vec![2., 1., 2., 3.]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(&*diff.gradient(), &new_tensor((2, 2), vec![4., 2., 4., 6.]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(&*diff.gradient(), &new_tensor((2, 2), vec![2., 1., 2., 3.]));
+    }
+
+    #[test]
+    fn debug() {
+        let node = WeightedBinCountBackward::new(
+            new_backward_input(1, vec![0.]),
+            new_index_input(1, vec![0]),
+            1,
+        );
+
+        let output = "WeightedBinCountBackward { gradient: Some([0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1), overwrite: true }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = WeightedBinCountBackward::new(
+            new_backward_input(1, vec![0.]),
+            new_index_input(1, vec![0]),
+            1,
+        );
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+
+    #[test]
+    fn no_grad() {
+        // WeightedBinCountBackward
+        let node = WeightedBinCountBackward::new(
+            new_backward_input((2, 2), vec![0.; 4]),
+            new_index_input((2, 2), vec![1, 0, 1, 2]),
+            3,
+        );
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+    }
+}
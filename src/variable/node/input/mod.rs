@@ -1,9 +1,11 @@
 use super::{
-    expect_tensor, expect_tensor_mut, Cache, Data, Dimension, Gradient, Overwrite, Tensor,
+    expect_tensor, expect_tensor_mut, Cache, Data, Dimension, Gradient, IndexData, IndexTensor,
+    Overwrite, Tensor,
 };
 use std::{
     cell::{Cell, Ref, RefCell, RefMut},
     fmt::{Debug, Display},
+    rc::Rc,
 };
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -11,17 +13,20 @@ use std::{
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 /// The forward component of a leaf of the computational graph.
+///
+/// The data may be shared with the caller, who can then update it without going through the
+/// variable.
 pub struct Input<D: Dimension> {
-    data: RefCell<Tensor<D>>,
+    data: Rc<RefCell<Tensor<D>>>,
 }
 
 impl<D: Dimension> Input<D> {
     pub fn new(data: Tensor<D>) -> super::super::Var<Self> {
-        let input = Self {
-            data: RefCell::new(data),
-        };
+        Self::shared(Rc::new(RefCell::new(data)))
+    }
 
-        super::super::Var::new(input)
+    pub fn shared(data: Rc<RefCell<Tensor<D>>>) -> super::super::Var<Self> {
+        super::super::Var::new(Self { data })
     }
 
     pub(crate) fn differentiable(&self) -> InputBackward<D> {
@@ -62,7 +67,7 @@ impl<D: Dimension> Debug for Input<D> {
 
 impl<D: Dimension> Display for Input<D> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.data.borrow())
+        crate::print::fmt_tensor(&self.data.borrow(), f)
     }
 }
 
@@ -116,12 +121,65 @@ impl<D: Dimension> Debug for InputBackward<D> {
 impl<D: Dimension> Display for InputBackward<D> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", &gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ IndexInput ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// The forward component of a leaf of the computational graph holding integer indices.
+pub struct IndexInput<D: Dimension> {
+    data: RefCell<IndexTensor<D>>,
+}
+
+impl<D: Dimension> IndexInput<D> {
+    pub fn new(data: IndexTensor<D>) -> super::super::IndexVar<Self> {
+        let input = Self {
+            data: RefCell::new(data),
+        };
+
+        super::super::IndexVar::new(input)
+    }
+}
+
+impl<D: Dimension> IndexData for IndexInput<D> {
+    type Dim = D;
+
+    fn data(&self) -> Ref<IndexTensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<IndexTensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<D: Dimension> Cache for IndexInput<D> {
+    fn was_computed(&self) -> bool {
+        true
+    }
+
+    fn reset_computation(&self) {}
+}
+
+impl<D: Dimension> Debug for IndexInput<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IndexInput")
+            .field("data", &self.data.borrow())
+            .finish()
+    }
+}
+
+impl<D: Dimension> Display for IndexInput<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -1,14 +1,20 @@
-use super::{Cache, Data, Gradient, Input, InputBackward, Overwrite, Tensor};
-use std::cell::{Cell, RefCell};
+use super::{
+    Cache, Data, Gradient, IndexData, IndexInput, IndexTensor, Input, InputBackward, Overwrite,
+    Tensor,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 mod forward {
 
-    use super::{Cache, Data, Input, RefCell, Tensor};
+    use super::{Cache, Data, Input, Rc, RefCell, Tensor};
 
     #[test]
     fn creation() {
         let input = Input {
-            data: RefCell::new(Tensor::zeros((3, 3))),
+            data: Rc::new(RefCell::new(Tensor::zeros((3, 3)))),
         };
         assert_eq!(*input.data(), Tensor::from_elem((3, 3), 0.));
         assert_eq!(*input.data_mut(), Tensor::from_elem((3, 3), 0.));
@@ -17,7 +23,7 @@ mod forward {
     #[test]
     fn was_computed_transition() {
         let input = Input {
-            data: RefCell::new(Tensor::zeros((3, 3))),
+            data: Rc::new(RefCell::new(Tensor::zeros((3, 3)))),
         };
 
         assert!(input.was_computed());
@@ -28,7 +34,7 @@ mod forward {
     #[test]
     fn debug() {
         let node = Input {
-            data: RefCell::new(Tensor::zeros(1)),
+            data: Rc::new(RefCell::new(Tensor::zeros(1))),
         };
         let output =
             "Input { data: [0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1 }";
@@ -39,7 +45,7 @@ mod forward {
     #[test]
     fn display() {
         let node = Input {
-            data: RefCell::new(Tensor::zeros(1)),
+            data: Rc::new(RefCell::new(Tensor::zeros(1))),
         };
 
         assert_eq!(format!("{}", node.data()), format!("{}", node));
@@ -107,3 +113,47 @@ mod backward {
         assert_eq!(format!("{}", node.gradient()), format!("{}", node));
     }
 }
+
+mod index {
+    use super::{Cache, IndexData, IndexInput, IndexTensor, RefCell};
+
+    #[test]
+    fn creation() {
+        let input = IndexInput {
+            data: RefCell::new(IndexTensor::zeros((3, 3))),
+        };
+        assert_eq!(*input.data(), IndexTensor::from_elem((3, 3), 0));
+        assert_eq!(*input.data_mut(), IndexTensor::from_elem((3, 3), 0));
+    }
+
+    #[test]
+    fn was_computed_transition() {
+        let input = IndexInput {
+            data: RefCell::new(IndexTensor::zeros((3, 3))),
+        };
+
+        assert!(input.was_computed());
+        input.reset_computation(); // does nothing
+        assert!(input.was_computed());
+    }
+
+    #[test]
+    fn debug() {
+        let node = IndexInput {
+            data: RefCell::new(IndexTensor::zeros(1)),
+        };
+        let output =
+            "IndexInput { data: [0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1 }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = IndexInput {
+            data: RefCell::new(IndexTensor::zeros(1)),
+        };
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
@@ -11,8 +11,9 @@ pub(crate) use binary::*;
 pub use binary::{
     Constant, Convolve, ConvolveWithGroups, PaddingMode, Reflective, Replicative, Zero,
 };
-pub use input::{Input, InputBackward};
+pub use input::{IndexInput, Input, InputBackward};
 pub(crate) use nary::*;
+pub(crate) use output::*;
 pub(crate) use unary::*;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -22,6 +23,7 @@ pub(crate) use unary::*;
 mod binary;
 mod input;
 mod nary;
+mod output;
 mod unary;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -31,6 +33,7 @@ mod unary;
 pub(crate) type Broadcasted<Lhs, Rhs> = <Lhs as DimMax<Rhs>>::Output;
 pub(crate) type BroadTensor<Lhs, Rhs> = Tensor<Broadcasted<Lhs, Rhs>>;
 pub(crate) type DynTensor = ArrayD<f32>;
+pub(crate) type IndexTensor<D> = Array<i64, D>;
 pub(crate) type Tensor<D> = Array<f32, D>;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -54,6 +57,26 @@ pub trait Data: Cache {
     fn data_mut(&self) -> RefMut<Tensor<Self::Dim>>;
 }
 
+/// Integer data representation.
+///
+/// This trait is implemented by all the internal forward components of [`IndexVar`], that carry
+/// integer indices, such as token ids or class labels, instead of floating point data.
+///
+/// It provides the `.data()` method that is used to retrieve a [`Ref`] to the indices stored
+/// inside the node.
+///
+/// [`IndexVar`]: crate::IndexVar
+pub trait IndexData: Cache {
+    /// The indices' dimensionality.
+    type Dim: Dimension;
+
+    /// Returns an immutable reference to the indices inside `self`.
+    fn data(&self) -> Ref<IndexTensor<Self::Dim>>;
+
+    /// Returns a mutable reference to the indices inside `self`.
+    fn data_mut(&self) -> RefMut<IndexTensor<Self::Dim>>;
+}
+
 /// Caching behavior.
 ///
 /// The two methods this trait provides, namely `.was_computed()` and `.reset_computation()`, are
@@ -163,6 +186,48 @@ pub trait Backward: Overwrite {
     fn with_grad(&self);
 }
 
+/// Multiple data representation.
+///
+/// This trait is implemented by the internal forward components that compute several outputs at
+/// once, such as the one splitting a tensor into chunks. The computation is performed once and
+/// shared among the outputs, each of which is exposed as a node of its own by [`Output`].
+pub trait MultiData: Forward {
+    /// The outputs' dimensionality.
+    type Dim: Dimension;
+
+    /// Returns an immutable reference to the data of the output number `output`.
+    fn data_of(&self, output: usize) -> Ref<Tensor<Self::Dim>>;
+
+    /// Returns a mutable reference to the data of the output number `output`.
+    fn data_of_mut(&self, output: usize) -> RefMut<Tensor<Self::Dim>>;
+}
+
+/// Multiple gradient representation.
+///
+/// This trait is implemented by the internal backward components that back-propagate the
+/// gradients of several outputs at once. Each output is exposed as a node of its own by
+/// [`OutputBackward`], while the back-propagation is performed once by the implementor, after
+/// the gradients of all the outputs have been computed.
+///
+/// Each output has its own overwrite status, the output whose status is still set when
+/// back-propagating did not receive any gradient during the current backward pass.
+pub trait MultiGradient: Backward {
+    /// The outputs' gradients dimensionality.
+    type Dim: Dimension;
+
+    /// Returns an immutable reference to the gradient of the output number `output`.
+    fn gradient_of(&self, output: usize) -> Ref<Tensor<Self::Dim>>;
+
+    /// Returns a mutable reference to the gradient of the output number `output`.
+    fn gradient_of_mut(&self, output: usize) -> RefMut<Tensor<Self::Dim>>;
+
+    /// Returns `true` if the gradient of the output number `output` can be overwritten.
+    fn can_overwrite_of(&self, output: usize) -> bool;
+
+    /// Sets the overwrite status of the output number `output`.
+    fn set_overwrite_of(&self, output: usize, state: bool);
+}
+
 /// Eval mode behavior.
 ///
 /// This trait is implemented by all the variables and all the components that admit multiple
@@ -427,18 +492,17 @@ pub fn push_vec_vec_gradient<T: ?Sized, S>(
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tensor Utilities ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-/// Creates an empty tensor whose shape is the result of broadcasting between those of `left` and
-/// `right`.
+/// Computes the shape resulting from broadcasting between those of `left` and `right`.
 ///
 /// # Arguments
 ///
 /// * `left` - left operand in the binary operations that admits broadcasting.
 ///
 /// * `right` - right operand in the binary operations that admits broadcasting.
-pub(crate) fn cobroadcasted_zeros<Lhs, Rhs>(
+pub(crate) fn cobroadcasted_shape<Lhs, Rhs>(
     left: &Tensor<Lhs>,
     right: &Tensor<Rhs>,
-) -> BroadTensor<Lhs, Rhs>
+) -> Broadcasted<Lhs, Rhs>
 where
     Lhs: Dimension + DimMax<Rhs>,
     Rhs: Dimension,
@@ -467,7 +531,26 @@ where
                 }
             }
         });
-    Tensor::zeros(out)
+    out
+}
+
+/// Creates an empty tensor whose shape is the result of broadcasting between those of `left` and
+/// `right`.
+///
+/// # Arguments
+///
+/// * `left` - left operand in the binary operations that admits broadcasting.
+///
+/// * `right` - right operand in the binary operations that admits broadcasting.
+pub(crate) fn cobroadcasted_zeros<Lhs, Rhs>(
+    left: &Tensor<Lhs>,
+    right: &Tensor<Rhs>,
+) -> BroadTensor<Lhs, Rhs>
+where
+    Lhs: Dimension + DimMax<Rhs>,
+    Rhs: Dimension,
+{
+    Tensor::zeros(cobroadcasted_shape(left, right))
 }
 
 /// Returns a `Ref` to `tensor`. This function is used to access gradients.
@@ -508,6 +591,38 @@ pub(crate) fn expect_tensor_mut<D: Dimension>(
     })
 }
 
+/// Reallocates the tensor stored in `data` if its shape differs from `shape`. This function is
+/// used by nodes to follow operands whose shape changed after the graph was built, e.g. when a
+/// batch of a different size is fed to the graph.
+///
+/// # Arguments
+///
+/// * `data` - buffer of a node.
+///
+/// * `shape` - shape that the buffer must have.
+pub(crate) fn fit_shape<A: Clone + Default, D: Dimension>(data: &RefCell<Array<A, D>>, shape: D) {
+    if data.borrow().raw_dim() != shape {
+        *data.borrow_mut() = Array::default(shape);
+    }
+}
+
+/// Reallocates the gradient stored in `gradient` if its shape differs from `shape`. De-allocated
+/// gradients are left untouched.
+///
+/// # Arguments
+///
+/// * `gradient` - gradient or buffer of a node.
+///
+/// * `shape` - shape that the gradient must have.
+pub(crate) fn fit_gradient_shape<D: Dimension>(gradient: &RefCell<Option<Tensor<D>>>, shape: D) {
+    let mut gradient = gradient.borrow_mut();
+    if let Some(tensor) = gradient.as_ref() {
+        if tensor.raw_dim() != shape {
+            *gradient = Some(Tensor::zeros(shape));
+        }
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Testing Utilities ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -592,3 +707,19 @@ where
 {
     Tensor::from_shape_vec(shape, elements).unwrap()
 }
+
+#[cfg(test)]
+/// Creates a new index input node whose indices will have shape `shape` and elements `elements`.
+///
+/// # Arguments
+///
+/// * `shape` - shape.
+///
+/// * `elements` - elements.
+fn new_index_input<D, Sh>(shape: Sh, elements: Vec<i64>) -> Rc<IndexInput<D>>
+where
+    D: Dimension + 'static,
+    Sh: Into<ndarray::StrideShape<D>>,
+{
+    IndexInput::new(IndexTensor::from_shape_vec(shape, elements).unwrap()).node
+}
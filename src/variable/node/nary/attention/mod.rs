@@ -0,0 +1,662 @@
+#[cfg(test)]
+use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
+use super::{
+    expect_tensor, expect_tensor_mut, fit_shape, push_gradient, Backward, Cache, Data, Eval,
+    Forward, Gradient, MultiData, MultiGradient, Overwrite, Tensor,
+};
+use ndarray::{linalg::general_mat_mul, ArrayViewMut3, Axis, CowArray, Dimension, Ix3, Zip};
+use rand::thread_rng;
+use rand_distr::{Bernoulli, Distribution};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// Returns `shape` with its last axis replaced by one of length `len`.
+fn with_last_axis<D: Dimension>(mut shape: D, len: usize) -> D {
+    let last = shape.ndim() - 1;
+    shape[last] = len;
+    shape
+}
+
+/// Views `array` as a stack of matrices by merging all of its axes but the last two.
+fn as_batch<D: Dimension>(array: &Tensor<D>) -> CowArray<f32, Ix3> {
+    let (batch, rows, cols) = batch_shape(array.shape());
+    array
+        .as_standard_layout()
+        .into_shape((batch, rows, cols))
+        .unwrap()
+}
+
+/// Mutably views `array`, which must be in standard layout, as a stack of matrices.
+fn as_batch_mut<D: Dimension>(array: &mut Tensor<D>) -> ArrayViewMut3<f32> {
+    let (batch, rows, cols) = batch_shape(array.shape());
+    array.view_mut().into_shape((batch, rows, cols)).unwrap()
+}
+
+fn batch_shape(shape: &[usize]) -> (usize, usize, usize) {
+    let ndim = shape.len();
+    let batch = shape[..ndim - 2].iter().product();
+    (batch, shape[ndim - 2], shape[ndim - 1])
+}
+
+/// Computes in-place the softmax of each row of `scores`. Rows whose elements are all negative
+/// infinity, i.e. rows that are fully masked, are set to zero.
+fn softmax_rows<D: Dimension>(scores: &mut Tensor<D>) {
+    let last = Axis(scores.ndim() - 1);
+    for mut row in scores.lanes_mut(last) {
+        let max = row.fold(f32::NEG_INFINITY, |max, &el| max.max(el));
+        if max == f32::NEG_INFINITY {
+            row.fill(0.);
+            continue;
+        }
+
+        row.mapv_inplace(|el| (el - max).exp());
+        let sum = row.sum();
+        row.mapv_inplace(|el| el / sum);
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ ScaledDotProductAttention ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct ScaledDotProductAttention<Q: ?Sized, K: ?Sized, V: ?Sized>
+where
+    Q: Data,
+    K: Data<Dim = Q::Dim>,
+    V: Data<Dim = Q::Dim>,
+{
+    query: Rc<Q>,
+    key: Rc<K>,
+    value: Rc<V>,
+    mask: Option<Tensor<Q::Dim>>,
+    attention: RefCell<Tensor<Q::Dim>>,
+    dropped: Option<RefCell<Tensor<Q::Dim>>>,
+    distr: Bernoulli,
+    p: f64,
+    data: RefCell<Tensor<Q::Dim>>,
+    computed: Cell<bool>,
+    train: Rc<Cell<bool>>,
+}
+
+impl<Q: ?Sized, K: ?Sized, V: ?Sized> ScaledDotProductAttention<Q, K, V>
+where
+    Q: Data,
+    K: Data<Dim = Q::Dim>,
+    V: Data<Dim = Q::Dim>,
+{
+    pub fn new(
+        query: Rc<Q>,
+        key: Rc<K>,
+        value: Rc<V>,
+        mask: Option<Tensor<Q::Dim>>,
+        p: f64,
+        status: Rc<Cell<bool>>,
+    ) -> Self {
+        if !(0. ..=1.).contains(&p) {
+            panic!(
+                "error: dropout probability has to be between 0 and 1, but got {}.",
+                p
+            );
+        }
+
+        let (attention_shape, shape) = {
+            let (query, key, value) = (query.data(), key.data(), value.data());
+            let ndim = query.ndim();
+            assert!(
+                ndim >= 2
+                    && query.shape()[..ndim - 2] == key.shape()[..ndim - 2]
+                    && key.shape()[..ndim - 1] == value.shape()[..ndim - 1]
+                    && query.shape()[ndim - 1] == key.shape()[ndim - 1],
+                "error: cannot attend with a query of shape {:?} to a key of shape {:?} and a \
+                value of shape {:?}.",
+                query.shape(),
+                key.shape(),
+                value.shape()
+            );
+
+            (
+                with_last_axis(query.raw_dim(), key.shape()[ndim - 2]),
+                with_last_axis(query.raw_dim(), value.shape()[ndim - 1]),
+            )
+        };
+        if let Some(mask) = &mask {
+            assert!(
+                mask.broadcast(attention_shape.clone()).is_some(),
+                "error: the attention mask of shape {:?} cannot be broadcast to shape {:?}.",
+                mask.shape(),
+                attention_shape.slice()
+            );
+        }
+
+        let dropped = if p > 0. {
+            Some(RefCell::new(Tensor::zeros(attention_shape.clone())))
+        } else {
+            None
+        };
+
+        Self {
+            query,
+            key,
+            value,
+            mask,
+            attention: RefCell::new(Tensor::zeros(attention_shape)),
+            dropped,
+            distr: Bernoulli::new(1. - p).unwrap(),
+            p,
+            data: RefCell::new(Tensor::zeros(shape)),
+            computed: Cell::new(false),
+            train: status,
+        }
+    }
+
+    /// Returns the attention probabilities, of shape *(..., L, S)*, computed during the last
+    /// forward pass.
+    pub(crate) fn attention(&self) -> Ref<Tensor<Q::Dim>> {
+        self.attention.borrow()
+    }
+
+    /// Returns the weights the values were averaged with, that is, the attention probabilities
+    /// after the dropout.
+    fn weights(&self) -> Ref<Tensor<Q::Dim>> {
+        match &self.dropped {
+            Some(dropped) => dropped.borrow(),
+            None => self.attention.borrow(),
+        }
+    }
+}
+
+impl<Q: ?Sized, K: ?Sized, V: ?Sized> Cache for ScaledDotProductAttention<Q, K, V>
+where
+    Q: Data,
+    K: Data<Dim = Q::Dim>,
+    V: Data<Dim = Q::Dim>,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<Q: ?Sized, K: ?Sized, V: ?Sized> Forward for ScaledDotProductAttention<Q, K, V>
+where
+    Q: Data,
+    K: Data<Dim = Q::Dim>,
+    V: Data<Dim = Q::Dim>,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let (query, key, value) = (self.query.data(), self.key.data(), self.value.data());
+        let ndim = query.ndim();
+        let attention_shape = with_last_axis(query.raw_dim(), key.shape()[ndim - 2]);
+        fit_shape(&self.attention, attention_shape.clone());
+        fit_shape(
+            &self.data,
+            with_last_axis(query.raw_dim(), value.shape()[ndim - 1]),
+        );
+
+        let mut attention = self.attention.borrow_mut();
+        let scale = 1. / (query.shape()[ndim - 1] as f32).sqrt();
+        for ((query, key), mut scores) in as_batch(&query)
+            .outer_iter()
+            .zip(as_batch(&key).outer_iter())
+            .zip(as_batch_mut(&mut attention).outer_iter_mut())
+        {
+            general_mat_mul(scale, &query, &key.t(), 0., &mut scores);
+        }
+        if let Some(mask) = &self.mask {
+            Zip::from(&mut *attention)
+                .and_broadcast(mask)
+                .for_each(|score, mask_el| *score += mask_el);
+        }
+        softmax_rows(&mut attention);
+
+        if let Some(dropped) = &self.dropped {
+            fit_shape(dropped, attention_shape);
+            let mut dropped = dropped.borrow_mut();
+            if self.train.get() {
+                let (mut thread_rng, p) = (thread_rng(), self.p as f32);
+                Zip::from(&mut *dropped)
+                    .and(&*attention)
+                    .for_each(|dropped_el, attention_el| {
+                        *dropped_el = if self.distr.sample(&mut thread_rng) {
+                            attention_el / (1. - p)
+                        } else {
+                            0.
+                        }
+                    });
+            } else {
+                dropped.assign(&*attention);
+            }
+        }
+        drop(attention);
+
+        let weights = self.weights();
+        let mut data = self.data.borrow_mut();
+        for ((weights, value), mut data) in as_batch(&weights)
+            .outer_iter()
+            .zip(as_batch(&value).outer_iter())
+            .zip(as_batch_mut(&mut data).outer_iter_mut())
+        {
+            general_mat_mul(1., &weights, &value, 0., &mut data);
+        }
+    }
+}
+
+impl<Q: ?Sized, K: ?Sized, V: ?Sized> Data for ScaledDotProductAttention<Q, K, V>
+where
+    Q: Data,
+    K: Data<Dim = Q::Dim>,
+    V: Data<Dim = Q::Dim>,
+{
+    type Dim = Q::Dim;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<Q: ?Sized, K: ?Sized, V: ?Sized> MultiData for ScaledDotProductAttention<Q, K, V>
+where
+    Q: Data,
+    K: Data<Dim = Q::Dim>,
+    V: Data<Dim = Q::Dim>,
+{
+    type Dim = Q::Dim;
+
+    /// The output number *0* is the result of the attention, the output number *1* are the
+    /// attention probabilities.
+    fn data_of(&self, output: usize) -> Ref<Tensor<Q::Dim>> {
+        match output {
+            0 => self.data.borrow(),
+            _ => self.attention.borrow(),
+        }
+    }
+
+    fn data_of_mut(&self, output: usize) -> RefMut<Tensor<Q::Dim>> {
+        match output {
+            0 => self.data.borrow_mut(),
+            _ => self.attention.borrow_mut(),
+        }
+    }
+}
+
+impl<Q: ?Sized, K: ?Sized, V: ?Sized> Eval for ScaledDotProductAttention<Q, K, V>
+where
+    Q: Data,
+    K: Data<Dim = Q::Dim>,
+    V: Data<Dim = Q::Dim>,
+{
+    fn train(&self) {
+        self.train.set(true);
+    }
+
+    fn eval(&self) {
+        self.train.set(false);
+    }
+}
+
+impl<Q: ?Sized, K: ?Sized, V: ?Sized> Debug for ScaledDotProductAttention<Q, K, V>
+where
+    Q: Data,
+    K: Data<Dim = Q::Dim>,
+    V: Data<Dim = Q::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScaledDotProductAttention")
+            .field("data", &self.data.borrow())
+            .field("attention", &self.attention.borrow())
+            .field("p", &self.p)
+            .field("train", &self.train.get())
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<Q: ?Sized, K: ?Sized, V: ?Sized> Display for ScaledDotProductAttention<Q, K, V>
+where
+    Q: Data,
+    K: Data<Dim = Q::Dim>,
+    V: Data<Dim = Q::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ ScaledDotProductAttentionBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct ScaledDotProductAttentionBackward<
+    QG: ?Sized,
+    KG: ?Sized,
+    VG: ?Sized,
+    Q: ?Sized,
+    K: ?Sized,
+    V: ?Sized,
+> where
+    Q: Data,
+    K: Data<Dim = Q::Dim>,
+    V: Data<Dim = Q::Dim>,
+    QG: Gradient<Dim = Q::Dim>,
+    KG: Gradient<Dim = Q::Dim>,
+    VG: Gradient<Dim = Q::Dim>,
+{
+    gradient: RefCell<Option<Tensor<Q::Dim>>>,
+    shape: Q::Dim,
+    overwrite: Cell<bool>,
+    weights: Option<WeightsOutput<Q::Dim>>,
+    query_grad: Rc<QG>,
+    key_grad: Rc<KG>,
+    value_grad: Rc<VG>,
+    forward: Rc<ScaledDotProductAttention<Q, K, V>>,
+}
+
+/// The gradient of the attention probabilities, along with the overwrite statuses of both the
+/// outputs, kept when the probabilities are an output of their own.
+struct WeightsOutput<D: Dimension> {
+    gradient: RefCell<Option<Tensor<D>>>,
+    shape: D,
+    overwrites: [Cell<bool>; 2],
+}
+
+impl<QG: ?Sized, KG: ?Sized, VG: ?Sized, Q: ?Sized, K: ?Sized, V: ?Sized>
+    ScaledDotProductAttentionBackward<QG, KG, VG, Q, K, V>
+where
+    Q: Data,
+    K: Data<Dim = Q::Dim>,
+    V: Data<Dim = Q::Dim>,
+    QG: Gradient<Dim = Q::Dim>,
+    KG: Gradient<Dim = Q::Dim>,
+    VG: Gradient<Dim = Q::Dim>,
+{
+    pub fn new(
+        query_grad: Rc<QG>,
+        key_grad: Rc<KG>,
+        value_grad: Rc<VG>,
+        forward: Rc<ScaledDotProductAttention<Q, K, V>>,
+    ) -> Self {
+        let shape = forward.data().raw_dim();
+
+        Self {
+            gradient: RefCell::new(Some(Tensor::zeros(shape.clone()))),
+            shape,
+            overwrite: Cell::new(true),
+            weights: None,
+            query_grad,
+            key_grad,
+            value_grad,
+            forward,
+        }
+    }
+
+    /// Creates a node that back-propagates the gradients of both the result and the attention
+    /// probabilities, which are exposed as the outputs number *0* and *1* respectively.
+    pub fn with_weights(
+        query_grad: Rc<QG>,
+        key_grad: Rc<KG>,
+        value_grad: Rc<VG>,
+        forward: Rc<ScaledDotProductAttention<Q, K, V>>,
+    ) -> Self {
+        let shape = forward.attention().raw_dim();
+        let weights = WeightsOutput {
+            gradient: RefCell::new(Some(Tensor::zeros(shape.clone()))),
+            shape,
+            overwrites: [Cell::new(true), Cell::new(true)],
+        };
+
+        Self {
+            weights: Some(weights),
+            ..Self::new(query_grad, key_grad, value_grad, forward)
+        }
+    }
+
+    fn weights_output(&self) -> &WeightsOutput<Q::Dim> {
+        self.weights
+            .as_ref()
+            .expect("error: the attention probabilities are not an output of this node.")
+    }
+}
+
+impl<QG: ?Sized, KG: ?Sized, VG: ?Sized, Q: ?Sized, K: ?Sized, V: ?Sized> Gradient
+    for ScaledDotProductAttentionBackward<QG, KG, VG, Q, K, V>
+where
+    Q: Data,
+    K: Data<Dim = Q::Dim>,
+    V: Data<Dim = Q::Dim>,
+    QG: Gradient<Dim = Q::Dim>,
+    KG: Gradient<Dim = Q::Dim>,
+    VG: Gradient<Dim = Q::Dim>,
+{
+    type Dim = Q::Dim;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<QG: ?Sized, KG: ?Sized, VG: ?Sized, Q: ?Sized, K: ?Sized, V: ?Sized> Overwrite
+    for ScaledDotProductAttentionBackward<QG, KG, VG, Q, K, V>
+where
+    Q: Data,
+    K: Data<Dim = Q::Dim>,
+    V: Data<Dim = Q::Dim>,
+    QG: Gradient<Dim = Q::Dim>,
+    KG: Gradient<Dim = Q::Dim>,
+    VG: Gradient<Dim = Q::Dim>,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<QG: ?Sized, KG: ?Sized, VG: ?Sized, Q: ?Sized, K: ?Sized, V: ?Sized> MultiGradient
+    for ScaledDotProductAttentionBackward<QG, KG, VG, Q, K, V>
+where
+    Q: Data,
+    K: Data<Dim = Q::Dim>,
+    V: Data<Dim = Q::Dim>,
+    QG: Gradient<Dim = Q::Dim>,
+    KG: Gradient<Dim = Q::Dim>,
+    VG: Gradient<Dim = Q::Dim>,
+{
+    type Dim = Q::Dim;
+
+    fn gradient_of(&self, output: usize) -> Ref<Tensor<Q::Dim>> {
+        match output {
+            0 => expect_tensor(&self.gradient),
+            _ => expect_tensor(&self.weights_output().gradient),
+        }
+    }
+
+    fn gradient_of_mut(&self, output: usize) -> RefMut<Tensor<Q::Dim>> {
+        match output {
+            0 => expect_tensor_mut(&self.gradient),
+            _ => expect_tensor_mut(&self.weights_output().gradient),
+        }
+    }
+
+    fn can_overwrite_of(&self, output: usize) -> bool {
+        self.weights_output().overwrites[output].get()
+    }
+
+    fn set_overwrite_of(&self, output: usize, state: bool) {
+        self.weights_output().overwrites[output].set(state);
+    }
+}
+
+impl<QG: ?Sized, KG: ?Sized, VG: ?Sized, Q: ?Sized, K: ?Sized, V: ?Sized> Backward
+    for ScaledDotProductAttentionBackward<QG, KG, VG, Q, K, V>
+where
+    Q: Data,
+    K: Data<Dim = Q::Dim>,
+    V: Data<Dim = Q::Dim>,
+    QG: Gradient<Dim = Q::Dim>,
+    KG: Gradient<Dim = Q::Dim>,
+    VG: Gradient<Dim = Q::Dim>,
+{
+    fn backward(&self) {
+        // When the attention probabilities are an output of their own, the outputs that received
+        // no gradient during this pass are skipped.
+        let (received, weights_received) = match &self.weights {
+            Some(weights) => (!weights.overwrites[0].get(), !weights.overwrites[1].get()),
+            None => (true, false),
+        };
+
+        let forward = &self.forward;
+        let (query, key, value) = (
+            forward.query.data(),
+            forward.key.data(),
+            forward.value.data(),
+        );
+        let (attention, weights) = (forward.attention(), forward.weights());
+        let scale = 1. / (query.shape()[query.ndim() - 1] as f32).sqrt();
+
+        let mut query_grad = Tensor::zeros(query.raw_dim());
+        let mut key_grad = Tensor::zeros(key.raw_dim());
+        let mut value_grad = Tensor::zeros(value.raw_dim());
+        let mut scores_grad = Tensor::zeros(attention.raw_dim());
+
+        // The gradient w.r.t. the values and w.r.t. the weights.
+        if received {
+            let gradient = self.gradient();
+            for (((gradient, weights), value), (mut value_grad, mut weights_grad)) in
+                as_batch(&gradient)
+                    .outer_iter()
+                    .zip(as_batch(&weights).outer_iter())
+                    .zip(as_batch(&value).outer_iter())
+                    .zip(
+                        as_batch_mut(&mut value_grad)
+                            .outer_iter_mut()
+                            .zip(as_batch_mut(&mut scores_grad).outer_iter_mut()),
+                    )
+            {
+                general_mat_mul(1., &weights.t(), &gradient, 0., &mut value_grad);
+                general_mat_mul(1., &gradient, &value.t(), 0., &mut weights_grad);
+            }
+        }
+
+        // The gradient w.r.t. the scores, through the dropout and the softmax.
+        Zip::from(&mut scores_grad)
+            .and(&*weights)
+            .for_each(|scores_grad_el, weights_el| *scores_grad_el *= weights_el);
+        if weights_received {
+            Zip::from(&mut scores_grad)
+                .and(&*self.gradient_of(1))
+                .and(&*attention)
+                .for_each(|scores_grad_el, grad_el, attention_el| {
+                    *scores_grad_el += grad_el * attention_el
+                });
+        }
+        let last = Axis(attention.ndim() - 1);
+        Zip::from(scores_grad.lanes_mut(last))
+            .and(attention.lanes(last))
+            .for_each(|mut scores_grad, attention| {
+                let sum = scores_grad.sum();
+                Zip::from(&mut scores_grad)
+                    .and(&attention)
+                    .for_each(|scores_grad_el, attention_el| *scores_grad_el -= attention_el * sum);
+            });
+
+        // The gradient w.r.t. the queries and the keys.
+        for (((scores_grad, query), key), (mut query_grad, mut key_grad)) in as_batch(&scores_grad)
+            .outer_iter()
+            .zip(as_batch(&query).outer_iter())
+            .zip(as_batch(&key).outer_iter())
+            .zip(
+                as_batch_mut(&mut query_grad)
+                    .outer_iter_mut()
+                    .zip(as_batch_mut(&mut key_grad).outer_iter_mut()),
+            )
+        {
+            general_mat_mul(scale, &scores_grad, &key, 0., &mut query_grad);
+            general_mat_mul(scale, &scores_grad.t(), &query, 0., &mut key_grad);
+        }
+
+        push_gradient(&*self.query_grad, &query_grad);
+        push_gradient(&*self.key_grad, &key_grad);
+        push_gradient(&*self.value_grad, &value_grad);
+
+        if let Some(weights) = &self.weights {
+            for overwrite in &weights.overwrites {
+                overwrite.set(true);
+            }
+        }
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+        if let Some(weights) = &self.weights {
+            *weights.gradient.borrow_mut() = None;
+        }
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape.clone()));
+        if let Some(weights) = &self.weights {
+            *weights.gradient.borrow_mut() = Some(Tensor::zeros(weights.shape.clone()));
+        }
+    }
+}
+
+impl<QG: ?Sized, KG: ?Sized, VG: ?Sized, Q: ?Sized, K: ?Sized, V: ?Sized> Debug
+    for ScaledDotProductAttentionBackward<QG, KG, VG, Q, K, V>
+where
+    Q: Data,
+    K: Data<Dim = Q::Dim>,
+    V: Data<Dim = Q::Dim>,
+    QG: Gradient<Dim = Q::Dim>,
+    KG: Gradient<Dim = Q::Dim>,
+    VG: Gradient<Dim = Q::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScaledDotProductAttentionBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<QG: ?Sized, KG: ?Sized, VG: ?Sized, Q: ?Sized, K: ?Sized, V: ?Sized> Display
+    for ScaledDotProductAttentionBackward<QG, KG, VG, Q, K, V>
+where
+    Q: Data,
+    K: Data<Dim = Q::Dim>,
+    V: Data<Dim = Q::Dim>,
+    QG: Gradient<Dim = Q::Dim>,
+    KG: Gradient<Dim = Q::Dim>,
+    VG: Gradient<Dim = Q::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
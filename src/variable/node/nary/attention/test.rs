@@ -0,0 +1,520 @@
+use super::{
+    assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Cache, Data, Eval,
+    Forward, Gradient, MultiData, MultiGradient, Overwrite, ScaledDotProductAttention,
+    ScaledDotProductAttentionBackward, Tensor,
+};
+use std::{cell::Cell, f32::consts::SQRT_2, rc::Rc};
+
+const QUERY: [f32; 4] = [1., 0., 0., 1.];
+const KEY: [f32; 6] = [1., 0., 0., 1., 1., 1.];
+const VALUE: [f32; 6] = [1., 2., 3., 4., 5., 6.];
+
+fn new_attention(
+    mask: Option<Tensor<ndarray::Ix2>>,
+    p: f64,
+) -> ScaledDotProductAttention<
+    crate::variable::Input<ndarray::Ix2>,
+    crate::variable::Input<ndarray::Ix2>,
+    crate::variable::Input<ndarray::Ix2>,
+> {
+    ScaledDotProductAttention::new(
+        new_input((2, 2), QUERY.to_vec()),
+        new_input((3, 2), KEY.to_vec()),
+        new_input((3, 2), VALUE.to_vec()),
+        mask,
+        p,
+        Rc::new(Cell::new(true)),
+    )
+}
+
+fn causal_mask() -> Tensor<ndarray::Ix2> {
+    new_tensor(
+        (2, 3),
+        vec![0., f32::NEG_INFINITY, 0., 0., 0., f32::NEG_INFINITY],
+    )
+}
+
+mod forward {
+    use super::{
+        assert_almost_equals, causal_mask, new_attention, new_input, new_tensor, Cache, Cell, Data,
+        Eval, Forward, MultiData, Rc, ScaledDotProductAttention, Tensor, KEY, QUERY, VALUE,
+    };
+
+    #[test]
+    fn creation() {
+        let node = new_attention(None, 0.);
+
+        assert_eq!(*node.data(), Tensor::from_elem((2, 2), 0.));
+        assert_eq!(*node.data_mut(), Tensor::from_elem((2, 2), 0.));
+        assert_eq!(*node.attention(), Tensor::from_elem((2, 3), 0.));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "error: cannot attend with a query of shape [2, 2] to a key of \
+                               shape [3, 3] and a value of shape [3, 2]."
+    )]
+    fn creation_fail() {
+        ScaledDotProductAttention::new(
+            new_input((2, 2), QUERY.to_vec()),
+            new_input((3, 3), vec![0.; 9]),
+            new_input((3, 2), VALUE.to_vec()),
+            None,
+            0.,
+            Rc::new(Cell::new(true)),
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "error: the attention mask of shape [2, 2] cannot be broadcast to \
+                               shape [2, 3]."
+    )]
+    fn creation_fail_mask() {
+        new_attention(Some(Tensor::zeros((2, 2))), 0.);
+    }
+
+    #[test]
+    #[should_panic(expected = "error: dropout probability has to be between 0 and 1, but got 1.5.")]
+    fn creation_fail_dropout() {
+        new_attention(None, 1.5);
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let node = new_attention(None, 0.);
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward() {
+        let value = new_input((3, 2), VALUE.to_vec());
+        let node = ScaledDotProductAttention::new(
+            new_input((2, 2), QUERY.to_vec()),
+            new_input((3, 2), KEY.to_vec()),
+            value.clone(),
+            None,
+            0.,
+            Rc::new(Cell::new(true)),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(
+            &*node.attention(),
+            &new_tensor(
+                (2, 3),
+                vec![0.401112, 0.197776, 0.401112, 0.197776, 0.401112, 0.401112],
+            ),
+        );
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 2), vec![3., 4., 3.406673, 4.406673]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *value.data_mut() = new_tensor((3, 2), vec![1.; 6]);
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 2), vec![3., 4., 3.406673, 4.406673]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((2, 2), vec![1.; 4]));
+    }
+
+    #[test]
+    fn outputs() {
+        let node = new_attention(None, 0.);
+
+        node.forward();
+        assert_eq!(*node.data_of(0), *node.data());
+        assert_eq!(*node.data_of(1), *node.attention());
+    }
+
+    #[test]
+    fn forward_masked() {
+        let node = new_attention(Some(causal_mask()), 0.);
+
+        node.forward();
+        assert_almost_equals(
+            &*node.attention(),
+            &new_tensor((2, 3), vec![0.5, 0., 0.5, 0.330238, 0.669762, 0.]),
+        );
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 2), vec![3., 4., 2.339523, 3.339523]),
+        );
+    }
+
+    #[test]
+    fn forward_fully_masked() {
+        let node = new_attention(Some(new_tensor((1, 3), vec![f32::NEG_INFINITY; 3])), 0.);
+
+        node.forward();
+        assert_eq!(*node.attention(), Tensor::from_elem((2, 3), 0.));
+        assert_eq!(*node.data(), Tensor::from_elem((2, 2), 0.));
+    }
+
+    #[test]
+    fn forward_batched() {
+        let node = ScaledDotProductAttention::new(
+            new_input((2, 2, 2), [QUERY, QUERY].concat()),
+            new_input((2, 3, 2), [KEY, KEY].concat()),
+            new_input((2, 3, 2), [VALUE, VALUE].concat()),
+            Some(causal_mask().insert_axis(ndarray::Axis(0))),
+            0.,
+            Rc::new(Cell::new(true)),
+        );
+
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor(
+                (2, 2, 2),
+                [3., 4., 2.339523, 3.339523, 3., 4., 2.339523, 3.339523].to_vec(),
+            ),
+        );
+    }
+
+    #[test]
+    fn forward_dropout() {
+        let node = new_attention(None, 1.);
+
+        node.forward();
+        assert_eq!(*node.data(), Tensor::from_elem((2, 2), 0.));
+
+        node.eval();
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 2), vec![3., 4., 3.406673, 4.406673]),
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let node = ScaledDotProductAttention::new(
+            new_input((1, 1), vec![0.]),
+            new_input((1, 1), vec![0.]),
+            new_input((1, 1), vec![0.]),
+            None,
+            0.,
+            Rc::new(Cell::new(true)),
+        );
+
+        let output = "ScaledDotProductAttention { data: [[0.0]], shape=[1, 1], strides=[1, 1], layout=CFcf (0xf), const ndim=2, attention: [[0.0]], shape=[1, 1], strides=[1, 1], layout=CFcf (0xf), const ndim=2, p: 0.0, train: true, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = new_attention(None, 0.);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        assert_almost_equals, causal_mask, new_attention, new_backward_input, new_tensor, Backward,
+        Forward, Gradient, MultiGradient, Overwrite, Rc, ScaledDotProductAttentionBackward, Tensor,
+        SQRT_2,
+    };
+
+    #[test]
+    fn creation() {
+        let node = ScaledDotProductAttentionBackward::new(
+            new_backward_input((2, 2), vec![0.; 4]),
+            new_backward_input((3, 2), vec![0.; 6]),
+            new_backward_input((3, 2), vec![0.; 6]),
+            Rc::new(new_attention(None, 0.)),
+        );
+
+        assert_eq!(*node.gradient(), Tensor::from_elem((2, 2), 0.));
+        assert_eq!(*node.gradient_mut(), Tensor::from_elem((2, 2), 0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn computation_state_transition() {
+        let query = new_backward_input((2, 2), vec![0.; 4]);
+        let key = new_backward_input((3, 2), vec![0.; 6]);
+        let value = new_backward_input((3, 2), vec![0.; 6]);
+        let forward = Rc::new(new_attention(None, 0.));
+        forward.forward();
+        let node = ScaledDotProductAttentionBackward::new(
+            query.clone(),
+            key.clone(),
+            value.clone(),
+            forward,
+        );
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!query.can_overwrite());
+        assert!(!key.can_overwrite());
+        assert!(!value.can_overwrite());
+
+        query.set_overwrite(true);
+        key.set_overwrite(true);
+        value.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(query.can_overwrite());
+        assert!(key.can_overwrite());
+        assert!(value.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(query.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!query.can_overwrite());
+        assert!(!key.can_overwrite());
+        assert!(!value.can_overwrite());
+    }
+
+    #[test]
+    fn backward() {
+        let query = new_backward_input((2, 2), vec![0.; 4]);
+        let key = new_backward_input((3, 2), vec![0.; 6]);
+        let value = new_backward_input((3, 2), vec![0.; 6]);
+        let forward = Rc::new(new_attention(None, 0.));
+        forward.forward();
+        let node = ScaledDotProductAttentionBackward::new(
+            query.clone(),
+            key.clone(),
+            value.clone(),
+            forward,
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((2, 2), vec![1.; 4]);
+        assert_almost_equals(&*node.gradient(), &new_tensor((2, 2), vec![1.; 4]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*query.gradient(),
+            &new_tensor((2, 2), vec![0., 1.134516, 0.230688, 0.67314]),
+        );
+        assert_almost_equals(
+            &*key.gradient(),
+            &new_tensor(
+                (3, 2),
+                vec![-1.134516, -0.67314, 0., -0.230688, 1.134516, 0.903828],
+            ),
+        );
+        assert_almost_equals(
+            &*value.gradient(),
+            &new_tensor(
+                (3, 2),
+                vec![0.598888, 0.598888, 0.598888, 0.598888, 0.802224, 0.802224],
+            ),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*query.gradient(),
+            &new_tensor((2, 2), vec![0., 2.269032, 0.461376, 1.34628]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        query.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*query.gradient(),
+            &new_tensor((2, 2), vec![0., 1.134516, 0.230688, 0.67314]),
+        );
+    }
+
+    #[test]
+    fn backward_weights() {
+        let query = new_backward_input((2, 2), vec![0.; 4]);
+        let key = new_backward_input((3, 2), vec![0.; 6]);
+        let value = new_backward_input((3, 2), vec![0.; 6]);
+        let forward = Rc::new(new_attention(None, 0.));
+        forward.forward();
+        let node = ScaledDotProductAttentionBackward::with_weights(
+            query.clone(),
+            key.clone(),
+            value.clone(),
+            forward,
+        );
+
+        assert_eq!(*node.gradient_of(1), Tensor::from_elem((2, 3), 0.));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Only The Result ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_of_mut(0) = new_tensor((2, 2), vec![1.; 4]);
+        *node.gradient_of_mut(1) = new_tensor((2, 3), vec![1., 0., 0., 0., 0., 1.]);
+        node.set_overwrite_of(0, false);
+        node.backward();
+        assert_almost_equals(
+            &*query.gradient(),
+            &new_tensor((2, 2), vec![0., 1.134516, 0.230688, 0.67314]),
+        );
+        assert!(node.can_overwrite_of(0));
+        assert!(node.can_overwrite_of(1));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Only The Weights ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        query.set_overwrite(true);
+        key.set_overwrite(true);
+        value.set_overwrite(true);
+        node.set_overwrite_of(1, false);
+        node.backward();
+        assert_almost_equals(
+            &*query.gradient(),
+            &new_tensor((2, 2), vec![0.056095, -0.169862, 0.113767, 0.056095]),
+        );
+        assert_almost_equals(
+            &*key.gradient(),
+            &new_tensor(
+                (3, 2),
+                vec![
+                    0.169862, -0.056095, -0.056095, -0.113767, -0.113767, 0.169862,
+                ],
+            ),
+        );
+        assert_almost_equals(&*value.gradient(), &Tensor::zeros((3, 2)));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Both ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        query.set_overwrite(true);
+        node.set_overwrite_of(0, false);
+        node.set_overwrite_of(1, false);
+        node.backward();
+        assert_almost_equals(
+            &*query.gradient(),
+            &new_tensor((2, 2), vec![0.056095, 0.964654, 0.344455, 0.729235]),
+        );
+    }
+
+    #[test]
+    fn backward_masked() {
+        let query = new_backward_input((2, 2), vec![0.; 4]);
+        let key = new_backward_input((3, 2), vec![0.; 6]);
+        let value = new_backward_input((3, 2), vec![0.; 6]);
+        let forward = Rc::new(new_attention(Some(causal_mask()), 0.));
+        forward.forward();
+        let node = ScaledDotProductAttentionBackward::new(
+            query.clone(),
+            key.clone(),
+            value.clone(),
+            forward,
+        );
+
+        *node.gradient_mut() = new_tensor((2, 2), vec![1.; 4]);
+        node.backward();
+        assert_almost_equals(
+            &*query.gradient(),
+            &new_tensor((2, 2), vec![0., SQRT_2, -0.625594, 0.625594]),
+        );
+        assert_almost_equals(
+            &*key.gradient(),
+            &new_tensor((3, 2), vec![-SQRT_2, -0.625594, 0., 0.625594, SQRT_2, 0.]),
+        );
+        assert_almost_equals(
+            &*value.gradient(),
+            &new_tensor(
+                (3, 2),
+                vec![0.830238, 0.830238, 0.669762, 0.669762, 0.5, 0.5],
+            ),
+        );
+    }
+
+    #[test]
+    fn backward_dropout() {
+        let query = new_backward_input((2, 2), vec![0.; 4]);
+        let key = new_backward_input((3, 2), vec![0.; 6]);
+        let value = new_backward_input((3, 2), vec![0.; 6]);
+        let forward = Rc::new(new_attention(None, 1.));
+        forward.forward();
+        let node = ScaledDotProductAttentionBackward::new(
+            query.clone(),
+            key.clone(),
+            value.clone(),
+            forward,
+        );
+
+        *node.gradient_mut() = new_tensor((2, 2), vec![1.; 4]);
+        node.backward();
+        assert_eq!(*query.gradient(), Tensor::from_elem((2, 2), 0.));
+        assert_eq!(*key.gradient(), Tensor::from_elem((3, 2), 0.));
+        assert_eq!(*value.gradient(), Tensor::from_elem((3, 2), 0.));
+    }
+
+    #[test]
+    fn no_grad() {
+        let node = ScaledDotProductAttentionBackward::new(
+            new_backward_input((2, 2), vec![0.; 4]),
+            new_backward_input((3, 2), vec![0.; 6]),
+            new_backward_input((3, 2), vec![0.; 6]),
+            Rc::new(new_attention(None, 0.)),
+        );
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+    }
+
+    #[test]
+    fn no_grad_weights() {
+        let node = ScaledDotProductAttentionBackward::with_weights(
+            new_backward_input((2, 2), vec![0.; 4]),
+            new_backward_input((3, 2), vec![0.; 6]),
+            new_backward_input((3, 2), vec![0.; 6]),
+            Rc::new(new_attention(None, 0.)),
+        );
+
+        node.no_grad();
+        assert!(node.weights.as_ref().unwrap().gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient_of(1), Tensor::zeros((2, 3)));
+    }
+
+    #[test]
+    fn debug() {
+        let node = ScaledDotProductAttentionBackward::new(
+            new_backward_input((2, 2), vec![0.; 4]),
+            new_backward_input((3, 2), vec![0.; 6]),
+            new_backward_input((3, 2), vec![0.; 6]),
+            Rc::new(new_attention(None, 0.)),
+        );
+
+        let output = "ScaledDotProductAttentionBackward { gradient: Some([[0.0, 0.0],\n [0.0, 0.0]], shape=[2, 2], strides=[2, 1], layout=Cc (0x5), const ndim=2), overwrite: true }";
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = ScaledDotProductAttentionBackward::new(
+            new_backward_input((2, 2), vec![0.; 4]),
+            new_backward_input((3, 2), vec![0.; 6]),
+            new_backward_input((3, 2), vec![0.; 6]),
+            Rc::new(new_attention(None, 0.)),
+        );
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+}
@@ -0,0 +1,465 @@
+#[cfg(test)]
+use super::{assert_almost_equals, new_backward_input, new_index_input, new_input, new_tensor};
+use super::{
+    expect_tensor, expect_tensor_mut, fit_shape, push_gradient, Backward, Cache, Data, Forward,
+    Gradient, IndexData, Input, InputBackward, Overwrite, Tensor,
+};
+use ndarray::{ArrayView1, Axis, Ix1, Ix2, Ix3};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// Returns the logarithm of the sum of the exponentials of `values`, computed stably. The result
+/// is negative infinity if all the values are.
+fn log_sum_exp<I: Iterator<Item = f32> + Clone>(values: I) -> f32 {
+    let max = values.clone().fold(f32::NEG_INFINITY, f32::max);
+    if max == f32::NEG_INFINITY {
+        return max;
+    }
+
+    max + values.map(|value| (value - max).exp()).sum::<f32>().ln()
+}
+
+/// Returns the tag at position `position` of `tags`, checking that it is in range.
+fn tag_at(tags: ArrayView1<i64>, position: usize, num_tags: usize) -> usize {
+    let tag = tags[position];
+    assert!(
+        tag >= 0 && (tag as usize) < num_tags,
+        "error: tag {} is out of range for {} tags.",
+        tag,
+        num_tags
+    );
+    tag as usize
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ CRFLogLikelihood ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct CRFLogLikelihood<E: ?Sized, I: ?Sized>
+where
+    E: Data<Dim = Ix3>,
+    I: IndexData<Dim = Ix2>,
+{
+    emissions: Rc<E>,
+    transitions: Rc<Input<Ix2>>,
+    start: Rc<Input<Ix1>>,
+    end: Rc<Input<Ix1>>,
+    tags: Rc<I>,
+    lengths: Vec<usize>,
+    alpha: RefCell<Tensor<Ix3>>,
+    log_partition: RefCell<Tensor<Ix1>>,
+    data: RefCell<Tensor<Ix1>>,
+    computed: Cell<bool>,
+}
+
+impl<E: ?Sized, I: ?Sized> CRFLogLikelihood<E, I>
+where
+    E: Data<Dim = Ix3>,
+    I: IndexData<Dim = Ix2>,
+{
+    pub fn new(
+        emissions: Rc<E>,
+        transitions: Rc<Input<Ix2>>,
+        start: Rc<Input<Ix1>>,
+        end: Rc<Input<Ix1>>,
+        tags: Rc<I>,
+        lengths: Vec<usize>,
+    ) -> Self {
+        let shape = emissions.data().shape().to_vec();
+        {
+            let (transitions, start, end, tags) =
+                (transitions.data(), start.data(), end.data(), tags.data());
+            let num_tags = shape[2];
+            assert!(
+                transitions.shape() == [num_tags, num_tags]
+                    && start.len() == num_tags
+                    && end.len() == num_tags,
+                "error: emissions of shape {:?} require transitions of shape [{}, {}] and start \
+                and end transitions of shape [{}], but got {:?}, {:?} and {:?}.",
+                &shape,
+                num_tags,
+                num_tags,
+                num_tags,
+                transitions.shape(),
+                start.shape(),
+                end.shape()
+            );
+            assert!(
+                tags.shape() == &shape[..2],
+                "error: tags of shape {:?} do not match emissions of shape {:?}.",
+                tags.shape(),
+                shape
+            );
+        }
+        assert_eq!(
+            lengths.len(),
+            shape[0],
+            "error: {} lengths were given for a batch of {} sequences.",
+            lengths.len(),
+            shape[0]
+        );
+        if let Some(len) = lengths.iter().find(|&&len| len == 0 || len > shape[1]) {
+            panic!(
+                "error: sequence length {} is not between 1 and {}.",
+                len, shape[1]
+            );
+        }
+
+        Self {
+            emissions,
+            transitions,
+            start,
+            end,
+            tags,
+            lengths,
+            alpha: RefCell::new(Tensor::zeros((shape[0], shape[1], shape[2]))),
+            log_partition: RefCell::new(Tensor::zeros(shape[0])),
+            data: RefCell::new(Tensor::zeros(shape[0])),
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<E: ?Sized, I: ?Sized> Cache for CRFLogLikelihood<E, I>
+where
+    E: Data<Dim = Ix3>,
+    I: IndexData<Dim = Ix2>,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<E: ?Sized, I: ?Sized> Forward for CRFLogLikelihood<E, I>
+where
+    E: Data<Dim = Ix3>,
+    I: IndexData<Dim = Ix2>,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let (emissions, transitions, start, end, tags) = (
+            self.emissions.data(),
+            self.transitions.data(),
+            self.start.data(),
+            self.end.data(),
+            self.tags.data(),
+        );
+        assert_eq!(
+            self.lengths.len(),
+            emissions.len_of(Axis(0)),
+            "error: {} lengths were given for a batch of {} sequences.",
+            self.lengths.len(),
+            emissions.len_of(Axis(0))
+        );
+        let num_tags = emissions.shape()[2];
+        fit_shape(&self.alpha, emissions.raw_dim());
+        fit_shape(&self.log_partition, Ix1(self.lengths.len()));
+        fit_shape(&self.data, Ix1(self.lengths.len()));
+
+        let mut alpha = self.alpha.borrow_mut();
+        let mut log_partition = self.log_partition.borrow_mut();
+        let mut data = self.data.borrow_mut();
+        for (sequence, &len) in self.lengths.iter().enumerate() {
+            let (emissions, tags, mut alpha) = (
+                emissions.index_axis(Axis(0), sequence),
+                tags.row(sequence),
+                alpha.index_axis_mut(Axis(0), sequence),
+            );
+
+            // The forward algorithm.
+            alpha.row_mut(0).assign(&(&emissions.row(0) + &*start));
+            for position in 1..len {
+                for tag in 0..num_tags {
+                    let incoming = log_sum_exp(
+                        alpha
+                            .row(position - 1)
+                            .iter()
+                            .zip(transitions.column(tag))
+                            .map(|(alpha_el, transition)| alpha_el + transition),
+                    );
+                    alpha[[position, tag]] = incoming + emissions[[position, tag]];
+                }
+            }
+            log_partition[sequence] = log_sum_exp(
+                alpha
+                    .row(len - 1)
+                    .iter()
+                    .zip(end.iter())
+                    .map(|(alpha_el, end_el)| alpha_el + end_el),
+            );
+
+            // The score of the given tags.
+            let mut previous = tag_at(tags, 0, num_tags);
+            let mut score = start[previous] + emissions[[0, previous]];
+            for position in 1..len {
+                let tag = tag_at(tags, position, num_tags);
+                score += transitions[[previous, tag]] + emissions[[position, tag]];
+                previous = tag;
+            }
+            score += end[previous];
+
+            data[sequence] = score - log_partition[sequence];
+        }
+    }
+}
+
+impl<E: ?Sized, I: ?Sized> Data for CRFLogLikelihood<E, I>
+where
+    E: Data<Dim = Ix3>,
+    I: IndexData<Dim = Ix2>,
+{
+    type Dim = Ix1;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<E: ?Sized, I: ?Sized> Debug for CRFLogLikelihood<E, I>
+where
+    E: Data<Dim = Ix3>,
+    I: IndexData<Dim = Ix2>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CRFLogLikelihood")
+            .field("data", &self.data.borrow())
+            .field("lengths", &self.lengths)
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<E: ?Sized, I: ?Sized> Display for CRFLogLikelihood<E, I>
+where
+    E: Data<Dim = Ix3>,
+    I: IndexData<Dim = Ix2>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ CRFLogLikelihoodBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct CRFLogLikelihoodBackward<EG: ?Sized, E: ?Sized, I: ?Sized>
+where
+    E: Data<Dim = Ix3>,
+    EG: Gradient<Dim = Ix3>,
+    I: IndexData<Dim = Ix2>,
+{
+    gradient: RefCell<Option<Tensor<Ix1>>>,
+    shape: Ix1,
+    overwrite: Cell<bool>,
+    emissions_grad: Rc<EG>,
+    transitions_grad: Rc<InputBackward<Ix2>>,
+    start_grad: Rc<InputBackward<Ix1>>,
+    end_grad: Rc<InputBackward<Ix1>>,
+    forward: Rc<CRFLogLikelihood<E, I>>,
+}
+
+impl<EG: ?Sized, E: ?Sized, I: ?Sized> CRFLogLikelihoodBackward<EG, E, I>
+where
+    E: Data<Dim = Ix3>,
+    EG: Gradient<Dim = Ix3>,
+    I: IndexData<Dim = Ix2>,
+{
+    pub fn new(
+        emissions_grad: Rc<EG>,
+        transitions_grad: Rc<InputBackward<Ix2>>,
+        start_grad: Rc<InputBackward<Ix1>>,
+        end_grad: Rc<InputBackward<Ix1>>,
+        forward: Rc<CRFLogLikelihood<E, I>>,
+    ) -> Self {
+        let shape = forward.data().raw_dim();
+
+        Self {
+            gradient: RefCell::new(Some(Tensor::zeros(shape))),
+            shape,
+            overwrite: Cell::new(true),
+            emissions_grad,
+            transitions_grad,
+            start_grad,
+            end_grad,
+            forward,
+        }
+    }
+}
+
+impl<EG: ?Sized, E: ?Sized, I: ?Sized> Gradient for CRFLogLikelihoodBackward<EG, E, I>
+where
+    E: Data<Dim = Ix3>,
+    EG: Gradient<Dim = Ix3>,
+    I: IndexData<Dim = Ix2>,
+{
+    type Dim = Ix1;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<EG: ?Sized, E: ?Sized, I: ?Sized> Overwrite for CRFLogLikelihoodBackward<EG, E, I>
+where
+    E: Data<Dim = Ix3>,
+    EG: Gradient<Dim = Ix3>,
+    I: IndexData<Dim = Ix2>,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<EG: ?Sized, E: ?Sized, I: ?Sized> Backward for CRFLogLikelihoodBackward<EG, E, I>
+where
+    E: Data<Dim = Ix3>,
+    EG: Gradient<Dim = Ix3>,
+    I: IndexData<Dim = Ix2>,
+{
+    fn backward(&self) {
+        let gradient = self.gradient();
+        let forward = &self.forward;
+        let (emissions, transitions, end, tags) = (
+            forward.emissions.data(),
+            forward.transitions.data(),
+            forward.end.data(),
+            forward.tags.data(),
+        );
+        let (alpha, log_partition) = (forward.alpha.borrow(), forward.log_partition.borrow());
+        let num_tags = emissions.shape()[2];
+
+        let mut emissions_grad = Tensor::zeros(emissions.raw_dim());
+        let mut transitions_grad = Tensor::zeros(transitions.raw_dim());
+        let mut start_grad = Tensor::zeros(num_tags);
+        let mut end_grad = Tensor::zeros(num_tags);
+
+        // The gradient of the score of the given tags minus the expected one, whose marginals are
+        // computed by running the backward algorithm.
+        for (sequence, &len) in forward.lengths.iter().enumerate() {
+            let (emissions, tags, alpha, mut emissions_grad) = (
+                emissions.index_axis(Axis(0), sequence),
+                tags.row(sequence),
+                alpha.index_axis(Axis(0), sequence),
+                emissions_grad.index_axis_mut(Axis(0), sequence),
+            );
+            let (seed, log_partition) = (gradient[sequence], log_partition[sequence]);
+
+            let mut beta = end.to_owned();
+            for position in (0..len).rev() {
+                let tag = tag_at(tags, position, num_tags);
+                for (current, beta_el) in beta.iter().enumerate() {
+                    let marginal = (alpha[[position, current]] + beta_el - log_partition).exp();
+                    emissions_grad[[position, current]] -= seed * marginal;
+                    if position == 0 {
+                        start_grad[current] -= seed * marginal;
+                    }
+                    if position == len - 1 {
+                        end_grad[current] -= seed * marginal;
+                    }
+                }
+                emissions_grad[[position, tag]] += seed;
+                if position == 0 {
+                    start_grad[tag] += seed;
+                }
+                if position == len - 1 {
+                    end_grad[tag] += seed;
+                }
+                if position == 0 {
+                    break;
+                }
+
+                let previous_tag = tag_at(tags, position - 1, num_tags);
+                transitions_grad[[previous_tag, tag]] += seed;
+                let mut previous_beta = Tensor::zeros(num_tags);
+                for previous in 0..num_tags {
+                    let outgoing = transitions.row(previous);
+                    let next = || {
+                        outgoing
+                            .iter()
+                            .zip(emissions.row(position))
+                            .zip(beta.iter())
+                            .map(|((transition, emission), beta_el)| {
+                                transition + emission + beta_el
+                            })
+                    };
+                    for (current, next_el) in next().enumerate() {
+                        let marginal =
+                            (alpha[[position - 1, previous]] + next_el - log_partition).exp();
+                        transitions_grad[[previous, current]] -= seed * marginal;
+                    }
+                    previous_beta[previous] = log_sum_exp(next());
+                }
+                beta = previous_beta;
+            }
+        }
+
+        push_gradient(&*self.emissions_grad, &emissions_grad);
+        push_gradient(&*self.transitions_grad, &transitions_grad);
+        push_gradient(&*self.start_grad, &start_grad);
+        push_gradient(&*self.end_grad, &end_grad);
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape));
+    }
+}
+
+impl<EG: ?Sized, E: ?Sized, I: ?Sized> Debug for CRFLogLikelihoodBackward<EG, E, I>
+where
+    E: Data<Dim = Ix3>,
+    EG: Gradient<Dim = Ix3>,
+    I: IndexData<Dim = Ix2>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CRFLogLikelihoodBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<EG: ?Sized, E: ?Sized, I: ?Sized> Display for CRFLogLikelihoodBackward<EG, E, I>
+where
+    E: Data<Dim = Ix3>,
+    EG: Gradient<Dim = Ix3>,
+    I: IndexData<Dim = Ix2>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
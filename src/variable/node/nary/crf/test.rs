@@ -0,0 +1,337 @@
+use super::{
+    assert_almost_equals, new_backward_input, new_index_input, new_input, new_tensor, Backward,
+    CRFLogLikelihood, CRFLogLikelihoodBackward, Cache, Data, Forward, Gradient, Overwrite, Tensor,
+};
+use crate::variable::{IndexInput, Input, InputBackward};
+use ndarray::{Ix1, Ix2, Ix3};
+use std::rc::Rc;
+
+// Two sequences of lengths 3 and 2 over 2 tags, the last position of the second one is padding.
+const EMISSIONS: [f32; 12] = [1., 0., 0., 1., 0.5, 0.5, 0., 2., 1., 0., 9., 9.];
+const TRANSITIONS: [f32; 4] = [0.5, -0.5, 0., 1.];
+const START: [f32; 2] = [0.2, -0.2];
+const END: [f32; 2] = [0., 0.3];
+const TAGS: [i64; 6] = [0, 1, 1, 1, 0, 0];
+
+fn new_crf(
+    emissions: Rc<Input<Ix3>>,
+    tags: Vec<i64>,
+    lengths: Vec<usize>,
+) -> CRFLogLikelihood<Input<Ix3>, IndexInput<Ix2>> {
+    CRFLogLikelihood::new(
+        emissions,
+        new_input((2, 2), TRANSITIONS.to_vec()),
+        new_input(2, START.to_vec()),
+        new_input(2, END.to_vec()),
+        new_index_input((2, 3), tags),
+        lengths,
+    )
+}
+
+type BackwardInputs = (
+    Rc<InputBackward<Ix3>>,
+    Rc<InputBackward<Ix2>>,
+    Rc<InputBackward<Ix1>>,
+    Rc<InputBackward<Ix1>>,
+);
+
+type Node = CRFLogLikelihoodBackward<InputBackward<Ix3>, Input<Ix3>, IndexInput<Ix2>>;
+
+fn new_crf_backward() -> (Node, BackwardInputs) {
+    let inputs = (
+        new_backward_input((2, 3, 2), vec![0.; 12]),
+        new_backward_input((2, 2), vec![0.; 4]),
+        new_backward_input(2, vec![0.; 2]),
+        new_backward_input(2, vec![0.; 2]),
+    );
+    let forward = Rc::new(new_crf(
+        new_input((2, 3, 2), EMISSIONS.to_vec()),
+        TAGS.to_vec(),
+        vec![3, 2],
+    ));
+    forward.forward();
+    let node = CRFLogLikelihoodBackward::new(
+        inputs.0.clone(),
+        inputs.1.clone(),
+        inputs.2.clone(),
+        inputs.3.clone(),
+        forward,
+    );
+
+    (node, inputs)
+}
+
+mod forward {
+    use super::{
+        assert_almost_equals, new_crf, new_index_input, new_input, new_tensor, CRFLogLikelihood,
+        Cache, Data, Forward, Tensor, EMISSIONS, END, START, TAGS, TRANSITIONS,
+    };
+
+    #[test]
+    fn creation() {
+        let node = new_crf(
+            new_input((2, 3, 2), EMISSIONS.to_vec()),
+            TAGS.to_vec(),
+            vec![3, 2],
+        );
+
+        assert_eq!(*node.data(), Tensor::from_elem(2, 0.));
+        assert_eq!(*node.data_mut(), Tensor::from_elem(2, 0.));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "error: emissions of shape [2, 3, 2] require transitions of shape [2, 2] and \
+                    start and end transitions of shape [2], but got [2, 2], [3] and [2]."
+    )]
+    fn creation_fail_transitions() {
+        CRFLogLikelihood::new(
+            new_input((2, 3, 2), EMISSIONS.to_vec()),
+            new_input((2, 2), TRANSITIONS.to_vec()),
+            new_input(3, vec![0.; 3]),
+            new_input(2, END.to_vec()),
+            new_index_input((2, 3), TAGS.to_vec()),
+            vec![3, 2],
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "error: tags of shape [2, 2] do not match emissions of shape [2, 3, 2]."
+    )]
+    fn creation_fail_tags() {
+        CRFLogLikelihood::new(
+            new_input((2, 3, 2), EMISSIONS.to_vec()),
+            new_input((2, 2), TRANSITIONS.to_vec()),
+            new_input(2, START.to_vec()),
+            new_input(2, END.to_vec()),
+            new_index_input((2, 2), vec![0; 4]),
+            vec![2, 2],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "error: sequence length 4 is not between 1 and 3.")]
+    fn creation_fail_lengths() {
+        new_crf(
+            new_input((2, 3, 2), EMISSIONS.to_vec()),
+            TAGS.to_vec(),
+            vec![4, 2],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "error: tag 2 is out of range for 2 tags.")]
+    fn forward_fail_tags() {
+        let node = new_crf(
+            new_input((2, 3, 2), EMISSIONS.to_vec()),
+            vec![0, 2, 1, 1, 0, 0],
+            vec![3, 2],
+        );
+
+        node.forward();
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let node = new_crf(
+            new_input((2, 3, 2), EMISSIONS.to_vec()),
+            TAGS.to_vec(),
+            vec![3, 2],
+        );
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward() {
+        let emissions = new_input((2, 3, 2), EMISSIONS.to_vec());
+        let node = new_crf(emissions.clone(), TAGS.to_vec(), vec![3, 2]);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor(2, vec![-1.238935, -1.009249]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        // The padding does not contribute to the log-likelihood.
+        emissions.data_mut()[[1, 2, 0]] = -9.;
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor(2, vec![-1.238935, -1.009249]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        emissions.data_mut()[[0, 0, 0]] = 0.;
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor(2, vec![-1.798617, -1.009249]));
+    }
+
+    #[test]
+    fn debug() {
+        let node = new_crf(
+            new_input((2, 3, 2), EMISSIONS.to_vec()),
+            TAGS.to_vec(),
+            vec![3, 2],
+        );
+
+        let output = "CRFLogLikelihood { data: [0.0, 0.0], shape=[2], strides=[1], layout=CFcf (0xf), const ndim=1, lengths: [3, 2], computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = new_crf(
+            new_input((2, 3, 2), EMISSIONS.to_vec()),
+            TAGS.to_vec(),
+            vec![3, 2],
+        );
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        assert_almost_equals, new_crf_backward, new_tensor, Backward, Gradient, Overwrite, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let (node, _) = new_crf_backward();
+
+        assert_eq!(*node.gradient(), Tensor::from_elem(2, 0.));
+        assert_eq!(*node.gradient_mut(), Tensor::from_elem(2, 0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn computation_state_transition() {
+        let (node, (emissions, transitions, start, end)) = new_crf_backward();
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!emissions.can_overwrite());
+        assert!(!transitions.can_overwrite());
+        assert!(!start.can_overwrite());
+        assert!(!end.can_overwrite());
+
+        emissions.set_overwrite(true);
+        transitions.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(emissions.can_overwrite());
+        assert!(transitions.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(emissions.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!emissions.can_overwrite());
+        assert!(!transitions.can_overwrite());
+    }
+
+    #[test]
+    fn backward() {
+        let (node, (emissions, transitions, start, end)) = new_crf_backward();
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor(2, vec![1.; 2]);
+        assert_almost_equals(&*node.gradient(), &new_tensor(2, vec![1.; 2]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*emissions.gradient(),
+            &new_tensor(
+                (2, 3, 2),
+                vec![
+                    0.436551, -0.436551, -0.223943, 0.223943, -0.31584, 0.31584, -0.143494,
+                    0.143494, 0.514178, -0.514178, 0., 0.,
+                ],
+            ),
+        );
+        assert_almost_equals(
+            &*transitions.gradient(),
+            &new_tensor((2, 2), vec![-0.465772, 0.534885, 0.440166, -0.50928]),
+        );
+        assert_almost_equals(
+            &*start.gradient(),
+            &new_tensor(2, vec![0.293057, -0.293057]),
+        );
+        assert_almost_equals(&*end.gradient(), &new_tensor(2, vec![0.198338, -0.198338]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*transitions.gradient(),
+            &new_tensor((2, 2), vec![-0.931544, 1.06977, 0.880332, -1.01856]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        transitions.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*transitions.gradient(),
+            &new_tensor((2, 2), vec![-0.465772, 0.534885, 0.440166, -0.50928]),
+        );
+    }
+
+    #[test]
+    fn backward_seed() {
+        let (node, (emissions, _, start, _)) = new_crf_backward();
+
+        *node.gradient_mut() = new_tensor(2, vec![0., -2.]);
+        node.backward();
+        assert_almost_equals(
+            &*emissions.gradient(),
+            &new_tensor(
+                (2, 3, 2),
+                vec![
+                    0., 0., 0., 0., 0., 0., 0.286988, -0.286988, -1.028356, 1.028356, 0., 0.,
+                ],
+            ),
+        );
+        assert_almost_equals(
+            &*start.gradient(),
+            &new_tensor(2, vec![0.286988, -0.286988]),
+        );
+    }
+
+    #[test]
+    fn no_grad() {
+        let (node, _) = new_crf_backward();
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+    }
+
+    #[test]
+    fn debug() {
+        let (node, _) = new_crf_backward();
+
+        let output = "CRFLogLikelihoodBackward { gradient: Some([0.0, 0.0], shape=[2], strides=[1], layout=CFcf (0xf), const ndim=1), overwrite: true }";
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let (node, _) = new_crf_backward();
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+}
@@ -1,13 +1,22 @@
+mod attention;
+mod crf;
 mod multi_concatenate;
 mod multi_stack;
+mod sampled_softmax;
 
 use super::{
-    expect_tensor, expect_tensor_mut, push_gradient, Backward, Cache, Data, Forward, Gradient,
-    Overwrite, Tensor,
+    expect_tensor, expect_tensor_mut, fit_shape, push_gradient, Backward, Cache, Data, Eval,
+    Forward, Gradient, IndexData, Input, InputBackward, MultiData, MultiGradient, Overwrite,
+    Tensor,
 };
 
 #[cfg(test)]
-use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
+use super::{assert_almost_equals, new_backward_input, new_index_input, new_input, new_tensor};
 
+pub(crate) use attention::{ScaledDotProductAttention, ScaledDotProductAttentionBackward};
+pub(crate) use crf::{CRFLogLikelihood, CRFLogLikelihoodBackward};
 pub(crate) use multi_concatenate::{MultiConcatenate, MultiConcatenateBackward};
 pub(crate) use multi_stack::{MultiStack, MultiStackBackward};
+pub(crate) use sampled_softmax::{
+    SampledObjective, SampledSoftmaxLoss, SampledSoftmaxLossBackward,
+};
@@ -1,8 +1,8 @@
 #[cfg(test)]
 use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
 use super::{
-    expect_tensor, expect_tensor_mut, push_gradient, Backward, Cache, Data, Forward, Gradient,
-    Overwrite, Tensor,
+    expect_tensor, expect_tensor_mut, fit_shape, push_gradient, Backward, Cache, Data, Forward,
+    Gradient, Overwrite, Tensor,
 };
 use ndarray::{Axis, Dimension, Slice, Zip};
 use std::{
@@ -78,6 +78,13 @@ where
         }
 
         self.computed.set(true);
+        let mut shape = self.operands[0].data().raw_dim();
+        shape[self.axis] = self
+            .operands
+            .iter()
+            .map(|operand| operand.data().len_of(Axis(self.axis)))
+            .sum();
+        fit_shape(&self.data, shape);
         let (axis, mut offset, mut data) = (self.axis, 0, self.data.borrow_mut());
 
         self.operands.iter().for_each(|operand| {
@@ -113,7 +120,7 @@ where
     D: Dimension,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(f, "{}", &self.data.borrow())
+        crate::print::fmt_tensor(&self.data.borrow(), f)
     }
 }
 
@@ -226,7 +233,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", &gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
@@ -1,8 +1,8 @@
 #[cfg(test)]
 use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
 use super::{
-    expect_tensor, expect_tensor_mut, push_gradient, Backward, Cache, Data, Forward, Gradient,
-    Overwrite, Tensor,
+    expect_tensor, expect_tensor_mut, fit_shape, push_gradient, Backward, Cache, Data, Forward,
+    Gradient, Overwrite, Tensor,
 };
 use ndarray::{Axis, Dimension, RemoveAxis, Zip};
 use std::{
@@ -82,6 +82,12 @@ where
         }
 
         self.computed.set(true);
+        let mut shape = self.operands[0]
+            .data()
+            .raw_dim()
+            .insert_axis(Axis(self.axis));
+        shape[self.axis] = self.operands.len();
+        fit_shape(&self.data, shape);
         let (mut data, axis) = (self.data.borrow_mut(), self.axis);
 
         self.operands
@@ -115,7 +121,7 @@ where
     D: Dimension + RemoveAxis,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(f, "{}", &self.data.borrow())
+        crate::print::fmt_tensor(&self.data.borrow(), f)
     }
 }
 
@@ -227,7 +233,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", &gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
@@ -0,0 +1,448 @@
+#[cfg(test)]
+use super::{assert_almost_equals, new_backward_input, new_index_input, new_input, new_tensor};
+use super::{
+    expect_tensor, expect_tensor_mut, fit_shape, push_gradient, Backward, Cache, Data, Forward,
+    Gradient, IndexData, Overwrite, Tensor,
+};
+use crate::nn::loss::{NegativeSampler, Reduction};
+use ndarray::{arr0, Array2, Axis, Ix0, Ix1, Ix2};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// The objective optimized by a [`SampledSoftmaxLoss`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SampledObjective {
+    /// The negative log likelihood of the target under a softmax restricted to the target and to
+    /// the negatives, whose logits are corrected by their sampling probabilities.
+    Softmax,
+    /// The logistic loss of telling the target apart from each of the negatives.
+    NegativeSampling,
+}
+
+/// Returns `ln(1 + exp(x))`, computed stably.
+fn softplus(x: f32) -> f32 {
+    x.max(0.) + (-x.abs()).exp().ln_1p()
+}
+
+/// Returns the logistic sigmoid of `x`.
+fn sigmoid(x: f32) -> f32 {
+    1. / (1. + (-x).exp())
+}
+
+/// Converts the target `target` to a class index, checking that it is in range.
+fn to_class(target: i64, classes: usize) -> usize {
+    assert!(
+        target >= 0 && (target as usize) < classes,
+        "error: target {} is out of range for {} classes.",
+        target,
+        classes
+    );
+    target as usize
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ SampledSoftmaxLoss ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct SampledSoftmaxLoss<T: ?Sized, U: ?Sized, I: ?Sized>
+where
+    T: Data<Dim = Ix2>,
+    U: Data<Dim = Ix2>,
+    I: IndexData<Dim = Ix1>,
+{
+    input: Rc<T>,
+    weight: Rc<U>,
+    target: Rc<I>,
+    sampler: NegativeSampler,
+    objective: SampledObjective,
+    reduction: Reduction,
+    classes: RefCell<Array2<usize>>,
+    coefficients: RefCell<Tensor<Ix2>>,
+    data: RefCell<Tensor<Ix0>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized, U: ?Sized, I: ?Sized> SampledSoftmaxLoss<T, U, I>
+where
+    T: Data<Dim = Ix2>,
+    U: Data<Dim = Ix2>,
+    I: IndexData<Dim = Ix1>,
+{
+    pub(crate) fn new(
+        input: Rc<T>,
+        weight: Rc<U>,
+        target: Rc<I>,
+        sampler: NegativeSampler,
+        objective: SampledObjective,
+        reduction: Reduction,
+    ) -> Self {
+        {
+            let (input, weight, target) = (input.data(), weight.data(), target.data());
+            assert!(
+                input.ncols() == weight.ncols(),
+                "error: an input of shape {:?} does not match an output embedding of shape {:?}.",
+                input.shape(),
+                weight.shape()
+            );
+            assert!(
+                target.len() == input.nrows(),
+                "error: a target of shape {:?} does not match an input of shape {:?}.",
+                target.shape(),
+                input.shape()
+            );
+            assert!(
+                sampler.classes() == weight.nrows(),
+                "error: the sampler draws from {} classes, but the output embedding has {}.",
+                sampler.classes(),
+                weight.nrows()
+            );
+        }
+
+        Self {
+            input,
+            weight,
+            target,
+            sampler,
+            objective,
+            reduction,
+            classes: RefCell::new(Array2::zeros((0, 0))),
+            coefficients: RefCell::new(Tensor::zeros((0, 0))),
+            data: RefCell::new(arr0(0.)),
+            computed: Cell::new(false),
+        }
+    }
+
+    /// Returns the classes whose logits were computed at the last forward pass, of shape
+    /// *(N, k + 1)*: the target of each sample followed by its negatives.
+    pub(crate) fn classes(&self) -> Ref<Array2<usize>> {
+        self.classes.borrow()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, I: ?Sized> Cache for SampledSoftmaxLoss<T, U, I>
+where
+    T: Data<Dim = Ix2>,
+    U: Data<Dim = Ix2>,
+    I: IndexData<Dim = Ix1>,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, I: ?Sized> Forward for SampledSoftmaxLoss<T, U, I>
+where
+    T: Data<Dim = Ix2>,
+    U: Data<Dim = Ix2>,
+    I: IndexData<Dim = Ix1>,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let (input, weight, target) = (self.input.data(), self.weight.data(), self.target.data());
+        let (batch_size, samples) = (input.nrows(), self.sampler.samples());
+        fit_shape(&self.classes, Ix2(batch_size, samples + 1));
+        fit_shape(&self.coefficients, Ix2(batch_size, samples + 1));
+
+        let mut classes = self.classes.borrow_mut();
+        let mut coefficients = self.coefficients.borrow_mut();
+        let mut total_loss = 0.;
+        for (sample, hidden) in input.axis_iter(Axis(0)).enumerate() {
+            let target = to_class(target[sample], weight.nrows());
+            let mut row_classes = classes.row_mut(sample);
+            row_classes[0] = target;
+            for class in row_classes.iter_mut().skip(1) {
+                *class = self.sampler.sample();
+            }
+
+            // The negatives that happen to be the target are left out.
+            let logits: Vec<Option<f32>> = row_classes
+                .iter()
+                .enumerate()
+                .map(|(position, &class)| {
+                    (position == 0 || class != target).then(|| hidden.dot(&weight.row(class)))
+                })
+                .collect();
+
+            let mut row_coefficients = coefficients.row_mut(sample);
+            row_coefficients.fill(0.);
+            match self.objective {
+                SampledObjective::Softmax => {
+                    // The sum over the negatives estimates the one over all the other classes.
+                    let adjusted: Vec<Option<f32>> = logits
+                        .iter()
+                        .zip(row_classes.iter())
+                        .enumerate()
+                        .map(|(position, (logit, &class))| {
+                            logit.map(|logit| {
+                                if position == 0 {
+                                    logit
+                                } else {
+                                    logit - self.sampler.log_expected_count(class)
+                                }
+                            })
+                        })
+                        .collect();
+                    let max = adjusted
+                        .iter()
+                        .flatten()
+                        .fold(f32::NEG_INFINITY, |max, &logit| max.max(logit));
+                    let log_partition = max
+                        + adjusted
+                            .iter()
+                            .flatten()
+                            .map(|logit| (logit - max).exp())
+                            .sum::<f32>()
+                            .ln();
+
+                    total_loss += log_partition - adjusted[0].unwrap();
+                    for (coefficient, logit) in row_coefficients.iter_mut().zip(&adjusted) {
+                        if let Some(logit) = logit {
+                            *coefficient = (logit - log_partition).exp();
+                        }
+                    }
+                    row_coefficients[0] -= 1.;
+                }
+                SampledObjective::NegativeSampling => {
+                    let target_logit = logits[0].unwrap();
+                    total_loss += softplus(-target_logit);
+                    row_coefficients[0] = sigmoid(target_logit) - 1.;
+                    for (coefficient, logit) in row_coefficients.iter_mut().zip(&logits).skip(1) {
+                        if let Some(logit) = logit {
+                            total_loss += softplus(*logit);
+                            *coefficient = sigmoid(*logit);
+                        }
+                    }
+                }
+            }
+        }
+
+        *self.data.borrow_mut() = match self.reduction {
+            Reduction::Mean => arr0(total_loss / batch_size.max(1) as f32),
+            Reduction::Sum => arr0(total_loss),
+        };
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, I: ?Sized> Data for SampledSoftmaxLoss<T, U, I>
+where
+    T: Data<Dim = Ix2>,
+    U: Data<Dim = Ix2>,
+    I: IndexData<Dim = Ix1>,
+{
+    type Dim = Ix0;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, I: ?Sized> Debug for SampledSoftmaxLoss<T, U, I>
+where
+    T: Data<Dim = Ix2>,
+    U: Data<Dim = Ix2>,
+    I: IndexData<Dim = Ix1>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SampledSoftmaxLoss")
+            .field("data", &self.data.borrow())
+            .field("objective", &self.objective)
+            .field("reduction", &self.reduction)
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, I: ?Sized> Display for SampledSoftmaxLoss<T, U, I>
+where
+    T: Data<Dim = Ix2>,
+    U: Data<Dim = Ix2>,
+    I: IndexData<Dim = Ix1>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ SampledSoftmaxLossBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct SampledSoftmaxLossBackward<TG: ?Sized, UG: ?Sized, T: ?Sized, U: ?Sized, I: ?Sized>
+where
+    TG: Gradient<Dim = Ix2>,
+    UG: Gradient<Dim = Ix2>,
+    T: Data<Dim = Ix2>,
+    U: Data<Dim = Ix2>,
+    I: IndexData<Dim = Ix1>,
+{
+    gradient: RefCell<Option<Tensor<Ix0>>>,
+    overwrite: Cell<bool>,
+    input_grad: Rc<TG>,
+    weight_grad: Rc<UG>,
+    forward: Rc<SampledSoftmaxLoss<T, U, I>>,
+}
+
+impl<TG: ?Sized, UG: ?Sized, T: ?Sized, U: ?Sized, I: ?Sized>
+    SampledSoftmaxLossBackward<TG, UG, T, U, I>
+where
+    TG: Gradient<Dim = Ix2>,
+    UG: Gradient<Dim = Ix2>,
+    T: Data<Dim = Ix2>,
+    U: Data<Dim = Ix2>,
+    I: IndexData<Dim = Ix1>,
+{
+    pub(crate) fn new(
+        input_grad: Rc<TG>,
+        weight_grad: Rc<UG>,
+        forward: Rc<SampledSoftmaxLoss<T, U, I>>,
+    ) -> Self {
+        Self {
+            gradient: RefCell::new(Some(arr0(0.))),
+            overwrite: Cell::new(true),
+            input_grad,
+            weight_grad,
+            forward,
+        }
+    }
+}
+
+impl<TG: ?Sized, UG: ?Sized, T: ?Sized, U: ?Sized, I: ?Sized> Gradient
+    for SampledSoftmaxLossBackward<TG, UG, T, U, I>
+where
+    TG: Gradient<Dim = Ix2>,
+    UG: Gradient<Dim = Ix2>,
+    T: Data<Dim = Ix2>,
+    U: Data<Dim = Ix2>,
+    I: IndexData<Dim = Ix1>,
+{
+    type Dim = Ix0;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<TG: ?Sized, UG: ?Sized, T: ?Sized, U: ?Sized, I: ?Sized> Overwrite
+    for SampledSoftmaxLossBackward<TG, UG, T, U, I>
+where
+    TG: Gradient<Dim = Ix2>,
+    UG: Gradient<Dim = Ix2>,
+    T: Data<Dim = Ix2>,
+    U: Data<Dim = Ix2>,
+    I: IndexData<Dim = Ix1>,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<TG: ?Sized, UG: ?Sized, T: ?Sized, U: ?Sized, I: ?Sized> Backward
+    for SampledSoftmaxLossBackward<TG, UG, T, U, I>
+where
+    TG: Gradient<Dim = Ix2>,
+    UG: Gradient<Dim = Ix2>,
+    T: Data<Dim = Ix2>,
+    U: Data<Dim = Ix2>,
+    I: IndexData<Dim = Ix1>,
+{
+    fn backward(&self) {
+        let forward = &self.forward;
+        let (input, weight) = (forward.input.data(), forward.weight.data());
+        let (classes, coefficients) = (forward.classes(), forward.coefficients.borrow());
+        let seed = match forward.reduction {
+            Reduction::Mean => self.gradient()[()] / input.nrows().max(1) as f32,
+            Reduction::Sum => self.gradient()[()],
+        };
+
+        let mut input_grad = Tensor::zeros(input.raw_dim());
+        let mut weight_grad = self.weight_grad.gradient_mut();
+        // Only the rows of the target and of the negatives receive a gradient.
+        if self.weight_grad.can_overwrite() {
+            weight_grad.fill(0.);
+            self.weight_grad.set_overwrite(false);
+        }
+        for (sample, hidden) in input.axis_iter(Axis(0)).enumerate() {
+            let mut hidden_grad = input_grad.row_mut(sample);
+            for (&class, &coefficient) in classes.row(sample).iter().zip(coefficients.row(sample)) {
+                if coefficient == 0. {
+                    continue;
+                }
+                hidden_grad.scaled_add(seed * coefficient, &weight.row(class));
+                weight_grad
+                    .row_mut(class)
+                    .scaled_add(seed * coefficient, &hidden);
+            }
+        }
+
+        push_gradient(&*self.input_grad, &input_grad);
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(arr0(0.));
+    }
+}
+
+impl<TG: ?Sized, UG: ?Sized, T: ?Sized, U: ?Sized, I: ?Sized> Debug
+    for SampledSoftmaxLossBackward<TG, UG, T, U, I>
+where
+    TG: Gradient<Dim = Ix2>,
+    UG: Gradient<Dim = Ix2>,
+    T: Data<Dim = Ix2>,
+    U: Data<Dim = Ix2>,
+    I: IndexData<Dim = Ix1>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SampledSoftmaxLossBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<TG: ?Sized, UG: ?Sized, T: ?Sized, U: ?Sized, I: ?Sized> Display
+    for SampledSoftmaxLossBackward<TG, UG, T, U, I>
+where
+    TG: Gradient<Dim = Ix2>,
+    UG: Gradient<Dim = Ix2>,
+    T: Data<Dim = Ix2>,
+    U: Data<Dim = Ix2>,
+    I: IndexData<Dim = Ix1>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
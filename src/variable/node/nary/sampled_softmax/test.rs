@@ -0,0 +1,317 @@
+use super::{
+    assert_almost_equals, new_backward_input, new_index_input, new_input, new_tensor, Backward,
+    Cache, Data, Forward, Gradient, NegativeSampler, Overwrite, Reduction, SampledObjective,
+    SampledSoftmaxLoss, SampledSoftmaxLossBackward, Tensor,
+};
+use crate::variable::{IndexInput, Input, InputBackward};
+use ndarray::{arr0, Ix1, Ix2};
+use std::rc::Rc;
+
+// Two hidden states over 4 classes. The sampler always draws class 2, which is the target of the
+// second hidden state and is therefore an accidental hit for it.
+const INPUT: [f32; 4] = [1., 0., 0.5, -1.];
+const WEIGHT: [f32; 8] = [0.1, 0.2, 0.3, -0.4, 0.5, 0.6, -0.7, 0.8];
+const TARGET: [i64; 2] = [0, 2];
+
+fn new_sampler() -> NegativeSampler {
+    NegativeSampler::unigram(&[0, 0, 1, 0], 1., 2)
+}
+
+fn new_loss(
+    target: Vec<i64>,
+    objective: SampledObjective,
+    reduction: Reduction,
+) -> SampledSoftmaxLoss<Input<Ix2>, Input<Ix2>, IndexInput<Ix1>> {
+    SampledSoftmaxLoss::new(
+        new_input((2, 2), INPUT.to_vec()),
+        new_input((4, 2), WEIGHT.to_vec()),
+        new_index_input(2, target),
+        new_sampler(),
+        objective,
+        reduction,
+    )
+}
+
+type Node = SampledSoftmaxLossBackward<
+    InputBackward<Ix2>,
+    InputBackward<Ix2>,
+    Input<Ix2>,
+    Input<Ix2>,
+    IndexInput<Ix1>,
+>;
+
+type BackwardInputs = (Rc<InputBackward<Ix2>>, Rc<InputBackward<Ix2>>);
+
+fn new_loss_backward(objective: SampledObjective) -> (Node, BackwardInputs) {
+    let inputs = (
+        new_backward_input((2, 2), vec![0.; 4]),
+        new_backward_input((4, 2), vec![0.; 8]),
+    );
+    let forward = Rc::new(new_loss(TARGET.to_vec(), objective, Reduction::Mean));
+    forward.forward();
+    let node = SampledSoftmaxLossBackward::new(inputs.0.clone(), inputs.1.clone(), forward);
+
+    (node, inputs)
+}
+
+mod forward {
+    use super::{
+        arr0, assert_almost_equals, new_index_input, new_input, new_loss, new_sampler, Cache, Data,
+        Forward, NegativeSampler, Reduction, SampledObjective, SampledSoftmaxLoss, INPUT, TARGET,
+        WEIGHT,
+    };
+
+    #[test]
+    fn creation() {
+        let node = new_loss(TARGET.to_vec(), SampledObjective::Softmax, Reduction::Mean);
+
+        assert_eq!(*node.data(), arr0(0.));
+        assert_eq!(*node.data_mut(), arr0(0.));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "error: an input of shape [2, 2] does not match an output embedding of shape [4, 3]."
+    )]
+    fn creation_fail_features() {
+        SampledSoftmaxLoss::new(
+            new_input((2, 2), INPUT.to_vec()),
+            new_input((4, 3), vec![0.; 12]),
+            new_index_input(2, TARGET.to_vec()),
+            new_sampler(),
+            SampledObjective::Softmax,
+            Reduction::Mean,
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "error: a target of shape [3] does not match an input of shape [2, 2]."
+    )]
+    fn creation_fail_target() {
+        SampledSoftmaxLoss::new(
+            new_input((2, 2), INPUT.to_vec()),
+            new_input((4, 2), WEIGHT.to_vec()),
+            new_index_input(3, vec![0; 3]),
+            new_sampler(),
+            SampledObjective::Softmax,
+            Reduction::Mean,
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "error: the sampler draws from 5 classes, but the output embedding has 4."
+    )]
+    fn creation_fail_sampler() {
+        SampledSoftmaxLoss::new(
+            new_input((2, 2), INPUT.to_vec()),
+            new_input((4, 2), WEIGHT.to_vec()),
+            new_index_input(2, TARGET.to_vec()),
+            NegativeSampler::uniform(5, 2),
+            SampledObjective::Softmax,
+            Reduction::Mean,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "error: target 4 is out of range for 4 classes.")]
+    fn forward_fail_target() {
+        let node = new_loss(vec![0, 4], SampledObjective::Softmax, Reduction::Mean);
+
+        node.forward();
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let node = new_loss(TARGET.to_vec(), SampledObjective::Softmax, Reduction::Mean);
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward_softmax() {
+        let node = new_loss(TARGET.to_vec(), SampledObjective::Softmax, Reduction::Sum);
+
+        node.forward();
+        assert_almost_equals(&*node.data(), &arr0(0.913015));
+        assert_eq!(*node.classes(), ndarray::array![[0, 2, 2], [2, 2, 2]]);
+
+        let node = new_loss(TARGET.to_vec(), SampledObjective::Softmax, Reduction::Mean);
+
+        node.forward();
+        assert_almost_equals(&*node.data(), &arr0(0.456508));
+    }
+
+    #[test]
+    fn forward_negative_sampling() {
+        let node = new_loss(
+            TARGET.to_vec(),
+            SampledObjective::NegativeSampling,
+            Reduction::Sum,
+        );
+
+        node.forward();
+        assert_almost_equals(&*node.data(), &arr0(3.475933));
+
+        let node = new_loss(
+            TARGET.to_vec(),
+            SampledObjective::NegativeSampling,
+            Reduction::Mean,
+        );
+
+        node.forward();
+        assert_almost_equals(&*node.data(), &arr0(1.737966));
+    }
+
+    #[test]
+    fn debug() {
+        let node = new_loss(TARGET.to_vec(), SampledObjective::Softmax, Reduction::Mean);
+
+        let output = "SampledSoftmaxLoss { data: 0.0, shape=[], strides=[], layout=CFcf (0xf), const ndim=0, objective: Softmax, reduction: Mean, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = new_loss(TARGET.to_vec(), SampledObjective::Softmax, Reduction::Mean);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        arr0, assert_almost_equals, new_loss_backward, new_tensor, Backward, Gradient, Overwrite,
+        SampledObjective, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let (node, _) = new_loss_backward(SampledObjective::Softmax);
+
+        assert_eq!(*node.gradient(), arr0(0.));
+        assert_eq!(*node.gradient_mut(), arr0(0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn computation_state_transition() {
+        let (node, (input, weight)) = new_loss_backward(SampledObjective::Softmax);
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!input.can_overwrite());
+        assert!(!weight.can_overwrite());
+
+        input.set_overwrite(true);
+        weight.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(input.can_overwrite());
+        assert!(weight.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(input.can_overwrite());
+        assert!(weight.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!input.can_overwrite());
+        assert!(!weight.can_overwrite());
+    }
+
+    #[test]
+    fn backward_softmax() {
+        let (node, (input, weight)) = new_loss_backward(SampledObjective::Softmax);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = arr0(1.);
+        assert_almost_equals(&*node.gradient(), &arr0(1.));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        // The accidental hits of the second hidden state leave it without a gradient.
+        node.backward();
+        assert_almost_equals(
+            &*input.gradient(),
+            &new_tensor((2, 2), vec![0.119738, 0.119738, 0., 0.]),
+        );
+        assert_almost_equals(
+            &*weight.gradient(),
+            &new_tensor((4, 2), vec![-0.299344, 0., 0., 0., 0.299344, 0., 0., 0.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*weight.gradient(),
+            &new_tensor((4, 2), vec![-0.598688, 0., 0., 0., 0.598688, 0., 0., 0.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        weight.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*weight.gradient(),
+            &new_tensor((4, 2), vec![-0.299344, 0., 0., 0., 0.299344, 0., 0., 0.]),
+        );
+    }
+
+    #[test]
+    fn backward_negative_sampling() {
+        let (node, (input, weight)) = new_loss_backward(SampledObjective::NegativeSampling);
+
+        *node.gradient_mut() = arr0(1.);
+        node.backward();
+        assert_almost_equals(
+            &*input.gradient(),
+            &new_tensor((2, 2), vec![0.287479, 0.325974, -0.146654, -0.175985]),
+        );
+        assert_almost_equals(
+            &*weight.gradient(),
+            &new_tensor(
+                (4, 2),
+                vec![-0.23751, 0., 0., 0., 0.475805, 0.293309, 0., 0.],
+            ),
+        );
+    }
+
+    #[test]
+    fn no_grad() {
+        let (node, _) = new_loss_backward(SampledObjective::Softmax);
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(()));
+    }
+
+    #[test]
+    fn debug() {
+        let (node, _) = new_loss_backward(SampledObjective::Softmax);
+
+        let output = "SampledSoftmaxLossBackward { gradient: Some(0.0, shape=[], strides=[], layout=CFcf (0xf), const ndim=0), overwrite: true }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let (node, _) = new_loss_backward(SampledObjective::Softmax);
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+}
@@ -0,0 +1,183 @@
+#[cfg(test)]
+use super::{new_backward_input, new_input, Chunk, ChunkBackward};
+use super::{
+    Backward, Cache, Data, Forward, Gradient, MultiData, MultiGradient, Overwrite, Tensor,
+};
+use std::{
+    cell::{Ref, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Output ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct Output<T: ?Sized>
+where
+    T: MultiData,
+{
+    operand: Rc<T>,
+    output: usize,
+}
+
+impl<T: ?Sized> Output<T>
+where
+    T: MultiData,
+{
+    pub fn new(operand: Rc<T>, output: usize) -> Self {
+        Self { operand, output }
+    }
+}
+
+impl<T: ?Sized> Cache for Output<T>
+where
+    T: MultiData,
+{
+    fn was_computed(&self) -> bool {
+        self.operand.was_computed()
+    }
+
+    fn reset_computation(&self) {
+        self.operand.reset_computation();
+    }
+}
+
+impl<T: ?Sized> Forward for Output<T>
+where
+    T: MultiData,
+{
+    fn forward(&self) {
+        // The computation is shared among all the outputs and cached by the operand.
+        self.operand.forward();
+    }
+}
+
+impl<T: ?Sized> Data for Output<T>
+where
+    T: MultiData,
+{
+    type Dim = T::Dim;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.operand.data_of(self.output)
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.operand.data_of_mut(self.output)
+    }
+}
+
+impl<T: ?Sized> Debug for Output<T>
+where
+    T: MultiData,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Output")
+            .field("data", &*self.data())
+            .field("output", &self.output)
+            .field("computed", &self.was_computed())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for Output<T>
+where
+    T: MultiData,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ OutputBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct OutputBackward<T: ?Sized>
+where
+    T: MultiGradient,
+{
+    operand: Rc<T>,
+    output: usize,
+}
+
+impl<T: ?Sized> OutputBackward<T>
+where
+    T: MultiGradient,
+{
+    pub fn new(operand: Rc<T>, output: usize) -> Self {
+        Self { operand, output }
+    }
+}
+
+impl<T: ?Sized> Gradient for OutputBackward<T>
+where
+    T: MultiGradient,
+{
+    type Dim = T::Dim;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        self.operand.gradient_of(self.output)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.operand.gradient_of_mut(self.output)
+    }
+}
+
+impl<T: ?Sized> Overwrite for OutputBackward<T>
+where
+    T: MultiGradient,
+{
+    fn can_overwrite(&self) -> bool {
+        self.operand.can_overwrite_of(self.output)
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.operand.set_overwrite_of(self.output, state);
+    }
+}
+
+impl<T: ?Sized> Backward for OutputBackward<T>
+where
+    T: MultiGradient,
+{
+    fn backward(&self) {
+        // The operand back-propagates the gradients of all its outputs at once, after them.
+    }
+
+    fn no_grad(&self) {
+        // The gradient is owned by the operand.
+    }
+
+    fn with_grad(&self) {
+        // The gradient is owned by the operand.
+    }
+}
+
+impl<T: ?Sized> Debug for OutputBackward<T>
+where
+    T: MultiGradient,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OutputBackward")
+            .field("gradient", &*self.gradient())
+            .field("output", &self.output)
+            .field("overwrite", &self.can_overwrite())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for OutputBackward<T>
+where
+    T: MultiGradient,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.gradient(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
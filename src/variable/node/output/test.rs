@@ -0,0 +1,94 @@
+use super::{
+    new_backward_input, new_input, Backward, Cache, Chunk, ChunkBackward, Data, Forward, Gradient,
+    MultiData, MultiGradient, Output, OutputBackward, Overwrite,
+};
+use std::rc::Rc;
+
+mod forward {
+    use super::{new_input, Cache, Chunk, Data, Forward, MultiData, Output, Rc};
+
+    #[test]
+    fn shared_computation() {
+        let input = new_input((2, 2), vec![1., 2., 3., 4.]);
+        let chunk = Rc::new(Chunk::new(input.clone(), ndarray::Dim([1, 2])));
+        let (first, second) = (Output::new(chunk.clone(), 0), Output::new(chunk.clone(), 1));
+
+        *input.data_mut() = ndarray::array![[5., 6.], [7., 8.]];
+        first.forward();
+        assert!(first.was_computed());
+        assert!(second.was_computed());
+        assert_eq!(*first.data(), ndarray::array![[5., 6.]]);
+        assert_eq!(*second.data(), *chunk.data_of(1));
+
+        second.reset_computation();
+        assert!(!first.was_computed());
+        assert!(!chunk.was_computed());
+    }
+
+    #[test]
+    fn debug() {
+        let input = new_input(2, vec![0.; 2]);
+        let node = Output::new(Rc::new(Chunk::new(input, ndarray::Dim([1]))), 1);
+
+        let output = "Output { data: [0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1, output: 1, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let input = new_input(2, vec![0.; 2]);
+        let node = Output::new(Rc::new(Chunk::new(input, ndarray::Dim([1]))), 1);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        new_backward_input, Backward, ChunkBackward, Gradient, MultiGradient, OutputBackward,
+        Overwrite, Rc,
+    };
+
+    #[test]
+    fn shared_gradient() {
+        let diff = new_backward_input((2, 2), vec![0.; 4]);
+        let chunk = Rc::new(ChunkBackward::new(diff.clone(), ndarray::Dim([1, 2]), 2));
+        let (first, second) = (
+            OutputBackward::new(chunk.clone(), 0),
+            OutputBackward::new(chunk.clone(), 1),
+        );
+
+        *first.gradient_mut() = ndarray::array![[1., 2.]];
+        first.set_overwrite(false);
+        assert!(!chunk.can_overwrite_of(0));
+        assert!(second.can_overwrite());
+
+        // The outputs do not back-propagate on their own.
+        first.backward();
+        second.backward();
+        assert_eq!(*diff.gradient(), ndarray::Array::zeros((2, 2)));
+
+        chunk.backward();
+        assert_eq!(*diff.gradient(), ndarray::array![[1., 2.], [0., 0.]]);
+        assert!(first.can_overwrite());
+    }
+
+    #[test]
+    fn debug() {
+        let diff = new_backward_input(2, vec![0.; 2]);
+        let node = OutputBackward::new(Rc::new(ChunkBackward::new(diff, ndarray::Dim([1]), 2)), 0);
+
+        let output = "OutputBackward { gradient: [0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1, output: 0, overwrite: true }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let diff = new_backward_input(2, vec![0.; 2]);
+        let node = OutputBackward::new(Rc::new(ChunkBackward::new(diff, ndarray::Dim([1]), 2)), 0);
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+}
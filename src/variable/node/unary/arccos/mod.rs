@@ -0,0 +1,232 @@
+#[cfg(test)]
+use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
+use super::{
+    expect_tensor, expect_tensor_mut, fit_shape, Backward, Cache, Data, Forward, Gradient,
+    Overwrite, Tensor,
+};
+use ndarray::Zip;
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ ArcCos ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct ArcCos<T: ?Sized>
+where
+    T: Data,
+{
+    operand: Rc<T>,
+    data: RefCell<Tensor<T::Dim>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized> ArcCos<T>
+where
+    T: Data,
+{
+    pub fn new(operand: Rc<T>) -> Self {
+        let data = RefCell::new(Tensor::zeros(operand.data().raw_dim()));
+
+        Self {
+            operand,
+            data,
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized> Cache for ArcCos<T>
+where
+    T: Data,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized> Forward for ArcCos<T>
+where
+    T: Data,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        fit_shape(&self.data, self.operand.data().raw_dim());
+        Zip::from(&mut *self.data.borrow_mut())
+            .and(&*self.operand.data())
+            .for_each(|v, o| *v = o.acos());
+    }
+}
+
+impl<T: ?Sized> Data for ArcCos<T>
+where
+    T: Data,
+{
+    type Dim = T::Dim;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized> Debug for ArcCos<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArcCos")
+            .field("data", &self.data.borrow())
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for ArcCos<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ ArcCosBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct ArcCosBackward<T: ?Sized, U: ?Sized>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    gradient: RefCell<Option<Tensor<T::Dim>>>,
+    shape: T::Dim,
+    overwrite: Cell<bool>,
+    diff_operand: Rc<T>,
+    no_diff_operand: Rc<U>,
+}
+
+impl<T: ?Sized, U: ?Sized> ArcCosBackward<T, U>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    pub fn new(diff_operand: Rc<T>, no_diff_operand: Rc<U>) -> Self {
+        let shape = diff_operand.gradient().raw_dim();
+
+        Self {
+            gradient: RefCell::new(Some(Tensor::zeros(shape.clone()))),
+            shape,
+            overwrite: Cell::new(true),
+            diff_operand,
+            no_diff_operand,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Gradient for ArcCosBackward<T, U>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    type Dim = T::Dim;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Overwrite for ArcCosBackward<T, U>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Backward for ArcCosBackward<T, U>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    fn backward(&self) {
+        let mut op_grad = self.diff_operand.gradient_mut();
+        let data = self.no_diff_operand.data();
+        let grad = self.gradient();
+
+        let zip = Zip::from(&mut *op_grad).and(&*grad).and(&*data);
+        if self.diff_operand.can_overwrite() {
+            zip.for_each(|op_grad_el, grad_el, data_el| {
+                *op_grad_el = -*grad_el / (1. - data_el * data_el).sqrt()
+            });
+            self.diff_operand.set_overwrite(false);
+        } else {
+            zip.for_each(|op_grad_el, grad_el, data_el| {
+                *op_grad_el += -*grad_el / (1. - data_el * data_el).sqrt()
+            });
+        }
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape.clone()));
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Debug for ArcCosBackward<T, U>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArcCosBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Display for ArcCosBackward<T, U>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
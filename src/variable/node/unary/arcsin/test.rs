@@ -0,0 +1,242 @@
+use super::{
+    assert_almost_equals, new_backward_input, new_input, new_tensor, ArcSin, ArcSinBackward,
+    Backward, Cache, Data, Forward, Gradient, Overwrite, Tensor,
+};
+
+mod forward {
+    use super::{
+        assert_almost_equals, new_input, new_tensor, ArcSin, Cache, Data, Forward, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let input = new_input(
+            (3, 3),
+            vec![-0.8, -0.6, -0.4, -0.2, 0.1, 0.2, 0.4, 0.6, 0.8],
+        );
+        let node = ArcSin::new(input);
+
+        assert_eq!(*node.data(), Tensor::from_elem((3, 3), 0.));
+        assert_eq!(*node.data_mut(), Tensor::from_elem((3, 3), 0.));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let input = new_input(
+            (3, 3),
+            vec![-0.8, -0.6, -0.4, -0.2, 0.1, 0.2, 0.4, 0.6, 0.8],
+        );
+        let node = ArcSin::new(input);
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[allow(clippy::approx_constant)]
+    #[test]
+    fn forward() {
+        let input = new_input(
+            (3, 3),
+            vec![-0.8, -0.6, -0.4, -0.2, 0.1, 0.2, 0.4, 0.6, 0.8],
+        );
+        let node = ArcSin::new(input.clone());
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor(
+                (3, 3),
+                vec![
+                    -0.927295, -0.643501, -0.411517, -0.201358, 0.100167, 0.201358, 0.411517,
+                    0.643501, 0.927295,
+                ],
+            ),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        input.data_mut().mapv_inplace(|el| el / 2.);
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor(
+                (3, 3),
+                vec![
+                    -0.927295, -0.643501, -0.411517, -0.201358, 0.100167, 0.201358, 0.411517,
+                    0.643501, 0.927295,
+                ],
+            ),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor(
+                (3, 3),
+                vec![
+                    -0.411517, -0.304693, -0.201358, -0.100167, 0.0500209, 0.100167, 0.201358,
+                    0.304693, 0.411517,
+                ],
+            ),
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let input = new_input(
+            (3, 3),
+            vec![-0.8, -0.6, -0.4, -0.2, 0.1, 0.2, 0.4, 0.6, 0.8],
+        );
+        let node = ArcSin::new(input);
+
+        let output = "ArcSin { data: [[0.0, 0.0, 0.0],\n [0.0, 0.0, 0.0],\n [0.0, 0.0, 0.0]], shape=[3, 3], strides=[3, 1], layout=Cc (0x5), const ndim=2, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let input = new_input(
+            (3, 3),
+            vec![-0.8, -0.6, -0.4, -0.2, 0.1, 0.2, 0.4, 0.6, 0.8],
+        );
+        let node = ArcSin::new(input);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        assert_almost_equals, new_backward_input, new_input, new_tensor, ArcSinBackward, Backward,
+        Gradient, Overwrite, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let node = ArcSinBackward::new(
+            new_backward_input(3, vec![0.; 3]),
+            new_input(3, vec![-0.5, 0.1, 0.7]),
+        );
+
+        assert_eq!(*node.gradient(), Tensor::from_elem(3, 0.));
+        assert_eq!(*node.gradient_mut(), Tensor::from_elem(3, 0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn computation_state_transition() {
+        let diff = new_backward_input(3, vec![0.; 3]);
+        let node = ArcSinBackward::new(diff.clone(), new_input(3, vec![-0.5, 0.1, 0.7]));
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        diff.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        diff.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!diff.can_overwrite());
+    }
+
+    #[allow(clippy::approx_constant)]
+    #[test]
+    fn backward() {
+        let diff = new_backward_input(3, vec![0.; 3]);
+        let node = ArcSinBackward::new(diff.clone(), new_input(3, vec![-0.5, 0.1, 0.7]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor(3, vec![1.; 3]);
+        assert_almost_equals(&*node.gradient(), &new_tensor(3, vec![1.; 3]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(3, vec![1.1547, 1.00504, 1.40028]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(3, vec![2.3094, 2.01008, 2.80056]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(3, vec![1.1547, 1.00504, 1.40028]),
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let diff = new_backward_input(3, vec![0.; 3]);
+        let node = ArcSinBackward::new(diff, new_input(3, vec![-0.5, 0.1, 0.7]));
+
+        let output = "ArcSinBackward { gradient: Some([0.0, 0.0, 0.0], shape=[3], strides=[1], layout=CFcf (0xf), const ndim=1), overwrite: true }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let diff = new_backward_input(3, vec![0.; 3]);
+        let node = ArcSinBackward::new(diff, new_input(3, vec![-0.5, 0.1, 0.7]));
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+
+    #[test]
+    fn no_grad() {
+        // ArcSinBackward
+        let node = ArcSinBackward::new(
+            new_backward_input((3, 3), vec![0.; 9]),
+            new_input((3, 3), vec![0.; 9]),
+        );
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+    }
+}
@@ -0,0 +1,338 @@
+#[cfg(test)]
+use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
+use super::{
+    expect_tensor, expect_tensor_mut, fit_shape, Backward, Cache, Data, Eval, Forward, Gradient,
+    Overwrite, Tensor,
+};
+use ndarray::{Axis, Dimension, Ix1, Zip};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ BatchNorm ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct BatchNorm<T: ?Sized>
+where
+    T: Data,
+{
+    operand: Rc<T>,
+    data: RefCell<Tensor<T::Dim>>,
+    inv_std: RefCell<Tensor<Ix1>>,
+    running_mean: Rc<RefCell<Tensor<Ix1>>>,
+    running_var: Rc<RefCell<Tensor<Ix1>>>,
+    momentum: f32,
+    eps: f32,
+    computed: Cell<bool>,
+    train: Rc<Cell<bool>>,
+}
+
+impl<T: ?Sized> BatchNorm<T>
+where
+    T: Data,
+{
+    pub fn new(
+        operand: Rc<T>,
+        running_mean: Rc<RefCell<Tensor<Ix1>>>,
+        running_var: Rc<RefCell<Tensor<Ix1>>>,
+        momentum: f32,
+        eps: f32,
+        status: Rc<Cell<bool>>,
+    ) -> Self {
+        let shape = operand.data().raw_dim();
+        let channels = running_mean.borrow().len();
+        assert!(
+            shape.ndim() >= 2 && shape[1] == channels,
+            "error: batch normalization over {} channels expects an input of shape (N, {}, ...), got {:?}.",
+            channels,
+            channels,
+            shape.slice()
+        );
+
+        Self {
+            operand,
+            data: RefCell::new(Tensor::zeros(shape)),
+            inv_std: RefCell::new(Tensor::zeros(channels)),
+            running_mean,
+            running_var,
+            momentum,
+            eps,
+            computed: Cell::new(false),
+            train: status,
+        }
+    }
+
+    /// Returns the inverse of the standard deviation of each channel used by the last
+    /// evaluation.
+    pub(crate) fn inv_std(&self) -> Ref<Tensor<Ix1>> {
+        self.inv_std.borrow()
+    }
+
+    pub(crate) fn status(&self) -> Rc<Cell<bool>> {
+        self.train.clone()
+    }
+}
+
+impl<T: ?Sized> Cache for BatchNorm<T>
+where
+    T: Data,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized> Forward for BatchNorm<T>
+where
+    T: Data,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let operand_data = self.operand.data();
+        fit_shape(&self.data, operand_data.raw_dim());
+
+        let (mut data, mut inv_std) = (self.data.borrow_mut(), self.inv_std.borrow_mut());
+        let (mut running_mean, mut running_var) = (
+            self.running_mean.borrow_mut(),
+            self.running_var.borrow_mut(),
+        );
+        let (operand_data, mut data) = (operand_data.view().into_dyn(), data.view_mut().into_dyn());
+        let size = operand_data.len() / inv_std.len();
+
+        for channel in 0..inv_std.len() {
+            let input = operand_data.index_axis(Axis(1), channel);
+            let (mean, var) = if self.train.get() {
+                let mean = input.sum() / size as f32;
+                let var = input.fold(0., |acc, el| acc + (el - mean).powi(2)) / size as f32;
+                let unbiased = var * size as f32 / (size.max(2) - 1) as f32;
+                running_mean[channel] =
+                    (1. - self.momentum) * running_mean[channel] + self.momentum * mean;
+                running_var[channel] =
+                    (1. - self.momentum) * running_var[channel] + self.momentum * unbiased;
+                (mean, var)
+            } else {
+                (running_mean[channel], running_var[channel])
+            };
+
+            let channel_inv_std = 1. / (var + self.eps).sqrt();
+            inv_std[channel] = channel_inv_std;
+            Zip::from(data.index_axis_mut(Axis(1), channel))
+                .and(&input)
+                .for_each(|data_el, input_el| *data_el = (input_el - mean) * channel_inv_std);
+        }
+    }
+}
+
+impl<T: ?Sized> Data for BatchNorm<T>
+where
+    T: Data,
+{
+    type Dim = T::Dim;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized> Eval for BatchNorm<T>
+where
+    T: Data,
+{
+    fn train(&self) {
+        self.train.set(true);
+    }
+
+    fn eval(&self) {
+        self.train.set(false);
+    }
+}
+
+impl<T: ?Sized> Debug for BatchNorm<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BatchNorm")
+            .field("data", &self.data.borrow())
+            .field("momentum", &self.momentum)
+            .field("eps", &self.eps)
+            .field("train", &self.train.get())
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for BatchNorm<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ BatchNormBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct BatchNormBackward<T: ?Sized, U: ?Sized>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    gradient: RefCell<Option<Tensor<T::Dim>>>,
+    shape: T::Dim,
+    overwrite: Cell<bool>,
+    diff_operand: Rc<T>,
+    no_diff_operand: Rc<BatchNorm<U>>,
+}
+
+impl<T: ?Sized, U: ?Sized> BatchNormBackward<T, U>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    pub fn new(diff_operand: Rc<T>, no_diff_operand: Rc<BatchNorm<U>>) -> Self {
+        let shape = diff_operand.gradient().raw_dim();
+
+        Self {
+            gradient: RefCell::new(Some(Tensor::zeros(shape.clone()))),
+            shape,
+            overwrite: Cell::new(true),
+            diff_operand,
+            no_diff_operand,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Gradient for BatchNormBackward<T, U>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    type Dim = T::Dim;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Overwrite for BatchNormBackward<T, U>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Backward for BatchNormBackward<T, U>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    fn backward(&self) {
+        let mut op_grad = self.diff_operand.gradient_mut();
+        if self.diff_operand.can_overwrite() {
+            op_grad.fill(0.);
+            self.diff_operand.set_overwrite(false);
+        }
+
+        let (grad, normalized) = (self.gradient(), self.no_diff_operand.data());
+        let inv_std = self.no_diff_operand.inv_std();
+        let train = self.no_diff_operand.status().get();
+        let (grad, normalized, mut op_grad) = (
+            grad.view().into_dyn(),
+            normalized.view().into_dyn(),
+            op_grad.view_mut().into_dyn(),
+        );
+        let size = grad.len() / inv_std.len();
+
+        for (channel, channel_inv_std) in inv_std.iter().enumerate() {
+            let grad = grad.index_axis(Axis(1), channel);
+            let normalized = normalized.index_axis(Axis(1), channel);
+            let zip = Zip::from(op_grad.index_axis_mut(Axis(1), channel))
+                .and(&grad)
+                .and(&normalized);
+
+            if train {
+                // The batch statistics depend on the input, too.
+                let grad_mean = grad.sum() / size as f32;
+                let proj_mean = Zip::from(&grad)
+                    .and(&normalized)
+                    .fold(0., |acc, grad_el, normalized_el| {
+                        acc + grad_el * normalized_el
+                    })
+                    / size as f32;
+                zip.for_each(|op_grad_el, grad_el, normalized_el| {
+                    *op_grad_el +=
+                        channel_inv_std * (grad_el - grad_mean - normalized_el * proj_mean)
+                });
+            } else {
+                zip.for_each(|op_grad_el, grad_el, _| *op_grad_el += channel_inv_std * grad_el);
+            }
+        }
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape.clone()));
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Debug for BatchNormBackward<T, U>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BatchNormBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Display for BatchNormBackward<T, U>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,239 @@
+use super::{
+    assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, BatchNorm,
+    BatchNormBackward, Cache, Data, Eval, Forward, Gradient, Overwrite, Tensor,
+};
+use crate::variable::{Input, InputBackward};
+use ndarray::{Ix1, Ix2};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+type Running = Rc<RefCell<Tensor<Ix1>>>;
+
+fn running(channels: usize) -> (Running, Running) {
+    (
+        Rc::new(RefCell::new(Tensor::zeros(channels))),
+        Rc::new(RefCell::new(Tensor::ones(channels))),
+    )
+}
+
+mod forward {
+    use super::{
+        assert_almost_equals, new_input, new_tensor, running, BatchNorm, Cache, Cell, Data, Eval,
+        Forward, Rc, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let (mean, var) = running(2);
+        let input = new_input((2, 2), vec![1., 2., 3., 6.]);
+        let node = BatchNorm::new(input, mean, var, 0.1, 0., Rc::new(Cell::new(true)));
+
+        assert_eq!(*node.data(), Tensor::from_elem((2, 2), 0.));
+        assert_eq!(*node.data_mut(), Tensor::from_elem((2, 2), 0.));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "error: batch normalization over 3 channels expects an input of shape (N, 3, ...), got [2, 2]."
+    )]
+    fn wrong_channels() {
+        let (mean, var) = running(3);
+        let input = new_input((2, 2), vec![1., 2., 3., 6.]);
+        BatchNorm::new(input, mean, var, 0.1, 0., Rc::new(Cell::new(true)));
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let (mean, var) = running(2);
+        let input = new_input((2, 2), vec![1., 2., 3., 6.]);
+        let node = BatchNorm::new(input, mean, var, 0.1, 0., Rc::new(Cell::new(true)));
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward_train() {
+        let (mean, var) = running(2);
+        let input = new_input((2, 2), vec![1., 2., 3., 6.]);
+        let node = BatchNorm::new(
+            input,
+            mean.clone(),
+            var.clone(),
+            0.1,
+            0.,
+            Rc::new(Cell::new(true)),
+        );
+
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((2, 2), vec![-1., -1., 1., 1.]));
+        assert_almost_equals(&*node.inv_std(), &new_tensor(2, vec![1., 0.5]));
+
+        // The running variance is unbiased.
+        assert_almost_equals(&*mean.borrow(), &new_tensor(2, vec![0.2, 0.4]));
+        assert_almost_equals(&*var.borrow(), &new_tensor(2, vec![1.1, 1.7]));
+    }
+
+    #[test]
+    fn forward_eval() {
+        let (mean, var) = running(2);
+        *mean.borrow_mut() = new_tensor(2, vec![1., 2.]);
+        *var.borrow_mut() = new_tensor(2, vec![4., 16.]);
+        let input = new_input((1, 2, 1, 2), vec![1., 3., 6., 10.]);
+        let node = BatchNorm::new(
+            input,
+            mean.clone(),
+            var.clone(),
+            0.1,
+            0.,
+            Rc::new(Cell::new(true)),
+        );
+
+        node.eval();
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((1, 2, 1, 2), vec![0., 1., 1., 2.]),
+        );
+        assert_almost_equals(&*mean.borrow(), &new_tensor(2, vec![1., 2.]));
+        assert_almost_equals(&*var.borrow(), &new_tensor(2, vec![4., 16.]));
+
+        node.train();
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((1, 2, 1, 2), vec![-1., 1., -1., 1.]),
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let (mean, var) = running(1);
+        let input = new_input((1, 1), vec![0.]);
+        let node = BatchNorm::new(input, mean, var, 0.1, 0., Rc::new(Cell::new(true)));
+
+        let output = "BatchNorm { data: [[0.0]], shape=[1, 1], strides=[1, 1], layout=CFcf (0xf), const ndim=2, momentum: 0.1, eps: 0.0, train: true, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let (mean, var) = running(1);
+        let input = new_input((1, 1), vec![0.]);
+        let node = BatchNorm::new(input, mean, var, 0.1, 0., Rc::new(Cell::new(true)));
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        assert_almost_equals, new_backward_input, new_input, new_tensor, running, Backward,
+        BatchNorm, BatchNormBackward, Cache, Cell, Eval, Forward, Gradient, Input, InputBackward,
+        Ix2, Overwrite, Rc, Tensor,
+    };
+
+    fn setup() -> (Rc<BatchNorm<Input<Ix2>>>, Rc<InputBackward<Ix2>>) {
+        let (mean, var) = running(1);
+        let input = new_input((3, 1), vec![-1., 0., 1.]);
+        let node = Rc::new(BatchNorm::new(
+            input,
+            mean,
+            var,
+            0.1,
+            0.,
+            Rc::new(Cell::new(true)),
+        ));
+        node.forward();
+
+        (node, new_backward_input((3, 1), vec![0.; 3]))
+    }
+
+    #[test]
+    fn creation() {
+        let (forward, diff) = setup();
+        let node = BatchNormBackward::new(diff, forward);
+
+        assert_eq!(*node.gradient(), Tensor::from_elem((3, 1), 0.));
+        assert_eq!(*node.gradient_mut(), Tensor::from_elem((3, 1), 0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn backward_train() {
+        let (forward, diff) = setup();
+        let node = BatchNormBackward::new(diff.clone(), forward);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((3, 1), vec![1., 0., 0.]);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        let expected = new_tensor((3, 1), vec![0.20412, -0.40825, 0.20412]);
+        assert_almost_equals(&*diff.gradient(), &expected);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(&*diff.gradient(), &(&expected * 2.));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(&*diff.gradient(), &expected);
+    }
+
+    #[test]
+    fn backward_eval() {
+        let (forward, diff) = setup();
+        forward.eval();
+        forward.reset_computation();
+        forward.forward();
+        let node = BatchNormBackward::new(diff.clone(), forward);
+
+        *node.gradient_mut() = new_tensor((3, 1), vec![1., 0., 0.]);
+        node.backward();
+        assert_almost_equals(&*diff.gradient(), &new_tensor((3, 1), vec![1., 0., 0.]));
+    }
+
+    #[test]
+    fn debug() {
+        let (forward, diff) = setup();
+        let node = BatchNormBackward::new(diff, forward);
+
+        let output = "BatchNormBackward { gradient: Some([[0.0],\n [0.0],\n [0.0]], shape=[3, 1], strides=[1, 1], layout=CFcf (0xf), const ndim=2), overwrite: true }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let (forward, diff) = setup();
+        let node = BatchNormBackward::new(diff, forward);
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+
+    #[test]
+    fn no_grad() {
+        // BatchNormBackward
+        let (forward, diff) = setup();
+        let node = BatchNormBackward::new(diff, forward);
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+    }
+}
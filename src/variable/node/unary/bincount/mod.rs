@@ -0,0 +1,122 @@
+#[cfg(test)]
+use super::{new_index_input, new_tensor};
+use super::{Cache, Data, Forward, IndexData, Tensor};
+use ndarray::Ix1;
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// Converts the element `index` of an indices variable to one of `bins` bins.
+fn to_bin(index: i64, bins: usize) -> usize {
+    assert!(
+        index >= 0 && (index as usize) < bins,
+        "error: index {} is out of range for a count with {} bins.",
+        index,
+        bins
+    );
+    index as usize
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ BinCount ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct BinCount<T: ?Sized>
+where
+    T: IndexData,
+{
+    operand: Rc<T>,
+    data: RefCell<Tensor<Ix1>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized> BinCount<T>
+where
+    T: IndexData,
+{
+    pub fn new(operand: Rc<T>, bins: usize) -> Self {
+        let data = Tensor::zeros(bins);
+
+        Self {
+            operand,
+            data: RefCell::new(data),
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized> Cache for BinCount<T>
+where
+    T: IndexData,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized> Forward for BinCount<T>
+where
+    T: IndexData,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let mut data = self.data.borrow_mut();
+        data.fill(0.);
+        let bins = data.len();
+        self.operand
+            .data()
+            .iter()
+            .for_each(|&index| data[to_bin(index, bins)] += 1.);
+    }
+}
+
+impl<T: ?Sized> Data for BinCount<T>
+where
+    T: IndexData,
+{
+    type Dim = Ix1;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized> Debug for BinCount<T>
+where
+    T: IndexData,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BinCount")
+            .field("data", &self.data.borrow())
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for BinCount<T>
+where
+    T: IndexData,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,76 @@
+use super::{new_index_input, new_tensor, BinCount, Cache, Data, Forward, IndexData, Tensor};
+
+#[test]
+fn creation() {
+    let input = new_index_input((2, 3), vec![3, 1, 3, 0, 1, 1]);
+    let node = BinCount::new(input, 5);
+
+    assert_eq!(*node.data(), Tensor::from_elem(5, 0.));
+    assert_eq!(*node.data_mut(), Tensor::from_elem(5, 0.));
+    assert!(!node.was_computed());
+}
+
+#[test]
+fn computation_was_computed_transition() {
+    let input = new_index_input((2, 3), vec![3, 1, 3, 0, 1, 1]);
+    let node = BinCount::new(input, 5);
+
+    node.forward();
+    assert!(node.was_computed());
+
+    node.forward();
+    assert!(node.was_computed());
+
+    node.reset_computation();
+    assert!(!node.was_computed());
+
+    node.reset_computation();
+    assert!(!node.was_computed());
+}
+
+#[test]
+fn forward() {
+    let input = new_index_input((2, 3), vec![3, 1, 3, 0, 1, 1]);
+    let node = BinCount::new(input.clone(), 5);
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    node.forward();
+    assert_eq!(*node.data(), new_tensor(5, vec![1., 3., 0., 2., 0.]));
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    input.data_mut().fill(4);
+    node.forward();
+    assert_eq!(*node.data(), new_tensor(5, vec![1., 3., 0., 2., 0.]));
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    node.reset_computation();
+    node.forward();
+    assert_eq!(*node.data(), new_tensor(5, vec![0., 0., 0., 0., 6.]));
+}
+
+#[test]
+#[should_panic(expected = "error: index 5 is out of range for a count with 5 bins.")]
+fn forward_out_of_range() {
+    let input = new_index_input(2, vec![0, 5]);
+    let node = BinCount::new(input, 5);
+
+    node.forward();
+}
+
+#[test]
+fn debug() {
+    let input = new_index_input(1, vec![0]);
+    let node = BinCount::new(input, 1);
+
+    let output = "BinCount { data: [0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1, computed: false }";
+
+    assert_eq!(output, format!("{:?}", node));
+}
+
+#[test]
+fn display() {
+    let input = new_index_input(1, vec![0]);
+    let node = BinCount::new(input, 1);
+
+    assert_eq!(format!("{}", node.data()), format!("{}", node));
+}
@@ -1,15 +1,50 @@
 #[cfg(test)]
 use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
 use super::{
-    expect_tensor, expect_tensor_mut, Backward, Cache, Data, Forward, Gradient, Overwrite, Tensor,
+    expect_tensor, expect_tensor_mut, fit_shape, Backward, Cache, Data, Forward, Gradient,
+    MultiData, MultiGradient, Overwrite, Tensor,
 };
-use ndarray::Zip;
+use ndarray::{Dimension, Zip};
 use std::{
     cell::{Cell, Ref, RefCell, RefMut},
     fmt::{Debug, Display},
     rc::Rc,
 };
 
+/// Returns the shape of the chunks of an operand that had shape `shape` when it was split in
+/// chunks of shape `chunk_shape`, and whose shape is now `new_shape`. Along each axis the number
+/// of chunks and the size of the skipped remainder stay the same, so that the chunks follow the
+/// operand when its shape changes after the graph was built.
+///
+/// # Panics
+///
+/// If an axis of `new_shape` became too short for the chunks along it.
+fn fit_chunk_shape<D: Dimension>(shape: &D, chunk_shape: &D, new_shape: &D) -> D {
+    if shape == new_shape {
+        return chunk_shape.clone();
+    }
+
+    let mut fitted = chunk_shape.clone();
+    for (axis, size) in fitted.slice_mut().iter_mut().enumerate() {
+        let (len, new_len) = (shape[axis], new_shape[axis]);
+        let chunks = len / *size;
+        if chunks == 0 || len == new_len {
+            continue;
+        }
+
+        let remainder = len - chunks * *size;
+        *size = new_len.saturating_sub(remainder) / chunks;
+        assert!(
+            *size > 0,
+            "error: an operand of shape {:?} cannot be split in {} chunks along axis {}.",
+            new_shape.slice(),
+            chunks,
+            axis
+        );
+    }
+    fitted
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Chunk ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -18,9 +53,9 @@ where
     T: Data,
 {
     operand: Rc<T>,
-    chunk_no: usize,
+    shape: T::Dim,
     chunk_shape: T::Dim,
-    data: RefCell<Tensor<T::Dim>>,
+    data: Vec<RefCell<Tensor<T::Dim>>>,
     computed: Cell<bool>,
 }
 
@@ -28,15 +63,28 @@ impl<T: ?Sized> Chunk<T>
 where
     T: Data,
 {
-    pub fn new(operand: Rc<T>, chunk: Tensor<T::Dim>, chunk_no: usize) -> Self {
+    pub fn new(operand: Rc<T>, chunk_shape: T::Dim) -> Self {
+        let data = operand
+            .data()
+            .exact_chunks(chunk_shape.clone())
+            .into_iter()
+            .map(|chunk| RefCell::new(chunk.to_owned()))
+            .collect();
+        let shape = operand.data().raw_dim();
+
         Self {
             operand,
-            chunk_shape: chunk.raw_dim(),
-            data: RefCell::new(chunk),
-            chunk_no,
+            shape,
+            chunk_shape,
+            data,
             computed: Cell::new(false),
         }
     }
+
+    /// Returns the number of chunks.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
 }
 
 impl<T: ?Sized> Cache for Chunk<T>
@@ -62,37 +110,31 @@ where
         }
 
         self.computed.set(true);
-        let (mut data, operand_data, chunk_shape, chunk_no) = (
-            self.data.borrow_mut(),
-            self.operand.data(),
-            &self.chunk_shape,
-            self.chunk_no,
-        );
-
-        let operand_data_chunk = operand_data
+        let operand_data = self.operand.data();
+        let chunk_shape = fit_chunk_shape(&self.shape, &self.chunk_shape, &operand_data.raw_dim());
+        operand_data
             .exact_chunks(chunk_shape.clone())
             .into_iter()
-            .skip(chunk_no)
-            .take(1)
-            .next()
-            .unwrap();
-
-        data.assign(&operand_data_chunk);
+            .zip(&self.data)
+            .for_each(|(operand_data_chunk, data)| {
+                fit_shape(data, chunk_shape.clone());
+                data.borrow_mut().assign(&operand_data_chunk)
+            });
     }
 }
 
-impl<T: ?Sized> Data for Chunk<T>
+impl<T: ?Sized> MultiData for Chunk<T>
 where
     T: Data,
 {
     type Dim = T::Dim;
 
-    fn data(&self) -> Ref<Tensor<Self::Dim>> {
-        self.data.borrow()
+    fn data_of(&self, output: usize) -> Ref<Tensor<Self::Dim>> {
+        self.data[output].borrow()
     }
 
-    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
-        self.data.borrow_mut()
+    fn data_of_mut(&self, output: usize) -> RefMut<Tensor<Self::Dim>> {
+        self.data[output].borrow_mut()
     }
 }
 
@@ -102,8 +144,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Chunk")
-            .field("data", &self.data.borrow())
-            .field("chunk_no", &self.chunk_no)
+            .field("data", &self.data)
             .field("computed", &self.computed.get())
             .finish()
     }
@@ -114,7 +155,11 @@ where
     T: Data,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(f, "{}", &self.data.borrow())
+        for data in &self.data {
+            crate::print::fmt_tensor(&data.borrow(), f)?;
+            writeln!(f)?;
+        }
+        Ok(())
     }
 }
 
@@ -125,55 +170,67 @@ pub struct ChunkBackward<T: ?Sized>
 where
     T: Gradient,
 {
-    gradient: RefCell<Option<Tensor<T::Dim>>>,
+    gradients: Vec<RefCell<Option<Tensor<T::Dim>>>>,
+    overwrites: Vec<Cell<bool>>,
     shape: T::Dim,
+    chunk_shape: T::Dim,
     overwrite: Cell<bool>,
     operand: Rc<T>,
-    chunk_no: usize,
 }
 
 impl<T: ?Sized> ChunkBackward<T>
 where
     T: Gradient,
 {
-    pub fn new(operand: Rc<T>, grad_chunk: Tensor<T::Dim>, chunk_no: usize) -> Self {
-        let shape = grad_chunk.raw_dim();
+    pub fn new(operand: Rc<T>, chunk_shape: T::Dim, chunks: usize) -> Self {
+        let shape = operand.gradient().raw_dim();
 
         Self {
-            gradient: RefCell::new(Some(grad_chunk)),
+            gradients: (0..chunks)
+                .map(|_| RefCell::new(Some(Tensor::zeros(chunk_shape.clone()))))
+                .collect(),
+            overwrites: (0..chunks).map(|_| Cell::new(true)).collect(),
             shape,
+            chunk_shape,
             overwrite: Cell::new(true),
             operand,
-            chunk_no,
         }
     }
 }
 
-impl<T: ?Sized> Gradient for ChunkBackward<T>
+impl<T: ?Sized> Overwrite for ChunkBackward<T>
 where
     T: Gradient,
 {
-    type Dim = T::Dim;
-
-    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
-        expect_tensor(&self.gradient)
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
     }
 
-    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
-        expect_tensor_mut(&self.gradient)
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
     }
 }
 
-impl<T: ?Sized> Overwrite for ChunkBackward<T>
+impl<T: ?Sized> MultiGradient for ChunkBackward<T>
 where
     T: Gradient,
 {
-    fn can_overwrite(&self) -> bool {
-        self.overwrite.get()
+    type Dim = T::Dim;
+
+    fn gradient_of(&self, output: usize) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradients[output])
     }
 
-    fn set_overwrite(&self, state: bool) {
-        self.overwrite.set(state);
+    fn gradient_of_mut(&self, output: usize) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradients[output])
+    }
+
+    fn can_overwrite_of(&self, output: usize) -> bool {
+        self.overwrites[output].get()
+    }
+
+    fn set_overwrite_of(&self, output: usize, state: bool) {
+        self.overwrites[output].set(state);
     }
 }
 
@@ -182,32 +239,43 @@ where
     T: Gradient,
 {
     fn backward(&self) {
-        let (mut diff_operand, grad, chunk_no) =
-            (self.operand.gradient_mut(), self.gradient(), self.chunk_no);
+        let mut operand_gradient = self.operand.gradient_mut();
+        if self.operand.can_overwrite() {
+            operand_gradient.fill(0.);
+            self.operand.set_overwrite(false);
+        }
 
-        let mut op_gradient_chunk = diff_operand
-            .exact_chunks_mut(self.shape.clone())
+        // The gradients of the chunks are fitted to the data of the outputs before the pass.
+        let chunk_shape =
+            fit_chunk_shape(&self.shape, &self.chunk_shape, &operand_gradient.raw_dim());
+        operand_gradient
+            .exact_chunks_mut(chunk_shape)
             .into_iter()
-            .skip(chunk_no)
-            .take(1)
-            .next()
-            .unwrap();
+            .enumerate()
+            // The chunks that received no gradient during this pass are skipped.
+            .filter(|(i, _)| !self.overwrites[*i].get())
+            .for_each(|(i, operand_gradient_chunk)| {
+                Zip::from(operand_gradient_chunk)
+                    .and(&*self.gradient_of(i))
+                    .for_each(|dest, src| *dest += src);
+            });
 
-        let zip = Zip::from(&mut op_gradient_chunk).and(&*grad);
-        if self.operand.can_overwrite() {
-            zip.for_each(|dest, src| *dest = *src);
-            self.operand.set_overwrite(false);
-        } else {
-            zip.for_each(|dest, src| *dest += src);
+        for overwrite in &self.overwrites {
+            overwrite.set(true);
         }
     }
 
     fn no_grad(&self) {
-        *self.gradient.borrow_mut() = None;
+        for gradient in &self.gradients {
+            *gradient.borrow_mut() = None;
+        }
     }
 
     fn with_grad(&self) {
-        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape.clone()));
+        // The gradients are fitted to the data of the outputs before the next backward pass.
+        for gradient in &self.gradients {
+            *gradient.borrow_mut() = Some(Tensor::zeros(self.chunk_shape.clone()));
+        }
     }
 }
 
@@ -217,8 +285,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ChunkBackward")
-            .field("gradient", &self.gradient.borrow())
-            .field("chunk_no", &self.chunk_no)
+            .field("gradients", &self.gradients)
             .field("overwrite", &self.overwrite.get())
             .finish()
     }
@@ -229,10 +296,14 @@ where
     T: Gradient,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", &gradient),
-            None => write!(f, "None"),
+        for gradient in &self.gradients {
+            match &*gradient.borrow() {
+                Some(gradient) => crate::print::fmt_tensor(gradient, f)?,
+                None => write!(f, "None")?,
+            }
+            writeln!(f)?;
         }
+        Ok(())
     }
 }
 
@@ -1,26 +1,38 @@
 use super::{
     assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Cache, Chunk,
-    ChunkBackward, Data, Forward, Gradient, Overwrite, Tensor,
+    ChunkBackward, Data, Forward, Gradient, MultiData, MultiGradient, Overwrite, Tensor,
 };
 
 mod forward {
 
-    use super::{assert_almost_equals, new_input, new_tensor, Cache, Chunk, Data, Forward, Tensor};
+    use super::{
+        assert_almost_equals, new_input, new_tensor, Cache, Chunk, Data, Forward, MultiData, Tensor,
+    };
 
     #[test]
     fn creation() {
         let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
-        let node = Chunk::new(input, Tensor::zeros((1, 3)), 0);
+        let node = Chunk::new(input, ndarray::Dim([1, 3]));
 
-        assert_eq!(*node.data(), Tensor::from_elem((1, 3), 0.));
-        assert_eq!(*node.data_mut(), Tensor::from_elem((1, 3), 0.));
+        assert_eq!(node.len(), 3);
+        assert_eq!(*node.data_of(0), new_tensor((1, 3), vec![-4., -3., -2.]));
+        assert_eq!(*node.data_of_mut(2), new_tensor((1, 3), vec![2., 3., 4.]));
         assert!(!node.was_computed());
     }
 
+    #[test]
+    fn remainder() {
+        let input = new_input((3, 3), vec![0.; 9]);
+        let node = Chunk::new(input, ndarray::Dim([2, 2]));
+
+        assert_eq!(node.len(), 1);
+        assert_eq!(*node.data_of(0), Tensor::zeros((2, 2)));
+    }
+
     #[test]
     fn computation_was_computed_transition() {
         let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
-        let node = Chunk::new(input, Tensor::zeros((1, 3)), 0);
+        let node = Chunk::new(input, ndarray::Dim([1, 3]));
 
         node.forward();
         assert!(node.was_computed());
@@ -38,11 +50,13 @@ mod forward {
     #[test]
     fn forward() {
         let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
-        let node = Chunk::new(input.clone(), Tensor::zeros((1, 3)), 0);
+        let node = Chunk::new(input.clone(), ndarray::Dim([1, 3]));
 
         // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
         node.forward();
-        assert_almost_equals(&*node.data(), &new_tensor((1, 3), vec![-4., -3., -2.]));
+        assert_almost_equals(&*node.data_of(0), &new_tensor((1, 3), vec![-4., -3., -2.]));
+        assert_almost_equals(&*node.data_of(1), &new_tensor((1, 3), vec![-1., 0., 1.]));
+        assert_almost_equals(&*node.data_of(2), &new_tensor((1, 3), vec![2., 3., 4.]));
 
         // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
         {
@@ -55,115 +69,144 @@ mod forward {
         );
 
         node.forward();
-        assert_almost_equals(&*node.data(), &new_tensor((1, 3), vec![-4., -3., -2.]));
+        assert_almost_equals(&*node.data_of(0), &new_tensor((1, 3), vec![-4., -3., -2.]));
 
         // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
         node.reset_computation();
         node.forward();
-        assert_almost_equals(&*node.data(), &new_tensor((1, 3), vec![-3., -2., -1.]));
+        assert_almost_equals(&*node.data_of(0), &new_tensor((1, 3), vec![-3., -2., -1.]));
+        assert_almost_equals(&*node.data_of(1), &new_tensor((1, 3), vec![0., 1., 2.]));
+        assert_almost_equals(&*node.data_of(2), &new_tensor((1, 3), vec![3., 4., 5.]));
+    }
+
+    #[test]
+    fn forward_shape_change() {
+        let input = new_input((2, 5), vec![0.; 10]);
+        let node = Chunk::new(input.clone(), ndarray::Dim([2, 2]));
+        assert_eq!(node.len(), 2);
+
+        // The number of chunks and the remainder along each axis stay the same.
+        *input.data_mut() = new_tensor((3, 7), (0..21).map(|el| el as f32).collect());
+        node.forward();
+        assert_eq!(
+            *node.data_of(0),
+            new_tensor((3, 3), vec![0., 1., 2., 7., 8., 9., 14., 15., 16.])
+        );
+        assert_eq!(
+            *node.data_of(1),
+            new_tensor((3, 3), vec![3., 4., 5., 10., 11., 12., 17., 18., 19.])
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "error: an operand of shape [2, 2] cannot be split in 2 chunks along axis 1."
+    )]
+    fn forward_shape_change_fail() {
+        let input = new_input((2, 5), vec![0.; 10]);
+        let node = Chunk::new(input.clone(), ndarray::Dim([2, 2]));
+
+        *input.data_mut() = Tensor::zeros((2, 2));
+        node.forward();
     }
 
     #[test]
     fn debug() {
-        let input = new_input(3, vec![0.; 3]);
-        let node = Chunk::new(input, ndarray::arr1(&[0.]), 1);
+        let input = new_input(2, vec![0.; 2]);
+        let node = Chunk::new(input, ndarray::Dim([1]));
 
-        let output = "Chunk { data: [0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1, chunk_no: 1, computed: false }";
+        let output = "Chunk { data: [RefCell { value: [0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1 }, RefCell { value: [0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1 }], computed: false }";
 
         assert_eq!(output, format!("{:?}", node));
     }
 
     #[test]
     fn display() {
-        let input = new_input(3, vec![0.; 3]);
-        let node = Chunk::new(input, ndarray::arr1(&[0.]), 1);
+        let input = new_input(2, vec![0.; 2]);
+        let node = Chunk::new(input, ndarray::Dim([1]));
 
-        assert_eq!(format!("{}", node.data()), format!("{}", node));
+        assert_eq!(
+            format!("{}\n{}\n", node.data_of(0), node.data_of(1)),
+            format!("{}", node)
+        );
     }
 }
 
 mod backward {
     use super::{
         assert_almost_equals, new_backward_input, new_tensor, Backward, ChunkBackward, Gradient,
-        Overwrite, Tensor,
+        MultiGradient, Overwrite, Tensor,
     };
 
     #[test]
     fn creation() {
         let node = ChunkBackward::new(
             new_backward_input((3, 3), vec![0.; 9]),
-            Tensor::zeros((1, 3)),
-            0,
+            ndarray::Dim([1, 3]),
+            3,
         );
 
-        assert_eq!(*node.gradient(), Tensor::from_elem((1, 3), 0.));
-        assert_eq!(*node.gradient_mut(), Tensor::from_elem((1, 3), 0.));
+        for chunk in 0..3 {
+            assert_eq!(*node.gradient_of(chunk), Tensor::from_elem((1, 3), 0.));
+            assert_eq!(*node.gradient_of_mut(chunk), Tensor::from_elem((1, 3), 0.));
+            assert!(node.can_overwrite_of(chunk));
+        }
         assert!(node.can_overwrite());
     }
 
     #[test]
     fn computation_state_transition() {
         let diff = new_backward_input((3, 3), vec![0.; 9]);
-        let node = ChunkBackward::new(diff.clone(), Tensor::zeros((1, 3)), 0);
+        let node = ChunkBackward::new(diff.clone(), ndarray::Dim([1, 3]), 3);
 
-        node.backward();
+        node.set_overwrite_of(1, false);
+        assert!(!node.can_overwrite_of(1));
         assert!(node.can_overwrite());
-        assert!(!diff.can_overwrite());
 
         node.backward();
+        assert!(node.can_overwrite_of(1));
         assert!(node.can_overwrite());
         assert!(!diff.can_overwrite());
 
-        diff.set_overwrite(true);
-        assert!(node.can_overwrite());
-        assert!(diff.can_overwrite());
-
-        diff.set_overwrite(true);
-        assert!(node.can_overwrite());
-        assert!(diff.can_overwrite());
-
         node.set_overwrite(false);
         assert!(!node.can_overwrite());
-        assert!(diff.can_overwrite());
+        assert!(node.can_overwrite_of(0));
 
-        node.set_overwrite(false);
+        diff.set_overwrite(true);
         assert!(!node.can_overwrite());
         assert!(diff.can_overwrite());
-
-        node.backward();
-        assert!(!node.can_overwrite());
-        assert!(!diff.can_overwrite());
-
-        node.backward();
-        assert!(!node.can_overwrite());
-        assert!(!diff.can_overwrite());
     }
 
     #[test]
     fn backward() {
         let diff = new_backward_input((3, 3), vec![0.; 9]);
-        let node = ChunkBackward::new(diff.clone(), Tensor::zeros((1, 3)), 0);
+        let node = ChunkBackward::new(diff.clone(), ndarray::Dim([1, 3]), 3);
 
         // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-        *node.gradient_mut() = new_tensor((1, 3), vec![1.; 3]);
-        assert_almost_equals(&*node.gradient(), &new_tensor((1, 3), vec![1.; 3]));
+        *node.gradient_of_mut(0) = new_tensor((1, 3), vec![1.; 3]);
+        *node.gradient_of_mut(2) = new_tensor((1, 3), vec![2.; 3]);
+        node.set_overwrite_of(0, false);
+        node.set_overwrite_of(2, false);
 
         // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
         node.backward();
         assert_almost_equals(
             &*diff.gradient(),
-            &new_tensor((3, 3), vec![1., 1., 1., 0., 0., 0., 0., 0., 0.]),
+            &new_tensor((3, 3), vec![1., 1., 1., 0., 0., 0., 2., 2., 2.]),
         );
 
         // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        // Only the first chunk received a gradient during this pass.
+        node.set_overwrite_of(0, false);
         node.backward();
         assert_almost_equals(
             &*diff.gradient(),
-            &new_tensor((3, 3), vec![2., 2., 2., 0., 0., 0., 0., 0., 0.]),
+            &new_tensor((3, 3), vec![2., 2., 2., 0., 0., 0., 2., 2., 2.]),
         );
 
         // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
         diff.set_overwrite(true);
+        node.set_overwrite_of(0, false);
         node.backward();
         assert_almost_equals(
             &*diff.gradient(),
@@ -175,38 +218,36 @@ mod backward {
     fn no_grad() {
         let node = ChunkBackward::new(
             new_backward_input((3, 3), vec![0.; 9]),
-            Tensor::zeros((1, 3)),
-            0,
+            ndarray::Dim([1, 3]),
+            3,
         );
 
         node.no_grad();
-        assert!(node.gradient.borrow().is_none());
+        assert!(node
+            .gradients
+            .iter()
+            .all(|gradient| gradient.borrow().is_none()));
 
         node.with_grad();
-        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+        assert_eq!(&*node.gradient_of(1), Tensor::zeros(node.chunk_shape));
     }
 
     #[test]
     fn debug() {
-        let node = ChunkBackward::new(
-            new_backward_input((3, 3), vec![0.; 9]),
-            Tensor::zeros((1, 3)),
-            0,
-        );
+        let node = ChunkBackward::new(new_backward_input(2, vec![0.; 2]), ndarray::Dim([1]), 1);
 
-        let output = "ChunkBackward { gradient: Some([[0.0, 0.0, 0.0]], shape=[1, 3], strides=[3, 1], layout=CFcf (0xf), const ndim=2), chunk_no: 0, overwrite: true }";
+        let output = "ChunkBackward { gradients: [RefCell { value: Some([0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1) }], overwrite: true }";
 
         assert_eq!(output, format!("{:?}", node));
     }
 
     #[test]
     fn display() {
-        let node = ChunkBackward::new(
-            new_backward_input((3, 3), vec![0.; 9]),
-            Tensor::zeros((1, 3)),
-            0,
-        );
+        let node = ChunkBackward::new(new_backward_input(2, vec![0.; 2]), ndarray::Dim([1]), 2);
 
-        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+        assert_eq!(
+            format!("{}\n{}\n", node.gradient_of(0), node.gradient_of(1)),
+            format!("{}", node)
+        );
     }
 }
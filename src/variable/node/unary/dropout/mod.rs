@@ -1,11 +1,11 @@
 #[cfg(test)]
 use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
 use super::{
-    expect_tensor, expect_tensor_mut, Backward, Cache, Data, Eval, Forward, Gradient, Overwrite,
-    Tensor,
+    expect_tensor, expect_tensor_mut, fit_shape, Backward, Cache, Data, Eval, Forward, Gradient,
+    Overwrite, Tensor,
 };
-use ndarray::Zip;
-use rand::thread_rng;
+use ndarray::{Axis, Zip};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use rand_distr::{Bernoulli, Distribution};
 use std::{
     cell::{Cell, Ref, RefCell, RefMut},
@@ -13,6 +13,48 @@ use std::{
     rc::Rc,
 };
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ DropoutMask ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// The source of the masks of the dropout nodes.
+///
+/// Each mask is drawn from a generator seeded with a fresh seed. When the mask is frozen the last
+/// seed is used again, so that every following evaluation of a node of the same shape draws the
+/// very same mask, even across different graphs. A mask kept active is applied in inference mode
+/// too, as done by Monte Carlo dropout.
+#[derive(Debug, Default)]
+pub struct DropoutMask {
+    frozen: Cell<bool>,
+    seed: Cell<u64>,
+    keep_active: Cell<bool>,
+}
+
+impl DropoutMask {
+    pub(crate) fn freeze(&self) {
+        self.frozen.set(true);
+    }
+
+    pub(crate) fn unfreeze(&self) {
+        self.frozen.set(false);
+    }
+
+    pub(crate) fn is_frozen(&self) -> bool {
+        self.frozen.get()
+    }
+
+    pub(crate) fn set_keep_active(&self, keep_active: bool) {
+        self.keep_active.set(keep_active);
+    }
+
+    fn rng(&self) -> StdRng {
+        if !self.frozen.get() {
+            self.seed.set(crate::random::with_rng(|rng| rng.gen()));
+        }
+
+        StdRng::seed_from_u64(self.seed.get())
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Dropout ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -27,13 +69,15 @@ where
     p: f64,
     computed: Cell<bool>,
     train: Rc<Cell<bool>>,
+    mask: Rc<DropoutMask>,
+    per_sample: bool,
 }
 
 impl<T: ?Sized> Dropout<T>
 where
     T: Data,
 {
-    pub fn new(operand: Rc<T>, p: f64, status: Rc<Cell<bool>>) -> Self {
+    pub fn new(operand: Rc<T>, p: f64, status: Rc<Cell<bool>>, mask: Rc<DropoutMask>) -> Self {
         if !(0. ..=1.).contains(&p) {
             panic!(
                 "error: dropout probability has to be between 0 and 1, but got {}.",
@@ -55,6 +99,22 @@ where
             p,
             computed: Cell::new(false),
             train: status,
+            mask,
+            per_sample: false,
+        }
+    }
+
+    /// Creates a dropout node zeroing whole samples, i.e. whole slices along the first axis,
+    /// instead of single elements, as needed by stochastic depth.
+    pub fn per_sample(
+        operand: Rc<T>,
+        p: f64,
+        status: Rc<Cell<bool>>,
+        mask: Rc<DropoutMask>,
+    ) -> Self {
+        Self {
+            per_sample: true,
+            ..Self::new(operand, p, status, mask)
         }
     }
 
@@ -65,6 +125,10 @@ where
     pub(crate) fn status(&self) -> Rc<Cell<bool>> {
         self.train.clone()
     }
+
+    fn is_active(&self) -> bool {
+        self.train.get() || self.mask.keep_active.get()
+    }
 }
 
 impl<T: ?Sized> Cache for Dropout<T>
@@ -90,8 +154,11 @@ where
         }
 
         self.computed.set(true);
-        if self.train.get() {
-            let mut thread_rng = thread_rng();
+        let shape = self.operand.data().raw_dim();
+        fit_shape(&self.data, shape.clone());
+        fit_shape(&self.noise, shape);
+        if self.is_active() {
+            let mut rng = self.mask.rng();
             let (mut noise, distr, p) = (self.noise.borrow_mut(), &self.distr, &self.p);
             if (*p - 1.).abs() <= f64::EPSILON {
                 Zip::from(&mut *self.data.borrow_mut()).for_each(|data_el| *data_el = 0.0);
@@ -100,8 +167,16 @@ where
                     .and(&*self.operand.data())
                     .for_each(|data_el, operand_data_el| *data_el = *operand_data_el);
             } else {
-                Zip::from(&mut *noise)
-                    .for_each(|noise_el| *noise_el = distr.sample(&mut thread_rng) as i32 as f32);
+                if self.per_sample && noise.ndim() > 0 {
+                    noise
+                        .view_mut()
+                        .into_dyn()
+                        .axis_iter_mut(Axis(0))
+                        .for_each(|mut sample| sample.fill(distr.sample(&mut rng) as i32 as f32));
+                } else {
+                    Zip::from(&mut *noise)
+                        .for_each(|noise_el| *noise_el = distr.sample(&mut rng) as i32 as f32);
+                }
                 Zip::from(&mut *self.data.borrow_mut())
                     .and(&*self.operand.data())
                     .and(&*noise)
@@ -163,7 +238,7 @@ where
     T: Data,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(f, "{}", &self.data.borrow())
+        crate::print::fmt_tensor(&self.data.borrow(), f)
     }
 }
 
@@ -245,7 +320,7 @@ where
     U: Data<Dim = T::Dim>,
 {
     fn backward(&self) {
-        if self.train.get() {
+        if self.train.get() || self.no_diff_operand.is_active() {
             let mut op_grad = self.diff_operand.gradient_mut();
             let grad = self.gradient();
             let p = &self.p;
@@ -264,13 +339,16 @@ where
                 }
             } else {
                 let noise = self.no_diff_operand.noise();
+                let scale = 1. - *p as f32;
                 let zip = Zip::from(&mut *op_grad).and(&*grad).and(&*noise);
                 if self.diff_operand.can_overwrite() {
-                    zip.for_each(|op_grad_el, grad_el, noise_el| *op_grad_el = *grad_el * noise_el);
+                    zip.for_each(|op_grad_el, grad_el, noise_el| {
+                        *op_grad_el = *grad_el * noise_el / scale
+                    });
                     self.diff_operand.set_overwrite(false);
                 } else {
                     zip.for_each(|op_grad_el, grad_el, noise_el| {
-                        *op_grad_el += *grad_el * noise_el
+                        *op_grad_el += *grad_el * noise_el / scale
                     });
                 }
             }
@@ -309,7 +387,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", &gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
@@ -1,18 +1,18 @@
 use super::{
     assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Cache, Cell, Data,
-    Dropout, DropoutBackward, Forward, Gradient, Overwrite, Rc, Tensor,
+    Dropout, DropoutBackward, DropoutMask, Forward, Gradient, Overwrite, Rc, Tensor,
 };
 
 mod forward {
     use super::{
-        assert_almost_equals, new_input, new_tensor, Cache, Cell, Data, Dropout, Forward, Rc,
-        Tensor,
+        assert_almost_equals, new_input, new_tensor, Cache, Cell, Data, Dropout, DropoutMask,
+        Forward, Rc, Tensor,
     };
 
     #[test]
     fn creation() {
         let input = new_input((3, 3), vec![1., 2., 3., 4., 5., 6., 7., 8., 9.]);
-        let node = Dropout::new(input, 0.5, Rc::new(Cell::new(true)));
+        let node = Dropout::new(input, 0.5, Rc::new(Cell::new(true)), Rc::default());
 
         assert_eq!(*node.data(), Tensor::from_elem((3, 3), 0.));
         assert_eq!(*node.data_mut(), Tensor::from_elem((3, 3), 0.));
@@ -25,13 +25,13 @@ mod forward {
     )]
     fn creation_less_than_zero() {
         let input = new_input((3, 3), vec![1., 2., 3., 4., 5., 6., 7., 8., 9.]);
-        let _ = Dropout::new(input, -0.5, Rc::new(Cell::new(true)));
+        let _ = Dropout::new(input, -0.5, Rc::new(Cell::new(true)), Rc::default());
     }
 
     #[test]
     fn computation_was_computed_transition() {
         let input = new_input((3, 3), vec![1., 2., 3., 4., 5., 6., 7., 8., 9.]);
-        let node = Dropout::new(input, 0.5, Rc::new(Cell::new(true)));
+        let node = Dropout::new(input, 0.5, Rc::new(Cell::new(true)), Rc::default());
 
         node.forward();
         assert!(node.was_computed());
@@ -49,7 +49,7 @@ mod forward {
     #[test]
     fn forward_p_one() {
         let input = new_input((3, 3), vec![1., 2., 3., 4., 5., 6., 7., 8., 9.]);
-        let node = Dropout::new(input.clone(), 1., Rc::new(Cell::new(true)));
+        let node = Dropout::new(input.clone(), 1., Rc::new(Cell::new(true)), Rc::default());
 
         // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
         node.forward();
@@ -77,7 +77,7 @@ mod forward {
     #[test]
     fn forward_scaling() {
         let input = new_input((3, 3), vec![3.; 9]);
-        let node = Dropout::new(input, 0.5, Rc::new(Cell::new(true)));
+        let node = Dropout::new(input, 0.5, Rc::new(Cell::new(true)), Rc::default());
 
         // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
         node.forward();
@@ -89,7 +89,7 @@ mod forward {
     #[test]
     fn forward_p_zero() {
         let input = new_input((3, 3), vec![1., 2., 3., 4., 5., 6., 7., 8., 9.]);
-        let node = Dropout::new(input.clone(), 0., Rc::new(Cell::new(true)));
+        let node = Dropout::new(input.clone(), 0., Rc::new(Cell::new(true)), Rc::default());
 
         // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
         node.forward();
@@ -123,10 +123,49 @@ mod forward {
         );
     }
 
+    #[test]
+    fn forward_frozen_mask() {
+        let input = new_input((10, 10), vec![1.; 100]);
+        let mask = Rc::new(DropoutMask::default());
+        let node = Dropout::new(input.clone(), 0.5, Rc::new(Cell::new(true)), mask.clone());
+
+        node.forward();
+        let first = node.data().clone();
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Frozen Mask ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        mask.freeze();
+        assert!(mask.is_frozen());
+
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(&*node.data(), &first);
+
+        // The mask is shared by the nodes of the same layer.
+        let other = Dropout::new(input, 0.5, Rc::new(Cell::new(true)), mask.clone());
+        other.forward();
+        assert_almost_equals(&*other.data(), &first);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Unfrozen Mask ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        mask.unfreeze();
+        assert!(!mask.is_frozen());
+    }
+
+    #[test]
+    fn forward_per_sample() {
+        let input = new_input((20, 5), vec![3.; 100]);
+        let node = Dropout::per_sample(input, 0.5, Rc::new(Cell::new(true)), Rc::default());
+
+        node.forward();
+        assert!(node.data().rows().into_iter().all(|row| {
+            row.iter().all(|el| el.abs() <= f32::EPSILON)
+                || row.iter().all(|el| (el - 6.).abs() <= f32::EPSILON)
+        }));
+    }
+
     #[test]
     fn debug() {
         let input = new_input((3, 3), vec![1., 2., 3., 4., 5., 6., 7., 8., 9.]);
-        let node = Dropout::new(input.clone(), 0., Rc::new(Cell::new(true)));
+        let node = Dropout::new(input.clone(), 0., Rc::new(Cell::new(true)), Rc::default());
 
         let output = "Dropout { data: [[0.0, 0.0, 0.0],\n [0.0, 0.0, 0.0],\n [0.0, 0.0, 0.0]], shape=[3, 3], strides=[3, 1], layout=Cc (0x5), const ndim=2, p: 0.0, noise: [[0.0, 0.0, 0.0],\n [0.0, 0.0, 0.0],\n [0.0, 0.0, 0.0]], shape=[3, 3], strides=[3, 1], layout=Cc (0x5), const ndim=2, train: true, computed: false }";
 
@@ -136,7 +175,7 @@ mod forward {
     #[test]
     fn display() {
         let input = new_input((3, 3), vec![1., 2., 3., 4., 5., 6., 7., 8., 9.]);
-        let node = Dropout::new(input.clone(), 0., Rc::new(Cell::new(true)));
+        let node = Dropout::new(input.clone(), 0., Rc::new(Cell::new(true)), Rc::default());
 
         assert_eq!(format!("{}", node.data()), format!("{}", node));
     }
@@ -144,8 +183,8 @@ mod forward {
 
 mod backward {
     use super::{
-        assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Cell, Dropout,
-        DropoutBackward, Gradient, Overwrite, Rc, Tensor,
+        assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Cell, Data,
+        Dropout, DropoutBackward, Forward, Gradient, Overwrite, Rc, Tensor,
     };
 
     #[test]
@@ -156,6 +195,7 @@ mod backward {
                 new_input((3, 3), vec![1.; 9]),
                 0.5,
                 Rc::new(Cell::new(true)),
+                Rc::default(),
             )),
             0.5,
             Rc::new(Cell::new(true)),
@@ -175,6 +215,7 @@ mod backward {
                 new_input((3, 3), vec![1.; 9]),
                 0.5,
                 Rc::new(Cell::new(true)),
+                Rc::default(),
             )),
             0.5,
             Rc::new(Cell::new(true)),
@@ -230,6 +271,7 @@ mod backward {
                 new_input((3, 3), vec![1.; 9]),
                 1.,
                 Rc::new(Cell::new(true)),
+                Rc::default(),
             )),
             1.,
             Rc::new(Cell::new(true)),
@@ -262,6 +304,7 @@ mod backward {
                 new_input((3, 3), vec![1.; 9]),
                 0.,
                 Rc::new(Cell::new(true)),
+                Rc::default(),
             )),
             0.,
             Rc::new(Cell::new(true)),
@@ -285,6 +328,31 @@ mod backward {
         assert_almost_equals(&*input.gradient(), &new_tensor((3, 3), vec![1.; 9]));
     }
 
+    #[test]
+    fn backward_scaling() {
+        let input = new_backward_input((3, 3), vec![0.; 9]);
+        let forward = Rc::new(Dropout::new(
+            new_input((3, 3), vec![1.; 9]),
+            0.5,
+            Rc::new(Cell::new(true)),
+            Rc::default(),
+        ));
+        let node = DropoutBackward::new(
+            input.clone(),
+            forward.clone(),
+            0.5,
+            Rc::new(Cell::new(true)),
+        );
+        forward.forward();
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((3, 3), vec![1.; 9]);
+
+        // The gradient is scaled as the data, by a factor of 1 / (1 - p).
+        node.backward();
+        assert_almost_equals(&*input.gradient(), &*forward.data());
+    }
+
     #[test]
     fn no_grad() {
         // DropoutBackward
@@ -294,6 +362,7 @@ mod backward {
                 new_input((3, 3), vec![0.; 9]),
                 0.5,
                 Rc::new(Cell::new(true)),
+                Rc::default(),
             )),
             0.5,
             Rc::new(Cell::new(true)),
@@ -315,6 +384,7 @@ mod backward {
                 new_input((3, 3), vec![1.; 9]),
                 0.,
                 Rc::new(Cell::new(true)),
+                Rc::default(),
             )),
             0.,
             Rc::new(Cell::new(true)),
@@ -334,6 +404,7 @@ mod backward {
                 new_input((3, 3), vec![1.; 9]),
                 0.,
                 Rc::new(Cell::new(true)),
+                Rc::default(),
             )),
             0.,
             Rc::new(Cell::new(true)),
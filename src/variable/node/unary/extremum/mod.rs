@@ -0,0 +1,503 @@
+#[cfg(test)]
+use super::new_input;
+use super::{fit_shape, Cache, Data, Forward, IndexData, IndexTensor, Tensor};
+use ndarray::{ArrayView1, Axis, Dimension, RemoveAxis, Zip};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    cmp::Ordering,
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// Compares `lhs` and `rhs` so that larger values come first, NaNs being the largest values.
+fn descending(lhs: f32, rhs: f32) -> Ordering {
+    match (lhs.is_nan(), rhs.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => rhs.partial_cmp(&lhs).unwrap(),
+    }
+}
+
+/// Compares `lhs` and `rhs` so that smaller values come first, NaNs being the smallest values.
+fn ascending(lhs: f32, rhs: f32) -> Ordering {
+    match (lhs.is_nan(), rhs.is_nan()) {
+        (false, false) => lhs.partial_cmp(&rhs).unwrap(),
+        _ => descending(lhs, rhs),
+    }
+}
+
+/// Returns the position of the first element of `lane` that comes before all the others
+/// according to `order`.
+fn first_by(lane: ArrayView1<f32>, order: impl Fn(f32, f32) -> Ordering) -> i64 {
+    let mut best = 0;
+    for (i, &el) in lane.iter().enumerate().skip(1) {
+        if order(el, lane[best]) == Ordering::Less {
+            best = i;
+        }
+    }
+    best as i64
+}
+
+/// Fills each lane of `ranks` along `axis` with the positions of the elements of the
+/// corresponding lane of `operand`, sorted according to `order`. If the lanes of `ranks` are
+/// shorter than those of `operand` only the first positions are kept.
+///
+/// The sort is stable, so that ties are broken in favor of the lowest position.
+fn rank<D: Dimension>(
+    ranks: &mut IndexTensor<D>,
+    operand: &Tensor<D>,
+    axis: usize,
+    order: impl Fn(f32, f32) -> Ordering,
+) {
+    let mut positions: Vec<usize> = Vec::with_capacity(operand.len_of(Axis(axis)));
+    Zip::from(ranks.lanes_mut(Axis(axis)))
+        .and(operand.lanes(Axis(axis)))
+        .for_each(|mut ranked, lane| {
+            positions.clear();
+            positions.extend(0..lane.len());
+            positions.sort_by(|&lhs, &rhs| order(lane[lhs], lane[rhs]));
+            ranked
+                .iter_mut()
+                .zip(&positions)
+                .for_each(|(el, &position)| *el = position as i64);
+        });
+}
+
+/// Asserts that an axis of length `len` is not empty.
+fn check_not_empty(len: usize, axis: usize) {
+    assert!(
+        len > 0,
+        "error: cannot find the extremum of the empty axis {}.",
+        axis
+    );
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ ArgMax ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct ArgMax<T: ?Sized>
+where
+    T: Data,
+    T::Dim: RemoveAxis,
+{
+    operand: Rc<T>,
+    axis: usize,
+    data: RefCell<IndexTensor<<T::Dim as Dimension>::Smaller>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized> ArgMax<T>
+where
+    T: Data,
+    T::Dim: RemoveAxis,
+{
+    pub fn new(operand: Rc<T>, axis: usize) -> Self {
+        let data = IndexTensor::zeros(operand.data().raw_dim().remove_axis(Axis(axis)));
+
+        Self {
+            operand,
+            axis,
+            data: RefCell::new(data),
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized> Cache for ArgMax<T>
+where
+    T: Data,
+    T::Dim: RemoveAxis,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized> Forward for ArgMax<T>
+where
+    T: Data,
+    T::Dim: RemoveAxis,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let operand = self.operand.data();
+        check_not_empty(operand.len_of(Axis(self.axis)), self.axis);
+        fit_shape(&self.data, operand.raw_dim().remove_axis(Axis(self.axis)));
+        Zip::from(&mut *self.data.borrow_mut())
+            .and(operand.lanes(Axis(self.axis)))
+            .for_each(|v, lane| *v = first_by(lane, descending));
+    }
+}
+
+impl<T: ?Sized> IndexData for ArgMax<T>
+where
+    T: Data,
+    T::Dim: RemoveAxis,
+{
+    type Dim = <T::Dim as Dimension>::Smaller;
+
+    fn data(&self) -> Ref<IndexTensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<IndexTensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized> Debug for ArgMax<T>
+where
+    T: Data,
+    T::Dim: RemoveAxis,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArgMax")
+            .field("data", &self.data.borrow())
+            .field("axis", &self.axis)
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for ArgMax<T>
+where
+    T: Data,
+    T::Dim: RemoveAxis,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ ArgMin ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct ArgMin<T: ?Sized>
+where
+    T: Data,
+    T::Dim: RemoveAxis,
+{
+    operand: Rc<T>,
+    axis: usize,
+    data: RefCell<IndexTensor<<T::Dim as Dimension>::Smaller>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized> ArgMin<T>
+where
+    T: Data,
+    T::Dim: RemoveAxis,
+{
+    pub fn new(operand: Rc<T>, axis: usize) -> Self {
+        let data = IndexTensor::zeros(operand.data().raw_dim().remove_axis(Axis(axis)));
+
+        Self {
+            operand,
+            axis,
+            data: RefCell::new(data),
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized> Cache for ArgMin<T>
+where
+    T: Data,
+    T::Dim: RemoveAxis,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized> Forward for ArgMin<T>
+where
+    T: Data,
+    T::Dim: RemoveAxis,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let operand = self.operand.data();
+        check_not_empty(operand.len_of(Axis(self.axis)), self.axis);
+        fit_shape(&self.data, operand.raw_dim().remove_axis(Axis(self.axis)));
+        Zip::from(&mut *self.data.borrow_mut())
+            .and(operand.lanes(Axis(self.axis)))
+            .for_each(|v, lane| *v = first_by(lane, ascending));
+    }
+}
+
+impl<T: ?Sized> IndexData for ArgMin<T>
+where
+    T: Data,
+    T::Dim: RemoveAxis,
+{
+    type Dim = <T::Dim as Dimension>::Smaller;
+
+    fn data(&self) -> Ref<IndexTensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<IndexTensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized> Debug for ArgMin<T>
+where
+    T: Data,
+    T::Dim: RemoveAxis,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArgMin")
+            .field("data", &self.data.borrow())
+            .field("axis", &self.axis)
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for ArgMin<T>
+where
+    T: Data,
+    T::Dim: RemoveAxis,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ TopK ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct TopK<T: ?Sized>
+where
+    T: Data,
+{
+    operand: Rc<T>,
+    k: usize,
+    axis: usize,
+    data: RefCell<IndexTensor<T::Dim>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized> TopK<T>
+where
+    T: Data,
+{
+    pub fn new(operand: Rc<T>, k: usize, axis: usize) -> Self {
+        let mut shape = operand.data().raw_dim();
+        shape[axis] = k;
+        let data = IndexTensor::zeros(shape);
+
+        Self {
+            operand,
+            k,
+            axis,
+            data: RefCell::new(data),
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized> Cache for TopK<T>
+where
+    T: Data,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized> Forward for TopK<T>
+where
+    T: Data,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let operand = self.operand.data();
+        let len = operand.len_of(Axis(self.axis));
+        assert!(
+            self.k <= len,
+            "error: cannot select the top {} elements of axis {} of length {}.",
+            self.k,
+            self.axis,
+            len
+        );
+
+        let mut shape = operand.raw_dim();
+        shape[self.axis] = self.k;
+        fit_shape(&self.data, shape);
+
+        rank(&mut self.data.borrow_mut(), &operand, self.axis, descending);
+    }
+}
+
+impl<T: ?Sized> IndexData for TopK<T>
+where
+    T: Data,
+{
+    type Dim = T::Dim;
+
+    fn data(&self) -> Ref<IndexTensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<IndexTensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized> Debug for TopK<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TopK")
+            .field("data", &self.data.borrow())
+            .field("k", &self.k)
+            .field("axis", &self.axis)
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for TopK<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ ArgSort ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct ArgSort<T: ?Sized>
+where
+    T: Data,
+{
+    operand: Rc<T>,
+    axis: usize,
+    data: RefCell<IndexTensor<T::Dim>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized> ArgSort<T>
+where
+    T: Data,
+{
+    pub fn new(operand: Rc<T>, axis: usize) -> Self {
+        let data = IndexTensor::zeros(operand.data().raw_dim());
+
+        Self {
+            operand,
+            axis,
+            data: RefCell::new(data),
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized> Cache for ArgSort<T>
+where
+    T: Data,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized> Forward for ArgSort<T>
+where
+    T: Data,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let operand = self.operand.data();
+        fit_shape(&self.data, operand.raw_dim());
+        // NaNs are placed after any other value.
+        rank(
+            &mut self.data.borrow_mut(),
+            &operand,
+            self.axis,
+            |lhs, rhs| descending(rhs, lhs),
+        );
+    }
+}
+
+impl<T: ?Sized> IndexData for ArgSort<T>
+where
+    T: Data,
+{
+    type Dim = T::Dim;
+
+    fn data(&self) -> Ref<IndexTensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<IndexTensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized> Debug for ArgSort<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArgSort")
+            .field("data", &self.data.borrow())
+            .field("axis", &self.axis)
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for ArgSort<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
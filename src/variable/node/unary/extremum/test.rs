@@ -0,0 +1,397 @@
+use super::{
+    new_input, ArgMax, ArgMin, ArgSort, Cache, Data, Forward, IndexData, IndexTensor, TopK,
+};
+
+mod arg_max {
+    use super::{new_input, ArgMax, Cache, Data, Forward, IndexData, IndexTensor};
+
+    #[test]
+    fn creation() {
+        let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+        let node = ArgMax::new(input, 1);
+
+        assert_eq!(*node.data(), IndexTensor::from_elem(3, 0));
+        assert_eq!(*node.data_mut(), IndexTensor::from_elem(3, 0));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+        let node = ArgMax::new(input, 1);
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward() {
+        let input = new_input((3, 3), vec![1., 5., 2., 3., 3., 0., -1., -2., 4.]);
+        let node = ArgMax::new(input.clone(), 1);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_eq!(*node.data(), IndexTensor::from(vec![1, 0, 2]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        input.data_mut().invert_axis(ndarray::Axis(1));
+        node.forward();
+        assert_eq!(*node.data(), IndexTensor::from(vec![1, 0, 2]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_eq!(*node.data(), IndexTensor::from(vec![1, 1, 0]));
+    }
+
+    #[test]
+    fn forward_first_axis() {
+        let input = new_input((2, 3), vec![1., 5., 2., 3., 3., 0.]);
+        let node = ArgMax::new(input, 0);
+
+        node.forward();
+        assert_eq!(*node.data(), IndexTensor::from(vec![1, 0, 0]));
+    }
+
+    #[test]
+    fn forward_nan() {
+        let input = new_input(3, vec![1., f32::NAN, 2.]);
+        let node = ArgMax::new(input, 0);
+
+        node.forward();
+        assert_eq!(*node.data(), IndexTensor::from_elem((), 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "error: cannot find the extremum of the empty axis 1.")]
+    fn forward_empty() {
+        let input = new_input((2, 0), vec![]);
+        let node = ArgMax::new(input, 1);
+
+        node.forward();
+    }
+
+    #[test]
+    fn debug() {
+        let input = new_input((1, 1), vec![1.]);
+        let node = ArgMax::new(input, 0);
+
+        let output = "ArgMax { data: [0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1, axis: 0, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let input = new_input((1, 1), vec![1.]);
+        let node = ArgMax::new(input, 0);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod arg_min {
+    use super::{new_input, ArgMin, Cache, Data, Forward, IndexData, IndexTensor};
+
+    #[test]
+    fn creation() {
+        let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+        let node = ArgMin::new(input, 0);
+
+        assert_eq!(*node.data(), IndexTensor::from_elem(3, 0));
+        assert_eq!(*node.data_mut(), IndexTensor::from_elem(3, 0));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+        let node = ArgMin::new(input, 0);
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward() {
+        let input = new_input((3, 3), vec![1., 5., 2., 3., 3., 0., -1., -2., 4.]);
+        let node = ArgMin::new(input.clone(), 1);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_eq!(*node.data(), IndexTensor::from(vec![0, 2, 1]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        input.data_mut().invert_axis(ndarray::Axis(1));
+        node.forward();
+        assert_eq!(*node.data(), IndexTensor::from(vec![0, 2, 1]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_eq!(*node.data(), IndexTensor::from(vec![2, 0, 1]));
+    }
+
+    #[test]
+    fn forward_nan() {
+        let input = new_input(3, vec![1., f32::NAN, -2.]);
+        let node = ArgMin::new(input, 0);
+
+        node.forward();
+        assert_eq!(*node.data(), IndexTensor::from_elem((), 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "error: cannot find the extremum of the empty axis 0.")]
+    fn forward_empty() {
+        let input = new_input((0, 2), vec![]);
+        let node = ArgMin::new(input, 0);
+
+        node.forward();
+    }
+
+    #[test]
+    fn debug() {
+        let input = new_input((1, 1), vec![1.]);
+        let node = ArgMin::new(input, 1);
+
+        let output = "ArgMin { data: [0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1, axis: 1, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let input = new_input((1, 1), vec![1.]);
+        let node = ArgMin::new(input, 1);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod top_k {
+    use super::{new_input, Cache, Data, Forward, IndexData, IndexTensor, TopK};
+
+    #[test]
+    fn creation() {
+        let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+        let node = TopK::new(input, 2, 1);
+
+        assert_eq!(*node.data(), IndexTensor::from_elem((3, 2), 0));
+        assert_eq!(*node.data_mut(), IndexTensor::from_elem((3, 2), 0));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+        let node = TopK::new(input, 2, 1);
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward() {
+        let input = new_input((3, 3), vec![1., 5., 2., 3., 3., 0., -1., -2., 4.]);
+        let node = TopK::new(input.clone(), 2, 1);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_eq!(
+            *node.data(),
+            IndexTensor::from_shape_vec((3, 2), vec![1, 2, 0, 1, 2, 0]).unwrap()
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        input.data_mut().invert_axis(ndarray::Axis(1));
+        node.forward();
+        assert_eq!(
+            *node.data(),
+            IndexTensor::from_shape_vec((3, 2), vec![1, 2, 0, 1, 2, 0]).unwrap()
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_eq!(
+            *node.data(),
+            IndexTensor::from_shape_vec((3, 2), vec![1, 0, 1, 2, 0, 2]).unwrap()
+        );
+    }
+
+    #[test]
+    fn forward_first_axis() {
+        let input = new_input((3, 2), vec![1., 5., 2., 3., 3., 0.]);
+        let node = TopK::new(input, 1, 0);
+
+        node.forward();
+        assert_eq!(
+            *node.data(),
+            IndexTensor::from_shape_vec((1, 2), vec![2, 0]).unwrap()
+        );
+    }
+
+    #[test]
+    fn forward_nan() {
+        let input = new_input(4, vec![1., f32::NAN, 2., -1.]);
+        let node = TopK::new(input, 4, 0);
+
+        node.forward();
+        assert_eq!(*node.data(), IndexTensor::from(vec![1, 2, 0, 3]));
+    }
+
+    #[test]
+    #[should_panic(expected = "error: cannot select the top 4 elements of axis 1 of length 3.")]
+    fn forward_too_many() {
+        let input = new_input((1, 3), vec![1., 2., 3.]);
+        let node = TopK::new(input, 4, 1);
+
+        node.forward();
+    }
+
+    #[test]
+    fn debug() {
+        let input = new_input(1, vec![1.]);
+        let node = TopK::new(input, 1, 0);
+
+        let output = "TopK { data: [0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1, k: 1, axis: 0, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let input = new_input(1, vec![1.]);
+        let node = TopK::new(input, 1, 0);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod arg_sort {
+    use super::{new_input, ArgSort, Cache, Data, Forward, IndexData, IndexTensor};
+
+    #[test]
+    fn creation() {
+        let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+        let node = ArgSort::new(input, 1);
+
+        assert_eq!(*node.data(), IndexTensor::from_elem((3, 3), 0));
+        assert_eq!(*node.data_mut(), IndexTensor::from_elem((3, 3), 0));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+        let node = ArgSort::new(input, 1);
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward() {
+        let input = new_input((3, 3), vec![1., 5., 2., 3., 3., 0., -1., -2., 4.]);
+        let node = ArgSort::new(input.clone(), 1);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_eq!(
+            *node.data(),
+            IndexTensor::from_shape_vec((3, 3), vec![0, 2, 1, 2, 0, 1, 1, 0, 2]).unwrap()
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        input.data_mut().invert_axis(ndarray::Axis(1));
+        node.forward();
+        assert_eq!(
+            *node.data(),
+            IndexTensor::from_shape_vec((3, 3), vec![0, 2, 1, 2, 0, 1, 1, 0, 2]).unwrap()
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_eq!(
+            *node.data(),
+            IndexTensor::from_shape_vec((3, 3), vec![2, 0, 1, 0, 1, 2, 1, 2, 0]).unwrap()
+        );
+    }
+
+    #[test]
+    fn forward_first_axis() {
+        let input = new_input((3, 2), vec![1., 5., 2., 3., 0., 3.]);
+        let node = ArgSort::new(input, 0);
+
+        node.forward();
+        assert_eq!(
+            *node.data(),
+            IndexTensor::from_shape_vec((3, 2), vec![2, 1, 0, 2, 1, 0]).unwrap()
+        );
+    }
+
+    #[test]
+    fn forward_nan() {
+        let input = new_input(4, vec![1., f32::NAN, -2., 1.]);
+        let node = ArgSort::new(input, 0);
+
+        node.forward();
+        assert_eq!(*node.data(), IndexTensor::from(vec![2, 0, 3, 1]));
+    }
+
+    #[test]
+    fn debug() {
+        let input = new_input(1, vec![1.]);
+        let node = ArgSort::new(input, 0);
+
+        let output = "ArgSort { data: [0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1, axis: 0, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let input = new_input(1, vec![1.]);
+        let node = ArgSort::new(input, 0);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
@@ -0,0 +1,232 @@
+#[cfg(test)]
+use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
+use super::{
+    expect_tensor, expect_tensor_mut, fit_shape, Backward, Cache, Data, Forward, Gradient,
+    Overwrite, Tensor,
+};
+use ndarray::{Dimension, Ix2};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// Returns the shape of `shape` flattened after its first axis.
+fn flattened<D: Dimension>(shape: &D) -> Ix2 {
+    let shape = shape.slice();
+    assert!(
+        !shape.is_empty(),
+        "error: cannot flatten a zero-dimensional variable."
+    );
+
+    Ix2(shape[0], shape[1..].iter().product())
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Flatten ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct Flatten<T: ?Sized>
+where
+    T: Data,
+{
+    operand: Rc<T>,
+    data: RefCell<Tensor<Ix2>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized> Flatten<T>
+where
+    T: Data,
+{
+    pub fn new(operand: Rc<T>) -> Self {
+        let data = RefCell::new(Tensor::zeros(flattened(&operand.data().raw_dim())));
+
+        Self {
+            operand,
+            data,
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized> Cache for Flatten<T>
+where
+    T: Data,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized> Forward for Flatten<T>
+where
+    T: Data,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let operand_data = self.operand.data();
+        fit_shape(&self.data, flattened(&operand_data.raw_dim()));
+        self.data
+            .borrow_mut()
+            .iter_mut()
+            .zip(operand_data.iter())
+            .for_each(|(data_el, operand_data_el)| *data_el = *operand_data_el);
+    }
+}
+
+impl<T: ?Sized> Data for Flatten<T>
+where
+    T: Data,
+{
+    type Dim = Ix2;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized> Debug for Flatten<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Flatten")
+            .field("data", &self.data.borrow())
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for Flatten<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ FlattenBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct FlattenBackward<T: ?Sized>
+where
+    T: Gradient,
+{
+    gradient: RefCell<Option<Tensor<Ix2>>>,
+    shape: Ix2,
+    overwrite: Cell<bool>,
+    operand: Rc<T>,
+}
+
+impl<T: ?Sized> FlattenBackward<T>
+where
+    T: Gradient,
+{
+    pub fn new(operand: Rc<T>) -> Self {
+        let shape = flattened(&operand.gradient().raw_dim());
+
+        Self {
+            gradient: RefCell::new(Some(Tensor::zeros(shape))),
+            shape,
+            overwrite: Cell::new(true),
+            operand,
+        }
+    }
+}
+
+impl<T: ?Sized> Gradient for FlattenBackward<T>
+where
+    T: Gradient,
+{
+    type Dim = Ix2;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<T: ?Sized> Overwrite for FlattenBackward<T>
+where
+    T: Gradient,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<T: ?Sized> Backward for FlattenBackward<T>
+where
+    T: Gradient,
+{
+    fn backward(&self) {
+        let mut op_grad = self.operand.gradient_mut();
+        let grad = self.gradient();
+
+        let zip = op_grad.iter_mut().zip(grad.iter());
+        if self.operand.can_overwrite() {
+            zip.for_each(|(op_grad_el, grad_el)| *op_grad_el = *grad_el);
+            self.operand.set_overwrite(false);
+        } else {
+            zip.for_each(|(op_grad_el, grad_el)| *op_grad_el += *grad_el);
+        }
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape));
+    }
+}
+
+impl<T: ?Sized> Debug for FlattenBackward<T>
+where
+    T: Gradient,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FlattenBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for FlattenBackward<T>
+where
+    T: Gradient,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
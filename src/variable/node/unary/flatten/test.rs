@@ -0,0 +1,199 @@
+use super::{
+    assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Cache, Data,
+    Flatten, FlattenBackward, Forward, Gradient, Overwrite, Tensor,
+};
+
+mod forward {
+    use super::{
+        assert_almost_equals, new_input, new_tensor, Cache, Data, Flatten, Forward, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let input = new_input((2, 2, 3), vec![0.; 12]);
+        let node = Flatten::new(input);
+
+        assert_eq!(*node.data(), Tensor::from_elem((2, 6), 0.));
+        assert_eq!(*node.data_mut(), Tensor::from_elem((2, 6), 0.));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let input = new_input((2, 2, 3), vec![0.; 12]);
+        let node = Flatten::new(input);
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward() {
+        let input = new_input((2, 2, 3), (0..12).map(|el| el as f32).collect());
+        let node = Flatten::new(input.clone());
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 6), (0..12).map(|el| el as f32).collect()),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        {
+            let mut data = input.data_mut();
+            *data = &*data + &Tensor::from_elem(1, 1.);
+        }
+
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 6), (0..12).map(|el| el as f32).collect()),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 6), (1..13).map(|el| el as f32).collect()),
+        );
+    }
+
+    #[test]
+    fn forward_non_standard_layout() {
+        let input = new_input((2, 3), vec![1., 2., 3., 4., 5., 6.]);
+        input.data_mut().swap_axes(0, 1);
+        let node = Flatten::new(input);
+
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((3, 2), vec![1., 4., 2., 5., 3., 6.]),
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let input = new_input((2, 1, 1), vec![0.; 2]);
+        let node = Flatten::new(input);
+
+        let output = "Flatten { data: [[0.0],\n [0.0]], shape=[2, 1], strides=[1, 1], layout=CFcf (0xf), const ndim=2, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let input = new_input((2, 1, 1), vec![0.; 2]);
+        let node = Flatten::new(input);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        assert_almost_equals, new_backward_input, new_tensor, Backward, FlattenBackward, Gradient,
+        Overwrite, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let node = FlattenBackward::new(new_backward_input((2, 2, 3), vec![0.; 12]));
+
+        assert_eq!(*node.gradient(), Tensor::from_elem((2, 6), 0.));
+        assert_eq!(*node.gradient_mut(), Tensor::from_elem((2, 6), 0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn computation_state_transition() {
+        let diff = new_backward_input((2, 2, 3), vec![0.; 12]);
+        let node = FlattenBackward::new(diff.clone());
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        diff.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!diff.can_overwrite());
+    }
+
+    #[test]
+    fn backward() {
+        let diff = new_backward_input((2, 2, 3), vec![0.; 12]);
+        let node = FlattenBackward::new(diff.clone());
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((2, 6), (0..12).map(|el| el as f32).collect());
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 2, 3), (0..12).map(|el| el as f32).collect()),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 2, 3), (0..12).map(|el| 2. * el as f32).collect()),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 2, 3), (0..12).map(|el| el as f32).collect()),
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let node = FlattenBackward::new(new_backward_input((2, 1, 1), vec![0.; 2]));
+
+        let output = "FlattenBackward { gradient: Some([[0.0],\n [0.0]], shape=[2, 1], strides=[1, 1], layout=CFcf (0xf), const ndim=2), overwrite: true }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = FlattenBackward::new(new_backward_input((2, 1, 1), vec![0.; 2]));
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+
+    #[test]
+    fn no_grad() {
+        // FlattenBackward
+        let node = FlattenBackward::new(new_backward_input((2, 2, 3), vec![0.; 12]));
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+    }
+}
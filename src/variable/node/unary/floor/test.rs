@@ -0,0 +1,87 @@
+use super::{assert_almost_equals, new_input, new_tensor, Cache, Data, Floor, Forward, Tensor};
+
+#[test]
+fn creation() {
+    let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+    let node = Floor::new(input);
+
+    assert_eq!(*node.data(), Tensor::from_elem((3, 3), 0.));
+    assert_eq!(*node.data_mut(), Tensor::from_elem((3, 3), 0.));
+    assert!(!node.was_computed());
+}
+
+#[test]
+fn computation_was_computed_transition() {
+    let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+    let node = Floor::new(input);
+
+    node.forward();
+    assert!(node.was_computed());
+
+    node.forward();
+    assert!(node.was_computed());
+
+    node.reset_computation();
+    assert!(!node.was_computed());
+
+    node.reset_computation();
+    assert!(!node.was_computed());
+}
+
+#[test]
+fn forward() {
+    let input = new_input(
+        (3, 3),
+        vec![-2.6, -1.4, -0.6, -0.2, 0.2, 0.5, 1.5, 2.4, 3.7],
+    );
+    let node = Floor::new(input.clone());
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    node.forward();
+    assert_almost_equals(
+        &*node.data(),
+        &new_tensor((3, 3), vec![-3., -2., -1., -1., 0., 0., 1., 2., 3.]),
+    );
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    {
+        let mut data = input.data_mut();
+        *data = &*data + &Tensor::from_elem(1, 1.);
+    }
+    assert_almost_equals(
+        &*input.data(),
+        &new_tensor((3, 3), vec![-1.6, -0.4, 0.4, 0.8, 1.2, 1.5, 2.5, 3.4, 4.7]),
+    );
+
+    node.forward();
+    assert_almost_equals(
+        &*node.data(),
+        &new_tensor((3, 3), vec![-3., -2., -1., -1., 0., 0., 1., 2., 3.]),
+    );
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    node.reset_computation();
+    node.forward();
+    assert_almost_equals(
+        &*node.data(),
+        &new_tensor((3, 3), vec![-2., -1., 0., 0., 1., 1., 2., 3., 4.]),
+    );
+}
+
+#[test]
+fn debug() {
+    let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+    let node = Floor::new(input);
+
+    let output = "Floor { data: [[0.0, 0.0, 0.0],\n [0.0, 0.0, 0.0],\n [0.0, 0.0, 0.0]], shape=[3, 3], strides=[3, 1], layout=Cc (0x5), const ndim=2, computed: false }";
+
+    assert_eq!(output, format!("{:?}", node));
+}
+
+#[test]
+fn display() {
+    let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+    let node = Floor::new(input);
+
+    assert_eq!(format!("{}", node.data()), format!("{}", node));
+}
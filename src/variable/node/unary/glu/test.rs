@@ -0,0 +1,228 @@
+use super::{
+    assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Cache, Data,
+    Forward, GLUBackward, Gradient, Overwrite, Tensor, GLU,
+};
+
+mod forward {
+    use super::{assert_almost_equals, new_input, new_tensor, Cache, Data, Forward, Tensor, GLU};
+
+    #[test]
+    fn creation() {
+        let input = new_input((2, 4), vec![1., 2., 3., 4., -1., 0., 1., 2.]);
+        let node = GLU::new(input, 1);
+
+        assert_eq!(*node.data(), Tensor::from_elem((2, 2), 0.));
+        assert_eq!(*node.data_mut(), Tensor::from_elem((2, 2), 0.));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    #[should_panic(expected = "error: the size of axis 0 must be even to be halved, got 3.")]
+    fn creation_fail() {
+        GLU::new(new_input((3, 2), vec![0.; 6]), 0);
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let input = new_input((2, 4), vec![1., 2., 3., 4., -1., 0., 1., 2.]);
+        let node = GLU::new(input, 1);
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward() {
+        let input = new_input((2, 4), vec![1., 2., 3., 4., -1., 0., 1., 2.]);
+        let node = GLU::new(input.clone(), 1);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 2), vec![0.952574, 1.964028, -0.731059, 0.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *input.data_mut() = new_tensor((2, 4), vec![0.; 8]);
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 2), vec![0.952574, 1.964028, -0.731059, 0.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((2, 2), vec![0.; 4]));
+    }
+
+    #[test]
+    fn forward_rows() {
+        let input = new_input((2, 2), vec![1., 2., 3., 4.]);
+        let node = GLU::new(input, 0);
+
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((1, 2), vec![0.952574, 1.964028]));
+    }
+
+    #[test]
+    fn debug() {
+        let input = new_input(2, vec![1., 2.]);
+        let node = GLU::new(input, 0);
+
+        let output = "GLU { data: [0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1, axis: 0, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let input = new_input((2, 4), vec![1., 2., 3., 4., -1., 0., 1., 2.]);
+        let node = GLU::new(input, 1);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, GLUBackward,
+        Gradient, Overwrite, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let node = GLUBackward::new(
+            new_backward_input((2, 4), vec![0.; 8]),
+            new_input((2, 4), vec![1., 2., 3., 4., -1., 0., 1., 2.]),
+            1,
+        );
+
+        assert_eq!(*node.gradient(), Tensor::from_elem((2, 2), 0.));
+        assert_eq!(*node.gradient_mut(), Tensor::from_elem((2, 2), 0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn computation_state_transition() {
+        let diff = new_backward_input((2, 4), vec![0.; 8]);
+        let node = GLUBackward::new(
+            diff.clone(),
+            new_input((2, 4), vec![1., 2., 3., 4., -1., 0., 1., 2.]),
+            1,
+        );
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        diff.set_overwrite(true);
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!diff.can_overwrite());
+    }
+
+    #[test]
+    fn backward() {
+        let diff = new_backward_input((2, 4), vec![0.; 8]);
+        let node = GLUBackward::new(
+            diff.clone(),
+            new_input((2, 4), vec![1., 2., 3., 4., -1., 0., 1., 2.]),
+            1,
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((2, 2), vec![1.; 4]);
+        assert_almost_equals(&*node.gradient(), &new_tensor((2, 2), vec![1.; 4]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (2, 4),
+                vec![
+                    0.952574, 0.982014, 0.045177, 0.035325, 0.731059, 0.880797, -0.196612, 0.,
+                ],
+            ),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (2, 4),
+                vec![
+                    1.905148, 1.964028, 0.090354, 0.07065, 1.462118, 1.761594, -0.393224, 0.,
+                ],
+            ),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (2, 4),
+                vec![
+                    0.952574, 0.982014, 0.045177, 0.035325, 0.731059, 0.880797, -0.196612, 0.,
+                ],
+            ),
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let node = GLUBackward::new(
+            new_backward_input(2, vec![0.; 2]),
+            new_input(2, vec![1., 2.]),
+            0,
+        );
+
+        let output = "GLUBackward { gradient: Some([0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1), axis: 0, overwrite: true }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = GLUBackward::new(
+            new_backward_input(2, vec![0.; 2]),
+            new_input(2, vec![1., 2.]),
+            0,
+        );
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+
+    #[test]
+    fn no_grad() {
+        let node = GLUBackward::new(
+            new_backward_input((2, 4), vec![0.; 8]),
+            new_input((2, 4), vec![0.; 8]),
+            1,
+        );
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+    }
+}
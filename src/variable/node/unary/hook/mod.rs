@@ -0,0 +1,154 @@
+#[cfg(test)]
+use super::{new_backward_input, new_input};
+use super::{Backward, Cache, Data, Forward, Gradient, Overwrite, Tensor};
+use std::{
+    cell::Cell,
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// A function called with the data or the gradient of a node.
+pub(crate) type Hook<D> = Box<dyn Fn(&Tensor<D>)>;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ ForwardHook ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Calls a user provided function on the data of its operand during the forward pass.
+///
+/// This node has no data of its own: it's only inserted in the forward history of a variable.
+pub struct ForwardHook<T: ?Sized>
+where
+    T: Data,
+{
+    operand: Rc<T>,
+    hook: Hook<T::Dim>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized + Data> ForwardHook<T> {
+    pub fn new(operand: Rc<T>, hook: Hook<T::Dim>) -> Self {
+        Self {
+            operand,
+            hook,
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized> Cache for ForwardHook<T>
+where
+    T: Data,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized> Forward for ForwardHook<T>
+where
+    T: Data,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        (self.hook)(&*self.operand.data());
+    }
+}
+
+impl<T: ?Sized> Debug for ForwardHook<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ForwardHook")
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for ForwardHook<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "ForwardHook")
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ BackwardHook ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+/// Calls a user provided function on the gradient of its operand during the backward pass.
+///
+/// This node has no gradient of its own: it's only inserted in the backward history of a
+/// differentiable variable. Its overwrite status mirrors the one of its operand.
+pub struct BackwardHook<T: ?Sized>
+where
+    T: Gradient,
+{
+    operand: Rc<T>,
+    hook: Hook<T::Dim>,
+}
+
+impl<T: ?Sized + Gradient> BackwardHook<T> {
+    pub fn new(operand: Rc<T>, hook: Hook<T::Dim>) -> Self {
+        Self { operand, hook }
+    }
+}
+
+impl<T: ?Sized> Overwrite for BackwardHook<T>
+where
+    T: Gradient,
+{
+    fn can_overwrite(&self) -> bool {
+        self.operand.can_overwrite()
+    }
+
+    fn set_overwrite(&self, _: bool) {}
+}
+
+impl<T: ?Sized> Backward for BackwardHook<T>
+where
+    T: Gradient,
+{
+    fn backward(&self) {
+        (self.hook)(&*self.operand.gradient());
+    }
+
+    fn no_grad(&self) {}
+
+    fn with_grad(&self) {}
+}
+
+impl<T: ?Sized> Debug for BackwardHook<T>
+where
+    T: Gradient,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BackwardHook")
+            .field("overwrite", &self.can_overwrite())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for BackwardHook<T>
+where
+    T: Gradient,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "BackwardHook")
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,119 @@
+use super::{
+    new_backward_input, new_input, Backward, BackwardHook, Cache, Forward, ForwardHook, Gradient,
+    Overwrite, Rc,
+};
+use std::cell::Cell;
+
+mod forward {
+    use super::{new_input, Cache, Cell, Forward, ForwardHook, Rc};
+
+    #[test]
+    fn creation() {
+        let node = ForwardHook::new(new_input(3, vec![1., 2., 3.]), Box::new(|_| {}));
+
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let node = ForwardHook::new(new_input(3, vec![1., 2., 3.]), Box::new(|_| {}));
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward() {
+        let sum = Rc::new(Cell::new(0.));
+        let calls = Rc::new(Cell::new(0));
+        let node = {
+            let (sum, calls) = (sum.clone(), calls.clone());
+            ForwardHook::new(
+                new_input(3, vec![1., 2., 3.]),
+                Box::new(move |data| {
+                    sum.set(data.sum());
+                    calls.set(calls.get() + 1);
+                }),
+            )
+        };
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_eq!(sum.get(), 6.);
+        assert_eq!(calls.get(), 1);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_eq!(calls.get(), 1);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn debug() {
+        let node = ForwardHook::new(new_input(3, vec![1., 2., 3.]), Box::new(|_| {}));
+
+        assert_eq!("ForwardHook { computed: false }", format!("{:?}", node));
+    }
+}
+
+mod backward {
+    use super::{new_backward_input, Backward, BackwardHook, Cell, Gradient, Overwrite, Rc};
+
+    #[test]
+    fn creation() {
+        let node = BackwardHook::new(new_backward_input(3, vec![0.; 3]), Box::new(|_| {}));
+
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn computation_state_transition() {
+        let diff = new_backward_input(3, vec![0.; 3]);
+        let node = BackwardHook::new(diff.clone(), Box::new(|_| {}));
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        diff.set_overwrite(false);
+        assert!(!node.can_overwrite());
+
+        node.set_overwrite(true);
+        assert!(!node.can_overwrite());
+        assert!(!diff.can_overwrite());
+    }
+
+    #[test]
+    fn backward() {
+        let diff = new_backward_input(3, vec![0.; 3]);
+        let sum = Rc::new(Cell::new(0.));
+        let node = {
+            let sum = sum.clone();
+            BackwardHook::new(diff.clone(), Box::new(move |grad| sum.set(grad.sum())))
+        };
+
+        *diff.gradient_mut() = ndarray::arr1(&[1., 2., 3.]);
+        node.backward();
+        assert_eq!(sum.get(), 6.);
+    }
+
+    #[test]
+    fn debug() {
+        let node = BackwardHook::new(new_backward_input(3, vec![0.; 3]), Box::new(|_| {}));
+
+        assert_eq!("BackwardHook { overwrite: true }", format!("{:?}", node));
+    }
+}
@@ -1,45 +1,120 @@
+mod arccos;
+mod arcsin;
+mod arctan;
+mod arctanh;
+mod batch_norm;
+mod bincount;
+mod ceil;
 mod chunk;
+mod cos;
+mod cosh;
+mod digamma;
 mod dropout;
+mod erf;
+mod erfc;
 mod exp;
+mod expm1;
+mod extremum;
+mod flatten;
+mod floor;
+mod glu;
+mod hook;
 mod leaky_relu;
+mod lgamma;
+mod log1p;
 mod logn;
 mod logsoftmax;
 mod mean;
 mod negation;
+mod norm;
+mod one_hot;
+mod pool;
 mod power;
+mod reciprocal;
 mod relu;
+mod renorm;
+mod rotary;
+mod round;
+mod rsqrt;
+mod scalar;
 mod sigmoid;
+mod sign;
+mod sin;
+mod sinh;
+mod smoothing;
 mod softmax;
 mod softplus;
+mod special;
 mod sqrt;
 mod sum;
+mod tan;
 mod tanh;
+mod to_indices;
 mod transpose;
+mod unique;
 mod unsqueeze;
 
 use super::{
-    expect_tensor, expect_tensor_mut, push_gradient, Backward, Cache, Data, Eval, Forward,
-    Gradient, Overwrite, Tensor,
+    expect_tensor, expect_tensor_mut, fit_shape, push_gradient, Backward, Cache, Data, Eval,
+    Forward, Gradient, IndexData, IndexTensor, MultiData, MultiGradient, Overwrite, Tensor,
 };
 
 #[cfg(test)]
-use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
+use super::{assert_almost_equals, new_backward_input, new_index_input, new_input, new_tensor};
 
+pub(crate) use arccos::{ArcCos, ArcCosBackward};
+pub(crate) use arcsin::{ArcSin, ArcSinBackward};
+pub(crate) use arctan::{ArcTan, ArcTanBackward};
+pub(crate) use arctanh::{ArcTanH, ArcTanHBackward};
+pub(crate) use batch_norm::{BatchNorm, BatchNormBackward};
+pub(crate) use bincount::BinCount;
+pub(crate) use ceil::Ceil;
 pub(crate) use chunk::{Chunk, ChunkBackward};
-pub(crate) use dropout::{Dropout, DropoutBackward};
+pub(crate) use cos::{Cos, CosBackward};
+pub(crate) use cosh::{CosH, CosHBackward};
+pub(crate) use digamma::{Digamma, DigammaBackward};
+pub(crate) use dropout::{Dropout, DropoutBackward, DropoutMask};
+pub(crate) use erf::{Erf, ErfBackward};
+pub(crate) use erfc::{Erfc, ErfcBackward};
 pub(crate) use exp::{Exp, ExpBackward};
+pub(crate) use expm1::{Expm1, Expm1Backward};
+pub(crate) use extremum::{ArgMax, ArgMin, ArgSort, TopK};
+pub(crate) use flatten::{Flatten, FlattenBackward};
+pub(crate) use floor::Floor;
+pub(crate) use glu::{GLUBackward, GLU};
+pub(crate) use hook::{BackwardHook, ForwardHook};
 pub(crate) use leaky_relu::{LeakyReLU, LeakyReLUBackward};
+pub(crate) use lgamma::{LogGamma, LogGammaBackward};
+pub(crate) use log1p::{Log1p, Log1pBackward};
 pub(crate) use logn::{Logn, LognBackward};
 pub(crate) use logsoftmax::{LogSoftmax, LogSoftmaxBackward};
 pub(crate) use mean::{Mean, MeanBackward};
 pub(crate) use negation::{Negation, NegationBackward};
+pub(crate) use norm::{Norm, NormBackward};
+pub(crate) use one_hot::OneHot;
+pub(crate) use pool::{AvgPool, AvgPoolBackward, MaxPool, MaxPoolBackward};
 pub(crate) use power::{Power, PowerBackward};
+pub(crate) use reciprocal::{Reciprocal, ReciprocalBackward};
 pub(crate) use relu::{ReLU, ReLUBackward};
+pub(crate) use renorm::{renorm, Renorm, RenormBackward};
+pub(crate) use rotary::{Rotary, RotaryBackward};
+pub(crate) use round::{Round, RoundBackward};
+pub(crate) use rsqrt::{Rsqrt, RsqrtBackward};
+pub(crate) use scalar::{
+    AddScalar, AddScalarBackward, MulScalar, MulScalarBackward, PowScalar, PowScalarBackward,
+};
 pub(crate) use sigmoid::{Sigmoid, SigmoidBackward};
+pub(crate) use sign::{Sign, SignBackward};
+pub(crate) use sin::{Sin, SinBackward};
+pub(crate) use sinh::{SinH, SinHBackward};
+pub(crate) use smoothing::{Ema, EmaBackward, RollingMean, RollingMeanBackward};
 pub(crate) use softmax::{Softmax, SoftmaxBackward};
 pub(crate) use softplus::{SoftPlus, SoftPlusBackward};
 pub(crate) use sqrt::{Sqrt, SqrtBackward};
 pub(crate) use sum::{Sum, SumBackward};
+pub(crate) use tan::{Tan, TanBackward};
 pub(crate) use tanh::{TanH, TanHBackward};
+pub(crate) use to_indices::ToIndices;
 pub(crate) use transpose::{Transpose, TransposeBackward};
+pub(crate) use unique::Unique;
 pub(crate) use unsqueeze::{Unsqueeze, UnsqueezeBackward};
@@ -0,0 +1,353 @@
+#[cfg(test)]
+use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
+use super::{
+    expect_tensor, expect_tensor_mut, fit_shape, Backward, Cache, Data, Forward, Gradient,
+    Overwrite, Tensor,
+};
+use ndarray::{Axis, Dimension, IxDyn, Zip};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// Returns the `p`-norm of `elements`.
+pub(super) fn p_norm<'a>(elements: impl Iterator<Item = &'a f32>, p: f32) -> f32 {
+    if p == 1. {
+        elements.map(|el| el.abs()).sum()
+    } else if p == 2. {
+        elements.map(|el| el * el).sum::<f32>().sqrt()
+    } else {
+        elements
+            .map(|el| el.abs().powf(p))
+            .sum::<f32>()
+            .powf(1. / p)
+    }
+}
+
+/// Returns the derivative of the `p`-norm `norm` with respect to one of its elements `el`.
+///
+/// The subgradient is taken to be zero where either `el` or `norm` is zero.
+pub(super) fn p_norm_derivative(el: f32, norm: f32, p: f32) -> f32 {
+    if el == 0. || norm == 0. {
+        0.
+    } else {
+        el.signum() * (el.abs() / norm).powf(p - 1.)
+    }
+}
+
+/// Returns the shape of the norms of an operand of shape `shape`. The norms are computed along
+/// `axis`, which is removed or kept with length 1 depending on `keepdim`, or over all the
+/// elements when `axis` is `None`.
+fn reduced_shape<D: Dimension>(shape: &[usize], axis: Option<usize>, keepdim: bool) -> D {
+    let shape: Vec<usize> = match axis {
+        Some(axis) if keepdim => shape
+            .iter()
+            .enumerate()
+            .map(|(i, &len)| if i == axis { 1 } else { len })
+            .collect(),
+        Some(axis) => shape
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != axis)
+            .map(|(_, &len)| len)
+            .collect(),
+        None => Vec::new(),
+    };
+
+    D::from_dimension(&IxDyn(&shape)).unwrap()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Norm ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct Norm<T: ?Sized, D>
+where
+    T: Data,
+    D: Dimension,
+{
+    operand: Rc<T>,
+    p: f32,
+    axis: Option<usize>,
+    keepdim: bool,
+    data: RefCell<Tensor<D>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized, D> Norm<T, D>
+where
+    T: Data,
+    D: Dimension,
+{
+    pub fn new(operand: Rc<T>, p: f32, axis: Option<usize>, keepdim: bool) -> Self {
+        assert!(
+            p >= 1.,
+            "error: the order of a norm must be at least 1, got {}.",
+            p
+        );
+        let data = Tensor::zeros(reduced_shape::<D>(operand.data().shape(), axis, keepdim));
+
+        Self {
+            operand,
+            p,
+            axis,
+            keepdim,
+            data: RefCell::new(data),
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized, D> Cache for Norm<T, D>
+where
+    T: Data,
+    D: Dimension,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized, D> Forward for Norm<T, D>
+where
+    T: Data,
+    D: Dimension,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let operand = self.operand.data();
+        fit_shape(
+            &self.data,
+            reduced_shape(operand.shape(), self.axis, self.keepdim),
+        );
+
+        let mut data = self.data.borrow_mut();
+        match self.axis {
+            Some(axis) => {
+                let mut data = data.view_mut().into_dyn();
+                if self.keepdim {
+                    data = data.index_axis_move(Axis(axis), 0);
+                }
+                Zip::from(data)
+                    .and(operand.view().into_dyn().lanes(Axis(axis)))
+                    .for_each(|v, lane| *v = p_norm(lane.iter(), self.p))
+            }
+            None => data.fill(p_norm(operand.iter(), self.p)),
+        }
+    }
+}
+
+impl<T: ?Sized, D> Data for Norm<T, D>
+where
+    T: Data,
+    D: Dimension,
+{
+    type Dim = D;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized, D> Debug for Norm<T, D>
+where
+    T: Data,
+    D: Dimension,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Norm")
+            .field("data", &self.data.borrow())
+            .field("p", &self.p)
+            .field("axis", &self.axis)
+            .field("keepdim", &self.keepdim)
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, D> Display for Norm<T, D>
+where
+    T: Data,
+    D: Dimension,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ NormBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct NormBackward<T: ?Sized, U: ?Sized, V: ?Sized>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    V: Data,
+{
+    gradient: RefCell<Option<Tensor<V::Dim>>>,
+    shape: V::Dim,
+    overwrite: Cell<bool>,
+    diff_operand: Rc<T>,
+    no_diff_operand: Rc<U>,
+    norm: Rc<V>,
+    p: f32,
+    axis: Option<usize>,
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> NormBackward<T, U, V>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    V: Data,
+{
+    pub fn new(
+        diff_operand: Rc<T>,
+        no_diff_operand: Rc<U>,
+        norm: Rc<V>,
+        p: f32,
+        axis: Option<usize>,
+    ) -> Self {
+        let shape = norm.data().raw_dim();
+
+        Self {
+            gradient: RefCell::new(Some(Tensor::zeros(shape.clone()))),
+            shape,
+            overwrite: Cell::new(true),
+            diff_operand,
+            no_diff_operand,
+            norm,
+            p,
+            axis,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Gradient for NormBackward<T, U, V>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    V: Data,
+{
+    type Dim = V::Dim;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Overwrite for NormBackward<T, U, V>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    V: Data,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Backward for NormBackward<T, U, V>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    V: Data,
+{
+    fn backward(&self) {
+        let mut op_grad = self.diff_operand.gradient_mut();
+        let (operand, norm, grad) = (
+            self.no_diff_operand.data(),
+            self.norm.data(),
+            self.gradient(),
+        );
+
+        // The norms and their gradient are broadcast along the reduced axis, which is restored
+        // unless it was kept.
+        let (norm, grad) = match self.axis {
+            Some(axis) if norm.ndim() < operand.ndim() => (
+                norm.view().into_dyn().insert_axis(Axis(axis)),
+                grad.view().into_dyn().insert_axis(Axis(axis)),
+            ),
+            _ => (norm.view().into_dyn(), grad.view().into_dyn()),
+        };
+        let p = self.p;
+
+        let zip = Zip::from(op_grad.view_mut().into_dyn())
+            .and(operand.view().into_dyn())
+            .and_broadcast(&norm)
+            .and_broadcast(&grad);
+        if self.diff_operand.can_overwrite() {
+            zip.for_each(|op_grad_el, &el, &norm, &grad| {
+                *op_grad_el = grad * p_norm_derivative(el, norm, p)
+            });
+            self.diff_operand.set_overwrite(false);
+        } else {
+            zip.for_each(|op_grad_el, &el, &norm, &grad| {
+                *op_grad_el += grad * p_norm_derivative(el, norm, p)
+            });
+        }
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape.clone()));
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Debug for NormBackward<T, U, V>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    V: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NormBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("p", &self.p)
+            .field("axis", &self.axis)
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized, V: ?Sized> Display for NormBackward<T, U, V>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+    V: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
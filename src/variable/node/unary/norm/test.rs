@@ -0,0 +1,335 @@
+use super::{
+    assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Cache, Data,
+    Forward, Gradient, Norm, NormBackward, Overwrite, Tensor,
+};
+use ndarray::{Ix0, Ix1, Ix2};
+
+mod forward {
+    use super::{
+        assert_almost_equals, new_input, new_tensor, Cache, Data, Forward, Ix0, Ix1, Ix2, Norm,
+    };
+
+    #[test]
+    fn creation() {
+        let input = new_input((2, 3), vec![3., -4., 0., 1., 2., -2.]);
+        let node = Norm::<_, Ix1>::new(input, 2., Some(1), false);
+
+        assert_eq!(*node.data(), new_tensor(2, vec![0.; 2]));
+        assert_eq!(*node.data_mut(), new_tensor(2, vec![0.; 2]));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    #[should_panic(expected = "error: the order of a norm must be at least 1, got 0.5.")]
+    fn fractional_order() {
+        let input = new_input((2, 3), vec![3., -4., 0., 1., 2., -2.]);
+        Norm::<_, Ix0>::new(input, 0.5, None, false);
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let input = new_input((2, 3), vec![3., -4., 0., 1., 2., -2.]);
+        let node = Norm::<_, Ix0>::new(input, 2., None, false);
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward() {
+        let input = new_input((2, 3), vec![3., -4., 0., 1., 2., -2.]);
+        let node = Norm::<_, Ix0>::new(input.clone(), 2., None, false);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((), vec![5.830952]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *input.data_mut() = new_tensor((2, 3), vec![0., 0., 0., 0., 3., 4.]);
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((), vec![5.830952]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((), vec![5.]));
+    }
+
+    #[test]
+    fn forward_axis() {
+        let input = new_input((2, 3), vec![3., -4., 0., 1., 2., -2.]);
+
+        let node = Norm::<_, Ix1>::new(input.clone(), 2., Some(1), false);
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor(2, vec![5., 3.]));
+
+        let node = Norm::<_, Ix1>::new(input.clone(), 1., Some(0), false);
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor(3, vec![4., 6., 2.]));
+
+        let node = Norm::<_, Ix1>::new(input, 3., Some(1), false);
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor(2, vec![4.497941, 2.571282]));
+    }
+
+    #[test]
+    fn forward_axis_keepdim() {
+        let input = new_input((2, 3), vec![3., -4., 0., 1., 2., -2.]);
+
+        let node = Norm::<_, Ix2>::new(input.clone(), 2., Some(1), true);
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((2, 1), vec![5., 3.]));
+
+        let node = Norm::<_, Ix2>::new(input, 1., Some(0), true);
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((1, 3), vec![4., 6., 2.]));
+    }
+
+    #[test]
+    fn debug() {
+        let input = new_input(2, vec![3., -4.]);
+        let node = Norm::<_, Ix0>::new(input, 2., None, false);
+
+        let output = "Norm { data: 0.0, shape=[], strides=[], layout=CFcf (0xf), const ndim=0, p: 2.0, axis: None, keepdim: false, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let input = new_input(2, vec![3., -4.]);
+        let node = Norm::<_, Ix0>::new(input, 2., None, false);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Forward,
+        Gradient, Ix0, Ix1, Ix2, Norm, NormBackward, Overwrite, Tensor,
+    };
+    use std::rc::Rc;
+
+    #[test]
+    fn creation() {
+        let input = new_input((2, 3), vec![3., -4., 0., 1., 2., -2.]);
+        let norm = Rc::new(Norm::<_, Ix1>::new(input.clone(), 2., Some(1), false));
+        let node = NormBackward::new(
+            new_backward_input((2, 3), vec![0.; 6]),
+            input,
+            norm,
+            2.,
+            Some(1),
+        );
+
+        assert_eq!(*node.gradient(), Tensor::from_elem(2, 0.));
+        assert_eq!(*node.gradient_mut(), Tensor::from_elem(2, 0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn computation_state_transition() {
+        let input = new_input((2, 3), vec![3., -4., 0., 1., 2., -2.]);
+        let norm = Rc::new(Norm::<_, Ix0>::new(input.clone(), 2., None, false));
+        let diff = new_backward_input((2, 3), vec![0.; 6]);
+        let node = NormBackward::new(diff.clone(), input, norm, 2., None);
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        diff.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        diff.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!diff.can_overwrite());
+    }
+
+    #[test]
+    fn backward() {
+        let input = new_input((2, 3), vec![3., -4., 0., 1., 2., -2.]);
+        let norm = Rc::new(Norm::<_, Ix0>::new(input.clone(), 2., None, false));
+        let diff = new_backward_input((2, 3), vec![0.; 6]);
+        let node = NormBackward::new(diff.clone(), input, norm.clone(), 2., None);
+        norm.forward();
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((), vec![1.]);
+        assert_almost_equals(&*node.gradient(), &new_tensor((), vec![1.]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (2, 3),
+                vec![0.514496, -0.685994, 0., 0.171499, 0.342997, -0.342997],
+            ),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (2, 3),
+                vec![1.028992, -1.371988, 0., 0.342997, 0.685994, -0.685994],
+            ),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (2, 3),
+                vec![0.514496, -0.685994, 0., 0.171499, 0.342997, -0.342997],
+            ),
+        );
+    }
+
+    #[test]
+    fn backward_axis() {
+        let input = new_input((2, 3), vec![3., -4., 0., 1., 2., -2.]);
+        let diff = new_backward_input((2, 3), vec![0.; 6]);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ L1 ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        let norm = Rc::new(Norm::<_, Ix1>::new(input.clone(), 1., Some(0), false));
+        let node = NormBackward::new(diff.clone(), input.clone(), norm.clone(), 1., Some(0));
+        norm.forward();
+
+        *node.gradient_mut() = new_tensor(3, vec![1., 2., 3.]);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![1., -2., 0., 1., 2., -3.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ L2 ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        let norm = Rc::new(Norm::<_, Ix1>::new(input.clone(), 2., Some(1), false));
+        let node = NormBackward::new(diff.clone(), input.clone(), norm.clone(), 2., Some(1));
+        norm.forward();
+
+        *node.gradient_mut() = new_tensor(2, vec![1., 1.]);
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![0.6, -0.8, 0., 0.333333, 0.666667, -0.666667]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ L3 ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        let norm = Rc::new(Norm::<_, Ix1>::new(input.clone(), 3., Some(1), false));
+        let node = NormBackward::new(diff.clone(), input, norm.clone(), 3., Some(1));
+        norm.forward();
+
+        *node.gradient_mut() = new_tensor(2, vec![1., 1.]);
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (2, 3),
+                vec![0.444851, -0.790847, 0., 0.151252, 0.605007, -0.605007],
+            ),
+        );
+    }
+
+    #[test]
+    fn backward_axis_keepdim() {
+        let input = new_input((2, 3), vec![3., -4., 0., 1., 2., -2.]);
+        let diff = new_backward_input((2, 3), vec![0.; 6]);
+
+        let norm = Rc::new(Norm::<_, Ix2>::new(input.clone(), 2., Some(1), true));
+        let node = NormBackward::new(diff.clone(), input, norm.clone(), 2., Some(1));
+        norm.forward();
+
+        *node.gradient_mut() = new_tensor((2, 1), vec![1., 1.]);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![0.6, -0.8, 0., 0.333333, 0.666667, -0.666667]),
+        );
+    }
+
+    #[test]
+    fn backward_zero_norm() {
+        let input = new_input(3, vec![0.; 3]);
+        let norm = Rc::new(Norm::<_, Ix0>::new(input.clone(), 2., None, false));
+        let diff = new_backward_input(3, vec![0.; 3]);
+        let node = NormBackward::new(diff.clone(), input, norm.clone(), 2., None);
+        norm.forward();
+
+        *node.gradient_mut() = new_tensor((), vec![1.]);
+        node.backward();
+        assert_almost_equals(&*diff.gradient(), &new_tensor(3, vec![0.; 3]));
+    }
+
+    #[test]
+    fn no_grad() {
+        let input = new_input((3, 3), vec![0.; 9]);
+        let norm = Rc::new(Norm::<_, Ix1>::new(input.clone(), 2., Some(1), false));
+        let node = NormBackward::new(
+            new_backward_input((3, 3), vec![0.; 9]),
+            input,
+            norm,
+            2.,
+            Some(1),
+        );
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+    }
+
+    #[test]
+    fn debug() {
+        let input = new_input(2, vec![3., -4.]);
+        let norm = Rc::new(Norm::<_, Ix0>::new(input.clone(), 2., None, false));
+        let node = NormBackward::new(new_backward_input(2, vec![0.; 2]), input, norm, 2., None);
+
+        let output = "NormBackward { gradient: Some(0.0, shape=[], strides=[], layout=CFcf (0xf), const ndim=0), p: 2.0, axis: None, overwrite: true }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let input = new_input(2, vec![3., -4.]);
+        let norm = Rc::new(Norm::<_, Ix0>::new(input.clone(), 2., None, false));
+        let node = NormBackward::new(new_backward_input(2, vec![0.; 2]), input, norm, 2., None);
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+}
@@ -0,0 +1,133 @@
+use super::{fit_shape, Cache, Data, Forward, IndexData, Tensor};
+#[cfg(test)]
+use super::{new_index_input, new_tensor};
+use ndarray::{Axis, Dimension};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// Returns the shape of the one-hot encoding of the indices of shape `shape`, that is `shape`
+/// with an additional last axis of length `classes`.
+fn encoded<D: Dimension>(shape: &D, classes: usize) -> D::Larger {
+    let mut encoded_shape = shape.insert_axis(Axis(shape.ndim()));
+    encoded_shape[shape.ndim()] = classes;
+    encoded_shape
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ OneHot ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct OneHot<T: ?Sized>
+where
+    T: IndexData,
+{
+    operand: Rc<T>,
+    classes: usize,
+    data: RefCell<Tensor<<T::Dim as Dimension>::Larger>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized> OneHot<T>
+where
+    T: IndexData,
+{
+    pub fn new(operand: Rc<T>, classes: usize) -> Self {
+        let data = Tensor::zeros(encoded(&operand.data().raw_dim(), classes));
+
+        Self {
+            operand,
+            classes,
+            data: RefCell::new(data),
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized> Cache for OneHot<T>
+where
+    T: IndexData,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized> Forward for OneHot<T>
+where
+    T: IndexData,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let indices = self.operand.data();
+        fit_shape(&self.data, encoded(&indices.raw_dim(), self.classes));
+
+        let mut data = self.data.borrow_mut();
+        data.fill(0.);
+        let last_axis = Axis(data.ndim() - 1);
+        data.lanes_mut(last_axis)
+            .into_iter()
+            .zip(indices.iter())
+            .for_each(|(mut encoding, &index)| {
+                assert!(
+                    index >= 0 && (index as usize) < self.classes,
+                    "error: index {} is out of range for a one-hot encoding with {} classes.",
+                    index,
+                    self.classes
+                );
+                encoding[index as usize] = 1.;
+            });
+    }
+}
+
+impl<T: ?Sized> Data for OneHot<T>
+where
+    T: IndexData,
+{
+    type Dim = <T::Dim as Dimension>::Larger;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized> Debug for OneHot<T>
+where
+    T: IndexData,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OneHot")
+            .field("data", &self.data.borrow())
+            .field("classes", &self.classes)
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for OneHot<T>
+where
+    T: IndexData,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,94 @@
+use super::{new_index_input, new_tensor, Cache, Data, Forward, IndexData, OneHot, Tensor};
+
+#[test]
+fn creation() {
+    let input = new_index_input(3, vec![0, 2, 1]);
+    let node = OneHot::new(input, 4);
+
+    assert_eq!(*node.data(), Tensor::from_elem((3, 4), 0.));
+    assert_eq!(*node.data_mut(), Tensor::from_elem((3, 4), 0.));
+    assert!(!node.was_computed());
+}
+
+#[test]
+fn computation_was_computed_transition() {
+    let input = new_index_input(3, vec![0, 2, 1]);
+    let node = OneHot::new(input, 4);
+
+    node.forward();
+    assert!(node.was_computed());
+
+    node.forward();
+    assert!(node.was_computed());
+
+    node.reset_computation();
+    assert!(!node.was_computed());
+
+    node.reset_computation();
+    assert!(!node.was_computed());
+}
+
+#[test]
+fn forward() {
+    let input = new_index_input((2, 2), vec![0, 2, 1, 2]);
+    let node = OneHot::new(input.clone(), 3);
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    node.forward();
+    assert_eq!(
+        *node.data(),
+        new_tensor(
+            (2, 2, 3),
+            vec![1., 0., 0., 0., 0., 1., 0., 1., 0., 0., 0., 1.]
+        )
+    );
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    input.data_mut().fill(1);
+    node.forward();
+    assert_eq!(
+        *node.data(),
+        new_tensor(
+            (2, 2, 3),
+            vec![1., 0., 0., 0., 0., 1., 0., 1., 0., 0., 0., 1.]
+        )
+    );
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    node.reset_computation();
+    node.forward();
+    assert_eq!(
+        *node.data(),
+        new_tensor(
+            (2, 2, 3),
+            vec![0., 1., 0., 0., 1., 0., 0., 1., 0., 0., 1., 0.]
+        )
+    );
+}
+
+#[test]
+#[should_panic(expected = "error: index 3 is out of range for a one-hot encoding with 3 classes.")]
+fn forward_out_of_range() {
+    let input = new_index_input(2, vec![0, 3]);
+    let node = OneHot::new(input, 3);
+
+    node.forward();
+}
+
+#[test]
+fn debug() {
+    let input = new_index_input(1, vec![0]);
+    let node = OneHot::new(input, 2);
+
+    let output = "OneHot { data: [[0.0, 0.0]], shape=[1, 2], strides=[2, 1], layout=CFcf (0xf), const ndim=2, classes: 2, computed: false }";
+
+    assert_eq!(output, format!("{:?}", node));
+}
+
+#[test]
+fn display() {
+    let input = new_index_input(1, vec![0]);
+    let node = OneHot::new(input, 2);
+
+    assert_eq!(format!("{}", node.data()), format!("{}", node));
+}
@@ -0,0 +1,589 @@
+#[cfg(test)]
+use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
+use super::{
+    expect_tensor, expect_tensor_mut, fit_shape, Backward, Cache, Data, Forward, Gradient,
+    Overwrite, Tensor,
+};
+use ndarray::{Dimension, Ix4};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// Returns the shape of the result of a pooling with the given window over an input of shape
+/// `shape`.
+fn pooled_shape(
+    shape: Ix4,
+    kernel: (usize, usize),
+    stride: (usize, usize),
+    padding: (usize, usize),
+) -> Ix4 {
+    let (n, c, h, w) = (shape[0], shape[1], shape[2], shape[3]);
+    assert!(
+        kernel.0 > 0 && kernel.1 > 0 && stride.0 > 0 && stride.1 > 0,
+        "error: kernel size and stride of a pooling must be positive."
+    );
+    assert!(
+        2 * padding.0 <= kernel.0 && 2 * padding.1 <= kernel.1,
+        "error: the padding of a pooling must be at most half of the kernel size."
+    );
+    assert!(
+        h + 2 * padding.0 >= kernel.0 && w + 2 * padding.1 >= kernel.1,
+        "error: the kernel {:?} is larger than the padded input of shape {:?}.",
+        kernel,
+        shape.slice()
+    );
+
+    Ix4(
+        n,
+        c,
+        (h + 2 * padding.0 - kernel.0) / stride.0 + 1,
+        (w + 2 * padding.1 - kernel.1) / stride.1 + 1,
+    )
+}
+
+/// Returns the row and column ranges of the input covered by the window of output entry
+/// `(i, j)`, clipped to the input of height `h` and width `w`.
+fn window(
+    (i, j): (usize, usize),
+    (h, w): (usize, usize),
+    kernel: (usize, usize),
+    stride: (usize, usize),
+    padding: (usize, usize),
+) -> (std::ops::Range<usize>, std::ops::Range<usize>) {
+    let (top, left) = (i * stride.0, j * stride.1);
+    (
+        top.saturating_sub(padding.0)..(top + kernel.0).saturating_sub(padding.0).min(h),
+        left.saturating_sub(padding.1)..(left + kernel.1).saturating_sub(padding.1).min(w),
+    )
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ MaxPool ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct MaxPool<T: ?Sized>
+where
+    T: Data<Dim = Ix4>,
+{
+    operand: Rc<T>,
+    data: RefCell<Tensor<Ix4>>,
+    indices: RefCell<Vec<usize>>,
+    kernel: (usize, usize),
+    stride: (usize, usize),
+    padding: (usize, usize),
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized> MaxPool<T>
+where
+    T: Data<Dim = Ix4>,
+{
+    pub fn new(
+        operand: Rc<T>,
+        kernel: (usize, usize),
+        stride: (usize, usize),
+        padding: (usize, usize),
+    ) -> Self {
+        let shape = pooled_shape(operand.data().raw_dim(), kernel, stride, padding);
+        let indices = RefCell::new(vec![0; shape.size()]);
+        let data = RefCell::new(Tensor::zeros(shape));
+
+        Self {
+            operand,
+            data,
+            indices,
+            kernel,
+            stride,
+            padding,
+            computed: Cell::new(false),
+        }
+    }
+
+    /// Returns the position of the maximum of each window, as an index into the flattened plane
+    /// of its channel.
+    pub(crate) fn indices(&self) -> Ref<Vec<usize>> {
+        self.indices.borrow()
+    }
+}
+
+impl<T: ?Sized> Cache for MaxPool<T>
+where
+    T: Data<Dim = Ix4>,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized> Forward for MaxPool<T>
+where
+    T: Data<Dim = Ix4>,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let operand_data = self.operand.data();
+        let shape = pooled_shape(
+            operand_data.raw_dim(),
+            self.kernel,
+            self.stride,
+            self.padding,
+        );
+        fit_shape(&self.data, shape);
+        let (h, w) = (operand_data.shape()[2], operand_data.shape()[3]);
+
+        let mut indices = self.indices.borrow_mut();
+        indices.resize(shape.size(), 0);
+        self.data
+            .borrow_mut()
+            .indexed_iter_mut()
+            .zip(indices.iter_mut())
+            .for_each(|(((b, c, i, j), data_el), index)| {
+                let (rows, columns) =
+                    window((i, j), (h, w), self.kernel, self.stride, self.padding);
+                let (mut max, mut argmax) = (f32::NEG_INFINITY, 0);
+                for row in rows {
+                    for column in columns.clone() {
+                        let el = operand_data[[b, c, row, column]];
+                        if el > max || el.is_nan() {
+                            max = el;
+                            argmax = row * w + column;
+                        }
+                    }
+                }
+                *data_el = max;
+                *index = argmax;
+            });
+    }
+}
+
+impl<T: ?Sized> Data for MaxPool<T>
+where
+    T: Data<Dim = Ix4>,
+{
+    type Dim = Ix4;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized> Debug for MaxPool<T>
+where
+    T: Data<Dim = Ix4>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MaxPool")
+            .field("data", &self.data.borrow())
+            .field("kernel", &self.kernel)
+            .field("stride", &self.stride)
+            .field("padding", &self.padding)
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for MaxPool<T>
+where
+    T: Data<Dim = Ix4>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ MaxPoolBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct MaxPoolBackward<T: ?Sized, U: ?Sized>
+where
+    T: Gradient<Dim = Ix4>,
+    U: Data<Dim = Ix4>,
+{
+    gradient: RefCell<Option<Tensor<Ix4>>>,
+    shape: Ix4,
+    overwrite: Cell<bool>,
+    diff_operand: Rc<T>,
+    no_diff_operand: Rc<MaxPool<U>>,
+}
+
+impl<T: ?Sized, U: ?Sized> MaxPoolBackward<T, U>
+where
+    T: Gradient<Dim = Ix4>,
+    U: Data<Dim = Ix4>,
+{
+    pub fn new(diff_operand: Rc<T>, no_diff_operand: Rc<MaxPool<U>>) -> Self {
+        let shape = no_diff_operand.data().raw_dim();
+
+        Self {
+            gradient: RefCell::new(Some(Tensor::zeros(shape))),
+            shape,
+            overwrite: Cell::new(true),
+            diff_operand,
+            no_diff_operand,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Gradient for MaxPoolBackward<T, U>
+where
+    T: Gradient<Dim = Ix4>,
+    U: Data<Dim = Ix4>,
+{
+    type Dim = Ix4;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Overwrite for MaxPoolBackward<T, U>
+where
+    T: Gradient<Dim = Ix4>,
+    U: Data<Dim = Ix4>,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Backward for MaxPoolBackward<T, U>
+where
+    T: Gradient<Dim = Ix4>,
+    U: Data<Dim = Ix4>,
+{
+    fn backward(&self) {
+        let mut op_grad = self.diff_operand.gradient_mut();
+        if self.diff_operand.can_overwrite() {
+            op_grad.fill(0.);
+            self.diff_operand.set_overwrite(false);
+        }
+
+        let w = op_grad.shape()[3];
+        let indices = self.no_diff_operand.indices();
+        self.gradient().indexed_iter().zip(indices.iter()).for_each(
+            |(((b, c, _, _), grad_el), index)| op_grad[[b, c, index / w, index % w]] += grad_el,
+        );
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape));
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Debug for MaxPoolBackward<T, U>
+where
+    T: Gradient<Dim = Ix4>,
+    U: Data<Dim = Ix4>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MaxPoolBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Display for MaxPoolBackward<T, U>
+where
+    T: Gradient<Dim = Ix4>,
+    U: Data<Dim = Ix4>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ AvgPool ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct AvgPool<T: ?Sized>
+where
+    T: Data<Dim = Ix4>,
+{
+    operand: Rc<T>,
+    data: RefCell<Tensor<Ix4>>,
+    kernel: (usize, usize),
+    stride: (usize, usize),
+    padding: (usize, usize),
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized> AvgPool<T>
+where
+    T: Data<Dim = Ix4>,
+{
+    pub fn new(
+        operand: Rc<T>,
+        kernel: (usize, usize),
+        stride: (usize, usize),
+        padding: (usize, usize),
+    ) -> Self {
+        let shape = pooled_shape(operand.data().raw_dim(), kernel, stride, padding);
+        let data = RefCell::new(Tensor::zeros(shape));
+
+        Self {
+            operand,
+            data,
+            kernel,
+            stride,
+            padding,
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized> Cache for AvgPool<T>
+where
+    T: Data<Dim = Ix4>,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized> Forward for AvgPool<T>
+where
+    T: Data<Dim = Ix4>,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let operand_data = self.operand.data();
+        fit_shape(
+            &self.data,
+            pooled_shape(
+                operand_data.raw_dim(),
+                self.kernel,
+                self.stride,
+                self.padding,
+            ),
+        );
+        let (h, w) = (operand_data.shape()[2], operand_data.shape()[3]);
+        let size = (self.kernel.0 * self.kernel.1) as f32;
+
+        self.data
+            .borrow_mut()
+            .indexed_iter_mut()
+            .for_each(|((b, c, i, j), data_el)| {
+                let (rows, columns) =
+                    window((i, j), (h, w), self.kernel, self.stride, self.padding);
+                let mut sum = 0.;
+                for row in rows {
+                    for column in columns.clone() {
+                        sum += operand_data[[b, c, row, column]];
+                    }
+                }
+                *data_el = sum / size;
+            });
+    }
+}
+
+impl<T: ?Sized> Data for AvgPool<T>
+where
+    T: Data<Dim = Ix4>,
+{
+    type Dim = Ix4;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized> Debug for AvgPool<T>
+where
+    T: Data<Dim = Ix4>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AvgPool")
+            .field("data", &self.data.borrow())
+            .field("kernel", &self.kernel)
+            .field("stride", &self.stride)
+            .field("padding", &self.padding)
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for AvgPool<T>
+where
+    T: Data<Dim = Ix4>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ AvgPoolBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct AvgPoolBackward<T: ?Sized>
+where
+    T: Gradient<Dim = Ix4>,
+{
+    gradient: RefCell<Option<Tensor<Ix4>>>,
+    shape: Ix4,
+    overwrite: Cell<bool>,
+    operand: Rc<T>,
+    kernel: (usize, usize),
+    stride: (usize, usize),
+    padding: (usize, usize),
+}
+
+impl<T: ?Sized> AvgPoolBackward<T>
+where
+    T: Gradient<Dim = Ix4>,
+{
+    pub fn new(
+        operand: Rc<T>,
+        kernel: (usize, usize),
+        stride: (usize, usize),
+        padding: (usize, usize),
+    ) -> Self {
+        let shape = pooled_shape(operand.gradient().raw_dim(), kernel, stride, padding);
+
+        Self {
+            gradient: RefCell::new(Some(Tensor::zeros(shape))),
+            shape,
+            overwrite: Cell::new(true),
+            operand,
+            kernel,
+            stride,
+            padding,
+        }
+    }
+}
+
+impl<T: ?Sized> Gradient for AvgPoolBackward<T>
+where
+    T: Gradient<Dim = Ix4>,
+{
+    type Dim = Ix4;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<T: ?Sized> Overwrite for AvgPoolBackward<T>
+where
+    T: Gradient<Dim = Ix4>,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<T: ?Sized> Backward for AvgPoolBackward<T>
+where
+    T: Gradient<Dim = Ix4>,
+{
+    fn backward(&self) {
+        let mut op_grad = self.operand.gradient_mut();
+        if self.operand.can_overwrite() {
+            op_grad.fill(0.);
+            self.operand.set_overwrite(false);
+        }
+
+        let (h, w) = (op_grad.shape()[2], op_grad.shape()[3]);
+        let size = (self.kernel.0 * self.kernel.1) as f32;
+        self.gradient()
+            .indexed_iter()
+            .for_each(|((b, c, i, j), grad_el)| {
+                let (rows, columns) =
+                    window((i, j), (h, w), self.kernel, self.stride, self.padding);
+                for row in rows {
+                    for column in columns.clone() {
+                        op_grad[[b, c, row, column]] += grad_el / size;
+                    }
+                }
+            });
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape));
+    }
+}
+
+impl<T: ?Sized> Debug for AvgPoolBackward<T>
+where
+    T: Gradient<Dim = Ix4>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AvgPoolBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("kernel", &self.kernel)
+            .field("stride", &self.stride)
+            .field("padding", &self.padding)
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for AvgPoolBackward<T>
+where
+    T: Gradient<Dim = Ix4>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
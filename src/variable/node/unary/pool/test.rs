@@ -0,0 +1,314 @@
+use super::{
+    assert_almost_equals, new_backward_input, new_input, new_tensor, AvgPool, AvgPoolBackward,
+    Backward, Cache, Data, Forward, Gradient, MaxPool, MaxPoolBackward, Overwrite, Tensor,
+};
+
+fn range(shape: (usize, usize, usize, usize)) -> Vec<f32> {
+    (0..shape.0 * shape.1 * shape.2 * shape.3)
+        .map(|el| el as f32)
+        .collect()
+}
+
+mod forward {
+    use super::{
+        assert_almost_equals, new_input, new_tensor, range, AvgPool, Cache, Data, Forward, MaxPool,
+        Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let input = new_input((2, 3, 4, 4), range((2, 3, 4, 4)));
+        let node = MaxPool::new(input.clone(), (2, 2), (2, 2), (0, 0));
+
+        assert_eq!(*node.data(), Tensor::from_elem((2, 3, 2, 2), 0.));
+        assert_eq!(*node.data_mut(), Tensor::from_elem((2, 3, 2, 2), 0.));
+        assert!(!node.was_computed());
+
+        let node = AvgPool::new(input, (3, 3), (2, 2), (1, 1));
+        assert_eq!(*node.data(), Tensor::from_elem((2, 3, 2, 2), 0.));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    #[should_panic(expected = "error: the padding of a pooling must be at most half")]
+    fn too_much_padding() {
+        MaxPool::new(
+            new_input((1, 1, 4, 4), vec![0.; 16]),
+            (2, 2),
+            (1, 1),
+            (2, 2),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "error: the kernel (5, 5) is larger than the padded input")]
+    fn too_large_kernel() {
+        AvgPool::new(
+            new_input((1, 1, 4, 4), vec![0.; 16]),
+            (5, 5),
+            (1, 1),
+            (0, 0),
+        );
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let input = new_input((1, 1, 4, 4), range((1, 1, 4, 4)));
+        let node = MaxPool::new(input, (2, 2), (2, 2), (0, 0));
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn max_pool() {
+        let input = new_input((1, 2, 4, 4), range((1, 2, 4, 4)));
+        let node = MaxPool::new(input.clone(), (2, 2), (2, 2), (0, 0));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((1, 2, 2, 2), vec![5., 7., 13., 15., 21., 23., 29., 31.]),
+        );
+        assert_eq!(*node.indices(), vec![5, 7, 13, 15, 5, 7, 13, 15]);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        input.data_mut().map_inplace(|el| *el = -*el);
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((1, 2, 2, 2), vec![5., 7., 13., 15., 21., 23., 29., 31.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor(
+                (1, 2, 2, 2),
+                vec![0., -2., -8., -10., -16., -18., -24., -26.],
+            ),
+        );
+        assert_eq!(*node.indices(), vec![0, 2, 8, 10, 0, 2, 8, 10]);
+    }
+
+    #[test]
+    fn max_pool_padded() {
+        let input = new_input((1, 1, 4, 4), range((1, 1, 4, 4)));
+        let node = MaxPool::new(input, (3, 3), (2, 2), (1, 1));
+
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((1, 1, 2, 2), vec![5., 7., 13., 15.]),
+        );
+    }
+
+    #[test]
+    fn avg_pool() {
+        let input = new_input((1, 1, 4, 4), range((1, 1, 4, 4)));
+        let node = AvgPool::new(input, (2, 2), (2, 2), (0, 0));
+
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((1, 1, 2, 2), vec![2.5, 4.5, 10.5, 12.5]),
+        );
+    }
+
+    #[test]
+    fn avg_pool_padded() {
+        let input = new_input((1, 1, 4, 4), range((1, 1, 4, 4)));
+        let node = AvgPool::new(input, (3, 3), (2, 2), (1, 1));
+
+        // Padded entries count as zeros.
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((1, 1, 2, 2), vec![10. / 9., 24. / 9., 51. / 9., 90. / 9.]),
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let input = new_input((1, 1, 2, 2), vec![0.; 4]);
+        let node = MaxPool::new(input, (2, 2), (2, 2), (0, 0));
+
+        let output = "MaxPool { data: [[[[0.0]]]], shape=[1, 1, 1, 1], strides=[1, 1, 1, 1], layout=CFcf (0xf), const ndim=4, kernel: (2, 2), stride: (2, 2), padding: (0, 0), computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let input = new_input((1, 1, 2, 2), vec![0.; 4]);
+        let node = AvgPool::new(input, (2, 2), (2, 2), (0, 0));
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        assert_almost_equals, new_backward_input, new_input, new_tensor, range, AvgPoolBackward,
+        Backward, Forward, Gradient, MaxPool, MaxPoolBackward, Overwrite, Tensor,
+    };
+    use std::rc::Rc;
+
+    #[test]
+    fn creation() {
+        let input = new_input((2, 3, 4, 4), range((2, 3, 4, 4)));
+        let node = MaxPoolBackward::new(
+            new_backward_input((2, 3, 4, 4), vec![0.; 96]),
+            Rc::new(MaxPool::new(input, (2, 2), (2, 2), (0, 0))),
+        );
+
+        assert_eq!(*node.gradient(), Tensor::from_elem((2, 3, 2, 2), 0.));
+        assert!(node.can_overwrite());
+
+        let node = AvgPoolBackward::new(
+            new_backward_input((2, 3, 4, 4), vec![0.; 96]),
+            (3, 3),
+            (1, 1),
+            (1, 1),
+        );
+        assert_eq!(*node.gradient(), Tensor::from_elem((2, 3, 4, 4), 0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn max_pool() {
+        let input = new_input((1, 1, 4, 4), range((1, 1, 4, 4)));
+        let forward = Rc::new(MaxPool::new(input, (2, 2), (2, 2), (0, 0)));
+        forward.forward();
+        let diff = new_backward_input((1, 1, 4, 4), vec![0.; 16]);
+        let node = MaxPoolBackward::new(diff.clone(), forward);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((1, 1, 2, 2), vec![1., 2., 3., 4.]);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        let expected = new_tensor(
+            (1, 1, 4, 4),
+            vec![
+                0., 0., 0., 0., 0., 1., 0., 2., 0., 0., 0., 0., 0., 3., 0., 4.,
+            ],
+        );
+        assert_almost_equals(&*diff.gradient(), &expected);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(&*diff.gradient(), &(&expected * 2.));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(&*diff.gradient(), &expected);
+    }
+
+    #[test]
+    fn avg_pool() {
+        let diff = new_backward_input((1, 1, 4, 4), vec![0.; 16]);
+        let node = AvgPoolBackward::new(diff.clone(), (2, 2), (2, 2), (0, 0));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((1, 1, 2, 2), vec![4., 8., 12., 16.]);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        let expected = new_tensor(
+            (1, 1, 4, 4),
+            vec![
+                1., 1., 2., 2., 1., 1., 2., 2., 3., 3., 4., 4., 3., 3., 4., 4.,
+            ],
+        );
+        assert_almost_equals(&*diff.gradient(), &expected);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(&*diff.gradient(), &(&expected * 2.));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(&*diff.gradient(), &expected);
+    }
+
+    #[test]
+    fn avg_pool_overlapping() {
+        let diff = new_backward_input((1, 1, 3, 3), vec![0.; 9]);
+        let node = AvgPoolBackward::new(diff.clone(), (2, 2), (1, 1), (0, 0));
+
+        *node.gradient_mut() = new_tensor((1, 1, 2, 2), vec![4.; 4]);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((1, 1, 3, 3), vec![1., 2., 1., 2., 4., 2., 1., 2., 1.]),
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let node = AvgPoolBackward::new(
+            new_backward_input((1, 1, 2, 2), vec![0.; 4]),
+            (2, 2),
+            (2, 2),
+            (0, 0),
+        );
+
+        let output = "AvgPoolBackward { gradient: Some([[[[0.0]]]], shape=[1, 1, 1, 1], strides=[1, 1, 1, 1], layout=CFcf (0xf), const ndim=4), kernel: (2, 2), stride: (2, 2), padding: (0, 0), overwrite: true }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = AvgPoolBackward::new(
+            new_backward_input((1, 1, 2, 2), vec![0.; 4]),
+            (2, 2),
+            (2, 2),
+            (0, 0),
+        );
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+
+    #[test]
+    fn no_grad() {
+        // MaxPoolBackward
+        let input = new_input((1, 1, 2, 2), vec![0.; 4]);
+        let node = MaxPoolBackward::new(
+            new_backward_input((1, 1, 2, 2), vec![0.; 4]),
+            Rc::new(MaxPool::new(input, (2, 2), (2, 2), (0, 0))),
+        );
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+
+        // AvgPoolBackward
+        let node = AvgPoolBackward::new(
+            new_backward_input((1, 1, 2, 2), vec![0.; 4]),
+            (2, 2),
+            (2, 2),
+            (0, 0),
+        );
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+    }
+}
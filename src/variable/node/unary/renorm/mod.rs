@@ -0,0 +1,291 @@
+#[cfg(test)]
+use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
+use super::{
+    expect_tensor, expect_tensor_mut, fit_shape,
+    norm::{p_norm, p_norm_derivative},
+    Backward, Cache, Data, Forward, Gradient, Overwrite, Tensor,
+};
+use ndarray::{ArrayViewMutD, Axis, Zip};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// Rescales in place each sub-tensor of `tensor` along `axis` whose `p`-norm exceeds `max_norm`,
+/// so that its norm becomes `max_norm`. The other sub-tensors are left untouched.
+pub(crate) fn renorm(mut tensor: ArrayViewMutD<f32>, p: f32, axis: usize, max_norm: f32) {
+    tensor.axis_iter_mut(Axis(axis)).for_each(|mut slice| {
+        let norm = p_norm(slice.iter(), p);
+        if norm > max_norm {
+            slice *= max_norm / norm;
+        }
+    });
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Renorm ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct Renorm<T: ?Sized>
+where
+    T: Data,
+{
+    operand: Rc<T>,
+    p: f32,
+    axis: usize,
+    max_norm: f32,
+    data: RefCell<Tensor<T::Dim>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized> Renorm<T>
+where
+    T: Data,
+{
+    pub fn new(operand: Rc<T>, p: f32, axis: usize, max_norm: f32) -> Self {
+        assert!(
+            p >= 1.,
+            "error: the order of a norm must be at least 1, got {}.",
+            p
+        );
+        let data = Tensor::zeros(operand.data().raw_dim());
+
+        Self {
+            operand,
+            p,
+            axis,
+            max_norm,
+            data: RefCell::new(data),
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized> Cache for Renorm<T>
+where
+    T: Data,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized> Forward for Renorm<T>
+where
+    T: Data,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let operand = self.operand.data();
+        fit_shape(&self.data, operand.raw_dim());
+
+        let mut data = self.data.borrow_mut();
+        data.assign(&*operand);
+        renorm(data.view_mut().into_dyn(), self.p, self.axis, self.max_norm);
+    }
+}
+
+impl<T: ?Sized> Data for Renorm<T>
+where
+    T: Data,
+{
+    type Dim = T::Dim;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized> Debug for Renorm<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Renorm")
+            .field("data", &self.data.borrow())
+            .field("p", &self.p)
+            .field("axis", &self.axis)
+            .field("max_norm", &self.max_norm)
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for Renorm<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ RenormBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct RenormBackward<T: ?Sized, U: ?Sized>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    gradient: RefCell<Option<Tensor<T::Dim>>>,
+    shape: T::Dim,
+    overwrite: Cell<bool>,
+    diff_operand: Rc<T>,
+    no_diff_operand: Rc<U>,
+    p: f32,
+    axis: usize,
+    max_norm: f32,
+}
+
+impl<T: ?Sized, U: ?Sized> RenormBackward<T, U>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    pub fn new(
+        diff_operand: Rc<T>,
+        no_diff_operand: Rc<U>,
+        p: f32,
+        axis: usize,
+        max_norm: f32,
+    ) -> Self {
+        let shape = diff_operand.gradient().raw_dim();
+
+        Self {
+            gradient: RefCell::new(Some(Tensor::zeros(shape.clone()))),
+            shape,
+            overwrite: Cell::new(true),
+            diff_operand,
+            no_diff_operand,
+            p,
+            axis,
+            max_norm,
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Gradient for RenormBackward<T, U>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    type Dim = T::Dim;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Overwrite for RenormBackward<T, U>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Backward for RenormBackward<T, U>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    fn backward(&self) {
+        let mut op_grad = self.diff_operand.gradient_mut();
+        let (operand, grad) = (self.no_diff_operand.data(), self.gradient());
+        let (p, max_norm) = (self.p, self.max_norm);
+
+        if self.diff_operand.can_overwrite() {
+            op_grad.fill(0.);
+            self.diff_operand.set_overwrite(false);
+        }
+
+        let axis = Axis(self.axis);
+        Zip::from(op_grad.view_mut().into_dyn().axis_iter_mut(axis))
+            .and(operand.view().into_dyn().axis_iter(axis))
+            .and(grad.view().into_dyn().axis_iter(axis))
+            .for_each(|mut op_grad_slice, slice, grad_slice| {
+                let norm = p_norm(slice.iter(), p);
+                if norm <= max_norm {
+                    op_grad_slice += &grad_slice;
+                    return;
+                }
+
+                // The scaling factor depends on the slice through its norm.
+                let scale = max_norm / norm;
+                let dot = (&slice * &grad_slice).sum();
+                Zip::from(&mut op_grad_slice)
+                    .and(&slice)
+                    .and(&grad_slice)
+                    .for_each(|op_grad_el, &el, &grad_el| {
+                        *op_grad_el +=
+                            scale * (grad_el - dot / norm * p_norm_derivative(el, norm, p))
+                    });
+            });
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape.clone()));
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Debug for RenormBackward<T, U>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RenormBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("p", &self.p)
+            .field("axis", &self.axis)
+            .field("max_norm", &self.max_norm)
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Display for RenormBackward<T, U>
+where
+    T: Gradient,
+    U: Data<Dim = T::Dim>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,252 @@
+use super::{
+    assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Cache, Data,
+    Forward, Gradient, Overwrite, Renorm, RenormBackward, Tensor,
+};
+
+mod forward {
+    use super::{
+        assert_almost_equals, new_input, new_tensor, Cache, Data, Forward, Renorm, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let input = new_input((2, 3), vec![3., -4., 0., 1., 2., -2.]);
+        let node = Renorm::new(input, 2., 0, 4.);
+
+        assert_eq!(*node.data(), Tensor::from_elem((2, 3), 0.));
+        assert_eq!(*node.data_mut(), Tensor::from_elem((2, 3), 0.));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    #[should_panic(expected = "error: the order of a norm must be at least 1, got 0.")]
+    fn zero_order() {
+        let input = new_input((2, 3), vec![3., -4., 0., 1., 2., -2.]);
+        Renorm::new(input, 0., 0, 4.);
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let input = new_input((2, 3), vec![3., -4., 0., 1., 2., -2.]);
+        let node = Renorm::new(input, 2., 0, 4.);
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward() {
+        let input = new_input((2, 3), vec![3., -4., 0., 1., 2., -2.]);
+        let node = Renorm::new(input.clone(), 2., 0, 4.);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 3), vec![2.4, -3.2, 0., 1., 2., -2.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *input.data_mut() = new_tensor((2, 3), vec![1., 0., 0., 0., 0., 8.]);
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 3), vec![2.4, -3.2, 0., 1., 2., -2.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 3), vec![1., 0., 0., 0., 0., 4.]),
+        );
+    }
+
+    #[test]
+    fn forward_columns() {
+        let input = new_input((2, 3), vec![3., -4., 0., 1., 2., -2.]);
+        let node = Renorm::new(input, 2., 1, 4.);
+
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 3), vec![3., -3.577709, 0., 1., 1.788854, -2.]),
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let input = new_input(1, vec![0.]);
+        let node = Renorm::new(input, 2., 0, 4.);
+
+        let output = "Renorm { data: [0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1, p: 2.0, axis: 0, max_norm: 4.0, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let input = new_input(1, vec![0.]);
+        let node = Renorm::new(input, 2., 0, 4.);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Gradient,
+        Overwrite, RenormBackward, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let node = RenormBackward::new(
+            new_backward_input((2, 3), vec![0.; 6]),
+            new_input((2, 3), vec![3., -4., 0., 1., 2., -2.]),
+            2.,
+            0,
+            4.,
+        );
+
+        assert_eq!(*node.gradient(), Tensor::from_elem((2, 3), 0.));
+        assert_eq!(*node.gradient_mut(), Tensor::from_elem((2, 3), 0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn computation_state_transition() {
+        let diff = new_backward_input((2, 3), vec![0.; 6]);
+        let node = RenormBackward::new(
+            diff.clone(),
+            new_input((2, 3), vec![3., -4., 0., 1., 2., -2.]),
+            2.,
+            0,
+            4.,
+        );
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        diff.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        diff.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!diff.can_overwrite());
+    }
+
+    #[test]
+    fn backward() {
+        let diff = new_backward_input((2, 3), vec![0.; 6]);
+        let node = RenormBackward::new(
+            diff.clone(),
+            new_input((2, 3), vec![3., -4., 0., 1., 2., -2.]),
+            2.,
+            0,
+            4.,
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((2, 3), vec![1., 2., 3., 1., 1., 1.]);
+        assert_almost_equals(
+            &*node.gradient(),
+            &new_tensor((2, 3), vec![1., 2., 3., 1., 1., 1.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![1.28, 0.96, 2.4, 1., 1., 1.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![2.56, 1.92, 4.8, 2., 2., 2.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![1.28, 0.96, 2.4, 1., 1., 1.]),
+        );
+    }
+
+    #[test]
+    fn no_grad() {
+        let node = RenormBackward::new(
+            new_backward_input((3, 3), vec![0.; 9]),
+            new_input((3, 3), vec![0.; 9]),
+            2.,
+            0,
+            4.,
+        );
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+    }
+
+    #[test]
+    fn debug() {
+        let node = RenormBackward::new(
+            new_backward_input(1, vec![0.]),
+            new_input(1, vec![0.]),
+            2.,
+            0,
+            4.,
+        );
+
+        let output = "RenormBackward { gradient: Some([0.0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1), p: 2.0, axis: 0, max_norm: 4.0, overwrite: true }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = RenormBackward::new(
+            new_backward_input(1, vec![0.]),
+            new_input(1, vec![0.]),
+            2.,
+            0,
+            4.,
+        );
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+}
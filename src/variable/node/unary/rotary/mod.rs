@@ -0,0 +1,280 @@
+#[cfg(test)]
+use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
+use super::{
+    expect_tensor, expect_tensor_mut, fit_shape, Backward, Cache, Data, Forward, Gradient,
+    Overwrite, Tensor,
+};
+use ndarray::{ArrayViewD, ArrayViewMutD, Axis, Zip};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// Rotates each pair of consecutive features of `source`, of shape *(..., L, d)*, by an angle
+/// proportional to its position along the second to last axis, and adds the result to
+/// `destination`. The rotation is undone when `inverse` is `true`.
+///
+/// The *i*-th pair of the sequence element at position *m* is rotated by *m · base^(-2i / d)*.
+fn rotate(mut destination: ArrayViewMutD<f32>, source: ArrayViewD<f32>, base: f32, inverse: bool) {
+    let ndim = source.ndim();
+    let features = source.shape()[ndim - 1];
+    let direction = if inverse { -1. } else { 1. };
+
+    Zip::indexed(destination.axis_iter_mut(Axis(ndim - 2)))
+        .and(source.axis_iter(Axis(ndim - 2)))
+        .for_each(|position, mut destination, source| {
+            let rotations: Vec<(f32, f32)> = (0..features / 2)
+                .map(|i| {
+                    let frequency = base.powf(-2. * i as f32 / features as f32);
+                    (direction * position as f32 * frequency).sin_cos()
+                })
+                .collect();
+
+            let last = Axis(ndim - 2);
+            Zip::from(destination.lanes_mut(last))
+                .and(source.lanes(last))
+                .for_each(|mut destination, source| {
+                    for (i, (sin, cos)) in rotations.iter().enumerate() {
+                        let (even, odd) = (source[2 * i], source[2 * i + 1]);
+                        destination[2 * i] += even * cos - odd * sin;
+                        destination[2 * i + 1] += even * sin + odd * cos;
+                    }
+                });
+        });
+}
+
+/// Panics if the features of a variable of shape `shape` cannot be rotated in pairs.
+fn check_shape(shape: &[usize]) {
+    assert!(
+        shape.len() >= 2 && shape[shape.len() - 1].is_multiple_of(2),
+        "error: rotary embeddings need a variable with at least two dimensions and an even \
+        number of features, got shape {:?}.",
+        shape
+    );
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Rotary ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct Rotary<T: ?Sized>
+where
+    T: Data,
+{
+    operand: Rc<T>,
+    base: f32,
+    data: RefCell<Tensor<T::Dim>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized> Rotary<T>
+where
+    T: Data,
+{
+    pub fn new(operand: Rc<T>, base: f32) -> Self {
+        check_shape(operand.data().shape());
+        let data = Tensor::zeros(operand.data().raw_dim());
+
+        Self {
+            operand,
+            base,
+            data: RefCell::new(data),
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized> Cache for Rotary<T>
+where
+    T: Data,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized> Forward for Rotary<T>
+where
+    T: Data,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let operand = self.operand.data();
+        fit_shape(&self.data, operand.raw_dim());
+
+        let mut data = self.data.borrow_mut();
+        data.fill(0.);
+        rotate(
+            data.view_mut().into_dyn(),
+            operand.view().into_dyn(),
+            self.base,
+            false,
+        );
+    }
+}
+
+impl<T: ?Sized> Data for Rotary<T>
+where
+    T: Data,
+{
+    type Dim = T::Dim;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized> Debug for Rotary<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Rotary")
+            .field("data", &self.data.borrow())
+            .field("base", &self.base)
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for Rotary<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ RotaryBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct RotaryBackward<T: ?Sized>
+where
+    T: Gradient,
+{
+    gradient: RefCell<Option<Tensor<T::Dim>>>,
+    shape: T::Dim,
+    overwrite: Cell<bool>,
+    diff_operand: Rc<T>,
+    base: f32,
+}
+
+impl<T: ?Sized> RotaryBackward<T>
+where
+    T: Gradient,
+{
+    pub fn new(diff_operand: Rc<T>, base: f32) -> Self {
+        let shape = diff_operand.gradient().raw_dim();
+
+        Self {
+            gradient: RefCell::new(Some(Tensor::zeros(shape.clone()))),
+            shape,
+            overwrite: Cell::new(true),
+            diff_operand,
+            base,
+        }
+    }
+}
+
+impl<T: ?Sized> Gradient for RotaryBackward<T>
+where
+    T: Gradient,
+{
+    type Dim = T::Dim;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<T: ?Sized> Overwrite for RotaryBackward<T>
+where
+    T: Gradient,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<T: ?Sized> Backward for RotaryBackward<T>
+where
+    T: Gradient,
+{
+    fn backward(&self) {
+        let mut op_grad = self.diff_operand.gradient_mut();
+        let grad = self.gradient();
+
+        if self.diff_operand.can_overwrite() {
+            op_grad.fill(0.);
+            self.diff_operand.set_overwrite(false);
+        }
+
+        // Rotations are orthogonal, their transpose is the inverse rotation.
+        rotate(
+            op_grad.view_mut().into_dyn(),
+            grad.view().into_dyn(),
+            self.base,
+            true,
+        );
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape.clone()));
+    }
+}
+
+impl<T: ?Sized> Debug for RotaryBackward<T>
+where
+    T: Gradient,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RotaryBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("base", &self.base)
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for RotaryBackward<T>
+where
+    T: Gradient,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
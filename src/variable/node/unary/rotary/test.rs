@@ -0,0 +1,250 @@
+use super::{
+    assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Cache, Data,
+    Forward, Gradient, Overwrite, Rotary, RotaryBackward, Tensor,
+};
+
+const INPUT: [f32; 12] = [1., 2., 3., 4., 1., 2., 3., 4., 1., 2., 3., 4.];
+
+mod forward {
+    use super::{
+        assert_almost_equals, new_input, new_tensor, Cache, Data, Forward, Rotary, Tensor, INPUT,
+    };
+
+    #[test]
+    fn creation() {
+        let node = Rotary::new(new_input((3, 4), INPUT.to_vec()), 100.);
+
+        assert_eq!(*node.data(), Tensor::from_elem((3, 4), 0.));
+        assert_eq!(*node.data_mut(), Tensor::from_elem((3, 4), 0.));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "error: rotary embeddings need a variable with at least two \
+                               dimensions and an even number of features, got shape [4, 3]."
+    )]
+    fn odd_features() {
+        Rotary::new(new_input((4, 3), INPUT.to_vec()), 100.);
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let node = Rotary::new(new_input((3, 4), INPUT.to_vec()), 100.);
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward() {
+        let input = new_input((3, 4), INPUT.to_vec());
+        let node = Rotary::new(input.clone(), 100.);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor(
+                (3, 4),
+                vec![
+                    1., 2., 3., 4., -1.14264, 1.922076, 2.585679, 4.279517, -2.234742, 0.077004,
+                    2.145522, 4.516274,
+                ],
+            ),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *input.data_mut() = new_tensor((3, 4), vec![0.; 12]);
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor(
+                (3, 4),
+                vec![
+                    1., 2., 3., 4., -1.14264, 1.922076, 2.585679, 4.279517, -2.234742, 0.077004,
+                    2.145522, 4.516274,
+                ],
+            ),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_eq!(*node.data(), Tensor::from_elem((3, 4), 0.));
+    }
+
+    #[test]
+    fn forward_batched() {
+        let node = Rotary::new(new_input((2, 3, 4), [INPUT, INPUT].concat()), 100.);
+
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor(
+                (2, 3, 4),
+                [
+                    1., 2., 3., 4., -1.14264, 1.922076, 2.585679, 4.279517, -2.234742, 0.077004,
+                    2.145522, 4.516274,
+                ]
+                .repeat(2),
+            ),
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let node = Rotary::new(new_input((1, 2), vec![0.; 2]), 100.);
+
+        let output = "Rotary { data: [[0.0, 0.0]], shape=[1, 2], strides=[2, 1], layout=CFcf (0xf), const ndim=2, base: 100.0, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = Rotary::new(new_input((1, 2), vec![0.; 2]), 100.);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        assert_almost_equals, new_backward_input, new_tensor, Backward, Gradient, Overwrite,
+        RotaryBackward, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let node = RotaryBackward::new(new_backward_input((3, 4), vec![0.; 12]), 100.);
+
+        assert_eq!(*node.gradient(), Tensor::from_elem((3, 4), 0.));
+        assert_eq!(*node.gradient_mut(), Tensor::from_elem((3, 4), 0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn computation_state_transition() {
+        let diff = new_backward_input((3, 4), vec![0.; 12]);
+        let node = RotaryBackward::new(diff.clone(), 100.);
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        diff.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        diff.set_overwrite(true);
+        assert!(node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!diff.can_overwrite());
+    }
+
+    #[test]
+    fn backward() {
+        let diff = new_backward_input((3, 4), vec![0.; 12]);
+        let node = RotaryBackward::new(diff.clone(), 100.);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((3, 4), vec![1.; 12]);
+        assert_almost_equals(&*node.gradient(), &new_tensor((3, 4), vec![1.; 12]));
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (3, 4),
+                vec![
+                    1., 1., 1., 1., 1.381773, -0.301169, 1.094838, 0.895171, 0.493151, -1.325444,
+                    1.178736, 0.781397,
+                ],
+            ),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (3, 4),
+                vec![
+                    2., 2., 2., 2., 2.763546, -0.602338, 2.189676, 1.790342, 0.986302, -2.650888,
+                    2.357472, 1.562794,
+                ],
+            ),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (3, 4),
+                vec![
+                    1., 1., 1., 1., 1.381773, -0.301169, 1.094838, 0.895171, 0.493151, -1.325444,
+                    1.178736, 0.781397,
+                ],
+            ),
+        );
+    }
+
+    #[test]
+    fn no_grad() {
+        let node = RotaryBackward::new(new_backward_input((3, 4), vec![0.; 12]), 100.);
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+    }
+
+    #[test]
+    fn debug() {
+        let node = RotaryBackward::new(new_backward_input((1, 2), vec![0.; 2]), 100.);
+
+        let output = "RotaryBackward { gradient: Some([[0.0, 0.0]], shape=[1, 2], strides=[2, 1], layout=CFcf (0xf), const ndim=2), base: 100.0, overwrite: true }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = RotaryBackward::new(new_backward_input((1, 2), vec![0.; 2]), 100.);
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+}
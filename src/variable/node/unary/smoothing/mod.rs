@@ -0,0 +1,500 @@
+#[cfg(test)]
+use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
+use super::{
+    expect_tensor, expect_tensor_mut, fit_shape, Backward, Cache, Data, Forward, Gradient,
+    Overwrite, Tensor,
+};
+use ndarray::{ArrayView1, ArrayViewMut1, Axis, Zip};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+/// Computes the exponential moving average of `lane` into `out`.
+fn ema(lane: ArrayView1<f32>, mut out: ArrayViewMut1<f32>, alpha: f32) {
+    let mut average = 0.;
+    for (t, (out_el, &el)) in out.iter_mut().zip(lane).enumerate() {
+        average = if t == 0 {
+            el
+        } else {
+            alpha * el + (1. - alpha) * average
+        };
+        *out_el = average;
+    }
+}
+
+/// Accumulates into `op_grad` the gradient of the exponential moving average of a lane.
+fn ema_backward(grad: ArrayView1<f32>, mut op_grad: ArrayViewMut1<f32>, alpha: f32) {
+    let mut carried = 0.;
+    for (t, (op_grad_el, &grad_el)) in op_grad.iter_mut().zip(grad).enumerate().rev() {
+        carried = grad_el + (1. - alpha) * carried;
+        *op_grad_el += if t == 0 { carried } else { alpha * carried };
+    }
+}
+
+/// Computes the trailing moving average of `lane` into `out`. The first elements are averaged
+/// over the shorter windows available.
+fn rolling_mean(lane: ArrayView1<f32>, mut out: ArrayViewMut1<f32>, window: usize) {
+    let mut sum = 0.;
+    for t in 0..lane.len() {
+        sum += lane[t];
+        if t >= window {
+            sum -= lane[t - window];
+        }
+        out[t] = sum / (t + 1).min(window) as f32;
+    }
+}
+
+/// Accumulates into `op_grad` the gradient of the trailing moving average of a lane.
+fn rolling_mean_backward(grad: ArrayView1<f32>, mut op_grad: ArrayViewMut1<f32>, window: usize) {
+    let scaled = |t: usize| grad[t] / (t + 1).min(window) as f32;
+    let mut sum = 0.;
+    for t in (0..grad.len()).rev() {
+        sum += scaled(t);
+        if t + window < grad.len() {
+            sum -= scaled(t + window);
+        }
+        op_grad[t] += sum;
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Ema ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct Ema<T: ?Sized>
+where
+    T: Data,
+{
+    operand: Rc<T>,
+    data: RefCell<Tensor<T::Dim>>,
+    axis: usize,
+    alpha: f32,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized> Ema<T>
+where
+    T: Data,
+{
+    pub fn new(operand: Rc<T>, axis: usize, alpha: f32) -> Self {
+        assert!(
+            alpha > 0. && alpha <= 1.,
+            "error: {} is not a valid smoothing factor.",
+            alpha
+        );
+        let data = RefCell::new(Tensor::zeros(operand.data().raw_dim()));
+
+        Self {
+            operand,
+            data,
+            axis,
+            alpha,
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized> Cache for Ema<T>
+where
+    T: Data,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized> Forward for Ema<T>
+where
+    T: Data,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        fit_shape(&self.data, self.operand.data().raw_dim());
+        let alpha = self.alpha;
+        Zip::from(self.data.borrow_mut().lanes_mut(Axis(self.axis)))
+            .and(self.operand.data().lanes(Axis(self.axis)))
+            .for_each(|out, lane| ema(lane, out, alpha));
+    }
+}
+
+impl<T: ?Sized> Data for Ema<T>
+where
+    T: Data,
+{
+    type Dim = T::Dim;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized> Debug for Ema<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ema")
+            .field("data", &self.data.borrow())
+            .field("axis", &self.axis)
+            .field("alpha", &self.alpha)
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for Ema<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ EmaBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct EmaBackward<T: ?Sized>
+where
+    T: Gradient,
+{
+    gradient: RefCell<Option<Tensor<T::Dim>>>,
+    shape: T::Dim,
+    overwrite: Cell<bool>,
+    operand: Rc<T>,
+    axis: usize,
+    alpha: f32,
+}
+
+impl<T: ?Sized> EmaBackward<T>
+where
+    T: Gradient,
+{
+    pub fn new(operand: Rc<T>, axis: usize, alpha: f32) -> Self {
+        let shape = operand.gradient().raw_dim();
+
+        Self {
+            gradient: RefCell::new(Some(Tensor::zeros(shape.clone()))),
+            shape,
+            overwrite: Cell::new(true),
+            operand,
+            axis,
+            alpha,
+        }
+    }
+}
+
+impl<T: ?Sized> Gradient for EmaBackward<T>
+where
+    T: Gradient,
+{
+    type Dim = T::Dim;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<T: ?Sized> Overwrite for EmaBackward<T>
+where
+    T: Gradient,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<T: ?Sized> Backward for EmaBackward<T>
+where
+    T: Gradient,
+{
+    fn backward(&self) {
+        let mut op_grad = self.operand.gradient_mut();
+        if self.operand.can_overwrite() {
+            op_grad.fill(0.);
+            self.operand.set_overwrite(false);
+        }
+
+        let alpha = self.alpha;
+        Zip::from(op_grad.lanes_mut(Axis(self.axis)))
+            .and(self.gradient().lanes(Axis(self.axis)))
+            .for_each(|op_grad_lane, grad_lane| ema_backward(grad_lane, op_grad_lane, alpha));
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape.clone()));
+    }
+}
+
+impl<T: ?Sized> Debug for EmaBackward<T>
+where
+    T: Gradient,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EmaBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("axis", &self.axis)
+            .field("alpha", &self.alpha)
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for EmaBackward<T>
+where
+    T: Gradient,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ RollingMean ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct RollingMean<T: ?Sized>
+where
+    T: Data,
+{
+    operand: Rc<T>,
+    data: RefCell<Tensor<T::Dim>>,
+    axis: usize,
+    window: usize,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized> RollingMean<T>
+where
+    T: Data,
+{
+    pub fn new(operand: Rc<T>, axis: usize, window: usize) -> Self {
+        assert!(window > 0, "error: the window must be positive.");
+        let data = RefCell::new(Tensor::zeros(operand.data().raw_dim()));
+
+        Self {
+            operand,
+            data,
+            axis,
+            window,
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized> Cache for RollingMean<T>
+where
+    T: Data,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized> Forward for RollingMean<T>
+where
+    T: Data,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        fit_shape(&self.data, self.operand.data().raw_dim());
+        let window = self.window;
+        Zip::from(self.data.borrow_mut().lanes_mut(Axis(self.axis)))
+            .and(self.operand.data().lanes(Axis(self.axis)))
+            .for_each(|out, lane| rolling_mean(lane, out, window));
+    }
+}
+
+impl<T: ?Sized> Data for RollingMean<T>
+where
+    T: Data,
+{
+    type Dim = T::Dim;
+
+    fn data(&self) -> Ref<Tensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized> Debug for RollingMean<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RollingMean")
+            .field("data", &self.data.borrow())
+            .field("axis", &self.axis)
+            .field("window", &self.window)
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for RollingMean<T>
+where
+    T: Data,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ RollingMeanBackward ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct RollingMeanBackward<T: ?Sized>
+where
+    T: Gradient,
+{
+    gradient: RefCell<Option<Tensor<T::Dim>>>,
+    shape: T::Dim,
+    overwrite: Cell<bool>,
+    operand: Rc<T>,
+    axis: usize,
+    window: usize,
+}
+
+impl<T: ?Sized> RollingMeanBackward<T>
+where
+    T: Gradient,
+{
+    pub fn new(operand: Rc<T>, axis: usize, window: usize) -> Self {
+        let shape = operand.gradient().raw_dim();
+
+        Self {
+            gradient: RefCell::new(Some(Tensor::zeros(shape.clone()))),
+            shape,
+            overwrite: Cell::new(true),
+            operand,
+            axis,
+            window,
+        }
+    }
+}
+
+impl<T: ?Sized> Gradient for RollingMeanBackward<T>
+where
+    T: Gradient,
+{
+    type Dim = T::Dim;
+
+    fn gradient(&self) -> Ref<Tensor<Self::Dim>> {
+        expect_tensor(&self.gradient)
+    }
+
+    fn gradient_mut(&self) -> RefMut<Tensor<Self::Dim>> {
+        expect_tensor_mut(&self.gradient)
+    }
+}
+
+impl<T: ?Sized> Overwrite for RollingMeanBackward<T>
+where
+    T: Gradient,
+{
+    fn can_overwrite(&self) -> bool {
+        self.overwrite.get()
+    }
+
+    fn set_overwrite(&self, state: bool) {
+        self.overwrite.set(state);
+    }
+}
+
+impl<T: ?Sized> Backward for RollingMeanBackward<T>
+where
+    T: Gradient,
+{
+    fn backward(&self) {
+        let mut op_grad = self.operand.gradient_mut();
+        if self.operand.can_overwrite() {
+            op_grad.fill(0.);
+            self.operand.set_overwrite(false);
+        }
+
+        let window = self.window;
+        Zip::from(op_grad.lanes_mut(Axis(self.axis)))
+            .and(self.gradient().lanes(Axis(self.axis)))
+            .for_each(|op_grad_lane, grad_lane| {
+                rolling_mean_backward(grad_lane, op_grad_lane, window)
+            });
+    }
+
+    fn no_grad(&self) {
+        *self.gradient.borrow_mut() = None;
+    }
+
+    fn with_grad(&self) {
+        *self.gradient.borrow_mut() = Some(Tensor::zeros(self.shape.clone()));
+    }
+}
+
+impl<T: ?Sized> Debug for RollingMeanBackward<T>
+where
+    T: Gradient,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RollingMeanBackward")
+            .field("gradient", &self.gradient.borrow())
+            .field("axis", &self.axis)
+            .field("window", &self.window)
+            .field("overwrite", &self.overwrite.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for RollingMeanBackward<T>
+where
+    T: Gradient,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &*self.gradient.borrow() {
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
@@ -0,0 +1,363 @@
+use super::{
+    assert_almost_equals, new_backward_input, new_input, new_tensor, Backward, Cache, Data, Ema,
+    EmaBackward, Forward, Gradient, Overwrite, RollingMean, RollingMeanBackward, Tensor,
+};
+
+mod forward {
+    use super::{
+        assert_almost_equals, new_input, new_tensor, Cache, Data, Ema, Forward, RollingMean, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let input = new_input((2, 3), vec![1., 2., 3., 4., 6., 8.]);
+        let node = Ema::new(input, 1, 0.5);
+
+        assert_eq!(*node.data(), Tensor::from_elem((2, 3), 0.));
+        assert_eq!(*node.data_mut(), Tensor::from_elem((2, 3), 0.));
+        assert!(!node.was_computed());
+
+        let input = new_input((2, 3), vec![1., 2., 3., 4., 6., 8.]);
+        let node = RollingMean::new(input, 1, 2);
+
+        assert_eq!(*node.data(), Tensor::from_elem((2, 3), 0.));
+        assert_eq!(*node.data_mut(), Tensor::from_elem((2, 3), 0.));
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    #[should_panic(expected = "error: 1.5 is not a valid smoothing factor.")]
+    fn creation_fail_ema() {
+        Ema::new(new_input(3, vec![0.; 3]), 0, 1.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "error: the window must be positive.")]
+    fn creation_fail_rolling_mean() {
+        RollingMean::new(new_input(3, vec![0.; 3]), 0, 0);
+    }
+
+    #[test]
+    fn computation_was_computed_transition() {
+        let input = new_input((2, 3), vec![1., 2., 3., 4., 6., 8.]);
+        let node = Ema::new(input, 1, 0.5);
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.forward();
+        assert!(node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+
+        node.reset_computation();
+        assert!(!node.was_computed());
+    }
+
+    #[test]
+    fn forward_ema() {
+        let input = new_input((2, 3), vec![1., 2., 3., 4., 6., 8.]);
+        let node = Ema::new(input.clone(), 1, 0.5);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 3), vec![1., 1.5, 2.25, 4., 5., 6.5]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *input.data_mut() = new_tensor((2, 3), vec![0.; 6]);
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 3), vec![1., 1.5, 2.25, 4., 5., 6.5]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((2, 3), vec![0.; 6]));
+    }
+
+    #[test]
+    fn forward_ema_rows() {
+        let input = new_input((2, 3), vec![1., 2., 3., 4., 6., 8.]);
+        let node = Ema::new(input, 0, 0.25);
+
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 3), vec![1., 2., 3., 1.75, 3., 4.25]),
+        );
+    }
+
+    #[test]
+    fn forward_rolling_mean() {
+        let input = new_input((2, 3), vec![1., 2., 3., 4., 6., 8.]);
+        let node = RollingMean::new(input.clone(), 1, 2);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 3), vec![1., 1.5, 2.5, 4., 5., 7.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *input.data_mut() = new_tensor((2, 3), vec![0.; 6]);
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 3), vec![1., 1.5, 2.5, 4., 5., 7.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.reset_computation();
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor((2, 3), vec![0.; 6]));
+    }
+
+    #[test]
+    fn forward_rolling_mean_rows() {
+        let input = new_input((2, 3), vec![1., 2., 3., 4., 6., 8.]);
+        let node = RollingMean::new(input, 0, 2);
+
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor((2, 3), vec![1., 2., 3., 2.5, 4., 5.5]),
+        );
+    }
+
+    #[test]
+    fn forward_rolling_mean_long_window() {
+        let input = new_input(4, vec![1., 3., 5., 7.]);
+        let node = RollingMean::new(input, 0, 10);
+
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor(4, vec![1., 2., 3., 4.]));
+    }
+
+    #[test]
+    fn forward_shape_change() {
+        let input = new_input((2, 2), vec![0.; 4]);
+        let (ema, rolling_mean) = (
+            Ema::new(input.clone(), 1, 0.5),
+            RollingMean::new(input.clone(), 1, 2),
+        );
+
+        *input.data_mut() = new_tensor((2, 3), vec![2., 4., 8., 0., 2., 2.]);
+        ema.forward();
+        rolling_mean.forward();
+        assert_almost_equals(
+            &*ema.data(),
+            &new_tensor((2, 3), vec![2., 3., 5.5, 0., 1., 1.5]),
+        );
+        assert_almost_equals(
+            &*rolling_mean.data(),
+            &new_tensor((2, 3), vec![2., 3., 6., 0., 1., 2.]),
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let input = new_input(2, vec![1., 2.]);
+        let node = Ema::new(input.clone(), 0, 0.5);
+
+        let output = "Ema { data: [0.0, 0.0], shape=[2], strides=[1], layout=CFcf (0xf), const ndim=1, axis: 0, alpha: 0.5, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+
+        let node = RollingMean::new(input, 0, 2);
+
+        let output = "RollingMean { data: [0.0, 0.0], shape=[2], strides=[1], layout=CFcf (0xf), const ndim=1, axis: 0, window: 2, computed: false }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let input = new_input((2, 3), vec![1., 2., 3., 4., 6., 8.]);
+        let node = Ema::new(input.clone(), 1, 0.5);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+
+        let node = RollingMean::new(input, 1, 2);
+
+        assert_eq!(format!("{}", node.data()), format!("{}", node));
+    }
+}
+
+mod backward {
+    use super::{
+        assert_almost_equals, new_backward_input, new_tensor, Backward, EmaBackward, Gradient,
+        Overwrite, RollingMeanBackward, Tensor,
+    };
+
+    #[test]
+    fn creation() {
+        let node = EmaBackward::new(new_backward_input((2, 3), vec![0.; 6]), 1, 0.5);
+
+        assert_eq!(*node.gradient(), Tensor::from_elem((2, 3), 0.));
+        assert_eq!(*node.gradient_mut(), Tensor::from_elem((2, 3), 0.));
+        assert!(node.can_overwrite());
+
+        let node = RollingMeanBackward::new(new_backward_input((2, 3), vec![0.; 6]), 1, 2);
+
+        assert_eq!(*node.gradient(), Tensor::from_elem((2, 3), 0.));
+        assert_eq!(*node.gradient_mut(), Tensor::from_elem((2, 3), 0.));
+        assert!(node.can_overwrite());
+    }
+
+    #[test]
+    fn computation_state_transition() {
+        let diff = new_backward_input((2, 3), vec![0.; 6]);
+        let node = EmaBackward::new(diff.clone(), 1, 0.5);
+
+        node.backward();
+        assert!(node.can_overwrite());
+        assert!(!diff.can_overwrite());
+
+        diff.set_overwrite(true);
+        node.set_overwrite(false);
+        assert!(!node.can_overwrite());
+        assert!(diff.can_overwrite());
+
+        node.backward();
+        assert!(!node.can_overwrite());
+        assert!(!diff.can_overwrite());
+    }
+
+    #[test]
+    fn backward_ema() {
+        let diff = new_backward_input((2, 3), vec![0.; 6]);
+        let node = EmaBackward::new(diff.clone(), 1, 0.5);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((2, 3), vec![1., 1., 1., 0., 0., 2.]);
+        assert_almost_equals(
+            &*node.gradient(),
+            &new_tensor((2, 3), vec![1., 1., 1., 0., 0., 2.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![1.75, 0.75, 0.5, 0.5, 0.5, 1.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![3.5, 1.5, 1., 1., 1., 2.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![1.75, 0.75, 0.5, 0.5, 0.5, 1.]),
+        );
+    }
+
+    #[test]
+    fn backward_rolling_mean() {
+        let diff = new_backward_input((2, 3), vec![0.; 6]);
+        let node = RollingMeanBackward::new(diff.clone(), 1, 2);
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        *node.gradient_mut() = new_tensor((2, 3), vec![1., 1., 1., 0., 0., 2.]);
+        assert_almost_equals(
+            &*node.gradient(),
+            &new_tensor((2, 3), vec![1., 1., 1., 0., 0., 2.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![1.5, 1., 0.5, 0., 1., 1.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![3., 2., 1., 0., 2., 2.]),
+        );
+
+        // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Third Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+        diff.set_overwrite(true);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor((2, 3), vec![1.5, 1., 0.5, 0., 1., 1.]),
+        );
+    }
+
+    #[test]
+    fn backward_rolling_mean_rows() {
+        let diff = new_backward_input((3, 2), vec![0.; 6]);
+        let node = RollingMeanBackward::new(diff.clone(), 0, 3);
+
+        *node.gradient_mut() = new_tensor((3, 2), vec![1.; 6]);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(
+                (3, 2),
+                vec![11. / 6., 11. / 6., 5. / 6., 5. / 6., 1. / 3., 1. / 3.],
+            ),
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let node = EmaBackward::new(new_backward_input(2, vec![0.; 2]), 0, 0.5);
+
+        let output = "EmaBackward { gradient: Some([0.0, 0.0], shape=[2], strides=[1], layout=CFcf (0xf), const ndim=1), axis: 0, alpha: 0.5, overwrite: true }";
+
+        assert_eq!(output, format!("{:?}", node));
+
+        let node = RollingMeanBackward::new(new_backward_input(2, vec![0.; 2]), 0, 2);
+
+        let output = "RollingMeanBackward { gradient: Some([0.0, 0.0], shape=[2], strides=[1], layout=CFcf (0xf), const ndim=1), axis: 0, window: 2, overwrite: true }";
+
+        assert_eq!(output, format!("{:?}", node));
+    }
+
+    #[test]
+    fn display() {
+        let node = EmaBackward::new(new_backward_input((2, 3), vec![0.; 6]), 1, 0.5);
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+
+        let node = RollingMeanBackward::new(new_backward_input((2, 3), vec![0.; 6]), 1, 2);
+
+        assert_eq!(format!("{}", node.gradient()), format!("{}", node));
+    }
+
+    #[test]
+    fn no_grad() {
+        let node = EmaBackward::new(new_backward_input((2, 3), vec![0.; 6]), 1, 0.5);
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+
+        let node = RollingMeanBackward::new(new_backward_input((2, 3), vec![0.; 6]), 1, 2);
+
+        node.no_grad();
+        assert!(node.gradient.borrow().is_none());
+
+        node.with_grad();
+        assert_eq!(&*node.gradient(), Tensor::zeros(node.shape));
+    }
+}
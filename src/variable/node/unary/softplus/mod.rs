@@ -1,7 +1,8 @@
 #[cfg(test)]
 use super::{assert_almost_equals, new_backward_input, new_input, new_tensor};
 use super::{
-    expect_tensor, expect_tensor_mut, Backward, Cache, Data, Forward, Gradient, Overwrite, Tensor,
+    expect_tensor, expect_tensor_mut, fit_shape, Backward, Cache, Data, Forward, Gradient,
+    Overwrite, Tensor,
 };
 use ndarray::Zip;
 use std::{
@@ -10,6 +11,25 @@ use std::{
     rc::Rc,
 };
 
+/// Computes the softplus of `x` with parameter `beta`, reverting to the identity when `beta * x`
+/// exceeds `threshold`.
+fn softplus(x: f32, beta: f32, threshold: f32) -> f32 {
+    if beta * x > threshold {
+        x
+    } else {
+        (beta * x).exp().ln_1p() / beta
+    }
+}
+
+/// Computes the derivative of the softplus at `x`, see [`softplus`].
+fn softplus_derivative(x: f32, beta: f32, threshold: f32) -> f32 {
+    if beta * x > threshold {
+        1.
+    } else {
+        1. / (1. + (-beta * x).exp())
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ SoftPlus ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -18,6 +38,8 @@ where
     T: Data,
 {
     operand: Rc<T>,
+    beta: f32,
+    threshold: f32,
     data: RefCell<Tensor<T::Dim>>,
     computed: Cell<bool>,
 }
@@ -26,11 +48,18 @@ impl<T: ?Sized> SoftPlus<T>
 where
     T: Data,
 {
-    pub fn new(operand: Rc<T>) -> Self {
+    pub fn new(operand: Rc<T>, beta: f32, threshold: f32) -> Self {
+        assert!(
+            beta > 0.,
+            "error: the beta of a softplus must be positive, got {}.",
+            beta
+        );
         let data = RefCell::new(Tensor::zeros(operand.data().raw_dim()));
 
         Self {
             operand,
+            beta,
+            threshold,
             data,
             computed: Cell::new(false),
         }
@@ -60,9 +89,10 @@ where
         }
 
         self.computed.set(true);
+        fit_shape(&self.data, self.operand.data().raw_dim());
         Zip::from(&mut *self.data.borrow_mut())
             .and(&*self.operand.data())
-            .for_each(|v, o| *v = (1.0 + o.exp()).ln());
+            .for_each(|v, o| *v = softplus(*o, self.beta, self.threshold));
     }
 }
 
@@ -88,6 +118,8 @@ where
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("SoftPlus")
             .field("data", &self.data.borrow())
+            .field("beta", &self.beta)
+            .field("threshold", &self.threshold)
             .field("computed", &self.computed.get())
             .finish()
     }
@@ -98,7 +130,7 @@ where
     T: Data,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        write!(f, "{}", &self.data.borrow())
+        crate::print::fmt_tensor(&self.data.borrow(), f)
     }
 }
 
@@ -115,6 +147,8 @@ where
     overwrite: Cell<bool>,
     diff_operand: Rc<T>,
     no_diff_operand: Rc<U>,
+    beta: f32,
+    threshold: f32,
 }
 
 impl<T: ?Sized, U: ?Sized> SoftPlusBackward<T, U>
@@ -122,7 +156,7 @@ where
     T: Gradient,
     U: Data<Dim = T::Dim>,
 {
-    pub fn new(diff_operand: Rc<T>, no_diff_operand: Rc<U>) -> Self {
+    pub fn new(diff_operand: Rc<T>, no_diff_operand: Rc<U>, beta: f32, threshold: f32) -> Self {
         let shape = diff_operand.gradient().raw_dim();
 
         Self {
@@ -131,6 +165,8 @@ where
             overwrite: Cell::new(true),
             diff_operand,
             no_diff_operand,
+            beta,
+            threshold,
         }
     }
 }
@@ -178,12 +214,12 @@ where
         let zip = Zip::from(&mut *op_grad).and(&*grad).and(&*op_data);
         if self.diff_operand.can_overwrite() {
             zip.for_each(|op_grad_el, grad_el, op_data_el| {
-                *op_grad_el = grad_el / (1.0 + (-*op_data_el).exp())
+                *op_grad_el = grad_el * softplus_derivative(*op_data_el, self.beta, self.threshold)
             });
             self.diff_operand.set_overwrite(false);
         } else {
             zip.for_each(|op_grad_el, grad_el, op_data_el| {
-                *op_grad_el += grad_el / (1.0 + (-*op_data_el).exp())
+                *op_grad_el += grad_el * softplus_derivative(*op_data_el, self.beta, self.threshold)
             });
         }
     }
@@ -217,7 +253,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match &*self.gradient.borrow() {
-            Some(gradient) => write!(f, "{}", &gradient),
+            Some(gradient) => crate::print::fmt_tensor(gradient, f),
             None => write!(f, "None"),
         }
     }
@@ -11,7 +11,7 @@ mod forward {
     #[test]
     fn creation() {
         let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
-        let node = SoftPlus::new(input);
+        let node = SoftPlus::new(input, 1., 20.);
 
         assert_eq!(*node.data(), Tensor::from_elem((3, 3), 0.));
         assert_eq!(*node.data_mut(), Tensor::from_elem((3, 3), 0.));
@@ -21,7 +21,7 @@ mod forward {
     #[test]
     fn computation_was_computed_transition() {
         let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
-        let node = SoftPlus::new(input);
+        let node = SoftPlus::new(input, 1., 20.);
 
         node.forward();
         assert!(node.was_computed());
@@ -40,7 +40,7 @@ mod forward {
     #[test]
     fn forward() {
         let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
-        let node = SoftPlus::new(input.clone());
+        let node = SoftPlus::new(input.clone(), 1., 20.);
 
         // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
         node.forward();
@@ -90,12 +90,39 @@ mod forward {
         );
     }
 
+    #[test]
+    fn forward_beta_threshold() {
+        let input = new_input(4, vec![-1., 0., 2., 30.]);
+        let node = SoftPlus::new(input, 2., 5.);
+
+        node.forward();
+        assert_almost_equals(
+            &*node.data(),
+            &new_tensor(4, vec![0.063464, 0.346574, 2.009075, 30.]),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "error: the beta of a softplus must be positive, got 0.")]
+    fn creation_non_positive_beta() {
+        SoftPlus::new(new_input(4, vec![-1., 0., 2., 30.]), 0., 20.);
+    }
+
+    #[test]
+    fn forward_large() {
+        let input = new_input(2, vec![100., 1000.]);
+        let node = SoftPlus::new(input, 1., 20.);
+
+        node.forward();
+        assert_almost_equals(&*node.data(), &new_tensor(2, vec![100., 1000.]));
+    }
+
     #[test]
     fn debug() {
         let input = new_input((3, 3), vec![1., 2., 3., 4., 5., 6., 7., 8., 9.]);
-        let node = SoftPlus::new(input.clone());
+        let node = SoftPlus::new(input.clone(), 1., 20.);
 
-        let output = "SoftPlus { data: [[0.0, 0.0, 0.0],\n [0.0, 0.0, 0.0],\n [0.0, 0.0, 0.0]], shape=[3, 3], strides=[3, 1], layout=Cc (0x5), const ndim=2, computed: false }";
+        let output = "SoftPlus { data: [[0.0, 0.0, 0.0],\n [0.0, 0.0, 0.0],\n [0.0, 0.0, 0.0]], shape=[3, 3], strides=[3, 1], layout=Cc (0x5), const ndim=2, beta: 1.0, threshold: 20.0, computed: false }";
 
         assert_eq!(output, format!("{:?}", node));
     }
@@ -103,7 +130,7 @@ mod forward {
     #[test]
     fn display() {
         let input = new_input((3, 3), vec![1., 2., 3., 4., 5., 6., 7., 8., 9.]);
-        let node = SoftPlus::new(input.clone());
+        let node = SoftPlus::new(input.clone(), 1., 20.);
 
         assert_eq!(format!("{}", node.data()), format!("{}", node));
     }
@@ -120,6 +147,8 @@ mod backward {
         let node = SoftPlusBackward::new(
             new_backward_input(3, vec![0.; 3]),
             new_input(3, vec![1., 2., 3.]),
+            1.,
+            20.,
         );
 
         assert_eq!(*node.gradient(), Tensor::from_elem(3, 0.));
@@ -130,7 +159,7 @@ mod backward {
     #[test]
     fn computation_state_transition() {
         let diff = new_backward_input(3, vec![0.; 3]);
-        let node = SoftPlusBackward::new(diff.clone(), new_input(3, vec![1., 2., 3.]));
+        let node = SoftPlusBackward::new(diff.clone(), new_input(3, vec![1., 2., 3.]), 1., 20.);
 
         node.backward();
         assert!(node.can_overwrite());
@@ -168,7 +197,7 @@ mod backward {
     #[test]
     fn backward() {
         let diff = new_backward_input(3, vec![0.; 3]);
-        let node = SoftPlusBackward::new(diff.clone(), new_input(3, vec![1., 2., 3.]));
+        let node = SoftPlusBackward::new(diff.clone(), new_input(3, vec![1., 2., 3.]), 1., 20.);
 
         // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Seed Gradient ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
         *node.gradient_mut() = new_tensor(3, vec![1.; 3]);
@@ -197,10 +226,24 @@ mod backward {
         );
     }
 
+    #[test]
+    fn backward_beta_threshold() {
+        let diff = new_backward_input(4, vec![0.; 4]);
+        let node =
+            SoftPlusBackward::new(diff.clone(), new_input(4, vec![-1., 0., 2., 30.]), 2., 5.);
+
+        *node.gradient_mut() = new_tensor(4, vec![1.; 4]);
+        node.backward();
+        assert_almost_equals(
+            &*diff.gradient(),
+            &new_tensor(4, vec![0.119203, 0.5, 0.982014, 1.]),
+        );
+    }
+
     #[test]
     fn debug() {
         let diff = new_backward_input(3, vec![0.; 3]);
-        let node = SoftPlusBackward::new(diff.clone(), new_input(3, vec![1., 2., 3.]));
+        let node = SoftPlusBackward::new(diff.clone(), new_input(3, vec![1., 2., 3.]), 1., 20.);
 
         let output = "SoftPlusBackward { gradient: Some([0.0, 0.0, 0.0], shape=[3], strides=[1], layout=CFcf (0xf), const ndim=1), overwrite: true }";
 
@@ -210,7 +253,7 @@ mod backward {
     #[test]
     fn display() {
         let diff = new_backward_input(3, vec![0.; 3]);
-        let node = SoftPlusBackward::new(diff.clone(), new_input(3, vec![1., 2., 3.]));
+        let node = SoftPlusBackward::new(diff.clone(), new_input(3, vec![1., 2., 3.]), 1., 20.);
 
         assert_eq!(format!("{}", node.gradient()), format!("{}", node));
     }
@@ -221,6 +264,8 @@ mod backward {
         let node = SoftPlusBackward::new(
             new_backward_input((3, 3), vec![0.; 9]),
             new_input((3, 3), vec![0.; 9]),
+            1.,
+            20.,
         );
 
         node.no_grad();
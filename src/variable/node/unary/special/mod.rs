@@ -0,0 +1,148 @@
+//! Scalar special functions used by the element-wise nodes.
+//!
+//! The computations are carried out in double precision and rounded to single precision at the
+//! end, which keeps the results accurate to the last few bits of an `f32`.
+use std::f64::consts::{FRAC_2_SQRT_PI, PI};
+
+/// Coefficients of the Lanczos approximation of the gamma function, with *g = 7*.
+const LANCZOS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+/// Returns the complementary error function of `x`, computed with a Chebyshev fitting whose
+/// fractional error is smaller than *1.2e-7* everywhere.
+fn erfc_f64(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1. / (1. + 0.5 * z);
+    let polynomial = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let result = t * polynomial.exp();
+
+    if x >= 0. {
+        result
+    } else {
+        2. - result
+    }
+}
+
+/// Returns the error function of `x`.
+pub(super) fn erf(x: f32) -> f32 {
+    let x = x as f64;
+
+    // Near the origin the complementary function would lose all the significant digits, so the
+    // Maclaurin series is used instead.
+    if x.abs() < 0.5 {
+        let (square, mut term, mut sum) = (x * x, x, x);
+        for n in 1..16 {
+            term *= -square / n as f64;
+            sum += term / (2 * n + 1) as f64;
+        }
+        (FRAC_2_SQRT_PI * sum) as f32
+    } else {
+        (1. - erfc_f64(x)) as f32
+    }
+}
+
+/// Returns the complementary error function of `x`, that is *1 - erf(x)*.
+pub(super) fn erfc(x: f32) -> f32 {
+    let x = x as f64;
+
+    if x.abs() < 0.5 {
+        (1. - erf(x as f32) as f64) as f32
+    } else {
+        erfc_f64(x) as f32
+    }
+}
+
+/// Returns the derivative of the error function at `x`.
+pub(super) fn erf_derivative(x: f32) -> f32 {
+    let x = x as f64;
+    (FRAC_2_SQRT_PI * (-x * x).exp()) as f32
+}
+
+fn ln_gamma_f64(x: f64) -> f64 {
+    if x < 0.5 {
+        // Reflection formula.
+        (PI / (PI * x).sin().abs()).ln() - ln_gamma_f64(1. - x)
+    } else {
+        let x = x - 1.;
+        let sum = LANCZOS
+            .iter()
+            .enumerate()
+            .skip(1)
+            .fold(LANCZOS[0], |acc, (i, c)| acc + c / (x + i as f64));
+        let t = x + 7.5;
+        0.5 * (2. * PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+    }
+}
+
+/// Returns the natural logarithm of the absolute value of the gamma function at `x`.
+pub(super) fn ln_gamma(x: f32) -> f32 {
+    ln_gamma_f64(x as f64) as f32
+}
+
+fn digamma_f64(mut x: f64) -> f64 {
+    if x < 0.5 {
+        // Reflection formula.
+        return digamma_f64(1. - x) - PI / (PI * x).tan();
+    }
+
+    // The recurrence moves the argument where the asymptotic expansion is accurate.
+    let mut result = 0.;
+    while x < 6. {
+        result -= 1. / x;
+        x += 1.;
+    }
+    let f = 1. / (x * x);
+    result + x.ln()
+        - 0.5 / x
+        - f * (1. / 12. - f * (1. / 120. - f * (1. / 252. - f * (1. / 240. - f / 132.))))
+}
+
+/// Returns the digamma function at `x`, that is the derivative of the logarithm of the gamma
+/// function.
+pub(super) fn digamma(x: f32) -> f32 {
+    digamma_f64(x as f64) as f32
+}
+
+fn trigamma_f64(mut x: f64) -> f64 {
+    if x < 0.5 {
+        // Reflection formula.
+        let sin = (PI * x).sin();
+        return PI * PI / (sin * sin) - trigamma_f64(1. - x);
+    }
+
+    // The recurrence moves the argument where the asymptotic expansion is accurate.
+    let mut result = 0.;
+    while x < 6. {
+        result += 1. / (x * x);
+        x += 1.;
+    }
+    let f = 1. / (x * x);
+    result + 1. / x + f / 2. + f / x * (1. / 6. - f * (1. / 30. - f * (1. / 42. - f / 30.)))
+}
+
+/// Returns the trigamma function at `x`, that is the derivative of the digamma function.
+pub(super) fn trigamma(x: f32) -> f32 {
+    trigamma_f64(x as f64) as f32
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
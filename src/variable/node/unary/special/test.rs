@@ -0,0 +1,115 @@
+use super::{digamma, erf, erfc, ln_gamma, trigamma};
+
+/// Asserts that `value` is within a relative tolerance of `target`.
+fn assert_close(value: f32, target: f64) {
+    assert!(
+        (value as f64 - target).abs() <= 1e-5 * target.abs().max(1e-6),
+        "{} is not close to {}",
+        value,
+        target
+    );
+}
+
+#[test]
+fn erf_values() {
+    let values = [
+        (-3.0, -0.9999779095030014),
+        (-1.2, -0.9103139782296353),
+        (-0.3, -0.3286267594591274),
+        (-0.01, -0.011283415555849618),
+        (0.0, 0.0),
+        (0.2, 0.22270258921047847),
+        (0.49, 0.511668261188523),
+        (0.5, 0.5204998778130465),
+        (1.0, 0.8427007929497149),
+        (2.5, 0.999593047982555),
+        (4.0, 0.9999999845827421),
+    ];
+
+    for (x, target) in values {
+        assert_close(erf(x), target);
+    }
+}
+
+#[test]
+fn erfc_values() {
+    let values = [
+        (-2.0, 1.9953222650189528),
+        (-0.4, 1.4283923550466684),
+        (0.0, 1.0),
+        (0.3, 0.6713732405408726),
+        (0.5, 0.4795001221869535),
+        (1.0, 0.15729920705028513),
+        (2.0, 0.004677734981047266),
+        (3.5, 7.430983723414128e-7),
+        (5.0, 1.537459794428035e-12),
+        (9.0, 4.13703174651381e-37),
+    ];
+
+    for (x, target) in values {
+        assert_close(erfc(x), target);
+    }
+}
+
+#[test]
+fn ln_gamma_values() {
+    let values = [
+        (-2.5, -0.056243716497674054),
+        (-0.5, 1.2655121234846454),
+        (0.001, 6.907178885383853),
+        (0.1, 2.252712651734206),
+        (0.5, 0.5723649429247001),
+        (1.0, 0.0),
+        (1.5, -0.12078223763524522),
+        (2.0, 0.0),
+        (3.7, 1.428072326665388),
+        (10.0, 12.801827480081469),
+        (100.0, 359.1342053695754),
+    ];
+
+    for (x, target) in values {
+        assert_close(ln_gamma(x), target);
+    }
+}
+
+#[test]
+fn digamma_values() {
+    let values = [
+        (-2.5, 1.103156640645243),
+        (-0.5, 0.03648997397857652),
+        (0.001, -1000.5755719318103),
+        (0.1, -10.423754940411076),
+        (0.5, -1.9635100260214235),
+        (1.0, -0.5772156649015329),
+        (1.5, 0.03648997397857652),
+        (2.0, 0.42278433509846713),
+        (3.7, 1.1671535393615113),
+        (10.0, 2.251752589066721),
+        (100.0, 4.600161852738087),
+    ];
+
+    for (x, target) in values {
+        assert_close(digamma(x), target);
+    }
+}
+
+#[test]
+fn trigamma_values() {
+    let values = [
+        (-2.5, 9.539246644989124),
+        (-0.5, 8.934802200544679),
+        (0.001, 1000001.6425331958),
+        (0.1, 101.43329915079275),
+        (0.5, 4.934802200544679),
+        (1.0, 1.6449340668482264),
+        (1.5, 0.9348022005446793),
+        (2.0, 0.6449340668482264),
+        (3.7, 0.3100378576700383),
+        (10.0, 0.10516633568168575),
+        (100.0, 0.010050166663333571),
+    ];
+
+    for (x, target) in values {
+        assert_close(trigamma(x), target);
+    }
+}
@@ -0,0 +1,79 @@
+use super::{new_input, Cache, Data, Forward, IndexData, IndexTensor, ToIndices};
+
+#[test]
+fn creation() {
+    let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+    let node = ToIndices::new(input);
+
+    assert_eq!(*node.data(), IndexTensor::from_elem((3, 3), 0));
+    assert_eq!(*node.data_mut(), IndexTensor::from_elem((3, 3), 0));
+    assert!(!node.was_computed());
+}
+
+#[test]
+fn computation_was_computed_transition() {
+    let input = new_input((3, 3), vec![-4., -3., -2., -1., 0., 1., 2., 3., 4.]);
+    let node = ToIndices::new(input);
+
+    node.forward();
+    assert!(node.was_computed());
+
+    node.forward();
+    assert!(node.was_computed());
+
+    node.reset_computation();
+    assert!(!node.was_computed());
+
+    node.reset_computation();
+    assert!(!node.was_computed());
+}
+
+#[test]
+fn forward() {
+    let input = new_input(
+        (3, 3),
+        vec![-2.6, -1.4, -0.6, -0.2, 0.2, 0.5, 1.5, 2.4, 3.7],
+    );
+    let node = ToIndices::new(input.clone());
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    node.forward();
+    assert_eq!(
+        *node.data(),
+        IndexTensor::from_shape_vec((3, 3), vec![-3, -1, -1, 0, 0, 1, 2, 2, 4]).unwrap()
+    );
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    input.data_mut().mapv_inplace(|el| el + 1.);
+    node.forward();
+    assert_eq!(
+        *node.data(),
+        IndexTensor::from_shape_vec((3, 3), vec![-3, -1, -1, 0, 0, 1, 2, 2, 4]).unwrap()
+    );
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    node.reset_computation();
+    node.forward();
+    assert_eq!(
+        *node.data(),
+        IndexTensor::from_shape_vec((3, 3), vec![-2, 0, 0, 1, 1, 2, 3, 3, 5]).unwrap()
+    );
+}
+
+#[test]
+fn debug() {
+    let input = new_input(1, vec![1.]);
+    let node = ToIndices::new(input);
+
+    let output = "ToIndices { data: [0], shape=[1], strides=[1], layout=CFcf (0xf), const ndim=1, computed: false }";
+
+    assert_eq!(output, format!("{:?}", node));
+}
+
+#[test]
+fn display() {
+    let input = new_input(1, vec![1.]);
+    let node = ToIndices::new(input);
+
+    assert_eq!(format!("{}", node.data()), format!("{}", node));
+}
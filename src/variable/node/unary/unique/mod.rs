@@ -0,0 +1,115 @@
+#[cfg(test)]
+use super::new_index_input;
+use super::{fit_shape, Cache, Forward, IndexData, IndexTensor};
+use ndarray::Ix1;
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Unique ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+pub struct Unique<T: ?Sized>
+where
+    T: IndexData,
+{
+    operand: Rc<T>,
+    data: RefCell<IndexTensor<Ix1>>,
+    computed: Cell<bool>,
+}
+
+impl<T: ?Sized> Unique<T>
+where
+    T: IndexData,
+{
+    pub fn new(operand: Rc<T>) -> Self {
+        // The number of distinct indices is known only after the forward pass.
+        let data = IndexTensor::zeros(0);
+
+        Self {
+            operand,
+            data: RefCell::new(data),
+            computed: Cell::new(false),
+        }
+    }
+}
+
+impl<T: ?Sized> Cache for Unique<T>
+where
+    T: IndexData,
+{
+    fn was_computed(&self) -> bool {
+        self.computed.get()
+    }
+
+    fn reset_computation(&self) {
+        self.computed.set(false);
+    }
+}
+
+impl<T: ?Sized> Forward for Unique<T>
+where
+    T: IndexData,
+{
+    fn forward(&self) {
+        if self.was_computed() {
+            return;
+        }
+
+        self.computed.set(true);
+        let mut distinct: Vec<i64> = self.operand.data().iter().copied().collect();
+        distinct.sort_unstable();
+        distinct.dedup();
+
+        fit_shape(&self.data, Ix1(distinct.len()));
+        self.data
+            .borrow_mut()
+            .iter_mut()
+            .zip(distinct)
+            .for_each(|(el, index)| *el = index);
+    }
+}
+
+impl<T: ?Sized> IndexData for Unique<T>
+where
+    T: IndexData,
+{
+    type Dim = Ix1;
+
+    fn data(&self) -> Ref<IndexTensor<Self::Dim>> {
+        self.data.borrow()
+    }
+
+    fn data_mut(&self) -> RefMut<IndexTensor<Self::Dim>> {
+        self.data.borrow_mut()
+    }
+}
+
+impl<T: ?Sized> Debug for Unique<T>
+where
+    T: IndexData,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Unique")
+            .field("data", &self.data.borrow())
+            .field("computed", &self.computed.get())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Display for Unique<T>
+where
+    T: IndexData,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        crate::print::fmt_tensor(&self.data.borrow(), f)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Tests ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+#[cfg(test)]
+mod test;
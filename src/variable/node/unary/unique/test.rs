@@ -0,0 +1,67 @@
+use super::{new_index_input, Cache, Forward, IndexData, IndexTensor, Unique};
+
+#[test]
+fn creation() {
+    let input = new_index_input((2, 3), vec![3, 1, 3, 0, 1, 1]);
+    let node = Unique::new(input);
+
+    assert_eq!(*node.data(), IndexTensor::zeros(0));
+    assert_eq!(*node.data_mut(), IndexTensor::zeros(0));
+    assert!(!node.was_computed());
+}
+
+#[test]
+fn computation_was_computed_transition() {
+    let input = new_index_input((2, 3), vec![3, 1, 3, 0, 1, 1]);
+    let node = Unique::new(input);
+
+    node.forward();
+    assert!(node.was_computed());
+
+    node.forward();
+    assert!(node.was_computed());
+
+    node.reset_computation();
+    assert!(!node.was_computed());
+
+    node.reset_computation();
+    assert!(!node.was_computed());
+}
+
+#[test]
+fn forward() {
+    let input = new_index_input((2, 3), vec![3, 1, 3, 0, 1, 1]);
+    let node = Unique::new(input.clone());
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ First Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    node.forward();
+    assert_eq!(*node.data(), IndexTensor::from(vec![0, 1, 3]));
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ No Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    *input.data_mut() = IndexTensor::from_shape_vec((2, 3), vec![-2, 5, 4, 4, 5, 7]).unwrap();
+    node.forward();
+    assert_eq!(*node.data(), IndexTensor::from(vec![0, 1, 3]));
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Second Evaluation ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    node.reset_computation();
+    node.forward();
+    assert_eq!(*node.data(), IndexTensor::from(vec![-2, 4, 5, 7]));
+}
+
+#[test]
+fn debug() {
+    let input = new_index_input(1, vec![1]);
+    let node = Unique::new(input);
+
+    let output = "Unique { data: [], shape=[0], strides=[0], layout=CFcf (0xf), const ndim=1, computed: false }";
+
+    assert_eq!(output, format!("{:?}", node));
+}
+
+#[test]
+fn display() {
+    let input = new_index_input(1, vec![1]);
+    let node = Unique::new(input);
+
+    assert_eq!(format!("{}", node.data()), format!("{}", node));
+}
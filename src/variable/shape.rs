@@ -0,0 +1,323 @@
+use super::{Data, Gradient, Var, VarDiff};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ ShapeError ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// The error returned by the checked graph-construction functions, such as [`Var::try_mm`] or
+/// [`try_cat`](crate::try_cat()), when the operands' shapes are not compatible with the
+/// requested operation.
+///
+/// It records the name of the operation together with the shapes of both of its operands.
+///
+/// ```
+/// use neuronika;
+///
+/// let x = neuronika::ones((3, 4));
+/// let y = neuronika::ones((3, 4));
+///
+/// let error = x.try_mm(y).unwrap_err();
+///
+/// assert_eq!(error.operation(), "mm");
+/// assert_eq!(error.lhs_shape(), &[3, 4]);
+/// assert_eq!(error.rhs_shape(), &[3, 4]);
+/// assert_eq!(
+///     error.to_string(),
+///     "error: mm between operands of shape [3, 4] and [3, 4]: \
+///      the number of columns of lhs, 4, differs from the number of rows of rhs, 3."
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShapeError {
+    operation: &'static str,
+    lhs_shape: Vec<usize>,
+    rhs_shape: Vec<usize>,
+    reason: String,
+}
+
+impl ShapeError {
+    pub(crate) fn new(
+        operation: &'static str,
+        lhs_shape: &[usize],
+        rhs_shape: &[usize],
+        reason: String,
+    ) -> Self {
+        Self {
+            operation,
+            lhs_shape: lhs_shape.to_vec(),
+            rhs_shape: rhs_shape.to_vec(),
+            reason,
+        }
+    }
+
+    /// Returns the name of the operation that failed.
+    pub fn operation(&self) -> &'static str {
+        self.operation
+    }
+
+    /// Returns the shape of the left hand side operand.
+    pub fn lhs_shape(&self) -> &[usize] {
+        &self.lhs_shape
+    }
+
+    /// Returns the shape of the right hand side operand.
+    pub fn rhs_shape(&self) -> &[usize] {
+        &self.rhs_shape
+    }
+}
+
+impl Display for ShapeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "error: {} between operands of shape {:?} and {:?}: {}.",
+            self.operation, self.lhs_shape, self.rhs_shape, self.reason
+        )
+    }
+}
+
+impl Error for ShapeError {}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Shaped ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Variables whose shape can be inspected when building the computational graph.
+///
+/// Both [`Var`] and [`VarDiff`] implement this trait, it is used by the checked
+/// graph-construction functions to validate their operands.
+pub trait Shaped {
+    /// Returns the shape of the variable's data.
+    fn shape(&self) -> Vec<usize>;
+}
+
+impl<T: ?Sized + Data> Shaped for Var<T> {
+    fn shape(&self) -> Vec<usize> {
+        self.node.data().shape().to_vec()
+    }
+}
+
+impl<T: ?Sized + Data, U: ?Sized + Gradient> Shaped for VarDiff<T, U> {
+    fn shape(&self) -> Vec<usize> {
+        self.var.shape()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Checks ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Checks that `lhs` and `rhs` can be multiplied as matrices, `rhs` is transposed if `transposed`
+/// is `true`.
+pub(crate) fn check_mm(lhs: &[usize], rhs: &[usize], transposed: bool) -> Result<(), ShapeError> {
+    let (operation, rhs_axis, rhs_name) = if transposed {
+        ("mm_t", 1, "columns")
+    } else {
+        ("mm", 0, "rows")
+    };
+
+    if lhs[1] != rhs[rhs_axis] {
+        return Err(ShapeError::new(
+            operation,
+            lhs,
+            rhs,
+            format!(
+                "the number of columns of lhs, {}, differs from the number of {} of rhs, {}",
+                lhs[1], rhs_name, rhs[rhs_axis]
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that the matrix `lhs` can be multiplied by the vector `rhs`.
+pub(crate) fn check_mv(lhs: &[usize], rhs: &[usize]) -> Result<(), ShapeError> {
+    if lhs[1] != rhs[0] {
+        return Err(ShapeError::new(
+            "mv",
+            lhs,
+            rhs,
+            format!(
+                "the number of columns of lhs, {}, differs from the length of rhs, {}",
+                lhs[1], rhs[0]
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that the vector `lhs` can be multiplied by the matrix `rhs`.
+pub(crate) fn check_vm(lhs: &[usize], rhs: &[usize]) -> Result<(), ShapeError> {
+    if lhs[0] != rhs[0] {
+        return Err(ShapeError::new(
+            "vm",
+            lhs,
+            rhs,
+            format!(
+                "the length of lhs, {}, differs from the number of rows of rhs, {}",
+                lhs[0], rhs[0]
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that the scalar product between the vectors `lhs` and `rhs` is defined.
+pub(crate) fn check_vv(lhs: &[usize], rhs: &[usize]) -> Result<(), ShapeError> {
+    if lhs[0] != rhs[0] {
+        return Err(ShapeError::new(
+            "vv",
+            lhs,
+            rhs,
+            format!(
+                "the length of lhs, {}, differs from the length of rhs, {}",
+                lhs[0], rhs[0]
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that `lhs` and `rhs` can be concatenated along `axis`.
+pub(crate) fn check_cat(lhs: &[usize], rhs: &[usize], axis: usize) -> Result<(), ShapeError> {
+    let error = |reason| Err(ShapeError::new("cat", lhs, rhs, reason));
+
+    if axis >= lhs.len() {
+        return error(format!(
+            "axis {} is out of bounds for operands with {} dimensions",
+            axis,
+            lhs.len()
+        ));
+    }
+    if let Some(mismatch) = (0..lhs.len()).find(|&i| i != axis && lhs[i] != rhs[i]) {
+        return error(format!(
+            "the operands differ along axis {}, only axis {} can differ",
+            mismatch, axis
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that `lhs` and `rhs` can be stacked along `axis`.
+pub(crate) fn check_stack(lhs: &[usize], rhs: &[usize], axis: usize) -> Result<(), ShapeError> {
+    let error = |reason| Err(ShapeError::new("stack", lhs, rhs, reason));
+
+    if axis > lhs.len() {
+        return error(format!(
+            "axis {} is out of bounds for a result with {} dimensions",
+            axis,
+            lhs.len() + 1
+        ));
+    }
+    if lhs != rhs {
+        return error("the operands must have the same shape".to_string());
+    }
+    Ok(())
+}
+
+/// Checks that the `input` and the `target` of an element-wise loss have the same shape.
+pub(crate) fn check_loss(
+    operation: &'static str,
+    input: &[usize],
+    target: &[usize],
+) -> Result<(), ShapeError> {
+    if input != target {
+        return Err(ShapeError::new(
+            operation,
+            input,
+            target,
+            "the input and the target must have the same shape".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that the `target` of the negative log likelihood has the shape of the `input` with the
+/// classes axis removed.
+pub(crate) fn check_nll_loss(input: &[usize], target: &[usize]) -> Result<(), ShapeError> {
+    let expected: Vec<usize> = input
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != 1)
+        .map(|(_, &len)| len)
+        .collect();
+
+    if expected != target {
+        return Err(ShapeError::new(
+            "nll_loss",
+            input,
+            target,
+            format!(
+                "the target was expected to be of shape {:?}, the input's shape without the \
+                 classes axis",
+                expected
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that the arguments are correct for the given **convolution**. It mirrors the checks
+/// that are performed when the convolution is evaluated, and adds the one on the number of input
+/// channels.
+pub(crate) fn check_convolution(
+    input: &[usize],
+    kernel: &[usize],
+    stride: &[usize],
+    dilation: &[usize],
+    padding: &[usize],
+    groups: usize,
+) -> Result<(), ShapeError> {
+    let error = |reason| Err(ShapeError::new("convolution", input, kernel, reason));
+
+    if input.len() < 3 || kernel.len() != input.len() {
+        return error(format!(
+            "the kernel must have as many dimensions as the input, {}, which must be at least 3",
+            input.len()
+        ));
+    }
+
+    let convolution_dimension = input.len() - 2;
+    for (name, argument) in [
+        ("padding", padding),
+        ("stride", stride),
+        ("dilation", dilation),
+    ] {
+        if argument.len() != convolution_dimension {
+            return error(format!(
+                "invalid {} {:?} for {}d convolution",
+                name, argument, convolution_dimension
+            ));
+        }
+    }
+
+    if groups == 0 || !input[1].is_multiple_of(groups) || !kernel[0].is_multiple_of(groups) {
+        return error(format!(
+            "the numbers of input and output channels, {} and {}, must be divisible by groups, {}",
+            input[1], kernel[0], groups
+        ));
+    }
+    if input[1] / groups != kernel[1] {
+        return error(format!(
+            "the input has {} channels per group, but the kernel expects {}",
+            input[1] / groups,
+            kernel[1]
+        ));
+    }
+
+    let too_large = input[2..]
+        .iter()
+        .zip(&kernel[2..])
+        .zip(padding.iter().zip(dilation))
+        .any(|((input_size, kernel_size), (padding, dilation))| {
+            kernel_size.saturating_sub(1) * dilation + 1 > input_size + padding * 2
+        });
+    if too_large {
+        return error("the dilated kernel is larger than the padded input".to_string());
+    }
+    Ok(())
+}
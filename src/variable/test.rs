@@ -11,6 +11,28 @@ fn data_mut() {
     assert_eq!(*x.data(), ndarray::array![[2., 2.,], [2., 2.,]]);
 }
 
+#[test]
+fn shared_data() {
+    let batch = std::rc::Rc::new(std::cell::RefCell::new(ndarray::array![[1., 2.], [3., 4.]]));
+    let x = crate::from_shared(batch.clone());
+    let y = x.clone().sum();
+
+    // The variable holds the array of the caller, not a copy of it.
+    assert_eq!(x.data().as_ptr(), batch.borrow().as_ptr());
+    y.forward();
+    assert_eq!(y.data()[()], 10.);
+
+    // A new array is moved in, and seen by the next evaluation.
+    let next = ndarray::array![[1., 1.], [1., 1.], [1., 1.]];
+    let pointer = next.as_ptr();
+    *batch.borrow_mut() = next;
+    assert_eq!(x.data().as_ptr(), pointer);
+
+    y.reset();
+    y.forward();
+    assert_eq!(y.data()[()], 6.);
+}
+
 #[test]
 fn grad_mut() {
     // Only VarDiff has a gradient.
@@ -188,6 +210,22 @@ fn parameters_test() {
     assert_eq!(w.parameters().len(), 3);
 }
 
+#[test]
+fn parameters_order() {
+    let x = crate::zeros(1).requires_grad();
+    let y = crate::zeros(2).requires_grad();
+    let z = crate::zeros(3).requires_grad();
+
+    // The parameters are listed in the order the computation reaches them.
+    let w = (z.clone() * 2.).sum() + y.sum() + (x.clone().sum() + z.sum()) + x.sum();
+    let lengths: Vec<_> = w
+        .parameters()
+        .iter()
+        .map(|param| param.data.len())
+        .collect();
+    assert_eq!(lengths, vec![3, 2, 1]);
+}
+
 #[test]
 fn sum() {
     let input = crate::ones((2, 2));
@@ -224,6 +262,88 @@ fn mean_diff() {
     assert_eq!(mean.past.parameters.len(), 1);
 }
 
+#[test]
+fn norm() {
+    let input = crate::ones((2, 2));
+    let norm = input.clone().norm(2.);
+
+    assert_eq!(norm.past.len(), 1);
+    assert!(norm.past.changeables.is_empty());
+
+    let norm = input.norm_axis::<ndarray::Ix1>(1., 0, false);
+
+    assert_eq!(norm.past.len(), 1);
+    assert!(norm.past.changeables.is_empty());
+}
+
+#[test]
+fn norm_diff() {
+    let input = crate::from_ndarray(ndarray::array![[3., -4.], [0., 2.]]).requires_grad();
+    let norm = input.clone().norm_axis::<ndarray::Ix1>(2., 1, false);
+
+    assert_eq!(norm.past.len(), 1);
+    assert_eq!(norm.past.parameters.len(), 1);
+
+    let output = input.clone().norm(1.) + norm.sum();
+    output.forward();
+    assert_eq!(output.data()[()], 9. + 7.);
+
+    output.backward(1.);
+    assert_eq!(*input.grad(), ndarray::array![[1.6, -1.8], [0., 2.]]);
+}
+
+#[test]
+#[should_panic(expected = "error: axis 2 is out of bounds for a variable of 2 dimensions.")]
+fn norm_axis_out_of_bounds() {
+    crate::ones((2, 2)).norm_axis::<ndarray::Ix1>(2., 2, false);
+}
+
+#[test]
+fn norm_axis_keepdim() {
+    let input = crate::from_ndarray(ndarray::array![[3., -4.], [0., 2.]]).requires_grad();
+    let output = (input.clone() / input.clone().norm_axis::<ndarray::Ix2>(2., 1, true)).sum();
+    output.forward();
+    assert_eq!(output.data()[()], -0.2 + 1.);
+
+    output.backward(1.);
+    assert_eq!(*input.grad(), ndarray::array![[0.224, 0.168], [0.5, 0.]]);
+}
+
+#[test]
+#[should_panic(
+    expected = "error: the norms along an axis of a variable of 2 dimensions have 2 dimensions, not 1."
+)]
+fn norm_axis_keepdim_mismatch() {
+    crate::ones((2, 2)).norm_axis::<ndarray::Ix1>(2., 1, true);
+}
+
+#[test]
+fn renorm() {
+    let input = crate::ones((2, 2));
+    let renorm = input.renorm(2., 0, 1.);
+
+    assert_eq!(renorm.past.len(), 1);
+    assert!(renorm.past.changeables.is_empty());
+}
+
+#[test]
+fn renorm_diff() {
+    let input = crate::from_ndarray(ndarray::array![[3., 4.], [0.5, 0.]]).requires_grad();
+    let renorm = input.clone().renorm(2., 0, 1.);
+
+    assert_eq!(renorm.past.len(), 1);
+    assert_eq!(renorm.past.parameters.len(), 1);
+
+    let output = renorm.sum();
+    output.forward();
+    output.backward(1.);
+
+    // The sum of the rescaled first row is constant along the directions that keep its norm.
+    let grad = input.grad();
+    assert!((grad[[0, 0]] * 3. + grad[[0, 1]] * 4.).abs() <= 1e-6);
+    assert_eq!(grad.row(1), ndarray::array![1., 1.]);
+}
+
 #[test]
 fn pow() {
     let input = crate::ones((2, 2));
@@ -387,658 +507,3199 @@ fn exp_diff() {
 }
 
 #[test]
-fn softmax() {
+fn sin() {
     let input = crate::ones((2, 2));
-    let softmax = input.softmax(1);
+    let sin = input.sin();
 
-    assert_eq!(softmax.past.len(), 1);
-    assert!(softmax.past.changeables.is_empty());
+    assert_eq!(sin.past.len(), 1);
+    assert!(sin.past.changeables.is_empty());
 }
 
 #[test]
-fn softmax_diff() {
+fn sin_diff() {
     let input = crate::ones((2, 2)).requires_grad();
-    let softmax = input.softmax(1);
+    let sin = input.sin();
 
-    assert_eq!(softmax.past.len(), 1);
-    assert_eq!(softmax.past.parameters.len(), 1);
+    assert_eq!(sin.past.len(), 1);
+    assert_eq!(sin.past.parameters.len(), 1);
 }
 
 #[test]
-fn log_softmax() {
+fn cos() {
     let input = crate::ones((2, 2));
-    let log_softmax = input.log_softmax(1);
+    let cos = input.cos();
 
-    assert_eq!(log_softmax.past.len(), 1);
-    assert!(log_softmax.past.changeables.is_empty());
+    assert_eq!(cos.past.len(), 1);
+    assert!(cos.past.changeables.is_empty());
 }
 
 #[test]
-fn log_softmax_diff() {
+fn cos_diff() {
     let input = crate::ones((2, 2)).requires_grad();
-    let log_softmax = input.log_softmax(1);
+    let cos = input.cos();
 
-    assert_eq!(log_softmax.past.len(), 1);
-    assert_eq!(log_softmax.past.parameters.len(), 1);
+    assert_eq!(cos.past.len(), 1);
+    assert_eq!(cos.past.parameters.len(), 1);
 }
 
 #[test]
-fn t() {
+fn tan() {
     let input = crate::ones((2, 2));
-    let t = input.t();
+    let tan = input.tan();
 
-    assert_eq!(t.past.len(), 1);
-    assert!(t.past.changeables.is_empty());
+    assert_eq!(tan.past.len(), 1);
+    assert!(tan.past.changeables.is_empty());
 }
 
 #[test]
-fn t_diff() {
+fn tan_diff() {
     let input = crate::ones((2, 2)).requires_grad();
-    let t = input.t();
+    let tan = input.tan();
 
-    assert_eq!(t.past.len(), 1);
-    assert_eq!(t.past.parameters.len(), 1);
+    assert_eq!(tan.past.len(), 1);
+    assert_eq!(tan.past.parameters.len(), 1);
 }
 
 #[test]
-fn dropout() {
+fn asin() {
     let input = crate::ones((2, 2));
-    let dropout = input.dropout(0.5);
+    let asin = input.asin();
 
-    assert_eq!(dropout.past.len(), 1);
-    assert_eq!(dropout.past.changeables.len(), 1);
+    assert_eq!(asin.past.len(), 1);
+    assert!(asin.past.changeables.is_empty());
 }
 
 #[test]
-fn dropout_diff() {
+fn asin_diff() {
     let input = crate::ones((2, 2)).requires_grad();
-    let dropout = input.dropout(0.5);
+    let asin = input.asin();
 
-    assert_eq!(dropout.past.len(), 1);
-    assert_eq!(dropout.past.parameters.len(), 1);
+    assert_eq!(asin.past.len(), 1);
+    assert_eq!(asin.past.parameters.len(), 1);
 }
 
 #[test]
-fn chunks() {
+fn acos() {
     let input = crate::ones((2, 2));
-    let chunks = input.chunks((1, 1));
+    let acos = input.acos();
 
-    assert_eq!(chunks[0].past.len(), 1);
-    assert_eq!(chunks[1].past.len(), 1);
-
-    assert!(chunks[0].past.changeables.is_empty());
-    assert!(chunks[1].past.changeables.is_empty());
+    assert_eq!(acos.past.len(), 1);
+    assert!(acos.past.changeables.is_empty());
 }
 
 #[test]
-fn chunks_diff() {
+fn acos_diff() {
     let input = crate::ones((2, 2)).requires_grad();
-    let chunks = input.chunks((1, 1));
-
-    assert_eq!(chunks[0].past.len(), 1);
-    assert_eq!(chunks[1].past.len(), 1);
+    let acos = input.acos();
 
-    assert_eq!(chunks[0].past.parameters.len(), 1);
-    assert_eq!(chunks[1].past.parameters.len(), 1);
+    assert_eq!(acos.past.len(), 1);
+    assert_eq!(acos.past.parameters.len(), 1);
 }
 
 #[test]
-fn unsqueeze() {
+fn atan() {
     let input = crate::ones((2, 2));
-    let unsqueeze = input.unsqueeze(0);
+    let atan = input.atan();
 
-    assert_eq!(unsqueeze.past.len(), 1);
-    assert!(unsqueeze.past.changeables.is_empty());
+    assert_eq!(atan.past.len(), 1);
+    assert!(atan.past.changeables.is_empty());
 }
 
 #[test]
-fn unsqueeze_diff() {
+fn atan_diff() {
     let input = crate::ones((2, 2)).requires_grad();
-    let unsqueeze = input.unsqueeze(0);
+    let atan = input.atan();
 
-    assert_eq!(unsqueeze.past.len(), 1);
-    assert_eq!(unsqueeze.past.parameters.len(), 1);
+    assert_eq!(atan.past.len(), 1);
+    assert_eq!(atan.past.parameters.len(), 1);
 }
 
 #[test]
-fn cat() {
-    let lhs = crate::ones((2, 2));
-    let rhs = crate::zeros((2, 2));
-    let cat = super::Cat::cat(lhs, rhs, 1);
+fn sinh() {
+    let input = crate::ones((2, 2));
+    let sinh = input.sinh();
 
-    assert_eq!(cat.past.len(), 1);
-    assert!(cat.past.changeables.is_empty());
+    assert_eq!(sinh.past.len(), 1);
+    assert!(sinh.past.changeables.is_empty());
+}
 
-    let lhs = crate::ones((2, 2));
-    let rhs = crate::zeros((2, 2)).requires_grad();
-    let cat = super::Cat::cat(lhs, rhs, 1);
+#[test]
+fn sinh_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let sinh = input.sinh();
 
-    assert_eq!(cat.past.len(), 1);
-    assert_eq!(cat.past.parameters.len(), 1);
+    assert_eq!(sinh.past.len(), 1);
+    assert_eq!(sinh.past.parameters.len(), 1);
 }
 
 #[test]
-fn cat_diff() {
-    let lhs = crate::ones((2, 2)).requires_grad();
-    let rhs = crate::zeros((2, 2));
-    let cat = super::Cat::cat(lhs, rhs, 1);
+fn cosh() {
+    let input = crate::ones((2, 2));
+    let cosh = input.cosh();
 
-    assert_eq!(cat.past.len(), 1);
-    assert_eq!(cat.past.parameters.len(), 1);
+    assert_eq!(cosh.past.len(), 1);
+    assert!(cosh.past.changeables.is_empty());
+}
 
-    let lhs = crate::ones((2, 2)).requires_grad();
-    let rhs = crate::zeros((2, 2)).requires_grad();
-    let cat = super::Cat::cat(lhs, rhs, 1);
+#[test]
+fn cosh_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let cosh = input.cosh();
 
-    assert_eq!(cat.past.len(), 1);
-    assert_eq!(cat.past.parameters.len(), 2);
+    assert_eq!(cosh.past.len(), 1);
+    assert_eq!(cosh.past.parameters.len(), 1);
 }
 
 #[test]
-fn multi_cat() {
-    let a = crate::ones((2, 2)) + 1.;
-    let b = 18. / crate::full((2, 2), 9.);
-    let c = crate::full((2, 1), 3.) * 4.;
+fn atanh() {
+    let input = crate::ones((2, 2));
+    let atanh = input.atanh();
 
-    let d = crate::Var::cat(&[a.into_dyn(), b.into_dyn(), c.into_dyn()], 1);
-    assert_eq!(d.past.len(), 4);
-    assert!(d.past.changeables.is_empty());
+    assert_eq!(atanh.past.len(), 1);
+    assert!(atanh.past.changeables.is_empty());
 }
 
 #[test]
-fn multi_cat_diff() {
-    let a = crate::ones((2, 2)).requires_grad() + 1.;
-    let b = 18. / crate::full((2, 2), 9.).requires_grad();
-    let c = crate::full((2, 1), 3.).requires_grad() * 4.;
+fn atanh_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let atanh = input.atanh();
 
-    let d = crate::VarDiff::cat(&[a.into_dyn(), b.into_dyn(), c.into_dyn()], 1);
-    assert_eq!(d.past.len(), 4);
-    assert_eq!(d.past.parameters.len(), 3);
+    assert_eq!(atanh.past.len(), 1);
+    assert_eq!(atanh.past.parameters.len(), 1);
 }
 
 #[test]
-fn stack() {
-    let lhs = crate::ones((2, 2));
-    let rhs = crate::zeros((2, 2));
-    let stack = super::Stack::stack(lhs, rhs, 1);
+fn erf() {
+    let input = crate::ones((2, 2));
+    let erf = input.erf();
 
-    assert_eq!(stack.past.len(), 1);
-    assert!(stack.past.changeables.is_empty());
+    assert_eq!(erf.past.len(), 1);
+    assert!(erf.past.changeables.is_empty());
+}
 
-    let lhs = crate::ones((2, 2));
-    let rhs = crate::zeros((2, 2)).requires_grad();
-    let cat = super::Stack::stack(lhs, rhs, 1);
+#[test]
+fn erf_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let erf = input.erf();
 
-    assert_eq!(cat.past.len(), 1);
-    assert_eq!(cat.past.parameters.len(), 1);
+    assert_eq!(erf.past.len(), 1);
+    assert_eq!(erf.past.parameters.len(), 1);
 }
 
 #[test]
-fn stack_diff() {
-    let lhs = crate::ones((2, 2)).requires_grad();
-    let rhs = crate::zeros((2, 2));
-    let cat = super::Stack::stack(lhs, rhs, 1);
+fn erfc() {
+    let input = crate::ones((2, 2));
+    let erfc = input.erfc();
 
-    assert_eq!(cat.past.len(), 1);
-    assert_eq!(cat.past.parameters.len(), 1);
+    assert_eq!(erfc.past.len(), 1);
+    assert!(erfc.past.changeables.is_empty());
+}
 
-    let lhs = crate::ones((2, 2)).requires_grad();
-    let rhs = crate::zeros((2, 2)).requires_grad();
-    let stack = super::Stack::stack(lhs, rhs, 1);
+#[test]
+fn erfc_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let erfc = input.erfc();
 
-    assert_eq!(stack.past.len(), 1);
-    assert_eq!(stack.past.parameters.len(), 2);
+    assert_eq!(erfc.past.len(), 1);
+    assert_eq!(erfc.past.parameters.len(), 1);
 }
 
 #[test]
-fn multi_stack() {
-    let a = crate::ones((2, 2)) + 1.;
-    let b = 18. / crate::full((2, 2), 9.);
-    let c = crate::full((2, 2), 3.) * 4.;
+fn lgamma() {
+    let input = crate::ones((2, 2));
+    let lgamma = input.lgamma();
 
-    let d = crate::Var::stack(&[a.into_dyn(), b.into_dyn(), c.into_dyn()], 0);
-    assert_eq!(d.past.len(), 4);
-    assert!(d.past.changeables.is_empty());
+    assert_eq!(lgamma.past.len(), 1);
+    assert!(lgamma.past.changeables.is_empty());
 }
 
 #[test]
-fn multi_stack_diff() {
-    let a = crate::ones((2, 2)).requires_grad() + 1.;
-    let b = 18. / crate::full((2, 2), 9.).requires_grad();
-    let c = crate::full((2, 2), 3.).requires_grad() * 4.;
+fn lgamma_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let lgamma = input.lgamma();
 
-    let d = crate::VarDiff::stack(&[a.into_dyn(), b.into_dyn(), c.into_dyn()], 0);
-    assert_eq!(d.past.len(), 4);
-    assert_eq!(d.past.parameters.len(), 3);
+    assert_eq!(lgamma.past.len(), 1);
+    assert_eq!(lgamma.past.parameters.len(), 1);
 }
 
 #[test]
-fn neg() {
+fn digamma() {
     let input = crate::ones((2, 2));
-    let neg = -input;
+    let digamma = input.digamma();
 
-    assert_eq!(neg.past.len(), 1);
-    assert!(neg.past.changeables.is_empty());
+    assert_eq!(digamma.past.len(), 1);
+    assert!(digamma.past.changeables.is_empty());
 }
 
 #[test]
-fn neg_diff() {
+fn digamma_diff() {
     let input = crate::ones((2, 2)).requires_grad();
-    let neg = -input;
+    let digamma = input.digamma();
 
-    assert_eq!(neg.past.len(), 1);
-    assert_eq!(neg.past.parameters.len(), 1);
+    assert_eq!(digamma.past.len(), 1);
+    assert_eq!(digamma.past.parameters.len(), 1);
 }
 
 #[test]
-fn add() {
-    let lhs = crate::ones((2, 2));
-    let rhs = crate::zeros((2, 2));
-    let add = lhs + rhs;
+fn log1p() {
+    let input = crate::ones((2, 2));
+    let log1p = input.log1p();
 
-    assert_eq!(add.past.len(), 1);
-    assert!(add.past.changeables.is_empty());
+    assert_eq!(log1p.past.len(), 1);
+    assert!(log1p.past.changeables.is_empty());
 }
 
 #[test]
-fn add_diff() {
-    let lhs = crate::ones((2, 2)).requires_grad();
-    let rhs = crate::zeros((2, 2));
-    let add = lhs + rhs;
+fn log1p_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let log1p = input.log1p();
 
-    assert_eq!(add.past.len(), 1);
-    assert_eq!(add.past.parameters.len(), 1);
+    assert_eq!(log1p.past.len(), 1);
+    assert_eq!(log1p.past.parameters.len(), 1);
+}
 
-    let lhs = crate::ones((2, 2));
-    let rhs = crate::zeros((2, 2)).requires_grad();
-    let add = lhs + rhs;
+#[test]
+fn expm1() {
+    let input = crate::ones((2, 2));
+    let expm1 = input.expm1();
 
-    assert_eq!(add.past.len(), 1);
-    assert_eq!(add.past.parameters.len(), 1);
+    assert_eq!(expm1.past.len(), 1);
+    assert!(expm1.past.changeables.is_empty());
+}
 
-    let lhs = crate::ones((2, 2)).requires_grad();
-    let rhs = crate::zeros((2, 2)).requires_grad();
-    let add = lhs + rhs;
+#[test]
+fn expm1_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let expm1 = input.expm1();
 
-    assert_eq!(add.past.len(), 1);
-    assert_eq!(add.past.parameters.len(), 2);
+    assert_eq!(expm1.past.len(), 1);
+    assert_eq!(expm1.past.parameters.len(), 1);
 }
 
 #[test]
-fn sub() {
-    let lhs = crate::ones((2, 2));
-    let rhs = crate::zeros((2, 2));
-    let sub = lhs - rhs;
+fn reciprocal() {
+    let input = crate::ones((2, 2));
+    let reciprocal = input.reciprocal();
 
-    assert_eq!(sub.past.len(), 1);
-    assert!(sub.past.changeables.is_empty());
+    assert_eq!(reciprocal.past.len(), 1);
+    assert!(reciprocal.past.changeables.is_empty());
 }
 
 #[test]
-fn sub_diff() {
-    let lhs = crate::ones((2, 2)).requires_grad();
-    let rhs = crate::zeros((2, 2));
-    let sub = lhs - rhs;
+fn reciprocal_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let reciprocal = input.reciprocal();
 
-    assert_eq!(sub.past.len(), 1);
-    assert_eq!(sub.past.parameters.len(), 1);
+    assert_eq!(reciprocal.past.len(), 1);
+    assert_eq!(reciprocal.past.parameters.len(), 1);
+}
 
-    let lhs = crate::ones((2, 2));
-    let rhs = crate::zeros((2, 2)).requires_grad();
-    let sub = lhs - rhs;
+#[test]
+fn rsqrt() {
+    let input = crate::ones((2, 2));
+    let rsqrt = input.rsqrt();
 
-    assert_eq!(sub.past.len(), 1);
-    assert_eq!(sub.past.parameters.len(), 1);
+    assert_eq!(rsqrt.past.len(), 1);
+    assert!(rsqrt.past.changeables.is_empty());
+}
 
-    let lhs = crate::ones((2, 2)).requires_grad();
-    let rhs = crate::zeros((2, 2)).requires_grad();
-    let sub = lhs - rhs;
+#[test]
+fn rsqrt_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let rsqrt = input.rsqrt();
 
-    assert_eq!(sub.past.len(), 1);
-    assert_eq!(sub.past.parameters.len(), 2);
+    assert_eq!(rsqrt.past.len(), 1);
+    assert_eq!(rsqrt.past.parameters.len(), 1);
+}
+
+#[test]
+fn sign() {
+    let input = crate::ones((2, 2));
+    let sign = input.sign();
+
+    assert_eq!(sign.past.len(), 1);
+    assert!(sign.past.changeables.is_empty());
+}
+
+#[test]
+fn sign_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let sign = input.sign();
+
+    assert_eq!(sign.past.len(), 1);
+    assert_eq!(sign.past.parameters.len(), 1);
+}
+
+#[test]
+fn round() {
+    let input = crate::ones((2, 2));
+    let round = input.round();
+
+    assert_eq!(round.past.len(), 1);
+    assert!(round.past.changeables.is_empty());
+}
+
+#[test]
+fn round_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let round = input.round();
+
+    assert_eq!(round.past.len(), 1);
+    assert_eq!(round.past.parameters.len(), 1);
+}
+
+#[test]
+fn floor_ceil() {
+    let input = crate::from_ndarray(ndarray::array![-1.5, 0.2, 2.]);
+    let floor = input.clone().floor();
+    let ceil = input.ceil();
+
+    assert_eq!(floor.past.len(), 1);
+    assert!(floor.past.changeables.is_empty());
+
+    floor.forward();
+    ceil.forward();
+    assert_eq!(*floor.data(), ndarray::array![-2., 0., 2.]);
+    assert_eq!(*ceil.data(), ndarray::array![-1., 1., 2.]);
+}
+
+#[test]
+fn floor_ceil_diff() {
+    let input = crate::from_ndarray(ndarray::array![-1.5, 0.2, 2.]).requires_grad();
+    let floor = input.clone().floor();
+
+    assert_eq!(floor.past.len(), 1);
+
+    let output = (input.clone() * floor + input.clone().ceil()).sum();
+    output.forward();
+    output.backward(1.);
+    assert_eq!(*input.grad(), ndarray::array![-2., 0., 2.]);
+}
+
+#[test]
+fn binary_linear() {
+    let lin = crate::nn::BinaryLinear::new(3, 2);
+    lin.weight
+        .data_mut()
+        .assign(&ndarray::array![[0.5, -0.2, 2.], [-3., 0.1, -0.7]]);
+    lin.bias.data_mut().fill(0.);
+
+    let y = lin
+        .forward(crate::from_ndarray(ndarray::array![[1., 2., 3.]]))
+        .sum();
+    y.forward();
+    y.backward(1.);
+
+    assert_eq!(y.data()[()], (1. - 2. + 3.) + (-1. + 2. - 3.));
+    assert_eq!(
+        *lin.weight.grad(),
+        ndarray::array![[1., 2., 0.], [0., 2., 3.]]
+    );
+}
+
+#[test]
+fn bilinear() {
+    use crate::nn::{Bilinear, FactorizedBilinearPooling, Register};
+
+    let bilinear = Bilinear::new(2, 3, 2);
+    bilinear.weight.data_mut().assign(&ndarray::array![
+        [[1., 0., 0.], [0., 1., 0.]],
+        [[0., 0., 1.], [1., 1., 1.]]
+    ]);
+    bilinear.bias.data_mut().assign(&ndarray::array![0.5, 0.]);
+
+    let input1 = crate::from_ndarray(ndarray::array![[1., 2.]]).requires_grad();
+    let input2 = crate::from_ndarray(ndarray::array![[1., 2., -1.]]).requires_grad();
+    let y = bilinear.forward(input1.clone(), input2.clone());
+    y.forward();
+    assert_eq!(*y.data(), ndarray::array![[5.5, 3.]]);
+
+    y.backward(1.);
+    assert_eq!(*input1.grad(), ndarray::array![[0., 4.]]);
+    assert_eq!(*input2.grad(), ndarray::array![[3., 4., 3.]]);
+    assert_eq!(
+        *bilinear.weight.grad(),
+        ndarray::array![
+            [[1., 2., -1.], [2., 4., -2.]],
+            [[1., 2., -1.], [2., 4., -2.]]
+        ]
+    );
+    assert_eq!(*bilinear.bias.grad(), ndarray::array![1., 1.]);
+    assert_eq!(bilinear.shape_inference()(&[4, 2]), vec![4, 2]);
+
+    // Each output feature sums a group of factor consecutive features of the product.
+    let pooling = FactorizedBilinearPooling::new(2, 3, 2, 2);
+    let (input1, input2) = (crate::rand((4, 2)), crate::rand((4, 3)));
+    let y = pooling.forward(
+        input1.clone().requires_grad(),
+        input2.clone().requires_grad(),
+    );
+    let product = pooling.proj1.forward(input1) * pooling.proj2.forward(input2);
+    y.forward();
+    product.forward();
+
+    let product = product.data();
+    for (row, product_row) in y.data().outer_iter().zip(product.outer_iter()) {
+        for (o, el) in row.iter().enumerate() {
+            assert!((el - product_row[2 * o] - product_row[2 * o + 1]).abs() < 1e-5);
+        }
+    }
+    assert_eq!(pooling.shape_inference()(&[4, 2]), vec![4, 2]);
+}
+
+#[test]
+#[should_panic(expected = "error: the factor must be positive.")]
+fn factorized_bilinear_pooling_fail() {
+    crate::nn::FactorizedBilinearPooling::new(2, 3, 2, 0);
+}
+
+#[test]
+fn glu() {
+    use crate::nn::{Register, GLU};
+
+    let input = crate::from_ndarray(ndarray::array![[1., 2., 0., 0.]]);
+    let glu = GLU::new(1);
+    let y = glu.forward(input.clone());
+    assert_eq!(y.past.len(), 1);
+
+    y.forward();
+    assert_eq!(*y.data(), ndarray::array![[0.5, 1.]]);
+    assert_eq!(glu.shape_inference()(&[3, 4]), vec![3, 2]);
+
+    let input = input.requires_grad();
+    let y = glu.forward(input.clone());
+    assert_eq!(y.past.len(), 1);
+
+    y.forward();
+    y.backward(1.);
+    assert_eq!(*input.grad(), ndarray::array![[0.5, 0.5, 0.25, 0.5]]);
+}
+
+#[test]
+fn highway() {
+    use crate::nn::{Highway, Register};
+
+    let highway = Highway::new(2);
+    assert_eq!(*highway.gate.bias.data(), ndarray::array![-1., -1.]);
+
+    highway
+        .transform
+        .weight
+        .data_mut()
+        .assign(&ndarray::array![[1., 0.], [0., 1.]]);
+    highway.transform.bias.data_mut().fill(0.);
+    highway.gate.weight.data_mut().fill(0.);
+    highway.gate.bias.data_mut().fill(0.);
+
+    // The gate lets half of the transformation through and carries over half of the input.
+    let input = crate::from_ndarray(ndarray::array![[1., -2.]]).requires_grad();
+    let y = highway.forward(input.clone());
+    y.forward();
+    assert_eq!(*y.data(), ndarray::array![[1., -1.]]);
+
+    y.backward(1.);
+    assert_eq!(*input.grad(), ndarray::array![[1., 0.5]]);
+    assert_eq!(*highway.gate.bias.grad(), ndarray::array![0., 0.5]);
+
+    let mut params = Vec::new();
+    highway.register_params(&mut params);
+    assert_eq!(params.len(), 4);
+    assert_eq!(highway.shape_inference()(&[5, 2]), vec![5, 2]);
+}
+
+#[test]
+fn squeeze_excitation() {
+    use crate::nn::{Register, SqueezeExcitation};
+
+    let se = SqueezeExcitation::new(2, 2);
+    assert_eq!(se.squeeze.weight.data().shape(), &[1, 2]);
+    assert_eq!(se.excitation.weight.data().shape(), &[2, 1]);
+
+    se.squeeze.weight.data_mut().fill(0.);
+    se.excitation.weight.data_mut().fill(0.);
+    se.excitation
+        .bias
+        .data_mut()
+        .assign(&ndarray::array![0., 100.]);
+
+    let input = crate::ones((3, 2, 4, 4)).requires_grad();
+    let y = se.forward(input.clone());
+    y.forward();
+    y.backward(1.);
+
+    for (channel, scale) in [(0, 0.5), (1, 1.)] {
+        let expected = ndarray::Array::from_elem((3, 4, 4), scale);
+        assert_eq!(y.data().index_axis(ndarray::Axis(1), channel), expected);
+        assert_eq!(input.grad().index_axis(ndarray::Axis(1), channel), expected);
+    }
+    assert_eq!(se.shape_inference()(&[3, 2, 4, 4]), vec![3, 2, 4, 4]);
+}
+
+#[test]
+#[should_panic(expected = "error: the reduction must be positive.")]
+fn squeeze_excitation_fail() {
+    crate::nn::SqueezeExcitation::new(4, 0);
+}
+
+#[test]
+fn residual_block() {
+    use crate::nn::{Register, ResidualBlock};
+
+    let block = ResidualBlock::basic(2, 2, 1);
+    assert!(block.shortcut.is_none());
+    block.convs.iter().for_each(|conv| {
+        conv.weight.data_mut().fill(0.);
+        conv.bias.data_mut().fill(0.);
+    });
+
+    // The residual branch is zero, so the block reduces to a rectified identity.
+    let input = crate::ones((3, 2, 4, 4)).requires_grad();
+    let y = block.forward(input.clone());
+    y.forward();
+    y.backward(1.);
+
+    assert_eq!(*y.data(), ndarray::Array::ones((3, 2, 4, 4)));
+    assert_eq!(*input.grad(), ndarray::Array::ones((3, 2, 4, 4)));
+    assert_eq!(block.shape_inference()(&[3, 2, 4, 4]), vec![3, 2, 4, 4]);
+
+    let block = ResidualBlock::bottleneck(4, 2, 8, 2);
+    let (conv, _) = block.shortcut.as_ref().unwrap();
+    assert_eq!(conv.weight.data().shape(), &[8, 4, 1, 1]);
+
+    let mut params = Vec::new();
+    block.register_params(&mut params);
+    // Three convolutions and batch normalizations plus the ones of the shortcut.
+    assert_eq!(params.len(), 16);
+    assert_eq!(block.non_trainable(), 2 * (2 + 2 + 8 + 8));
+
+    let y = block.forward(crate::rand((3, 4, 5, 5)).requires_grad());
+    y.forward();
+    assert_eq!(y.data().shape(), &[3, 8, 3, 3]);
+    assert_eq!(block.shape_inference()(&[3, 4, 5, 5]), vec![3, 8, 3, 3]);
+}
+
+#[test]
+fn temporal_block() {
+    use crate::nn::{Register, TemporalBlock};
+
+    let block = TemporalBlock::new(2, 2, 3, 1, 0.);
+    assert!(block.downsample.is_none());
+    block
+        .convs
+        .iter()
+        .for_each(|conv| conv.bias.data_mut().fill(0.));
+    block.gains.iter().for_each(|gain| gain.data_mut().fill(0.));
+
+    // The gains are zero, so the block reduces to a rectified identity.
+    let input = crate::ones((3, 2, 6)).requires_grad();
+    let y = block.forward(input.clone());
+    y.forward();
+    y.backward(1.);
+
+    assert_eq!(*y.data(), ndarray::Array::ones((3, 2, 6)));
+    assert_eq!(*input.grad(), ndarray::Array::ones((3, 2, 6)));
+
+    let block = TemporalBlock::new(3, 4, 2, 2, 0.);
+    let mut params = Vec::new();
+    block.register_params(&mut params);
+    // Direction, bias and gain of two convolutions plus the downsampling weight and bias.
+    assert_eq!(params.len(), 8);
+    assert_eq!(block.shape_inference()(&[5, 3, 7]), vec![5, 4, 7]);
+}
+
+#[test]
+#[should_panic(expected = "error: the dilation must be positive.")]
+fn temporal_block_fail() {
+    crate::nn::TemporalBlock::new(2, 2, 3, 0, 0.);
+}
+
+#[test]
+fn tcn() {
+    use crate::nn::{Register, TCN};
+
+    let tcn = TCN::new(2, &[4, 4, 3], 2, 0.);
+    let dilations: Vec<_> = tcn
+        .blocks
+        .iter()
+        .map(|block| block.convs[0].dilation)
+        .collect();
+    assert_eq!(dilations, vec![1, 2, 4]);
+    assert_eq!(tcn.receptive_field(), 15);
+    assert_eq!(tcn.shape_inference()(&[1, 2, 20]), vec![1, 3, 20]);
+
+    // The outputs up to a step do not depend on the following steps of the input.
+    let input = crate::rand((1, 2, 20)).requires_grad();
+    let y = tcn.forward(input.clone());
+    y.forward();
+    let outputs = y.data().slice(ndarray::s![.., .., ..10]).to_owned();
+
+    input
+        .data_mut()
+        .slice_mut(ndarray::s![.., .., 10..])
+        .fill(5.);
+    y.forward();
+    assert_eq!(y.data().shape(), &[1, 3, 20]);
+    assert_eq!(y.data().slice(ndarray::s![.., .., ..10]), outputs);
+
+    // The first step of the output depends only on the first step of the input.
+    let first = y.clone().chunks((1, 3, 1)).swap_remove(0).sum();
+    first.forward();
+    first.backward(1.);
+    assert!(input
+        .grad()
+        .slice(ndarray::s![.., .., 1..])
+        .iter()
+        .all(|&el| el == 0.));
+}
+
+#[test]
+fn softmax() {
+    let input = crate::ones((2, 2));
+    let softmax = input.softmax(1);
+
+    assert_eq!(softmax.past.len(), 1);
+    assert!(softmax.past.changeables.is_empty());
+}
+
+#[test]
+fn softmax_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let softmax = input.softmax(1);
+
+    assert_eq!(softmax.past.len(), 1);
+    assert_eq!(softmax.past.parameters.len(), 1);
+}
+
+#[test]
+fn log_softmax() {
+    let input = crate::ones((2, 2));
+    let log_softmax = input.log_softmax(1);
+
+    assert_eq!(log_softmax.past.len(), 1);
+    assert!(log_softmax.past.changeables.is_empty());
+}
+
+#[test]
+fn log_softmax_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let log_softmax = input.log_softmax(1);
+
+    assert_eq!(log_softmax.past.len(), 1);
+    assert_eq!(log_softmax.past.parameters.len(), 1);
+}
+
+#[test]
+fn t() {
+    let input = crate::ones((2, 2));
+    let t = input.t();
+
+    assert_eq!(t.past.len(), 1);
+    assert!(t.past.changeables.is_empty());
+}
+
+#[test]
+fn t_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let t = input.t();
+
+    assert_eq!(t.past.len(), 1);
+    assert_eq!(t.past.parameters.len(), 1);
+}
+
+#[test]
+fn dropout() {
+    let input = crate::ones((2, 2));
+    let dropout = input.dropout(0.5);
+
+    assert_eq!(dropout.past.len(), 1);
+    assert_eq!(dropout.past.changeables.len(), 1);
+}
+
+#[test]
+fn dropout_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let dropout = input.dropout(0.5);
+
+    assert_eq!(dropout.past.len(), 1);
+    assert_eq!(dropout.past.parameters.len(), 1);
+}
+
+#[test]
+fn predict_mc() {
+    use crate::nn::{self, Dropout, ModelStatus, Module};
+
+    struct Network {
+        drop: Dropout,
+        status: ModelStatus,
+    }
+
+    impl Module for Network {
+        fn status(&self) -> &ModelStatus {
+            &self.status
+        }
+    }
+
+    let mut status = ModelStatus::default();
+    let network = Network {
+        drop: status.register(Dropout::new(0.5)),
+        status,
+    };
+    let output = network.drop.forward(crate::ones((10, 10)));
+    network.eval();
+
+    // Each element is either 0 or 2, so the samples can't all be the same.
+    let (mean, variance) = nn::predict_mc(&network, &output, 50);
+    assert!(mean.iter().all(|el| (0. ..=2.).contains(el)));
+    assert!(variance.iter().any(|el| *el > 0.));
+    assert!((mean.mean().unwrap() - 1.).abs() < 0.1);
+
+    // The status of the model is restored, so that dropout is disabled again.
+    assert!(!network.is_training());
+    output.forward();
+    assert_eq!(*output.data(), ndarray::Array::ones((10, 10)));
+
+    // A single sample has no variance.
+    let (mean, variance) = nn::predict_mc(&network, &output, 1);
+    assert!(mean.iter().all(|el| *el == 0. || *el == 2.));
+    assert_eq!(variance, ndarray::Array::zeros((10, 10)));
+}
+
+#[test]
+#[should_panic(expected = "error: the number of samples must be positive.")]
+fn predict_mc_fail() {
+    use crate::nn::{Dropout, ModelStatus, Module};
+
+    struct Network {
+        drop: Dropout,
+        status: ModelStatus,
+    }
+
+    impl Module for Network {
+        fn status(&self) -> &ModelStatus {
+            &self.status
+        }
+    }
+
+    let mut status = ModelStatus::default();
+    let network = Network {
+        drop: status.register(Dropout::new(0.5)),
+        status,
+    };
+    let output = network.drop.forward(crate::ones(3));
+
+    crate::nn::predict_mc(&network, &output, 0);
+}
+
+#[test]
+fn drop_path() {
+    use crate::{nn::DropPath, Eval};
+
+    let drop_path = DropPath::new(0.5);
+    let input = crate::ones((10, 3)).requires_grad();
+    let output = drop_path.forward(input.clone());
+
+    output.forward();
+    output.backward(1.);
+    // Whole samples are either dropped or scaled.
+    for (data, grad) in output.data().rows().into_iter().zip(input.grad().rows()) {
+        assert!(data.iter().all(|el| *el == data[0]) && (data[0] == 0. || data[0] == 2.));
+        assert_eq!(data, grad);
+    }
+
+    drop_path.eval();
+    output.forward();
+    assert_eq!(*output.data(), ndarray::Array::ones((10, 3)));
+}
+
+#[test]
+fn variational_dropout() {
+    use crate::{nn::VariationalDropout, Eval};
+
+    let dropout = VariationalDropout::new(0.5);
+    let mask = dropout.mask((4, 6));
+    let (first, second) = (
+        crate::ones((4, 6)).requires_grad() * mask.clone(),
+        crate::full((4, 6), 3.).requires_grad() * mask.clone(),
+    );
+    let output = first.clone() + second.clone();
+
+    // Both the time steps are dropped out with the same mask.
+    output.forward();
+    assert_eq!(*second.data(), &*first.data() * 3.);
+    assert!(mask.data().iter().all(|el| *el == 0. || *el == 2.));
+
+    dropout.eval();
+    output.forward();
+    assert_eq!(*mask.data(), ndarray::Array::ones((4, 6)));
+}
+
+#[test]
+fn zoneout() {
+    use crate::{nn::Zoneout, Eval};
+
+    let previous = crate::ones((3, 4)).requires_grad();
+    let next = crate::full((3, 4), 3.).requires_grad();
+
+    // Each element is either preserved or updated.
+    let zoneout = Zoneout::new(0.5);
+    let output = zoneout.forward(previous.clone(), next.clone());
+    output.forward();
+    assert!(output.data().iter().all(|el| *el == 1. || *el == 3.));
+
+    output.backward(1.);
+    ndarray::Zip::from(&*previous.grad())
+        .and(&*next.grad())
+        .for_each(|previous, next| assert_eq!(previous + next, 1.));
+
+    // The expectation is computed during evaluation.
+    zoneout.eval();
+    output.forward();
+    assert_eq!(*output.data(), ndarray::Array::from_elem((3, 4), 2.));
+
+    let output = Zoneout::new(1.).forward(previous, next);
+    output.forward();
+    assert_eq!(*output.data(), ndarray::Array::ones((3, 4)));
+}
+
+#[test]
+fn packed_sequence() {
+    use crate::nn::PackedSequence;
+
+    let sequences = crate::from_ndarray(ndarray::Array::from_shape_fn((3, 4, 2), |(i, j, k)| {
+        (i * 8 + j * 2 + k) as f32
+    }))
+    .requires_grad();
+    let packed = PackedSequence::pack(sequences.clone(), &[2, 4, 3]);
+
+    assert_eq!(packed.batch_sizes(), &[3, 3, 2, 1]);
+    assert_eq!(packed.sorted_indices(), &[1, 2, 0]);
+    assert_eq!(packed.lengths(), vec![2, 4, 3]);
+
+    packed.data().forward();
+    assert_eq!(
+        *packed.data().data(),
+        ndarray::array![
+            [8., 9.],
+            [16., 17.],
+            [0., 1.],
+            [10., 11.],
+            [18., 19.],
+            [2., 3.],
+            [12., 13.],
+            [20., 21.],
+            [14., 15.]
+        ]
+    );
+
+    // Padding restores the original order, with zeros past the end of each sequence.
+    let padded = packed.pad();
+    padded.forward();
+    let mut expected = sequences.data().clone();
+    expected.slice_mut(ndarray::s![0, 2.., ..]).fill(0.);
+    expected.slice_mut(ndarray::s![2, 3.., ..]).fill(0.);
+    assert_eq!(*padded.data(), expected);
+
+    padded.backward(1.);
+    let mut expected = ndarray::Array::ones((3, 4, 2));
+    expected.slice_mut(ndarray::s![0, 2.., ..]).fill(0.);
+    expected.slice_mut(ndarray::s![2, 3.., ..]).fill(0.);
+    assert_eq!(*sequences.grad(), expected);
+}
+
+#[test]
+#[should_panic(
+    expected = "error: the lengths of the sequences must be between 1 and 4, got [2, 5]."
+)]
+fn packed_sequence_fail() {
+    crate::nn::PackedSequence::pack(crate::zeros((2, 4, 3)).requires_grad(), &[2, 5]);
+}
+
+#[test]
+fn gru_packed() {
+    use crate::nn::{PackedSequence, GRU};
+
+    let gru = GRU::new(2, 3, 2, true);
+    let sequences = crate::from_ndarray(ndarray::Array::from_shape_fn((3, 4, 2), |(i, j, k)| {
+        ((i * 8 + j * 2 + k) as f32 / 10.).sin()
+    }))
+    .requires_grad();
+    let lengths = [2, 4, 3];
+
+    let (output, states) = gru.forward(&PackedSequence::pack(sequences.clone(), &lengths));
+    let padded = output.pad();
+    padded.forward();
+    // The states share most of their nodes with the output, which were computed already.
+    states.iter().for_each(|state| {
+        state.reset();
+        state.forward();
+    });
+
+    // Each sequence is fed alone to the cells, without any padding.
+    for (i, &len) in lengths.iter().enumerate() {
+        let mut input: Vec<_> = (0..len)
+            .map(|t| {
+                crate::from_ndarray(sequences.data().slice(ndarray::s![i..=i, t, ..]).to_owned())
+                    .requires_grad()
+                    .into_dyn()
+            })
+            .collect();
+
+        for (layer, cells) in gru.cells.chunks(2).enumerate() {
+            let mut outputs = vec![Vec::new(); len];
+            for (direction, cell) in cells.iter().enumerate() {
+                let mut hidden = crate::zeros((1, 3)).requires_grad().into_dyn();
+                let order: Vec<_> = match direction {
+                    0 => (0..len).collect(),
+                    _ => (0..len).rev().collect(),
+                };
+                for t in order {
+                    hidden = cell.forward(hidden, input[t].clone()).into_dyn();
+                    outputs[t].push(hidden.clone());
+                }
+
+                hidden.forward();
+                let state = states[layer * 2 + direction].data();
+                assert!(state
+                    .row(i)
+                    .iter()
+                    .zip(hidden.data().iter())
+                    .all(|(a, b)| (a - b).abs() < 1e-6));
+            }
+            input = outputs
+                .iter()
+                .map(|outputs| crate::VarDiff::cat(outputs, 1).into_dyn())
+                .collect();
+        }
+
+        for (t, step) in input.iter().enumerate() {
+            step.forward();
+            assert!(padded
+                .data()
+                .slice(ndarray::s![i, t, ..])
+                .iter()
+                .zip(step.data().iter())
+                .all(|(a, b)| (a - b).abs() < 1e-6));
+        }
+        assert!(padded
+            .data()
+            .slice(ndarray::s![i, len.., ..])
+            .iter()
+            .all(|el| *el == 0.));
+    }
+}
+
+#[test]
+fn scaled_dot_product_attention() {
+    use crate::nn::{init, Dropout};
+
+    let query = crate::zeros((2, 3, 4)).requires_grad();
+    let key = crate::zeros((2, 5, 4)).requires_grad();
+    let value = crate::zeros((2, 5, 2)).requires_grad();
+    init::ones(&value);
+    let dropout = Dropout::new(1.);
+    let attention = crate::nn::scaled_dot_product_attention(
+        query.clone(),
+        key.clone(),
+        value.clone(),
+        None,
+        Some(&dropout),
+    );
+
+    assert_eq!(attention.var.past.len(), 1);
+    assert_eq!(attention.var.past.changeables.len(), 1);
+    assert_eq!(attention.past.len(), 1);
+    assert_eq!(attention.past.parameters.len(), 3);
+
+    attention.forward();
+    assert_eq!(*attention.data(), ndarray::Array::zeros((2, 3, 2)));
+
+    attention.eval();
+    attention.forward();
+    assert_eq!(*attention.data(), ndarray::Array::ones((2, 3, 2)));
+
+    attention.backward(1.);
+    assert_eq!(*value.grad(), ndarray::Array::from_elem((2, 5, 2), 0.6));
+    assert_eq!(*query.grad(), ndarray::Array::zeros((2, 3, 4)));
+}
+
+#[test]
+fn attention_dropout_status() {
+    use crate::nn::{Dropout, ModelStatus};
+
+    let mut status = ModelStatus::default();
+    let dropout = status.register(Dropout::new(1.));
+    let (out, weights) = crate::nn::scaled_dot_product_attention_with_weights(
+        crate::zeros((3, 4)).requires_grad(),
+        crate::zeros((5, 4)).requires_grad(),
+        crate::ones((5, 2)).requires_grad(),
+        None,
+        Some(&dropout),
+    );
+
+    out.forward();
+    assert_eq!(*out.data(), ndarray::Array::zeros((3, 2)));
+
+    // The dropout follows the status of the model it is registered with.
+    status.eval();
+    out.forward();
+    assert_eq!(*out.data(), ndarray::Array::ones((3, 2)));
+    assert_eq!(*weights.data(), ndarray::Array::from_elem((3, 5), 0.2));
+}
+
+#[test]
+fn attention_weights() {
+    use crate::nn::{scaled_dot_product_attention_with_weights, MultiheadAttention};
+
+    let (query_data, key_data) = (crate::rand((3, 4)), crate::rand((5, 4)));
+    let value_data = crate::rand((5, 2));
+
+    let query = crate::full((3, 4), 0.).requires_grad();
+    query.set_data(query_data.data().clone());
+    let key = crate::full((5, 4), 0.).requires_grad();
+    key.set_data(key_data.data().clone());
+    let value = crate::full((5, 2), 0.).requires_grad();
+    value.set_data(value_data.data().clone());
+    let (out, weights) =
+        scaled_dot_product_attention_with_weights(query.clone(), key, value.clone(), None, None);
+    let loss = out.sum() + (weights.clone() * weights.clone()).sum();
+    loss.forward();
+    loss.backward(1.);
+
+    // The same computation carried out step by step.
+    let expected_query = query_data.requires_grad();
+    let expected_value = value_data.requires_grad();
+    let expected_weights = (expected_query.clone().mm_t(key_data.requires_grad()) * 0.5).softmax(1);
+    let expected_loss = expected_weights.clone().mm(expected_value.clone()).sum()
+        + (expected_weights.clone() * expected_weights.clone()).sum();
+    expected_loss.forward();
+    expected_loss.backward(1.);
+
+    assert!(weights
+        .data()
+        .iter()
+        .zip(expected_weights.data().iter())
+        .all(|(el, expected)| (el - expected).abs() < 1e-5));
+    for (grad, expected) in [
+        (query.grad(), expected_query.grad()),
+        (value.grad(), expected_value.grad()),
+    ] {
+        assert!(grad
+            .iter()
+            .zip(expected.iter())
+            .all(|(el, expected)| (el - expected).abs() < 1e-5));
+    }
+
+    // The weights of all the heads are stacked.
+    let attention = MultiheadAttention::new(4, 2);
+    let sequence = crate::rand((3, 4)).requires_grad();
+    let (out, weights) =
+        attention.forward_with_weights(sequence.clone(), sequence.clone(), sequence.clone(), None);
+    let expected = attention.forward(sequence.clone(), sequence.clone(), sequence, None);
+    out.forward();
+    weights.forward();
+    expected.forward();
+
+    assert_eq!(*out.data(), *expected.data());
+    assert_eq!(weights.data().shape(), &[2, 3, 3]);
+    assert!(weights
+        .data()
+        .sum_axis(ndarray::Axis(2))
+        .iter()
+        .all(|sum| (sum - 1.).abs() < 1e-5));
+}
+
+#[test]
+fn positional_encoding() {
+    use crate::nn::{PositionalEncoding, Register};
+
+    let input = crate::zeros((2, 3, 4)).requires_grad();
+    let encoding = PositionalEncoding::sinusoidal(5, 4);
+    let encoded = encoding.forward(input.clone());
+
+    encoded.forward();
+    assert_eq!(encoded.data().shape(), &[2, 3, 4]);
+    assert_eq!(
+        encoded.data().slice(ndarray::s![1, 1, ..]),
+        ndarray::array![1_f32.sin(), 1_f32.cos(), 0.01_f32.sin(), 0.01_f32.cos()]
+    );
+
+    encoded.backward(1.);
+    assert_eq!(*input.grad(), ndarray::Array::ones((2, 3, 4)));
+
+    let mut params = Vec::new();
+    encoding.register_params(&mut params);
+    assert!(params.is_empty());
+    assert_eq!(encoding.non_trainable(), 20);
+
+    let encoding = PositionalEncoding::learned(5, 4);
+    encoding.register_params(&mut params);
+    assert_eq!(params.len(), 1);
+    assert_eq!(encoding.non_trainable(), 0);
+}
+
+#[test]
+#[should_panic(expected = "error: a sequence of length 6 is longer than the 5 positions")]
+fn positional_encoding_too_long() {
+    crate::nn::PositionalEncoding::learned(5, 4).forward(crate::zeros((6, 4)).requires_grad());
+}
+
+#[test]
+fn rotary_embedding() {
+    let rotary = crate::nn::RotaryEmbedding::new(10.);
+    let query = crate::full((3, 4), 1.).requires_grad();
+    let key = crate::from_ndarray(
+        ndarray::Array::from_shape_vec((3, 4), [1., -2., 0.5, 3.].repeat(3)).unwrap(),
+    )
+    .requires_grad();
+    let scores = rotary.forward(query.clone()).mm_t(rotary.forward(key));
+
+    assert_eq!(scores.past.len(), 3);
+    assert_eq!(scores.past.parameters.len(), 2);
+
+    // The scores depend only on the relative positions of the queries and of the keys.
+    scores.forward();
+    let scores = scores.data();
+    assert!((scores[[0, 0]] - scores[[2, 2]]).abs() < 1e-5);
+    assert!((scores[[0, 1]] - scores[[1, 2]]).abs() < 1e-5);
+    assert!((scores[[1, 0]] - scores[[2, 1]]).abs() < 1e-5);
+}
+
+#[test]
+fn attention_masks() {
+    use crate::nn;
+
+    let mask = nn::padding_mask(&[1, 2], 2) + nn::causal_mask(2);
+    assert_eq!(mask.past.len(), 1);
+
+    mask.forward();
+    let inf = f32::NEG_INFINITY;
+    assert_eq!(
+        *mask.data(),
+        ndarray::array![[[0., inf], [0., inf]], [[0., inf], [0., 0.]]]
+    );
+
+    let scores = crate::ones((2, 2, 2))
+        .masked_fill(mask.clone(), inf)
+        .softmax(2);
+    scores.forward();
+    assert_eq!(
+        *scores.data(),
+        ndarray::array![[[1., 0.], [1., 0.]], [[1., 0.], [0.5, 0.5]]]
+    );
+
+    let value = crate::from_ndarray(ndarray::array![[[1.], [3.]], [[1.], [3.]]]).requires_grad();
+    let out = nn::scaled_dot_product_attention(
+        crate::ones((2, 2, 2)).requires_grad(),
+        crate::ones((2, 2, 2)).requires_grad(),
+        value,
+        Some(&mask.data()),
+        None,
+    );
+    out.forward();
+    assert_eq!(*out.data(), ndarray::array![[[1.], [1.]], [[1.], [2.]]]);
+}
+
+#[test]
+#[should_panic(expected = "error: a sequence of length 3 cannot be padded to length 2.")]
+fn padding_mask_too_long() {
+    crate::nn::padding_mask(&[1, 3], 2);
+}
+
+#[test]
+fn masked_pool() {
+    let input = crate::from_ndarray(ndarray::array![[[1., 2.], [5., 6.]], [[3., -4.], [1., 0.]]])
+        .requires_grad();
+    let mask = crate::nn::sequence_mask(&[1, 2], 2);
+
+    let mean = input.clone().masked_mean_pool(mask.clone());
+    let max = input.clone().masked_max_pool(mask);
+    let y = (mean.clone() + max.clone()).sum();
+    y.forward();
+    y.backward(1.);
+
+    assert_eq!(*mean.data(), ndarray::array![[1., 2.], [2., -2.]]);
+    assert_eq!(*max.data(), ndarray::array![[1., 2.], [3., 0.]]);
+    // The padding position of the first sequence receives no gradient.
+    assert_eq!(
+        *input.grad(),
+        ndarray::array![[[2., 2.], [0., 0.]], [[1.5, 0.5], [0.5, 1.5]]]
+    );
+}
+
+#[test]
+#[should_panic(expected = "error: a sequence of length 3 cannot be padded to length 2.")]
+fn sequence_mask_too_long() {
+    crate::nn::sequence_mask(&[1, 3], 2);
+}
+
+#[test]
+fn graph_conv() {
+    use crate::nn::{init, GraphConv};
+
+    // A path 0 - 1 - 2, whose nodes have degrees 2, 3 and 2 once the self-loops are added.
+    let edge_index = ndarray::array![[0, 1, 1, 2], [1, 0, 2, 1]];
+    let conv = GraphConv::new(1, 1);
+    init::ones(&conv.weight);
+    let features = crate::from_ndarray(ndarray::array![[1.], [2.], [3.]]).requires_grad();
+
+    let out = conv.forward(features.clone(), &edge_index);
+    assert_eq!(out.past.parameters.len(), 3);
+
+    out.forward();
+    let (two, six) = (2_f32, 6_f32.sqrt());
+    let expected = ndarray::array![
+        [1. / two + 2. / six],
+        [1. / six + 2. / 3. + 3. / six],
+        [2. / six + 3. / two]
+    ];
+    assert!(out
+        .data()
+        .iter()
+        .zip(expected.iter())
+        .all(|(out, expected)| (out - expected).abs() < 1e-5));
+
+    out.backward(1.);
+    assert!((conv.bias.grad()[0] - 3.).abs() < 1e-5);
+}
+
+#[test]
+#[should_panic(expected = "error: the edge from 0 to 3 is out of range for 3 nodes.")]
+fn graph_conv_out_of_range() {
+    crate::nn::GraphConv::new(1, 1).forward(
+        crate::zeros((3, 1)).requires_grad(),
+        &ndarray::array![[0], [3]],
+    );
+}
+
+#[test]
+fn gat_conv() {
+    use crate::nn::{init, GATConv, Register};
+
+    let edge_index = ndarray::array![[0, 1, 1, 2], [1, 0, 2, 1]];
+    let conv = GATConv::new(1, 1, 2);
+    init::ones(&conv.weight);
+    conv.att_src.iter().for_each(init::zeros);
+    conv.att_dst.iter().for_each(init::zeros);
+    let features = crate::from_ndarray(ndarray::array![[1.], [2.], [3.]]).requires_grad();
+
+    // With null attention vectors each node averages its neighbours uniformly.
+    let out = conv.forward(features.clone(), &edge_index);
+    out.forward();
+    assert_eq!(
+        *out.data(),
+        ndarray::array![[1.5, 1.5], [2., 2.], [2.5, 2.5]]
+    );
+
+    out.backward(1.);
+    assert_eq!(*conv.bias.grad(), ndarray::array![3., 3.]);
+    assert_eq!(features.grad().len(), 3);
+
+    let mut params = Vec::new();
+    conv.register_params(&mut params);
+    assert_eq!(params.len(), 6);
+}
+
+#[test]
+fn params_to_vec() {
+    use crate::nn::{params_to_vec, vec_to_params};
+
+    let w = crate::from_ndarray(ndarray::array![[1., 2.], [3., 4.]]).requires_grad();
+    let b = crate::from_ndarray(ndarray::array![5.]).requires_grad();
+    let out = w.clone().sum() + b.clone();
+
+    let mut params = out.parameters();
+    let values = params_to_vec(&params);
+    assert_eq!(values.len(), 5);
+
+    vec_to_params(
+        &mut params,
+        &values.iter().map(|el| -el).collect::<Vec<_>>(),
+    );
+    assert_eq!(*w.data(), ndarray::array![[-1., -2.], [-3., -4.]]);
+    assert_eq!(*b.data(), ndarray::array![-5.]);
+}
+
+#[test]
+#[should_panic(expected = "error: 4 values were given for parameters of 5 elements.")]
+fn vec_to_params_wrong_length() {
+    let w = crate::ones((2, 2)).requires_grad();
+    let b = crate::ones(1).requires_grad();
+    let out = w.sum() + b;
+
+    crate::nn::vec_to_params(&mut out.parameters(), &[0.; 4]);
+}
+
+#[test]
+fn sync_params() {
+    use crate::nn::{sync_params, Linear, ModelStatus, Module};
+
+    struct Model {
+        linear: Linear,
+        status: ModelStatus,
+    }
+
+    impl Module for Model {
+        fn status(&self) -> &ModelStatus {
+            &self.status
+        }
+    }
+
+    let model = |weight: f32, bias: f32| {
+        let mut status = ModelStatus::default();
+        let linear = status.register(Linear::new(2, 1));
+        linear.weight.data_mut().fill(weight);
+        linear.bias.data_mut().fill(bias);
+        Model { linear, status }
+    };
+
+    let source = model(1., 2.);
+    let target = model(-1., 0.);
+
+    sync_params(&target, &source, 0.25);
+    assert_eq!(*target.linear.weight.data(), ndarray::array![[-0.5, -0.5]]);
+    assert_eq!(*target.linear.bias.data(), ndarray::array![0.5]);
+
+    sync_params(&target, &source, 1.);
+    assert_eq!(*target.linear.weight.data(), *source.linear.weight.data());
+    assert_eq!(*target.linear.bias.data(), *source.linear.bias.data());
+    // The source is left unchanged.
+    assert_eq!(*source.linear.weight.data(), ndarray::array![[1., 1.]]);
+}
+
+#[test]
+#[should_panic(
+    expected = "error: the parameters of the source and of the target models have different shapes."
+)]
+fn sync_params_fail() {
+    use crate::nn::{sync_params, Linear, ModelStatus, Module};
+
+    struct Model(ModelStatus);
+
+    impl Module for Model {
+        fn status(&self) -> &ModelStatus {
+            &self.0
+        }
+    }
+
+    let mut source = ModelStatus::default();
+    source.register(Linear::new(2, 3));
+    let mut target = ModelStatus::default();
+    target.register(Linear::new(3, 2));
+
+    sync_params(&Model(target), &Model(source), 0.5);
+}
+
+#[test]
+fn crf() {
+    use crate::nn::{init, Register, CRF};
+
+    let crf = CRF::new(2);
+    init::zeros(&crf.start_transitions);
+    init::zeros(&crf.end_transitions);
+    *crf.transitions.data_mut() = ndarray::array![[0., -2.], [1., 0.]];
+    let emissions = crate::from_ndarray(ndarray::array![[[1., 0.], [0., 0.5]]]).requires_grad();
+
+    // The likelihoods of all the possible sequences of tags sum to one.
+    let likelihoods: f32 = [[0, 0], [0, 1], [1, 0], [1, 1]]
+        .iter()
+        .map(|tags| {
+            let tags =
+                crate::indices(ndarray::Array::from_shape_vec((1, 2), tags.to_vec()).unwrap());
+            let log_likelihood = crf.forward(emissions.clone(), tags, &[2]);
+            assert_eq!(log_likelihood.past.parameters.len(), 4);
+
+            log_likelihood.forward();
+            let likelihood = log_likelihood.data()[0].exp();
+            likelihood
+        })
+        .sum();
+    assert!((likelihoods - 1.).abs() < 1e-5);
+
+    assert_eq!(crf.decode(&emissions.data(), &[2]), vec![vec![0, 0]]);
+    assert_eq!(crf.decode(&emissions.data(), &[1]), vec![vec![0]]);
+    *crf.transitions.data_mut() = ndarray::array![[0., 2.], [1., 0.]];
+    assert_eq!(crf.decode(&emissions.data(), &[2]), vec![vec![0, 1]]);
+
+    let mut params = Vec::new();
+    crf.register_params(&mut params);
+    assert_eq!(params.len(), 3);
+    assert_eq!((crf.shape_inference())(&[8, 5, 2]), vec![8]);
+}
+
+#[test]
+fn forward_hook() {
+    let input = crate::ones((2, 2)).register_forward_hook(|_| {});
+
+    assert_eq!(input.past.len(), 1);
+}
+
+#[test]
+fn hooks_diff() {
+    let input = (crate::ones((2, 2)).requires_grad() * 2.)
+        .register_forward_hook(|_| {})
+        .register_backward_hook(|_| {});
+
+    assert_eq!(input.var.past.len(), 2);
+    assert_eq!(input.past.len(), 2);
+    assert_eq!(input.past.parameters.len(), 1);
+}
+
+#[test]
+fn to_dot() {
+    let x = crate::ones((2, 2));
+    let y = crate::ones((2, 2)).requires_grad();
+    let z = (x.clone() + y).relu() + x;
+
+    let dot = z.to_dot();
+    assert_eq!(dot.lines().filter(|line| line.contains("label")).count(), 5);
+    assert_eq!(dot.lines().filter(|line| line.contains("->")).count(), 5);
+    assert_eq!(dot.matches("fillcolor").count(), 4);
+    assert!(dot.contains("ReLU [2, 2]"));
+}
+
+#[test]
+fn chunks() {
+    let input = crate::ones((2, 2));
+    let chunks = input.chunks((1, 1));
+
+    assert_eq!(chunks[0].past.len(), 1);
+    assert_eq!(chunks[1].past.len(), 1);
+
+    assert!(chunks[0].past.changeables.is_empty());
+    assert!(chunks[1].past.changeables.is_empty());
+}
+
+#[test]
+fn chunks_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let chunks = input.chunks((1, 1));
+
+    // The backward node shared by all the chunks and the chunk's own one.
+    assert_eq!(chunks[0].past.len(), 2);
+    assert_eq!(chunks[1].past.len(), 2);
+
+    assert_eq!(chunks[0].past.parameters.len(), 1);
+    assert_eq!(chunks[1].past.parameters.len(), 1);
+}
+
+#[test]
+fn unsqueeze() {
+    let input = crate::ones((2, 2));
+    let unsqueeze = input.unsqueeze(0);
+
+    assert_eq!(unsqueeze.past.len(), 1);
+    assert!(unsqueeze.past.changeables.is_empty());
+}
+
+#[test]
+fn unsqueeze_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let unsqueeze = input.unsqueeze(0);
+
+    assert_eq!(unsqueeze.past.len(), 1);
+    assert_eq!(unsqueeze.past.parameters.len(), 1);
+}
+
+#[test]
+fn cat() {
+    let lhs = crate::ones((2, 2));
+    let rhs = crate::zeros((2, 2));
+    let cat = super::Cat::cat(lhs, rhs, 1);
+
+    assert_eq!(cat.past.len(), 1);
+    assert!(cat.past.changeables.is_empty());
+
+    let lhs = crate::ones((2, 2));
+    let rhs = crate::zeros((2, 2)).requires_grad();
+    let cat = super::Cat::cat(lhs, rhs, 1);
+
+    assert_eq!(cat.past.len(), 1);
+    assert_eq!(cat.past.parameters.len(), 1);
+}
+
+#[test]
+fn cat_diff() {
+    let lhs = crate::ones((2, 2)).requires_grad();
+    let rhs = crate::zeros((2, 2));
+    let cat = super::Cat::cat(lhs, rhs, 1);
+
+    assert_eq!(cat.past.len(), 1);
+    assert_eq!(cat.past.parameters.len(), 1);
+
+    let lhs = crate::ones((2, 2)).requires_grad();
+    let rhs = crate::zeros((2, 2)).requires_grad();
+    let cat = super::Cat::cat(lhs, rhs, 1);
+
+    assert_eq!(cat.past.len(), 1);
+    assert_eq!(cat.past.parameters.len(), 2);
+}
+
+#[test]
+fn multi_cat() {
+    let a = crate::ones((2, 2)) + 1.;
+    let b = 18. / crate::full((2, 2), 9.);
+    let c = crate::full((2, 1), 3.) * 4.;
+
+    let d = crate::Var::cat(&[a.into_dyn(), b.into_dyn(), c.into_dyn()], 1);
+    assert_eq!(d.past.len(), 5);
+    assert!(d.past.changeables.is_empty());
+}
+
+#[test]
+fn multi_cat_diff() {
+    let a = crate::ones((2, 2)).requires_grad() + 1.;
+    let b = 18. / crate::full((2, 2), 9.).requires_grad();
+    let c = crate::full((2, 1), 3.).requires_grad() * 4.;
+
+    let d = crate::VarDiff::cat(&[a.into_dyn(), b.into_dyn(), c.into_dyn()], 1);
+    assert_eq!(d.past.len(), 5);
+    assert_eq!(d.past.parameters.len(), 3);
+}
+
+#[test]
+fn stack() {
+    let lhs = crate::ones((2, 2));
+    let rhs = crate::zeros((2, 2));
+    let stack = super::Stack::stack(lhs, rhs, 1);
+
+    assert_eq!(stack.past.len(), 1);
+    assert!(stack.past.changeables.is_empty());
+
+    let lhs = crate::ones((2, 2));
+    let rhs = crate::zeros((2, 2)).requires_grad();
+    let cat = super::Stack::stack(lhs, rhs, 1);
+
+    assert_eq!(cat.past.len(), 1);
+    assert_eq!(cat.past.parameters.len(), 1);
+}
+
+#[test]
+fn stack_diff() {
+    let lhs = crate::ones((2, 2)).requires_grad();
+    let rhs = crate::zeros((2, 2));
+    let cat = super::Stack::stack(lhs, rhs, 1);
+
+    assert_eq!(cat.past.len(), 1);
+    assert_eq!(cat.past.parameters.len(), 1);
+
+    let lhs = crate::ones((2, 2)).requires_grad();
+    let rhs = crate::zeros((2, 2)).requires_grad();
+    let stack = super::Stack::stack(lhs, rhs, 1);
+
+    assert_eq!(stack.past.len(), 1);
+    assert_eq!(stack.past.parameters.len(), 2);
+}
+
+#[test]
+fn multi_stack() {
+    let a = crate::ones((2, 2)) + 1.;
+    let b = 18. / crate::full((2, 2), 9.);
+    let c = crate::full((2, 2), 3.) * 4.;
+
+    let d = crate::Var::stack(&[a.into_dyn(), b.into_dyn(), c.into_dyn()], 0);
+    assert_eq!(d.past.len(), 5);
+    assert!(d.past.changeables.is_empty());
+}
+
+#[test]
+fn multi_stack_diff() {
+    let a = crate::ones((2, 2)).requires_grad() + 1.;
+    let b = 18. / crate::full((2, 2), 9.).requires_grad();
+    let c = crate::full((2, 2), 3.).requires_grad() * 4.;
+
+    let d = crate::VarDiff::stack(&[a.into_dyn(), b.into_dyn(), c.into_dyn()], 0);
+    assert_eq!(d.past.len(), 5);
+    assert_eq!(d.past.parameters.len(), 3);
+}
+
+#[test]
+fn neg() {
+    let input = crate::ones((2, 2));
+    let neg = -input;
+
+    assert_eq!(neg.past.len(), 1);
+    assert!(neg.past.changeables.is_empty());
+}
+
+#[test]
+fn neg_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let neg = -input;
+
+    assert_eq!(neg.past.len(), 1);
+    assert_eq!(neg.past.parameters.len(), 1);
+}
+
+#[test]
+fn add() {
+    let lhs = crate::ones((2, 2));
+    let rhs = crate::zeros((2, 2));
+    let add = lhs + rhs;
+
+    assert_eq!(add.past.len(), 1);
+    assert!(add.past.changeables.is_empty());
+}
+
+#[test]
+fn add_diff() {
+    let lhs = crate::ones((2, 2)).requires_grad();
+    let rhs = crate::zeros((2, 2));
+    let add = lhs + rhs;
+
+    assert_eq!(add.past.len(), 1);
+    assert_eq!(add.past.parameters.len(), 1);
+
+    let lhs = crate::ones((2, 2));
+    let rhs = crate::zeros((2, 2)).requires_grad();
+    let add = lhs + rhs;
+
+    assert_eq!(add.past.len(), 1);
+    assert_eq!(add.past.parameters.len(), 1);
+
+    let lhs = crate::ones((2, 2)).requires_grad();
+    let rhs = crate::zeros((2, 2)).requires_grad();
+    let add = lhs + rhs;
+
+    assert_eq!(add.past.len(), 1);
+    assert_eq!(add.past.parameters.len(), 2);
+}
+
+#[test]
+fn sub() {
+    let lhs = crate::ones((2, 2));
+    let rhs = crate::zeros((2, 2));
+    let sub = lhs - rhs;
+
+    assert_eq!(sub.past.len(), 1);
+    assert!(sub.past.changeables.is_empty());
+}
+
+#[test]
+fn sub_diff() {
+    let lhs = crate::ones((2, 2)).requires_grad();
+    let rhs = crate::zeros((2, 2));
+    let sub = lhs - rhs;
+
+    assert_eq!(sub.past.len(), 1);
+    assert_eq!(sub.past.parameters.len(), 1);
+
+    let lhs = crate::ones((2, 2));
+    let rhs = crate::zeros((2, 2)).requires_grad();
+    let sub = lhs - rhs;
+
+    assert_eq!(sub.past.len(), 1);
+    assert_eq!(sub.past.parameters.len(), 1);
+
+    let lhs = crate::ones((2, 2)).requires_grad();
+    let rhs = crate::zeros((2, 2)).requires_grad();
+    let sub = lhs - rhs;
+
+    assert_eq!(sub.past.len(), 1);
+    assert_eq!(sub.past.parameters.len(), 2);
 }
 
 #[test]
 fn mul() {
     let lhs = crate::ones((2, 2));
     let rhs = crate::zeros((2, 2));
-    let mul = lhs * rhs;
+    let mul = lhs * rhs;
+
+    assert_eq!(mul.past.len(), 1);
+    assert!(mul.past.changeables.is_empty());
+}
+
+#[test]
+fn mul_diff() {
+    let lhs = crate::ones((2, 2)).requires_grad();
+    let rhs = crate::zeros((2, 2));
+    let mul = lhs * rhs;
+
+    assert_eq!(mul.past.len(), 1);
+    assert_eq!(mul.past.parameters.len(), 1);
+
+    let lhs = crate::ones((2, 2));
+    let rhs = crate::zeros((2, 2)).requires_grad();
+    let mul = lhs * rhs;
+
+    assert_eq!(mul.past.len(), 1);
+    assert_eq!(mul.past.parameters.len(), 1);
+
+    let lhs = crate::ones((2, 2)).requires_grad();
+    let rhs = crate::zeros((2, 2)).requires_grad();
+    let mul = lhs * rhs;
+
+    assert_eq!(mul.past.len(), 1);
+    assert_eq!(mul.past.parameters.len(), 2);
+}
+
+#[test]
+fn div() {
+    let lhs = crate::ones((2, 2));
+    let rhs = crate::zeros((2, 2));
+    let div = lhs / rhs;
+
+    assert_eq!(div.past.len(), 1);
+    assert!(div.past.changeables.is_empty());
+}
+
+#[test]
+fn div_diff() {
+    let lhs = crate::ones((2, 2)).requires_grad();
+    let rhs = crate::zeros((2, 2));
+    let div = lhs / rhs;
+
+    assert_eq!(div.past.len(), 1);
+    assert_eq!(div.past.parameters.len(), 1);
+
+    let lhs = crate::ones((2, 2));
+    let rhs = crate::zeros((2, 2)).requires_grad();
+    let div = lhs / rhs;
+
+    assert_eq!(div.past.len(), 1);
+    assert_eq!(div.past.parameters.len(), 1);
+
+    let lhs = crate::ones((2, 2)).requires_grad();
+    let rhs = crate::zeros((2, 2)).requires_grad();
+    let div = lhs / rhs;
+
+    assert_eq!(div.past.len(), 1);
+    assert_eq!(div.past.parameters.len(), 2);
+}
+
+#[test]
+fn maximum() {
+    let lhs = crate::ones((2, 2));
+    let rhs = crate::zeros(2);
+    let maximum = crate::maximum(lhs, rhs);
+
+    assert_eq!(maximum.past.len(), 1);
+    assert!(maximum.past.changeables.is_empty());
+}
+
+#[test]
+fn maximum_diff() {
+    use crate::Maximum;
+
+    let lhs = crate::ones((2, 2)).requires_grad();
+    let rhs = crate::zeros(2);
+    let maximum = lhs.maximum(rhs);
+
+    assert_eq!(maximum.past.len(), 1);
+    assert_eq!(maximum.past.parameters.len(), 1);
+
+    let lhs = crate::ones((2, 2));
+    let rhs = crate::zeros(2).requires_grad();
+    let maximum = lhs.maximum(rhs);
+
+    assert_eq!(maximum.past.len(), 1);
+    assert_eq!(maximum.past.parameters.len(), 1);
+
+    let lhs = crate::ones((2, 2)).requires_grad();
+    let rhs = crate::zeros(2).requires_grad();
+    let maximum = lhs.maximum(rhs);
+
+    assert_eq!(maximum.past.len(), 1);
+    assert_eq!(maximum.past.parameters.len(), 2);
+}
+
+#[test]
+fn minimum() {
+    let lhs = crate::ones((2, 2));
+    let rhs = crate::zeros(2);
+    let minimum = crate::minimum(lhs, rhs);
+
+    assert_eq!(minimum.past.len(), 1);
+    assert!(minimum.past.changeables.is_empty());
+}
+
+#[test]
+fn minimum_diff() {
+    use crate::Minimum;
+
+    let lhs = crate::ones((2, 2)).requires_grad();
+    let rhs = crate::zeros(2);
+    let minimum = lhs.minimum(rhs);
+
+    assert_eq!(minimum.past.len(), 1);
+    assert_eq!(minimum.past.parameters.len(), 1);
+
+    let lhs = crate::ones((2, 2));
+    let rhs = crate::zeros(2).requires_grad();
+    let minimum = lhs.minimum(rhs);
+
+    assert_eq!(minimum.past.len(), 1);
+    assert_eq!(minimum.past.parameters.len(), 1);
+
+    let lhs = crate::ones((2, 2)).requires_grad();
+    let rhs = crate::zeros(2).requires_grad();
+    let minimum = lhs.minimum(rhs);
+
+    assert_eq!(minimum.past.len(), 1);
+    assert_eq!(minimum.past.parameters.len(), 2);
+}
+
+#[test]
+fn maximum_minimum_backward() {
+    let lhs = crate::from_ndarray(ndarray::array![[-1., 2.], [3., 0.]]).requires_grad();
+    let rhs = crate::from_ndarray(ndarray::array![0., 1.]).requires_grad();
+    let upper = crate::full(2, 2.5);
+
+    let clamped = crate::minimum(crate::maximum(lhs.clone(), rhs.clone()), upper);
+    clamped.forward();
+    assert_eq!(*clamped.data(), ndarray::array![[0., 2.], [2.5, 1.]]);
+
+    clamped.backward(1.);
+    assert_eq!(*lhs.grad(), ndarray::array![[0., 1.], [0., 0.]]);
+    assert_eq!(*rhs.grad(), ndarray::array![1., 1.]);
+}
+
+#[test]
+fn comparisons() {
+    let lhs = crate::from_ndarray(ndarray::array![-1., 0., 1.]);
+    let rhs = crate::zeros(3);
+
+    let masks = [
+        (lhs.clone().gt(rhs.clone()), ndarray::array![0., 0., 1.]),
+        (lhs.clone().lt(rhs.clone()), ndarray::array![1., 0., 0.]),
+        (lhs.clone().ge(rhs.clone()), ndarray::array![0., 1., 1.]),
+        (lhs.clone().le(rhs.clone()), ndarray::array![1., 1., 0.]),
+        (lhs.eq(rhs), ndarray::array![0., 1., 0.]),
+    ];
+
+    for (mask, expected) in masks {
+        assert_eq!(mask.past.len(), 1);
+        assert!(mask.past.changeables.is_empty());
+
+        mask.forward();
+        assert_eq!(*mask.data(), expected);
+    }
+}
+
+#[test]
+fn comparison_diff() {
+    let lhs = crate::ones((2, 2)).requires_grad();
+    let mask = lhs.clone().gt(crate::zeros(2));
+
+    assert_eq!(mask.past.len(), 1);
+
+    mask.forward();
+    assert_eq!(*mask.data(), ndarray::array![[1., 1.], [1., 1.]]);
+}
+
+#[test]
+fn masked_fill() {
+    let input = crate::ones((2, 2));
+    let masked = input.masked_fill(crate::zeros(2), 0.);
+
+    assert_eq!(masked.past.len(), 1);
+    assert!(masked.past.changeables.is_empty());
+}
+
+#[test]
+fn masked_fill_diff() {
+    let input = crate::from_ndarray(ndarray::array![[1., -2.], [-3., 4.]]).requires_grad();
+    let mask = input.clone().lt(crate::full((), 0.));
+    let masked = input.clone().masked_fill(mask, 0.);
+
+    assert_eq!(masked.past.len(), 1);
+    assert_eq!(masked.past.parameters.len(), 1);
+
+    masked.forward();
+    assert_eq!(*masked.data(), ndarray::array![[1., 0.], [0., 4.]]);
+
+    masked.backward(1.);
+    assert_eq!(*input.grad(), ndarray::array![[1., 0.], [0., 1.]]);
+}
+
+#[test]
+fn tensor_pow() {
+    let base = crate::ones((2, 2));
+    let exponent = crate::zeros(2);
+    let pow = crate::pow(base, exponent);
+
+    assert_eq!(pow.past.len(), 1);
+    assert!(pow.past.changeables.is_empty());
+}
+
+#[test]
+fn tensor_pow_diff() {
+    use crate::Pow;
+
+    let base = crate::ones((2, 2)).requires_grad();
+    let exponent = crate::zeros(2);
+    let pow = base.powf(exponent);
+
+    assert_eq!(pow.past.len(), 1);
+    assert_eq!(pow.past.parameters.len(), 1);
+
+    let base = crate::ones((2, 2));
+    let exponent = crate::zeros(2).requires_grad();
+    let pow = base.powf(exponent);
+
+    assert_eq!(pow.past.len(), 1);
+    assert_eq!(pow.past.parameters.len(), 1);
+
+    let base = crate::full((2, 2), 2.).requires_grad();
+    let exponent = crate::full(2, 3.).requires_grad();
+    let pow = base.clone().powf(exponent.clone());
+
+    assert_eq!(pow.past.len(), 1);
+    assert_eq!(pow.past.parameters.len(), 2);
+
+    pow.forward();
+    assert_eq!(*pow.data(), ndarray::array![[8., 8.], [8., 8.]]);
+
+    pow.backward(1.);
+    assert_eq!(*base.grad(), ndarray::array![[12., 12.], [12., 12.]]);
+    assert_eq!(
+        *exponent.grad(),
+        ndarray::array![16. * 2_f32.ln(), 16. * 2_f32.ln()]
+    );
+}
+
+#[test]
+fn vv() {
+    let lhs = crate::ones(2);
+    let rhs = crate::zeros(2);
+    let vv = lhs.vv(rhs);
+
+    assert_eq!(vv.past.len(), 1);
+    assert!(vv.past.changeables.is_empty());
+}
+
+#[test]
+fn vv_diff() {
+    let lhs = crate::ones(2);
+    let rhs = crate::zeros(2).requires_grad();
+    let vv = lhs.vv(rhs);
+
+    assert_eq!(vv.past.len(), 1);
+    assert_eq!(vv.past.parameters.len(), 1);
+
+    let lhs = crate::ones(2).requires_grad();
+    let rhs = crate::zeros(2);
+    let vv = lhs.vv(rhs);
+
+    assert_eq!(vv.past.len(), 1);
+    assert_eq!(vv.past.parameters.len(), 1);
+
+    let lhs = crate::ones(2).requires_grad();
+    let rhs = crate::zeros(2).requires_grad();
+    let vv = lhs.vv(rhs);
+
+    assert_eq!(vv.past.len(), 1);
+    assert_eq!(vv.past.parameters.len(), 2);
+}
+
+#[test]
+fn vm() {
+    let lhs = crate::ones(2);
+    let rhs = crate::zeros((2, 2));
+    let vm = lhs.vm(rhs);
+
+    assert_eq!(vm.past.len(), 1);
+    assert!(vm.past.changeables.is_empty());
+}
+
+#[test]
+fn vm_diff() {
+    let lhs = crate::ones(2);
+    let rhs = crate::zeros((2, 2)).requires_grad();
+    let vm = lhs.vm(rhs);
+
+    assert_eq!(vm.past.len(), 1);
+    assert_eq!(vm.past.parameters.len(), 1);
+
+    let lhs = crate::ones(2).requires_grad();
+    let rhs = crate::zeros((2, 2));
+    let vm = lhs.vm(rhs);
+
+    assert_eq!(vm.past.len(), 1);
+    assert_eq!(vm.past.parameters.len(), 1);
+
+    let lhs = crate::ones(2).requires_grad();
+    let rhs = crate::zeros((2, 2)).requires_grad();
+    let vm = lhs.vm(rhs);
+
+    assert_eq!(vm.past.len(), 1);
+    assert_eq!(vm.past.parameters.len(), 2);
+}
+
+#[test]
+fn mv() {
+    let lhs = crate::zeros((2, 2));
+    let rhs = crate::ones(2);
+    let mv = lhs.mv(rhs);
+
+    assert_eq!(mv.past.len(), 1);
+    assert!(mv.past.changeables.is_empty());
+}
+
+#[test]
+fn mv_diff() {
+    let lhs = crate::zeros((2, 2)).requires_grad();
+    let rhs = crate::ones(2);
+    let mv = lhs.mv(rhs);
+
+    assert_eq!(mv.past.len(), 1);
+    assert_eq!(mv.past.parameters.len(), 1);
+
+    let lhs = crate::zeros((2, 2));
+    let rhs = crate::ones(2).requires_grad();
+    let mv = lhs.mv(rhs);
+
+    assert_eq!(mv.past.len(), 1);
+    assert_eq!(mv.past.parameters.len(), 1);
+
+    let lhs = crate::zeros((2, 2)).requires_grad();
+    let rhs = crate::ones(2).requires_grad();
+    let mv = lhs.mv(rhs);
+
+    assert_eq!(mv.past.len(), 1);
+    assert_eq!(mv.past.parameters.len(), 2);
+}
+
+#[test]
+fn mm() {
+    let lhs = crate::zeros((2, 2));
+    let rhs = crate::ones((2, 2));
+    let mm = lhs.mm(rhs);
+
+    assert_eq!(mm.past.len(), 1);
+    assert!(mm.past.changeables.is_empty());
+}
+
+#[test]
+fn mm_diff() {
+    let lhs = crate::zeros((2, 2)).requires_grad();
+    let rhs = crate::ones((2, 2));
+    let mm = lhs.mm(rhs);
+
+    assert_eq!(mm.past.len(), 1);
+    assert_eq!(mm.past.parameters.len(), 1);
+
+    let lhs = crate::zeros((2, 2));
+    let rhs = crate::ones((2, 2)).requires_grad();
+    let mm = lhs.mm(rhs);
+
+    assert_eq!(mm.past.len(), 1);
+    assert_eq!(mm.past.parameters.len(), 1);
+
+    let lhs = crate::zeros((2, 2)).requires_grad();
+    let rhs = crate::ones((2, 2)).requires_grad();
+    let mm = lhs.mm(rhs);
+
+    assert_eq!(mm.past.len(), 1);
+    assert_eq!(mm.past.parameters.len(), 2);
+}
+
+#[test]
+fn mm_t() {
+    let lhs = crate::zeros((2, 2));
+    let rhs = crate::ones((2, 2));
+    let mm_t = lhs.mm_t(rhs);
+
+    assert_eq!(mm_t.past.len(), 1);
+    assert!(mm_t.past.changeables.is_empty());
+}
+
+#[test]
+fn mm_t_diff() {
+    let lhs = crate::zeros((2, 2)).requires_grad();
+    let rhs = crate::ones((2, 2));
+    let mm_t = lhs.mm_t(rhs);
+
+    assert_eq!(mm_t.past.len(), 1);
+    assert_eq!(mm_t.past.parameters.len(), 1);
+
+    let lhs = crate::zeros((2, 2));
+    let rhs = crate::ones((2, 2)).requires_grad();
+    let mm_t = lhs.mm_t(rhs);
+
+    assert_eq!(mm_t.past.len(), 1);
+    assert_eq!(mm_t.past.parameters.len(), 1);
+
+    let lhs = crate::zeros((2, 2)).requires_grad();
+    let rhs = crate::ones((2, 2)).requires_grad();
+    let mm_t = lhs.mm_t(rhs);
+
+    assert_eq!(mm_t.past.len(), 1);
+    assert_eq!(mm_t.past.parameters.len(), 2);
+}
+
+#[test]
+fn convolve() {
+    use crate::Convolve;
+
+    let kernel = crate::zeros((2, 2, 2, 2));
+    let input = crate::ones((4, 2, 6, 6));
+    let convolve = super::Var::convolve(
+        input,
+        kernel,
+        &[1, 1],
+        &[1, 1],
+        &[0, 0],
+        crate::variable::Zero,
+    );
+
+    assert_eq!(convolve.past.len(), 1);
+    assert!(convolve.past.changeables.is_empty());
+}
+
+#[test]
+fn convolve_diff() {
+    use crate::Convolve;
+
+    let kernel = crate::zeros((2, 2, 2, 2)).requires_grad();
+    let input = crate::ones((4, 2, 6, 6));
+    let convolve = super::Var::convolve(
+        input,
+        kernel,
+        &[1, 1],
+        &[1, 1],
+        &[0, 0],
+        crate::variable::Zero,
+    );
+
+    assert_eq!(convolve.past.len(), 1);
+    assert_eq!(convolve.past.parameters.len(), 1);
+
+    let kernel = crate::zeros((2, 2, 2, 2)).requires_grad();
+    let input = crate::ones((4, 2, 6, 6)).requires_grad();
+    let convolve = super::VarDiff::convolve(
+        input,
+        kernel,
+        &[1, 1],
+        &[1, 1],
+        &[0, 0],
+        crate::variable::Zero,
+    );
 
-    assert_eq!(mul.past.len(), 1);
-    assert!(mul.past.changeables.is_empty());
+    assert_eq!(convolve.past.len(), 1);
+    assert_eq!(convolve.past.parameters.len(), 2);
 }
 
 #[test]
-fn mul_diff() {
-    let lhs = crate::ones((2, 2)).requires_grad();
-    let rhs = crate::zeros((2, 2));
-    let mul = lhs * rhs;
+fn convolve_groups() {
+    use crate::ConvolveWithGroups;
 
-    assert_eq!(mul.past.len(), 1);
-    assert_eq!(mul.past.parameters.len(), 1);
+    let kernel = crate::zeros((2, 2, 2, 2));
+    let input = crate::ones((4, 2, 6, 6));
+    let convolve = super::Var::convolve_with_groups(
+        input,
+        kernel,
+        &[1, 1],
+        &[1, 1],
+        &[0, 0],
+        crate::variable::Zero,
+        2,
+    );
 
-    let lhs = crate::ones((2, 2));
-    let rhs = crate::zeros((2, 2)).requires_grad();
-    let mul = lhs * rhs;
+    assert_eq!(convolve.past.len(), 1);
+    assert!(convolve.past.changeables.is_empty());
+}
 
-    assert_eq!(mul.past.len(), 1);
-    assert_eq!(mul.past.parameters.len(), 1);
+#[test]
+fn convolve_groups_diff() {
+    use crate::ConvolveWithGroups;
 
-    let lhs = crate::ones((2, 2)).requires_grad();
-    let rhs = crate::zeros((2, 2)).requires_grad();
-    let mul = lhs * rhs;
+    let kernel = crate::zeros((2, 2, 2, 2)).requires_grad();
+    let input = crate::ones((4, 2, 6, 6));
+    let convolve = super::Var::convolve_with_groups(
+        input,
+        kernel,
+        &[1, 1],
+        &[1, 1],
+        &[0, 0],
+        crate::variable::Zero,
+        2,
+    );
 
-    assert_eq!(mul.past.len(), 1);
-    assert_eq!(mul.past.parameters.len(), 2);
+    assert_eq!(convolve.past.len(), 1);
+    assert_eq!(convolve.past.parameters.len(), 1);
+
+    let kernel = crate::zeros((2, 2, 2, 2)).requires_grad();
+    let input = crate::ones((4, 2, 6, 6)).requires_grad();
+    let convolve = super::VarDiff::convolve_with_groups(
+        input,
+        kernel,
+        &[1, 1],
+        &[1, 1],
+        &[0, 0],
+        crate::variable::Zero,
+        2,
+    );
+
+    assert_eq!(convolve.past.len(), 1);
+    assert_eq!(convolve.past.parameters.len(), 2);
+}
+
+#[test]
+fn checked_matrix_products() {
+    let lhs = crate::ones((3, 4));
+
+    assert!(lhs.clone().try_mm(crate::ones((4, 2))).is_ok());
+    assert!(lhs.clone().try_mm_t(crate::ones((2, 4))).is_ok());
+    assert!(lhs.clone().try_mv(crate::ones(4)).is_ok());
+
+    let error = lhs.clone().try_mm(crate::ones((3, 2))).unwrap_err();
+    assert_eq!(error.operation(), "mm");
+    assert_eq!(error.lhs_shape(), &[3, 4]);
+    assert_eq!(error.rhs_shape(), &[3, 2]);
+
+    let error = lhs.clone().try_mm_t(crate::ones((4, 3))).unwrap_err();
+    assert_eq!(error.operation(), "mm_t");
+    assert_eq!(
+        error.to_string(),
+        "error: mm_t between operands of shape [3, 4] and [4, 3]: the number of columns of lhs, \
+         4, differs from the number of columns of rhs, 3."
+    );
+
+    let error = lhs.requires_grad().try_mv(crate::ones(3)).unwrap_err();
+    assert_eq!(error.operation(), "mv");
+
+    let lhs = crate::ones(3).requires_grad();
+    assert!(lhs.clone().try_vm(crate::ones((3, 2))).is_ok());
+    assert!(lhs.clone().try_vv(crate::ones(3)).is_ok());
+    assert_eq!(
+        lhs.clone()
+            .try_vm(crate::ones((2, 3)))
+            .unwrap_err()
+            .operation(),
+        "vm"
+    );
+    assert_eq!(lhs.try_vv(crate::ones(2)).unwrap_err().rhs_shape(), &[2]);
+}
+
+#[test]
+fn checked_cat_stack() {
+    let lhs = crate::ones((2, 3));
+
+    assert!(crate::try_cat(lhs.clone(), crate::ones((5, 3)), 0).is_ok());
+    assert!(crate::try_stack(lhs.clone(), crate::ones((2, 3)), 2).is_ok());
+
+    let error = crate::try_cat(lhs.clone(), crate::ones((5, 3)), 1).unwrap_err();
+    assert_eq!(error.operation(), "cat");
+    assert_eq!(error.rhs_shape(), &[5, 3]);
+    assert!(crate::try_cat(lhs.clone(), crate::ones((2, 3)), 2).is_err());
+
+    let error = crate::try_stack(lhs.clone(), crate::ones((3, 2)), 0).unwrap_err();
+    assert_eq!(error.operation(), "stack");
+    assert!(crate::try_stack(lhs, crate::ones((2, 3)), 3).is_err());
+}
+
+#[test]
+fn checked_convolve() {
+    use crate::{Convolve, ConvolveWithGroups};
+
+    let convolve = super::Var::try_convolve(
+        crate::ones((4, 2, 6, 6)),
+        crate::zeros((3, 2, 2, 2)).requires_grad(),
+        &[1, 1],
+        &[1, 1],
+        &[0, 0],
+        crate::variable::Zero,
+    );
+    assert!(convolve.is_ok());
+
+    let error = super::Var::try_convolve(
+        crate::ones((4, 2, 6, 6)),
+        crate::zeros((3, 3, 2, 2)),
+        &[1, 1],
+        &[1, 1],
+        &[0, 0],
+        crate::variable::Zero,
+    )
+    .unwrap_err();
+    assert_eq!(error.operation(), "convolution");
+    assert_eq!(error.lhs_shape(), &[4, 2, 6, 6]);
+    assert_eq!(error.rhs_shape(), &[3, 3, 2, 2]);
+
+    let error = super::Var::try_convolve(
+        crate::ones((4, 2, 6, 6)),
+        crate::zeros((3, 2, 7, 7)),
+        &[1, 1],
+        &[1, 1],
+        &[0, 0],
+        crate::variable::Zero,
+    );
+    assert!(error.is_err());
+
+    let error = super::Var::try_convolve(
+        crate::ones((4, 2, 6, 6)),
+        crate::zeros((3, 2, 2, 2)),
+        &[1],
+        &[1, 1],
+        &[0, 0],
+        crate::variable::Zero,
+    );
+    assert!(error.is_err());
+
+    let convolve = super::VarDiff::try_convolve_with_groups(
+        crate::ones((4, 4, 6, 6)).requires_grad(),
+        crate::zeros((2, 2, 2, 2)).requires_grad(),
+        &[1, 1],
+        &[1, 1],
+        &[0, 0],
+        crate::variable::Zero,
+        2,
+    );
+    assert!(convolve.is_ok());
+
+    let error = super::Var::try_convolve_with_groups(
+        crate::ones((4, 4, 6, 6)),
+        crate::zeros((2, 2, 2, 2)),
+        &[1, 1],
+        &[1, 1],
+        &[0, 0],
+        crate::variable::Zero,
+        3,
+    );
+    assert!(error.is_err());
+}
+
+#[test]
+fn checked_losses() {
+    use crate::nn::loss::{try_kldiv_loss, try_mse_loss, try_nll_loss, Reduction};
+
+    let input = crate::ones((3, 5)).requires_grad();
+    assert!(try_mse_loss(input.clone(), crate::ones((3, 5)), Reduction::Mean).is_ok());
+    assert!(try_kldiv_loss(input.clone(), crate::ones((5, 3)), Reduction::Sum).is_err());
+
+    assert!(try_nll_loss(input.clone(), crate::zeros(3), Reduction::Mean).is_ok());
+    let error = try_nll_loss(input, crate::zeros(5), Reduction::Mean).unwrap_err();
+    assert_eq!(error.operation(), "nll_loss");
+    assert_eq!(error.lhs_shape(), &[3, 5]);
+    assert_eq!(error.rhs_shape(), &[5]);
+}
+
+#[test]
+fn distillation_loss() {
+    use crate::nn::loss::{distillation_loss, Reduction};
+
+    let student = crate::from_ndarray(ndarray::array![[1., 2., 3.], [1., 0., -1.]]).requires_grad();
+    let teacher = crate::from_ndarray(ndarray::array![[3., 2., 1.], [0., 0., 0.]]).requires_grad();
+    let target = crate::from_ndarray(ndarray::array![2., 0.]);
+
+    let loss = distillation_loss(
+        student.clone(),
+        teacher.detach(),
+        target,
+        2.,
+        0.25,
+        Reduction::Mean,
+    );
+    loss.forward();
+    loss.backward(1.);
+
+    assert!((loss.data()[()] - 0.5066115).abs() < 1e-6);
+    assert!(student.grad().iter().any(|grad| *grad != 0.));
+    assert!(teacher.grad().iter().all(|grad| *grad == 0.));
+}
+
+#[test]
+#[should_panic(expected = "error: 1.5 is not a valid mixing weight.")]
+fn distillation_loss_invalid_alpha() {
+    use crate::nn::loss::{distillation_loss, Reduction};
+
+    distillation_loss(
+        crate::zeros((2, 3)).requires_grad(),
+        crate::zeros((2, 3)),
+        crate::zeros(2),
+        1.,
+        1.5,
+        Reduction::Sum,
+    );
+}
+
+#[test]
+fn dynamic_shapes() {
+    use crate::nn::loss::{mse_loss, Reduction};
+
+    let build = |input: &super::Var<super::Input<ndarray::Ix2>>,
+                 target: &super::Var<super::Input<ndarray::Ix2>>| {
+        let weight =
+            crate::from_ndarray(ndarray::array![[1., -1.], [0.5, 2.], [-3., 1.]]).requires_grad();
+        let bias = crate::from_ndarray(ndarray::array![[0.1, -0.2]]).requires_grad();
+        let output = (input.clone().mm(weight.clone()) + bias.clone()).relu();
+        let concatenated = crate::cat(output.clone(), output.clone(), 1).sum();
+        let loss = mse_loss(output, target.clone(), Reduction::Mean) + concatenated;
+        (loss, weight, bias)
+    };
+
+    let input = crate::from_ndarray(
+        ndarray::Array::linspace(-1., 1., 12)
+            .into_shape((4, 3))
+            .unwrap(),
+    );
+    let target = crate::ones((4, 2));
+    let (loss, weight, bias) = build(&input, &target);
+    loss.forward();
+    loss.backward(1.);
+
+    // Feeds a batch of a different size to the same graph.
+    let batch = ndarray::Array::linspace(-2., 3., 18)
+        .into_shape((6, 3))
+        .unwrap();
+    *input.data_mut() = batch.clone();
+    *target.data_mut() = ndarray::Array::zeros((6, 2));
+    weight.grad_mut().fill(0.);
+    bias.grad_mut().fill(0.);
+    loss.forward();
+    loss.backward(1.);
+
+    let (expected, expected_weight, expected_bias) =
+        build(&crate::from_ndarray(batch), &crate::zeros((6, 2)));
+    expected.forward();
+    expected.backward(1.);
+
+    let close = |lhs: f32, rhs: f32| (lhs - rhs).abs() < 1e-4;
+    assert!(close(loss.data()[()], expected.data()[()]));
+    assert!(ndarray::Zip::from(&*weight.grad())
+        .and(&*expected_weight.grad())
+        .all(|&lhs, &rhs| close(lhs, rhs)));
+    assert!(ndarray::Zip::from(&*bias.grad())
+        .and(&*expected_bias.grad())
+        .all(|&lhs, &rhs| close(lhs, rhs)));
+}
+
+#[test]
+fn dynamic_shapes_convolution() {
+    use crate::Convolve;
+
+    let input = crate::ones((2, 2, 6, 6));
+    let kernel = crate::ones((3, 2, 2, 2)).requires_grad();
+    let output = super::Var::convolve(
+        input.clone(),
+        kernel.clone(),
+        &[1, 1],
+        &[1, 1],
+        &[1, 1],
+        crate::variable::Zero,
+    )
+    .sum();
+
+    output.forward();
+    output.backward(1.);
+    let (data, grad) = (output.data()[()], kernel.grad().clone());
+
+    // Both the output and the kernel's gradient scale linearly with the batch size.
+    *input.data_mut() = ndarray::Array::ones((5, 2, 6, 6));
+    kernel.grad_mut().fill(0.);
+    output.forward();
+    output.backward(1.);
+
+    assert_eq!(output.data()[()], data * 2.5);
+    assert_eq!(*kernel.grad(), grad * 2.5);
+}
+
+#[test]
+fn dynamic_shapes_chunks() {
+    let input = crate::from_ndarray(ndarray::array![[1., 2., 3., 4.], [5., 6., 7., 8.]]);
+    let weight = crate::ones((1, 4)).requires_grad();
+    let chunks = (input.clone() * weight.clone()).chunks((2, 2));
+    let output = (chunks[0].clone() * 2. + chunks[1].clone()).sum();
+
+    output.forward();
+    output.backward(1.);
+    assert_eq!(output.data()[()], 50.);
+
+    // The chunks follow the batch axis, the number of chunks stays the same.
+    *input.data_mut() = ndarray::array![[1., 2., 3., 4.], [5., 6., 7., 8.], [1., 1., 1., 1.]];
+    weight.grad_mut().fill(0.);
+    output.forward();
+    output.backward(1.);
+
+    assert_eq!(output.data()[()], 56.);
+    assert_eq!(
+        *chunks[1].data(),
+        ndarray::array![[3., 4.], [7., 8.], [1., 1.]]
+    );
+    assert_eq!(*weight.grad(), ndarray::array![[14., 18., 11., 13.]]);
+}
+
+#[test]
+fn dynamic_shapes_smoothing() {
+    let input = crate::from_ndarray(ndarray::array![[1., 2.], [3., 4.]]);
+    let weight = crate::ones((1, 2)).requires_grad();
+    let output = (input.clone() * weight.clone()).rolling_mean(0, 2).sum();
+
+    output.forward();
+    output.backward(1.);
+    assert_eq!(output.data()[()], 8.);
+    assert_eq!(*weight.grad(), ndarray::array![[3., 5.]]);
+
+    // The smoothed axis grows.
+    *input.data_mut() = ndarray::array![[1., 2.], [3., 4.], [5., 6.]];
+    weight.grad_mut().fill(0.);
+    output.forward();
+    output.backward(1.);
+
+    assert_eq!(output.data()[()], 17.);
+    assert_eq!(*weight.grad(), ndarray::array![[7., 10.]]);
+}
+
+#[test]
+fn dynamic_shapes_scatter() {
+    let input = crate::from_ndarray(ndarray::array![[1., 2.], [3., 4.], [5., 6.]]);
+    let indices = crate::indices(ndarray::array![1, 0, 1]);
+    let weight = crate::ones((1, 2)).requires_grad();
+    let output = (input.clone() * weight.clone())
+        .scatter_mean(0, indices.clone(), 2)
+        .sum();
+
+    output.forward();
+    output.backward(1.);
+    assert_eq!(output.data()[()], 14.);
+    assert_eq!(*weight.grad(), ndarray::array![[6., 8.]]);
+
+    // The number of segments stays the same, the length of the reduced axis follows the input.
+    *input.data_mut() = ndarray::array![[1., 2.], [3., 4.], [5., 6.], [1., 1.]];
+    *indices.data_mut() = ndarray::array![1, 0, 1, 0];
+    weight.grad_mut().fill(0.);
+    output.forward();
+    output.backward(1.);
+
+    assert_eq!(output.data()[()], 11.5);
+    assert_eq!(*weight.grad(), ndarray::array![[5., 6.5]]);
+}
+
+#[test]
+fn multiple_backward() {
+    let build = || {
+        let input = crate::from_ndarray(ndarray::array![[1., 2.], [-1., 0.5], [3., -2.]]);
+        let weight = crate::from_ndarray(ndarray::array![[0.5, -1.], [2., 1.]]).requires_grad();
+        let hidden = input.mm(weight.clone()).tanh();
+        let first = hidden.clone().sum();
+        let second = (hidden.clone() * hidden).mean();
+        (first, second, weight)
+    };
+
+    let (first, second, weight) = build();
+    first.forward();
+    second.forward();
+    first.backward(1.);
+    second.backward(1.);
+
+    let (expected_first, expected_second, expected_weight) = build();
+    let expected = expected_first + expected_second;
+    expected.forward();
+    expected.backward(1.);
+
+    let close = |lhs: &ndarray::Array2<f32>, rhs: &ndarray::Array2<f32>| {
+        ndarray::Zip::from(lhs)
+            .and(rhs)
+            .all(|lhs, rhs| (lhs - rhs).abs() < 1e-6)
+    };
+    assert!(close(&weight.grad(), &expected_weight.grad()));
+
+    // Backward passes accumulate the gradients of the leaves until they are zeroed.
+    let accumulated = weight.grad().clone();
+    first.backward(1.);
+    second.backward(1.);
+    assert!(close(&weight.grad(), &(&accumulated * 2.)));
+
+    second.zero_grad();
+    assert!(weight.grad().iter().all(|el| *el == 0.));
+}
+
+#[test]
+fn backward_with() {
+    let x = crate::from_ndarray(ndarray::array![[1., 2.], [3., 4.]]).requires_grad();
+    let w = crate::from_ndarray(ndarray::array![[1., -1.], [0., 2.]]).requires_grad();
+    let y = x.clone().mm(w.clone());
+
+    y.forward();
+    // Seeding with ones is equivalent to a plain backward pass.
+    y.backward_with(ndarray::Array::ones((2, 2)));
+    let (x_grad, w_grad) = (x.grad().clone(), w.grad().clone());
+
+    y.zero_grad();
+    y.backward(1.);
+    assert_eq!(*x.grad(), x_grad);
+    assert_eq!(*w.grad(), w_grad);
+
+    // Vector-Jacobian product selecting the first row of the output.
+    y.zero_grad();
+    y.backward_with(ndarray::array![[1., 0.], [0., 0.]]);
+    assert_eq!(*x.grad(), ndarray::array![[1., 0.], [0., 0.]]);
+    assert_eq!(*w.grad(), ndarray::array![[1., 0.], [2., 0.]]);
+}
+
+#[test]
+#[should_panic]
+fn backward_with_wrong_shape() {
+    let x = crate::ones((2, 3)).requires_grad();
+    let y = x * 2.;
+
+    y.forward();
+    y.backward_with(ndarray::Array::ones((3, 2)));
+}
+
+#[test]
+fn var_eval() {
+    let x = crate::full((3, 3), 2.);
+    let y = x.dropout(0.9);
+
+    y.eval();
+    y.forward();
+    // Dropout is disabled in evaluation mode.
+    assert_eq!(*y.data(), ndarray::Array::from_elem((3, 3), 2.));
+}
+
+#[test]
+fn into_inference() {
+    let w = crate::from_ndarray(ndarray::array![[1., -1.], [0., 2.]]).requires_grad();
+    let x = crate::from_ndarray(ndarray::array![[1., 2.], [3., 4.]]);
+    let y = x.clone().mm(w.clone()).dropout(0.9);
+
+    let y = y.into_inference();
+    y.forward();
+    // Dropout is disabled in evaluation mode.
+    assert_eq!(*y.data(), ndarray::array![[1., 3.], [3., 5.]]);
+
+    *x.data_mut() = ndarray::array![[1., 0.], [0., 1.]];
+    y.forward();
+    assert_eq!(*y.data(), *w.data());
+
+    // The gradients of the parameters can be re-allocated for training.
+    w.with_grad();
+    let loss = (x.mm(w.clone()) * 2.).sum();
+    loss.forward();
+    loss.backward(1.);
+    assert_eq!(*w.grad(), ndarray::array![[2., 2.], [2., 2.]]);
+}
+
+#[test]
+fn chunks_backward() {
+    let input = crate::from_ndarray(ndarray::array![[1., 2.], [3., 4.]]).requires_grad();
+    let mut chunks = input.clone().chunks((1, 2));
+    let (second, first) = (chunks.pop().unwrap(), chunks.pop().unwrap());
+
+    let y = (first.clone() * 2.).sum() + (second * 3.).sum();
+    y.forward();
+    y.backward(1.);
+    assert_eq!(*input.grad(), ndarray::array![[2., 2.], [3., 3.]]);
+
+    // The chunks that are not part of the graph do not contribute to the gradient.
+    let z = (first * 5.).sum();
+    z.forward();
+    input.zero_grad();
+    z.backward(1.);
+    assert_eq!(*input.grad(), ndarray::array![[5., 5.], [0., 0.]]);
+}
+
+#[test]
+fn to_indices() {
+    let input = crate::ones((2, 2));
+    let indices = input.to_indices();
+
+    assert_eq!(indices.past.len(), 1);
+    assert!(indices.past.changeables.is_empty());
+}
+
+#[test]
+fn one_hot() {
+    let labels = crate::from_ndarray(ndarray::array![2., 0.]);
+    let encoded = labels.clone().to_indices().one_hot(3);
+
+    assert_eq!(encoded.past.len(), 2);
+    encoded.forward();
+    assert_eq!(*encoded.data(), ndarray::array![[0., 0., 1.], [1., 0., 0.]]);
+
+    // The indices are recomputed from the labels at each forward pass.
+    *labels.data_mut() = ndarray::array![1., 1.];
+    encoded.forward();
+    assert_eq!(*encoded.data(), ndarray::array![[0., 1., 0.], [0., 1., 0.]]);
+}
+
+#[test]
+fn gather() {
+    let input = crate::ones((2, 2));
+    let gather = input.gather(1, crate::indices(ndarray::array![[0], [1]]));
+
+    assert_eq!(gather.past.len(), 1);
+    assert!(gather.past.changeables.is_empty());
+}
+
+#[test]
+fn gather_diff() {
+    let input = crate::ones((2, 2)).requires_grad();
+    let gather = input.gather(1, crate::indices(ndarray::array![[0], [1]]));
+
+    assert_eq!(gather.past.len(), 1);
+    assert_eq!(gather.past.parameters.len(), 1);
+}
+
+#[test]
+fn scatter_add() {
+    let input = crate::ones((3, 2));
+    let scatter = input.scatter_add(0, crate::indices(ndarray::array![1, 0, 1]), 2);
+
+    assert_eq!(scatter.past.len(), 1);
+    assert!(scatter.past.changeables.is_empty());
+}
+
+#[test]
+fn scatter_max_diff() {
+    let input = crate::from_ndarray(ndarray::array![[1., 4.], [3., 2.], [0., 5.]]).requires_grad();
+    let scatter = input
+        .clone()
+        .scatter_max(0, crate::indices(ndarray::array![0, 0, 1]), 2);
+
+    assert_eq!(scatter.past.len(), 1);
+    assert_eq!(scatter.past.parameters.len(), 1);
+
+    scatter.forward();
+    assert_eq!(*scatter.data(), ndarray::array![[3., 4.], [0., 5.]]);
+
+    scatter.backward(1.);
+    assert_eq!(*input.grad(), ndarray::array![[0., 1.], [1., 0.], [1., 1.]]);
+}
+
+#[test]
+fn embedding() {
+    let weight = crate::from_ndarray(ndarray::array![[1., 2.], [3., 4.], [5., 6.]]).requires_grad();
+    let tokens = crate::indices(ndarray::array![2, 0, 2]);
+    let y = tokens.clone().embedding(weight.clone()).sum();
+
+    y.forward();
+    assert_eq!(*y.data(), ndarray::arr0(25.));
+    y.backward(1.);
+    assert_eq!(
+        *weight.grad(),
+        ndarray::array![[1., 1.], [0., 0.], [2., 2.]]
+    );
+
+    // Feeding new indices to the leaf is enough to recompute the graph.
+    *tokens.data_mut() = ndarray::array![1, 1, 1];
+    y.forward();
+    assert_eq!(*y.data(), ndarray::arr0(21.));
+}
+
+#[test]
+fn argmax() {
+    let input = crate::ones((2, 3));
+    let argmax = input.argmax(1);
+
+    assert_eq!(argmax.past.len(), 1);
+    assert!(argmax.past.changeables.is_empty());
 }
 
 #[test]
-fn div() {
-    let lhs = crate::ones((2, 2));
-    let rhs = crate::zeros((2, 2));
-    let div = lhs / rhs;
+fn argmin_diff() {
+    let input = crate::ones((2, 3)).requires_grad();
+    let argmin = input.argmin(0);
 
-    assert_eq!(div.past.len(), 1);
-    assert!(div.past.changeables.is_empty());
+    assert_eq!(argmin.past.len(), 1);
+    assert_eq!(argmin.data().shape(), &[3]);
 }
 
 #[test]
-fn div_diff() {
-    let lhs = crate::ones((2, 2)).requires_grad();
-    let rhs = crate::zeros((2, 2));
-    let div = lhs / rhs;
+#[should_panic(expected = "error: axis 2 is out of bounds for a variable of 2 dimensions.")]
+fn argmax_out_of_bounds() {
+    crate::ones((2, 3)).argmax(2);
+}
 
-    assert_eq!(div.past.len(), 1);
-    assert_eq!(div.past.parameters.len(), 1);
+#[test]
+fn topk() {
+    let input = crate::from_ndarray(ndarray::array![[3., 1., 2.], [0., 5., 4.]]);
+    let (values, indices) = input.clone().topk(2, 1);
 
-    let lhs = crate::ones((2, 2));
-    let rhs = crate::zeros((2, 2)).requires_grad();
-    let div = lhs / rhs;
+    assert_eq!(values.past.len(), 2);
+    values.forward();
+    assert_eq!(*values.data(), ndarray::array![[3., 2.], [5., 4.]]);
+    assert_eq!(*indices.data(), ndarray::array![[0, 2], [1, 2]]);
 
-    assert_eq!(div.past.len(), 1);
-    assert_eq!(div.past.parameters.len(), 1);
+    // The selection is recomputed at each forward pass.
+    *input.data_mut() = ndarray::array![[1., 2., 3.], [6., 5., 4.]];
+    values.forward();
+    assert_eq!(*values.data(), ndarray::array![[3., 2.], [6., 5.]]);
+    assert_eq!(*indices.data(), ndarray::array![[2, 1], [0, 1]]);
+}
 
-    let lhs = crate::ones((2, 2)).requires_grad();
-    let rhs = crate::zeros((2, 2)).requires_grad();
-    let div = lhs / rhs;
+#[test]
+fn topk_diff() {
+    let input = crate::from_ndarray(ndarray::array![[3., 1., 2.], [0., 5., 4.]]).requires_grad();
+    let (values, _) = input.clone().topk(1, 1);
+    let y = values.sum();
 
-    assert_eq!(div.past.len(), 1);
-    assert_eq!(div.past.parameters.len(), 2);
+    assert_eq!(y.past.parameters.len(), 1);
+    y.forward();
+    assert_eq!(*y.data(), ndarray::arr0(8.));
+    y.backward(1.);
+    assert_eq!(*input.grad(), ndarray::array![[1., 0., 0.], [0., 1., 0.]]);
 }
 
 #[test]
-fn vv() {
-    let lhs = crate::ones(2);
-    let rhs = crate::zeros(2);
-    let vv = lhs.vv(rhs);
+fn sort() {
+    let input = crate::from_ndarray(ndarray::array![[0.3, 0.1, 0.2], [1., 3., 2.]]);
+    let (values, indices) = input.clone().sort(1);
 
-    assert_eq!(vv.past.len(), 1);
-    assert!(vv.past.changeables.is_empty());
+    assert_eq!(values.past.len(), 2);
+    values.forward();
+    assert_eq!(
+        *values.data(),
+        ndarray::array![[0.1, 0.2, 0.3], [1., 2., 3.]]
+    );
+    assert_eq!(*indices.data(), ndarray::array![[1, 2, 0], [0, 2, 1]]);
+
+    // The permutation is recomputed at each forward pass.
+    *input.data_mut() = ndarray::array![[0.1, 0.2, 0.3], [3., 2., 1.]];
+    values.forward();
+    assert_eq!(
+        *values.data(),
+        ndarray::array![[0.1, 0.2, 0.3], [1., 2., 3.]]
+    );
+    assert_eq!(*indices.data(), ndarray::array![[0, 1, 2], [2, 1, 0]]);
 }
 
 #[test]
-fn vv_diff() {
-    let lhs = crate::ones(2);
-    let rhs = crate::zeros(2).requires_grad();
-    let vv = lhs.vv(rhs);
+fn sort_diff() {
+    let input = crate::from_ndarray(ndarray::array![[3., 1., 2.], [0., 5., 4.]]).requires_grad();
+    let ranks = crate::from_ndarray(ndarray::array![1., 2., 3.]);
+    let (values, _) = input.clone().sort(1);
+    let y = (values * ranks).sum();
 
-    assert_eq!(vv.past.len(), 1);
-    assert_eq!(vv.past.parameters.len(), 1);
+    assert_eq!(y.past.parameters.len(), 1);
+    y.forward();
+    assert_eq!(*y.data(), ndarray::arr0(37.));
+    y.backward(1.);
+    assert_eq!(*input.grad(), ndarray::array![[3., 1., 2.], [1., 3., 2.]]);
+}
 
-    let lhs = crate::ones(2).requires_grad();
-    let rhs = crate::zeros(2);
-    let vv = lhs.vv(rhs);
+#[test]
+#[should_panic(expected = "error: axis 1 is out of bounds for a variable of 1 dimensions.")]
+fn argsort_out_of_bounds() {
+    crate::ones(3).argsort(1);
+}
 
-    assert_eq!(vv.past.len(), 1);
-    assert_eq!(vv.past.parameters.len(), 1);
+#[test]
+fn unique() {
+    let labels = crate::from_ndarray(ndarray::array![2., 0., 2.]);
+    let unique = labels.clone().to_indices().unique();
 
-    let lhs = crate::ones(2).requires_grad();
-    let rhs = crate::zeros(2).requires_grad();
-    let vv = lhs.vv(rhs);
+    assert_eq!(unique.past.len(), 2);
+    unique.forward();
+    assert_eq!(*unique.data(), ndarray::array![0, 2]);
 
-    assert_eq!(vv.past.len(), 1);
-    assert_eq!(vv.past.parameters.len(), 2);
+    // The length of the result follows the data.
+    *labels.data_mut() = ndarray::array![3., 1., 2.];
+    unique.forward();
+    assert_eq!(*unique.data(), ndarray::array![1, 2, 3]);
 }
 
 #[test]
-fn vm() {
-    let lhs = crate::ones(2);
-    let rhs = crate::zeros((2, 2));
-    let vm = lhs.vm(rhs);
+fn bincount() {
+    let labels = crate::indices(ndarray::array![[1, 1], [0, 3]]);
+    let counts = labels.bincount(4);
 
-    assert_eq!(vm.past.len(), 1);
-    assert!(vm.past.changeables.is_empty());
+    assert_eq!(counts.past.len(), 1);
+    counts.forward();
+    assert_eq!(*counts.data(), ndarray::array![1., 2., 0., 1.]);
 }
 
 #[test]
-fn vm_diff() {
-    let lhs = crate::ones(2);
-    let rhs = crate::zeros((2, 2)).requires_grad();
-    let vm = lhs.vm(rhs);
-
-    assert_eq!(vm.past.len(), 1);
-    assert_eq!(vm.past.parameters.len(), 1);
+fn weighted_bincount_diff() {
+    let weights = crate::from_ndarray(ndarray::array![[1., 2.], [3., 4.]]).requires_grad();
+    let labels = crate::indices(ndarray::array![[1, 1], [0, 1]]);
+    let y =
+        (weights.clone().bincount(labels, 2) * crate::from_ndarray(ndarray::array![-1., 2.])).sum();
 
-    let lhs = crate::ones(2).requires_grad();
-    let rhs = crate::zeros((2, 2));
-    let vm = lhs.vm(rhs);
+    assert_eq!(y.past.parameters.len(), 1);
+    y.forward();
+    assert_eq!(*y.data(), ndarray::arr0(11.));
+    y.backward(1.);
+    assert_eq!(*weights.grad(), ndarray::array![[2., 2.], [-1., 2.]]);
+}
 
-    assert_eq!(vm.past.len(), 1);
-    assert_eq!(vm.past.parameters.len(), 1);
+#[test]
+fn set_data() {
+    let input = crate::zeros((2, 2));
+    let output = input.clone().sum();
 
-    let lhs = crate::ones(2).requires_grad();
-    let rhs = crate::zeros((2, 2)).requires_grad();
-    let vm = lhs.vm(rhs);
+    input.set_data(ndarray::array![[1., 2.], [3., 4.]].view());
+    output.forward();
+    assert_eq!(*output.data(), ndarray::arr0(10.));
+}
 
-    assert_eq!(vm.past.len(), 1);
-    assert_eq!(vm.past.parameters.len(), 2);
+#[test]
+#[should_panic(expected = "error: cannot set data of shape [3] into a variable of shape [2].")]
+fn set_data_fail() {
+    crate::zeros(2).set_data(ndarray::array![1., 2., 3.]);
 }
 
 #[test]
-fn mv() {
-    let lhs = crate::zeros((2, 2));
-    let rhs = crate::ones(2);
-    let mv = lhs.mv(rhs);
+fn set_data_diff() {
+    let w = crate::zeros(2).requires_grad();
+    let y = w.clone().sum();
 
-    assert_eq!(mv.past.len(), 1);
-    assert!(mv.past.changeables.is_empty());
+    w.set_data(ndarray::array![1., 2.]);
+    // The parameters still refer to the data of the leaf.
+    assert_eq!(y.parameters()[0].data, ndarray::array![1., 2.].into_dyn());
 }
 
 #[test]
-fn mv_diff() {
-    let lhs = crate::zeros((2, 2)).requires_grad();
-    let rhs = crate::ones(2);
-    let mv = lhs.mv(rhs);
+fn reset() {
+    let input = crate::zeros(2);
+    let shared = input.clone() + 1.;
+    let (first, second) = (shared.clone() * 2., shared.clone() * 3.);
 
-    assert_eq!(mv.past.len(), 1);
-    assert_eq!(mv.past.parameters.len(), 1);
+    first.forward();
+    input.set_data(ndarray::array![1., 1.]);
 
-    let lhs = crate::zeros((2, 2));
-    let rhs = crate::ones(2).requires_grad();
-    let mv = lhs.mv(rhs);
+    second.forward();
+    assert_eq!(*second.data(), ndarray::array![3., 3.]);
 
-    assert_eq!(mv.past.len(), 1);
-    assert_eq!(mv.past.parameters.len(), 1);
+    second.reset();
+    second.forward();
+    assert_eq!(*second.data(), ndarray::array![6., 6.]);
+}
 
-    let lhs = crate::zeros((2, 2)).requires_grad();
-    let rhs = crate::ones(2).requires_grad();
-    let mv = lhs.mv(rhs);
+#[test]
+fn graph() {
+    let x = crate::from_ndarray(ndarray::array![1., 2.]);
+    let w = crate::from_ndarray(ndarray::array![3., 4.]).requires_grad();
+    let graph = (x.clone() * w.clone()).exp().sum().into_graph();
 
-    assert_eq!(mv.past.len(), 1);
-    assert_eq!(mv.past.parameters.len(), 2);
+    assert_eq!(graph.node_count(), 5);
+    graph.forward();
+    graph.backward(1.);
+    graph.backward(1.);
+    assert_eq!(
+        *w.grad(),
+        ndarray::array![2. * 3_f32.exp(), 4. * 8_f32.exp()]
+    );
+
+    graph.zero_grad();
+    assert_eq!(*w.grad(), ndarray::array![0., 0.]);
 }
 
 #[test]
-fn mm() {
-    let lhs = crate::zeros((2, 2));
-    let rhs = crate::ones((2, 2));
-    let mm = lhs.mm(rhs);
+fn graph_non_differentiable() {
+    let x = crate::zeros(2);
+    let y = (x.clone() + 1.).sum();
+    let graph = y.clone().into_graph();
 
-    assert_eq!(mm.past.len(), 1);
-    assert!(mm.past.changeables.is_empty());
+    x.set_data(ndarray::array![1., 2.]);
+    graph.forward();
+    assert_eq!(*y.data(), ndarray::arr0(5.));
+    graph.zero_grad();
 }
 
 #[test]
-fn mm_diff() {
-    let lhs = crate::zeros((2, 2)).requires_grad();
-    let rhs = crate::ones((2, 2));
-    let mm = lhs.mm(rhs);
+#[should_panic(expected = "error: cannot back-propagate through a non differentiable graph.")]
+fn graph_non_differentiable_backward() {
+    crate::zeros(2).sum().into_graph().backward(1.);
+}
 
-    assert_eq!(mm.past.len(), 1);
-    assert_eq!(mm.past.parameters.len(), 1);
+#[test]
+fn graph_join() {
+    let x = crate::zeros(2);
+    let w = crate::from_ndarray(ndarray::array![1., 2.]).requires_grad();
+    let shared = (x.clone() * w.clone()).exp();
+    let first = shared.clone().sum();
+    let second = (shared * 2.).sum();
+    let third = (x.clone() + 1.).sum();
 
-    let lhs = crate::zeros((2, 2));
-    let rhs = crate::ones((2, 2)).requires_grad();
-    let mm = lhs.mm(rhs);
+    let graph = crate::Graph::join(vec![
+        first.clone().into_graph(),
+        second.clone().into_graph(),
+        third.clone().into_graph(),
+    ]);
+    assert_eq!(graph.node_count(), 9);
 
-    assert_eq!(mm.past.len(), 1);
-    assert_eq!(mm.past.parameters.len(), 1);
+    x.set_data(ndarray::array![1., 1.]);
+    graph.forward();
+    assert_eq!(*first.data(), ndarray::arr0(1_f32.exp() + 2_f32.exp()));
+    assert_eq!(
+        *second.data(),
+        ndarray::arr0(2. * (1_f32.exp() + 2_f32.exp()))
+    );
+    assert_eq!(*third.data(), ndarray::arr0(4.));
 
-    let lhs = crate::zeros((2, 2)).requires_grad();
-    let rhs = crate::ones((2, 2)).requires_grad();
-    let mm = lhs.mm(rhs);
+    graph.backward(1.);
+    assert_eq!(
+        *w.grad(),
+        ndarray::array![3. * 1_f32.exp(), 3. * 2_f32.exp()]
+    );
 
-    assert_eq!(mm.past.len(), 1);
-    assert_eq!(mm.past.parameters.len(), 2);
+    graph.zero_grad();
+    assert_eq!(*w.grad(), ndarray::array![0., 0.]);
 }
 
 #[test]
-fn mm_t() {
-    let lhs = crate::zeros((2, 2));
-    let rhs = crate::ones((2, 2));
-    let mm_t = lhs.mm_t(rhs);
+fn graph_join_dependent_outputs() {
+    let w = crate::from_ndarray(ndarray::array![1., 2.]).requires_grad();
+    let first = (w.clone() * 2.).sum();
+    let second = first.clone() * 3.;
 
-    assert_eq!(mm_t.past.len(), 1);
-    assert!(mm_t.past.changeables.is_empty());
+    let graph = crate::Graph::join(vec![first.into_graph(), second.into_graph()]);
+    graph.forward();
+    graph.backward(1.);
+    assert_eq!(*w.grad(), ndarray::array![8., 8.]);
 }
 
 #[test]
-fn mm_t_diff() {
-    let lhs = crate::zeros((2, 2)).requires_grad();
-    let rhs = crate::ones((2, 2));
-    let mm_t = lhs.mm_t(rhs);
-
-    assert_eq!(mm_t.past.len(), 1);
-    assert_eq!(mm_t.past.parameters.len(), 1);
+#[should_panic(expected = "error: cannot back-propagate through a non differentiable graph.")]
+fn graph_join_non_differentiable_backward() {
+    let graph = crate::Graph::join(vec![
+        crate::zeros(2).sum().into_graph(),
+        crate::ones(2).sum().into_graph(),
+    ]);
+    graph.backward(1.);
+}
 
-    let lhs = crate::zeros((2, 2));
-    let rhs = crate::ones((2, 2)).requires_grad();
-    let mm_t = lhs.mm_t(rhs);
+#[test]
+fn reference_operators() {
+    let x = crate::from_ndarray(ndarray::array![1., 2.]);
+    let w = crate::from_ndarray(ndarray::array![3., 4.]).requires_grad();
 
-    assert_eq!(mm_t.past.len(), 1);
-    assert_eq!(mm_t.past.parameters.len(), 1);
+    let a = &x + &x;
+    let b = 1. - &x;
+    let c = &x * &w;
+    let d = &w / 2. - &x;
+    let e = -&w + 1. / &w;
+    let y = (c + d + e).sum();
 
-    let lhs = crate::zeros((2, 2)).requires_grad();
-    let rhs = crate::ones((2, 2)).requires_grad();
-    let mm_t = lhs.mm_t(rhs);
+    a.forward();
+    b.forward();
+    y.forward();
+    assert_eq!(*a.data(), ndarray::array![2., 4.]);
+    assert_eq!(*b.data(), ndarray::array![0., -1.]);
 
-    assert_eq!(mm_t.past.len(), 1);
-    assert_eq!(mm_t.past.parameters.len(), 2);
+    y.backward(1.);
+    let expected = ndarray::array![1. + 0.5 - 1. - 1. / 9., 2. + 0.5 - 1. - 1. / 16.];
+    assert!(w
+        .grad()
+        .iter()
+        .zip(expected.iter())
+        .all(|(grad, expected)| (grad - expected).abs() < 1e-6));
 }
 
 #[test]
-fn convolve() {
-    use crate::Convolve;
+fn scalar_operators() {
+    let x = crate::from_ndarray(ndarray::array![1., 4., 9.]).requires_grad();
+    let y = ((x.clone() + 1.) * 2. - 1.) / 4. + (3. - x.clone()) + crate::pow(x.clone(), 0.5);
+    assert_eq!(y.past.len(), 9);
 
-    let kernel = crate::zeros((2, 2, 2, 2));
-    let input = crate::ones((4, 2, 6, 6));
-    let convolve = super::Var::convolve(
-        input,
-        kernel,
-        &[1, 1],
-        &[1, 1],
-        &[0, 0],
-        crate::variable::Zero,
-    );
+    y.forward();
+    assert_eq!(*y.data(), ndarray::array![3.75, 3.25, 1.75]);
 
-    assert_eq!(convolve.past.len(), 1);
-    assert!(convolve.past.changeables.is_empty());
+    y.backward(1.);
+    let expected = ndarray::array![0., -0.25, -1. / 3.];
+    assert!(x
+        .grad()
+        .iter()
+        .zip(expected.iter())
+        .all(|(grad, expected)| (grad - expected).abs() < 1e-6));
+
+    let z = 2. / crate::from_ndarray(ndarray::array![1., 4.]);
+    z.forward();
+    assert_eq!(*z.data(), ndarray::array![2., 0.5]);
 }
 
 #[test]
-fn convolve_diff() {
-    use crate::Convolve;
-
-    let kernel = crate::zeros((2, 2, 2, 2)).requires_grad();
-    let input = crate::ones((4, 2, 6, 6));
-    let convolve = super::Var::convolve(
-        input,
-        kernel,
-        &[1, 1],
-        &[1, 1],
-        &[0, 0],
-        crate::variable::Zero,
-    );
-
-    assert_eq!(convolve.past.len(), 1);
-    assert_eq!(convolve.past.parameters.len(), 1);
+fn item() {
+    let x = crate::from_ndarray(ndarray::array![[1., 2.], [3., 4.]]).requires_grad();
+    let loss = x.clone().sum();
+    loss.forward();
 
-    let kernel = crate::zeros((2, 2, 2, 2)).requires_grad();
-    let input = crate::ones((4, 2, 6, 6)).requires_grad();
-    let convolve = super::VarDiff::convolve(
-        input,
-        kernel,
-        &[1, 1],
-        &[1, 1],
-        &[0, 0],
-        crate::variable::Zero,
-    );
+    assert_eq!(loss.item(), 10.);
+    assert_eq!(crate::full((1, 1), 2.).item(), 2.);
+    assert_eq!(x.to_vec(), vec![1., 2., 3., 4.]);
 
-    assert_eq!(convolve.past.len(), 1);
-    assert_eq!(convolve.past.parameters.len(), 2);
+    let transposed = x.clone().t();
+    transposed.forward();
+    assert_eq!(transposed.to_vec(), vec![1., 3., 2., 4.]);
 }
 
 #[test]
-fn convolve_groups() {
-    use crate::ConvolveWithGroups;
+#[should_panic(expected = "error: cannot convert a variable of shape [2] to a scalar.")]
+fn item_fail() {
+    crate::ones(2).item();
+}
 
-    let kernel = crate::zeros((2, 2, 2, 2));
-    let input = crate::ones((4, 2, 6, 6));
-    let convolve = super::Var::convolve_with_groups(
-        input,
-        kernel,
-        &[1, 1],
-        &[1, 1],
-        &[0, 0],
-        crate::variable::Zero,
-        2,
-    );
+#[test]
+fn display() {
+    let x = crate::from_ndarray(ndarray::array![[1., 2.], [3., 4.]] / 3.).requires_grad();
 
-    assert_eq!(convolve.past.len(), 1);
-    assert!(convolve.past.changeables.is_empty());
+    assert_eq!(format!("{}", x), format!("{}", x.data()));
+    assert_eq!(format!("{:.1}", x), "[[0.3, 0.7],\n [1.0, 1.3]]");
 }
 
 #[test]
-fn convolve_groups_diff() {
-    use crate::ConvolveWithGroups;
-
-    let kernel = crate::zeros((2, 2, 2, 2)).requires_grad();
-    let input = crate::ones((4, 2, 6, 6));
-    let convolve = super::Var::convolve_with_groups(
-        input,
-        kernel,
-        &[1, 1],
-        &[1, 1],
-        &[0, 0],
-        crate::variable::Zero,
-        2,
-    );
+fn smoothing() {
+    // A batch of two series of four steps.
+    let input =
+        crate::from_ndarray(ndarray::array![[2., 4., 6., 8.], [1., 1., 5., 5.]]).requires_grad();
 
-    assert_eq!(convolve.past.len(), 1);
-    assert_eq!(convolve.past.parameters.len(), 1);
+    let ema = input.clone().ema(1, 0.5);
+    let rolling = input.clone().rolling_mean(1, 2);
+    assert_eq!(ema.past.len(), 1);
+    let y = (ema.clone() + rolling.clone()).sum();
+    y.forward();
+    y.backward(1.);
 
-    let kernel = crate::zeros((2, 2, 2, 2)).requires_grad();
-    let input = crate::ones((4, 2, 6, 6)).requires_grad();
-    let convolve = super::VarDiff::convolve_with_groups(
-        input,
-        kernel,
-        &[1, 1],
-        &[1, 1],
-        &[0, 0],
-        crate::variable::Zero,
-        2,
+    assert_eq!(
+        *ema.data(),
+        ndarray::array![[2., 3., 4.5, 6.25], [1., 1., 3., 4.]]
+    );
+    assert_eq!(
+        *rolling.data(),
+        ndarray::array![[2., 3., 5., 7.], [1., 1., 3., 5.]]
+    );
+    // The gradients of both averages sum to the number of steps along each series.
+    assert!((input.grad().sum() - 16.).abs() < 1e-6);
+
+    // The non-differentiable versions give the same results.
+    let ema = input.detach().ema(1, 0.5);
+    ema.forward();
+    assert_eq!(
+        *ema.data(),
+        ndarray::array![[2., 3., 4.5, 6.25], [1., 1., 3., 4.]]
     );
+}
 
-    assert_eq!(convolve.past.len(), 1);
-    assert_eq!(convolve.past.parameters.len(), 2);
+#[test]
+fn sampled_losses() {
+    use crate::nn::loss::{
+        negative_sampling_loss, sampled_softmax_loss, NegativeSampler, Reduction,
+    };
+
+    let hidden = crate::rand((2, 4)).requires_grad();
+    let weight = crate::rand((50, 4)).requires_grad();
+    let target = crate::indices(ndarray::array![3, 41]);
+
+    // The same seed draws the same negatives.
+    let losses: Vec<f32> = (0..2)
+        .map(|_| {
+            let sampler = NegativeSampler::uniform(50, 3).with_seed(7);
+            let loss = sampled_softmax_loss(
+                hidden.clone(),
+                weight.clone(),
+                target.clone(),
+                &sampler,
+                Reduction::Mean,
+            );
+            loss.forward();
+            loss.item()
+        })
+        .collect();
+    assert_eq!(losses[0], losses[1]);
+
+    // Only the rows of the targets and of the negatives receive a gradient.
+    let sampler = NegativeSampler::uniform(50, 3).with_seed(7);
+    let loss = negative_sampling_loss(hidden, weight.clone(), target, &sampler, Reduction::Sum);
+    loss.forward();
+    loss.backward(1.);
+    let touched = weight
+        .grad()
+        .rows()
+        .into_iter()
+        .filter(|row| row.iter().any(|grad| *grad != 0.))
+        .count();
+    assert!((2..=8).contains(&touched));
+    assert_ne!(weight.grad().row(3), ndarray::Array::zeros(4));
+    assert_ne!(weight.grad().row(41), ndarray::Array::zeros(4));
 }
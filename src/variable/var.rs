@@ -1,17 +1,25 @@
 use super::{
-    Addition, AdditionBackwardUnary, Cat, Changeable, Chunk, Concatenate, ConcatenateBackwardRight,
-    Data, Division, DivisionBackwardRight, Dropout, Eval, Exp, Forward, Gradient, Input,
-    InputBackward, LeakyReLU, LogSoftmax, Logn, MatMatMul, MatMatMulT, MatVecMul, MatrixMatrixMul,
+    check_mm, check_mv, check_vm, check_vv, AddScalar, Addition, AdditionBackwardUnary, ArcCos,
+    ArcSin, ArcTan, ArcTanH, ArgMax, ArgMin, ArgSort, AvgPool, BatchNorm, Cat, Ceil, Changeable,
+    Chunk, Comparator, Comparison, Concatenate, ConcatenateBackwardRight, Cos, CosH, Data, Digamma,
+    Division, DivisionBackwardRight, Dropout, DropoutMask, Ema, Erf, Erfc, Eval, Exp, Expm1,
+    Flatten, Floor, Forward, ForwardHook, Gather, Gradient, Graph, IndexData, IndexVar, Input,
+    InputBackward, LeakyReLU, Log1p, LogGamma, LogSoftmax, Logn, MaskedFill, MaskedMaxPool,
+    MaskedMeanPool, MatMatMul, MatMatMulT, MatVecMul, MatrixMatrixMul,
     MatrixMatrixMulBackwardRight, MatrixMatrixMulT, MatrixMatrixMulTBackwardRight, MatrixVectorMul,
-    MatrixVectorMulBackwardRight, Mean, MultiConcatenate, MultiStack, Multiplication,
-    MultiplicationBackwardUnary, Negation, Overwrite, Power, RawParam, ReLU, Sigmoid, SoftPlus,
-    Softmax, Sqrt, Stack, StackBackwardRight, Subtraction, SubtractionBackwardRight, Sum, TanH,
-    Tensor, Transpose, Unsqueeze, VarDiff, VarDiffHistory, VarHistory, VecMatMul, VecVecMul,
-    VectorMatrixMul, VectorMatrixMulBackwardRight, VectorVectorMul, VectorVectorMulBackwardUnary,
-    OPERATIONS_COUNTER,
+    MatrixVectorMulBackwardRight, MaxPool, Maximum, MaximumBackwardUnary, Mean, Minimum,
+    MinimumBackwardUnary, MulScalar, MultiConcatenate, MultiStack, Multiplication,
+    MultiplicationBackwardUnary, Negation, Norm, Output, Overwrite, ParamSet, Pow, PowScalar,
+    Power, RawParam, ReLU, Reciprocal, Renorm, RollingMean, Round, Rsqrt, Scatter,
+    SegmentReduction, ShapeError, Shaped, Sigmoid, Sign, Sin, SinH, SoftPlus, Softmax, Sqrt, Stack,
+    StackBackwardRight, Subtraction, SubtractionBackwardRight, Sum, Tan, TanH, Tensor, TensorPower,
+    TensorPowerBackwardRight, ToIndices, TopK, Transpose, Unsqueeze, VarDiff, VarDiffHistory,
+    VarHistory, VecMatMul, VecVecMul, VectorMatrixMul, VectorMatrixMulBackwardRight,
+    VectorVectorMul, VectorVectorMulBackwardUnary, WeightedBinCount, GLU, OPERATIONS_COUNTER,
 };
 use ndarray::{
-    concatenate, stack, Axis, DimMax, Dimension, IntoDimension, Ix0, Ix1, Ix2, RemoveAxis,
+    concatenate, stack, ArrayBase, Axis, DimMax, Dimension, IntoDimension, Ix0, Ix1, Ix2, Ix3, Ix4,
+    RemoveAxis,
 };
 #[cfg(feature = "serialize")]
 use serde::{
@@ -19,8 +27,7 @@ use serde::{
     ser::{Serialize, Serializer},
 };
 use std::{
-    cell::{Cell, Ref, RefMut},
-    collections::HashSet,
+    cell::{Cell, Ref, RefCell, RefMut},
     fmt::{Debug, Display},
     ops::{Add, Div, Mul, Neg, Sub},
     rc::Rc,
@@ -80,11 +87,12 @@ impl<D: Dimension> Var<Input<D>> {
     ///
     /// let x_diff = x.requires_grad();
     ///```
-    pub fn requires_grad(self) -> VarDiff<Input<D>, InputBackward<D>> {
+    pub fn requires_grad(mut self) -> VarDiff<Input<D>, InputBackward<D>> {
         debug_assert!(self.past.is_empty(), "error: the variable is not a leaf.");
+        self.past.set_requires_grad();
         let node = Rc::new(self.node.differentiable());
         let mut gradient = node.gradient_mut();
-        let mut parameters = HashSet::new();
+        let mut parameters = ParamSet::default();
         parameters.insert(RawParam::new(
             self.node.data_mut().as_mut_ptr(),
             gradient.as_mut_ptr(),
@@ -97,13 +105,52 @@ impl<D: Dimension> Var<Input<D>> {
             past: VarDiffHistory::new(parameters),
         }
     }
+
+    /// Copies `data` into `self`, so that the graph built on top of it can be evaluated on new
+    /// inputs without being rebuilt.
+    ///
+    /// The data is copied in place, thus no memory is allocated. To feed data of a different
+    /// shape, such as a smaller last batch, assign a new array through
+    /// [`.data_mut()`](Var::data_mut()) instead.
+    ///
+    /// # Panics
+    ///
+    /// If the shape of `data` differs from the one of `self`.
+    ///
+    /// ```
+    /// use ndarray::array;
+    ///
+    /// let input = neuronika::zeros(2);
+    /// let output = input.clone() * 2.;
+    ///
+    /// input.set_data(array![1., 2.]);
+    /// output.forward();
+    /// assert_eq!(*output.data(), array![2., 4.]);
+    ///
+    /// input.set_data(array![3., 4.]);
+    /// output.forward();
+    /// assert_eq!(*output.data(), array![6., 8.]);
+    /// ```
+    pub fn set_data<S: ndarray::Data<Elem = f32>>(&self, data: ArrayBase<S, D>) {
+        let mut current = self.node.data_mut();
+        assert_eq!(
+            current.shape(),
+            data.shape(),
+            "error: cannot set data of shape {:?} into a variable of shape {:?}.",
+            data.shape(),
+            current.shape()
+        );
+        current.assign(&data);
+    }
 }
 
 impl<T: Data + Forward> Var<T> {
     /// Creates a new variable from a node.
     pub(crate) fn from(node: T, mut past: VarHistory) -> Self {
+        let id = unsafe { OPERATIONS_COUNTER.next() };
         let node = Rc::new(node);
-        past.append_forward(unsafe { OPERATIONS_COUNTER.next() }, node.clone());
+        past.append_node(id, &node);
+        past.append_forward(id, node.clone());
 
         Var { node, past }
     }
@@ -115,8 +162,9 @@ where
 {
     /// Creates a new variable from a changeable node.
     pub(crate) fn from_changeable(node: T, mut past: VarHistory) -> Self {
-        let node = Rc::new(node);
         let id = unsafe { OPERATIONS_COUNTER.next() };
+        let node = Rc::new(node);
+        past.append_node(id, &node);
         past.append_forward(id, node.clone());
         past.append_changeable(Changeable {
             id,
@@ -148,38 +196,51 @@ where
 {
     /// Propagates the computations forwards and populates all the variables from the leaves of the
     /// graph to `self`.
+    ///
+    /// The shapes of the nodes are inferred again at each evaluation, so that a graph can be fed
+    /// inputs of different shapes, such as batches of different sizes, without being rebuilt. This
+    /// holds for non-differentiable leaves only, the shape of the parameters cannot change.
+    ///
+    /// ```
+    /// let input = neuronika::ones((4, 3));
+    /// let weight = neuronika::ones((3, 2)).requires_grad();
+    /// let output = input.clone().mm(weight);
+    ///
+    /// output.forward();
+    /// assert_eq!(output.data().shape(), &[4, 2]);
+    ///
+    /// *input.data_mut() = ndarray::Array::ones((7, 3));
+    /// output.forward();
+    /// assert_eq!(output.data().shape(), &[7, 2]);
+    /// ```
     pub fn forward(&self) {
-        if self.node.was_computed() {
-            // If the user has already called `.forward()` on this var,
-            // then he wants to recompute it.
-            assert_eq!(self.past.len(), self.past.buffer().len());
-            for node in self.past.buffer().iter() {
-                node.reset_computation();
-            }
-        }
-
-        self.past.prepare_buffer();
-
-        let buffer = self.past.buffer();
-        let mut res = buffer.binary_search_by(|n| {
-            if n.was_computed() {
-                std::cmp::Ordering::Less
-            } else {
-                std::cmp::Ordering::Greater
-            }
-        });
-
-        if let Err(i) = res {
-            if buffer.get(i).is_some() {
-                res = Ok(i);
-            }
-        };
+        self.past.forward(self.node.was_computed());
+    }
 
-        if let Ok(pos) = res {
-            for node in &buffer[pos..] {
-                node.forward();
-            }
-        }
+    /// Marks all the nodes from the leaves of the graph to `self` as not computed, so that the
+    /// next call to [`.forward()`](Var::forward()) evaluates all of them again.
+    ///
+    /// A call to `.forward()` already evaluates the whole graph again if `self` was computed.
+    /// This is needed when new data is fed to a graph some of whose nodes were computed through
+    /// other variables, as in the following example.
+    ///
+    /// ```
+    /// use ndarray::array;
+    ///
+    /// let input = neuronika::zeros(2);
+    /// let shared = input.clone().exp();
+    /// let (first, second) = (shared.clone() * 2., shared + 1.);
+    ///
+    /// first.forward();
+    /// input.set_data(array![1., 1.]);
+    ///
+    /// // `shared` was computed by `first`, it must be evaluated again.
+    /// second.reset();
+    /// second.forward();
+    /// assert_eq!(*second.data(), array![1f32.exp() + 1., 1f32.exp() + 1.]);
+    /// ```
+    pub fn reset(&self) {
+        self.past.reset();
     }
 
     /// This has effect only on certain **ancestor** variables of `self`. It sets such variables
@@ -209,9 +270,72 @@ where
     pub fn eval(&self) {
         for changeable in &self.past.changeables {
             let Changeable { id: _, node } = changeable;
-            node.train();
+            node.eval();
         }
     }
+
+    /// Registers a forward hook on `self` and returns the hooked variable.
+    ///
+    /// The hook is called with the data of `self` every time it is computed by a forward pass
+    /// issued on the returned variable or on any of its descendants. This is useful to inspect
+    /// intermediate results, such as the activations of a layer, without modifying the model.
+    ///
+    /// # Arguments
+    ///
+    /// `hook` - function to be called with the data of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{cell::Cell, rc::Rc};
+    ///
+    /// let seen = Rc::new(Cell::new(0.));
+    /// let hooked = {
+    ///     let seen = seen.clone();
+    ///     neuronika::ones(3).register_forward_hook(move |data| seen.set(data.sum()))
+    /// };
+    ///
+    /// let y = hooked * 2.;
+    /// y.forward();
+    /// assert_eq!(seen.get(), 3.);
+    /// ```
+    pub fn register_forward_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&Tensor<T::Dim>) + 'static,
+    {
+        let node = Rc::new(ForwardHook::new(self.node.clone(), Box::new(hook)));
+        self.past
+            .append_forward(unsafe { OPERATIONS_COUNTER.next() }, node);
+
+        self
+    }
+
+    /// Renders the computational graph of `self` in the
+    /// [Graphviz DOT language](https://graphviz.org/doc/info/lang.html).
+    ///
+    /// Each node is labeled with its type and the shape of its data, differentiable nodes are
+    /// filled. The output can be rendered, for instance, with `dot -Tsvg`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let x = neuronika::ones((2, 3));
+    /// let y = x.clone() + x.exp();
+    ///
+    /// let dot = y.to_dot();
+    /// assert!(dot.starts_with("digraph {"));
+    /// assert!(dot.contains("Exp [2, 3]"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        self.past.to_dot()
+    }
+
+    /// Turns `self` into a handle to its whole computational graph.
+    ///
+    /// See also [`Graph`].
+    pub fn into_graph(self) -> Graph {
+        Graph::new(self.past, None, Vec::new())
+    }
 }
 
 impl<T: ?Sized> Var<T>
@@ -238,6 +362,34 @@ where
     {
         VecVecMul::vv(self, rhs)
     }
+
+    /// Checked version of [`.vm()`](Self::vm()).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ShapeError`] if the length of `self` differs from the number of rows of `rhs`.
+    pub fn try_vm<Rhs>(self, rhs: Rhs) -> Result<<Self as VecMatMul<Rhs>>::Output, ShapeError>
+    where
+        Self: VecMatMul<Rhs>,
+        Rhs: Shaped,
+    {
+        check_vm(&self.shape(), &rhs.shape())?;
+        Ok(VecMatMul::vm(self, rhs))
+    }
+
+    /// Checked version of [`.vv()`](Self::vv()).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ShapeError`] if the lengths of `self` and `rhs` differ.
+    pub fn try_vv<Rhs>(self, rhs: Rhs) -> Result<<Self as VecVecMul<Rhs>>::Output, ShapeError>
+    where
+        Self: VecVecMul<Rhs>,
+        Rhs: Shaped,
+    {
+        check_vv(&self.shape(), &rhs.shape())?;
+        Ok(VecVecMul::vv(self, rhs))
+    }
 }
 
 impl<T: Data<Dim = Ix2> + 'static> Var<T> {
@@ -273,14 +425,60 @@ impl<T: Data<Dim = Ix2> + 'static> Var<T> {
     {
         MatVecMul::mv(self, rhs)
     }
+
+    /// Checked version of [`.mm()`](Self::mm()).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ShapeError`] if the number of columns of `self` differs from the number of
+    /// rows of `rhs`.
+    pub fn try_mm<Rhs>(self, rhs: Rhs) -> Result<<Self as MatMatMul<Rhs>>::Output, ShapeError>
+    where
+        Self: MatMatMul<Rhs>,
+        Rhs: Shaped,
+    {
+        check_mm(&self.shape(), &rhs.shape(), false)?;
+        Ok(MatMatMul::mm(self, rhs))
+    }
+
+    /// Checked version of [`.mm_t()`](Self::mm_t()).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ShapeError`] if the number of columns of `self` differs from the number of
+    /// columns of `rhs`.
+    pub fn try_mm_t<Rhs>(self, rhs: Rhs) -> Result<<Self as MatMatMulT<Rhs>>::Output, ShapeError>
+    where
+        Self: MatMatMulT<Rhs>,
+        Rhs: Shaped,
+    {
+        check_mm(&self.shape(), &rhs.shape(), true)?;
+        Ok(MatMatMulT::mm_t(self, rhs))
+    }
+
+    /// Checked version of [`.mv()`](Self::mv()).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ShapeError`] if the number of columns of `self` differs from the length of
+    /// `rhs`.
+    pub fn try_mv<Rhs>(self, rhs: Rhs) -> Result<<Self as MatVecMul<Rhs>>::Output, ShapeError>
+    where
+        Self: MatVecMul<Rhs>,
+        Rhs: Shaped,
+    {
+        check_mv(&self.shape(), &rhs.shape())?;
+        Ok(MatVecMul::mv(self, rhs))
+    }
 }
 
 impl<T: Data + 'static> Var<T> {
     pub(crate) fn new(node: T) -> Self {
-        Self {
-            node: Rc::new(node),
-            past: VarHistory::new(),
-        }
+        let node = Rc::new(node);
+        let mut past = VarHistory::new();
+        past.append_node(unsafe { OPERATIONS_COUNTER.next() }, &node);
+
+        Self { node, past }
     }
 }
 
@@ -304,6 +502,34 @@ where
         self.node.data_mut()
     }
 
+    /// Returns the only element of the data inside `self`, such as the value of a loss or of a
+    /// metric computed with [`.forward()`](Var::forward()).
+    ///
+    /// ```
+    /// let x = neuronika::from_ndarray(ndarray::array![1., 2., 3.]);
+    /// let y = x.sum();
+    /// y.forward();
+    /// assert_eq!(y.item(), 6.);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the data doesn't have exactly one element.
+    pub fn item(&self) -> f32 {
+        let data = self.data();
+        assert!(
+            data.len() == 1,
+            "error: cannot convert a variable of shape {:?} to a scalar.",
+            data.shape()
+        );
+        data.iter().copied().next().unwrap()
+    }
+
+    /// Returns the elements of the data inside `self` in row-major order.
+    pub fn to_vec(&self) -> Vec<f32> {
+        self.data().iter().copied().collect()
+    }
+
     /// Returns the sum of all elements in `self`.
     pub fn sum(self) -> Var<Sum<T>> {
         Var::from(Sum::new(self.node), self.past)
@@ -314,6 +540,94 @@ where
         Var::from(Mean::new(self.node), self.past)
     }
 
+    /// Computes the `p`-norm of all the elements of `self` and returns a scalar variable with the
+    /// result.
+    ///
+    /// *norm(x) = (Σ |xᵢ|ᵖ)^(1/p)*
+    ///
+    /// With `p` equal to 2 this is the Euclidean norm of a vector and the Frobenius norm of a
+    /// matrix. Norms along a single axis are computed by [`.norm_axis()`](Var::norm_axis()).
+    ///
+    /// ```
+    /// let x = neuronika::from_ndarray(ndarray::array![[3., 0.], [0., -4.]]);
+    ///
+    /// let l1 = x.clone().norm(1.);
+    /// let frobenius = x.norm(2.);
+    /// l1.forward();
+    /// frobenius.forward();
+    /// assert_eq!(l1.data()[()], 7.);
+    /// assert_eq!(frobenius.data()[()], 5.);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `p` is smaller than 1.
+    pub fn norm(self, p: f32) -> Var<Norm<T, Ix0>> {
+        Var::from(Norm::new(self.node, p, None, false), self.past)
+    }
+
+    /// Computes the `p`-norms of the lanes of `self` along `axis` and returns a variable with the
+    /// result.
+    ///
+    /// The result has the shape of `self` with `axis` removed or, if `keepdim` is `true`, kept
+    /// with length 1 so that the norms broadcast against `self`. The dimensionality `D` of the
+    /// result must agree with `keepdim`.
+    ///
+    /// ```
+    /// use ndarray::{Ix1, Ix2};
+    ///
+    /// let x = neuronika::from_ndarray(ndarray::array![[3., 4.], [0., -2.]]);
+    ///
+    /// let norms = x.clone().norm_axis::<Ix1>(2., 1, false);
+    /// norms.forward();
+    /// assert_eq!(*norms.data(), ndarray::array![5., 2.]);
+    ///
+    /// let normalized = x.clone() / x.norm_axis::<Ix2>(2., 1, true);
+    /// normalized.forward();
+    /// assert_eq!(*normalized.data(), ndarray::array![[0.6, 0.8], [0., -1.]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `p` is smaller than 1, if `axis` is out of bounds or if the dimensionality of `D` does
+    /// not agree with `keepdim`.
+    pub fn norm_axis<D: Dimension>(self, p: f32, axis: usize, keepdim: bool) -> Var<Norm<T, D>> {
+        self.check_axis(axis);
+        let ndim = self.data().ndim() - usize::from(!keepdim);
+        assert!(
+            D::NDIM.map_or(true, |d| d == ndim),
+            "error: the norms along an axis of a variable of {} dimensions have {} dimensions, not {}.",
+            self.data().ndim(),
+            ndim,
+            D::NDIM.unwrap_or_default()
+        );
+        Var::from(Norm::new(self.node, p, Some(axis), keepdim), self.past)
+    }
+
+    /// Rescales each sub-tensor of `self` along `axis` whose `p`-norm exceeds `max_norm`, so that
+    /// its norm becomes `max_norm`, and returns a variable with the result. The other sub-tensors
+    /// are left untouched.
+    ///
+    /// With `axis` equal to 0 the rows of a matrix are constrained, which is the common case for
+    /// embedding tables. To enforce the constraint on the parameters themselves after each update
+    /// see [`MaxNorm`](crate::optim::MaxNorm).
+    ///
+    /// ```
+    /// let x = neuronika::from_ndarray(ndarray::array![[3., -4.], [0.5, 0.]]);
+    ///
+    /// let y = x.renorm(2., 0, 1.);
+    /// y.forward();
+    /// assert_eq!(*y.data(), ndarray::array![[0.6, -0.8], [0.5, 0.]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `p` is smaller than 1 or `axis` is out of bounds.
+    pub fn renorm(self, p: f32, axis: usize, max_norm: f32) -> Var<Renorm<T>> {
+        self.check_axis(axis);
+        Var::from(Renorm::new(self.node, p, axis, max_norm), self.past)
+    }
+
     /// Takes the power of each element in `self` with exponent `exp` and returns a variable with the
     /// result.
     pub fn pow(self, exp: i32) -> Var<Power<T>> {
@@ -325,124 +639,906 @@ where
         Var::from(Sqrt::new(self.node), self.past)
     }
 
+    /// Takes the sign of each element in `self` and returns a variable with the result.
+    ///
+    /// *Sign(x) = 1 if x >= 0, -1 otherwise*
+    pub fn sign(self) -> Var<Sign<T>> {
+        Var::from(Sign::new(self.node), self.past)
+    }
+
+    /// Rounds each element in `self` to the nearest integer and returns a variable with the
+    /// result. Half-way cases are rounded away from zero.
+    pub fn round(self) -> Var<Round<T>> {
+        Var::from(Round::new(self.node), self.past)
+    }
+
+    /// Rounds each element in `self` down to the nearest integer and returns a variable with the
+    /// result.
+    ///
+    /// ```
+    /// let x = neuronika::from_ndarray(ndarray::array![-1.5, 0.2, 2.7]);
+    ///
+    /// let y = x.floor();
+    /// y.forward();
+    /// assert_eq!(*y.data(), ndarray::array![-2., 0., 2.]);
+    /// ```
+    pub fn floor(self) -> Var<Floor<T>> {
+        Var::from(Floor::new(self.node), self.past)
+    }
+
+    /// Rounds each element in `self` up to the nearest integer and returns a variable with the
+    /// result.
+    pub fn ceil(self) -> Var<Ceil<T>> {
+        Var::from(Ceil::new(self.node), self.past)
+    }
+
     /// Applies the *rectified linear unit* element-wise and returns a variable with the
     /// result.
     ///
-    /// *ReLU(x) = max(0, x)*
-    pub fn relu(self) -> Var<ReLU<T>> {
-        Var::from(ReLU::new(self.node), self.past)
+    /// *ReLU(x) = max(0, x)*
+    pub fn relu(self) -> Var<ReLU<T>> {
+        Var::from(ReLU::new(self.node), self.past)
+    }
+
+    /// Applies the *leaky rectified linear unit* element-wise and returns a variable with
+    /// the result.
+    ///
+    /// *LeakyReLU(x) = max(0, x) + 0.01 * min(0, x)*
+    pub fn leaky_relu(self) -> Var<LeakyReLU<T>> {
+        Var::from(LeakyReLU::new(self.node), self.past)
+    }
+
+    /// Applies the *softplus* element-wise and returns a variable with the result.
+    ///
+    /// *Softplus(x) = log(1 + exp(x))*
+    ///
+    /// For numerical stability the function reverts to the identity where *x* is larger than 20.
+    /// See also [`.softplus_with()`](Var::softplus_with()).
+    pub fn softplus(self) -> Var<SoftPlus<T>> {
+        self.softplus_with(1., 20.)
+    }
+
+    /// Applies the *softplus* with parameter `beta` element-wise and returns a variable with the
+    /// result.
+    ///
+    /// *Softplus(x) = 1 / beta * log(1 + exp(beta * x))*
+    ///
+    /// For numerical stability the function reverts to the identity where *beta * x* is larger
+    /// than `threshold`.
+    ///
+    /// ```
+    /// let x = neuronika::from_ndarray(ndarray::array![0., 1000.]);
+    ///
+    /// let y = x.softplus_with(2., 20.);
+    /// y.forward();
+    /// assert_eq!(*y.data(), ndarray::array![std::f32::consts::LN_2 / 2., 1000.]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `beta` is not positive.
+    pub fn softplus_with(self, beta: f32, threshold: f32) -> Var<SoftPlus<T>> {
+        Var::from(SoftPlus::new(self.node, beta, threshold), self.past)
+    }
+
+    /// Applies the *sigmoid* element-wise and returns a variable with the result.
+    pub fn sigmoid(self) -> Var<Sigmoid<T>> {
+        Var::from(Sigmoid::new(self.node), self.past)
+    }
+
+    /// Applies the *tanh* element-wise and returns a variable with the result.
+    pub fn tanh(self) -> Var<TanH<T>> {
+        Var::from(TanH::new(self.node), self.past)
+    }
+
+    /// Applies the *natural logarithm* element-wise and returns a variable with the result.
+    pub fn ln(self) -> Var<Logn<T>> {
+        Var::from(Logn::new(self.node), self.past)
+    }
+
+    /// Applies the *exponential* element-wise and returns a variable with the result.
+    pub fn exp(self) -> Var<Exp<T>> {
+        Var::from(Exp::new(self.node), self.past)
+    }
+
+    /// Applies the *sine* element-wise and returns a variable with the result.
+    pub fn sin(self) -> Var<Sin<T>> {
+        Var::from(Sin::new(self.node), self.past)
+    }
+
+    /// Applies the *cosine* element-wise and returns a variable with the result.
+    pub fn cos(self) -> Var<Cos<T>> {
+        Var::from(Cos::new(self.node), self.past)
+    }
+
+    /// Applies the *tangent* element-wise and returns a variable with the result.
+    pub fn tan(self) -> Var<Tan<T>> {
+        Var::from(Tan::new(self.node), self.past)
+    }
+
+    /// Applies the *arcsine* element-wise and returns a variable with the result.
+    pub fn asin(self) -> Var<ArcSin<T>> {
+        Var::from(ArcSin::new(self.node), self.past)
+    }
+
+    /// Applies the *arccosine* element-wise and returns a variable with the result.
+    pub fn acos(self) -> Var<ArcCos<T>> {
+        Var::from(ArcCos::new(self.node), self.past)
+    }
+
+    /// Applies the *arctangent* element-wise and returns a variable with the result.
+    pub fn atan(self) -> Var<ArcTan<T>> {
+        Var::from(ArcTan::new(self.node), self.past)
+    }
+
+    /// Applies the *hyperbolic sine* element-wise and returns a variable with the result.
+    pub fn sinh(self) -> Var<SinH<T>> {
+        Var::from(SinH::new(self.node), self.past)
+    }
+
+    /// Applies the *hyperbolic cosine* element-wise and returns a variable with the result.
+    pub fn cosh(self) -> Var<CosH<T>> {
+        Var::from(CosH::new(self.node), self.past)
+    }
+
+    /// Applies the *inverse hyperbolic tangent* element-wise and returns a variable with the result.
+    pub fn atanh(self) -> Var<ArcTanH<T>> {
+        Var::from(ArcTanH::new(self.node), self.past)
+    }
+
+    /// Applies the *error function* element-wise and returns a variable with the result.
+    ///
+    /// *erf(x) = 2 / sqrt(π) ∫₀ˣ exp(-t²) dt*
+    ///
+    /// ```
+    /// use std::f32::consts::SQRT_2;
+    ///
+    /// // The exact GELU activation.
+    /// let x = neuronika::from_ndarray(ndarray::array![-1., 0., 1.]);
+    /// let gelu = x.clone() * ((x / SQRT_2).erf() + 1.) * 0.5;
+    ///
+    /// gelu.forward();
+    /// let target = ndarray::array![-0.158655, 0., 0.841345];
+    /// assert!(gelu.data().iter().zip(&target).all(|(l, r)| (l - r).abs() < 1e-6));
+    /// ```
+    pub fn erf(self) -> Var<Erf<T>> {
+        Var::from(Erf::new(self.node), self.past)
+    }
+
+    /// Applies the *complementary error function* element-wise and returns a variable with the
+    /// result.
+    ///
+    /// *erfc(x) = 1 - erf(x)*, computed without the loss of precision of the subtraction for
+    /// large *x*.
+    pub fn erfc(self) -> Var<Erfc<T>> {
+        Var::from(Erfc::new(self.node), self.past)
+    }
+
+    /// Applies the natural logarithm of the absolute value of the *gamma function* element-wise
+    /// and returns a variable with the result.
+    ///
+    /// The result is infinite at the non-positive integers, where the gamma function has poles.
+    pub fn lgamma(self) -> Var<LogGamma<T>> {
+        Var::from(LogGamma::new(self.node), self.past)
+    }
+
+    /// Applies the *digamma function*, the derivative of the logarithm of the gamma function,
+    /// element-wise and returns a variable with the result.
+    ///
+    /// The function is not defined at the non-positive integers.
+    pub fn digamma(self) -> Var<Digamma<T>> {
+        Var::from(Digamma::new(self.node), self.past)
+    }
+
+    /// Applies *ln(1 + x)* element-wise and returns a variable with the result.
+    ///
+    /// The result is accurate also for values of *x* close to zero, where `1 + x` would round to
+    /// one.
+    pub fn log1p(self) -> Var<Log1p<T>> {
+        Var::from(Log1p::new(self.node), self.past)
+    }
+
+    /// Applies *exp(x) - 1* element-wise and returns a variable with the result.
+    ///
+    /// The result is accurate also for values of *x* close to zero, where the subtraction would
+    /// cancel the significant digits.
+    pub fn expm1(self) -> Var<Expm1<T>> {
+        Var::from(Expm1::new(self.node), self.past)
+    }
+
+    /// Takes the reciprocal *1 / x* element-wise and returns a variable with the result.
+    pub fn reciprocal(self) -> Var<Reciprocal<T>> {
+        Var::from(Reciprocal::new(self.node), self.past)
+    }
+
+    /// Takes the reciprocal of the square root *1 / sqrt(x)* element-wise and returns a variable
+    /// with the result.
+    pub fn rsqrt(self) -> Var<Rsqrt<T>> {
+        Var::from(Rsqrt::new(self.node), self.past)
+    }
+
+    /// Applies the *softmax* to `self` and returns a variable with the result.
+    ///
+    /// The *softmax* is applied to all slices along `axis`, and will re-scale them so
+    ///  that the elements lie in the range *[0, 1]* and sum to 1.0.
+    pub fn softmax(self, axis: usize) -> Var<Softmax<T>> {
+        Var::from(Softmax::new(self.node, axis), self.past)
+    }
+
+    /// Applies the *log-softmax* to `self` and returns a variable with the result.
+    ///
+    /// Applies a softmax followed by a logarithm. While mathematically equivalent to
+    /// *log(softmax(x))*, doing these two operations separately is slower, and numerically
+    /// unstable. This function uses an alternative formulation to compute the output and
+    /// gradient correctly.
+    ///
+    /// See also [`.softmax()`].
+    ///
+    /// [`.softmax()`]: Var::softmax()
+    pub fn log_softmax(self, axis: usize) -> Var<LogSoftmax<T>> {
+        Var::from(LogSoftmax::new(self.node, axis), self.past)
+    }
+
+    /// Applies the *gated linear unit* to `self` and returns a variable with the result.
+    ///
+    /// `self` is split in two halves *a* and *b* along `axis`, and *a* is gated by the sigmoid
+    /// of *b*, that is, *a ⊙ σ(b)* is computed.
+    ///
+    /// # Panics
+    ///
+    /// If the size of `axis` is odd.
+    pub fn glu(self, axis: usize) -> Var<GLU<T>> {
+        Var::from(GLU::new(self.node, axis), self.past)
+    }
+
+    /// Computes the *exponential moving average* of `self` along `axis` and returns a variable
+    /// with the result.
+    ///
+    /// ```text
+    /// y₀ = x₀
+    ///
+    /// yₜ = α xₜ + (1 - α) yₜ₋₁
+    /// ```
+    ///
+    /// Each element only depends on the ones preceding it along `axis`, which is usually the time
+    /// axis of a batch of series.
+    ///
+    /// # Panics
+    ///
+    /// If `alpha` doesn't lie in *(0, 1]*.
+    pub fn ema(self, axis: usize, alpha: f32) -> Var<Ema<T>> {
+        Var::from(Ema::new(self.node, axis, alpha), self.past)
+    }
+
+    /// Computes the trailing *moving average* of `self` along `axis`, over windows of `window`
+    /// elements, and returns a variable with the result.
+    ///
+    /// The result has the same shape as `self`, the first `window - 1` elements of each lane being
+    /// averaged over the shorter windows available. Each element only depends on the ones
+    /// preceding it along `axis`.
+    ///
+    /// # Panics
+    ///
+    /// If `window` is zero.
+    pub fn rolling_mean(self, axis: usize, window: usize) -> Var<RollingMean<T>> {
+        Var::from(RollingMean::new(self.node, axis, window), self.past)
+    }
+
+    /// Returns a variable equivalent to `self` with its dimensions reversed.
+    pub fn t(self) -> Var<Transpose<T>> {
+        Var::from(Transpose::new(self.node), self.past)
+    }
+
+    /// Applies *dropout* to `self` and returns a variable with the result.
+    ///
+    /// It is strongly suggested to use [`nn::Dropout`] instead of this method when working with
+    /// neural networks.
+    ///
+    /// During training, randomly zeroes some of the elements of `self` with probability *p* using
+    /// samples from a Bernoulli distribution. Each channel will be zeroed out independently on
+    /// every forward call.
+    ///
+    /// This has proven to be an effective technique for regularization and preventing the
+    /// co-adaptation of neurons as described in the paper
+    /// [Improving neural networks by preventing co-adaptation of feature detectors](https://arxiv.org/abs/1207.0580).
+    ///
+    /// Furthermore, the outputs are scaled by a factor of 1/(1 - p) during training. This means
+    /// that during evaluation the resulting variable simply computes an identity function.
+    ///
+    /// [`nn::Dropout`]: crate::nn::Dropout
+    pub fn dropout(self, p: f64) -> Var<Dropout<T>> {
+        self.dropout_with_status(p, Rc::new(Cell::new(true)), Rc::default())
+    }
+
+    /// Creates a new dropout variable with a status and a mask. This method is used in the
+    /// `Dropout` component of the `nn` module.
+    pub(crate) fn dropout_with_status(
+        self,
+        p: f64,
+        status: Rc<Cell<bool>>,
+        mask: Rc<DropoutMask>,
+    ) -> Var<Dropout<T>> {
+        Var::from_changeable(Dropout::new(self.node, p, status, mask), self.past)
+    }
+
+    /// Creates a new dropout variable zeroing whole samples, with a status and a mask. This
+    /// method is used in the `DropPath` component of the `nn` module.
+    pub(crate) fn drop_path_with_status(
+        self,
+        p: f64,
+        status: Rc<Cell<bool>>,
+        mask: Rc<DropoutMask>,
+    ) -> Var<Dropout<T>> {
+        Var::from_changeable(Dropout::per_sample(self.node, p, status, mask), self.past)
+    }
+
+    /// Splits `self` into a certain number of chunks of size `chunk_size` **skipping** the
+    /// remainder along each dimension that doesn’t fit evenly.
+    ///
+    /// The chunks are computed at once and share the same computation.
+    ///
+    /// If the shape of `self` changes after the graph is built, the number of chunks and the size
+    /// of the skipped remainder along each axis stay the same, while the size of the chunks
+    /// follows the new shape.
+    pub fn chunks<E: IntoDimension<Dim = T::Dim>>(
+        self,
+        chunk_size: E,
+    ) -> Vec<Var<Output<Chunk<T>>>> {
+        let chunk = Rc::new(Chunk::new(self.node, chunk_size.into_dimension()));
+
+        (0..chunk.len())
+            .map(|i| Var::from(Output::new(chunk.clone(), i), self.past.clone()))
+            .collect()
+    }
+
+    /// Returns a new variable with a dimension of size one inserted at the position specified by
+    /// `axis`.
+    pub fn unsqueeze(self, axis: usize) -> Var<Unsqueeze<T>> {
+        Var::from(Unsqueeze::new(self.node, axis), self.past)
+    }
+
+    /// Returns a new two-dimensional variable whose rows are the elements of each entry of `self`
+    /// along the first axis, laid out in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// If `self` is zero-dimensional.
+    pub fn flatten(self) -> Var<Flatten<T>> {
+        Var::from(Flatten::new(self.node), self.past)
+    }
+
+    /// Rounds the elements of `self` to the nearest integers and returns them as an index
+    /// variable.
+    ///
+    /// This is useful to feed the operations consuming indices with data that is produced as
+    /// floating point, such as labels read by a data loader.
+    ///
+    /// ```
+    /// let labels = neuronika::from_ndarray(ndarray::array![0., 2., 1.]);
+    ///
+    /// let indices = labels.to_indices();
+    /// indices.forward();
+    /// assert_eq!(*indices.data(), ndarray::array![0, 2, 1]);
+    /// ```
+    pub fn to_indices(self) -> IndexVar<ToIndices<T>> {
+        IndexVar::from(ToIndices::new(self.node), self.past)
+    }
+
+    /// Gathers the elements of `self` along `axis` at the positions given by `indices`.
+    ///
+    /// The result has the shape of `indices`. Its element at position *p* is the element of `self`
+    /// at position *p* with the coordinate along `axis` replaced by the index at position *p*,
+    /// e.g. for a two-dimensional variable and `axis` equal to 1,
+    /// `result[[i, j]] = self[[i, indices[[i, j]]]]`.
+    ///
+    /// ```
+    /// let scores = neuronika::from_ndarray(ndarray::array![[0.1, 0.7, 0.2], [0.5, 0.3, 0.2]]);
+    /// let labels = neuronika::indices(ndarray::array![[1], [0]]);
+    ///
+    /// let picked = scores.gather(1, labels);
+    /// picked.forward();
+    /// assert_eq!(*picked.data(), ndarray::array![[0.7], [0.5]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds, or, during the forward pass, if an index is out of range or
+    /// `indices` is larger than `self` along any other axis.
+    pub fn gather<I: ?Sized>(self, axis: usize, indices: IndexVar<I>) -> Var<Gather<T, I>>
+    where
+        I: IndexData<Dim = T::Dim> + 'static,
+    {
+        self.check_axis(axis);
+        let mut past = self.past;
+        past.merge(indices.past);
+        Var::from(Gather::new(self.node, indices.node, axis), past)
+    }
+
+    /// Sums the slices of `self` along `axis` that belong to the same segment.
+    ///
+    /// `indices` assigns each slice of `self` along `axis` to one of `num_segments` segments, so
+    /// that `result.index_axis(axis, s)` is the sum of the slices `i` with `indices[i] == s`. The
+    /// result has the shape of `self` with `axis` of length `num_segments`, and empty segments
+    /// are zero. This is the aggregation step of graph neural networks and of grouped pooling.
+    /// When the shape of `self` changes between evaluations the number of segments stays the same.
+    ///
+    /// ```
+    /// let messages = neuronika::from_ndarray(ndarray::array![[1., 2.], [3., 4.], [5., 6.]]);
+    /// let targets = neuronika::indices(ndarray::array![1, 0, 1]);
+    ///
+    /// let aggregated = messages.scatter_add(0, targets, 3);
+    /// aggregated.forward();
+    /// assert_eq!(*aggregated.data(), ndarray::array![[3., 4.], [6., 8.], [0., 0.]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds, if the length of `indices` differs from the length of `self`
+    /// along `axis`, also after a change of shape, or, during the forward pass, if an index is out
+    /// of range.
+    pub fn scatter_add<I: ?Sized>(
+        self,
+        axis: usize,
+        indices: IndexVar<I>,
+        num_segments: usize,
+    ) -> Var<Scatter<T, I>>
+    where
+        I: IndexData<Dim = Ix1> + 'static,
+    {
+        self.scatter(axis, indices, num_segments, SegmentReduction::Add)
+    }
+
+    /// Averages the slices of `self` along `axis` that belong to the same segment.
+    ///
+    /// Empty segments are zero, see [`.scatter_add()`](Var::scatter_add()) for more details.
+    pub fn scatter_mean<I: ?Sized>(
+        self,
+        axis: usize,
+        indices: IndexVar<I>,
+        num_segments: usize,
+    ) -> Var<Scatter<T, I>>
+    where
+        I: IndexData<Dim = Ix1> + 'static,
+    {
+        self.scatter(axis, indices, num_segments, SegmentReduction::Mean)
+    }
+
+    /// Takes the element-wise maximum of the slices of `self` along `axis` that belong to the
+    /// same segment.
+    ///
+    /// Empty segments are zero, see [`.scatter_add()`](Var::scatter_add()) for more details.
+    pub fn scatter_max<I: ?Sized>(
+        self,
+        axis: usize,
+        indices: IndexVar<I>,
+        num_segments: usize,
+    ) -> Var<Scatter<T, I>>
+    where
+        I: IndexData<Dim = Ix1> + 'static,
+    {
+        self.scatter(axis, indices, num_segments, SegmentReduction::Max)
+    }
+
+    /// Returns the indices of the largest elements of `self` along `axis`.
+    ///
+    /// The result has the shape of `self` with `axis` removed. Ties are broken in favor of the
+    /// lowest index and NaNs are considered larger than any other value.
+    ///
+    /// ```
+    /// let scores = neuronika::from_ndarray(ndarray::array![[0.1, 0.7, 0.2], [0.5, 0.3, 0.5]]);
+    ///
+    /// let predictions = scores.argmax(1);
+    /// predictions.forward();
+    /// assert_eq!(*predictions.data(), ndarray::array![1, 0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds, or, during the forward pass, if `axis` is empty.
+    pub fn argmax(self, axis: usize) -> IndexVar<ArgMax<T>>
+    where
+        T::Dim: RemoveAxis,
+    {
+        self.check_axis(axis);
+        IndexVar::from(ArgMax::new(self.node, axis), self.past)
+    }
+
+    /// Returns the indices of the smallest elements of `self` along `axis`.
+    ///
+    /// The result has the shape of `self` with `axis` removed. Ties are broken in favor of the
+    /// lowest index and NaNs are considered smaller than any other value.
+    ///
+    /// ```
+    /// let scores = neuronika::from_ndarray(ndarray::array![[0.1, 0.7, 0.2], [0.5, 0.3, 0.3]]);
+    ///
+    /// let indices = scores.argmin(1);
+    /// indices.forward();
+    /// assert_eq!(*indices.data(), ndarray::array![0, 1]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds, or, during the forward pass, if `axis` is empty.
+    pub fn argmin(self, axis: usize) -> IndexVar<ArgMin<T>>
+    where
+        T::Dim: RemoveAxis,
+    {
+        self.check_axis(axis);
+        IndexVar::from(ArgMin::new(self.node, axis), self.past)
+    }
+
+    /// Returns the `k` largest elements of `self` along `axis` together with their indices.
+    ///
+    /// Both results have the shape of `self` with `axis` of length `k`. The elements are sorted in
+    /// descending order, ties being broken in favor of the lowest index and NaNs being considered
+    /// larger than any other value. The values are gathered from `self` with the returned indices,
+    /// see [`.gather()`](Var::gather()).
+    ///
+    /// ```
+    /// let scores = neuronika::from_ndarray(ndarray::array![[0.1, 0.7, 0.2], [0.5, 0.3, 0.4]]);
+    ///
+    /// let (values, indices) = scores.topk(2, 1);
+    /// values.forward();
+    /// assert_eq!(*values.data(), ndarray::array![[0.7, 0.2], [0.5, 0.4]]);
+    /// assert_eq!(*indices.data(), ndarray::array![[1, 2], [0, 2]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds, or, during the forward pass, if `k` is larger than the length
+    /// of `axis`.
+    #[allow(clippy::type_complexity)]
+    pub fn topk(self, k: usize, axis: usize) -> (Var<Gather<T, TopK<T>>>, IndexVar<TopK<T>>) {
+        self.check_axis(axis);
+        let indices = IndexVar::from(TopK::new(self.node.clone(), k, axis), self.past.clone());
+        (self.gather(axis, indices.clone()), indices)
+    }
+
+    /// Returns the indices that sort the elements of `self` along `axis` in ascending order.
+    ///
+    /// The result has the shape of `self`. The sort is stable, so that equal elements keep their
+    /// relative order, and NaNs are placed after any other value.
+    ///
+    /// ```
+    /// let x = neuronika::from_ndarray(ndarray::array![[0.3, 0.1, 0.2], [2., 2., 1.]]);
+    ///
+    /// let indices = x.argsort(1);
+    /// indices.forward();
+    /// assert_eq!(*indices.data(), ndarray::array![[1, 2, 0], [2, 0, 1]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds.
+    pub fn argsort(self, axis: usize) -> IndexVar<ArgSort<T>> {
+        self.check_axis(axis);
+        IndexVar::from(ArgSort::new(self.node, axis), self.past)
     }
 
-    /// Applies the *leaky rectified linear unit* element-wise and returns a variable with
-    /// the result.
+    /// Sorts the elements of `self` along `axis` in ascending order, returning the sorted values
+    /// together with the indices that sort them, see [`.argsort()`](Var::argsort()).
     ///
-    /// *LeakyReLU(x) = max(0, x) + 0.01 * min(0, x)*
-    pub fn leaky_relu(self) -> Var<LeakyReLU<T>> {
-        Var::from(LeakyReLU::new(self.node), self.past)
+    /// ```
+    /// let x = neuronika::from_ndarray(ndarray::array![0.3, 0.1, 0.2]);
+    ///
+    /// let (values, indices) = x.sort(0);
+    /// values.forward();
+    /// assert_eq!(*values.data(), ndarray::array![0.1, 0.2, 0.3]);
+    /// assert_eq!(*indices.data(), ndarray::array![1, 2, 0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds.
+    #[allow(clippy::type_complexity)]
+    pub fn sort(self, axis: usize) -> (Var<Gather<T, ArgSort<T>>>, IndexVar<ArgSort<T>>) {
+        let indices = self.clone().argsort(axis);
+        (self.gather(axis, indices.clone()), indices)
     }
 
-    /// Applies the *softplus* element-wise and returns a variable with the result.
+    /// Sums the elements of `self` into `bins` bins, adding each element to the bin given by the
+    /// index at the same position of `indices`.
     ///
-    /// *Softplus(x) = log(1 + exp(x))*
-    pub fn softplus(self) -> Var<SoftPlus<T>> {
-        Var::from(SoftPlus::new(self.node), self.past)
+    /// The result is a one-dimensional variable of length `bins`. When `self` is filled with ones
+    /// this is equivalent to counting the indices, see [`IndexVar::bincount()`].
+    ///
+    /// ```
+    /// let weights = neuronika::from_ndarray(ndarray::array![0.5, 1., 2., 4.]);
+    /// let labels = neuronika::indices(ndarray::array![1, 0, 1, 2]);
+    ///
+    /// let sums = weights.bincount(labels, 3);
+    /// sums.forward();
+    /// assert_eq!(*sums.data(), ndarray::array![1., 2.5, 4.]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// During the forward pass, if `indices` and `self` have different shapes, or if an index is
+    /// negative or is not smaller than `bins`.
+    pub fn bincount<I: ?Sized>(
+        self,
+        indices: IndexVar<I>,
+        bins: usize,
+    ) -> Var<WeightedBinCount<T, I>>
+    where
+        I: IndexData<Dim = T::Dim> + 'static,
+    {
+        let mut past = self.past;
+        past.merge(indices.past);
+        Var::from(WeightedBinCount::new(self.node, indices.node, bins), past)
     }
 
-    /// Applies the *sigmoid* element-wise and returns a variable with the result.
-    pub fn sigmoid(self) -> Var<Sigmoid<T>> {
-        Var::from(Sigmoid::new(self.node), self.past)
+    /// Compares `self` with `other` element-wise, returning a mask that holds one where `self` is
+    /// greater than `other` and zero elsewhere.
+    ///
+    /// The variables are broadcast to a common shape, and comparisons involving `NaN`s are false.
+    /// Masks are not differentiable: they select elements through multiplication or
+    /// [`.masked_fill()`](Var::masked_fill()), so that control flow can be expressed inside the
+    /// graph.
+    ///
+    /// ```
+    /// let x = neuronika::from_ndarray(ndarray::array![-1., 0.5, 2.]);
+    /// let threshold = neuronika::full((), 0.);
+    ///
+    /// let positive = x.clone().gt(threshold);
+    /// positive.forward();
+    /// assert_eq!(*positive.data(), ndarray::array![0., 1., 1.]);
+    ///
+    /// // Selects between two branches.
+    /// let selected = positive.clone() * x.clone() + (1. - positive) * x.exp();
+    /// selected.forward();
+    /// assert_eq!(selected.data()[0], (-1f32).exp());
+    /// ```
+    pub fn gt<U: ?Sized>(self, other: Var<U>) -> Var<Comparison<T, U>>
+    where
+        U: Data + 'static,
+        T::Dim: DimMax<U::Dim>,
+    {
+        self.compare(other, Comparator::Greater)
     }
 
-    /// Applies the *tanh* element-wise and returns a variable with the result.
-    pub fn tanh(self) -> Var<TanH<T>> {
-        Var::from(TanH::new(self.node), self.past)
+    /// Compares `self` with `other` element-wise, returning a mask that holds one where `self` is
+    /// smaller than `other` and zero elsewhere.
+    ///
+    /// See [`.gt()`](Var::gt()) for more details.
+    pub fn lt<U: ?Sized>(self, other: Var<U>) -> Var<Comparison<T, U>>
+    where
+        U: Data + 'static,
+        T::Dim: DimMax<U::Dim>,
+    {
+        self.compare(other, Comparator::Less)
     }
 
-    /// Applies the *natural logarithm* element-wise and returns a variable with the result.
-    pub fn ln(self) -> Var<Logn<T>> {
-        Var::from(Logn::new(self.node), self.past)
+    /// Compares `self` with `other` element-wise, returning a mask that holds one where `self` is
+    /// greater than or equal to `other` and zero elsewhere.
+    ///
+    /// See [`.gt()`](Var::gt()) for more details.
+    pub fn ge<U: ?Sized>(self, other: Var<U>) -> Var<Comparison<T, U>>
+    where
+        U: Data + 'static,
+        T::Dim: DimMax<U::Dim>,
+    {
+        self.compare(other, Comparator::GreaterEqual)
     }
 
-    /// Applies the *exponential* element-wise and returns a variable with the result.
-    pub fn exp(self) -> Var<Exp<T>> {
-        Var::from(Exp::new(self.node), self.past)
+    /// Compares `self` with `other` element-wise, returning a mask that holds one where `self` is
+    /// smaller than or equal to `other` and zero elsewhere.
+    ///
+    /// See [`.gt()`](Var::gt()) for more details.
+    pub fn le<U: ?Sized>(self, other: Var<U>) -> Var<Comparison<T, U>>
+    where
+        U: Data + 'static,
+        T::Dim: DimMax<U::Dim>,
+    {
+        self.compare(other, Comparator::LessEqual)
     }
 
-    /// Applies the *softmax* to `self` and returns a variable with the result.
+    /// Compares `self` with `other` element-wise, returning a mask that holds one where `self` is
+    /// equal to `other` and zero elsewhere.
     ///
-    /// The *softmax* is applied to all slices along `axis`, and will re-scale them so
-    ///  that the elements lie in the range *[0, 1]* and sum to 1.0.
-    pub fn softmax(self, axis: usize) -> Var<Softmax<T>> {
-        Var::from(Softmax::new(self.node, axis), self.past)
+    /// See [`.gt()`](Var::gt()) for more details.
+    pub fn eq<U: ?Sized>(self, other: Var<U>) -> Var<Comparison<T, U>>
+    where
+        U: Data + 'static,
+        T::Dim: DimMax<U::Dim>,
+    {
+        self.compare(other, Comparator::Equal)
     }
 
-    /// Applies the *log-softmax* to `self` and returns a variable with the result.
+    /// Replaces the elements of `self` for which `mask` is not zero with `value`.
     ///
-    /// Applies a softmax followed by a logarithm. While mathematically equivalent to
-    /// *log(softmax(x))*, doing these two operations separately is slower, and numerically
-    /// unstable. This function uses an alternative formulation to compute the output and
-    /// gradient correctly.
+    /// `mask` is broadcast to the shape of `self` and is usually the result of a comparison. This
+    /// is useful to exclude elements from a softmax by filling them with minus infinity.
     ///
-    /// See also [`.softmax()`].
+    /// ```
+    /// let scores = neuronika::from_ndarray(ndarray::array![[1., 2.], [3., 4.]]);
+    /// let padding = neuronika::from_ndarray(ndarray::array![0., 1.]);
     ///
-    /// [`.softmax()`]: Var::softmax()
-    pub fn log_softmax(self, axis: usize) -> Var<LogSoftmax<T>> {
-        Var::from(LogSoftmax::new(self.node, axis), self.past)
+    /// let masked = scores.masked_fill(padding, f32::NEG_INFINITY);
+    /// masked.forward();
+    /// assert_eq!(
+    ///     *masked.data(),
+    ///     ndarray::array![[1., f32::NEG_INFINITY], [3., f32::NEG_INFINITY]]
+    /// );
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// During the forward pass, if `mask` cannot be broadcast to the shape of `self`.
+    pub fn masked_fill<U: ?Sized>(self, mask: Var<U>, value: f32) -> Var<MaskedFill<T, U>>
+    where
+        U: Data + 'static,
+    {
+        let mut past = self.past;
+        past.merge(mask.past);
+        Var::from(MaskedFill::new(self.node, mask.node, value), past)
+    }
+
+    /// Creates a new batch normalization variable with a status. This method is used in the
+    /// `BatchNorm1d` and `BatchNorm2d` components of the `nn` module.
+    pub(crate) fn batch_norm_with_status(
+        self,
+        running_mean: Rc<RefCell<Tensor<Ix1>>>,
+        running_var: Rc<RefCell<Tensor<Ix1>>>,
+        momentum: f32,
+        eps: f32,
+        status: Rc<Cell<bool>>,
+    ) -> Var<BatchNorm<T>> {
+        Var::from_changeable(
+            BatchNorm::new(self.node, running_mean, running_var, momentum, eps, status),
+            self.past,
+        )
     }
 
-    /// Returns a variable equivalent to `self` with its dimensions reversed.
-    pub fn t(self) -> Var<Transpose<T>> {
-        Var::from(Transpose::new(self.node), self.past)
+    /// Asserts that `axis` is within the bounds of `self`.
+    pub(crate) fn check_axis(&self, axis: usize) {
+        assert!(
+            axis < self.data().ndim(),
+            "error: axis {} is out of bounds for a variable of {} dimensions.",
+            axis,
+            self.data().ndim()
+        );
     }
 
-    /// Applies *dropout* to `self` and returns a variable with the result.
+    /// Compares `self` with `other` element-wise according to `comparator`.
+    fn compare<U: ?Sized>(self, other: Var<U>, comparator: Comparator) -> Var<Comparison<T, U>>
+    where
+        U: Data + 'static,
+        T::Dim: DimMax<U::Dim>,
+    {
+        let mut past = self.past;
+        past.merge(other.past);
+        Var::from(Comparison::new(self.node, other.node, comparator), past)
+    }
+
+    /// Reduces the slices of `self` along `axis` that belong to the same segment according to
+    /// `reduction`.
+    pub(crate) fn scatter<I: ?Sized>(
+        self,
+        axis: usize,
+        indices: IndexVar<I>,
+        num_segments: usize,
+        reduction: SegmentReduction,
+    ) -> Var<Scatter<T, I>>
+    where
+        I: IndexData<Dim = Ix1> + 'static,
+    {
+        self.check_axis(axis);
+        let mut past = self.past;
+        past.merge(indices.past);
+        Var::from(
+            Scatter::new(self.node, indices.node, axis, num_segments, reduction),
+            past,
+        )
+    }
+}
+
+impl<T: ?Sized> Var<T>
+where
+    T: Data<Dim = Ix4> + 'static,
+{
+    /// Applies a two-dimensional max pooling over `self`, which must be of shape *(N, C, H, W)*.
     ///
-    /// It is strongly suggested to use [`nn::Dropout`] instead of this method when working with
-    /// neural networks.
+    /// # Arguments
     ///
-    /// During training, randomly zeroes some of the elements of `self` with probability *p* using
-    /// samples from a Bernoulli distribution. Each channel will be zeroed out independently on
-    /// every forward call.
+    /// * `kernel_size` - size of the pooling window.
     ///
-    /// This has proven to be an effective technique for regularization and preventing the
-    /// co-adaptation of neurons as described in the paper
-    /// [Improving neural networks by preventing co-adaptation of feature detectors](https://arxiv.org/abs/1207.0580).
+    /// * `stride` - stride of the pooling window.
     ///
-    /// Furthermore, the outputs are scaled by a factor of 1/(1 - p) during training. This means
-    /// that during evaluation the resulting variable simply computes an identity function.
+    /// * `padding` - implicit negative infinity padding added on both sides of each spatial axis.
     ///
-    /// [`nn::Dropout`]: crate::nn::Dropout
-    pub fn dropout(self, p: f64) -> Var<Dropout<T>> {
-        self.dropout_with_status(p, Rc::new(Cell::new(true)))
+    /// # Panics
+    ///
+    /// If the padding is larger than half of the window, or if the window doesn't fit the padded
+    /// input.
+    pub fn max_pool2d(
+        self,
+        kernel_size: (usize, usize),
+        stride: (usize, usize),
+        padding: (usize, usize),
+    ) -> Var<MaxPool<T>> {
+        Var::from(
+            MaxPool::new(self.node, kernel_size, stride, padding),
+            self.past,
+        )
     }
 
-    /// Creates a new dropout variable with a status. This method is used in the `Dropout` component
-    ///  of the `nn` module.
-    pub(crate) fn dropout_with_status(self, p: f64, status: Rc<Cell<bool>>) -> Var<Dropout<T>> {
-        Var::from_changeable(Dropout::new(self.node, p, status), self.past)
+    /// Applies a two-dimensional average pooling over `self`, which must be of shape
+    /// *(N, C, H, W)*.
+    ///
+    /// # Arguments
+    ///
+    /// * `kernel_size` - size of the pooling window.
+    ///
+    /// * `stride` - stride of the pooling window.
+    ///
+    /// * `padding` - implicit zero padding added on both sides of each spatial axis, the padded
+    /// entries are included in the averages.
+    ///
+    /// # Panics
+    ///
+    /// If the padding is larger than half of the window, or if the window doesn't fit the padded
+    /// input.
+    pub fn avg_pool2d(
+        self,
+        kernel_size: (usize, usize),
+        stride: (usize, usize),
+        padding: (usize, usize),
+    ) -> Var<AvgPool<T>> {
+        Var::from(
+            AvgPool::new(self.node, kernel_size, stride, padding),
+            self.past,
+        )
     }
+}
 
-    /// Splits `self` into a certain number of chunks of size `chunk_size` **skipping** the
-    /// remainder along each dimension that doesn’t fit evenly.
-    pub fn chunks<E: IntoDimension<Dim = T::Dim>>(self, chunk_size: E) -> Vec<Var<Chunk<T>>> {
-        self.node
-            .data()
-            .exact_chunks(chunk_size)
-            .into_iter()
-            .enumerate()
-            .map(|(i, chunk)| {
-                Var::from(
-                    Chunk::new(self.node.clone(), chunk.to_owned(), i),
-                    self.past.clone(),
-                )
-            })
-            .collect()
+impl<T: ?Sized> Var<T>
+where
+    T: Data<Dim = Ix3> + 'static,
+{
+    /// Averages `self`, a batch of padded sequences of shape *(N, L, E)*, over time, ignoring the
+    /// padding positions.
+    ///
+    /// `mask` has shape *(N, L)* and holds zero at the padding positions of each sequence, see
+    /// also [`nn::sequence_mask()`](crate::nn::sequence_mask()). The result has shape *(N, E)*,
+    /// and is zero for the sequences made only of padding.
+    ///
+    /// ```
+    /// let sequences = neuronika::from_ndarray(ndarray::array![[[1., 2.], [3., 4.], [9., 9.]]]);
+    /// let mask = neuronika::from_ndarray(ndarray::array![[1., 1., 0.]]);
+    ///
+    /// let pooled = sequences.masked_mean_pool(mask);
+    /// pooled.forward();
+    /// assert_eq!(*pooled.data(), ndarray::array![[2., 3.]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the shape of `mask` is not *(N, L)*.
+    pub fn masked_mean_pool<U: ?Sized>(self, mask: Var<U>) -> Var<MaskedMeanPool<T, U>>
+    where
+        U: Data<Dim = Ix2> + 'static,
+    {
+        let mut past = self.past;
+        past.merge(mask.past);
+        Var::from(MaskedMeanPool::new(self.node, mask.node), past)
     }
 
-    /// Returns a new variable with a dimension of size one inserted at the position specified by
-    /// `axis`.
-    pub fn unsqueeze(self, axis: usize) -> Var<Unsqueeze<T>> {
-        Var::from(Unsqueeze::new(self.node, axis), self.past)
+    /// Takes the maximum of `self`, a batch of padded sequences of shape *(N, L, E)*, over time,
+    /// ignoring the padding positions.
+    ///
+    /// See [`.masked_mean_pool()`](Var::masked_mean_pool()) for more details.
+    ///
+    /// # Panics
+    ///
+    /// If the shape of `mask` is not *(N, L)*.
+    pub fn masked_max_pool<U: ?Sized>(self, mask: Var<U>) -> Var<MaskedMaxPool<T, U>>
+    where
+        U: Data<Dim = Ix2> + 'static,
+    {
+        let mut past = self.past;
+        past.merge(mask.past);
+        Var::from(MaskedMaxPool::new(self.node, mask.node), past)
     }
 }
 
@@ -487,7 +1583,7 @@ where
         let mut past = variables[0].past.clone();
         operands.push(variables[0].node.clone());
 
-        variables.iter().cloned().skip(1).for_each(|variable| {
+        variables.iter().skip(1).cloned().for_each(|variable| {
             past.merge(variable.past);
             operands.push(variable.node);
         });
@@ -544,7 +1640,7 @@ where
         let mut past = variables[0].past.clone();
         operands.push(variables[0].node.clone());
 
-        variables.iter().cloned().skip(1).for_each(|variable| {
+        variables.iter().skip(1).cloned().for_each(|variable| {
             past.merge(variable.past);
             operands.push(variable.node);
         });
@@ -568,48 +1664,44 @@ where
 impl<T: ?Sized> Add<f32> for Var<T>
 where
     T: Data + 'static,
-    T::Dim: DimMax<Ix0>,
 {
-    type Output = Var<Addition<T, Input<Ix0>>>;
+    type Output = Var<AddScalar<T>>;
 
     fn add(self, rhs: f32) -> Self::Output {
-        self + crate::full((), rhs)
+        Var::from(AddScalar::new(self.node, rhs), self.past)
     }
 }
 
 impl<T: ?Sized> Sub<f32> for Var<T>
 where
     T: Data + 'static,
-    T::Dim: DimMax<Ix0>,
 {
-    type Output = Var<Subtraction<T, Input<Ix0>>>;
+    type Output = Var<AddScalar<T>>;
 
     fn sub(self, rhs: f32) -> Self::Output {
-        self - crate::full((), rhs)
+        Var::from(AddScalar::new(self.node, -rhs), self.past)
     }
 }
 
 impl<T: ?Sized> Mul<f32> for Var<T>
 where
     T: Data + 'static,
-    T::Dim: DimMax<Ix0>,
 {
-    type Output = Var<Multiplication<T, Input<Ix0>>>;
+    type Output = Var<MulScalar<T>>;
 
     fn mul(self, rhs: f32) -> Self::Output {
-        self * crate::full((), rhs)
+        Var::from(MulScalar::new(self.node, rhs), self.past)
     }
 }
 
 impl<T: ?Sized> Div<f32> for Var<T>
 where
     T: Data + 'static,
-    T::Dim: DimMax<Ix0>,
 {
-    type Output = Var<Division<T, Input<Ix0>>>;
+    type Output = Var<MulScalar<T>>;
 
     fn div(self, rhs: f32) -> Self::Output {
-        self / crate::full((), rhs)
+        Var::from(MulScalar::new(self.node, 1. / rhs), self.past)
     }
 }
 
@@ -618,48 +1710,45 @@ where
 impl<T: ?Sized> Add<Var<T>> for f32
 where
     T: Data + 'static,
-    Ix0: DimMax<T::Dim>,
 {
-    type Output = Var<Addition<Input<Ix0>, T>>;
+    type Output = Var<AddScalar<T>>;
 
     fn add(self, rhs: Var<T>) -> Self::Output {
-        crate::full((), self) + rhs
+        rhs + self
     }
 }
 
 impl<T: ?Sized> Sub<Var<T>> for f32
 where
     T: Data + 'static,
-    Ix0: DimMax<T::Dim>,
 {
-    type Output = Var<Subtraction<Input<Ix0>, T>>;
+    type Output = Var<AddScalar<Negation<T>>>;
 
     fn sub(self, rhs: Var<T>) -> Self::Output {
-        crate::full((), self) - rhs
+        -rhs + self
     }
 }
 
 impl<T: ?Sized> Mul<Var<T>> for f32
 where
     T: Data + 'static,
-    Ix0: DimMax<T::Dim>,
 {
-    type Output = Var<Multiplication<Input<Ix0>, T>>;
+    type Output = Var<MulScalar<T>>;
 
     fn mul(self, rhs: Var<T>) -> Self::Output {
-        crate::full((), self) * rhs
+        rhs * self
     }
 }
 
 impl<T: ?Sized> Div<Var<T>> for f32
 where
     T: Data + 'static,
-    Ix0: DimMax<T::Dim>,
 {
-    type Output = Var<Division<Input<Ix0>, T>>;
+    type Output = Var<MulScalar<Reciprocal<T>>>;
 
     fn div(self, rhs: Var<T>) -> Self::Output {
-        crate::full((), self) / rhs
+        let reciprocal = rhs.reciprocal();
+        Var::from(MulScalar::new(reciprocal.node, self), reciprocal.past)
     }
 }
 
@@ -804,6 +1893,78 @@ where
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ References ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Implements the arithmetic operators for references to non-differentiable variables, and for
+/// references to variables as right-hand side operands, by means of shallow copies.
+macro_rules! reference_operators {
+    ($($trait:ident $method:ident),*) => {$(
+        impl<'a, T: ?Sized, Rhs> $trait<Rhs> for &'a Var<T>
+        where
+            T: Data + 'static,
+            Var<T>: $trait<Rhs>,
+        {
+            type Output = <Var<T> as $trait<Rhs>>::Output;
+
+            fn $method(self, rhs: Rhs) -> Self::Output {
+                self.clone().$method(rhs)
+            }
+        }
+
+        impl<'a, T: ?Sized, R: ?Sized> $trait<&'a Var<R>> for Var<T>
+        where
+            T: Data + 'static,
+            R: Data + 'static,
+            Var<T>: $trait<Var<R>>,
+        {
+            type Output = <Var<T> as $trait<Var<R>>>::Output;
+
+            fn $method(self, rhs: &'a Var<R>) -> Self::Output {
+                self.$method(rhs.clone())
+            }
+        }
+
+        impl<'a, T: ?Sized, F: ?Sized, B: ?Sized> $trait<&'a VarDiff<F, B>> for Var<T>
+        where
+            T: Data + 'static,
+            F: Data + 'static,
+            B: Gradient + 'static,
+            Var<T>: $trait<VarDiff<F, B>>,
+        {
+            type Output = <Var<T> as $trait<VarDiff<F, B>>>::Output;
+
+            fn $method(self, rhs: &'a VarDiff<F, B>) -> Self::Output {
+                self.$method(rhs.clone())
+            }
+        }
+
+        impl<'a, T: ?Sized> $trait<&'a Var<T>> for f32
+        where
+            T: Data + 'static,
+            f32: $trait<Var<T>>,
+        {
+            type Output = <f32 as $trait<Var<T>>>::Output;
+
+            fn $method(self, rhs: &'a Var<T>) -> Self::Output {
+                self.$method(rhs.clone())
+            }
+        }
+    )*};
+}
+
+reference_operators!(Add add, Sub sub, Mul mul, Div div);
+
+impl<T: ?Sized> Neg for &Var<T>
+where
+    T: Data + 'static,
+{
+    type Output = Var<Negation<T>>;
+
+    fn neg(self) -> Self::Output {
+        -self.clone()
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Algebraic Operations Implementations ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -1024,6 +2185,123 @@ where
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~ Maximum and Minimum traits implementations ~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Maximum ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<F1: ?Sized, F2: ?Sized> Maximum<Var<F2>> for Var<F1>
+where
+    F1: Data + 'static,
+    F2: Data + 'static,
+    F1::Dim: Dimension + DimMax<F2::Dim>,
+{
+    type Output = Var<super::node::Maximum<F1, F2>>;
+
+    fn maximum(mut self, rhs: Var<F2>) -> Self::Output {
+        self.past.merge(rhs.past);
+        Var::from(super::node::Maximum::new(self.node, rhs.node), self.past)
+    }
+}
+
+impl<F1: ?Sized, F2: ?Sized, B2: ?Sized> Maximum<VarDiff<F2, B2>> for Var<F1>
+where
+    F1: Data + 'static,
+    F2: Data + 'static,
+    B2: Gradient + Overwrite + 'static,
+    F1::Dim: Dimension + DimMax<F2::Dim>,
+    B2::Dim: Dimension + DimMax<F1::Dim>,
+{
+    type Output = VarDiff<super::node::Maximum<F1, F2>, MaximumBackwardUnary<F2, B2, F1>>;
+
+    fn maximum(self, rhs: VarDiff<F2, B2>) -> Self::Output {
+        let node = MaximumBackwardUnary::new(rhs.var.node.clone(), rhs.node, self.node.clone());
+        VarDiff::from(node, rhs.past, Maximum::maximum(self, rhs.var))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Minimum ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<F1: ?Sized, F2: ?Sized> Minimum<Var<F2>> for Var<F1>
+where
+    F1: Data + 'static,
+    F2: Data + 'static,
+    F1::Dim: Dimension + DimMax<F2::Dim>,
+{
+    type Output = Var<super::node::Minimum<F1, F2>>;
+
+    fn minimum(mut self, rhs: Var<F2>) -> Self::Output {
+        self.past.merge(rhs.past);
+        Var::from(super::node::Minimum::new(self.node, rhs.node), self.past)
+    }
+}
+
+impl<F1: ?Sized, F2: ?Sized, B2: ?Sized> Minimum<VarDiff<F2, B2>> for Var<F1>
+where
+    F1: Data + 'static,
+    F2: Data + 'static,
+    B2: Gradient + Overwrite + 'static,
+    F1::Dim: Dimension + DimMax<F2::Dim>,
+    B2::Dim: Dimension + DimMax<F1::Dim>,
+{
+    type Output = VarDiff<super::node::Minimum<F1, F2>, MinimumBackwardUnary<F2, B2, F1>>;
+
+    fn minimum(self, rhs: VarDiff<F2, B2>) -> Self::Output {
+        let node = MinimumBackwardUnary::new(rhs.var.node.clone(), rhs.node, self.node.clone());
+        VarDiff::from(node, rhs.past, Minimum::minimum(self, rhs.var))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Pow trait implementations ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<T: ?Sized> Pow<f32> for Var<T>
+where
+    T: Data + 'static,
+{
+    type Output = Var<PowScalar<T>>;
+
+    fn powf(self, exponent: f32) -> Self::Output {
+        Var::from(PowScalar::new(self.node, exponent), self.past)
+    }
+}
+
+impl<F1: ?Sized, F2: ?Sized> Pow<Var<F2>> for Var<F1>
+where
+    F1: Data + 'static,
+    F2: Data + 'static,
+    F1::Dim: Dimension + DimMax<F2::Dim>,
+{
+    type Output = Var<TensorPower<F1, F2>>;
+
+    fn powf(mut self, exponent: Var<F2>) -> Self::Output {
+        self.past.merge(exponent.past);
+        Var::from(TensorPower::new(self.node, exponent.node), self.past)
+    }
+}
+
+impl<F1: ?Sized, F2: ?Sized, B2: ?Sized> Pow<VarDiff<F2, B2>> for Var<F1>
+where
+    F1: Data + 'static,
+    F2: Data + 'static,
+    B2: Gradient + Overwrite + 'static,
+    F1::Dim: Dimension + DimMax<F2::Dim>,
+    F1::Dim: Dimension + DimMax<B2::Dim>,
+{
+    type Output = VarDiff<TensorPower<F1, F2>, TensorPowerBackwardRight<F1, F2, B2>>;
+
+    fn powf(self, exponent: VarDiff<F2, B2>) -> Self::Output {
+        let node = TensorPowerBackwardRight::new(
+            self.node.clone(),
+            exponent.var.node.clone(),
+            exponent.node,
+        );
+        VarDiff::from(node, exponent.past, self.powf(exponent.var))
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Debug ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 impl<T: ?Sized> Debug for Var<T>
@@ -1045,7 +2323,7 @@ where
     T: Data + Display,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.node)
+        Display::fmt(&*self.node, f)
     }
 }
 
@@ -1,31 +1,46 @@
 use super::{
-    Addition, AdditionBackward, AdditionBackwardUnary, Backward, Cat, Chunk, ChunkBackward,
-    Concatenate, ConcatenateBackward, ConcatenateBackwardLeft, Data, Division, DivisionBackward,
-    DivisionBackwardLeft, DivisionBackwardRight, Dropout, DropoutBackward, Exp, ExpBackward,
-    Forward, Gradient, Input, LeakyReLU, LeakyReLUBackward, LogSoftmax, LogSoftmaxBackward, Logn,
-    LognBackward, MatMatMul, MatMatMulT, MatVecMul, MatrixMatrixMul, MatrixMatrixMulBackward,
-    MatrixMatrixMulBackwardLeft, MatrixMatrixMulT, MatrixMatrixMulTBackward,
-    MatrixMatrixMulTBackwardLeft, MatrixVectorMul, MatrixVectorMulBackward,
-    MatrixVectorMulBackwardLeft, Mean, MeanBackward, MultiConcatenate, MultiConcatenateBackward,
-    MultiStack, MultiStackBackward, Multiplication, MultiplicationBackward,
-    MultiplicationBackwardUnary, Negation, NegationBackward, Overwrite, Param, Power,
-    PowerBackward, RawParam, ReLU, ReLUBackward, Sigmoid, SigmoidBackward, SoftPlus,
-    SoftPlusBackward, Softmax, SoftmaxBackward, Sqrt, SqrtBackward, Stack, StackBackward,
-    StackBackwardLeft, Subtraction, SubtractionBackward, SubtractionBackwardLeft,
-    SubtractionBackwardRight, Sum, SumBackward, TanH, TanHBackward, Tensor, Transpose,
-    TransposeBackward, Unsqueeze, UnsqueezeBackward, Var, VarDiffHistory, VecMatMul, VecVecMul,
-    VectorMatrixMul, VectorMatrixMulBackward, VectorMatrixMulBackwardLeft, VectorVectorMul,
-    VectorVectorMulBackward, VectorVectorMulBackwardUnary, OPERATIONS_COUNTER,
+    check_mm, check_mv, check_vm, check_vv, AddScalar, AddScalarBackward, Addition,
+    AdditionBackward, AdditionBackwardUnary, ArcCos, ArcCosBackward, ArcSin, ArcSinBackward,
+    ArcTan, ArcTanBackward, ArcTanH, ArcTanHBackward, ArgMax, ArgMin, ArgSort, AvgPool,
+    AvgPoolBackward, Backward, BackwardHook, BatchNorm, BatchNormBackward, Cat, Ceil, Chunk,
+    ChunkBackward, Comparison, Concatenate, ConcatenateBackward, ConcatenateBackwardLeft, Cos,
+    CosBackward, CosH, CosHBackward, Data, Digamma, DigammaBackward, Division, DivisionBackward,
+    DivisionBackwardLeft, Dropout, DropoutBackward, DropoutMask, Ema, EmaBackward, Erf,
+    ErfBackward, Erfc, ErfcBackward, Exp, ExpBackward, Expm1, Expm1Backward, Flatten,
+    FlattenBackward, Floor, Forward, GLUBackward, Gather, GatherBackward, Gradient, Graph,
+    IndexData, IndexVar, Input, LeakyReLU, LeakyReLUBackward, Log1p, Log1pBackward, LogGamma,
+    LogGammaBackward, LogSoftmax, LogSoftmaxBackward, Logn, LognBackward, MaskedFill,
+    MaskedFillBackward, MaskedMaxPool, MaskedMaxPoolBackward, MaskedMeanPool,
+    MaskedMeanPoolBackward, MatMatMul, MatMatMulT, MatVecMul, MatrixMatrixMul,
+    MatrixMatrixMulBackward, MatrixMatrixMulBackwardLeft, MatrixMatrixMulT,
+    MatrixMatrixMulTBackward, MatrixMatrixMulTBackwardLeft, MatrixVectorMul,
+    MatrixVectorMulBackward, MatrixVectorMulBackwardLeft, MaxPool, MaxPoolBackward, Maximum,
+    MaximumBackward, MaximumBackwardUnary, Mean, MeanBackward, Minimum, MinimumBackward,
+    MinimumBackwardUnary, MulScalar, MulScalarBackward, MultiConcatenate, MultiConcatenateBackward,
+    MultiData, MultiGradient, MultiStack, MultiStackBackward, Multiplication,
+    MultiplicationBackward, MultiplicationBackwardUnary, Negation, NegationBackward, Norm,
+    NormBackward, Output, OutputBackward, Overwrite, Param, Pow, PowScalar, PowScalarBackward,
+    Power, PowerBackward, RawParam, ReLU, ReLUBackward, Reciprocal, ReciprocalBackward, Renorm,
+    RenormBackward, RollingMean, RollingMeanBackward, Round, RoundBackward, Rsqrt, RsqrtBackward,
+    Scatter, ScatterBackward, SegmentReduction, ShapeError, Shaped, Sigmoid, SigmoidBackward, Sign,
+    SignBackward, Sin, SinBackward, SinH, SinHBackward, SoftPlus, SoftPlusBackward, Softmax,
+    SoftmaxBackward, Sqrt, SqrtBackward, Stack, StackBackward, StackBackwardLeft, Subtraction,
+    SubtractionBackward, SubtractionBackwardLeft, Sum, SumBackward, Tan, TanBackward, TanH,
+    TanHBackward, Tensor, TensorPower, TensorPowerBackward, TensorPowerBackwardLeft, TopK,
+    Transpose, TransposeBackward, Unsqueeze, UnsqueezeBackward, Var, VarDiffHistory, VecMatMul,
+    VecVecMul, VectorMatrixMul, VectorMatrixMulBackward, VectorMatrixMulBackwardLeft,
+    VectorVectorMul, VectorVectorMulBackward, VectorVectorMulBackwardUnary, WeightedBinCount,
+    WeightedBinCountBackward, GLU, OPERATIONS_COUNTER,
 };
 use crate::nn::Register;
-use ndarray::{DimMax, Dimension, IntoDimension, Ix0, Ix1, Ix2, RemoveAxis};
+use ndarray::{ArrayBase, DimMax, Dimension, IntoDimension, Ix0, Ix1, Ix2, Ix3, Ix4, RemoveAxis};
 #[cfg(feature = "serialize")]
 use serde::{
     de::{Deserialize, Deserializer},
     ser::{Serialize, Serializer},
 };
 use std::{
-    cell::{Cell, Ref, RefMut},
+    cell::{Cell, Ref, RefCell, RefMut},
     fmt::{Debug, Display},
     ops::{Add, Div, Mul, Neg, Sub},
     rc::Rc,
@@ -72,14 +87,53 @@ where
     T: Data + Forward + 'static,
     U: Gradient + Backward + 'static,
 {
-    pub(crate) fn from(node: U, mut past: VarDiffHistory, var: Var<T>) -> VarDiff<T, U> {
+    pub(crate) fn from(node: U, mut past: VarDiffHistory, mut var: Var<T>) -> VarDiff<T, U> {
+        var.past.set_requires_grad();
+        let id = unsafe { OPERATIONS_COUNTER.next() };
         let node = Rc::new(node);
-        past.append_backward(unsafe { OPERATIONS_COUNTER.next() }, node.clone());
+        past.append_node(id, &node, &var.node);
+        past.append_backward(id, node.clone());
 
         VarDiff { var, node, past }
     }
 }
 
+impl<T: ?Sized, U> VarDiff<Output<T>, OutputBackward<U>>
+where
+    T: MultiData + 'static,
+    U: MultiGradient<Dim = T::Dim> + 'static,
+{
+    /// Returns the differentiable outputs of `node`, whose data are the ones of `vars`.
+    pub(crate) fn outputs(
+        node: U,
+        mut past: VarDiffHistory,
+        vars: Vec<Var<Output<T>>>,
+    ) -> Vec<Self> {
+        let node = Rc::new(node);
+
+        // The gradients of all the outputs are back-propagated at once, after them.
+        past.append_backward(unsafe { OPERATIONS_COUNTER.next() }, node.clone());
+
+        vars.into_iter()
+            .enumerate()
+            .map(|(i, var)| VarDiff::from(OutputBackward::new(node.clone(), i), past.clone(), var))
+            .collect()
+    }
+}
+
+impl<D: Dimension> VarDiff<Input<D>, super::InputBackward<D>> {
+    /// Copies `data` into `self`.
+    ///
+    /// See also [`Var::set_data()`].
+    ///
+    /// # Panics
+    ///
+    /// If the shape of `data` differs from the one of `self`.
+    pub fn set_data<S: ndarray::Data<Elem = f32>>(&self, data: ArrayBase<S, D>) {
+        self.var.set_data(data)
+    }
+}
+
 impl<D, T, U> VarDiff<T, U>
 where
     D: Dimension,
@@ -119,6 +173,21 @@ where
         self.var.node.data_mut()
     }
 
+    /// Returns the only element of the data inside `self`, such as the value of a loss computed
+    /// with [`.forward()`](VarDiff::forward()).
+    ///
+    /// # Panics
+    ///
+    /// If the data doesn't have exactly one element.
+    pub fn item(&self) -> f32 {
+        self.var.item()
+    }
+
+    /// Returns the elements of the data inside `self` in row-major order.
+    pub fn to_vec(&self) -> Vec<f32> {
+        self.var.to_vec()
+    }
+
     /// Returns an immutable reference to the gradient inside `self`.
     ///
     /// At the differentiable variable's creation the gradient is filled with zeros. You can
@@ -167,6 +236,14 @@ where
         }
     }
 
+    /// Marks all the nodes from the leaves of the graph to `self` as not computed, so that the
+    /// next call to [`.forward()`](VarDiff::forward()) evaluates all of them again.
+    ///
+    /// See also [`Var::reset()`].
+    pub fn reset(&self) {
+        self.var.reset();
+    }
+
     /// Back-propagates through the computational graph and populates the gradients of the
     /// differentiable leaves that are ancestors of `self`. Before back-propagating the gradient
     /// of `self` is seeded with `seed`, thus, the leaves' gradients will be scaled accordingly.
@@ -176,15 +253,84 @@ where
     ///
     /// The leaves whose gradients are populated by this method are also those referred by the
     /// vector of [`Param`] returned by [`.parameters()`](VarDiff::parameters()).
+    ///
+    /// The graph is retained after the backward pass, so this method can be called multiple
+    /// times, even on different variables that share part of their history, such as the losses
+    /// of a multi-task model. The gradients of the intermediate variables are recomputed from
+    /// scratch at each call, while the ones of the leaves are **accumulated**. Use
+    /// [`.zero_grad()`](VarDiff::zero_grad()) to reset them.
+    ///
+    /// ```
+    /// let x = neuronika::ones(3).requires_grad();
+    /// let shared = x.clone() * 2.;
+    /// let first = shared.clone().sum();
+    /// let second = (shared * 3.).sum();
+    ///
+    /// first.forward();
+    /// second.forward();
+    ///
+    /// first.backward(1.);
+    /// assert_eq!(*x.grad(), ndarray::array![2., 2., 2.]);
+    ///
+    /// second.backward(1.);
+    /// assert_eq!(*x.grad(), ndarray::array![8., 8., 8.]);
+    ///
+    /// x.zero_grad();
+    /// second.backward(1.);
+    /// assert_eq!(*x.grad(), ndarray::array![6., 6., 6.]);
+    /// ```
     pub fn backward(&self, seed: f32) {
-        debug_assert!(!self.past.is_empty());
-
+        self.past.fit_gradients();
         self.node.gradient_mut().fill(seed);
-        self.past.prepare_buffer();
-        let buffer = self.past.buffer();
-        for node in buffer.iter().rev() {
-            node.backward();
+        self.propagate();
+    }
+
+    /// Back-propagates through the computational graph seeding the gradient of `self` with
+    /// `gradient`, which must have the same shape of the variable's data.
+    ///
+    /// This allows to start the backward pass from non-scalar variables with a custom cotangent,
+    /// thus computing vector-Jacobian products. In all other respects this method behaves as
+    /// [`.backward()`](VarDiff::backward()).
+    ///
+    /// # Panics
+    ///
+    /// If the shape of `gradient` differs from the one of `self`.
+    ///
+    /// ```
+    /// use ndarray::array;
+    ///
+    /// let x = neuronika::from_ndarray(array![1., 2., 3.]).requires_grad();
+    /// let y = x.clone() * x.clone();
+    ///
+    /// y.forward();
+    /// y.backward_with(array![1., 0., -1.]);
+    ///
+    /// assert_eq!(*x.grad(), array![2., 0., -6.]);
+    /// ```
+    pub fn backward_with(&self, gradient: Tensor<U::Dim>) {
+        self.past.fit_gradients();
+        {
+            let mut seed = self.node.gradient_mut();
+            assert_eq!(
+                seed.shape(),
+                gradient.shape(),
+                "error: the seed gradient of shape {:?} does not match the variable of shape {:?}.",
+                gradient.shape(),
+                seed.shape()
+            );
+            seed.assign(&gradient);
         }
+        self.propagate();
+    }
+
+    /// Runs the backward pass from `self`, whose gradient has already been seeded.
+    fn propagate(&self) {
+        debug_assert!(!self.past.is_empty());
+
+        // The gradients of the intermediate nodes may hold the results of a previous backward
+        // pass over a shared portion of the graph, they must be overwritten.
+        self.past.set_overwrite();
+        self.past.backward();
 
         debug_assert_eq!(
             self.var.past.len(),
@@ -215,6 +361,14 @@ where
         }
     }
 
+    /// Returns a non-differentiable variable sharing the data of `self`.
+    ///
+    /// The returned variable still computes `self` and its ancestors during the forward pass,
+    /// but no gradient will flow back through it.
+    pub fn detach(&self) -> Var<T> {
+        self.var.clone()
+    }
+
     /// Disables gradient computation and de-allocates the gradient for `self` and all of its
     /// ancestors.
     pub fn no_grad(&self) {
@@ -254,6 +408,110 @@ where
         // Status is shared.
         self.var.eval();
     }
+
+    /// Compiles `self` for inference, returning a forward-only variable with the same data.
+    ///
+    /// All the backward nodes of the computational graph are dropped together with the overwrite
+    /// bookkeeping, and the gradients of `self` and of all its ancestors are de-allocated. The
+    /// resulting variable is set in evaluation mode.
+    ///
+    /// The gradients of the differentiable leaves that are still referred elsewhere, such as the
+    /// parameters of a model, can be re-allocated with [`.with_grad()`](VarDiff::with_grad()).
+    ///
+    /// ```
+    /// use ndarray::array;
+    ///
+    /// let w = neuronika::from_ndarray(array![[1., 2.], [3., 4.]]).requires_grad();
+    /// let x = neuronika::from_ndarray(array![1., -1.]);
+    /// let y = w.mv(x.clone()).relu().dropout(0.5);
+    ///
+    /// let y = y.into_inference();
+    /// *x.data_mut() = array![1., 1.];
+    /// y.forward();
+    ///
+    /// assert_eq!(*y.data(), array![3., 7.]);
+    /// ```
+    pub fn into_inference(self) -> Var<T> {
+        self.no_grad();
+        self.var.eval();
+
+        self.var
+    }
+
+    /// Registers a forward hook on `self` and returns the hooked differentiable variable.
+    ///
+    /// See also [`Var::register_forward_hook()`].
+    ///
+    /// # Arguments
+    ///
+    /// `hook` - function to be called with the data of `self`.
+    pub fn register_forward_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&Tensor<T::Dim>) + 'static,
+    {
+        self.var = self.var.register_forward_hook(hook);
+        self
+    }
+
+    /// Registers a backward hook on `self` and returns the hooked differentiable variable.
+    ///
+    /// The hook is called with the gradient of `self` every time it is back-propagated through
+    /// by a backward pass issued on the returned differentiable variable or on any of its
+    /// descendants. Variables computed from `self` before the registration of the hook don't
+    /// contribute to the gradient the hook is called with.
+    ///
+    /// # Arguments
+    ///
+    /// `hook` - function to be called with the gradient of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{cell::Cell, rc::Rc};
+    ///
+    /// let seen = Rc::new(Cell::new(0.));
+    /// let x = neuronika::ones(3).requires_grad();
+    /// let hooked = {
+    ///     let seen = seen.clone();
+    ///     (x * 2.).register_backward_hook(move |grad| seen.set(grad.sum()))
+    /// };
+    ///
+    /// let y = hooked.sum();
+    /// y.forward();
+    /// y.backward(1.);
+    /// assert_eq!(seen.get(), 3.);
+    /// ```
+    pub fn register_backward_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&Tensor<U::Dim>) + 'static,
+    {
+        let node = Rc::new(BackwardHook::new(self.node.clone(), Box::new(hook)));
+        self.past
+            .append_backward(unsafe { OPERATIONS_COUNTER.next() }, node);
+
+        self
+    }
+
+    /// Renders the computational graph of `self` in the
+    /// [Graphviz DOT language](https://graphviz.org/doc/info/lang.html).
+    ///
+    /// See also [`Var::to_dot()`].
+    pub fn to_dot(&self) -> String {
+        self.var.to_dot()
+    }
+
+    /// Turns `self` into a handle to its whole computational graph.
+    ///
+    /// See also [`Graph`].
+    pub fn into_graph(self) -> Graph {
+        let node = self.node;
+        let seed = move |seed| {
+            node.gradient_mut().fill(seed);
+            node.set_overwrite(false);
+        };
+
+        Graph::new(self.var.past, Some(self.past), vec![Box::new(seed)])
+    }
 }
 
 impl<T: ?Sized, U: ?Sized> VarDiff<T, U>
@@ -281,6 +539,34 @@ where
     {
         VecVecMul::vv(self, rhs)
     }
+
+    /// Checked version of [`.vm()`](Self::vm()).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ShapeError`] if the length of `self` differs from the number of rows of `rhs`.
+    pub fn try_vm<Rhs>(self, rhs: Rhs) -> Result<<Self as VecMatMul<Rhs>>::Output, ShapeError>
+    where
+        Self: VecMatMul<Rhs>,
+        Rhs: Shaped,
+    {
+        check_vm(&self.shape(), &rhs.shape())?;
+        Ok(VecMatMul::vm(self, rhs))
+    }
+
+    /// Checked version of [`.vv()`](Self::vv()).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ShapeError`] if the lengths of `self` and `rhs` differ.
+    pub fn try_vv<Rhs>(self, rhs: Rhs) -> Result<<Self as VecVecMul<Rhs>>::Output, ShapeError>
+    where
+        Self: VecVecMul<Rhs>,
+        Rhs: Shaped,
+    {
+        check_vv(&self.shape(), &rhs.shape())?;
+        Ok(VecVecMul::vv(self, rhs))
+    }
 }
 
 impl<T, U> VarDiff<T, U>
@@ -320,6 +606,51 @@ where
     {
         MatVecMul::mv(self, rhs)
     }
+
+    /// Checked version of [`.mm()`](Self::mm()).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ShapeError`] if the number of columns of `self` differs from the number of
+    /// rows of `rhs`.
+    pub fn try_mm<Rhs>(self, rhs: Rhs) -> Result<<Self as MatMatMul<Rhs>>::Output, ShapeError>
+    where
+        Self: MatMatMul<Rhs>,
+        Rhs: Shaped,
+    {
+        check_mm(&self.shape(), &rhs.shape(), false)?;
+        Ok(MatMatMul::mm(self, rhs))
+    }
+
+    /// Checked version of [`.mm_t()`](Self::mm_t()).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ShapeError`] if the number of columns of `self` differs from the number of
+    /// columns of `rhs`.
+    pub fn try_mm_t<Rhs>(self, rhs: Rhs) -> Result<<Self as MatMatMulT<Rhs>>::Output, ShapeError>
+    where
+        Self: MatMatMulT<Rhs>,
+        Rhs: Shaped,
+    {
+        check_mm(&self.shape(), &rhs.shape(), true)?;
+        Ok(MatMatMulT::mm_t(self, rhs))
+    }
+
+    /// Checked version of [`.mv()`](Self::mv()).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ShapeError`] if the number of columns of `self` differs from the length of
+    /// `rhs`.
+    pub fn try_mv<Rhs>(self, rhs: Rhs) -> Result<<Self as MatVecMul<Rhs>>::Output, ShapeError>
+    where
+        Self: MatVecMul<Rhs>,
+        Rhs: Shaped,
+    {
+        check_mv(&self.shape(), &rhs.shape())?;
+        Ok(MatVecMul::mv(self, rhs))
+    }
 }
 
 impl<T: ?Sized, U: ?Sized> VarDiff<T, U>
@@ -367,6 +698,18 @@ where
             .collect()
     }
 
+    /// Zeroes the gradients of all the differentiable leaves that are ancestors of the variable,
+    /// i.e. of those referred by the vector of [`Param`] returned by
+    /// [`.parameters()`](VarDiff::parameters()).
+    ///
+    /// As [`.backward()`](VarDiff::backward()) accumulates the gradients of the leaves, this must
+    /// be called between two backward passes whose contributions must not be summed.
+    pub fn zero_grad(&self) {
+        for mut param in self.parameters() {
+            param.grad.fill(0.);
+        }
+    }
+
     /// Returns the sum of all elements in `self`.
     pub fn sum(self) -> VarDiff<Sum<T>, SumBackward<U>> {
         let node = SumBackward::new(self.node);
@@ -379,6 +722,62 @@ where
         VarDiff::from(node, self.past, self.var.mean())
     }
 
+    /// Computes the `p`-norm of all the elements of `self` and returns a differentiable scalar
+    /// variable with the result. See [`Var::norm()`] for more details.
+    ///
+    /// The gradient of an element is *sign(xᵢ) (|xᵢ| / norm(x))^(p - 1)*, the subgradient being
+    /// zero where the element or the norm are zero.
+    ///
+    /// # Panics
+    ///
+    /// If `p` is smaller than 1.
+    #[allow(clippy::type_complexity)]
+    pub fn norm(self, p: f32) -> VarDiff<Norm<T, Ix0>, NormBackward<U, T, Norm<T, Ix0>>> {
+        let operand = self.var.node.clone();
+        let var = self.var.norm(p);
+        let node = NormBackward::new(self.node, operand, var.node.clone(), p, None);
+        VarDiff::from(node, self.past, var)
+    }
+
+    /// Computes the `p`-norms of the lanes of `self` along `axis` and returns a differentiable
+    /// variable with the result. See [`Var::norm_axis()`] and [`VarDiff::norm()`] for more
+    /// details.
+    ///
+    /// # Panics
+    ///
+    /// If `p` is smaller than 1, if `axis` is out of bounds or if the dimensionality of `D` does
+    /// not agree with `keepdim`.
+    pub fn norm_axis<D: Dimension>(
+        self,
+        p: f32,
+        axis: usize,
+        keepdim: bool,
+    ) -> VarDiff<Norm<T, D>, NormBackward<U, T, Norm<T, D>>> {
+        let operand = self.var.node.clone();
+        let var = self.var.norm_axis(p, axis, keepdim);
+        let node = NormBackward::new(self.node, operand, var.node.clone(), p, Some(axis));
+        VarDiff::from(node, self.past, var)
+    }
+
+    /// Rescales each sub-tensor of `self` along `axis` whose `p`-norm exceeds `max_norm`, so that
+    /// its norm becomes `max_norm`, and returns a differentiable variable with the result. See
+    /// [`Var::renorm()`] for more details.
+    ///
+    /// The gradient accounts for the dependency of the scaling factor on the sub-tensor.
+    ///
+    /// # Panics
+    ///
+    /// If `p` is smaller than 1 or `axis` is out of bounds.
+    pub fn renorm(
+        self,
+        p: f32,
+        axis: usize,
+        max_norm: f32,
+    ) -> VarDiff<Renorm<T>, RenormBackward<U, T>> {
+        let node = RenormBackward::new(self.node, self.var.node.clone(), p, axis, max_norm);
+        VarDiff::from(node, self.past, self.var.renorm(p, axis, max_norm))
+    }
+
     /// Takes the power of each element in `self` with exponent `exp` and returns a differentiable
     /// variable with the result.
     pub fn pow(self, exp: i32) -> VarDiff<Power<T>, PowerBackward<U, T>> {
@@ -393,6 +792,67 @@ where
         VarDiff::from(node, self.past, var)
     }
 
+    /// Takes the sign of each element in `self` and returns a differentiable variable with the
+    /// result.
+    ///
+    /// *Sign(x) = 1 if x >= 0, -1 otherwise*
+    ///
+    /// As the derivative of the sign is zero almost everywhere, the gradient is computed with the
+    /// *straight-through estimator*: it's passed through unchanged where *|x| <= 1* and zeroed
+    /// elsewhere.
+    ///
+    /// ```
+    /// let x = neuronika::from_ndarray(ndarray::array![-2., -0.5, 0.5, 3.]).requires_grad();
+    /// let y = x.clone().sign().sum();
+    ///
+    /// y.forward();
+    /// y.backward(1.);
+    ///
+    /// assert_eq!(*x.grad(), ndarray::array![0., 1., 1., 0.]);
+    /// ```
+    pub fn sign(self) -> VarDiff<Sign<T>, SignBackward<U, T>> {
+        let node = SignBackward::new(self.node, self.var.node.clone());
+        VarDiff::from(node, self.past, self.var.sign())
+    }
+
+    /// Rounds each element in `self` to the nearest integer and returns a differentiable variable
+    /// with the result. Half-way cases are rounded away from zero.
+    ///
+    /// The gradient is computed with the *straight-through estimator*, that treats the rounding as
+    /// the identity. A rounding that blocks the gradient, whose true value is zero almost
+    /// everywhere, is obtained with `.detach().round()`.
+    pub fn round(self) -> VarDiff<Round<T>, RoundBackward<U>> {
+        let node = RoundBackward::new(self.node);
+        VarDiff::from(node, self.past, self.var.round())
+    }
+
+    /// Rounds each element in `self` down to the nearest integer and returns a variable with the
+    /// result.
+    ///
+    /// The floor has a zero gradient almost everywhere, hence the result is not differentiable and
+    /// no gradient flows back through it. It can nonetheless be combined with differentiable
+    /// variables, e.g. to compute the fractional part of `self`.
+    ///
+    /// ```
+    /// let x = neuronika::from_ndarray(ndarray::array![-1.5, 0.2, 2.7]).requires_grad();
+    ///
+    /// let fractional = x.clone() - x.clone().floor();
+    /// fractional.forward();
+    /// fractional.backward(1.);
+    /// assert_eq!(*x.grad(), ndarray::array![1., 1., 1.]);
+    /// ```
+    pub fn floor(self) -> Var<Floor<T>> {
+        self.var.floor()
+    }
+
+    /// Rounds each element in `self` up to the nearest integer and returns a variable with the
+    /// result.
+    ///
+    /// The result is not differentiable. See [`VarDiff::floor()`] for more details.
+    pub fn ceil(self) -> Var<Ceil<T>> {
+        self.var.ceil()
+    }
+
     /// Applies the *rectified linear unit* element-wise and and returns a differentiable
     /// variable with the result.
     ///
@@ -414,9 +874,31 @@ where
     /// Applies the *softplus* element-wise and returns a differentiable variable with the result.
     ///
     /// *Softplus(x) = log(1 + exp(x))*
+    ///
+    /// Where *x* is larger than 20 the function reverts to the identity, and so does its gradient.
+    /// See also [`Var::softplus()`].
     pub fn softplus(self) -> VarDiff<SoftPlus<T>, SoftPlusBackward<U, T>> {
-        let node = SoftPlusBackward::new(self.node, self.var.node.clone());
-        VarDiff::from(node, self.past, self.var.softplus())
+        self.softplus_with(1., 20.)
+    }
+
+    /// Applies the *softplus* with parameter `beta` element-wise and returns a differentiable
+    /// variable with the result.
+    ///
+    /// *Softplus(x) = 1 / beta * log(1 + exp(beta * x))*
+    ///
+    /// Where *beta * x* is larger than `threshold` the function reverts to the identity, and so
+    /// does its gradient. See also [`Var::softplus_with()`].
+    ///
+    /// # Panics
+    ///
+    /// If `beta` is not positive.
+    pub fn softplus_with(
+        self,
+        beta: f32,
+        threshold: f32,
+    ) -> VarDiff<SoftPlus<T>, SoftPlusBackward<U, T>> {
+        let node = SoftPlusBackward::new(self.node, self.var.node.clone(), beta, threshold);
+        VarDiff::from(node, self.past, self.var.softplus_with(beta, threshold))
     }
 
     /// Applies the *sigmoid* element-wise and returns a differentiable variable with the result.
@@ -426,130 +908,794 @@ where
         VarDiff::from(node, self.past, var)
     }
 
-    /// Applies the *tanh* element-wise and returns a differentiable variable with the result.
-    pub fn tanh(self) -> VarDiff<TanH<T>, TanHBackward<U, TanH<T>>> {
-        let var = self.var.tanh();
-        let node = TanHBackward::new(self.node, var.node.clone());
-        VarDiff::from(node, self.past, var)
+    /// Applies the *tanh* element-wise and returns a differentiable variable with the result.
+    pub fn tanh(self) -> VarDiff<TanH<T>, TanHBackward<U, TanH<T>>> {
+        let var = self.var.tanh();
+        let node = TanHBackward::new(self.node, var.node.clone());
+        VarDiff::from(node, self.past, var)
+    }
+
+    /// Applies the *natural logarithm* element-wise and returns a differentiable variable with the
+    /// result.
+    pub fn ln(self) -> VarDiff<Logn<T>, LognBackward<U, T>> {
+        let node = LognBackward::new(self.node, self.var.node.clone());
+        VarDiff::from(node, self.past, self.var.ln())
+    }
+
+    /// Applies the *exponential* element-wise and returns a differentiable variable with the
+    /// result.
+    pub fn exp(self) -> VarDiff<Exp<T>, ExpBackward<U, Exp<T>>> {
+        let var = self.var.exp();
+        let node = ExpBackward::new(self.node, var.node.clone());
+        VarDiff::from(node, self.past, var)
+    }
+
+    /// Applies the *sine* element-wise and returns a differentiable variable with the result.
+    pub fn sin(self) -> VarDiff<Sin<T>, SinBackward<U, T>> {
+        let node = SinBackward::new(self.node, self.var.node.clone());
+        VarDiff::from(node, self.past, self.var.sin())
+    }
+
+    /// Applies the *cosine* element-wise and returns a differentiable variable with the result.
+    pub fn cos(self) -> VarDiff<Cos<T>, CosBackward<U, T>> {
+        let node = CosBackward::new(self.node, self.var.node.clone());
+        VarDiff::from(node, self.past, self.var.cos())
+    }
+
+    /// Applies the *tangent* element-wise and returns a differentiable variable with the result.
+    pub fn tan(self) -> VarDiff<Tan<T>, TanBackward<U, T>> {
+        let node = TanBackward::new(self.node, self.var.node.clone());
+        VarDiff::from(node, self.past, self.var.tan())
+    }
+
+    /// Applies the *arcsine* element-wise and returns a differentiable variable with the result.
+    pub fn asin(self) -> VarDiff<ArcSin<T>, ArcSinBackward<U, T>> {
+        let node = ArcSinBackward::new(self.node, self.var.node.clone());
+        VarDiff::from(node, self.past, self.var.asin())
+    }
+
+    /// Applies the *arccosine* element-wise and returns a differentiable variable with the result.
+    pub fn acos(self) -> VarDiff<ArcCos<T>, ArcCosBackward<U, T>> {
+        let node = ArcCosBackward::new(self.node, self.var.node.clone());
+        VarDiff::from(node, self.past, self.var.acos())
+    }
+
+    /// Applies the *arctangent* element-wise and returns a differentiable variable with the result.
+    pub fn atan(self) -> VarDiff<ArcTan<T>, ArcTanBackward<U, T>> {
+        let node = ArcTanBackward::new(self.node, self.var.node.clone());
+        VarDiff::from(node, self.past, self.var.atan())
+    }
+
+    /// Applies the *hyperbolic sine* element-wise and returns a differentiable variable with the result.
+    pub fn sinh(self) -> VarDiff<SinH<T>, SinHBackward<U, T>> {
+        let node = SinHBackward::new(self.node, self.var.node.clone());
+        VarDiff::from(node, self.past, self.var.sinh())
+    }
+
+    /// Applies the *hyperbolic cosine* element-wise and returns a differentiable variable with the result.
+    pub fn cosh(self) -> VarDiff<CosH<T>, CosHBackward<U, T>> {
+        let node = CosHBackward::new(self.node, self.var.node.clone());
+        VarDiff::from(node, self.past, self.var.cosh())
+    }
+
+    /// Applies the *inverse hyperbolic tangent* element-wise and returns a differentiable variable with the result.
+    pub fn atanh(self) -> VarDiff<ArcTanH<T>, ArcTanHBackward<U, T>> {
+        let node = ArcTanHBackward::new(self.node, self.var.node.clone());
+        VarDiff::from(node, self.past, self.var.atanh())
+    }
+
+    /// Applies the *error function* element-wise and returns a differentiable variable with the
+    /// result.
+    ///
+    /// *erf(x) = 2 / sqrt(π) ∫₀ˣ exp(-t²) dt*
+    pub fn erf(self) -> VarDiff<Erf<T>, ErfBackward<U, T>> {
+        let node = ErfBackward::new(self.node, self.var.node.clone());
+        VarDiff::from(node, self.past, self.var.erf())
+    }
+
+    /// Applies the *complementary error function* element-wise and returns a differentiable
+    /// variable with the result.
+    ///
+    /// *erfc(x) = 1 - erf(x)*, computed without the loss of precision of the subtraction for
+    /// large *x*.
+    pub fn erfc(self) -> VarDiff<Erfc<T>, ErfcBackward<U, T>> {
+        let node = ErfcBackward::new(self.node, self.var.node.clone());
+        VarDiff::from(node, self.past, self.var.erfc())
+    }
+
+    /// Applies the natural logarithm of the absolute value of the *gamma function* element-wise
+    /// and returns a differentiable variable with the result.
+    ///
+    /// The result is infinite at the non-positive integers, where the gamma function has poles.
+    pub fn lgamma(self) -> VarDiff<LogGamma<T>, LogGammaBackward<U, T>> {
+        let node = LogGammaBackward::new(self.node, self.var.node.clone());
+        VarDiff::from(node, self.past, self.var.lgamma())
+    }
+
+    /// Applies the *digamma function*, the derivative of the logarithm of the gamma function,
+    /// element-wise and returns a differentiable variable with the result.
+    ///
+    /// The function is not defined at the non-positive integers.
+    pub fn digamma(self) -> VarDiff<Digamma<T>, DigammaBackward<U, T>> {
+        let node = DigammaBackward::new(self.node, self.var.node.clone());
+        VarDiff::from(node, self.past, self.var.digamma())
+    }
+
+    /// Applies *ln(1 + x)* element-wise and returns a differentiable variable with the result.
+    ///
+    /// The result is accurate also for values of *x* close to zero, where `1 + x` would round to
+    /// one.
+    pub fn log1p(self) -> VarDiff<Log1p<T>, Log1pBackward<U, T>> {
+        let node = Log1pBackward::new(self.node, self.var.node.clone());
+        VarDiff::from(node, self.past, self.var.log1p())
+    }
+
+    /// Applies *exp(x) - 1* element-wise and returns a differentiable variable with the result.
+    ///
+    /// The result is accurate also for values of *x* close to zero, where the subtraction would
+    /// cancel the significant digits.
+    pub fn expm1(self) -> VarDiff<Expm1<T>, Expm1Backward<U, T>> {
+        let node = Expm1Backward::new(self.node, self.var.node.clone());
+        VarDiff::from(node, self.past, self.var.expm1())
+    }
+
+    /// Takes the reciprocal *1 / x* element-wise and returns a differentiable variable with the
+    /// result.
+    pub fn reciprocal(self) -> VarDiff<Reciprocal<T>, ReciprocalBackward<U, T>> {
+        let node = ReciprocalBackward::new(self.node, self.var.node.clone());
+        VarDiff::from(node, self.past, self.var.reciprocal())
+    }
+
+    /// Takes the reciprocal of the square root *1 / sqrt(x)* element-wise and returns a
+    /// differentiable variable with the result.
+    pub fn rsqrt(self) -> VarDiff<Rsqrt<T>, RsqrtBackward<U, T>> {
+        let node = RsqrtBackward::new(self.node, self.var.node.clone());
+        VarDiff::from(node, self.past, self.var.rsqrt())
+    }
+
+    /// Applies the *softmax* to `self` and returns a differentiable variable with the result.
+    ///
+    /// The *softmax* is applied to all slices along `axis`, and will re-scale them so
+    ///  that the elements lie in the range *[0, 1]* and sum to 1.0.
+    pub fn softmax(self, axis: usize) -> VarDiff<Softmax<T>, SoftmaxBackward<U, Softmax<T>>> {
+        let var = self.var.softmax(axis);
+        let node = SoftmaxBackward::new(self.node, var.node.clone(), axis);
+        VarDiff::from(node, self.past, var)
+    }
+
+    /// Applies the *log-softmax* to `self` and returns a differentiable variable with the result.
+    ///
+    /// Applies a softmax followed by a logarithm. While mathematically equivalent to
+    /// *log(softmax(x))*, doing these two operations separately is slower, and numerically
+    /// unstable. This function uses an alternative formulation to compute the output and
+    /// gradient correctly.
+    ///
+    /// See also [`.softmax()`].
+    ///
+    /// [`.softmax()`]: VarDiff::softmax()
+    pub fn log_softmax(
+        self,
+        axis: usize,
+    ) -> VarDiff<LogSoftmax<T>, LogSoftmaxBackward<U, LogSoftmax<T>>> {
+        let var = self.var.log_softmax(axis);
+        let node = LogSoftmaxBackward::new(self.node, var.node.clone(), axis);
+        VarDiff::from(node, self.past, var)
+    }
+
+    /// Applies the *gated linear unit* to `self` and returns a differentiable variable with the
+    /// result.
+    ///
+    /// `self` is split in two halves *a* and *b* along `axis`, and *a* is gated by the sigmoid
+    /// of *b*, that is, *a ⊙ σ(b)* is computed.
+    ///
+    /// # Panics
+    ///
+    /// If the size of `axis` is odd.
+    pub fn glu(self, axis: usize) -> VarDiff<GLU<T>, GLUBackward<U, T>> {
+        let node = GLUBackward::new(self.node, self.var.node.clone(), axis);
+        VarDiff::from(node, self.past, self.var.glu(axis))
+    }
+
+    /// Computes the *exponential moving average* of `self` along `axis` and returns a
+    /// differentiable variable with the result. See [`Var::ema()`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// If `alpha` doesn't lie in *(0, 1]*.
+    pub fn ema(self, axis: usize, alpha: f32) -> VarDiff<Ema<T>, EmaBackward<U>> {
+        let var = self.var.ema(axis, alpha);
+        let node = EmaBackward::new(self.node, axis, alpha);
+        VarDiff::from(node, self.past, var)
+    }
+
+    /// Computes the trailing *moving average* of `self` along `axis`, over windows of `window`
+    /// elements, and returns a differentiable variable with the result. See
+    /// [`Var::rolling_mean()`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// If `window` is zero.
+    pub fn rolling_mean(
+        self,
+        axis: usize,
+        window: usize,
+    ) -> VarDiff<RollingMean<T>, RollingMeanBackward<U>> {
+        let var = self.var.rolling_mean(axis, window);
+        let node = RollingMeanBackward::new(self.node, axis, window);
+        VarDiff::from(node, self.past, var)
+    }
+
+    /// Returns a differentiable variable equivalent to `self` with its dimensions reversed.
+    pub fn t(self) -> VarDiff<Transpose<T>, TransposeBackward<U>> {
+        let node = TransposeBackward::new(self.node);
+        VarDiff::from(node, self.past, self.var.t())
+    }
+
+    /// Applies *dropout* to `self` and returns a differentiable variable with the result.
+    ///
+    /// It is strongly suggested to use [`nn::Dropout`] instead of this method when working with
+    /// neural networks.
+    ///
+    /// During training, randomly zeroes some of the elements of `self` with probability *p* using
+    /// samples from a Bernoulli distribution. Each channel will be zeroed out independently on
+    /// every forward call.
+    ///
+    /// This has proven to be an effective technique for regularization and preventing the
+    /// co-adaptation of neurons as described in the paper
+    /// [Improving neural networks by preventing co-adaptation of feature detectors](https://arxiv.org/abs/1207.0580).
+    ///
+    /// Furthermore, the outputs are scaled by a factor of 1/(1 - p) during training. This means
+    /// that during evaluation the resulting variable simply computes an identity function.
+    ///
+    /// [`nn::Dropout`]: crate::nn::Dropout
+    pub fn dropout(self, p: f64) -> VarDiff<Dropout<T>, DropoutBackward<U, T>> {
+        self.dropout_with_status(p, Rc::new(Cell::new(true)), Rc::default())
+    }
+
+    /// Creates a new dropout differentiable variable sharing the status and the mask with its
+    /// internal val.
+    pub(crate) fn dropout_with_status(
+        self,
+        p: f64,
+        status: Rc<Cell<bool>>,
+        mask: Rc<DropoutMask>,
+    ) -> VarDiff<Dropout<T>, DropoutBackward<U, T>> {
+        let var = self.var.dropout_with_status(p, status, mask);
+        let node = DropoutBackward::new(self.node, var.node.clone(), p, var.node.status());
+        VarDiff::from(node, self.past, var)
+    }
+
+    /// Creates a new dropout differentiable variable zeroing whole samples, sharing the status
+    /// and the mask with its internal var.
+    pub(crate) fn drop_path_with_status(
+        self,
+        p: f64,
+        status: Rc<Cell<bool>>,
+        mask: Rc<DropoutMask>,
+    ) -> VarDiff<Dropout<T>, DropoutBackward<U, T>> {
+        let var = self.var.drop_path_with_status(p, status, mask);
+        let node = DropoutBackward::new(self.node, var.node.clone(), p, var.node.status());
+        VarDiff::from(node, self.past, var)
+    }
+
+    /// Splits `self` into a certain number of chunks of size `chunk_size` **skipping** the
+    /// remainder along each dimension that doesn’t fit evenly.
+    ///
+    /// The chunks are computed at once and share the same computation, as do their gradients.
+    ///
+    /// If the shape of `self` changes after the graph is built, the number of chunks and the size
+    /// of the skipped remainder along each axis stay the same, while the size of the chunks
+    /// follows the new shape.
+    #[allow(clippy::type_complexity)]
+    pub fn chunks<E>(
+        self,
+        chunk_size: E,
+    ) -> Vec<VarDiff<Output<Chunk<T>>, OutputBackward<ChunkBackward<U>>>>
+    where
+        E: IntoDimension<Dim = T::Dim>,
+    {
+        let chunk_shape = chunk_size.into_dimension();
+        let vars = self.var.chunks(chunk_shape.clone());
+        let node = ChunkBackward::new(self.node, chunk_shape, vars.len());
+
+        VarDiff::outputs(node, self.past, vars)
+    }
+
+    /// Returns a new differentiable variable with a dimension of size one inserted at the position
+    /// specified by `axis`.
+    pub fn unsqueeze(self, axis: usize) -> VarDiff<Unsqueeze<T>, UnsqueezeBackward<U>> {
+        VarDiff::from(
+            UnsqueezeBackward::new(self.node, axis),
+            self.past,
+            self.var.unsqueeze(axis),
+        )
+    }
+
+    /// Returns a new two-dimensional differentiable variable whose rows are the elements of each
+    /// entry of `self` along the first axis, laid out in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// If `self` is zero-dimensional.
+    pub fn flatten(self) -> VarDiff<Flatten<T>, FlattenBackward<U>> {
+        VarDiff::from(
+            FlattenBackward::new(self.node),
+            self.past,
+            self.var.flatten(),
+        )
+    }
+
+    /// Gathers the elements of `self` along `axis` at the positions given by `indices`.
+    ///
+    /// The result has the shape of `indices`, see [`Var::gather()`] for a complete description.
+    /// Each element of `self` receives the gradients of all the entries that gathered it, the
+    /// elements that are not gathered receive a zero gradient.
+    ///
+    /// ```
+    /// let log_probs = neuronika::from_ndarray(ndarray::array![[-0.1, -2.3], [-1.6, -0.2]])
+    ///     .requires_grad();
+    /// let labels = neuronika::indices(ndarray::array![[0], [1]]);
+    ///
+    /// let loss = -log_probs.clone().gather(1, labels).sum();
+    /// loss.forward();
+    /// loss.backward(1.);
+    /// assert_eq!(*log_probs.grad(), ndarray::array![[-1., 0.], [0., -1.]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds, or, during the forward pass, if an index is out of range or
+    /// `indices` is larger than `self` along any other axis.
+    pub fn gather<I: ?Sized>(
+        self,
+        axis: usize,
+        indices: IndexVar<I>,
+    ) -> VarDiff<Gather<T, I>, GatherBackward<U, I>>
+    where
+        I: IndexData<Dim = T::Dim> + 'static,
+    {
+        VarDiff::from(
+            GatherBackward::new(self.node, indices.node.clone(), axis),
+            self.past,
+            self.var.gather(axis, indices),
+        )
+    }
+
+    /// Sums the slices of `self` along `axis` that belong to the same segment.
+    ///
+    /// See [`Var::scatter_add()`] for a complete description. Each slice of `self` receives the
+    /// gradient of its segment.
+    ///
+    /// ```
+    /// let messages = neuronika::from_ndarray(ndarray::array![[1., 2.], [3., 4.], [5., 6.]])
+    ///     .requires_grad();
+    /// let targets = neuronika::indices(ndarray::array![1, 0, 1]);
+    ///
+    /// let aggregated = messages.clone().scatter_add(0, targets, 2);
+    /// let loss = (aggregated * neuronika::from_ndarray(ndarray::array![[1.], [2.]])).sum();
+    /// loss.forward();
+    /// loss.backward(1.);
+    /// assert_eq!(*messages.grad(), ndarray::array![[2., 2.], [1., 1.], [2., 2.]]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds, if the length of `indices` differs from the length of `self`
+    /// along `axis`, also after a change of shape, or, during the forward pass, if an index is out
+    /// of range.
+    pub fn scatter_add<I: ?Sized>(
+        self,
+        axis: usize,
+        indices: IndexVar<I>,
+        num_segments: usize,
+    ) -> VarDiff<Scatter<T, I>, ScatterBackward<U, T, I>>
+    where
+        I: IndexData<Dim = Ix1> + 'static,
+    {
+        self.scatter(axis, indices, num_segments, SegmentReduction::Add)
+    }
+
+    /// Averages the slices of `self` along `axis` that belong to the same segment.
+    ///
+    /// Each slice of `self` receives the gradient of its segment divided by the segment's size,
+    /// see [`Var::scatter_add()`] for more details.
+    pub fn scatter_mean<I: ?Sized>(
+        self,
+        axis: usize,
+        indices: IndexVar<I>,
+        num_segments: usize,
+    ) -> VarDiff<Scatter<T, I>, ScatterBackward<U, T, I>>
+    where
+        I: IndexData<Dim = Ix1> + 'static,
+    {
+        self.scatter(axis, indices, num_segments, SegmentReduction::Mean)
+    }
+
+    /// Takes the element-wise maximum of the slices of `self` along `axis` that belong to the
+    /// same segment.
+    ///
+    /// Only the first element attaining each maximum receives the gradient, see
+    /// [`Var::scatter_add()`] for more details.
+    pub fn scatter_max<I: ?Sized>(
+        self,
+        axis: usize,
+        indices: IndexVar<I>,
+        num_segments: usize,
+    ) -> VarDiff<Scatter<T, I>, ScatterBackward<U, T, I>>
+    where
+        I: IndexData<Dim = Ix1> + 'static,
+    {
+        self.scatter(axis, indices, num_segments, SegmentReduction::Max)
+    }
+
+    /// Reduces the slices of `self` along `axis` that belong to the same segment according to
+    /// `reduction`.
+    fn scatter<I: ?Sized>(
+        self,
+        axis: usize,
+        indices: IndexVar<I>,
+        num_segments: usize,
+        reduction: SegmentReduction,
+    ) -> VarDiff<Scatter<T, I>, ScatterBackward<U, T, I>>
+    where
+        I: IndexData<Dim = Ix1> + 'static,
+    {
+        let var = self.var.scatter(axis, indices, num_segments, reduction);
+        VarDiff::from(
+            ScatterBackward::new(self.node, var.node.clone()),
+            self.past,
+            var,
+        )
+    }
+
+    /// Returns the indices of the largest elements of `self` along `axis`, see
+    /// [`Var::argmax()`].
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds, or, during the forward pass, if `axis` is empty.
+    pub fn argmax(self, axis: usize) -> IndexVar<ArgMax<T>>
+    where
+        T::Dim: RemoveAxis,
+    {
+        self.var.argmax(axis)
+    }
+
+    /// Returns the indices of the smallest elements of `self` along `axis`, see
+    /// [`Var::argmin()`].
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds, or, during the forward pass, if `axis` is empty.
+    pub fn argmin(self, axis: usize) -> IndexVar<ArgMin<T>>
+    where
+        T::Dim: RemoveAxis,
+    {
+        self.var.argmin(axis)
+    }
+
+    /// Returns the `k` largest elements of `self` along `axis` together with their indices, see
+    /// [`Var::topk()`].
+    ///
+    /// The values are differentiable: each selected element of `self` receives the gradient of
+    /// the entry it was selected for, while the others receive a zero gradient.
+    ///
+    /// ```
+    /// let scores = neuronika::from_ndarray(ndarray::array![0.1, 0.7, 0.2]).requires_grad();
+    ///
+    /// let (values, _) = scores.clone().topk(2, 0);
+    /// let total = values.sum();
+    /// total.forward();
+    /// total.backward(1.);
+    /// assert_eq!(*scores.grad(), ndarray::array![0., 1., 1.]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds, or, during the forward pass, if `k` is larger than the length
+    /// of `axis`.
+    #[allow(clippy::type_complexity)]
+    pub fn topk(
+        self,
+        k: usize,
+        axis: usize,
+    ) -> (
+        VarDiff<Gather<T, TopK<T>>, GatherBackward<U, TopK<T>>>,
+        IndexVar<TopK<T>>,
+    ) {
+        self.var.check_axis(axis);
+        let indices = IndexVar::from(
+            TopK::new(self.var.node.clone(), k, axis),
+            self.var.past.clone(),
+        );
+        (self.gather(axis, indices.clone()), indices)
+    }
+
+    /// Returns the indices that sort the elements of `self` along `axis` in ascending order, see
+    /// [`Var::argsort()`].
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds.
+    pub fn argsort(self, axis: usize) -> IndexVar<ArgSort<T>> {
+        self.var.argsort(axis)
+    }
+
+    /// Sorts the elements of `self` along `axis` in ascending order, returning the sorted values
+    /// together with the indices that sort them, see [`Var::sort()`].
+    ///
+    /// The gradient of each sorted value is routed back through the permutation to the element of
+    /// `self` it comes from.
+    ///
+    /// ```
+    /// let x = neuronika::from_ndarray(ndarray::array![0.3, 0.1, 0.2]).requires_grad();
+    /// let weights = neuronika::from_ndarray(ndarray::array![1., 2., 3.]);
+    ///
+    /// let (values, _) = x.clone().sort(0);
+    /// let y = (values * weights).sum();
+    /// y.forward();
+    /// y.backward(1.);
+    /// assert_eq!(*x.grad(), ndarray::array![3., 1., 2.]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is out of bounds.
+    #[allow(clippy::type_complexity)]
+    pub fn sort(
+        self,
+        axis: usize,
+    ) -> (
+        VarDiff<Gather<T, ArgSort<T>>, GatherBackward<U, ArgSort<T>>>,
+        IndexVar<ArgSort<T>>,
+    ) {
+        let indices = self.var.clone().argsort(axis);
+        (self.gather(axis, indices.clone()), indices)
+    }
+
+    /// Sums the elements of `self` into `bins` bins, adding each element to the bin given by the
+    /// index at the same position of `indices`, see [`Var::bincount()`].
+    ///
+    /// Each element of `self` receives the gradient of the bin it was added to.
+    ///
+    /// ```
+    /// let weights = neuronika::from_ndarray(ndarray::array![0.5, 1., 2., 4.]).requires_grad();
+    /// let labels = neuronika::indices(ndarray::array![1, 0, 1, 2]);
+    /// let scale = neuronika::from_ndarray(ndarray::array![1., 2., 3.]);
+    ///
+    /// let y = (weights.clone().bincount(labels, 3) * scale).sum();
+    /// y.forward();
+    /// y.backward(1.);
+    /// assert_eq!(*weights.grad(), ndarray::array![2., 1., 2., 3.]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// During the forward pass, if `indices` and `self` have different shapes, or if an index is
+    /// negative or is not smaller than `bins`.
+    pub fn bincount<I: ?Sized>(
+        self,
+        indices: IndexVar<I>,
+        bins: usize,
+    ) -> VarDiff<WeightedBinCount<T, I>, WeightedBinCountBackward<U, I>>
+    where
+        I: IndexData<Dim = T::Dim> + 'static,
+    {
+        VarDiff::from(
+            WeightedBinCountBackward::new(self.node, indices.node.clone(), bins),
+            self.past,
+            self.var.bincount(indices, bins),
+        )
+    }
+
+    /// Compares `self` with `other` element-wise, returning a mask that holds one where `self` is
+    /// greater than `other` and zero elsewhere.
+    ///
+    /// The mask is not differentiable. See [`Var::gt()`] for more details.
+    pub fn gt<V: ?Sized>(self, other: Var<V>) -> Var<Comparison<T, V>>
+    where
+        V: Data + 'static,
+        T::Dim: DimMax<V::Dim>,
+    {
+        self.var.gt(other)
+    }
+
+    /// Compares `self` with `other` element-wise, returning a mask that holds one where `self` is
+    /// smaller than `other` and zero elsewhere.
+    ///
+    /// The mask is not differentiable. See [`Var::gt()`] for more details.
+    pub fn lt<V: ?Sized>(self, other: Var<V>) -> Var<Comparison<T, V>>
+    where
+        V: Data + 'static,
+        T::Dim: DimMax<V::Dim>,
+    {
+        self.var.lt(other)
     }
 
-    /// Applies the *natural logarithm* element-wise and returns a differentiable variable with the
-    /// result.
-    pub fn ln(self) -> VarDiff<Logn<T>, LognBackward<U, T>> {
-        let node = LognBackward::new(self.node, self.var.node.clone());
-        VarDiff::from(node, self.past, self.var.ln())
+    /// Compares `self` with `other` element-wise, returning a mask that holds one where `self` is
+    /// greater than or equal to `other` and zero elsewhere.
+    ///
+    /// The mask is not differentiable. See [`Var::gt()`] for more details.
+    pub fn ge<V: ?Sized>(self, other: Var<V>) -> Var<Comparison<T, V>>
+    where
+        V: Data + 'static,
+        T::Dim: DimMax<V::Dim>,
+    {
+        self.var.ge(other)
     }
 
-    /// Applies the *exponential* element-wise and returns a differentiable variable with the
-    /// result.
-    pub fn exp(self) -> VarDiff<Exp<T>, ExpBackward<U, Exp<T>>> {
-        let var = self.var.exp();
-        let node = ExpBackward::new(self.node, var.node.clone());
-        VarDiff::from(node, self.past, var)
+    /// Compares `self` with `other` element-wise, returning a mask that holds one where `self` is
+    /// smaller than or equal to `other` and zero elsewhere.
+    ///
+    /// The mask is not differentiable. See [`Var::gt()`] for more details.
+    pub fn le<V: ?Sized>(self, other: Var<V>) -> Var<Comparison<T, V>>
+    where
+        V: Data + 'static,
+        T::Dim: DimMax<V::Dim>,
+    {
+        self.var.le(other)
     }
 
-    /// Applies the *softmax* to `self` and returns a differentiable variable with the result.
+    /// Compares `self` with `other` element-wise, returning a mask that holds one where `self` is
+    /// equal to `other` and zero elsewhere.
     ///
-    /// The *softmax* is applied to all slices along `axis`, and will re-scale them so
-    ///  that the elements lie in the range *[0, 1]* and sum to 1.0.
-    pub fn softmax(self, axis: usize) -> VarDiff<Softmax<T>, SoftmaxBackward<U, Softmax<T>>> {
-        let var = self.var.softmax(axis);
-        let node = SoftmaxBackward::new(self.node, var.node.clone(), axis);
-        VarDiff::from(node, self.past, var)
+    /// The mask is not differentiable. See [`Var::gt()`] for more details.
+    pub fn eq<V: ?Sized>(self, other: Var<V>) -> Var<Comparison<T, V>>
+    where
+        V: Data + 'static,
+        T::Dim: DimMax<V::Dim>,
+    {
+        self.var.eq(other)
     }
 
-    /// Applies the *log-softmax* to `self` and returns a differentiable variable with the result.
+    /// Replaces the elements of `self` for which `mask` is not zero with `value`.
     ///
-    /// Applies a softmax followed by a logarithm. While mathematically equivalent to
-    /// *log(softmax(x))*, doing these two operations separately is slower, and numerically
-    /// unstable. This function uses an alternative formulation to compute the output and
-    /// gradient correctly.
+    /// The replaced elements receive no gradient. See [`Var::masked_fill()`] for more details.
     ///
-    /// See also [`.softmax()`].
+    /// # Panics
     ///
-    /// [`.softmax()`]: VarDiff::softmax()
-    pub fn log_softmax(
+    /// During the forward pass, if `mask` cannot be broadcast to the shape of `self`.
+    pub fn masked_fill<V: ?Sized>(
         self,
-        axis: usize,
-    ) -> VarDiff<LogSoftmax<T>, LogSoftmaxBackward<U, LogSoftmax<T>>> {
-        let var = self.var.log_softmax(axis);
-        let node = LogSoftmaxBackward::new(self.node, var.node.clone(), axis);
-        VarDiff::from(node, self.past, var)
+        mask: Var<V>,
+        value: f32,
+    ) -> VarDiff<MaskedFill<T, V>, MaskedFillBackward<U, V>>
+    where
+        V: Data + 'static,
+    {
+        VarDiff::from(
+            MaskedFillBackward::new(self.node, mask.node.clone()),
+            self.past,
+            self.var.masked_fill(mask, value),
+        )
     }
 
-    /// Returns a differentiable variable equivalent to `self` with its dimensions reversed.
-    pub fn t(self) -> VarDiff<Transpose<T>, TransposeBackward<U>> {
-        let node = TransposeBackward::new(self.node);
-        VarDiff::from(node, self.past, self.var.t())
+    /// Creates a new batch normalization differentiable variable sharing the status with its
+    /// internal val.
+    pub(crate) fn batch_norm_with_status(
+        self,
+        running_mean: Rc<RefCell<Tensor<Ix1>>>,
+        running_var: Rc<RefCell<Tensor<Ix1>>>,
+        momentum: f32,
+        eps: f32,
+        status: Rc<Cell<bool>>,
+    ) -> VarDiff<BatchNorm<T>, BatchNormBackward<U, T>> {
+        let var = self
+            .var
+            .batch_norm_with_status(running_mean, running_var, momentum, eps, status);
+        let node = BatchNormBackward::new(self.node, var.node.clone());
+        VarDiff::from(node, self.past, var)
     }
+}
 
-    /// Applies *dropout* to `self` and returns a differentiable variable with the result.
+impl<T: ?Sized, U: ?Sized> VarDiff<T, U>
+where
+    T: Data<Dim = Ix4> + 'static,
+    U: Gradient<Dim = Ix4> + 'static,
+{
+    /// Applies a two-dimensional max pooling over `self`, which must be of shape *(N, C, H, W)*.
     ///
-    /// It is strongly suggested to use [`nn::Dropout`] instead of this method when working with
-    /// neural networks.
+    /// # Arguments
     ///
-    /// During training, randomly zeroes some of the elements of `self` with probability *p* using
-    /// samples from a Bernoulli distribution. Each channel will be zeroed out independently on
-    /// every forward call.
+    /// * `kernel_size` - size of the pooling window.
     ///
-    /// This has proven to be an effective technique for regularization and preventing the
-    /// co-adaptation of neurons as described in the paper
-    /// [Improving neural networks by preventing co-adaptation of feature detectors](https://arxiv.org/abs/1207.0580).
+    /// * `stride` - stride of the pooling window.
     ///
-    /// Furthermore, the outputs are scaled by a factor of 1/(1 - p) during training. This means
-    /// that during evaluation the resulting variable simply computes an identity function.
+    /// * `padding` - implicit negative infinity padding added on both sides of each spatial axis.
     ///
-    /// [`nn::Dropout`]: crate::nn::Dropout
-    pub fn dropout(self, p: f64) -> VarDiff<Dropout<T>, DropoutBackward<U, T>> {
-        self.dropout_with_status(p, Rc::new(Cell::new(true)))
+    /// # Panics
+    ///
+    /// If the padding is larger than half of the window, or if the window doesn't fit the padded
+    /// input.
+    pub fn max_pool2d(
+        self,
+        kernel_size: (usize, usize),
+        stride: (usize, usize),
+        padding: (usize, usize),
+    ) -> VarDiff<MaxPool<T>, MaxPoolBackward<U, T>> {
+        let var = self.var.max_pool2d(kernel_size, stride, padding);
+        let node = MaxPoolBackward::new(self.node, var.node.clone());
+        VarDiff::from(node, self.past, var)
     }
 
-    /// Creates a new dropout differentiable variable sharing the status with its internal val.
-    pub(crate) fn dropout_with_status(
+    /// Applies a two-dimensional average pooling over `self`, which must be of shape
+    /// *(N, C, H, W)*.
+    ///
+    /// # Arguments
+    ///
+    /// * `kernel_size` - size of the pooling window.
+    ///
+    /// * `stride` - stride of the pooling window.
+    ///
+    /// * `padding` - implicit zero padding added on both sides of each spatial axis, the padded
+    /// entries are included in the averages.
+    ///
+    /// # Panics
+    ///
+    /// If the padding is larger than half of the window, or if the window doesn't fit the padded
+    /// input.
+    pub fn avg_pool2d(
         self,
-        p: f64,
-        status: Rc<Cell<bool>>,
-    ) -> VarDiff<Dropout<T>, DropoutBackward<U, T>> {
-        let var = self.var.dropout_with_status(p, status);
-        let node = DropoutBackward::new(self.node, var.node.clone(), p, var.node.status());
-        VarDiff::from(node, self.past, var)
+        kernel_size: (usize, usize),
+        stride: (usize, usize),
+        padding: (usize, usize),
+    ) -> VarDiff<AvgPool<T>, AvgPoolBackward<U>> {
+        VarDiff::from(
+            AvgPoolBackward::new(self.node, kernel_size, stride, padding),
+            self.past,
+            self.var.avg_pool2d(kernel_size, stride, padding),
+        )
     }
+}
 
-    /// Splits `self` into a certain number of chunks of size `chunk_size` **skipping** the
-    /// remainder along each dimension that doesn’t fit evenly.
-    pub fn chunks<E>(self, chunk_size: E) -> Vec<VarDiff<Chunk<T>, ChunkBackward<U>>>
+impl<T: ?Sized, U: ?Sized> VarDiff<T, U>
+where
+    T: Data<Dim = Ix3> + 'static,
+    U: Gradient<Dim = Ix3> + 'static,
+{
+    /// Averages `self`, a batch of padded sequences of shape *(N, L, E)*, over time, ignoring the
+    /// padding positions.
+    ///
+    /// The padding positions receive no gradient. See [`Var::masked_mean_pool()`] for more
+    /// details.
+    ///
+    /// # Panics
+    ///
+    /// If the shape of `mask` is not *(N, L)*.
+    pub fn masked_mean_pool<V: ?Sized>(
+        self,
+        mask: Var<V>,
+    ) -> VarDiff<MaskedMeanPool<T, V>, MaskedMeanPoolBackward<U, V>>
     where
-        E: IntoDimension<Dim = T::Dim>,
+        V: Data<Dim = Ix2> + 'static,
     {
-        self.var
-            .node
-            .data()
-            .exact_chunks(chunk_size)
-            .into_iter()
-            .enumerate()
-            .map(|(i, chunk)| {
-                let var = Var::from(
-                    Chunk::new(self.var.node.clone(), chunk.to_owned(), i),
-                    self.var.past.clone(),
-                );
-                VarDiff::from(
-                    ChunkBackward::new(self.node.clone(), chunk.map(|_| 0.), i),
-                    self.past.clone(),
-                    var,
-                )
-            })
-            .collect()
-    }
-
-    /// Returns a new differentiable variable with a dimension of size one inserted at the position
-    /// specified by `axis`.
-    pub fn unsqueeze(self, axis: usize) -> VarDiff<Unsqueeze<T>, UnsqueezeBackward<U>> {
         VarDiff::from(
-            UnsqueezeBackward::new(self.node, axis),
+            MaskedMeanPoolBackward::new(self.node, mask.node.clone()),
             self.past,
-            self.var.unsqueeze(axis),
+            self.var.masked_mean_pool(mask),
         )
     }
+
+    /// Takes the maximum of `self`, a batch of padded sequences of shape *(N, L, E)*, over time,
+    /// ignoring the padding positions.
+    ///
+    /// The padding positions receive no gradient. See [`Var::masked_mean_pool()`] for more
+    /// details.
+    ///
+    /// # Panics
+    ///
+    /// If the shape of `mask` is not *(N, L)*.
+    pub fn masked_max_pool<V: ?Sized>(
+        self,
+        mask: Var<V>,
+    ) -> VarDiff<MaskedMaxPool<T, V>, MaskedMaxPoolBackward<U, T, V>>
+    where
+        V: Data<Dim = Ix2> + 'static,
+    {
+        let var = self.var.masked_max_pool(mask);
+        let node = MaskedMaxPoolBackward::new(self.node, var.node.clone());
+        VarDiff::from(node, self.past, var)
+    }
 }
 
 impl<D> VarDiff<dyn Data<Dim = D>, dyn Gradient<Dim = D>>
@@ -600,7 +1746,7 @@ where
         let mut past = variables[0].past.clone();
         operands.push(variables[0].node.clone());
 
-        variables.iter().cloned().skip(1).for_each(|variable| {
+        variables.iter().skip(1).cloned().for_each(|variable| {
             past.merge(variable.past);
             operands.push(variable.node);
         });
@@ -658,7 +1804,7 @@ where
         let mut past = variables[0].past.clone();
         operands.push(variables[0].node.clone());
 
-        variables.iter().cloned().skip(1).for_each(|variable| {
+        variables.iter().skip(1).cloned().for_each(|variable| {
             past.merge(variable.past);
             operands.push(variable.node);
         });
@@ -677,12 +1823,11 @@ impl<T: ?Sized, U: ?Sized> Add<f32> for VarDiff<T, U>
 where
     T: Data + 'static,
     U: Gradient<Dim = T::Dim> + 'static,
-    T::Dim: DimMax<Ix0>,
 {
-    type Output = VarDiff<Addition<T, Input<Ix0>>, AdditionBackwardUnary<U, Input<Ix0>>>;
+    type Output = VarDiff<AddScalar<T>, AddScalarBackward<U>>;
 
     fn add(self, rhs: f32) -> Self::Output {
-        self + crate::full((), rhs)
+        VarDiff::from(AddScalarBackward::new(self.node), self.past, self.var + rhs)
     }
 }
 
@@ -690,12 +1835,11 @@ impl<T: ?Sized, U: ?Sized> Sub<f32> for VarDiff<T, U>
 where
     T: Data + 'static,
     U: Gradient<Dim = T::Dim> + 'static,
-    T::Dim: DimMax<Ix0>,
 {
-    type Output = VarDiff<Subtraction<T, Input<Ix0>>, SubtractionBackwardLeft<U, Input<Ix0>>>;
+    type Output = VarDiff<AddScalar<T>, AddScalarBackward<U>>;
 
     fn sub(self, rhs: f32) -> Self::Output {
-        self - crate::full((), rhs)
+        self + -rhs
     }
 }
 
@@ -703,13 +1847,12 @@ impl<T: ?Sized, U: ?Sized> Mul<f32> for VarDiff<T, U>
 where
     T: Data + 'static,
     U: Gradient<Dim = T::Dim> + 'static,
-    T::Dim: DimMax<Ix0>,
 {
-    type Output =
-        VarDiff<Multiplication<T, Input<Ix0>>, MultiplicationBackwardUnary<U, Input<Ix0>>>;
+    type Output = VarDiff<MulScalar<T>, MulScalarBackward<U>>;
 
     fn mul(self, rhs: f32) -> Self::Output {
-        self * crate::full((), rhs)
+        let node = MulScalarBackward::new(self.node, rhs);
+        VarDiff::from(node, self.past, self.var * rhs)
     }
 }
 
@@ -717,12 +1860,12 @@ impl<T: ?Sized, U: ?Sized> Div<f32> for VarDiff<T, U>
 where
     T: Data + 'static,
     U: Gradient<Dim = T::Dim> + 'static,
-    T::Dim: DimMax<Ix0>,
 {
-    type Output = VarDiff<Division<T, Input<Ix0>>, DivisionBackwardLeft<U, Input<Ix0>>>;
+    type Output = VarDiff<MulScalar<T>, MulScalarBackward<U>>;
 
     fn div(self, rhs: f32) -> Self::Output {
-        self / crate::full((), rhs)
+        let node = MulScalarBackward::new(self.node, 1. / rhs);
+        VarDiff::from(node, self.past, self.var / rhs)
     }
 }
 
@@ -732,12 +1875,11 @@ impl<T: ?Sized, U: ?Sized> Add<VarDiff<T, U>> for f32
 where
     T: Data + 'static,
     U: Gradient<Dim = T::Dim> + 'static,
-    Ix0: DimMax<T::Dim>,
 {
-    type Output = VarDiff<Addition<Input<Ix0>, T>, AdditionBackwardUnary<U, Input<Ix0>>>;
+    type Output = VarDiff<AddScalar<T>, AddScalarBackward<U>>;
 
     fn add(self, rhs: VarDiff<T, U>) -> Self::Output {
-        crate::full((), self) + rhs
+        rhs + self
     }
 }
 
@@ -745,12 +1887,11 @@ impl<T: ?Sized, U: ?Sized> Sub<VarDiff<T, U>> for f32
 where
     T: Data + 'static,
     U: Gradient<Dim = T::Dim> + 'static,
-    Ix0: DimMax<T::Dim>,
 {
-    type Output = VarDiff<Subtraction<Input<Ix0>, T>, SubtractionBackwardRight<U, Input<Ix0>>>;
+    type Output = VarDiff<AddScalar<Negation<T>>, AddScalarBackward<NegationBackward<U>>>;
 
     fn sub(self, rhs: VarDiff<T, U>) -> Self::Output {
-        crate::full((), self) - rhs
+        -rhs + self
     }
 }
 
@@ -758,13 +1899,11 @@ impl<T: ?Sized, U: ?Sized> Mul<VarDiff<T, U>> for f32
 where
     T: Data + 'static,
     U: Gradient<Dim = T::Dim> + 'static,
-    Ix0: DimMax<T::Dim>,
 {
-    type Output =
-        VarDiff<Multiplication<Input<Ix0>, T>, MultiplicationBackwardUnary<U, Input<Ix0>>>;
+    type Output = VarDiff<MulScalar<T>, MulScalarBackward<U>>;
 
     fn mul(self, rhs: VarDiff<T, U>) -> Self::Output {
-        crate::full((), self) * rhs
+        rhs * self
     }
 }
 
@@ -772,12 +1911,17 @@ impl<T: ?Sized, U: ?Sized> Div<VarDiff<T, U>> for f32
 where
     T: Data + 'static,
     U: Gradient<Dim = T::Dim> + 'static,
-    Ix0: DimMax<T::Dim>,
 {
-    type Output = VarDiff<Division<Input<Ix0>, T>, DivisionBackwardRight<Input<Ix0>, T, U>>;
+    type Output = VarDiff<MulScalar<Reciprocal<T>>, MulScalarBackward<ReciprocalBackward<U, T>>>;
 
     fn div(self, rhs: VarDiff<T, U>) -> Self::Output {
-        crate::full((), self) / rhs
+        let reciprocal = rhs.reciprocal();
+        let var = Var::from(
+            MulScalar::new(reciprocal.var.node, self),
+            reciprocal.var.past,
+        );
+        let node = MulScalarBackward::new(reciprocal.node, self);
+        VarDiff::from(node, reciprocal.past, var)
     }
 }
 
@@ -949,6 +2093,85 @@ where
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ References ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Implements the arithmetic operators for references to differentiable variables, and for
+/// references to variables as right-hand side operands, by means of shallow copies.
+macro_rules! reference_operators {
+    ($($trait:ident $method:ident),*) => {$(
+        impl<'a, T: ?Sized, U: ?Sized, Rhs> $trait<Rhs> for &'a VarDiff<T, U>
+        where
+            T: Data + 'static,
+            U: Gradient + 'static,
+            VarDiff<T, U>: $trait<Rhs>,
+        {
+            type Output = <VarDiff<T, U> as $trait<Rhs>>::Output;
+
+            fn $method(self, rhs: Rhs) -> Self::Output {
+                self.clone().$method(rhs)
+            }
+        }
+
+        impl<'a, T: ?Sized, U: ?Sized, R: ?Sized> $trait<&'a Var<R>> for VarDiff<T, U>
+        where
+            T: Data + 'static,
+            U: Gradient + 'static,
+            R: Data + 'static,
+            VarDiff<T, U>: $trait<Var<R>>,
+        {
+            type Output = <VarDiff<T, U> as $trait<Var<R>>>::Output;
+
+            fn $method(self, rhs: &'a Var<R>) -> Self::Output {
+                self.$method(rhs.clone())
+            }
+        }
+
+        impl<'a, T: ?Sized, U: ?Sized, F: ?Sized, B: ?Sized> $trait<&'a VarDiff<F, B>>
+            for VarDiff<T, U>
+        where
+            T: Data + 'static,
+            U: Gradient + 'static,
+            F: Data + 'static,
+            B: Gradient + 'static,
+            VarDiff<T, U>: $trait<VarDiff<F, B>>,
+        {
+            type Output = <VarDiff<T, U> as $trait<VarDiff<F, B>>>::Output;
+
+            fn $method(self, rhs: &'a VarDiff<F, B>) -> Self::Output {
+                self.$method(rhs.clone())
+            }
+        }
+
+        impl<'a, T: ?Sized, U: ?Sized> $trait<&'a VarDiff<T, U>> for f32
+        where
+            T: Data + 'static,
+            U: Gradient + 'static,
+            f32: $trait<VarDiff<T, U>>,
+        {
+            type Output = <f32 as $trait<VarDiff<T, U>>>::Output;
+
+            fn $method(self, rhs: &'a VarDiff<T, U>) -> Self::Output {
+                self.$method(rhs.clone())
+            }
+        }
+    )*};
+}
+
+reference_operators!(Add add, Sub sub, Mul mul, Div div);
+
+impl<T: ?Sized, U: ?Sized> Neg for &VarDiff<T, U>
+where
+    T: Data + 'static,
+    U: Gradient + 'static,
+    VarDiff<T, U>: Neg,
+{
+    type Output = <VarDiff<T, U> as Neg>::Output;
+
+    fn neg(self) -> Self::Output {
+        -self.clone()
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Algebraic Operations Implementations ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -1228,6 +2451,149 @@ where
     fn register_status(&mut self, _: Rc<Cell<bool>>) {}
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~ Maximum and Minimum traits implementations ~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Maximum ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<F1: ?Sized, B1: ?Sized, F2: ?Sized> Maximum<Var<F2>> for VarDiff<F1, B1>
+where
+    F1: Data + 'static,
+    F2: Data + 'static,
+    B1: Gradient + 'static,
+    F1::Dim: Dimension + DimMax<F2::Dim>,
+    B1::Dim: Dimension + DimMax<F2::Dim>,
+{
+    type Output = VarDiff<super::node::Maximum<F1, F2>, MaximumBackwardUnary<F1, B1, F2>>;
+
+    fn maximum(self, rhs: Var<F2>) -> Self::Output {
+        let node = MaximumBackwardUnary::new(self.var.node.clone(), self.node, rhs.node.clone());
+        VarDiff::from(node, self.past, Maximum::maximum(self.var, rhs))
+    }
+}
+
+impl<F1: ?Sized, B1: ?Sized, F2: ?Sized, B2: ?Sized> Maximum<VarDiff<F2, B2>> for VarDiff<F1, B1>
+where
+    F1: Data + 'static,
+    F2: Data + 'static,
+    B1: Gradient + 'static,
+    B2: Gradient + 'static,
+    F1::Dim: Dimension + DimMax<F2::Dim>,
+    B1::Dim: Dimension + DimMax<B2::Dim>,
+{
+    type Output = VarDiff<super::node::Maximum<F1, F2>, MaximumBackward<F1, B1, F2, B2>>;
+
+    fn maximum(mut self, rhs: VarDiff<F2, B2>) -> Self::Output {
+        self.past.merge(rhs.past);
+        let node = MaximumBackward::new(
+            self.var.node.clone(),
+            self.node,
+            rhs.var.node.clone(),
+            rhs.node,
+        );
+        VarDiff::from(node, self.past, Maximum::maximum(self.var, rhs.var))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Minimum ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<F1: ?Sized, B1: ?Sized, F2: ?Sized> Minimum<Var<F2>> for VarDiff<F1, B1>
+where
+    F1: Data + 'static,
+    F2: Data + 'static,
+    B1: Gradient + 'static,
+    F1::Dim: Dimension + DimMax<F2::Dim>,
+    B1::Dim: Dimension + DimMax<F2::Dim>,
+{
+    type Output = VarDiff<super::node::Minimum<F1, F2>, MinimumBackwardUnary<F1, B1, F2>>;
+
+    fn minimum(self, rhs: Var<F2>) -> Self::Output {
+        let node = MinimumBackwardUnary::new(self.var.node.clone(), self.node, rhs.node.clone());
+        VarDiff::from(node, self.past, Minimum::minimum(self.var, rhs))
+    }
+}
+
+impl<F1: ?Sized, B1: ?Sized, F2: ?Sized, B2: ?Sized> Minimum<VarDiff<F2, B2>> for VarDiff<F1, B1>
+where
+    F1: Data + 'static,
+    F2: Data + 'static,
+    B1: Gradient + 'static,
+    B2: Gradient + 'static,
+    F1::Dim: Dimension + DimMax<F2::Dim>,
+    B1::Dim: Dimension + DimMax<B2::Dim>,
+{
+    type Output = VarDiff<super::node::Minimum<F1, F2>, MinimumBackward<F1, B1, F2, B2>>;
+
+    fn minimum(mut self, rhs: VarDiff<F2, B2>) -> Self::Output {
+        self.past.merge(rhs.past);
+        let node = MinimumBackward::new(
+            self.var.node.clone(),
+            self.node,
+            rhs.var.node.clone(),
+            rhs.node,
+        );
+        VarDiff::from(node, self.past, Minimum::minimum(self.var, rhs.var))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Pow trait implementations ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<T: ?Sized, U: ?Sized> Pow<f32> for VarDiff<T, U>
+where
+    T: Data + 'static,
+    U: Gradient<Dim = T::Dim> + 'static,
+{
+    type Output = VarDiff<PowScalar<T>, PowScalarBackward<U, T>>;
+
+    fn powf(self, exponent: f32) -> Self::Output {
+        let node = PowScalarBackward::new(self.node, self.var.node.clone(), exponent);
+        VarDiff::from(node, self.past, self.var.powf(exponent))
+    }
+}
+
+impl<F1: ?Sized, B1: ?Sized, F2: ?Sized> Pow<Var<F2>> for VarDiff<F1, B1>
+where
+    F1: Data + 'static,
+    F2: Data + 'static,
+    B1: Gradient + 'static,
+    F1::Dim: Dimension + DimMax<F2::Dim>,
+    B1::Dim: Dimension + DimMax<F2::Dim>,
+{
+    type Output = VarDiff<TensorPower<F1, F2>, TensorPowerBackwardLeft<F1, B1, F2>>;
+
+    fn powf(self, exponent: Var<F2>) -> Self::Output {
+        let node =
+            TensorPowerBackwardLeft::new(self.var.node.clone(), self.node, exponent.node.clone());
+        VarDiff::from(node, self.past, self.var.powf(exponent))
+    }
+}
+
+impl<F1: ?Sized, B1: ?Sized, F2: ?Sized, B2: ?Sized> Pow<VarDiff<F2, B2>> for VarDiff<F1, B1>
+where
+    F1: Data + 'static,
+    F2: Data + 'static,
+    B1: Gradient + 'static,
+    B2: Gradient + 'static,
+    F1::Dim: Dimension + DimMax<F2::Dim>,
+    B1::Dim: Dimension + DimMax<B2::Dim>,
+{
+    type Output = VarDiff<TensorPower<F1, F2>, TensorPowerBackward<F1, B1, F2, B2>>;
+
+    fn powf(mut self, exponent: VarDiff<F2, B2>) -> Self::Output {
+        self.past.merge(exponent.past);
+        let node = TensorPowerBackward::new(
+            self.var.node.clone(),
+            self.node,
+            exponent.var.node.clone(),
+            exponent.node,
+        );
+        VarDiff::from(node, self.past, self.var.powf(exponent.var))
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~ Debug ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 impl<T: ?Sized, U: ?Sized> Debug for VarDiff<T, U>
@@ -1253,7 +2619,7 @@ where
     U: Gradient + Display,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.var)
+        Display::fmt(&self.var, f)
     }
 }
 